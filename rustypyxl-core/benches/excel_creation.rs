@@ -28,8 +28,7 @@ fn create_large_workbook(rows: u32, cols: u32) -> Workbook {
         }
     }
 
-    workbook.worksheets.push(worksheet);
-    workbook.sheet_names.push("Data".to_string());
+    workbook.push_sheet("Data".to_string(), worksheet).unwrap();
 
     workbook
 }
@@ -0,0 +1,379 @@
+//! Arrow IPC (Feather v2) import and export.
+//!
+//! The same column handling as the parquet support, for pipelines that
+//! already keep their data in Arrow IPC files (pandas `to_feather`, polars
+//! `write_ipc`). Both the IPC file format and the streaming format are read;
+//! files are written in the file format, which is what Feather v2 is.
+
+use crate::error::{Result, RustypyxlError};
+use crate::parquet_import::{
+    select_columns, write_batches, ColumnLayout, ColumnType, ParquetExportResult,
+    ParquetImportOptions, ParquetImportResult,
+};
+use crate::Workbook;
+
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::ipc::writer::{FileWriter, IpcWriteOptions};
+use arrow::ipc::CompressionType;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom};
+
+/// The magic bytes an Arrow IPC file (as opposed to a stream) starts with.
+const IPC_FILE_MAGIC: &[u8; 6] = b"ARROW1";
+
+/// Buffer compression for Arrow IPC export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IpcCompression {
+    /// No compression (default); the file can be memory-mapped as is.
+    #[default]
+    None,
+    /// LZ4 frame compression, pyarrow's default for Feather.
+    Lz4,
+    /// Zstd compression.
+    Zstd,
+}
+
+impl From<IpcCompression> for Option<CompressionType> {
+    fn from(c: IpcCompression) -> Self {
+        match c {
+            IpcCompression::None => None,
+            IpcCompression::Lz4 => Some(CompressionType::LZ4_FRAME),
+            IpcCompression::Zstd => Some(CompressionType::ZSTD),
+        }
+    }
+}
+
+/// Options for Arrow IPC export.
+#[derive(Debug, Clone)]
+pub struct ArrowIpcExportOptions {
+    /// Column name mappings (original_name -> new_name).
+    pub column_renames: HashMap<String, String>,
+    /// Whether the first row contains headers. Default: true.
+    pub has_headers: bool,
+    /// Buffer compression. Default: none.
+    pub compression: IpcCompression,
+    /// Column type hints (column_name -> type).
+    pub column_types: HashMap<String, ColumnType>,
+    /// Rows per record batch. Default: 65536.
+    pub batch_size: usize,
+}
+
+/// Default matches `new()`: headers on and a non-zero batch size.
+impl Default for ArrowIpcExportOptions {
+    fn default() -> Self {
+        Self {
+            column_renames: HashMap::new(),
+            has_headers: true,
+            compression: IpcCompression::default(),
+            column_types: HashMap::new(),
+            batch_size: 65536,
+        }
+    }
+}
+
+impl ArrowIpcExportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the first row contains headers.
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Add a column rename mapping.
+    pub fn rename_column(mut self, from: &str, to: &str) -> Self {
+        self.column_renames.insert(from.to_string(), to.to_string());
+        self
+    }
+
+    /// Set buffer compression.
+    pub fn with_compression(mut self, compression: IpcCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Set type hint for a column.
+    pub fn with_column_type(mut self, column: &str, col_type: ColumnType) -> Self {
+        self.column_types.insert(column.to_string(), col_type);
+        self
+    }
+
+    /// Set the number of rows per record batch.
+    pub fn with_batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size;
+        self
+    }
+}
+
+impl Workbook {
+    /// Import an Arrow IPC (Feather v2) file into a worksheet, starting at
+    /// (`start_row`, `start_col`). Takes the same options as
+    /// [`Workbook::insert_from_parquet`], except that `batch_size` is unused:
+    /// an IPC file is read in the batches it was written with. Unselected
+    /// columns are never decoded.
+    pub fn insert_from_arrow_ipc(
+        &mut self,
+        sheet_name: &str,
+        path: &str,
+        start_row: u32,
+        start_col: u32,
+        options: Option<ParquetImportOptions>,
+    ) -> Result<ParquetImportResult> {
+        let opts = options.unwrap_or_default();
+        let mut file = File::open(path).map_err(|e| {
+            RustypyxlError::ParseError(format!("Failed to open Arrow IPC file: {}", e))
+        })?;
+
+        let mut magic = [0u8; 6];
+        let is_file = file.read_exact(&mut magic).is_ok() && &magic == IPC_FILE_MAGIC;
+        file.seek(SeekFrom::Start(0))?;
+        let read_error =
+            |e| RustypyxlError::ParseError(format!("Failed to read Arrow IPC schema: {}", e));
+
+        // Read the schema first, then reopen with the selection pushed down.
+        let schema = if is_file {
+            FileReader::try_new_buffered(&mut file, None)
+                .map_err(read_error)?
+                .schema()
+        } else {
+            StreamReader::try_new_buffered(&mut file, None)
+                .map_err(read_error)?
+                .schema()
+        };
+        file.seek(SeekFrom::Start(0))?;
+        let all_column_names: Vec<String> =
+            schema.fields().iter().map(|f| f.name().clone()).collect();
+        let (columns_to_import, final_column_names) =
+            select_columns(&all_column_names, &opts, "Arrow IPC file")?;
+
+        // As for parquet, the projected batch keeps file-schema order.
+        let mut projected: Vec<usize> = columns_to_import.clone();
+        projected.sort_unstable();
+        projected.dedup();
+        let batch_indices: Vec<usize> = columns_to_import
+            .iter()
+            .map(|idx| projected.iter().position(|p| p == idx).unwrap())
+            .collect();
+
+        let worksheet = self.get_sheet_by_name_mut(sheet_name)?;
+        if is_file {
+            let reader =
+                FileReader::try_new(BufReader::new(file), Some(projected)).map_err(read_error)?;
+            write_batches(
+                worksheet,
+                final_column_names,
+                &batch_indices,
+                reader,
+                start_row,
+                start_col,
+                opts.include_headers,
+                opts.style.as_ref(),
+            )
+        } else {
+            let reader =
+                StreamReader::try_new(BufReader::new(file), Some(projected)).map_err(read_error)?;
+            write_batches(
+                worksheet,
+                final_column_names,
+                &batch_indices,
+                reader,
+                start_row,
+                start_col,
+                opts.include_headers,
+                opts.style.as_ref(),
+            )
+        }
+    }
+
+    /// Export a worksheet's used range to an Arrow IPC (Feather v2) file,
+    /// typing each column as [`Workbook::export_to_parquet`] does.
+    pub fn export_to_arrow_ipc(
+        &self,
+        sheet_name: &str,
+        path: &str,
+        options: Option<ArrowIpcExportOptions>,
+    ) -> Result<ParquetExportResult> {
+        let worksheet = self.get_sheet_by_name(sheet_name)?;
+        let (min_row, min_col, max_row, max_col) = worksheet.dimensions();
+        if max_row < min_row || max_col < min_col {
+            return Err(RustypyxlError::custom("Worksheet is empty"));
+        }
+        self.export_range_to_arrow_ipc(
+            sheet_name, path, min_row, min_col, max_row, max_col, options,
+        )
+    }
+
+    /// Export a specific range from a worksheet to an Arrow IPC file. Rows
+    /// are written `batch_size` at a time.
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_range_to_arrow_ipc(
+        &self,
+        sheet_name: &str,
+        path: &str,
+        min_row: u32,
+        min_col: u32,
+        max_row: u32,
+        max_col: u32,
+        options: Option<ArrowIpcExportOptions>,
+    ) -> Result<ParquetExportResult> {
+        if max_row < min_row || max_col < min_col {
+            return Err(RustypyxlError::custom("Invalid range"));
+        }
+        let options = options.unwrap_or_default();
+        let worksheet = self.get_sheet_by_name(sheet_name)?;
+        let layout = ColumnLayout::new(
+            worksheet,
+            (min_row, min_col, max_row, max_col),
+            options.has_headers,
+            &options.column_renames,
+            &options.column_types,
+        );
+
+        let write_error = |e| RustypyxlError::custom(format!("Failed to write Arrow IPC: {}", e));
+        let file = File::create(path)
+            .map_err(|e| RustypyxlError::custom(format!("Failed to create file: {}", e)))?;
+        let write_options = IpcWriteOptions::default()
+            .try_with_compression(options.compression.into())
+            .map_err(write_error)?;
+        let mut writer =
+            FileWriter::try_new_with_options(BufWriter::new(file), &layout.schema, write_options)
+                .map_err(write_error)?;
+        for batch in layout.batches(options.batch_size) {
+            writer.write(&batch?).map_err(write_error)?;
+        }
+        writer.finish().map_err(write_error)?;
+        drop(writer);
+
+        let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        Ok(ParquetExportResult {
+            rows_exported: layout.num_data_rows(),
+            columns_exported: layout.column_names.len() as u32,
+            column_names: layout.column_names,
+            file_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::CellValue;
+    use arrow::array::{Float64Array, Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::ipc::writer::StreamWriter;
+    use arrow::record_batch::RecordBatch;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn workbook() -> Workbook {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+        ws.set_cell_value(1, 1, "id");
+        ws.set_cell_value(1, 2, "name");
+        ws.set_cell_value(1, 3, "score");
+        for row in 2..=6 {
+            ws.set_cell_value(row, 1, (row - 1) as f64);
+            ws.set_cell_value(row, 2, format!("item {}", row - 1));
+            ws.set_cell_value(row, 3, row as f64 * 1.5);
+        }
+        wb
+    }
+
+    #[test]
+    fn test_round_trip_with_compression() {
+        let dir = TempDir::new().unwrap();
+        for compression in [
+            IpcCompression::None,
+            IpcCompression::Lz4,
+            IpcCompression::Zstd,
+        ] {
+            let path = dir.path().join("data.arrow");
+            let path = path.to_str().unwrap();
+            let options = ArrowIpcExportOptions::new()
+                .with_compression(compression)
+                .with_batch_size(2)
+                .rename_column("score", "points");
+            let result = workbook()
+                .export_to_arrow_ipc("Data", path, Some(options))
+                .unwrap();
+            assert_eq!(result.rows_exported, 5);
+            assert_eq!(result.column_names, ["id", "name", "points"]);
+
+            let mut wb = Workbook::new();
+            wb.create_sheet(Some("Copy".to_string())).unwrap();
+            let imported = wb.insert_from_arrow_ipc("Copy", path, 2, 2, None).unwrap();
+            assert_eq!(imported.rows_imported, 5);
+            assert_eq!(imported.range_with_headers(), "B2:D7");
+            let ws = wb.get_sheet_by_name("Copy").unwrap();
+            assert_eq!(ws.get_cell_value(2, 4), Some(&CellValue::from("points")));
+            assert_eq!(ws.get_cell_value(7, 2), Some(&CellValue::Number(5.0)));
+            assert_eq!(ws.get_cell_value(3, 3), Some(&CellValue::from("item 1")));
+            assert_eq!(ws.get_cell_value(7, 4), Some(&CellValue::Number(9.0)));
+        }
+    }
+
+    #[test]
+    fn test_import_stream_format_and_selection() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("data.arrows");
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+            Field::new("c", DataType::Float64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from(vec![Some(1), None])),
+                Arc::new(StringArray::from(vec!["x", "y"])),
+                Arc::new(Float64Array::from(vec![0.5, 1.5])),
+            ],
+        )
+        .unwrap();
+        let mut writer = StreamWriter::try_new(File::create(&path).unwrap(), &schema).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("S".to_string())).unwrap();
+        let options = ParquetImportOptions::new()
+            .select_columns(vec!["c".to_string(), "a".to_string()])
+            .rename_column("c", "ratio");
+        let result = wb
+            .insert_from_arrow_ipc("S", path.to_str().unwrap(), 1, 1, Some(options))
+            .unwrap();
+        assert_eq!(result.column_names, ["ratio", "a"]);
+        let ws = wb.get_sheet_by_name("S").unwrap();
+        assert_eq!(ws.get_cell_value(2, 1), Some(&CellValue::Number(0.5)));
+        assert_eq!(ws.get_cell_value(2, 2), Some(&CellValue::Number(1.0)));
+        assert!(ws.get_cell(3, 2).is_none());
+
+        let missing = ParquetImportOptions::new().select_columns(vec!["zzz".to_string()]);
+        let err = wb
+            .insert_from_arrow_ipc("S", path.to_str().unwrap(), 1, 1, Some(missing))
+            .unwrap_err();
+        assert!(err.to_string().contains("Arrow IPC file"), "{err}");
+    }
+
+    #[test]
+    fn test_errors() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("bad.arrow");
+        std::fs::write(&path, b"not arrow at all").unwrap();
+        let mut wb = workbook();
+        assert!(wb
+            .insert_from_arrow_ipc("Data", path.to_str().unwrap(), 1, 1, None)
+            .is_err());
+        assert!(wb
+            .insert_from_arrow_ipc("Data", "/nonexistent/x.arrow", 1, 1, None)
+            .is_err());
+        let empty = Workbook::new();
+        let out = dir.path().join("out.arrow");
+        assert!(empty
+            .export_to_arrow_ipc("Sheet", out.to_str().unwrap(), None)
+            .is_err());
+    }
+}
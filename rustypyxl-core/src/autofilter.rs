@@ -84,6 +84,20 @@ impl FilterOperator {
             FilterOperator::LessThanOrEqual => "lessThanOrEqual",
         }
     }
+
+    /// Parse an XML operator name ("greaterThan") or a comparison symbol
+    /// (">", ">=", "<", "<=", "=", "!=" or "<>").
+    pub fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "equal" | "=" | "==" => FilterOperator::Equal,
+            "notEqual" | "!=" | "<>" => FilterOperator::NotEqual,
+            "greaterThan" | ">" => FilterOperator::GreaterThan,
+            "greaterThanOrEqual" | ">=" => FilterOperator::GreaterThanOrEqual,
+            "lessThan" | "<" => FilterOperator::LessThan,
+            "lessThanOrEqual" | "<=" => FilterOperator::LessThanOrEqual,
+            _ => return None,
+        })
+    }
 }
 
 /// Color filter configuration.
@@ -239,8 +253,10 @@ impl AutoFilter {
         }
     }
 
-    /// Add a column filter.
+    /// Add a column filter. A column holds one filter, so this replaces any
+    /// filter already set on the same column.
     pub fn add_filter(&mut self, column: FilterColumn) {
+        self.columns.retain(|c| c.column_id != column.column_id);
         self.columns.push(column);
     }
 
@@ -339,5 +355,23 @@ mod coverage_tests {
         af.sort_by(2, true);
         assert_eq!(af.columns.len(), 2);
         assert_eq!(af.range, "A1:C10");
+
+        af.add_filter(FilterColumn::values(0, vec!["c".into()]));
+        assert_eq!(af.columns.len(), 2);
+        assert_eq!(af.columns[1].filter, FilterType::Values(vec!["c".into()]));
+    }
+
+    #[test]
+    fn test_operator_parse() {
+        assert_eq!(
+            FilterOperator::parse(">="),
+            Some(FilterOperator::GreaterThanOrEqual)
+        );
+        assert_eq!(
+            FilterOperator::parse("notEqual"),
+            Some(FilterOperator::NotEqual)
+        );
+        assert_eq!(FilterOperator::parse("<>"), Some(FilterOperator::NotEqual));
+        assert_eq!(FilterOperator::parse("between"), None);
     }
 }
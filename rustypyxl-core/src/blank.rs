@@ -0,0 +1,175 @@
+//! How blank cells read back and how writing a blank treats a cell.
+//!
+//! openpyxl reads blanks as `None` and clears a cell when `None` is written;
+//! pandas-minded code often wants `""` instead, or wants blanks left out of
+//! iteration, or wants `None` in a batch of values to leave the cell alone.
+//! A [`BlankPolicy`] on the workbook picks between these.
+
+use crate::cell::CellValue;
+use crate::error::{Result, RustypyxlError};
+
+/// What a blank cell (no value) reads back as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlankRead {
+    /// Blank cells read as `None`, as in openpyxl.
+    #[default]
+    None,
+    /// Blank cells read as an empty string.
+    EmptyString,
+    /// Blank cells are left out of row and column iteration, and rows or
+    /// columns with no values at all are skipped. A single cell read
+    /// directly still reads as `None`.
+    Skip,
+}
+
+/// What writing a blank value (`None`) does to a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlankWrite {
+    /// Clear the cell's value, keeping its style, as in openpyxl.
+    #[default]
+    Clear,
+    /// Leave the cell's current value untouched.
+    Ignore,
+}
+
+impl BlankRead {
+    /// Parse "none", "empty_string" or "skip".
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "none" => Ok(BlankRead::None),
+            "empty_string" => Ok(BlankRead::EmptyString),
+            "skip" => Ok(BlankRead::Skip),
+            _ => Err(RustypyxlError::custom(format!(
+                "Unknown blank read mode '{}': expected none, empty_string or skip",
+                name
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BlankRead::None => "none",
+            BlankRead::EmptyString => "empty_string",
+            BlankRead::Skip => "skip",
+        }
+    }
+}
+
+impl BlankWrite {
+    /// Parse "clear" or "ignore".
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "clear" => Ok(BlankWrite::Clear),
+            "ignore" => Ok(BlankWrite::Ignore),
+            _ => Err(RustypyxlError::custom(format!(
+                "Unknown blank write mode '{}': expected clear or ignore",
+                name
+            ))),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BlankWrite::Clear => "clear",
+            BlankWrite::Ignore => "ignore",
+        }
+    }
+}
+
+/// Blank-cell handling for reads and writes. The default matches openpyxl.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BlankPolicy {
+    pub read: BlankRead,
+    pub write: BlankWrite,
+}
+
+impl BlankPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_read(mut self, read: BlankRead) -> Self {
+        self.read = read;
+        self
+    }
+
+    pub fn with_write(mut self, write: BlankWrite) -> Self {
+        self.write = write;
+        self
+    }
+
+    /// The value a read yields for `value`; `None` when the cell is blank
+    /// and should be left out of iteration.
+    pub fn read_value<'a>(&self, value: &'a CellValue) -> Option<std::borrow::Cow<'a, CellValue>> {
+        use std::borrow::Cow;
+        match (value, self.read) {
+            (CellValue::Empty, BlankRead::None) => Some(Cow::Borrowed(value)),
+            (CellValue::Empty, BlankRead::EmptyString) => Some(Cow::Owned(CellValue::from(""))),
+            (CellValue::Empty, BlankRead::Skip) => None,
+            _ => Some(Cow::Borrowed(value)),
+        }
+    }
+
+    /// Whether writing `value` should leave the cell as it is.
+    pub fn ignores_write(&self, value: &CellValue) -> bool {
+        matches!(value, CellValue::Empty) && self.write == BlankWrite::Ignore
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_modes() {
+        let blank = CellValue::Empty;
+        let number = CellValue::Number(1.0);
+        let policy = BlankPolicy::new();
+        assert_eq!(
+            policy.read_value(&blank).as_deref(),
+            Some(&CellValue::Empty)
+        );
+
+        let policy = policy.with_read(BlankRead::EmptyString);
+        assert_eq!(
+            policy.read_value(&blank).as_deref(),
+            Some(&CellValue::from(""))
+        );
+        assert_eq!(policy.read_value(&number).as_deref(), Some(&number));
+
+        let policy = policy.with_read(BlankRead::Skip);
+        assert!(policy.read_value(&blank).is_none());
+        assert_eq!(policy.read_value(&number).as_deref(), Some(&number));
+    }
+
+    #[test]
+    fn test_parse_modes() {
+        assert_eq!(
+            BlankRead::parse("Empty_String").unwrap(),
+            BlankRead::EmptyString
+        );
+        assert_eq!(BlankWrite::parse("ignore").unwrap(), BlankWrite::Ignore);
+        assert!(BlankRead::parse("zero").is_err());
+        assert_eq!(BlankWrite::Clear.as_str(), "clear");
+    }
+
+    #[test]
+    fn test_workbook_blank_writes() {
+        let mut wb = crate::Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.set_cell_value_in_sheet("Data", 1, 1, CellValue::Number(5.0))
+            .unwrap();
+
+        wb.set_blank_policy(BlankPolicy::new().with_write(BlankWrite::Ignore));
+        wb.set_cell_value_in_sheet("Data", 1, 1, CellValue::Empty)
+            .unwrap();
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::Number(5.0)));
+
+        wb.set_blank_policy(BlankPolicy::default());
+        wb.set_cell_value_in_sheet("Data", 1, 1, CellValue::Empty)
+            .unwrap();
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::Empty));
+    }
+}
@@ -0,0 +1,308 @@
+//! Building independent sheets in parallel and assembling them into one
+//! workbook.
+//!
+//! A [`Workbook`] is a single mutable value, so its sheets cannot be filled
+//! from several threads at once. [`WorkbookBuilder`] gives each sheet its own
+//! [`SheetPart`]: a worksheet plus the [`StyleRegistry`] its cells' style
+//! indices refer to. The parts are built on the rayon pool, then added to the
+//! workbook in the order they were declared, with each part's styles merged
+//! into the workbook's registry and its cells' indices remapped to match.
+//!
+//! ```
+//! use rustypyxl::{CellValue, WorkbookBuilder};
+//!
+//! let regions = ["North", "South", "East"];
+//! let mut builder = WorkbookBuilder::new();
+//! for region in regions {
+//!     builder = builder.sheet(region, move |part| {
+//!         part.worksheet.set_cell_value(1, 1, CellValue::from(region));
+//!         Ok(())
+//!     });
+//! }
+//! let wb = builder.build().unwrap();
+//! assert_eq!(wb.sheet_names(), regions);
+//! ```
+
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::error::{Result, RustypyxlError};
+use crate::style::StyleRegistry;
+use crate::workbook::Workbook;
+use crate::worksheet::Worksheet;
+
+/// A sheet built apart from a workbook, with the styles its cells'
+/// `style_index` values refer to.
+#[derive(Debug, Clone)]
+pub struct SheetPart {
+    pub worksheet: Worksheet,
+    pub styles: StyleRegistry,
+}
+
+impl SheetPart {
+    /// An empty sheet with a default style registry.
+    pub fn new(title: impl Into<String>) -> Self {
+        SheetPart {
+            worksheet: Worksheet::new(title.into()),
+            styles: StyleRegistry::new(),
+        }
+    }
+}
+
+type SheetJob<'a> = Box<dyn FnOnce(&mut SheetPart) -> Result<()> + Send + 'a>;
+
+/// Declares sheets whose contents are built in parallel; see the
+/// [module docs](self).
+pub struct WorkbookBuilder<'a> {
+    base: Workbook,
+    jobs: Vec<(String, SheetJob<'a>)>,
+}
+
+impl Default for WorkbookBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> WorkbookBuilder<'a> {
+    /// A builder that assembles into a new, empty workbook.
+    pub fn new() -> Self {
+        Self::with_base(Workbook::new())
+    }
+
+    /// A builder that appends its sheets to `base`, e.g. a template whose
+    /// styles and existing sheets should be kept.
+    pub fn with_base(base: Workbook) -> Self {
+        WorkbookBuilder {
+            base,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Declare a sheet called `title`, filled by `build` on a worker thread.
+    /// The closure may borrow from the caller for as long as the builder
+    /// lives.
+    pub fn sheet<F>(mut self, title: impl Into<String>, build: F) -> Self
+    where
+        F: FnOnce(&mut SheetPart) -> Result<()> + Send + 'a,
+    {
+        self.jobs.push((title.into(), Box::new(build)));
+        self
+    }
+
+    /// Build every declared sheet in parallel and add them to the workbook
+    /// in declaration order. Fails with the first sheet (in that order)
+    /// whose closure failed; duplicate titles are rejected before any sheet
+    /// is built.
+    pub fn build(self) -> Result<Workbook> {
+        let mut workbook = self.base;
+        for (i, (title, _)) in self.jobs.iter().enumerate() {
+            let taken = workbook.sheet_position(title).is_some()
+                || self.jobs[..i].iter().any(|(other, _)| other == title);
+            if taken {
+                return Err(RustypyxlError::WorksheetAlreadyExists(title.clone()));
+            }
+        }
+
+        let parts: Vec<Result<SheetPart>> = self
+            .jobs
+            .into_par_iter()
+            .map(|(title, build)| {
+                let mut part = SheetPart::new(title.as_str());
+                build(&mut part).map_err(|e| {
+                    RustypyxlError::custom(format!("Building sheet '{}' failed: {}", title, e))
+                })?;
+                Ok(part)
+            })
+            .collect();
+        for part in parts {
+            workbook.add_sheet_part(part?)?;
+        }
+        Ok(workbook)
+    }
+}
+
+impl Workbook {
+    /// Add a sheet built apart from this workbook, merging the styles its
+    /// cells use into this workbook's registry. The sheet keeps the title
+    /// it was built with, which must not be taken.
+    pub fn add_sheet_part(&mut self, part: SheetPart) -> Result<&mut Worksheet> {
+        let SheetPart {
+            mut worksheet,
+            styles,
+        } = part;
+        if self.sheet_position(&worksheet.title).is_some() {
+            return Err(RustypyxlError::WorksheetAlreadyExists(worksheet.title));
+        }
+        for table in &worksheet.tables {
+            let clash = self
+                .worksheets
+                .iter()
+                .flat_map(|ws| &ws.tables)
+                .any(|t| t.name.eq_ignore_ascii_case(&table.name));
+            if clash {
+                return Err(RustypyxlError::custom(format!(
+                    "Table name '{}' is already used in this workbook",
+                    table.name
+                )));
+            }
+        }
+
+        let mut remap: HashMap<u32, u32> = HashMap::new();
+        for cell in worksheet.cells.values_mut() {
+            let Some(index) = cell.style_index else {
+                continue;
+            };
+            let mapped = match remap.get(&index) {
+                Some(mapped) => *mapped,
+                None => {
+                    let mapped = self.import_cell_style(&styles, index)?;
+                    remap.insert(index, mapped);
+                    mapped
+                }
+            };
+            cell.style_index = Some(mapped);
+        }
+
+        worksheet.uid = self.allocate_sheet_uid();
+        self.push_sheet(worksheet.title.clone(), worksheet)
+    }
+
+    /// The index in this workbook's registry of cell format `index` from
+    /// `source`, adding the format, and the named style it is linked to,
+    /// when missing.
+    fn import_cell_style(&mut self, source: &StyleRegistry, index: u32) -> Result<u32> {
+        let style = source.get_cell_style(index as usize).ok_or_else(|| {
+            RustypyxlError::custom(format!(
+                "Style index {} is not in the sheet's style registry",
+                index
+            ))
+        })?;
+        if let Some(name) = style.named_style.as_deref() {
+            if self.styles.named_style_index(name).is_none() {
+                if let Some(named) = source.named_styles.iter().find(|ns| ns.name == name) {
+                    self.styles.add_named_style(named.clone())?;
+                }
+            }
+        }
+        Ok(self.styles.get_or_add_cell_xf(&style) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::CellValue;
+    use crate::named_styles::NamedStyle;
+    use crate::style::{CellStyle, Font};
+    use std::sync::Arc;
+
+    fn bold() -> CellStyle {
+        CellStyle {
+            font: Some(Font {
+                bold: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sheets_keep_order_and_styles_merge() {
+        let totals = [10.0, 20.0, 30.0];
+        let mut builder = WorkbookBuilder::new();
+        for (i, total) in totals.iter().enumerate() {
+            builder = builder.sheet(format!("Region{}", i + 1), move |part| {
+                // Each part registers formats in a different order, so the
+                // same style gets a different local index per sheet.
+                if i % 2 == 1 {
+                    part.styles.get_or_add_cell_xf(&CellStyle {
+                        number_format: Some("0.0".into()),
+                        ..Default::default()
+                    });
+                }
+                let xf = part.styles.get_or_add_cell_xf(&bold()) as u32;
+                part.worksheet
+                    .set_cell_value(1, 1, CellValue::Number(*total));
+                part.worksheet.get_or_create_cell_mut(1, 1).style_index = Some(xf);
+                Ok(())
+            });
+        }
+        let wb = builder.build().unwrap();
+
+        assert_eq!(wb.sheet_names(), ["Region1", "Region2", "Region3"]);
+        let indices: Vec<u32> = wb
+            .worksheets
+            .iter()
+            .map(|ws| ws.get_cell(1, 1).unwrap().style_index.unwrap())
+            .collect();
+        assert!(indices.iter().all(|i| *i == indices[0]), "{:?}", indices);
+        let style = wb.styles.get_cell_style(indices[0] as usize).unwrap();
+        assert!(style.font.unwrap().bold);
+        let uids: Vec<u64> = wb.worksheets.iter().map(|ws| ws.uid).collect();
+        assert!(uids.iter().all(|uid| *uid != 0));
+        assert_ne!(uids[0], uids[1]);
+    }
+
+    #[test]
+    fn named_styles_are_carried_over() {
+        let highlight = NamedStyle::new("Highlight", bold());
+        let wb = WorkbookBuilder::new()
+            .sheet("Data", move |part| {
+                let idx = part.styles.add_named_style(highlight)?;
+                let mut style = part.styles.named_styles[idx].style.clone();
+                style.named_style = Some(Arc::from("Highlight"));
+                let xf = part.styles.get_or_add_cell_xf(&style) as u32;
+                part.worksheet.set_cell_value(1, 1, CellValue::Number(1.0));
+                part.worksheet.get_or_create_cell_mut(1, 1).style_index = Some(xf);
+                Ok(())
+            })
+            .build()
+            .unwrap();
+
+        let xf = wb.worksheets[0]
+            .get_cell(1, 1)
+            .unwrap()
+            .style_index
+            .unwrap();
+        let style = wb.styles.get_cell_style(xf as usize).unwrap();
+        assert_eq!(style.named_style.as_deref(), Some("Highlight"));
+        assert!(wb.styles.named_style_index("Highlight").is_some());
+    }
+
+    #[test]
+    fn closures_borrow_input_and_errors_name_the_sheet() {
+        let rows: Vec<f64> = (0..100).map(f64::from).collect();
+        let wb = WorkbookBuilder::new()
+            .sheet("Numbers", |part| {
+                for (i, n) in rows.iter().enumerate() {
+                    part.worksheet
+                        .set_cell_value(i as u32 + 1, 1, CellValue::Number(*n));
+                }
+                Ok(())
+            })
+            .build()
+            .unwrap();
+        assert_eq!(
+            wb.worksheets[0].get_cell_value(100, 1),
+            Some(&CellValue::Number(99.0))
+        );
+
+        let Err(err) = WorkbookBuilder::new()
+            .sheet("Good", |_| Ok(()))
+            .sheet("Bad", |_| Err(RustypyxlError::custom("no data")))
+            .build()
+        else {
+            panic!("a failing sheet should fail the build");
+        };
+        assert!(err.to_string().contains("'Bad'"), "{err}");
+
+        let mut base = Workbook::new();
+        base.create_sheet(Some("Cover".to_string())).unwrap();
+        assert!(WorkbookBuilder::with_base(base)
+            .sheet("Cover", |_| Ok(()))
+            .build()
+            .is_err());
+    }
+}
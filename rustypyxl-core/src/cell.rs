@@ -1,5 +1,6 @@
 //! Cell value types and utilities.
 
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use std::fmt;
 use std::sync::Arc;
 
@@ -14,8 +15,10 @@ pub enum CellValue {
     Number(f64),
     /// Boolean value.
     Boolean(bool),
-    /// Date value stored as ISO 8601 string or Excel serial number.
-    Date(String),
+    /// Date, time of day or date-time. Loaded from numbers with a date
+    /// number format and from ISO 8601 `t="d"` cells; saved as an Excel
+    /// serial (see [`crate::dates`]).
+    Date(DateValue),
     /// Formula (without the leading '=' sign).
     Formula(String),
     /// Empty cell.
@@ -58,6 +61,15 @@ impl CellValue {
         }
     }
 
+    /// Get the value as a date and time, converted as
+    /// [`DateValue::to_datetime`] does.
+    pub fn as_datetime(&self) -> Option<NaiveDateTime> {
+        match self {
+            CellValue::Date(d) => Some(d.to_datetime()),
+            _ => None,
+        }
+    }
+
     /// Get the formula string (without '=' prefix).
     pub fn as_formula(&self) -> Option<&str> {
         match self {
@@ -79,6 +91,104 @@ impl CellValue {
     }
 }
 
+/// A date cell's value. Which of date, time or date-time it holds is kept,
+/// so a value set as a date reads back as one and saves with a date format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DateValue {
+    /// A calendar date (`2024-03-15`).
+    Date(NaiveDate),
+    /// A time of day (`10:30:00`).
+    Time(NaiveTime),
+    /// A date and time (`2024-03-15T10:30:00`).
+    DateTime(NaiveDateTime),
+}
+
+impl DateValue {
+    /// Parse ISO 8601 text: `YYYY-MM-DD`, `HH:MM[:SS[.fff]]`, or a date and
+    /// time joined by `T` or a space. A date-time with a UTC offset (`Z`,
+    /// `+02:00`) is converted to UTC, as cells have no time zone.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(text) {
+            return Some(DateValue::DateTime(dt.naive_utc()));
+        }
+        if let Some(dt) = crate::csv::parse_iso_date(text) {
+            return Some(if text.len() == 10 {
+                DateValue::Date(dt.date())
+            } else {
+                DateValue::DateTime(dt)
+            });
+        }
+        ["%H:%M:%S%.f", "%H:%M"]
+            .iter()
+            .find_map(|format| NaiveTime::parse_from_str(text, format).ok())
+            .map(DateValue::Time)
+    }
+
+    /// The value as a date-time: a date at midnight, and a time of day on
+    /// 1899-12-31, where [`crate::dates::from_excel`] puts serials below 1.
+    pub fn to_datetime(&self) -> NaiveDateTime {
+        match self {
+            DateValue::Date(d) => d.and_time(NaiveTime::MIN),
+            DateValue::Time(t) => crate::dates::EXCEL_DAY_ZERO.and_time(*t),
+            DateValue::DateTime(dt) => *dt,
+        }
+    }
+
+    /// The value an Excel serial stands for (see [`crate::dates::from_excel`]):
+    /// a time of day for a 1900-system serial below 1, otherwise a
+    /// date-time. `None` where `from_excel` has no date.
+    pub fn from_excel(serial: f64, date1904: bool) -> Option<Self> {
+        let dt = crate::dates::from_excel(serial, date1904)?;
+        Some(if !date1904 && crate::dates::is_time_of_day(&dt) {
+            DateValue::Time(dt.time())
+        } else {
+            DateValue::DateTime(dt)
+        })
+    }
+
+    /// The Excel serial for the value in either date system. A time of day
+    /// is the bare fraction of a day, so it round-trips through
+    /// [`from_excel`](Self::from_excel) in the 1900 system.
+    pub fn to_excel(&self, date1904: bool) -> f64 {
+        match self {
+            DateValue::Time(t) => {
+                crate::dates::to_excel(&crate::dates::EXCEL_DAY_ZERO.and_time(*t), false)
+            }
+            _ => crate::dates::to_excel(&self.to_datetime(), date1904),
+        }
+    }
+}
+
+impl fmt::Display for DateValue {
+    /// ISO 8601, with fractional seconds only when there are some.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateValue::Date(d) => write!(f, "{}", d.format("%Y-%m-%d")),
+            DateValue::Time(t) => write!(f, "{}", t.format("%H:%M:%S%.f")),
+            DateValue::DateTime(dt) => write!(f, "{}", dt.format("%Y-%m-%dT%H:%M:%S%.f")),
+        }
+    }
+}
+
+impl From<NaiveDate> for DateValue {
+    fn from(d: NaiveDate) -> Self {
+        DateValue::Date(d)
+    }
+}
+
+impl From<NaiveTime> for DateValue {
+    fn from(t: NaiveTime) -> Self {
+        DateValue::Time(t)
+    }
+}
+
+impl From<NaiveDateTime> for DateValue {
+    fn from(dt: NaiveDateTime) -> Self {
+        DateValue::DateTime(dt)
+    }
+}
+
 impl fmt::Display for CellValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -128,6 +238,24 @@ impl From<bool> for CellValue {
     }
 }
 
+impl From<NaiveDateTime> for CellValue {
+    fn from(dt: NaiveDateTime) -> Self {
+        CellValue::Date(DateValue::DateTime(dt))
+    }
+}
+
+impl From<NaiveDate> for CellValue {
+    fn from(d: NaiveDate) -> Self {
+        CellValue::Date(DateValue::Date(d))
+    }
+}
+
+impl From<DateValue> for CellValue {
+    fn from(d: DateValue) -> Self {
+        CellValue::Date(d)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,6 +299,66 @@ mod tests {
         assert_eq!(val.to_string(), "");
     }
 
+    #[test]
+    fn test_date_value_parse_and_display() {
+        let date = DateValue::parse("2024-03-15").unwrap();
+        assert_eq!(
+            date,
+            DateValue::Date(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap())
+        );
+        assert_eq!(date.to_string(), "2024-03-15");
+
+        let time = DateValue::parse("18:15:00").unwrap();
+        assert_eq!(time.to_string(), "18:15:00");
+        assert!(crate::dates::is_time_of_day(&time.to_datetime()));
+
+        let dt = DateValue::parse("2024-03-15T10:30:45.250").unwrap();
+        assert_eq!(dt.to_string(), "2024-03-15T10:30:45.250");
+        assert_eq!(
+            DateValue::parse("2024-03-15 10:30:45.250"),
+            Some(dt),
+            "a space separates date and time too"
+        );
+
+        // Offsets are folded into UTC.
+        assert_eq!(
+            DateValue::parse("2024-03-01T14:00:00+02:00")
+                .unwrap()
+                .to_string(),
+            "2024-03-01T12:00:00"
+        );
+        assert_eq!(DateValue::parse("not a date"), None);
+
+        let value = CellValue::from(date);
+        assert_eq!(value.data_type_code(), "d");
+        assert_eq!(value.to_string(), "2024-03-15");
+        assert_eq!(value.as_datetime(), Some(date.to_datetime()));
+    }
+
+    #[test]
+    fn test_date_value_serials() {
+        assert_eq!(
+            DateValue::from_excel(0.75, false),
+            Some(DateValue::Time(NaiveTime::from_hms_opt(18, 0, 0).unwrap()))
+        );
+        let dt = NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_opt(10, 30, 0)
+            .unwrap();
+        assert_eq!(
+            DateValue::from_excel(45366.4375, false),
+            Some(DateValue::DateTime(dt))
+        );
+        assert_eq!(DateValue::from_excel(-1.0, false), None);
+
+        let time = DateValue::Time(NaiveTime::from_hms_opt(10, 30, 0).unwrap());
+        assert_eq!(time.to_excel(false), 0.4375);
+        assert_eq!(time.to_excel(true), 0.4375);
+        let day = DateValue::Date(dt.date());
+        assert_eq!(day.to_excel(false), 45366.0);
+        assert_eq!(day.to_excel(true), 45366.0 - 1462.0);
+    }
+
     #[test]
     fn test_cell_value_from() {
         let val: CellValue = "Hello".into();
@@ -7,10 +7,14 @@
 //! title, legend, and axes intact; and charts present in a loaded file are read back
 //! into this model, so they survive a load/save round-trip. Supported types: bar,
 //! column, line, area, pie, doughnut, and scatter; other types fall back to a bar
-//! chart on write. Axis min/max and per-series colors are written but not read back.
+//! chart on write. Axis titles, axis min/max and per-series colors are written but
+//! not read back.
 
 use std::collections::HashMap;
 
+use crate::error::{Result, RustypyxlError};
+use crate::utils::{column_to_letter, parse_coordinate, parse_range};
+
 /// Type of chart.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ChartType {
@@ -259,6 +263,18 @@ impl ChartAnchor {
     }
 }
 
+impl From<&str> for ChartAnchor {
+    fn from(cell: &str) -> Self {
+        ChartAnchor::at(cell)
+    }
+}
+
+impl From<String> for ChartAnchor {
+    fn from(cell: String) -> Self {
+        ChartAnchor::at(cell)
+    }
+}
+
 /// A chart in an Excel worksheet.
 #[derive(Clone, Debug)]
 pub struct Chart {
@@ -391,6 +407,212 @@ impl Chart {
     }
 }
 
+/// Split a data reference like `Sheet1!$B$1:$C$5` into one value series per
+/// column, as openpyxl's `add_data` does. With `titles_from_data` the first
+/// row holds the series names and is left out of the values.
+pub fn series_from_reference(reference: &str, titles_from_data: bool) -> Result<Vec<ChartSeries>> {
+    let invalid = || {
+        RustypyxlError::InvalidCoordinate(format!(
+            "chart data must be a sheet-qualified range like Sheet1!$B$1:$B$5, got {:?}",
+            reference
+        ))
+    };
+    let (sheet, area) = reference.rsplit_once('!').ok_or_else(invalid)?;
+    if sheet.is_empty() {
+        return Err(invalid());
+    }
+    let area = area.replace('$', "");
+    let ((min_row, min_col), (max_row, max_col)) = if area.contains(':') {
+        parse_range(&area)?
+    } else {
+        let cell = parse_coordinate(&area)?;
+        (cell, cell)
+    };
+    if min_row > max_row || min_col > max_col {
+        return Err(invalid());
+    }
+    let first_value_row = if titles_from_data {
+        min_row + 1
+    } else {
+        min_row
+    };
+    if first_value_row > max_row {
+        return Err(RustypyxlError::InvalidCoordinate(format!(
+            "chart data {:?} has a title row but no values",
+            reference
+        )));
+    }
+
+    Ok((min_col..=max_col)
+        .map(|col| {
+            let letters = column_to_letter(col);
+            let mut series = ChartSeries::new(format!(
+                "{sheet}!${letters}${first_value_row}:${letters}${max_row}"
+            ));
+            if titles_from_data {
+                series = series.with_name(format!("{sheet}!${letters}${min_row}"));
+            }
+            series
+        })
+        .collect())
+}
+
+/// Builder methods shared by the typed chart wrappers. Each wrapper owns a
+/// [`Chart`] of its kind and converts into it for [`Worksheet::add_chart`].
+///
+/// [`Worksheet::add_chart`]: crate::Worksheet::add_chart
+macro_rules! typed_chart {
+    ($name:ident) => {
+        impl $name {
+            /// Set the chart title.
+            pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+                self.0 = self.0.with_title(title);
+                self
+            }
+
+            /// Set the legend.
+            pub fn with_legend(mut self, legend: ChartLegend) -> Self {
+                self.0 = self.0.with_legend(legend);
+                self
+            }
+
+            /// Set the chart size in inches.
+            pub fn with_size_inches(mut self, width: f64, height: f64) -> Self {
+                self.0 = self.0.with_size_inches(width, height);
+                self
+            }
+
+            /// Add a data series.
+            pub fn add_series(&mut self, series: ChartSeries) {
+                self.0.add_series(series);
+            }
+
+            /// Add one series per column of `reference`; see
+            /// [`series_from_reference`].
+            pub fn add_data(&mut self, reference: &str, titles_from_data: bool) -> Result<()> {
+                for series in series_from_reference(reference, titles_from_data)? {
+                    self.0.add_series(series);
+                }
+                Ok(())
+            }
+
+            /// Use `reference` as the categories of every series added so far.
+            pub fn set_categories<S: Into<String>>(&mut self, reference: S) {
+                let reference = reference.into();
+                for series in &mut self.0.series {
+                    series.categories = Some(reference.clone());
+                }
+            }
+
+            /// The chart this builder produces.
+            pub fn chart(&self) -> &Chart {
+                &self.0
+            }
+        }
+
+        impl From<$name> for Chart {
+            fn from(typed: $name) -> Chart {
+                typed.0
+            }
+        }
+    };
+}
+
+/// A bar chart: vertical columns by default, horizontal bars after
+/// [`BarChart::horizontal`].
+#[derive(Clone, Debug)]
+pub struct BarChart(Chart);
+
+impl BarChart {
+    /// Create a clustered column chart.
+    pub fn new() -> Self {
+        BarChart(Chart::column())
+    }
+
+    /// Draw horizontal bars instead of columns.
+    pub fn horizontal(mut self) -> Self {
+        self.0.chart_type = ChartType::Bar;
+        self.0.bar_direction = BarDirection::Bar;
+        self
+    }
+
+    /// Set how multiple series share a category.
+    pub fn with_grouping(mut self, grouping: BarGrouping) -> Self {
+        self.0 = self.0.with_grouping(grouping);
+        self
+    }
+
+    /// Set the category axis.
+    pub fn with_x_axis(mut self, axis: ChartAxis) -> Self {
+        self.0 = self.0.with_x_axis(axis);
+        self
+    }
+
+    /// Set the value axis.
+    pub fn with_y_axis(mut self, axis: ChartAxis) -> Self {
+        self.0 = self.0.with_y_axis(axis);
+        self
+    }
+}
+
+impl Default for BarChart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+typed_chart!(BarChart);
+
+/// A line chart.
+#[derive(Clone, Debug)]
+pub struct LineChart(Chart);
+
+impl LineChart {
+    /// Create a line chart.
+    pub fn new() -> Self {
+        LineChart(Chart::line())
+    }
+
+    /// Set the category axis.
+    pub fn with_x_axis(mut self, axis: ChartAxis) -> Self {
+        self.0 = self.0.with_x_axis(axis);
+        self
+    }
+
+    /// Set the value axis.
+    pub fn with_y_axis(mut self, axis: ChartAxis) -> Self {
+        self.0 = self.0.with_y_axis(axis);
+        self
+    }
+}
+
+impl Default for LineChart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+typed_chart!(LineChart);
+
+/// A pie chart. Only its first series is drawn; it has no axes.
+#[derive(Clone, Debug)]
+pub struct PieChart(Chart);
+
+impl PieChart {
+    /// Create a pie chart.
+    pub fn new() -> Self {
+        PieChart(Chart::pie())
+    }
+}
+
+impl Default for PieChart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+typed_chart!(PieChart);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -442,6 +664,37 @@ mod tests {
         assert_eq!(chart.width, 5486400);
     }
 
+    #[test]
+    fn test_series_from_reference() {
+        let series = series_from_reference("'My Data'!$B$1:$C$4", true).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].values, "'My Data'!$B$2:$B$4");
+        assert_eq!(series[0].name.as_deref(), Some("'My Data'!$B$1"));
+        assert_eq!(series[1].values, "'My Data'!$C$2:$C$4");
+
+        let single = series_from_reference("S!A1:A3", false).unwrap();
+        assert_eq!(single[0].values, "S!$A$1:$A$3");
+        assert!(single[0].name.is_none());
+
+        assert!(series_from_reference("A1:A3", false).is_err());
+        assert!(series_from_reference("S!A1:C1", true).is_err());
+    }
+
+    #[test]
+    fn test_typed_charts() {
+        let mut bar = BarChart::new().horizontal().with_title("Sales");
+        bar.add_data("S!$B$1:$B$4", true).unwrap();
+        bar.set_categories("S!$A$2:$A$4");
+        let chart: Chart = bar.into();
+        assert_eq!(chart.chart_type, ChartType::Bar);
+        assert_eq!(chart.bar_direction, BarDirection::Bar);
+        assert_eq!(chart.series[0].categories.as_deref(), Some("S!$A$2:$A$4"));
+
+        assert_eq!(BarChart::new().chart().chart_type, ChartType::Column);
+        assert_eq!(Chart::from(LineChart::new()).chart_type, ChartType::Line);
+        assert_eq!(Chart::from(PieChart::default()).chart_type, ChartType::Pie);
+    }
+
     #[test]
     fn test_chart_type_xml() {
         assert_eq!(ChartType::Bar.xml_type(), "barChart");
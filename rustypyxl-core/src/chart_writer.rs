@@ -3,7 +3,7 @@
 //! (xl/drawings/drawingM.xml). Write-path only; reading charts back on load is a
 //! separate concern.
 
-use crate::chart::{Chart, ChartSeries, ChartType};
+use crate::chart::{Chart, ChartAxis, ChartSeries, ChartType};
 use crate::writer::escape_xml;
 
 const CAT_AX_ID: &str = "111111111";
@@ -125,13 +125,32 @@ fn legend_xml(chart: &Chart) -> String {
     }
 }
 
-/// A `<c:catAx>` or `<c:valAx>`.
-fn axis(kind: &str, ax_id: &str, cross_id: &str, pos: &str) -> String {
+/// A `<c:catAx>` or `<c:valAx>`, carrying the configured title and bounds.
+fn axis(kind: &str, ax_id: &str, cross_id: &str, pos: &str, config: Option<&ChartAxis>) -> String {
+    let mut bounds = String::new();
+    let mut title = String::new();
+    if let Some(config) = config {
+        // CT_Scaling orders max before min.
+        if let Some(max) = config.max_val {
+            bounds.push_str(&format!(r#"<c:max val="{}"/>"#, max));
+        }
+        if let Some(min) = config.min_val {
+            bounds.push_str(&format!(r#"<c:min val="{}"/>"#, min));
+        }
+        if let Some(text) = &config.title {
+            title = format!(
+                r#"<c:title><c:tx><c:rich><a:bodyPr/><a:p><a:r><a:t>{}</a:t></a:r></a:p></c:rich></c:tx><c:overlay val="0"/></c:title>"#,
+                escape_xml(text)
+            );
+        }
+    }
     format!(
-        r#"<c:{kind}><c:axId val="{id}"/><c:scaling><c:orientation val="minMax"/></c:scaling><c:delete val="0"/><c:axPos val="{pos}"/><c:crossAx val="{cross}"/></c:{kind}>"#,
+        r#"<c:{kind}><c:axId val="{id}"/><c:scaling><c:orientation val="minMax"/>{bounds}</c:scaling><c:delete val="0"/><c:axPos val="{pos}"/>{title}<c:crossAx val="{cross}"/></c:{kind}>"#,
         kind = kind,
         id = ax_id,
+        bounds = bounds,
         pos = pos,
+        title = title,
         cross = cross_id
     )
 }
@@ -163,8 +182,20 @@ pub fn chart_xml(chart: &Chart) -> String {
             body.push_str(&format!(r#"<c:axId val="{}"/>"#, VAL_AX_ID));
             body.push_str("</c:scatterChart>");
             // scatter uses two value axes
-            body.push_str(&axis("valAx", X_VAL_AX_ID, VAL_AX_ID, "b"));
-            body.push_str(&axis("valAx", VAL_AX_ID, X_VAL_AX_ID, "l"));
+            body.push_str(&axis(
+                "valAx",
+                X_VAL_AX_ID,
+                VAL_AX_ID,
+                "b",
+                chart.x_axis.as_ref(),
+            ));
+            body.push_str(&axis(
+                "valAx",
+                VAL_AX_ID,
+                X_VAL_AX_ID,
+                "l",
+                chart.y_axis.as_ref(),
+            ));
             body
         }
         _ => {
@@ -204,11 +235,27 @@ pub fn chart_xml(chart: &Chart) -> String {
             for (i, s) in chart.series.iter().enumerate() {
                 body.push_str(&category_series(i, s));
             }
+            if elem == "barChart" && chart.bar_grouping != crate::chart::BarGrouping::Clustered {
+                // Stacked bars draw on top of each other, not side by side.
+                body.push_str(r#"<c:overlap val="100"/>"#);
+            }
             body.push_str(&format!(r#"<c:axId val="{}"/>"#, CAT_AX_ID));
             body.push_str(&format!(r#"<c:axId val="{}"/>"#, VAL_AX_ID));
             body.push_str(&format!("</c:{}>", elem));
-            body.push_str(&axis("catAx", CAT_AX_ID, VAL_AX_ID, "b"));
-            body.push_str(&axis("valAx", VAL_AX_ID, CAT_AX_ID, "l"));
+            body.push_str(&axis(
+                "catAx",
+                CAT_AX_ID,
+                VAL_AX_ID,
+                "b",
+                chart.x_axis.as_ref(),
+            ));
+            body.push_str(&axis(
+                "valAx",
+                VAL_AX_ID,
+                CAT_AX_ID,
+                "l",
+                chart.y_axis.as_ref(),
+            ));
             body
         }
     };
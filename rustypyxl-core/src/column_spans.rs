@@ -0,0 +1,267 @@
+//! Per-column settings stored as runs of consecutive columns.
+//!
+//! Worksheets describe columns with `<col min max>` ranges, and a formatted
+//! sheet often sets one width across hundreds (or all 16,384) columns. Keeping
+//! those runs as spans instead of one entry per column keeps loading cheap and
+//! lets the writer emit the same compact ranges back.
+
+use std::collections::BTreeMap;
+
+/// A map from column number (1-based) to a value, stored as non-overlapping
+/// spans of consecutive columns. Neighbouring spans with equal values are
+/// merged, so [`spans`](Self::spans) always yields the fewest ranges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnSpans<V> {
+    /// Spans keyed by their first column, holding (last column, value).
+    spans: BTreeMap<u32, (u32, V)>,
+}
+
+impl<V> Default for ColumnSpans<V> {
+    fn default() -> Self {
+        ColumnSpans {
+            spans: BTreeMap::new(),
+        }
+    }
+}
+
+impl<V: Clone + PartialEq> ColumnSpans<V> {
+    /// An empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True if no column has a value.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Remove every span.
+    pub fn clear(&mut self) {
+        self.spans.clear();
+    }
+
+    /// The value for a column, if one is set.
+    pub fn get(&self, column: &u32) -> Option<&V> {
+        self.spans
+            .range(..=*column)
+            .next_back()
+            .filter(|(_, (max, _))| *max >= *column)
+            .map(|(_, (_, value))| value)
+    }
+
+    /// True if the column has a value.
+    pub fn contains_key(&self, column: &u32) -> bool {
+        self.get(column).is_some()
+    }
+
+    /// Set the value of a single column.
+    pub fn insert(&mut self, column: u32, value: V) {
+        self.insert_span(column, column, value);
+    }
+
+    /// Set the value of every column from `min` to `max` inclusive, replacing
+    /// whatever those columns held. Does nothing if `min > max`.
+    pub fn insert_span(&mut self, min: u32, max: u32, value: V) {
+        if min > max {
+            return;
+        }
+        self.remove_span(min, max);
+
+        let (mut start, mut end) = (min, max);
+        if let Some((&left, (left_max, left_value))) = self.spans.range(..min).next_back() {
+            if left_max.checked_add(1) == Some(min) && *left_value == value {
+                start = left;
+            }
+        }
+        if let Some(next) = max.checked_add(1) {
+            if let Some((right_max, right_value)) = self.spans.get(&next) {
+                if *right_value == value {
+                    end = *right_max;
+                    self.spans.remove(&next);
+                }
+            }
+        }
+        self.spans.insert(start, (end, value));
+    }
+
+    /// Clear a single column.
+    pub fn remove(&mut self, column: &u32) {
+        self.remove_span(*column, *column);
+    }
+
+    /// Clear every column from `min` to `max` inclusive, splitting spans that
+    /// extend past either end.
+    pub fn remove_span(&mut self, min: u32, max: u32) {
+        if min > max {
+            return;
+        }
+        let overlapping: Vec<u32> = self
+            .spans
+            .range(..=max)
+            .rev()
+            .take_while(|(_, (end, _))| *end >= min)
+            .map(|(&start, _)| start)
+            .collect();
+        for start in overlapping {
+            let (end, value) = self.spans.remove(&start).expect("span listed above");
+            if start < min {
+                self.spans.insert(start, (min - 1, value.clone()));
+            }
+            if end > max {
+                self.spans.insert(max + 1, (end, value));
+            }
+        }
+    }
+
+    /// The spans in column order, as (first column, last column, value).
+    pub fn spans(&self) -> impl Iterator<Item = (u32, u32, &V)> + '_ {
+        self.spans
+            .iter()
+            .map(|(&min, (max, value))| (min, *max, value))
+    }
+
+    /// Split columns `start..=end` into runs over which both `self` and
+    /// `other` are constant, as (first column, last column, value in self,
+    /// value in other). Runs where neither has a value are included, and
+    /// neighbouring runs with equal values are merged.
+    #[allow(clippy::type_complexity)]
+    pub fn overlay<'a, W: Clone + PartialEq>(
+        &'a self,
+        other: &'a ColumnSpans<W>,
+        start: u32,
+        end: u32,
+    ) -> Vec<(u32, u32, Option<&'a V>, Option<&'a W>)> {
+        if start > end {
+            return Vec::new();
+        }
+        let (lo, hi) = (start as u64, end as u64 + 1);
+        let mut points: Vec<u64> = vec![lo, hi];
+        let bounds = self
+            .spans()
+            .map(|(a, b, _)| (a, b))
+            .chain(other.spans().map(|(a, b, _)| (a, b)));
+        for (min, max) in bounds {
+            points.extend(
+                [min as u64, max as u64 + 1]
+                    .into_iter()
+                    .filter(|p| lo < *p && *p < hi),
+            );
+        }
+        points.sort_unstable();
+        points.dedup();
+
+        let mut out: Vec<(u32, u32, Option<&V>, Option<&W>)> = Vec::new();
+        for pair in points.windows(2) {
+            let (min, max) = (pair[0] as u32, (pair[1] - 1) as u32);
+            let (a, b) = (self.get(&min), other.get(&min));
+            match out.last_mut() {
+                Some(last) if last.2 == a && last.3 == b => last.1 = max,
+                _ => out.push((min, max, a, b)),
+            }
+        }
+        out
+    }
+
+    /// Split columns `start..=end` into runs of equal value, including runs
+    /// with no value.
+    pub fn segments(&self, start: u32, end: u32) -> Vec<(u32, u32, Option<&V>)> {
+        self.overlay(self, start, end)
+            .into_iter()
+            .map(|(min, max, value, _)| (min, max, value))
+            .collect()
+    }
+
+    /// Every column that has a value, in order. A span covering many columns
+    /// yields each of them; prefer [`spans`](Self::spans) where ranges will do.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &V)> + '_ {
+        self.spans()
+            .flat_map(|(min, max, value)| (min..=max).map(move |column| (column, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(map: &ColumnSpans<f64>) -> Vec<(u32, u32, f64)> {
+        map.spans().map(|(a, b, v)| (a, b, *v)).collect()
+    }
+
+    #[test]
+    fn equal_neighbours_merge() {
+        let mut map = ColumnSpans::new();
+        for column in 1..=5 {
+            map.insert(column, 12.0);
+        }
+        map.insert(7, 12.0);
+        assert_eq!(spans(&map), vec![(1, 5, 12.0), (7, 7, 12.0)]);
+        map.insert(6, 12.0);
+        assert_eq!(spans(&map), vec![(1, 7, 12.0)]);
+        assert_eq!(map.get(&4), Some(&12.0));
+        assert_eq!(map.get(&8), None);
+    }
+
+    #[test]
+    fn overwrite_splits_span() {
+        let mut map = ColumnSpans::new();
+        map.insert_span(1, 16384, 9.0);
+        map.insert(3, 20.0);
+        assert_eq!(
+            spans(&map),
+            vec![(1, 2, 9.0), (3, 3, 20.0), (4, 16384, 9.0)]
+        );
+        map.insert(3, 9.0);
+        assert_eq!(spans(&map), vec![(1, 16384, 9.0)]);
+    }
+
+    #[test]
+    fn remove_trims_and_splits() {
+        let mut map = ColumnSpans::new();
+        map.insert_span(2, 10, 5.0);
+        map.insert_span(12, 14, 6.0);
+        map.remove_span(4, 12);
+        assert_eq!(spans(&map), vec![(2, 3, 5.0), (13, 14, 6.0)]);
+        map.remove(&2);
+        assert!(!map.contains_key(&2));
+        assert_eq!(
+            map.iter().map(|(c, _)| c).collect::<Vec<_>>(),
+            vec![3, 13, 14]
+        );
+    }
+
+    #[test]
+    fn overlay_splits_at_every_boundary() {
+        let mut widths = ColumnSpans::new();
+        widths.insert_span(1, 10, 8.0);
+        let mut hidden = ColumnSpans::new();
+        hidden.insert_span(4, 12, true);
+        let runs: Vec<_> = widths
+            .overlay(&hidden, 1, 20)
+            .into_iter()
+            .map(|(a, b, w, h)| (a, b, w.copied(), h.copied()))
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                (1, 3, Some(8.0), None),
+                (4, 10, Some(8.0), Some(true)),
+                (11, 12, None, Some(true)),
+                (13, 20, None, None),
+            ]
+        );
+        assert_eq!(
+            widths.segments(5, u32::MAX),
+            vec![(5, 10, Some(&8.0)), (11, u32::MAX, None)]
+        );
+    }
+
+    #[test]
+    fn edge_columns() {
+        let mut map = ColumnSpans::new();
+        map.insert(u32::MAX, 1.0);
+        map.insert(u32::MAX - 1, 1.0);
+        assert_eq!(spans(&map), vec![(u32::MAX - 1, u32::MAX, 1.0)]);
+        map.insert_span(5, 4, 2.0);
+        assert_eq!(map.spans().count(), 1);
+    }
+}
@@ -0,0 +1,232 @@
+//! Column-major cell storage for [`Storage::Columnar`] worksheets.
+//!
+//! A cell in the default map costs its key, a full [`CellData`] and the
+//! map's slack, well over a hundred bytes, even when all it holds is a
+//! number. A columnar sheet keeps such cells, a value and nothing else, in
+//! one dense vector per column covering a run of rows, so a rectangle of
+//! numbers costs little more than the values themselves. Cells with a
+//! style, number format, hyperlink, comment, rich text or a formula stay in
+//! the map, and the worksheet moves a cell over as soon as one is set.
+
+use std::collections::BTreeMap;
+
+use crate::cell::CellValue;
+use crate::worksheet::CellData;
+
+/// Rows a column run may grow by to reach a new value, beyond its own
+/// length, before the value is kept in the cell map instead. Keeps a stray
+/// cell far below a table from padding the column with empty slots.
+const MIN_GAP: usize = 64;
+
+/// How a worksheet stores its cells; see [`crate::Worksheet::with_storage`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Storage {
+    /// One map entry per cell. Suits sparse and heavily formatted sheets.
+    #[default]
+    Map,
+    /// Plain values in dense per-column vectors, for large rectangular
+    /// data. Uses several times less memory when most cells are bare
+    /// numbers or strings.
+    Columnar,
+}
+
+impl Storage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Storage::Map => "map",
+            Storage::Columnar => "columnar",
+        }
+    }
+}
+
+/// Values down one column for the rows `first_row..first_row + values.len()`.
+/// `CellValue::Empty` marks a row with no value.
+///
+/// The slots are whole `CellValue`s rather than per-type vectors so that
+/// [`crate::Worksheet::get_cell_value`] can lend a `&CellValue` for any
+/// cell; at 24 bytes a slot is still a sixth of a map cell.
+#[derive(Clone, Debug, Default)]
+struct Column {
+    first_row: u32,
+    values: Vec<CellValue>,
+    filled: usize,
+}
+
+/// The column runs of a columnar worksheet, by column number.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ColumnStore {
+    columns: BTreeMap<u32, Column>,
+}
+
+/// Whether `value` can live in a column: formulas carry cached results and
+/// shared-formula state alongside them, and an empty value is no value.
+pub(crate) fn fits_column(value: &CellValue) -> bool {
+    !matches!(value, CellValue::Formula(_) | CellValue::Empty)
+}
+
+/// Whether `cell` holds nothing but its value, so dropping it to a bare
+/// value loses nothing. A `data_type` naming the value's own type is
+/// implied by the value.
+pub(crate) fn is_bare(cell: &CellData) -> bool {
+    fits_column(&cell.value) && holds_only_value(cell)
+}
+
+/// Whether everything on `cell` apart from its value is unset.
+pub(crate) fn holds_only_value(cell: &CellData) -> bool {
+    cell.style.is_none()
+        && cell.style_index.is_none()
+        && cell.number_format.is_none()
+        && cell
+            .data_type
+            .is_none_or(|t| t == cell.value.data_type_code())
+        && cell.hyperlink.is_none()
+        && cell.comment.is_none()
+        && cell.cached_formula_value.is_none()
+        && cell.rich_text.is_none()
+}
+
+impl ColumnStore {
+    pub(crate) fn get(&self, row: u32, column: u32) -> Option<&CellValue> {
+        let col = self.columns.get(&column)?;
+        let idx = row.checked_sub(col.first_row)? as usize;
+        col.values.get(idx).filter(|v| !v.is_empty())
+    }
+
+    /// Store `value` at (`row`, `column`), or hand it back when the column's
+    /// run would have to stretch too far to reach the row.
+    pub(crate) fn set(&mut self, row: u32, column: u32, value: CellValue) -> Option<CellValue> {
+        let col = self.columns.entry(column).or_default();
+        if col.values.is_empty() {
+            col.first_row = row;
+        }
+        let len = col.values.len();
+        let reach = len.max(MIN_GAP);
+        if row < col.first_row {
+            let gap = (col.first_row - row) as usize;
+            if gap > reach {
+                return Some(value);
+            }
+            col.values
+                .splice(0..0, std::iter::repeat_n(CellValue::Empty, gap));
+            col.first_row = row;
+        }
+        let idx = (row - col.first_row) as usize;
+        if idx >= len {
+            if idx - len > reach {
+                return Some(value);
+            }
+            col.values.resize(idx + 1, CellValue::Empty);
+        }
+        let old = std::mem::replace(&mut col.values[idx], value);
+        if old.is_empty() {
+            col.filled += 1;
+        }
+        None
+    }
+
+    /// Remove and return the value at (`row`, `column`).
+    pub(crate) fn take(&mut self, row: u32, column: u32) -> Option<CellValue> {
+        let col = self.columns.get_mut(&column)?;
+        let idx = row.checked_sub(col.first_row)? as usize;
+        let old = std::mem::take(col.values.get_mut(idx)?);
+        if old.is_empty() {
+            return None;
+        }
+        col.filled -= 1;
+        if col.filled == 0 {
+            self.columns.remove(&column);
+        } else {
+            while col.values.last().is_some_and(CellValue::is_empty) {
+                col.values.pop();
+            }
+        }
+        Some(old)
+    }
+
+    /// Number of values stored.
+    pub(crate) fn len(&self) -> usize {
+        self.columns.values().map(|col| col.filled).sum()
+    }
+
+    /// Every value with its (row, column), column by column.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = ((u32, u32), &CellValue)> + '_ {
+        self.columns.iter().flat_map(|(&column, col)| {
+            (col.first_row..)
+                .zip(&col.values)
+                .filter(|(_, value)| !value.is_empty())
+                .map(move |(row, value)| ((row, column), value))
+        })
+    }
+
+    /// Every value with its (row, column), emptying the store.
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = ((u32, u32), CellValue)> {
+        std::mem::take(&mut self.columns)
+            .into_iter()
+            .flat_map(|(column, col)| {
+                (col.first_row..)
+                    .zip(col.values)
+                    .filter(|(_, value)| !value.is_empty())
+                    .map(move |(row, value)| ((row, column), value))
+            })
+    }
+
+    /// Release the spare capacity left by growing the runs.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        for col in self.columns.values_mut() {
+            col.values.shrink_to_fit();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_grow_both_ways_and_shrink() {
+        let mut store = ColumnStore::default();
+        assert!(store.set(10, 2, CellValue::Number(1.0)).is_none());
+        assert!(store.set(12, 2, CellValue::Number(3.0)).is_none());
+        assert!(store.set(8, 2, CellValue::from("top")).is_none());
+        assert_eq!(store.get(8, 2), Some(&CellValue::from("top")));
+        assert_eq!(store.get(11, 2), None);
+        assert_eq!(store.get(12, 2), Some(&CellValue::Number(3.0)));
+        assert_eq!(store.len(), 3);
+
+        assert_eq!(store.take(12, 2), Some(CellValue::Number(3.0)));
+        assert_eq!(store.take(12, 2), None);
+        let cells: Vec<_> = store.iter().map(|(pos, _)| pos).collect();
+        assert_eq!(cells, [(8, 2), (10, 2)]);
+
+        store.take(8, 2);
+        store.take(10, 2);
+        assert_eq!(store.len(), 0);
+        assert!(store.columns.is_empty());
+    }
+
+    #[test]
+    fn far_rows_are_handed_back() {
+        let mut store = ColumnStore::default();
+        store.set(1, 1, CellValue::Number(1.0));
+        let far = CellValue::Number(2.0);
+        assert_eq!(store.set(1_000_000, 1, far.clone()), Some(far));
+        assert!(store
+            .set(1 + MIN_GAP as u32, 1, CellValue::Number(3.0))
+            .is_none());
+        assert_eq!(store.drain().count(), 2);
+        assert_eq!(store.len(), 0);
+    }
+
+    #[test]
+    fn bare_cells() {
+        let mut cell = CellData::with_value(CellValue::Number(1.0));
+        assert!(is_bare(&cell));
+        cell.data_type = Some("n");
+        assert!(is_bare(&cell));
+        cell.number_format = Some("0.00".into());
+        assert!(!is_bare(&cell));
+        assert!(!is_bare(&CellData::with_value(CellValue::Formula(
+            "A1".to_string()
+        ))));
+    }
+}
@@ -0,0 +1,468 @@
+//! Cell comments: legacy notes and modern threaded comments.
+//!
+//! Every comment is written to the sheet's legacy comments part together with
+//! a VML Note shape, which is what Excel needs to display it at all. A comment
+//! with a [`thread`](Comment::thread) is a threaded comment: the conversation
+//! lives in `xl/threadedComments/`, its authors in `xl/persons/person.xml`,
+//! and the legacy part carries the placeholder text that versions of Excel
+//! without threaded comments show instead, as Excel itself writes it.
+
+use std::collections::HashMap;
+
+use crate::error::{Result, RustypyxlError};
+use crate::rich_text::RichText;
+use crate::utils::{column_to_letter, parse_coordinate};
+use crate::worksheet::{cell_key, decode_cell_key, Worksheet};
+use crate::writer::escape_xml;
+
+/// Namespace of the threadedComments and persons parts.
+const THREADED_NS: &str = "http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments";
+
+/// Author written for a note that has none.
+pub(crate) const DEFAULT_AUTHOR: &str = "RustyPyXL";
+
+/// One entry of a threaded comment conversation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadedComment {
+    /// Display name of the person who wrote it.
+    pub author: String,
+    pub text: String,
+    /// When it was written, as stored in the file ("2024-03-01T09:30:00.00").
+    pub created: Option<String>,
+}
+
+impl ThreadedComment {
+    pub fn new<A: Into<String>, T: Into<String>>(author: A, text: T) -> Self {
+        ThreadedComment {
+            author: author.into(),
+            text: text.into(),
+            created: None,
+        }
+    }
+}
+
+/// A cell comment.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Comment {
+    /// Plain text. For a threaded comment, the text of the first entry.
+    pub text: String,
+    /// Who wrote it; `None` writes a generic author.
+    pub author: Option<String>,
+    /// Formatted runs. When set they are written instead of `text`, which
+    /// holds their plain form.
+    pub rich_text: Option<RichText>,
+    /// Box width in points; `None` keeps the sheet's comment shape or the
+    /// default size.
+    pub width: Option<f64>,
+    /// Box height in points, as `width`.
+    pub height: Option<f64>,
+    /// The conversation of a threaded comment, first entry first. Empty for
+    /// a plain note.
+    pub thread: Vec<ThreadedComment>,
+}
+
+impl Comment {
+    /// A plain note.
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Comment {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// A threaded comment started by `author`.
+    pub fn threaded<A: Into<String>, T: Into<String>>(author: A, text: T) -> Self {
+        let first = ThreadedComment::new(author, text);
+        Comment {
+            text: first.text.clone(),
+            author: Some(first.author.clone()),
+            thread: vec![first],
+            ..Default::default()
+        }
+    }
+
+    /// Set the author.
+    pub fn with_author<S: Into<String>>(mut self, author: S) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
+    /// Set the box size in points.
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+
+    /// Use formatted runs for the text.
+    pub fn with_rich_text(mut self, rich_text: RichText) -> Self {
+        self.text = rich_text.plain();
+        self.rich_text = Some(rich_text);
+        self
+    }
+
+    /// Whether this is a threaded comment rather than a note.
+    pub fn is_threaded(&self) -> bool {
+        !self.thread.is_empty()
+    }
+
+    /// Add a reply to a threaded comment. Fails on a plain note, which
+    /// Excel cannot turn into a conversation.
+    pub fn reply<A: Into<String>, T: Into<String>>(&mut self, author: A, text: T) -> Result<()> {
+        if !self.is_threaded() {
+            return Err(RustypyxlError::custom(
+                "Only threaded comments take replies; this cell has a note",
+            ));
+        }
+        self.thread.push(ThreadedComment::new(author, text));
+        Ok(())
+    }
+
+    /// The text stored in the legacy comments part: the note itself, or for
+    /// a threaded comment the placeholder Excel writes for older versions.
+    pub(crate) fn legacy_text(&self) -> String {
+        let Some((first, replies)) = self.thread.split_first() else {
+            return self.text.clone();
+        };
+        let mut text = String::from(
+            "[Threaded comment]\n\nYour version of Excel allows you to read this threaded comment; \
+             however, any edits to it will get removed if the file is opened in a newer version \
+             of Excel. Learn more: https://go.microsoft.com/fwlink/?linkid=870924\n\nComment:\n    ",
+        );
+        text.push_str(&first.text);
+        for reply in replies {
+            text.push_str("\nReply:\n    ");
+            text.push_str(&reply.text);
+        }
+        text
+    }
+}
+
+impl From<String> for Comment {
+    fn from(text: String) -> Self {
+        Comment::new(text)
+    }
+}
+
+impl From<&str> for Comment {
+    fn from(text: &str) -> Self {
+        Comment::new(text)
+    }
+}
+
+impl Worksheet {
+    /// Start a threaded comment on a cell, or reply to the one already
+    /// there. Fails if the cell has a plain note.
+    pub fn add_threaded_comment(
+        &mut self,
+        row: u32,
+        column: u32,
+        author: &str,
+        text: &str,
+    ) -> Result<()> {
+        let existing = self
+            .cells
+            .get_mut(&cell_key(row, column))
+            .and_then(|cell| cell.comment.as_mut());
+        match existing {
+            Some(comment) => comment.reply(author, text),
+            None => {
+                self.set_cell_comment(row, column, Comment::threaded(author, text));
+                Ok(())
+            }
+        }
+    }
+
+    /// Cells with comments as ((row, column), comment), in row-major order.
+    pub(crate) fn sorted_comments(&self) -> Vec<((u32, u32), &Comment)> {
+        let mut comments: Vec<((u32, u32), &Comment)> = self
+            .cells
+            .iter()
+            .filter_map(|(key, cell)| Some((decode_cell_key(*key), cell.comment.as_deref()?)))
+            .collect();
+        comments.sort_unstable_by_key(|(cell, _)| *cell);
+        comments
+    }
+
+    pub(crate) fn has_threaded_comments(&self) -> bool {
+        self.cells
+            .values()
+            .any(|cell| cell.comment.as_ref().is_some_and(|c| c.is_threaded()))
+    }
+}
+
+/// Id of one threaded comment entry, stable for a given sheet, cell and
+/// position in the thread so the legacy part can cite the first entry.
+pub(crate) fn thread_entry_id(sheet_id: u32, row: u32, column: u32, index: usize) -> String {
+    format!(
+        "{{{:08X}-{:04X}-4000-8000-{:012X}}}",
+        row,
+        sheet_id,
+        (u64::from(column) << 16) | index as u64
+    )
+}
+
+/// Id of the n-th (0-based) entry in the persons part.
+fn person_id(index: usize) -> String {
+    format!("{{{:08X}-0000-4000-8000-000000000000}}", index + 1)
+}
+
+/// Distinct authors of every threaded comment entry in the workbook, in
+/// order of first appearance; their positions are the persons part ids.
+pub(crate) fn collect_persons(worksheets: &[Worksheet]) -> Vec<String> {
+    let mut persons: Vec<String> = Vec::new();
+    for ws in worksheets.iter().filter(|ws| ws.opaque.is_none()) {
+        for (_, comment) in ws.sorted_comments() {
+            for entry in &comment.thread {
+                if !persons.contains(&entry.author) {
+                    persons.push(entry.author.clone());
+                }
+            }
+        }
+    }
+    persons
+}
+
+/// The persons part listing threaded comment authors.
+pub(crate) fn persons_xml(persons: &[String]) -> String {
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<personList xmlns=\"{}\" xmlns:x=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">",
+        THREADED_NS
+    );
+    for (i, name) in persons.iter().enumerate() {
+        let name = escape_xml(name);
+        xml.push_str(&format!(
+            "<person displayName=\"{}\" id=\"{}\" userId=\"{}\" providerId=\"None\"/>",
+            name,
+            person_id(i),
+            name
+        ));
+    }
+    xml.push_str("</personList>");
+    xml
+}
+
+/// The threadedComments part for one sheet.
+pub(crate) fn threaded_comments_xml(
+    worksheet: &Worksheet,
+    sheet_id: u32,
+    persons: &[String],
+) -> String {
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<ThreadedComments xmlns=\"{}\" xmlns:x=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">",
+        THREADED_NS
+    );
+    for ((row, col), comment) in worksheet.sorted_comments() {
+        let coord = format!("{}{}", column_to_letter(col), row);
+        let root_id = thread_entry_id(sheet_id, row, col, 0);
+        for (i, entry) in comment.thread.iter().enumerate() {
+            let person = persons
+                .iter()
+                .position(|p| *p == entry.author)
+                .unwrap_or_default();
+            xml.push_str(&format!("<threadedComment ref=\"{}\"", coord));
+            if let Some(created) = &entry.created {
+                xml.push_str(&format!(" dT=\"{}\"", escape_xml(created)));
+            }
+            xml.push_str(&format!(
+                " personId=\"{}\" id=\"{}\"",
+                person_id(person),
+                thread_entry_id(sheet_id, row, col, i)
+            ));
+            if i > 0 {
+                xml.push_str(&format!(" parentId=\"{}\"", root_id));
+            }
+            xml.push_str(&format!(
+                "><text>{}</text></threadedComment>",
+                escape_xml(&entry.text)
+            ));
+        }
+    }
+    xml.push_str("</ThreadedComments>");
+    xml
+}
+
+/// Person id to display name, from a persons part.
+pub(crate) fn parse_persons(xml: &[u8]) -> HashMap<String, String> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut persons = HashMap::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e) | Event::Empty(e)) if e.local_name().as_ref() == b"person" => {
+                let mut id = None;
+                let mut name = None;
+                for attr in e.attributes().flatten() {
+                    let value = attr.unescape_value().map(|v| v.to_string()).ok();
+                    match attr.key.local_name().as_ref() {
+                        b"id" => id = value,
+                        b"displayName" => name = value,
+                        _ => {}
+                    }
+                }
+                if let (Some(id), Some(name)) = (id, name) {
+                    persons.insert(id, name);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    persons
+}
+
+/// Attach the conversations in a threadedComments part to the sheet's
+/// comments, replacing the placeholder text loaded from the legacy part.
+pub(crate) fn parse_threaded_comments(
+    xml: &[u8],
+    persons: &HashMap<String, String>,
+    worksheet: &mut Worksheet,
+) -> Result<()> {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    struct Entry {
+        cell: (u32, u32),
+        id: Option<String>,
+        parent: Option<String>,
+        comment: ThreadedComment,
+    }
+
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut current: Option<Entry> = None;
+    let mut in_text = false;
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"threadedComment" => {
+                    let mut entry = Entry {
+                        cell: (0, 0),
+                        id: None,
+                        parent: None,
+                        comment: ThreadedComment::new("", ""),
+                    };
+                    for attr in e.attributes().flatten() {
+                        let value = attr.unescape_value().map(|v| v.to_string()).ok();
+                        match attr.key.local_name().as_ref() {
+                            b"ref" => {
+                                entry.cell = value
+                                    .and_then(|v| parse_coordinate(&v).ok())
+                                    .unwrap_or((0, 0))
+                            }
+                            b"id" => entry.id = value,
+                            b"parentId" => entry.parent = value,
+                            b"dT" => entry.comment.created = value,
+                            b"personId" => {
+                                entry.comment.author = value
+                                    .and_then(|id| persons.get(&id).cloned())
+                                    .unwrap_or_default()
+                            }
+                            _ => {}
+                        }
+                    }
+                    current = Some(entry);
+                }
+                b"text" => in_text = true,
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_text => {
+                if let Some(entry) = current.as_mut() {
+                    entry
+                        .comment
+                        .text
+                        .push_str(&e.unescape().unwrap_or_default());
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"text" => in_text = false,
+                b"threadedComment" => entries.extend(current.take()),
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(RustypyxlError::ParseError(format!(
+                    "XML parsing error in threaded comments: {}",
+                    e
+                )));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    // First entries start threads; replies join the thread of their parent.
+    let mut threads: Vec<(Entry, Vec<ThreadedComment>)> = Vec::new();
+    for entry in entries.into_iter().filter(|e| e.cell.0 > 0) {
+        let parent = entry.parent.as_ref().and_then(|parent| {
+            threads
+                .iter_mut()
+                .find(|(root, _)| root.id.as_ref() == Some(parent))
+        });
+        match parent {
+            Some((_, replies)) => replies.push(entry.comment),
+            None => threads.push((entry, Vec::new())),
+        }
+    }
+    for (root, replies) in threads {
+        let (row, col) = root.cell;
+        let cell = worksheet.cells.entry(cell_key(row, col)).or_default();
+        let comment = cell.comment.get_or_insert_with(Default::default);
+        comment.text = root.comment.text.clone();
+        comment.author = Some(root.comment.author.clone());
+        comment.rich_text = None;
+        comment.thread = std::iter::once(root.comment).chain(replies).collect();
+        worksheet.update_dimensions(row, col);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_threaded_comments_xml_round_trip() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.add_threaded_comment(2, 3, "Ann", "Is this right?")
+            .unwrap();
+        ws.add_threaded_comment(2, 3, "Bob", "Yes <checked>")
+            .unwrap();
+        ws.set_cell_comment(1, 1, "just a note");
+        assert!(ws.add_threaded_comment(1, 1, "Ann", "nope").is_err());
+
+        let persons = collect_persons(std::slice::from_ref(&ws));
+        assert_eq!(persons, ["Ann", "Bob"]);
+        let xml = threaded_comments_xml(&ws, 1, &persons);
+        assert_eq!(xml.matches("<threadedComment ").count(), 2);
+        assert!(xml.contains(&format!("parentId=\"{}\"", thread_entry_id(1, 2, 3, 0))));
+        assert!(xml.contains("Yes &lt;checked&gt;"));
+
+        let mut loaded = Worksheet::new("Sheet1");
+        let persons = parse_persons(persons_xml(&persons).as_bytes());
+        parse_threaded_comments(xml.as_bytes(), &persons, &mut loaded).unwrap();
+        let cell = loaded.get_cell(2, 3).unwrap();
+        let comment = cell.comment.as_ref().unwrap();
+        assert_eq!(comment.text, "Is this right?");
+        assert_eq!(comment.author.as_deref(), Some("Ann"));
+        assert_eq!(
+            comment.thread[1],
+            ThreadedComment::new("Bob", "Yes <checked>")
+        );
+    }
+
+    #[test]
+    fn test_legacy_placeholder_text() {
+        let mut comment = Comment::threaded("Ann", "First");
+        comment.reply("Bob", "Second").unwrap();
+        let text = comment.legacy_text();
+        assert!(text.starts_with("[Threaded comment]"));
+        assert!(text.ends_with("Comment:\n    First\nReply:\n    Second"));
+        assert_eq!(Comment::new("note").legacy_text(), "note");
+    }
+}
@@ -399,6 +399,27 @@ impl IconSetStyle {
         })
     }
 
+    /// How many icons the set has.
+    pub fn icon_count(&self) -> u32 {
+        match self {
+            IconSetStyle::ThreeArrows
+            | IconSetStyle::ThreeArrowsGray
+            | IconSetStyle::ThreeFlags
+            | IconSetStyle::ThreeTrafficLights
+            | IconSetStyle::ThreeSigns
+            | IconSetStyle::ThreeSymbols
+            | IconSetStyle::ThreeSymbols2 => 3,
+            IconSetStyle::FourArrows
+            | IconSetStyle::FourArrowsGray
+            | IconSetStyle::FourRating
+            | IconSetStyle::FourTrafficLights => 4,
+            IconSetStyle::FiveArrows
+            | IconSetStyle::FiveArrowsGray
+            | IconSetStyle::FiveRating
+            | IconSetStyle::FiveQuarters => 5,
+        }
+    }
+
     /// Get the XML type name.
     pub fn xml_type(&self) -> &'static str {
         match self {
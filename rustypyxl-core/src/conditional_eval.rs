@@ -0,0 +1,696 @@
+//! Working out what conditional formatting shows.
+//!
+//! A file stores only the rules; Excel decides which cells they highlight
+//! each time it draws the sheet. [`Workbook::conditional_format_results`]
+//! evaluates the rules against the current values so HTML and text exports,
+//! or any other renderer, can show the highlighting a user would see.
+//! Formulas go through the workbook's formula engine ([`crate::formula`]),
+//! so a rule whose formula needs a function outside its subset never
+//! matches. Time-period rules depend on the day they are looked at and are
+//! not evaluated.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::conditional::{
+    ConditionalColor, ConditionalFormat, ConditionalFormatType, ConditionalOperator,
+    ConditionalRule, IconSetStyle,
+};
+use crate::error::Result;
+use crate::formula::{compare, FormulaValue};
+use crate::translate::translate_formula;
+use crate::utils::range_boundaries;
+use crate::workbook::Workbook;
+
+/// What conditional formatting does to one cell.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConditionalResult {
+    /// 1-based row.
+    pub row: u32,
+    /// 1-based column.
+    pub column: u32,
+    /// The formats of the matching rules merged into one, a higher-priority
+    /// rule's setting winning; `None` when only scales, bars or icons apply.
+    pub format: Option<ConditionalFormat>,
+    /// The priorities of the rules that apply, highest priority first.
+    pub priorities: Vec<u32>,
+    /// The fill a color scale gives the cell, as ARGB.
+    pub scale_color: Option<ConditionalColor>,
+    /// The length of a data bar, from 0.0 at the bar's minimum to 1.0 at
+    /// its maximum.
+    pub data_bar: Option<f64>,
+    /// The icon set and the cell's icon in it, 0 being the icon for the
+    /// lowest values.
+    pub icon: Option<(IconSetStyle, u32)>,
+}
+
+/// What one rule does to one cell.
+enum Effect {
+    Format,
+    Color(ConditionalColor),
+    Bar(f64),
+    Icon(IconSetStyle, u32),
+}
+
+/// A rectangle as `(min_row, min_col, max_row, max_col)`.
+type Bounds = (u32, u32, u32, u32);
+
+impl Workbook {
+    /// Evaluate the conditional formatting on sheet `sheet_name` against its
+    /// current values and list the cells it changes, in row order. Rules
+    /// apply in priority order, and a matching rule marked stop-if-true
+    /// keeps lower-priority rules off that cell. Only cells within the
+    /// sheet's used range are evaluated.
+    pub fn conditional_format_results(&self, sheet_name: &str) -> Result<Vec<ConditionalResult>> {
+        let ws = self.get_sheet_by_name(sheet_name)?;
+        let mut rules: Vec<(&ConditionalRule, Vec<Bounds>)> = Vec::new();
+        for cf in &ws.conditional_formatting {
+            let areas = sqref_areas(&cf.range, ws.max_row(), ws.max_column());
+            for rule in &cf.rules {
+                rules.push((rule, areas.clone()));
+            }
+        }
+        rules.sort_by_key(|(rule, _)| rule.priority);
+
+        let mut results: HashMap<(u32, u32), ConditionalResult> = HashMap::new();
+        let mut stopped = HashSet::new();
+        for (rule, areas) in rules {
+            let Some(&(top, left, _, _)) = areas.first() else {
+                continue;
+            };
+            let mut seen = HashSet::new();
+            let cells: Vec<(u32, u32)> = areas
+                .iter()
+                .flat_map(|&(r1, c1, r2, c2)| {
+                    (r1..=r2).flat_map(move |r| (c1..=c2).map(move |c| (r, c)))
+                })
+                .filter(|cell| seen.insert(*cell))
+                .collect();
+            let values = cells
+                .iter()
+                .map(|&(row, col)| self.evaluate_cell(sheet_name, row, col))
+                .collect::<Result<Vec<_>>>()?;
+            let input = RuleInput {
+                wb: self,
+                sheet: sheet_name,
+                anchor: (top, left),
+                cells: &cells,
+                values: &values,
+            };
+
+            for (&(row, column), effect) in cells.iter().zip(input.effects(rule)) {
+                let Some(effect) = effect else {
+                    continue;
+                };
+                if stopped.contains(&(row, column)) {
+                    continue;
+                }
+                let result = results
+                    .entry((row, column))
+                    .or_insert_with(|| ConditionalResult {
+                        row,
+                        column,
+                        ..Default::default()
+                    });
+                result.priorities.push(rule.priority);
+                match effect {
+                    Effect::Format => {
+                        if let Some(format) = &rule.format {
+                            result.format = Some(match result.format.take() {
+                                Some(higher) => merge_formats(higher, format),
+                                None => format.clone(),
+                            });
+                        }
+                        if rule.stop_if_true {
+                            stopped.insert((row, column));
+                        }
+                    }
+                    Effect::Color(color) => {
+                        result.scale_color.get_or_insert(color);
+                    }
+                    Effect::Bar(length) => {
+                        result.data_bar.get_or_insert(length);
+                    }
+                    Effect::Icon(style, icon) => {
+                        result.icon.get_or_insert((style, icon));
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<ConditionalResult> = results.into_values().collect();
+        results.sort_unstable_by_key(|r| (r.row, r.column));
+        Ok(results)
+    }
+}
+
+/// The areas of a space-separated `sqref`, whole rows and columns included,
+/// clipped to the used range. Parts that do not parse are skipped.
+fn sqref_areas(sqref: &str, max_row: u32, max_col: u32) -> Vec<Bounds> {
+    sqref
+        .split_whitespace()
+        .filter_map(|part| range_boundaries(part).ok())
+        .map(|(r1, c1, r2, c2)| {
+            (
+                r1.unwrap_or(1),
+                c1.unwrap_or(1),
+                r2.unwrap_or(max_row).min(max_row),
+                c2.unwrap_or(max_col).min(max_col),
+            )
+        })
+        .filter(|&(r1, c1, r2, c2)| r1 <= r2 && c1 <= c2)
+        .collect()
+}
+
+/// `higher` with each setting it leaves open taken from `lower`.
+fn merge_formats(higher: ConditionalFormat, lower: &ConditionalFormat) -> ConditionalFormat {
+    let lower = lower.clone();
+    ConditionalFormat {
+        font_color: higher.font_color.or(lower.font_color),
+        bold: higher.bold.or(lower.bold),
+        italic: higher.italic.or(lower.italic),
+        underline: higher.underline.or(lower.underline),
+        strikethrough: higher.strikethrough.or(lower.strikethrough),
+        fill_color: higher.fill_color.or(lower.fill_color),
+        border_color: higher.border_color.or(lower.border_color),
+        number_format: higher.number_format.or(lower.number_format),
+    }
+}
+
+/// One rule's cells and their values.
+struct RuleInput<'a> {
+    wb: &'a Workbook,
+    sheet: &'a str,
+    /// The top-left cell of the first area, which relative references in
+    /// the rule's formulas are written for.
+    anchor: (u32, u32),
+    cells: &'a [(u32, u32)],
+    values: &'a [FormulaValue],
+}
+
+impl RuleInput<'_> {
+    /// What `rule` does to each cell, in the order of `cells`.
+    fn effects(&self, rule: &ConditionalRule) -> Vec<Option<Effect>> {
+        let matches = |test: &dyn Fn(usize) -> bool| {
+            (0..self.cells.len())
+                .map(|i| test(i).then_some(Effect::Format))
+                .collect()
+        };
+        let text = |i: usize| self.values[i].to_text().ok().map(|t| t.to_lowercase());
+        let needle = rule.text.as_deref().unwrap_or("").to_lowercase();
+        let blank = |i: usize| match &self.values[i] {
+            FormulaValue::Empty => true,
+            FormulaValue::Text(t) => t.trim().is_empty(),
+            _ => false,
+        };
+
+        match rule.rule_type {
+            ConditionalFormatType::CellIs => matches(&|i| self.cell_is(rule, i)),
+            ConditionalFormatType::Expression => matches(&|i| {
+                rule.formula1
+                    .as_deref()
+                    .is_some_and(|f| is_true(&self.formula(f, i)))
+            }),
+            ConditionalFormatType::ContainsText => {
+                matches(&|i| text(i).is_some_and(|t| t.contains(&needle)))
+            }
+            ConditionalFormatType::NotContainsText => {
+                matches(&|i| text(i).is_some_and(|t| !t.contains(&needle)))
+            }
+            ConditionalFormatType::BeginsWith => {
+                matches(&|i| text(i).is_some_and(|t| t.starts_with(&needle)))
+            }
+            ConditionalFormatType::EndsWith => {
+                matches(&|i| text(i).is_some_and(|t| t.ends_with(&needle)))
+            }
+            ConditionalFormatType::ContainsBlanks => matches(&blank),
+            ConditionalFormatType::NotContainsBlanks => matches(&|i| !blank(i)),
+            ConditionalFormatType::ContainsErrors => matches(&|i| self.values[i].is_error()),
+            ConditionalFormatType::NotContainsErrors => matches(&|i| !self.values[i].is_error()),
+            ConditionalFormatType::Top10 => {
+                let mut numbers = self.numbers();
+                if rule.bottom {
+                    numbers.sort_by(|a, b| a.total_cmp(b));
+                } else {
+                    numbers.sort_by(|a, b| b.total_cmp(a));
+                }
+                let rank = rule.rank.unwrap_or(10) as usize;
+                let count = match rule.percent {
+                    true => (numbers.len() * rank / 100).max(1),
+                    false => rank,
+                };
+                let Some(&cutoff) = numbers.get(count.min(numbers.len()).saturating_sub(1)) else {
+                    return matches(&|_| false);
+                };
+                matches(&|i| match self.values[i] {
+                    FormulaValue::Number(n) if rule.bottom => n <= cutoff,
+                    FormulaValue::Number(n) => n >= cutoff,
+                    _ => false,
+                })
+            }
+            ConditionalFormatType::AboveAverage => {
+                let numbers = self.numbers();
+                if numbers.is_empty() {
+                    return matches(&|_| false);
+                }
+                let count = numbers.len() as f64;
+                let mean = numbers.iter().sum::<f64>() / count;
+                let spread = rule.std_dev.map_or(0.0, |k| {
+                    let variance = numbers.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / count;
+                    k as f64 * variance.sqrt()
+                });
+                matches(&|i| match self.values[i] {
+                    FormulaValue::Number(n) if rule.above_average => {
+                        let bound = mean + spread;
+                        n > bound || (rule.equal_average && n == bound)
+                    }
+                    FormulaValue::Number(n) => {
+                        let bound = mean - spread;
+                        n < bound || (rule.equal_average && n == bound)
+                    }
+                    _ => false,
+                })
+            }
+            ConditionalFormatType::DuplicateValues | ConditionalFormatType::UniqueValues => {
+                let keys: Vec<Option<String>> = self.values.iter().map(value_key).collect();
+                let mut counts: HashMap<&str, usize> = HashMap::new();
+                for key in keys.iter().flatten() {
+                    *counts.entry(key).or_default() += 1;
+                }
+                let duplicates = rule.rule_type == ConditionalFormatType::DuplicateValues;
+                matches(&|i| {
+                    keys[i]
+                        .as_deref()
+                        .is_some_and(|key| (counts[key] > 1) == duplicates)
+                })
+            }
+            ConditionalFormatType::ColorScale => self.color_scale(rule),
+            ConditionalFormatType::DataBar => self.data_bar(rule),
+            ConditionalFormatType::IconSet => self.icon_set(rule),
+            ConditionalFormatType::TimePeriod => matches(&|_| false),
+        }
+    }
+
+    /// Evaluate `formula`, written for the anchor cell, for cell `i`.
+    fn formula(&self, formula: &str, i: usize) -> FormulaValue {
+        let (row, col) = self.cells[i];
+        let formula = translate_formula(
+            formula,
+            row as i64 - self.anchor.0 as i64,
+            col as i64 - self.anchor.1 as i64,
+        );
+        self.wb
+            .evaluate_formula_at(self.sheet, &formula, Some((row, col)))
+            .unwrap_or_else(|_| FormulaValue::Error("#REF!".to_string()))
+    }
+
+    /// Whether cell `i` passes a cell-value rule.
+    fn cell_is(&self, rule: &ConditionalRule, i: usize) -> bool {
+        let value = &self.values[i];
+        let Some(operator) = &rule.operator else {
+            return false;
+        };
+        let Some(first) = rule.formula1.as_deref().map(|f| self.formula(f, i)) else {
+            return false;
+        };
+        if value.is_error() || first.is_error() {
+            return false;
+        }
+        let test =
+            |op: &str, other: &FormulaValue| compare(op, value, other) == FormulaValue::Bool(true);
+        let between = || {
+            let second = rule
+                .formula2
+                .as_deref()
+                .map_or(FormulaValue::Empty, |f| self.formula(f, i));
+            let (low, high) = match compare("<=", &first, &second) == FormulaValue::Bool(true) {
+                true => (&first, &second),
+                false => (&second, &first),
+            };
+            test(">=", low) && test("<=", high)
+        };
+        match operator {
+            ConditionalOperator::LessThan => test("<", &first),
+            ConditionalOperator::LessThanOrEqual => test("<=", &first),
+            ConditionalOperator::Equal => test("=", &first),
+            ConditionalOperator::NotEqual => test("<>", &first),
+            ConditionalOperator::GreaterThanOrEqual => test(">=", &first),
+            ConditionalOperator::GreaterThan => test(">", &first),
+            ConditionalOperator::Between => between(),
+            ConditionalOperator::NotBetween => !between(),
+        }
+    }
+
+    /// The numeric values, which is all scales, bars and ranking rules look at.
+    fn numbers(&self) -> Vec<f64> {
+        self.values
+            .iter()
+            .filter_map(|v| match v {
+                FormulaValue::Number(n) => Some(*n),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The value a scale, bar or icon threshold (a `cfvo`) stands for, given
+    /// the numbers sorted ascending.
+    fn threshold(&self, kind: &str, value: Option<&str>, sorted: &[f64]) -> Option<f64> {
+        let (min, max) = (*sorted.first()?, *sorted.last()?);
+        let number = || {
+            let value = value?;
+            value.trim().parse::<f64>().ok().or_else(|| {
+                match self.wb.evaluate_formula_at(self.sheet, value, None).ok()? {
+                    FormulaValue::Number(n) => Some(n),
+                    _ => None,
+                }
+            })
+        };
+        match kind {
+            "min" | "autoMin" => Some(min),
+            "max" | "autoMax" => Some(max),
+            "num" | "formula" => number(),
+            "percent" => Some(min + (max - min) * number()? / 100.0),
+            "percentile" => {
+                let rank = (number()? / 100.0).clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+                let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+                Some(sorted[below] + (sorted[above] - sorted[below]) * rank.fract())
+            }
+            _ => None,
+        }
+    }
+
+    fn sorted_numbers(&self) -> Vec<f64> {
+        let mut numbers = self.numbers();
+        numbers.sort_by(|a, b| a.total_cmp(b));
+        numbers
+    }
+
+    fn color_scale(&self, rule: &ConditionalRule) -> Vec<Option<Effect>> {
+        let none = || (0..self.cells.len()).map(|_| None).collect();
+        let Some(scale) = &rule.color_scale else {
+            return none();
+        };
+        let sorted = self.sorted_numbers();
+        let min = self.threshold(&scale.min_type, scale.min_value.as_deref(), &sorted);
+        let max = self.threshold(&scale.max_type, scale.max_value.as_deref(), &sorted);
+        let (Some(min), Some(max), Some(low), Some(high)) =
+            (min, max, rgb(&scale.min_color), rgb(&scale.max_color))
+        else {
+            return none();
+        };
+        let mid = match &scale.mid_color {
+            Some(color) => {
+                let kind = scale.mid_type.as_deref().unwrap_or("percentile");
+                match (
+                    self.threshold(kind, scale.mid_value.as_deref(), &sorted),
+                    rgb(color),
+                ) {
+                    (Some(at), Some(color)) => Some((at, color)),
+                    _ => return none(),
+                }
+            }
+            None => None,
+        };
+        self.values
+            .iter()
+            .map(|value| {
+                let FormulaValue::Number(n) = *value else {
+                    return None;
+                };
+                let color = match mid {
+                    Some((at, mid)) if n <= at => blend(low, mid, fraction(n, min, at)),
+                    Some((at, mid)) => blend(mid, high, fraction(n, at, max)),
+                    None => blend(low, high, fraction(n, min, max)),
+                };
+                Some(Effect::Color(color))
+            })
+            .collect()
+    }
+
+    fn data_bar(&self, rule: &ConditionalRule) -> Vec<Option<Effect>> {
+        let sorted = self.sorted_numbers();
+        let bounds = rule.data_bar.as_ref().and_then(|bar| {
+            Some((
+                self.threshold(&bar.min_type, bar.min_value.as_deref(), &sorted)?,
+                self.threshold(&bar.max_type, bar.max_value.as_deref(), &sorted)?,
+            ))
+        });
+        self.values
+            .iter()
+            .map(|value| match (value, bounds) {
+                (FormulaValue::Number(n), Some((min, max))) => {
+                    Some(Effect::Bar(fraction(*n, min, max)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn icon_set(&self, rule: &ConditionalRule) -> Vec<Option<Effect>> {
+        let sorted = self.sorted_numbers();
+        let Some(icons) = &rule.icon_set else {
+            return (0..self.cells.len()).map(|_| None).collect();
+        };
+        let count = icons.style.icon_count();
+        let thresholds: Option<Vec<f64>> = if icons.thresholds.len() == count as usize {
+            icons
+                .thresholds
+                .iter()
+                .map(|(kind, value)| self.threshold(kind, Some(value), &sorted))
+                .collect()
+        } else {
+            (0..count)
+                .map(|k| {
+                    let percent = (k as f64 * 100.0 / count as f64).round().to_string();
+                    self.threshold("percent", Some(&percent), &sorted)
+                })
+                .collect()
+        };
+        self.values
+            .iter()
+            .map(|value| {
+                let (FormulaValue::Number(n), Some(thresholds)) = (value, &thresholds) else {
+                    return None;
+                };
+                let icon = thresholds.iter().rposition(|t| n >= t).unwrap_or(0) as u32;
+                let icon = if icons.reverse {
+                    count - 1 - icon
+                } else {
+                    icon
+                };
+                Some(Effect::Icon(icons.style.clone(), icon))
+            })
+            .collect()
+    }
+}
+
+/// Whether a rule formula's result counts as true.
+fn is_true(value: &FormulaValue) -> bool {
+    match value {
+        FormulaValue::Bool(b) => *b,
+        FormulaValue::Number(n) => *n != 0.0,
+        _ => false,
+    }
+}
+
+/// What duplicate and unique rules compare: text ignoring case, numbers and
+/// booleans as written. Blanks and errors take no part.
+fn value_key(value: &FormulaValue) -> Option<String> {
+    match value {
+        FormulaValue::Empty | FormulaValue::Error(_) => None,
+        FormulaValue::Text(t) => Some(format!("s{}", t.to_lowercase())),
+        other => other.to_text().ok().map(|t| format!("n{}", t)),
+    }
+}
+
+/// Where `n` falls between `low` and `high`, from 0.0 to 1.0.
+fn fraction(n: f64, low: f64, high: f64) -> f64 {
+    if high <= low {
+        return if n >= high { 1.0 } else { 0.0 };
+    }
+    ((n - low) / (high - low)).clamp(0.0, 1.0)
+}
+
+/// The red, green and blue of an RGB color; `None` for theme colors, which
+/// have no fixed value.
+fn rgb(color: &ConditionalColor) -> Option<[u8; 3]> {
+    let hex = color.rgb.as_deref()?;
+    let hex = &hex[hex.len().checked_sub(6)?..];
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// The color `t` of the way from `from` to `to`.
+fn blend(from: [u8; 3], to: [u8; 3], t: f64) -> ConditionalColor {
+    let mix = |i: usize| (from[i] as f64 + (to[i] as f64 - from[i] as f64) * t).round() as u8;
+    ConditionalColor::rgb(format!("FF{:02X}{:02X}{:02X}", mix(0), mix(1), mix(2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::CellValue;
+    use crate::conditional::{ColorScale, ConditionalFormatting, DataBar, IconSet};
+
+    fn sheet_with(values: &[f64], rules: Vec<ConditionalRule>) -> Workbook {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("S".to_string())).unwrap();
+        let ws = wb.get_sheet_by_name_mut("S").unwrap();
+        for (i, v) in values.iter().enumerate() {
+            ws.set_cell_value(i as u32 + 1, 1, CellValue::Number(*v));
+        }
+        let mut cf = ConditionalFormatting::new(format!("A1:A{}", values.len()));
+        for rule in rules {
+            cf.add_rule(rule);
+        }
+        ws.add_conditional_formatting(cf);
+        wb
+    }
+
+    fn fill(color: &str) -> ConditionalFormat {
+        ConditionalFormat::new().with_fill(ConditionalColor::rgb(color))
+    }
+
+    fn rows(results: &[ConditionalResult]) -> Vec<u32> {
+        results.iter().map(|r| r.row).collect()
+    }
+
+    #[test]
+    fn cell_value_and_expression_rules() {
+        let wb = sheet_with(
+            &[5.0, 15.0, 25.0],
+            vec![
+                ConditionalRule::cell_is(ConditionalOperator::GreaterThan, "10")
+                    .with_format(fill("FFFF0000")),
+                ConditionalRule::formula("MOD(A1,2)=1")
+                    .with_format(
+                        ConditionalFormat::new()
+                            .with_bold(true)
+                            .with_fill(ConditionalColor::green()),
+                    )
+                    .with_priority(2),
+            ],
+        );
+        let results = wb.conditional_format_results("S").unwrap();
+        assert_eq!(rows(&results), [1, 2, 3]);
+        // Row 1 matches only the expression; rows 2 and 3 match both and
+        // keep the first rule's fill.
+        assert_eq!(results[0].priorities, [2]);
+        assert_eq!(
+            results[0].format.as_ref().unwrap().fill_color,
+            Some(ConditionalColor::green())
+        );
+        let merged = results[1].format.as_ref().unwrap();
+        assert_eq!(merged.fill_color, Some(ConditionalColor::rgb("FFFF0000")));
+        assert_eq!(merged.bold, Some(true));
+        assert_eq!(results[1].priorities, [1, 2]);
+
+        let mut between = ConditionalRule::cell_is(ConditionalOperator::Between, "20");
+        between.formula2 = Some("10".to_string());
+        let wb = sheet_with(
+            &[5.0, 15.0, 20.0, 25.0],
+            vec![between.with_format(fill("FF00FF00"))],
+        );
+        assert_eq!(rows(&wb.conditional_format_results("S").unwrap()), [2, 3]);
+    }
+
+    #[test]
+    fn stop_if_true_keeps_later_rules_off() {
+        let mut first = ConditionalRule::cell_is(ConditionalOperator::LessThan, "10")
+            .with_format(fill("FFFF0000"));
+        first.stop_if_true = true;
+        let second = ConditionalRule::formula("TRUE")
+            .with_format(ConditionalFormat::new().with_bold(true))
+            .with_priority(2);
+        let wb = sheet_with(&[5.0, 50.0], vec![first, second]);
+        let results = wb.conditional_format_results("S").unwrap();
+        assert_eq!(results[0].priorities, [1]);
+        assert_eq!(results[0].format.as_ref().unwrap().bold, None);
+        assert_eq!(results[1].priorities, [2]);
+    }
+
+    #[test]
+    fn ranking_and_text_rules() {
+        let values = [1.0, 9.0, 4.0, 9.0, 7.0];
+        let rule_rows = |rule: ConditionalRule| {
+            rows(
+                &sheet_with(&values, vec![rule.with_format(fill("FFFF0000"))])
+                    .conditional_format_results("S")
+                    .unwrap(),
+            )
+        };
+        assert_eq!(rule_rows(ConditionalRule::top(2)), [2, 4]);
+        assert_eq!(rule_rows(ConditionalRule::bottom(2)), [1, 3]);
+        assert_eq!(rule_rows(ConditionalRule::above_average()), [2, 4, 5]);
+        assert_eq!(rule_rows(ConditionalRule::below_average()), [1, 3]);
+        assert_eq!(rule_rows(ConditionalRule::duplicate_values()), [2, 4]);
+        assert_eq!(rule_rows(ConditionalRule::unique_values()), [1, 3, 5]);
+        assert_eq!(rule_rows(ConditionalRule::contains_text("9")), [2, 4]);
+        assert!(rule_rows(ConditionalRule::time_period("today")).is_empty());
+    }
+
+    #[test]
+    fn scales_bars_and_icons() {
+        let wb = sheet_with(
+            &[0.0, 50.0, 100.0],
+            vec![
+                ConditionalRule::with_color_scale(ColorScale::two_color(
+                    ConditionalColor::rgb("FF000000"),
+                    ConditionalColor::rgb("FFFFFFFF"),
+                )),
+                ConditionalRule::with_data_bar(DataBar::new()).with_priority(2),
+                ConditionalRule::with_icon_set(IconSet::new(IconSetStyle::ThreeArrows))
+                    .with_priority(3),
+            ],
+        );
+        let results = wb.conditional_format_results("S").unwrap();
+        let colors: Vec<_> = results
+            .iter()
+            .map(|r| r.scale_color.clone().unwrap().rgb.unwrap())
+            .collect();
+        assert_eq!(colors, ["FF000000", "FF808080", "FFFFFFFF"]);
+        let bars: Vec<_> = results.iter().map(|r| r.data_bar.unwrap()).collect();
+        assert_eq!(bars, [0.0, 0.5, 1.0]);
+        let icons: Vec<_> = results.iter().map(|r| r.icon.clone().unwrap().1).collect();
+        assert_eq!(icons, [0, 1, 2]);
+        assert!(results.iter().all(|r| r.format.is_none()));
+
+        let three = sheet_with(
+            &[0.0, 10.0, 100.0],
+            vec![ConditionalRule::with_color_scale(ColorScale::three_color(
+                ConditionalColor::rgb("FF000000"),
+                ConditionalColor::rgb("FF0000FF"),
+                ConditionalColor::rgb("FFFFFFFF"),
+            ))],
+        );
+        let results = three.conditional_format_results("S").unwrap();
+        assert_eq!(
+            results[1].scale_color.as_ref().unwrap().rgb.as_deref(),
+            Some("FF0000FF")
+        );
+    }
+
+    #[test]
+    fn rules_from_a_loaded_file() {
+        let wb = sheet_with(
+            &[5.0, 15.0],
+            vec![
+                ConditionalRule::cell_is(ConditionalOperator::GreaterThan, "10")
+                    .with_format(fill("FFFF0000")),
+            ],
+        );
+        let wb = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+        let results = wb.conditional_format_results("S").unwrap();
+        assert_eq!(rows(&results), [2]);
+        assert_eq!(
+            results[0]
+                .format
+                .as_ref()
+                .unwrap()
+                .fill_color
+                .as_ref()
+                .and_then(|c| c.rgb.as_deref()),
+            Some("FFFF0000")
+        );
+        assert!(wb.conditional_format_results("Missing").is_err());
+    }
+}
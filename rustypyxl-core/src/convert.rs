@@ -0,0 +1,204 @@
+//! Typed conversion of cell values on read.
+//!
+//! A [`ValueConverter`] turns whatever a cell holds into one requested type,
+//! so a caller reading a column of numeric text, or date serials without a
+//! date format, gets ints or dates back instead of post-processing them.
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::cell::CellValue;
+use crate::csv::parse_iso_date;
+use crate::error::{Result, RustypyxlError};
+use crate::worksheet::CellData;
+
+/// A named conversion applied to cell values on read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueConverter {
+    /// Whole numbers; fractions are truncated toward zero, as Python's `int()`.
+    Int,
+    /// Floating-point numbers.
+    Float,
+    /// Text, in the form the cell displays without a number format.
+    Str,
+    /// Booleans; numbers are true when non-zero, and text may be
+    /// true/false, yes/no or 1/0.
+    Bool,
+    /// Calendar dates, from date cells, serial numbers or ISO-8601 text.
+    Date,
+    /// Dates with a time of day, from the same sources as `Date`.
+    DateTime,
+}
+
+/// A cell value after conversion. Blank cells stay `Empty` whatever the
+/// converter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConvertedValue {
+    Empty,
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl ValueConverter {
+    /// Parse a converter name: "int", "float", "str", "bool", "date" or
+    /// "datetime".
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "int" => Some(ValueConverter::Int),
+            "float" => Some(ValueConverter::Float),
+            "str" => Some(ValueConverter::Str),
+            "bool" => Some(ValueConverter::Bool),
+            "date" => Some(ValueConverter::Date),
+            "datetime" => Some(ValueConverter::DateTime),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ValueConverter::Int => "int",
+            ValueConverter::Float => "float",
+            ValueConverter::Str => "str",
+            ValueConverter::Bool => "bool",
+            ValueConverter::Date => "date",
+            ValueConverter::DateTime => "datetime",
+        }
+    }
+
+    /// Convert a cell. A formula cell converts its cached result when the
+    /// file has one, and its formula text otherwise.
+    pub fn convert_cell(self, cell: &CellData, date1904: bool) -> Result<ConvertedValue> {
+        match (&cell.value, &cell.cached_formula_value) {
+            (CellValue::Formula(_), Some(cached)) => {
+                self.convert(&CellValue::from(cached.as_str()), date1904)
+            }
+            (value, _) => self.convert(value, date1904),
+        }
+    }
+
+    /// Convert a value, or fail when it has no sensible reading as the
+    /// requested type (e.g. "abc" as an int).
+    pub fn convert(self, value: &CellValue, date1904: bool) -> Result<ConvertedValue> {
+        if matches!(value, CellValue::Empty) {
+            return Ok(ConvertedValue::Empty);
+        }
+        let converted = match self {
+            ValueConverter::Int => to_f64(value)
+                .filter(|n| n.is_finite() && n.abs() < 9.2e18)
+                .map(|n| ConvertedValue::Int(n.trunc() as i64)),
+            ValueConverter::Float => to_f64(value).map(ConvertedValue::Float),
+            ValueConverter::Str => Some(ConvertedValue::Str(value.to_string())),
+            ValueConverter::Bool => to_bool(value).map(ConvertedValue::Bool),
+            ValueConverter::Date => {
+                to_datetime(value, date1904).map(|dt| ConvertedValue::Date(dt.date()))
+            }
+            ValueConverter::DateTime => to_datetime(value, date1904).map(ConvertedValue::DateTime),
+        };
+        converted.ok_or_else(|| {
+            RustypyxlError::custom(format!("cannot convert {} to {}", value, self.name()))
+        })
+    }
+}
+
+fn to_f64(value: &CellValue) -> Option<f64> {
+    match value {
+        CellValue::Number(n) => Some(*n),
+        CellValue::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
+        CellValue::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn to_bool(value: &CellValue) -> Option<bool> {
+    match value {
+        CellValue::Boolean(b) => Some(*b),
+        CellValue::Number(n) => Some(*n != 0.0),
+        CellValue::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "1" => Some(true),
+            "false" | "no" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn to_datetime(value: &CellValue, date1904: bool) -> Option<NaiveDateTime> {
+    match value {
+        CellValue::Number(n) => crate::dates::from_excel(*n, date1904),
+        CellValue::Date(d) => Some(d.to_datetime()),
+        CellValue::String(s) => parse_iso_date(s.trim()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(converter: ValueConverter, value: CellValue) -> Result<ConvertedValue> {
+        converter.convert(&value, false)
+    }
+
+    #[test]
+    fn test_numeric_converters() {
+        use ConvertedValue::*;
+        assert_eq!(
+            convert(ValueConverter::Int, CellValue::Number(2.9)).unwrap(),
+            Int(2)
+        );
+        assert_eq!(
+            convert(ValueConverter::Int, CellValue::from(" 42 ")).unwrap(),
+            Int(42)
+        );
+        assert_eq!(
+            convert(ValueConverter::Float, CellValue::Number(3.0)).unwrap(),
+            Float(3.0)
+        );
+        assert_eq!(
+            convert(ValueConverter::Bool, CellValue::from("Yes")).unwrap(),
+            Bool(true)
+        );
+        assert_eq!(
+            convert(ValueConverter::Int, CellValue::Empty).unwrap(),
+            Empty
+        );
+        let err = convert(ValueConverter::Int, CellValue::from("abc")).unwrap_err();
+        assert!(err.to_string().contains("cannot convert abc to int"));
+    }
+
+    #[test]
+    fn test_date_converters() {
+        let may_first = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        // 45413 is 2024-05-01 in the 1900 date system
+        assert_eq!(
+            convert(ValueConverter::Date, CellValue::Number(45413.75)).unwrap(),
+            ConvertedValue::Date(may_first)
+        );
+        assert_eq!(
+            convert(
+                ValueConverter::DateTime,
+                CellValue::from("2024-05-01 18:00")
+            )
+            .unwrap(),
+            ConvertedValue::DateTime(may_first.and_hms_opt(18, 0, 0).unwrap())
+        );
+        assert!(convert(ValueConverter::Date, CellValue::Boolean(true)).is_err());
+    }
+
+    #[test]
+    fn test_formula_cells_use_cached_result() {
+        let mut cell = CellData::with_value(CellValue::Formula("A1*2".to_string()));
+        assert_eq!(
+            ValueConverter::Str.convert_cell(&cell, false).unwrap(),
+            ConvertedValue::Str("=A1*2".to_string())
+        );
+        cell.cached_formula_value = Some("14".to_string());
+        assert_eq!(
+            ValueConverter::Int.convert_cell(&cell, false).unwrap(),
+            ConvertedValue::Int(14)
+        );
+    }
+}
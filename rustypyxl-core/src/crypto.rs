@@ -7,8 +7,10 @@
 //! (the `encrypt` feature) does the reverse, producing the container from a
 //! saved ZIP -- validated against msoffcrypto-tool.
 //!
-//! Only **agile encryption** (Office 2010+, EncryptionInfo version 4.4) is
-//! supported -- effectively all modern files. The AES block cipher and SHA
+//! **Agile encryption** (Office 2010+, EncryptionInfo version 4.4) covers
+//! effectively all modern files; **standard encryption** (Office 2007 and some
+//! third-party writers, versions 2.2 / 3.2 / 4.2 with AES) is read as well.
+//! The older RC4 schemes are not supported. The AES block cipher and SHA
 //! hashes come from the RustCrypto crates already in the build (zip pulls them
 //! for AES-encrypted ZIP entries); only the compound-file reader is new. The
 //! CBC chaining is done here rather than via another crate, since it is a plain
@@ -46,9 +48,12 @@ pub fn decrypt(data: &[u8], password: &str) -> Result<Vec<u8>> {
     let minor = u16::from_le_bytes([info[2], info[3]]);
     if (major, minor) == (4, 4) {
         decrypt_agile(&info[8..], &package, password)
+    } else if matches!(major, 2..=4) && minor == 2 {
+        decrypt_standard(&info, &package, password)
     } else {
         Err(RustypyxlError::InvalidFormat(format!(
-            "unsupported encryption version {major}.{minor}; only agile (4.4) is supported"
+            "unsupported encryption version {major}.{minor}; only agile (4.4) and \
+             standard (x.2) encryption are supported"
         )))
     }
 }
@@ -657,6 +662,109 @@ fn decrypt_agile(xml: &[u8], package: &[u8], password: &str) -> Result<Vec<u8>>
     Ok(out)
 }
 
+/// `fAES` in the standard EncryptionInfo flags: the package is AES, not RC4.
+const STANDARD_FLAG_AES: u32 = 0x20;
+
+/// Decrypt a standard-encrypted package: a binary EncryptionHeader and
+/// EncryptionVerifier, a SHA-1 key derived from the password, and the whole
+/// package in AES-ECB.
+fn decrypt_standard(info: &[u8], package: &[u8], password: &str) -> Result<Vec<u8>> {
+    let truncated = || RustypyxlError::InvalidFormat("truncated EncryptionInfo".into());
+    let u32_at = |off: usize| -> Result<u32> {
+        info.get(off..off + 4)
+            .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+            .ok_or_else(truncated)
+    };
+
+    if u32_at(4)? & STANDARD_FLAG_AES == 0 {
+        return Err(RustypyxlError::InvalidFormat(
+            "RC4-encrypted workbooks are not supported".into(),
+        ));
+    }
+    let header_size = u32_at(8)? as usize;
+    let header_start = 12;
+    if header_size < 32 {
+        return Err(truncated());
+    }
+    let alg_id = u32_at(header_start + 8)?;
+    let key_bits = match (alg_id, u32_at(header_start + 16)?) {
+        (0x660E, 0) => 128,
+        (0x660F, 0) => 192,
+        (0x6610, 0) => 256,
+        (0x660E..=0x6610, bits) => bits as usize,
+        _ => {
+            return Err(RustypyxlError::InvalidFormat(format!(
+                "unsupported standard encryption algorithm 0x{alg_id:04X}"
+            )))
+        }
+    };
+
+    let verifier = header_start + header_size;
+    let salt_size = u32_at(verifier)? as usize;
+    let salt = info
+        .get(verifier + 4..verifier + 4 + salt_size)
+        .ok_or_else(truncated)?;
+    let rest = verifier + 4 + salt_size;
+    let encrypted_verifier = info.get(rest..rest + 16).ok_or_else(truncated)?;
+    let verifier_hash_size = u32_at(rest + 16)? as usize;
+    let encrypted_verifier_hash = info.get(rest + 20..rest + 52).ok_or_else(truncated)?;
+
+    let key = standard_key(password, salt, key_bits / 8);
+    let verifier = aes_ecb_decrypt(&key, encrypted_verifier)?;
+    let verifier_hash = aes_ecb_decrypt(&key, encrypted_verifier_hash)?;
+    let expected = Sha1::digest(&verifier);
+    let hash_len = verifier_hash_size.min(expected.len());
+    if expected[..hash_len] != verifier_hash[..hash_len] {
+        return Err(RustypyxlError::InvalidFormat(
+            "incorrect password for encrypted workbook".into(),
+        ));
+    }
+
+    if package.len() < 8 {
+        return Err(RustypyxlError::InvalidFormat(
+            "truncated EncryptedPackage".into(),
+        ));
+    }
+    let total_size = u64::from_le_bytes(package[..8].try_into().unwrap()) as usize;
+    if total_size > package.len() {
+        return Err(RustypyxlError::InvalidFormat(
+            "encrypted package size prefix exceeds the stream".into(),
+        ));
+    }
+    // Some writers pad the stream past the last cipher block.
+    let body = &package[8..];
+    let mut out = aes_ecb_decrypt(&key, &body[..body.len() - body.len() % 16])?;
+    out.truncate(total_size);
+    Ok(out)
+}
+
+/// The standard-encryption key: SHA-1 over salt and password, iterated 50000
+/// times, finalized with block 0 and stretched through the CryptoAPI
+/// `CryptDeriveKey` ipad/opad construction.
+fn standard_key(password: &str, salt: &[u8], key_bytes: usize) -> Vec<u8> {
+    let pw16: Vec<u8> = password
+        .encode_utf16()
+        .flat_map(|u| u.to_le_bytes())
+        .collect();
+    let mut h = HashAlgo::Sha1.hash(&[salt, &pw16].concat());
+    for i in 0..50_000u32 {
+        h = HashAlgo::Sha1.hash(&[&i.to_le_bytes()[..], &h].concat());
+    }
+    let h_final = HashAlgo::Sha1.hash(&[&h[..], &0u32.to_le_bytes()].concat());
+
+    let stretch = |pad: u8| {
+        let mut buf = [pad; 64];
+        for (b, h) in buf.iter_mut().zip(&h_final) {
+            *b ^= h;
+        }
+        HashAlgo::Sha1.hash(&buf)
+    };
+    let mut key = stretch(0x36);
+    key.extend(stretch(0x5c));
+    key.truncate(key_bytes);
+    key
+}
+
 /// Derive a key from the password: an iterated hash (spinCount) mixed with a
 /// per-purpose block key, truncated/padded to the required length.
 fn derive_key(
@@ -734,6 +842,41 @@ fn aes_cbc_decrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>> {
     Ok(out)
 }
 
+/// AES-ECB decrypt (no padding removal). `data` must be a multiple of 16 bytes.
+fn aes_ecb_decrypt(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    use aes::cipher::generic_array::GenericArray;
+    use aes::cipher::{BlockDecrypt, KeyInit};
+
+    if data.is_empty() || !data.len().is_multiple_of(16) {
+        return Err(RustypyxlError::InvalidFormat(
+            "encrypted block is not a multiple of 16 bytes".into(),
+        ));
+    }
+
+    macro_rules! run {
+        ($cipher:ty) => {{
+            let cipher = <$cipher>::new(GenericArray::from_slice(key));
+            let mut out = data.to_vec();
+            for chunk in out.chunks_mut(16) {
+                cipher.decrypt_block(GenericArray::from_mut_slice(chunk));
+            }
+            out
+        }};
+    }
+
+    let out = match key.len() {
+        16 => run!(aes::Aes128),
+        24 => run!(aes::Aes192),
+        32 => run!(aes::Aes256),
+        n => {
+            return Err(RustypyxlError::InvalidFormat(format!(
+                "unsupported AES key size {n}"
+            )))
+        }
+    };
+    Ok(out)
+}
+
 // ---------- encryption (writing) ----------
 
 /// Block keys for the agile data-integrity HMAC.
@@ -746,6 +889,8 @@ const BLOCK_KEY_HMAC_VALUE: [u8; 8] = [0xa0, 0x67, 0x7f, 0x02, 0xb2, 0x2c, 0x84,
 /// container. Uses AES-256-CBC and SHA-512, matching what Office writes.
 #[cfg(feature = "encrypt")]
 pub fn encrypt(plain: &[u8], password: &str) -> Result<Vec<u8>> {
+    use crate::utils::base64_encode;
+
     let algo = HashAlgo::Sha512;
     let key_bytes = 32usize; // AES-256
     let block = 16usize;
@@ -920,33 +1065,6 @@ fn aes_cbc_encrypt(key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>> {
     Ok(out)
 }
 
-/// Standard-alphabet base64 encoder (mirrors the in-crate decoder).
-#[cfg(feature = "encrypt")]
-fn base64_encode(data: &[u8]) -> String {
-    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
-    for chunk in data.chunks(3) {
-        let b = [
-            chunk[0],
-            *chunk.get(1).unwrap_or(&0),
-            *chunk.get(2).unwrap_or(&0),
-        ];
-        out.push(ALPHABET[(b[0] >> 2) as usize] as char);
-        out.push(ALPHABET[(((b[0] & 0x03) << 4) | (b[1] >> 4)) as usize] as char);
-        if chunk.len() > 1 {
-            out.push(ALPHABET[(((b[1] & 0x0f) << 2) | (b[2] >> 6)) as usize] as char);
-        } else {
-            out.push('=');
-        }
-        if chunk.len() > 2 {
-            out.push(ALPHABET[(b[2] & 0x3f) as usize] as char);
-        } else {
-            out.push('=');
-        }
-    }
-    out
-}
-
 /// Parse the two agile-encryption elements out of the EncryptionInfo XML.
 fn parse_agile_xml(xml: &[u8]) -> Result<AgileParams> {
     use quick_xml::events::Event;
@@ -1020,34 +1138,84 @@ fn parse_agile_xml(xml: &[u8]) -> Result<AgileParams> {
     Ok(p)
 }
 
-/// Minimal standard-alphabet base64 decoder (avoids a base64 dependency).
+/// Decode a base64 attribute of the EncryptionInfo XML.
 fn base64_decode(s: &str) -> Result<Vec<u8>> {
-    fn val(c: u8) -> Option<u8> {
-        match c {
-            b'A'..=b'Z' => Some(c - b'A'),
-            b'a'..=b'z' => Some(c - b'a' + 26),
-            b'0'..=b'9' => Some(c - b'0' + 52),
-            b'+' => Some(62),
-            b'/' => Some(63),
-            _ => None,
+    crate::utils::base64_decode(s)
+        .ok_or_else(|| RustypyxlError::InvalidFormat("invalid base64 in EncryptionInfo".into()))
+}
+
+#[cfg(all(test, feature = "encrypt"))]
+mod tests {
+    use super::*;
+
+    /// Standard-encrypt `plain` with AES-128 the way Office 2007 does, for
+    /// the reader to undo.
+    fn standard_encrypt(plain: &[u8], password: &str) -> Vec<u8> {
+        use aes::cipher::generic_array::GenericArray;
+        use aes::cipher::{BlockEncrypt, KeyInit};
+
+        let salt: Vec<u8> = (0..16).collect();
+        let key = standard_key(password, &salt, 16);
+        let cipher = aes::Aes128::new(GenericArray::from_slice(&key));
+        let ecb = |data: &[u8]| {
+            let mut out = pad_to_16(data);
+            for chunk in out.chunks_mut(16) {
+                cipher.encrypt_block(GenericArray::from_mut_slice(chunk));
+            }
+            out
+        };
+
+        let verifier = [7u8; 16];
+        let mut header = Vec::new();
+        for field in [0x24u32, 0, 0x660E, 0x8004, 128, 0x18, 0, 0] {
+            header.extend_from_slice(&field.to_le_bytes());
         }
+        header.extend_from_slice(&[0, 0]);
+        let mut info = vec![0x04, 0x00, 0x02, 0x00, 0x24, 0x00, 0x00, 0x00];
+        info.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        info.extend_from_slice(&header);
+        info.extend_from_slice(&16u32.to_le_bytes());
+        info.extend_from_slice(&salt);
+        info.extend_from_slice(&ecb(&verifier));
+        info.extend_from_slice(&20u32.to_le_bytes());
+        info.extend_from_slice(&ecb(&Sha1::digest(verifier)));
+
+        let mut package = (plain.len() as u64).to_le_bytes().to_vec();
+        package.extend_from_slice(&ecb(plain));
+        cfb::write_container(&[("EncryptionInfo", &info), ("EncryptedPackage", &package)]).unwrap()
     }
-    let mut out = Vec::with_capacity(s.len() * 3 / 4);
-    let mut buf: u32 = 0;
-    let mut bits = 0;
-    for &c in s.trim().as_bytes() {
-        if matches!(c, b'=' | b'\n' | b'\r' | b' ' | b'\t') {
-            continue;
-        }
-        let v = val(c).ok_or_else(|| {
-            RustypyxlError::InvalidFormat("invalid base64 in EncryptionInfo".into())
-        })?;
-        buf = (buf << 6) | v as u32;
-        bits += 6;
-        if bits >= 8 {
-            bits -= 8;
-            out.push((buf >> bits) as u8);
-        }
+
+    #[test]
+    fn standard_key_matches_reference() {
+        // Computed independently with Python's hashlib from the spec's
+        // derivation (ECMA-376 part 2.3.4.7).
+        let salt: Vec<u8> = (0..16).collect();
+        let key = standard_key("s3cret", &salt, 16);
+        let hex: String = key.iter().map(|b| format!("{b:02x}")).collect();
+        assert_eq!(hex, "f5efd6a2f82844711bc978b9e9c1affd");
+    }
+
+    #[test]
+    fn decrypts_standard_encryption() {
+        let mut wb = crate::Workbook::new();
+        wb.create_sheet(Some("Secret".to_string())).unwrap();
+        wb.set_cell_value_in_sheet("Secret", 1, 1, crate::CellValue::from("hello"))
+            .unwrap();
+        let plain = wb.save_to_bytes().unwrap();
+        let encrypted = standard_encrypt(&plain, "s3cret");
+        assert!(is_encrypted(&encrypted));
+
+        assert_eq!(decrypt(&encrypted, "s3cret").unwrap(), plain);
+        let err = decrypt(&encrypted, "wrong").unwrap_err();
+        assert!(err.to_string().contains("password"), "{err}");
+
+        let loaded = crate::Workbook::load_from_bytes_with_password(&encrypted, "s3cret").unwrap();
+        assert_eq!(
+            loaded
+                .get_sheet_by_name("Secret")
+                .unwrap()
+                .get_cell_value(1, 1),
+            Some(&crate::CellValue::from("hello"))
+        );
     }
-    Ok(out)
 }
@@ -0,0 +1,1272 @@
+//! CSV import and export.
+//!
+//! Sheets are streamed row by row into a buffered writer, so exporting a large
+//! workbook never materializes a whole sheet as one string. Imports read one
+//! record at a time, so a large file is never held in memory either; only the
+//! cells it produces are.
+
+use crate::cell::CellValue;
+use crate::error::{Result, RustypyxlError};
+use crate::import_style::ImportStyleOptions;
+use crate::workbook::Workbook;
+use crate::worksheet::Worksheet;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Name of the manifest written next to the per-sheet CSV files.
+pub const CSV_MANIFEST_NAME: &str = "index.json";
+
+/// One sheet written by [`Workbook::export_all_to_csv`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvSheetExport {
+    /// The worksheet title.
+    pub sheet: String,
+    /// File name of the CSV, relative to the export directory.
+    pub file_name: String,
+    /// Number of records written.
+    pub rows: u32,
+    /// Number of fields per record.
+    pub columns: u32,
+}
+
+impl Workbook {
+    /// Export every worksheet to `dir` as one CSV file per sheet, plus an
+    /// `index.json` manifest mapping sheet titles to file names.
+    ///
+    /// File names are derived from the sheet titles with characters that are
+    /// unsafe in file names replaced, and deduplicated case-insensitively.
+    /// The directory is created if missing. Each CSV covers the sheet's used
+    /// range; formula cells export their cached result when one is present.
+    pub fn export_all_to_csv<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<CsvSheetExport>> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let mut used_names = HashSet::new();
+        let mut exports = Vec::with_capacity(self.worksheets.len());
+        for ws in &self.worksheets {
+            let file_name = unique_file_name(&sanitize_file_stem(ws.title()), &mut used_names);
+            let mut out = BufWriter::new(File::create(dir.join(&file_name))?);
+            let (rows, columns) = write_sheet_csv(ws, &mut out)?;
+            out.flush()?;
+            exports.push(CsvSheetExport {
+                sheet: ws.title().to_string(),
+                file_name,
+                rows,
+                columns,
+            });
+        }
+
+        let mut manifest = BufWriter::new(File::create(dir.join(CSV_MANIFEST_NAME))?);
+        write_manifest(&exports, &mut manifest)?;
+        manifest.flush()?;
+        Ok(exports)
+    }
+}
+
+/// Stream a worksheet's used range as CSV records (RFC 4180, CRLF line ends).
+/// Returns the (rows, columns) written; an empty sheet writes nothing.
+pub fn write_sheet_csv<W: Write>(ws: &Worksheet, out: &mut W) -> Result<(u32, u32)> {
+    ws.write_csv(out, &CsvExportOptions::default())
+}
+
+/// Text encoding of a CSV file. Excel writes UTF-8 (with a byte-order mark)
+/// or the Windows ANSI code page, which for Western locales is Windows-1252.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CsvEncoding {
+    /// UTF-8 (default). A byte-order mark at the start of a file is skipped.
+    #[default]
+    Utf8,
+    /// ISO-8859-1: every byte is the code point of the same value.
+    Latin1,
+    /// Windows-1252: Latin-1 with printable characters (`€`, curly quotes,
+    /// dashes) in 0x80-0x9F.
+    Windows1252,
+}
+
+/// Windows-1252 characters for bytes 0x80-0x9F. The five unassigned bytes
+/// map to the C1 control of the same value, as browsers decode them.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+impl CsvEncoding {
+    /// Look an encoding up by a common label ("utf-8", "utf-8-sig",
+    /// "latin-1", "iso-8859-1", "cp1252", "windows-1252"), ignoring case.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.to_ascii_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" | "utf-8-sig" => Some(CsvEncoding::Utf8),
+            "latin-1" | "latin1" | "iso-8859-1" | "iso8859-1" => Some(CsvEncoding::Latin1),
+            "cp1252" | "windows-1252" => Some(CsvEncoding::Windows1252),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            CsvEncoding::Utf8 => "UTF-8",
+            CsvEncoding::Latin1 => "ISO-8859-1",
+            CsvEncoding::Windows1252 => "Windows-1252",
+        }
+    }
+
+    /// `None` when the bytes are not valid UTF-8; the single-byte encodings
+    /// decode anything.
+    fn decode(self, bytes: Vec<u8>) -> Option<String> {
+        match self {
+            CsvEncoding::Utf8 => String::from_utf8(bytes).ok(),
+            CsvEncoding::Latin1 => Some(bytes.iter().map(|&b| b as char).collect()),
+            CsvEncoding::Windows1252 => Some(
+                bytes
+                    .iter()
+                    .map(|&b| match b {
+                        0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+                        _ => b as char,
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// `text` in this encoding, using `scratch` when it has to be converted.
+    fn encode<'a>(self, text: &'a str, scratch: &'a mut Vec<u8>) -> Result<&'a [u8]> {
+        if self == CsvEncoding::Utf8 || text.is_ascii() {
+            return Ok(text.as_bytes());
+        }
+        scratch.clear();
+        for c in text.chars() {
+            let byte = match (self, c as u32) {
+                (_, 0..=0x7F) | (CsvEncoding::Latin1, 0x80..=0xFF) => Some(c as u8),
+                (CsvEncoding::Windows1252, 0xA0..=0xFF) => Some(c as u8),
+                (CsvEncoding::Windows1252, _) => WINDOWS_1252_HIGH
+                    .iter()
+                    .position(|&high| high == c)
+                    .map(|i| 0x80 + i as u8),
+                _ => None,
+            };
+            scratch.push(byte.ok_or_else(|| {
+                RustypyxlError::custom(format!("{:?} cannot be encoded as {}", c, self.label()))
+            })?);
+        }
+        Ok(scratch)
+    }
+}
+
+/// When [`Worksheet::write_csv`] quotes a field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CsvQuoting {
+    /// Only fields containing the delimiter, the quote character or a line
+    /// break (default).
+    #[default]
+    Minimal,
+    /// Every field that has a value.
+    All,
+    /// Every field except numbers and booleans.
+    NonNumeric,
+    /// Never; a field that would need quotes is an error.
+    Never,
+}
+
+/// Options for CSV export.
+#[derive(Debug, Clone)]
+pub struct CsvExportOptions {
+    /// Field delimiter. Default: `,`.
+    pub delimiter: u8,
+    /// Quote character. Default: `"`.
+    pub quote: u8,
+    /// Which fields are quoted. Default: [`CsvQuoting::Minimal`].
+    pub quoting: CsvQuoting,
+    /// Text encoding. Default: UTF-8.
+    pub encoding: CsvEncoding,
+    /// Start a UTF-8 file with a byte-order mark, which Excel needs to open
+    /// it as UTF-8 rather than the ANSI code page. Default: false.
+    pub write_bom: bool,
+    /// Record terminator. Default: `\r\n`, as RFC 4180 and Excel use.
+    pub line_terminator: String,
+    /// chrono format for date-time cells; ISO 8601 when `None`.
+    pub date_format: Option<String>,
+    /// Range to export (e.g. "A1:D100"); the used range when `None`.
+    pub range: Option<String>,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            quoting: CsvQuoting::default(),
+            encoding: CsvEncoding::default(),
+            write_bom: false,
+            line_terminator: "\r\n".to_string(),
+            date_format: None,
+            range: None,
+        }
+    }
+}
+
+impl CsvExportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the field delimiter.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quote character.
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set which fields are quoted.
+    pub fn with_quoting(mut self, quoting: CsvQuoting) -> Self {
+        self.quoting = quoting;
+        self
+    }
+
+    /// Set the text encoding.
+    pub fn with_encoding(mut self, encoding: CsvEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set whether a UTF-8 file starts with a byte-order mark.
+    pub fn with_bom(mut self, write_bom: bool) -> Self {
+        self.write_bom = write_bom;
+        self
+    }
+
+    /// Set the record terminator.
+    pub fn with_line_terminator(mut self, terminator: &str) -> Self {
+        self.line_terminator = terminator.to_string();
+        self
+    }
+
+    /// Set the chrono format for date-time cells.
+    pub fn with_date_format(mut self, format: &str) -> Self {
+        self.date_format = Some(format.to_string());
+        self
+    }
+
+    /// Export only `range` instead of the used range.
+    pub fn with_range(mut self, range: &str) -> Self {
+        self.range = Some(range.to_string());
+        self
+    }
+}
+
+/// Result of a CSV export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvExportResult {
+    /// Number of records written.
+    pub rows_exported: u32,
+    /// Number of fields per record.
+    pub columns_exported: u32,
+    /// File size in bytes.
+    pub file_size: u64,
+}
+
+impl Worksheet {
+    /// Export the used range, or `options.range`, to a CSV file.
+    ///
+    /// Rows are streamed to disk one at a time. Formula cells export their
+    /// cached result when one is present and `=formula` otherwise; booleans
+    /// export as TRUE/FALSE and integral numbers without a trailing ".0".
+    pub fn export_to_csv<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: Option<CsvExportOptions>,
+    ) -> Result<CsvExportResult> {
+        let path = path.as_ref();
+        let options = options.unwrap_or_default();
+        let mut out = BufWriter::new(File::create(path)?);
+        let (rows, columns) = self.write_csv(&mut out, &options)?;
+        out.flush()?;
+        drop(out);
+        Ok(CsvExportResult {
+            rows_exported: rows,
+            columns_exported: columns,
+            file_size: std::fs::metadata(path)?.len(),
+        })
+    }
+
+    /// Write the used range, or `options.range`, to `out` as CSV records.
+    /// Returns the (rows, columns) written; an empty sheet writes no records.
+    pub fn write_csv<W: Write>(
+        &self,
+        out: &mut W,
+        options: &CsvExportOptions,
+    ) -> Result<(u32, u32)> {
+        check_dialect(options.delimiter, Some(options.quote))?;
+        if options.write_bom && options.encoding == CsvEncoding::Utf8 {
+            out.write_all(UTF8_BOM)?;
+        }
+        let Some((min_row, min_col, max_row, max_col)) =
+            self.render_bounds(options.range.as_deref())?
+        else {
+            return Ok((0, 0));
+        };
+        let mut itoa_buf = itoa::Buffer::new();
+        let mut ryu_buf = ryu::Buffer::new();
+        let mut scratch = Vec::new();
+
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                if col > min_col {
+                    out.write_all(&[options.delimiter])?;
+                }
+                let Some(cell) = self.get_cell(row, col) else {
+                    continue;
+                };
+                let mut field = |text: &str, numeric: bool| {
+                    write_field(out, text, numeric, options, &mut scratch)
+                };
+                match &cell.value {
+                    CellValue::Empty => {}
+                    CellValue::String(s) => field(s, false)?,
+                    CellValue::Number(n) => {
+                        field(format_csv_number(*n, &mut itoa_buf, &mut ryu_buf), true)?
+                    }
+                    CellValue::Boolean(b) => field(if *b { "TRUE" } else { "FALSE" }, true)?,
+                    CellValue::Formula(f) => match &cell.cached_formula_value {
+                        Some(cached) => {
+                            let numeric = matches!(cell.data_type, None | Some("n"))
+                                && cached.parse::<f64>().is_ok();
+                            field(cached, numeric)?
+                        }
+                        None => field(&format!("={}", f), false)?,
+                    },
+                    CellValue::Date(d) => match &options.date_format {
+                        Some(format) => field(&d.to_datetime().format(format).to_string(), false)?,
+                        None => field(&d.to_string(), false)?,
+                    },
+                }
+            }
+            out.write_all(options.line_terminator.as_bytes())?;
+        }
+        Ok((max_row - min_row + 1, max_col - min_col + 1))
+    }
+}
+
+/// Write one field, quoted as `options.quoting` asks. `numeric` marks numbers
+/// and booleans, which [`CsvQuoting::NonNumeric`] leaves bare.
+fn write_field<W: Write>(
+    out: &mut W,
+    value: &str,
+    numeric: bool,
+    options: &CsvExportOptions,
+    scratch: &mut Vec<u8>,
+) -> Result<()> {
+    let (delimiter, quote) = (options.delimiter as char, options.quote as char);
+    let needs_quotes = value.contains([delimiter, quote, '\r', '\n']);
+    let quoted = match options.quoting {
+        CsvQuoting::Minimal => needs_quotes,
+        CsvQuoting::All => true,
+        CsvQuoting::NonNumeric => needs_quotes || !numeric,
+        CsvQuoting::Never if needs_quotes => {
+            return Err(RustypyxlError::custom(format!(
+                "CSV field {:?} needs quotes, but quoting is disabled",
+                value
+            )))
+        }
+        CsvQuoting::Never => false,
+    };
+    let bytes = options.encoding.encode(value, scratch)?;
+    if !quoted {
+        out.write_all(bytes)?;
+        return Ok(());
+    }
+    // Every supported encoding is ASCII-compatible, so the quote byte only
+    // occurs where the quote character does.
+    out.write_all(&[options.quote])?;
+    for (i, part) in bytes.split(|&b| b == options.quote).enumerate() {
+        if i > 0 {
+            out.write_all(&[options.quote, options.quote])?;
+        }
+        out.write_all(part)?;
+    }
+    out.write_all(&[options.quote])?;
+    Ok(())
+}
+
+/// Integral values print without a trailing ".0" (as Excel's CSV export
+/// does); everything else uses the shortest round-tripping representation.
+fn format_csv_number<'a>(
+    n: f64,
+    itoa_buf: &'a mut itoa::Buffer,
+    ryu_buf: &'a mut ryu::Buffer,
+) -> &'a str {
+    if !n.is_finite() {
+        return "#NUM!";
+    }
+    if n.fract() == 0.0 && n.abs() < 9.007_199_254_740_992e15 {
+        itoa_buf.format(n as i64)
+    } else {
+        ryu_buf.format(n)
+    }
+}
+
+/// Delimiter and quote must be distinct ASCII characters other than a line
+/// break, so records can be split on raw bytes in any supported encoding.
+fn check_dialect(delimiter: u8, quote: Option<u8>) -> Result<()> {
+    let usable = |b: u8| b.is_ascii() && b != b'\r' && b != b'\n';
+    if !usable(delimiter) || quote.is_some_and(|q| !usable(q) || q == delimiter) {
+        return Err(RustypyxlError::custom(
+            "CSV delimiter and quote must be distinct ASCII characters other than a line break",
+        ));
+    }
+    Ok(())
+}
+
+/// Result of a CSV import.
+#[derive(Debug, Clone)]
+pub struct CsvImportResult {
+    /// Number of rows imported (excluding header).
+    pub rows_imported: u32,
+    /// Number of columns imported.
+    pub columns_imported: u32,
+    /// Starting row of data (1-indexed).
+    pub start_row: u32,
+    /// Starting column of data (1-indexed).
+    pub start_col: u32,
+    /// Ending row of data (1-indexed).
+    pub end_row: u32,
+    /// Ending column of data (1-indexed).
+    pub end_col: u32,
+    /// Column names as imported (after any renaming). Without a header
+    /// record these are `Column1`, `Column2`, ...
+    pub column_names: Vec<String>,
+}
+
+impl CsvImportResult {
+    /// Get the range string (e.g., "A1:Z1000") for the imported data including headers.
+    pub fn range_with_headers(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            crate::utils::column_to_letter(self.start_col),
+            self.start_row,
+            crate::utils::column_to_letter(self.end_col),
+            self.end_row
+        )
+    }
+
+    /// Get the range string for just the data (excluding headers).
+    pub fn data_range(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            crate::utils::column_to_letter(self.start_col),
+            self.start_row + 1,
+            crate::utils::column_to_letter(self.end_col),
+            self.end_row
+        )
+    }
+
+    /// Get the range string for just the headers.
+    pub fn header_range(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            crate::utils::column_to_letter(self.start_col),
+            self.start_row,
+            crate::utils::column_to_letter(self.end_col),
+            self.start_row
+        )
+    }
+}
+
+/// Options for CSV import.
+#[derive(Debug, Clone)]
+pub struct CsvImportOptions {
+    /// Field delimiter. Default: `,`.
+    pub delimiter: u8,
+    /// Quote character, or `None` to read quotes as ordinary text.
+    /// Default: `"`.
+    pub quote: Option<u8>,
+    /// Text encoding of the file. Default: UTF-8.
+    pub encoding: CsvEncoding,
+    /// If true, the first record holds the column names and is written as
+    /// a text header row. Default: true.
+    pub has_headers: bool,
+    /// Column name mappings (original_name -> new_name).
+    pub column_renames: HashMap<String, String>,
+    /// Specific columns to import (by name). If empty, import all.
+    pub columns: Vec<String>,
+    /// Turn fields that look like numbers, TRUE/FALSE or ISO 8601 dates into
+    /// typed cells; when false every field is text. Default: true.
+    pub infer_types: bool,
+    /// Extra chrono formats (e.g. `%d/%m/%Y`) tried for dates before any
+    /// other inference.
+    pub date_formats: Vec<String>,
+    /// Header/freeze/filter/width styling applied to the imported block.
+    pub style: Option<ImportStyleOptions>,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: Some(b'"'),
+            encoding: CsvEncoding::default(),
+            has_headers: true,
+            column_renames: HashMap::new(),
+            columns: Vec::new(),
+            infer_types: true,
+            date_formats: Vec::new(),
+            style: None,
+        }
+    }
+}
+
+impl CsvImportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the field delimiter.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quote character, or `None` to disable quoting.
+    pub fn with_quote(mut self, quote: Option<u8>) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set the text encoding.
+    pub fn with_encoding(mut self, encoding: CsvEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set whether the first record holds the column names.
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Add a column rename mapping.
+    pub fn rename_column(mut self, from: &str, to: &str) -> Self {
+        self.column_renames.insert(from.to_string(), to.to_string());
+        self
+    }
+
+    /// Select specific columns to import.
+    pub fn select_columns(mut self, columns: Vec<String>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Set whether field types are inferred.
+    pub fn with_type_inference(mut self, infer_types: bool) -> Self {
+        self.infer_types = infer_types;
+        self
+    }
+
+    /// Add a chrono format to try for dates.
+    pub fn with_date_format(mut self, format: &str) -> Self {
+        self.date_formats.push(format.to_string());
+        self
+    }
+
+    /// Style the imported block (see [`ImportStyleOptions`]).
+    pub fn with_style(mut self, style: ImportStyleOptions) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Workbook {
+    /// Import a CSV file into a worksheet, starting at (`start_row`,
+    /// `start_col`).
+    ///
+    /// The file is read one record at a time, so its size is bounded only by
+    /// the cells it produces. Records may differ in length. Empty fields
+    /// leave their cell empty; see [`CsvImportOptions::infer_types`] for how
+    /// the rest are typed.
+    pub fn insert_from_csv<P: AsRef<Path>>(
+        &mut self,
+        sheet_name: &str,
+        path: P,
+        start_row: u32,
+        start_col: u32,
+        options: Option<CsvImportOptions>,
+    ) -> Result<CsvImportResult> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| RustypyxlError::ParseError(format!("Failed to open CSV file: {}", e)))?;
+        let worksheet = self.get_sheet_by_name_mut(sheet_name)?;
+        worksheet.insert_csv(
+            BufReader::new(file),
+            start_row,
+            start_col,
+            &options.unwrap_or_default(),
+        )
+    }
+}
+
+impl Worksheet {
+    /// Write CSV records read from `input` into this worksheet, as
+    /// [`Workbook::insert_from_csv`] does for a file.
+    pub fn insert_csv<R: BufRead>(
+        &mut self,
+        input: R,
+        start_row: u32,
+        start_col: u32,
+        options: &CsvImportOptions,
+    ) -> Result<CsvImportResult> {
+        if start_row == 0 || start_col == 0 {
+            return Err(RustypyxlError::custom(
+                "start_row and start_col are 1-based",
+            ));
+        }
+        check_dialect(options.delimiter, options.quote)?;
+        let mut reader = RecordReader::new(input, options);
+        let decode = |bytes: Vec<u8>, line: u64| {
+            options.encoding.decode(bytes).ok_or_else(|| {
+                RustypyxlError::ParseError(format!(
+                    "CSV line {} is not valid {}",
+                    line,
+                    options.encoding.label()
+                ))
+            })
+        };
+
+        let mut record = Vec::new();
+        let mut pending = reader.read_record(&mut record)?;
+        let mut names = Vec::new();
+        if options.has_headers && pending {
+            for bytes in record.drain(..) {
+                names.push(decode(bytes, reader.line_number)?);
+            }
+            pending = false;
+        }
+
+        // Selected columns by index in the record, in output order. Without a
+        // header the names to select by are Column1, Column2, ...
+        let selected: Option<Vec<usize>> = if options.columns.is_empty() {
+            None
+        } else {
+            let position = |name: &String| {
+                if options.has_headers {
+                    names.iter().position(|n| n == name)
+                } else {
+                    name.strip_prefix("Column")
+                        .and_then(|n| n.parse::<usize>().ok())
+                        .filter(|&n| n >= 1)
+                        .map(|n| n - 1)
+                }
+            };
+            let indices = options
+                .columns
+                .iter()
+                .map(|name| {
+                    position(name).ok_or_else(|| {
+                        RustypyxlError::ParseError(format!(
+                            "Column '{}' not found in CSV file (available: {})",
+                            name,
+                            names.join(", ")
+                        ))
+                    })
+                })
+                .collect::<Result<_>>()?;
+            Some(indices)
+        };
+
+        let data_start = if options.has_headers {
+            start_row + 1
+        } else {
+            start_row
+        };
+        let mut rows_imported: u32 = 0;
+        let mut width = names.len();
+        while pending || reader.read_record(&mut record)? {
+            pending = false;
+            let row = data_start + rows_imported;
+            let line = reader.line_number;
+            let put = |ws: &mut Worksheet, offset: usize, bytes: Vec<u8>| -> Result<()> {
+                let value = field_value(decode(bytes, line)?, options);
+                if value != CellValue::Empty {
+                    ws.set_cell_value(row, start_col + offset as u32, value);
+                }
+                Ok(())
+            };
+            match &selected {
+                Some(indices) => {
+                    for (offset, &index) in indices.iter().enumerate() {
+                        if let Some(bytes) = record.get_mut(index) {
+                            put(self, offset, std::mem::take(bytes))?;
+                        }
+                    }
+                }
+                None => {
+                    width = width.max(record.len());
+                    for (offset, bytes) in record.drain(..).enumerate() {
+                        put(self, offset, bytes)?;
+                    }
+                }
+            }
+            rows_imported += 1;
+        }
+
+        let columns = selected.as_ref().map_or(width, Vec::len);
+        let column_names: Vec<String> = (0..columns)
+            .map(|offset| {
+                let index = selected.as_ref().map_or(offset, |s| s[offset]);
+                let original = names
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("Column{}", index + 1));
+                options
+                    .column_renames
+                    .get(&original)
+                    .cloned()
+                    .unwrap_or(original)
+            })
+            .collect();
+        if options.has_headers {
+            for (offset, name) in column_names.iter().enumerate() {
+                if !name.is_empty() {
+                    self.set_cell_value(
+                        start_row,
+                        start_col + offset as u32,
+                        CellValue::String(Arc::from(name.as_str())),
+                    );
+                }
+            }
+        }
+
+        let end_row = if rows_imported > 0 {
+            data_start + rows_imported - 1
+        } else {
+            start_row
+        };
+        let end_col = start_col + columns.max(1) as u32 - 1;
+        if let Some(style) = &options.style {
+            self.apply_import_style(
+                (start_row, start_col),
+                (end_row, end_col),
+                options.has_headers,
+                style,
+            );
+        }
+
+        Ok(CsvImportResult {
+            rows_imported,
+            columns_imported: columns as u32,
+            start_row,
+            start_col,
+            end_row,
+            end_col,
+            column_names,
+        })
+    }
+}
+
+/// Splits a byte stream into CSV records. Delimiter and quote are ASCII, so
+/// records split on the raw bytes of any supported encoding and the fields
+/// are decoded afterwards.
+struct RecordReader<R> {
+    input: R,
+    delimiter: u8,
+    quote: Option<u8>,
+    /// Skip a UTF-8 byte-order mark at the start of the input.
+    skip_bom: bool,
+    line: Vec<u8>,
+    /// Line the last record ended on (1-based), for error messages.
+    line_number: u64,
+}
+
+impl<R: BufRead> RecordReader<R> {
+    fn new(input: R, options: &CsvImportOptions) -> Self {
+        RecordReader {
+            input,
+            delimiter: options.delimiter,
+            quote: options.quote,
+            skip_bom: options.encoding == CsvEncoding::Utf8,
+            line: Vec::new(),
+            line_number: 0,
+        }
+    }
+
+    /// Read the next record's fields into `fields`; false at end of input.
+    /// Blank lines hold no record and are skipped. A quote opens a quoted
+    /// field only at the start of a field, and a doubled quote inside one is
+    /// a literal quote; line breaks inside quotes are part of the field.
+    fn read_record(&mut self, fields: &mut Vec<Vec<u8>>) -> Result<bool> {
+        fields.clear();
+        loop {
+            self.line.clear();
+            if self.input.read_until(b'\n', &mut self.line)? == 0 {
+                return Ok(false);
+            }
+            self.line_number += 1;
+            if std::mem::take(&mut self.skip_bom) && self.line.starts_with(UTF8_BOM) {
+                self.line.drain(..UTF8_BOM.len());
+            }
+            if !self.line.iter().all(|&b| b == b'\r' || b == b'\n') {
+                break;
+            }
+        }
+
+        let mut field = Vec::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut i = 0;
+        loop {
+            let Some(&b) = self.line.get(i) else {
+                if !in_quotes {
+                    break;
+                }
+                // The quoted field runs on past the line break.
+                if self.input.read_until(b'\n', &mut self.line)? == 0 {
+                    return Err(RustypyxlError::ParseError(format!(
+                        "CSV quoted field is not closed by the end of the file (line {})",
+                        self.line_number
+                    )));
+                }
+                self.line_number += 1;
+                continue;
+            };
+            i += 1;
+            if in_quotes {
+                if Some(b) != self.quote {
+                    field.push(b);
+                } else if self.line.get(i) == Some(&b) {
+                    field.push(b);
+                    i += 1;
+                } else {
+                    in_quotes = false;
+                }
+            } else if b == self.delimiter {
+                fields.push(std::mem::take(&mut field));
+                quoted = false;
+            } else if Some(b) == self.quote && field.is_empty() && !quoted {
+                quoted = true;
+                in_quotes = true;
+            } else if b == b'\n' || (b == b'\r' && matches!(self.line.get(i), Some(b'\n') | None)) {
+                break;
+            } else {
+                field.push(b);
+            }
+        }
+        fields.push(field);
+        Ok(true)
+    }
+}
+
+/// The cell value for one decoded field. Inference tries the caller's date
+/// formats, then TRUE/FALSE (any case), numbers, and ISO 8601 dates, in that
+/// order; anything else is text.
+fn field_value(text: String, options: &CsvImportOptions) -> CellValue {
+    if text.is_empty() {
+        return CellValue::Empty;
+    }
+    if !options.infer_types {
+        return CellValue::String(Arc::from(text));
+    }
+    for format in &options.date_formats {
+        if let Some(dt) = NaiveDateTime::parse_from_str(&text, format)
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(&text, format)
+                    .ok()
+                    .map(|d| d.and_time(NaiveTime::MIN))
+            })
+        {
+            return CellValue::from(dt);
+        }
+    }
+    if text.eq_ignore_ascii_case("true") {
+        return CellValue::Boolean(true);
+    }
+    if text.eq_ignore_ascii_case("false") {
+        return CellValue::Boolean(false);
+    }
+    if let Some(n) = parse_number(&text) {
+        return CellValue::Number(n);
+    }
+    if let Some(dt) = parse_iso_date(&text) {
+        return CellValue::from(dt);
+    }
+    CellValue::String(Arc::from(text))
+}
+
+/// A plain decimal number. Text that would not survive as one stays text:
+/// leading zeros ("007", zip codes), integers longer than the 15 digits
+/// Excel keeps (card and account numbers), and inf/NaN spellings.
+fn parse_number(text: &str) -> Option<f64> {
+    let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text).as_bytes();
+    if !unsigned
+        .first()
+        .is_some_and(|b| b.is_ascii_digit() || *b == b'.')
+    {
+        return None;
+    }
+    if unsigned.len() > 1 && unsigned[0] == b'0' && unsigned[1].is_ascii_digit() {
+        return None;
+    }
+    if !unsigned
+        .iter()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'e' | b'E' | b'-' | b'+'))
+    {
+        return None;
+    }
+    if unsigned.iter().all(u8::is_ascii_digit) && unsigned.len() > 15 {
+        return None;
+    }
+    text.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
+/// `YYYY-MM-DD`, optionally followed by a time (`T` or a space, then
+/// `HH:MM`, `HH:MM:SS` or `HH:MM:SS.fff`).
+pub(crate) fn parse_iso_date(text: &str) -> Option<NaiveDateTime> {
+    if text.len() < 10 || text.as_bytes()[4] != b'-' {
+        return None;
+    }
+    if text.len() == 10 {
+        return NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .ok()
+            .map(|d| d.and_time(NaiveTime::MIN));
+    }
+    [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+}
+
+/// Turn a sheet title into a file stem that is valid on every major
+/// filesystem: path separators, reserved punctuation, and control characters
+/// become `_`, and Windows device names get a `_` suffix.
+fn sanitize_file_stem(title: &str) -> String {
+    let mut stem: String = title
+        .chars()
+        .map(|c| match c {
+            '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    // Windows silently drops trailing dots and spaces
+    let trimmed_len = stem.trim_end_matches(['.', ' ']).len();
+    stem.truncate(trimmed_len);
+    if stem.is_empty() {
+        return "sheet".to_string();
+    }
+
+    const RESERVED: [&str; 22] = [
+        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    ];
+    let base = stem.split('.').next().unwrap_or_default();
+    if RESERVED.iter().any(|r| r.eq_ignore_ascii_case(base)) {
+        stem.push('_');
+    }
+    stem
+}
+
+/// Append `.csv`, adding a numeric suffix when the name (compared
+/// case-insensitively, for case-insensitive filesystems) is already taken.
+fn unique_file_name(stem: &str, used: &mut HashSet<String>) -> String {
+    let mut name = format!("{}.csv", stem);
+    let mut counter = 2;
+    while !used.insert(name.to_lowercase()) {
+        name = format!("{}_{}.csv", stem, counter);
+        counter += 1;
+    }
+    name
+}
+
+fn write_manifest<W: Write>(exports: &[CsvSheetExport], out: &mut W) -> Result<()> {
+    out.write_all(b"{\n  \"sheets\": [")?;
+    for (i, export) in exports.iter().enumerate() {
+        out.write_all(if i == 0 { b"\n" } else { b",\n" })?;
+        out.write_all(b"    {\"name\": ")?;
+        write_json_string(out, &export.sheet)?;
+        out.write_all(b", \"file\": ")?;
+        write_json_string(out, &export.file_name)?;
+        write!(
+            out,
+            ", \"rows\": {}, \"columns\": {}}}",
+            export.rows, export.columns
+        )?;
+    }
+    if !exports.is_empty() {
+        out.write_all(b"\n  ")?;
+    }
+    out.write_all(b"]\n}\n")?;
+    Ok(())
+}
+
+/// Write `s` as a quoted JSON string.
+pub(crate) fn write_json_string<W: Write>(out: &mut W, s: &str) -> std::io::Result<()> {
+    out.write_all(b"\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_all(b"\\\"")?,
+            '\\' => out.write_all(b"\\\\")?,
+            '\n' => out.write_all(b"\\n")?,
+            '\r' => out.write_all(b"\\r")?,
+            '\t' => out.write_all(b"\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => {
+                let mut utf8 = [0u8; 4];
+                out.write_all(c.encode_utf8(&mut utf8).as_bytes())?;
+            }
+        }
+    }
+    out.write_all(b"\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv_of(ws: &Worksheet) -> String {
+        let mut out = Vec::new();
+        write_sheet_csv(ws, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_quoting_and_value_forms() {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_value(1, 1, "plain");
+        ws.set_cell_value(1, 2, "a,b");
+        ws.set_cell_value(1, 3, "say \"hi\"");
+        ws.set_cell_value(2, 1, CellValue::Number(3.0));
+        ws.set_cell_value(2, 2, CellValue::Number(0.25));
+        ws.set_cell_value(2, 3, CellValue::Boolean(true));
+        assert_eq!(
+            csv_of(&ws),
+            "plain,\"a,b\",\"say \"\"hi\"\"\"\r\n3,0.25,TRUE\r\n"
+        );
+    }
+
+    #[test]
+    fn test_used_range_and_gaps() {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_value(2, 2, "x");
+        ws.set_cell_value(3, 4, "y");
+        assert_eq!(csv_of(&ws), "x,,\r\n,,y\r\n");
+    }
+
+    #[test]
+    fn test_formula_prefers_cached_value() {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_formula(1, 1, "1+1");
+        ws.set_cell_formula(1, 2, "A1*2");
+        ws.get_cell_mut(1, 1).unwrap().cached_formula_value = Some("2".to_string());
+        assert_eq!(csv_of(&ws), "2,=A1*2\r\n");
+    }
+
+    fn import(text: &[u8], options: &CsvImportOptions) -> (Worksheet, CsvImportResult) {
+        let mut ws = Worksheet::new("S");
+        let result = ws.insert_csv(text, 1, 1, options).unwrap();
+        (ws, result)
+    }
+
+    fn value(ws: &Worksheet, row: u32, col: u32) -> Option<&CellValue> {
+        ws.get_cell_value(row, col)
+    }
+
+    #[test]
+    fn test_import_infers_types() {
+        let text = b"\xEF\xBB\xBFid,qty,ok,when,note\r\n\
+                     007,1.5,TRUE,2024-03-15,\"a, \"\"b\"\"\nc\"\r\n\
+                     \r\n\
+                     1234567890123456,-2e3,false,2024-03-15 10:30:15,\r\n";
+        let (ws, result) = import(text, &CsvImportOptions::new());
+        assert_eq!(result.column_names, ["id", "qty", "ok", "when", "note"]);
+        assert_eq!(result.rows_imported, 2);
+        assert_eq!(result.range_with_headers(), "A1:E3");
+
+        let when = |h, m, s| {
+            CellValue::from(
+                NaiveDate::from_ymd_opt(2024, 3, 15)
+                    .unwrap()
+                    .and_hms_opt(h, m, s)
+                    .unwrap(),
+            )
+        };
+        assert_eq!(value(&ws, 2, 1), Some(&CellValue::from("007")));
+        assert_eq!(value(&ws, 2, 2), Some(&CellValue::Number(1.5)));
+        assert_eq!(value(&ws, 2, 3), Some(&CellValue::Boolean(true)));
+        assert_eq!(value(&ws, 2, 4), Some(&when(0, 0, 0)));
+        assert_eq!(value(&ws, 2, 5), Some(&CellValue::from("a, \"b\"\nc")));
+        // Past Excel's 15 digits a number would lose digits, so it stays text.
+        assert_eq!(value(&ws, 3, 1), Some(&CellValue::from("1234567890123456")));
+        assert_eq!(value(&ws, 3, 2), Some(&CellValue::Number(-2000.0)));
+        assert_eq!(value(&ws, 3, 3), Some(&CellValue::Boolean(false)));
+        assert_eq!(value(&ws, 3, 4), Some(&when(10, 30, 15)));
+        assert_eq!(value(&ws, 3, 5), None);
+    }
+
+    #[test]
+    fn test_import_options() {
+        let text = b"x;y;z\n05/01/2024;\"q\";3\n1;2;3;4\n";
+        let options = CsvImportOptions::new()
+            .with_delimiter(b';')
+            .with_quote(None)
+            .with_headers(false)
+            .with_date_format("%d/%m/%Y")
+            .select_columns(vec!["Column3".to_string(), "Column1".to_string()])
+            .rename_column("Column3", "third");
+        let mut ws = Worksheet::new("S");
+        let result = ws.insert_csv(&text[..], 2, 2, &options).unwrap();
+        assert_eq!(result.column_names, ["third", "Column1"]);
+        assert_eq!(result.rows_imported, 3);
+        assert_eq!(result.range_with_headers(), "B2:C4");
+        assert_eq!(value(&ws, 2, 2), Some(&CellValue::from("z")));
+        assert_eq!(
+            value(&ws, 3, 3),
+            Some(&CellValue::from(
+                NaiveDate::from_ymd_opt(2024, 1, 5)
+                    .unwrap()
+                    .and_time(NaiveTime::MIN)
+            ))
+        );
+        assert_eq!(value(&ws, 3, 2), Some(&CellValue::Number(3.0)));
+        assert!(ws.get_cell(3, 4).is_none());
+
+        let (ws, result) = import(
+            b"a,b\n1,TRUE,x\n",
+            &CsvImportOptions::new().with_type_inference(false),
+        );
+        assert_eq!(result.column_names, ["a", "b", "Column3"]);
+        assert_eq!(value(&ws, 2, 1), Some(&CellValue::from("1")));
+        assert_eq!(value(&ws, 2, 3), Some(&CellValue::from("x")));
+        assert_eq!(value(&ws, 1, 3), Some(&CellValue::from("Column3")));
+    }
+
+    #[test]
+    fn test_import_encodings_and_errors() {
+        let text = b"name\n\x80 caf\xE9\n";
+        let (ws, _) = import(
+            text,
+            &CsvImportOptions::new().with_encoding(CsvEncoding::Windows1252),
+        );
+        assert_eq!(value(&ws, 2, 1), Some(&CellValue::from("\u{20AC} café")));
+        let (ws, _) = import(
+            text,
+            &CsvImportOptions::new().with_encoding(CsvEncoding::Latin1),
+        );
+        assert_eq!(value(&ws, 2, 1), Some(&CellValue::from("\u{80} café")));
+
+        let mut ws = Worksheet::new("S");
+        let utf8 = ws.insert_csv(&text[..], 1, 1, &CsvImportOptions::new());
+        assert!(utf8.unwrap_err().to_string().contains("line 2"));
+        let unclosed = ws.insert_csv(&b"a\n\"open,1\n"[..], 1, 1, &CsvImportOptions::new());
+        assert!(unclosed.is_err());
+        let missing = CsvImportOptions::new().select_columns(vec!["nope".to_string()]);
+        assert!(ws.insert_csv(&b"a,b\n"[..], 1, 1, &missing).is_err());
+        let clash = CsvImportOptions::new().with_delimiter(b'"');
+        assert!(ws.insert_csv(&b"a\n"[..], 1, 1, &clash).is_err());
+    }
+
+    #[test]
+    fn test_export_options() {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_value(1, 1, "a;b");
+        ws.set_cell_value(1, 2, CellValue::Number(2.5));
+        ws.set_cell_value(1, 3, CellValue::Boolean(false));
+        ws.set_cell_value(2, 1, "\u{20AC}5");
+        ws.set_cell_value(
+            2,
+            2,
+            NaiveDate::from_ymd_opt(2024, 3, 15)
+                .unwrap()
+                .and_hms_opt(9, 5, 0)
+                .unwrap(),
+        );
+        let csv = |options: CsvExportOptions| {
+            let mut out = Vec::new();
+            ws.write_csv(&mut out, &options).map(|_| out)
+        };
+
+        let out = csv(CsvExportOptions::new()
+            .with_delimiter(b';')
+            .with_quoting(CsvQuoting::NonNumeric)
+            .with_line_terminator("\n")
+            .with_date_format("%d/%m/%Y %H:%M"))
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\"a;b\";2.5;FALSE\n\"\u{20AC}5\";\"15/03/2024 09:05\";\n"
+        );
+
+        let out = csv(CsvExportOptions::new()
+            .with_quoting(CsvQuoting::All)
+            .with_encoding(CsvEncoding::Windows1252)
+            .with_range("A2"))
+        .unwrap();
+        assert_eq!(out, b"\"\x805\"\r\n");
+
+        let out = csv(CsvExportOptions::new().with_bom(true).with_range("C1")).unwrap();
+        assert_eq!(out, b"\xEF\xBB\xBFFALSE\r\n");
+
+        assert!(csv(CsvExportOptions::new().with_encoding(CsvEncoding::Latin1)).is_err());
+        let never = CsvExportOptions::new()
+            .with_delimiter(b';')
+            .with_quoting(CsvQuoting::Never);
+        assert!(csv(never.clone()).is_err());
+        assert_eq!(csv(never.with_range("B1:C1")).unwrap(), b"2.5;FALSE\r\n");
+    }
+
+    #[test]
+    fn test_encoding_labels() {
+        assert_eq!(
+            CsvEncoding::from_label("UTF-8-SIG"),
+            Some(CsvEncoding::Utf8)
+        );
+        assert_eq!(
+            CsvEncoding::from_label("latin_1"),
+            Some(CsvEncoding::Latin1)
+        );
+        assert_eq!(
+            CsvEncoding::from_label("cp1252"),
+            Some(CsvEncoding::Windows1252)
+        );
+        assert_eq!(CsvEncoding::from_label("utf-16"), None);
+    }
+
+    #[test]
+    fn test_sanitize_file_stem() {
+        assert_eq!(sanitize_file_stem("Q1/Q2: sales"), "Q1_Q2_ sales");
+        assert_eq!(sanitize_file_stem("con"), "con_");
+        assert_eq!(sanitize_file_stem("..."), "sheet");
+        assert_eq!(sanitize_file_stem("Data. "), "Data");
+    }
+
+    #[test]
+    fn test_unique_file_name_is_case_insensitive() {
+        let mut used = HashSet::new();
+        assert_eq!(unique_file_name("Data", &mut used), "Data.csv");
+        assert_eq!(unique_file_name("data", &mut used), "data_2.csv");
+        assert_eq!(unique_file_name("DATA", &mut used), "DATA_3.csv");
+    }
+
+    #[test]
+    fn test_json_string_escaping() {
+        let mut out = Vec::new();
+        write_json_string(&mut out, "a\"b\\c\n\u{1}é").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\"a\\\"b\\\\c\\n\\u0001é\""
+        );
+    }
+}
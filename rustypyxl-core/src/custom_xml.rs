@@ -0,0 +1,240 @@
+//! Escape hatches for features the library doesn't model: raw XML fragments
+//! spliced into a sheet's generated XML, and extra package parts saved with
+//! the workbook.
+//!
+//! Fragments are checked for well-formedness when added but are otherwise
+//! written as given; keeping them schema-valid (element order, namespace
+//! declarations) is up to the caller. They are written by [`Workbook::save`]
+//! and friends, not by the streaming writer.
+
+use crate::error::{Result, RustypyxlError};
+use crate::passthrough::{is_writer_owned, PreservedPart, PreservedWorkbookRel};
+use crate::workbook::Workbook;
+use crate::worksheet::Worksheet;
+
+/// Where in a worksheet's XML a fragment goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XmlInjectionPoint {
+    /// Directly after `</sheetData>`, where `sheetCalcPr` and
+    /// `sheetProtection` sit in the schema.
+    AfterSheetData,
+    /// Inside the sheet's `<extLst>`, written last in `<worksheet>`. Each
+    /// fragment should be an `<ext uri="...">` element declaring the
+    /// namespaces it uses.
+    ExtLst,
+}
+
+impl XmlInjectionPoint {
+    /// Parse "after_sheet_data" or "ext_lst" (also "extLst").
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "after_sheet_data" | "afterSheetData" => Some(XmlInjectionPoint::AfterSheetData),
+            "ext_lst" | "extLst" => Some(XmlInjectionPoint::ExtLst),
+            _ => None,
+        }
+    }
+}
+
+/// Check that `xml` is a sequence of well-formed elements.
+fn check_fragment(xml: &str) -> Result<()> {
+    let wrapped = format!("<fragment>{}</fragment>", xml);
+    let mut reader = quick_xml::Reader::from_str(&wrapped);
+    loop {
+        match reader.read_event() {
+            Ok(quick_xml::events::Event::Eof) => return Ok(()),
+            Ok(_) => {}
+            Err(e) => {
+                return Err(RustypyxlError::custom(format!(
+                    "XML fragment is not well-formed: {}",
+                    e
+                )))
+            }
+        }
+    }
+}
+
+impl Worksheet {
+    /// Add a raw XML fragment to write at `point` of this sheet's XML on
+    /// save. Fragments at the same point are written in the order added.
+    pub fn add_xml_fragment(&mut self, point: XmlInjectionPoint, xml: &str) -> Result<()> {
+        check_fragment(xml)?;
+        self.xml_fragments.push((point, xml.to_string()));
+        Ok(())
+    }
+
+    /// The fragments added for `point`, in order.
+    pub fn xml_fragments_at(&self, point: XmlInjectionPoint) -> impl Iterator<Item = &str> {
+        self.xml_fragments
+            .iter()
+            .filter(move |(p, _)| *p == point)
+            .map(|(_, xml)| xml.as_str())
+    }
+
+    /// Remove every fragment added to this sheet.
+    pub fn clear_xml_fragments(&mut self) {
+        self.xml_fragments.clear();
+    }
+}
+
+impl Workbook {
+    /// Save an extra part at package `path` (e.g. `"customXml/item1.xml"`)
+    /// with the workbook, listed in `[Content_Types].xml` under
+    /// `content_type`, which may only be left out for `.rels` parts. With a
+    /// `rel_type`, workbook.xml.rels gets a relationship of that type to it.
+    ///
+    /// Paths the writer produces itself (worksheets, styles, drawings, ...)
+    /// and paths already taken are rejected.
+    pub fn add_custom_part(
+        &mut self,
+        path: &str,
+        bytes: Vec<u8>,
+        content_type: Option<&str>,
+        rel_type: Option<&str>,
+    ) -> Result<()> {
+        let path = path.trim_start_matches('/');
+        if path.is_empty() || path.ends_with('/') {
+            return Err(RustypyxlError::custom(format!(
+                "Invalid part path '{}'",
+                path
+            )));
+        }
+        if is_writer_owned(path) {
+            return Err(RustypyxlError::custom(format!(
+                "Part '{}' is written by the library and cannot be replaced",
+                path
+            )));
+        }
+        let taken = self.preserved.parts.iter().any(|p| p.path == path)
+            || self.pivots.parts.iter().any(|(p, _)| p == path);
+        if taken {
+            return Err(RustypyxlError::custom(format!(
+                "Part '{}' already exists",
+                path
+            )));
+        }
+        if content_type.is_none() && !path.ends_with(".rels") {
+            return Err(RustypyxlError::custom(format!(
+                "Part '{}' needs a content type",
+                path
+            )));
+        }
+
+        if let Some(rel_type) = rel_type {
+            let target = match path.strip_prefix("xl/") {
+                Some(inside) => inside.to_string(),
+                None => format!("../{}", path),
+            };
+            self.preserved.rels.push(PreservedWorkbookRel {
+                id: format!("rIdCustom{}", self.preserved.rels.len() + 1),
+                rel_type: rel_type.to_string(),
+                target,
+                external: false,
+            });
+        }
+        self.preserved.parts.push(PreservedPart {
+            path: path.to_string(),
+            bytes,
+            content_type: content_type.map(str::to_string),
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Read};
+
+    fn saved(wb: &Workbook) -> zip::ZipArchive<Cursor<Vec<u8>>> {
+        let bytes = wb.save_to_bytes().unwrap();
+        zip::ZipArchive::new(Cursor::new(bytes)).unwrap()
+    }
+
+    fn part(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, path: &str) -> String {
+        let mut xml = String::new();
+        archive
+            .by_name(path)
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    }
+
+    #[test]
+    fn fragments_land_at_their_points() {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("S".to_string())).unwrap();
+        ws.set_cell_value(1, 1, crate::CellValue::Number(1.0));
+        ws.add_xml_fragment(
+            XmlInjectionPoint::AfterSheetData,
+            r#"<sheetCalcPr fullCalcOnLoad="1"/>"#,
+        )
+        .unwrap();
+        let ext = r#"<ext uri="{custom}" xmlns:my="urn:my"><my:note>hi</my:note></ext>"#;
+        ws.add_xml_fragment(XmlInjectionPoint::ExtLst, ext).unwrap();
+
+        let xml = part(&mut saved(&wb), "xl/worksheets/sheet1.xml");
+        assert!(xml.contains(r#"</sheetData><sheetCalcPr fullCalcOnLoad="1"/>"#));
+        assert!(xml.ends_with(&format!("<extLst>{}</extLst></worksheet>", ext)));
+    }
+
+    #[test]
+    fn malformed_fragment_is_rejected() {
+        let mut ws = Worksheet::new("S");
+        assert!(ws
+            .add_xml_fragment(XmlInjectionPoint::ExtLst, "<ext><open></ext>")
+            .is_err());
+        assert_eq!(ws.xml_fragments_at(XmlInjectionPoint::ExtLst).count(), 0);
+    }
+
+    #[test]
+    fn custom_part_is_saved_and_related() {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("S".to_string())).unwrap();
+        wb.add_custom_part(
+            "customXml/item1.xml",
+            b"<data/>".to_vec(),
+            Some("application/xml"),
+            Some("http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml"),
+        )
+        .unwrap();
+
+        let mut archive = saved(&wb);
+        assert_eq!(part(&mut archive, "customXml/item1.xml"), "<data/>");
+        assert!(part(&mut archive, "[Content_Types].xml")
+            .contains(r#"PartName="/customXml/item1.xml" ContentType="application/xml""#));
+        assert!(part(&mut archive, "xl/_rels/workbook.xml.rels")
+            .contains(r#"Target="../customXml/item1.xml""#));
+
+        // The round trip keeps it as an ordinary preserved part.
+        let bytes = wb.save_to_bytes().unwrap();
+        let reloaded = Workbook::load_from_bytes(&bytes).unwrap();
+        assert!(reloaded
+            .preserved
+            .parts
+            .iter()
+            .any(|p| p.path == "customXml/item1.xml"));
+    }
+
+    #[test]
+    fn custom_part_paths_are_checked() {
+        let mut wb = Workbook::new();
+        let xml = || b"<x/>".to_vec();
+        assert!(wb
+            .add_custom_part(
+                "xl/worksheets/sheet9.xml",
+                xml(),
+                Some("application/xml"),
+                None
+            )
+            .is_err());
+        assert!(wb
+            .add_custom_part("extra/a.xml", xml(), None, None)
+            .is_err());
+        wb.add_custom_part("/extra/a.xml", xml(), Some("application/xml"), None)
+            .unwrap();
+        assert!(wb
+            .add_custom_part("extra/a.xml", xml(), Some("application/xml"), None)
+            .is_err());
+    }
+}
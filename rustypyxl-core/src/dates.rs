@@ -0,0 +1,189 @@
+//! Excel serial dates.
+//!
+//! A date in a spreadsheet is a number of days since an epoch, with the time
+//! of day as the fraction; only the cell's number format says it is a date.
+//! Two epochs exist. In the 1900 system (the default) serial 1 is 1900-01-01
+//! and serial 60 is a 1900-02-29 that never happened, kept from Lotus 1-2-3.
+//! In the 1904 system of old Mac workbooks (`<workbookPr date1904="1"/>`)
+//! serial 0 is 1904-01-01.
+//!
+//! On load, a number whose format [`is_date_format`] becomes a
+//! [`CellValue::Date`](crate::CellValue::Date); on save every date is written
+//! as a serial in the workbook's date system.
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::cell::DateValue;
+
+pub use crate::numfmt::is_date_format;
+
+/// Format given to a date-time that has no date format of its own.
+pub const DEFAULT_DATETIME_FORMAT: &str = "yyyy-mm-dd h:mm:ss";
+
+/// Format given to a date (a date-time at midnight) that has no date format
+/// of its own.
+pub const DEFAULT_DATE_FORMAT: &str = "yyyy-mm-dd";
+
+/// Format given to a time of day (see [`is_time_of_day`]) that has no date
+/// format of its own.
+pub const DEFAULT_TIME_FORMAT: &str = "h:mm:ss";
+
+/// The day 1900-system serials below 1 fall on: Excel's "1900-01-00",
+/// which is 1899-12-31. A bare time of day is a date-time on this day.
+pub const EXCEL_DAY_ZERO: NaiveDate = NaiveDate::from_ymd_opt(1899, 12, 31).unwrap();
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// Day 0 of the serial count, before the 1900 system's leap-day shift.
+fn epoch(date1904: bool) -> NaiveDate {
+    if date1904 {
+        NaiveDate::from_ymd_opt(1904, 1, 1).unwrap()
+    } else {
+        NaiveDate::from_ymd_opt(1899, 12, 30).unwrap()
+    }
+}
+
+/// The date and time an Excel serial stands for, to the millisecond: whole
+/// days since the epoch of the chosen system (`date1904` for the 1904
+/// system), with the fraction as the time of day. `None` for negative,
+/// non-finite or out-of-range serials, which Excel shows as `#####`.
+///
+/// In the 1900 system serials below 1 are times of day on
+/// [`EXCEL_DAY_ZERO`]. Serial 60 is Excel's phantom 1900-02-29; chrono has
+/// no such day, so it reads as 1900-02-28, the same date as serial 59.
+/// [`to_excel`] gives 59 back for it, so the phantom day does not survive a
+/// round trip.
+pub fn from_excel(serial: f64, date1904: bool) -> Option<NaiveDateTime> {
+    if !serial.is_finite() || serial < 0.0 {
+        return None;
+    }
+    let mut days = serial.floor();
+    let ms = ((serial - days) * MS_PER_DAY).round() as i64;
+    if !date1904 && days < 60.0 {
+        days += 1.0;
+    }
+    let midnight = epoch(date1904)
+        .and_hms_opt(0, 0, 0)?
+        .checked_add_signed(Duration::try_days(days as i64)?)?;
+    midnight.checked_add_signed(Duration::milliseconds(ms))
+}
+
+/// The Excel serial for a date and time in the 1900 system, or the 1904
+/// system when `date1904` is set; the inverse of [`from_excel`]. Dates
+/// before the epoch give negative serials, which Excel will not display.
+pub fn to_excel(value: &NaiveDateTime, date1904: bool) -> f64 {
+    let days = (value.date() - epoch(date1904)).num_days() as f64;
+    let time = value.time();
+    let fraction = (time.num_seconds_from_midnight() as f64 * 1000.0
+        + time.nanosecond() as f64 / 1_000_000.0)
+        / MS_PER_DAY;
+    let serial = days + fraction;
+    // Before 1900-03-01 the 1900 system has no phantom leap day to skip.
+    if !date1904 && serial < 61.0 {
+        serial - 1.0
+    } else {
+        serial
+    }
+}
+
+/// Whether a date-time from [`from_excel`] is a bare time of day, that is
+/// a 1900-system serial below 1, which lands on [`EXCEL_DAY_ZERO`].
+pub fn is_time_of_day(value: &NaiveDateTime) -> bool {
+    value.date() == EXCEL_DAY_ZERO
+}
+
+/// The number format a date is saved with when its cell has none that
+/// [`is_date_format`]: [`DEFAULT_TIME_FORMAT`] for a time of day,
+/// [`DEFAULT_DATE_FORMAT`] for a date or a date-time at midnight, and
+/// [`DEFAULT_DATETIME_FORMAT`] otherwise. Without one the serial would
+/// show as a plain number.
+pub fn default_format(value: &DateValue) -> &'static str {
+    match value {
+        DateValue::Time(_) => DEFAULT_TIME_FORMAT,
+        DateValue::Date(_) => DEFAULT_DATE_FORMAT,
+        DateValue::DateTime(dt) if is_time_of_day(dt) => DEFAULT_TIME_FORMAT,
+        DateValue::DateTime(dt) if dt.time() == NaiveTime::MIN => DEFAULT_DATE_FORMAT,
+        DateValue::DateTime(_) => DEFAULT_DATETIME_FORMAT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dt(y: i32, m: u32, d: u32, h: u32, mi: u32, s: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, mi, s)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_1900_system() {
+        assert_eq!(from_excel(1.0, false), Some(dt(1900, 1, 1, 0, 0, 0)));
+        assert_eq!(from_excel(59.0, false), Some(dt(1900, 2, 28, 0, 0, 0)));
+        assert_eq!(from_excel(61.0, false), Some(dt(1900, 3, 1, 0, 0, 0)));
+        assert_eq!(
+            from_excel(45366.4375, false),
+            Some(dt(2024, 3, 15, 10, 30, 0))
+        );
+        assert_eq!(from_excel(0.75, false), Some(dt(1899, 12, 31, 18, 0, 0)));
+        assert!(is_time_of_day(&from_excel(0.75, false).unwrap()));
+        assert!(!is_time_of_day(&from_excel(1.75, false).unwrap()));
+
+        for serial in [1.0, 59.0, 61.0, 45366.4375, 0.75, 2958465.0] {
+            let value = from_excel(serial, false).unwrap();
+            assert_eq!(to_excel(&value, false), serial, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_phantom_leap_day() {
+        // Serial 60 is 1900-02-29, a day that does not exist; it reads as the
+        // day before and saves back as 59.
+        let feb28 = dt(1900, 2, 28, 0, 0, 0);
+        assert_eq!(from_excel(60.0, false), Some(feb28));
+        assert_eq!(from_excel(60.0, false), from_excel(59.0, false));
+        assert_eq!(from_excel(60.5, false), Some(dt(1900, 2, 28, 12, 0, 0)));
+        assert_eq!(to_excel(&feb28, false), 59.0);
+        // The 1904 system has no such day.
+        assert_eq!(from_excel(60.0, true), Some(dt(1904, 3, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn test_1904_system() {
+        assert_eq!(from_excel(0.0, true), Some(dt(1904, 1, 1, 0, 0, 0)));
+        assert_eq!(from_excel(43904.5, true), Some(dt(2024, 3, 15, 12, 0, 0)));
+        assert_eq!(to_excel(&dt(2024, 3, 15, 12, 0, 0), true), 43904.5);
+        // The same day is 1462 serials apart in the two systems.
+        let day = dt(2000, 6, 1, 0, 0, 0);
+        assert_eq!(to_excel(&day, false) - to_excel(&day, true), 1462.0);
+    }
+
+    #[test]
+    fn test_times_round_to_the_millisecond() {
+        let value = from_excel(45366.0 + 45045.0 / 86400.0, false).unwrap();
+        assert_eq!(value, dt(2024, 3, 15, 12, 30, 45));
+    }
+
+    #[test]
+    fn test_unrepresentable_serials() {
+        assert_eq!(from_excel(-1.0, false), None);
+        assert_eq!(from_excel(f64::NAN, false), None);
+        assert_eq!(from_excel(1e300, false), None);
+    }
+
+    #[test]
+    fn test_default_format() {
+        let at = |value: NaiveDateTime| default_format(&DateValue::DateTime(value));
+        assert_eq!(at(dt(2024, 1, 2, 0, 0, 0)), "yyyy-mm-dd");
+        assert_eq!(at(dt(2024, 1, 2, 9, 0, 0)), "yyyy-mm-dd h:mm:ss");
+        assert_eq!(at(dt(1899, 12, 31, 18, 15, 0)), "h:mm:ss");
+        let day = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        assert_eq!(default_format(&DateValue::Date(day)), "yyyy-mm-dd");
+        assert_eq!(
+            default_format(&DateValue::Time(NaiveTime::from_hms_opt(9, 0, 0).unwrap())),
+            "h:mm:ss"
+        );
+    }
+}
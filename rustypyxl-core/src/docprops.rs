@@ -0,0 +1,260 @@
+//! Document properties: who made the file, its title, and when
+//! (`docProps/core.xml`), and the application that wrote it
+//! (`docProps/app.xml`).
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::error::{Result, RustypyxlError};
+use crate::writer::escape_xml;
+
+/// Properties written to `docProps/core.xml` and `docProps/app.xml`.
+///
+/// Timestamps are UTC. A timestamp left as `None` is stamped with the time
+/// of saving; set a fixed value for byte-for-byte reproducible output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentProperties {
+    /// `dc:creator`, the author.
+    pub creator: Option<String>,
+    /// `cp:lastModifiedBy`.
+    pub last_modified_by: Option<String>,
+    /// `dc:title`.
+    pub title: Option<String>,
+    /// The `Application` named in app.xml.
+    pub application: String,
+    /// `dcterms:created`; `None` for the time of saving.
+    pub created: Option<NaiveDateTime>,
+    /// `dcterms:modified`; `None` for the time of saving.
+    pub modified: Option<NaiveDateTime>,
+}
+
+impl Default for DocumentProperties {
+    fn default() -> Self {
+        DocumentProperties {
+            creator: Some("rustypyxl".to_string()),
+            last_modified_by: None,
+            title: None,
+            application: "rustypyxl".to_string(),
+            created: None,
+            modified: None,
+        }
+    }
+}
+
+impl DocumentProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_creator(mut self, creator: impl Into<String>) -> Self {
+        self.creator = Some(creator.into());
+        self
+    }
+
+    pub fn with_last_modified_by(mut self, name: impl Into<String>) -> Self {
+        self.last_modified_by = Some(name.into());
+        self
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_application(mut self, application: impl Into<String>) -> Self {
+        self.application = application.into();
+        self
+    }
+
+    pub fn with_created(mut self, created: NaiveDateTime) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    pub fn with_modified(mut self, modified: NaiveDateTime) -> Self {
+        self.modified = Some(modified);
+        self
+    }
+
+    /// Fix both timestamps, so saving the same workbook twice gives the
+    /// same bytes.
+    pub fn with_fixed_timestamps(self, at: NaiveDateTime) -> Self {
+        self.with_created(at).with_modified(at)
+    }
+
+    /// The `docProps/core.xml` part, with unset timestamps stamped `now`.
+    pub fn core_xml(&self, now: NaiveDateTime) -> String {
+        let mut xml = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:dcmitype="http://purl.org/dc/dcmitype/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+"#,
+        );
+        for (tag, value) in [
+            ("dc:title", &self.title),
+            ("dc:creator", &self.creator),
+            ("cp:lastModifiedBy", &self.last_modified_by),
+        ] {
+            if let Some(value) = value {
+                xml.push_str(&format!("<{tag}>{}</{tag}>\n", escape_xml(value)));
+            }
+        }
+        for (tag, value) in [
+            ("dcterms:created", self.created),
+            ("dcterms:modified", self.modified),
+        ] {
+            xml.push_str(&format!(
+                "<{tag} xsi:type=\"dcterms:W3CDTF\">{}</{tag}>\n",
+                format_timestamp(&value.unwrap_or(now))
+            ));
+        }
+        xml.push_str("</cp:coreProperties>");
+        xml
+    }
+
+    /// The `docProps/app.xml` part.
+    pub fn app_xml(&self) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+<Application>{}</Application>
+</Properties>"#,
+            escape_xml(&self.application)
+        )
+    }
+
+    /// Read creator, lastModifiedBy, title and the creation time from a
+    /// `docProps/core.xml` part. The modification time is left unset, so the
+    /// next save records when the file was modified.
+    pub(crate) fn from_core_xml(xml: &[u8]) -> Self {
+        let mut props = DocumentProperties {
+            creator: None,
+            ..Default::default()
+        };
+        let mut reader = Reader::from_reader(xml);
+        let mut buf = Vec::new();
+        let mut current: Option<Vec<u8>> = None;
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => current = Some(e.local_name().as_ref().to_vec()),
+                Ok(Event::Text(t)) => {
+                    let Some(name) = current.as_deref() else {
+                        continue;
+                    };
+                    let Ok(text) = t.unescape() else { continue };
+                    let text = text.trim().to_string();
+                    match name {
+                        b"creator" => props.creator = Some(text),
+                        b"lastModifiedBy" => props.last_modified_by = Some(text),
+                        b"title" => props.title = Some(text),
+                        b"created" => props.created = parse_timestamp(&text).ok(),
+                        _ => {}
+                    }
+                }
+                Ok(Event::End(_)) => current = None,
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        props
+    }
+}
+
+impl DocumentProperties {
+    /// The time stamped on the archive's entries: the fixed modification
+    /// time when there is one, so a fixed-timestamp save is reproducible.
+    pub(crate) fn zip_time(&self) -> Option<zip::DateTime> {
+        use chrono::{Datelike, Timelike};
+        let at = self.modified?;
+        zip::DateTime::from_date_and_time(
+            u16::try_from(at.year()).ok()?,
+            at.month() as u8,
+            at.day() as u8,
+            at.hour() as u8,
+            at.minute() as u8,
+            at.second() as u8,
+        )
+        .ok()
+    }
+}
+
+/// The current time in UTC, for stamping unset timestamps.
+pub(crate) fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+/// Format a UTC timestamp as W3CDTF, e.g. `2024-05-17T09:30:00Z`.
+pub fn format_timestamp(value: &NaiveDateTime) -> String {
+    value.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// Parse an ISO-8601 timestamp, converting one with a UTC offset to UTC.
+/// A timestamp without an offset is taken to be UTC already.
+pub fn parse_timestamp(value: &str) -> Result<NaiveDateTime> {
+    let value = value.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.naive_utc());
+    }
+    let naive = value.trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(naive, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S%.f"))
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(naive, "%Y-%m-%d")
+                .map(|d| d.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+        })
+        .map_err(|_| RustypyxlError::custom(format!("Invalid timestamp '{}'", value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> NaiveDateTime {
+        parse_timestamp(s).unwrap()
+    }
+
+    #[test]
+    fn test_core_xml_round_trip() {
+        let props = DocumentProperties::new()
+            .with_creator("Finance <team>")
+            .with_last_modified_by("Nightly job")
+            .with_title("Q3 report")
+            .with_created(at("2024-01-02T03:04:05Z"));
+        let xml = props.core_xml(at("2024-06-01T00:00:00"));
+        assert!(xml.contains("<dc:creator>Finance &lt;team&gt;</dc:creator>"));
+        assert!(xml.contains(
+            "<dcterms:modified xsi:type=\"dcterms:W3CDTF\">2024-06-01T00:00:00Z</dcterms:modified>"
+        ));
+
+        let read = DocumentProperties::from_core_xml(xml.as_bytes());
+        assert_eq!(read.creator.as_deref(), Some("Finance <team>"));
+        assert_eq!(read.last_modified_by.as_deref(), Some("Nightly job"));
+        assert_eq!(read.title.as_deref(), Some("Q3 report"));
+        assert_eq!(read.created, Some(at("2024-01-02T03:04:05")));
+        assert_eq!(read.modified, None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_offsets() {
+        assert_eq!(at("2024-05-17T11:30:00+02:00"), at("2024-05-17T09:30:00Z"));
+        assert_eq!(at("2024-05-17"), at("2024-05-17T00:00:00"));
+        assert!(parse_timestamp("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_fixed_timestamps_are_reproducible() {
+        let mut wb = crate::Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.properties = DocumentProperties::new()
+            .with_title("Stable")
+            .with_fixed_timestamps(at("2020-02-02T02:02:02Z"));
+        let first = wb.save_to_bytes().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        assert_eq!(first, wb.save_to_bytes().unwrap());
+
+        let reloaded = crate::Workbook::load_from_bytes(&first).unwrap();
+        assert_eq!(reloaded.properties.title.as_deref(), Some("Stable"));
+        assert_eq!(reloaded.properties.created, Some(at("2020-02-02T02:02:02")));
+    }
+}
@@ -0,0 +1,701 @@
+//! In-place editing that leaves everything but the edited cells untouched.
+//!
+//! [`Workbook::load`](crate::Workbook::load) models a workbook and writes a
+//! new one on save, so whatever the model lacks (slicers, sparklines, custom
+//! XML, extension lists) is lost. [`EditableWorkbook`] keeps the original
+//! archive instead. Cell edits are queued, and on save they are spliced into
+//! the XML of the worksheets they touch; every other part is copied through
+//! still compressed, and the unedited stretches of an edited worksheet keep
+//! their exact bytes.
+//!
+//! ```no_run
+//! use rustypyxl::edit::EditableWorkbook;
+//! use rustypyxl::CellValue;
+//!
+//! let mut wb = EditableWorkbook::open("report.xlsx").unwrap();
+//! wb.set_cell("Summary", "B2", CellValue::Number(42.0)).unwrap();
+//! wb.save("report.xlsx").unwrap();
+//! ```
+//!
+//! New strings are written inline, so the shared strings part is not
+//! rewritten either. Formulas that depend on an edited cell keep their cached
+//! results until Excel recalculates them.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Cursor, Write};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::cell::CellValue;
+use crate::error::{Result, RustypyxlError};
+use crate::read_only::ReadOnlyWorkbook;
+use crate::utils::{coordinate_from_row_col, parse_coordinate, parse_coordinate_bytes};
+use crate::workbook::{NumberPrecision, Workbook};
+use crate::writer::format_cell_value_with;
+
+const CALC_CHAIN_PART: &str = "xl/calcChain.xml";
+
+/// Queued edits of one sheet, by (row, column).
+type SheetEdits = BTreeMap<(u32, u32), CellValue>;
+
+/// A workbook opened for targeted cell edits; see the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct EditableWorkbook {
+    data: Vec<u8>,
+    reader: ReadOnlyWorkbook,
+    edits: HashMap<String, SheetEdits>,
+    number_precision: NumberPrecision,
+}
+
+impl EditableWorkbook {
+    /// Open a workbook file for editing.
+    pub fn open(path: &str) -> Result<Self> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// Open an in-memory workbook for editing.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        let reader = ReadOnlyWorkbook::from_bytes(data.clone())?;
+        Ok(EditableWorkbook {
+            data,
+            reader,
+            edits: HashMap::new(),
+            number_precision: NumberPrecision::default(),
+        })
+    }
+
+    /// Names of the worksheets, in workbook order.
+    pub fn sheet_names(&self) -> &[String] {
+        self.reader.sheet_names()
+    }
+
+    /// A reader over the workbook as it was opened, without the edits.
+    pub fn original(&self) -> &ReadOnlyWorkbook {
+        &self.reader
+    }
+
+    /// Set how edited numbers are written.
+    pub fn set_number_precision(&mut self, precision: NumberPrecision) {
+        self.number_precision = precision;
+    }
+
+    /// Queue a new value for a cell. The cell keeps its style;
+    /// [`CellValue::Empty`] clears the value.
+    pub fn set_value(
+        &mut self,
+        sheet: &str,
+        row: u32,
+        column: u32,
+        value: CellValue,
+    ) -> Result<()> {
+        self.check_sheet(sheet)?;
+        if !(1..=1_048_576).contains(&row) || !(1..=16_384).contains(&column) {
+            return Err(RustypyxlError::InvalidCoordinate(format!(
+                "row {} column {} is outside the sheet",
+                row, column
+            )));
+        }
+        self.edits
+            .entry(sheet.to_string())
+            .or_default()
+            .insert((row, column), value);
+        Ok(())
+    }
+
+    /// Queue a new value for the cell at `coord`, e.g. "B2".
+    pub fn set_cell(&mut self, sheet: &str, coord: &str, value: CellValue) -> Result<()> {
+        let (row, column) = parse_coordinate(coord)?;
+        self.set_value(sheet, row, column, value)
+    }
+
+    /// The value a cell will have when saved: its queued edit, or the value
+    /// in the file. Reading from the file scans the sheet up to the cell.
+    pub fn get_value(&self, sheet: &str, row: u32, column: u32) -> Result<CellValue> {
+        self.check_sheet(sheet)?;
+        if let Some(value) = self.edits.get(sheet).and_then(|e| e.get(&(row, column))) {
+            return Ok(value.clone());
+        }
+        for streamed in self.reader.rows(sheet)? {
+            let streamed = streamed?;
+            if streamed.index > row {
+                break;
+            }
+            if streamed.index == row {
+                return Ok(streamed
+                    .cells
+                    .into_iter()
+                    .find(|cell| cell.column == column)
+                    .map(|cell| cell.value)
+                    .unwrap_or_default());
+            }
+        }
+        Ok(CellValue::Empty)
+    }
+
+    /// Number of queued cell edits across all sheets.
+    pub fn pending_edits(&self) -> usize {
+        self.edits.values().map(BTreeMap::len).sum()
+    }
+
+    /// Drop the queued edits.
+    pub fn discard_edits(&mut self) {
+        self.edits.clear();
+    }
+
+    /// Save the workbook with the queued edits applied. A file with macros
+    /// cannot be saved under a `.xlsx` name, and a target held open by
+    /// another process fails with [`RustypyxlError::FileLocked`].
+    pub fn save(&self, path: &str) -> Result<()> {
+        let path = std::path::Path::new(path);
+        crate::macros::check_macro_extension(
+            path,
+            crate::sniff::detect_format(&self.data).has_macros,
+        )?;
+        let bytes = self.save_to_bytes()?;
+        let mut file = crate::file_lock::create_file(path)?;
+        file.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// The workbook with the queued edits applied, as bytes. Only the edited
+    /// worksheets are rewritten, and the calculation chain is dropped when an
+    /// edit replaced a formula (Excel rebuilds it).
+    pub fn save_to_bytes(&self) -> Result<Vec<u8>> {
+        let mut archive = ZipArchive::new(Cursor::new(&self.data[..]))?;
+
+        let mut patched: HashMap<String, Vec<u8>> = HashMap::new();
+        let mut replaced_formula = false;
+        for (sheet, edits) in &self.edits {
+            if edits.is_empty() {
+                continue;
+            }
+            let path = self.reader.sheet_path(sheet)?;
+            let xml = Workbook::read_zip_file_to_vec(&mut archive, &path)?;
+            let sheet_patch = patch_sheet_xml(&xml, edits, self.number_precision)?;
+            replaced_formula |= sheet_patch.replaced_formula;
+            patched.insert(path, sheet_patch.xml);
+        }
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let (name, options) = {
+                let entry = archive.by_index_raw(i)?;
+                let options = SimpleFileOptions::default()
+                    .compression_method(entry.compression())
+                    .last_modified_time(entry.last_modified().unwrap_or_default());
+                (entry.name().to_string(), options)
+            };
+            let rewritten = if let Some(xml) = patched.remove(&name) {
+                Some(xml)
+            } else if !replaced_formula {
+                None
+            } else if name == CALC_CHAIN_PART {
+                continue;
+            } else if name == "[Content_Types].xml" {
+                let xml = Workbook::read_zip_file_to_vec(&mut archive, &name)?;
+                Some(remove_elements(&xml, |e| {
+                    e.local_name().as_ref() == b"Override"
+                        && attr_value(e, b"PartName").as_deref() == Some("/xl/calcChain.xml")
+                })?)
+            } else if name == "xl/_rels/workbook.xml.rels" {
+                let xml = Workbook::read_zip_file_to_vec(&mut archive, &name)?;
+                Some(remove_elements(&xml, |e| {
+                    e.local_name().as_ref() == b"Relationship"
+                        && attr_value(e, b"Type").is_some_and(|t| t.ends_with("/calcChain"))
+                })?)
+            } else {
+                None
+            };
+            match rewritten {
+                Some(xml) => {
+                    zip.start_file(name, options)?;
+                    zip.write_all(&xml)?;
+                }
+                None => zip.raw_copy_file(archive.by_index_raw(i)?)?,
+            }
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+
+    fn check_sheet(&self, sheet: &str) -> Result<()> {
+        if self.sheet_names().iter().any(|name| name == sheet) {
+            Ok(())
+        } else {
+            Err(RustypyxlError::WorksheetNotFound(sheet.to_string()))
+        }
+    }
+}
+
+/// A worksheet part with edits spliced in.
+struct PatchedSheet {
+    xml: Vec<u8>,
+    /// An edit overwrote a cell holding a formula.
+    replaced_formula: bool,
+}
+
+/// Splices edits into worksheet XML. The edits are consumed in (row, column)
+/// order as the parse passes their place in `<sheetData>`.
+struct Splicer<'a> {
+    edits: Vec<((u32, u32), &'a CellValue)>,
+    next: usize,
+    /// Namespace prefix of the sheet's elements, e.g. "x:", or empty.
+    prefix: String,
+    precision: NumberPrecision,
+}
+
+impl Splicer<'_> {
+    fn peek(&self) -> Option<(u32, u32)> {
+        self.edits.get(self.next).map(|(at, _)| *at)
+    }
+
+    /// New cells for the edits in `row` left of `column`.
+    fn cells_before(&mut self, row: u32, column: u32) -> String {
+        let mut out = String::new();
+        while let Some((r, c)) = self.peek() {
+            if r != row || c >= column {
+                break;
+            }
+            let value = self.edits[self.next].1;
+            self.next += 1;
+            out.push_str(&self.cell(row, c, value, None));
+        }
+        out
+    }
+
+    /// New rows for the edits above `row`. Rows whose edits only clear
+    /// cells are left out.
+    fn rows_before(&mut self, row: u32) -> String {
+        let mut out = String::new();
+        while let Some((r, _)) = self.peek() {
+            if r >= row {
+                break;
+            }
+            let cells = self.cells_before(r, u32::MAX);
+            if !cells.is_empty() {
+                out.push_str(&self.tag(&format!("<row r=\"{}\">", r)));
+                out.push_str(&cells);
+                out.push_str(&self.tag("</row>"));
+            }
+        }
+        out
+    }
+
+    /// A cell element holding `value`, keeping the style of the cell it
+    /// replaces. Empty with no style is no cell at all.
+    fn cell(&self, row: u32, column: u32, value: &CellValue, style: Option<&str>) -> String {
+        let coord = coordinate_from_row_col(row, column);
+        let mut xml = String::new();
+        if value.is_empty() {
+            if let Some(style) = style {
+                xml = format!("<c r=\"{}\" s=\"{}\"/>", coord, style);
+            }
+        } else {
+            format_cell_value_with(&mut xml, &coord, value, self.precision);
+            if let Some(style) = style {
+                let after_ref = "<c r=\"\"".len() + coord.len();
+                xml.insert_str(after_ref, &format!(" s=\"{}\"", style));
+            }
+        }
+        self.tag(&xml)
+    }
+
+    /// Put the sheet's namespace prefix on generated elements.
+    fn tag(&self, xml: &str) -> String {
+        if self.prefix.is_empty() {
+            return xml.to_string();
+        }
+        xml.replace("</", "\u{0}")
+            .replace('<', &format!("<{}", self.prefix))
+            .replace('\u{0}', &format!("</{}", self.prefix))
+    }
+}
+
+fn patch_sheet_xml(
+    xml: &[u8],
+    edits: &SheetEdits,
+    precision: NumberPrecision,
+) -> Result<PatchedSheet> {
+    let mut splicer = Splicer {
+        edits: edits.iter().map(|(at, value)| (*at, value)).collect(),
+        next: 0,
+        prefix: String::new(),
+        precision,
+    };
+    let mut out = Vec::with_capacity(xml.len() + 64 * edits.len());
+    let mut copied = 0usize;
+    let mut replaced_formula = false;
+
+    let mut in_sheet_data = false;
+    let mut current_row: Option<u32> = None;
+    let mut last_row = 0u32;
+    let mut last_col = 0u32;
+    // The edit replacing the open `<c>`: its position, value and style.
+    let mut replacing: Option<((u32, u32), &CellValue, Option<String>)> = None;
+
+    let mut reader = Reader::from_reader(xml);
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event()?;
+        let end = reader.buffer_position() as usize;
+        match &event {
+            Event::Eof => break,
+            Event::Start(e) | Event::Empty(e) => {
+                let empty = matches!(event, Event::Empty(_));
+                match e.local_name().as_ref() {
+                    b"dimension" if !in_sheet_data => {
+                        if let Some(tag) = widen_dimension(e, &xml[start..end], edits) {
+                            out.extend_from_slice(&xml[copied..start]);
+                            out.extend_from_slice(tag.as_bytes());
+                            copied = end;
+                        }
+                    }
+                    b"sheetData" => {
+                        let name = e.name();
+                        splicer.prefix = match name.prefix() {
+                            Some(p) => format!("{}:", String::from_utf8_lossy(p.as_ref())),
+                            None => String::new(),
+                        };
+                        if empty {
+                            let rows = splicer.rows_before(u32::MAX);
+                            if !rows.is_empty() {
+                                out.extend_from_slice(&xml[copied..start]);
+                                out.extend_from_slice(splicer.tag("<sheetData>").as_bytes());
+                                out.extend_from_slice(rows.as_bytes());
+                                out.extend_from_slice(splicer.tag("</sheetData>").as_bytes());
+                                copied = end;
+                            }
+                        } else {
+                            in_sheet_data = true;
+                        }
+                    }
+                    b"row" if in_sheet_data => {
+                        let row = attr_value(e, b"r")
+                            .and_then(|r| r.parse().ok())
+                            .unwrap_or(last_row + 1);
+                        last_row = row;
+                        last_col = 0;
+                        out.extend_from_slice(&xml[copied..start]);
+                        copied = start;
+                        out.extend_from_slice(splicer.rows_before(row).as_bytes());
+                        if empty {
+                            let cells = splicer.cells_before(row, u32::MAX);
+                            if !cells.is_empty() {
+                                let tag = &xml[start..end];
+                                let open = tag.strip_suffix(b"/>").unwrap_or(tag);
+                                out.extend_from_slice(open.trim_ascii_end());
+                                out.push(b'>');
+                                out.extend_from_slice(cells.as_bytes());
+                                out.extend_from_slice(splicer.tag("</row>").as_bytes());
+                                copied = end;
+                            }
+                        } else {
+                            current_row = Some(row);
+                        }
+                    }
+                    b"c" if current_row.is_some() => {
+                        let row = current_row.unwrap_or_default();
+                        let col = attr_value(e, b"r")
+                            .and_then(|r| parse_coordinate_bytes(r.as_bytes()))
+                            .map(|(_, c)| c)
+                            .unwrap_or(last_col + 1);
+                        last_col = col;
+                        out.extend_from_slice(&xml[copied..start]);
+                        copied = start;
+                        out.extend_from_slice(splicer.cells_before(row, col).as_bytes());
+                        if splicer.peek() == Some((row, col)) {
+                            let value = splicer.edits[splicer.next].1;
+                            splicer.next += 1;
+                            let style = attr_value(e, b"s");
+                            if empty {
+                                out.extend_from_slice(
+                                    splicer.cell(row, col, value, style.as_deref()).as_bytes(),
+                                );
+                                copied = end;
+                            } else {
+                                replacing = Some(((row, col), value, style));
+                            }
+                        }
+                    }
+                    b"f" if replacing.is_some() => {
+                        replaced_formula = true;
+                        if let Some(range) = attr_value(e, b"ref") {
+                            let ((row, col), _, _) = replacing.as_ref().unwrap();
+                            return Err(RustypyxlError::custom(format!(
+                                "{} anchors a formula spanning {}; edit mode cannot replace it",
+                                coordinate_from_row_col(*row, *col),
+                                range
+                            )));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"c" => {
+                    if let Some(((row, col), value, style)) = replacing.take() {
+                        out.extend_from_slice(
+                            splicer.cell(row, col, value, style.as_deref()).as_bytes(),
+                        );
+                        copied = end;
+                    }
+                }
+                b"row" if current_row.is_some() => {
+                    let row = current_row.take().unwrap_or_default();
+                    out.extend_from_slice(&xml[copied..start]);
+                    copied = start;
+                    out.extend_from_slice(splicer.cells_before(row, u32::MAX).as_bytes());
+                }
+                b"sheetData" => {
+                    in_sheet_data = false;
+                    out.extend_from_slice(&xml[copied..start]);
+                    copied = start;
+                    out.extend_from_slice(splicer.rows_before(u32::MAX).as_bytes());
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    out.extend_from_slice(&xml[copied..]);
+    Ok(PatchedSheet {
+        xml: out,
+        replaced_formula,
+    })
+}
+
+/// The `<dimension>` tag widened to cover the edited cells, or None when it
+/// already does.
+fn widen_dimension(e: &BytesStart, tag: &[u8], edits: &SheetEdits) -> Option<String> {
+    let old = attr_value(e, b"ref")?;
+    let (first, last) = old.split_once(':').unwrap_or((&old, &old));
+    let (mut min_row, mut min_col) = parse_coordinate(first).ok()?;
+    let (mut max_row, mut max_col) = parse_coordinate(last).ok()?;
+    let was = (min_row, min_col, max_row, max_col);
+    for (row, col) in edits
+        .iter()
+        .filter(|(_, v)| !v.is_empty())
+        .map(|(at, _)| *at)
+    {
+        min_row = min_row.min(row);
+        min_col = min_col.min(col);
+        max_row = max_row.max(row);
+        max_col = max_col.max(col);
+    }
+    if (min_row, min_col, max_row, max_col) == was {
+        return None;
+    }
+    let new = format!(
+        "{}:{}",
+        coordinate_from_row_col(min_row, min_col),
+        coordinate_from_row_col(max_row, max_col)
+    );
+    Some(String::from_utf8_lossy(tag).replacen(old.as_str(), &new, 1))
+}
+
+/// Cut the elements `remove` picks (with their content) out of `xml`,
+/// leaving every other byte as it was.
+fn remove_elements(xml: &[u8], remove: impl Fn(&BytesStart) -> bool) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(xml.len());
+    let mut copied = 0usize;
+    let mut skipping: Option<(usize, usize)> = None;
+    let mut reader = Reader::from_reader(xml);
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event()?;
+        let end = reader.buffer_position() as usize;
+        match (&event, skipping) {
+            (Event::Eof, _) => break,
+            (Event::Start(_), Some((from, depth))) => skipping = Some((from, depth + 1)),
+            (Event::End(_), Some((from, 1))) => {
+                out.extend_from_slice(&xml[copied..from]);
+                copied = end;
+                skipping = None;
+            }
+            (Event::End(_), Some((from, depth))) => skipping = Some((from, depth - 1)),
+            (Event::Start(e), None) if remove(e) => skipping = Some((start, 1)),
+            (Event::Empty(e), None) if remove(e) => {
+                out.extend_from_slice(&xml[copied..start]);
+                copied = end;
+            }
+            _ => {}
+        }
+    }
+    out.extend_from_slice(&xml[copied..]);
+    Ok(out)
+}
+
+fn attr_value(e: &BytesStart, key: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.as_ref() == key)
+        .map(|a| String::from_utf8_lossy(&a.value).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn edits(list: &[((u32, u32), CellValue)]) -> SheetEdits {
+        list.iter().cloned().collect()
+    }
+
+    fn patch(xml: &str, list: &[((u32, u32), CellValue)]) -> String {
+        let patched = patch_sheet_xml(xml.as_bytes(), &edits(list), NumberPrecision::default());
+        String::from_utf8(patched.unwrap().xml).unwrap()
+    }
+
+    #[test]
+    fn replaces_cells_and_keeps_their_style() {
+        let xml = r#"<worksheet><dimension ref="A1:B2"/><sheetData><row r="1" spans="1:2"><c r="A1" s="3"><v>1</v></c><c r="B1"><v>2</v></c></row></sheetData><extLst><ext uri="x"/></extLst></worksheet>"#;
+        let out = patch(xml, &[((1, 1), CellValue::Number(5.0))]);
+        assert_eq!(
+            out,
+            r#"<worksheet><dimension ref="A1:B2"/><sheetData><row r="1" spans="1:2"><c r="A1" s="3"><v>5</v></c><c r="B1"><v>2</v></c></row></sheetData><extLst><ext uri="x"/></extLst></worksheet>"#
+        );
+    }
+
+    #[test]
+    fn inserts_cells_and_rows_in_order() {
+        let xml = r#"<worksheet><dimension ref="B2"/><sheetData><row r="2"><c r="B2"><v>1</v></c></row><row r="4"/></sheetData></worksheet>"#;
+        let out = patch(
+            xml,
+            &[
+                ((1, 1), CellValue::Boolean(true)),
+                ((2, 1), CellValue::Number(0.5)),
+                ((2, 3), CellValue::String(Arc::from("a<b"))),
+                ((4, 2), CellValue::Formula("B2*2".to_string())),
+                ((6, 1), CellValue::Number(9.0)),
+            ],
+        );
+        assert_eq!(
+            out,
+            concat!(
+                r#"<worksheet><dimension ref="A1:C6"/><sheetData>"#,
+                r#"<row r="1"><c r="A1" t="b"><v>1</v></c></row>"#,
+                r#"<row r="2"><c r="A2"><v>0.5</v></c><c r="B2"><v>1</v></c>"#,
+                r#"<c r="C2" t="inlineStr"><is><t>a&lt;b</t></is></c></row>"#,
+                r#"<row r="4"><c r="B4"><f>B2*2</f></c></row>"#,
+                r#"<row r="6"><c r="A6"><v>9</v></c></row></sheetData></worksheet>"#
+            )
+        );
+    }
+
+    #[test]
+    fn clearing_keeps_styled_cell_and_uses_prefix() {
+        let xml = r#"<x:worksheet xmlns:x="urn"><x:sheetData><x:row r="1"><x:c r="A1" s="2"><x:v>1</x:v></x:c></x:row></x:sheetData></x:worksheet>"#;
+        let out = patch(
+            xml,
+            &[((1, 1), CellValue::Empty), ((1, 2), CellValue::Number(3.0))],
+        );
+        assert_eq!(
+            out,
+            r#"<x:worksheet xmlns:x="urn"><x:sheetData><x:row r="1"><x:c r="A1" s="2"/><x:c r="B1"><x:v>3</x:v></x:c></x:row></x:sheetData></x:worksheet>"#
+        );
+    }
+
+    #[test]
+    fn empty_sheet_data_gets_rows() {
+        let xml = r#"<worksheet><sheetData/></worksheet>"#;
+        let out = patch(xml, &[((3, 2), CellValue::Number(1.0))]);
+        assert_eq!(
+            out,
+            r#"<worksheet><sheetData><row r="3"><c r="B3"><v>1</v></c></row></sheetData></worksheet>"#
+        );
+    }
+
+    #[test]
+    fn shared_formula_anchor_is_refused() {
+        let xml = r#"<worksheet><sheetData><row r="1"><c r="A1"><f t="shared" ref="A1:A3" si="0">B1</f><v>1</v></c></row></sheetData></worksheet>"#;
+        let result = patch_sheet_xml(
+            xml.as_bytes(),
+            &edits(&[((1, 1), CellValue::Number(1.0))]),
+            NumberPrecision::default(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn save_rewrites_only_the_edited_sheet() {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.create_sheet(Some("Other".to_string())).unwrap();
+        wb.set_cell_value_in_sheet("Data", 1, 1, CellValue::Number(1.0))
+            .unwrap();
+        wb.set_cell_value_in_sheet("Other", 1, 1, CellValue::Number(2.0))
+            .unwrap();
+        wb.add_custom_part(
+            "customXml/item1.xml",
+            b"<data>kept</data>".to_vec(),
+            Some("application/xml"),
+            None,
+        )
+        .unwrap();
+        let original = wb.save_to_bytes().unwrap();
+
+        let mut editable = EditableWorkbook::from_bytes(original.clone()).unwrap();
+        assert_eq!(editable.sheet_names(), ["Data", "Other"]);
+        editable
+            .set_cell("Data", "B3", CellValue::String(Arc::from("new")))
+            .unwrap();
+        assert!(editable
+            .set_cell("Missing", "A1", CellValue::Empty)
+            .is_err());
+        assert_eq!(
+            editable.get_value("Data", 3, 2).unwrap(),
+            CellValue::String(Arc::from("new"))
+        );
+        assert_eq!(
+            editable.get_value("Data", 1, 1).unwrap(),
+            CellValue::Number(1.0)
+        );
+        assert_eq!(editable.pending_edits(), 1);
+        let saved = editable.save_to_bytes().unwrap();
+
+        let raw_entries = |data: &[u8]| {
+            let mut archive = ZipArchive::new(Cursor::new(data.to_vec())).unwrap();
+            (0..archive.len())
+                .map(|i| {
+                    let mut entry = archive.by_index_raw(i).unwrap();
+                    let mut raw = Vec::new();
+                    std::io::Read::read_to_end(&mut entry, &mut raw).unwrap();
+                    (entry.name().to_string(), raw)
+                })
+                .collect::<Vec<_>>()
+        };
+        let before = raw_entries(&original);
+        let after = raw_entries(&saved);
+        assert_eq!(before.len(), after.len());
+        for ((name, old), (new_name, new)) in before.iter().zip(&after) {
+            assert_eq!(name, new_name);
+            if name == "xl/worksheets/sheet1.xml" {
+                assert_ne!(old, new);
+            } else {
+                assert_eq!(old, new, "{} changed", name);
+            }
+        }
+
+        let reloaded = Workbook::load_from_bytes(&saved).unwrap();
+        let data = reloaded.get_sheet_by_name("Data").unwrap();
+        assert_eq!(data.get_cell_value(1, 1), Some(&CellValue::Number(1.0)));
+        assert_eq!(
+            data.get_cell_value(3, 2),
+            Some(&CellValue::String(Arc::from("new")))
+        );
+    }
+
+    #[test]
+    fn remove_elements_leaves_other_bytes() {
+        let xml = br#"<Types><Override PartName="/a"/>
+<Override PartName="/xl/calcChain.xml"/><Default Extension="x"><y/></Default></Types>"#;
+        let out = remove_elements(xml, |e| {
+            attr_value(e, b"PartName").as_deref() == Some("/xl/calcChain.xml")
+                || e.local_name().as_ref() == b"Default"
+        })
+        .unwrap();
+        assert_eq!(out, b"<Types><Override PartName=\"/a\"/>\n</Types>");
+    }
+}
@@ -41,6 +41,9 @@ pub enum RustypyxlError {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    #[error("File is locked by another process (is it open in Excel?): {0}")]
+    FileLocked(String),
+
     #[error("S3 error: {0}")]
     S3Error(String),
 
@@ -0,0 +1,225 @@
+//! Handling for a save target that another process holds open.
+//!
+//! On Windows, Excel opens workbooks without share-write access, so
+//! creating the file while it is open fails with a sharing violation. That
+//! surfaces as [`RustypyxlError::FileLocked`] rather than a bare I/O error,
+//! and [`LockedFilePolicy`] lets a caller wait for the lock to clear or
+//! write next to the locked file instead.
+
+use crate::error::{Result, RustypyxlError};
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How many "name (n).xlsx" candidates [`LockedFilePolicy::SaveAlternate`]
+/// tries before giving up.
+const MAX_ALTERNATES: u32 = 99;
+
+/// What to do when the save target is locked by another process.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum LockedFilePolicy {
+    /// Return [`RustypyxlError::FileLocked`] immediately.
+    #[default]
+    Fail,
+    /// Try again up to `attempts` more times, sleeping `initial_delay`
+    /// before the first retry and doubling it before each one after.
+    Retry {
+        attempts: u32,
+        initial_delay: Duration,
+    },
+    /// Save beside the locked file as "name (1).xlsx", "name (2).xlsx", ...
+    /// using the first name that is free.
+    SaveAlternate,
+}
+
+impl LockedFilePolicy {
+    /// Retry `attempts` times, starting from a 250ms delay.
+    pub fn retry(attempts: u32) -> Self {
+        LockedFilePolicy::Retry {
+            attempts,
+            initial_delay: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Whether an I/O error means another process has the file open without
+/// sharing (ERROR_SHARING_VIOLATION or ERROR_LOCK_VIOLATION). Unix has no
+/// mandatory locks of this kind, so this is always false there.
+pub fn is_sharing_violation(err: &io::Error) -> bool {
+    #[cfg(windows)]
+    {
+        matches!(err.raw_os_error(), Some(32 | 33))
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = err;
+        false
+    }
+}
+
+/// Create (or truncate) `path` for writing, reporting a sharing violation
+/// as [`RustypyxlError::FileLocked`].
+pub fn create_file(path: &Path) -> Result<File> {
+    File::create(path).map_err(|e| {
+        if is_sharing_violation(&e) {
+            RustypyxlError::FileLocked(path.display().to_string())
+        } else {
+            e.into()
+        }
+    })
+}
+
+/// Create `path` for writing under `policy`. Returns the file and the path
+/// actually created, which differs from `path` under
+/// [`LockedFilePolicy::SaveAlternate`].
+pub fn create_with_policy(path: &Path, policy: &LockedFilePolicy) -> Result<(File, PathBuf)> {
+    open_with_policy(path, policy, create_file, std::thread::sleep)
+}
+
+/// "dir/report.xlsx" -> "dir/report (n).xlsx".
+fn alternate_path(path: &Path, n: u32) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{} ({}).{}", stem, n, ext.to_string_lossy()),
+        None => format!("{} ({})", stem, n),
+    };
+    path.with_file_name(name)
+}
+
+fn open_with_policy<T>(
+    path: &Path,
+    policy: &LockedFilePolicy,
+    mut create: impl FnMut(&Path) -> Result<T>,
+    mut sleep: impl FnMut(Duration),
+) -> Result<(T, PathBuf)> {
+    let first = create(path);
+    let locked = match first {
+        Err(RustypyxlError::FileLocked(locked)) => locked,
+        other => return other.map(|file| (file, path.to_path_buf())),
+    };
+    match policy {
+        LockedFilePolicy::Fail => Err(RustypyxlError::FileLocked(locked)),
+        LockedFilePolicy::Retry {
+            attempts,
+            initial_delay,
+        } => {
+            let mut delay = *initial_delay;
+            for _ in 0..*attempts {
+                sleep(delay);
+                delay = delay.saturating_mul(2);
+                match create(path) {
+                    Err(RustypyxlError::FileLocked(_)) => continue,
+                    other => return other.map(|file| (file, path.to_path_buf())),
+                }
+            }
+            Err(RustypyxlError::FileLocked(locked))
+        }
+        LockedFilePolicy::SaveAlternate => {
+            for n in 1..=MAX_ALTERNATES {
+                let candidate = alternate_path(path, n);
+                // Never overwrite an earlier alternate.
+                if candidate.exists() {
+                    continue;
+                }
+                match create(&candidate) {
+                    Err(RustypyxlError::FileLocked(_)) => continue,
+                    other => return other.map(|file| (file, candidate)),
+                }
+            }
+            Err(RustypyxlError::FileLocked(locked))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked_until(n: usize) -> impl FnMut(&Path) -> Result<PathBuf> {
+        let mut calls = 0;
+        move |path: &Path| {
+            calls += 1;
+            if calls <= n {
+                Err(RustypyxlError::FileLocked(path.display().to_string()))
+            } else {
+                Ok(path.to_path_buf())
+            }
+        }
+    }
+
+    #[test]
+    fn test_fail_reports_file_locked() {
+        let err = open_with_policy(
+            Path::new("a.xlsx"),
+            &LockedFilePolicy::Fail,
+            locked_until(1),
+            |_| {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, RustypyxlError::FileLocked(p) if p == "a.xlsx"));
+    }
+
+    #[test]
+    fn test_retry_backs_off_until_unlocked() {
+        let mut slept = Vec::new();
+        let policy = LockedFilePolicy::Retry {
+            attempts: 5,
+            initial_delay: Duration::from_millis(10),
+        };
+        let (_, path) = open_with_policy(Path::new("a.xlsx"), &policy, locked_until(3), |d| {
+            slept.push(d.as_millis())
+        })
+        .unwrap();
+        assert_eq!(path, Path::new("a.xlsx"));
+        assert_eq!(slept, vec![10, 20, 40]);
+
+        let policy = LockedFilePolicy::Retry {
+            attempts: 2,
+            initial_delay: Duration::ZERO,
+        };
+        assert!(matches!(
+            open_with_policy(Path::new("a.xlsx"), &policy, locked_until(10), |_| {}),
+            Err(RustypyxlError::FileLocked(_))
+        ));
+    }
+
+    #[test]
+    fn test_save_alternate_picks_next_free_name() {
+        let dir = std::env::temp_dir().join(format!("rustypyxl-lock-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("report.xlsx");
+        std::fs::write(dir.join("report (1).xlsx"), b"earlier").unwrap();
+
+        let (_, path) = open_with_policy(
+            &target,
+            &LockedFilePolicy::SaveAlternate,
+            locked_until(1),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(path, dir.join("report (2).xlsx"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_other_errors_pass_through() {
+        let err = open_with_policy(
+            Path::new("a.xlsx"),
+            &LockedFilePolicy::SaveAlternate,
+            |_: &Path| -> Result<()> { Err(io::Error::other("disk full").into()) },
+            |_| {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, RustypyxlError::Io(_)));
+    }
+
+    #[test]
+    fn test_alternate_path() {
+        assert_eq!(
+            alternate_path(Path::new("out/data.xlsx"), 3),
+            Path::new("out/data (3).xlsx")
+        );
+        assert_eq!(alternate_path(Path::new("data"), 1), Path::new("data (1)"));
+    }
+}
@@ -55,7 +55,7 @@ impl FormulaValue {
     }
 
     /// Coerce to text (numbers use a plain representation; blanks are empty).
-    fn to_text(&self) -> Result<String, FormulaValue> {
+    pub(crate) fn to_text(&self) -> Result<String, FormulaValue> {
         match self {
             FormulaValue::Text(t) => Ok(t.clone()),
             FormulaValue::Number(n) => Ok(format_number_plain(*n)),
@@ -621,7 +621,7 @@ fn eval_binary(op: &str, l: &Expr, r: &Expr, resolver: &mut dyn CellResolver) ->
 
 /// Compare two values with Excel semantics: numbers numerically, text
 /// case-insensitively, with numbers sorting before text.
-fn compare(op: &str, l: &FormulaValue, r: &FormulaValue) -> FormulaValue {
+pub(crate) fn compare(op: &str, l: &FormulaValue, r: &FormulaValue) -> FormulaValue {
     use std::cmp::Ordering;
     let ord = match (l, r) {
         (FormulaValue::Number(a), FormulaValue::Number(b)) => {
@@ -1064,7 +1064,7 @@ fn countif(args: &[Expr], resolver: &mut dyn CellResolver) -> FormulaValue {
 /// Match a value against a SUMIF/COUNTIF criterion: a comparison operator
 /// prefix (>, <, >=, <=, <>, =) followed by a number, or an exact
 /// number/text match.
-fn criteria_matches(value: &FormulaValue, criteria: &str) -> bool {
+pub(crate) fn criteria_matches(value: &FormulaValue, criteria: &str) -> bool {
     let criteria = criteria.trim();
     let (op, rest) = if let Some(r) = criteria.strip_prefix(">=") {
         (">=", r)
@@ -0,0 +1,650 @@
+//! Localized formula input: convert a formula typed in a non-English Excel
+//! (`SUMME(A1;A2)`, `SI(A1>0,5;"oui";"non")`) to the en-US form the file
+//! format stores (`SUM(A1,A2)`, `IF(A1>0.5,"oui","non")`).
+//!
+//! Function names and the `TRUE`/`FALSE` constants are looked up in the
+//! locale's table (case-insensitively; names the table doesn't know pass
+//! through unchanged), the argument separator becomes `,` and the decimal
+//! comma becomes `.`. Inside array constants a `.` or `\` column separator
+//! becomes `,`, and the separator between the parts of a structured
+//! reference (`Tabelle1[[#Daten];[Betrag]]`) becomes `,` too. String
+//! literals, quoted sheet names and the names inside brackets are copied
+//! untouched.
+
+use std::collections::HashMap;
+
+/// How formulas are written in a particular Excel display language.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormulaLocale {
+    /// Locale code this was built from (`"de"`, `"fr"`, ...).
+    pub code: String,
+    /// Separator between function arguments.
+    pub list_separator: char,
+    /// Decimal separator in number literals.
+    pub decimal_separator: char,
+    /// Localized function name (upper case) to English name.
+    functions: HashMap<String, String>,
+    /// Localized `TRUE`/`FALSE` (upper case) to English.
+    constants: HashMap<String, String>,
+}
+
+const GERMAN: &[(&str, &str)] = &[
+    ("SUMME", "SUM"),
+    ("MITTELWERT", "AVERAGE"),
+    ("ANZAHL", "COUNT"),
+    ("ANZAHL2", "COUNTA"),
+    ("ANZAHLLEEREZELLEN", "COUNTBLANK"),
+    ("WENN", "IF"),
+    ("WENNFEHLER", "IFERROR"),
+    ("WENNS", "IFS"),
+    ("UND", "AND"),
+    ("ODER", "OR"),
+    ("NICHT", "NOT"),
+    ("SVERWEIS", "VLOOKUP"),
+    ("WVERWEIS", "HLOOKUP"),
+    ("XVERWEIS", "XLOOKUP"),
+    ("VERGLEICH", "MATCH"),
+    ("ZÄHLENWENN", "COUNTIF"),
+    ("ZÄHLENWENNS", "COUNTIFS"),
+    ("SUMMEWENN", "SUMIF"),
+    ("SUMMEWENNS", "SUMIFS"),
+    ("MITTELWERTWENN", "AVERAGEIF"),
+    ("RUNDEN", "ROUND"),
+    ("AUFRUNDEN", "ROUNDUP"),
+    ("ABRUNDEN", "ROUNDDOWN"),
+    ("GANZZAHL", "INT"),
+    ("KÜRZEN", "TRUNC"),
+    ("REST", "MOD"),
+    ("POTENZ", "POWER"),
+    ("WURZEL", "SQRT"),
+    ("HEUTE", "TODAY"),
+    ("JETZT", "NOW"),
+    ("DATUM", "DATE"),
+    ("JAHR", "YEAR"),
+    ("MONAT", "MONTH"),
+    ("TAG", "DAY"),
+    ("WOCHENTAG", "WEEKDAY"),
+    ("NETTOARBEITSTAGE", "NETWORKDAYS"),
+    ("MONATSENDE", "EOMONTH"),
+    ("EDATUM", "EDATE"),
+    ("VERKETTEN", "CONCATENATE"),
+    ("TEXTVERKETTEN", "TEXTJOIN"),
+    ("LINKS", "LEFT"),
+    ("RECHTS", "RIGHT"),
+    ("TEIL", "MID"),
+    ("LÄNGE", "LEN"),
+    ("GROSS", "UPPER"),
+    ("KLEIN", "LOWER"),
+    ("GLÄTTEN", "TRIM"),
+    ("WECHSELN", "SUBSTITUTE"),
+    ("FINDEN", "FIND"),
+    ("SUCHEN", "SEARCH"),
+    ("WERT", "VALUE"),
+    ("ISTLEER", "ISBLANK"),
+    ("ISTFEHLER", "ISERROR"),
+    ("ISTZAHL", "ISNUMBER"),
+    ("ISTTEXT", "ISTEXT"),
+    ("INDIREKT", "INDIRECT"),
+    ("BEREICH.VERSCHIEBEN", "OFFSET"),
+    ("ZEILE", "ROW"),
+    ("SPALTE", "COLUMN"),
+    ("ZEILEN", "ROWS"),
+    ("SPALTEN", "COLUMNS"),
+    ("PRODUKT", "PRODUCT"),
+    ("SUMMENPRODUKT", "SUMPRODUCT"),
+    ("TEILERGEBNIS", "SUBTOTAL"),
+    ("ZUFALLSZAHL", "RAND"),
+    ("EINDEUTIG", "UNIQUE"),
+    ("SORTIEREN", "SORT"),
+];
+
+const FRENCH: &[(&str, &str)] = &[
+    ("SOMME", "SUM"),
+    ("MOYENNE", "AVERAGE"),
+    ("NB", "COUNT"),
+    ("NBVAL", "COUNTA"),
+    ("NB.VIDE", "COUNTBLANK"),
+    ("SI", "IF"),
+    ("SIERREUR", "IFERROR"),
+    ("SI.CONDITIONS", "IFS"),
+    ("ET", "AND"),
+    ("OU", "OR"),
+    ("NON", "NOT"),
+    ("RECHERCHEV", "VLOOKUP"),
+    ("RECHERCHEH", "HLOOKUP"),
+    ("RECHERCHEX", "XLOOKUP"),
+    ("EQUIV", "MATCH"),
+    ("NB.SI", "COUNTIF"),
+    ("NB.SI.ENS", "COUNTIFS"),
+    ("SOMME.SI", "SUMIF"),
+    ("SOMME.SI.ENS", "SUMIFS"),
+    ("MOYENNE.SI", "AVERAGEIF"),
+    ("ARRONDI", "ROUND"),
+    ("ARRONDI.SUP", "ROUNDUP"),
+    ("ARRONDI.INF", "ROUNDDOWN"),
+    ("ENT", "INT"),
+    ("TRONQUE", "TRUNC"),
+    ("PUISSANCE", "POWER"),
+    ("RACINE", "SQRT"),
+    ("AUJOURDHUI", "TODAY"),
+    ("MAINTENANT", "NOW"),
+    ("ANNEE", "YEAR"),
+    ("MOIS", "MONTH"),
+    ("JOUR", "DAY"),
+    ("JOURSEM", "WEEKDAY"),
+    ("NB.JOURS.OUVRES", "NETWORKDAYS"),
+    ("FIN.MOIS", "EOMONTH"),
+    ("MOIS.DECALER", "EDATE"),
+    ("CONCATENER", "CONCATENATE"),
+    ("JOINDRE.TEXTE", "TEXTJOIN"),
+    ("GAUCHE", "LEFT"),
+    ("DROITE", "RIGHT"),
+    ("STXT", "MID"),
+    ("NBCAR", "LEN"),
+    ("MAJUSCULE", "UPPER"),
+    ("MINUSCULE", "LOWER"),
+    ("SUPPRESPACE", "TRIM"),
+    ("SUBSTITUE", "SUBSTITUTE"),
+    ("TROUVE", "FIND"),
+    ("CHERCHE", "SEARCH"),
+    ("TEXTE", "TEXT"),
+    ("CNUM", "VALUE"),
+    ("ESTVIDE", "ISBLANK"),
+    ("ESTERREUR", "ISERROR"),
+    ("ESTNUM", "ISNUMBER"),
+    ("ESTTEXTE", "ISTEXT"),
+    ("DECALER", "OFFSET"),
+    ("LIGNE", "ROW"),
+    ("COLONNE", "COLUMN"),
+    ("LIGNES", "ROWS"),
+    ("COLONNES", "COLUMNS"),
+    ("PRODUIT", "PRODUCT"),
+    ("SOMMEPROD", "SUMPRODUCT"),
+    ("SOUS.TOTAL", "SUBTOTAL"),
+    ("ALEA", "RAND"),
+    ("MEDIANE", "MEDIAN"),
+    ("FILTRE", "FILTER"),
+    ("TRIER", "SORT"),
+];
+
+const SPANISH: &[(&str, &str)] = &[
+    ("SUMA", "SUM"),
+    ("PROMEDIO", "AVERAGE"),
+    ("CONTAR", "COUNT"),
+    ("CONTARA", "COUNTA"),
+    ("CONTAR.BLANCO", "COUNTBLANK"),
+    ("SI", "IF"),
+    ("SI.ERROR", "IFERROR"),
+    ("SI.CONJUNTO", "IFS"),
+    ("Y", "AND"),
+    ("O", "OR"),
+    ("NO", "NOT"),
+    ("BUSCARV", "VLOOKUP"),
+    ("BUSCARH", "HLOOKUP"),
+    ("BUSCARX", "XLOOKUP"),
+    ("INDICE", "INDEX"),
+    ("COINCIDIR", "MATCH"),
+    ("CONTAR.SI", "COUNTIF"),
+    ("CONTAR.SI.CONJUNTO", "COUNTIFS"),
+    ("SUMAR.SI", "SUMIF"),
+    ("SUMAR.SI.CONJUNTO", "SUMIFS"),
+    ("PROMEDIO.SI", "AVERAGEIF"),
+    ("REDONDEAR", "ROUND"),
+    ("REDONDEAR.MAS", "ROUNDUP"),
+    ("REDONDEAR.MENOS", "ROUNDDOWN"),
+    ("ENTERO", "INT"),
+    ("TRUNCAR", "TRUNC"),
+    ("RESIDUO", "MOD"),
+    ("POTENCIA", "POWER"),
+    ("RAIZ", "SQRT"),
+    ("HOY", "TODAY"),
+    ("AHORA", "NOW"),
+    ("FECHA", "DATE"),
+    ("AÑO", "YEAR"),
+    ("MES", "MONTH"),
+    ("DIA", "DAY"),
+    ("DIASEM", "WEEKDAY"),
+    ("DIAS.LAB", "NETWORKDAYS"),
+    ("FIN.MES", "EOMONTH"),
+    ("FECHA.MES", "EDATE"),
+    ("CONCATENAR", "CONCATENATE"),
+    ("UNIRCADENAS", "TEXTJOIN"),
+    ("IZQUIERDA", "LEFT"),
+    ("DERECHA", "RIGHT"),
+    ("EXTRAE", "MID"),
+    ("LARGO", "LEN"),
+    ("MAYUSC", "UPPER"),
+    ("MINUSC", "LOWER"),
+    ("ESPACIOS", "TRIM"),
+    ("SUSTITUIR", "SUBSTITUTE"),
+    ("ENCONTRAR", "FIND"),
+    ("HALLAR", "SEARCH"),
+    ("TEXTO", "TEXT"),
+    ("VALOR", "VALUE"),
+    ("ESBLANCO", "ISBLANK"),
+    ("ESERROR", "ISERROR"),
+    ("ESNUMERO", "ISNUMBER"),
+    ("ESTEXTO", "ISTEXT"),
+    ("INDIRECTO", "INDIRECT"),
+    ("DESREF", "OFFSET"),
+    ("FILA", "ROW"),
+    ("COLUMNA", "COLUMN"),
+    ("FILAS", "ROWS"),
+    ("COLUMNAS", "COLUMNS"),
+    ("PRODUCTO", "PRODUCT"),
+    ("SUMAPRODUCTO", "SUMPRODUCT"),
+    ("SUBTOTALES", "SUBTOTAL"),
+    ("ALEATORIO", "RAND"),
+    ("MEDIANA", "MEDIAN"),
+    ("UNICOS", "UNIQUE"),
+    ("FILTRAR", "FILTER"),
+    ("ORDENAR", "SORT"),
+];
+
+const ITALIAN: &[(&str, &str)] = &[
+    ("SOMMA", "SUM"),
+    ("MEDIA", "AVERAGE"),
+    ("CONTA.NUMERI", "COUNT"),
+    ("CONTA.VALORI", "COUNTA"),
+    ("CONTA.VUOTE", "COUNTBLANK"),
+    ("SE", "IF"),
+    ("SE.ERRORE", "IFERROR"),
+    ("E", "AND"),
+    ("O", "OR"),
+    ("NON", "NOT"),
+    ("CERCA.VERT", "VLOOKUP"),
+    ("CERCA.ORIZZ", "HLOOKUP"),
+    ("CONFRONTA", "MATCH"),
+    ("CONTA.SE", "COUNTIF"),
+    ("CONTA.PIÙ.SE", "COUNTIFS"),
+    ("SOMMA.SE", "SUMIF"),
+    ("SOMMA.PIÙ.SE", "SUMIFS"),
+    ("ARROTONDA", "ROUND"),
+    ("ARROTONDA.PER.ECC", "ROUNDUP"),
+    ("ARROTONDA.PER.DIF", "ROUNDDOWN"),
+    ("TRONCA", "TRUNC"),
+    ("ASS", "ABS"),
+    ("RESTO", "MOD"),
+    ("POTENZA", "POWER"),
+    ("RADQ", "SQRT"),
+    ("OGGI", "TODAY"),
+    ("ADESSO", "NOW"),
+    ("DATA", "DATE"),
+    ("ANNO", "YEAR"),
+    ("MESE", "MONTH"),
+    ("GIORNO", "DAY"),
+    ("GIORNO.SETTIMANA", "WEEKDAY"),
+    ("CONCATENA", "CONCATENATE"),
+    ("SINISTRA", "LEFT"),
+    ("DESTRA", "RIGHT"),
+    ("STRINGA.ESTRAI", "MID"),
+    ("LUNGHEZZA", "LEN"),
+    ("MAIUSC", "UPPER"),
+    ("MINUSC", "LOWER"),
+    ("ANNULLA.SPAZI", "TRIM"),
+    ("SOSTITUISCI", "SUBSTITUTE"),
+    ("TROVA", "FIND"),
+    ("RICERCA", "SEARCH"),
+    ("TESTO", "TEXT"),
+    ("VALORE", "VALUE"),
+    ("VAL.VUOTO", "ISBLANK"),
+    ("VAL.ERRORE", "ISERROR"),
+    ("VAL.NUMERO", "ISNUMBER"),
+    ("VAL.TESTO", "ISTEXT"),
+    ("INDIRETTO", "INDIRECT"),
+    ("SCARTO", "OFFSET"),
+    ("RIF.RIGA", "ROW"),
+    ("RIF.COLONNA", "COLUMN"),
+    ("RIGHE", "ROWS"),
+    ("COLONNE", "COLUMNS"),
+    ("PRODOTTO", "PRODUCT"),
+    ("MATR.SOMMA.PRODOTTO", "SUMPRODUCT"),
+    ("SUBTOTALE", "SUBTOTAL"),
+    ("CASUALE", "RAND"),
+    ("MEDIANA", "MEDIAN"),
+];
+
+const DUTCH: &[(&str, &str)] = &[
+    ("SOM", "SUM"),
+    ("GEMIDDELDE", "AVERAGE"),
+    ("AANTAL", "COUNT"),
+    ("AANTALARG", "COUNTA"),
+    ("ALS", "IF"),
+    ("ALS.FOUT", "IFERROR"),
+    ("EN", "AND"),
+    ("OF", "OR"),
+    ("NIET", "NOT"),
+    ("VERT.ZOEKEN", "VLOOKUP"),
+    ("HORIZ.ZOEKEN", "HLOOKUP"),
+    ("VERGELIJKEN", "MATCH"),
+    ("AANTAL.ALS", "COUNTIF"),
+    ("AANTALLEN.ALS", "COUNTIFS"),
+    ("SOM.ALS", "SUMIF"),
+    ("SOMMEN.ALS", "SUMIFS"),
+    ("AFRONDEN", "ROUND"),
+    ("AFRONDEN.NAAR.BOVEN", "ROUNDUP"),
+    ("AFRONDEN.NAAR.BENEDEN", "ROUNDDOWN"),
+    ("INTEGER", "INT"),
+    ("GEHEEL", "TRUNC"),
+    ("REST", "MOD"),
+    ("MACHT", "POWER"),
+    ("WORTEL", "SQRT"),
+    ("VANDAAG", "TODAY"),
+    ("NU", "NOW"),
+    ("DATUM", "DATE"),
+    ("JAAR", "YEAR"),
+    ("MAAND", "MONTH"),
+    ("DAG", "DAY"),
+    ("WEEKDAG", "WEEKDAY"),
+    ("TEKST.SAMENVOEGEN", "CONCATENATE"),
+    ("LINKS", "LEFT"),
+    ("RECHTS", "RIGHT"),
+    ("DEEL", "MID"),
+    ("LENGTE", "LEN"),
+    ("HOOFDLETTERS", "UPPER"),
+    ("KLEINE.LETTERS", "LOWER"),
+    ("SPATIES.WISSEN", "TRIM"),
+    ("SUBSTITUEREN", "SUBSTITUTE"),
+    ("VIND.ALLES", "FIND"),
+    ("VIND.SPEC", "SEARCH"),
+    ("TEKST", "TEXT"),
+    ("WAARDE", "VALUE"),
+    ("ISLEEG", "ISBLANK"),
+    ("ISFOUT", "ISERROR"),
+    ("ISGETAL", "ISNUMBER"),
+    ("ISTEKST", "ISTEXT"),
+    ("VERSCHUIVING", "OFFSET"),
+    ("RIJ", "ROW"),
+    ("KOLOM", "COLUMN"),
+    ("RIJEN", "ROWS"),
+    ("KOLOMMEN", "COLUMNS"),
+    ("SOMPRODUCT", "SUMPRODUCT"),
+    ("SUBTOTAAL", "SUBTOTAL"),
+    ("ASELECT", "RAND"),
+    ("MEDIAAN", "MEDIAN"),
+];
+
+impl FormulaLocale {
+    /// A locale with no translated names, using `list_separator` between
+    /// arguments and `decimal_separator` in numbers. Add names with
+    /// [`FormulaLocale::with_function`].
+    pub fn new(code: &str, list_separator: char, decimal_separator: char) -> Self {
+        FormulaLocale {
+            code: code.to_string(),
+            list_separator,
+            decimal_separator,
+            functions: HashMap::new(),
+            constants: HashMap::new(),
+        }
+    }
+
+    /// The built-in locale for a language code: `de`, `fr`, `es`, `it` or
+    /// `nl`. A region suffix (`de-AT`, `fr_CH`) is ignored. `None` for
+    /// languages without a table.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let (functions, true_name, false_name) = match language.as_str() {
+            "de" => (GERMAN, "WAHR", "FALSCH"),
+            "fr" => (FRENCH, "VRAI", "FAUX"),
+            "es" => (SPANISH, "VERDADERO", "FALSO"),
+            "it" => (ITALIAN, "VERO", "FALSO"),
+            "nl" => (DUTCH, "WAAR", "ONWAAR"),
+            _ => return None,
+        };
+        let mut locale = FormulaLocale::new(&language, ';', ',')
+            .with_constant(true_name, "TRUE")
+            .with_constant(false_name, "FALSE");
+        for &(local, english) in functions {
+            locale = locale.with_function(local, english);
+        }
+        Some(locale)
+    }
+
+    /// Translate the function `localized` to `english`.
+    pub fn with_function(mut self, localized: &str, english: &str) -> Self {
+        self.functions
+            .insert(localized.to_uppercase(), english.to_uppercase());
+        self
+    }
+
+    /// Translate the constant `localized` (a boolean name) to `english`.
+    pub fn with_constant(mut self, localized: &str, english: &str) -> Self {
+        self.constants
+            .insert(localized.to_uppercase(), english.to_uppercase());
+        self
+    }
+
+    /// Convert `formula` (with or without its leading `=`) to en-US form.
+    pub fn to_canonical(&self, formula: &str) -> String {
+        let chars: Vec<char> = formula.chars().collect();
+        let mut out = String::with_capacity(formula.len());
+        let mut in_array = false;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            match c {
+                '"' | '\'' => i = copy_quoted(&chars, i, &mut out),
+                '[' => i = copy_bracketed(&chars, i, self.list_separator, &mut out),
+                '{' => {
+                    in_array = true;
+                    out.push(c);
+                    i += 1;
+                }
+                '}' => {
+                    in_array = false;
+                    out.push(c);
+                    i += 1;
+                }
+                c if c.is_ascii_digit() => i = self.copy_number(&chars, i, &mut out),
+                c if c.is_alphabetic() || c == '_' || c == '$' => {
+                    let end = name_end(&chars, i, in_array);
+                    let token: String = chars[i..end].iter().collect();
+                    let upper = token.to_uppercase();
+                    let next = chars.get(end).copied();
+                    let after_sheet = i > 0 && chars[i - 1] == '!';
+                    let replacement = match next {
+                        Some('(') => self.functions.get(&upper),
+                        Some('!') | Some('[') => None,
+                        _ if after_sheet => None,
+                        _ => self.constants.get(&upper),
+                    };
+                    out.push_str(replacement.map_or(token.as_str(), String::as_str));
+                    i = end;
+                }
+                c if c == self.list_separator => {
+                    // Inside an array constant the list separator is the
+                    // row separator, which en-US writes as `;`.
+                    out.push(if in_array { ';' } else { ',' });
+                    i += 1;
+                }
+                '.' | '\\' if in_array && self.decimal_separator != '.' => {
+                    out.push(',');
+                    i += 1;
+                }
+                _ => {
+                    out.push(c);
+                    i += 1;
+                }
+            }
+        }
+        out
+    }
+
+    /// Copy a number literal starting at `start`, rewriting its decimal
+    /// separator. Returns the index just past it.
+    fn copy_number(&self, chars: &[char], start: usize, out: &mut String) -> usize {
+        let mut i = start;
+        while i < chars.len() && chars[i].is_ascii_digit() {
+            out.push(chars[i]);
+            i += 1;
+        }
+        let decimal = self.decimal_separator;
+        if chars.get(i) == Some(&decimal) && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+            out.push('.');
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+        // An exponent or a trailing reference part (`1E5`, row `1:3`) copies as-is.
+        while i < chars.len() && chars[i].is_ascii_alphanumeric() {
+            out.push(chars[i]);
+            i += 1;
+        }
+        i
+    }
+}
+
+/// End of a name token: letters, digits, `_`, `$` and (outside array
+/// constants) the `.` many localized function names contain.
+fn name_end(chars: &[char], start: usize, in_array: bool) -> usize {
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_alphanumeric() || c == '_' || c == '$' || (c == '.' && !in_array) {
+            i += 1;
+        } else {
+            break;
+        }
+    }
+    i
+}
+
+/// Copy a `"string"` or `'sheet name'` literal, with its doubled-quote
+/// escapes, starting at the opening quote.
+fn copy_quoted(chars: &[char], start: usize, out: &mut String) -> usize {
+    let quote = chars[start];
+    out.push(quote);
+    let mut i = start + 1;
+    while i < chars.len() {
+        out.push(chars[i]);
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                out.push(quote);
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Copy a bracketed part (`Table1[[#Data],[Col]]`, `[1]Sheet1`) including
+/// nested brackets, turning `separator` between the parts into `,`.
+fn copy_bracketed(chars: &[char], start: usize, separator: char, out: &mut String) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        i += 1;
+        if c == separator && depth == 1 {
+            out.push(',');
+            continue;
+        }
+        out.push(c);
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn de() -> FormulaLocale {
+        FormulaLocale::from_code("de-DE").unwrap()
+    }
+
+    #[test]
+    fn german_functions_and_separators() {
+        assert_eq!(de().to_canonical("SUMME(A1;A2)"), "SUM(A1,A2)");
+        assert_eq!(
+            de().to_canonical("=WENN(A1>0,5;SVERWEIS(B1;Daten!A:B;2;FALSCH);\"n/a\")"),
+            "=IF(A1>0.5,VLOOKUP(B1,Daten!A:B,2,FALSE),\"n/a\")"
+        );
+        assert_eq!(
+            de().to_canonical("zählenwenn(A:A;\">1\")"),
+            "COUNTIF(A:A,\">1\")"
+        );
+    }
+
+    #[test]
+    fn literals_and_sheet_names_are_untouched() {
+        assert_eq!(
+            de().to_canonical("VERKETTEN(\"SUMME(1;2)\";'Summe; Blatt'!A1)"),
+            "CONCATENATE(\"SUMME(1;2)\",'Summe; Blatt'!A1)"
+        );
+        assert_eq!(
+            de().to_canonical("SUMME(Tabelle1[[#Daten];[Betrag]])"),
+            "SUM(Tabelle1[[#Daten],[Betrag]])"
+        );
+        // A sheet or defined name that happens to match a local word stays.
+        assert_eq!(
+            de().to_canonical("WAHR!A1+Blatt!WAHR"),
+            "WAHR!A1+Blatt!WAHR"
+        );
+    }
+
+    #[test]
+    fn array_constants() {
+        assert_eq!(de().to_canonical("SUMME({1,5.2;3\\4})"), "SUM({1.5,2;3,4})");
+        assert_eq!(de().to_canonical("{WAHR.FALSCH}"), "{TRUE,FALSE}");
+    }
+
+    #[test]
+    fn dotted_names_and_unknown_functions() {
+        let fr = FormulaLocale::from_code("fr").unwrap();
+        assert_eq!(
+            fr.to_canonical("NB.SI.ENS(A:A;\"x\";B:B;VRAI)+MAFONCTION(1)"),
+            "COUNTIFS(A:A,\"x\",B:B,TRUE)+MAFONCTION(1)"
+        );
+        assert!(FormulaLocale::from_code("xx").is_none());
+    }
+
+    #[test]
+    fn workbook_converts_formulas_it_stores() {
+        use crate::{CellValue, Workbook};
+
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("S".to_string())).unwrap();
+        wb.set_formula_locale(Some(de()));
+        wb.set_cell_value_in_sheet("S", 1, 1, CellValue::Formula("SUMME(B1;B2)".into()))
+            .unwrap();
+        wb.set_cell_formula(2, 1, "MITTELWERT(B1:B2)".to_string())
+            .unwrap();
+        wb.set_cell_value(3, 1, CellValue::from("SUMME(B1;B2)"))
+            .unwrap();
+
+        let ws = wb.get_sheet_by_name("S").unwrap();
+        let formula = |row| ws.get_cell_value(row, 1).cloned();
+        assert_eq!(formula(1), Some(CellValue::Formula("SUM(B1,B2)".into())));
+        assert_eq!(
+            formula(2),
+            Some(CellValue::Formula("AVERAGE(B1:B2)".into()))
+        );
+        // Plain text is never touched.
+        assert_eq!(formula(3), Some(CellValue::from("SUMME(B1;B2)")));
+    }
+
+    #[test]
+    fn custom_locale() {
+        let locale = FormulaLocale::new("sv", ';', ',').with_function("summa", "sum");
+        assert_eq!(locale.to_canonical("SUMMA(1,5;2)"), "SUM(1.5,2)");
+    }
+}
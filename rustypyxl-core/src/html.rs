@@ -0,0 +1,401 @@
+//! HTML table export.
+//!
+//! [`Worksheet::to_html`] renders a sheet, or a range of it, as one `<table>`
+//! for previews in web pages and emails. Cells show their formatted value;
+//! merges become `rowspan`/`colspan`. With styles on, fonts, solid fills,
+//! borders, alignment, column widths and row heights are written as inline
+//! `style` attributes, since mail clients drop `<style>` blocks. Theme and
+//! indexed colors have no fixed value and are left out.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+use crate::cell::CellValue;
+use crate::error::Result;
+use crate::rich_text::RunFont;
+use crate::style::{BorderStyle, Color, Font};
+use crate::utils::{parse_coordinate, parse_range};
+use crate::worksheet::{CellData, Worksheet};
+use crate::writer::escape_xml;
+
+impl Worksheet {
+    /// Render `range` ("A1:D20"; the used range when `None`) as an HTML
+    /// table. `include_styles` adds inline CSS for the cells' formatting.
+    pub fn to_html(&self, range: Option<&str>, include_styles: bool) -> Result<String> {
+        let Some((min_row, min_col, max_row, max_col)) = self.render_bounds(range)? else {
+            return Ok("<table></table>".to_string());
+        };
+
+        // Top-left cell of each merge -> (rowspan, colspan), clipped to the
+        // range; the cells a merge covers are skipped.
+        let mut spans = HashMap::new();
+        let mut covered = HashSet::new();
+        for (start, end) in &self.merged_cells {
+            let (Ok((r1, c1)), Ok((r2, c2))) = (parse_coordinate(start), parse_coordinate(end))
+            else {
+                continue;
+            };
+            let (r1, c1) = (r1.max(min_row), c1.max(min_col));
+            let (r2, c2) = (r2.min(max_row), c2.min(max_col));
+            if r1 > r2 || c1 > c2 {
+                continue;
+            }
+            spans.insert((r1, c1), (r2 - r1 + 1, c2 - c1 + 1));
+            for row in r1..=r2 {
+                for col in c1..=c2 {
+                    if (row, col) != (r1, c1) {
+                        covered.insert((row, col));
+                    }
+                }
+            }
+        }
+
+        let mut html = String::from("<table");
+        if include_styles {
+            html.push_str(" style=\"border-collapse:collapse\"");
+        }
+        html.push('>');
+        if include_styles && (min_col..=max_col).any(|c| self.column_dimensions.contains_key(&c)) {
+            html.push_str("<colgroup>");
+            for col in min_col..=max_col {
+                match self.column_dimensions.get(&col) {
+                    Some(width) => {
+                        let _ = write!(html, "<col style=\"width:{}px\">", width_px(*width));
+                    }
+                    None => html.push_str("<col>"),
+                }
+            }
+            html.push_str("</colgroup>");
+        }
+
+        for row in min_row..=max_row {
+            html.push_str("<tr");
+            if let (true, Some(height)) = (include_styles, self.row_dimensions.get(&row)) {
+                let _ = write!(html, " style=\"height:{}pt\"", height);
+            }
+            html.push('>');
+            for col in min_col..=max_col {
+                if covered.contains(&(row, col)) {
+                    continue;
+                }
+                let cell = self.get_cell(row, col);
+                html.push_str("<td");
+                if let Some(&(rows, cols)) = spans.get(&(row, col)) {
+                    if rows > 1 {
+                        let _ = write!(html, " rowspan=\"{}\"", rows);
+                    }
+                    if cols > 1 {
+                        let _ = write!(html, " colspan=\"{}\"", cols);
+                    }
+                }
+                if include_styles {
+                    let css = cell.as_deref().map(cell_css).unwrap_or_default();
+                    if !css.is_empty() {
+                        let _ = write!(html, " style=\"{}\"", css);
+                    }
+                }
+                html.push('>');
+                if let Some(cell) = cell {
+                    push_cell_text(&mut html, &cell, include_styles);
+                }
+                html.push_str("</td>");
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+        Ok(html)
+    }
+
+    /// (min_row, min_col, max_row, max_col) of `range`, or of the used
+    /// range when `None`; `None` for an empty sheet with no range given.
+    pub(crate) fn render_bounds(
+        &self,
+        range: Option<&str>,
+    ) -> Result<Option<(u32, u32, u32, u32)>> {
+        Ok(match range {
+            Some(range) => {
+                let range = range.replace('$', "");
+                let ((r1, c1), (r2, c2)) = if range.contains(':') {
+                    parse_range(&range)?
+                } else {
+                    let cell = parse_coordinate(&range)?;
+                    (cell, cell)
+                };
+                Some((r1.min(r2), c1.min(c2), r1.max(r2), c1.max(c2)))
+            }
+            None if self.cell_count() == 0 => None,
+            None => Some(self.dimensions()),
+        })
+    }
+}
+
+/// Column width in characters to pixels, as Excel converts it for Calibri 11.
+fn width_px(width: f64) -> u32 {
+    (width * 7.0 + 5.0).round().max(0.0) as u32
+}
+
+/// The cell's display text; rich text keeps its runs as styled spans.
+fn push_cell_text(html: &mut String, cell: &CellData, include_styles: bool) {
+    if let (true, Some(rich)) = (include_styles, &cell.rich_text) {
+        for run in &rich.runs {
+            let css = run.font.as_ref().map(run_css).unwrap_or_default();
+            if css.is_empty() {
+                push_text(html, &run.text);
+            } else {
+                let _ = write!(html, "<span style=\"{}\">", css);
+                push_text(html, &run.text);
+                html.push_str("</span>");
+            }
+        }
+        return;
+    }
+    push_text(html, &cell.formatted_value());
+}
+
+/// Escaped text with line breaks kept.
+fn push_text(html: &mut String, text: &str) {
+    html.push_str(&escape_xml(text).replace('\n', "<br>"));
+}
+
+fn cell_css(cell: &CellData) -> String {
+    let mut css = String::new();
+    let style = cell.style.as_deref();
+    if let Some(font) = style.and_then(|s| s.font.as_ref()) {
+        push_font_css(&mut css, font);
+    }
+    if let Some(fill) = style.and_then(|s| s.fill.as_ref()) {
+        let solid = fill.pattern_type.as_deref() == Some("solid");
+        if let (true, Some(color)) = (solid, fill.fg_color.as_ref().and_then(css_color)) {
+            let _ = write!(css, "background-color:{};", color);
+        }
+    }
+    if let Some(border) = style.and_then(|s| s.border.as_ref()) {
+        for (side, edge) in [
+            ("top", &border.top),
+            ("right", &border.right),
+            ("bottom", &border.bottom),
+            ("left", &border.left),
+        ] {
+            if let Some(edge) = edge.as_ref().and_then(border_css) {
+                let _ = write!(css, "border-{}:{};", side, edge);
+            }
+        }
+    }
+    let alignment = style.and_then(|s| s.alignment.as_ref());
+    match alignment.and_then(|a| a.horizontal.as_deref()) {
+        Some(h @ ("left" | "center" | "right" | "justify")) => {
+            let _ = write!(css, "text-align:{};", h);
+        }
+        Some("centerContinuous") => css.push_str("text-align:center;"),
+        Some(_) => {}
+        // Excel's general alignment puts numbers on the right.
+        None => {
+            if matches!(cell.value, CellValue::Number(_))
+                || (matches!(cell.value, CellValue::Formula(_))
+                    && matches!(cell.data_type, None | Some("n"))
+                    && cell.cached_formula_value.is_some())
+            {
+                css.push_str("text-align:right;");
+            }
+        }
+    }
+    match alignment.and_then(|a| a.vertical.as_deref()) {
+        Some("top") => css.push_str("vertical-align:top;"),
+        Some("center") => css.push_str("vertical-align:middle;"),
+        Some("bottom") => css.push_str("vertical-align:bottom;"),
+        _ => {}
+    }
+    if alignment.is_some_and(|a| a.wrap_text) {
+        css.push_str("white-space:pre-wrap;");
+    }
+    css
+}
+
+fn push_font_css(css: &mut String, font: &Font) {
+    push_run_css(
+        css,
+        &RunFont {
+            bold: font.bold,
+            italic: font.italic,
+            underline: font.underline.clone(),
+            strike: font.strike,
+            size: font.size,
+            color: font.color.clone(),
+            name: font.name.clone(),
+            vert_align: font.vert_align.clone(),
+        },
+    );
+}
+
+fn run_css(font: &RunFont) -> String {
+    let mut css = String::new();
+    push_run_css(&mut css, font);
+    css
+}
+
+fn push_run_css(css: &mut String, font: &RunFont) {
+    if let Some(name) = &font.name {
+        // Quotes inside a double-quoted attribute would end it.
+        let _ = write!(css, "font-family:'{}';", name.replace(['\'', '"'], ""));
+    }
+    if let Some(size) = font.size {
+        let _ = write!(css, "font-size:{}pt;", size);
+    }
+    if font.bold {
+        css.push_str("font-weight:bold;");
+    }
+    if font.italic {
+        css.push_str("font-style:italic;");
+    }
+    match (font.underline.is_some(), font.strike) {
+        (true, true) => css.push_str("text-decoration:underline line-through;"),
+        (true, false) => css.push_str("text-decoration:underline;"),
+        (false, true) => css.push_str("text-decoration:line-through;"),
+        (false, false) => {}
+    }
+    if let Some(color) = font.color.as_ref().and_then(css_color) {
+        let _ = write!(css, "color:{};", color);
+    }
+    match font.vert_align.as_deref() {
+        Some("superscript") => css.push_str("vertical-align:super;"),
+        Some("subscript") => css.push_str("vertical-align:sub;"),
+        _ => {}
+    }
+}
+
+fn border_css(edge: &BorderStyle) -> Option<String> {
+    let (width, line) = match edge.style.as_str() {
+        "thin" | "hair" => ("1px", "solid"),
+        "medium" => ("2px", "solid"),
+        "thick" => ("3px", "solid"),
+        "double" => ("3px", "double"),
+        "dashed" | "dashDot" | "dashDotDot" => ("1px", "dashed"),
+        "mediumDashed" | "mediumDashDot" | "mediumDashDotDot" | "slantDashDot" => ("2px", "dashed"),
+        "dotted" => ("1px", "dotted"),
+        _ => return None,
+    };
+    let color = edge
+        .color
+        .as_ref()
+        .and_then(css_color)
+        .unwrap_or_else(|| "#000000".to_string());
+    Some(format!("{} {} {}", width, line, color))
+}
+
+/// `#RRGGBB` for an explicit color; the alpha byte Excel stores is dropped.
+fn css_color(color: &Color) -> Option<String> {
+    let argb = color.argb()?;
+    let rgb = &argb[argb.len().saturating_sub(6)..];
+    rgb.bytes()
+        .all(|b| b.is_ascii_hexdigit())
+        .then(|| format!("#{}", rgb.to_ascii_uppercase()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rich_text::{RichText, TextRun};
+    use crate::style::{Alignment, Border, CellStyle, Fill};
+    use std::sync::Arc;
+
+    fn styled(ws: &mut Worksheet, row: u32, col: u32, style: CellStyle) {
+        ws.get_or_create_cell_mut(row, col).style = Some(Arc::new(style));
+    }
+
+    #[test]
+    fn test_values_and_merges() {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_value(1, 1, "Name & <Title>");
+        ws.set_cell_value(2, 1, "a");
+        ws.set_cell_value(2, 2, 3.5);
+        ws.set_cell_value(3, 3, true);
+        ws.merged_cells.push(("A1".to_string(), "C1".to_string()));
+
+        let html = ws.to_html(None, false).unwrap();
+        assert_eq!(
+            html,
+            "<table><tr><td colspan=\"3\">Name &amp; &lt;Title&gt;</td></tr>\
+             <tr><td>a</td><td>3.5</td><td></td></tr>\
+             <tr><td></td><td></td><td>TRUE</td></tr></table>"
+        );
+    }
+
+    #[test]
+    fn test_range_clips_merges_and_applies_number_formats() {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_value(2, 2, 0.25);
+        ws.get_or_create_cell_mut(2, 2).number_format = Some("0.0%".into());
+        ws.merged_cells.push(("A1".to_string(), "B3".to_string()));
+
+        let html = ws.to_html(Some("$B$2:C2"), false).unwrap();
+        assert_eq!(html, "<table><tr><td>25.0%</td><td></td></tr></table>");
+        let html = ws.to_html(Some("B1:B3"), false).unwrap();
+        assert!(html.contains("<td rowspan=\"3\"></td>"));
+    }
+
+    #[test]
+    fn test_styles_become_inline_css() {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_value(1, 1, "Total");
+        styled(
+            &mut ws,
+            1,
+            1,
+            CellStyle::new()
+                .with_font(Font::new().with_bold(true).with_color("FFFF0000"))
+                .with_fill(Fill::solid("#DDEEFF"))
+                .with_border(Border::all(BorderStyle::thin()))
+                .with_alignment(Alignment::new().with_horizontal("center")),
+        );
+        ws.set_cell_value(1, 2, 10.0);
+        ws.column_dimensions.insert(1, 20.0);
+        ws.row_dimensions.insert(1, 30.0);
+
+        let html = ws.to_html(None, true).unwrap();
+        assert!(html.starts_with(
+            "<table style=\"border-collapse:collapse\"><colgroup>\
+             <col style=\"width:145px\"><col></colgroup><tr style=\"height:30pt\">"
+        ));
+        assert!(html.contains("font-weight:bold;color:#FF0000;"));
+        assert!(html.contains("background-color:#DDEEFF;"));
+        assert!(html.contains("border-top:1px solid #000000;"));
+        assert!(html.contains("text-align:center;"));
+        assert!(html.contains("<td style=\"text-align:right;\">10</td>"));
+        assert!(!ws.to_html(None, false).unwrap().contains("style"));
+    }
+
+    #[test]
+    fn test_rich_text_runs_become_spans() {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_rich_text(
+            1,
+            1,
+            RichText::new(vec![
+                TextRun::plain("Hi "),
+                TextRun::formatted(
+                    "there",
+                    RunFont {
+                        italic: true,
+                        ..Default::default()
+                    },
+                ),
+            ]),
+        );
+        assert_eq!(
+            ws.to_html(None, true).unwrap(),
+            "<table style=\"border-collapse:collapse\"><tr><td>Hi \
+             <span style=\"font-style:italic;\">there</span></td></tr></table>"
+        );
+        assert!(ws
+            .to_html(None, false)
+            .unwrap()
+            .contains("<td>Hi there</td>"));
+    }
+
+    #[test]
+    fn test_empty_sheet_and_bad_range() {
+        let ws = Worksheet::new("S".to_string());
+        assert_eq!(ws.to_html(None, true).unwrap(), "<table></table>");
+        assert!(ws.to_html(Some("not a range"), true).is_err());
+    }
+}
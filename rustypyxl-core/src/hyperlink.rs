@@ -0,0 +1,107 @@
+//! Cell hyperlinks.
+//!
+//! A link either points outside the workbook (a URL, `mailto:` address or
+//! file path), in which case the sheet's `<hyperlink>` element refers to an
+//! external relationship in the sheet's .rels part by `r:id`, or to a place
+//! inside it ("Sheet2!A1", a defined name), written as the `location`
+//! attribute. Excel only follows external links that have the relationship.
+
+/// A hyperlink on a cell.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hyperlink {
+    /// The URL for an external link; the location ("Sheet2!A1", without a
+    /// leading '#') for an internal one.
+    pub target: String,
+    /// Whether `target` is outside the workbook.
+    pub is_external: bool,
+    /// Text Excel shows when hovering over the cell.
+    pub tooltip: Option<String>,
+    /// The `display` attribute: the text the link was created with. Excel
+    /// shows the cell's value, so this is informational.
+    pub display: Option<String>,
+}
+
+impl Hyperlink {
+    /// A link from its address: "#Sheet2!A1" is internal, anything else
+    /// (a URL, "mailto:..." or a file path) external.
+    pub fn new<S: Into<String>>(address: S) -> Self {
+        let address = address.into();
+        match address.strip_prefix('#') {
+            Some(location) => Hyperlink::internal(location),
+            None => Hyperlink::external(address),
+        }
+    }
+
+    /// A link to a URL or file outside the workbook.
+    pub fn external<S: Into<String>>(url: S) -> Self {
+        Hyperlink {
+            target: url.into(),
+            is_external: true,
+            ..Default::default()
+        }
+    }
+
+    /// A link to a location in the workbook ("Sheet2!A1" or a defined name);
+    /// a leading '#' is dropped.
+    pub fn internal<S: Into<String>>(location: S) -> Self {
+        let location = location.into();
+        Hyperlink {
+            target: location
+                .strip_prefix('#')
+                .map(str::to_string)
+                .unwrap_or(location),
+            is_external: false,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_tooltip<S: Into<String>>(mut self, tooltip: S) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    pub fn with_display<S: Into<String>>(mut self, display: S) -> Self {
+        self.display = Some(display.into());
+        self
+    }
+
+    /// The link as one string: the URL, or "#" and the location for an
+    /// internal link. [`Hyperlink::new`] parses it back.
+    pub fn address(&self) -> String {
+        if self.is_external {
+            self.target.clone()
+        } else {
+            format!("#{}", self.target)
+        }
+    }
+}
+
+impl From<String> for Hyperlink {
+    fn from(address: String) -> Self {
+        Hyperlink::new(address)
+    }
+}
+
+impl From<&str> for Hyperlink {
+    fn from(address: &str) -> Self {
+        Hyperlink::new(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_round_trip() {
+        let external = Hyperlink::from("https://example.com/?q=1");
+        assert!(external.is_external);
+        assert_eq!(external.address(), "https://example.com/?q=1");
+
+        let internal = Hyperlink::from("#'My Sheet'!B2");
+        assert!(!internal.is_external);
+        assert_eq!(internal.target, "'My Sheet'!B2");
+        assert_eq!(internal.address(), "#'My Sheet'!B2");
+        assert_eq!(Hyperlink::internal("Sheet2!A1").address(), "#Sheet2!A1");
+    }
+}
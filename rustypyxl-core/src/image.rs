@@ -5,8 +5,8 @@
 //! **Status: write path + round-trip.** An [`Image`] added to a worksheet is embedded on
 //! save (media part, drawing anchor, and relationships), and images already present in a
 //! loaded file are preserved when it is saved again. PNG, JPEG, GIF, BMP, and TIFF are
-//! detected from magic bytes or extension. Charts and images on one sheet share a single
-//! drawing part.
+//! detected from magic bytes or extension, and an image is shown at its own pixel size
+//! unless a size is given. Charts and images on one sheet share a single drawing part.
 
 use std::path::PathBuf;
 
@@ -107,6 +107,41 @@ impl ImageFormat {
     }
 }
 
+/// The pixel dimensions stored in a PNG, JPEG, GIF or BMP header, or None
+/// for other formats and truncated data.
+pub fn pixel_size(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32);
+    let be32 = |at: usize| Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?));
+    let le32 = |at: usize| Some(i32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?));
+
+    let size = match ImageFormat::from_bytes(data)? {
+        // IHDR is always the first chunk.
+        ImageFormat::Png => (be32(16)?, be32(20)?),
+        ImageFormat::Gif => (le16(6)?, le16(8)?),
+        // Height is negative for top-down bitmaps.
+        ImageFormat::Bmp => (le32(18)?.unsigned_abs(), le32(22)?.unsigned_abs()),
+        ImageFormat::Jpeg => {
+            // Walk the segments to the first start-of-frame marker.
+            let mut at = 2;
+            loop {
+                if *data.get(at)? != 0xFF {
+                    return None;
+                }
+                let marker = *data.get(at + 1)?;
+                let is_frame =
+                    matches!(marker, 0xC0..=0xCF) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+                if is_frame {
+                    break (be16(at + 7)?, be16(at + 5)?);
+                }
+                at += 2 + be16(at + 2)? as usize;
+            }
+        }
+        _ => return None,
+    };
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
+
 /// Anchor type for positioning images.
 #[derive(Clone, Debug, PartialEq)]
 pub enum ImageAnchorType {
@@ -200,13 +235,14 @@ impl Image {
     /// Create a new image from bytes.
     pub fn from_bytes(data: Vec<u8>, anchor: ImageAnchor) -> Option<Self> {
         let format = ImageFormat::from_bytes(&data)?;
+        let (width, height) = natural_size_emu(&data);
         Some(Image {
             data,
             format,
             source_path: None,
             anchor,
-            width: 914400,  // 1 inch default
-            height: 914400, // 1 inch default
+            width,
+            height,
             alt_text: None,
             description: None,
             name: None,
@@ -226,13 +262,14 @@ impl Image {
                 std::io::Error::new(std::io::ErrorKind::InvalidData, "Unknown image format")
             })?;
 
+        let (width, height) = natural_size_emu(&data);
         Ok(Image {
             data,
             format,
             source_path: Some(path.to_path_buf()),
             anchor,
-            width: 914400,
-            height: 914400,
+            width,
+            height,
             alt_text: None,
             description: None,
             name: None,
@@ -267,6 +304,15 @@ impl Image {
     }
 }
 
+/// The size an image is shown at by default: its own pixel size at 96 DPI,
+/// as openpyxl does, or one inch square when the header gives none.
+fn natural_size_emu(data: &[u8]) -> (u32, u32) {
+    match pixel_size(data) {
+        Some((w, h)) => (w.saturating_mul(9525), h.saturating_mul(9525)),
+        None => (914400, 914400),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,4 +458,37 @@ mod coverage_tests {
         assert!(img.source_path.is_some());
         let _ = std::fs::remove_file(&path);
     }
+
+    #[test]
+    fn pixel_size_reads_headers() {
+        let mut png = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 13];
+        png.extend(b"IHDR");
+        png.extend(640u32.to_be_bytes());
+        png.extend(480u32.to_be_bytes());
+        assert_eq!(pixel_size(&png), Some((640, 480)));
+        let img = Image::from_bytes(png, ImageAnchor::one_cell("A1")).unwrap();
+        assert_eq!((img.width, img.height), (640 * 9525, 480 * 9525));
+
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend([0x20, 0x00, 0x10, 0x00]);
+        assert_eq!(pixel_size(&gif), Some((32, 16)));
+
+        let mut bmp = b"BM".to_vec();
+        bmp.resize(18, 0);
+        bmp.extend(100i32.to_le_bytes());
+        bmp.extend((-50i32).to_le_bytes());
+        assert_eq!(pixel_size(&bmp), Some((100, 50)));
+
+        // SOI, an APP0 segment, then a baseline SOF0 frame.
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00,
+            0x78, 0x00, 0xA0,
+        ];
+        assert_eq!(pixel_size(&jpeg), Some((160, 120)));
+
+        assert_eq!(
+            pixel_size(&[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]),
+            None
+        );
+    }
 }
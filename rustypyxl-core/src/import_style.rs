@@ -0,0 +1,288 @@
+//! Presentation options for imported data.
+//!
+//! The importers write raw values; [`ImportStyleOptions`] lets the same call
+//! also style the header row, freeze it, put an autofilter on it, and size the
+//! columns, so an import lands as a readable sheet without follow-up calls.
+
+use crate::autofilter::AutoFilter;
+use crate::cell::CellValue;
+use crate::style::{Border, BorderStyle, CellStyle, Fill, Font};
+use crate::utils::coordinate_from_row_col;
+use crate::worksheet::Worksheet;
+use std::sync::Arc;
+
+/// How an import should style the range it wrote. Every field defaults to
+/// "leave as is"; [`ImportStyleOptions::presentable`] turns everything on.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportStyleOptions {
+    /// Font for the header cells.
+    pub header_font: Option<Font>,
+    /// Fill for the header cells.
+    pub header_fill: Option<Fill>,
+    /// Border for the header cells.
+    pub header_border: Option<Border>,
+    /// Freeze the rows down to and including the header.
+    pub freeze_header: bool,
+    /// Put an autofilter over the header and data.
+    pub auto_filter: bool,
+    /// Set each column's width from the type of data it holds.
+    pub size_columns: bool,
+}
+
+impl ImportStyleOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bold header on a light gray fill with a thin bottom border, frozen and
+    /// filtered, with columns sized to their data.
+    pub fn presentable() -> Self {
+        ImportStyleOptions {
+            header_font: Some(Font::new().with_bold(true)),
+            header_fill: Some(Fill::solid("FFD9D9D9")),
+            header_border: Some(Border::new().with_bottom(BorderStyle::thin())),
+            freeze_header: true,
+            auto_filter: true,
+            size_columns: true,
+        }
+    }
+
+    /// Set the header font.
+    pub fn with_header_font(mut self, font: Font) -> Self {
+        self.header_font = Some(font);
+        self
+    }
+
+    /// Set the header fill.
+    pub fn with_header_fill(mut self, fill: Fill) -> Self {
+        self.header_fill = Some(fill);
+        self
+    }
+
+    /// Set the header border.
+    pub fn with_header_border(mut self, border: Border) -> Self {
+        self.header_border = Some(border);
+        self
+    }
+
+    /// Set whether to freeze the header row.
+    pub fn with_freeze_header(mut self, freeze: bool) -> Self {
+        self.freeze_header = freeze;
+        self
+    }
+
+    /// Set whether to add an autofilter on the header row.
+    pub fn with_auto_filter(mut self, auto_filter: bool) -> Self {
+        self.auto_filter = auto_filter;
+        self
+    }
+
+    /// Set whether to size columns by data type.
+    pub fn with_column_sizing(mut self, size_columns: bool) -> Self {
+        self.size_columns = size_columns;
+        self
+    }
+}
+
+/// Widths (Excel character units) used when sizing imported columns.
+const MIN_WIDTH: f64 = 8.0;
+const MAX_TEXT_WIDTH: f64 = 50.0;
+const BOOLEAN_WIDTH: f64 = 8.0;
+const PADDING: f64 = 2.0;
+/// Excel's hard maximum column width.
+const MAX_WIDTH: f64 = 255.0;
+/// Room for the filter dropdown button drawn in each header cell.
+const FILTER_BUTTON: f64 = 2.0;
+
+impl Worksheet {
+    /// Style an imported block spanning rows `start_row..=end_row` and columns
+    /// `start_col..=end_col`. With `has_header`, the first row is the header:
+    /// it takes the header font/fill/border and anchors the freeze and the
+    /// autofilter, which are skipped for headerless imports.
+    pub fn apply_import_style(
+        &mut self,
+        (start_row, start_col): (u32, u32),
+        (end_row, end_col): (u32, u32),
+        has_header: bool,
+        options: &ImportStyleOptions,
+    ) {
+        if end_row < start_row || end_col < start_col {
+            return;
+        }
+
+        if has_header {
+            let header_style = options.header_font.is_some()
+                || options.header_fill.is_some()
+                || options.header_border.is_some();
+            if header_style {
+                for col in start_col..=end_col {
+                    self.merge_header_style(start_row, col, options);
+                }
+            }
+            if options.freeze_header {
+                self.freeze_panes = Some(coordinate_from_row_col(start_row + 1, 1));
+            }
+            if options.auto_filter {
+                self.auto_filter = Some(AutoFilter::new(format!(
+                    "{}:{}",
+                    coordinate_from_row_col(start_row, start_col),
+                    coordinate_from_row_col(end_row, end_col)
+                )));
+            }
+        }
+
+        if options.size_columns {
+            let data_start = if has_header { start_row + 1 } else { start_row };
+            for col in start_col..=end_col {
+                let mut width = self.typed_column_width(col, data_start, end_row);
+                if has_header {
+                    let header_len = self
+                        .get_cell(start_row, col)
+                        .map(|c| display_len(&c.value, None))
+                        .unwrap_or(0) as f64;
+                    let button = if options.auto_filter {
+                        FILTER_BUTTON
+                    } else {
+                        0.0
+                    };
+                    width = width.max(header_len + PADDING + button);
+                }
+                self.set_column_width(col, width.min(MAX_WIDTH));
+            }
+        }
+    }
+
+    /// Merge the header font/fill/border into one cell's existing style.
+    fn merge_header_style(&mut self, row: u32, col: u32, options: &ImportStyleOptions) {
+        let cell = self.get_or_create_cell_mut(row, col);
+        let mut style = cell
+            .style
+            .as_deref()
+            .cloned()
+            .unwrap_or_else(CellStyle::new);
+        if let Some(font) = &options.header_font {
+            style.font = Some(font.clone());
+        }
+        if let Some(fill) = &options.header_fill {
+            style.fill = Some(fill.clone());
+        }
+        if let Some(border) = &options.header_border {
+            style.border = Some(border.clone());
+        }
+        cell.style = Some(Arc::new(style));
+        cell.style_index = None;
+    }
+
+    /// Width for one column of imported data, chosen by what it holds:
+    /// booleans get a fixed width, dates and numbers fit their formatted
+    /// text, and text fits its longest line up to a cap so one long value
+    /// doesn't produce an unusably wide column.
+    fn typed_column_width(&self, col: u32, first_row: u32, last_row: u32) -> f64 {
+        let mut longest = 0usize;
+        let mut all_boolean = true;
+        let mut any_text = false;
+        let mut saw_value = false;
+        for row in first_row..=last_row {
+            let Some(cell) = self.get_cell(row, col) else {
+                continue;
+            };
+            match &cell.value {
+                CellValue::Empty => continue,
+                CellValue::Boolean(_) => {}
+                CellValue::String(_) | CellValue::Formula(_) => {
+                    all_boolean = false;
+                    any_text = true;
+                }
+                CellValue::Number(_) | CellValue::Date(_) => all_boolean = false,
+            }
+            saw_value = true;
+            longest = longest.max(display_len(&cell.value, cell.number_format.as_deref()));
+        }
+
+        if !saw_value {
+            return MIN_WIDTH;
+        }
+        if all_boolean {
+            return BOOLEAN_WIDTH;
+        }
+        let width = (longest as f64 + PADDING).max(MIN_WIDTH);
+        if any_text {
+            width.min(MAX_TEXT_WIDTH)
+        } else {
+            width
+        }
+    }
+}
+
+/// Character count of a value as displayed under `number_format`, measured
+/// on its longest line.
+fn display_len(value: &CellValue, number_format: Option<&str>) -> usize {
+    let display = crate::numfmt::format_value(value, number_format.unwrap_or("General"));
+    display
+        .split('\n')
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Worksheet {
+        let mut ws = Worksheet::new("Data");
+        ws.set_cell_value(1, 1, "name");
+        ws.set_cell_value(1, 2, "ok");
+        ws.set_cell_value(1, 3, "when");
+        ws.set_cell_value(2, 1, "a fairly long customer name");
+        ws.set_cell_value(2, 2, CellValue::Boolean(true));
+        ws.set_cell_value(2, 3, CellValue::Number(45000.0));
+        ws.set_cell_number_format(2, 3, "yyyy-mm-dd");
+        ws
+    }
+
+    #[test]
+    fn test_presentable_styles_header_freezes_and_filters() {
+        let mut ws = sample();
+        ws.apply_import_style((1, 1), (2, 3), true, &ImportStyleOptions::presentable());
+
+        let cell = ws.get_cell(1, 2).unwrap();
+
+        let header = cell.style.as_ref().unwrap();
+        assert!(header.font.as_ref().unwrap().bold);
+        assert!(header.fill.is_some());
+        assert!(header.border.as_ref().unwrap().bottom.is_some());
+        assert!(ws.get_cell(2, 2).unwrap().style.is_none());
+        assert_eq!(ws.freeze_panes.as_deref(), Some("A2"));
+        assert_eq!(ws.auto_filter.as_ref().unwrap().range, "A1:C2");
+    }
+
+    #[test]
+    fn test_column_widths_follow_data_type() {
+        let mut ws = sample();
+        ws.apply_import_style(
+            (1, 1),
+            (2, 3),
+            true,
+            &ImportStyleOptions::new().with_column_sizing(true),
+        );
+
+        // text fits its longest value
+        assert_eq!(ws.get_column_width(1), Some(29.0));
+        // booleans are fixed width, widened only for the header
+        assert_eq!(ws.get_column_width(2), Some(BOOLEAN_WIDTH));
+        // dates fit their formatted text ("2023-03-15")
+        assert_eq!(ws.get_column_width(3), Some(12.0));
+    }
+
+    #[test]
+    fn test_headerless_import_skips_header_features() {
+        let mut ws = sample();
+        ws.apply_import_style((2, 1), (2, 3), false, &ImportStyleOptions::presentable());
+
+        assert!(ws.freeze_panes.is_none());
+        assert!(ws.auto_filter.is_none());
+        assert!(ws.get_cell(2, 1).unwrap().style.is_none());
+        assert!(ws.get_column_width(1).is_some());
+    }
+}
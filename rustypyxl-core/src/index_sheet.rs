@@ -0,0 +1,205 @@
+//! A table-of-contents sheet.
+//!
+//! [`Workbook::build_index_sheet`] puts a sheet in front of the others that
+//! lists every sheet as an internal hyperlink, with its row count and an
+//! optional description, so a reader of a many-sheet workbook can jump
+//! straight to the one they need.
+
+use std::collections::HashMap;
+
+use crate::cell::CellValue;
+use crate::error::Result;
+use crate::hyperlink::Hyperlink;
+use crate::style::Font;
+use crate::workbook::{qualify_sheet_range, Workbook};
+use crate::worksheet::{SheetVisibility, Worksheet};
+
+/// Widest column the index sizes to fit its content.
+const MAX_WIDTH: f64 = 80.0;
+
+/// Options for [`Workbook::build_index_sheet`].
+#[derive(Debug, Clone)]
+pub struct IndexSheetOptions {
+    /// Title of the index sheet. Default: "Contents".
+    pub title: String,
+    /// Add a Rows column with each sheet's last used row. Default: true.
+    pub row_counts: bool,
+    /// List hidden and very hidden sheets too. Default: false.
+    pub include_hidden: bool,
+    /// Text for a Description column, by sheet name. The column is left
+    /// out when there are none.
+    pub descriptions: HashMap<String, String>,
+}
+
+impl Default for IndexSheetOptions {
+    fn default() -> Self {
+        Self {
+            title: "Contents".to_string(),
+            row_counts: true,
+            include_hidden: false,
+            descriptions: HashMap::new(),
+        }
+    }
+}
+
+impl IndexSheetOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the index sheet.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Set whether the Rows column is written.
+    pub fn with_row_counts(mut self, row_counts: bool) -> Self {
+        self.row_counts = row_counts;
+        self
+    }
+
+    /// Set whether hidden sheets are listed.
+    pub fn with_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    /// Describe sheet `sheet` in the Description column.
+    pub fn with_description(mut self, sheet: &str, description: &str) -> Self {
+        self.descriptions
+            .insert(sheet.to_string(), description.to_string());
+        self
+    }
+}
+
+impl Workbook {
+    /// Add a table-of-contents sheet as the first tab: a header row, then
+    /// one row per sheet in tab order whose name links to the sheet's A1,
+    /// followed by its row count and description as `options` ask. A sheet
+    /// already titled `options.title` is replaced, so calling this again
+    /// after adding sheets brings the index up to date. Returns the new
+    /// sheet.
+    pub fn build_index_sheet(&mut self, options: &IndexSheetOptions) -> Result<&mut Worksheet> {
+        if self.sheet_position(&options.title).is_some() {
+            self.remove_sheet(&options.title)?;
+        }
+        let entries: Vec<(String, u32, Option<&String>)> = self
+            .worksheets
+            .iter()
+            .zip(&self.sheet_names)
+            .filter(|(ws, _)| options.include_hidden || ws.visibility == SheetVisibility::Visible)
+            .map(|(ws, name)| (name.clone(), ws.max_row(), options.descriptions.get(name)))
+            .collect();
+        let describe = entries
+            .iter()
+            .any(|(_, _, description)| description.is_some());
+
+        let mut headers = vec!["Sheet"];
+        if options.row_counts {
+            headers.push("Rows");
+        }
+        if describe {
+            headers.push("Description");
+        }
+        let index = self.create_sheet(Some(options.title.clone()))?;
+        let bold = Font::new().with_bold(true);
+        for (col, header) in headers.iter().enumerate() {
+            index.set_cell_value(1, col as u32 + 1, *header);
+            index.set_cell_font(1, col as u32 + 1, bold.clone());
+        }
+        let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+        for (i, (name, rows, description)) in entries.iter().enumerate() {
+            let row = i as u32 + 2;
+            let location = qualify_sheet_range(name, "A1");
+            index.set_hyperlink(row, 1, Hyperlink::internal(location), Some(name), true);
+            widths[0] = widths[0].max(name.chars().count());
+            let mut col = 2;
+            if options.row_counts {
+                index.set_cell_value(row, col, CellValue::Number(*rows as f64));
+                col += 1;
+            }
+            if let Some(description) = description {
+                index.set_cell_value(row, col, description.as_str());
+                widths[col as usize - 1] =
+                    widths[col as usize - 1].max(description.chars().count());
+            }
+        }
+        for (col, width) in widths.iter().enumerate() {
+            index.set_column_width(col as u32 + 1, (*width as f64 + 2.0).min(MAX_WIDTH));
+        }
+        index.set_freeze_panes(Some("A2".to_string()));
+
+        self.move_sheet(&options.title, 0)?;
+        Ok(&mut self.worksheets[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workbook() -> Workbook {
+        let mut wb = Workbook::new();
+        for name in ["Sales", "Q1 Costs", "Scratch"] {
+            wb.create_sheet(Some(name.to_string())).unwrap();
+        }
+        let ws = wb.get_sheet_by_name_mut("Sales").unwrap();
+        for row in 1..=40 {
+            ws.set_cell_value(row, 1, CellValue::Number(row as f64));
+        }
+        wb.get_sheet_by_name_mut("Scratch").unwrap().visibility = SheetVisibility::Hidden;
+        wb
+    }
+
+    #[test]
+    fn index_lists_visible_sheets_with_links() {
+        let mut wb = workbook();
+        let options = IndexSheetOptions::new().with_description("Sales", "Daily sales");
+        wb.build_index_sheet(&options).unwrap();
+        assert_eq!(wb.sheet_names, ["Contents", "Sales", "Q1 Costs", "Scratch"]);
+
+        let ws = wb.get_sheet_by_name("Contents").unwrap();
+        assert_eq!(
+            ws.get_cell_value(1, 3),
+            Some(&CellValue::from("Description"))
+        );
+        assert_eq!(ws.get_cell_value(2, 1), Some(&CellValue::from("Sales")));
+        assert_eq!(ws.get_cell_value(2, 2), Some(&CellValue::Number(40.0)));
+        assert_eq!(
+            ws.get_cell_value(2, 3),
+            Some(&CellValue::from("Daily sales"))
+        );
+        assert_eq!(ws.get_cell_value(3, 2), Some(&CellValue::Number(0.0)));
+        assert_eq!(ws.get_cell_value(4, 1), None);
+
+        let cell = ws.get_cell(3, 1).unwrap();
+
+        let link = cell.hyperlink.as_deref().unwrap();
+        assert!(!link.is_external);
+        assert_eq!(link.target, "'Q1 Costs'!$A$1");
+        assert_eq!(ws.freeze_panes.as_deref(), Some("A2"));
+    }
+
+    #[test]
+    fn rebuilding_replaces_the_index() {
+        let mut wb = workbook();
+        let options = IndexSheetOptions::new()
+            .with_title("TOC")
+            .with_row_counts(false)
+            .with_hidden(true);
+        wb.build_index_sheet(&options).unwrap();
+        wb.create_sheet(Some("Later".to_string())).unwrap();
+        wb.build_index_sheet(&options).unwrap();
+
+        assert_eq!(
+            wb.sheet_names,
+            ["TOC", "Sales", "Q1 Costs", "Scratch", "Later"]
+        );
+        let ws = wb.get_sheet_by_name("TOC").unwrap();
+        assert_eq!(ws.get_cell_value(1, 2), None);
+        assert_eq!(ws.get_cell_value(4, 1), Some(&CellValue::from("Scratch")));
+        assert_eq!(ws.get_cell_value(5, 1), Some(&CellValue::from("Later")));
+        assert_eq!(ws.max_row(), 5);
+    }
+}
@@ -0,0 +1,716 @@
+//! Worksheets as JSON records.
+//!
+//! [`Worksheet::to_json`] turns a sheet into an array of row objects keyed by
+//! the header row, and [`Worksheet::write_ndjson`] streams the same objects
+//! one per line to any writer, for piping sheet data into web services
+//! without a dataframe library. [`Workbook::insert_from_json`] reads either
+//! form back; NDJSON is read a line at a time.
+
+use crate::cell::CellValue;
+use crate::csv::{parse_iso_date, write_json_string};
+use crate::error::{Result, RustypyxlError};
+use crate::import_style::ImportStyleOptions;
+use crate::json::JsonValue;
+use crate::workbook::Workbook;
+use crate::worksheet::{CellData, Worksheet};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Options for JSON export.
+#[derive(Debug, Clone)]
+pub struct JsonExportOptions {
+    /// Range to export (e.g. "A1:D100"); the used range when `None`.
+    pub range: Option<String>,
+    /// Whether the first row holds the keys. Without it the keys are
+    /// `Column1`, `Column2`, ... Default: true.
+    pub has_headers: bool,
+    /// Leave empty cells out of their object instead of writing `null`.
+    /// Default: false.
+    pub skip_nulls: bool,
+    /// chrono format for date-time cells; ISO 8601 when `None`.
+    pub date_format: Option<String>,
+}
+
+impl Default for JsonExportOptions {
+    fn default() -> Self {
+        Self {
+            range: None,
+            has_headers: true,
+            skip_nulls: false,
+            date_format: None,
+        }
+    }
+}
+
+impl JsonExportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Export only `range` instead of the used range.
+    pub fn with_range(mut self, range: &str) -> Self {
+        self.range = Some(range.to_string());
+        self
+    }
+
+    /// Set whether the first row holds the keys.
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Set whether empty cells are left out of their object.
+    pub fn with_skip_nulls(mut self, skip_nulls: bool) -> Self {
+        self.skip_nulls = skip_nulls;
+        self
+    }
+
+    /// Set the chrono format for date-time cells.
+    pub fn with_date_format(mut self, format: &str) -> Self {
+        self.date_format = Some(format.to_string());
+        self
+    }
+}
+
+/// One cell as a JSON value.
+enum Field<'a> {
+    Null,
+    Number(f64),
+    Bool(bool),
+    Text(Cow<'a, str>),
+}
+
+impl Field<'_> {
+    fn into_owned(self) -> Field<'static> {
+        match self {
+            Field::Null => Field::Null,
+            Field::Number(n) => Field::Number(n),
+            Field::Bool(b) => Field::Bool(b),
+            Field::Text(t) => Field::Text(Cow::Owned(t.into_owned())),
+        }
+    }
+}
+
+impl Worksheet {
+    /// The used range, or `options.range`, as a JSON array of row objects
+    /// keyed by the header row. Rows without any value are left out. Numbers
+    /// and booleans keep their JSON types, dates are strings, formulas give
+    /// their cached result (`null` when never calculated), and NaN or
+    /// infinity becomes `null`.
+    pub fn to_json(&self, options: &JsonExportOptions) -> Result<String> {
+        let mut out = Vec::new();
+        self.write_records(&mut out, options, false)?;
+        // Everything written is UTF-8 text.
+        Ok(String::from_utf8(out).expect("JSON output is UTF-8"))
+    }
+
+    /// Write the same row objects as [`Worksheet::to_json`] to `out` as
+    /// newline-delimited JSON, one object per line, streaming row by row.
+    /// Returns the number of objects written.
+    pub fn write_ndjson<W: Write>(&self, out: &mut W, options: &JsonExportOptions) -> Result<u32> {
+        self.write_records(out, options, true)
+    }
+
+    fn write_records<W: Write>(
+        &self,
+        out: &mut W,
+        options: &JsonExportOptions,
+        ndjson: bool,
+    ) -> Result<u32> {
+        let bounds = self.render_bounds(options.range.as_deref())?;
+        let Some((min_row, min_col, max_row, max_col)) = bounds else {
+            if !ndjson {
+                out.write_all(b"[]")?;
+            }
+            return Ok(0);
+        };
+        let keys = self.record_keys(min_row, min_col, max_col, options.has_headers);
+        let data_start = if options.has_headers {
+            min_row + 1
+        } else {
+            min_row
+        };
+
+        let mut itoa_buf = itoa::Buffer::new();
+        let mut ryu_buf = ryu::Buffer::new();
+        let mut written = 0u32;
+        if !ndjson {
+            out.write_all(b"[")?;
+        }
+        for row in data_start..=max_row {
+            let fields: Vec<Field> = (min_col..=max_col)
+                .map(|col| match self.get_cell(row, col) {
+                    Some(Cow::Borrowed(cell)) => field(cell, options),
+                    Some(Cow::Owned(cell)) => field(&cell, options).into_owned(),
+                    None => Field::Null,
+                })
+                .collect();
+            if fields.iter().all(|f| matches!(f, Field::Null)) {
+                continue;
+            }
+            if !ndjson && written > 0 {
+                out.write_all(b",")?;
+            }
+            out.write_all(b"{")?;
+            let mut first = true;
+            for (key, field) in keys.iter().zip(&fields) {
+                if options.skip_nulls && matches!(field, Field::Null) {
+                    continue;
+                }
+                if !first {
+                    out.write_all(b",")?;
+                }
+                first = false;
+                write_json_string(out, key)?;
+                out.write_all(b":")?;
+                match field {
+                    Field::Null => out.write_all(b"null")?,
+                    Field::Bool(b) => out.write_all(if *b { b"true" } else { b"false" })?,
+                    Field::Number(n) if !n.is_finite() => out.write_all(b"null")?,
+                    Field::Number(n) if n.fract() == 0.0 && n.abs() < 9.007_199_254_740_992e15 => {
+                        out.write_all(itoa_buf.format(*n as i64).as_bytes())?
+                    }
+                    Field::Number(n) => out.write_all(ryu_buf.format(*n).as_bytes())?,
+                    Field::Text(text) => write_json_string(out, text)?,
+                }
+            }
+            out.write_all(b"}")?;
+            if ndjson {
+                out.write_all(b"\n")?;
+            }
+            written += 1;
+        }
+        if !ndjson {
+            out.write_all(b"]")?;
+        }
+        Ok(written)
+    }
+
+    /// Object keys for columns `min_col..=max_col`: the header row's values,
+    /// with blanks named `ColumnN` and repeats suffixed `_2`, `_3`, ... so
+    /// every key is distinct.
+    fn record_keys(
+        &self,
+        header_row: u32,
+        min_col: u32,
+        max_col: u32,
+        has_headers: bool,
+    ) -> Vec<String> {
+        let mut seen = HashSet::new();
+        (min_col..=max_col)
+            .enumerate()
+            .map(|(i, col)| {
+                let header = has_headers
+                    .then(|| self.get_cell_value(header_row, col))
+                    .flatten()
+                    .map(|v| v.to_string())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| format!("Column{}", i + 1));
+                let mut key = header.clone();
+                let mut n = 2;
+                while !seen.insert(key.clone()) {
+                    key = format!("{}_{}", header, n);
+                    n += 1;
+                }
+                key
+            })
+            .collect()
+    }
+}
+
+fn field<'a>(cell: &'a CellData, options: &JsonExportOptions) -> Field<'a> {
+    match &cell.value {
+        CellValue::Empty => Field::Null,
+        CellValue::String(s) => Field::Text(Cow::Borrowed(s)),
+        CellValue::Number(n) => Field::Number(*n),
+        CellValue::Boolean(b) => Field::Bool(*b),
+        CellValue::Date(d) => Field::Text(Cow::Owned(match &options.date_format {
+            Some(format) => d.to_datetime().format(format).to_string(),
+            None => d.to_string(),
+        })),
+        CellValue::Formula(_) => match (&cell.cached_formula_value, cell.data_type) {
+            (None, _) => Field::Null,
+            (Some(cached), Some("b")) => Field::Bool(cached == "1"),
+            (Some(cached), Some("str" | "e")) => Field::Text(Cow::Borrowed(cached)),
+            (Some(cached), _) => cached
+                .parse()
+                .map(Field::Number)
+                .unwrap_or(Field::Text(Cow::Borrowed(cached))),
+        },
+    }
+}
+
+/// Result of a JSON import.
+#[derive(Debug, Clone)]
+pub struct JsonImportResult {
+    /// Number of rows imported (excluding header).
+    pub rows_imported: u32,
+    /// Number of columns imported.
+    pub columns_imported: u32,
+    /// Starting row of data (1-indexed).
+    pub start_row: u32,
+    /// Starting column of data (1-indexed).
+    pub start_col: u32,
+    /// Ending row of data (1-indexed).
+    pub end_row: u32,
+    /// Ending column of data (1-indexed).
+    pub end_col: u32,
+    /// Column names as imported (after any renaming), in column order.
+    pub column_names: Vec<String>,
+}
+
+impl JsonImportResult {
+    /// Get the range string (e.g., "A1:Z1000") for the imported data including headers.
+    pub fn range_with_headers(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            crate::utils::column_to_letter(self.start_col),
+            self.start_row,
+            crate::utils::column_to_letter(self.end_col),
+            self.end_row
+        )
+    }
+
+    /// Get the range string for just the data (excluding headers).
+    pub fn data_range(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            crate::utils::column_to_letter(self.start_col),
+            self.start_row + 1,
+            crate::utils::column_to_letter(self.end_col),
+            self.end_row
+        )
+    }
+
+    /// Get the range string for just the headers.
+    pub fn header_range(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            crate::utils::column_to_letter(self.start_col),
+            self.start_row,
+            crate::utils::column_to_letter(self.end_col),
+            self.start_row
+        )
+    }
+}
+
+/// Options for JSON import.
+#[derive(Debug, Clone)]
+pub struct JsonImportOptions {
+    /// Write the keys as a header row. Default: true.
+    pub has_headers: bool,
+    /// Keys to import, in column order. If empty, every key is imported, in
+    /// the order first seen.
+    pub columns: Vec<String>,
+    /// Column name mappings (original_name -> new_name).
+    pub column_renames: HashMap<String, String>,
+    /// Turn strings holding ISO 8601 dates into date cells. Default: true.
+    pub parse_dates: bool,
+    /// Header/freeze/filter/width styling applied to the imported block.
+    pub style: Option<ImportStyleOptions>,
+}
+
+impl Default for JsonImportOptions {
+    fn default() -> Self {
+        Self {
+            has_headers: true,
+            columns: Vec::new(),
+            column_renames: HashMap::new(),
+            parse_dates: true,
+            style: None,
+        }
+    }
+}
+
+impl JsonImportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the keys are written as a header row.
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Select specific keys to import.
+    pub fn select_columns(mut self, columns: Vec<String>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Add a column rename mapping.
+    pub fn rename_column(mut self, from: &str, to: &str) -> Self {
+        self.column_renames.insert(from.to_string(), to.to_string());
+        self
+    }
+
+    /// Set whether ISO 8601 date strings become dates.
+    pub fn with_date_parsing(mut self, parse_dates: bool) -> Self {
+        self.parse_dates = parse_dates;
+        self
+    }
+
+    /// Style the imported block (see [`ImportStyleOptions`]).
+    pub fn with_style(mut self, style: ImportStyleOptions) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl Workbook {
+    /// Import a JSON file into a worksheet, starting at (`start_row`,
+    /// `start_col`). The file holds either an array of objects or one object
+    /// per line (NDJSON); each object becomes a row and each key a column.
+    pub fn insert_from_json<P: AsRef<Path>>(
+        &mut self,
+        sheet_name: &str,
+        path: P,
+        start_row: u32,
+        start_col: u32,
+        options: Option<JsonImportOptions>,
+    ) -> Result<JsonImportResult> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| RustypyxlError::ParseError(format!("Failed to open JSON file: {}", e)))?;
+        let worksheet = self.get_sheet_by_name_mut(sheet_name)?;
+        worksheet.insert_json(
+            BufReader::new(file),
+            start_row,
+            start_col,
+            &options.unwrap_or_default(),
+        )
+    }
+}
+
+impl Worksheet {
+    /// Write JSON records read from `input` into this worksheet, as
+    /// [`Workbook::insert_from_json`] does for a file. Nulls leave their cell
+    /// empty; nested arrays and objects are written as JSON text.
+    pub fn insert_json<R: BufRead>(
+        &mut self,
+        mut input: R,
+        start_row: u32,
+        start_col: u32,
+        options: &JsonImportOptions,
+    ) -> Result<JsonImportResult> {
+        if start_row == 0 || start_col == 0 {
+            return Err(RustypyxlError::custom(
+                "start_row and start_col are 1-based",
+            ));
+        }
+        let data_start = if options.has_headers {
+            start_row + 1
+        } else {
+            start_row
+        };
+        let mut sink = RecordSink {
+            index: options
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(i, key)| (key.clone(), i))
+                .collect(),
+            columns: options.columns.clone(),
+            fixed: !options.columns.is_empty(),
+            rows: 0,
+        };
+
+        if first_byte(&mut input)? == Some(b'[') {
+            let mut text = String::new();
+            input.read_to_string(&mut text)?;
+            let JsonValue::Array(records) = JsonValue::parse(&text)? else {
+                return Err(RustypyxlError::ParseError(
+                    "expected a JSON array of objects".to_string(),
+                ));
+            };
+            for record in records {
+                sink.add(self, record, data_start, start_col, options.parse_dates)?;
+            }
+        } else {
+            for (number, line) in input.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record = JsonValue::parse(&line).map_err(|e| {
+                    RustypyxlError::ParseError(format!("NDJSON line {}: {}", number + 1, e))
+                })?;
+                sink.add(self, record, data_start, start_col, options.parse_dates)?;
+            }
+        }
+
+        let column_names: Vec<String> = sink
+            .columns
+            .into_iter()
+            .map(|key| options.column_renames.get(&key).cloned().unwrap_or(key))
+            .collect();
+        if options.has_headers {
+            for (offset, name) in column_names.iter().enumerate() {
+                self.set_cell_value(
+                    start_row,
+                    start_col + offset as u32,
+                    CellValue::String(Arc::from(name.as_str())),
+                );
+            }
+        }
+
+        let rows_imported = sink.rows;
+        let end_row = if rows_imported > 0 {
+            data_start + rows_imported - 1
+        } else {
+            start_row
+        };
+        let end_col = start_col + column_names.len().max(1) as u32 - 1;
+        if let Some(style) = &options.style {
+            self.apply_import_style(
+                (start_row, start_col),
+                (end_row, end_col),
+                options.has_headers,
+                style,
+            );
+        }
+
+        Ok(JsonImportResult {
+            rows_imported,
+            columns_imported: column_names.len() as u32,
+            start_row,
+            start_col,
+            end_row,
+            end_col,
+            column_names,
+        })
+    }
+}
+
+/// Collects the column for each key as records arrive.
+struct RecordSink {
+    columns: Vec<String>,
+    index: HashMap<String, usize>,
+    /// Only the selected keys are imported; others are ignored.
+    fixed: bool,
+    rows: u32,
+}
+
+impl RecordSink {
+    fn add(
+        &mut self,
+        ws: &mut Worksheet,
+        record: JsonValue,
+        data_start: u32,
+        start_col: u32,
+        parse_dates: bool,
+    ) -> Result<()> {
+        let JsonValue::Object(entries) = record else {
+            return Err(RustypyxlError::ParseError(format!(
+                "JSON record {} is not an object",
+                self.rows + 1
+            )));
+        };
+        let row = data_start + self.rows;
+        for (key, value) in entries {
+            let col = match self.index.get(&key) {
+                Some(&col) => col,
+                None if self.fixed => continue,
+                None => {
+                    self.index.insert(key.clone(), self.columns.len());
+                    self.columns.push(key);
+                    self.columns.len() - 1
+                }
+            };
+            let value = match value {
+                JsonValue::Null => continue,
+                JsonValue::Bool(b) => CellValue::Boolean(b),
+                JsonValue::Number(n) => CellValue::Number(n),
+                JsonValue::String(s) => match parse_dates.then(|| parse_iso_date(&s)).flatten() {
+                    Some(dt) => CellValue::from(dt),
+                    None => CellValue::String(Arc::from(s)),
+                },
+                nested => CellValue::String(Arc::from(nested.to_compact_string())),
+            };
+            ws.set_cell_value(row, start_col + col as u32, value);
+        }
+        self.rows += 1;
+        Ok(())
+    }
+}
+
+/// The first byte after any whitespace and UTF-8 byte-order mark, left
+/// unconsumed; `None` for empty input.
+fn first_byte<R: BufRead>(input: &mut R) -> Result<Option<u8>> {
+    let mut at_start = true;
+    loop {
+        let buf = input.fill_buf()?;
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        if std::mem::take(&mut at_start) && buf.starts_with(b"\xEF\xBB\xBF") {
+            input.consume(3);
+            continue;
+        }
+        match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(i) => {
+                let byte = buf[i];
+                input.consume(i);
+                return Ok(Some(byte));
+            }
+            None => {
+                let len = buf.len();
+                input.consume(len);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn sheet() -> Worksheet {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_value(1, 1, "name");
+        ws.set_cell_value(1, 2, "qty");
+        ws.set_cell_value(1, 3, "name");
+        ws.set_cell_value(2, 1, "Widget \"A\"");
+        ws.set_cell_value(2, 2, 3.0);
+        ws.set_cell_value(2, 3, true);
+        ws.set_cell_value(4, 2, 0.5);
+        ws.set_cell_value(
+            4,
+            4,
+            NaiveDate::from_ymd_opt(2024, 3, 15)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap(),
+        );
+        ws
+    }
+
+    #[test]
+    fn test_to_json_keys_and_types() {
+        let json = sheet().to_json(&JsonExportOptions::new()).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"name":"Widget \"A\"","qty":3,"name_2":true,"Column4":null},{"name":null,"qty":0.5,"name_2":null,"Column4":"2024-03-15T12:00:00"}]"#
+        );
+        // The output is valid JSON.
+        assert!(JsonValue::parse(&json).is_ok());
+    }
+
+    #[test]
+    fn test_ndjson_options() {
+        let mut out = Vec::new();
+        let options = JsonExportOptions::new()
+            .with_headers(false)
+            .with_skip_nulls(true)
+            .with_range("B2:D4")
+            .with_date_format("%Y-%m-%d");
+        let written = sheet().write_ndjson(&mut out, &options).unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"Column1\":3,\"Column2\":true}\n{\"Column1\":0.5,\"Column3\":\"2024-03-15\"}\n"
+        );
+        let empty = Worksheet::new("E");
+        assert_eq!(empty.to_json(&JsonExportOptions::new()).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_formula_cells_export_cached_results() {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_value(1, 1, "total");
+        ws.set_cell_value(1, 2, "check");
+        ws.set_cell_formula(2, 1, "SUM(C1:C9)");
+        ws.get_cell_mut(2, 1).unwrap().cached_formula_value = Some("42".to_string());
+        ws.set_cell_formula(2, 2, "C1>0");
+        let cell = ws.get_cell_mut(2, 2).unwrap();
+        cell.cached_formula_value = Some("1".to_string());
+        cell.data_type = Some("b");
+        ws.set_cell_value(3, 1, f64::NAN);
+        assert_eq!(
+            ws.to_json(&JsonExportOptions::new()).unwrap(),
+            r#"[{"total":42,"check":true},{"total":null,"check":null}]"#
+        );
+    }
+
+    #[test]
+    fn test_insert_json_array_and_ndjson() {
+        let text =
+            br#" [{"a": 1, "b": "2024-03-15"}, {"b": "x", "c": [1, {"d": null}]}, {"a": null}]"#;
+        let mut ws = Worksheet::new("S");
+        let result = ws
+            .insert_json(&text[..], 1, 1, &JsonImportOptions::new())
+            .unwrap();
+        assert_eq!(result.column_names, ["a", "b", "c"]);
+        assert_eq!(result.rows_imported, 3);
+        assert_eq!(result.range_with_headers(), "A1:C4");
+        assert_eq!(ws.get_cell_value(2, 1), Some(&CellValue::Number(1.0)));
+        assert_eq!(
+            ws.get_cell_value(2, 2),
+            Some(&CellValue::from(
+                NaiveDate::from_ymd_opt(2024, 3, 15)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            ))
+        );
+        assert_eq!(
+            ws.get_cell_value(3, 3),
+            Some(&CellValue::from(r#"[1,{"d":null}]"#))
+        );
+        assert!(ws.get_cell(4, 1).is_none());
+
+        let lines = b"{\"x\": 1, \"y\": true}\n\n{\"y\": false, \"z\": \"2024-01-01\"}\n";
+        let options = JsonImportOptions::new()
+            .with_headers(false)
+            .with_date_parsing(false)
+            .select_columns(vec!["z".to_string(), "y".to_string()])
+            .rename_column("z", "when");
+        let mut ws = Worksheet::new("S");
+        let result = ws.insert_json(&lines[..], 5, 2, &options).unwrap();
+        assert_eq!(result.column_names, ["when", "y"]);
+        assert_eq!(result.range_with_headers(), "B5:C6");
+        assert_eq!(ws.get_cell_value(5, 3), Some(&CellValue::Boolean(true)));
+        assert_eq!(
+            ws.get_cell_value(6, 2),
+            Some(&CellValue::from("2024-01-01"))
+        );
+        assert!(ws.get_cell(5, 2).is_none());
+    }
+
+    #[test]
+    fn test_insert_json_errors() {
+        let mut ws = Worksheet::new("S");
+        let options = JsonImportOptions::new();
+        let err = ws
+            .insert_json(&b"{\"a\": 1}\n{\"a\": \n"[..], 1, 1, &options)
+            .unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+        assert!(ws.insert_json(&b"[1, 2]"[..], 1, 1, &options).is_err());
+        assert!(ws.insert_json(&b"{}"[..], 0, 1, &options).is_err());
+        let empty = ws.insert_json(&b"  \n"[..], 1, 1, &options).unwrap();
+        assert_eq!(empty.rows_imported, 0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let source = sheet();
+        let json = source.to_json(&JsonExportOptions::new()).unwrap();
+        let mut ws = Worksheet::new("S");
+        ws.insert_json(json.as_bytes(), 1, 1, &JsonImportOptions::new())
+            .unwrap();
+        for (row, col) in [(2, 1), (2, 2), (2, 3), (3, 2), (3, 4)] {
+            // Row 3 of the source is blank, so its row 4 lands on row 3.
+            let source_row = if row == 3 { 4 } else { row };
+            assert_eq!(
+                ws.get_cell_value(row, col),
+                source.get_cell_value(source_row, col)
+            );
+        }
+        assert_eq!(ws.get_cell_value(1, 3), Some(&CellValue::from("name_2")));
+    }
+}
@@ -26,21 +26,68 @@
 //! println!("Sheet title: {}", ws.title());
 //! ```
 
+pub mod blank;
+pub mod builder;
 pub mod cell;
 pub mod chart;
 pub mod chart_writer;
+pub mod column_spans;
+pub mod columnar;
+pub mod comments;
 pub mod conditional;
+pub mod conditional_eval;
+pub mod convert;
 #[cfg(feature = "decrypt")]
 pub mod crypto;
+pub mod csv;
+pub mod custom_xml;
+pub mod dates;
+pub mod docprops;
 pub mod drawing_writer;
+pub mod edit;
 pub mod error;
+pub mod file_lock;
 pub mod formula;
+pub mod formula_locale;
+pub mod html;
+pub mod hyperlink;
 pub mod image;
+pub mod import_style;
+pub mod index_sheet;
+pub mod json;
+pub mod json_records;
+pub mod limits;
+pub mod macros;
+pub mod named_styles;
 pub mod numfmt;
+pub mod opaque_sheet;
+pub mod outline;
+pub mod passthrough;
 pub mod pivot;
+pub mod protection;
+pub mod range_style;
+pub mod read_only;
+pub mod references;
+pub mod render;
+pub mod report;
 pub mod rich_text;
+pub mod salvage;
+pub mod search;
+pub mod sheetview;
+pub mod skeleton;
+pub mod sniff;
+pub mod stats;
+pub mod string_counts;
 pub mod style;
+pub mod style_palette;
+pub mod tags;
+pub mod text_grid;
+pub mod text_widths;
+pub mod theme;
+pub mod translate;
 pub mod utils;
+pub mod validation;
+pub mod verify;
 pub mod workbook;
 pub mod worksheet;
 pub mod writer;
@@ -51,7 +98,9 @@ pub mod pagesetup;
 pub mod streaming;
 pub mod table;
 
-// Optional parquet support
+// Optional parquet and Arrow IPC support
+#[cfg(feature = "parquet")]
+pub mod arrow_io;
 #[cfg(feature = "parquet")]
 pub mod parquet_import;
 
@@ -60,22 +109,61 @@ pub mod parquet_import;
 pub mod s3;
 
 // Re-export main types at crate level
-pub use cell::CellValue;
+pub use blank::{BlankPolicy, BlankRead, BlankWrite};
+pub use builder::{SheetPart, WorkbookBuilder};
+pub use cell::{CellValue, DateValue};
+pub use column_spans::ColumnSpans;
+pub use columnar::Storage;
+pub use comments::{Comment, ThreadedComment};
+pub use conditional_eval::ConditionalResult;
+pub use convert::{ConvertedValue, ValueConverter};
+pub use csv::{
+    write_sheet_csv, CsvEncoding, CsvExportOptions, CsvExportResult, CsvImportOptions,
+    CsvImportResult, CsvQuoting, CsvSheetExport,
+};
+pub use custom_xml::XmlInjectionPoint;
+pub use docprops::DocumentProperties;
 pub use error::{Result, RustypyxlError};
 pub use formula::{evaluate as evaluate_formula, CellResolver, FormulaValue};
+pub use formula_locale::FormulaLocale;
+pub use hyperlink::Hyperlink;
+pub use import_style::ImportStyleOptions;
+pub use index_sheet::IndexSheetOptions;
+pub use json_records::{JsonExportOptions, JsonImportOptions, JsonImportResult};
+pub use limits::{LimitKind, LimitViolation};
+pub use named_styles::NamedStyle;
 pub use numfmt::{builtin_format_code, format_number, format_value};
+pub use outline::DimensionProps;
+pub use protection::{PasswordHash, WorkbookProtection};
+pub use range_style::NumberScale;
+pub use references::{BrokenReference, ReferenceOwner, ReferenceProblem};
 pub use rich_text::{RichText, RunFont, TextRun};
+pub use salvage::SalvageReport;
+pub use search::{MatchKind, SearchMatch, SearchOptions};
+pub use sheetview::{SheetProperties, SheetView, SheetViewType};
+pub use sniff::{detect_format, detect_format_file, FileFormat, FormatInfo};
 pub use style::{
     Alignment, Border, BorderStyle, CellStyle, Color, Fill, Font, GradientFill, GradientStop,
     Protection,
 };
+pub use style_palette::{PaletteRef, PaletteStyle, StylePalette};
+pub use text_grid::WidthPolicy;
+pub use text_widths::ColumnWidthStats;
+pub use theme::Theme;
 pub use utils::{
     column_to_letter, coordinate_from_row_col, letter_to_column, parse_coordinate,
-    parse_coordinate_bytes, parse_f64_bytes, parse_range, parse_u32_bytes,
+    parse_coordinate_bytes, parse_f64_bytes, parse_range, parse_u32_bytes, range_boundaries,
+};
+pub use verify::SaveVerification;
+pub use workbook::{
+    CompressionLevel, NamedRange, NonFiniteNumbers, NumberPrecision, SharedStrings, Workbook,
+};
+pub use worksheet::{
+    CellData, CommentShape, DataValidation, SheetVisibility, Worksheet, WorksheetProtection,
 };
-pub use workbook::{CompressionLevel, NamedRange, Workbook};
-pub use worksheet::{CellData, DataValidation, SheetVisibility, Worksheet, WorksheetProtection};
 
+#[cfg(feature = "parquet")]
+pub use arrow_io::{ArrowIpcExportOptions, IpcCompression};
 #[cfg(feature = "parquet")]
 pub use parquet_import::{
     ColumnType, ParquetCompression, ParquetExportOptions, ParquetExportResult,
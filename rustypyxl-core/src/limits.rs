@@ -0,0 +1,246 @@
+//! Checking a workbook against Excel's specification limits.
+//!
+//! The writer will happily produce a sheet name of 40 characters, a 50,000
+//! character string or 70,000 hyperlinks on one sheet, and Excel then refuses
+//! to open the file or "repairs" it by dropping content. [`Workbook::check_limits`]
+//! reports everything over those limits so the problem can be fixed before the
+//! file goes out.
+
+use std::fmt;
+
+use crate::cell::CellValue;
+use crate::utils::coordinate_from_row_col;
+use crate::workbook::Workbook;
+
+/// Longest sheet name Excel accepts.
+pub const MAX_SHEET_NAME_CHARS: usize = 31;
+/// Most characters a cell can hold.
+pub const MAX_STRING_CHARS: usize = 32_767;
+/// Longest formula contents, in characters.
+pub const MAX_FORMULA_CHARS: usize = 8_192;
+/// Most distinct cell formats (cellXfs) in a workbook.
+pub const MAX_CELL_FORMATS: usize = 64_000;
+/// Most hyperlinks on one sheet.
+pub const MAX_HYPERLINKS_PER_SHEET: usize = 65_530;
+/// Merged ranges on one sheet treated as too many. Excel documents no hard
+/// cap, so this borrows the per-sheet hyperlink cap as a practical ceiling.
+pub const MAX_MERGED_RANGES_PER_SHEET: usize = 65_530;
+/// Rows on a sheet.
+pub const MAX_ROWS: usize = 1_048_576;
+/// Columns on a sheet.
+pub const MAX_COLUMNS: usize = 16_384;
+
+/// Which limit was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    SheetNameLength,
+    StringLength,
+    FormulaLength,
+    CellFormats,
+    HyperlinksPerSheet,
+    MergedRangesPerSheet,
+    Rows,
+    Columns,
+}
+
+impl LimitKind {
+    /// A short snake_case name, as used in messages and by the bindings.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LimitKind::SheetNameLength => "sheet_name_length",
+            LimitKind::StringLength => "string_length",
+            LimitKind::FormulaLength => "formula_length",
+            LimitKind::CellFormats => "cell_formats",
+            LimitKind::HyperlinksPerSheet => "hyperlinks_per_sheet",
+            LimitKind::MergedRangesPerSheet => "merged_ranges_per_sheet",
+            LimitKind::Rows => "rows",
+            LimitKind::Columns => "columns",
+        }
+    }
+
+    /// The maximum Excel allows.
+    pub fn limit(self) -> usize {
+        match self {
+            LimitKind::SheetNameLength => MAX_SHEET_NAME_CHARS,
+            LimitKind::StringLength => MAX_STRING_CHARS,
+            LimitKind::FormulaLength => MAX_FORMULA_CHARS,
+            LimitKind::CellFormats => MAX_CELL_FORMATS,
+            LimitKind::HyperlinksPerSheet => MAX_HYPERLINKS_PER_SHEET,
+            LimitKind::MergedRangesPerSheet => MAX_MERGED_RANGES_PER_SHEET,
+            LimitKind::Rows => MAX_ROWS,
+            LimitKind::Columns => MAX_COLUMNS,
+        }
+    }
+}
+
+/// One limit exceeded, found by [`Workbook::check_limits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitViolation {
+    pub kind: LimitKind,
+    /// The sheet concerned; `None` for the workbook-wide format count.
+    pub sheet: Option<String>,
+    /// The offending cell, for string and formula lengths.
+    pub location: Option<String>,
+    /// The length or count found.
+    pub actual: usize,
+}
+
+impl LimitViolation {
+    /// The maximum that was exceeded.
+    pub fn limit(&self) -> usize {
+        self.kind.limit()
+    }
+}
+
+impl fmt::Display for LimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind.as_str())?;
+        match (&self.sheet, &self.location) {
+            (Some(sheet), Some(location)) => write!(f, " {}!{}", sheet, location)?,
+            (Some(sheet), None) => write!(f, " {}", sheet)?,
+            _ => {}
+        }
+        write!(
+            f,
+            ": {} exceeds Excel's maximum of {}",
+            self.actual,
+            self.limit()
+        )
+    }
+}
+
+impl Workbook {
+    /// List everything over Excel's limits: sheet names over 31 characters,
+    /// cell strings over 32,767 characters, formulas over 8,192, more than
+    /// 64,000 cell formats, more than 65,530 hyperlinks or merged ranges on
+    /// a sheet, and cells past row 1,048,576 or column XFD. Sheets are
+    /// reported in tab order and cells in row order; an empty list means
+    /// Excel will accept the file on these counts.
+    pub fn check_limits(&self) -> Vec<LimitViolation> {
+        let mut found = Vec::new();
+        let mut check = |kind: LimitKind, sheet: Option<&str>, location, actual| {
+            if actual > kind.limit() {
+                found.push(LimitViolation {
+                    kind,
+                    sheet: sheet.map(str::to_string),
+                    location,
+                    actual,
+                });
+            }
+        };
+
+        for (ws, name) in self.worksheets.iter().zip(&self.sheet_names) {
+            let sheet = Some(name.as_str());
+            check(
+                LimitKind::SheetNameLength,
+                sheet,
+                None,
+                name.chars().count(),
+            );
+            if ws.opaque.is_some() {
+                continue;
+            }
+
+            let mut cells: Vec<_> = ws.all_cells().collect();
+            cells.sort_unstable_by_key(|(pos, _)| *pos);
+            let mut hyperlinks = 0;
+            for ((row, col), cell) in cells {
+                let location = || Some(coordinate_from_row_col(row, col));
+                match &cell.value {
+                    CellValue::String(s) if s.len() > MAX_STRING_CHARS => check(
+                        LimitKind::StringLength,
+                        sheet,
+                        location(),
+                        s.chars().count(),
+                    ),
+                    CellValue::Formula(f) if f.len() > MAX_FORMULA_CHARS => check(
+                        LimitKind::FormulaLength,
+                        sheet,
+                        location(),
+                        f.chars().count(),
+                    ),
+                    _ => {}
+                }
+                hyperlinks += usize::from(cell.hyperlink.is_some());
+            }
+            check(LimitKind::HyperlinksPerSheet, sheet, None, hyperlinks);
+            check(
+                LimitKind::MergedRangesPerSheet,
+                sheet,
+                None,
+                ws.merged_cells.len(),
+            );
+            check(LimitKind::Rows, sheet, None, ws.max_row() as usize);
+            check(LimitKind::Columns, sheet, None, ws.max_column() as usize);
+        }
+
+        let (styles, _) = self.resolve_styles_for_save();
+        check(LimitKind::CellFormats, None, None, styles.cell_xfs.len());
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{CellStyle, Font};
+
+    #[test]
+    fn test_within_limits() {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+        ws.set_cell_value(1, 1, "ok");
+        ws.set_cell_value(2, 1, CellValue::Formula("SUM(A1:A10)".to_string()));
+        assert!(wb.check_limits().is_empty());
+    }
+
+    #[test]
+    fn test_reports_each_limit() {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+        ws.set_cell_value(3, 2, "x".repeat(MAX_STRING_CHARS + 1));
+        ws.set_cell_value(4, 2, CellValue::Formula("1+".repeat(5_000) + "1"));
+        ws.set_cell_value(1, MAX_COLUMNS as u32 + 1, 1.0);
+        wb.rename_sheet("Data", "A sheet name that is far too long")
+            .unwrap();
+
+        let found = wb.check_limits();
+        let kinds: Vec<LimitKind> = found.iter().map(|v| v.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                LimitKind::SheetNameLength,
+                LimitKind::StringLength,
+                LimitKind::FormulaLength,
+                LimitKind::Columns,
+            ]
+        );
+        assert_eq!(found[1].location.as_deref(), Some("B3"));
+        assert_eq!(found[1].actual, MAX_STRING_CHARS + 1);
+        assert_eq!(
+            found[2].to_string(),
+            "formula_length A sheet name that is far too long!B4: 10001 exceeds Excel's maximum of 8192"
+        );
+    }
+
+    #[test]
+    fn test_counts_formats_that_save_would_create() {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+        // One format short of the limit in the registry, plus two distinct
+        // styles that only get their xfs when saved.
+        ws.set_cell_style(1, 1, CellStyle::new().with_font(Font::new().with_size(9.0)));
+        ws.set_cell_style(
+            2,
+            1,
+            CellStyle::new().with_font(Font::new().with_size(10.0)),
+        );
+        let filler = wb.styles.cell_xfs[0].clone();
+        wb.styles.cell_xfs.resize(MAX_CELL_FORMATS - 1, filler);
+
+        let found = wb.check_limits();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, LimitKind::CellFormats);
+        assert_eq!(found[0].actual, MAX_CELL_FORMATS + 1);
+    }
+}
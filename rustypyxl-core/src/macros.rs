@@ -0,0 +1,230 @@
+//! Macro-enabled workbooks (`.xlsm`, `.xltm`).
+//!
+//! A VBA project is an unmodeled part kept in [`Workbook::preserved`], and
+//! Excel 4 macro sheets are kept as opaque sheets, so both survive a
+//! load/save round-trip. What the writer has to get right is the workbook
+//! part's content type, which Excel checks against the file extension: a
+//! macro-enabled package saved as `.xlsx` does not open, and neither does a
+//! plain one saved as `.xlsm`.
+
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::path::Path;
+
+use crate::error::{Result, RustypyxlError};
+use crate::passthrough::{rels_path_for, XLSX_WORKBOOK_CONTENT_TYPE};
+use crate::workbook::{resolve_rel_target, Workbook};
+
+/// Main part content type of a macro-enabled workbook.
+const XLSM_WORKBOOK_CONTENT_TYPE: &str = "application/vnd.ms-excel.sheet.macroEnabled.main+xml";
+/// Main part content type of a template.
+const XLTX_WORKBOOK_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.template.main+xml";
+/// Main part content type of a macro-enabled template.
+const XLTM_WORKBOOK_CONTENT_TYPE: &str = "application/vnd.ms-excel.template.macroEnabled.main+xml";
+
+/// Relationship type of the workbook's VBA project.
+const VBA_PROJECT_REL: &str = "http://schemas.microsoft.com/office/2006/relationships/vbaProject";
+
+/// Fail when a workbook with macros is about to be written to a file whose
+/// extension promises there are none.
+pub(crate) fn check_macro_extension(path: &Path, has_macros: bool) -> Result<()> {
+    match extension(path).as_deref() {
+        Some(ext @ ("xlsx" | "xltx")) if has_macros => Err(RustypyxlError::custom(format!(
+            "Workbook contains macros, which a .{} file cannot hold; save it as .{} \
+             or remove the VBA project first",
+            ext,
+            if ext == "xltx" { "xltm" } else { "xlsm" }
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+}
+
+impl Workbook {
+    /// Whether the workbook carries a VBA project.
+    pub fn has_vba(&self) -> bool {
+        self.preserved
+            .rels
+            .iter()
+            .any(|rel| rel.rel_type == VBA_PROJECT_REL)
+    }
+
+    /// Whether the workbook has macros of any kind: a VBA project or Excel 4
+    /// macro sheets. Such a workbook can only be saved as `.xlsm` or `.xltm`.
+    pub fn has_macros(&self) -> bool {
+        self.has_vba()
+            || self.worksheets.iter().any(|ws| {
+                ws.opaque.as_ref().is_some_and(|opaque| {
+                    opaque.kind != crate::opaque_sheet::OpaqueSheetKind::Dialogsheet
+                })
+            })
+    }
+
+    /// Drop the VBA project (and its signature parts), as openpyxl does
+    /// unless a workbook is loaded with `keep_vba=True`. A macro-enabled
+    /// content type reverts to the plain one unless macro sheets remain.
+    pub fn remove_vba(&mut self) {
+        let mut doomed: HashSet<String> = HashSet::new();
+        let mut pending: Vec<String> = self
+            .preserved
+            .rels
+            .iter()
+            .filter(|rel| rel.rel_type == VBA_PROJECT_REL && !rel.external)
+            .map(|rel| resolve_rel_target("xl/workbook.xml", &rel.target))
+            .collect();
+        while let Some(path) = pending.pop() {
+            if !doomed.insert(path.clone()) {
+                continue;
+            }
+            let rels_path = rels_path_for(&path);
+            if let Some(rels) = self.preserved.parts.iter().find(|p| p.path == rels_path) {
+                let targets = Self::parse_sheet_rels(Cursor::new(&rels.bytes)).unwrap_or_default();
+                pending.extend(
+                    targets
+                        .values()
+                        .filter(|rel| !rel.external)
+                        .map(|rel| resolve_rel_target(&path, &rel.target)),
+                );
+            }
+            doomed.insert(rels_path);
+        }
+        self.preserved.parts.retain(|p| !doomed.contains(&p.path));
+        self.preserved
+            .rels
+            .retain(|rel| rel.rel_type != VBA_PROJECT_REL);
+
+        if !self.has_macros() {
+            self.preserved.workbook_content_type =
+                match self.preserved.workbook_content_type.as_deref() {
+                    Some(XLTM_WORKBOOK_CONTENT_TYPE) => {
+                        Some(XLTX_WORKBOOK_CONTENT_TYPE.to_string())
+                    }
+                    Some(ct) if ct.contains("macroEnabled") => None,
+                    other => other.map(str::to_string),
+                };
+        }
+    }
+
+    /// The workbook part content type for a save with no file name: the
+    /// loaded one, or the macro-enabled one when there are macros.
+    pub(crate) fn main_content_type(&self) -> &str {
+        match self.preserved.workbook_content_type.as_deref() {
+            Some(ct) => ct,
+            None if self.has_macros() => XLSM_WORKBOOK_CONTENT_TYPE,
+            None => XLSX_WORKBOOK_CONTENT_TYPE,
+        }
+    }
+
+    /// The file extension that goes with [`main_content_type`]
+    /// (`xlsx`, `xltx`, `xlsm` or `xltm`), for saves that pick their own
+    /// file name.
+    ///
+    /// [`main_content_type`]: Self::main_content_type
+    pub(crate) fn main_extension(&self) -> &'static str {
+        match self.main_content_type() {
+            XLTX_WORKBOOK_CONTENT_TYPE => "xltx",
+            XLSM_WORKBOOK_CONTENT_TYPE => "xlsm",
+            XLTM_WORKBOOK_CONTENT_TYPE => "xltm",
+            _ => "xlsx",
+        }
+    }
+
+    /// The workbook part content type for a save to `path`, which Excel
+    /// requires to agree with the extension. Fails for a `.xlsx`/`.xltx`
+    /// target when the workbook has macros.
+    pub(crate) fn main_content_type_for(&self, path: &Path) -> Result<&str> {
+        check_macro_extension(path, self.has_macros())?;
+        Ok(match extension(path).as_deref() {
+            Some("xlsx") => XLSX_WORKBOOK_CONTENT_TYPE,
+            Some("xltx") => XLTX_WORKBOOK_CONTENT_TYPE,
+            Some("xlsm") => XLSM_WORKBOOK_CONTENT_TYPE,
+            Some("xltm") => XLTM_WORKBOOK_CONTENT_TYPE,
+            _ => self.main_content_type(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::passthrough::{PreservedPart, PreservedWorkbookRel};
+
+    fn with_vba() -> Workbook {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.preserved.rels.push(PreservedWorkbookRel {
+            id: "rId9".to_string(),
+            rel_type: VBA_PROJECT_REL.to_string(),
+            target: "vbaProject.bin".to_string(),
+            external: false,
+        });
+        wb.preserved.parts.push(PreservedPart {
+            path: "xl/vbaProject.bin".to_string(),
+            bytes: vec![0xD0, 0xCF, 0x11, 0xE0],
+            content_type: Some("application/vnd.ms-office.vbaProject".to_string()),
+        });
+        wb.preserved.parts.push(PreservedPart {
+            path: "xl/_rels/vbaProject.bin.rels".to_string(),
+            bytes: br#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.microsoft.com/office/2006/relationships/vbaProjectSignature" Target="vbaProjectSignature.bin"/></Relationships>"#.to_vec(),
+            content_type: None,
+        });
+        wb.preserved.parts.push(PreservedPart {
+            path: "xl/vbaProjectSignature.bin".to_string(),
+            bytes: vec![1, 2, 3],
+            content_type: Some("application/vnd.ms-office.vbaProjectSignature".to_string()),
+        });
+        wb.preserved.parts.push(PreservedPart {
+            path: "xl/theme/theme1.xml".to_string(),
+            bytes: b"<a:theme/>".to_vec(),
+            content_type: Some("application/vnd.openxmlformats-officedocument.theme+xml".into()),
+        });
+        wb.preserved.workbook_content_type = Some(XLSM_WORKBOOK_CONTENT_TYPE.to_string());
+        wb
+    }
+
+    #[test]
+    fn content_type_follows_extension() {
+        let plain = Workbook::new();
+        assert_eq!(
+            plain.main_content_type_for(Path::new("out.XLSM")).unwrap(),
+            XLSM_WORKBOOK_CONTENT_TYPE
+        );
+        assert_eq!(
+            plain.main_content_type_for(Path::new("out.xlsx")).unwrap(),
+            XLSX_WORKBOOK_CONTENT_TYPE
+        );
+        assert_eq!(plain.main_content_type(), XLSX_WORKBOOK_CONTENT_TYPE);
+
+        let macros = with_vba();
+        assert!(macros.has_vba() && macros.has_macros());
+        assert_eq!(
+            macros.main_content_type_for(Path::new("out")).unwrap(),
+            XLSM_WORKBOOK_CONTENT_TYPE
+        );
+        assert_eq!(
+            macros.main_content_type_for(Path::new("out.xltm")).unwrap(),
+            XLTM_WORKBOOK_CONTENT_TYPE
+        );
+        let err = macros
+            .main_content_type_for(Path::new("out.xlsx"))
+            .unwrap_err();
+        assert!(err.to_string().contains(".xlsm"), "{err}");
+    }
+
+    #[test]
+    fn remove_vba_drops_project_and_signature() {
+        let mut wb = with_vba();
+        wb.remove_vba();
+        assert!(!wb.has_vba());
+        let kept: Vec<&str> = wb.preserved.parts.iter().map(|p| p.path.as_str()).collect();
+        assert_eq!(kept, ["xl/theme/theme1.xml"]);
+        assert!(wb.preserved.rels.is_empty());
+        assert_eq!(wb.preserved.workbook_content_type, None);
+        assert!(wb.main_content_type_for(Path::new("out.xlsx")).is_ok());
+    }
+}
@@ -0,0 +1,434 @@
+//! Named cell styles: the `<cellStyles>` of styles.xml.
+//!
+//! Excel's style gallery ("Normal", "Heading 1", "Good", ...) and styles a
+//! user defines are each stored as a cellStyleXfs entry plus a `<cellStyle>`
+//! that names it, and every cell format links to one through `xfId`. A cell
+//! given a named style takes all of its formatting and keeps the link, so
+//! Excel shows the style as applied. Built-in styles are added to the
+//! workbook the first time a cell uses them, as in openpyxl.
+//!
+//! These are unrelated to the JSON palette styles of
+//! [`StylePalette`](crate::StylePalette), which are applied to cells but
+//! never written to the file.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::cell::InternedString;
+use crate::error::{Result, RustypyxlError};
+use crate::style::{Border, BorderStyle, CellStyle, CellXf, Color, Fill, Font, StyleRegistry};
+use crate::workbook::Workbook;
+
+/// A named cell style.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NamedStyle {
+    /// Name shown in Excel's style gallery.
+    pub name: String,
+    /// The formatting a cell takes when given this style.
+    pub style: CellStyle,
+    /// Excel's id for a built-in style ("Normal" is 0, "Heading 1" 16);
+    /// None for a custom style.
+    pub builtin_id: Option<u32>,
+    /// Left out of Excel's style gallery.
+    pub hidden: bool,
+}
+
+impl NamedStyle {
+    /// A custom style.
+    pub fn new<S: Into<String>>(name: S, style: CellStyle) -> Self {
+        NamedStyle {
+            name: name.into(),
+            style,
+            builtin_id: None,
+            hidden: false,
+        }
+    }
+
+    /// The "Normal" style every workbook has.
+    pub fn normal() -> Self {
+        NamedStyle::new("Normal", CellStyle::new()).with_builtin_id(0)
+    }
+
+    /// Mark this as Excel's built-in style `id`.
+    pub fn with_builtin_id(mut self, id: u32) -> Self {
+        self.builtin_id = Some(id);
+        self
+    }
+
+    /// One of Excel's built-in styles, by its gallery name ("Good", "Total",
+    /// "Heading 1" or openpyxl's "Headline 1", ...), formatted as in the
+    /// default Office theme.
+    pub fn builtin(name: &str) -> Option<Self> {
+        let id = BUILTIN_IDS
+            .iter()
+            .find(|(builtin, _)| builtin.eq_ignore_ascii_case(name))
+            .map(|&(_, id)| id)
+            .or_else(|| {
+                let level = name.strip_prefix("Heading ")?.parse::<u32>().ok()?;
+                (1..=4).contains(&level).then_some(15 + level)
+            })?;
+        Some(NamedStyle::new(name, builtin_style(id)).with_builtin_id(id))
+    }
+}
+
+/// Built-in style names, as openpyxl spells them, and their Excel ids.
+const BUILTIN_IDS: [(&str, u32); 26] = [
+    ("Normal", 0),
+    ("Comma", 3),
+    ("Currency", 4),
+    ("Percent", 5),
+    ("Comma [0]", 6),
+    ("Currency [0]", 7),
+    ("Hyperlink", 8),
+    ("Followed Hyperlink", 9),
+    ("Note", 10),
+    ("Warning Text", 11),
+    ("Title", 15),
+    ("Headline 1", 16),
+    ("Headline 2", 17),
+    ("Headline 3", 18),
+    ("Headline 4", 19),
+    ("Input", 20),
+    ("Output", 21),
+    ("Calculation", 22),
+    ("Check Cell", 23),
+    ("Linked Cell", 24),
+    ("Total", 25),
+    ("Good", 26),
+    ("Bad", 27),
+    ("Neutral", 28),
+    ("Explanatory Text", 53),
+    ("Accent1", 29),
+];
+
+fn body_font() -> Font {
+    Font::new().with_name("Calibri").with_size(11.0)
+}
+
+fn boxed(style: &str, color: &str) -> Border {
+    Border::all(BorderStyle::new(style).with_color(color))
+}
+
+/// The formatting of built-in style `id` in the Office theme.
+fn builtin_style(id: u32) -> CellStyle {
+    let heading = |size| {
+        body_font()
+            .with_size(size)
+            .with_bold(true)
+            .with_color(Color::theme(3))
+    };
+    let underlined = |style: fn() -> BorderStyle, tint: Option<f64>| {
+        let mut color = Color::theme(4);
+        color.tint = tint;
+        Border::new().with_bottom(style().with_color(color))
+    };
+    let style = CellStyle::new();
+    match id {
+        3 => style.with_number_format(r#"_(* #,##0.00_);_(* \(#,##0.00\);_(* "-"??_);_(@_)"#),
+        4 => style
+            .with_number_format(r#"_("$"* #,##0.00_);_("$"* \(#,##0.00\);_("$"* "-"??_);_(@_)"#),
+        5 => style.with_number_format("0%"),
+        6 => style.with_number_format(r#"_(* #,##0_);_(* \(#,##0\);_(* "-"_);_(@_)"#),
+        7 => style.with_number_format(r#"_("$"* #,##0_);_("$"* \(#,##0\);_("$"* "-"_);_(@_)"#),
+        8 => style.with_font(
+            body_font()
+                .with_color(Color::theme(10))
+                .with_underline("single"),
+        ),
+        9 => style.with_font(
+            body_font()
+                .with_color(Color::theme(11))
+                .with_underline("single"),
+        ),
+        10 => style
+            .with_fill(Fill::solid("FFFFFFCC"))
+            .with_border(boxed("thin", "FFB2B2B2")),
+        11 => style.with_font(body_font().with_color("FFFF0000")),
+        15 => style.with_font(
+            Font::new()
+                .with_name("Calibri Light")
+                .with_size(18.0)
+                .with_color(Color::theme(3)),
+        ),
+        16 => style
+            .with_font(heading(15.0))
+            .with_border(underlined(BorderStyle::thick, None)),
+        17 => style
+            .with_font(heading(13.0))
+            .with_border(underlined(BorderStyle::thick, Some(0.499984740745262))),
+        18 => style
+            .with_font(heading(11.0))
+            .with_border(underlined(BorderStyle::medium, Some(0.399975585192419))),
+        19 => style.with_font(heading(11.0)),
+        20 => style
+            .with_font(body_font().with_color("FF3F3F76"))
+            .with_fill(Fill::solid("FFFFCC99"))
+            .with_border(boxed("thin", "FF7F7F7F")),
+        21 => style
+            .with_font(body_font().with_bold(true).with_color("FF3F3F3F"))
+            .with_fill(Fill::solid("FFF2F2F2"))
+            .with_border(boxed("thin", "FF3F3F3F")),
+        22 => style
+            .with_font(body_font().with_bold(true).with_color("FFFA7D00"))
+            .with_fill(Fill::solid("FFF2F2F2"))
+            .with_border(boxed("thin", "FF7F7F7F")),
+        23 => style
+            .with_font(body_font().with_bold(true).with_color(Color::theme(0)))
+            .with_fill(Fill::solid("FFA5A5A5"))
+            .with_border(boxed("double", "FF3F3F3F")),
+        24 => style
+            .with_font(body_font().with_color("FFFA7D00"))
+            .with_border(
+                Border::new().with_bottom(BorderStyle::new("double").with_color("FFFF8001")),
+            ),
+        25 => style
+            .with_font(body_font().with_bold(true).with_color(Color::theme(1)))
+            .with_border(
+                Border::new()
+                    .with_top(BorderStyle::thin().with_color(Color::theme(4)))
+                    .with_bottom(BorderStyle::new("double").with_color(Color::theme(4))),
+            ),
+        26 => style
+            .with_font(body_font().with_color("FF006100"))
+            .with_fill(Fill::solid("FFC6EFCE")),
+        27 => style
+            .with_font(body_font().with_color("FF9C0006"))
+            .with_fill(Fill::solid("FFFFC7CE")),
+        28 => style
+            .with_font(body_font().with_color("FF9C5700"))
+            .with_fill(Fill::solid("FFFFEB9C")),
+        29 => style
+            .with_font(body_font().with_color(Color::theme(0)))
+            .with_fill(Fill::solid(Color::theme(4))),
+        53 => style.with_font(body_font().with_italic(true).with_color("FF7F7F7F")),
+        _ => style,
+    }
+}
+
+/// A `<cellStyle>` element as read from styles.xml.
+#[derive(Clone, Debug)]
+pub(crate) struct CellStyleEntry {
+    name: String,
+    xf_id: u32,
+    builtin_id: Option<u32>,
+    hidden: bool,
+}
+
+impl CellStyleEntry {
+    pub(crate) fn from_element(e: &quick_xml::events::BytesStart) -> Self {
+        let mut entry = CellStyleEntry {
+            name: String::new(),
+            xf_id: 0,
+            builtin_id: None,
+            hidden: false,
+        };
+        for attr in e.attributes().flatten() {
+            let value = attr
+                .unescape_value()
+                .map(|v| v.into_owned())
+                .unwrap_or_default();
+            match attr.key.local_name().as_ref() {
+                b"name" => entry.name = value,
+                b"xfId" => entry.xf_id = value.parse().unwrap_or(0),
+                b"builtinId" => entry.builtin_id = value.parse().ok(),
+                b"hidden" => entry.hidden = value == "1" || value == "true",
+                _ => {}
+            }
+        }
+        entry
+    }
+}
+
+impl StyleRegistry {
+    /// The named style called `name`.
+    pub fn named_style(&self, name: &str) -> Option<&NamedStyle> {
+        self.named_styles.iter().find(|ns| ns.name == name)
+    }
+
+    /// Position of the named style called `name` in
+    /// [`StyleRegistry::named_styles`].
+    pub fn named_style_index(&self, name: &str) -> Option<usize> {
+        self.named_styles.iter().position(|ns| ns.name == name)
+    }
+
+    /// Add a named style; its name must not be taken.
+    pub fn add_named_style(&mut self, style: NamedStyle) -> Result<usize> {
+        if style.name.trim().is_empty() {
+            return Err(RustypyxlError::custom("A named style needs a name"));
+        }
+        if self.named_style_index(&style.name).is_some() {
+            return Err(RustypyxlError::custom(format!(
+                "Style {} exists already",
+                style.name
+            )));
+        }
+        if self.named_styles.is_empty() && style.builtin_id != Some(0) {
+            self.named_styles.push(NamedStyle::normal());
+        }
+        self.named_styles.push(style);
+        Ok(self.named_styles.len() - 1)
+    }
+
+    /// Position of the named style `name`, adding it first when it is a
+    /// built-in style the workbook does not have yet.
+    pub(crate) fn resolve_named_style(&mut self, name: &str) -> Result<usize> {
+        if let Some(idx) = self.named_style_index(name) {
+            return Ok(idx);
+        }
+        match NamedStyle::builtin(name) {
+            Some(style) => self.add_named_style(style),
+            None => Err(RustypyxlError::custom(format!("No named style '{}'", name))),
+        }
+    }
+
+    /// Fill [`StyleRegistry::named_styles`] from a file's cellStyleXfs and
+    /// `<cellStyle>` entries, "Normal" first. Returns the name of each
+    /// other style by its xfId in the file.
+    pub(crate) fn read_named_styles(
+        &mut self,
+        style_xfs: &[CellStyle],
+        entries: &[CellStyleEntry],
+    ) -> HashMap<u32, InternedString> {
+        self.named_styles.clear();
+        let is_normal = |e: &CellStyleEntry| e.builtin_id == Some(0);
+        let normal = entries
+            .iter()
+            .find(|e| is_normal(e))
+            .or_else(|| entries.iter().find(|e| e.name == "Normal"));
+        let mut names = HashMap::new();
+        for entry in normal.into_iter().chain(entries.iter()) {
+            if entry.name.is_empty() || self.named_style_index(&entry.name).is_some() {
+                continue;
+            }
+            let first = self.named_styles.is_empty();
+            self.named_styles.push(NamedStyle {
+                name: entry.name.clone(),
+                style: style_xfs
+                    .get(entry.xf_id as usize)
+                    .cloned()
+                    .unwrap_or_default(),
+                builtin_id: entry.builtin_id,
+                hidden: entry.hidden,
+            });
+            if !first {
+                names
+                    .entry(entry.xf_id)
+                    .or_insert_with(|| Arc::from(entry.name.as_str()));
+            }
+        }
+        if normal.is_none() {
+            self.named_styles.insert(0, NamedStyle::normal());
+        }
+        names
+    }
+
+    /// The cellStyleXfs entries for [`StyleRegistry::named_styles`], in
+    /// order, registering their fonts, fills, borders and formats.
+    pub(crate) fn cell_style_xfs(&mut self) -> Vec<CellXf> {
+        let styles: Vec<CellStyle> = if self.named_styles.is_empty() {
+            vec![CellStyle::new()]
+        } else {
+            self.named_styles
+                .iter()
+                .map(|ns| ns.style.clone())
+                .collect()
+        };
+        styles
+            .iter()
+            .map(|style| CellXf {
+                xf_id: 0,
+                apply_font: false,
+                apply_fill: false,
+                apply_border: false,
+                apply_number_format: false,
+                apply_alignment: false,
+                apply_protection: false,
+                ..self.build_xf(style)
+            })
+            .collect()
+    }
+}
+
+impl Workbook {
+    /// Add a named cell style to the workbook. Errors when the name is
+    /// taken.
+    pub fn add_named_style(&mut self, style: NamedStyle) -> Result<()> {
+        self.styles.add_named_style(style).map(|_| ())
+    }
+
+    /// Give a cell the named style `name`: the cell takes all of the style's
+    /// formatting, replacing its own, and is linked to the style. Built-in
+    /// styles ("Good", "Heading 1", ...) are added on first use.
+    pub fn set_cell_named_style(
+        &mut self,
+        sheet_name: &str,
+        row: u32,
+        column: u32,
+        name: &str,
+    ) -> Result<()> {
+        self.get_sheet_by_name(sheet_name)?;
+        let idx = self.styles.resolve_named_style(name)?;
+        let named = &self.styles.named_styles[idx];
+        let mut style = named.style.clone();
+        style.named_style = (idx != 0).then(|| Arc::from(named.name.as_str()));
+        let xf_index = self.styles.get_or_add_cell_xf(&style) as u32;
+
+        let cell = self
+            .get_sheet_by_name_mut(sheet_name)?
+            .get_or_create_cell_mut(row, column);
+        cell.style = Some(Arc::new(style));
+        cell.style_index = Some(xf_index);
+        cell.number_format = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtins_by_name() {
+        let good = NamedStyle::builtin("Good").unwrap();
+        assert_eq!(good.builtin_id, Some(26));
+        assert_eq!(
+            good.style.fill.unwrap().fg_color.unwrap().argb().as_deref(),
+            Some("FFC6EFCE")
+        );
+        let heading = NamedStyle::builtin("Heading 1").unwrap();
+        assert_eq!(heading.name, "Heading 1");
+        assert_eq!(heading.builtin_id, Some(16));
+        assert_eq!(
+            NamedStyle::builtin("Headline 1").unwrap().builtin_id,
+            Some(16)
+        );
+        assert!(NamedStyle::builtin("Heading 9").is_none());
+        assert!(NamedStyle::builtin("Fancy").is_none());
+    }
+
+    #[test]
+    fn registry_keeps_normal_first_and_rejects_duplicates() {
+        let mut styles = StyleRegistry::new();
+        assert_eq!(styles.named_styles[0].name, "Normal");
+        let idx = styles
+            .add_named_style(NamedStyle::new(
+                "Key",
+                CellStyle::new().with_number_format("0.0"),
+            ))
+            .unwrap();
+        assert_eq!(idx, 1);
+        assert!(styles
+            .add_named_style(NamedStyle::new("Key", CellStyle::new()))
+            .is_err());
+        assert_eq!(styles.resolve_named_style("Total").unwrap(), 2);
+        assert!(styles.resolve_named_style("Unknown").is_err());
+
+        let linked = CellStyle {
+            named_style: Some(Arc::from("Key")),
+            ..CellStyle::new().with_number_format("0.0")
+        };
+        let xf = styles.get_or_add_cell_xf(&linked);
+        assert_eq!(styles.cell_xfs[xf].xf_id, 1);
+        assert_eq!(styles.get_cell_style(xf).unwrap(), linked);
+    }
+}
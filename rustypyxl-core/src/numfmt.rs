@@ -6,10 +6,12 @@
 //!
 //! Scope is the common subset: the positive;negative;zero;text sections, digit
 //! placeholders (`0` `#` `?`), thousands grouping and decimals, `%`, scaling
-//! commas, quoted/escaped literals, currency (`[$…]`), and date/time tokens
-//! (`yyyy mm dd hh ss AM/PM` plus elapsed `[h] [m] [s]`). Exotic constructs
-//! (fractions `?/?`, scientific `E+`, literals interleaved between digits like
-//! SSN masks) are not covered and fall back to a best effort.
+//! commas, quoted/escaped literals, currency (`[$…]`), scientific notation
+//! (`0.00E+00`, engineering `##0.0E+0`), fractions (`# ?/?`, `# ??/??`,
+//! fixed denominators like `# ?/8`) and date/time tokens (`yyyy mm dd hh ss
+//! AM/PM` plus elapsed `[h] [m] [s]`). Exotic constructs (literals
+//! interleaved between digits like SSN masks) are not covered and fall back
+//! to a best effort.
 
 use crate::cell::CellValue;
 
@@ -69,7 +71,10 @@ pub fn format_value(value: &CellValue, code: &str) -> String {
             }
         }
         CellValue::String(s) => format_text(s, code),
-        CellValue::Date(s) => s.clone(),
+        // ISO text under General; a date format renders it like a serial,
+        // which format_number counts in the 1900 system.
+        CellValue::Date(d) if is_date_format(code) => format_number(d.to_excel(false), code),
+        CellValue::Date(d) => d.to_string(),
         CellValue::Formula(f) => f.clone(),
         CellValue::Empty => String::new(),
     }
@@ -138,10 +143,23 @@ pub fn format_number(value: f64, code: &str) -> String {
     if force_negative && value != 0.0 {
         out.push('-');
     }
-    out.push_str(&render_numeric(value.abs(), &section));
+    let body = render_scientific(value.abs(), &section)
+        .or_else(|| render_fraction(value.abs(), &section))
+        .unwrap_or_else(|| render_numeric(value.abs(), &section));
+    out.push_str(&body);
     out
 }
 
+/// Whether a format code renders numbers as dates or times.
+pub fn is_date_format(code: &str) -> bool {
+    if code.is_empty() || code.eq_ignore_ascii_case("general") {
+        return false;
+    }
+    split_sections(code)
+        .first()
+        .is_some_and(|section| is_datetime_section(section))
+}
+
 /// Excel's General format: shortest round-tripping representation, integers
 /// without a decimal point.
 fn format_general(value: f64) -> String {
@@ -321,7 +339,8 @@ fn render_numeric(value: f64, section: &str) -> String {
             && int_ph_positions.first().is_some_and(|&f| i > f)
             && int_ph_positions.last().is_some_and(|&l| i < l)
     });
-    // Trailing commas after the last integer placeholder scale the value down.
+    // Trailing commas after the last integer placeholder scale the value down,
+    // as do commas after the last decimal placeholder ("0.0,," is millions).
     let trailing_commas = match int_ph_positions.last() {
         Some(&last) => toks
             .iter()
@@ -330,6 +349,13 @@ fn render_numeric(value: f64, section: &str) -> String {
             .filter(|t| matches!(t, NumTok::Comma))
             .count(),
         None => 0,
+    } + match toks.iter().rposition(|t| matches!(t, NumTok::Ph(_))) {
+        Some(last) if last > int_end => toks
+            .iter()
+            .skip(last + 1)
+            .filter(|t| matches!(t, NumTok::Comma))
+            .count(),
+        _ => 0,
     };
 
     let mut scaled = value;
@@ -477,6 +503,239 @@ fn assemble(
     out
 }
 
+// ------- scientific / fractions -------
+
+/// Byte offsets of the characters of a section that are format syntax rather
+/// than quoted, escaped or bracketed literals.
+fn unquoted_positions(section: &str) -> Vec<(usize, char)> {
+    let mut out = Vec::new();
+    let mut chars = section.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                for (_, q) in chars.by_ref() {
+                    if q == '"' {
+                        break;
+                    }
+                }
+            }
+            '\\' | '_' | '*' => {
+                chars.next();
+            }
+            '[' => {
+                for (_, b) in chars.by_ref() {
+                    if b == ']' {
+                        break;
+                    }
+                }
+            }
+            _ => out.push((i, c)),
+        }
+    }
+    out
+}
+
+fn is_placeholder(c: char) -> bool {
+    matches!(c, '0' | '#' | '?')
+}
+
+/// Literal text of a section fragment, with quotes and escapes removed.
+fn literal_text(fragment: &str) -> String {
+    tokenize_numeric(fragment)
+        .into_iter()
+        .filter_map(|t| match t {
+            NumTok::Lit(c) => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Render a non-negative number under a scientific section (`0.00E+00`),
+/// or None when the section has no exponent. With `#` among several integer
+/// placeholders (`##0.0E+0`) the exponent is a multiple of their count.
+fn render_scientific(value: f64, section: &str) -> Option<String> {
+    let positions = unquoted_positions(section);
+    let (e_idx, e_char) =
+        positions
+            .iter()
+            .zip(positions.iter().skip(1))
+            .find_map(|(&(i, c), &(_, next))| {
+                (matches!(c, 'E' | 'e') && matches!(next, '+' | '-')).then_some((i, c))
+            })?;
+    let mantissa_section = &section[..e_idx];
+    let sign_char = section[e_idx + 1..].chars().next()?;
+    let exponent_part = &section[e_idx + 2..];
+    let exponent_width = exponent_part
+        .chars()
+        .take_while(|&c| is_placeholder(c))
+        .count();
+    let exponent_zeros = exponent_part
+        .chars()
+        .take(exponent_width)
+        .filter(|&c| c == '0')
+        .count();
+    let rest = literal_text(&exponent_part[exponent_width..]);
+
+    let toks = tokenize_numeric(mantissa_section);
+    let dot = toks.iter().position(|t| matches!(t, NumTok::Dot));
+    let int_tokens = &toks[..dot.unwrap_or(toks.len())];
+    let int_places = int_tokens
+        .iter()
+        .filter(|t| matches!(t, NumTok::Ph(_)))
+        .count()
+        .max(1) as i32;
+    let frac_places = dot.map_or(0, |d| {
+        toks[d + 1..]
+            .iter()
+            .filter(|t| matches!(t, NumTok::Ph(_)))
+            .count()
+    }) as i32;
+    let engineering = int_places > 1 && int_tokens.iter().any(|t| matches!(t, NumTok::Ph('#')));
+
+    let magnitude = if value == 0.0 {
+        0
+    } else {
+        value.log10().floor() as i32
+    };
+    let (mut exponent, step) = if engineering {
+        (magnitude.div_euclid(int_places) * int_places, int_places)
+    } else {
+        (magnitude - (int_places - 1), 1)
+    };
+    // Rounding the mantissa can carry it into the next power of ten.
+    let factor = 10f64.powi(frac_places);
+    let limit = 10f64.powi(int_places);
+    let rounded = |exponent: i32| (value / 10f64.powi(exponent) * factor).round() / factor;
+    if value != 0.0 && rounded(exponent) >= limit {
+        exponent += step;
+    }
+    let mantissa = if value == 0.0 { 0.0 } else { rounded(exponent) };
+
+    let mut out = render_numeric(mantissa, mantissa_section);
+    out.push(e_char);
+    if exponent < 0 {
+        out.push('-');
+    } else if sign_char == '+' {
+        out.push('+');
+    }
+    out.push_str(&format!(
+        "{:0width$}",
+        exponent.unsigned_abs(),
+        width = exponent_zeros
+    ));
+    out.push_str(&rest);
+    Some(out)
+}
+
+/// The fraction with denominator at most `max_denominator` closest to
+/// `value`, preferring the smallest denominator on ties.
+fn closest_fraction(value: f64, max_denominator: u64) -> (u64, u64) {
+    let mut best = (value.round() as u64, 1);
+    let mut best_err = (value - value.round()).abs();
+    for denominator in 2..=max_denominator {
+        let numerator = (value * denominator as f64).round();
+        let err = (value - numerator / denominator as f64).abs();
+        if err < best_err - f64::EPSILON {
+            best = (numerator as u64, denominator);
+            best_err = err;
+        }
+    }
+    best
+}
+
+/// Render a non-negative number under a fraction section (`# ?/?`,
+/// `?/??`, `# ?/8`), or None when the section is not a fraction.
+fn render_fraction(value: f64, section: &str) -> Option<String> {
+    let positions = unquoted_positions(section);
+    let slash = positions.iter().find(|&&(_, c)| c == '/')?.0;
+    let (left, right) = (&section[..slash], &section[slash + 1..]);
+
+    // Numerator: the placeholder run ending at the slash.
+    let numerator_width = left
+        .chars()
+        .rev()
+        .take_while(|&c| is_placeholder(c))
+        .count();
+    if numerator_width == 0 {
+        return None;
+    }
+    let numerator_ph: String = left[left.len() - numerator_width..].to_string();
+    let whole_section = &left[..left.len() - numerator_width];
+    let has_whole = whole_section.chars().any(is_placeholder);
+
+    // Denominator: fixed digits, or a placeholder run setting its width.
+    let fixed_digits: String = right.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let fixed = fixed_digits
+        .parse::<u64>()
+        .ok()
+        .filter(|&d| d > 0 && !fixed_digits.starts_with('0'));
+    let denominator_width = match fixed {
+        Some(_) => fixed_digits.len(),
+        None => right.chars().take_while(|&c| is_placeholder(c)).count(),
+    };
+    if denominator_width == 0 {
+        return None;
+    }
+    let denominator_ph = &right[..denominator_width];
+    let rest = literal_text(&right[denominator_width..]);
+
+    let (mut whole, fraction) = if has_whole {
+        (value.trunc(), value.fract())
+    } else {
+        (0.0, value)
+    };
+    let (mut numerator, denominator) = match fixed {
+        Some(d) => ((fraction * d as f64).round() as u64, d),
+        None => {
+            let max = 10u64.pow(denominator_width.min(4) as u32) - 1;
+            closest_fraction(fraction, max)
+        }
+    };
+    if has_whole && numerator == denominator {
+        whole += 1.0;
+        numerator = 0;
+    }
+
+    let pad = |text: String, placeholders: &str, left_align: bool| -> String {
+        let width = placeholders.chars().count();
+        let fill = if placeholders.contains('?') {
+            ' '
+        } else if placeholders.chars().all(|c| c == '0') {
+            '0'
+        } else {
+            return text;
+        };
+        let padding: String = std::iter::repeat_n(fill, width.saturating_sub(text.len())).collect();
+        if left_align {
+            text + &padding
+        } else {
+            padding + &text
+        }
+    };
+
+    let mut out = String::new();
+    if has_whole && numerator == 0 {
+        // A whole number shows blanks where the fraction would be.
+        out.push_str(&render_numeric(whole, &whole_section.replace('#', "0")));
+        let width = numerator_width + 1 + denominator_width;
+        out.extend(std::iter::repeat_n(' ', width));
+    } else {
+        if has_whole {
+            out.push_str(&render_numeric(whole, whole_section));
+        } else {
+            out.push_str(&literal_text(whole_section));
+        }
+        out.push_str(&pad(numerator.to_string(), &numerator_ph, false));
+        out.push('/');
+        match fixed {
+            Some(_) => out.push_str(&fixed_digits),
+            None => out.push_str(&pad(denominator.to_string(), denominator_ph, true)),
+        }
+    }
+    out.push_str(&rest);
+    Some(out)
+}
+
 // ------- date / time -------
 
 /// Whether a section should be interpreted as a date/time rather than a number.
@@ -858,6 +1117,8 @@ mod tests {
     fn scaling_commas() {
         assert_eq!(f(1_500_000.0, "#,##0,"), "1,500");
         assert_eq!(f(1_500_000_000.0, "#,##0,,"), "1,500");
+        assert_eq!(f(1_250_000.0, "#,##0.0,,\"M\""), "1.3M");
+        assert_eq!(f(-42_000.0, "#,##0,\"K\""), "-42K");
     }
 
     #[test]
@@ -929,10 +1190,9 @@ mod coverage_tests {
         assert_eq!(format_value(&CellValue::Boolean(true), "0"), "TRUE");
         assert_eq!(format_value(&CellValue::Boolean(false), "0"), "FALSE");
         assert_eq!(format_value(&CellValue::Empty, "0"), "");
-        assert_eq!(
-            format_value(&CellValue::Date("2023-01-01".into()), "yyyy"),
-            "2023-01-01"
-        );
+        let iso = CellValue::Date(crate::DateValue::parse("2023-01-01").unwrap());
+        assert_eq!(format_value(&iso, "yyyy"), "2023");
+        assert_eq!(format_value(&iso, "General"), "2023-01-01");
         assert_eq!(
             format_value(&CellValue::Formula("SUM(A1)".into()), "0"),
             "SUM(A1)"
@@ -973,6 +1233,38 @@ mod coverage_tests {
         assert_eq!(format_number(1.5, "[s]"), "129600");
     }
 
+    #[test]
+    fn scientific() {
+        assert_eq!(format_number(12345.678, "0.00E+00"), "1.23E+04");
+        assert_eq!(format_number(0.00012, "0.00E+00"), "1.20E-04");
+        assert_eq!(format_number(-12345.0, "0.00E+00"), "-1.23E+04");
+        assert_eq!(format_number(0.0, "0.00E+00"), "0.00E+00");
+        assert_eq!(format_number(99999.0, "0.0E+0"), "1.0E+5");
+        assert_eq!(format_number(12345.0, "0.0E-0"), "1.2E4");
+        // Engineering notation: exponent in steps of three
+        assert_eq!(format_number(12345.0, "##0.0E+0"), "12.3E+3");
+        assert_eq!(format_number(0.5, "##0.0E+0"), "500.0E-3");
+        assert_eq!(
+            format_number(1.5e10, builtin_format_code(11).unwrap()),
+            "1.50E+10"
+        );
+    }
+
+    #[test]
+    fn fractions() {
+        assert_eq!(format_number(1.5, "# ?/?"), "1 1/2");
+        assert_eq!(format_number(0.75, "# ?/?"), " 3/4");
+        assert_eq!(format_number(2.0, "# ?/?"), "2    ");
+        assert_eq!(format_number(-1.25, "# ?/?"), "-1 1/4");
+        assert_eq!(format_number(1.0 / 3.0, "?/?"), "1/3");
+        assert_eq!(format_number(1.5, "?/?"), "3/2");
+        assert_eq!(format_number(std::f64::consts::PI, "# ??/??"), "3 14/99");
+        assert_eq!(format_number(0.1, "# ??/??"), "  1/10");
+        assert_eq!(format_number(0.5, "# ?/8"), " 4/8");
+        assert_eq!(format_number(0.3, "# ?/4"), " 1/4");
+        assert_eq!(format_number(0.999, "# ?/?"), "1    ");
+    }
+
     #[test]
     fn currency_and_literal_quotes() {
         assert_eq!(format_number(9.0, "\"$\"0.00"), "$9.00");
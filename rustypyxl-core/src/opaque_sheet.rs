@@ -0,0 +1,346 @@
+//! Sheets that are loaded and saved verbatim rather than modeled.
+//!
+//! A workbook's `<sheets>` list can point at dialog sheets (Excel 5 forms)
+//! and Excel 4 macro sheets as well as worksheets. Neither is something this
+//! library edits, but dropping them would shift every sheet index after them
+//! (and with it `localSheetId` on defined names and the active tab), so they
+//! load as a [`Worksheet`](crate::Worksheet) placeholder carrying the
+//! original part in [`OpaqueSheet`] and are written back as they came.
+
+use crate::cell::InternedString;
+use crate::rich_text::RichText;
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::HashMap;
+use std::io::Cursor;
+
+/// Relationship type of a dialog sheet in workbook.xml.rels.
+const DIALOGSHEET_REL: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/dialogsheet";
+/// Relationship type of an Excel 4 macro sheet.
+const MACROSHEET_REL: &str = "http://schemas.microsoft.com/office/2006/relationships/xlMacrosheet";
+/// Relationship type of an international Excel 4 macro sheet.
+const INTL_MACROSHEET_REL: &str =
+    "http://schemas.microsoft.com/office/2006/relationships/xlIntlMacrosheet";
+
+/// What kind of non-worksheet sheet an [`OpaqueSheet`] holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpaqueSheetKind {
+    /// An Excel 5 dialog sheet (`xl/dialogsheets/`).
+    Dialogsheet,
+    /// An Excel 4 macro sheet (`xl/macrosheets/`).
+    Macrosheet,
+    /// An Excel 4 international macro sheet (`xl/macrosheets/`).
+    IntlMacrosheet,
+}
+
+impl OpaqueSheetKind {
+    /// Classify a sheet from its workbook relationship type, falling back to
+    /// its part path for producers that use a nonstandard type URI. `None`
+    /// for anything that should be parsed as a worksheet.
+    pub fn detect(rel_type: Option<&str>, part_path: &str) -> Option<Self> {
+        match rel_type {
+            Some(DIALOGSHEET_REL) => return Some(OpaqueSheetKind::Dialogsheet),
+            Some(MACROSHEET_REL) => return Some(OpaqueSheetKind::Macrosheet),
+            Some(INTL_MACROSHEET_REL) => return Some(OpaqueSheetKind::IntlMacrosheet),
+            _ => {}
+        }
+        if part_path.starts_with("xl/dialogsheets/") {
+            Some(OpaqueSheetKind::Dialogsheet)
+        } else if part_path.starts_with("xl/macrosheets/") {
+            Some(OpaqueSheetKind::Macrosheet)
+        } else {
+            None
+        }
+    }
+
+    /// Name used for this kind in the Python API and error messages.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OpaqueSheetKind::Dialogsheet => "dialogsheet",
+            OpaqueSheetKind::Macrosheet => "macrosheet",
+            OpaqueSheetKind::IntlMacrosheet => "intlmacrosheet",
+        }
+    }
+
+    /// Package directory the part is written to.
+    pub(crate) fn part_dir(self) -> &'static str {
+        match self {
+            OpaqueSheetKind::Dialogsheet => "dialogsheets",
+            OpaqueSheetKind::Macrosheet | OpaqueSheetKind::IntlMacrosheet => "macrosheets",
+        }
+    }
+
+    /// Relationship type for the workbook.xml.rels entry.
+    pub(crate) fn rel_type(self) -> &'static str {
+        match self {
+            OpaqueSheetKind::Dialogsheet => DIALOGSHEET_REL,
+            OpaqueSheetKind::Macrosheet => MACROSHEET_REL,
+            OpaqueSheetKind::IntlMacrosheet => INTL_MACROSHEET_REL,
+        }
+    }
+
+    /// Content type for the part's [Content_Types].xml override.
+    pub(crate) fn content_type(self) -> &'static str {
+        match self {
+            OpaqueSheetKind::Dialogsheet => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.dialogsheet+xml"
+            }
+            OpaqueSheetKind::Macrosheet => "application/vnd.ms-excel.macrosheet+xml",
+            OpaqueSheetKind::IntlMacrosheet => "application/vnd.ms-excel.intlmacrosheet+xml",
+        }
+    }
+}
+
+/// A sheet kept byte for byte from a loaded file.
+///
+/// Only the sheet part and the parts its own relationships point at directly
+/// are kept; parts those reference in turn (e.g. images inside a dialog
+/// sheet's drawing) are not.
+#[derive(Clone, Debug)]
+pub struct OpaqueSheet {
+    pub kind: OpaqueSheetKind,
+    /// The sheet part. Shared-string cells are rewritten as inline strings
+    /// at load, since the shared string table is rebuilt on save.
+    pub xml: Vec<u8>,
+    /// The sheet's relationships, with the parts they point at.
+    pub rels: Vec<PreservedRel>,
+}
+
+/// One relationship of an [`OpaqueSheet`].
+#[derive(Clone, Debug)]
+pub struct PreservedRel {
+    /// Relationship id, as cited by `r:id` attributes in the sheet XML.
+    pub id: String,
+    /// Relationship type URI.
+    pub rel_type: String,
+    /// Original target. For internal parts only its file name is reused.
+    pub target: String,
+    /// The target part's bytes and content type; `None` for external
+    /// targets and parts missing from the archive.
+    pub part: Option<(Vec<u8>, String)>,
+}
+
+/// Where an opaque sheet's related part is written: a directory per sheet,
+/// so parts from different sheets, or from this library's own generated
+/// drawings, can't collide. Returns (package path, target relative to the
+/// sheet part).
+pub(crate) fn related_part_path(
+    kind: OpaqueSheetKind,
+    sheet_id: u32,
+    target: &str,
+) -> (String, String) {
+    let file = target.rsplit('/').next().unwrap_or(target);
+    (
+        format!("xl/{}/parts{}/{}", kind.part_dir(), sheet_id, file),
+        format!("parts{}/{}", sheet_id, file),
+    )
+}
+
+/// Content types declared in a package's [Content_Types].xml: overrides by
+/// part path (without the leading '/') and defaults by lowercase extension.
+#[derive(Default)]
+pub(crate) struct ContentTypes {
+    overrides: HashMap<String, String>,
+    defaults: HashMap<String, String>,
+}
+
+impl ContentTypes {
+    pub(crate) fn parse(xml: &[u8]) -> Self {
+        let mut types = ContentTypes::default();
+        let mut reader = Reader::from_reader(xml);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Empty(e)) | Ok(Event::Start(e)) => {
+                    let attr = |key: &[u8]| {
+                        e.attributes()
+                            .flatten()
+                            .find(|a| a.key.local_name().as_ref() == key)
+                            .map(|a| String::from_utf8_lossy(&a.value).into_owned())
+                    };
+                    match e.local_name().as_ref() {
+                        b"Override" => {
+                            if let (Some(part), Some(ct)) =
+                                (attr(b"PartName"), attr(b"ContentType"))
+                            {
+                                types
+                                    .overrides
+                                    .insert(part.trim_start_matches('/').to_string(), ct);
+                            }
+                        }
+                        b"Default" => {
+                            if let (Some(ext), Some(ct)) =
+                                (attr(b"Extension"), attr(b"ContentType"))
+                            {
+                                types.defaults.insert(ext.to_ascii_lowercase(), ct);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        types
+    }
+
+    /// Content type of the part at `path`, by override then by extension.
+    pub(crate) fn lookup(&self, path: &str) -> Option<String> {
+        if let Some(ct) = self.overrides.get(path) {
+            return Some(ct.clone());
+        }
+        let ext = path.rsplit_once('.')?.1.to_ascii_lowercase();
+        self.defaults.get(&ext).cloned()
+    }
+}
+
+/// Rewrite `t="s"` cells as inline strings so the part no longer depends on
+/// the loaded shared string table. Rich text keeps only its plain text.
+/// Returns the input unchanged if it has no shared-string cells or can't be
+/// parsed.
+pub(crate) fn inline_shared_strings(
+    xml: &[u8],
+    shared_strings: &[(InternedString, Option<RichText>)],
+) -> Vec<u8> {
+    if !xml.windows(5).any(|w| w == b"t=\"s\"" || w == b"t='s'") {
+        return xml.to_vec();
+    }
+    try_inline_shared_strings(xml, shared_strings).unwrap_or_else(|| xml.to_vec())
+}
+
+fn try_inline_shared_strings(
+    xml: &[u8],
+    shared_strings: &[(InternedString, Option<RichText>)],
+) -> Option<Vec<u8>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut writer = Writer::new(Cursor::new(Vec::with_capacity(xml.len())));
+    let mut buf = Vec::new();
+    // Prefix of the cell element (e.g. "x:") so the inserted <is>/<t> match it.
+    let mut shared_cell: Option<String> = None;
+    let mut in_value = false;
+
+    loop {
+        let event = reader.read_event_into(&mut buf).ok()?;
+        match event {
+            Event::Start(e) if e.local_name().as_ref() == b"c" => {
+                let is_shared = e
+                    .attributes()
+                    .flatten()
+                    .any(|a| a.key.as_ref() == b"t" && a.value.as_ref() == b"s");
+                if !is_shared {
+                    writer.write_event(Event::Start(e)).ok()?;
+                } else {
+                    let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                    let prefix = name.strip_suffix('c').unwrap_or("").to_string();
+                    let mut cell = BytesStart::new(name);
+                    for attr in e.attributes().flatten() {
+                        if attr.key.as_ref() == b"t" {
+                            cell.push_attribute(("t", "inlineStr"));
+                        } else {
+                            cell.push_attribute(attr);
+                        }
+                    }
+                    writer.write_event(Event::Start(cell)).ok()?;
+                    shared_cell = Some(prefix);
+                }
+            }
+            Event::Start(e) if shared_cell.is_some() && e.local_name().as_ref() == b"v" => {
+                in_value = true;
+            }
+            Event::Text(t) if in_value => {
+                let prefix = shared_cell.as_deref().unwrap_or("");
+                let index: usize = t.unescape().ok()?.trim().parse().ok()?;
+                let text = shared_strings.get(index)?.0.as_ref();
+                let mut t_start = BytesStart::new(format!("{}t", prefix));
+                t_start.push_attribute(("xml:space", "preserve"));
+                writer
+                    .write_event(Event::Start(BytesStart::new(format!("{}is", prefix))))
+                    .ok()?;
+                writer.write_event(Event::Start(t_start)).ok()?;
+                writer.write_event(Event::Text(BytesText::new(text))).ok()?;
+                writer
+                    .write_event(Event::End(quick_xml::events::BytesEnd::new(format!(
+                        "{}t",
+                        prefix
+                    ))))
+                    .ok()?;
+                writer
+                    .write_event(Event::End(quick_xml::events::BytesEnd::new(format!(
+                        "{}is",
+                        prefix
+                    ))))
+                    .ok()?;
+            }
+            Event::End(e) if in_value && e.local_name().as_ref() == b"v" => {
+                in_value = false;
+            }
+            Event::End(e) if e.local_name().as_ref() == b"c" => {
+                shared_cell = None;
+                writer.write_event(Event::End(e)).ok()?;
+            }
+            Event::Eof => break,
+            other => {
+                writer.write_event(other).ok()?;
+            }
+        }
+        buf.clear();
+    }
+    Some(writer.into_inner().into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_detect_by_rel_type_and_path() {
+        assert_eq!(
+            OpaqueSheetKind::detect(Some(DIALOGSHEET_REL), "xl/whatever.xml"),
+            Some(OpaqueSheetKind::Dialogsheet)
+        );
+        assert_eq!(
+            OpaqueSheetKind::detect(None, "xl/macrosheets/sheet1.xml"),
+            Some(OpaqueSheetKind::Macrosheet)
+        );
+        assert_eq!(
+            OpaqueSheetKind::detect(
+                Some(
+                    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet"
+                ),
+                "xl/worksheets/sheet1.xml"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_inline_shared_strings() {
+        let sst = vec![(Arc::from("zero"), None), (Arc::from("a < b"), None)];
+        let xml = br#"<xm:macrosheet xmlns:xm="m"><xm:sheetData><xm:row r="1"><xm:c r="A1" t="s"><xm:v>1</xm:v></xm:c><xm:c r="B1"><xm:v>2</xm:v></xm:c></xm:row></xm:sheetData></xm:macrosheet>"#;
+        let out = String::from_utf8(inline_shared_strings(xml, &sst)).unwrap();
+        assert!(out.contains(
+            r#"<xm:c r="A1" t="inlineStr"><xm:is><xm:t xml:space="preserve">a &lt; b</xm:t></xm:is></xm:c>"#
+        ));
+        assert!(out.contains(r#"<xm:c r="B1"><xm:v>2</xm:v></xm:c>"#));
+    }
+
+    #[test]
+    fn test_content_type_lookup() {
+        let types = ContentTypes::parse(
+            br#"<Types><Default Extension="vml" ContentType="application/vnd.openxmlformats-officedocument.vmlDrawing"/><Override PartName="/xl/drawings/drawing1.xml" ContentType="application/vnd.openxmlformats-officedocument.drawing+xml"/></Types>"#,
+        );
+        assert_eq!(
+            types.lookup("xl/drawings/drawing1.xml").as_deref(),
+            Some("application/vnd.openxmlformats-officedocument.drawing+xml")
+        );
+        assert_eq!(
+            types.lookup("xl/drawings/vmlDrawing1.VML").as_deref(),
+            Some("application/vnd.openxmlformats-officedocument.vmlDrawing")
+        );
+        assert!(types.lookup("xl/other.bin").is_none());
+    }
+}
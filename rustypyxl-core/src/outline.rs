@@ -0,0 +1,408 @@
+//! Row and column grouping and visibility: outline levels and the
+//! hidden/collapsed/bestFit flags Excel keeps on `<row>` and `<col>` elements.
+//!
+//! Grouping rows 5..=15 at level 1 marks each of them `outlineLevel="1"`;
+//! hiding the group also hides the rows and marks the summary row below
+//! (row 16) `collapsed="1"`, which is where Excel draws the expand button.
+//! With `summary_below` off in [`crate::SheetProperties`] the summary row is
+//! the one above the group instead (row 4); columns likewise follow
+//! `summary_right`.
+
+use crate::error::{Result, RustypyxlError};
+use crate::worksheet::Worksheet;
+
+/// Deepest outline level Excel supports.
+pub const MAX_OUTLINE_LEVEL: u8 = 7;
+
+/// Grouping and visibility of one row or column. The default (level 0,
+/// visible, expanded) is what a row or column without an entry has.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DimensionProps {
+    /// Outline level, 0 (not grouped) to 7.
+    pub outline_level: u8,
+    /// Hidden from view.
+    pub hidden: bool,
+    /// The group this row or column summarizes is collapsed.
+    pub collapsed: bool,
+    /// The column's width was fitted to its content. Columns only.
+    pub best_fit: bool,
+}
+
+impl DimensionProps {
+    /// True if nothing differs from a plain row or column.
+    pub fn is_default(&self) -> bool {
+        *self == DimensionProps::default()
+    }
+
+    /// Drop the outline level and show and expand the row or column.
+    fn clear_grouping(&mut self) {
+        self.outline_level = 0;
+        self.hidden = false;
+        self.collapsed = false;
+    }
+}
+
+fn check_group(kind: &str, start: u32, end: u32, level: u8) -> Result<()> {
+    if start == 0 || start > end {
+        return Err(RustypyxlError::custom(format!(
+            "Invalid {} group {}..{}: expected 1 <= start <= end",
+            kind, start, end
+        )));
+    }
+    if !(1..=MAX_OUTLINE_LEVEL).contains(&level) {
+        return Err(RustypyxlError::custom(format!(
+            "Outline level must be between 1 and {}, got {}",
+            MAX_OUTLINE_LEVEL, level
+        )));
+    }
+    Ok(())
+}
+
+impl Worksheet {
+    /// Group rows `start..=end` at outline `level` (1-7). With `hidden`, the
+    /// rows are hidden and the group shown collapsed.
+    pub fn group_rows(&mut self, start: u32, end: u32, level: u8, hidden: bool) -> Result<()> {
+        check_group("row", start, end, level)?;
+        for row in start..=end {
+            let props = self.row_props.entry(row).or_default();
+            props.outline_level = level;
+            props.hidden = hidden;
+        }
+        if let Some(summary) = self.row_summary(start, end) {
+            self.update_row_props(summary, |p| p.collapsed = hidden);
+        }
+        Ok(())
+    }
+
+    /// Remove rows `start..=end` from any group, showing and expanding them.
+    pub fn ungroup_rows(&mut self, start: u32, end: u32) {
+        for row in start..=end {
+            self.update_row_props(row, DimensionProps::clear_grouping);
+        }
+        if let Some(summary) = self.row_summary(start, end) {
+            self.update_row_props(summary, |p| p.collapsed = false);
+        }
+    }
+
+    /// Group columns `start..=end` (1-based) at outline `level` (1-7). With
+    /// `hidden`, the columns are hidden and the group shown collapsed.
+    pub fn group_columns(&mut self, start: u32, end: u32, level: u8, hidden: bool) -> Result<()> {
+        check_group("column", start, end, level)?;
+        self.update_column_props(start, end, |p| {
+            p.outline_level = level;
+            p.hidden = hidden;
+        });
+        if let Some(summary) = self.column_summary(start, end) {
+            self.update_column_props(summary, summary, |p| p.collapsed = hidden);
+        }
+        Ok(())
+    }
+
+    /// Remove columns `start..=end` from any group, showing and expanding them.
+    pub fn ungroup_columns(&mut self, start: u32, end: u32) {
+        self.update_column_props(start, end, DimensionProps::clear_grouping);
+        if let Some(summary) = self.column_summary(start, end) {
+            self.update_column_props(summary, summary, |p| p.collapsed = false);
+        }
+    }
+
+    /// Show the outline down to `level`, as Excel's numbered outline
+    /// buttons do: groups at `level` (1-7) and deeper are collapsed and
+    /// their rows and columns hidden; shallower groups are expanded.
+    /// `collapse_outline(1)` leaves only the top-level summaries visible.
+    pub fn collapse_outline(&mut self, level: u8) -> Result<()> {
+        if !(1..=MAX_OUTLINE_LEVEL).contains(&level) {
+            return Err(RustypyxlError::custom(format!(
+                "Outline level must be between 1 and {}, got {}",
+                MAX_OUTLINE_LEVEL, level
+            )));
+        }
+        self.show_row_outline(level);
+        self.show_column_outline(level);
+        Ok(())
+    }
+
+    /// Expand every row and column group.
+    pub fn expand_outline(&mut self) {
+        self.show_row_outline(MAX_OUTLINE_LEVEL + 1);
+        self.show_column_outline(MAX_OUTLINE_LEVEL + 1);
+    }
+
+    /// The summary row of a group over `start..=end`, per `summary_below`.
+    fn row_summary(&self, start: u32, end: u32) -> Option<u32> {
+        if self.sheet_properties.summary_below {
+            end.checked_add(1)
+        } else {
+            start.checked_sub(1).filter(|row| *row > 0)
+        }
+    }
+
+    /// The summary column of a group over `start..=end`, per `summary_right`.
+    fn column_summary(&self, start: u32, end: u32) -> Option<u32> {
+        if self.sheet_properties.summary_right {
+            end.checked_add(1)
+        } else {
+            start.checked_sub(1).filter(|column| *column > 0)
+        }
+    }
+
+    /// Hide grouped rows at `collapse_from` and deeper, show the rest, and
+    /// mark each summary row whose group is collapsed.
+    fn show_row_outline(&mut self, collapse_from: u8) {
+        let mut summaries: Vec<(u32, bool)> = Vec::new();
+        for (&row, props) in &self.row_props {
+            if props.outline_level == 0 {
+                continue;
+            }
+            let Some(summary) = self.row_summary(row, row) else {
+                continue;
+            };
+            let summary_level = self.row_props(summary).outline_level;
+            if summary_level < props.outline_level {
+                summaries.push((summary, summary_level + 1 >= collapse_from));
+            }
+        }
+        for props in self.row_props.values_mut() {
+            props.collapsed = false;
+            if props.outline_level > 0 {
+                props.hidden = props.outline_level >= collapse_from;
+            }
+        }
+        for (row, collapsed) in summaries {
+            self.update_row_props(row, |p| p.collapsed = collapsed);
+        }
+        self.row_props.retain(|_, props| !props.is_default());
+    }
+
+    /// Column counterpart of [`Self::show_row_outline`], run over spans.
+    fn show_column_outline(&mut self, collapse_from: u8) {
+        let spans: Vec<(u32, u32, DimensionProps)> = self
+            .column_props
+            .spans()
+            .map(|(min, max, props)| (min, max, *props))
+            .collect();
+        let mut summaries: Vec<(u32, bool)> = Vec::new();
+        for &(min, max, props) in &spans {
+            if props.outline_level == 0 {
+                continue;
+            }
+            let Some(summary) = self.column_summary(min, max) else {
+                continue;
+            };
+            let summary_level = self.column_props(summary).outline_level;
+            if summary_level < props.outline_level {
+                summaries.push((summary, summary_level + 1 >= collapse_from));
+            }
+        }
+        for (min, max, _) in spans {
+            self.update_column_props(min, max, |p| {
+                p.collapsed = false;
+                if p.outline_level > 0 {
+                    p.hidden = p.outline_level >= collapse_from;
+                }
+            });
+        }
+        for (column, collapsed) in summaries {
+            self.update_column_props(column, column, |p| p.collapsed = collapsed);
+        }
+    }
+
+    /// Hide or show a row.
+    pub fn set_row_hidden(&mut self, row: u32, hidden: bool) {
+        self.update_row_props(row, |p| p.hidden = hidden);
+    }
+
+    /// True if the row is hidden.
+    pub fn is_row_hidden(&self, row: u32) -> bool {
+        self.row_props(row).hidden
+    }
+
+    /// Hide or show a column.
+    pub fn set_column_hidden(&mut self, column: u32, hidden: bool) {
+        self.update_column_props(column, column, |p| p.hidden = hidden);
+    }
+
+    /// True if the column is hidden.
+    pub fn is_column_hidden(&self, column: u32) -> bool {
+        self.column_props(column).hidden
+    }
+
+    /// Mark whether a column's width was fitted to its content (`bestFit`).
+    pub fn set_column_best_fit(&mut self, column: u32, best_fit: bool) {
+        self.update_column_props(column, column, |p| p.best_fit = best_fit);
+    }
+
+    /// The row's grouping and visibility.
+    pub fn row_props(&self, row: u32) -> DimensionProps {
+        self.row_props.get(&row).copied().unwrap_or_default()
+    }
+
+    /// The column's grouping and visibility.
+    pub fn column_props(&self, column: u32) -> DimensionProps {
+        self.column_props.get(&column).copied().unwrap_or_default()
+    }
+
+    /// Deepest row outline level on the sheet (0 if no rows are grouped).
+    pub fn max_row_outline_level(&self) -> u8 {
+        self.row_props
+            .values()
+            .map(|p| p.outline_level)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Deepest column outline level on the sheet (0 if no columns are grouped).
+    pub fn max_column_outline_level(&self) -> u8 {
+        self.column_props
+            .spans()
+            .map(|(_, _, p)| p.outline_level)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Edit one row's props, dropping the entry once it is back to default.
+    pub fn update_row_props(&mut self, row: u32, f: impl FnOnce(&mut DimensionProps)) {
+        let mut props = self.row_props(row);
+        f(&mut props);
+        if props.is_default() {
+            self.row_props.remove(&row);
+        } else {
+            self.row_props.insert(row, props);
+        }
+    }
+
+    /// Edit the props of columns `start..=end`, keeping runs of equal props
+    /// as single spans.
+    pub fn update_column_props(&mut self, start: u32, end: u32, f: impl Fn(&mut DimensionProps)) {
+        let current: Vec<(u32, u32, DimensionProps)> = self
+            .column_props
+            .segments(start, end)
+            .into_iter()
+            .map(|(min, max, props)| (min, max, props.copied().unwrap_or_default()))
+            .collect();
+        for (min, max, mut props) in current {
+            f(&mut props);
+            if props.is_default() {
+                self.column_props.remove_span(min, max);
+            } else {
+                self.column_props.insert_span(min, max, props);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_rows_marks_summary_row() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.group_rows(5, 7, 1, true).unwrap();
+        assert_eq!(ws.row_props(6).outline_level, 1);
+        assert!(ws.row_props(6).hidden);
+        assert!(ws.row_props(8).collapsed);
+        assert_eq!(ws.row_props(8).outline_level, 0);
+        assert_eq!(ws.max_row_outline_level(), 1);
+
+        ws.group_rows(6, 6, 2, false).unwrap();
+        assert_eq!(ws.max_row_outline_level(), 2);
+
+        ws.ungroup_rows(5, 7);
+        assert!(ws.row_props.is_empty());
+    }
+
+    #[test]
+    fn group_columns_keeps_spans() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.group_columns(2, 4, 1, false).unwrap();
+        ws.group_columns(3, 3, 2, true).unwrap();
+        let spans: Vec<_> = ws
+            .column_props
+            .spans()
+            .map(|(a, b, p)| (a, b, p.outline_level))
+            .collect();
+        assert_eq!(spans, vec![(2, 2, 1), (3, 3, 2), (4, 4, 1)]);
+        assert!(ws.column_props(4).collapsed);
+        assert_eq!(ws.max_column_outline_level(), 2);
+
+        ws.ungroup_columns(2, 4);
+        assert!(ws.column_props.is_empty());
+    }
+
+    #[test]
+    fn hidden_and_best_fit_flags() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.set_row_hidden(3, true);
+        ws.set_column_hidden(2, true);
+        assert!(ws.is_row_hidden(3));
+        assert!(ws.is_column_hidden(2));
+        assert!(!ws.is_column_hidden(3));
+
+        ws.set_cell_value(1, 4, "a fairly long heading");
+        let width = ws.auto_fit_column(4).unwrap();
+        assert!(ws.column_props(4).best_fit);
+        ws.set_column_width(4, width + 5.0);
+        assert!(!ws.column_props(4).best_fit);
+
+        ws.set_row_hidden(3, false);
+        ws.set_column_hidden(2, false);
+        assert!(ws.row_props.is_empty());
+        assert!(ws.column_props.is_empty());
+    }
+
+    #[test]
+    fn collapse_outline_to_level() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.group_rows(2, 10, 1, false).unwrap();
+        ws.group_rows(4, 6, 2, false).unwrap();
+        ws.group_columns(2, 3, 1, false).unwrap();
+
+        ws.collapse_outline(2).unwrap();
+        assert!(!ws.is_row_hidden(3));
+        assert!(ws.is_row_hidden(5));
+        assert!(ws.row_props(7).collapsed);
+        assert!(!ws.row_props(11).collapsed);
+        assert!(!ws.is_column_hidden(2));
+
+        ws.collapse_outline(1).unwrap();
+        assert!(ws.is_row_hidden(3));
+        assert!(ws.row_props(11).collapsed);
+        assert!(ws.is_column_hidden(3));
+        assert!(ws.column_props(4).collapsed);
+
+        ws.expand_outline();
+        assert!((2..=10).all(|row| !ws.is_row_hidden(row)));
+        assert!(!ws.row_props(7).collapsed && !ws.row_props(11).collapsed);
+        assert!(!ws.column_props(4).collapsed);
+        assert!(ws.collapse_outline(0).is_err());
+    }
+
+    #[test]
+    fn summary_above_and_left() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.sheet_properties = crate::SheetProperties::new()
+            .with_summary_below(false)
+            .with_summary_right(false);
+        ws.group_rows(5, 8, 1, true).unwrap();
+        ws.group_columns(3, 4, 1, true).unwrap();
+        assert!(ws.row_props(4).collapsed);
+        assert!(!ws.row_props(9).collapsed);
+        assert!(ws.column_props(2).collapsed);
+
+        ws.expand_outline();
+        assert!(!ws.row_props(4).collapsed);
+        ws.collapse_outline(1).unwrap();
+        assert!(ws.row_props(4).collapsed);
+        assert!(ws.is_row_hidden(8));
+    }
+
+    #[test]
+    fn invalid_groups_rejected() {
+        let mut ws = Worksheet::new("Sheet1");
+        assert!(ws.group_rows(0, 3, 1, false).is_err());
+        assert!(ws.group_rows(5, 3, 1, false).is_err());
+        assert!(ws.group_columns(1, 3, 8, false).is_err());
+        assert!(ws.group_columns(1, 3, 0, false).is_err());
+    }
+}
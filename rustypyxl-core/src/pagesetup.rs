@@ -3,6 +3,14 @@
 //! This module provides structures for configuring print layout, margins,
 //! headers, footers, and other page setup options.
 
+use crate::workbook::{qualify_sheet_range, NamedRange};
+use crate::worksheet::Worksheet;
+
+/// Defined name Excel keeps a sheet's print area in.
+const PRINT_AREA_NAME: &str = "_xlnm.Print_Area";
+/// Defined name Excel keeps a sheet's print titles in.
+const PRINT_TITLES_NAME: &str = "_xlnm.Print_Titles";
+
 /// Paper size constants.
 #[derive(Clone, Debug, PartialEq, Default)]
 pub enum PaperSize {
@@ -457,6 +465,11 @@ impl Default for PageSetup {
 }
 
 impl PageSetup {
+    /// Whether fitting to a number of pages is on (Excel's `fitToPage`).
+    pub fn fits_to_page(&self) -> bool {
+        self.fit_to_width.is_some() || self.fit_to_height.is_some()
+    }
+
     /// Create new page setup with default values.
     pub fn new() -> Self {
         Self::default()
@@ -519,6 +532,107 @@ impl PageSetup {
     }
 }
 
+/// The sheet-scoped `_xlnm.Print_Area` and `_xlnm.Print_Titles` names for
+/// the print areas and titles set on the sheets.
+pub(crate) fn print_defined_names(
+    sheet_names: &[String],
+    worksheets: &[Worksheet],
+) -> Vec<NamedRange> {
+    let mut names = Vec::new();
+    for (idx, (sheet, ws)) in sheet_names.iter().zip(worksheets).enumerate() {
+        let Some(ps) = ws.page_setup.as_ref() else {
+            continue;
+        };
+        let mut push = |name: &str, range: String| {
+            names.push(NamedRange {
+                name: name.to_string(),
+                range,
+                local_sheet_id: Some(idx as u32),
+                hidden: false,
+                comment: None,
+            })
+        };
+        if let Some(area) = ps.print_area.as_deref().filter(|a| !a.is_empty()) {
+            push(PRINT_AREA_NAME, qualify_sheet_range(sheet, area));
+        }
+        let titles: Vec<String> = [&ps.print_titles.rows, &ps.print_titles.cols]
+            .into_iter()
+            .flatten()
+            .map(|t| qualify_sheet_range(sheet, t))
+            .collect();
+        if !titles.is_empty() {
+            push(PRINT_TITLES_NAME, titles.join(","));
+        }
+    }
+    names
+}
+
+/// Move the print area and print titles names of a loaded workbook onto the
+/// sheets they belong to, so they read back from the page setup and are not
+/// written twice on save. Names that do not resolve (`#REF!`) stay put.
+pub(crate) fn take_print_names(named_ranges: &mut Vec<NamedRange>, worksheets: &mut [Worksheet]) {
+    named_ranges.retain(|nr| {
+        let is_area = nr.name.eq_ignore_ascii_case(PRINT_AREA_NAME);
+        if !is_area && !nr.name.eq_ignore_ascii_case(PRINT_TITLES_NAME) {
+            return true;
+        }
+        let Some(ws) = nr
+            .local_sheet_id
+            .and_then(|idx| worksheets.get_mut(idx as usize))
+        else {
+            return true;
+        };
+        let Some(ranges) = local_ranges(&nr.range) else {
+            return true;
+        };
+        let ps = ws.page_setup.get_or_insert_with(PageSetup::new);
+        if is_area {
+            ps.print_area = Some(ranges.join(","));
+        } else {
+            for range in ranges {
+                if range.bytes().all(|b| b.is_ascii_digit() || b == b':') {
+                    ps.print_titles.rows = Some(range);
+                } else {
+                    ps.print_titles.cols = Some(range);
+                }
+            }
+        }
+        false
+    });
+}
+
+/// `Sheet1!$A$1:$D$20,'My Sheet'!$F:$G` as `["A1:D20", "F:G"]`; None for
+/// `#REF!` and other references that are not plain ranges.
+fn local_ranges(reference: &str) -> Option<Vec<String>> {
+    let mut ranges = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    let bytes = reference.as_bytes();
+    for i in 0..=bytes.len() {
+        match bytes.get(i) {
+            Some(b'\'') => in_quotes = !in_quotes,
+            Some(b',') | None if !in_quotes => {
+                let part = &reference[start..i];
+                let local = part
+                    .rsplit_once('!')
+                    .map_or(part, |(_, r)| r)
+                    .replace('$', "");
+                if local.is_empty()
+                    || !local
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || b == b':')
+                {
+                    return None;
+                }
+                ranges.push(local);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    Some(ranges)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,26 +3,34 @@
 //! This module provides fast import/export of Parquet files directly to/from Excel worksheets,
 //! bypassing FFI overhead for maximum performance.
 
-use crate::cell::CellValue;
+use crate::cell::{CellValue, DateValue};
 use crate::error::{Result, RustypyxlError};
+use crate::import_style::ImportStyleOptions;
+use crate::streaming::{StreamingSheet, StreamingWorkbook, DATETIME_STYLE, DATE_STYLE};
+use crate::utils::{push_coordinate, MAX_ROW};
+use crate::workbook::{NonFiniteNumbers, NumberPrecision};
 use crate::worksheet::Worksheet;
+use crate::writer::{format_cell_value, push_inline_string_cell, push_number_cell};
 use crate::Workbook;
 
 use arrow::array::{
-    Array, ArrayRef, BooleanArray, Date32Array, Date64Array, Decimal128Array, Decimal256Array,
-    Float16Array, Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array,
-    LargeStringArray, StringArray, TimestampMicrosecondArray, TimestampMillisecondArray,
-    TimestampNanosecondArray, TimestampSecondArray, UInt16Array, UInt32Array, UInt64Array,
-    UInt8Array,
+    Array, ArrayRef, BooleanArray, BooleanBuilder, Date32Array, Date32Builder, Date64Array,
+    Decimal128Array, Decimal256Array, Float16Array, Float32Array, Float64Array, Float64Builder,
+    Int16Array, Int32Array, Int64Array, Int64Builder, Int8Array, LargeStringArray, StringArray,
+    StringBuilder, TimestampMicrosecondArray, TimestampMillisecondArray,
+    TimestampMillisecondBuilder, TimestampNanosecondArray, TimestampSecondArray, UInt16Array,
+    UInt32Array, UInt64Array, UInt8Array,
 };
 use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
-use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
 use parquet::arrow::ArrowWriter;
 use parquet::arrow::ProjectionMask;
 use parquet::basic::Compression;
 use parquet::file::properties::WriterProperties;
 use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::fs::File;
 use std::sync::Arc;
 
@@ -91,6 +99,8 @@ pub struct ParquetImportOptions {
     pub columns: Vec<String>,
     /// Batch size for reading. Default: 65536.
     pub batch_size: usize,
+    /// Header/freeze/filter/width styling applied to the imported block.
+    pub style: Option<ImportStyleOptions>,
 }
 
 /// Default matches `new()`: a derived Default would zero `batch_size` and
@@ -102,6 +112,7 @@ impl Default for ParquetImportOptions {
             include_headers: true,
             columns: Vec::new(),
             batch_size: 65536,
+            style: None,
         }
     }
 }
@@ -134,6 +145,12 @@ impl ParquetImportOptions {
         self.batch_size = size;
         self
     }
+
+    /// Style the imported block (see [`ImportStyleOptions`]).
+    pub fn with_style(mut self, style: ImportStyleOptions) -> Self {
+        self.style = Some(style);
+        self
+    }
 }
 
 impl Workbook {
@@ -160,151 +177,272 @@ impl Workbook {
         start_col: u32,
         options: Option<ParquetImportOptions>,
     ) -> Result<ParquetImportResult> {
-        let options = options.unwrap_or_default();
-        let opts = if options.batch_size == 0 {
-            ParquetImportOptions {
-                batch_size: 65536,
-                ..options
-            }
-        } else {
-            options
-        };
+        let opts = options.unwrap_or_default();
+        let (reader, batch_indices, final_column_names, _) = open_parquet(path, &opts)?;
 
-        // Open the parquet file
-        let file = File::open(path).map_err(|e| {
-            RustypyxlError::ParseError(format!("Failed to open parquet file: {}", e))
-        })?;
-
-        // Build the reader
-        let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| {
-            RustypyxlError::ParseError(format!("Failed to read parquet metadata: {}", e))
-        })?;
-
-        // Get schema and determine columns to read
-        let schema = builder.schema().clone();
-        let all_column_names: Vec<String> =
-            schema.fields().iter().map(|f| f.name().clone()).collect();
+        // Get the worksheet
+        let worksheet = self.get_sheet_by_name_mut(sheet_name)?;
 
-        // Determine which columns to import (in requested order); an unknown
-        // name is an error rather than a silently dropped column
-        let columns_to_import: Vec<usize> = if opts.columns.is_empty() {
-            (0..all_column_names.len()).collect()
-        } else {
-            opts.columns
-                .iter()
-                .map(|name| {
-                    all_column_names
-                        .iter()
-                        .position(|n| n == name)
-                        .ok_or_else(|| {
-                            RustypyxlError::ParseError(format!(
-                                "Column '{}' not found in parquet file (available: {})",
-                                name,
-                                all_column_names.join(", ")
-                            ))
-                        })
-                })
-                .collect::<Result<_>>()?
-        };
+        write_batches(
+            worksheet,
+            final_column_names,
+            &batch_indices,
+            reader,
+            start_row,
+            start_col,
+            opts.include_headers,
+            opts.style.as_ref(),
+        )
+    }
+}
 
-        if columns_to_import.is_empty() {
+impl Worksheet {
+    /// Write Arrow record batches into this worksheet, one column per schema
+    /// field, starting at (`start_row`, `start_col`). The schema's field names
+    /// become a header row when `include_headers` is set.
+    ///
+    /// Batches are consumed one at a time, so a streaming source (an Arrow C
+    /// stream, a parquet reader) is never collected in full. `style`, if
+    /// given, is applied to the written block afterwards.
+    pub fn insert_record_batches<I>(
+        &mut self,
+        schema: &Schema,
+        batches: I,
+        start_row: u32,
+        start_col: u32,
+        include_headers: bool,
+        style: Option<&ImportStyleOptions>,
+    ) -> Result<ParquetImportResult>
+    where
+        I: IntoIterator<Item = std::result::Result<RecordBatch, ArrowError>>,
+    {
+        let column_names: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
+        if column_names.is_empty() {
             return Err(RustypyxlError::ParseError(
-                "No matching columns found in parquet file".to_string(),
+                "Arrow schema has no columns".to_string(),
             ));
         }
+        let batch_indices: Vec<usize> = (0..column_names.len()).collect();
+        write_batches(
+            self,
+            column_names,
+            &batch_indices,
+            batches,
+            start_row,
+            start_col,
+            include_headers,
+            style,
+        )
+    }
 
-        // Push the selection down to the reader so unselected columns are
-        // never decoded. The projected batch keeps file-schema order, so map
-        // each requested column to its position within the projection.
-        let mut projected: Vec<usize> = columns_to_import.clone();
-        projected.sort_unstable();
-        projected.dedup();
-        let batch_indices: Vec<usize> = columns_to_import
-            .iter()
-            .map(|idx| projected.iter().position(|p| p == idx).unwrap())
-            .collect();
-        let projection = ProjectionMask::roots(builder.parquet_schema(), projected.iter().copied());
-
-        // Build reader with batch size
-        let reader = builder
-            .with_batch_size(opts.batch_size)
-            .with_projection(projection)
-            .build()
-            .map_err(|e| {
-                RustypyxlError::ParseError(format!("Failed to build parquet reader: {}", e))
-            })?;
-
-        // Get the worksheet
-        let worksheet = self.get_sheet_by_name_mut(sheet_name)?;
+    /// The cells of `range` ("A1:D20"; the used range when `None`) as one
+    /// Arrow record batch, each column typed as the parquet export would
+    /// type it. With `has_headers` the range's first row names the columns;
+    /// otherwise they are Column1, Column2, ...
+    pub fn to_record_batch(&self, range: Option<&str>, has_headers: bool) -> Result<RecordBatch> {
+        let Some((min_row, min_col, max_row, max_col)) = self.render_bounds(range)? else {
+            return Ok(RecordBatch::new_empty(Arc::new(Schema::empty())));
+        };
+        let data_start_row = if has_headers { min_row + 1 } else { min_row };
+
+        let mut fields = Vec::new();
+        let mut arrays = Vec::new();
+        for col in min_col..=max_col {
+            let name = has_headers
+                .then(|| self.get_cell_value(min_row, col))
+                .flatten()
+                .filter(|v| !matches!(v, CellValue::Empty))
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| format!("Column{}", col - min_col + 1));
+            let values: Vec<Option<&CellValue>> = (data_start_row..=max_row)
+                .map(|row| self.get_cell_value(row, col))
+                .collect();
+            let (field, array) = build_arrow_column(&name, &values, ColumnType::Auto);
+            fields.push(field);
+            arrays.push(array);
+        }
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+            .map_err(|e| RustypyxlError::custom(format!("Failed to create record batch: {}", e)))
+    }
+}
 
-        // Prepare column names (with renames applied)
-        let final_column_names: Vec<String> = columns_to_import
-            .iter()
-            .map(|&idx| {
-                let original = &all_column_names[idx];
-                opts.column_renames
-                    .get(original)
-                    .cloned()
-                    .unwrap_or_else(|| original.clone())
-            })
-            .collect();
+/// Open a parquet file for import: a reader over just the selected columns,
+/// each output column's position within the projected batches, the output
+/// column names, and the file's row count.
+fn open_parquet(
+    path: &str,
+    opts: &ParquetImportOptions,
+) -> Result<(ParquetRecordBatchReader, Vec<usize>, Vec<String>, u64)> {
+    let batch_size = if opts.batch_size == 0 {
+        65536
+    } else {
+        opts.batch_size
+    };
 
-        let mut current_row = start_row;
+    // Open the parquet file
+    let file = File::open(path)
+        .map_err(|e| RustypyxlError::ParseError(format!("Failed to open parquet file: {}", e)))?;
+
+    // Build the reader
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file).map_err(|e| {
+        RustypyxlError::ParseError(format!("Failed to read parquet metadata: {}", e))
+    })?;
+
+    // Get schema and determine columns to read
+    let schema = builder.schema().clone();
+    let all_column_names: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
+    let (columns_to_import, final_column_names) =
+        select_columns(&all_column_names, opts, "parquet file")?;
+
+    // Push the selection down to the reader so unselected columns are
+    // never decoded. The projected batch keeps file-schema order, so map
+    // each requested column to its position within the projection.
+    let mut projected: Vec<usize> = columns_to_import.clone();
+    projected.sort_unstable();
+    projected.dedup();
+    let batch_indices: Vec<usize> = columns_to_import
+        .iter()
+        .map(|idx| projected.iter().position(|p| p == idx).unwrap())
+        .collect();
+    let projection = ProjectionMask::roots(builder.parquet_schema(), projected.iter().copied());
+
+    let num_rows = builder.metadata().file_metadata().num_rows().max(0) as u64;
+
+    // Build reader with batch size
+    let reader = builder
+        .with_batch_size(batch_size)
+        .with_projection(projection)
+        .build()
+        .map_err(|e| {
+            RustypyxlError::ParseError(format!("Failed to build parquet reader: {}", e))
+        })?;
 
-        // Write headers if requested
-        if opts.include_headers {
-            for (col_offset, name) in final_column_names.iter().enumerate() {
-                let col = start_col + col_offset as u32;
-                worksheet.set_cell_value(
-                    current_row,
-                    col,
-                    CellValue::String(Arc::from(name.as_str())),
-                );
-            }
-            current_row += 1;
-        }
+    Ok((reader, batch_indices, final_column_names, num_rows))
+}
 
-        let _data_start_row = current_row;
-        let mut total_rows: u32 = 0;
+/// The file columns to import, in output order, and their names after
+/// renaming. An unknown selected name is an error rather than a silently
+/// dropped column.
+pub(crate) fn select_columns(
+    all_column_names: &[String],
+    opts: &ParquetImportOptions,
+    source: &str,
+) -> Result<(Vec<usize>, Vec<String>)> {
+    let columns_to_import: Vec<usize> = if opts.columns.is_empty() {
+        (0..all_column_names.len()).collect()
+    } else {
+        opts.columns
+            .iter()
+            .map(|name| {
+                all_column_names
+                    .iter()
+                    .position(|n| n == name)
+                    .ok_or_else(|| {
+                        RustypyxlError::ParseError(format!(
+                            "Column '{}' not found in {} (available: {})",
+                            name,
+                            source,
+                            all_column_names.join(", ")
+                        ))
+                    })
+            })
+            .collect::<Result<_>>()?
+    };
 
-        // Read batches and write to worksheet
-        for batch_result in reader {
-            let batch = batch_result.map_err(|e| {
-                RustypyxlError::ParseError(format!("Failed to read parquet batch: {}", e))
-            })?;
+    if columns_to_import.is_empty() {
+        return Err(RustypyxlError::ParseError(format!(
+            "No matching columns found in {}",
+            source
+        )));
+    }
 
-            let num_rows = batch.num_rows();
+    let names = columns_to_import
+        .iter()
+        .map(|&idx| {
+            let original = &all_column_names[idx];
+            opts.column_renames
+                .get(original)
+                .cloned()
+                .unwrap_or_else(|| original.clone())
+        })
+        .collect();
+    Ok((columns_to_import, names))
+}
 
-            // Process each column
-            for (col_offset, &batch_idx) in batch_indices.iter().enumerate() {
-                let col = start_col + col_offset as u32;
-                let array = batch.column(batch_idx);
+/// Write an optional header row and then every batch's selected columns
+/// (`batch_indices`, in output order) below it, then apply any import style.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn write_batches<I>(
+    worksheet: &mut Worksheet,
+    column_names: Vec<String>,
+    batch_indices: &[usize],
+    batches: I,
+    start_row: u32,
+    start_col: u32,
+    include_headers: bool,
+    style: Option<&ImportStyleOptions>,
+) -> Result<ParquetImportResult>
+where
+    I: IntoIterator<Item = std::result::Result<RecordBatch, ArrowError>>,
+{
+    let mut current_row = start_row;
+
+    if include_headers {
+        for (col_offset, name) in column_names.iter().enumerate() {
+            let col = start_col + col_offset as u32;
+            worksheet.set_cell_value(
+                current_row,
+                col,
+                CellValue::String(Arc::from(name.as_str())),
+            );
+        }
+        current_row += 1;
+    }
 
-                write_arrow_array_to_worksheet(worksheet, array, current_row, col, num_rows);
-            }
+    let mut total_rows: u32 = 0;
+    for batch_result in batches {
+        let batch = batch_result.map_err(|e| {
+            RustypyxlError::ParseError(format!("Failed to read Arrow batch: {}", e))
+        })?;
+        let num_rows = batch.num_rows();
 
-            current_row += num_rows as u32;
-            total_rows += num_rows as u32;
+        for (col_offset, &batch_idx) in batch_indices.iter().enumerate() {
+            let col = start_col + col_offset as u32;
+            let array = batch.column(batch_idx);
+            write_arrow_array_to_worksheet(worksheet, array, current_row, col, num_rows);
         }
 
-        let end_row_with_header = if opts.include_headers && total_rows > 0 {
-            start_row + total_rows
-        } else if total_rows > 0 {
-            start_row + total_rows - 1
-        } else {
-            start_row
-        };
+        current_row += num_rows as u32;
+        total_rows += num_rows as u32;
+    }
 
-        Ok(ParquetImportResult {
-            rows_imported: total_rows,
-            columns_imported: columns_to_import.len() as u32,
-            start_row,
-            start_col,
-            end_row: end_row_with_header,
-            end_col: start_col + columns_to_import.len() as u32 - 1,
-            column_names: final_column_names,
-        })
+    let end_row_with_header = if include_headers && total_rows > 0 {
+        start_row + total_rows
+    } else if total_rows > 0 {
+        start_row + total_rows - 1
+    } else {
+        start_row
+    };
+
+    let end_col = start_col + batch_indices.len() as u32 - 1;
+    if let Some(style) = style {
+        worksheet.apply_import_style(
+            (start_row, start_col),
+            (end_row_with_header, end_col),
+            include_headers,
+            style,
+        );
     }
+
+    Ok(ParquetImportResult {
+        rows_imported: total_rows,
+        columns_imported: batch_indices.len() as u32,
+        start_row,
+        start_col,
+        end_row: end_row_with_header,
+        end_col,
+        column_names,
+    })
 }
 
 /// Number formats applied to imported date/timestamp cells so Excel renders
@@ -359,7 +497,12 @@ fn set_date_cell(
     format: &crate::cell::InternedString,
 ) {
     let cell = worksheet.get_or_create_cell_mut(row, col);
-    cell.value = CellValue::Number(serial);
+    // A date-time, as loading the saved file would give it; serials from
+    // before 1900 have no date and stay numbers.
+    cell.value = match DateValue::from_excel(serial, false) {
+        Some(date) => CellValue::Date(date),
+        None => CellValue::Number(serial),
+    };
     cell.number_format = Some(format.clone());
     // Force re-resolution of the style at save time
     cell.style_index = None;
@@ -679,6 +822,252 @@ fn write_uint_array(
     }
 }
 
+impl StreamingWorkbook {
+    /// Append the rows of a Parquet file to `sheet`, below any rows already
+    /// written, with the conversions [`Workbook::insert_from_parquet`] uses.
+    /// Each record batch is written into the sheet XML as it is decoded and
+    /// no cells are built, so memory stays bounded by one batch however
+    /// large the file. Data starts in column A.
+    ///
+    /// Date and timestamp columns are shown with date formats; other import
+    /// styles need per-cell styles a streamed sheet doesn't have, so
+    /// `options.style` is an error.
+    pub fn append_from_parquet(
+        &mut self,
+        sheet: &mut StreamingSheet,
+        path: &str,
+        options: Option<ParquetImportOptions>,
+    ) -> Result<ParquetImportResult> {
+        let opts = options.unwrap_or_default();
+        if opts.style.is_some() {
+            return Err(RustypyxlError::custom(
+                "Import styles are not supported when streaming",
+            ));
+        }
+        let (reader, batch_indices, column_names, num_rows) = open_parquet(path, &opts)?;
+        let width = column_names.len() as u32;
+        let start_row = sheet.current_row + 1;
+
+        // Refuse up front rather than leave a half-written sheet behind
+        let total_rows = num_rows + u64::from(opts.include_headers);
+        if u64::from(sheet.current_row) + total_rows > u64::from(MAX_ROW) {
+            return Err(RustypyxlError::custom(format!(
+                "{} rows do not fit below row {} (Excel's row limit is 1,048,576)",
+                total_rows, sheet.current_row
+            )));
+        }
+
+        let mut coord = String::with_capacity(12);
+        if opts.include_headers {
+            self.append_row_with(sheet, width, |buf, row| {
+                for (col_idx, name) in column_names.iter().enumerate() {
+                    coord.clear();
+                    push_coordinate(&mut coord, row, col_idx as u32 + 1);
+                    push_inline_string_cell(buf, &coord, name);
+                }
+                Ok(())
+            })?;
+        }
+
+        let non_finite = self.non_finite_numbers;
+        let precision = self.number_precision;
+        let mut rows_imported: u32 = 0;
+        for batch in reader {
+            let batch = batch.map_err(|e| {
+                RustypyxlError::ParseError(format!("Failed to read Arrow batch: {}", e))
+            })?;
+            let columns: Vec<StreamColumn> = batch_indices
+                .iter()
+                .map(|&idx| StreamColumn::new(batch.column(idx)))
+                .collect();
+            for i in 0..batch.num_rows() {
+                self.append_row_with(sheet, width, |buf, row| {
+                    for (col_idx, column) in columns.iter().enumerate() {
+                        let col = col_idx as u32 + 1;
+                        column.write_cell(buf, &mut coord, (row, col), i, non_finite, precision)?;
+                    }
+                    Ok(())
+                })?;
+            }
+            rows_imported += batch.num_rows() as u32;
+        }
+
+        Ok(ParquetImportResult {
+            rows_imported,
+            columns_imported: width,
+            start_row,
+            start_col: 1,
+            end_row: sheet.current_row.max(start_row),
+            end_col: width,
+            column_names,
+        })
+    }
+}
+
+/// One column of a record batch, downcast (or cast to a common type) once
+/// per batch so that streaming it out is a per-cell write with no lookups.
+enum StreamColumn {
+    Empty,
+    Boolean(BooleanArray),
+    Number(Float64Array),
+    Int64(Int64Array),
+    UInt64(UInt64Array),
+    Text(StringArray),
+    LargeText(LargeStringArray),
+    /// Excel date serials and the style index that shows them as dates.
+    Serial(Float64Array, u32),
+}
+
+impl StreamColumn {
+    fn new(array: &ArrayRef) -> Self {
+        use arrow::array::AsArray;
+        use arrow::compute::cast;
+        use arrow::datatypes::{Date32Type, Float64Type, Int64Type, UInt64Type};
+
+        const UNIX_EPOCH_SERIAL: f64 = 25569.0;
+        // Raw Int64 values of a date/time column as serials, given the
+        // column's units per day
+        let serials = |per_day: f64, style: u32| {
+            cast(array, &DataType::Int64).ok().map(|raw| {
+                let serials = raw
+                    .as_primitive::<Int64Type>()
+                    .unary(|v| v as f64 / per_day + UNIX_EPOCH_SERIAL);
+                StreamColumn::Serial(serials, style)
+            })
+        };
+        const MS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+        let column = match array.data_type() {
+            DataType::Null => Some(StreamColumn::Empty),
+            DataType::Boolean => Some(StreamColumn::Boolean(array.as_boolean().clone())),
+            DataType::Int64 => Some(StreamColumn::Int64(
+                array.as_primitive::<Int64Type>().clone(),
+            )),
+            DataType::UInt64 => Some(StreamColumn::UInt64(
+                array.as_primitive::<UInt64Type>().clone(),
+            )),
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::Float16
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Decimal128(..)
+            | DataType::Decimal256(..) => cast(array, &DataType::Float64)
+                .ok()
+                .map(|n| StreamColumn::Number(n.as_primitive::<Float64Type>().clone())),
+            DataType::Utf8 => Some(StreamColumn::Text(array.as_string::<i32>().clone())),
+            DataType::LargeUtf8 => Some(StreamColumn::LargeText(array.as_string::<i64>().clone())),
+            DataType::Date32 => {
+                let serials = array
+                    .as_primitive::<Date32Type>()
+                    .unary(|days| days as f64 + UNIX_EPOCH_SERIAL);
+                Some(StreamColumn::Serial(serials, DATE_STYLE))
+            }
+            DataType::Date64 => serials(MS_PER_DAY, DATE_STYLE),
+            // Timestamps are converted in UTC, as on import into a Workbook
+            DataType::Timestamp(unit, _tz) => {
+                let per_ms = match unit {
+                    TimeUnit::Second => 1e-3,
+                    TimeUnit::Millisecond => 1.0,
+                    TimeUnit::Microsecond => 1e3,
+                    TimeUnit::Nanosecond => 1e6,
+                };
+                serials(MS_PER_DAY * per_ms, DATETIME_STYLE)
+            }
+            _ => None,
+        };
+        // Anything else is written as its display text
+        column.unwrap_or_else(|| {
+            let formatter = arrow::util::display::ArrayFormatter::try_new(
+                array.as_ref(),
+                &arrow::util::display::FormatOptions::default(),
+            );
+            let text: StringArray = match formatter {
+                Ok(fmt) => (0..array.len())
+                    .map(|i| array.is_valid(i).then(|| fmt.value(i).to_string()))
+                    .collect(),
+                Err(_) => StringArray::new_null(array.len()),
+            };
+            StreamColumn::Text(text)
+        })
+    }
+
+    /// Write row `i` of this column as the cell at `(row, col)`; nulls are
+    /// left out. `coord` is scratch space for the cell reference.
+    fn write_cell(
+        &self,
+        buf: &mut String,
+        coord: &mut String,
+        (row, col): (u32, u32),
+        i: usize,
+        non_finite: NonFiniteNumbers,
+        precision: NumberPrecision,
+    ) -> Result<()> {
+        let valid = match self {
+            StreamColumn::Empty => false,
+            StreamColumn::Boolean(a) => a.is_valid(i),
+            StreamColumn::Number(a) | StreamColumn::Serial(a, _) => a.is_valid(i),
+            StreamColumn::Int64(a) => a.is_valid(i),
+            StreamColumn::UInt64(a) => a.is_valid(i),
+            StreamColumn::Text(a) => a.is_valid(i),
+            StreamColumn::LargeText(a) => a.is_valid(i),
+        };
+        if !valid {
+            return Ok(());
+        }
+        coord.clear();
+        push_coordinate(coord, row, col);
+        match self {
+            StreamColumn::Empty => {}
+            StreamColumn::Boolean(a) => {
+                format_cell_value(buf, coord, &CellValue::Boolean(a.value(i)))
+            }
+            StreamColumn::Number(a) => {
+                let n = a.value(i);
+                if !n.is_finite() {
+                    match non_finite {
+                        NonFiniteNumbers::Error => {
+                            return Err(RustypyxlError::custom(format!(
+                                "{} holds a NaN or infinite number, which cannot be written",
+                                coord
+                            )))
+                        }
+                        NonFiniteNumbers::Empty => return Ok(()),
+                        NonFiniteNumbers::NumError => {}
+                    }
+                }
+                push_number_cell(buf, coord, n, None, precision);
+            }
+            StreamColumn::Int64(a) => {
+                let v = a.value(i);
+                if v.unsigned_abs() <= MAX_EXACT_INT {
+                    push_number_cell(buf, coord, v as f64, None, precision);
+                } else {
+                    push_inline_string_cell(buf, coord, itoa::Buffer::new().format(v));
+                }
+            }
+            StreamColumn::UInt64(a) => {
+                let v = a.value(i);
+                if v <= MAX_EXACT_INT {
+                    push_number_cell(buf, coord, v as f64, None, precision);
+                } else {
+                    push_inline_string_cell(buf, coord, itoa::Buffer::new().format(v));
+                }
+            }
+            StreamColumn::Text(a) => push_inline_string_cell(buf, coord, a.value(i)),
+            StreamColumn::LargeText(a) => push_inline_string_cell(buf, coord, a.value(i)),
+            StreamColumn::Serial(a, style) => {
+                push_number_cell(buf, coord, a.value(i), Some(*style), precision)
+            }
+        }
+        Ok(())
+    }
+}
+
 // ============================================================================
 // EXPORT FUNCTIONALITY
 // ============================================================================
@@ -908,32 +1297,87 @@ impl Workbook {
         options: ParquetExportOptions,
     ) -> Result<ParquetExportResult> {
         let worksheet = self.get_sheet_by_name(sheet_name)?;
+        let layout = ColumnLayout::new(
+            worksheet,
+            (min_row, min_col, max_row, max_col),
+            options.has_headers,
+            &options.column_renames,
+            &options.column_types,
+        );
 
-        let num_cols = (max_col - min_col + 1) as usize;
-        let data_start_row = if options.has_headers {
-            min_row + 1
-        } else {
-            min_row
-        };
-        let num_data_rows = if max_row >= data_start_row {
-            (max_row - data_start_row + 1) as usize
-        } else {
-            0
-        };
+        // Write to parquet
+        let file = File::create(path)
+            .map_err(|e| RustypyxlError::custom(format!("Failed to create file: {}", e)))?;
+
+        let props = WriterProperties::builder()
+            .set_compression(options.compression.into())
+            .set_max_row_group_size(options.row_group_size)
+            .build();
+
+        let mut writer =
+            ArrowWriter::try_new(file, layout.schema.clone(), Some(props)).map_err(|e| {
+                RustypyxlError::custom(format!("Failed to create parquet writer: {}", e))
+            })?;
+
+        // Pass 2: build and write one RecordBatch per row-group-sized chunk
+        for batch in layout.batches(options.row_group_size) {
+            writer
+                .write(&batch?)
+                .map_err(|e| RustypyxlError::custom(format!("Failed to write batch: {}", e)))?;
+        }
+
+        writer
+            .close()
+            .map_err(|e| RustypyxlError::custom(format!("Failed to close writer: {}", e)))?;
+
+        // Get file size
+        let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+        Ok(ParquetExportResult {
+            rows_exported: layout.num_data_rows(),
+            columns_exported: layout.column_names.len() as u32,
+            column_names: layout.column_names,
+            file_size,
+        })
+    }
+}
+
+/// A worksheet range laid out as typed Arrow columns. Column types are
+/// resolved up front in a streaming scan, so that every chunk
+/// [`ColumnLayout::batches`] builds shares one schema and peak memory is
+/// bounded by a chunk rather than the whole range.
+pub(crate) struct ColumnLayout<'a> {
+    worksheet: &'a Worksheet,
+    min_col: u32,
+    data_start_row: u32,
+    max_row: u32,
+    pub(crate) column_names: Vec<String>,
+    column_types: Vec<ColumnType>,
+    pub(crate) schema: Arc<Schema>,
+}
+
+impl<'a> ColumnLayout<'a> {
+    /// Name and type the columns of `bounds` (min_row, min_col, max_row,
+    /// max_col). With `has_headers` the first row names the columns, after
+    /// `renames`; `column_types` hints override inference by final name.
+    pub(crate) fn new(
+        worksheet: &'a Worksheet,
+        (min_row, min_col, max_row, max_col): (u32, u32, u32, u32),
+        has_headers: bool,
+        renames: &HashMap<String, String>,
+        column_types: &HashMap<String, ColumnType>,
+    ) -> Self {
+        let data_start_row = if has_headers { min_row + 1 } else { min_row };
 
         // Extract column names
-        let column_names: Vec<String> = if options.has_headers {
+        let column_names: Vec<String> = if has_headers {
             (min_col..=max_col)
                 .map(|col| {
                     let original = worksheet
                         .get_cell_value(min_row, col)
                         .map(|v| v.to_string())
                         .unwrap_or_else(|| format!("Column{}", col - min_col + 1));
-                    options
-                        .column_renames
-                        .get(&original)
-                        .cloned()
-                        .unwrap_or(original)
+                    renames.get(&original).cloned().unwrap_or(original)
                 })
                 .collect()
         } else {
@@ -942,132 +1386,233 @@ impl Workbook {
                 .collect()
         };
 
-        // Pass 1: resolve each column's type, materializing one column at a
-        // time at most (only for columns without an explicit hint)
-        let resolved_types: Vec<ColumnType> = column_names
+        // Pass 1: resolve each column's type in a streaming scan (only for
+        // columns without an explicit hint)
+        let has_data = max_row >= data_start_row;
+        let column_types: Vec<ColumnType> = column_names
             .iter()
             .enumerate()
             .map(|(col_idx, col_name)| {
-                let hint = options
-                    .column_types
+                let hint = column_types
                     .get(col_name)
                     .copied()
                     .unwrap_or(ColumnType::Auto);
-                if hint != ColumnType::Auto || num_data_rows == 0 {
+                if hint != ColumnType::Auto || !has_data {
                     return hint;
                 }
                 let col = min_col + col_idx as u32;
-                let col_data: Vec<Option<&CellValue>> = (data_start_row..=max_row)
-                    .map(|row| worksheet.get_cell_value(row, col))
-                    .collect();
-                infer_column_type(&col_data)
+                let mut inference = TypeInference::default();
+                for row in data_start_row..=max_row {
+                    if let Some(value) = worksheet.get_cell_value(row, col) {
+                        inference.add(value);
+                    }
+                }
+                inference.column_type()
             })
             .collect();
 
         let fields: Vec<Field> = column_names
             .iter()
-            .zip(&resolved_types)
-            .map(|(name, col_type)| {
-                // Field metadata must match what build_arrow_column produces
-                let (field, _) = build_arrow_column(name, &[], *col_type);
-                field
-            })
+            .zip(&column_types)
+            .map(|(name, col_type)| arrow_field(name, *col_type))
             .collect();
-        let schema = Arc::new(Schema::new(fields));
-
-        // Write to parquet
-        let file = File::create(path)
-            .map_err(|e| RustypyxlError::custom(format!("Failed to create file: {}", e)))?;
-
-        let props = WriterProperties::builder()
-            .set_compression(options.compression.into())
-            .set_max_row_group_size(options.row_group_size)
-            .build();
 
-        let mut writer = ArrowWriter::try_new(file, schema.clone(), Some(props)).map_err(|e| {
-            RustypyxlError::custom(format!("Failed to create parquet writer: {}", e))
-        })?;
+        ColumnLayout {
+            worksheet,
+            min_col,
+            data_start_row,
+            max_row,
+            column_names,
+            column_types,
+            schema: Arc::new(Schema::new(fields)),
+        }
+    }
 
-        // Pass 2: build and write one RecordBatch per row-group-sized chunk
-        let chunk_rows = options.row_group_size.max(1) as u32;
-        let mut chunk_start = data_start_row;
-        while chunk_start <= max_row && num_data_rows > 0 {
-            let chunk_end = chunk_start.saturating_add(chunk_rows - 1).min(max_row);
+    /// Number of rows below the header, if any.
+    pub(crate) fn num_data_rows(&self) -> u32 {
+        (self.max_row + 1).saturating_sub(self.data_start_row)
+    }
 
-            let mut arrays: Vec<ArrayRef> = Vec::with_capacity(num_cols);
-            for (col_idx, col_name) in column_names.iter().enumerate() {
-                let col = min_col + col_idx as u32;
-                let col_data: Vec<Option<&CellValue>> = (chunk_start..=chunk_end)
-                    .map(|row| worksheet.get_cell_value(row, col))
-                    .collect();
-                let (_, array) = build_arrow_column(col_name, &col_data, resolved_types[col_idx]);
-                arrays.push(array);
+    /// The data rows as record batches of at most `chunk_rows` rows each.
+    /// Cells are appended straight into one builder per column, kept for
+    /// the whole export and emptied by each chunk's `finish`.
+    pub(crate) fn batches(
+        &self,
+        chunk_rows: usize,
+    ) -> impl Iterator<Item = Result<RecordBatch>> + '_ {
+        let chunk_rows = chunk_rows.clamp(1, u32::MAX as usize) as u32;
+        let capacity = chunk_rows.min(self.num_data_rows()) as usize;
+        let mut builders: Vec<ColumnBuilder> = self
+            .column_types
+            .iter()
+            .map(|col_type| ColumnBuilder::new(*col_type, capacity))
+            .collect();
+        let mut chunk_start = self.data_start_row;
+        std::iter::from_fn(move || {
+            if chunk_start > self.max_row {
+                return None;
             }
-
-            let batch = RecordBatch::try_new(schema.clone(), arrays).map_err(|e| {
-                RustypyxlError::custom(format!("Failed to create record batch: {}", e))
-            })?;
-            writer
-                .write(&batch)
-                .map_err(|e| RustypyxlError::custom(format!("Failed to write batch: {}", e)))?;
-
+            let chunk_end = chunk_start.saturating_add(chunk_rows - 1).min(self.max_row);
+            for (col_idx, builder) in builders.iter_mut().enumerate() {
+                let col = self.min_col + col_idx as u32;
+                for row in chunk_start..=chunk_end {
+                    builder.append(self.worksheet.get_cell_value(row, col));
+                }
+            }
+            let arrays: Vec<ArrayRef> = builders.iter_mut().map(ColumnBuilder::finish).collect();
             chunk_start = chunk_end + 1;
-        }
-
-        writer
-            .close()
-            .map_err(|e| RustypyxlError::custom(format!("Failed to close writer: {}", e)))?;
-
-        // Get file size
-        let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
-
-        Ok(ParquetExportResult {
-            rows_exported: num_data_rows as u32,
-            columns_exported: num_cols as u32,
-            column_names,
-            file_size,
+            Some(
+                RecordBatch::try_new(self.schema.clone(), arrays).map_err(|e| {
+                    RustypyxlError::custom(format!("Failed to create record batch: {}", e))
+                }),
+            )
         })
     }
 }
 
-/// Infer column type from cell values.
-fn infer_column_type(values: &[Option<&CellValue>]) -> ColumnType {
-    let mut has_string = false;
-    let mut has_number = false;
-    let mut has_boolean = false;
-    let mut all_integers = true;
+/// Column type inference over cell values, fed one value at a time so a
+/// column can be typed without collecting it first.
+#[derive(Default)]
+struct TypeInference {
+    has_string: bool,
+    has_number: bool,
+    has_boolean: bool,
+    has_datetime: bool,
+    /// Calendar dates without a time, which alone make a Date32 column.
+    has_date: bool,
+    has_fraction: bool,
+}
 
-    for value in values.iter().flatten() {
+impl TypeInference {
+    fn add(&mut self, value: &CellValue) {
         match value {
-            CellValue::String(_) | CellValue::Formula(_) | CellValue::Date(_) => {
-                has_string = true;
+            CellValue::String(_) | CellValue::Formula(_) => {
+                self.has_string = true;
+            }
+            CellValue::Date(DateValue::Date(_)) => {
+                self.has_date = true;
+            }
+            CellValue::Date(_) => {
+                self.has_datetime = true;
             }
             CellValue::Number(n) => {
-                has_number = true;
+                self.has_number = true;
                 if n.fract() != 0.0 {
-                    all_integers = false;
+                    self.has_fraction = true;
                 }
             }
             CellValue::Boolean(_) => {
-                has_boolean = true;
+                self.has_boolean = true;
             }
             CellValue::Empty => {}
         }
     }
 
-    // Priority: if any strings, use string; otherwise prefer numbers
-    if has_string {
-        ColumnType::String
-    } else if has_number {
-        if all_integers {
-            ColumnType::Int64
+    fn column_type(&self) -> ColumnType {
+        // Priority: if any strings, use string; otherwise prefer numbers. Dates
+        // mixed with anything else are only representable as text.
+        let has_temporal = self.has_datetime || self.has_date;
+        if self.has_string || (has_temporal && (self.has_number || self.has_boolean)) {
+            ColumnType::String
+        } else if self.has_datetime {
+            ColumnType::DateTime
+        } else if self.has_date {
+            ColumnType::Date
+        } else if self.has_number {
+            if self.has_fraction {
+                ColumnType::Float64
+            } else {
+                ColumnType::Int64
+            }
+        } else if self.has_boolean {
+            ColumnType::Boolean
         } else {
-            ColumnType::Float64
+            ColumnType::String // default for empty columns
+        }
+    }
+}
+
+/// Infer column type from cell values.
+fn infer_column_type(values: &[Option<&CellValue>]) -> ColumnType {
+    let mut inference = TypeInference::default();
+    for value in values.iter().flatten() {
+        inference.add(value);
+    }
+    inference.column_type()
+}
+
+/// The Arrow field for a column of the given (resolved) type.
+fn arrow_field(name: &str, col_type: ColumnType) -> Field {
+    let data_type = match col_type {
+        ColumnType::String | ColumnType::Auto => DataType::Utf8,
+        ColumnType::Float64 => DataType::Float64,
+        ColumnType::Int64 => DataType::Int64,
+        ColumnType::Boolean => DataType::Boolean,
+        ColumnType::Date => DataType::Date32,
+        ColumnType::DateTime => DataType::Timestamp(TimeUnit::Millisecond, None),
+    };
+    Field::new(name, data_type, true)
+}
+
+/// An Arrow array builder for one column, converting cell values to the
+/// column's type as they are appended. `finish` returns the values so far and
+/// leaves the builder empty for reuse.
+enum ColumnBuilder {
+    String(StringBuilder),
+    Float64(Float64Builder),
+    Int64(Int64Builder),
+    Boolean(BooleanBuilder),
+    // Excel serial number to days since Unix epoch
+    Date(Date32Builder),
+    // Excel serial number to milliseconds since Unix epoch
+    DateTime(TimestampMillisecondBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(col_type: ColumnType, capacity: usize) -> Self {
+        match col_type {
+            ColumnType::String | ColumnType::Auto => {
+                ColumnBuilder::String(StringBuilder::with_capacity(capacity, capacity * 8))
+            }
+            ColumnType::Float64 => ColumnBuilder::Float64(Float64Builder::with_capacity(capacity)),
+            ColumnType::Int64 => ColumnBuilder::Int64(Int64Builder::with_capacity(capacity)),
+            ColumnType::Boolean => ColumnBuilder::Boolean(BooleanBuilder::with_capacity(capacity)),
+            ColumnType::Date => ColumnBuilder::Date(Date32Builder::with_capacity(capacity)),
+            ColumnType::DateTime => {
+                ColumnBuilder::DateTime(TimestampMillisecondBuilder::with_capacity(capacity))
+            }
+        }
+    }
+
+    fn append(&mut self, value: Option<&CellValue>) {
+        match self {
+            ColumnBuilder::String(b) => match value {
+                Some(cv) => {
+                    // Formats into the builder's value buffer; this cannot fail
+                    let _ = write!(b, "{}", cv);
+                    b.append_value("");
+                }
+                None => b.append_null(),
+            },
+            ColumnBuilder::Float64(b) => b.append_option(value.and_then(cell_value_to_f64)),
+            ColumnBuilder::Int64(b) => b.append_option(value.and_then(cell_value_to_i64)),
+            ColumnBuilder::Boolean(b) => b.append_option(value.and_then(cell_value_to_bool)),
+            ColumnBuilder::Date(b) => b.append_option(value.and_then(cell_value_to_date32)),
+            ColumnBuilder::DateTime(b) => {
+                b.append_option(value.and_then(cell_value_to_timestamp_ms))
+            }
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            ColumnBuilder::String(b) => Arc::new(b.finish()),
+            ColumnBuilder::Float64(b) => Arc::new(b.finish()),
+            ColumnBuilder::Int64(b) => Arc::new(b.finish()),
+            ColumnBuilder::Boolean(b) => Arc::new(b.finish()),
+            ColumnBuilder::Date(b) => Arc::new(b.finish()),
+            ColumnBuilder::DateTime(b) => Arc::new(b.finish()),
         }
-    } else if has_boolean {
-        ColumnType::Boolean
-    } else {
-        ColumnType::String // default for empty columns
     }
 }
 
@@ -1082,68 +1627,11 @@ fn build_arrow_column(
     } else {
         type_hint
     };
-
-    match col_type {
-        ColumnType::String | ColumnType::Auto => {
-            let arr: StringArray = values.iter().map(|v| v.map(|cv| cv.to_string())).collect();
-            (
-                Field::new(name, DataType::Utf8, true),
-                Arc::new(arr) as ArrayRef,
-            )
-        }
-        ColumnType::Float64 => {
-            let arr: Float64Array = values
-                .iter()
-                .map(|v| v.and_then(cell_value_to_f64))
-                .collect();
-            (
-                Field::new(name, DataType::Float64, true),
-                Arc::new(arr) as ArrayRef,
-            )
-        }
-        ColumnType::Int64 => {
-            let arr: Int64Array = values
-                .iter()
-                .map(|v| v.and_then(cell_value_to_i64))
-                .collect();
-            (
-                Field::new(name, DataType::Int64, true),
-                Arc::new(arr) as ArrayRef,
-            )
-        }
-        ColumnType::Boolean => {
-            let arr: BooleanArray = values
-                .iter()
-                .map(|v| v.and_then(cell_value_to_bool))
-                .collect();
-            (
-                Field::new(name, DataType::Boolean, true),
-                Arc::new(arr) as ArrayRef,
-            )
-        }
-        ColumnType::Date => {
-            // Excel serial number to days since Unix epoch
-            let arr: Date32Array = values
-                .iter()
-                .map(|v| v.and_then(cell_value_to_date32))
-                .collect();
-            (
-                Field::new(name, DataType::Date32, true),
-                Arc::new(arr) as ArrayRef,
-            )
-        }
-        ColumnType::DateTime => {
-            // Excel serial number to milliseconds since Unix epoch
-            let arr: TimestampMillisecondArray = values
-                .iter()
-                .map(|v| v.and_then(cell_value_to_timestamp_ms))
-                .collect();
-            (
-                Field::new(name, DataType::Timestamp(TimeUnit::Millisecond, None), true),
-                Arc::new(arr) as ArrayRef,
-            )
-        }
+    let mut builder = ColumnBuilder::new(col_type, values.len());
+    for value in values {
+        builder.append(*value);
     }
+    (arrow_field(name, col_type), builder.finish())
 }
 
 fn cell_value_to_f64(value: &CellValue) -> Option<f64> {
@@ -1192,6 +1680,11 @@ fn cell_value_to_date32(value: &CellValue) -> Option<i32> {
             // Unix epoch (1970-01-01) is Excel serial 25569
             Some((*n as i32) - 25569)
         }
+        CellValue::Date(d) => {
+            let dt = d.to_datetime();
+            let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1)?;
+            i32::try_from((dt.date() - epoch).num_days()).ok()
+        }
         _ => None,
     }
 }
@@ -1205,6 +1698,7 @@ fn cell_value_to_timestamp_ms(value: &CellValue) -> Option<i64> {
             let ms = days_since_unix * 24.0 * 60.0 * 60.0 * 1000.0;
             Some(ms as i64)
         }
+        CellValue::Date(d) => Some(d.to_datetime().and_utc().timestamp_millis()),
         _ => None,
     }
 }
@@ -1376,6 +1870,71 @@ mod tests {
         assert_eq!(infer_column_type(&values2), ColumnType::Float64);
     }
 
+    #[test]
+    fn test_infer_column_type_datetimes() {
+        let day = chrono::NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        // A date-time, as a date-formatted serial loads.
+        let v1 = CellValue::from(day.and_time(chrono::NaiveTime::MIN));
+        let v2 = CellValue::Number(1.0);
+        assert_eq!(infer_column_type(&[Some(&v1), None]), ColumnType::DateTime);
+        assert_eq!(
+            infer_column_type(&[Some(&v1), Some(&v2)]),
+            ColumnType::String
+        );
+
+        // Values set as dates make a Date32 column unless a time is mixed in.
+        let iso_day = CellValue::Date(DateValue::Date(day));
+        let iso_time = CellValue::Date(DateValue::parse("2024-03-15T08:00:00").unwrap());
+        assert_eq!(infer_column_type(&[Some(&iso_day)]), ColumnType::Date);
+        assert_eq!(cell_value_to_date32(&iso_day), Some(19797));
+        assert_eq!(
+            infer_column_type(&[Some(&iso_day), Some(&iso_time)]),
+            ColumnType::DateTime
+        );
+        assert_eq!(
+            cell_value_to_timestamp_ms(&iso_time),
+            Some(1_710_489_600_000)
+        );
+    }
+
+    #[test]
+    fn test_to_record_batch() {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_value(1, 1, "name");
+        ws.set_cell_value(1, 2, "qty");
+        ws.set_cell_value(1, 3, "when");
+        ws.set_cell_value(2, 1, "a");
+        ws.set_cell_value(2, 2, 3.0);
+        ws.set_cell_value(3, 1, "b");
+        ws.set_cell_value(3, 2, 4.0);
+        let when = chrono::NaiveDate::from_ymd_opt(2024, 3, 15)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        ws.set_cell_value(3, 3, when);
+
+        let batch = ws.to_record_batch(None, true).unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        let schema = batch.schema();
+        let names: Vec<_> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, ["name", "qty", "when"]);
+        assert_eq!(schema.field(1).data_type(), &DataType::Int64);
+        let when_col = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert!(when_col.is_null(0));
+        assert_eq!(when_col.value(1), when.and_utc().timestamp_millis());
+
+        let batch = ws.to_record_batch(Some("B2:B3"), false).unwrap();
+        assert_eq!(batch.schema().field(0).name(), "Column1");
+        assert_eq!(batch.num_rows(), 2);
+
+        let empty = Worksheet::new("E".to_string());
+        assert_eq!(empty.to_record_batch(None, true).unwrap().num_columns(), 0);
+    }
+
     #[test]
     fn test_infer_column_type_strings() {
         let v1 = CellValue::String(Arc::from("hello"));
@@ -1442,6 +2001,45 @@ mod tests {
         std::fs::remove_file(&path).ok();
     }
 
+    #[test]
+    fn test_chunked_export_reuses_builders_across_chunks() {
+        let mut ws = Worksheet::new("Data".to_string());
+        ws.set_cell_value(1, 1, CellValue::from("name"));
+        ws.set_cell_value(1, 2, CellValue::from("score"));
+        for r in 0..25u32 {
+            // Every third row leaves the name empty
+            if r % 3 != 0 {
+                ws.set_cell_value(r + 2, 1, CellValue::from(format!("item{}", r)));
+            }
+            ws.set_cell_value(r + 2, 2, CellValue::Number(r as f64 + 0.5));
+        }
+
+        let layout = ColumnLayout::new(&ws, (1, 1, 26, 2), true, &HashMap::new(), &HashMap::new());
+        let batches: Vec<RecordBatch> = layout.batches(10).map(|b| b.unwrap()).collect();
+        assert_eq!(
+            batches.iter().map(|b| b.num_rows()).collect::<Vec<_>>(),
+            vec![10, 10, 5]
+        );
+
+        // A builder emptied by finish() must not leak values into the next chunk
+        let last = &batches[2];
+        let names = last
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(names.value(0), "item20");
+        assert!(names.is_null(1) && names.is_null(4)); // r = 21 and 24
+        assert_eq!(names.value(3), "item23");
+        let scores = last
+            .column(1)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(scores.value(4), 24.5);
+        assert!(batches.iter().all(|b| b.schema() == layout.schema));
+    }
+
     #[test]
     fn test_export_roundtrip() {
         // Create a workbook with test data
@@ -1671,4 +2269,136 @@ mod tests {
         assert_eq!(result.rows_exported, 2);
         assert!(result.file_size > 0);
     }
+
+    #[test]
+    fn test_insert_record_batches_streams_every_batch() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("n", DataType::Int64, true),
+        ]));
+        let batch = |names: Vec<&str>, ns: Vec<i64>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(StringArray::from(names)) as ArrayRef,
+                    Arc::new(Int64Array::from(ns)) as ArrayRef,
+                ],
+            )
+        };
+        let batches = vec![batch(vec!["a", "b"], vec![1, 2]), batch(vec!["c"], vec![3])];
+
+        let mut ws = Worksheet::new("Data");
+        let result = ws
+            .insert_record_batches(&schema, batches, 2, 3, true, None)
+            .unwrap();
+
+        assert_eq!(result.rows_imported, 3);
+        assert_eq!(result.range_with_headers(), "C2:D5");
+        assert_eq!(
+            ws.get_cell_value(2, 3),
+            Some(&CellValue::String(Arc::from("name")))
+        );
+        assert_eq!(
+            ws.get_cell_value(5, 3),
+            Some(&CellValue::String(Arc::from("c")))
+        );
+        assert_eq!(ws.get_cell_value(5, 4), Some(&CellValue::Number(3.0)));
+    }
+
+    #[test]
+    fn test_parquet_import_applies_style() {
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)]));
+        let array: ArrayRef = Arc::new(Int64Array::from(vec![1i64, 2, 3]));
+        let batch = RecordBatch::try_new(schema.clone(), vec![array]).unwrap();
+        let file = NamedTempFile::new().unwrap();
+        let mut writer = ArrowWriter::try_new(file.reopen().unwrap(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        let opts = ParquetImportOptions::new().with_style(ImportStyleOptions::presentable());
+        wb.insert_from_parquet("Data", file.path().to_str().unwrap(), 1, 1, Some(opts))
+            .unwrap();
+
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        assert_eq!(ws.freeze_panes.as_deref(), Some("A2"));
+        assert_eq!(ws.auto_filter.as_ref().unwrap().range, "A1:A4");
+        assert!(ws.get_cell(1, 1).unwrap().style.is_some());
+    }
+
+    #[test]
+    fn test_append_from_parquet_streams_rows() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("qty", DataType::Int32, true),
+            Field::new("id", DataType::Int64, true),
+            Field::new("day", DataType::Date32, true),
+            Field::new("at", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+        ]));
+        let arrays: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(vec![Some("a & b"), None, Some("c")])),
+            Arc::new(Int32Array::from(vec![1, 2, 3])),
+            Arc::new(Int64Array::from(vec![1i64 << 60, 7, 8])),
+            // 2024-05-01
+            Arc::new(Date32Array::from(vec![19844, 19845, 19846])),
+            // 2024-05-01T12:00:00
+            Arc::new(TimestampMillisecondArray::from(vec![
+                1_714_564_800_000i64,
+                0,
+                0,
+            ])),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), arrays).unwrap();
+        let parquet = NamedTempFile::new().unwrap();
+        let mut writer = ArrowWriter::try_new(parquet.reopen().unwrap(), schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let out = NamedTempFile::with_suffix(".xlsx").unwrap();
+        let out_path = out.path().to_str().unwrap();
+        let mut wb = StreamingWorkbook::new(out_path).unwrap();
+        let mut sheet = wb.create_sheet("Data").unwrap();
+        wb.append_row(&mut sheet, vec![CellValue::from("Report")])
+            .unwrap();
+        // Small batches, so rows from several batches must line up
+        let opts = ParquetImportOptions::new().with_batch_size(2);
+        let result = wb
+            .append_from_parquet(&mut sheet, parquet.path().to_str().unwrap(), Some(opts))
+            .unwrap();
+        assert_eq!(result.rows_imported, 3);
+        assert_eq!(result.range_with_headers(), "A2:E5");
+
+        let style = ParquetImportOptions::new().with_style(ImportStyleOptions::presentable());
+        assert!(wb
+            .append_from_parquet(&mut sheet, parquet.path().to_str().unwrap(), Some(style))
+            .is_err());
+        wb.close(sheet).unwrap();
+
+        let loaded = Workbook::load(out_path).unwrap();
+        let ws = loaded.get_sheet_by_name("Data").unwrap();
+        assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::from("Report")));
+        assert_eq!(ws.get_cell_value(2, 5), Some(&CellValue::from("at")));
+        assert_eq!(ws.get_cell_value(3, 1), Some(&CellValue::from("a & b")));
+        assert!(ws.get_cell_value(4, 1).is_none());
+        assert_eq!(ws.get_cell_value(5, 2), Some(&CellValue::Number(3.0)));
+        assert_eq!(
+            ws.get_cell_value(3, 3),
+            Some(&CellValue::from((1i64 << 60).to_string()))
+        );
+
+        let day = ws.get_cell(3, 4).unwrap();
+        assert_eq!(day.number_format.as_deref(), Some(DATE_FORMAT));
+        let may_first = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        assert_eq!(
+            day.value,
+            CellValue::from(may_first.and_hms_opt(0, 0, 0).unwrap())
+        );
+        let at = ws.get_cell(3, 5).unwrap();
+        assert_eq!(at.number_format.as_deref(), Some(DATETIME_FORMAT));
+        assert_eq!(
+            at.value,
+            CellValue::from(may_first.and_hms_opt(12, 0, 0).unwrap())
+        );
+    }
 }
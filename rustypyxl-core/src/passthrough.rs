@@ -0,0 +1,164 @@
+//! Workbook-level package parts that are not modeled but are carried through a
+//! load/save round-trip unchanged: the theme, a VBA project, external link
+//! parts, custom XML, data connections, and whatever those parts reference in
+//! turn.
+//!
+//! Parts under directories the writer regenerates (worksheets, drawings,
+//! charts, media, tables, comments and threaded comments, persons, pivot
+//! parts) are never preserved here; those are either modeled or, for pivots,
+//! kept by [`PivotArtifacts`].
+//!
+//! [`PivotArtifacts`]: crate::workbook::PivotArtifacts
+
+/// Relationship types in workbook.xml.rels that the writer emits itself (or,
+/// for calcChain, deliberately drops because it goes stale on any edit).
+const REGENERATED_REL_TYPES: &[&str] = &[
+    "/worksheet",
+    "/chartsheet",
+    "/dialogsheet",
+    "/xlMacrosheet",
+    "/xlIntlMacrosheet",
+    "/sharedStrings",
+    "/styles",
+    "/pivotCacheDefinition",
+    "/calcChain",
+    "/person",
+];
+
+/// Package paths the writer produces on save; a preserved part must not
+/// collide with them.
+const WRITER_OWNED_PREFIXES: &[&str] = &[
+    "[Content_Types].xml",
+    "_rels/",
+    "docProps/",
+    "xl/workbook.xml",
+    "xl/_rels/workbook.xml.rels",
+    "xl/styles.xml",
+    "xl/sharedStrings.xml",
+    "xl/calcChain.xml",
+    "xl/worksheets/",
+    "xl/dialogsheets/",
+    "xl/macrosheets/",
+    "xl/drawings/",
+    "xl/charts/",
+    "xl/media/",
+    "xl/tables/",
+    "xl/comments",
+    "xl/threadedComments/",
+    "xl/persons/",
+    "xl/pivotCache/",
+    "xl/pivotTables/",
+];
+
+/// The main part content type of a plain .xlsx workbook.
+pub(crate) const XLSX_WORKBOOK_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml";
+
+/// A workbook.xml.rels entry pointing at a preserved part.
+#[derive(Clone, Debug)]
+pub struct PreservedWorkbookRel {
+    /// Relationship id in the source file.
+    pub id: String,
+    /// Relationship type URI.
+    pub rel_type: String,
+    /// Target as written in the source, relative to `xl/`.
+    pub target: String,
+    /// TargetMode="External".
+    pub external: bool,
+}
+
+/// One preserved package part.
+#[derive(Clone, Debug)]
+pub struct PreservedPart {
+    /// Package path without a leading '/', e.g. "xl/theme/theme1.xml".
+    pub path: String,
+    /// Raw bytes.
+    pub bytes: Vec<u8>,
+    /// Content type from the source `[Content_Types].xml`; None for `.rels`
+    /// parts, which the package default covers.
+    pub content_type: Option<String>,
+}
+
+/// Unmodeled workbook-level parts kept from a loaded file. Empty for
+/// workbooks created from scratch.
+#[derive(Clone, Debug, Default)]
+pub struct PreservedParts {
+    /// Workbook relationships to the preserved parts.
+    pub rels: Vec<PreservedWorkbookRel>,
+    /// The parts, including their own `.rels` parts and everything reachable
+    /// from them.
+    pub parts: Vec<PreservedPart>,
+    /// The raw `<externalReferences>` element from workbook.xml, whose `r:id`s
+    /// cite entries in `rels`.
+    pub external_references_xml: Option<String>,
+    /// The source's workbook content type when it is not the plain .xlsx one
+    /// (e.g. macro-enabled), so the preserved VBA project stays valid.
+    pub workbook_content_type: Option<String>,
+}
+
+impl PreservedParts {
+    /// Whether there is anything to preserve.
+    pub fn is_empty(&self) -> bool {
+        self.rels.is_empty()
+            && self.parts.is_empty()
+            && self.external_references_xml.is_none()
+            && self.workbook_content_type.is_none()
+    }
+}
+
+/// Whether a workbook relationship type is one the writer regenerates.
+pub(crate) fn is_regenerated_rel(rel_type: &str) -> bool {
+    REGENERATED_REL_TYPES
+        .iter()
+        .any(|suffix| rel_type.ends_with(suffix))
+}
+
+/// Whether the writer produces `path` itself on save.
+pub(crate) fn is_writer_owned(path: &str) -> bool {
+    WRITER_OWNED_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+}
+
+/// The `.rels` part that belongs to `path`.
+pub(crate) fn rels_path_for(path: &str) -> String {
+    match path.rfind('/') {
+        Some(idx) => format!("{}/_rels/{}.rels", &path[..idx], &path[idx + 1..]),
+        None => format!("_rels/{}.rels", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regenerated_and_owned() {
+        assert!(is_regenerated_rel(
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet"
+        ));
+        assert!(is_regenerated_rel(
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/calcChain"
+        ));
+        assert!(!is_regenerated_rel(
+            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme"
+        ));
+        assert!(!is_regenerated_rel(
+            "http://schemas.microsoft.com/office/2006/relationships/vbaProject"
+        ));
+
+        assert!(is_writer_owned("xl/media/image1.png"));
+        assert!(is_writer_owned("xl/comments1.xml"));
+        assert!(!is_writer_owned("xl/theme/theme1.xml"));
+        assert!(!is_writer_owned("customXml/item1.xml"));
+    }
+
+    #[test]
+    fn test_rels_path_for() {
+        assert_eq!(
+            rels_path_for("xl/theme/theme1.xml"),
+            "xl/theme/_rels/theme1.xml.rels"
+        );
+        assert_eq!(rels_path_for("item.xml"), "_rels/item.xml.rels");
+    }
+}
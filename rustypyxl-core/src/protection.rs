@@ -0,0 +1,461 @@
+//! Password verifiers for sheet and workbook protection.
+//!
+//! Protection passwords are never stored. Excel keeps one of two verifiers:
+//!
+//! - the legacy 16-bit XOR hash in a `password` attribute, which any
+//!   spreadsheet program understands but which collides trivially; and
+//! - a salted, iterated hash (`algorithmName`, `hashValue`, `saltValue`,
+//!   `spinCount`), which is what Excel writes today.
+//!
+//! New passwords get the iterated SHA-512 form ([`PasswordHash::new`]) when
+//! the `encrypt` feature is on, and the legacy verifier otherwise. Verifiers
+//! loaded from a file are kept as they are and checked with whichever scheme
+//! they use; checking an iterated hash needs the SHA-2 digests of the
+//! `decrypt` feature.
+
+use quick_xml::events::BytesStart;
+#[cfg(feature = "decrypt")]
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Algorithm Excel uses for new protection passwords.
+pub const DEFAULT_ALGORITHM: &str = "SHA-512";
+/// Hash iterations Excel uses for new protection passwords.
+pub const DEFAULT_SPIN_COUNT: u32 = 100_000;
+
+/// Compute the legacy 16-bit Excel password verifier
+/// (CreatePasswordVerifier_Method1 from MS-XLS 2.2.9), as stored in the
+/// `password` attribute of `sheetProtection`.
+pub fn legacy_password_hash(password: &str) -> u16 {
+    let bytes = password.as_bytes();
+    let mut verifier: u16 = 0;
+    for &b in bytes
+        .iter()
+        .rev()
+        .chain(std::iter::once(&(bytes.len() as u8)))
+    {
+        verifier = ((verifier >> 14) & 0x0001) | ((verifier << 1) & 0x7fff);
+        verifier ^= b as u16;
+    }
+    verifier ^ 0xCE4B
+}
+
+/// Check `password` against a legacy verifier as written in a file (hex).
+pub fn verify_legacy_password(hash: &str, password: &str) -> bool {
+    u16::from_str_radix(hash.trim(), 16).is_ok_and(|h| h == legacy_password_hash(password))
+}
+
+/// A salted, iterated password hash as stored in protection elements
+/// (ECMA-376 Part 1, 18.2.29). Salt and hash are base64, as in the file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordHash {
+    /// Hash algorithm name, e.g. "SHA-512".
+    pub algorithm_name: String,
+    /// Base64 of the final hash.
+    pub hash_value: String,
+    /// Base64 of the salt.
+    pub salt_value: String,
+    /// Number of extra hash rounds.
+    pub spin_count: u32,
+}
+
+impl PasswordHash {
+    /// Hash `password` the way Excel does: SHA-512, a fresh 16-byte salt and
+    /// 100,000 rounds. Requires the `encrypt` feature.
+    #[cfg(feature = "encrypt")]
+    pub fn new(password: &str) -> Self {
+        Self::with_salt(password, &fresh_salt(), DEFAULT_SPIN_COUNT)
+    }
+
+    /// Hash `password` with SHA-512, a fresh salt and `spin_count` rounds.
+    /// Requires the `encrypt` feature.
+    #[cfg(feature = "encrypt")]
+    pub fn with_spin_count(password: &str, spin_count: u32) -> Self {
+        Self::with_salt(password, &fresh_salt(), spin_count)
+    }
+
+    /// Hash `password` with SHA-512, a given salt and round count. Requires
+    /// the `decrypt` feature.
+    #[cfg(feature = "decrypt")]
+    pub fn with_salt(password: &str, salt: &[u8], spin_count: u32) -> Self {
+        let hash =
+            iterate(DEFAULT_ALGORITHM, salt, password, spin_count).expect("SHA-512 is supported");
+        PasswordHash {
+            algorithm_name: DEFAULT_ALGORITHM.to_string(),
+            hash_value: crate::utils::base64_encode(&hash),
+            salt_value: crate::utils::base64_encode(salt),
+            spin_count,
+        }
+    }
+
+    /// Whether `password` produces this hash. False for algorithms other than
+    /// SHA-256/384/512, for malformed salts, and always without the `decrypt`
+    /// feature, which provides the digests.
+    pub fn verify(&self, password: &str) -> bool {
+        #[cfg(feature = "decrypt")]
+        {
+            use crate::utils::{base64_decode, base64_encode};
+            let Some(salt) = base64_decode(&self.salt_value) else {
+                return false;
+            };
+            iterate(&self.algorithm_name, &salt, password, self.spin_count)
+                .is_some_and(|hash| base64_encode(&hash) == self.hash_value.trim())
+        }
+        #[cfg(not(feature = "decrypt"))]
+        {
+            let _ = password;
+            false
+        }
+    }
+
+    /// Read `{prefix}AlgorithmName`, `{prefix}HashValue`, ... off an element
+    /// (`algorithmName`, ... when `prefix` is empty). None unless the
+    /// algorithm, hash and salt are all there.
+    pub(crate) fn from_element(e: &BytesStart, prefix: &str) -> Option<Self> {
+        let mut algorithm_name = None;
+        let mut hash_value = None;
+        let mut salt_value = None;
+        let mut spin_count = 0;
+        for attr in e.attributes().flatten() {
+            let key = attr.key.local_name();
+            let Some(key) = std::str::from_utf8(key.as_ref())
+                .ok()
+                .and_then(|k| k.strip_prefix(prefix))
+            else {
+                continue;
+            };
+            let value = String::from_utf8_lossy(&attr.value).into_owned();
+            match key.to_ascii_lowercase().as_str() {
+                "algorithmname" => algorithm_name = Some(value),
+                "hashvalue" => hash_value = Some(value),
+                "saltvalue" => salt_value = Some(value),
+                "spincount" => spin_count = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+        Some(PasswordHash {
+            algorithm_name: algorithm_name?,
+            hash_value: hash_value?,
+            salt_value: salt_value?,
+            spin_count,
+        })
+    }
+
+    /// Write the four attributes, named as [`from_element`](Self::from_element)
+    /// reads them.
+    pub(crate) fn push_attributes(&self, e: &mut BytesStart, prefix: &str) {
+        let name = |field: &str| {
+            if prefix.is_empty() {
+                let mut chars = field.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            } else {
+                format!("{prefix}{field}")
+            }
+        };
+        e.push_attribute((name("AlgorithmName").as_str(), self.algorithm_name.as_str()));
+        e.push_attribute((name("HashValue").as_str(), self.hash_value.as_str()));
+        e.push_attribute((name("SaltValue").as_str(), self.salt_value.as_str()));
+        e.push_attribute((
+            name("SpinCount").as_str(),
+            self.spin_count.to_string().as_str(),
+        ));
+    }
+}
+
+/// The verifiers to store for a new password: an iterated SHA-512 hash, or
+/// without the `encrypt` feature the legacy verifier (hex) in its place.
+pub(crate) fn new_verifier(password: &str) -> (Option<PasswordHash>, Option<String>) {
+    #[cfg(feature = "encrypt")]
+    {
+        (Some(PasswordHash::new(password)), None)
+    }
+    #[cfg(not(feature = "encrypt"))]
+    {
+        (
+            None,
+            Some(format!("{:04X}", legacy_password_hash(password))),
+        )
+    }
+}
+
+/// The protection verifiers a password can be checked against, newest
+/// first.
+pub(crate) fn verify(hash: Option<&PasswordHash>, legacy: Option<&str>, password: &str) -> bool {
+    match (hash, legacy) {
+        (Some(hash), _) => hash.verify(password),
+        (None, Some(legacy)) => verify_legacy_password(legacy, password),
+        // Intended: protection without a password (Excel's "Protect Sheet"
+        // with the password box left empty) is lifted by any password,
+        // including an empty one, just as Excel unprotects it without asking.
+        (None, None) => true,
+    }
+}
+
+/// Workbook-level protection: locks the sheet structure (adding, removing,
+/// renaming, reordering sheets), the window layout, or revision tracking.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorkbookProtection {
+    /// Block structural changes to the sheets.
+    pub lock_structure: bool,
+    /// Block moving and resizing the workbook windows.
+    pub lock_windows: bool,
+    /// Block turning off revision tracking.
+    pub lock_revision: bool,
+    /// Iterated hash of the password for lifting the protection.
+    pub workbook_hash: Option<PasswordHash>,
+    /// Legacy verifier of that password, as loaded from a file (hex).
+    pub workbook_password: Option<String>,
+    /// Iterated hash of the password for lifting revision locking.
+    pub revisions_hash: Option<PasswordHash>,
+    /// Legacy verifier of the revisions password, as loaded (hex).
+    pub revisions_password: Option<String>,
+}
+
+impl Default for WorkbookProtection {
+    fn default() -> Self {
+        WorkbookProtection {
+            lock_structure: true,
+            lock_windows: false,
+            lock_revision: false,
+            workbook_hash: None,
+            workbook_password: None,
+            revisions_hash: None,
+            revisions_password: None,
+        }
+    }
+}
+
+impl WorkbookProtection {
+    /// Structure protection without a password.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require `password` to lift the protection.
+    pub fn with_password(mut self, password: &str) -> Self {
+        (self.workbook_hash, self.workbook_password) = new_verifier(password);
+        self
+    }
+
+    /// Require `password` to lift revision locking.
+    pub fn with_revisions_password(mut self, password: &str) -> Self {
+        (self.revisions_hash, self.revisions_password) = new_verifier(password);
+        self
+    }
+
+    pub fn with_lock_windows(mut self, lock: bool) -> Self {
+        self.lock_windows = lock;
+        self
+    }
+
+    pub fn with_lock_revision(mut self, lock: bool) -> Self {
+        self.lock_revision = lock;
+        self
+    }
+
+    /// Whether a password is needed to lift the protection.
+    pub fn has_password(&self) -> bool {
+        self.workbook_hash.is_some() || self.workbook_password.is_some()
+    }
+
+    /// Whether `password` lifts the protection.
+    pub fn verify_password(&self, password: &str) -> bool {
+        verify(
+            self.workbook_hash.as_ref(),
+            self.workbook_password.as_deref(),
+            password,
+        )
+    }
+
+    /// Whether `password` lifts revision locking.
+    pub fn verify_revisions_password(&self, password: &str) -> bool {
+        verify(
+            self.revisions_hash.as_ref(),
+            self.revisions_password.as_deref(),
+            password,
+        )
+    }
+
+    /// Read a `<workbookProtection>` element.
+    pub(crate) fn from_element(e: &BytesStart) -> Self {
+        let mut protection = WorkbookProtection {
+            lock_structure: false,
+            workbook_hash: PasswordHash::from_element(e, "workbook"),
+            revisions_hash: PasswordHash::from_element(e, "revisions"),
+            ..Default::default()
+        };
+        for attr in e.attributes().flatten() {
+            let value = String::from_utf8_lossy(&attr.value);
+            let on = matches!(value.as_ref(), "1" | "true");
+            match attr.key.local_name().as_ref() {
+                b"lockStructure" => protection.lock_structure = on,
+                b"lockWindows" => protection.lock_windows = on,
+                b"lockRevision" => protection.lock_revision = on,
+                b"workbookPassword" => protection.workbook_password = Some(value.into_owned()),
+                b"revisionsPassword" => protection.revisions_password = Some(value.into_owned()),
+                _ => {}
+            }
+        }
+        protection
+    }
+
+    /// The `<workbookProtection>` element of a workbook.xml part, if any.
+    pub(crate) fn find_in(workbook_xml: &[u8]) -> Option<Self> {
+        use quick_xml::events::Event;
+        let mut reader = quick_xml::Reader::from_reader(workbook_xml);
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"workbookProtection" => return Some(Self::from_element(&e)),
+                    // Protection precedes the sheet list; stop looking there.
+                    b"bookViews" | b"sheets" => return None,
+                    _ => {}
+                },
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => {}
+            }
+            buf.clear();
+        }
+    }
+
+    /// The `<workbookProtection>` element to write.
+    pub(crate) fn to_element(&self) -> BytesStart<'static> {
+        let mut e = BytesStart::new("workbookProtection");
+        if let Some(ref hash) = self.workbook_hash {
+            hash.push_attributes(&mut e, "workbook");
+        } else if let Some(ref legacy) = self.workbook_password {
+            e.push_attribute(("workbookPassword", legacy.as_str()));
+        }
+        if let Some(ref hash) = self.revisions_hash {
+            hash.push_attributes(&mut e, "revisions");
+        } else if let Some(ref legacy) = self.revisions_password {
+            e.push_attribute(("revisionsPassword", legacy.as_str()));
+        }
+        e.push_attribute(("lockStructure", if self.lock_structure { "1" } else { "0" }));
+        e.push_attribute(("lockWindows", if self.lock_windows { "1" } else { "0" }));
+        e.push_attribute(("lockRevision", if self.lock_revision { "1" } else { "0" }));
+        e
+    }
+}
+
+/// H0 = H(salt + UTF-16LE password); Hn = H(Hn-1 + n as u32 LE).
+#[cfg(feature = "decrypt")]
+fn iterate(algorithm: &str, salt: &[u8], password: &str, spin_count: u32) -> Option<Vec<u8>> {
+    fn run<D: Digest>(salt: &[u8], password: &[u8], spin_count: u32) -> Vec<u8> {
+        let mut hash = D::new()
+            .chain_update(salt)
+            .chain_update(password)
+            .finalize();
+        for i in 0..spin_count {
+            hash = D::new()
+                .chain_update(&hash)
+                .chain_update(i.to_le_bytes())
+                .finalize();
+        }
+        hash.to_vec()
+    }
+    let password: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    match algorithm.to_ascii_uppercase().replace('-', "").as_str() {
+        "SHA512" => Some(run::<Sha512>(salt, &password, spin_count)),
+        "SHA384" => Some(run::<Sha384>(salt, &password, spin_count)),
+        "SHA256" => Some(run::<Sha256>(salt, &password, spin_count)),
+        _ => None,
+    }
+}
+
+/// 16 random bytes. A salt only has to be unique, so if the OS has no
+/// randomness to give, the clock and a counter stand in.
+#[cfg(feature = "encrypt")]
+fn fresh_salt() -> [u8; 16] {
+    let mut salt = [0u8; 16];
+    if getrandom::fill(&mut salt).is_err() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        salt[..8].copy_from_slice(&nanos.to_le_bytes());
+        salt[8..].copy_from_slice(&COUNTER.fetch_add(1, Ordering::Relaxed).to_le_bytes());
+    }
+    salt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_password_hash_known_vectors() {
+        // Reference values from openpyxl's hash_password / MS-XLS Method1.
+        assert_eq!(legacy_password_hash("test"), 0xCBEB);
+        assert_eq!(legacy_password_hash(""), 0xCE4B);
+        assert_eq!(legacy_password_hash("password"), 0x83AF);
+        assert!(verify_legacy_password("CBEB", "test"));
+        assert!(!verify_legacy_password("CBEB", "Test"));
+    }
+
+    #[cfg(feature = "decrypt")]
+    #[test]
+    fn test_sha512_known_vector() {
+        // Reference computed independently from the ECMA-376 algorithm.
+        let salt = crate::utils::base64_decode("hVQ3rYn2ZEm1QU+kJ5sQxQ==").unwrap();
+        let hash = PasswordHash::with_salt("password", &salt, 100_000);
+        assert_eq!(hash.algorithm_name, "SHA-512");
+        assert_eq!(hash.salt_value, "hVQ3rYn2ZEm1QU+kJ5sQxQ==");
+        assert_eq!(
+            hash.hash_value,
+            "7V0rSekUB0zmR1mu/tmoPbQ/GpXgiUlNFHNt9FzTKTw0fSmlZ4pBYE/0nAF/ne0RnZ0OGwceR89jzFL52S9+tg=="
+        );
+        assert!(hash.verify("password"));
+        assert!(!hash.verify("Password"));
+    }
+
+    #[cfg(feature = "encrypt")]
+    #[test]
+    fn test_fresh_hashes_are_salted() {
+        let a = PasswordHash::with_salt("pw", &fresh_salt(), 10);
+        let b = PasswordHash::with_salt("pw", &fresh_salt(), 10);
+        assert_ne!(a.salt_value, b.salt_value);
+        assert_ne!(a.hash_value, b.hash_value);
+        assert!(a.verify("pw") && b.verify("pw"));
+
+        let mut other = a.clone();
+        other.algorithm_name = "MD5".to_string();
+        assert!(!other.verify("pw"));
+    }
+
+    #[test]
+    fn test_no_password_accepts_any_password() {
+        assert!(verify(None, None, "anything"));
+        assert!(verify(None, None, ""));
+        let protection = WorkbookProtection::new();
+        assert!(!protection.has_password());
+        assert!(protection.verify_password("anything"));
+        assert!(protection.verify_revisions_password(""));
+    }
+
+    #[test]
+    fn test_new_password_is_never_stored_in_plain() {
+        let protection = WorkbookProtection::new().with_password("book");
+        assert!(protection.has_password());
+        assert!(protection.verify_password("book"));
+        assert!(!protection.verify_password("Book"));
+        assert_ne!(protection.workbook_password.as_deref(), Some("book"));
+    }
+
+    #[cfg(feature = "decrypt")]
+    #[test]
+    fn test_workbook_protection_element_roundtrip() {
+        let mut protection = WorkbookProtection::new().with_lock_windows(true);
+        protection.workbook_hash = Some(PasswordHash::with_salt("book", b"0123456789abcdef", 5));
+        protection.revisions_password = Some("CBEB".to_string());
+        let read = WorkbookProtection::from_element(&protection.to_element());
+        assert_eq!(read, protection);
+        assert!(read.verify_password("book"));
+        assert!(!read.verify_password("nope"));
+        assert!(read.verify_revisions_password("test"));
+    }
+}
@@ -0,0 +1,676 @@
+//! Styling helpers that apply one look across a cell range.
+//!
+//! These resolve each distinct style to a registry xf once and share it
+//! between cells, rather than leaving every cell for the save pass to
+//! resolve individually: striping a 100k-row range yields two xfs, not
+//! 100k identical style objects.
+
+use crate::cell::{CellValue, InternedString};
+use crate::error::{Result, RustypyxlError};
+use crate::numfmt::is_date_format;
+use crate::style::{CellStyle, Color, Fill, Protection, StyleRegistry};
+use crate::utils::{parse_coordinate, parse_range};
+use crate::workbook::Workbook;
+use crate::worksheet::{CellData, Worksheet};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+impl Workbook {
+    /// Layer `style` over every cell in `range` (e.g. "B2:F20"), creating
+    /// blank cells as needed. Only the components `style` sets are applied;
+    /// each cell keeps the rest of its own style, including protection and
+    /// number format. See [`CellStyle::merge`].
+    pub fn set_range_style(
+        &mut self,
+        sheet_name: &str,
+        range: &str,
+        style: &CellStyle,
+    ) -> Result<()> {
+        let ((min_row, min_col), (max_row, max_col)) = parse_area(range)?;
+        let (ws, styles) = self.sheet_and_styles(sheet_name)?;
+        let mut merger = StyleMerger::new(style.clone());
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                merger.apply(styles, ws.get_or_create_cell_mut(row, col));
+            }
+        }
+        Ok(())
+    }
+
+    /// Layer `style` over one cell's existing style, with the same semantics
+    /// as [`Workbook::set_range_style`].
+    pub fn merge_cell_style(
+        &mut self,
+        sheet_name: &str,
+        row: u32,
+        column: u32,
+        style: &CellStyle,
+    ) -> Result<()> {
+        let (ws, styles) = self.sheet_and_styles(sheet_name)?;
+        StyleMerger::new(style.clone()).apply(styles, ws.get_or_create_cell_mut(row, column));
+        Ok(())
+    }
+
+    /// Merge `range` (see [`Worksheet::merge_cells`]) and, in the same call,
+    /// set the anchor cell's value and layer `style` over every covered cell.
+    /// Excel draws a merge's borders and fill from the covered cells, so
+    /// styling only the anchor leaves the right and bottom edges bare.
+    ///
+    /// Nothing changes if the merge fails.
+    pub fn merge_cells_styled(
+        &mut self,
+        sheet_name: &str,
+        range: &str,
+        value: Option<CellValue>,
+        style: Option<&CellStyle>,
+    ) -> Result<()> {
+        let ((min_row, min_col), _) = parse_area(range)?;
+        self.sheet_and_styles(sheet_name)?.0.merge_cells(range)?;
+        if let Some(value) = value {
+            self.set_cell_value_in_sheet(sheet_name, min_row, min_col, value)?;
+        }
+        if let Some(style) = style {
+            self.set_range_style(sheet_name, range, style)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the palette style `style_name` (see [`StyleRegistry::palette`])
+    /// to every cell in `range`, layered like [`Workbook::set_range_style`].
+    pub fn apply_named_style(
+        &mut self,
+        sheet_name: &str,
+        range: &str,
+        style_name: &str,
+    ) -> Result<()> {
+        let style = self.styles.palette.resolve(style_name)?;
+        self.set_range_style(sheet_name, range, &style)
+    }
+
+    /// Stripe the rows of `range` (e.g. "A1:F500") with alternating solid
+    /// fills: the first banded row gets `color_a`, the next `color_b`, and so
+    /// on. With `header`, the range's first row is left unbanded. A `None`
+    /// color leaves that band's rows unfilled.
+    ///
+    /// Blank cells in the range are created so the bands are continuous.
+    /// Cells that already carry a style keep their font, border, number
+    /// format, alignment, and protection; only the fill is replaced.
+    pub fn apply_banding(
+        &mut self,
+        sheet_name: &str,
+        range: &str,
+        color_a: Option<Color>,
+        color_b: Option<Color>,
+        header: bool,
+    ) -> Result<()> {
+        let ((min_row, min_col), (max_row, max_col)) = parse_area(range)?;
+        let first_band_row = if header { min_row + 1 } else { min_row };
+        let (ws, styles) = self.sheet_and_styles(sheet_name)?;
+
+        let mut bands = [color_a, color_b].map(|color| {
+            color.map(|c| StyleMerger::new(CellStyle::new().with_fill(Fill::solid(c))))
+        });
+        for row in first_band_row..=max_row {
+            let Some(merger) = &mut bands[((row - first_band_row) % 2) as usize] else {
+                continue;
+            };
+            for col in min_col..=max_col {
+                merger.apply(styles, ws.get_or_create_cell_mut(row, col));
+            }
+        }
+        Ok(())
+    }
+
+    /// Lock a sheet's model in one pass: formula cells are locked (and, with
+    /// `hidden`, their formulas hidden from the formula bar), every other
+    /// stored cell is unlocked, and sheet protection is turned on. Returns
+    /// the number of formula cells locked.
+    ///
+    /// Existing protection settings are kept; `password`, when given,
+    /// replaces the sheet password. Fonts, fills and formats are untouched,
+    /// and the cells share two xfs between them. Cells that were never
+    /// written have no style to unlock and stay locked, as in Excel.
+    pub fn protect_formulas(
+        &mut self,
+        sheet_name: &str,
+        hidden: bool,
+        password: Option<&str>,
+    ) -> Result<usize> {
+        let (ws, styles) = self.sheet_and_styles(sheet_name)?;
+        let mut locked = StyleMerger::new(
+            CellStyle::new().with_protection(Protection::new().with_hidden(hidden)),
+        );
+        let mut unlocked =
+            StyleMerger::new(CellStyle::new().with_protection(Protection::unlocked()));
+        let mut formulas = 0;
+        ws.unpack_columns();
+        for cell in ws.cells.values_mut() {
+            if cell.value.is_formula() {
+                locked.apply(styles, cell);
+                formulas += 1;
+            } else {
+                unlocked.apply(styles, cell);
+            }
+        }
+
+        match ws.protection.as_mut() {
+            Some(protection) => {
+                protection.sheet = true;
+                if let Some(password) = password {
+                    protection.set_password(password);
+                }
+            }
+            None => ws.enable_protection(password.map(str::to_string)),
+        }
+        Ok(formulas)
+    }
+
+    /// Borrow a sheet and the style registry at the same time.
+    fn sheet_and_styles(
+        &mut self,
+        sheet_name: &str,
+    ) -> Result<(&mut Worksheet, &mut StyleRegistry)> {
+        let idx = self
+            .sheet_position(sheet_name)
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(sheet_name.to_string()))?;
+        Ok((&mut self.worksheets[idx], &mut self.styles))
+    }
+}
+
+/// A merged style and its xf index, alongside the style it was merged from.
+struct MergedStyle {
+    /// Held so the source's address, used as the cache key, can't be reused
+    /// by a style allocated later in the same pass.
+    _source: Option<Arc<CellStyle>>,
+    style: Arc<CellStyle>,
+    xf_index: u32,
+}
+
+/// Applies one overlay style to many cells, resolving each distinct result
+/// to an xf once. Cells that start from the same style (the same `Arc`, as
+/// loaded cells and earlier bulk calls share them) and the same per-cell
+/// number format end up sharing one merged style and one xf index.
+struct StyleMerger {
+    overlay: CellStyle,
+    /// Keyed by the source style's address and the cell's own number format.
+    merged: HashMap<(usize, Option<InternedString>), MergedStyle>,
+}
+
+impl StyleMerger {
+    fn new(overlay: CellStyle) -> Self {
+        StyleMerger {
+            overlay,
+            merged: HashMap::new(),
+        }
+    }
+
+    fn apply(&mut self, styles: &mut StyleRegistry, cell: &mut CellData) {
+        // A format set directly on the cell only counts when its style has
+        // none; the save pass resolves them the same way.
+        let own_format = match &cell.style {
+            Some(style) if style.number_format.is_some() => None,
+            _ => cell.number_format.clone(),
+        };
+        let source = cell.style.as_ref().map_or(0, |s| Arc::as_ptr(s) as usize);
+        let overlay = &self.overlay;
+        let merged = self
+            .merged
+            .entry((source, own_format.clone()))
+            .or_insert_with(|| {
+                let mut base = cell.style.as_deref().cloned().unwrap_or_default();
+                if base.number_format.is_none() {
+                    base.number_format = own_format;
+                }
+                let style = base.merge(overlay);
+                MergedStyle {
+                    _source: cell.style.clone(),
+                    xf_index: styles.get_or_add_cell_xf(&style) as u32,
+                    style: Arc::new(style),
+                }
+            });
+        cell.style = Some(merged.style.clone());
+        cell.style_index = Some(merged.xf_index);
+    }
+}
+
+/// Unit a range of figures is displayed in by
+/// [`Worksheet::apply_scaled_number_format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberScale {
+    /// Whole numbers with thousands separators.
+    Units,
+    /// Thousands, e.g. 42,000 shows as "42K".
+    Thousands,
+    /// Millions to one decimal, e.g. 1,250,000 shows as "1.3M".
+    Millions,
+    /// Billions to one decimal.
+    Billions,
+}
+
+impl NumberScale {
+    /// The format code for this scale. Each trailing comma divides the
+    /// displayed value by 1000 without changing the stored one.
+    pub fn format_code(self) -> &'static str {
+        match self {
+            NumberScale::Units => "#,##0",
+            NumberScale::Thousands => "#,##0,\"K\"",
+            NumberScale::Millions => "#,##0.0,,\"M\"",
+            NumberScale::Billions => "#,##0.0,,,\"B\"",
+        }
+    }
+
+    /// The largest scale that still shows `magnitude` as at least 1.
+    pub fn for_magnitude(magnitude: f64) -> Self {
+        let magnitude = magnitude.abs();
+        if magnitude >= 1e9 {
+            NumberScale::Billions
+        } else if magnitude >= 1e6 {
+            NumberScale::Millions
+        } else if magnitude >= 1e3 {
+            NumberScale::Thousands
+        } else {
+            NumberScale::Units
+        }
+    }
+
+    /// Parse a scale name: "units", "thousands", "millions" or "billions".
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "units" => Some(NumberScale::Units),
+            "thousands" => Some(NumberScale::Thousands),
+            "millions" => Some(NumberScale::Millions),
+            "billions" => Some(NumberScale::Billions),
+            _ => None,
+        }
+    }
+}
+
+impl Worksheet {
+    /// Give every numeric cell in `range` a scaled number format such as
+    /// `#,##0,"K"`. With `scale` of `None` the scale is picked from the data:
+    /// the largest absolute value in the range decides, so a column of
+    /// figures is shown in one consistent unit. Returns the scale applied.
+    ///
+    /// Numbers, and formulas whose cached result is numeric, are formatted;
+    /// text, booleans, blanks, and date-formatted cells are left alone.
+    pub fn apply_scaled_number_format(
+        &mut self,
+        range: &str,
+        scale: Option<NumberScale>,
+    ) -> Result<NumberScale> {
+        let ((min_row, min_col), (max_row, max_col)) = parse_area(range)?;
+        let mut targets = Vec::new();
+        let mut largest = 0.0f64;
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                if let Some(value) = self.get_cell(row, col).as_deref().and_then(numeric_value) {
+                    largest = largest.max(value.abs());
+                    targets.push((row, col));
+                }
+            }
+        }
+
+        let scale = scale.unwrap_or_else(|| NumberScale::for_magnitude(largest));
+        let code: InternedString = Arc::from(scale.format_code());
+        for (row, col) in targets {
+            let cell = self.get_or_create_cell_mut(row, col);
+            // A format carried on the style wins over the cell's own at save
+            // time, so it has to be replaced there too.
+            if let Some(style) = &cell.style {
+                if style.number_format.is_some() {
+                    let mut style = (**style).clone();
+                    style.number_format = Some(code.clone());
+                    cell.style = Some(Arc::new(style));
+                }
+            }
+            cell.number_format = Some(code.clone());
+            cell.style_index = None;
+        }
+        Ok(scale)
+    }
+}
+
+/// Bounds of a range like "A1:C10", also accepting a single cell ("B2").
+fn parse_area(range: &str) -> Result<((u32, u32), (u32, u32))> {
+    let (start, end) = if range.contains(':') {
+        parse_range(range)?
+    } else {
+        let cell = parse_coordinate(range)?;
+        (cell, cell)
+    };
+    if end.0 < start.0 || end.1 < start.1 {
+        return Err(RustypyxlError::InvalidCoordinate(format!(
+            "Invalid range: {}",
+            range
+        )));
+    }
+    Ok((start, end))
+}
+
+/// The number a cell displays, if it displays one and isn't formatted as a
+/// date.
+fn numeric_value(cell: &CellData) -> Option<f64> {
+    let format = cell
+        .style
+        .as_ref()
+        .and_then(|s| s.number_format.as_deref())
+        .or(cell.number_format.as_deref());
+    if format.is_some_and(is_date_format) {
+        return None;
+    }
+    match &cell.value {
+        CellValue::Number(n) if n.is_finite() => Some(*n),
+        CellValue::Formula(_) => cell
+            .cached_formula_value
+            .as_deref()
+            .and_then(|v| v.parse::<f64>().ok())
+            .filter(|n| n.is_finite()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::Font;
+
+    fn workbook() -> Workbook {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb
+    }
+
+    fn fill_rgb(wb: &Workbook, row: u32, col: u32) -> Option<String> {
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        let style = ws.get_cell(row, col)?.style.clone()?;
+        style.fill.as_ref()?.fg_color.as_ref()?.rgb.clone()
+    }
+
+    #[test]
+    fn test_merge_cells_styled_styles_every_covered_cell() {
+        use crate::style::{Border, BorderStyle};
+        let mut wb = workbook();
+        wb.set_cell_value_in_sheet("Data", 2, 3, CellValue::from("hidden"))
+            .unwrap();
+        let style = CellStyle::new()
+            .with_fill(Fill::solid(Color::rgb("FFFFCC00")))
+            .with_border(Border::all(BorderStyle::thin()));
+        wb.merge_cells_styled(
+            "Data",
+            "B2:D3",
+            Some(CellValue::from("Title")),
+            Some(&style),
+        )
+        .unwrap();
+
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        assert_eq!(ws.merged_cells, vec![("B2".to_string(), "D3".to_string())]);
+        assert_eq!(ws.get_cell_value(2, 2), Some(&CellValue::from("Title")));
+        assert_eq!(ws.get_cell_value(2, 3), Some(&CellValue::Empty));
+        for row in 2..=3 {
+            for col in 2..=4 {
+                assert_eq!(fill_rgb(&wb, row, col).as_deref(), Some("FFFFCC00"));
+            }
+        }
+        assert_eq!(fill_rgb(&wb, 1, 2), None);
+
+        // An overlapping merge fails without touching the cells
+        let err = wb.merge_cells_styled("Data", "D3:E4", Some(CellValue::from("x")), Some(&style));
+        assert!(err.is_err());
+        assert_eq!(fill_rgb(&wb, 4, 5), None);
+        assert_eq!(wb.get_sheet_by_name("Data").unwrap().merged_cells.len(), 1);
+    }
+
+    #[test]
+    fn test_banding_alternates_and_shares_two_xfs() {
+        let mut wb = workbook();
+        let before = wb.styles.cell_xfs.len();
+        wb.apply_banding(
+            "Data",
+            "A1:C101",
+            Some(Color::rgb("FFEEEEEE")),
+            Some(Color::rgb("FFFFFFFF")),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(fill_rgb(&wb, 1, 1), None);
+        assert_eq!(fill_rgb(&wb, 2, 1).as_deref(), Some("FFEEEEEE"));
+        assert_eq!(fill_rgb(&wb, 3, 3).as_deref(), Some("FFFFFFFF"));
+        assert_eq!(fill_rgb(&wb, 101, 2).as_deref(), Some("FFFFFFFF"));
+        assert_eq!(wb.styles.cell_xfs.len(), before + 2);
+
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        let a = ws.get_cell(2, 1).unwrap().style.clone().unwrap();
+        let b = ws.get_cell(4, 3).unwrap().style.clone().unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn test_banding_keeps_existing_style_components() {
+        let mut wb = workbook();
+        {
+            let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+            ws.set_cell_font(1, 1, Font::new().with_bold(true));
+            ws.set_cell_number_format(1, 2, "0.00%");
+        }
+        wb.apply_banding("Data", "A1:B2", Some(Color::rgb("FFDDEBF7")), None, false)
+            .unwrap();
+
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        let bold = ws.get_cell(1, 1).unwrap().style.clone().unwrap();
+        assert!(bold.font.as_ref().unwrap().bold);
+        assert!(bold.fill.is_some());
+        let pct = ws.get_cell(1, 2).unwrap().style.clone().unwrap();
+        assert_eq!(pct.number_format.as_deref(), Some("0.00%"));
+        // The None band is left alone
+        assert!(ws.get_cell(2, 1).is_none());
+    }
+
+    #[test]
+    fn test_scaled_format_auto_picks_from_largest_value() {
+        let mut ws = Worksheet::new("Data");
+        ws.set_cell_value(1, 1, "Revenue");
+        ws.set_cell_value(2, 1, 950.0);
+        ws.set_cell_value(3, 1, 1_250_000.0);
+        ws.set_cell_value(4, 1, 45000.0);
+        ws.set_cell_number_format(4, 1, "yyyy-mm-dd");
+
+        let scale = ws.apply_scaled_number_format("A1:A4", None).unwrap();
+        assert_eq!(scale, NumberScale::Millions);
+        assert!(ws.get_cell(1, 1).unwrap().number_format.is_none());
+        assert_eq!(
+            ws.get_cell(2, 1).unwrap().number_format.as_deref(),
+            Some("#,##0.0,,\"M\"")
+        );
+        assert_eq!(
+            ws.get_cell(4, 1).unwrap().number_format.as_deref(),
+            Some("yyyy-mm-dd")
+        );
+    }
+
+    #[test]
+    fn test_scaled_format_explicit_scale_replaces_style_format() {
+        let mut ws = Worksheet::new("Data");
+        ws.set_cell_value(1, 1, 42_000.0);
+        ws.get_or_create_cell_mut(1, 1).style =
+            Some(Arc::new(CellStyle::new().with_number_format("0.00")));
+
+        ws.apply_scaled_number_format("A1", Some(NumberScale::Thousands))
+            .unwrap();
+        let cell = ws.get_cell(1, 1).unwrap();
+        let style = cell.style.as_ref().unwrap();
+        assert_eq!(style.number_format.as_deref(), Some("#,##0,\"K\""));
+        assert_eq!(
+            crate::numfmt::format_value(&cell.value, style.number_format.as_deref().unwrap()),
+            "42K"
+        );
+    }
+
+    #[test]
+    fn test_range_style_preserves_protection_and_formats() {
+        use crate::style::{Border, BorderStyle, Protection};
+
+        let mut wb = workbook();
+        {
+            let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+            for row in 1..=3 {
+                ws.get_or_create_cell_mut(row, 1).style = Some(Arc::new(
+                    CellStyle::new().with_protection(Protection::unlocked()),
+                ));
+            }
+            ws.set_cell_number_format(1, 2, "0.00%");
+        }
+        let before = wb.styles.cell_xfs.len();
+        wb.set_range_style(
+            "Data",
+            "A1:B3",
+            &CellStyle::new().with_border(Border::all(BorderStyle::thin())),
+        )
+        .unwrap();
+
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        let unlocked = ws.get_cell(3, 1).unwrap();
+        let style = unlocked.style.as_ref().unwrap();
+        assert!(!style.protection.as_ref().unwrap().locked);
+        assert!(style.border.is_some());
+        let pct = ws.get_cell(1, 2).unwrap().style.clone().unwrap();
+        assert_eq!(pct.number_format.as_deref(), Some("0.00%"));
+        assert!(pct.border.is_some());
+        // The three unlocked cells started from separate style objects but
+        // merge to equal styles: unlocked, percent, and plain bordered.
+        assert_eq!(wb.styles.cell_xfs.len(), before + 3);
+        assert_eq!(ws.get_cell(1, 1).unwrap().style_index, unlocked.style_index);
+    }
+
+    #[test]
+    fn test_merge_cell_style_survives_save() {
+        use crate::style::Protection;
+
+        let mut wb = workbook();
+        wb.merge_cell_style(
+            "Data",
+            1,
+            1,
+            &CellStyle::new()
+                .with_number_format("0.0")
+                .with_protection(Protection::unlocked()),
+        )
+        .unwrap();
+        wb.merge_cell_style(
+            "Data",
+            1,
+            1,
+            &CellStyle::new().with_fill(Fill::solid("FFFF00")),
+        )
+        .unwrap();
+
+        let bytes = wb.save_to_bytes().unwrap();
+        let loaded = Workbook::load_from_bytes(&bytes).unwrap();
+        let cell = loaded
+            .get_sheet_by_name("Data")
+            .unwrap()
+            .get_cell(1, 1)
+            .unwrap();
+        let style = cell.style.as_ref().unwrap();
+        assert_eq!(style.number_format.as_deref(), Some("0.0"));
+        assert!(!style.protection.as_ref().unwrap().locked);
+        assert!(style.fill.is_some());
+    }
+
+    #[test]
+    fn test_banding_unknown_sheet() {
+        let mut wb = workbook();
+        assert!(wb
+            .apply_banding("Nope", "A1:B2", Some(Color::rgb("FF000000")), None, true)
+            .is_err());
+    }
+
+    #[test]
+    fn test_apply_named_style_from_json_palette() {
+        let mut wb = workbook();
+        wb.styles
+            .from_json(
+                r#"{"fills": {"band": {"pattern": "solid", "fg_color": "FFDDEBF7"}},
+                    "styles": {"header": {"fill": "band", "font": {"bold": true}}}}"#,
+            )
+            .unwrap();
+        wb.apply_named_style("Data", "A1:B1", "header").unwrap();
+
+        assert_eq!(fill_rgb(&wb, 1, 2).as_deref(), Some("FFDDEBF7"));
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        assert!(
+            ws.get_cell(1, 1)
+                .unwrap()
+                .style
+                .as_ref()
+                .unwrap()
+                .font
+                .as_ref()
+                .unwrap()
+                .bold
+        );
+        assert!(wb.apply_named_style("Data", "A2", "missing").is_err());
+
+        let exported = wb.styles.to_json();
+        let mut other = workbook();
+        other.styles.from_json(&exported).unwrap();
+        assert_eq!(other.styles.palette, wb.styles.palette);
+    }
+
+    #[test]
+    fn test_protect_formulas_locks_only_formula_cells() {
+        let mut wb = workbook();
+        wb.set_cell_value_in_sheet("Data", 1, 1, CellValue::Number(2.0))
+            .unwrap();
+        wb.set_cell_value_in_sheet("Data", 1, 2, CellValue::Number(3.0))
+            .unwrap();
+        wb.set_cell_value_in_sheet("Data", 2, 1, CellValue::Formula("A1*B1".to_string()))
+            .unwrap();
+        wb.get_sheet_by_name_mut("Data").unwrap().set_cell_style(
+            1,
+            2,
+            CellStyle::new().with_font(Font::new().with_bold(true)),
+        );
+
+        let locked = wb.protect_formulas("Data", true, Some("secret")).unwrap();
+        assert_eq!(locked, 1);
+
+        let ws = wb.get_sheet_by_name("Data").unwrap();
+        let protection = |row, col| {
+            ws.get_cell(row, col)
+                .unwrap()
+                .style
+                .as_ref()
+                .unwrap()
+                .protection
+                .clone()
+                .unwrap()
+        };
+        assert_eq!(protection(2, 1), Protection::new().with_hidden(true));
+        assert_eq!(protection(1, 1), Protection::unlocked());
+        assert_eq!(protection(1, 2), Protection::unlocked());
+        let cell = ws.get_cell(1, 2).unwrap();
+        let bold = cell.style.as_ref().unwrap();
+        assert!(bold.font.as_ref().unwrap().bold);
+        assert!(ws.is_protected());
+        assert!(ws.protection.as_ref().unwrap().verify_password("secret"));
+    }
+
+    #[test]
+    fn test_protect_formulas_keeps_existing_protection_flags() {
+        let mut wb = workbook();
+        let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+        ws.enable_protection(None);
+        ws.protection.as_mut().unwrap().sort = false;
+        wb.protect_formulas("Data", false, None).unwrap();
+        let protection = wb
+            .get_sheet_by_name("Data")
+            .unwrap()
+            .protection
+            .clone()
+            .unwrap();
+        assert!(protection.sheet && !protection.sort);
+        assert!(wb.protect_formulas("Missing", false, None).is_err());
+    }
+}
@@ -0,0 +1,768 @@
+//! Read-only, row-streaming access to large workbooks, like openpyxl's
+//! `load_workbook(..., read_only=True)`.
+//!
+//! [`Workbook::load`](crate::Workbook::load) materializes every cell, which
+//! for a multi-million-row sheet costs gigabytes. [`ReadOnlyWorkbook`] only
+//! loads the workbook index, shared strings and number formats up front;
+//! [`ReadOnlyWorkbook::rows`] then decompresses and parses one sheet part
+//! on a background thread, handing rows over a small bounded channel, so
+//! memory stays flat however long the sheet is. Dropping the iterator stops
+//! the parse.
+//!
+//! ```no_run
+//! use rustypyxl::read_only::ReadOnlyWorkbook;
+//!
+//! let wb = ReadOnlyWorkbook::open("huge.xlsx").unwrap();
+//! for row in wb.rows("Data").unwrap() {
+//!     let row = row.unwrap();
+//!     println!("{} has {} cells", row.index, row.cells.len());
+//! }
+//! ```
+
+use crate::cell::{CellValue, InternedString};
+use crate::error::{Result, RustypyxlError};
+use crate::utils::{parse_coordinate_bytes, parse_f64_bytes};
+use crate::workbook::Workbook;
+#[cfg(feature = "fast-hash")]
+use hashbrown::{HashMap, HashSet};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+#[cfg(not(feature = "fast-hash"))]
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Read, Seek};
+use std::path::PathBuf;
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::Arc;
+use zip::ZipArchive;
+
+/// Rows parsed ahead of the consumer.
+const ROW_BUFFER: usize = 256;
+
+/// One populated cell of a streamed row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StreamedCell {
+    /// 1-based column.
+    pub column: u32,
+    /// The value, as [`Workbook::load`] would give it.
+    pub value: CellValue,
+    /// The cell's number format code, when its style sets one.
+    pub number_format: Option<InternedString>,
+}
+
+/// A row as it appears in the sheet: only the cells present in the file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StreamedRow {
+    /// 1-based row number.
+    pub index: u32,
+    /// The row's cells in column order.
+    pub cells: Vec<StreamedCell>,
+}
+
+impl StreamedRow {
+    /// The values of columns `min_col..=max_col`, with `Empty` for cells not
+    /// in the file.
+    pub fn values(&self, min_col: u32, max_col: u32) -> Vec<CellValue> {
+        let mut out = vec![CellValue::Empty; (max_col + 1).saturating_sub(min_col) as usize];
+        for cell in &self.cells {
+            if (min_col..=max_col).contains(&cell.column) {
+                out[(cell.column - min_col) as usize] = cell.value.clone();
+            }
+        }
+        out
+    }
+}
+
+/// Appended to a shared string cut short by
+/// [`ReadOnlyOptions::max_string_len`].
+pub const TRUNCATION_MARKER: &str = "\u{2026}";
+
+/// How a [`ReadOnlyWorkbook`] loads its shared strings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReadOnlyOptions {
+    /// Keep at most this many characters of each shared string; longer ones
+    /// are cut there and end with [`TRUNCATION_MARKER`]. The rest of the
+    /// text is never held in memory. None keeps every string whole.
+    pub max_string_len: Option<usize>,
+}
+
+impl ReadOnlyOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cut shared strings longer than `chars` characters.
+    pub fn with_max_string_len(mut self, chars: usize) -> Self {
+        self.max_string_len = Some(chars);
+        self
+    }
+}
+
+/// The shared string table packed end to end in one buffer. A string costs
+/// its bytes plus one offset, rather than an allocation and a fat pointer
+/// of its own; cells get an `Arc<str>` made when their row is parsed.
+#[derive(Debug, Default)]
+struct StringArena {
+    text: String,
+    ends: Vec<usize>,
+}
+
+impl StringArena {
+    fn get(&self, idx: usize) -> Option<&str> {
+        let end = *self.ends.get(idx)?;
+        let start = idx.checked_sub(1).map_or(0, |prev| self.ends[prev]);
+        Some(&self.text[start..end])
+    }
+}
+
+/// Stream-parse sharedStrings.xml into an arena, keeping only each `<si>`'s
+/// plain text (rich-text runs are flattened) and cutting it at `max_len`
+/// characters.
+fn parse_shared_strings<R: BufRead>(reader: R, max_len: Option<usize>) -> Result<StringArena> {
+    let mut reader = Reader::from_reader(reader);
+    reader.config_mut().trim_text(false);
+    let mut arena = StringArena::default();
+    let mut buf = Vec::new();
+    let mut in_t = false;
+    // Characters of the current string kept so far, and whether any were cut.
+    let mut kept = 0usize;
+    let mut cut = false;
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"t" => in_t = true,
+            Ok(Event::Text(e)) if in_t && !cut => {
+                let text = e.unescape().unwrap_or_default();
+                let room = max_len.map_or(usize::MAX, |max| max - kept);
+                match text.char_indices().nth(room) {
+                    Some((at, _)) => {
+                        arena.text.push_str(&text[..at]);
+                        cut = true;
+                    }
+                    None => {
+                        arena.text.push_str(&text);
+                        kept += text.chars().count();
+                    }
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"t" => in_t = false,
+                b"si" => {
+                    if cut {
+                        arena.text.push_str(TRUNCATION_MARKER);
+                    }
+                    arena.ends.push(arena.text.len());
+                    kept = 0;
+                    cut = false;
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"si" => {
+                arena.ends.push(arena.text.len());
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(RustypyxlError::ParseError(format!(
+                    "XML parsing error: {}",
+                    e
+                )))
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+    arena.text.shrink_to_fit();
+    arena.ends.shrink_to_fit();
+    Ok(arena)
+}
+
+/// Where the package bytes come from; each row iterator opens its own
+/// archive over it.
+#[derive(Clone, Debug)]
+enum Source {
+    Path(PathBuf),
+    Bytes(Arc<[u8]>),
+}
+
+/// Data shared by a workbook and all of its row iterators.
+#[derive(Debug)]
+struct Shared {
+    source: Source,
+    shared_strings: StringArena,
+    /// Number format code per cellXfs index.
+    number_formats: HashMap<u32, InternedString>,
+    /// The cellXfs indexes whose format is a date format.
+    date_styles: HashSet<u32>,
+    date1904: bool,
+}
+
+/// A workbook opened for streaming reads. Cheap to clone.
+#[derive(Clone, Debug)]
+pub struct ReadOnlyWorkbook {
+    sheet_names: Vec<String>,
+    /// Package path of each sheet's part, parallel to `sheet_names`.
+    sheet_paths: Vec<String>,
+    date1904: bool,
+    shared: Arc<Shared>,
+}
+
+impl ReadOnlyWorkbook {
+    /// Open a workbook file for streaming reads.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self> {
+        Self::open_with_options(path, &ReadOnlyOptions::default())
+    }
+
+    /// Open a workbook file for streaming reads, with `options` applied to
+    /// its shared strings.
+    pub fn open_with_options<P: Into<PathBuf>>(path: P, options: &ReadOnlyOptions) -> Result<Self> {
+        let path = path.into();
+        let file = File::open(&path)?;
+        let explain = |e: RustypyxlError| {
+            let info = crate::sniff::detect_format_file(&path.to_string_lossy()).ok();
+            crate::sniff::explain_load_error(e, info, None)
+        };
+        let archive =
+            ZipArchive::new(BufReader::new(file)).map_err(|e| explain(RustypyxlError::from(e)))?;
+        Self::index(archive, Source::Path(path.clone()), options).map_err(explain)
+    }
+
+    /// Open an in-memory workbook for streaming reads.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        Self::from_bytes_with_options(data, &ReadOnlyOptions::default())
+    }
+
+    /// Open an in-memory workbook for streaming reads, with `options`
+    /// applied to its shared strings.
+    pub fn from_bytes_with_options(data: Vec<u8>, options: &ReadOnlyOptions) -> Result<Self> {
+        let data: Arc<[u8]> = data.into();
+        let explain = |e: RustypyxlError| {
+            let info = crate::sniff::detect_format(&data);
+            crate::sniff::explain_load_error(e, Some(info), None)
+        };
+        let archive = ZipArchive::new(Cursor::new(data.clone()))
+            .map_err(|e| explain(RustypyxlError::from(e)))?;
+        Self::index(archive, Source::Bytes(data.clone()), options).map_err(explain)
+    }
+
+    fn index<R: Read + Seek>(
+        mut archive: ZipArchive<R>,
+        source: Source,
+        options: &ReadOnlyOptions,
+    ) -> Result<Self> {
+        let workbook_xml = Workbook::read_zip_file_to_vec(&mut archive, "xl/workbook.xml")?;
+        let (sheets, _, _, date1904) = Workbook::parse_workbook_xml(Cursor::new(&workbook_xml))?;
+        let rels = match Workbook::read_zip_file_to_vec(&mut archive, "xl/_rels/workbook.xml.rels")
+        {
+            Ok(xml) => Workbook::parse_workbook_rels(Cursor::new(&xml))?,
+            Err(_) => HashMap::new(),
+        };
+
+        let mut sheet_names = Vec::new();
+        let mut sheet_paths = Vec::new();
+        for (name, sheet_id, rid, _) in sheets {
+            let rel = rels.get(&rid);
+            // Chartsheets, dialog and macro sheets have no rows to stream.
+            if rel.is_some_and(|r| !r.rel_type.ends_with("/worksheet")) {
+                continue;
+            }
+            let path = match rel.map(|r| r.target.as_str()) {
+                Some(target) => match target.strip_prefix('/') {
+                    Some(absolute) => absolute.to_string(),
+                    None => format!("xl/{}", target),
+                },
+                None => format!("xl/worksheets/sheet{}.xml", sheet_id),
+            };
+            sheet_names.push(name);
+            sheet_paths.push(path);
+        }
+
+        // Parsed straight off the decompressor, so a huge table is never
+        // held as XML as well as text.
+        let shared_strings = match archive.by_name("xl/sharedStrings.xml") {
+            Ok(part) => parse_shared_strings(BufReader::new(part), options.max_string_len)?,
+            Err(_) => StringArena::default(),
+        };
+        let number_formats: HashMap<u32, InternedString> =
+            match Workbook::read_zip_file_to_vec(&mut archive, "xl/styles.xml") {
+                Ok(xml) => Workbook::parse_styles_xml(&xml)?
+                    .0
+                    .into_iter()
+                    .filter_map(|(id, style)| style.number_format.clone().map(|f| (id, f)))
+                    .collect(),
+                Err(_) => HashMap::new(),
+            };
+        let date_styles = number_formats
+            .iter()
+            .filter(|(_, format)| crate::dates::is_date_format(format))
+            .map(|(id, _)| *id)
+            .collect();
+
+        Ok(ReadOnlyWorkbook {
+            sheet_names,
+            sheet_paths,
+            date1904,
+            shared: Arc::new(Shared {
+                source,
+                shared_strings,
+                number_formats,
+                date_styles,
+                date1904,
+            }),
+        })
+    }
+
+    /// Names of the worksheets, in workbook order.
+    pub fn sheet_names(&self) -> &[String] {
+        &self.sheet_names
+    }
+
+    /// Whether the workbook uses the 1904 date system.
+    pub fn date1904(&self) -> bool {
+        self.date1904
+    }
+
+    pub(crate) fn sheet_path(&self, sheet: &str) -> Result<String> {
+        self.sheet_names
+            .iter()
+            .position(|name| name == sheet)
+            .map(|idx| self.sheet_paths[idx].clone())
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(sheet.to_string()))
+    }
+
+    /// The sheet's declared used range (its `<dimension ref>`), e.g.
+    /// "A1:F2000000". Only the start of the part is read. `None` when the
+    /// file does not declare one.
+    pub fn dimension(&self, sheet: &str) -> Result<Option<String>> {
+        let path = self.sheet_path(sheet)?;
+        let shared = self.shared.clone();
+        with_part(&shared.source, &path, |part| {
+            let mut reader = Reader::from_reader(part);
+            let mut buf = Vec::new();
+            loop {
+                match reader.read_event_into(&mut buf)? {
+                    Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                        b"dimension" => {
+                            return Ok(e
+                                .try_get_attribute("ref")
+                                .ok()
+                                .flatten()
+                                .map(|a| String::from_utf8_lossy(&a.value).into_owned()));
+                        }
+                        b"sheetData" => return Ok(None),
+                        _ => {}
+                    },
+                    Event::Eof => return Ok(None),
+                    _ => {}
+                }
+                buf.clear();
+            }
+        })
+    }
+
+    /// Stream the rows of `sheet` in file order. Rows with no cells in the
+    /// file are skipped. Each item is a parse result; iteration ends after
+    /// the first error.
+    pub fn rows(&self, sheet: &str) -> Result<RowIter> {
+        let path = self.sheet_path(sheet)?;
+        let shared = self.shared.clone();
+        let (tx, rx) = sync_channel(ROW_BUFFER);
+        std::thread::spawn(move || {
+            let result = with_part(&shared.source, &path, |part| {
+                parse_rows(part, &shared, |row| tx.send(Ok(row)).is_ok())
+            });
+            if let Err(e) = result {
+                let _ = tx.send(Err(e));
+            }
+        });
+        Ok(RowIter { rx, done: false })
+    }
+}
+
+/// Iterator over a sheet's rows; see [`ReadOnlyWorkbook::rows`].
+#[derive(Debug)]
+pub struct RowIter {
+    rx: Receiver<Result<StreamedRow>>,
+    done: bool,
+}
+
+impl Iterator for RowIter {
+    type Item = Result<StreamedRow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.rx.recv() {
+            Ok(Ok(row)) => Some(Ok(row)),
+            Ok(Err(e)) => {
+                self.done = true;
+                Some(Err(e))
+            }
+            // The parser finished and hung up.
+            Err(_) => {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+/// Open `path` inside the package and run `f` over a buffered reader of it.
+fn with_part<T>(
+    source: &Source,
+    path: &str,
+    f: impl FnOnce(&mut dyn BufRead) -> Result<T>,
+) -> Result<T> {
+    fn run<R: Read + Seek, T>(
+        archive: R,
+        path: &str,
+        f: impl FnOnce(&mut dyn BufRead) -> Result<T>,
+    ) -> Result<T> {
+        let mut archive = ZipArchive::new(archive)?;
+        let part = archive.by_name(path).map_err(|e| {
+            RustypyxlError::InvalidFormat(format!("Failed to find {} in archive: {}", path, e))
+        })?;
+        let mut part = BufReader::new(part);
+        f(&mut part)
+    }
+    match source {
+        Source::Path(p) => run(BufReader::new(File::open(p)?), path, f),
+        Source::Bytes(bytes) => run(Cursor::new(bytes.clone()), path, f),
+    }
+}
+
+/// Parse `<sheetData>` rows, handing each to `emit` until it returns false.
+fn parse_rows(
+    part: &mut dyn BufRead,
+    shared: &Shared,
+    mut emit: impl FnMut(StreamedRow) -> bool,
+) -> Result<()> {
+    let mut reader = Reader::from_reader(part);
+    // Whitespace inside <t> and <v> is significant.
+    reader.config_mut().trim_text(false);
+    let mut buf = Vec::new();
+
+    let mut row = StreamedRow::default();
+    let mut in_row = false;
+    // `r` is optional on <row> and <c>; positions are then implied.
+    let mut next_row = 1u32;
+    let mut next_col = 1u32;
+
+    let mut column = 0u32;
+    let mut cell_type: &[u8] = b"";
+    let mut style_id: Option<u32> = None;
+    let mut text = String::new();
+    let mut formula: Option<String> = None;
+    let mut shared_si: Option<String> = None;
+    let mut shared_formulas = crate::translate::SharedFormulas::default();
+    let mut in_value = false;
+    let mut in_formula = false;
+    let mut in_inline_text = false;
+    let mut saw_value = false;
+
+    fn cell_start(
+        e: &quick_xml::events::BytesStart,
+        next_col: &mut u32,
+    ) -> (u32, &'static [u8], Option<u32>) {
+        let mut column = *next_col;
+        let mut cell_type: &'static [u8] = b"";
+        let mut style_id = None;
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"r" => {
+                    if let Some((_, c)) = parse_coordinate_bytes(&attr.value) {
+                        column = c;
+                    }
+                }
+                b"t" => {
+                    cell_type = match attr.value.as_ref() {
+                        b"s" => b"s",
+                        b"str" => b"str",
+                        b"b" => b"b",
+                        b"d" => b"d",
+                        b"e" => b"e",
+                        b"inlineStr" => b"inlineStr",
+                        _ => b"",
+                    }
+                }
+                b"s" => style_id = crate::utils::parse_u32_bytes(&attr.value),
+                _ => {}
+            }
+        }
+        *next_col = column.saturating_add(1);
+        (column, cell_type, style_id)
+    }
+
+    fn row_start(e: &quick_xml::events::BytesStart, next_row: &mut u32) -> u32 {
+        let index = e
+            .try_get_attribute("r")
+            .ok()
+            .flatten()
+            .and_then(|a| crate::utils::parse_u32_bytes(&a.value))
+            .unwrap_or(*next_row);
+        *next_row = index.saturating_add(1);
+        index
+    }
+
+    let finish_cell = |row: &mut StreamedRow,
+                       column: u32,
+                       cell_type: &[u8],
+                       style_id: Option<u32>,
+                       text: &str,
+                       formula: Option<String>,
+                       saw_value: bool| {
+        let value = if let Some(f) = formula {
+            CellValue::Formula(f)
+        } else if !saw_value {
+            if matches!(cell_type, b"s" | b"str" | b"inlineStr") {
+                CellValue::from("")
+            } else {
+                return;
+            }
+        } else {
+            match cell_type {
+                b"s" => text
+                    .trim()
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|idx| shared.shared_strings.get(idx))
+                    .map(CellValue::from)
+                    .unwrap_or_else(|| CellValue::from("")),
+                b"b" => CellValue::Boolean(text.trim() == "1"),
+                b"d" => crate::cell::DateValue::parse(text)
+                    .map(CellValue::Date)
+                    .unwrap_or_else(|| CellValue::from(text)),
+                b"str" | b"e" | b"inlineStr" => CellValue::from(text),
+                _ => match parse_f64_bytes(text.trim().as_bytes()) {
+                    Some(n) => match style_id
+                        .filter(|id| shared.date_styles.contains(id))
+                        .and_then(|_| crate::cell::DateValue::from_excel(n, shared.date1904))
+                    {
+                        Some(date) => CellValue::Date(date),
+                        None => CellValue::Number(n),
+                    },
+                    None => CellValue::from(text),
+                },
+            }
+        };
+        row.cells.push(StreamedCell {
+            column,
+            value,
+            number_format: style_id.and_then(|id| shared.number_formats.get(&id).cloned()),
+        });
+    };
+
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"row" => {
+                    in_row = true;
+                    row = StreamedRow {
+                        index: row_start(&e, &mut next_row),
+                        cells: Vec::new(),
+                    };
+                    next_col = 1;
+                }
+                b"c" if in_row => {
+                    (column, cell_type, style_id) = cell_start(&e, &mut next_col);
+                    text.clear();
+                    formula = None;
+                    saw_value = false;
+                }
+                b"v" => in_value = true,
+                b"f" => {
+                    in_formula = true;
+                    shared_si = Workbook::shared_formula_group(&e);
+                }
+                b"t" => in_inline_text = true,
+                _ => {}
+            },
+            Event::Empty(e) => match e.local_name().as_ref() {
+                b"row" => {
+                    row_start(&e, &mut next_row);
+                }
+                b"c" if in_row => {
+                    let (column, cell_type, style_id) = cell_start(&e, &mut next_col);
+                    finish_cell(&mut row, column, cell_type, style_id, "", None, false);
+                }
+                b"f" if in_row => {
+                    if let Some(si) = Workbook::shared_formula_group(&e) {
+                        formula = shared_formulas.member(&si, row.index, column);
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(e) => {
+                if in_value || in_inline_text {
+                    text.push_str(&e.unescape().unwrap_or_default());
+                    saw_value = true;
+                } else if in_formula {
+                    formula
+                        .get_or_insert_with(String::new)
+                        .push_str(&e.unescape().unwrap_or_default());
+                }
+            }
+            Event::CData(e) if in_value || in_inline_text => {
+                text.push_str(&String::from_utf8_lossy(&e));
+                saw_value = true;
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"v" => in_value = false,
+                b"f" => {
+                    in_formula = false;
+                    if let Some(si) = shared_si.take() {
+                        match &formula {
+                            Some(f) => shared_formulas.anchor(&si, row.index, column, f),
+                            None => formula = shared_formulas.member(&si, row.index, column),
+                        }
+                    }
+                }
+                b"t" => in_inline_text = false,
+                b"c" if in_row => finish_cell(
+                    &mut row,
+                    column,
+                    cell_type,
+                    style_id,
+                    &text,
+                    formula.take(),
+                    saw_value,
+                ),
+                b"row" => {
+                    in_row = false;
+                    if !row.cells.is_empty() && !emit(std::mem::take(&mut row)) {
+                        return Ok(());
+                    }
+                }
+                b"sheetData" => return Ok(()),
+                _ => {}
+            },
+            Event::Eof => return Ok(()),
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Workbook;
+
+    fn sample() -> Vec<u8> {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.create_sheet(Some("Empty".to_string())).unwrap();
+        for row in 1..=1000u32 {
+            wb.set_cell_value_in_sheet("Data", row, 1, CellValue::from(format!("r{row}")))
+                .unwrap();
+            wb.set_cell_value_in_sheet("Data", row, 3, CellValue::Number(row as f64 * 1.5))
+                .unwrap();
+        }
+        wb.set_cell_value_in_sheet("Data", 1, 4, CellValue::Boolean(true))
+            .unwrap();
+        wb.set_cell_value_in_sheet("Data", 2, 4, CellValue::Formula("C1+C2".into()))
+            .unwrap();
+        let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+        ws.set_cell_number_format(3, 3, "0.00%");
+        wb.save_to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_streams_rows_matching_full_load() {
+        let bytes = sample();
+        let full = Workbook::load_from_bytes(&bytes).unwrap();
+        let ro = ReadOnlyWorkbook::from_bytes(bytes).unwrap();
+        assert_eq!(ro.sheet_names(), ["Data", "Empty"]);
+        assert_eq!(ro.dimension("Data").unwrap().as_deref(), Some("A1:D1000"));
+
+        let rows: Vec<StreamedRow> = ro.rows("Data").unwrap().map(|r| r.unwrap()).collect();
+        assert_eq!(rows.len(), 1000);
+        let ws = full.get_sheet_by_name("Data").unwrap();
+        for row in &rows {
+            for cell in &row.cells {
+                assert_eq!(Some(&cell.value), ws.get_cell_value(row.index, cell.column));
+            }
+        }
+        assert_eq!(
+            rows[0].values(1, 4),
+            vec![
+                CellValue::from("r1"),
+                CellValue::Empty,
+                CellValue::Number(1.5),
+                CellValue::Boolean(true)
+            ]
+        );
+        assert_eq!(rows[1].cells[2].value, CellValue::Formula("C1+C2".into()));
+        assert_eq!(rows[2].cells[1].number_format.as_deref(), Some("0.00%"));
+
+        assert_eq!(ro.rows("Empty").unwrap().count(), 0);
+        assert!(ro.rows("Missing").is_err());
+    }
+
+    #[test]
+    fn test_dropping_the_iterator_stops_early() {
+        let ro = ReadOnlyWorkbook::from_bytes(sample()).unwrap();
+        let first: Vec<u32> = ro
+            .rows("Data")
+            .unwrap()
+            .take(3)
+            .map(|r| r.unwrap().index)
+            .collect();
+        assert_eq!(first, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_implied_positions_and_inline_strings() {
+        let xml = br#"<worksheet><sheetData>
+            <row><c t="inlineStr"><is><t>a </t></is></c><c><v>2</v></c></row>
+            <row r="5"><c r="C5" t="inlineStr"><is><r><t>x</t></r><r><t>y</t></r></is></c><c t="s"/></row>
+        </sheetData></worksheet>"#;
+        let shared = Shared {
+            source: Source::Bytes(Arc::from(Vec::new())),
+            shared_strings: StringArena::default(),
+            number_formats: HashMap::new(),
+            date_styles: HashSet::new(),
+            date1904: false,
+        };
+        let mut rows = Vec::new();
+        parse_rows(&mut Cursor::new(xml), &shared, |r| {
+            rows.push(r);
+            true
+        })
+        .unwrap();
+        assert_eq!(rows[0].index, 1);
+        assert_eq!(
+            rows[0].values(1, 2),
+            vec![CellValue::from("a "), CellValue::Number(2.0)]
+        );
+        assert_eq!(rows[1].index, 5);
+        assert_eq!(rows[1].cells[0].column, 3);
+        assert_eq!(rows[1].cells[0].value, CellValue::from("xy"));
+        assert_eq!(
+            rows[1].cells[1],
+            StreamedCell {
+                column: 4,
+                value: CellValue::from(""),
+                number_format: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_shared_strings_arena_and_truncation() {
+        let xml = r#"<sst><si><t>short</t></si><si/><si><r><t>ab</t></r><r><t>cd&amp;</t></r></si><si><t>café au lait</t></si></sst>"#.as_bytes();
+        let whole = parse_shared_strings(Cursor::new(xml), None).unwrap();
+        let got: Vec<_> = (0..4).map(|i| whole.get(i).unwrap()).collect();
+        assert_eq!(got, ["short", "", "abcd&", "caf\u{e9} au lait"]);
+        assert_eq!(whole.get(4), None);
+
+        // The cut falls across runs and on a character, not a byte.
+        let cut = parse_shared_strings(Cursor::new(xml), Some(4)).unwrap();
+        let got: Vec<_> = (0..4).map(|i| cut.get(i).unwrap()).collect();
+        assert_eq!(
+            got,
+            ["shor\u{2026}", "", "abcd\u{2026}", "caf\u{e9}\u{2026}"]
+        );
+        let exact = parse_shared_strings(Cursor::new(xml), Some(5)).unwrap();
+        assert_eq!(exact.get(0), Some("short"));
+    }
+}
@@ -0,0 +1,416 @@
+//! Finding references that no longer point anywhere.
+//!
+//! Deleting rows, columns or sheets leaves formulas, validation rules,
+//! conditional formats, chart series and defined names behind that may now
+//! hold `#REF!` or name a sheet that is gone. Excel opens such a workbook
+//! without complaint and shows the errors only where the cells are looked
+//! at. [`Workbook::check_references`] lists them all so automation can fail
+//! before the file ships.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::cell::CellValue;
+use crate::utils::coordinate_from_row_col;
+use crate::workbook::Workbook;
+use crate::worksheet::decode_cell_key;
+
+/// What holds a broken reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceOwner {
+    /// A cell formula.
+    Formula,
+    /// A data validation rule's formula1 or formula2.
+    DataValidation,
+    /// A conditional formatting rule's formula.
+    ConditionalFormat,
+    /// A chart series' values or categories.
+    Chart,
+    /// A defined name.
+    DefinedName,
+}
+
+impl ReferenceOwner {
+    /// A short snake_case name, as used in messages and by the bindings.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReferenceOwner::Formula => "formula",
+            ReferenceOwner::DataValidation => "data_validation",
+            ReferenceOwner::ConditionalFormat => "conditional_format",
+            ReferenceOwner::Chart => "chart",
+            ReferenceOwner::DefinedName => "defined_name",
+        }
+    }
+}
+
+/// Why a reference is broken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferenceProblem {
+    /// The text contains `#REF!`.
+    RefError,
+    /// The text names a sheet the workbook does not have.
+    MissingSheet(String),
+}
+
+impl fmt::Display for ReferenceProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReferenceProblem::RefError => write!(f, "contains #REF!"),
+            ReferenceProblem::MissingSheet(name) => write!(f, "refers to missing sheet '{}'", name),
+        }
+    }
+}
+
+/// One broken reference found by [`Workbook::check_references`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenReference {
+    /// What holds the reference.
+    pub owner: ReferenceOwner,
+    /// The sheet it lives on; `None` for a workbook-global defined name.
+    pub sheet: Option<String>,
+    /// Where on that sheet: the cell of a formula, the range of a
+    /// validation or conditional format, "chart 1 series 2", or the name.
+    pub location: String,
+    /// The formula or reference text.
+    pub text: String,
+    /// What is wrong with it.
+    pub problem: ReferenceProblem,
+}
+
+impl fmt::Display for BrokenReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.sheet {
+            Some(sheet) => write!(f, "{} {}!{}", self.owner.as_str(), sheet, self.location)?,
+            None => write!(f, "{} {}", self.owner.as_str(), self.location)?,
+        }
+        write!(f, " {}: {}", self.problem, self.text)
+    }
+}
+
+impl Workbook {
+    /// List every formula, data validation, conditional format, chart
+    /// series and defined name whose text holds `#REF!` or names a sheet
+    /// this workbook no longer has. Sheets are reported in tab order and
+    /// cells in row order; an empty list means nothing is broken.
+    ///
+    /// References into other workbooks (`[1]Sheet1!A1`) are not checked.
+    pub fn check_references(&self) -> Vec<BrokenReference> {
+        let sheets: HashSet<String> = self
+            .sheet_names
+            .iter()
+            .map(|name| name.to_lowercase())
+            .collect();
+        let mut found = Vec::new();
+        let mut check = |owner, sheet: Option<&str>, location: String, text: &str| {
+            if let Some(problem) = find_problem(text, &sheets) {
+                found.push(BrokenReference {
+                    owner,
+                    sheet: sheet.map(str::to_string),
+                    location,
+                    text: text.to_string(),
+                    problem,
+                });
+            }
+        };
+
+        for (ws, name) in self.worksheets.iter().zip(&self.sheet_names) {
+            if ws.opaque.is_some() {
+                continue;
+            }
+            let mut formulas: Vec<(u64, &str)> = ws
+                .cells
+                .iter()
+                .filter_map(|(key, cell)| match &cell.value {
+                    CellValue::Formula(f) => Some((*key, f.as_str())),
+                    _ => None,
+                })
+                .collect();
+            formulas.sort_unstable_by_key(|(key, _)| *key);
+            for (key, formula) in formulas {
+                let (row, col) = decode_cell_key(key);
+                check(
+                    ReferenceOwner::Formula,
+                    Some(name),
+                    coordinate_from_row_col(row, col),
+                    formula,
+                );
+            }
+
+            let mut validations: Vec<_> = ws.data_validations.iter().collect();
+            validations.sort_unstable_by_key(|(cell, _)| **cell);
+            for (&(row, col), dv) in validations {
+                let location = dv
+                    .sqref
+                    .clone()
+                    .unwrap_or_else(|| coordinate_from_row_col(row, col));
+                for formula in [&dv.formula1, &dv.formula2].into_iter().flatten() {
+                    check(
+                        ReferenceOwner::DataValidation,
+                        Some(name),
+                        location.clone(),
+                        formula,
+                    );
+                }
+            }
+
+            for cf in &ws.conditional_formatting {
+                for rule in &cf.rules {
+                    for formula in [&rule.formula1, &rule.formula2].into_iter().flatten() {
+                        check(
+                            ReferenceOwner::ConditionalFormat,
+                            Some(name),
+                            cf.range.clone(),
+                            formula,
+                        );
+                    }
+                }
+            }
+
+            for (c, chart) in ws.charts.iter().enumerate() {
+                for (s, series) in chart.series.iter().enumerate() {
+                    let location = format!("chart {} series {}", c + 1, s + 1);
+                    for reference in std::iter::once(&series.values).chain(&series.categories) {
+                        check(
+                            ReferenceOwner::Chart,
+                            Some(name),
+                            location.clone(),
+                            reference,
+                        );
+                    }
+                }
+            }
+        }
+
+        for named in &self.named_ranges {
+            let scope = named
+                .local_sheet_id
+                .and_then(|idx| self.sheet_names.get(idx as usize));
+            check(
+                ReferenceOwner::DefinedName,
+                scope.map(String::as_str),
+                named.name.clone(),
+                &named.range,
+            );
+        }
+        found
+    }
+}
+
+/// The first problem in formula or reference `text`, if any. String
+/// literals and structured references are skipped; sheet names are
+/// compared case-insensitively, as Excel does.
+fn find_problem(text: &str, sheets: &HashSet<String>) -> Option<ReferenceProblem> {
+    let chars: Vec<char> = text.chars().collect();
+    let missing = |name: &str| {
+        (!sheets.contains(&name.to_lowercase()))
+            .then(|| ReferenceProblem::MissingSheet(name.to_string()))
+    };
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => i = quoted_end(&chars, i, '"').0,
+            '\'' => {
+                let (end, name) = quoted_end(&chars, i, '\'');
+                if chars.get(end) == Some(&'!') && !name.starts_with('[') {
+                    // 'First:Last'!A1 spans several sheets.
+                    for sheet in name.split(':') {
+                        if let Some(problem) = missing(sheet) {
+                            return Some(problem);
+                        }
+                    }
+                }
+                i = end;
+            }
+            '[' => {
+                i = bracket_end(&chars, i);
+                // [1]Sheet1!A1 refers to another workbook: skip its sheet.
+                if chars.get(i).is_some_and(|c| is_name_char(*c)) {
+                    i = name_end(&chars, i);
+                }
+            }
+            '#' => {
+                let rest: String = chars[i..].iter().take(5).collect();
+                if rest.eq_ignore_ascii_case("#REF!") {
+                    return Some(ReferenceProblem::RefError);
+                }
+                i += 1;
+            }
+            c if is_name_char(c) => {
+                let end = name_end(&chars, i);
+                let first: String = chars[i..end].iter().collect();
+                match chars.get(end) {
+                    Some('!') => {
+                        if let Some(problem) = missing(&first) {
+                            return Some(problem);
+                        }
+                    }
+                    // Sheet1:Sheet3!A1, as opposed to a range such as A1:B2.
+                    Some(':') if chars.get(end + 1).is_some_and(|c| is_name_char(*c)) => {
+                        let end2 = name_end(&chars, end + 1);
+                        if chars.get(end2) == Some(&'!') {
+                            let last: String = chars[end + 1..end2].iter().collect();
+                            if let Some(problem) = missing(&first).or_else(|| missing(&last)) {
+                                return Some(problem);
+                            }
+                            i = end2;
+                            continue;
+                        }
+                    }
+                    _ => {}
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '$' | '\\')
+}
+
+fn name_end(chars: &[char], start: usize) -> usize {
+    let mut end = start;
+    while end < chars.len() && is_name_char(chars[end]) {
+        end += 1;
+    }
+    end
+}
+
+/// The index just past a quoted literal starting at `start`, and its
+/// unescaped contents (a doubled quote stands for one).
+fn quoted_end(chars: &[char], start: usize, quote: char) -> (usize, String) {
+    let mut text = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                text.push(quote);
+                i += 2;
+                continue;
+            }
+            return (i + 1, text);
+        }
+        text.push(chars[i]);
+        i += 1;
+    }
+    (i, text)
+}
+
+/// The index just past a (possibly nested) bracket group starting at
+/// `start`. An apostrophe escapes the next character, as in structured
+/// references.
+fn bracket_end(chars: &[char], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        match chars[i] {
+            '\'' => i += 1,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart::{Chart, ChartSeries, ChartType};
+    use crate::validation::DataValidation;
+    use crate::workbook::NamedRange;
+
+    fn sheets(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_lowercase()).collect()
+    }
+
+    #[test]
+    fn scanner_finds_ref_errors_and_missing_sheets() {
+        let known = sheets(&["Data", "My Sheet"]);
+        let check = |text| find_problem(text, &known);
+        assert_eq!(check("SUM(A1:B2)"), None);
+        assert_eq!(check("data!A1+'My Sheet'!B2"), None);
+        assert_eq!(check("A1+#REF!"), Some(ReferenceProblem::RefError));
+        assert_eq!(check("Data!#ref!"), Some(ReferenceProblem::RefError));
+        assert_eq!(
+            check("SUM(Old!A1:A9)"),
+            Some(ReferenceProblem::MissingSheet("Old".to_string()))
+        );
+        assert_eq!(
+            check("'Gone ''Q1'''!A1"),
+            Some(ReferenceProblem::MissingSheet("Gone 'Q1'".to_string()))
+        );
+        assert_eq!(
+            check("SUM(Data:Archive!B2)"),
+            Some(ReferenceProblem::MissingSheet("Archive".to_string()))
+        );
+        // Literals, structured and external references are not sheet names.
+        assert_eq!(check(r#""Old!A1 #REF!"&A1"#), None);
+        assert_eq!(check("SUM(Table1[Old!])"), None);
+        assert_eq!(check("[1]Old!A1+'[Book.xlsx]Old'!B1"), None);
+    }
+
+    #[test]
+    fn check_references_after_removing_a_sheet() {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Summary".to_string())).unwrap();
+        wb.create_sheet(Some("Q1".to_string())).unwrap();
+        let ws = wb.get_sheet_by_name_mut("Summary").unwrap();
+        ws.set_cell_formula(2, 1, "SUM(Q1!B2:B9)");
+        ws.set_cell_formula(1, 1, "A2*2");
+        ws.data_validations.insert(
+            (1, 3),
+            DataValidation {
+                validation_type: "list".to_string(),
+                formula1: Some("Q1!$A$1:$A$5".to_string()),
+                sqref: Some("C1:C10".to_string()),
+                ..Default::default()
+            },
+        );
+        let mut chart = Chart::new(ChartType::Bar);
+        chart.series.push(ChartSeries::new("Summary!$A$1:$A$3"));
+        chart
+            .series
+            .push(ChartSeries::new("Q1!$B$2:$B$9").with_categories("Q1!$A$2:$A$9"));
+        ws.charts.push(chart);
+        wb.named_ranges.push(NamedRange::new("Totals", "Q1!$B$10"));
+        wb.named_ranges
+            .push(NamedRange::new("Kept", "Summary!$A$1"));
+        assert_eq!(wb.check_references(), Vec::new());
+
+        wb.remove_sheet("Q1").unwrap();
+        wb.get_sheet_by_name_mut("Summary")
+            .unwrap()
+            .set_cell_formula(3, 1, "#REF!+1");
+        let broken = wb.check_references();
+        let summary: Vec<(ReferenceOwner, &str)> = broken
+            .iter()
+            .map(|b| (b.owner, b.location.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ReferenceOwner::Formula, "A2"),
+                (ReferenceOwner::Formula, "A3"),
+                (ReferenceOwner::DataValidation, "C1:C10"),
+                (ReferenceOwner::Chart, "chart 1 series 2"),
+                (ReferenceOwner::Chart, "chart 1 series 2"),
+                (ReferenceOwner::DefinedName, "Totals"),
+            ]
+        );
+        assert_eq!(
+            broken[0].to_string(),
+            "formula Summary!A2 refers to missing sheet 'Q1': SUM(Q1!B2:B9)"
+        );
+        assert_eq!(broken[1].problem, ReferenceProblem::RefError);
+        assert_eq!(broken[5].sheet, None);
+    }
+}
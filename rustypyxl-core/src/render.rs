@@ -0,0 +1,315 @@
+//! Print-ready export through an external renderer.
+//!
+//! rustypyxl does not lay out pages itself. [`Workbook::export_pdf`] saves
+//! the workbook to a scratch file and hands it to a [`Renderer`], which turns
+//! it into a PDF. Everything page-related travels inside the saved file:
+//! orientation, paper size, margins, scaling and fit-to-page, print areas,
+//! print titles, headers and footers are all written from each sheet's
+//! [`PageSetup`](crate::pagesetup::PageSetup), so a renderer that honours
+//! them (LibreOffice does) prints what Excel would.
+//!
+//! Two renderers ship: [`LibreOfficeRenderer`] drives `soffice --headless`,
+//! and [`CommandRenderer`] runs any program with the input and output paths
+//! substituted into its arguments. Implement [`Renderer`] for anything else.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::{Result, RustypyxlError};
+use crate::workbook::Workbook;
+
+/// Turns a saved xlsx file into a PDF.
+pub trait Renderer {
+    /// Render the workbook at `input` to a PDF at `output`.
+    fn render_pdf(&self, input: &Path, output: &Path) -> Result<()>;
+}
+
+/// Renders with LibreOffice in headless mode.
+#[derive(Clone, Debug)]
+pub struct LibreOfficeRenderer {
+    /// The `soffice` executable; found on `PATH` by default.
+    pub program: PathBuf,
+}
+
+impl Default for LibreOfficeRenderer {
+    fn default() -> Self {
+        LibreOfficeRenderer {
+            program: PathBuf::from("soffice"),
+        }
+    }
+}
+
+impl LibreOfficeRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a specific `soffice` executable.
+    pub fn with_program<P: Into<PathBuf>>(mut self, program: P) -> Self {
+        self.program = program.into();
+        self
+    }
+}
+
+impl Renderer for LibreOfficeRenderer {
+    fn render_pdf(&self, input: &Path, output: &Path) -> Result<()> {
+        // soffice names its output after the input and only takes a
+        // directory, so convert next to the input and move the result.
+        let out_dir = input.parent().unwrap_or_else(|| Path::new("."));
+        let mut command = Command::new(&self.program);
+        command
+            .arg("--headless")
+            .arg("--convert-to")
+            .arg("pdf")
+            .arg("--outdir")
+            .arg(out_dir)
+            .arg(input);
+        run(&mut command)?;
+        let produced = input.with_extension("pdf");
+        move_file(&produced, output)
+    }
+}
+
+/// Runs a user-supplied command. In each argument, `{input}` becomes the
+/// xlsx path and `{output}` the PDF path to write.
+#[derive(Clone, Debug)]
+pub struct CommandRenderer {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+}
+
+impl CommandRenderer {
+    pub fn new<P: Into<PathBuf>>(program: P, args: Vec<String>) -> Self {
+        CommandRenderer {
+            program: program.into(),
+            args,
+        }
+    }
+}
+
+impl Renderer for CommandRenderer {
+    fn render_pdf(&self, input: &Path, output: &Path) -> Result<()> {
+        let input = input.to_string_lossy();
+        let output = output.to_string_lossy();
+        let mut command = Command::new(&self.program);
+        command.args(
+            self.args
+                .iter()
+                .map(|arg| arg.replace("{input}", &input).replace("{output}", &output)),
+        );
+        run(&mut command)
+    }
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let result = command.output().map_err(|e| {
+        RustypyxlError::custom(format!("could not run renderer {}: {}", program, e))
+    })?;
+    if !result.status.success() {
+        return Err(RustypyxlError::custom(format!(
+            "renderer {} failed ({}): {}",
+            program,
+            result.status,
+            String::from_utf8_lossy(&result.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Move a file, copying when a rename cannot cross filesystems.
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+/// A directory of its own under the system temp dir, removed on drop.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new() -> Result<Self> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let dir = std::env::temp_dir().join(format!(
+            "rustypyxl-render-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir(&dir)?;
+        Ok(ScratchDir(dir))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+impl Workbook {
+    /// Export the workbook to a PDF at `path` with LibreOffice (`soffice`
+    /// on `PATH`).
+    pub fn export_pdf(&self, path: &str) -> Result<()> {
+        self.export_pdf_with(path, &LibreOfficeRenderer::default())
+    }
+
+    /// Export the workbook to a PDF at `path` with `renderer`. A file
+    /// already at `path` is removed first, so a renderer that reports
+    /// success without writing the PDF is an error rather than leaving the
+    /// old one in place.
+    pub fn export_pdf_with(&self, path: &str, renderer: &dyn Renderer) -> Result<()> {
+        let scratch = ScratchDir::new()?;
+        // A workbook with macros must keep a macro-enabled extension.
+        let input = scratch
+            .0
+            .join("workbook")
+            .with_extension(self.main_extension());
+        self.save(&input.to_string_lossy())?;
+        let output = Path::new(path);
+        if output.is_file() {
+            std::fs::remove_file(output)?;
+        }
+        renderer.render_pdf(&input, output)?;
+        if !output.is_file() {
+            return Err(RustypyxlError::custom(format!(
+                "renderer did not write {}",
+                path
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Records what it was given and writes a stand-in PDF.
+    struct Recorder(RefCell<Option<Vec<u8>>>);
+
+    impl Renderer for Recorder {
+        fn render_pdf(&self, input: &Path, output: &Path) -> Result<()> {
+            *self.0.borrow_mut() = Some(std::fs::read(input)?);
+            std::fs::write(output, b"%PDF-1.7")?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_export_hands_the_saved_workbook_to_the_renderer() {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Report".to_string())).unwrap();
+        let dir = ScratchDir::new().unwrap();
+        let out = dir.0.join("out.pdf");
+
+        let recorder = Recorder(RefCell::new(None));
+        wb.export_pdf_with(&out.to_string_lossy(), &recorder)
+            .unwrap();
+        let saved = recorder.0.borrow_mut().take().unwrap();
+        let reloaded = Workbook::load_from_bytes(&saved).unwrap();
+        assert_eq!(reloaded.sheet_names, ["Report"]);
+        assert_eq!(std::fs::read(&out).unwrap(), b"%PDF-1.7");
+    }
+
+    #[test]
+    fn test_renderer_that_writes_nothing_is_an_error() {
+        struct Silent;
+        impl Renderer for Silent {
+            fn render_pdf(&self, _: &Path, _: &Path) -> Result<()> {
+                Ok(())
+            }
+        }
+        let dir = ScratchDir::new().unwrap();
+        let out = dir.0.join("missing.pdf");
+        let err = Workbook::new()
+            .export_pdf_with(&out.to_string_lossy(), &Silent)
+            .unwrap_err();
+        assert!(err.to_string().contains("did not write"));
+    }
+
+    #[test]
+    fn test_stale_pdf_does_not_count_as_output() {
+        struct Silent;
+        impl Renderer for Silent {
+            fn render_pdf(&self, _: &Path, _: &Path) -> Result<()> {
+                Ok(())
+            }
+        }
+        let dir = ScratchDir::new().unwrap();
+        let out = dir.0.join("old.pdf");
+        std::fs::write(&out, b"%PDF-1.4 from an earlier run").unwrap();
+        let err = Workbook::new()
+            .export_pdf_with(&out.to_string_lossy(), &Silent)
+            .unwrap_err();
+        assert!(err.to_string().contains("did not write"));
+        assert!(!out.exists());
+    }
+
+    #[test]
+    fn test_workbook_with_macros_exports() {
+        use crate::passthrough::PreservedWorkbookRel;
+
+        /// Records the scratch file name and writes a stand-in PDF.
+        struct Named(RefCell<Option<PathBuf>>);
+        impl Renderer for Named {
+            fn render_pdf(&self, input: &Path, output: &Path) -> Result<()> {
+                *self.0.borrow_mut() = Some(input.to_path_buf());
+                std::fs::write(output, b"%PDF-1.7")?;
+                Ok(())
+            }
+        }
+
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.preserved.rels.push(PreservedWorkbookRel {
+            id: "rId9".to_string(),
+            rel_type: "http://schemas.microsoft.com/office/2006/relationships/vbaProject"
+                .to_string(),
+            target: "vbaProject.bin".to_string(),
+            external: false,
+        });
+        assert!(wb.has_macros());
+
+        let dir = ScratchDir::new().unwrap();
+        let out = dir.0.join("macros.pdf");
+        let renderer = Named(RefCell::new(None));
+        wb.export_pdf_with(&out.to_string_lossy(), &renderer)
+            .unwrap();
+        let input = renderer.0.borrow_mut().take().unwrap();
+        assert_eq!(input.extension().unwrap(), "xlsm");
+        assert_eq!(std::fs::read(&out).unwrap(), b"%PDF-1.7");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_renderer_substitutes_paths() {
+        let dir = ScratchDir::new().unwrap();
+        let out = dir.0.join("copy.pdf");
+        let renderer = CommandRenderer::new(
+            "sh",
+            vec![
+                "-c".to_string(),
+                "cp \"$0\" \"$1\"".to_string(),
+                "{input}".to_string(),
+                "{output}".to_string(),
+            ],
+        );
+        Workbook::new()
+            .export_pdf_with(&out.to_string_lossy(), &renderer)
+            .unwrap();
+        assert!(std::fs::read(&out).unwrap().starts_with(b"PK"));
+
+        let failing = CommandRenderer::new("sh", vec!["-c".to_string(), "exit 3".to_string()]);
+        assert!(Workbook::new()
+            .export_pdf_with(&out.to_string_lossy(), &failing)
+            .is_err());
+    }
+}
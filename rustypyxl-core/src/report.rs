@@ -0,0 +1,652 @@
+//! Declarative report layout.
+//!
+//! A [`Report`] is a list of sections -- a title block, key/value header
+//! lines, data tables with an optional totals row, and notes -- that
+//! [`Worksheet::write_report`] lays out top to bottom with one blank row
+//! between sections and a consistent look, so callers never compute
+//! coordinates themselves.
+//!
+//! ```
+//! use rustypyxl::report::{Report, ReportColumn, ReportTable};
+//! use rustypyxl::table::TotalsRowFunction;
+//! use rustypyxl::{CellValue, Worksheet};
+//!
+//! let report = Report::new()
+//!     .with_title("Quarterly sales", Some("All regions"))
+//!     .with_key_values(vec![("Quarter".into(), CellValue::from("Q3"))])
+//!     .with_table(
+//!         ReportTable::new(vec![
+//!             ReportColumn::new("Region"),
+//!             ReportColumn::new("Revenue")
+//!                 .with_number_format("#,##0")
+//!                 .with_total(TotalsRowFunction::Sum),
+//!         ])
+//!         .with_row(vec!["North".into(), 1200.0.into()])
+//!         .with_row(vec!["South".into(), 800.0.into()])
+//!         .with_totals_label("Total"),
+//!     );
+//! let mut ws = Worksheet::new("Report");
+//! let layout = ws.write_report(&report);
+//! assert_eq!(layout.tables[0].totals_row, Some(9));
+//! ```
+
+use crate::cell::CellValue;
+use crate::style::{Alignment, Border, BorderStyle, CellStyle, Color, Fill, Font};
+use crate::table::TotalsRowFunction;
+use crate::utils::column_to_letter;
+use crate::worksheet::Worksheet;
+use std::sync::Arc;
+
+/// Widest column a report will size to fit its content.
+const MAX_AUTO_WIDTH: f64 = 60.0;
+
+/// One column of a [`ReportTable`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportColumn {
+    /// Header text.
+    pub header: String,
+    /// Number format applied to the column's data and total cells.
+    pub number_format: Option<String>,
+    /// Column width; sized to fit the header and data when `None`.
+    pub width: Option<f64>,
+    /// Aggregate written in the totals row.
+    pub total: TotalsRowFunction,
+}
+
+impl ReportColumn {
+    /// Create a column with a header and no total.
+    pub fn new<S: Into<String>>(header: S) -> Self {
+        ReportColumn {
+            header: header.into(),
+            number_format: None,
+            width: None,
+            total: TotalsRowFunction::None,
+        }
+    }
+
+    /// Set the number format.
+    pub fn with_number_format<S: Into<String>>(mut self, format: S) -> Self {
+        self.number_format = Some(format.into());
+        self
+    }
+
+    /// Set a fixed width.
+    pub fn with_width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set the totals row aggregate.
+    pub fn with_total(mut self, total: TotalsRowFunction) -> Self {
+        self.total = total;
+        self
+    }
+}
+
+/// A data table section: a header row, data rows, and optionally a totals
+/// row of formulas over the data.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReportTable {
+    /// Optional caption written above the header row.
+    pub caption: Option<String>,
+    /// Column schema.
+    pub columns: Vec<ReportColumn>,
+    /// Data rows; short rows leave their trailing cells blank and extra
+    /// values beyond the schema are dropped.
+    pub rows: Vec<Vec<CellValue>>,
+    /// Label for the totals row, written in the first column when that
+    /// column has no aggregate of its own.
+    pub totals_label: Option<String>,
+}
+
+impl ReportTable {
+    /// Create a table with the given columns and no rows.
+    pub fn new(columns: Vec<ReportColumn>) -> Self {
+        ReportTable {
+            columns,
+            ..Default::default()
+        }
+    }
+
+    /// Set the caption.
+    pub fn with_caption<S: Into<String>>(mut self, caption: S) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Append one data row.
+    pub fn with_row(mut self, row: Vec<CellValue>) -> Self {
+        self.rows.push(row);
+        self
+    }
+
+    /// Replace the data rows.
+    pub fn with_rows(mut self, rows: Vec<Vec<CellValue>>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Label the totals row.
+    pub fn with_totals_label<S: Into<String>>(mut self, label: S) -> Self {
+        self.totals_label = Some(label.into());
+        self
+    }
+
+    /// Whether a totals row is written: some column aggregates, or a label
+    /// was given.
+    pub fn has_totals_row(&self) -> bool {
+        self.totals_label.is_some()
+            || self
+                .columns
+                .iter()
+                .any(|c| c.total != TotalsRowFunction::None)
+    }
+}
+
+/// One section of a [`Report`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReportSection {
+    /// A large title with an optional subtitle line beneath it.
+    Title {
+        text: String,
+        subtitle: Option<String>,
+    },
+    /// "Label: value" lines, label in the first column.
+    KeyValues(Vec<(String, CellValue)>),
+    /// A data table.
+    Table(ReportTable),
+    /// Free-text notes under an optional heading.
+    Notes {
+        heading: Option<String>,
+        lines: Vec<String>,
+    },
+}
+
+/// A report: sections laid out in order, styled with one accent color.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    /// The sections, top to bottom.
+    pub sections: Vec<ReportSection>,
+    /// Title text and table header fill color.
+    pub accent: Color,
+    /// Fill for every other data row, or `None` for no banding.
+    pub banding: Option<Color>,
+}
+
+impl Default for Report {
+    fn default() -> Self {
+        Report {
+            sections: Vec::new(),
+            accent: Color::rgb("FF1F4E79"),
+            banding: Some(Color::rgb("FFF2F2F2")),
+        }
+    }
+}
+
+impl Report {
+    /// Create an empty report with the default look.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a title block.
+    pub fn with_title<S: Into<String>>(mut self, text: S, subtitle: Option<&str>) -> Self {
+        self.sections.push(ReportSection::Title {
+            text: text.into(),
+            subtitle: subtitle.map(str::to_string),
+        });
+        self
+    }
+
+    /// Add a block of key/value lines.
+    pub fn with_key_values(mut self, pairs: Vec<(String, CellValue)>) -> Self {
+        self.sections.push(ReportSection::KeyValues(pairs));
+        self
+    }
+
+    /// Add a data table.
+    pub fn with_table(mut self, table: ReportTable) -> Self {
+        self.sections.push(ReportSection::Table(table));
+        self
+    }
+
+    /// Add notes.
+    pub fn with_notes(mut self, heading: Option<&str>, lines: Vec<String>) -> Self {
+        self.sections.push(ReportSection::Notes {
+            heading: heading.map(str::to_string),
+            lines,
+        });
+        self
+    }
+
+    /// Set the accent color.
+    pub fn with_accent<C: Into<Color>>(mut self, color: C) -> Self {
+        self.accent = color.into();
+        self
+    }
+
+    /// Set (or with `None`, turn off) the data row banding color.
+    pub fn with_banding(mut self, color: Option<Color>) -> Self {
+        self.banding = color;
+        self
+    }
+
+    /// Columns spanned by the widest section; at least 1.
+    fn width(&self) -> u32 {
+        self.sections
+            .iter()
+            .map(|section| match section {
+                ReportSection::KeyValues(pairs) if !pairs.is_empty() => 2,
+                ReportSection::Table(table) => table.columns.len() as u32,
+                _ => 1,
+            })
+            .max()
+            .unwrap_or(1)
+            .max(1)
+    }
+}
+
+/// Where a table section landed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportTableLayout {
+    /// Row of the column headers.
+    pub header_row: u32,
+    /// First data row.
+    pub first_data_row: u32,
+    /// Last data row; `first_data_row - 1` when the table has no rows.
+    pub last_data_row: u32,
+    /// Row of the totals, if written.
+    pub totals_row: Option<u32>,
+    /// Header plus data, e.g. "A5:C12", ready for a chart or a table.
+    pub range: String,
+}
+
+/// Where [`Worksheet::write_report`] put things.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReportLayout {
+    /// The range each section occupies, in section order.
+    pub sections: Vec<String>,
+    /// Layout of each table section, in order.
+    pub tables: Vec<ReportTableLayout>,
+    /// Last row written.
+    pub last_row: u32,
+}
+
+/// The shared styles of one render; each is one `Arc` reused by every cell
+/// that needs it.
+struct Palette {
+    title: Arc<CellStyle>,
+    subtitle: Arc<CellStyle>,
+    heading: Arc<CellStyle>,
+    key: Arc<CellStyle>,
+    header: Arc<CellStyle>,
+    note: Arc<CellStyle>,
+}
+
+impl Palette {
+    fn new(report: &Report) -> Self {
+        let bold = |size: f64| Font::new().with_bold(true).with_size(size);
+        Palette {
+            title: Arc::new(
+                CellStyle::new().with_font(bold(16.0).with_color(report.accent.clone())),
+            ),
+            subtitle: Arc::new(
+                CellStyle::new().with_font(Font::new().with_italic(true).with_color("FF595959")),
+            ),
+            heading: Arc::new(CellStyle::new().with_font(bold(12.0))),
+            key: Arc::new(CellStyle::new().with_font(Font::new().with_bold(true))),
+            header: Arc::new(
+                CellStyle::new()
+                    .with_font(Font::new().with_bold(true).with_color("FFFFFFFF"))
+                    .with_fill(Fill::solid(report.accent.clone()))
+                    .with_alignment(Alignment::new().with_vertical("center"))
+                    .with_border(Border::new().with_bottom(BorderStyle::thin())),
+            ),
+            note: Arc::new(
+                CellStyle::new().with_font(Font::new().with_italic(true).with_color("FF595959")),
+            ),
+        }
+    }
+}
+
+fn put(ws: &mut Worksheet, row: u32, col: u32, value: CellValue, style: &Arc<CellStyle>) {
+    ws.set_cell_value(row, col, value);
+    let cell = ws.get_or_create_cell_mut(row, col);
+    cell.style = Some(style.clone());
+    cell.style_index = None;
+}
+
+fn total_formula(total: &TotalsRowFunction, range: &str) -> Option<String> {
+    let function = match total {
+        TotalsRowFunction::None => return None,
+        TotalsRowFunction::Custom(formula) => {
+            return Some(formula.trim_start_matches('=').to_string())
+        }
+        TotalsRowFunction::Sum => "SUM",
+        TotalsRowFunction::Average => "AVERAGE",
+        TotalsRowFunction::Count => "COUNTA",
+        TotalsRowFunction::CountNums => "COUNT",
+        TotalsRowFunction::Max => "MAX",
+        TotalsRowFunction::Min => "MIN",
+        TotalsRowFunction::StdDev => "STDEV",
+        TotalsRowFunction::Var => "VAR",
+    };
+    Some(format!("{function}({range})"))
+}
+
+fn displayed_width(value: &CellValue, format: Option<&str>) -> f64 {
+    let text = crate::numfmt::format_value(value, format.unwrap_or("General"));
+    text.split('\n')
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0) as f64
+}
+
+impl Worksheet {
+    /// Lay `report` out on this sheet, starting in column A on the first row
+    /// (or two rows below existing content), and return where each section
+    /// went. Columns without a fixed width are widened to fit table content;
+    /// nothing is ever narrowed.
+    pub fn write_report(&mut self, report: &Report) -> ReportLayout {
+        let palette = Palette::new(report);
+        let width = report.width();
+        let last_col = column_to_letter(width);
+        let mut row = if self.max_row() == 0 {
+            1
+        } else {
+            self.max_row() + 2
+        };
+        let mut layout = ReportLayout::default();
+        // Content width per column (1-based index - 1) from key/value and
+        // table cells; titles and notes overflow instead of widening.
+        let mut widths = vec![0.0f64; width as usize];
+        let mut fixed = vec![None; width as usize];
+
+        for (i, section) in report.sections.iter().enumerate() {
+            if i > 0 {
+                row += 1;
+            }
+            let start = row;
+            let mut section_width = 1;
+            match section {
+                ReportSection::Title { text, subtitle } => {
+                    put(self, row, 1, text.as_str().into(), &palette.title);
+                    self.set_row_height(row, 21.0);
+                    if width > 1 {
+                        // Rows below the existing content are fresh, so this
+                        // can only clash with a merge hanging past the last
+                        // cell; the title then simply stays unmerged.
+                        let _ = self.merge_cells(&format!("A{row}:{last_col}{row}"));
+                        section_width = width;
+                    }
+                    if let Some(subtitle) = subtitle {
+                        row += 1;
+                        put(self, row, 1, subtitle.as_str().into(), &palette.subtitle);
+                    }
+                }
+                ReportSection::KeyValues(pairs) => {
+                    for (key, value) in pairs {
+                        put(self, row, 1, key.as_str().into(), &palette.key);
+                        self.set_cell_value(row, 2, value.clone());
+                        widths[0] = widths[0].max(key.chars().count() as f64 * 1.05);
+                        widths[1] = widths[1].max(displayed_width(value, None));
+                        row += 1;
+                    }
+                    row = row.saturating_sub(1).max(start);
+                    section_width = 2;
+                }
+                ReportSection::Table(table) => {
+                    layout.tables.push(self.write_report_table(
+                        table,
+                        report,
+                        &palette,
+                        &mut row,
+                        &mut widths,
+                        &mut fixed,
+                    ));
+                    section_width = table.columns.len().max(1) as u32;
+                }
+                ReportSection::Notes { heading, lines } => {
+                    if let Some(heading) = heading {
+                        put(self, row, 1, heading.as_str().into(), &palette.heading);
+                        row += 1;
+                    }
+                    for line in lines {
+                        put(self, row, 1, line.as_str().into(), &palette.note);
+                        row += 1;
+                    }
+                    row = row.saturating_sub(1).max(start);
+                }
+            }
+            layout
+                .sections
+                .push(format!("A{start}:{}{row}", column_to_letter(section_width)));
+            layout.last_row = row;
+            row += 1;
+        }
+
+        for (idx, (content, fixed)) in widths.iter().zip(&fixed).enumerate() {
+            let column = idx as u32 + 1;
+            let wanted = match fixed {
+                Some(width) => *width,
+                None if *content > 0.0 => (content + 2.0).min(MAX_AUTO_WIDTH),
+                None => continue,
+            };
+            if self.get_column_width(column).is_none_or(|w| w < wanted) {
+                self.set_column_width(column, wanted);
+            }
+        }
+        layout
+    }
+
+    fn write_report_table(
+        &mut self,
+        table: &ReportTable,
+        report: &Report,
+        palette: &Palette,
+        row: &mut u32,
+        widths: &mut [f64],
+        fixed: &mut [Option<f64>],
+    ) -> ReportTableLayout {
+        if let Some(caption) = &table.caption {
+            put(self, *row, 1, caption.as_str().into(), &palette.heading);
+            *row += 1;
+        }
+        let header_row = *row;
+        for (c, column) in table.columns.iter().enumerate() {
+            put(
+                self,
+                header_row,
+                c as u32 + 1,
+                column.header.as_str().into(),
+                &palette.header,
+            );
+            widths[c] = widths[c].max(column.header.chars().count() as f64 * 1.05);
+            if column.width.is_some() {
+                fixed[c] = column.width;
+            }
+        }
+
+        // One style per (column, banded) pair, shared down the column.
+        let data_style = |column: &ReportColumn, banded: bool| {
+            let mut style = CellStyle::new();
+            if let Some(format) = &column.number_format {
+                style = style.with_number_format(format);
+            }
+            if let (true, Some(band)) = (banded, &report.banding) {
+                style = style.with_fill(Fill::solid(band.clone()));
+            }
+            (style != CellStyle::new()).then(|| Arc::new(style))
+        };
+        let styles: Vec<[Option<Arc<CellStyle>>; 2]> = table
+            .columns
+            .iter()
+            .map(|column| [data_style(column, false), data_style(column, true)])
+            .collect();
+
+        for (r, values) in table.rows.iter().enumerate() {
+            let row_idx = header_row + 1 + r as u32;
+            for (c, column) in table.columns.iter().enumerate() {
+                let value = values.get(c).cloned().unwrap_or(CellValue::Empty);
+                widths[c] = widths[c].max(displayed_width(&value, column.number_format.as_deref()));
+                match &styles[c][r % 2] {
+                    Some(style) => put(self, row_idx, c as u32 + 1, value, style),
+                    None if value != CellValue::Empty => {
+                        self.set_cell_value(row_idx, c as u32 + 1, value)
+                    }
+                    None => {}
+                }
+            }
+        }
+        let first_data_row = header_row + 1;
+        let last_data_row = header_row + table.rows.len() as u32;
+        *row = last_data_row;
+
+        let totals_row = table.has_totals_row().then(|| {
+            *row += 1;
+            let totals_row = *row;
+            let border = Border::new()
+                .with_top(BorderStyle::thin())
+                .with_bottom(BorderStyle::new("double"));
+            for (c, column) in table.columns.iter().enumerate() {
+                let col = c as u32 + 1;
+                let letter = column_to_letter(col);
+                let range = format!("{letter}{first_data_row}:{letter}{last_data_row}");
+                let value = match total_formula(&column.total, &range) {
+                    Some(formula) if !table.rows.is_empty() => CellValue::Formula(formula),
+                    Some(_) => CellValue::Empty,
+                    None if c == 0 => table
+                        .totals_label
+                        .as_deref()
+                        .map_or(CellValue::Empty, CellValue::from),
+                    None => CellValue::Empty,
+                };
+                let mut style = CellStyle::new()
+                    .with_font(Font::new().with_bold(true))
+                    .with_border(border.clone());
+                // Counts are plain integers whatever the column shows.
+                let keeps_format = !matches!(
+                    column.total,
+                    TotalsRowFunction::None
+                        | TotalsRowFunction::Count
+                        | TotalsRowFunction::CountNums
+                );
+                if let (Some(format), true) = (&column.number_format, keeps_format) {
+                    style = style.with_number_format(format);
+                }
+                put(self, totals_row, col, value, &Arc::new(style));
+            }
+            totals_row
+        });
+
+        ReportTableLayout {
+            header_row,
+            first_data_row,
+            last_data_row,
+            totals_row,
+            range: format!(
+                "A{header_row}:{}{}",
+                column_to_letter(table.columns.len().max(1) as u32),
+                last_data_row
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sales() -> ReportTable {
+        ReportTable::new(vec![
+            ReportColumn::new("Region"),
+            ReportColumn::new("Units").with_total(TotalsRowFunction::Sum),
+            ReportColumn::new("Revenue")
+                .with_number_format("#,##0.00")
+                .with_total(TotalsRowFunction::Average),
+        ])
+        .with_row(vec!["North".into(), 10.0.into(), 1200.0.into()])
+        .with_row(vec!["South".into(), 4.0.into()])
+        .with_totals_label("Total")
+    }
+
+    #[test]
+    fn test_layout_and_totals() {
+        let report = Report::new()
+            .with_title("Sales", Some("FY24"))
+            .with_key_values(vec![("Owner".into(), "Ops".into())])
+            .with_table(sales().with_caption("By region"))
+            .with_notes(Some("Notes"), vec!["Unaudited.".into()]);
+        let mut ws = Worksheet::new("R");
+        let layout = ws.write_report(&report);
+
+        // Title 1-2, blank, key/values 4, blank, caption 6, header 7, data
+        // 8-9, totals 10, blank, notes 12-13.
+        assert_eq!(layout.sections, vec!["A1:C2", "A4:B4", "A6:C10", "A12:A13"]);
+        let table = &layout.tables[0];
+        assert_eq!(table.header_row, 7);
+        assert_eq!((table.first_data_row, table.last_data_row), (8, 9));
+        assert_eq!(table.totals_row, Some(10));
+        assert_eq!(table.range, "A7:C9");
+        assert_eq!(layout.last_row, 13);
+
+        assert_eq!(ws.merged_cells, vec![("A1".to_string(), "C1".to_string())]);
+        assert_eq!(ws.get_cell_value(10, 1), Some(&CellValue::from("Total")));
+        assert_eq!(
+            ws.get_cell_value(10, 2),
+            Some(&CellValue::Formula("SUM(B8:B9)".into()))
+        );
+        assert_eq!(
+            ws.get_cell_value(10, 3),
+            Some(&CellValue::Formula("AVERAGE(C8:C9)".into()))
+        );
+        // The short row's missing Revenue cell still carries the band.
+        assert!(ws.get_cell(9, 3).unwrap().style.is_some());
+    }
+
+    #[test]
+    fn test_styles_are_shared_and_widths_fit() {
+        let mut ws = Worksheet::new("R");
+        ws.write_report(&Report::new().with_table(sales()));
+
+        let header_a = ws.get_cell(1, 1).unwrap().style.clone().unwrap();
+        let header_c = ws.get_cell(1, 3).unwrap().style.clone().unwrap();
+        assert!(Arc::ptr_eq(&header_a, &header_c));
+        assert!(header_a.font.as_ref().unwrap().bold);
+        assert_eq!(
+            ws.get_cell(2, 3)
+                .unwrap()
+                .style
+                .as_ref()
+                .unwrap()
+                .number_format
+                .as_deref(),
+            Some("#,##0.00")
+        );
+        // "1,200.00" is 8 characters.
+        assert_eq!(ws.get_column_width(3), Some(10.0));
+    }
+
+    #[test]
+    fn test_appends_below_existing_content() {
+        let mut ws = Worksheet::new("R");
+        ws.set_cell_value(3, 1, "existing");
+        let layout = ws.write_report(&Report::new().with_title("Later", None));
+        assert_eq!(layout.sections, vec!["A5:A5"]);
+    }
+
+    #[test]
+    fn test_empty_table_writes_no_formulas() {
+        let mut ws = Worksheet::new("R");
+        let layout = ws.write_report(&Report::new().with_table(ReportTable::new(vec![
+            ReportColumn::new("N").with_total(TotalsRowFunction::Sum),
+        ])));
+        let table = &layout.tables[0];
+        assert!(table.last_data_row < table.first_data_row);
+        assert_eq!(table.totals_row, Some(2));
+        assert!(!matches!(
+            ws.get_cell_value(2, 1),
+            Some(CellValue::Formula(_))
+        ));
+    }
+}
@@ -113,14 +113,19 @@ pub async fn save_to_s3_async(
 ) -> Result<()> {
     let client = create_s3_client(config).await?;
 
-    let data = workbook.save_to_bytes()?;
+    let data = workbook.save_to_bytes_for(key)?;
+    let content_type = if key.to_ascii_lowercase().ends_with(".xlsm") {
+        "application/vnd.ms-excel.sheet.macroEnabled.12"
+    } else {
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    };
 
     client
         .put_object()
         .bucket(bucket)
         .key(key)
         .body(data.into())
-        .content_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet")
+        .content_type(content_type)
         .send()
         .await
         .map_err(|e| {
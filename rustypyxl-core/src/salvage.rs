@@ -0,0 +1,527 @@
+//! Recovering what can be read from a damaged workbook archive.
+//!
+//! A truncated upload loses the ZIP central directory, which lives at the end
+//! of the file, so [`Workbook::load`] rejects it outright even though most of
+//! the parts are still there. [`Workbook::salvage`] ignores the central
+//! directory and walks the local file headers instead, decompressing each
+//! entry on its own and keeping those whose data is complete and passes its
+//! CRC check. The workbook part is then trimmed to the sheets that survived
+//! (or rebuilt from the worksheet parts when it was lost itself) and the
+//! result is loaded as usual, alongside a [`SalvageReport`] of what was lost.
+
+use std::collections::HashSet;
+use std::io::{Cursor, Write};
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use zip::ZipArchive;
+
+use crate::error::{Result, RustypyxlError};
+use crate::workbook::Workbook;
+
+const LOCAL_HEADER: &[u8; 4] = b"PK\x03\x04";
+const CENTRAL_HEADER: &[u8; 4] = b"PK\x01\x02";
+const DATA_DESCRIPTOR: &[u8; 4] = b"PK\x07\x08";
+const LOCAL_HEADER_LEN: usize = 30;
+
+/// General-purpose flag bits of a local file header.
+const FLAG_ENCRYPTED: u16 = 0x0001;
+const FLAG_DATA_DESCRIPTOR: u16 = 0x0008;
+
+const WORKBOOK_PART: &str = "xl/workbook.xml";
+const WORKBOOK_RELS_PART: &str = "xl/_rels/workbook.xml.rels";
+
+/// What [`Workbook::salvage`] recovered and what it had to give up.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SalvageReport {
+    /// The archive's central directory was missing or unreadable, so a
+    /// normal load would have failed.
+    pub central_directory_damaged: bool,
+    /// The data ended in the middle of an entry.
+    pub truncated: bool,
+    /// Parts that decompressed cleanly, in archive order.
+    pub recovered_parts: Vec<String>,
+    /// Parts that were found but could not be read, with the reason.
+    pub damaged_parts: Vec<(String, String)>,
+    /// Sheets in the salvaged workbook.
+    pub recovered_sheets: Vec<String>,
+    /// Sheets the workbook part lists whose worksheet part was lost.
+    pub lost_sheets: Vec<String>,
+    /// The workbook part was lost and was rebuilt from the worksheet parts;
+    /// the sheets are then named "Sheet1", "Sheet2", ... after their parts.
+    pub workbook_part_rebuilt: bool,
+    /// Defined names were dropped because sheets they may refer to by
+    /// position were lost.
+    pub defined_names_dropped: bool,
+}
+
+impl SalvageReport {
+    /// Whether nothing was lost: the archive read back completely.
+    pub fn is_intact(&self) -> bool {
+        !self.central_directory_damaged
+            && !self.truncated
+            && self.damaged_parts.is_empty()
+            && self.lost_sheets.is_empty()
+            && !self.workbook_part_rebuilt
+    }
+}
+
+impl Workbook {
+    /// Recover what can be read from a damaged .xlsx file, such as a
+    /// truncated upload. Returns the partial workbook and a report of the
+    /// parts and sheets that were lost; fails only when no worksheet at all
+    /// could be recovered.
+    pub fn salvage(path: &str) -> Result<(Workbook, SalvageReport)> {
+        let data = std::fs::read(path)?;
+        Self::salvage_from_bytes(&data)
+    }
+
+    /// Recover what can be read from the bytes of a damaged .xlsx file. See
+    /// [`Workbook::salvage`].
+    pub fn salvage_from_bytes(data: &[u8]) -> Result<(Workbook, SalvageReport)> {
+        let mut report = SalvageReport {
+            central_directory_damaged: ZipArchive::new(Cursor::new(data)).is_err(),
+            ..Default::default()
+        };
+
+        let mut parts: Vec<(String, Vec<u8>)> = Vec::new();
+        for entry in scan_local_entries(data, &mut report) {
+            if parts.iter().any(|(name, _)| *name == entry.name) {
+                continue;
+            }
+            match entry.extract(data) {
+                Ok(bytes) => {
+                    report.recovered_parts.push(entry.name.clone());
+                    parts.push((entry.name, bytes));
+                }
+                Err(reason) => report.damaged_parts.push((entry.name, reason)),
+            }
+        }
+
+        repair_workbook_part(&mut parts, &mut report)?;
+        if report.recovered_sheets.is_empty() {
+            return Err(RustypyxlError::custom(
+                "No worksheet could be recovered from the archive",
+            ));
+        }
+
+        let workbook = Workbook::load_from_bytes(&repack(&parts)?)?;
+        Ok((workbook, report))
+    }
+}
+
+/// An entry found by walking the local file headers.
+struct LocalEntry {
+    name: String,
+    flags: u16,
+    method: u16,
+    mod_time: u16,
+    mod_date: u16,
+    crc32: u32,
+    uncompressed_size: u32,
+    data: std::ops::Range<usize>,
+}
+
+impl LocalEntry {
+    /// Decompress the entry and check its CRC by wrapping it in a
+    /// single-entry archive of its own.
+    fn extract(&self, data: &[u8]) -> std::result::Result<Vec<u8>, String> {
+        if self.flags & FLAG_ENCRYPTED != 0 {
+            return Err("entry is encrypted".to_string());
+        }
+        let archive = self.single_entry_archive(&data[self.data.clone()]);
+        let mut archive = ZipArchive::new(Cursor::new(archive)).map_err(|e| e.to_string())?;
+        Workbook::read_zip_file_to_vec(&mut archive, &self.name).map_err(|e| e.to_string())
+    }
+
+    fn single_entry_archive(&self, compressed: &[u8]) -> Vec<u8> {
+        let name = self.name.as_bytes();
+        // Sizes now come from the header, never from a trailing descriptor.
+        let flags = self.flags & !FLAG_DATA_DESCRIPTOR;
+        let mut out = Vec::with_capacity(compressed.len() + 2 * name.len() + 128);
+
+        out.extend_from_slice(LOCAL_HEADER);
+        push_u16(&mut out, 20);
+        self.push_common_fields(&mut out, flags, compressed.len());
+        push_u16(&mut out, 0); // extra field length
+        out.extend_from_slice(name);
+        out.extend_from_slice(compressed);
+
+        let directory_offset = out.len();
+        out.extend_from_slice(CENTRAL_HEADER);
+        push_u16(&mut out, 20); // version made by
+        push_u16(&mut out, 20); // version needed
+        self.push_common_fields(&mut out, flags, compressed.len());
+        push_u16(&mut out, 0); // extra field length
+        push_u16(&mut out, 0); // comment length
+        push_u16(&mut out, 0); // disk number
+        push_u16(&mut out, 0); // internal attributes
+        push_u32(&mut out, 0); // external attributes
+        push_u32(&mut out, 0); // local header offset
+        out.extend_from_slice(name);
+        let directory_len = out.len() - directory_offset;
+
+        out.extend_from_slice(b"PK\x05\x06");
+        push_u16(&mut out, 0); // this disk
+        push_u16(&mut out, 0); // directory disk
+        push_u16(&mut out, 1); // entries on this disk
+        push_u16(&mut out, 1); // entries in total
+        push_u32(&mut out, directory_len as u32);
+        push_u32(&mut out, directory_offset as u32);
+        push_u16(&mut out, 0); // comment length
+        out
+    }
+
+    /// The fields shared by the local and central headers, from the flags
+    /// through the name length.
+    fn push_common_fields(&self, out: &mut Vec<u8>, flags: u16, compressed_len: usize) {
+        push_u16(out, flags);
+        push_u16(out, self.method);
+        push_u16(out, self.mod_time);
+        push_u16(out, self.mod_date);
+        push_u32(out, self.crc32);
+        push_u32(out, compressed_len as u32);
+        push_u32(out, self.uncompressed_size);
+        push_u16(out, self.name.len() as u16);
+    }
+}
+
+/// Walk the local file headers from the start of the data. An entry whose
+/// data runs past the end marks the archive truncated; a header that does not
+/// parse is skipped and the scan resumes at the next signature.
+fn scan_local_entries(data: &[u8], report: &mut SalvageReport) -> Vec<LocalEntry> {
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = find(data, LOCAL_HEADER, pos) {
+        pos = start + LOCAL_HEADER.len();
+        let Some(header) = data.get(start..start + LOCAL_HEADER_LEN) else {
+            report.truncated = true;
+            break;
+        };
+        let name_len = read_u16(header, 26) as usize;
+        let extra_len = read_u16(header, 28) as usize;
+        let name_start = start + LOCAL_HEADER_LEN;
+        let Some(name) = data.get(name_start..name_start + name_len) else {
+            report.truncated = true;
+            break;
+        };
+        // Signature bytes inside compressed data can look like a header;
+        // a name that is not UTF-8 gives those away.
+        let Ok(name) = std::str::from_utf8(name) else {
+            continue;
+        };
+        if name.is_empty() || name.ends_with('/') {
+            continue;
+        }
+
+        let mut entry = LocalEntry {
+            name: name.to_string(),
+            flags: read_u16(header, 6),
+            method: read_u16(header, 8),
+            mod_time: read_u16(header, 10),
+            mod_date: read_u16(header, 12),
+            crc32: read_u32(header, 14),
+            uncompressed_size: read_u32(header, 22),
+            data: 0..0,
+        };
+        let data_start = name_start + name_len + extra_len;
+        let end = if entry.flags & FLAG_DATA_DESCRIPTOR != 0 {
+            locate_descriptor(data, data_start).map(|(end, crc32, size)| {
+                entry.crc32 = crc32;
+                entry.uncompressed_size = size;
+                end
+            })
+        } else {
+            let end = data_start + read_u32(header, 18) as usize;
+            (end <= data.len()).then_some(end)
+        };
+        let Some(end) = end else {
+            report.truncated = true;
+            report
+                .damaged_parts
+                .push((entry.name, "data is truncated".to_string()));
+            continue;
+        };
+        entry.data = data_start..end;
+        pos = end;
+        entries.push(entry);
+    }
+    entries
+}
+
+/// Find the end of an entry written with a trailing data descriptor, whose
+/// header leaves the sizes and CRC zero. Returns the end of the compressed
+/// data with the CRC and uncompressed size from the descriptor.
+fn locate_descriptor(data: &[u8], data_start: usize) -> Option<(usize, u32, u32)> {
+    // A signed descriptor whose compressed size matches the distance covered.
+    let mut pos = data_start;
+    while let Some(at) = find(data, DATA_DESCRIPTOR, pos) {
+        let fields = data.get(at + 4..at + 16)?;
+        if read_u32(fields, 4) as usize == at - data_start {
+            return Some((at, read_u32(fields, 0), read_u32(fields, 8)));
+        }
+        pos = at + 1;
+    }
+    // The signature is optional: try the 12 bytes before the next header.
+    let next = [LOCAL_HEADER, CENTRAL_HEADER]
+        .iter()
+        .filter_map(|sig| find(data, *sig, data_start))
+        .min()?;
+    let at = next.checked_sub(12).filter(|at| *at >= data_start)?;
+    let fields = &data[at..next];
+    (read_u32(fields, 4) as usize == at - data_start)
+        .then(|| (at, read_u32(fields, 0), read_u32(fields, 8)))
+}
+
+/// Make the workbook part agree with the recovered worksheet parts: drop the
+/// sheets whose part was lost, or rebuild the part when it was lost itself.
+fn repair_workbook_part(
+    parts: &mut Vec<(String, Vec<u8>)>,
+    report: &mut SalvageReport,
+) -> Result<()> {
+    let workbook_xml = parts
+        .iter()
+        .find(|(name, _)| name == WORKBOOK_PART)
+        .map(|(_, xml)| xml.clone());
+    let sheet_info =
+        workbook_xml.and_then(|xml| Workbook::parse_workbook_xml(Cursor::new(xml)).ok());
+
+    let Some((sheets, named_ranges, _, _)) = sheet_info else {
+        return rebuild_workbook_part(parts, report);
+    };
+
+    let rels = parts
+        .iter()
+        .find(|(name, _)| name == WORKBOOK_RELS_PART)
+        .and_then(|(_, xml)| Workbook::parse_workbook_rels(Cursor::new(xml)).ok())
+        .unwrap_or_default();
+    let mut lost_ids = HashSet::new();
+    for (name, sheet_id, rid, _) in sheets {
+        let path = Workbook::sheet_part_path(rels.get(&rid), sheet_id);
+        if parts.iter().any(|(part, _)| *part == path) {
+            report.recovered_sheets.push(name);
+        } else {
+            report.lost_sheets.push(name);
+            lost_ids.insert(rid);
+        }
+    }
+    if lost_ids.is_empty() {
+        return Ok(());
+    }
+
+    report.defined_names_dropped = !named_ranges.is_empty();
+    for (name, xml) in parts.iter_mut() {
+        if name == WORKBOOK_PART {
+            *xml = drop_sheets(xml, &lost_ids)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove the `<sheet>` elements with the given relationship ids, and the
+/// defined names, whose local sheet ids count sheet positions.
+fn drop_sheets(workbook_xml: &[u8], lost_ids: &HashSet<String>) -> Result<Vec<u8>> {
+    let dropped = |e: &BytesStart| match e.local_name().as_ref() {
+        b"definedNames" => true,
+        b"sheet" => e.attributes().flatten().any(|a| {
+            a.key.local_name().as_ref() == b"id"
+                && lost_ids.contains(String::from_utf8_lossy(&a.value).as_ref())
+        }),
+        _ => false,
+    };
+
+    let mut reader = Reader::from_reader(workbook_xml);
+    let mut writer = Writer::new(Vec::with_capacity(workbook_xml.len()));
+    let mut buf = Vec::new();
+    let mut skip_depth = 0usize;
+    loop {
+        let event = reader.read_event_into(&mut buf)?;
+        let keep = match &event {
+            Event::Eof => break,
+            Event::Start(e) => {
+                if skip_depth > 0 || dropped(e) {
+                    skip_depth += 1;
+                }
+                skip_depth == 0
+            }
+            Event::End(_) if skip_depth > 0 => {
+                skip_depth -= 1;
+                false
+            }
+            Event::Empty(e) => skip_depth == 0 && !dropped(e),
+            _ => skip_depth == 0,
+        };
+        if keep {
+            writer.write_event(event)?;
+        }
+        buf.clear();
+    }
+    Ok(writer.into_inner())
+}
+
+/// Write a workbook part listing every recovered `xl/worksheets/sheetN.xml`
+/// as "SheetN" with sheetId N, which the loader resolves to that part without
+/// relationships.
+fn rebuild_workbook_part(
+    parts: &mut Vec<(String, Vec<u8>)>,
+    report: &mut SalvageReport,
+) -> Result<()> {
+    let mut numbers: Vec<u32> = parts
+        .iter()
+        .filter_map(|(name, _)| {
+            name.strip_prefix("xl/worksheets/sheet")?
+                .strip_suffix(".xml")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    numbers.sort_unstable();
+
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" \
+         xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><sheets>",
+    );
+    for n in &numbers {
+        xml.push_str(&format!(
+            "<sheet name=\"Sheet{n}\" sheetId=\"{n}\" r:id=\"rIdSalvaged{n}\"/>"
+        ));
+        report.recovered_sheets.push(format!("Sheet{n}"));
+    }
+    xml.push_str("</sheets></workbook>");
+
+    // The surviving relationships belong to the lost part and could point a
+    // rebuilt sheet at the wrong worksheet.
+    parts.retain(|(name, _)| name != WORKBOOK_PART && name != WORKBOOK_RELS_PART);
+    parts.push((WORKBOOK_PART.to_string(), xml.into_bytes()));
+    report.workbook_part_rebuilt = true;
+    Ok(())
+}
+
+/// Store the recovered parts in a fresh archive for the regular loader.
+fn repack(parts: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for (name, bytes) in parts {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(bytes)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
+
+fn find(data: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    data.get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| from + i)
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+fn push_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::CellValue;
+
+    fn two_sheet_file() -> Vec<u8> {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.create_sheet(Some("Notes".to_string())).unwrap();
+        wb.set_cell_value_in_sheet("Data", 1, 1, CellValue::Number(42.0))
+            .unwrap();
+        wb.set_cell_value_in_sheet("Notes", 1, 1, CellValue::Number(7.0))
+            .unwrap();
+        wb.save_to_bytes().unwrap()
+    }
+
+    /// Offset of the first byte of a part's data, from its local header.
+    fn part_data_offset(data: &[u8], name: &str) -> usize {
+        scan_local_entries(data, &mut SalvageReport::default())
+            .into_iter()
+            .find(|entry| entry.name == name)
+            .unwrap()
+            .data
+            .start
+    }
+
+    fn number_at(wb: &Workbook, sheet: &str) -> Option<CellValue> {
+        wb.get_sheet_by_name(sheet)
+            .unwrap()
+            .get_cell_value(1, 1)
+            .cloned()
+    }
+
+    #[test]
+    fn intact_archive_salvages_everything() {
+        let (wb, report) = Workbook::salvage_from_bytes(&two_sheet_file()).unwrap();
+        assert!(report.is_intact(), "{:?}", report);
+        assert_eq!(report.recovered_sheets, vec!["Data", "Notes"]);
+        assert_eq!(number_at(&wb, "Notes"), Some(CellValue::Number(7.0)));
+    }
+
+    #[test]
+    fn truncated_archive_keeps_complete_sheets() {
+        let data = two_sheet_file();
+        let cut = part_data_offset(&data, "xl/worksheets/sheet2.xml") + 5;
+        assert!(Workbook::load_from_bytes(&data[..cut]).is_err());
+
+        let (wb, report) = Workbook::salvage_from_bytes(&data[..cut]).unwrap();
+        assert!(report.central_directory_damaged);
+        assert!(report.truncated);
+        assert_eq!(report.recovered_sheets, vec!["Data"]);
+        assert_eq!(report.lost_sheets, vec!["Notes"]);
+        assert!(report
+            .damaged_parts
+            .iter()
+            .any(|(name, _)| name == "xl/worksheets/sheet2.xml"));
+        assert_eq!(wb.sheet_names, vec!["Data"]);
+        assert_eq!(number_at(&wb, "Data"), Some(CellValue::Number(42.0)));
+    }
+
+    #[test]
+    fn corrupted_sheet_is_reported_and_skipped() {
+        let mut data = two_sheet_file();
+        let at = part_data_offset(&data, "xl/worksheets/sheet1.xml") + 8;
+        data[at] ^= 0xFF;
+
+        let (wb, report) = Workbook::salvage_from_bytes(&data).unwrap();
+        assert!(!report.central_directory_damaged);
+        assert!(!report.is_intact());
+        assert_eq!(report.lost_sheets, vec!["Data"]);
+        assert_eq!(wb.sheet_names, vec!["Notes"]);
+        assert_eq!(number_at(&wb, "Notes"), Some(CellValue::Number(7.0)));
+    }
+
+    #[test]
+    fn lost_workbook_part_is_rebuilt_from_worksheets() {
+        let mut data = two_sheet_file();
+        let at = part_data_offset(&data, WORKBOOK_PART) + 8;
+        data[at] ^= 0xFF;
+
+        let (wb, report) = Workbook::salvage_from_bytes(&data).unwrap();
+        assert!(report.workbook_part_rebuilt);
+        assert_eq!(wb.sheet_names, vec!["Sheet1", "Sheet2"]);
+        assert_eq!(number_at(&wb, "Sheet1"), Some(CellValue::Number(42.0)));
+        assert_eq!(number_at(&wb, "Sheet2"), Some(CellValue::Number(7.0)));
+    }
+
+    #[test]
+    fn nothing_recoverable_is_an_error() {
+        assert!(Workbook::salvage_from_bytes(b"not a zip file").is_err());
+    }
+}
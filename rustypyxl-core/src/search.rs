@@ -0,0 +1,278 @@
+//! Find where a value lives across a workbook.
+//!
+//! [`Workbook::search`] scans sheets in parallel for cells whose displayed
+//! text (and optionally formula) contains a query, case-insensitively, and
+//! ranks the hits so the most likely answer comes first: whole-cell matches,
+//! then matches at the start of the text, then at the start of a word, then
+//! anywhere. Each hit carries a snippet of the text around the match.
+
+use rayon::prelude::*;
+
+use crate::cell::CellValue;
+use crate::error::{Result, RustypyxlError};
+use crate::utils::coordinate_from_row_col;
+use crate::workbook::Workbook;
+use crate::worksheet::Worksheet;
+
+/// Characters of context kept either side of the match in a snippet.
+const SNIPPET_CONTEXT: usize = 20;
+
+/// How well a cell matched the query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MatchKind {
+    /// The whole text equals the query.
+    Exact,
+    /// The text starts with the query.
+    Prefix,
+    /// The query starts a word inside the text.
+    Word,
+    /// The query appears somewhere in the text.
+    Contains,
+}
+
+impl MatchKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchKind::Exact => "exact",
+            MatchKind::Prefix => "prefix",
+            MatchKind::Word => "word",
+            MatchKind::Contains => "contains",
+        }
+    }
+}
+
+/// One cell found by [`Workbook::search`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchMatch {
+    pub sheet: String,
+    pub row: u32,
+    pub column: u32,
+    /// A1 coordinate of the cell.
+    pub coordinate: String,
+    /// The matched text, cut to the match and some context around it.
+    pub snippet: String,
+    pub kind: MatchKind,
+    /// Whether the match is in the formula rather than the displayed value.
+    pub in_formula: bool,
+}
+
+/// Options for [`Workbook::search`].
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    /// Sheets to search, by name. Default: all of them.
+    pub sheets: Option<Vec<String>>,
+    /// Also match formula text (with its leading '='). Default: false.
+    pub in_formulas: bool,
+    /// Keep at most this many of the best matches. Default: no limit.
+    pub limit: Option<usize>,
+}
+
+impl SearchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Search only these sheets.
+    pub fn with_sheets(mut self, sheets: &[&str]) -> Self {
+        self.sheets = Some(sheets.iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    /// Set whether formula text is searched.
+    pub fn with_formulas(mut self, in_formulas: bool) -> Self {
+        self.in_formulas = in_formulas;
+        self
+    }
+
+    /// Keep at most `limit` matches.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl Workbook {
+    /// Find the cells whose displayed text contains `query`, ignoring case,
+    /// best matches first. A formula cell's displayed text is its cached
+    /// result; with `options.in_formulas` its formula is searched too and the
+    /// better of the two matches counts. Equally good matches keep tab, row
+    /// and column order.
+    pub fn search(&self, query: &str, options: &SearchOptions) -> Result<Vec<SearchMatch>> {
+        if query.is_empty() {
+            return Err(RustypyxlError::custom("search query is empty"));
+        }
+        let sheets: Vec<(usize, &Worksheet)> = match &options.sheets {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    self.sheet_position(name)
+                        .map(|idx| (idx, &self.worksheets[idx]))
+                        .ok_or_else(|| RustypyxlError::WorksheetNotFound(name.clone()))
+                })
+                .collect::<Result<_>>()?,
+            None => self.worksheets.iter().enumerate().collect(),
+        };
+
+        let needle = query.to_lowercase();
+        let mut found: Vec<(usize, SearchMatch)> = sheets
+            .par_iter()
+            .flat_map_iter(|&(idx, ws)| {
+                search_sheet(ws, &self.sheet_names[idx], &needle, options.in_formulas)
+                    .into_iter()
+                    .map(move |m| (idx, m))
+            })
+            .collect();
+        found.sort_by_key(|(idx, m)| (m.kind, m.in_formula, *idx, m.row, m.column));
+        if let Some(limit) = options.limit {
+            found.truncate(limit);
+        }
+        Ok(found.into_iter().map(|(_, m)| m).collect())
+    }
+}
+
+fn search_sheet(ws: &Worksheet, sheet: &str, needle: &str, in_formulas: bool) -> Vec<SearchMatch> {
+    let mut found = Vec::new();
+    for ((row, column), cell) in ws.iter_cells() {
+        let shown = cell.formatted_value();
+        let mut best = match_text(&shown, needle).map(|hit| (hit, shown, false));
+        if let (true, CellValue::Formula(_)) = (in_formulas, &cell.value) {
+            let formula = cell.value.to_string();
+            if let Some(hit) = match_text(&formula, needle) {
+                if best.as_ref().is_none_or(|(b, _, _)| hit.0 < b.0) {
+                    best = Some((hit, formula, true));
+                }
+            }
+        }
+        if let Some(((kind, at), text, in_formula)) = best {
+            found.push(SearchMatch {
+                sheet: sheet.to_string(),
+                row,
+                column,
+                coordinate: coordinate_from_row_col(row, column),
+                snippet: snippet(&text, at, needle.chars().count()),
+                kind,
+                in_formula,
+            });
+        }
+    }
+    found
+}
+
+/// The best way `text` matches `needle` (already lowercased), with the
+/// character offset of that match.
+fn match_text(text: &str, needle: &str) -> Option<(MatchKind, usize)> {
+    let lower = text.to_lowercase();
+    if lower == needle {
+        return Some((MatchKind::Exact, 0));
+    }
+    if lower.starts_with(needle) {
+        return Some((MatchKind::Prefix, 0));
+    }
+    let mut first = None;
+    for (pos, _) in lower.match_indices(needle) {
+        let before = lower[..pos].chars().next_back();
+        let chars = lower[..pos].chars().count();
+        if before.is_some_and(|c| !c.is_alphanumeric()) {
+            return Some((MatchKind::Word, chars));
+        }
+        first.get_or_insert(chars);
+    }
+    first.map(|chars| (MatchKind::Contains, chars))
+}
+
+/// Cut `text` to the `len` characters at `at` plus some context, marking
+/// trimmed ends with an ellipsis.
+fn snippet(text: &str, at: usize, len: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let at = at.min(chars.len());
+    let start = at.saturating_sub(SNIPPET_CONTEXT);
+    let end = (at + len + SNIPPET_CONTEXT).min(chars.len());
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    out.extend(&chars[start..end]);
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workbook() -> Workbook {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("Model".to_string())).unwrap();
+        ws.set_cell_value(1, 1, "Revenue");
+        ws.set_cell_value(2, 1, "Net revenue after tax");
+        ws.set_cell_value(3, 1, "Prerevenue costs");
+        ws.set_cell_value(4, 1, CellValue::Formula("SUM(Revenue)".to_string()));
+        ws.get_cell_mut(4, 1).unwrap().cached_formula_value = Some("1200".to_string());
+        let ws = wb.create_sheet(Some("Notes".to_string())).unwrap();
+        ws.set_cell_value(5, 2, "revenue");
+        ws.set_cell_value(6, 2, 1200.0);
+        wb
+    }
+
+    fn hits(found: &[SearchMatch]) -> Vec<(&str, &str, MatchKind)> {
+        found
+            .iter()
+            .map(|m| (m.sheet.as_str(), m.coordinate.as_str(), m.kind))
+            .collect()
+    }
+
+    #[test]
+    fn matches_rank_across_sheets() {
+        let found = workbook().search("REVENUE", &SearchOptions::new()).unwrap();
+        assert_eq!(
+            hits(&found),
+            [
+                ("Model", "A1", MatchKind::Exact),
+                ("Notes", "B5", MatchKind::Exact),
+                ("Model", "A2", MatchKind::Word),
+                ("Model", "A3", MatchKind::Contains),
+            ]
+        );
+        assert_eq!(found[2].snippet, "Net revenue after tax");
+    }
+
+    #[test]
+    fn formulas_sheets_and_limit() {
+        let wb = workbook();
+        let found = wb.search("1200", &SearchOptions::new()).unwrap();
+        assert_eq!(
+            hits(&found),
+            [
+                ("Model", "A4", MatchKind::Exact),
+                ("Notes", "B6", MatchKind::Exact)
+            ]
+        );
+
+        let options = SearchOptions::new()
+            .with_formulas(true)
+            .with_sheets(&["Model"]);
+        let found = wb.search("sum(", &options).unwrap();
+        assert_eq!(hits(&found), [("Model", "A4", MatchKind::Word)]);
+        assert!(found[0].in_formula);
+        assert_eq!(found[0].snippet, "=SUM(Revenue)");
+
+        let options = SearchOptions::new().with_limit(1);
+        assert_eq!(wb.search("revenue", &options).unwrap().len(), 1);
+        assert!(wb.search("", &SearchOptions::new()).is_err());
+        let options = SearchOptions::new().with_sheets(&["Missing"]);
+        assert!(wb.search("x", &options).is_err());
+    }
+
+    #[test]
+    fn snippets_trim_long_text() {
+        let text = format!("{}needle{}", "a".repeat(30), "b".repeat(30));
+        let found = match_text(&text, "needle").unwrap();
+        assert_eq!(found, (MatchKind::Contains, 30));
+        assert_eq!(
+            snippet(&text, found.1, 6),
+            format!("…{}needle{}…", "a".repeat(20), "b".repeat(20))
+        );
+    }
+}
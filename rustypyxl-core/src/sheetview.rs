@@ -0,0 +1,215 @@
+//! How a worksheet is displayed: gridlines, zoom, right-to-left layout, view
+//! type, scroll position and selection (`<sheetView>`), and the tab color
+//! (`<sheetPr>`).
+
+use crate::error::{Result, RustypyxlError};
+use crate::style::Color;
+
+/// The layout Excel opens the sheet in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SheetViewType {
+    /// The ordinary grid.
+    #[default]
+    Normal,
+    /// Page break preview, with print areas outlined.
+    PageBreakPreview,
+    /// Page layout, showing pages with headers and margins.
+    PageLayout,
+}
+
+impl SheetViewType {
+    /// The `view` attribute value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SheetViewType::Normal => "normal",
+            SheetViewType::PageBreakPreview => "pageBreakPreview",
+            SheetViewType::PageLayout => "pageLayout",
+        }
+    }
+
+    /// Parse a `view` attribute value.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "normal" => Ok(SheetViewType::Normal),
+            "pageBreakPreview" => Ok(SheetViewType::PageBreakPreview),
+            "pageLayout" => Ok(SheetViewType::PageLayout),
+            _ => Err(RustypyxlError::custom(format!(
+                "Unknown sheet view '{}': expected normal, pageBreakPreview or pageLayout",
+                value
+            ))),
+        }
+    }
+}
+
+/// Display settings of a worksheet's (first) sheet view.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SheetView {
+    /// Show the cell gridlines.
+    pub show_grid_lines: bool,
+    /// Zoom in percent, 10 to 400.
+    pub zoom_scale: u32,
+    /// Lay the sheet out right to left (column A on the right).
+    pub right_to_left: bool,
+    /// View type.
+    pub view: SheetViewType,
+    /// The cell at the top left of the scrollable area, i.e. where the sheet
+    /// is scrolled to. With frozen panes it is the first cell shown below
+    /// and right of them. `None` opens at the top.
+    pub top_left_cell: Option<String>,
+    /// The active (cursor) cell.
+    pub active_cell: Option<String>,
+    /// The selected ranges as a space-separated sqref ("A10:C12 E1");
+    /// `None` selects just the active cell.
+    pub selection: Option<String>,
+}
+
+impl Default for SheetView {
+    fn default() -> Self {
+        SheetView {
+            show_grid_lines: true,
+            zoom_scale: 100,
+            right_to_left: false,
+            view: SheetViewType::Normal,
+            top_left_cell: None,
+            active_cell: None,
+            selection: None,
+        }
+    }
+}
+
+impl SheetView {
+    /// Smallest zoom Excel accepts.
+    pub const MIN_ZOOM: u32 = 10;
+    /// Largest zoom Excel accepts.
+    pub const MAX_ZOOM: u32 = 400;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_show_grid_lines(mut self, show: bool) -> Self {
+        self.show_grid_lines = show;
+        self
+    }
+
+    /// Zoom in percent; clamped to 10..=400.
+    pub fn with_zoom_scale(mut self, zoom: u32) -> Self {
+        self.zoom_scale = zoom.clamp(Self::MIN_ZOOM, Self::MAX_ZOOM);
+        self
+    }
+
+    pub fn with_right_to_left(mut self, rtl: bool) -> Self {
+        self.right_to_left = rtl;
+        self
+    }
+
+    pub fn with_view(mut self, view: SheetViewType) -> Self {
+        self.view = view;
+        self
+    }
+
+    pub fn with_top_left_cell<S: Into<String>>(mut self, cell: S) -> Self {
+        self.top_left_cell = Some(cell.into());
+        self
+    }
+
+    /// Select `sqref` with `active_cell` as the cursor.
+    pub fn with_selection<A: Into<String>, S: Into<String>>(
+        mut self,
+        active_cell: A,
+        sqref: S,
+    ) -> Self {
+        self.active_cell = Some(active_cell.into());
+        self.selection = Some(sqref.into());
+        self
+    }
+
+    /// Set the zoom in percent, failing outside 10..=400.
+    pub fn set_zoom_scale(&mut self, zoom: u32) -> Result<()> {
+        if !(Self::MIN_ZOOM..=Self::MAX_ZOOM).contains(&zoom) {
+            return Err(RustypyxlError::custom(format!(
+                "zoomScale must be between {} and {}, got {}",
+                Self::MIN_ZOOM,
+                Self::MAX_ZOOM,
+                zoom
+            )));
+        }
+        self.zoom_scale = zoom;
+        Ok(())
+    }
+}
+
+/// Sheet-level properties kept in `<sheetPr>`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SheetProperties {
+    /// Color of the sheet's tab.
+    pub tab_color: Option<Color>,
+    /// Summary rows sit below their row groups (Excel's default), rather
+    /// than above.
+    pub summary_below: bool,
+    /// Summary columns sit right of their column groups (Excel's default),
+    /// rather than left.
+    pub summary_right: bool,
+}
+
+impl Default for SheetProperties {
+    fn default() -> Self {
+        SheetProperties {
+            tab_color: None,
+            summary_below: true,
+            summary_right: true,
+        }
+    }
+}
+
+impl SheetProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tab_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.tab_color = Some(color.into());
+        self
+    }
+
+    pub fn with_summary_below(mut self, below: bool) -> Self {
+        self.summary_below = below;
+        self
+    }
+
+    pub fn with_summary_right(mut self, right: bool) -> Self {
+        self.summary_right = right;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sheet_view_builders() {
+        let view = SheetView::new()
+            .with_show_grid_lines(false)
+            .with_zoom_scale(1000)
+            .with_right_to_left(true)
+            .with_view(SheetViewType::PageBreakPreview);
+        assert!(!view.show_grid_lines);
+        assert_eq!(view.zoom_scale, 400);
+        assert!(view.right_to_left);
+        assert_eq!(view.view.as_str(), "pageBreakPreview");
+    }
+
+    #[test]
+    fn test_zoom_validation() {
+        let mut view = SheetView::new();
+        assert!(view.set_zoom_scale(5).is_err());
+        view.set_zoom_scale(150).unwrap();
+        assert_eq!(view.zoom_scale, 150);
+        assert_eq!(
+            SheetViewType::parse("pageLayout").unwrap(),
+            SheetViewType::PageLayout
+        );
+        assert!(SheetViewType::parse("print").is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Workbook skeletons: the layout of a workbook without its data, for
+//! starting next period's file from this period's.
+
+use crate::utils::parse_coordinate;
+use crate::workbook::Workbook;
+use crate::worksheet::{cell_key, CellMap, Worksheet};
+
+impl Workbook {
+    /// A new workbook with the same sheets, styles and named ranges, where
+    /// each sheet keeps only its first `header_rows` rows of cells.
+    ///
+    /// Kept per sheet: column widths, the header rows' heights, merges and
+    /// comments, data validations, conditional formatting, the autofilter,
+    /// freeze panes, page setup, protection and visibility. Charts, images,
+    /// tables and pivot tables describe the data and are left out.
+    pub fn clone_structure(&self, header_rows: u32) -> Workbook {
+        let mut skeleton = Workbook::new();
+        skeleton.compression = self.compression;
+        skeleton.styles = self.styles.clone();
+        skeleton.named_ranges = self.named_ranges.clone();
+        skeleton.active_sheet = self.active_sheet;
+        skeleton.date1904 = self.date1904;
+        skeleton.protection = self.protection.clone();
+        skeleton.preserved = self.preserved.clone();
+        for (name, ws) in self.sheet_names.iter().zip(&self.worksheets) {
+            let mut sheet = ws.structure(header_rows);
+            sheet.uid = skeleton.allocate_sheet_uid();
+            skeleton
+                .push_sheet(name.clone(), sheet)
+                .expect("source sheet names are unique");
+        }
+        skeleton
+    }
+}
+
+impl Worksheet {
+    /// This sheet's layout and first `header_rows` rows, without the rest.
+    fn structure(&self, header_rows: u32) -> Worksheet {
+        let in_header = |row: u32| row <= header_rows;
+        let cells: CellMap = self
+            .all_cells()
+            .filter(|((row, _), _)| in_header(*row))
+            .map(|((row, col), cell)| (cell_key(row, col), cell.into_owned()))
+            .collect();
+        let mut sheet = Worksheet {
+            cells,
+            merged_cells: self
+                .merged_cells
+                .iter()
+                .filter(|(_, end)| parse_coordinate(end).is_ok_and(|(row, _)| in_header(row)))
+                .cloned()
+                .collect(),
+            row_dimensions: self
+                .row_dimensions
+                .iter()
+                .filter(|(row, _)| in_header(**row))
+                .map(|(row, height)| (*row, *height))
+                .collect(),
+            comment_shapes: self
+                .comment_shapes
+                .iter()
+                .filter(|((row, _), _)| in_header(*row))
+                .map(|(key, shape)| (*key, shape.clone()))
+                .collect(),
+            row_tags: self
+                .row_tags
+                .iter()
+                .filter(|(row, _)| in_header(**row))
+                .map(|(row, tag)| (*row, tag.clone()))
+                .collect(),
+            cell_tags: self
+                .cell_tags
+                .iter()
+                .filter(|((row, _), _)| in_header(*row))
+                .map(|(key, tag)| (*key, tag.clone()))
+                .collect(),
+            row_props: self
+                .row_props
+                .iter()
+                .filter(|(row, _)| in_header(**row))
+                .map(|(row, props)| (*row, *props))
+                .collect(),
+            column_dimensions: self.column_dimensions.clone(),
+            column_props: self.column_props.clone(),
+            data_validations: self.data_validations.clone(),
+            protection: self.protection.clone(),
+            auto_filter: self.auto_filter.clone(),
+            conditional_formatting: self.conditional_formatting.clone(),
+            page_setup: self.page_setup.clone(),
+            freeze_panes: self.freeze_panes.clone(),
+            sheet_view: self.sheet_view.clone(),
+            sheet_properties: self.sheet_properties.clone(),
+            visibility: self.visibility,
+            opaque: self.opaque.clone(),
+            ..Worksheet::new(self.title.clone())
+        };
+        sheet.set_storage(self.storage());
+        sheet.recompute_dimensions();
+        sheet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::worksheet::DataValidation;
+
+    #[test]
+    fn test_clone_structure_keeps_layout_and_headers() {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("March".to_string())).unwrap();
+        ws.set_cell_value(1, 1, "Region");
+        ws.set_cell_value(1, 2, "Sales");
+        ws.set_cell_value(2, 1, "North");
+        ws.set_cell_value(2, 2, 120.0);
+        ws.column_dimensions.insert(1, 24.0);
+        ws.row_dimensions.insert(1, 30.0);
+        ws.row_dimensions.insert(2, 18.0);
+        ws.merged_cells.push(("C1".to_string(), "D1".to_string()));
+        ws.merged_cells.push(("C2".to_string(), "D3".to_string()));
+        ws.add_validation(DataValidation::list(["North", "South"]).with_sqref("A2:A500"))
+            .unwrap();
+        ws.freeze_panes = Some("A2".to_string());
+        wb.create_named_range("Regions".to_string(), "'March'!$A$2:$A$500".to_string())
+            .unwrap();
+
+        let skeleton = wb.clone_structure(1);
+        assert_eq!(skeleton.sheet_names, ["March"]);
+        let sheet = &skeleton.worksheets[0];
+        assert_ne!(sheet.uid, 0);
+        assert_eq!(sheet.get_cell_value(1, 2).cloned(), Some("Sales".into()));
+        assert!(sheet.get_cell_value(2, 1).is_none());
+        assert_eq!(sheet.max_row(), 1);
+        assert_eq!(sheet.column_dimensions.get(&1), Some(&24.0));
+        assert_eq!(sheet.row_dimensions.len(), 1);
+        assert_eq!(sheet.merged_cells, [("C1".to_string(), "D1".to_string())]);
+        assert!(sheet.data_validation_at(300, 1).is_some());
+        assert_eq!(sheet.freeze_panes.as_deref(), Some("A2"));
+        assert_eq!(skeleton.named_ranges.len(), 1);
+
+        // The original is untouched.
+        assert!(wb.worksheets[0].get_cell_value(2, 2).is_some());
+    }
+}
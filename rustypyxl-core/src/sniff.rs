@@ -0,0 +1,328 @@
+//! Spreadsheet format detection from file contents rather than extensions.
+//!
+//! `detect_format` looks at the magic bytes and, for ZIP packages, at the
+//! content types and part names, so a caller can route a file to the right
+//! loader before trying to open it. The loaders use the same check to say
+//! what a file is when they cannot read it ("this is an encrypted file",
+//! "this is a legacy .xls") instead of surfacing a bare ZIP error.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+
+use zip::ZipArchive;
+
+use crate::error::{Result, RustypyxlError};
+
+/// The OLE2 / Compound File Binary signature shared by legacy `.xls` files
+/// and encrypted OOXML packages.
+const CFB_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// How much of a text file is sampled when guessing whether it is CSV.
+const TEXT_SAMPLE: usize = 8192;
+
+/// Delimiters considered when recognising delimited text, in tie-break order.
+const DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+
+/// The kind of file `detect_format` found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileFormat {
+    /// An OOXML workbook without macros (`.xlsx`, `.xltx`).
+    Xlsx,
+    /// An OOXML workbook with macros (`.xlsm`, `.xltm`, `.xlam`).
+    Xlsm,
+    /// An Excel binary workbook (`.xlsb`).
+    Xlsb,
+    /// A legacy Excel 97-2003 BIFF workbook in a compound file (`.xls`).
+    Xls,
+    /// An OpenDocument spreadsheet (`.ods`).
+    Ods,
+    /// Delimited text.
+    Csv,
+    /// A password-encrypted OOXML package (a compound file holding an
+    /// `EncryptedPackage` stream).
+    Encrypted,
+    /// A ZIP archive that is not a spreadsheet package.
+    Zip,
+    /// Anything else.
+    Unknown,
+}
+
+impl FileFormat {
+    /// Short lowercase name: "xlsx", "xlsm", "xlsb", "xls", "ods", "csv",
+    /// "encrypted", "zip" or "unknown".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileFormat::Xlsx => "xlsx",
+            FileFormat::Xlsm => "xlsm",
+            FileFormat::Xlsb => "xlsb",
+            FileFormat::Xls => "xls",
+            FileFormat::Ods => "ods",
+            FileFormat::Csv => "csv",
+            FileFormat::Encrypted => "encrypted",
+            FileFormat::Zip => "zip",
+            FileFormat::Unknown => "unknown",
+        }
+    }
+}
+
+/// What `detect_format` found out about a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatInfo {
+    pub format: FileFormat,
+    /// The package holds a VBA project.
+    pub has_macros: bool,
+    /// The package is a template (`.xltx`/`.xltm`).
+    pub template: bool,
+    /// The delimiter guessed for CSV text.
+    pub delimiter: Option<char>,
+}
+
+impl FormatInfo {
+    fn of(format: FileFormat) -> Self {
+        FormatInfo {
+            format,
+            has_macros: false,
+            template: false,
+            delimiter: None,
+        }
+    }
+
+    /// Whether `Workbook::load` can open the file without a password.
+    pub fn is_loadable(&self) -> bool {
+        matches!(self.format, FileFormat::Xlsx | FileFormat::Xlsm)
+    }
+}
+
+/// Identify the format of a file held in memory.
+pub fn detect_format(data: &[u8]) -> FormatInfo {
+    if data.starts_with(b"PK") {
+        return match ZipArchive::new(std::io::Cursor::new(data)) {
+            Ok(mut archive) => inspect_package(&mut archive),
+            Err(_) => FormatInfo::of(FileFormat::Unknown),
+        };
+    }
+    if data.starts_with(&CFB_MAGIC) {
+        return inspect_compound(data);
+    }
+    inspect_text(&data[..data.len().min(TEXT_SAMPLE)])
+}
+
+/// Identify the format of a file on disk. ZIP packages are inspected
+/// through their central directory, so large workbooks are not read whole.
+pub fn detect_format_file(path: &str) -> Result<FormatInfo> {
+    let mut file = File::open(path)?;
+    let mut head = Vec::with_capacity(TEXT_SAMPLE);
+    (&mut file)
+        .take(TEXT_SAMPLE as u64)
+        .read_to_end(&mut head)?;
+    if head.starts_with(b"PK") {
+        file.rewind()?;
+        return Ok(match ZipArchive::new(BufReader::new(file)) {
+            Ok(mut archive) => inspect_package(&mut archive),
+            Err(_) => FormatInfo::of(FileFormat::Unknown),
+        });
+    }
+    if head.starts_with(&CFB_MAGIC) {
+        return Ok(inspect_compound(&std::fs::read(path)?));
+    }
+    Ok(inspect_text(&head))
+}
+
+/// The error a loader should give for a file it cannot open, or None when
+/// the format is one it reads (so the original error stands).
+pub(crate) fn unsupported_format_error(info: &FormatInfo) -> Option<RustypyxlError> {
+    let what = match info.format {
+        FileFormat::Xlsx | FileFormat::Xlsm => return None,
+        FileFormat::Encrypted => {
+            return Some(RustypyxlError::InvalidFormat(
+                "workbook is encrypted; open it with a password".into(),
+            ))
+        }
+        FileFormat::Xls => "a legacy Excel 97-2003 (.xls) workbook",
+        FileFormat::Xlsb => "an Excel binary (.xlsb) workbook",
+        FileFormat::Ods => "an OpenDocument (.ods) spreadsheet",
+        FileFormat::Csv => "CSV text",
+        FileFormat::Zip => "a ZIP archive without a workbook",
+        FileFormat::Unknown => return None,
+    };
+    Some(RustypyxlError::InvalidFormat(format!(
+        "file is {}; only xlsx and xlsm workbooks can be loaded",
+        what
+    )))
+}
+
+/// Explain a failed load by what the file actually is: an encrypted package
+/// says so (naming `password_loader` when there is one) and other
+/// spreadsheet formats are named. Falls back to `err` when the file looked
+/// like a workbook after all.
+pub(crate) fn explain_load_error(
+    err: RustypyxlError,
+    info: Option<FormatInfo>,
+    password_loader: Option<&str>,
+) -> RustypyxlError {
+    match (info, password_loader) {
+        (Some(info), Some(loader)) if info.format == FileFormat::Encrypted => {
+            RustypyxlError::InvalidFormat(format!(
+                "workbook is encrypted; open it with a password via {}",
+                loader
+            ))
+        }
+        (Some(info), _) => unsupported_format_error(&info).unwrap_or(err),
+        (None, _) => err,
+    }
+}
+
+fn inspect_package<R: Read + Seek>(archive: &mut ZipArchive<R>) -> FormatInfo {
+    if let Ok(mut mimetype) = archive.by_name("mimetype") {
+        let mut text = String::new();
+        if mimetype.read_to_string(&mut text).is_ok()
+            && text.trim() == "application/vnd.oasis.opendocument.spreadsheet"
+        {
+            return FormatInfo::of(FileFormat::Ods);
+        }
+    }
+
+    let mut content_types = String::new();
+    if let Ok(mut part) = archive.by_name("[Content_Types].xml") {
+        let _ = part.read_to_string(&mut content_types);
+    }
+    let has_part = |archive: &ZipArchive<R>, name: &str| archive.index_for_name(name).is_some();
+    let has_vba = has_part(archive, "xl/vbaProject.bin");
+
+    let binary = content_types.contains("application/vnd.ms-excel.sheet.binary.macroEnabled.main")
+        || has_part(archive, "xl/workbook.bin");
+    if binary {
+        return FormatInfo {
+            has_macros: has_vba,
+            ..FormatInfo::of(FileFormat::Xlsb)
+        };
+    }
+
+    let macro_main = content_types.contains("application/vnd.ms-excel.sheet.macroEnabled.main+xml")
+        || content_types.contains("application/vnd.ms-excel.template.macroEnabled.main+xml")
+        || content_types.contains("application/vnd.ms-excel.addin.macroEnabled.main+xml");
+    let template = content_types.contains("spreadsheetml.template.main+xml")
+        || content_types.contains("application/vnd.ms-excel.template.macroEnabled.main+xml");
+    if macro_main
+        || content_types.contains("spreadsheetml.")
+        || has_part(archive, "xl/workbook.xml")
+    {
+        let has_macros = macro_main || has_vba;
+        let format = if has_macros {
+            FileFormat::Xlsm
+        } else {
+            FileFormat::Xlsx
+        };
+        return FormatInfo {
+            has_macros,
+            template,
+            ..FormatInfo::of(format)
+        };
+    }
+    FormatInfo::of(FileFormat::Zip)
+}
+
+/// Tell an encrypted OOXML package from a BIFF workbook by the stream names
+/// in the compound file's directory (stored as UTF-16LE).
+fn inspect_compound(data: &[u8]) -> FormatInfo {
+    let has_stream = |name: &str| {
+        let needle: Vec<u8> = name.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        data.windows(needle.len()).any(|w| w == needle.as_slice())
+    };
+    if has_stream("EncryptedPackage") {
+        FormatInfo::of(FileFormat::Encrypted)
+    } else if has_stream("Workbook") || has_stream("Book") {
+        FormatInfo {
+            has_macros: has_stream("_VBA_PROJECT_CUR"),
+            ..FormatInfo::of(FileFormat::Xls)
+        }
+    } else {
+        FormatInfo::of(FileFormat::Unknown)
+    }
+}
+
+/// Recognise delimited text: valid UTF-8 (a multibyte character cut off by
+/// the sample's end is fine), no NULs, and a delimiter in the first line.
+fn inspect_text(sample: &[u8]) -> FormatInfo {
+    let sample = sample.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(sample);
+    let text = match std::str::from_utf8(sample) {
+        Ok(text) => text,
+        Err(e) if e.error_len().is_none() => {
+            std::str::from_utf8(&sample[..e.valid_up_to()]).unwrap_or_default()
+        }
+        Err(_) => return FormatInfo::of(FileFormat::Unknown),
+    };
+    if text.is_empty() || text.contains('\0') {
+        return FormatInfo::of(FileFormat::Unknown);
+    }
+    let first_line = text.lines().next().unwrap_or_default();
+    let mut best: Option<(char, usize)> = None;
+    for delimiter in DELIMITERS {
+        let count = first_line.matches(delimiter).count();
+        if count > 0 && best.is_none_or(|(_, most)| count > most) {
+            best = Some((delimiter, count));
+        }
+    }
+    match best {
+        Some((delimiter, _)) => FormatInfo {
+            delimiter: Some(delimiter),
+            ..FormatInfo::of(FileFormat::Csv)
+        },
+        None => FormatInfo::of(FileFormat::Unknown),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compound_with(streams: &[&str]) -> Vec<u8> {
+        let mut data = CFB_MAGIC.to_vec();
+        data.resize(512, 0);
+        for name in streams {
+            data.extend(name.encode_utf16().flat_map(u16::to_le_bytes));
+            data.resize(data.len() + 16, 0);
+        }
+        data
+    }
+
+    #[test]
+    fn test_compound_files() {
+        let encrypted = compound_with(&["EncryptionInfo", "EncryptedPackage"]);
+        assert_eq!(detect_format(&encrypted).format, FileFormat::Encrypted);
+        let xls = detect_format(&compound_with(&["Workbook", "_VBA_PROJECT_CUR"]));
+        assert_eq!(xls.format, FileFormat::Xls);
+        assert!(xls.has_macros);
+        assert_eq!(
+            detect_format(&compound_with(&["WordDocument"])).format,
+            FileFormat::Unknown
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        let csv = detect_format(b"\xEF\xBB\xBFname;qty\nbolt;4\n");
+        assert_eq!(csv.format, FileFormat::Csv);
+        assert_eq!(csv.delimiter, Some(';'));
+        assert_eq!(
+            detect_format(b"a\tb,c\td\n").delimiter,
+            Some('\t'),
+            "the most frequent delimiter wins"
+        );
+        assert_eq!(detect_format(b"just words").format, FileFormat::Unknown);
+        assert_eq!(detect_format(b"a,\0b").format, FileFormat::Unknown);
+        assert_eq!(detect_format(b"").format, FileFormat::Unknown);
+        // A character split by the sample boundary is not binary.
+        assert_eq!(detect_format(b"x,\xC3").format, FileFormat::Csv);
+    }
+
+    #[test]
+    fn test_unsupported_format_error() {
+        let xls = FormatInfo::of(FileFormat::Xls);
+        assert!(unsupported_format_error(&xls)
+            .unwrap()
+            .to_string()
+            .contains(".xls"));
+        assert!(unsupported_format_error(&FormatInfo::of(FileFormat::Xlsm)).is_none());
+    }
+}
@@ -0,0 +1,203 @@
+//! Range statistics without writing a formula.
+//!
+//! [`Worksheet::compute`] runs SUM, AVERAGE, MIN, MAX, COUNT, COUNTA or
+//! COUNTIF over a range, so a generated total can be checked in place. Only
+//! populated cells are visited, which keeps a whole-column range over sparse
+//! data cheap. Values follow Excel's rules for references: text and booleans
+//! are skipped by the numeric functions, and an error cell makes the result
+//! that error. Formula cells count with their cached result; one that was
+//! never calculated counts as blank.
+
+use crate::cell::CellValue;
+use crate::error::{Result, RustypyxlError};
+use crate::formula::{criteria_matches, FormulaValue};
+use crate::utils::{parse_coordinate, parse_range};
+use crate::worksheet::{CellData, Worksheet};
+
+impl Worksheet {
+    /// Apply `function` (SUM, AVERAGE, MIN, MAX, COUNT, COUNTA or COUNTIF,
+    /// in any case) to `range`, e.g. `compute("SUM", "B2:B1000", None)`.
+    /// COUNTIF takes its criterion (">10", "apples", "<>0") in
+    /// `criteria`. Results are what Excel would show, errors included
+    /// (AVERAGE of no numbers is `#DIV/0!`).
+    pub fn compute(
+        &self,
+        function: &str,
+        range: &str,
+        criteria: Option<&str>,
+    ) -> Result<FormulaValue> {
+        let range = range.replace('$', "");
+        let ((r1, c1), (r2, c2)) = if range.contains(':') {
+            parse_range(&range)?
+        } else {
+            let cell = parse_coordinate(&range)?;
+            (cell, cell)
+        };
+        let (min_row, max_row) = (r1.min(r2), r1.max(r2));
+        let (min_col, max_col) = (c1.min(c2), c1.max(c2));
+
+        // The populated cells in range, in row-major order so the first
+        // error found is the one Excel would report.
+        let mut cells: Vec<_> = self
+            .all_cells()
+            .filter(|((row, col), _)| {
+                (min_row..=max_row).contains(row) && (min_col..=max_col).contains(col)
+            })
+            .collect();
+        cells.sort_unstable_by_key(|(pos, _)| *pos);
+        let values: Vec<FormulaValue> = cells.iter().map(|(_, cell)| cell_value(cell)).collect();
+
+        let function = function.to_ascii_uppercase();
+        if function == "COUNTIF" {
+            let Some(criteria) = criteria else {
+                return Err(RustypyxlError::custom("COUNTIF needs a criterion"));
+            };
+            let mut count = values
+                .iter()
+                .filter(|v| criteria_matches(v, criteria))
+                .count() as u64;
+            // Cells with no entry are blanks, and some criteria match blanks.
+            if criteria_matches(&FormulaValue::Empty, criteria) {
+                let area = (max_row - min_row + 1) as u64 * (max_col - min_col + 1) as u64;
+                count += area - values.len() as u64;
+            }
+            return Ok(FormulaValue::Number(count as f64));
+        }
+        if function == "COUNTA" {
+            let count = values.iter().filter(|v| **v != FormulaValue::Empty).count();
+            return Ok(FormulaValue::Number(count as f64));
+        }
+        if function == "COUNT" {
+            let count = values
+                .iter()
+                .filter(|v| matches!(v, FormulaValue::Number(_)))
+                .count();
+            return Ok(FormulaValue::Number(count as f64));
+        }
+
+        let mut numbers = Vec::new();
+        for value in values {
+            match value {
+                FormulaValue::Number(n) => numbers.push(n),
+                FormulaValue::Error(_) => return Ok(value),
+                _ => {}
+            }
+        }
+        Ok(match function.as_str() {
+            "SUM" => FormulaValue::Number(numbers.iter().sum()),
+            "AVERAGE" if numbers.is_empty() => FormulaValue::Error("#DIV/0!".to_string()),
+            "AVERAGE" => FormulaValue::Number(numbers.iter().sum::<f64>() / numbers.len() as f64),
+            "MIN" if numbers.is_empty() => FormulaValue::Number(0.0),
+            "MIN" => FormulaValue::Number(numbers.iter().copied().fold(f64::INFINITY, f64::min)),
+            "MAX" if numbers.is_empty() => FormulaValue::Number(0.0),
+            "MAX" => FormulaValue::Number(numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+            _ => {
+                return Err(RustypyxlError::custom(format!(
+                    "unsupported function {function}: expected SUM, AVERAGE, MIN, MAX, COUNT, COUNTA or COUNTIF"
+                )))
+            }
+        })
+    }
+}
+
+/// A cell as a reference sees it: a formula by its cached result, an error
+/// cell as its error.
+fn cell_value(cell: &CellData) -> FormulaValue {
+    match (&cell.value, cell.data_type) {
+        (CellValue::Formula(_), data_type) => match (&cell.cached_formula_value, data_type) {
+            (None, _) => FormulaValue::Empty,
+            (Some(cached), Some("e")) => FormulaValue::Error(cached.clone()),
+            (Some(cached), Some("b")) => FormulaValue::Bool(cached == "1"),
+            (Some(cached), Some("str")) => FormulaValue::Text(cached.clone()),
+            (Some(cached), _) => cached
+                .parse()
+                .map(FormulaValue::Number)
+                .unwrap_or_else(|_| FormulaValue::Text(cached.clone())),
+        },
+        (CellValue::String(s), Some("e")) => FormulaValue::Error(s.to_string()),
+        (value, _) => crate::workbook::cell_value_to_formula(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet() -> Worksheet {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_value(1, 2, "Amount");
+        ws.set_cell_value(2, 2, 10.0);
+        ws.set_cell_value(3, 2, 2.5);
+        ws.set_cell_value(4, 2, true);
+        ws.set_cell_value(5, 2, "n/a");
+        ws.set_cell_value(6, 2, -4.0);
+        ws
+    }
+
+    fn number(ws: &Worksheet, function: &str, range: &str) -> f64 {
+        match ws.compute(function, range, None).unwrap() {
+            FormulaValue::Number(n) => n,
+            other => panic!("{function}: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_aggregates_skip_text_and_booleans() {
+        let ws = sheet();
+        assert_eq!(number(&ws, "SUM", "B2:B1000"), 8.5);
+        assert_eq!(number(&ws, "sum", "$B$1:$B$3"), 12.5);
+        assert_eq!(number(&ws, "AVERAGE", "B2:B1000"), 8.5 / 3.0);
+        assert_eq!(number(&ws, "MIN", "B2:B1000"), -4.0);
+        assert_eq!(number(&ws, "MAX", "B2:B1000"), 10.0);
+        assert_eq!(number(&ws, "COUNT", "B1:B1000"), 3.0);
+        assert_eq!(number(&ws, "COUNTA", "B1:B1000"), 6.0);
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        let ws = sheet();
+        assert_eq!(number(&ws, "SUM", "D1:D10"), 0.0);
+        assert_eq!(number(&ws, "MAX", "D1:D10"), 0.0);
+        assert_eq!(
+            ws.compute("AVERAGE", "D1:D10", None).unwrap(),
+            FormulaValue::Error("#DIV/0!".to_string())
+        );
+    }
+
+    #[test]
+    fn test_countif() {
+        let ws = sheet();
+        let count = |criteria| ws.compute("COUNTIF", "B1:B10", Some(criteria)).unwrap();
+        assert_eq!(count(">0"), FormulaValue::Number(2.0));
+        assert_eq!(count("N/A"), FormulaValue::Number(1.0));
+        assert_eq!(count("<>10"), FormulaValue::Number(9.0));
+        // Four of the ten cells have never been written.
+        assert_eq!(count(""), FormulaValue::Number(4.0));
+        assert!(ws.compute("COUNTIF", "B1:B10", None).is_err());
+    }
+
+    #[test]
+    fn test_formula_results_and_errors() {
+        let mut ws = sheet();
+        let cell = ws.get_or_create_cell_mut(7, 2);
+        cell.value = CellValue::Formula("SUM(B2:B6)".to_string());
+        cell.cached_formula_value = Some("8.5".to_string());
+        assert_eq!(number(&ws, "SUM", "B2:B7"), 17.0);
+
+        let cell = ws.get_or_create_cell_mut(8, 2);
+        cell.value = CellValue::Formula("1/0".to_string());
+        cell.cached_formula_value = Some("#DIV/0!".to_string());
+        cell.data_type = Some("e");
+        assert_eq!(
+            ws.compute("SUM", "B2:B8", None).unwrap(),
+            FormulaValue::Error("#DIV/0!".to_string())
+        );
+        assert_eq!(number(&ws, "COUNTA", "B8"), 1.0);
+    }
+
+    #[test]
+    fn test_unsupported_function() {
+        assert!(sheet().compute("MEDIAN", "B1:B5", None).is_err());
+        assert!(sheet().compute("SUM", "not a range", None).is_err());
+    }
+}
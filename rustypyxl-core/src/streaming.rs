@@ -4,25 +4,50 @@
 //! without holding them in memory, similar to openpyxl's write_only mode.
 
 use crate::cell::CellValue;
+use crate::docprops::DocumentProperties;
 use crate::error::{Result, RustypyxlError};
-use crate::writer::{escape_xml, format_cell_value};
+use crate::workbook::{NonFiniteNumbers, NumberPrecision};
+use crate::writer::{escape_xml, format_cell_value_with, CALC_ID};
 
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use zip::write::{ExtendedFileOptions, FileOptions};
 use zip::{CompressionMethod, ZipWriter};
 
+/// Style indices of the fixed styles part for dates (`yyyy-mm-dd`) and
+/// date-times (`yyyy-mm-dd hh:mm:ss`), used by bulk imports.
+#[cfg(feature = "parquet")]
+pub(crate) const DATE_STYLE: u32 = 1;
+#[cfg(feature = "parquet")]
+pub(crate) const DATETIME_STYLE: u32 = 2;
+
 /// A streaming sheet that writes rows directly to the ZIP file.
 pub struct StreamingSheet {
     #[allow(dead_code)]
     name: String,
-    current_row: u32,
+    pub(crate) current_row: u32,
     max_col: u32,
     /// Position of this sheet in the workbook; append_row rejects handles
     /// whose sheet is no longer the open one.
     index: usize,
 }
 
+/// Progress of a streaming write, taken when buffered rows were flushed to
+/// disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// 1 for the first checkpoint, counting up.
+    pub sequence: u64,
+    /// The sheet open at the checkpoint, if any.
+    pub sheet: Option<String>,
+    /// Rows appended to that sheet so far.
+    pub sheet_rows: u32,
+    /// Rows appended across all sheets so far.
+    pub rows_written: u64,
+    /// Bytes of the file on disk, all of which are durable.
+    pub bytes_emitted: u64,
+}
+
 /// A write-only workbook that streams data directly to disk.
 ///
 /// This is much more memory efficient than the standard Workbook for large files,
@@ -58,16 +83,33 @@ pub struct StreamingSheet {
 /// ```
 pub struct StreamingWorkbook {
     zip: ZipWriter<BufWriter<File>>,
+    /// A second handle to the output file, for syncing and measuring it.
+    file: File,
     options: FileOptions<'static, ExtendedFileOptions>,
     sheets: Vec<String>,
     current_sheet_idx: Option<usize>,
     sheet_xml_started: bool,
+    pub(crate) non_finite_numbers: NonFiniteNumbers,
+    pub(crate) number_precision: NumberPrecision,
+    pub(crate) properties: DocumentProperties,
+    /// Reused row XML buffer for `append_row_with`.
+    row_buf: String,
+    /// Rows appended to the open sheet, and to the sheets finished before it.
+    sheet_rows: u32,
+    finished_rows: u64,
+    /// Take a checkpoint every this many rows.
+    checkpoint_interval: Option<u64>,
+    rows_since_checkpoint: u64,
+    last_checkpoint: Option<Checkpoint>,
+    /// A formula was written, so Excel must calculate on open.
+    wrote_formulas: bool,
 }
 
 impl StreamingWorkbook {
     /// Create a new streaming workbook that writes to the given path.
     pub fn new(path: &str) -> Result<Self> {
         let file = File::create(path)?;
+        let handle = file.try_clone()?;
         let writer = BufWriter::with_capacity(1024 * 1024, file); // 1MB buffer
         let zip = ZipWriter::new(writer);
 
@@ -77,13 +119,105 @@ impl StreamingWorkbook {
 
         Ok(StreamingWorkbook {
             zip,
+            file: handle,
             options,
             sheets: Vec::new(),
             current_sheet_idx: None,
             sheet_xml_started: false,
+            non_finite_numbers: NonFiniteNumbers::default(),
+            number_precision: NumberPrecision::default(),
+            properties: DocumentProperties::default(),
+            row_buf: String::new(),
+            sheet_rows: 0,
+            finished_rows: 0,
+            checkpoint_interval: None,
+            rows_since_checkpoint: 0,
+            last_checkpoint: None,
+            wrote_formulas: false,
         })
     }
 
+    /// Push every row appended so far through the compressor and onto disk,
+    /// and return a checkpoint of the progress. After it returns the rows
+    /// survive a crash of the process, although the file only opens once
+    /// the workbook is closed. Flushing ends a compressed block early, so
+    /// flushing very often makes the file somewhat larger.
+    pub fn flush(&mut self) -> Result<Checkpoint> {
+        self.zip.flush()?;
+        self.file.sync_data()?;
+        let checkpoint = Checkpoint {
+            sequence: self.last_checkpoint.as_ref().map_or(0, |c| c.sequence) + 1,
+            sheet: self.current_sheet_idx.map(|idx| self.sheets[idx].clone()),
+            sheet_rows: self.sheet_rows,
+            rows_written: self.rows_written(),
+            bytes_emitted: self.file.metadata()?.len(),
+        };
+        self.rows_since_checkpoint = 0;
+        self.last_checkpoint = Some(checkpoint.clone());
+        Ok(checkpoint)
+    }
+
+    /// Flush and take a checkpoint automatically every `rows` appended
+    /// rows, or never with `None` (the default). The latest one is
+    /// available from [`last_checkpoint`](Self::last_checkpoint) for a
+    /// monitor to report.
+    pub fn set_checkpoint_interval(&mut self, rows: Option<u64>) {
+        self.checkpoint_interval = rows.filter(|&n| n > 0);
+    }
+
+    /// The most recent checkpoint, from [`flush`](Self::flush) or the
+    /// checkpoint interval.
+    pub fn last_checkpoint(&self) -> Option<&Checkpoint> {
+        self.last_checkpoint.as_ref()
+    }
+
+    /// Rows appended across all sheets so far, flushed or not.
+    pub fn rows_written(&self) -> u64 {
+        self.finished_rows + u64::from(self.sheet_rows)
+    }
+
+    /// Count a row appended to the open sheet, flushing when the checkpoint
+    /// interval is reached.
+    fn row_appended(&mut self) -> Result<()> {
+        self.sheet_rows += 1;
+        self.rows_since_checkpoint += 1;
+        if self
+            .checkpoint_interval
+            .is_some_and(|every| self.rows_since_checkpoint >= every)
+        {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Set how NaN and infinite numbers are written. With
+    /// [`NonFiniteNumbers::Error`], `append_row` rejects a row holding one
+    /// before any of it is written.
+    pub fn set_non_finite_numbers(&mut self, policy: NonFiniteNumbers) {
+        self.non_finite_numbers = policy;
+    }
+
+    /// Set how numbers are written, for the rows appended from now on.
+    pub fn set_number_precision(&mut self, precision: NumberPrecision) {
+        self.number_precision = precision;
+    }
+
+    /// Set the author, title and timestamps written to docProps when the
+    /// workbook is closed. A fixed modification time also dates the archive
+    /// entries written from now on, so set it before the first sheet for a
+    /// reproducible file.
+    pub fn set_properties(&mut self, properties: DocumentProperties) {
+        if let Some(at) = properties.zip_time() {
+            self.options = self.options.clone().last_modified_time(at);
+        }
+        self.properties = properties;
+    }
+
+    /// The document properties written on close.
+    pub fn properties(&self) -> &DocumentProperties {
+        &self.properties
+    }
+
     /// Create a new sheet, finalizing the previously open sheet (if any).
     /// Returns a StreamingSheet handle for writing rows; the old handle
     /// becomes unusable once a new sheet is opened.
@@ -109,6 +243,7 @@ impl StreamingWorkbook {
         self.sheets.push(name.to_string());
         let idx = self.sheets.len() - 1;
         self.current_sheet_idx = Some(idx);
+        self.sheet_rows = 0;
 
         // Start the sheet XML file
         let path = format!("xl/worksheets/sheet{}.xml", idx + 1);
@@ -131,8 +266,9 @@ impl StreamingWorkbook {
         })
     }
 
-    /// Append a row to the given sheet, which must be the currently open one.
-    pub fn append_row(&mut self, sheet: &mut StreamingSheet, values: Vec<CellValue>) -> Result<()> {
+    /// Check that `sheet` is the open sheet and can take one more row of
+    /// `width` cells.
+    fn check_row(&self, sheet: &StreamingSheet, width: usize) -> Result<()> {
         if self.current_sheet_idx != Some(sheet.index) {
             return Err(RustypyxlError::custom(
                 "This sheet is no longer the open sheet (a newer sheet was created or it was closed)",
@@ -143,40 +279,100 @@ impl StreamingWorkbook {
                 "Exceeded Excel's row limit of 1,048,576",
             ));
         }
-        if values.len() > 16_384 {
+        if width > 16_384 {
             return Err(RustypyxlError::custom(
                 "Row exceeds Excel's column limit of 16,384",
             ));
         }
+        Ok(())
+    }
 
-        sheet.current_row += 1;
-        let row_num = sheet.current_row;
-
-        if values.is_empty() {
-            return Ok(());
+    /// Append a row to the given sheet, which must be the currently open one.
+    pub fn append_row(&mut self, sheet: &mut StreamingSheet, values: Vec<CellValue>) -> Result<()> {
+        self.check_row(sheet, values.len())?;
+
+        if self.non_finite_numbers == NonFiniteNumbers::Error {
+            if let Some(col) = values
+                .iter()
+                .position(|v| matches!(v, CellValue::Number(n) if !n.is_finite()))
+            {
+                return Err(RustypyxlError::custom(format!(
+                    "{} holds a NaN or infinite number, which cannot be written",
+                    crate::utils::coordinate_from_row_col(sheet.current_row + 1, col as u32 + 1)
+                )));
+            }
         }
 
-        // Track max column
-        if values.len() as u32 > sheet.max_col {
-            sheet.max_col = values.len() as u32;
+        if values.is_empty() {
+            sheet.current_row += 1;
+            return self.row_appended();
         }
 
-        // Build row XML
-        let mut row_xml = format!("<row r=\"{}\">", row_num);
+        self.wrote_formulas |= values.iter().any(|v| matches!(v, CellValue::Formula(_)));
+        let skip_non_finite = self.non_finite_numbers == NonFiniteNumbers::Empty;
+        let precision = self.number_precision;
+        self.append_row_with(sheet, values.len() as u32, |row_xml, row_num| {
+            // One scratch buffer for the whole row rather than a String per cell
+            let mut coord = String::with_capacity(12);
+            for (col_idx, value) in values.iter().enumerate() {
+                if skip_non_finite && matches!(value, CellValue::Number(n) if !n.is_finite()) {
+                    continue;
+                }
+                let col = (col_idx + 1) as u32;
+                coord.clear();
+                crate::utils::push_coordinate(&mut coord, row_num, col);
+                format_cell_value_with(row_xml, &coord, value, precision);
+            }
+            Ok(())
+        })
+    }
 
-        // One scratch buffer for the whole row rather than a String per cell
-        let mut coord = String::with_capacity(12);
-        for (col_idx, value) in values.iter().enumerate() {
-            let col = (col_idx + 1) as u32;
-            coord.clear();
-            crate::utils::push_coordinate(&mut coord, row_num, col);
-            format_cell_value(&mut row_xml, &coord, value);
+    /// Append every row `rows` yields to the given sheet, pulling them one
+    /// at a time so the source never has to be held in memory. Returns the
+    /// number of rows appended; on an error, the rows before the failing
+    /// one have been written.
+    pub fn append_rows_from<I>(&mut self, sheet: &mut StreamingSheet, rows: I) -> Result<u64>
+    where
+        I: IntoIterator<Item = Vec<CellValue>>,
+    {
+        let mut appended = 0;
+        for row in rows {
+            self.append_row(sheet, row)?;
+            appended += 1;
         }
+        Ok(appended)
+    }
 
-        row_xml.push_str("</row>\n");
-        self.zip.write_all(row_xml.as_bytes())?;
-
-        Ok(())
+    /// Append a row of `width` cells whose XML `write_cells` renders into
+    /// the row buffer, given the row number, for bulk sources that don't
+    /// build a `CellValue` per cell. Nothing is written if it fails.
+    pub(crate) fn append_row_with<F>(
+        &mut self,
+        sheet: &mut StreamingSheet,
+        width: u32,
+        write_cells: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&mut String, u32) -> Result<()>,
+    {
+        self.check_row(sheet, width as usize)?;
+        let row_num = sheet.current_row + 1;
+
+        let mut buf = std::mem::take(&mut self.row_buf);
+        buf.clear();
+        buf.push_str("<row r=\"");
+        buf.push_str(itoa::Buffer::new().format(row_num));
+        buf.push_str("\">");
+        let written = write_cells(&mut buf, row_num).and_then(|()| {
+            buf.push_str("</row>\n");
+            Ok(self.zip.write_all(buf.as_bytes())?)
+        });
+        self.row_buf = buf;
+        written?;
+
+        sheet.current_row = row_num;
+        sheet.max_col = sheet.max_col.max(width);
+        self.row_appended()
     }
 
     /// Finalize the currently open sheet's XML part.
@@ -194,6 +390,7 @@ impl StreamingWorkbook {
 
         self.sheet_xml_started = false;
         self.current_sheet_idx = None;
+        self.finished_rows += u64::from(std::mem::take(&mut self.sheet_rows));
 
         Ok(())
     }
@@ -282,19 +479,12 @@ impl StreamingWorkbook {
     fn write_doc_props(&mut self) -> Result<()> {
         self.zip
             .start_file("docProps/core.xml", self.options.clone())?;
-        self.zip.write_all(br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/">
-<dc:creator>rustypyxl</dc:creator>
-</cp:coreProperties>"#)?;
+        self.zip
+            .write_all(self.properties.core_xml(crate::docprops::now()).as_bytes())?;
 
         self.zip
             .start_file("docProps/app.xml", self.options.clone())?;
-        self.zip.write_all(
-            br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties">
-<Application>rustypyxl</Application>
-</Properties>"#,
-        )?;
+        self.zip.write_all(self.properties.app_xml().as_bytes())?;
         Ok(())
     }
 
@@ -319,7 +509,12 @@ impl StreamingWorkbook {
             ));
         }
 
-        content.push_str("</sheets>\n</workbook>");
+        content.push_str("</sheets>\n<calcPr calcId=\"");
+        content.push_str(CALC_ID);
+        if self.wrote_formulas {
+            content.push_str("\" fullCalcOnLoad=\"1");
+        }
+        content.push_str("\"/>\n</workbook>");
         self.zip.write_all(content.as_bytes())?;
         Ok(())
     }
@@ -355,11 +550,12 @@ impl StreamingWorkbook {
         self.zip.start_file("xl/styles.xml", self.options.clone())?;
         self.zip.write_all(br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+<numFmts count="2"><numFmt numFmtId="164" formatCode="yyyy-mm-dd"/><numFmt numFmtId="165" formatCode="yyyy-mm-dd hh:mm:ss"/></numFmts>
 <fonts count="1"><font><sz val="11"/><name val="Calibri"/></font></fonts>
 <fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills>
 <borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders>
 <cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs>
-<cellXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/></cellXfs>
+<cellXfs count="3"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="164" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/><xf numFmtId="165" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/></cellXfs>
 <cellStyles count="1"><cellStyle name="Normal" xfId="0" builtinId="0"/></cellStyles>
 </styleSheet>"#)?;
         Ok(())
@@ -372,6 +568,60 @@ mod tests {
     use std::sync::Arc;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_streaming_non_finite_numbers() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_str().unwrap();
+        let mut wb = StreamingWorkbook::new(path).unwrap();
+        let mut sheet = wb.create_sheet("S").unwrap();
+        wb.append_row(&mut sheet, vec![CellValue::Number(f64::NAN)])
+            .unwrap();
+
+        wb.set_non_finite_numbers(NonFiniteNumbers::Error);
+        let row = vec![CellValue::Number(1.0), CellValue::Number(f64::INFINITY)];
+        let err = wb.append_row(&mut sheet, row).unwrap_err();
+        assert!(err.to_string().contains("B2"), "{err}");
+
+        wb.set_non_finite_numbers(NonFiniteNumbers::Empty);
+        let row = vec![CellValue::Number(f64::NEG_INFINITY), CellValue::Number(2.0)];
+        wb.append_row(&mut sheet, row).unwrap();
+        wb.close(sheet).unwrap();
+
+        let loaded = crate::Workbook::load(path).unwrap();
+        let ws = loaded.get_sheet_by_name("S").unwrap();
+        assert!(matches!(
+            &ws.get_cell(1, 1).unwrap().value,
+            CellValue::String(v) if v.as_ref() == "#NUM!"
+        ));
+        // The rejected row was never written, so the next one is row 2,
+        // without its infinite number.
+        assert!(ws.get_cell(2, 1).is_none());
+        assert_eq!(ws.get_cell_value(2, 2), Some(&CellValue::Number(2.0)));
+    }
+
+    #[test]
+    fn test_streaming_document_properties() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_str().unwrap();
+        let created = crate::docprops::parse_timestamp("2023-03-04T05:06:07Z").unwrap();
+        let mut wb = StreamingWorkbook::new(path).unwrap();
+        wb.set_properties(
+            DocumentProperties::new()
+                .with_creator("ETL")
+                .with_created(created),
+        );
+        let mut props = wb.properties().clone();
+        props.title = Some("Export".to_string());
+        wb.set_properties(props);
+        let sheet = wb.create_sheet("S").unwrap();
+        wb.close(sheet).unwrap();
+
+        let loaded = crate::Workbook::load(path).unwrap();
+        assert_eq!(loaded.properties.creator.as_deref(), Some("ETL"));
+        assert_eq!(loaded.properties.title.as_deref(), Some("Export"));
+        assert_eq!(loaded.properties.created, Some(created));
+    }
+
     #[test]
     fn test_streaming_multiple_sheets() {
         let temp = NamedTempFile::new().unwrap();
@@ -407,6 +657,65 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_streaming_append_rows_from_iterator() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_str().unwrap();
+
+        let mut wb = StreamingWorkbook::new(path).unwrap();
+        let mut sheet = wb.create_sheet("Data").unwrap();
+        let rows = (1..=500).map(|i| vec![CellValue::Number(i as f64)]);
+        assert_eq!(wb.append_rows_from(&mut sheet, rows).unwrap(), 500);
+
+        // A failing row stops the pull; the rows before it stay written.
+        wb.set_non_finite_numbers(NonFiniteNumbers::Error);
+        let rows = [1.0, f64::NAN, 3.0].map(|n| vec![CellValue::Number(n)]);
+        assert!(wb.append_rows_from(&mut sheet, rows).is_err());
+        assert_eq!(sheet.current_row, 501);
+        wb.close(sheet).unwrap();
+
+        let loaded = crate::Workbook::load(path).unwrap();
+        let ws = loaded.get_sheet_by_name("Data").unwrap();
+        assert_eq!(ws.get_cell_value(500, 1), Some(&CellValue::Number(500.0)));
+        assert_eq!(ws.get_cell_value(501, 1), Some(&CellValue::Number(1.0)));
+        assert!(ws.get_cell(502, 1).is_none());
+    }
+
+    #[test]
+    fn test_streaming_flush_and_checkpoints() {
+        let temp = NamedTempFile::new().unwrap();
+        let path = temp.path().to_str().unwrap();
+
+        let mut wb = StreamingWorkbook::new(path).unwrap();
+        wb.set_checkpoint_interval(Some(100));
+        let mut first = wb.create_sheet("First").unwrap();
+        for i in 0..250 {
+            wb.append_row(&mut first, vec![CellValue::Number(i as f64)])
+                .unwrap();
+        }
+        let auto = wb.last_checkpoint().unwrap().clone();
+        assert_eq!(auto.sequence, 2);
+        assert_eq!(auto.rows_written, 200);
+        assert_eq!(auto.sheet.as_deref(), Some("First"));
+
+        let mut second = wb.create_sheet("Second").unwrap();
+        wb.append_row(&mut second, vec![CellValue::Number(1.0)])
+            .unwrap();
+        let manual = wb.flush().unwrap();
+        assert_eq!(manual.sequence, 3);
+        assert_eq!(manual.sheet.as_deref(), Some("Second"));
+        assert_eq!(manual.sheet_rows, 1);
+        assert_eq!(manual.rows_written, 251);
+        assert!(manual.bytes_emitted > auto.bytes_emitted);
+        assert_eq!(manual.bytes_emitted, std::fs::metadata(path).unwrap().len());
+        wb.close(second).unwrap();
+
+        // Sync-flushed deflate streams still read back as a whole.
+        let loaded = crate::Workbook::load(path).unwrap();
+        let ws = loaded.get_sheet_by_name("First").unwrap();
+        assert_eq!(ws.get_cell_value(250, 1), Some(&CellValue::Number(249.0)));
+    }
+
     #[test]
     fn test_streaming_finish_without_sheets_creates_default() {
         let temp = NamedTempFile::new().unwrap();
@@ -0,0 +1,79 @@
+//! Per-sheet counts of string values for [`SharedStrings::Incremental`].
+//!
+//! A sheet tracking its strings holds one of these, and the worksheet's
+//! value setters adjust it as cells change, so saving can build the shared
+//! string table from the distinct values instead of visiting every cell.
+//!
+//! [`SharedStrings::Incremental`]: crate::workbook::SharedStrings::Incremental
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use crate::cell::{CellValue, InternedString};
+use crate::worksheet::CellData;
+
+/// How many cells hold each plain string. Rich-text cells are not counted:
+/// they are written inline and never reference the shared table.
+#[derive(Clone, Debug, Default)]
+pub struct StringCounts {
+    counts: HashMap<InternedString, usize>,
+}
+
+impl StringCounts {
+    /// Count the strings in `cells`.
+    pub(crate) fn from_cells<C: Borrow<CellData>>(cells: impl Iterator<Item = C>) -> Self {
+        let mut counts = StringCounts::default();
+        for cell in cells {
+            counts.add(cell.borrow());
+        }
+        counts
+    }
+
+    /// Count `cell`'s value, if it is a plain string.
+    pub(crate) fn add(&mut self, cell: &CellData) {
+        if let Some(s) = plain_string(cell) {
+            self.add_string(s);
+        }
+    }
+
+    /// Count one more cell holding `s`.
+    pub(crate) fn add_string(&mut self, s: &InternedString) {
+        *self.counts.entry(s.clone()).or_insert(0) += 1;
+    }
+
+    /// Count one less cell holding `s`.
+    pub(crate) fn remove_string(&mut self, s: &str) {
+        if let Some(count) = self.counts.get_mut(s) {
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(s);
+            }
+        }
+    }
+
+    /// Number of cells holding `value`.
+    pub fn get(&self, value: &str) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Each distinct string with the number of cells holding it.
+    pub fn iter(&self) -> impl Iterator<Item = (&InternedString, usize)> {
+        self.counts.iter().map(|(s, &n)| (s, n))
+    }
+}
+
+pub(crate) fn plain_string(cell: &CellData) -> Option<&InternedString> {
+    match &cell.value {
+        CellValue::String(s) if cell.rich_text.is_none() => Some(s),
+        _ => None,
+    }
+}
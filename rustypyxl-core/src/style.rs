@@ -87,6 +87,9 @@ impl<S: AsRef<str>> From<S> for Color {
     }
 }
 
+/// The blue of Excel's built-in Hyperlink style in the default Office theme.
+pub const HYPERLINK_COLOR: &str = "FF0563C1";
+
 /// Font properties for cell styling.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Font {
@@ -157,6 +160,14 @@ impl Font {
         self
     }
 
+    /// This font with Excel's Hyperlink look: blue and single-underlined.
+    /// Name, size and the other properties are kept.
+    pub fn as_hyperlink(mut self) -> Self {
+        self.underline = Some("single".to_string());
+        self.color = Some(Color::rgb(HYPERLINK_COLOR));
+        self
+    }
+
     /// Set vertical alignment (superscript/subscript).
     pub fn with_vert_align<S: Into<String>>(mut self, vert_align: S) -> Self {
         self.vert_align = Some(vert_align.into());
@@ -171,7 +182,10 @@ pub struct Alignment {
     pub horizontal: Option<String>,
     /// Vertical alignment: top, center, bottom, justify, distributed.
     pub vertical: Option<String>,
-    /// Wrap text within cell.
+    /// Wrap text within cell. Line breaks in a value are kept either way
+    /// (the writer marks such strings `xml:space="preserve"`), but Excel only
+    /// shows them as separate lines when wrapping is on; otherwise the cell
+    /// renders on one line.
     pub wrap_text: bool,
     /// Text rotation angle (-90 to 90).
     pub text_rotation: Option<i32>,
@@ -491,6 +505,9 @@ pub struct CellStyle {
     pub number_format: Option<crate::cell::InternedString>,
     /// Protection properties.
     pub protection: Option<Protection>,
+    /// The named cell style (see [`NamedStyle`](crate::NamedStyle)) the
+    /// cell is linked to; None for "Normal".
+    pub named_style: Option<crate::cell::InternedString>,
 }
 
 impl CellStyle {
@@ -540,6 +557,41 @@ impl CellStyle {
         self.protection = Some(protection);
         self
     }
+
+    /// Layer `overlay` over this style. Every component `overlay` sets
+    /// replaces this style's; the ones it leaves unset (protection, number
+    /// format, ...) are kept. A pattern fill and a gradient fill share the
+    /// cell's one fill slot, so setting either clears the other.
+    pub fn merge(&self, overlay: &CellStyle) -> CellStyle {
+        let mut merged = self.clone();
+        if overlay.font.is_some() {
+            merged.font = overlay.font.clone();
+        }
+        if overlay.alignment.is_some() {
+            merged.alignment = overlay.alignment.clone();
+        }
+        if overlay.border.is_some() {
+            merged.border = overlay.border.clone();
+        }
+        if overlay.fill.is_some() {
+            merged.fill = overlay.fill.clone();
+            merged.gradient_fill = None;
+        }
+        if overlay.gradient_fill.is_some() {
+            merged.gradient_fill = overlay.gradient_fill.clone();
+            merged.fill = None;
+        }
+        if overlay.number_format.is_some() {
+            merged.number_format = overlay.number_format.clone();
+        }
+        if overlay.protection.is_some() {
+            merged.protection = overlay.protection.clone();
+        }
+        if overlay.named_style.is_some() {
+            merged.named_style = overlay.named_style.clone();
+        }
+        merged
+    }
 }
 
 /// A cell format entry (cellXf) that combines references to fonts, fills, borders, and number formats.
@@ -569,6 +621,9 @@ pub struct CellXf {
     pub apply_alignment: bool,
     /// Whether protection is applied.
     pub apply_protection: bool,
+    /// Index of the named style this format is based on, into
+    /// [`StyleRegistry::named_styles`] (the file's cellStyleXfs).
+    pub xf_id: usize,
 }
 
 /// Registry of all styles in a workbook.
@@ -590,6 +645,14 @@ pub struct StyleRegistry {
     /// dxfId. Only populated on load; save regenerates the list from the
     /// conditional-formatting rules themselves.
     pub dxfs: Vec<crate::conditional::ConditionalFormat>,
+    /// Named fonts, fills, borders, number formats and styles, exchanged as
+    /// JSON with [`StyleRegistry::to_json`] and [`StyleRegistry::from_json`].
+    /// Not written to the file; a palette style reaches cells through
+    /// [`Workbook::apply_named_style`](crate::Workbook::apply_named_style).
+    pub palette: crate::style_palette::StylePalette,
+    /// The named cell styles written to `<cellStyles>`; a cell format's
+    /// `xf_id` indexes this list. "Normal" is always first.
+    pub named_styles: Vec<crate::named_styles::NamedStyle>,
 }
 
 impl StyleRegistry {
@@ -618,6 +681,23 @@ impl StyleRegistry {
         registry.cell_xfs.push(CellXf::default());
 
         registry
+            .named_styles
+            .push(crate::named_styles::NamedStyle::normal());
+
+        registry
+    }
+
+    /// The named-style palette as a JSON document.
+    pub fn to_json(&self) -> String {
+        self.palette.to_json()
+    }
+
+    /// Load a palette JSON document, adding its entries to the palette and
+    /// replacing any with the same name.
+    pub fn from_json(&mut self, json: &str) -> crate::error::Result<()> {
+        let palette = crate::style_palette::StylePalette::from_json(json)?;
+        self.palette.extend(palette);
+        Ok(())
     }
 
     /// Get or create a font index.
@@ -750,8 +830,9 @@ impl StyleRegistry {
         }
     }
 
-    /// Get or create a cell format (xf) index for a CellStyle.
-    pub fn get_or_add_cell_xf(&mut self, style: &CellStyle) -> usize {
+    /// The cell format for `style`, registering its font, fill, border and
+    /// number format.
+    pub(crate) fn build_xf(&mut self, style: &CellStyle) -> CellXf {
         let font_id = style
             .font
             .as_ref()
@@ -776,7 +857,7 @@ impl StyleRegistry {
             .map(|nf| self.get_or_add_num_fmt(nf))
             .unwrap_or(0);
 
-        let xf = CellXf {
+        CellXf {
             font_id,
             fill_id,
             border_id,
@@ -789,7 +870,17 @@ impl StyleRegistry {
             apply_number_format: style.number_format.is_some(),
             apply_alignment: style.alignment.is_some(),
             apply_protection: style.protection.is_some(),
-        };
+            xf_id: style
+                .named_style
+                .as_deref()
+                .and_then(|name| self.named_style_index(name))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Get or create a cell format (xf) index for a CellStyle.
+    pub fn get_or_add_cell_xf(&mut self, style: &CellStyle) -> usize {
+        let xf = self.build_xf(style);
 
         // Check if this exact xf already exists
         if let Some(idx) = self.cell_xfs.iter().position(|x| x == &xf) {
@@ -843,6 +934,13 @@ impl StyleRegistry {
             gradient_fill: None, // TODO: Add gradient fill support
             number_format,
             protection,
+            named_style: match xf.xf_id {
+                0 => None,
+                id => self
+                    .named_styles
+                    .get(id)
+                    .map(|ns| std::sync::Arc::from(ns.name.as_str())),
+            },
         })
     }
 
@@ -1108,6 +1206,25 @@ mod coverage_tests {
         assert_eq!(s.number_format.as_deref(), Some("0.00%"));
     }
 
+    #[test]
+    fn cell_style_merge_keeps_untouched_components() {
+        let base = CellStyle::new()
+            .with_font(Font::new().with_bold(true))
+            .with_number_format("0.00%")
+            .with_protection(Protection::unlocked())
+            .with_gradient_fill(GradientFill::linear("FFF", "000"));
+        let merged = base.merge(
+            &CellStyle::new()
+                .with_border(Border::all(BorderStyle::thin()))
+                .with_fill(Fill::solid("EEEEEE")),
+        );
+        assert!(merged.font.as_ref().unwrap().bold);
+        assert_eq!(merged.number_format.as_deref(), Some("0.00%"));
+        assert!(!merged.protection.as_ref().unwrap().locked);
+        assert!(merged.border.is_some() && merged.fill.is_some());
+        assert!(merged.gradient_fill.is_none());
+    }
+
     #[test]
     fn style_registry_dedup_and_num_fmt() {
         let mut reg = StyleRegistry::new();
@@ -0,0 +1,639 @@
+//! Named style palettes that can be kept in a versioned JSON document and
+//! loaded into any workbook.
+//!
+//! A palette names fonts, fills, borders and number formats once, and
+//! defines named styles that refer to them by name (or inline them):
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "fonts": {"heading": {"name": "Calibri", "size": 14, "bold": true, "color": "FF1F4E79"}},
+//!   "fills": {"band": {"pattern": "solid", "fg_color": "FFDDEBF7"}},
+//!   "borders": {"box": {"left": {"style": "thin"}, "right": {"style": "thin"}}},
+//!   "number_formats": {"money": "#,##0.00"},
+//!   "styles": {
+//!     "header": {"font": "heading", "fill": "band", "alignment": {"horizontal": "center"}},
+//!     "amount": {"number_format": "money", "protection": {"locked": false}}
+//!   }
+//! }
+//! ```
+//!
+//! Colors are an ARGB/RGB hex string, or an object with `theme`, `tint`,
+//! `indexed` and/or `rgb`. A style's `number_format` is looked up in
+//! `number_formats` first and otherwise taken as a literal format code.
+
+use crate::error::{Result, RustypyxlError};
+use crate::json::JsonValue;
+use crate::style::{Alignment, Border, BorderStyle, CellStyle, Color, Fill, Font, Protection};
+use std::collections::BTreeMap;
+
+/// The palette document version written by [`StylePalette::to_json`].
+pub const PALETTE_VERSION: u32 = 1;
+
+/// A style component given either by its palette name or inline.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteRef<T> {
+    Named(String),
+    Inline(T),
+}
+
+/// A named style: the components it sets, by reference into the palette or
+/// inline. Components left `None` are not applied.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PaletteStyle {
+    pub font: Option<PaletteRef<Font>>,
+    pub fill: Option<PaletteRef<Fill>>,
+    pub border: Option<PaletteRef<Border>>,
+    pub alignment: Option<Alignment>,
+    /// A `number_formats` name or a literal format code.
+    pub number_format: Option<String>,
+    pub protection: Option<Protection>,
+}
+
+/// A workbook's named fonts, fills, borders, number formats and styles.
+/// Entries are kept sorted by name so the JSON output is stable.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StylePalette {
+    pub fonts: BTreeMap<String, Font>,
+    pub fills: BTreeMap<String, Fill>,
+    pub borders: BTreeMap<String, Border>,
+    pub number_formats: BTreeMap<String, String>,
+    pub styles: BTreeMap<String, PaletteStyle>,
+}
+
+impl StylePalette {
+    /// Create an empty palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the palette defines nothing.
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+            && self.fills.is_empty()
+            && self.borders.is_empty()
+            && self.number_formats.is_empty()
+            && self.styles.is_empty()
+    }
+
+    /// Add or replace a named style.
+    pub fn add_style<S: Into<String>>(&mut self, name: S, style: PaletteStyle) {
+        self.styles.insert(name.into(), style);
+    }
+
+    /// Add every entry of `other`, replacing same-named entries.
+    pub fn extend(&mut self, other: StylePalette) {
+        self.fonts.extend(other.fonts);
+        self.fills.extend(other.fills);
+        self.borders.extend(other.borders);
+        self.number_formats.extend(other.number_formats);
+        self.styles.extend(other.styles);
+    }
+
+    /// The full cell style a named style stands for.
+    pub fn resolve(&self, name: &str) -> Result<CellStyle> {
+        let style = self
+            .styles
+            .get(name)
+            .ok_or_else(|| RustypyxlError::custom(format!("Named style not found: {name}")))?;
+        let mut cell_style = CellStyle::new();
+        cell_style.font = lookup(&self.fonts, &style.font, "font")?;
+        cell_style.fill = lookup(&self.fills, &style.fill, "fill")?;
+        cell_style.border = lookup(&self.borders, &style.border, "border")?;
+        cell_style.alignment = style.alignment.clone();
+        cell_style.protection = style.protection.clone();
+        if let Some(fmt) = &style.number_format {
+            let code = self.number_formats.get(fmt).unwrap_or(fmt);
+            cell_style = cell_style.with_number_format(code);
+        }
+        Ok(cell_style)
+    }
+
+    /// Serialize as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        fn section<T>(
+            entries: &BTreeMap<String, T>,
+            to_json: impl Fn(&T) -> JsonValue,
+        ) -> JsonValue {
+            JsonValue::Object(
+                entries
+                    .iter()
+                    .map(|(name, v)| (name.clone(), to_json(v)))
+                    .collect(),
+            )
+        }
+        JsonValue::Object(vec![
+            ("version".into(), JsonValue::Number(PALETTE_VERSION as f64)),
+            ("fonts".into(), section(&self.fonts, font_to_json)),
+            ("fills".into(), section(&self.fills, fill_to_json)),
+            ("borders".into(), section(&self.borders, border_to_json)),
+            (
+                "number_formats".into(),
+                section(&self.number_formats, |f| JsonValue::String(f.clone())),
+            ),
+            ("styles".into(), section(&self.styles, style_to_json)),
+        ])
+        .to_pretty_string()
+    }
+
+    /// Parse a palette document. Unknown keys are rejected so a typo does not
+    /// silently drop a setting, and every named reference must resolve.
+    pub fn from_json(text: &str) -> Result<Self> {
+        let doc = JsonValue::parse(text)?;
+        let entries = object(&doc, "palette")?;
+        let mut palette = StylePalette::new();
+        for (key, value) in entries {
+            match key.as_str() {
+                "version" => {
+                    let version = number(value, "version")?;
+                    if version > PALETTE_VERSION as f64 {
+                        return Err(invalid(format!(
+                            "palette version {version} is newer than supported version {PALETTE_VERSION}"
+                        )));
+                    }
+                }
+                "fonts" => palette.fonts = parse_section(value, "fonts", font_from_json)?,
+                "fills" => palette.fills = parse_section(value, "fills", fill_from_json)?,
+                "borders" => palette.borders = parse_section(value, "borders", border_from_json)?,
+                "number_formats" => {
+                    palette.number_formats = parse_section(value, "number_formats", |v, path| {
+                        string(v, path).map(str::to_string)
+                    })?
+                }
+                "styles" => palette.styles = parse_section(value, "styles", style_from_json)?,
+                other => return Err(invalid(format!("unknown palette key {other:?}"))),
+            }
+        }
+        for name in palette.styles.keys() {
+            palette.resolve(name)?;
+        }
+        Ok(palette)
+    }
+}
+
+fn lookup<T: Clone>(
+    named: &BTreeMap<String, T>,
+    reference: &Option<PaletteRef<T>>,
+    kind: &str,
+) -> Result<Option<T>> {
+    match reference {
+        None => Ok(None),
+        Some(PaletteRef::Inline(value)) => Ok(Some(value.clone())),
+        Some(PaletteRef::Named(name)) => named
+            .get(name)
+            .cloned()
+            .map(Some)
+            .ok_or_else(|| RustypyxlError::custom(format!("Palette {kind} not found: {name}"))),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Serialization
+// ---------------------------------------------------------------------------
+
+fn color_to_json(color: &Color) -> JsonValue {
+    match color {
+        Color {
+            rgb: Some(rgb),
+            theme: None,
+            indexed: None,
+            tint: None,
+        } => JsonValue::String(rgb.clone()),
+        _ => {
+            let mut entries = Vec::new();
+            if let Some(rgb) = &color.rgb {
+                entries.push(("rgb".into(), JsonValue::String(rgb.clone())));
+            }
+            if let Some(theme) = color.theme {
+                entries.push(("theme".into(), JsonValue::Number(theme as f64)));
+            }
+            if let Some(indexed) = color.indexed {
+                entries.push(("indexed".into(), JsonValue::Number(indexed as f64)));
+            }
+            if let Some(tint) = color.tint {
+                entries.push(("tint".into(), JsonValue::Number(tint)));
+            }
+            JsonValue::Object(entries)
+        }
+    }
+}
+
+fn push_str(entries: &mut Vec<(String, JsonValue)>, key: &str, value: &Option<String>) {
+    if let Some(v) = value {
+        entries.push((key.into(), JsonValue::String(v.clone())));
+    }
+}
+
+fn push_flag(entries: &mut Vec<(String, JsonValue)>, key: &str, value: bool) {
+    if value {
+        entries.push((key.into(), JsonValue::Bool(true)));
+    }
+}
+
+fn push_color(entries: &mut Vec<(String, JsonValue)>, key: &str, value: &Option<Color>) {
+    if let Some(c) = value {
+        entries.push((key.into(), color_to_json(c)));
+    }
+}
+
+fn font_to_json(font: &Font) -> JsonValue {
+    let mut e = Vec::new();
+    push_str(&mut e, "name", &font.name);
+    if let Some(size) = font.size {
+        e.push(("size".into(), JsonValue::Number(size)));
+    }
+    push_flag(&mut e, "bold", font.bold);
+    push_flag(&mut e, "italic", font.italic);
+    push_str(&mut e, "underline", &font.underline);
+    push_flag(&mut e, "strike", font.strike);
+    push_color(&mut e, "color", &font.color);
+    push_str(&mut e, "vert_align", &font.vert_align);
+    JsonValue::Object(e)
+}
+
+fn fill_to_json(fill: &Fill) -> JsonValue {
+    let mut e = Vec::new();
+    push_str(&mut e, "pattern", &fill.pattern_type);
+    push_color(&mut e, "fg_color", &fill.fg_color);
+    push_color(&mut e, "bg_color", &fill.bg_color);
+    JsonValue::Object(e)
+}
+
+fn border_to_json(border: &Border) -> JsonValue {
+    let sides = [
+        ("left", &border.left),
+        ("right", &border.right),
+        ("top", &border.top),
+        ("bottom", &border.bottom),
+        ("diagonal", &border.diagonal),
+    ];
+    JsonValue::Object(
+        sides
+            .into_iter()
+            .filter_map(|(key, side)| {
+                let side = side.as_ref()?;
+                let mut e = vec![("style".into(), JsonValue::String(side.style.clone()))];
+                push_color(&mut e, "color", &side.color);
+                Some((key.to_string(), JsonValue::Object(e)))
+            })
+            .collect(),
+    )
+}
+
+fn alignment_to_json(alignment: &Alignment) -> JsonValue {
+    let mut e = Vec::new();
+    push_str(&mut e, "horizontal", &alignment.horizontal);
+    push_str(&mut e, "vertical", &alignment.vertical);
+    push_flag(&mut e, "wrap_text", alignment.wrap_text);
+    if let Some(rotation) = alignment.text_rotation {
+        e.push(("text_rotation".into(), JsonValue::Number(rotation as f64)));
+    }
+    if let Some(indent) = alignment.indent {
+        e.push(("indent".into(), JsonValue::Number(indent as f64)));
+    }
+    push_flag(&mut e, "shrink_to_fit", alignment.shrink_to_fit);
+    JsonValue::Object(e)
+}
+
+fn ref_to_json<T>(reference: &PaletteRef<T>, inline: impl Fn(&T) -> JsonValue) -> JsonValue {
+    match reference {
+        PaletteRef::Named(name) => JsonValue::String(name.clone()),
+        PaletteRef::Inline(value) => inline(value),
+    }
+}
+
+fn style_to_json(style: &PaletteStyle) -> JsonValue {
+    let mut e = Vec::new();
+    if let Some(font) = &style.font {
+        e.push(("font".into(), ref_to_json(font, font_to_json)));
+    }
+    if let Some(fill) = &style.fill {
+        e.push(("fill".into(), ref_to_json(fill, fill_to_json)));
+    }
+    if let Some(border) = &style.border {
+        e.push(("border".into(), ref_to_json(border, border_to_json)));
+    }
+    if let Some(alignment) = &style.alignment {
+        e.push(("alignment".into(), alignment_to_json(alignment)));
+    }
+    push_str(&mut e, "number_format", &style.number_format);
+    if let Some(protection) = &style.protection {
+        e.push((
+            "protection".into(),
+            JsonValue::Object(vec![
+                ("locked".into(), JsonValue::Bool(protection.locked)),
+                ("hidden".into(), JsonValue::Bool(protection.hidden)),
+            ]),
+        ));
+    }
+    JsonValue::Object(e)
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+fn invalid(msg: String) -> RustypyxlError {
+    RustypyxlError::ParseError(format!("style palette: {msg}"))
+}
+
+fn object<'a>(value: &'a JsonValue, path: &str) -> Result<&'a [(String, JsonValue)]> {
+    value
+        .as_object()
+        .ok_or_else(|| invalid(format!("{path} must be an object")))
+}
+
+fn string<'a>(value: &'a JsonValue, path: &str) -> Result<&'a str> {
+    value
+        .as_str()
+        .ok_or_else(|| invalid(format!("{path} must be a string")))
+}
+
+fn number(value: &JsonValue, path: &str) -> Result<f64> {
+    value
+        .as_f64()
+        .ok_or_else(|| invalid(format!("{path} must be a number")))
+}
+
+fn unsigned(value: &JsonValue, path: &str) -> Result<u32> {
+    let n = number(value, path)?;
+    if n < 0.0 || n.fract() != 0.0 || n > u32::MAX as f64 {
+        return Err(invalid(format!("{path} must be a non-negative integer")));
+    }
+    Ok(n as u32)
+}
+
+fn boolean(value: &JsonValue, path: &str) -> Result<bool> {
+    value
+        .as_bool()
+        .ok_or_else(|| invalid(format!("{path} must be true or false")))
+}
+
+fn unknown(path: &str, key: &str) -> RustypyxlError {
+    invalid(format!("unknown key {key:?} in {path}"))
+}
+
+fn parse_section<T>(
+    value: &JsonValue,
+    path: &str,
+    parse: impl Fn(&JsonValue, &str) -> Result<T>,
+) -> Result<BTreeMap<String, T>> {
+    object(value, path)?
+        .iter()
+        .map(|(name, v)| Ok((name.clone(), parse(v, &format!("{path}.{name}"))?)))
+        .collect()
+}
+
+fn color_from_json(value: &JsonValue, path: &str) -> Result<Color> {
+    if let Some(rgb) = value.as_str() {
+        return Ok(Color::rgb(rgb));
+    }
+    let mut color = Color::default();
+    for (key, v) in object(value, path)? {
+        let field = format!("{path}.{key}");
+        match key.as_str() {
+            "rgb" => color.rgb = Some(string(v, &field)?.to_string()),
+            "theme" => color.theme = Some(unsigned(v, &field)?),
+            "indexed" => color.indexed = Some(unsigned(v, &field)?),
+            "tint" => color.tint = Some(number(v, &field)?),
+            other => return Err(unknown(path, other)),
+        }
+    }
+    if color.is_empty() {
+        return Err(invalid(format!("{path} sets no color")));
+    }
+    Ok(color)
+}
+
+fn font_from_json(value: &JsonValue, path: &str) -> Result<Font> {
+    let mut font = Font::new();
+    for (key, v) in object(value, path)? {
+        let field = format!("{path}.{key}");
+        match key.as_str() {
+            "name" => font.name = Some(string(v, &field)?.to_string()),
+            "size" => font.size = Some(number(v, &field)?),
+            "bold" => font.bold = boolean(v, &field)?,
+            "italic" => font.italic = boolean(v, &field)?,
+            "underline" => font.underline = Some(string(v, &field)?.to_string()),
+            "strike" => font.strike = boolean(v, &field)?,
+            "color" => font.color = Some(color_from_json(v, &field)?),
+            "vert_align" => font.vert_align = Some(string(v, &field)?.to_string()),
+            other => return Err(unknown(path, other)),
+        }
+    }
+    Ok(font)
+}
+
+fn fill_from_json(value: &JsonValue, path: &str) -> Result<Fill> {
+    let mut fill = Fill::new();
+    for (key, v) in object(value, path)? {
+        let field = format!("{path}.{key}");
+        match key.as_str() {
+            "pattern" => fill.pattern_type = Some(string(v, &field)?.to_string()),
+            "fg_color" => fill.fg_color = Some(color_from_json(v, &field)?),
+            "bg_color" => fill.bg_color = Some(color_from_json(v, &field)?),
+            other => return Err(unknown(path, other)),
+        }
+    }
+    Ok(fill)
+}
+
+fn border_from_json(value: &JsonValue, path: &str) -> Result<Border> {
+    let mut border = Border::new();
+    for (key, v) in object(value, path)? {
+        let field = format!("{path}.{key}");
+        let slot = match key.as_str() {
+            "left" => &mut border.left,
+            "right" => &mut border.right,
+            "top" => &mut border.top,
+            "bottom" => &mut border.bottom,
+            "diagonal" => &mut border.diagonal,
+            other => return Err(unknown(path, other)),
+        };
+        let mut side: Option<BorderStyle> = None;
+        let mut color = None;
+        for (side_key, sv) in object(v, &field)? {
+            let side_field = format!("{field}.{side_key}");
+            match side_key.as_str() {
+                "style" => side = Some(BorderStyle::new(string(sv, &side_field)?)),
+                "color" => color = Some(color_from_json(sv, &side_field)?),
+                other => return Err(unknown(&field, other)),
+            }
+        }
+        let mut side = side.ok_or_else(|| invalid(format!("{field}.style is required")))?;
+        side.color = color;
+        *slot = Some(side);
+    }
+    Ok(border)
+}
+
+fn alignment_from_json(value: &JsonValue, path: &str) -> Result<Alignment> {
+    let mut alignment = Alignment::new();
+    for (key, v) in object(value, path)? {
+        let field = format!("{path}.{key}");
+        match key.as_str() {
+            "horizontal" => alignment.horizontal = Some(string(v, &field)?.to_string()),
+            "vertical" => alignment.vertical = Some(string(v, &field)?.to_string()),
+            "wrap_text" => alignment.wrap_text = boolean(v, &field)?,
+            "text_rotation" => alignment.text_rotation = Some(number(v, &field)? as i32),
+            "indent" => alignment.indent = Some(unsigned(v, &field)?),
+            "shrink_to_fit" => alignment.shrink_to_fit = boolean(v, &field)?,
+            other => return Err(unknown(path, other)),
+        }
+    }
+    Ok(alignment)
+}
+
+fn protection_from_json(value: &JsonValue, path: &str) -> Result<Protection> {
+    let mut protection = Protection::new();
+    for (key, v) in object(value, path)? {
+        let field = format!("{path}.{key}");
+        match key.as_str() {
+            "locked" => protection.locked = boolean(v, &field)?,
+            "hidden" => protection.hidden = boolean(v, &field)?,
+            other => return Err(unknown(path, other)),
+        }
+    }
+    Ok(protection)
+}
+
+fn ref_from_json<T>(
+    value: &JsonValue,
+    path: &str,
+    inline: impl Fn(&JsonValue, &str) -> Result<T>,
+) -> Result<PaletteRef<T>> {
+    match value.as_str() {
+        Some(name) => Ok(PaletteRef::Named(name.to_string())),
+        None => inline(value, path).map(PaletteRef::Inline),
+    }
+}
+
+fn style_from_json(value: &JsonValue, path: &str) -> Result<PaletteStyle> {
+    let mut style = PaletteStyle::default();
+    for (key, v) in object(value, path)? {
+        let field = format!("{path}.{key}");
+        match key.as_str() {
+            "font" => style.font = Some(ref_from_json(v, &field, font_from_json)?),
+            "fill" => style.fill = Some(ref_from_json(v, &field, fill_from_json)?),
+            "border" => style.border = Some(ref_from_json(v, &field, border_from_json)?),
+            "alignment" => style.alignment = Some(alignment_from_json(v, &field)?),
+            "number_format" => style.number_format = Some(string(v, &field)?.to_string()),
+            "protection" => style.protection = Some(protection_from_json(v, &field)?),
+            other => return Err(unknown(path, other)),
+        }
+    }
+    Ok(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corporate() -> StylePalette {
+        let mut palette = StylePalette::new();
+        palette.fonts.insert(
+            "heading".into(),
+            Font::new()
+                .with_name("Calibri")
+                .with_size(14.0)
+                .with_bold(true)
+                .with_color(Color::theme(4).with_tint(-0.25)),
+        );
+        palette.fills.insert("band".into(), Fill::solid("FFDDEBF7"));
+        palette.borders.insert(
+            "box".into(),
+            Border::all(BorderStyle::thin().with_color("FF000000")),
+        );
+        palette
+            .number_formats
+            .insert("money".into(), "#,##0.00".into());
+        palette.add_style(
+            "header",
+            PaletteStyle {
+                font: Some(PaletteRef::Named("heading".into())),
+                fill: Some(PaletteRef::Named("band".into())),
+                border: Some(PaletteRef::Named("box".into())),
+                alignment: Some(
+                    Alignment::new()
+                        .with_horizontal("center")
+                        .with_wrap_text(true),
+                ),
+                ..Default::default()
+            },
+        );
+        palette.add_style(
+            "amount",
+            PaletteStyle {
+                font: Some(PaletteRef::Inline(Font::new().with_italic(true))),
+                number_format: Some("money".into()),
+                protection: Some(Protection::unlocked()),
+                ..Default::default()
+            },
+        );
+        palette
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let palette = corporate();
+        let json = palette.to_json();
+        assert!(json.contains("\"version\": 1"));
+        assert!(json.contains("\"fg_color\": \"FFDDEBF7\""));
+        assert!(json.contains("\"theme\": 4"));
+        assert_eq!(StylePalette::from_json(&json).unwrap(), palette);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let palette = corporate();
+        let header = palette.resolve("header").unwrap();
+        assert_eq!(header.font.as_ref().unwrap().size, Some(14.0));
+        assert_eq!(header.fill, Some(Fill::solid("FFDDEBF7")));
+        assert!(header.border.unwrap().left.is_some());
+        assert!(header.number_format.is_none());
+
+        let amount = palette.resolve("amount").unwrap();
+        assert_eq!(amount.number_format.as_deref(), Some("#,##0.00"));
+        assert!(amount.font.unwrap().italic);
+        assert!(!amount.protection.unwrap().locked);
+
+        assert!(palette.resolve("missing").is_err());
+    }
+
+    #[test]
+    fn test_literal_number_format() {
+        let palette =
+            StylePalette::from_json(r#"{"styles": {"pct": {"number_format": "0.0%"}}}"#).unwrap();
+        assert_eq!(
+            palette.resolve("pct").unwrap().number_format.as_deref(),
+            Some("0.0%")
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_documents() {
+        for bad in [
+            r#"{"styles": {"h": {"font": "nope"}}}"#,
+            r#"{"fonts": {"f": {"bold": "yes"}}}"#,
+            r#"{"fonts": {"f": {"colour": "FF000000"}}}"#,
+            r#"{"borders": {"b": {"left": {"color": "FF000000"}}}}"#,
+            r#"{"version": 2}"#,
+            r#"{"palette": {}}"#,
+            r#"[]"#,
+        ] {
+            assert!(StylePalette::from_json(bad).is_err(), "{bad} should fail");
+        }
+    }
+
+    #[test]
+    fn test_extend_replaces_same_names() {
+        let mut palette = corporate();
+        let overlay = StylePalette::from_json(
+            r#"{"fills": {"band": {"pattern": "solid", "fg_color": "FFFFFF00"}}}"#,
+        )
+        .unwrap();
+        palette.extend(overlay);
+        assert_eq!(palette.fills["band"], Fill::solid("FFFFFF00"));
+        assert_eq!(palette.styles.len(), 2);
+    }
+}
@@ -3,6 +3,15 @@
 //! This module provides structures for creating and managing Excel Tables,
 //! which provide structured references, auto-filtering, and formatting.
 
+use std::fmt;
+
+use crate::cell::CellValue;
+use crate::error::{Result, RustypyxlError};
+use crate::translate::translate_formula;
+use crate::utils::{column_to_letter, coordinate_from_row_col, parse_coordinate, parse_range};
+use crate::workbook::{qualify_sheet_range, Workbook};
+use crate::worksheet::Worksheet;
+
 /// Table style preset names.
 #[derive(Clone, Debug, PartialEq)]
 pub enum TableStyle {
@@ -95,6 +104,41 @@ impl TotalsRowFunction {
             TotalsRowFunction::Custom(_) => Some("custom"),
         }
     }
+
+    /// Parse a function by its XML name ("sum", "countNums", ...), ignoring
+    /// case. "none" is [`TotalsRowFunction::None`]; custom formulas have no
+    /// name and are not parsed.
+    pub fn parse(name: &str) -> Option<Self> {
+        [
+            TotalsRowFunction::None,
+            TotalsRowFunction::Average,
+            TotalsRowFunction::Count,
+            TotalsRowFunction::CountNums,
+            TotalsRowFunction::Max,
+            TotalsRowFunction::Min,
+            TotalsRowFunction::StdDev,
+            TotalsRowFunction::Sum,
+            TotalsRowFunction::Var,
+        ]
+        .into_iter()
+        .find(|f| f.xml_name().unwrap_or("none").eq_ignore_ascii_case(name))
+    }
+
+    /// The SUBTOTAL function number Excel writes for this function. The
+    /// 10x variants skip rows hidden by the table's filter.
+    pub fn subtotal_id(&self) -> Option<u32> {
+        match self {
+            TotalsRowFunction::Average => Some(101),
+            TotalsRowFunction::CountNums => Some(102),
+            TotalsRowFunction::Count => Some(103),
+            TotalsRowFunction::Max => Some(104),
+            TotalsRowFunction::Min => Some(105),
+            TotalsRowFunction::StdDev => Some(107),
+            TotalsRowFunction::Sum => Some(109),
+            TotalsRowFunction::Var => Some(110),
+            TotalsRowFunction::None | TotalsRowFunction::Custom(_) => None,
+        }
+    }
 }
 
 /// A column in an Excel Table.
@@ -264,6 +308,24 @@ impl Table {
         }
     }
 
+    /// The totals-row formula (without the leading '=') for `column`, e.g.
+    /// `SUBTOTAL(109,Sales[Amount])`. `None` when the column has no function.
+    pub fn totals_row_formula(&self, column: &TableColumn) -> Option<String> {
+        match &column.totals_row_function {
+            TotalsRowFunction::Custom(formula) => {
+                Some(formula.strip_prefix('=').unwrap_or(formula).to_string())
+            }
+            function => function.subtotal_id().map(|id| {
+                format!(
+                    "SUBTOTAL({},{}[{}])",
+                    id,
+                    self.name,
+                    escape_column_name(&column.name)
+                )
+            }),
+        }
+    }
+
     /// Get a structured reference for the table.
     pub fn structured_ref(&self, column: Option<&str>, specifier: Option<&str>) -> String {
         let mut ref_str = format!("{}[", self.name);
@@ -284,6 +346,997 @@ impl Table {
     }
 }
 
+/// Escape a column name for use inside a structured reference, where
+/// `[`, `]`, `#` and `'` must be prefixed with `'`.
+fn escape_column_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for ch in name.chars() {
+        if matches!(ch, '[' | ']' | '#' | '\'') {
+            out.push('\'');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Check `name` against Excel's rules for table names: a letter, `_` or
+/// `\` first, then letters, digits, `_` and `.`, at most 255 characters,
+/// and not something Excel would read as a cell reference.
+pub fn validate_table_name(name: &str) -> Result<()> {
+    let invalid = |why: &str| {
+        Err(RustypyxlError::custom(format!(
+            "Invalid table name '{}': {}",
+            name, why
+        )))
+    };
+    let mut chars = name.chars();
+    match chars.next() {
+        None => return invalid("name is empty"),
+        Some(c) if !(c.is_alphabetic() || c == '_' || c == '\\') => {
+            return invalid("must start with a letter, '_' or '\\'")
+        }
+        _ => {}
+    }
+    if chars.any(|c| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '\\')) {
+        return invalid("only letters, digits, '_', '.' and '\\' are allowed");
+    }
+    if name.chars().count() > 255 {
+        return invalid("longer than 255 characters");
+    }
+    if matches!(name, "C" | "c" | "R" | "r") || parse_coordinate(name).is_ok() || is_r1c1(name) {
+        return invalid("looks like a cell reference");
+    }
+    Ok(())
+}
+
+/// `R1C1`-style references such as "R2C3" (also "R2" and "C3").
+fn is_r1c1(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    let rest = match upper.strip_prefix('R') {
+        Some(rest) => rest.trim_start_matches(|c: char| c.is_ascii_digit()),
+        None => &upper,
+    };
+    let rest = match rest.strip_prefix('C') {
+        Some(rest) => rest.trim_start_matches(|c: char| c.is_ascii_digit()),
+        None => rest,
+    };
+    rest.is_empty() && upper != *rest
+}
+
+impl Worksheet {
+    /// Turn `range` (header row included) into a table named `name` with
+    /// banded rows and filter dropdowns. Column names come from the header
+    /// cells; blank headers become "Column1", "Column2", ... and repeated
+    /// ones get a numeric suffix, as Excel does. Fails if the name is not a
+    /// valid table name, is already used on this sheet, or the range
+    /// overlaps another table. Returns the table for further adjustment.
+    pub fn create_table(
+        &mut self,
+        name: &str,
+        range: &str,
+        style: TableStyle,
+    ) -> Result<&mut Table> {
+        validate_table_name(name)?;
+        let ((first_row, first_col), (last_row, last_col)) = parse_range(range)?;
+        for table in &self.tables {
+            if table.name.eq_ignore_ascii_case(name) {
+                return Err(RustypyxlError::custom(format!(
+                    "Table '{}' already exists",
+                    table.name
+                )));
+            }
+            let ((r1, c1), (r2, c2)) = parse_range(&table.range)?;
+            if first_row <= r2 && r1 <= last_row && first_col <= c2 && c1 <= last_col {
+                return Err(RustypyxlError::custom(format!(
+                    "Range {} overlaps table '{}' ({})",
+                    range, table.name, table.range
+                )));
+            }
+        }
+
+        let mut table = Table::new(self.tables.len() as u32 + 1, name, range).with_style(style);
+        table.range = format!(
+            "{}{}:{}{}",
+            column_to_letter(first_col),
+            first_row,
+            column_to_letter(last_col),
+            last_row
+        );
+        table.columns = self.header_columns(Some(first_row), first_col, last_col);
+        self.tables.push(table);
+        Ok(self.tables.last_mut().expect("table was just added"))
+    }
+
+    /// Table columns for `first_col..=last_col`, named from the cells on
+    /// `header_row` when given. Names are made unique case-insensitively.
+    fn header_columns(
+        &self,
+        header_row: Option<u32>,
+        first_col: u32,
+        last_col: u32,
+    ) -> Vec<TableColumn> {
+        let mut seen: Vec<String> = Vec::new();
+        (first_col..=last_col)
+            .enumerate()
+            .map(|(i, col)| {
+                let base = header_row
+                    .and_then(|row| self.get_cell_value(row, col))
+                    .filter(|v| !matches!(v, CellValue::Empty))
+                    .map(|v| v.to_string())
+                    .filter(|v| !v.is_empty())
+                    .unwrap_or_else(|| format!("Column{}", i + 1));
+                let mut header = base.clone();
+                let mut n = 2;
+                while seen.iter().any(|s| s.eq_ignore_ascii_case(&header)) {
+                    header = format!("{}{}", base, n);
+                    n += 1;
+                }
+                seen.push(header.clone());
+                TableColumn::new(i as u32 + 1, &header)
+            })
+            .collect()
+    }
+
+    /// Give the table `name` a totals row: set each listed column's
+    /// function and write its `SUBTOTAL` formula (or custom formula) into
+    /// the row beneath the data, growing the table's range by one row. The
+    /// first column is labelled "Total" unless it gets a function itself.
+    /// Calling it again on a table that already has a totals row rewrites
+    /// that row in place. Returns the totals row number.
+    pub fn add_table_totals_row(
+        &mut self,
+        name: &str,
+        totals: &[(&str, TotalsRowFunction)],
+    ) -> Result<u32> {
+        let idx = self
+            .tables
+            .iter()
+            .position(|t| t.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| RustypyxlError::custom(format!("Table '{}' does not exist", name)))?;
+        let ((first_row, first_col), (mut last_row, last_col)) =
+            parse_range(&self.tables[idx].range)?;
+
+        // Tables created without explicit headers take them from the sheet.
+        if self.tables[idx].columns.is_empty() {
+            let header_row = self.tables[idx].header_row.then_some(first_row);
+            self.tables[idx].columns = self.header_columns(header_row, first_col, last_col);
+        }
+
+        let table = &self.tables[idx];
+        let mut positions = Vec::with_capacity(totals.len());
+        for (column, _) in totals {
+            let pos = table
+                .columns
+                .iter()
+                .position(|c| c.name.eq_ignore_ascii_case(column))
+                .ok_or_else(|| {
+                    RustypyxlError::custom(format!(
+                        "Table '{}' has no column '{}'",
+                        table.name, column
+                    ))
+                })?;
+            positions.push(pos);
+        }
+
+        let table = &mut self.tables[idx];
+        if !table.totals_row {
+            last_row += 1;
+            table.range = format!(
+                "{}{}:{}{}",
+                column_to_letter(first_col),
+                first_row,
+                column_to_letter(last_col),
+                last_row
+            );
+            table.totals_row = true;
+        }
+        for (pos, (_, function)) in positions.into_iter().zip(totals) {
+            table.columns[pos].totals_row_function = function.clone();
+        }
+        if table.columns[0].totals_row_function == TotalsRowFunction::None
+            && table.columns[0].totals_row_label.is_none()
+        {
+            table.columns[0].totals_row_label = Some("Total".to_string());
+        }
+
+        let cells: Vec<(u32, CellValue)> = table
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let value = match (table.totals_row_formula(column), &column.totals_row_label) {
+                    (Some(formula), _) => CellValue::Formula(formula),
+                    (None, Some(label)) => CellValue::from(label.as_str()),
+                    (None, None) => CellValue::Empty,
+                };
+                (first_col + i as u32, value)
+            })
+            .collect();
+        for (col, value) in cells {
+            self.set_cell_value(last_row, col, value);
+        }
+        Ok(last_row)
+    }
+
+    /// Fill column `target_col` with `formula_template` on each of `rows`
+    /// (first, last), as Excel does when a formula is typed into a table
+    /// column. `[@Col]` (or `[@[Col Name]]`) refers to column `Col` on the
+    /// same row; plain A1 references in the template are written for the
+    /// first row and move down with each row, as a fill-down would.
+    ///
+    /// When `target_col` lies inside a table, the formula is stored with
+    /// structured references (`Sales[[#This Row],[Qty]]`), `rows` defaults
+    /// to the table's data rows, and a template that covers all of them also
+    /// becomes the column's `calculatedColumnFormula`, so Excel extends it
+    /// to rows added later. Elsewhere `rows` is required, `[@Col]` names a
+    /// header on the row above the first one, and the formula is written
+    /// with A1 references. Returns the rows that were filled.
+    pub fn add_calculated_column(
+        &mut self,
+        target_col: u32,
+        formula_template: &str,
+        rows: Option<(u32, u32)>,
+    ) -> Result<(u32, u32)> {
+        if target_col == 0 {
+            return Err(RustypyxlError::custom("Column index must be at least 1"));
+        }
+        let template = formula_template
+            .strip_prefix('=')
+            .unwrap_or(formula_template);
+        let mut table_idx = None;
+        for (i, table) in self.tables.iter().enumerate() {
+            let ((r1, c1), (r2, c2)) = parse_range(&table.range)?;
+            let in_rows = rows.is_none_or(|(first, last)| first <= r2 && r1 <= last);
+            if (c1..=c2).contains(&target_col) && in_rows {
+                table_idx = Some(i);
+                break;
+            }
+        }
+
+        let (first, last, formula) = match table_idx {
+            Some(idx) => {
+                let ((first_row, first_col), (last_row, last_col)) =
+                    parse_range(&self.tables[idx].range)?;
+                if self.tables[idx].columns.is_empty() {
+                    let header_row = self.tables[idx].header_row.then_some(first_row);
+                    self.tables[idx].columns = self.header_columns(header_row, first_col, last_col);
+                }
+                let table = &self.tables[idx];
+                let body = (
+                    first_row + table.header_row as u32,
+                    last_row - table.totals_row as u32,
+                );
+                let (first, last) = rows.unwrap_or(body);
+                if first > last || first < body.0 || last > body.1 {
+                    return Err(RustypyxlError::custom(format!(
+                        "Rows {}-{} are not within the data rows of table '{}' ({}-{})",
+                        first, last, table.name, body.0, body.1
+                    )));
+                }
+                let formula = rewrite_structured_refs(template, |name, group| {
+                    if name.is_some_and(|n| !n.eq_ignore_ascii_case(&table.name)) {
+                        return Ok(None);
+                    }
+                    Ok(Some(match group {
+                        RefGroup::ThisRow(column, _) => {
+                            let column = table
+                                .columns
+                                .iter()
+                                .find(|c| c.name.eq_ignore_ascii_case(&column))
+                                .ok_or_else(|| {
+                                    RustypyxlError::custom(format!(
+                                        "Table '{}' has no column '{}'",
+                                        table.name, column
+                                    ))
+                                })?;
+                            format!(
+                                "{}[[#This Row],[{}]]",
+                                table.name,
+                                escape_column_name(&column.name)
+                            )
+                        }
+                        RefGroup::Other(text) => format!("{}{}", table.name, text),
+                    }))
+                })?;
+                if (first, last) == body {
+                    let column = (target_col - first_col) as usize;
+                    self.tables[idx].columns[column].calculated_column_formula =
+                        Some(formula.clone());
+                }
+                (first, last, formula)
+            }
+            None => {
+                let (first, last) = rows.ok_or_else(|| {
+                    RustypyxlError::custom(format!(
+                        "Column {} is not in a table, so the rows to fill must be given",
+                        column_to_letter(target_col)
+                    ))
+                })?;
+                if first == 0 || first > last {
+                    return Err(RustypyxlError::custom(format!(
+                        "Invalid row span {}-{}",
+                        first, last
+                    )));
+                }
+                let formula =
+                    rewrite_structured_refs(template, |name, group| match (name, group) {
+                        (Some(_), _) => Ok(None),
+                        (None, RefGroup::ThisRow(column, _)) => {
+                            let header = (first > 1)
+                                .then(|| {
+                                    self.iter_row(first - 1).find(|(_, cell)| {
+                                        cell.value.to_string().eq_ignore_ascii_case(&column)
+                                    })
+                                })
+                                .flatten()
+                                .ok_or_else(|| {
+                                    RustypyxlError::custom(format!(
+                                        "No header '{}' on row {}",
+                                        column,
+                                        first.saturating_sub(1)
+                                    ))
+                                })?;
+                            Ok(Some(format!("{}{}", column_to_letter(header.0), first)))
+                        }
+                        (None, RefGroup::Other(text)) => Err(RustypyxlError::custom(format!(
+                            "Structured reference '{}' needs a table around column {}",
+                            text,
+                            column_to_letter(target_col)
+                        ))),
+                    })?;
+                (first, last, formula)
+            }
+        };
+
+        for row in first..=last {
+            let formula = translate_formula(&formula, (row - first) as i64, 0);
+            self.set_cell_value(row, target_col, CellValue::Formula(formula));
+        }
+        Ok((first, last))
+    }
+}
+
+/// A bracket group of a structured reference, as met in a formula.
+enum RefGroup<'a> {
+    /// `[@Col]` or `[@[Col]]`: the named column on the formula's own row,
+    /// with escapes removed, and the group as written.
+    ThisRow(String, &'a str),
+    /// Any other group, brackets included, e.g. `[Col]` or `[#Totals]`.
+    Other(&'a str),
+}
+
+impl<'a> RefGroup<'a> {
+    /// The group as written, brackets included.
+    fn text(&self) -> &'a str {
+        match self {
+            RefGroup::ThisRow(_, text) | RefGroup::Other(text) => text,
+        }
+    }
+}
+
+/// Rewrite the structured references in `formula`. `rewrite` gets each
+/// reference's table name (`None` for an unqualified `[...]`) and bracket
+/// group, and returns its replacement, or `None` to keep it as written.
+/// String literals and quoted sheet names are copied untouched.
+fn rewrite_structured_refs<F>(formula: &str, mut rewrite: F) -> Result<String>
+where
+    F: FnMut(Option<&str>, RefGroup<'_>) -> Result<Option<String>>,
+{
+    let mut out = String::with_capacity(formula.len());
+    let mut rest = formula;
+    while let Some(pos) = rest.find(['"', '\'', '[']) {
+        let (before, from) = rest.split_at(pos);
+        if !from.starts_with('[') {
+            let quote = from.chars().next().unwrap_or('"');
+            let end = from[1..].find(quote).map_or(from.len(), |i| i + 2);
+            out.push_str(before);
+            out.push_str(&from[..end]);
+            rest = &from[end..];
+            continue;
+        }
+
+        let name_start = before
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '\\'))
+            .map_or(0, |i| {
+                i + before[i..].chars().next().map_or(1, char::len_utf8)
+            });
+        let name = &before[name_start..];
+        let end = bracket_group_end(from).ok_or_else(|| {
+            RustypyxlError::custom(format!("Unclosed '[' in formula '{}'", formula))
+        })?;
+        let group_text = &from[..end];
+        let group = match group_text[1..end - 1].strip_prefix('@') {
+            Some(column) => {
+                let column = column
+                    .strip_prefix('[')
+                    .and_then(|c| c.strip_suffix(']'))
+                    .unwrap_or(column);
+                RefGroup::ThisRow(unescape_column_name(column), group_text)
+            }
+            None => RefGroup::Other(group_text),
+        };
+        let table = (!name.is_empty()).then_some(name);
+        match rewrite(table, group)? {
+            Some(replacement) => {
+                out.push_str(&before[..name_start]);
+                out.push_str(&replacement);
+            }
+            None => {
+                out.push_str(before);
+                out.push_str(group_text);
+            }
+        }
+        rest = &from[end..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Byte length of the bracket group `text` starts with, nested groups and
+/// `'`-escaped characters included.
+fn bracket_group_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\'' => escaped = true,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Undo [`escape_column_name`].
+fn unescape_column_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' => out.extend(chars.next()),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+/// A special item of a structured reference, choosing which rows of the
+/// table it covers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableItem {
+    /// `#All`: the header, data and totals rows.
+    All,
+    /// `#Data`: the data rows; what a reference without items covers.
+    Data,
+    /// `#Headers`: the header row.
+    Headers,
+    /// `#Totals`: the totals row.
+    Totals,
+    /// `#This Row`, shown as `@` in Excel: the formula's own row.
+    ThisRow,
+}
+
+impl TableItem {
+    /// Parse an item as written in a formula, e.g. "#Data" or "#This Row",
+    /// ignoring case.
+    pub fn parse(text: &str) -> Option<Self> {
+        [
+            TableItem::All,
+            TableItem::Data,
+            TableItem::Headers,
+            TableItem::Totals,
+            TableItem::ThisRow,
+        ]
+        .into_iter()
+        .find(|item| item.as_str().eq_ignore_ascii_case(text.trim()))
+    }
+
+    /// The item as stored in a file, e.g. "#This Row".
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TableItem::All => specifiers::ALL,
+            TableItem::Data => specifiers::DATA,
+            TableItem::Headers => specifiers::HEADERS,
+            TableItem::Totals => specifiers::TOTALS,
+            TableItem::ThisRow => specifiers::THIS_ROW,
+        }
+    }
+}
+
+/// A structured reference such as `Sales[[#Data],[Qty]:[Price]]`, split
+/// into its table, items and columns. Displays in the form Excel stores.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StructuredRef {
+    /// The table name; `None` for an unqualified reference like `[@Qty]`,
+    /// which only has meaning inside a table.
+    pub table: Option<String>,
+    /// The special items; empty means `#Data`.
+    pub items: Vec<TableItem>,
+    /// The first and last column, escapes removed (the same name twice for
+    /// a single column); `None` for every column.
+    pub columns: Option<(String, String)>,
+}
+
+impl StructuredRef {
+    /// Parse `text`: a table name, or nothing, followed by one bracket
+    /// group, as in `Sales[Qty]`, `Sales[[#Totals],[Qty]]`,
+    /// `Sales[[Qty]:[Price]]`, `Sales[#All]`, `Sales[]`, `[@Qty]` or
+    /// `[@[Unit Price]]`.
+    pub fn parse(text: &str) -> Result<Self> {
+        let invalid = |why: &str| {
+            Err(RustypyxlError::custom(format!(
+                "Invalid structured reference '{}': {}",
+                text, why
+            )))
+        };
+        let Some(open) = text.find('[') else {
+            return invalid("no '[' after the table name");
+        };
+        let (name, group) = text.split_at(open);
+        if bracket_group_end(group) != Some(group.len()) {
+            return invalid("unbalanced brackets");
+        }
+        if name
+            .chars()
+            .any(|c| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '\\'))
+        {
+            return invalid("not a table name");
+        }
+
+        let mut reference = StructuredRef {
+            table: (!name.is_empty()).then(|| name.to_string()),
+            items: Vec::new(),
+            columns: None,
+        };
+        let mut rest = &group[1..group.len() - 1];
+        if let Some(after) = rest.strip_prefix('@') {
+            reference.items.push(TableItem::ThisRow);
+            rest = after;
+        }
+        rest = rest.trim();
+        if !rest.starts_with('[') {
+            match TableItem::parse(rest) {
+                _ if rest.is_empty() => {}
+                Some(item) => reference.items.push(item),
+                None => {
+                    let column = unescape_column_name(rest);
+                    reference.columns = Some((column.clone(), column));
+                }
+            }
+            return Ok(reference);
+        }
+
+        let mut range_follows = false;
+        while !rest.is_empty() {
+            let Some(end) = rest
+                .starts_with('[')
+                .then(|| bracket_group_end(rest))
+                .flatten()
+            else {
+                return invalid("expected a bracketed item or column");
+            };
+            let part = &rest[1..end - 1];
+            rest = rest[end..].trim_start();
+            match (
+                TableItem::parse(part),
+                &mut reference.columns,
+                range_follows,
+            ) {
+                (Some(item), None, false) => reference.items.push(item),
+                (Some(_), _, _) => return invalid("items must come before the columns"),
+                (None, None, false) => {
+                    let column = unescape_column_name(part);
+                    reference.columns = Some((column.clone(), column));
+                }
+                (None, Some((_, last)), true) => *last = unescape_column_name(part),
+                (None, _, _) => return invalid("columns must be one column or a range"),
+            }
+            range_follows = false;
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after.trim_start();
+            } else if let Some(after) = rest.strip_prefix(':') {
+                range_follows = true;
+                rest = after.trim_start();
+            } else if !rest.is_empty() {
+                return invalid("expected ',' or ':' between parts");
+            }
+        }
+        if range_follows {
+            return invalid("column range has no last column");
+        }
+        Ok(reference)
+    }
+}
+
+impl fmt::Display for StructuredRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(table) = &self.table {
+            f.write_str(table)?;
+        }
+        let mut parts: Vec<String> = self
+            .items
+            .iter()
+            .map(|item| format!("[{}]", item.as_str()))
+            .collect();
+        match &self.columns {
+            None if parts.is_empty() => f.write_str("[]"),
+            None if parts.len() == 1 => write!(f, "[{}]", self.items[0].as_str()),
+            Some((first, last)) if parts.is_empty() && first == last && is_plain_column(first) => {
+                write!(f, "[{}]", escape_column_name(first))
+            }
+            columns => {
+                if let Some((first, last)) = columns {
+                    let mut part = format!("[{}]", escape_column_name(first));
+                    if first != last {
+                        part.push_str(&format!(":[{}]", escape_column_name(last)));
+                    }
+                    parts.push(part);
+                }
+                write!(f, "[{}]", parts.join(","))
+            }
+        }
+    }
+}
+
+/// Whether column `name` may stand alone in single brackets, as in
+/// `Sales[Qty]`. Names with spaces or punctuation need `Sales[[Unit Price]]`.
+fn is_plain_column(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+impl Table {
+    /// The cells `reference` covers in this table, as an A1 range like
+    /// "B2:B10" (or a single cell). `row` is the formula's own row, which
+    /// `#This Row` needs. Fails when the reference names a column the table
+    /// lacks, a header or totals row it does not have, or a row outside
+    /// its data.
+    pub fn structured_ref_range(
+        &self,
+        reference: &StructuredRef,
+        row: Option<u32>,
+    ) -> Result<String> {
+        let fail = |why: String| {
+            RustypyxlError::custom(format!(
+                "'{}' does not resolve in table '{}': {}",
+                reference, self.name, why
+            ))
+        };
+        let ((top, left), (bottom, right)) = parse_range(&self.range)?;
+        let data = (
+            top + self.header_row as u32,
+            bottom.saturating_sub(self.totals_row as u32),
+        );
+        let items = match reference.items.is_empty() {
+            true => &[TableItem::Data][..],
+            false => &reference.items[..],
+        };
+        let mut rows: Option<(u32, u32)> = None;
+        for &item in items {
+            let (first, last) = match item {
+                TableItem::All => (top, bottom),
+                TableItem::Data => data,
+                TableItem::Headers if self.header_row => (top, top),
+                TableItem::Totals if self.totals_row => (bottom, bottom),
+                TableItem::ThisRow => match row {
+                    Some(row) if (data.0..=data.1).contains(&row) => (row, row),
+                    _ => return Err(fail("the formula is not on a data row".to_string())),
+                },
+                item => return Err(fail(format!("the table has no {} row", item.as_str()))),
+            };
+            if first > last {
+                return Err(fail("the table has no data rows".to_string()));
+            }
+            rows = Some(rows.map_or((first, last), |(a, b)| (a.min(first), b.max(last))));
+        }
+        let (first_row, last_row) = rows.unwrap_or(data);
+
+        let (first_col, last_col) = match &reference.columns {
+            None => (left, right),
+            Some((first, last)) => {
+                let find = |name: &str| {
+                    self.columns
+                        .iter()
+                        .position(|c| c.name.eq_ignore_ascii_case(name))
+                        .map(|i| left + i as u32)
+                        .ok_or_else(|| fail(format!("no column '{}'", name)))
+                };
+                let (a, b) = (find(first)?, find(last)?);
+                (a.min(b), a.max(b))
+            }
+        };
+        let mut range = coordinate_from_row_col(first_row, first_col);
+        if (first_row, first_col) != (last_row, last_col) {
+            range.push(':');
+            range.push_str(&coordinate_from_row_col(last_row, last_col));
+        }
+        Ok(range)
+    }
+}
+
+impl Workbook {
+    /// The sheet index and table named `name`, ignoring case.
+    fn find_table(&self, name: &str) -> Option<(usize, &Table)> {
+        self.worksheets
+            .iter()
+            .enumerate()
+            .filter(|(_, ws)| ws.opaque.is_none())
+            .find_map(|(i, ws)| {
+                ws.tables
+                    .iter()
+                    .find(|t| t.name.eq_ignore_ascii_case(name))
+                    .map(|t| (i, t))
+            })
+    }
+
+    /// Replace each structured reference in `formula` with the range it
+    /// covers, so `SUM(Sales[Qty])` becomes `SUM(Data!$B$2:$B$10)`. `cell`
+    /// is the (row, column) the formula sits in on `sheet_name`; `#This Row`
+    /// and unqualified references such as `[@Qty]` need it. Fails on a
+    /// reference that does not resolve.
+    pub fn expand_structured_refs(
+        &self,
+        sheet_name: &str,
+        formula: &str,
+        cell: Option<(u32, u32)>,
+    ) -> Result<String> {
+        let sheet = self
+            .sheet_position(sheet_name)
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(sheet_name.to_string()))?;
+        self.expand_structured_refs_at(sheet, formula, cell)
+    }
+
+    pub(crate) fn expand_structured_refs_at(
+        &self,
+        sheet: usize,
+        formula: &str,
+        cell: Option<(u32, u32)>,
+    ) -> Result<String> {
+        rewrite_structured_refs(formula, |name, group| {
+            let found = match name {
+                Some(name) => self.find_table(name),
+                None => cell.and_then(|(row, col)| {
+                    self.worksheets[sheet]
+                        .tables
+                        .iter()
+                        .find(|t| {
+                            parse_range(&t.range).is_ok_and(|((r1, c1), (r2, c2))| {
+                                (r1..=r2).contains(&row) && (c1..=c2).contains(&col)
+                            })
+                        })
+                        .map(|t| (sheet, t))
+                }),
+            };
+            let Some((idx, table)) = found else {
+                return match (name, &group) {
+                    (Some(name), _) => Err(RustypyxlError::custom(format!(
+                        "Table '{}' does not exist",
+                        name
+                    ))),
+                    (None, RefGroup::ThisRow(..)) => Err(RustypyxlError::custom(format!(
+                        "'{}' is not inside a table",
+                        group.text()
+                    ))),
+                    // e.g. the workbook part of `[1]Sheet1!A1`
+                    (None, RefGroup::Other(_)) => Ok(None),
+                };
+            };
+            let reference =
+                StructuredRef::parse(&format!("{}{}", name.unwrap_or(""), group.text()))?;
+            let range = table.structured_ref_range(&reference, cell.map(|(row, _)| row))?;
+            Ok(Some(qualify_sheet_range(&self.sheet_names[idx], &range)))
+        })
+    }
+
+    /// Rename the table `old` to `new` and rewrite the structured
+    /// references to it in cell formulas, data validations, conditional
+    /// formats, defined names and table formulas on every sheet, as Excel
+    /// does. Fails if `new` is not a valid table name or is already used by
+    /// another table or a defined name.
+    pub fn rename_table(&mut self, old: &str, new: &str) -> Result<()> {
+        validate_table_name(new)?;
+        let (sheet, table) = self
+            .find_table(old)
+            .ok_or_else(|| RustypyxlError::custom(format!("Table '{}' does not exist", old)))?;
+        let old = table.name.clone();
+        let taken = self
+            .worksheets
+            .iter()
+            .flat_map(|ws| &ws.tables)
+            .any(|t| t.name.eq_ignore_ascii_case(new) && t.name != old)
+            || self
+                .named_ranges
+                .iter()
+                .any(|n| n.name.eq_ignore_ascii_case(new));
+        if taken {
+            return Err(RustypyxlError::custom(format!(
+                "The name '{}' is already in use",
+                new
+            )));
+        }
+
+        self.rewrite_table_refs(|name, group| {
+            Ok(name
+                .filter(|n| n.eq_ignore_ascii_case(&old))
+                .map(|_| format!("{}{}", new, group.text())))
+        })?;
+        if let Some(table) = self.worksheets[sheet]
+            .tables
+            .iter_mut()
+            .find(|t| t.name == old)
+        {
+            table.name = new.to_string();
+            table.display_name = new.to_string();
+        }
+        Ok(())
+    }
+
+    /// Give the table `name` the new `range`, as Excel's Resize Table does.
+    /// The header row stays on its row and the new range must overlap the
+    /// old one. Columns still in the range keep their names and settings,
+    /// added ones are named from their header cells, and references to
+    /// columns that fall out become `#REF!`; other structured references
+    /// follow the table and need no change. Cells are not moved, so a
+    /// totals row must be written again with
+    /// [`Worksheet::add_table_totals_row`].
+    pub fn resize_table(&mut self, name: &str, range: &str) -> Result<()> {
+        let (sheet, table) = self
+            .find_table(name)
+            .ok_or_else(|| RustypyxlError::custom(format!("Table '{}' does not exist", name)))?;
+        let ((top, left), (bottom, right)) = parse_range(&table.range)?;
+        let ((new_top, new_left), (new_bottom, new_right)) = parse_range(range)?;
+        let invalid = |why: String| {
+            Err(RustypyxlError::custom(format!(
+                "Cannot resize table '{}' to {}: {}",
+                table.name, range, why
+            )))
+        };
+        if table.header_row && new_top != top {
+            return invalid(format!("the header row must stay on row {}", top));
+        }
+        if new_top > bottom || new_bottom < top || new_left > right || new_right < left {
+            return invalid("the new range must overlap the old one".to_string());
+        }
+        if new_bottom - new_top < table.header_row as u32 + table.totals_row as u32 {
+            return invalid("the table needs at least one data row".to_string());
+        }
+        let ws = &self.worksheets[sheet];
+        for other in ws.tables.iter().filter(|t| t.name != table.name) {
+            let ((r1, c1), (r2, c2)) = parse_range(&other.range)?;
+            if r1 <= new_bottom && new_top <= r2 && c1 <= new_right && new_left <= c2 {
+                return invalid(format!("it would overlap table '{}'", other.name));
+            }
+        }
+
+        let headers = ws.header_columns(table.header_row.then_some(new_top), new_left, new_right);
+        let mut next_id = table.columns.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+        let kept = |col: u32| {
+            (left..=right)
+                .contains(&col)
+                .then(|| table.columns.get((col - left) as usize))
+                .flatten()
+        };
+        let mut columns: Vec<TableColumn> =
+            (new_left..=new_right).filter_map(kept).cloned().collect();
+        for (col, header) in (new_left..=new_right).zip(headers) {
+            if kept(col).is_some() {
+                continue;
+            }
+            let mut column = header;
+            let base = column.name.clone();
+            let mut n = 2;
+            while columns
+                .iter()
+                .any(|c| c.name.eq_ignore_ascii_case(&column.name))
+            {
+                column.name = format!("{}{}", base, n);
+                n += 1;
+            }
+            column.id = next_id;
+            next_id += 1;
+            let at = (col - new_left) as usize;
+            columns.insert(at.min(columns.len()), column);
+        }
+        let dropped: Vec<String> = table
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !(new_left..=new_right).contains(&(left + *i as u32)))
+            .map(|(_, c)| c.name.clone())
+            .collect();
+        let table_name = table.name.clone();
+
+        if !dropped.is_empty() {
+            self.rewrite_table_refs(|name, group| {
+                let Some(name) = name.filter(|n| n.eq_ignore_ascii_case(&table_name)) else {
+                    return Ok(None);
+                };
+                let Ok(reference) = StructuredRef::parse(&format!("{}{}", name, group.text()))
+                else {
+                    return Ok(None);
+                };
+                let gone = reference.columns.is_some_and(|(first, last)| {
+                    dropped
+                        .iter()
+                        .any(|d| d.eq_ignore_ascii_case(&first) || d.eq_ignore_ascii_case(&last))
+                });
+                Ok(gone.then(|| "#REF!".to_string()))
+            })?;
+        }
+        if let Some(table) = self.worksheets[sheet]
+            .tables
+            .iter_mut()
+            .find(|t| t.name == table_name)
+        {
+            table.range = format!(
+                "{}:{}",
+                coordinate_from_row_col(new_top, new_left),
+                coordinate_from_row_col(new_bottom, new_right)
+            );
+            table.columns = columns;
+        }
+        Ok(())
+    }
+
+    /// Run [`rewrite_structured_refs`] with `rewrite` over every formula
+    /// that can hold a structured reference: cell formulas, data validation
+    /// and conditional format formulas, defined names, and tables'
+    /// calculated column and custom totals formulas.
+    fn rewrite_table_refs<F>(&mut self, mut rewrite: F) -> Result<()>
+    where
+        F: FnMut(Option<&str>, RefGroup<'_>) -> Result<Option<String>>,
+    {
+        let mut apply = |text: &mut String| -> Result<()> {
+            if text.contains('[') {
+                *text = rewrite_structured_refs(text, &mut rewrite)?;
+            }
+            Ok(())
+        };
+        for ws in self.worksheets.iter_mut().filter(|ws| ws.opaque.is_none()) {
+            for cell in ws.cells.values_mut() {
+                if let CellValue::Formula(formula) = &mut cell.value {
+                    apply(formula)?;
+                }
+            }
+            for dv in ws.data_validations.values_mut() {
+                for formula in [&mut dv.formula1, &mut dv.formula2].into_iter().flatten() {
+                    apply(formula)?;
+                }
+            }
+            for cf in &mut ws.conditional_formatting {
+                for rule in &mut cf.rules {
+                    for formula in [&mut rule.formula1, &mut rule.formula2]
+                        .into_iter()
+                        .flatten()
+                    {
+                        apply(formula)?;
+                    }
+                }
+            }
+            for table in &mut ws.tables {
+                for column in &mut table.columns {
+                    if let Some(formula) = &mut column.calculated_column_formula {
+                        apply(formula)?;
+                    }
+                    if let TotalsRowFunction::Custom(formula) = &mut column.totals_row_function {
+                        apply(formula)?;
+                    }
+                }
+            }
+        }
+        for named in &mut self.named_ranges {
+            apply(&mut named.range)?;
+        }
+        Ok(())
+    }
+}
+
 /// Structured reference specifiers.
 pub mod specifiers {
     /// All data (no headers or totals).
@@ -336,6 +1389,141 @@ mod tests {
         assert_eq!(table.columns[1].totals_row_function, TotalsRowFunction::Sum);
     }
 
+    #[test]
+    fn test_create_table_names_columns_from_headers() {
+        let mut ws = Worksheet::new("Sheet1".to_string());
+        ws.set_cell_value(1, 1, CellValue::from("Name"));
+        ws.set_cell_value(1, 3, CellValue::from("name"));
+        ws.set_cell_value(1, 4, CellValue::Number(2024.0));
+        let table = ws
+            .create_table("People", "a1:d5", TableStyle::Medium(9))
+            .unwrap();
+        assert_eq!(table.range, "A1:D5");
+        assert!(table.auto_filter && table.show_row_stripes);
+        let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Name", "Column2", "name2", "2024"]);
+    }
+
+    #[test]
+    fn test_create_table_rejects_conflicts() {
+        let mut ws = Worksheet::new("Sheet1".to_string());
+        ws.create_table("First", "A1:B4", TableStyle::default())
+            .unwrap();
+        assert!(ws
+            .create_table("first", "D1:E4", TableStyle::default())
+            .is_err());
+        assert!(ws
+            .create_table("Second", "B4:C6", TableStyle::default())
+            .is_err());
+        let second = ws
+            .create_table("Second", "C1:C4", TableStyle::default())
+            .unwrap();
+        assert_eq!(second.id, 2);
+    }
+
+    #[test]
+    fn test_validate_table_name() {
+        for ok in ["Sales", "_t", "\\x", "Q1.Data", "Tbl_2", "RC1x"] {
+            assert!(validate_table_name(ok).is_ok(), "{ok}");
+        }
+        for bad in [
+            "", "1st", "My Table", "A1", "xfd100", "R1C1", "r2", "C", "a-b",
+        ] {
+            assert!(validate_table_name(bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_totals_function_parse() {
+        assert_eq!(
+            TotalsRowFunction::parse("sum"),
+            Some(TotalsRowFunction::Sum)
+        );
+        assert_eq!(
+            TotalsRowFunction::parse("COUNTNUMS"),
+            Some(TotalsRowFunction::CountNums)
+        );
+        assert_eq!(
+            TotalsRowFunction::parse("none"),
+            Some(TotalsRowFunction::None)
+        );
+        assert_eq!(TotalsRowFunction::parse("median"), None);
+    }
+
+    #[test]
+    fn test_totals_row_formula() {
+        let mut table = Table::with_headers(1, "Sales", "A1:C5", &["Region", "Amount", "Qty #"]);
+        table.set_column_totals("Amount", TotalsRowFunction::Sum);
+        table.set_column_totals("Qty #", TotalsRowFunction::Count);
+        assert_eq!(table.totals_row_formula(&table.columns[0]), None);
+        assert_eq!(
+            table.totals_row_formula(&table.columns[1]).as_deref(),
+            Some("SUBTOTAL(109,Sales[Amount])")
+        );
+        assert_eq!(
+            table.totals_row_formula(&table.columns[2]).as_deref(),
+            Some("SUBTOTAL(103,Sales[Qty '#])")
+        );
+        table.set_column_totals("Amount", TotalsRowFunction::Custom("=MEDIAN(B2:B4)".into()));
+        assert_eq!(
+            table.totals_row_formula(&table.columns[1]).as_deref(),
+            Some("MEDIAN(B2:B4)")
+        );
+    }
+
+    #[test]
+    fn test_add_table_totals_row() {
+        let mut ws = Worksheet::new("S".to_string());
+        for (col, header) in ["Region", "Amount", "Qty"].iter().enumerate() {
+            ws.set_cell_value(1, col as u32 + 1, *header);
+        }
+        for row in 2..=4 {
+            ws.set_cell_value(row, 1, "North");
+            ws.set_cell_value(row, 2, CellValue::Number(row as f64));
+            ws.set_cell_value(row, 3, CellValue::Number(1.0));
+        }
+        ws.add_table(Table::new(1, "Sales", "A1:C4"));
+
+        let row = ws
+            .add_table_totals_row(
+                "sales",
+                &[
+                    ("Amount", TotalsRowFunction::Sum),
+                    ("qty", TotalsRowFunction::Count),
+                ],
+            )
+            .unwrap();
+        assert_eq!(row, 5);
+        let table = &ws.tables[0];
+        assert_eq!(table.range, "A1:C5");
+        assert!(table.totals_row);
+        assert_eq!(ws.get_cell_value(5, 1), Some(&CellValue::from("Total")));
+        assert_eq!(
+            ws.get_cell_value(5, 2),
+            Some(&CellValue::Formula("SUBTOTAL(109,Sales[Amount])".into()))
+        );
+        assert_eq!(
+            ws.get_cell_value(5, 3),
+            Some(&CellValue::Formula("SUBTOTAL(103,Sales[Qty])".into()))
+        );
+
+        // A second call rewrites the same row instead of growing the table.
+        let row = ws
+            .add_table_totals_row("Sales", &[("Amount", TotalsRowFunction::Average)])
+            .unwrap();
+        assert_eq!(row, 5);
+        assert_eq!(ws.tables[0].range, "A1:C5");
+        assert_eq!(
+            ws.get_cell_value(5, 2),
+            Some(&CellValue::Formula("SUBTOTAL(101,Sales[Amount])".into()))
+        );
+
+        assert!(ws
+            .add_table_totals_row("Sales", &[("Missing", TotalsRowFunction::Sum)])
+            .is_err());
+        assert!(ws.add_table_totals_row("Nope", &[]).is_err());
+    }
+
     #[test]
     fn test_structured_reference() {
         let table = Table::new(1, "Sales", "A1:C10");
@@ -419,4 +1607,286 @@ mod coverage_tests {
         let sref = t.structured_ref(Some("Sales"), None);
         assert!(sref.contains("Sales"));
     }
+
+    fn order_sheet() -> Worksheet {
+        let mut ws = Worksheet::new("S".to_string());
+        for (col, header) in ["Item", "Qty", "Unit Price", "Total"].iter().enumerate() {
+            ws.set_cell_value(1, col as u32 + 1, *header);
+        }
+        for row in 2..=4 {
+            ws.set_cell_value(row, 1, "Widget");
+            ws.set_cell_value(row, 2, CellValue::Number(row as f64));
+            ws.set_cell_value(row, 3, CellValue::Number(1.5));
+        }
+        ws
+    }
+
+    #[test]
+    fn calculated_column_in_table_uses_structured_refs() {
+        let mut ws = order_sheet();
+        ws.create_table("Orders", "A1:D4", TableStyle::default())
+            .unwrap();
+        let rows = ws
+            .add_calculated_column(4, "=[@Qty]*[@[Unit Price]]+SUM(orders[Qty])+B$2", None)
+            .unwrap();
+        assert_eq!(rows, (2, 4));
+        let expected =
+            "Orders[[#This Row],[Qty]]*Orders[[#This Row],[Unit Price]]+SUM(Orders[Qty])+B$2";
+        assert_eq!(
+            ws.tables[0].columns[3].calculated_column_formula.as_deref(),
+            Some(expected)
+        );
+        for row in 2..=4 {
+            assert_eq!(
+                ws.get_cell_value(row, 4),
+                Some(&CellValue::Formula(expected.to_string()))
+            );
+        }
+
+        // A partial fill writes the cells but is not the column's formula.
+        ws.tables[0].columns[3].calculated_column_formula = None;
+        ws.add_calculated_column(4, "[@Qty]", Some((3, 3))).unwrap();
+        assert!(ws.tables[0].columns[3].calculated_column_formula.is_none());
+        assert!(ws.add_calculated_column(4, "[@Qty]", Some((1, 4))).is_err());
+        assert!(ws.add_calculated_column(4, "[@Missing]", None).is_err());
+    }
+
+    #[test]
+    fn calculated_column_outside_table_uses_a1_refs() {
+        let mut ws = order_sheet();
+        ws.add_calculated_column(4, "=[@Qty]*[@[unit price]]*$F$1+A2", Some((2, 4)))
+            .unwrap();
+        assert_eq!(
+            ws.get_cell_value(2, 4),
+            Some(&CellValue::Formula("B2*C2*$F$1+A2".into()))
+        );
+        assert_eq!(
+            ws.get_cell_value(4, 4),
+            Some(&CellValue::Formula("B4*C4*$F$1+A4".into()))
+        );
+        // Text and other tables' references are left alone.
+        ws.add_calculated_column(5, "\"[@Qty]\"&Other[@Qty]&[@Item]", Some((2, 2)))
+            .unwrap();
+        assert_eq!(
+            ws.get_cell_value(2, 5),
+            Some(&CellValue::Formula("\"[@Qty]\"&Other[@Qty]&A2".into()))
+        );
+
+        assert!(ws.add_calculated_column(4, "[@Qty]", None).is_err());
+        assert!(ws
+            .add_calculated_column(4, "[@Nope]", Some((2, 4)))
+            .is_err());
+        assert!(ws
+            .add_calculated_column(4, "SUM([Qty])", Some((2, 4)))
+            .is_err());
+    }
+
+    #[test]
+    fn structured_refs_parse_and_display() {
+        let parsed = |text: &str| StructuredRef::parse(text).unwrap();
+        let r = parsed("Sales[[#Headers],[#Data],[Qty]:[Unit Price]]");
+        assert_eq!(r.table.as_deref(), Some("Sales"));
+        assert_eq!(r.items, vec![TableItem::Headers, TableItem::Data]);
+        assert_eq!(r.columns, Some(("Qty".into(), "Unit Price".into())));
+        assert_eq!(
+            r.to_string(),
+            "Sales[[#Headers],[#Data],[Qty]:[Unit Price]]"
+        );
+
+        let r = parsed("[@[Price '#1]]");
+        assert_eq!(r.table, None);
+        assert_eq!(r.items, vec![TableItem::ThisRow]);
+        assert_eq!(r.columns, Some(("Price #1".into(), "Price #1".into())));
+        assert_eq!(r.to_string(), "[[#This Row],[Price '#1]]");
+
+        assert_eq!(parsed("Sales[Qty]").to_string(), "Sales[Qty]");
+        assert_eq!(
+            parsed("Sales[[Unit Price]]").to_string(),
+            "Sales[[Unit Price]]"
+        );
+        assert_eq!(parsed("Sales[#all]").items, vec![TableItem::All]);
+        assert_eq!(parsed("Sales[#all]").to_string(), "Sales[#All]");
+        assert_eq!(parsed("Sales[]").to_string(), "Sales[]");
+        assert_eq!(parsed("T[@]").to_string(), "T[#This Row]");
+
+        for bad in [
+            "Sales",
+            "Sales[Qty",
+            "Sales[[Qty],[#Totals]]",
+            "Sales[[A],[B]]",
+            "Sales[[A]:]",
+            "a b[Qty]",
+        ] {
+            assert!(StructuredRef::parse(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn structured_refs_resolve_to_ranges() {
+        let table =
+            Table::with_headers(1, "Sales", "B2:D6", &["Item", "Qty", "Price"]).with_totals_row();
+        let range =
+            |text: &str, row| table.structured_ref_range(&StructuredRef::parse(text).unwrap(), row);
+        assert_eq!(range("Sales[Qty]", None).unwrap(), "C3:C5");
+        assert_eq!(range("Sales[]", None).unwrap(), "B3:D5");
+        assert_eq!(range("Sales[#All]", None).unwrap(), "B2:D6");
+        assert_eq!(range("Sales[[#Totals],[Price]]", None).unwrap(), "D6");
+        assert_eq!(
+            range("Sales[[#Headers],[#Data],[Item]:[Qty]]", None).unwrap(),
+            "B2:C5"
+        );
+        assert_eq!(range("Sales[[#This Row],[qty]]", Some(4)).unwrap(), "C4");
+        assert!(range("Sales[@Qty]", Some(6)).is_err());
+        assert!(range("Sales[@Qty]", None).is_err());
+        assert!(range("Sales[Missing]", None).is_err());
+        let bare = Table::new(2, "Bare", "A1:A3").without_header_row();
+        assert!(bare
+            .structured_ref_range(&StructuredRef::parse("Bare[#Headers]").unwrap(), None)
+            .is_err());
+    }
+
+    fn sales_workbook() -> Workbook {
+        let mut wb = Workbook::new();
+        wb.create_sheet(Some("Data".to_string())).unwrap();
+        wb.create_sheet(Some("Report".to_string())).unwrap();
+        let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+        for (col, header) in ["Item", "Qty", "Price"].iter().enumerate() {
+            ws.set_cell_value(1, col as u32 + 1, *header);
+        }
+        for row in 2..=4 {
+            ws.set_cell_value(row, 1, "Widget");
+            ws.set_cell_value(row, 2, CellValue::Number(row as f64));
+            ws.set_cell_value(row, 3, CellValue::Number(10.0));
+        }
+        ws.create_table("Sales", "A1:C4", TableStyle::default())
+            .unwrap();
+        ws.add_calculated_column(4, "[@Qty]*[@Price]", Some((2, 4)))
+            .unwrap();
+        wb
+    }
+
+    #[test]
+    fn structured_refs_evaluate() {
+        use crate::formula::FormulaValue;
+        let mut wb = sales_workbook();
+        let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+        ws.set_cell_value(
+            2,
+            5,
+            CellValue::Formula("[@Qty]*Sales[[#This Row],[Price]]".into()),
+        );
+        assert_eq!(
+            wb.expand_structured_refs("Report", "SUM(Sales[Qty])+\"[x]\"", None)
+                .unwrap(),
+            "SUM(Data!$B$2:$B$4)+\"[x]\""
+        );
+        assert_eq!(
+            wb.evaluate_formula("Report", "=SUM(Sales[Qty])").unwrap(),
+            FormulaValue::Number(9.0)
+        );
+        assert_eq!(
+            wb.evaluate_formula("Report", "=SUM(Sales[Missing])")
+                .unwrap(),
+            FormulaValue::Error("#REF!".into())
+        );
+        assert_eq!(
+            wb.evaluate_formula("Report", "=Sales[@Qty]").unwrap(),
+            FormulaValue::Error("#REF!".into())
+        );
+        // Cell D3 is outside the table, so its `[@Qty]` was written as B3.
+        assert_eq!(
+            wb.evaluate_cell("Data", 3, 4).unwrap(),
+            FormulaValue::Number(30.0)
+        );
+        // E2 sits outside the table too, so its bare `[@Qty]` cannot resolve.
+        assert_eq!(
+            wb.evaluate_cell("Data", 2, 5).unwrap(),
+            FormulaValue::Error("#REF!".into())
+        );
+    }
+
+    #[test]
+    fn rename_table_rewrites_references() {
+        let mut wb = sales_workbook();
+        let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+        ws.set_cell_value(
+            2,
+            3,
+            CellValue::Formula("Sales[[#This Row],[Qty]]*2".into()),
+        );
+        ws.tables[0].columns[2].calculated_column_formula =
+            Some("sales[[#This Row],[Qty]]*2".into());
+        wb.get_sheet_by_name_mut("Report").unwrap().set_cell_value(
+            1,
+            1,
+            CellValue::Formula("SUM(Sales[Qty])&\"Sales[Qty]\"&Other[Qty]".into()),
+        );
+        wb.create_named_range("Quantities".to_string(), "Sales[Qty]".to_string())
+            .unwrap();
+
+        wb.rename_table("SALES", "Orders").unwrap();
+        let data = wb.get_sheet_by_name("Data").unwrap();
+        assert_eq!(data.tables[0].name, "Orders");
+        assert_eq!(data.tables[0].display_name, "Orders");
+        assert_eq!(
+            data.get_cell_value(2, 3),
+            Some(&CellValue::Formula("Orders[[#This Row],[Qty]]*2".into()))
+        );
+        assert_eq!(
+            data.tables[0].columns[2]
+                .calculated_column_formula
+                .as_deref(),
+            Some("Orders[[#This Row],[Qty]]*2")
+        );
+        assert_eq!(
+            wb.get_sheet_by_name("Report").unwrap().get_cell_value(1, 1),
+            Some(&CellValue::Formula(
+                "SUM(Orders[Qty])&\"Sales[Qty]\"&Other[Qty]".into()
+            ))
+        );
+        assert_eq!(wb.named_ranges[0].range, "Orders[Qty]");
+
+        assert!(wb.rename_table("Orders", "Quantities").is_err());
+        assert!(wb.rename_table("Orders", "A1").is_err());
+        assert!(wb.rename_table("Missing", "Other").is_err());
+        wb.rename_table("Orders", "ORDERS").unwrap();
+    }
+
+    #[test]
+    fn resize_table_updates_columns_and_drops_references() {
+        let mut wb = sales_workbook();
+        let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+        ws.set_cell_value(1, 4, "Total");
+        ws.set_cell_value(1, 5, "Qty");
+        ws.tables[0].columns[2].totals_row_function = TotalsRowFunction::Sum;
+        wb.get_sheet_by_name_mut("Report").unwrap().set_cell_value(
+            1,
+            1,
+            CellValue::Formula("SUM(Sales[Price])+SUM(Sales[Qty])".into()),
+        );
+
+        wb.resize_table("Sales", "B1:E6").unwrap();
+        let table = &wb.get_sheet_by_name("Data").unwrap().tables[0];
+        assert_eq!(table.range, "B1:E6");
+        let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Qty", "Price", "Total", "Qty2"]);
+        let ids: Vec<u32> = table.columns.iter().map(|c| c.id).collect();
+        assert_eq!(ids, [2, 3, 4, 5]);
+        assert_eq!(table.columns[1].totals_row_function, TotalsRowFunction::Sum);
+        assert_eq!(
+            wb.evaluate_formula("Report", "=SUM(Sales[Qty])").unwrap(),
+            crate::formula::FormulaValue::Number(9.0)
+        );
+
+        wb.resize_table("Sales", "C1:E6").unwrap();
+        assert_eq!(
+            wb.get_sheet_by_name("Report").unwrap().get_cell_value(1, 1),
+            Some(&CellValue::Formula("SUM(Sales[Price])+SUM(#REF!)".into()))
+        );
+
+        assert!(wb.resize_table("Sales", "C2:E6").is_err());
+        assert!(wb.resize_table("Sales", "G1:H6").is_err());
+        assert!(wb.resize_table("Sales", "C1:E1").is_err());
+        assert!(wb.resize_table("Missing", "A1:B2").is_err());
+    }
 }
@@ -0,0 +1,226 @@
+//! Hidden, machine-readable tags on rows and cells.
+//!
+//! A tag is an arbitrary string (a record id, say) attached to a row or a
+//! cell so that a program can recognise its own rows after a person has
+//! edited the file. Tags are not kept in a custom part keyed by row number,
+//! because that goes stale the moment someone inserts or deletes rows.
+//! Each tag is saved as a hidden, sheet-scoped defined name whose reference
+//! points at the tagged row or cell, with the tag text in the name's comment.
+//! Excel re-points defined names as rows and columns move, so on load the
+//! tag is found wherever its row ended up. A tag whose row was deleted comes
+//! back as `#REF!` and is dropped.
+
+use crate::error::{Result, RustypyxlError};
+use crate::utils::parse_coordinate;
+use crate::workbook::{qualify_sheet_range, NamedRange};
+use crate::worksheet::Worksheet;
+
+/// Prefix of the defined names that carry tags.
+pub const TAG_NAME_PREFIX: &str = "_rpxTag";
+
+/// Longest tag Excel keeps: a defined name's comment holds 255 characters.
+pub const MAX_TAG_LEN: usize = 255;
+
+/// Check that `tag` fits in a defined-name comment.
+pub fn validate_tag(tag: &str) -> Result<()> {
+    if tag.is_empty() {
+        return Err(RustypyxlError::custom("Tag must not be empty"));
+    }
+    if tag.chars().count() > MAX_TAG_LEN {
+        return Err(RustypyxlError::custom(format!(
+            "Tag is longer than {} characters",
+            MAX_TAG_LEN
+        )));
+    }
+    Ok(())
+}
+
+impl Worksheet {
+    /// Tag a row (1-based), replacing any tag it already has.
+    pub fn set_row_tag<S: Into<String>>(&mut self, row: u32, tag: S) -> Result<()> {
+        let tag = tag.into();
+        validate_tag(&tag)?;
+        self.row_tags.insert(row, tag);
+        Ok(())
+    }
+
+    /// The tag on a row, if any.
+    pub fn get_row_tag(&self, row: u32) -> Option<&str> {
+        self.row_tags.get(&row).map(String::as_str)
+    }
+
+    /// Remove a row's tag, returning it.
+    pub fn remove_row_tag(&mut self, row: u32) -> Option<String> {
+        self.row_tags.remove(&row)
+    }
+
+    /// The row carrying `tag`, if any.
+    pub fn find_row_by_tag(&self, tag: &str) -> Option<u32> {
+        self.row_tags
+            .iter()
+            .filter(|(_, t)| t.as_str() == tag)
+            .map(|(row, _)| *row)
+            .min()
+    }
+
+    /// Tag a cell, replacing any tag it already has.
+    pub fn set_cell_tag<S: Into<String>>(&mut self, row: u32, column: u32, tag: S) -> Result<()> {
+        let tag = tag.into();
+        validate_tag(&tag)?;
+        self.cell_tags.insert((row, column), tag);
+        Ok(())
+    }
+
+    /// The tag on a cell, if any.
+    pub fn get_cell_tag(&self, row: u32, column: u32) -> Option<&str> {
+        self.cell_tags.get(&(row, column)).map(String::as_str)
+    }
+
+    /// Remove a cell's tag, returning it.
+    pub fn remove_cell_tag(&mut self, row: u32, column: u32) -> Option<String> {
+        self.cell_tags.remove(&(row, column))
+    }
+
+    /// The (row, column) of the cell carrying `tag`, if any.
+    pub fn find_cell_by_tag(&self, tag: &str) -> Option<(u32, u32)> {
+        self.cell_tags
+            .iter()
+            .filter(|(_, t)| t.as_str() == tag)
+            .map(|(pos, _)| *pos)
+            .min()
+    }
+}
+
+/// The hidden defined names that save every sheet's tags, numbered across
+/// the workbook in sheet, then row/column order.
+pub(crate) fn tag_defined_names(
+    sheet_names: &[String],
+    worksheets: &[Worksheet],
+) -> Vec<NamedRange> {
+    let mut names = Vec::new();
+    for (idx, (sheet, ws)) in sheet_names.iter().zip(worksheets).enumerate() {
+        let mut rows: Vec<_> = ws.row_tags.iter().collect();
+        rows.sort_unstable_by_key(|(row, _)| **row);
+        let mut cells: Vec<_> = ws.cell_tags.iter().collect();
+        cells.sort_unstable_by_key(|(pos, _)| **pos);
+
+        let targets =
+            rows.into_iter()
+                .map(|(row, tag)| (format!("${}:${}", row, row), tag))
+                .chain(cells.into_iter().map(|((row, col), tag)| {
+                    (crate::utils::coordinate_from_row_col(*row, *col), tag)
+                }));
+        for (target, tag) in targets {
+            names.push(NamedRange {
+                name: format!("{}{}", TAG_NAME_PREFIX, names.len() + 1),
+                range: qualify_sheet_range(sheet, &target),
+                local_sheet_id: Some(idx as u32),
+                hidden: true,
+                comment: Some(tag.clone()),
+            });
+        }
+    }
+    names
+}
+
+/// Move tag names out of a loaded workbook's defined names and onto the
+/// sheets they point at. Names whose reference no longer resolves (the row
+/// was deleted) are dropped.
+pub(crate) fn take_tags(named_ranges: &mut Vec<NamedRange>, worksheets: &mut [Worksheet]) {
+    named_ranges.retain(|nr| {
+        let Some(suffix) = nr.name.strip_prefix(TAG_NAME_PREFIX) else {
+            return true;
+        };
+        if suffix.is_empty() || !suffix.bytes().all(|b| b.is_ascii_digit()) {
+            return true;
+        }
+        let ws = nr
+            .local_sheet_id
+            .and_then(|idx| worksheets.get_mut(idx as usize));
+        if let (Some(ws), Some(tag), Some(target)) = (ws, &nr.comment, parse_target(&nr.range)) {
+            match target {
+                Target::Row(row) => ws.row_tags.insert(row, tag.clone()),
+                Target::Cell(row, col) => ws.cell_tags.insert((row, col), tag.clone()),
+            };
+        }
+        false
+    });
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    Row(u32),
+    Cell(u32, u32),
+}
+
+/// Parse `Sheet!$5:$5` or `'My Sheet'!$B$5`; `#REF!` and anything else
+/// gives None.
+fn parse_target(range: &str) -> Option<Target> {
+    let local = range.rsplit_once('!').map_or(range, |(_, r)| r);
+    let local = local.replace('$', "");
+    if let Some((a, b)) = local.split_once(':') {
+        let (a, b): (u32, u32) = (a.parse().ok()?, b.parse().ok()?);
+        return (a == b && a > 0).then_some(Target::Row(a));
+    }
+    parse_coordinate(&local)
+        .ok()
+        .map(|(row, col)| Target::Cell(row, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(parse_target("Data!$7:$7"), Some(Target::Row(7)));
+        assert_eq!(parse_target("'It''s'!$C$2"), Some(Target::Cell(2, 3)));
+        assert_eq!(parse_target("Data!#REF!"), None);
+        assert_eq!(parse_target("#REF!"), None);
+        assert_eq!(parse_target("Data!$2:$4"), None);
+    }
+
+    #[test]
+    fn test_names_round_trip_through_take_tags() {
+        let mut ws = Worksheet::new("My Data");
+        ws.set_row_tag(4, "order-17").unwrap();
+        ws.set_cell_tag(2, 3, "total").unwrap();
+        let names = tag_defined_names(&["My Data".to_string()], std::slice::from_ref(&ws));
+        let refs: Vec<(&str, &str)> = names
+            .iter()
+            .map(|n| (n.name.as_str(), n.range.as_str()))
+            .collect();
+        assert_eq!(
+            refs,
+            [
+                ("_rpxTag1", "'My Data'!$4:$4"),
+                ("_rpxTag2", "'My Data'!$C$2")
+            ]
+        );
+        assert!(names
+            .iter()
+            .all(|n| n.hidden && n.local_sheet_id == Some(0)));
+
+        let mut all = names;
+        all.push(NamedRange {
+            name: "_rpxTagged".to_string(),
+            range: "Sheet1!$A$1".to_string(),
+            local_sheet_id: None,
+            hidden: false,
+            comment: None,
+        });
+        let mut sheets = vec![Worksheet::new("My Data")];
+        take_tags(&mut all, &mut sheets);
+        assert_eq!(all.len(), 1, "unrelated names are kept");
+        assert_eq!(sheets[0].find_row_by_tag("order-17"), Some(4));
+        assert_eq!(sheets[0].get_cell_tag(2, 3), Some("total"));
+    }
+
+    #[test]
+    fn test_tag_length_is_limited() {
+        let mut ws = Worksheet::new("S");
+        assert!(ws.set_row_tag(1, "").is_err());
+        assert!(ws.set_row_tag(1, "x".repeat(256)).is_err());
+        assert!(ws.set_row_tag(1, "é".repeat(255)).is_ok());
+    }
+}
@@ -0,0 +1,322 @@
+//! Plain-text table rendering.
+//!
+//! [`Worksheet::to_text_grid`] lays a sheet, or a range of it, out as aligned
+//! text for logs, CI diffs and terminal previews. Cells show their formatted
+//! value; numbers and dates sit on the right and booleans in the middle, as
+//! under Excel's general alignment, unless the cell is aligned otherwise.
+//! Merged cells show their value in the top-left cell only.
+
+use std::collections::HashSet;
+
+use crate::cell::CellValue;
+use crate::error::Result;
+use crate::utils::parse_coordinate;
+use crate::worksheet::{CellData, Worksheet};
+
+/// Excel's default column width, in characters.
+const DEFAULT_COLUMN_WIDTH: f64 = 8.43;
+
+/// How wide each column of a text grid is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WidthPolicy {
+    /// As wide as the column's longest value.
+    #[default]
+    Content,
+    /// The sheet's column width in characters. Text that does not fit is cut
+    /// short with `…`; numbers that do not fit show as `###`, as in Excel.
+    Sheet,
+    /// The same width for every column, cut as for `Sheet`.
+    Fixed(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Debug, Clone)]
+struct GridCell {
+    text: String,
+    align: Align,
+    /// A number or date, shown as `###` when cut.
+    numeric: bool,
+}
+
+impl Default for GridCell {
+    fn default() -> Self {
+        GridCell {
+            text: String::new(),
+            align: Align::Left,
+            numeric: false,
+        }
+    }
+}
+
+impl Worksheet {
+    /// Render `range` ("A1:D20"; the used range when `None`) as an aligned
+    /// text table, one line per row. `markdown` gives a pipe table with the
+    /// first row as its header; otherwise the grid is boxed in `+`, `-` and
+    /// `|`. An empty sheet renders as an empty string.
+    pub fn to_text_grid(
+        &self,
+        range: Option<&str>,
+        width_policy: WidthPolicy,
+        markdown: bool,
+    ) -> Result<String> {
+        let Some((min_row, min_col, max_row, max_col)) = self.render_bounds(range)? else {
+            return Ok(String::new());
+        };
+
+        let mut covered = HashSet::new();
+        for (start, end) in &self.merged_cells {
+            if let (Ok((r1, c1)), Ok((r2, c2))) = (parse_coordinate(start), parse_coordinate(end)) {
+                for row in r1..=r2 {
+                    for col in c1..=c2 {
+                        if (row, col) != (r1, c1) {
+                            covered.insert((row, col));
+                        }
+                    }
+                }
+            }
+        }
+
+        let rows: Vec<Vec<GridCell>> = (min_row..=max_row)
+            .map(|row| {
+                (min_col..=max_col)
+                    .map(|col| match self.get_cell(row, col) {
+                        Some(cell) if !covered.contains(&(row, col)) => grid_cell(&cell, markdown),
+                        _ => GridCell::default(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let widths: Vec<usize> = (min_col..=max_col)
+            .enumerate()
+            .map(|(i, col)| match width_policy {
+                WidthPolicy::Content => rows
+                    .iter()
+                    .map(|row| row[i].text.chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(if markdown { 3 } else { 1 }),
+                WidthPolicy::Sheet => {
+                    let width = self
+                        .column_dimensions
+                        .get(&col)
+                        .copied()
+                        .unwrap_or(DEFAULT_COLUMN_WIDTH);
+                    width.floor().max(1.0) as usize
+                }
+                WidthPolicy::Fixed(width) => width.max(1),
+            })
+            .collect();
+
+        let mut out = String::new();
+        if markdown {
+            for (index, row) in rows.iter().enumerate() {
+                push_row(&mut out, row, &widths);
+                if index == 0 {
+                    out.push('|');
+                    for (i, width) in widths.iter().enumerate() {
+                        // Right-align a column whose values are all numbers.
+                        let mut body = rows[1..].iter().map(|row| &row[i]);
+                        let right = body.clone().any(|c| !c.text.is_empty())
+                            && body.all(|c| c.text.is_empty() || c.numeric);
+                        out.push(' ');
+                        if right && *width > 1 {
+                            out.push_str(&"-".repeat(width - 1));
+                            out.push(':');
+                        } else {
+                            out.push_str(&"-".repeat(*width));
+                        }
+                        out.push_str(" |");
+                    }
+                    out.push('\n');
+                }
+            }
+        } else {
+            let rule: String = widths
+                .iter()
+                .map(|w| format!("+{}", "-".repeat(w + 2)))
+                .collect::<String>()
+                + "+\n";
+            out.push_str(&rule);
+            for row in &rows {
+                push_row(&mut out, row, &widths);
+            }
+            out.push_str(&rule);
+        }
+        Ok(out)
+    }
+}
+
+/// The cell's display text on one line, and how it sits in its column.
+fn grid_cell(cell: &CellData, markdown: bool) -> GridCell {
+    let mut text = cell.formatted_value().replace(['\r', '\n'], " ");
+    if markdown {
+        text = text.replace('|', "\\|");
+    }
+    let numeric = match &cell.value {
+        CellValue::Number(_) | CellValue::Date(_) => true,
+        CellValue::Formula(_) => {
+            cell.cached_formula_value.is_some() && matches!(cell.data_type, None | Some("n"))
+        }
+        _ => false,
+    };
+    let horizontal = cell
+        .style
+        .as_ref()
+        .and_then(|s| s.alignment.as_ref())
+        .and_then(|a| a.horizontal.as_deref());
+    let align = match horizontal {
+        Some("left") => Align::Left,
+        Some("center" | "centerContinuous") => Align::Center,
+        Some("right") => Align::Right,
+        _ if numeric => Align::Right,
+        _ if matches!(cell.value, CellValue::Boolean(_)) => Align::Center,
+        _ => Align::Left,
+    };
+    GridCell {
+        text,
+        align,
+        numeric,
+    }
+}
+
+fn push_row(out: &mut String, row: &[GridCell], widths: &[usize]) {
+    out.push('|');
+    for (cell, &width) in row.iter().zip(widths) {
+        out.push(' ');
+        out.push_str(&fit(cell, width));
+        out.push_str(" |");
+    }
+    out.push('\n');
+}
+
+/// The cell's text padded or cut to exactly `width` characters.
+fn fit(cell: &GridCell, width: usize) -> String {
+    let len = cell.text.chars().count();
+    if len > width {
+        if cell.numeric {
+            return "#".repeat(width);
+        }
+        let mut cut: String = cell.text.chars().take(width - 1).collect();
+        cut.push('…');
+        return cut;
+    }
+    let pad = width - len;
+    let (left, right) = match cell.align {
+        Align::Left => (0, pad),
+        Align::Center => (pad / 2, pad - pad / 2),
+        Align::Right => (pad, 0),
+    };
+    format!("{}{}{}", " ".repeat(left), cell.text, " ".repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{Alignment, CellStyle};
+    use std::sync::Arc;
+
+    fn sheet() -> Worksheet {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_value(1, 1, "Item");
+        ws.set_cell_value(1, 2, "Qty");
+        ws.set_cell_value(1, 3, "Paid");
+        ws.set_cell_value(2, 1, "Widget");
+        ws.set_cell_value(2, 2, 12.0);
+        ws.set_cell_value(2, 3, true);
+        ws.set_cell_value(3, 1, "Gear | large");
+        ws.set_cell_value(3, 2, 1234.5);
+        ws.set_cell_number_format(3, 2, "#,##0.00");
+        ws
+    }
+
+    #[test]
+    fn test_plain_grid() {
+        let grid = sheet()
+            .to_text_grid(None, WidthPolicy::Content, false)
+            .unwrap();
+        assert_eq!(
+            grid,
+            "+--------------+----------+------+\n\
+             | Item         | Qty      | Paid |\n\
+             | Widget       |       12 | TRUE |\n\
+             | Gear | large | 1,234.50 |      |\n\
+             +--------------+----------+------+\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_grid() {
+        let grid = sheet()
+            .to_text_grid(Some("A1:B3"), WidthPolicy::Content, true)
+            .unwrap();
+        assert_eq!(
+            grid,
+            "| Item          | Qty      |\n\
+             | ------------- | -------: |\n\
+             | Widget        |       12 |\n\
+             | Gear \\| large | 1,234.50 |\n"
+        );
+    }
+
+    #[test]
+    fn test_sheet_widths_cut_text_and_hide_numbers() {
+        let mut ws = sheet();
+        ws.column_dimensions.insert(1, 4.0);
+        ws.column_dimensions.insert(2, 5.5);
+        let grid = ws
+            .to_text_grid(Some("A2:B3"), WidthPolicy::Sheet, false)
+            .unwrap();
+        assert_eq!(
+            grid,
+            "+------+-------+\n\
+             | Wid… |    12 |\n\
+             | Gea… | ##### |\n\
+             +------+-------+\n"
+        );
+        let fixed = ws
+            .to_text_grid(Some("A1"), WidthPolicy::Fixed(2), false)
+            .unwrap();
+        assert_eq!(fixed, "+----+\n| I… |\n+----+\n");
+    }
+
+    #[test]
+    fn test_alignment_and_merges() {
+        let mut ws = Worksheet::new("S".to_string());
+        ws.set_cell_value(1, 1, "Title");
+        ws.set_cell_value(1, 2, "hidden by the merge");
+        ws.merged_cells.push(("A1".to_string(), "B1".to_string()));
+        ws.set_cell_value(2, 1, 7.0);
+        ws.get_or_create_cell_mut(2, 1).style = Some(Arc::new(CellStyle {
+            alignment: Some(Alignment {
+                horizontal: Some("left".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        ws.set_cell_value(2, 2, "wide text");
+        let grid = ws.to_text_grid(None, WidthPolicy::Content, false).unwrap();
+        assert_eq!(
+            grid,
+            "+-------+-----------+\n\
+             | Title |           |\n\
+             | 7     | wide text |\n\
+             +-------+-----------+\n"
+        );
+    }
+
+    #[test]
+    fn test_empty_sheet() {
+        let ws = Worksheet::new("S".to_string());
+        assert_eq!(
+            ws.to_text_grid(None, WidthPolicy::Content, true).unwrap(),
+            ""
+        );
+    }
+}
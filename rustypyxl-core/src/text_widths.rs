@@ -0,0 +1,102 @@
+//! Per-column text width statistics.
+//!
+//! [`Worksheet::column_text_widths`] measures the displayed text of every
+//! populated cell the way autofit does (values rendered under their number
+//! format, wide characters counting double) and summarises each column, so
+//! layout code can pick widths, spot outliers or decide to wrap without
+//! pulling the data out of the sheet.
+
+use std::collections::BTreeMap;
+
+use crate::error::Result;
+use crate::worksheet::{autofit_bounds, Worksheet};
+
+/// Text width summary for one column, in Excel character units.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnWidthStats {
+    /// Column number (1-based).
+    pub column: u32,
+    /// Number of populated cells measured.
+    pub count: usize,
+    /// Width of the widest displayed value.
+    pub max: f64,
+    /// 95th percentile width (nearest rank), which ignores the odd very long
+    /// value that would otherwise blow a column out.
+    pub p95: f64,
+    /// Mean width.
+    pub mean: f64,
+}
+
+impl ColumnWidthStats {
+    fn from_lengths(column: u32, mut lengths: Vec<f64>) -> Self {
+        lengths.sort_by(f64::total_cmp);
+        let count = lengths.len();
+        let rank = ((count as f64) * 0.95).ceil() as usize;
+        ColumnWidthStats {
+            column,
+            count,
+            max: lengths[count - 1],
+            p95: lengths[rank.max(1) - 1],
+            mean: lengths.iter().sum::<f64>() / count as f64,
+        }
+    }
+}
+
+impl Worksheet {
+    /// Width statistics for each populated column, in column order. `range`
+    /// ("A2:D500", "B:D" or a single cell) limits the cells measured; `None`
+    /// measures the whole sheet. Lengths are those autofit works from:
+    /// characters of the displayed value, before font scaling and padding.
+    pub fn column_text_widths(&self, range: Option<&str>) -> Result<Vec<ColumnWidthStats>> {
+        let bounds = match range {
+            Some(range) => autofit_bounds(range)?,
+            None => (1, 1, u32::MAX, u32::MAX),
+        };
+        let mut lengths: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+        self.for_each_text_width(bounds, |column, _, length| {
+            lengths.entry(column).or_default().push(length);
+        });
+        Ok(lengths
+            .into_iter()
+            .map(|(column, lengths)| ColumnWidthStats::from_lengths(column, lengths))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::CellValue;
+
+    #[test]
+    fn test_column_text_widths() {
+        let mut ws = Worksheet::new("Sheet1".to_string());
+        ws.set_cell_value(1, 1, CellValue::from("Name"));
+        for row in 2..=21 {
+            ws.set_cell_value(row, 1, CellValue::from("abcde"));
+        }
+        ws.set_cell_value(22, 1, CellValue::from("a much longer outlier value"));
+        ws.set_cell_value(1, 2, CellValue::Number(1234567.0));
+        ws.set_cell_number_format(1, 2, "#,##0");
+        ws.set_cell_value(2, 2, CellValue::from("東京"));
+
+        let stats = ws.column_text_widths(None).unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let a = &stats[0];
+        assert_eq!((a.column, a.count), (1, 22));
+        assert_eq!(a.max, 27.0);
+        assert_eq!(a.p95, 5.0);
+
+        let b = &stats[1];
+        assert_eq!(b.max, 9.0, "measured as 1,234,567");
+        assert_eq!(b.p95, 9.0);
+        assert_eq!(b.mean, 6.5, "wide characters count double");
+
+        let header_only = ws.column_text_widths(Some("A1:B1")).unwrap();
+        assert_eq!(header_only[0].max, 4.0);
+        assert_eq!(header_only[1].count, 1);
+        assert!(ws.column_text_widths(Some("C:C")).unwrap().is_empty());
+        assert!(ws.column_text_widths(Some("nonsense")).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Resolving theme and indexed colors to concrete values.
+//!
+//! A [`Color`] that names a theme slot or a legacy palette index has no value
+//! of its own: it depends on the workbook's theme part
+//! (`xl/theme/theme1.xml`) and on the `<indexedColors>` override that
+//! styles.xml may carry. [`Theme`] holds both, parsed on load or the Office
+//! defaults for new workbooks, and [`Color::to_rgb`] turns any color into
+//! `FFRRGGBB` hex with its tint applied.
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+use crate::style::Color;
+
+/// The color scheme slots in theme1.xml order.
+const SCHEME_SLOTS: [&str; 12] = [
+    "dk1", "lt1", "dk2", "lt2", "accent1", "accent2", "accent3", "accent4", "accent5", "accent6",
+    "hlink", "folHlink",
+];
+
+/// The Office 2013+ theme colors, in [`SCHEME_SLOTS`] order.
+const OFFICE_COLORS: [&str; 12] = [
+    "000000", "FFFFFF", "44546A", "E7E6E6", "4472C4", "ED7D31", "A5A5A5", "FFC000", "5B9BD5",
+    "70AD47", "0563C1", "954F72",
+];
+
+/// The legacy 64-color palette `indexed` refers to unless styles.xml
+/// overrides it.
+const DEFAULT_INDEXED: [&str; 64] = [
+    "000000", "FFFFFF", "FF0000", "00FF00", "0000FF", "FFFF00", "FF00FF", "00FFFF", //
+    "000000", "FFFFFF", "FF0000", "00FF00", "0000FF", "FFFF00", "FF00FF", "00FFFF", //
+    "800000", "008000", "000080", "808000", "800080", "008080", "C0C0C0", "808080", //
+    "9999FF", "993366", "FFFFCC", "CCFFFF", "660066", "FF8080", "0066CC", "CCCCFF", //
+    "000080", "FF00FF", "FFFF00", "00FFFF", "800080", "800000", "008080", "0000FF", //
+    "00CCFF", "CCFFFF", "CCFFCC", "FFFF99", "99CCFF", "FF99CC", "CC99FF", "FFCC99", //
+    "3366FF", "33CCCC", "99CC00", "FFCC00", "FF9900", "FF6600", "666699", "969696", //
+    "003366", "339966", "003300", "333300", "993300", "993366", "333399", "333333",
+];
+
+/// Indexed 64 and 65 are the system foreground and background, which Excel
+/// shows as black and white.
+const SYSTEM_FOREGROUND: u32 = 64;
+const SYSTEM_BACKGROUND: u32 = 65;
+
+/// A workbook's theme color scheme and indexed palette.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    /// RRGGBB hex of each scheme slot: dk1, lt1, dk2, lt2, accent1-6, hlink,
+    /// folHlink.
+    pub colors: [String; 12],
+    /// RRGGBB hex of the indexed palette.
+    pub indexed: Vec<String>,
+}
+
+impl Default for Theme {
+    /// The Office theme and the standard indexed palette.
+    fn default() -> Self {
+        Theme {
+            colors: OFFICE_COLORS.map(String::from),
+            indexed: DEFAULT_INDEXED.iter().map(|&c| c.to_string()).collect(),
+        }
+    }
+}
+
+impl Theme {
+    /// Read the color scheme of a theme part. Slots the part does not define
+    /// keep their Office default; system colors use their `lastClr`.
+    pub fn parse(xml: &[u8]) -> Self {
+        let mut theme = Theme::default();
+        let mut reader = Reader::from_reader(xml);
+        let mut buf = Vec::new();
+        let mut in_scheme = false;
+        let mut slot: Option<usize> = None;
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    let name = e.local_name();
+                    let name = name.as_ref();
+                    if name == b"clrScheme" {
+                        in_scheme = true;
+                    } else if in_scheme {
+                        if let Some(i) = SCHEME_SLOTS.iter().position(|s| s.as_bytes() == name) {
+                            slot = Some(i);
+                        } else if let Some(i) = slot {
+                            let attr = match name {
+                                b"srgbClr" => b"val".as_slice(),
+                                b"sysClr" => b"lastClr".as_slice(),
+                                _ => continue,
+                            };
+                            if let Some(hex) = attr_value(&e, attr).and_then(normalize_rgb) {
+                                theme.colors[i] = hex;
+                            }
+                        }
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    let name = e.local_name();
+                    if name.as_ref() == b"clrScheme" {
+                        break;
+                    }
+                    if SCHEME_SLOTS.iter().any(|s| s.as_bytes() == name.as_ref()) {
+                        slot = None;
+                    }
+                }
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        theme
+    }
+
+    /// Replace the indexed palette with styles.xml's `<indexedColors>`, when
+    /// it has one.
+    pub fn read_indexed_colors(&mut self, styles_xml: &[u8]) {
+        let mut reader = Reader::from_reader(styles_xml);
+        let mut buf = Vec::new();
+        let mut in_indexed = false;
+        let mut palette = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"indexedColors" => {
+                    in_indexed = true;
+                }
+                Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                    if in_indexed && e.local_name().as_ref() == b"rgbColor" =>
+                {
+                    let hex = attr_value(&e, b"rgb").and_then(normalize_rgb);
+                    palette.push(hex.unwrap_or_else(|| "000000".to_string()));
+                }
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"indexedColors" => break,
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+        if !palette.is_empty() {
+            self.indexed = palette;
+        }
+    }
+
+    /// RRGGBB of a `theme` color index. Indexes 0-3 are lt1, dk1, lt2, dk2:
+    /// spreadsheets swap each light/dark pair relative to the scheme order.
+    pub fn theme_color(&self, index: u32) -> Option<&str> {
+        let slot = match index {
+            0 => 1,
+            1 => 0,
+            2 => 3,
+            3 => 2,
+            n => n as usize,
+        };
+        self.colors.get(slot).map(String::as_str)
+    }
+
+    /// RRGGBB of an `indexed` color.
+    pub fn indexed_color(&self, index: u32) -> Option<&str> {
+        match index {
+            SYSTEM_FOREGROUND => Some("000000"),
+            SYSTEM_BACKGROUND => Some("FFFFFF"),
+            n => self.indexed.get(n as usize).map(String::as_str),
+        }
+    }
+}
+
+impl Color {
+    /// The concrete `FFRRGGBB` value of this color under `theme`, with the
+    /// tint applied. An explicit `rgb` wins over `theme`, which wins over
+    /// `indexed`. None when the color is empty or refers past the end of the
+    /// scheme or palette.
+    pub fn to_rgb(&self, theme: &Theme) -> Option<String> {
+        let base = if let Some(argb) = self.argb() {
+            normalize_rgb(&argb)?
+        } else if let Some(index) = self.theme {
+            theme.theme_color(index)?.to_string()
+        } else {
+            theme.indexed_color(self.indexed?)?.to_string()
+        };
+        let rgb = match self.tint {
+            Some(tint) if tint != 0.0 => apply_tint(&base, tint),
+            _ => base,
+        };
+        Some(format!("FF{}", rgb))
+    }
+}
+
+fn attr_value(e: &quick_xml::events::BytesStart, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|a| a.key.local_name().as_ref() == name)
+        .and_then(|a| a.unescape_value().ok())
+        .map(|v| v.into_owned())
+}
+
+/// Uppercase RRGGBB from RRGGBB or AARRGGBB hex, with or without '#'.
+fn normalize_rgb(hex: impl AsRef<str>) -> Option<String> {
+    let hex = hex.as_ref();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    let rgb = match hex.len() {
+        6 => hex,
+        8 => &hex[2..],
+        _ => return None,
+    };
+    rgb.chars()
+        .all(|c| c.is_ascii_hexdigit())
+        .then(|| rgb.to_ascii_uppercase())
+}
+
+/// Lighten (tint > 0) or darken (tint < 0) RRGGBB the way Excel does: by
+/// moving the color's HLS luminance toward white or black.
+fn apply_tint(rgb: &str, tint: f64) -> String {
+    let channel = |i: usize| f64::from(u8::from_str_radix(&rgb[i..i + 2], 16).unwrap_or(0)) / 255.0;
+    let (h, l, s) = rgb_to_hls(channel(0), channel(2), channel(4));
+    let tint = tint.clamp(-1.0, 1.0);
+    let l = if tint < 0.0 {
+        l * (1.0 + tint)
+    } else {
+        l * (1.0 - tint) + tint
+    };
+    let (r, g, b) = hls_to_rgb(h, l, s);
+    let byte = |v: f64| (v * 255.0).round().clamp(0.0, 255.0) as u8;
+    format!("{:02X}{:02X}{:02X}", byte(r), byte(g), byte(b))
+}
+
+fn rgb_to_hls(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    if max == min {
+        return (0.0, l, 0.0);
+    }
+    let d = max - min;
+    let s = if l <= 0.5 {
+        d / (max + min)
+    } else {
+        d / (2.0 - max - min)
+    };
+    let h = if max == r {
+        (g - b) / d
+    } else if max == g {
+        2.0 + (b - r) / d
+    } else {
+        4.0 + (r - g) / d
+    };
+    ((h / 6.0).rem_euclid(1.0), l, s)
+}
+
+fn hls_to_rgb(h: f64, l: f64, s: f64) -> (f64, f64, f64) {
+    if s == 0.0 {
+        return (l, l, l);
+    }
+    let m2 = if l <= 0.5 {
+        l * (1.0 + s)
+    } else {
+        l + s - l * s
+    };
+    let m1 = 2.0 * l - m2;
+    let value = |hue: f64| {
+        let hue = hue.rem_euclid(1.0);
+        if hue < 1.0 / 6.0 {
+            m1 + (m2 - m1) * hue * 6.0
+        } else if hue < 0.5 {
+            m2
+        } else if hue < 2.0 / 3.0 {
+            m1 + (m2 - m1) * (2.0 / 3.0 - hue) * 6.0
+        } else {
+            m1
+        }
+    };
+    (value(h + 1.0 / 3.0), value(h), value(h - 1.0 / 3.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THEME_XML: &str = r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+<a:themeElements><a:clrScheme name="Custom">
+<a:dk1><a:sysClr val="windowText" lastClr="111111"/></a:dk1>
+<a:lt1><a:sysClr val="window" lastClr="FEFEFE"/></a:lt1>
+<a:dk2><a:srgbClr val="1F497D"/></a:dk2>
+<a:lt2><a:srgbClr val="eeece1"/></a:lt2>
+<a:accent1><a:srgbClr val="4F81BD"/></a:accent1>
+</a:clrScheme></a:themeElements></a:theme>"#;
+
+    #[test]
+    fn parses_scheme_and_keeps_defaults_for_missing_slots() {
+        let theme = Theme::parse(THEME_XML.as_bytes());
+        assert_eq!(theme.theme_color(0), Some("FEFEFE"));
+        assert_eq!(theme.theme_color(1), Some("111111"));
+        assert_eq!(theme.theme_color(2), Some("EEECE1"));
+        assert_eq!(theme.theme_color(3), Some("1F497D"));
+        assert_eq!(theme.theme_color(4), Some("4F81BD"));
+        assert_eq!(theme.theme_color(5), Some("ED7D31"));
+        assert_eq!(theme.theme_color(12), None);
+    }
+
+    #[test]
+    fn resolves_rgb_theme_and_indexed() {
+        let theme = Theme::default();
+        assert_eq!(
+            Color::rgb("#336699").to_rgb(&theme).as_deref(),
+            Some("FF336699")
+        );
+        assert_eq!(Color::theme(1).to_rgb(&theme).as_deref(), Some("FF000000"));
+        assert_eq!(Color::theme(10).to_rgb(&theme).as_deref(), Some("FF0563C1"));
+        assert_eq!(
+            Color::indexed(2).to_rgb(&theme).as_deref(),
+            Some("FFFF0000")
+        );
+        assert_eq!(
+            Color::indexed(64).to_rgb(&theme).as_deref(),
+            Some("FF000000")
+        );
+        assert_eq!(Color::indexed(99).to_rgb(&theme), None);
+        assert_eq!(Color::default().to_rgb(&theme), None);
+    }
+
+    #[test]
+    fn applies_tint() {
+        let theme = Theme::default();
+        let white = Color::theme(0).with_tint(-0.1499984740745262);
+        assert_eq!(white.to_rgb(&theme).as_deref(), Some("FFD9D9D9"));
+        let black = Color::theme(1).with_tint(0.5);
+        assert_eq!(black.to_rgb(&theme).as_deref(), Some("FF808080"));
+        assert_eq!(
+            Color::rgb("FF4472C4")
+                .with_tint(0.0)
+                .to_rgb(&theme)
+                .as_deref(),
+            Some("FF4472C4")
+        );
+
+        // Excel's swatches for the Office accent1 shades, which it rounds
+        // through its own integer HLS; within two steps per channel.
+        let close = |tint, expected: &str| {
+            let got = Color::theme(4).with_tint(tint).to_rgb(&theme).unwrap();
+            for i in (2..8).step_by(2) {
+                let a = i32::from_str_radix(&got[i..i + 2], 16).unwrap();
+                let b = i32::from_str_radix(&expected[i - 2..i], 16).unwrap();
+                assert!((a - b).abs() <= 2, "{} vs {}", got, expected);
+            }
+        };
+        close(0.7999816888943144, "D9E1F2");
+        close(0.5999938962981048, "B4C6E7");
+        close(-0.249977111117893, "305496");
+        close(-0.499984740745262, "203764");
+    }
+
+    #[test]
+    fn custom_indexed_palette() {
+        let mut theme = Theme::default();
+        theme.read_indexed_colors(
+            br#"<styleSheet><colors><indexedColors>
+<rgbColor rgb="FF010203"/><rgbColor rgb="FFAABBCC"/>
+</indexedColors></colors></styleSheet>"#,
+        );
+        assert_eq!(theme.indexed_color(1), Some("AABBCC"));
+        assert_eq!(theme.indexed_color(2), None);
+        assert_eq!(theme.indexed_color(65), Some("FFFFFF"));
+
+        let mut untouched = Theme::default();
+        untouched.read_indexed_colors(b"<styleSheet/>");
+        assert_eq!(untouched, Theme::default());
+    }
+}
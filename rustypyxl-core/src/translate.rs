@@ -0,0 +1,297 @@
+//! Formula translation: rewrite the relative references in a formula as if
+//! it were copied or moved by some number of rows and columns, the way
+//! openpyxl's `Translator` and Excel's copy/paste do.
+//!
+//! `A1`, `B$2`, `$C3`, `A1:B2`, whole-row (`3:5`) and whole-column (`B:D`)
+//! references move; the `$`-anchored parts stay put. A reference pushed off
+//! the sheet becomes `#REF!`. String literals, sheet names, function names,
+//! defined names and structured references (`Table[Col]`) are left alone.
+
+use crate::utils::{column_to_letter, letter_to_column, MAX_COLUMN, MAX_ROW};
+use std::collections::HashMap;
+
+/// Translate `formula` (with or without its leading `=`) by `rows` and
+/// `cols`.
+pub fn translate_formula(formula: &str, rows: i64, cols: i64) -> String {
+    if rows == 0 && cols == 0 {
+        return formula.to_string();
+    }
+    let chars: Vec<char> = formula.chars().collect();
+    let mut out = String::with_capacity(formula.len() + 8);
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => i = copy_quoted(&chars, i, '"', &mut out),
+            '\'' => i = copy_quoted(&chars, i, '\'', &mut out),
+            '[' => i = copy_bracketed(&chars, i, &mut out),
+            c if is_token_char(c) => {
+                let end = token_end(&chars, i);
+                let token: String = chars[i..end].iter().collect();
+                let next = chars.get(end).copied();
+                if next == Some('!') || next == Some('(') || next == Some('[') {
+                    // Sheet name, function call or table name.
+                    out.push_str(&token);
+                    i = end;
+                    continue;
+                }
+                // A range whose halves are both rows or both columns.
+                if next == Some(':') && end + 1 < chars.len() && is_token_char(chars[end + 1]) {
+                    let end2 = token_end(&chars, end + 1);
+                    let second: String = chars[end + 1..end2].iter().collect();
+                    let whole = match (parse_part(&token), parse_part(&second)) {
+                        (Some(Part::Row(a)), Some(Part::Row(b))) => {
+                            Some(shift_rows(a, rows).zip(shift_rows(b, rows)))
+                        }
+                        (Some(Part::Column(a)), Some(Part::Column(b))) => {
+                            Some(shift_columns(a, cols).zip(shift_columns(b, cols)))
+                        }
+                        _ => None,
+                    };
+                    if let Some(shifted) = whole {
+                        match shifted {
+                            Some((a, b)) => {
+                                out.push_str(&a);
+                                out.push(':');
+                                out.push_str(&b);
+                            }
+                            None => out.push_str("#REF!"),
+                        }
+                        i = end2;
+                        continue;
+                    }
+                }
+                match parse_part(&token) {
+                    Some(Part::Cell(col, row)) => {
+                        match shift_columns(col, cols).zip(shift_rows(row, rows)) {
+                            Some((col, row)) => {
+                                out.push_str(&col);
+                                out.push_str(&row);
+                            }
+                            None => out.push_str("#REF!"),
+                        }
+                    }
+                    _ => out.push_str(&token),
+                }
+                i = end;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// One side of a reference: (absolute?, value).
+type Anchored = (bool, u32);
+
+enum Part {
+    Cell(Anchored, Anchored),
+    Row(Anchored),
+    Column(Anchored),
+}
+
+/// Shared formula groups met while reading a worksheet. In
+/// `<f t="shared" si="0">`, only the group's first cell carries the formula
+/// text; every other cell in the group names the group and takes the anchor
+/// formula translated by its offset from the anchor.
+#[derive(Debug, Default)]
+pub(crate) struct SharedFormulas {
+    anchors: HashMap<String, (u32, u32, String)>,
+}
+
+impl SharedFormulas {
+    /// Record the formula text of group `si`, found at (`row`, `col`).
+    pub(crate) fn anchor(&mut self, si: &str, row: u32, col: u32, formula: &str) {
+        self.anchors
+            .insert(si.to_string(), (row, col, formula.to_string()));
+    }
+
+    /// The formula of the cell at (`row`, `col`) in group `si`, or `None`
+    /// when the group's anchor has not been seen.
+    pub(crate) fn member(&self, si: &str, row: u32, col: u32) -> Option<String> {
+        let (anchor_row, anchor_col, formula) = self.anchors.get(si)?;
+        Some(translate_formula(
+            formula,
+            row as i64 - *anchor_row as i64,
+            col as i64 - *anchor_col as i64,
+        ))
+    }
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '$' | '\\')
+}
+
+fn token_end(chars: &[char], start: usize) -> usize {
+    let mut end = start;
+    while end < chars.len() && is_token_char(chars[end]) {
+        end += 1;
+    }
+    end
+}
+
+/// Copy a `"string"` or `'sheet name'` literal (doubled quotes escape).
+fn copy_quoted(chars: &[char], start: usize, quote: char, out: &mut String) -> usize {
+    out.push(quote);
+    let mut i = start + 1;
+    while i < chars.len() {
+        out.push(chars[i]);
+        if chars[i] == quote {
+            if chars.get(i + 1) == Some(&quote) {
+                out.push(quote);
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Copy a (possibly nested) structured-reference bracket group.
+fn copy_bracketed(chars: &[char], start: usize, out: &mut String) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        out.push(c);
+        i += 1;
+        match c {
+            // An apostrophe escapes the next character inside a column name.
+            '\'' if i < chars.len() => {
+                out.push(chars[i]);
+                i += 1;
+            }
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    i
+}
+
+/// Split `$B$12`, `B`, or `$12` into its anchored parts.
+fn parse_part(token: &str) -> Option<Part> {
+    let (col_abs, rest) = match token.strip_prefix('$') {
+        Some(rest) => (true, rest),
+        None => (false, token),
+    };
+    let letters = rest.chars().take_while(|c| c.is_ascii_alphabetic()).count();
+    let (col_str, rest) = rest.split_at(letters);
+    let (row_abs, row_str) = match rest.strip_prefix('$') {
+        Some(r) => (true, r),
+        None => (false, rest),
+    };
+    let col = if col_str.is_empty() {
+        None
+    } else if col_str.len() <= 3 {
+        Some(letter_to_column(col_str).ok()?)
+    } else {
+        return None;
+    };
+    let row = if row_str.is_empty() {
+        None
+    } else if row_str.bytes().all(|b| b.is_ascii_digit()) {
+        Some(
+            row_str
+                .parse::<u32>()
+                .ok()
+                .filter(|r| (1..=MAX_ROW).contains(r))?,
+        )
+    } else {
+        return None;
+    };
+    match (col, row) {
+        (Some(c), Some(r)) => Some(Part::Cell((col_abs, c), (row_abs, r))),
+        // "$5" reads the leading `$` as the column's, but it anchors the row.
+        (None, Some(r)) if !row_abs => Some(Part::Row((col_abs, r))),
+        (Some(c), None) if !row_abs => Some(Part::Column((col_abs, c))),
+        _ => None,
+    }
+}
+
+fn shift_rows((absolute, row): Anchored, delta: i64) -> Option<String> {
+    let prefix = if absolute { "$" } else { "" };
+    if absolute {
+        return Some(format!("{}{}", prefix, row));
+    }
+    let row = row as i64 + delta;
+    (1..=MAX_ROW as i64)
+        .contains(&row)
+        .then(|| format!("{}{}", prefix, row))
+}
+
+fn shift_columns((absolute, col): Anchored, delta: i64) -> Option<String> {
+    if absolute {
+        return Some(format!("${}", column_to_letter(col)));
+    }
+    let col = col as i64 + delta;
+    (1..=MAX_COLUMN as i64)
+        .contains(&col)
+        .then(|| column_to_letter(col as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_and_absolute_parts() {
+        assert_eq!(
+            translate_formula("=A1+$B$2+C$3+$D4", 2, 1),
+            "=B3+$B$2+D$3+$D6"
+        );
+        assert_eq!(translate_formula("=SUM(A1:B10)", 1, 0), "=SUM(A2:B11)");
+        assert_eq!(translate_formula("SUM(A1:B10)", 0, 0), "SUM(A1:B10)");
+    }
+
+    #[test]
+    fn test_whole_rows_and_columns() {
+        assert_eq!(translate_formula("=SUM(3:5)", 2, 0), "=SUM(5:7)");
+        assert_eq!(translate_formula("=SUM($3:$5)", 2, 0), "=SUM($3:$5)");
+        assert_eq!(translate_formula("=SUM(B:D)", 5, -1), "=SUM(A:C)");
+        assert_eq!(translate_formula("=SUM($B:D)", 0, 1), "=SUM($B:E)");
+    }
+
+    #[test]
+    fn test_leaves_non_references_alone() {
+        assert_eq!(
+            translate_formula("=IF(A1=\"B2\",LOG10(C3),'My A1'!D4&Sales[Qty])", 1, 1),
+            "=IF(B2=\"B2\",LOG10(D4),'My A1'!E5&Sales[Qty])"
+        );
+        assert_eq!(
+            translate_formula("=Rate*1.5E+3+TRUE+Sheet2!A1", 1, 0),
+            "=Rate*1.5E+3+TRUE+Sheet2!A2"
+        );
+        assert_eq!(
+            translate_formula("=SUM(T[[#This Row],[a'[b]]])+A1", 1, 0),
+            "=SUM(T[[#This Row],[a'[b]]])+A2"
+        );
+    }
+
+    #[test]
+    fn test_off_sheet_becomes_ref_error() {
+        assert_eq!(translate_formula("=A1+B5", -2, 0), "=#REF!+B3");
+        assert_eq!(translate_formula("=SUM(A:B)", 0, -1), "=SUM(#REF!)");
+        assert_eq!(translate_formula("=XFD1", 0, 1), "=#REF!");
+    }
+
+    #[test]
+    fn test_shared_formula_members() {
+        let mut shared = SharedFormulas::default();
+        assert_eq!(shared.member("0", 3, 4), None);
+        shared.anchor("0", 2, 4, "B2*C2+$A$1");
+        assert_eq!(shared.member("0", 5, 4).unwrap(), "B5*C5+$A$1");
+        assert_eq!(shared.member("0", 2, 5).unwrap(), "C2*D2+$A$1");
+        assert_eq!(shared.member("1", 5, 4), None);
+    }
+}
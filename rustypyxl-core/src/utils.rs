@@ -202,10 +202,130 @@ pub fn parse_range(range: &str) -> Result<((u32, u32), (u32, u32))> {
     Ok((start, end))
 }
 
+/// Bounds of a reference that may leave out its rows or its columns, as
+/// `(min_row, min_col, max_row, max_col)`: `"A1:C10"` and `"B2"` have all
+/// four, `"B:D"` and `"B"` have no rows, `"3:5"` and `"5"` no columns.
+/// Corners may come in either order; `$` anchors are ignored.
+pub type RangeBoundaries = (Option<u32>, Option<u32>, Option<u32>, Option<u32>);
+
+/// Parse a cell, range, whole-column or whole-row reference; see
+/// [`RangeBoundaries`].
+pub fn range_boundaries(range: &str) -> Result<RangeBoundaries> {
+    let invalid = || RustypyxlError::InvalidCoordinate(format!("Invalid range: {}", range));
+    let cleaned = range.trim().replace('$', "");
+    let (first, last) = cleaned
+        .split_once(':')
+        .unwrap_or((cleaned.as_str(), cleaned.as_str()));
+    let part = |s: &str| -> Result<(Option<u32>, Option<u32>)> {
+        if !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphabetic()) {
+            Ok((None, Some(letter_to_column(s)?)))
+        } else if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            match s.parse::<u32>() {
+                Ok(row) if row > 0 => Ok((Some(row), None)),
+                _ => Err(invalid()),
+            }
+        } else {
+            let (row, col) = parse_coordinate(s)?;
+            Ok((Some(row), Some(col)))
+        }
+    };
+    let ((r1, c1), (r2, c2)) = (part(first)?, part(last)?);
+    if r1.is_some() != r2.is_some() || c1.is_some() != c2.is_some() {
+        return Err(invalid());
+    }
+    let min = |a: Option<u32>, b: Option<u32>| a.zip(b).map(|(a, b)| a.min(b));
+    let max = |a: Option<u32>, b: Option<u32>| a.zip(b).map(|(a, b)| a.max(b));
+    Ok((min(r1, r2), min(c1, c2), max(r1, r2), max(c1, c2)))
+}
+
+#[cfg(feature = "decrypt")]
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard-alphabet, padded base64 of `data`, as OOXML stores salts,
+/// hashes and key material.
+#[cfg(feature = "decrypt")]
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        out.push(BASE64_ALPHABET[(b[0] >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b[0] & 0x03) << 4) | (b[1] >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b[1] & 0x0f) << 2) | (b[2] >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b[2] & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+/// Decode standard-alphabet base64, skipping padding and whitespace. None on
+/// any other character outside the alphabet.
+#[cfg(feature = "decrypt")]
+pub(crate) fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    let mut buf: u32 = 0;
+    let mut bits = 0;
+    for c in s.bytes() {
+        if matches!(c, b'=' | b'\n' | b'\r' | b' ' | b'\t') {
+            continue;
+        }
+        let v = BASE64_ALPHABET.iter().position(|&a| a == c)?;
+        buf = (buf << 6) | v as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buf >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_range_boundaries() {
+        assert_eq!(
+            range_boundaries("C10:A1").unwrap(),
+            (Some(1), Some(1), Some(10), Some(3))
+        );
+        assert_eq!(
+            range_boundaries("$B$2").unwrap(),
+            (Some(2), Some(2), Some(2), Some(2))
+        );
+        assert_eq!(
+            range_boundaries("D:B").unwrap(),
+            (None, Some(2), None, Some(4))
+        );
+        assert_eq!(
+            range_boundaries("C").unwrap(),
+            (None, Some(3), None, Some(3))
+        );
+        assert_eq!(
+            range_boundaries("3:5").unwrap(),
+            (Some(3), None, Some(5), None)
+        );
+        assert_eq!(
+            range_boundaries("7").unwrap(),
+            (Some(7), None, Some(7), None)
+        );
+        assert!(range_boundaries("A1:C").is_err());
+        assert!(range_boundaries("0").is_err());
+        assert!(range_boundaries("").is_err());
+    }
+
     #[test]
     fn test_parse_coordinate() {
         assert_eq!(parse_coordinate("A1").unwrap(), (1, 1));
@@ -335,4 +455,15 @@ mod tests {
         assert_eq!(parse_u32_bytes(b"123"), Some(123));
         assert_eq!(parse_u32_bytes(b"4294967295"), Some(u32::MAX));
     }
+
+    #[cfg(feature = "decrypt")]
+    #[test]
+    fn test_base64_roundtrip() {
+        for data in [&b""[..], b"f", b"fo", b"foo", b"foob", &[0xff, 0x00, 0x7f]] {
+            assert_eq!(base64_decode(&base64_encode(data)).unwrap(), data);
+        }
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_decode(" Zm9v\r\nYmFy ").unwrap(), b"foobar");
+        assert!(base64_decode("not*base64").is_none());
+    }
 }
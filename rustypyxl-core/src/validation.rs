@@ -0,0 +1,231 @@
+//! Data validation rules: what may be typed into a range of cells, the
+//! dropdown list offered there, and the prompt and error boxes Excel shows.
+//!
+//! A rule covers its `sqref`, one or more ranges separated by spaces
+//! ("A2:A100 C2:C100"). On a [`Worksheet`](crate::Worksheet) rules are keyed
+//! by the top-left cell of their first range; use
+//! [`Worksheet::data_validation_at`](crate::Worksheet::data_validation_at)
+//! to find the rule covering any cell.
+
+use crate::error::{Result, RustypyxlError};
+use crate::utils::parse_coordinate;
+
+/// A data validation rule.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataValidation {
+    /// Type: whole, decimal, list, date, time, textLength, custom.
+    pub validation_type: String,
+    /// Comparison operator: between, notBetween, equal, notEqual, greaterThan,
+    /// lessThan, greaterThanOrEqual, lessThanOrEqual. None means Excel's
+    /// default, "between".
+    pub operator: Option<String>,
+    /// First formula/value constraint.
+    pub formula1: Option<String>,
+    /// Second formula/value constraint (for between/notBetween).
+    pub formula2: Option<String>,
+    /// Severity of the error dialog: stop, warning, or information.
+    pub error_style: Option<String>,
+    /// Allow blank values.
+    pub allow_blank: bool,
+    /// Show error message on invalid input.
+    pub show_error: bool,
+    /// Error dialog title.
+    pub error_title: Option<String>,
+    /// Error message text.
+    pub error_message: Option<String>,
+    /// Show input message when cell is selected.
+    pub show_input: bool,
+    /// Input prompt title.
+    pub prompt_title: Option<String>,
+    /// Input prompt message.
+    pub prompt_message: Option<String>,
+    /// Hide the in-cell dropdown of a list rule. Stored as `showDropDown`,
+    /// which despite its name hides the arrow when set.
+    pub hide_dropdown: bool,
+    /// Full sqref the rule applies to (may span multiple cells/ranges).
+    /// When None, the rule applies to the single cell it is keyed under.
+    pub sqref: Option<String>,
+}
+
+impl Default for DataValidation {
+    fn default() -> Self {
+        DataValidation {
+            validation_type: "whole".to_string(),
+            operator: None,
+            formula1: None,
+            formula2: None,
+            error_style: None,
+            allow_blank: true,
+            show_error: true,
+            error_title: None,
+            error_message: None,
+            show_input: true,
+            prompt_title: None,
+            prompt_message: None,
+            hide_dropdown: false,
+            sqref: None,
+        }
+    }
+}
+
+impl DataValidation {
+    /// A rule of the given type (whole, decimal, list, date, time,
+    /// textLength, custom).
+    pub fn new<S: Into<String>>(validation_type: S) -> Self {
+        DataValidation {
+            validation_type: validation_type.into(),
+            ..Default::default()
+        }
+    }
+
+    /// A dropdown of fixed choices. Quotes inside a choice are doubled as
+    /// Excel expects; choices cannot contain commas.
+    pub fn list<I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let items: Vec<String> = items
+            .into_iter()
+            .map(|item| item.as_ref().replace('"', "\"\""))
+            .collect();
+        Self::new("list").with_formula1(format!("\"{}\"", items.join(",")))
+    }
+
+    /// A dropdown of the values in a range, e.g. "$A$1:$A$5" or
+    /// "Lists!$A$1:$A$5".
+    pub fn list_from_range<S: AsRef<str>>(range: S) -> Self {
+        Self::new("list").with_formula1(range.as_ref().trim_start_matches('='))
+    }
+
+    pub fn with_operator<S: Into<String>>(mut self, operator: S) -> Self {
+        self.operator = Some(operator.into());
+        self
+    }
+
+    pub fn with_formula1<S: Into<String>>(mut self, formula: S) -> Self {
+        self.formula1 = Some(formula.into());
+        self
+    }
+
+    pub fn with_formula2<S: Into<String>>(mut self, formula: S) -> Self {
+        self.formula2 = Some(formula.into());
+        self
+    }
+
+    /// Set the cells the rule covers ("A1:A10", or several ranges separated
+    /// by spaces).
+    pub fn with_sqref<S: Into<String>>(mut self, sqref: S) -> Self {
+        self.sqref = Some(sqref.into());
+        self
+    }
+
+    /// Show an error box with this title and message on invalid input.
+    pub fn with_error(mut self, title: Option<String>, message: Option<String>) -> Self {
+        self.show_error = true;
+        self.error_title = title;
+        self.error_message = message;
+        self
+    }
+
+    /// Error box severity: "stop" (default) rejects the input, "warning"
+    /// and "information" let the user keep it.
+    pub fn with_error_style<S: Into<String>>(mut self, style: S) -> Self {
+        self.error_style = Some(style.into());
+        self
+    }
+
+    /// Show an input prompt with this title and message when a covered cell
+    /// is selected.
+    pub fn with_prompt(mut self, title: Option<String>, message: Option<String>) -> Self {
+        self.show_input = true;
+        self.prompt_title = title;
+        self.prompt_message = message;
+        self
+    }
+
+    pub fn with_allow_blank(mut self, allow: bool) -> Self {
+        self.allow_blank = allow;
+        self
+    }
+
+    pub fn with_hide_dropdown(mut self, hide: bool) -> Self {
+        self.hide_dropdown = hide;
+        self
+    }
+
+    /// Extend the rule to another cell or range.
+    pub fn add_range(&mut self, range: &str) -> Result<()> {
+        parse_sqref(range)?;
+        let range = range.trim();
+        self.sqref = Some(match self.sqref.take().filter(|s| !s.trim().is_empty()) {
+            Some(sqref) => format!("{sqref} {range}"),
+            None => range.to_string(),
+        });
+        Ok(())
+    }
+
+    /// The ranges of the sqref as (min_row, min_col, max_row, max_col).
+    /// Empty when there is no sqref or it does not parse.
+    pub fn ranges(&self) -> Vec<(u32, u32, u32, u32)> {
+        self.sqref
+            .as_deref()
+            .and_then(|sqref| parse_sqref(sqref).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether the sqref covers (row, column).
+    pub fn covers(&self, row: u32, column: u32) -> bool {
+        self.ranges()
+            .iter()
+            .any(|&(r1, c1, r2, c2)| (r1..=r2).contains(&row) && (c1..=c2).contains(&column))
+    }
+}
+
+/// Parse a space-separated list of cells and ranges into
+/// (min_row, min_col, max_row, max_col) boxes. `$` markers are ignored.
+pub fn parse_sqref(sqref: &str) -> Result<Vec<(u32, u32, u32, u32)>> {
+    let ranges: Vec<_> = sqref
+        .split_whitespace()
+        .map(|part| {
+            let part = part.replace('$', "");
+            let (start, end) = part.split_once(':').unwrap_or((&part, &part));
+            let (r1, c1) = parse_coordinate(start)?;
+            let (r2, c2) = parse_coordinate(end)?;
+            Ok((r1.min(r2), c1.min(c2), r1.max(r2), c1.max(c2)))
+        })
+        .collect::<Result<_>>()?;
+    if ranges.is_empty() {
+        return Err(RustypyxlError::InvalidCoordinate(
+            "empty data validation range".to_string(),
+        ));
+    }
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_quotes_items() {
+        let dv = DataValidation::list(["Yes", "No", "Say \"maybe\""]);
+        assert_eq!(dv.validation_type, "list");
+        assert_eq!(dv.formula1.as_deref(), Some("\"Yes,No,Say \"\"maybe\"\"\""));
+        let dv = DataValidation::list_from_range("=Lists!$A$1:$A$3");
+        assert_eq!(dv.formula1.as_deref(), Some("Lists!$A$1:$A$3"));
+    }
+
+    #[test]
+    fn test_ranges_and_coverage() {
+        let mut dv = DataValidation::new("whole").with_sqref("A1:A10");
+        dv.add_range("$C$2").unwrap();
+        dv.add_range("E5:D3").unwrap();
+        assert_eq!(dv.sqref.as_deref(), Some("A1:A10 $C$2 E5:D3"));
+        assert_eq!(dv.ranges(), vec![(1, 1, 10, 1), (2, 3, 2, 3), (3, 4, 5, 5)]);
+        assert!(dv.covers(10, 1) && dv.covers(2, 3) && dv.covers(4, 5));
+        assert!(!dv.covers(11, 1) && !dv.covers(1, 3));
+        assert!(dv.add_range("not a cell").is_err());
+        assert!(parse_sqref("  ").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! Checking a saved file against the workbook that wrote it.
+//!
+//! [`Workbook::verify_saved`] parses every XML part of the saved archive
+//! strictly (the loader is lenient and would skip what it cannot read),
+//! reloads the file, and compares sheet names, used ranges and a sample of
+//! cell values with the in-memory workbook. A pipeline can call it after
+//! saving to catch a writer regression before the file leaves the building.
+
+use std::io::{Cursor, Read};
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use zip::ZipArchive;
+
+use crate::cell::CellValue;
+use crate::error::{Result, RustypyxlError};
+use crate::workbook::{NumberPrecision, Workbook};
+use crate::worksheet::{decode_cell_key, Worksheet};
+
+/// Cells compared per sheet; larger sheets are sampled evenly.
+pub const SAMPLE_CELLS_PER_SHEET: usize = 1000;
+
+/// Mismatches listed in a failed verification's message.
+const MAX_REPORTED: usize = 10;
+
+/// What a successful verification looked at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveVerification {
+    /// XML parts parsed strictly.
+    pub parts_checked: usize,
+    /// Worksheets compared.
+    pub sheets_checked: usize,
+    /// Cell values compared.
+    pub cells_checked: usize,
+}
+
+impl Workbook {
+    /// Check that `data`, the bytes this workbook was just saved as, is
+    /// well-formed and reads back as the same workbook: same sheets, same
+    /// used ranges and, for a sample of cells, the same values. Fails with
+    /// a message listing the first differences found.
+    pub fn verify_saved(&self, data: &[u8]) -> Result<SaveVerification> {
+        let parts_checked = check_xml_parts(data)?;
+        let reloaded = Workbook::load_from_bytes(data)
+            .map_err(|e| RustypyxlError::custom(format!("Saved file does not load: {}", e)))?;
+
+        let mut problems = Vec::new();
+        if reloaded.sheet_names != self.sheet_names {
+            problems.push(format!(
+                "sheets {:?} were read back as {:?}",
+                self.sheet_names, reloaded.sheet_names
+            ));
+        }
+
+        let mut sheets_checked = 0;
+        let mut cells_checked = 0;
+        for (written, read) in self.worksheets.iter().zip(&reloaded.worksheets) {
+            if written.opaque.is_some() || written.title != read.title {
+                continue;
+            }
+            sheets_checked += 1;
+            let (bounds, read_bounds) = (value_bounds(written), value_bounds(read));
+            if bounds != read_bounds {
+                problems.push(format!(
+                    "{}: used range {} was read back as {}",
+                    written.title,
+                    describe_bounds(bounds),
+                    describe_bounds(read_bounds)
+                ));
+            }
+            for (row, col) in sample_cells(written) {
+                let value = written.get_cell_value(row, col).unwrap();
+                let read_value = read.get_cell_value(row, col).unwrap_or(&CellValue::Empty);
+                cells_checked += 1;
+                if !values_match(value, read_value, self) {
+                    problems.push(format!(
+                        "{}!{}: {:?} was read back as {:?}",
+                        written.title,
+                        crate::utils::coordinate_from_row_col(row, col),
+                        value,
+                        read_value
+                    ));
+                }
+            }
+        }
+
+        if !problems.is_empty() {
+            let more = problems.len().saturating_sub(MAX_REPORTED);
+            let mut message = format!(
+                "Saved file failed verification: {}",
+                problems[..problems.len().min(MAX_REPORTED)].join("; ")
+            );
+            if more > 0 {
+                message.push_str(&format!(" (and {} more)", more));
+            }
+            return Err(RustypyxlError::custom(message));
+        }
+        Ok(SaveVerification {
+            parts_checked,
+            sheets_checked,
+            cells_checked,
+        })
+    }
+
+    /// Save to `path`, then read the file back and [verify](Self::verify_saved)
+    /// it. The file stays on disk when verification fails.
+    pub fn save_verified(&self, path: &str) -> Result<SaveVerification> {
+        self.save(path)?;
+        self.verify_saved(&std::fs::read(path)?)
+    }
+}
+
+/// Parse every XML part to the end, failing on the first malformed one.
+fn check_xml_parts(data: &[u8]) -> Result<usize> {
+    let mut archive = ZipArchive::new(Cursor::new(data))?;
+    let mut checked = 0;
+    let mut body = Vec::new();
+    let mut buf = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name().to_string();
+        if !(name.ends_with(".xml") || name.ends_with(".rels") || name.ends_with(".vml")) {
+            continue;
+        }
+        body.clear();
+        file.read_to_end(&mut body)?;
+        let mut reader = Reader::from_reader(body.as_slice());
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(RustypyxlError::custom(format!(
+                        "Saved file failed verification: {} is not well-formed XML at byte {}: {}",
+                        name,
+                        reader.buffer_position(),
+                        e
+                    )))
+                }
+            }
+            buf.clear();
+        }
+        checked += 1;
+    }
+    Ok(checked)
+}
+
+/// (min_row, min_col, max_row, max_col) over cells holding a value, or None
+/// for a sheet without values. Style-only cells are left out, since whether
+/// they are written depends on the style, not the data.
+fn value_bounds(ws: &Worksheet) -> Option<(u32, u32, u32, u32)> {
+    value_cells(ws).fold(None, |bounds, (row, col)| {
+        Some(match bounds {
+            None => (row, col, row, col),
+            Some((r1, c1, r2, c2)) => (r1.min(row), c1.min(col), r2.max(row), c2.max(col)),
+        })
+    })
+}
+
+fn describe_bounds(bounds: Option<(u32, u32, u32, u32)>) -> String {
+    match bounds {
+        None => "(empty)".to_string(),
+        Some((r1, c1, r2, c2)) => format!(
+            "{}:{}",
+            crate::utils::coordinate_from_row_col(r1, c1),
+            crate::utils::coordinate_from_row_col(r2, c2)
+        ),
+    }
+}
+
+/// Positions of the cells holding a value, in no particular order.
+fn value_cells(ws: &Worksheet) -> impl Iterator<Item = (u32, u32)> + '_ {
+    ws.cells
+        .iter()
+        .filter(|(_, cell)| !cell.value.is_empty())
+        .map(|(&key, _)| decode_cell_key(key))
+        .chain(ws.column_cells().map(|(pos, _)| pos))
+}
+
+/// Up to [`SAMPLE_CELLS_PER_SHEET`] cells with values, spread evenly over
+/// the sheet in row order and always including the last one.
+fn sample_cells(ws: &Worksheet) -> Vec<(u32, u32)> {
+    let mut keys: Vec<(u32, u32)> = value_cells(ws).collect();
+    keys.sort_unstable();
+    if keys.len() <= SAMPLE_CELLS_PER_SHEET {
+        return keys;
+    }
+    let step = keys.len().div_ceil(SAMPLE_CELLS_PER_SHEET);
+    let last = keys[keys.len() - 1];
+    let mut sample: Vec<(u32, u32)> = keys.into_iter().step_by(step).collect();
+    if sample.last() != Some(&last) {
+        sample.push(last);
+    }
+    sample
+}
+
+/// Whether a value read back is what was written, allowing for what the
+/// writer legitimately changes: numbers rounded to 15 digits under Excel
+/// precision, non-finite numbers replaced, control characters stripped
+/// from text, and date-times rounded to the millisecond.
+fn values_match(written: &CellValue, read: &CellValue, wb: &Workbook) -> bool {
+    match (written, read) {
+        (CellValue::Number(n), _) if !n.is_finite() => true,
+        (CellValue::Number(a), CellValue::Number(b)) => match wb.number_precision {
+            NumberPrecision::Shortest => a == b,
+            NumberPrecision::Excel => (a - b).abs() <= a.abs().max(b.abs()) * 1e-14,
+        },
+        // A number in a date-formatted cell reads back as a date-time
+        (CellValue::Number(n), CellValue::Date(d)) | (CellValue::Date(d), CellValue::Number(n)) => {
+            (n - d.to_excel(wb.date1904)).abs() < 1e-6
+        }
+        // Compared as serials: a date saves as one, and a time of day in a
+        // 1904 workbook reads back as a date-time on 1904-01-01.
+        (CellValue::Date(a), CellValue::Date(b)) => {
+            ((a.to_excel(wb.date1904) - b.to_excel(wb.date1904)) * 86_400_000.0).abs() <= 1.0
+        }
+        (CellValue::String(a), CellValue::String(b)) => same_text(a, b),
+        (CellValue::Formula(a), CellValue::Formula(b)) => {
+            a.trim_start_matches('=') == b.trim_start_matches('=')
+        }
+        (a, b) if a == b => true,
+        // Dates given as text and the like: compare what the cell shows
+        (a, b) => same_text(&a.to_string(), &b.to_string()),
+    }
+}
+
+fn same_text(written: &str, read: &str) -> bool {
+    let legible = |s: &str| -> String {
+        s.replace("\r\n", "\n")
+            .chars()
+            .filter(|&c| !(c.is_ascii() && crate::writer::is_illegal_xml_char(c as u8)))
+            .collect()
+    };
+    written == read || legible(written) == legible(read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_workbook() -> Workbook {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+        for row in 1..=2500 {
+            ws.set_cell_value(row, 1, CellValue::Number(row as f64 * 0.1));
+            ws.set_cell_value(row, 2, CellValue::from(format!("r{row}\u{1}")));
+        }
+        ws.set_cell_value(1, 3, CellValue::Formula("SUM(A1:A10)".to_string()));
+        ws.set_cell_value(2, 3, CellValue::Number(f64::NAN));
+        wb
+    }
+
+    #[test]
+    fn test_verify_saved_accepts_own_output() {
+        let wb = sample_workbook();
+        let bytes = wb.save_to_bytes().unwrap();
+        let check = wb.verify_saved(&bytes).unwrap();
+        assert_eq!(check.sheets_checked, 1);
+        assert!(check.parts_checked >= 5);
+        // 5002 values, sampled down to at most a thousand and the last one
+        assert!(check.cells_checked > 500 && check.cells_checked <= SAMPLE_CELLS_PER_SHEET + 1);
+    }
+
+    #[test]
+    fn test_verify_saved_reports_differences() {
+        let wb = sample_workbook();
+        let bytes = wb.save_to_bytes().unwrap();
+
+        let mut changed = sample_workbook();
+        let ws = changed.get_sheet_by_name_mut("Data").unwrap();
+        ws.set_cell_value(1, 1, CellValue::Number(-1.0));
+        ws.set_cell_value(3000, 4, CellValue::from("extra"));
+        let err = changed.verify_saved(&bytes).unwrap_err().to_string();
+        assert!(
+            err.contains("Data!A1: Number(-1.0) was read back as Number(0.1)"),
+            "{err}"
+        );
+        assert!(
+            err.contains("used range A1:D3000 was read back as A1:C2500"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_verify_saved_rejects_malformed_xml() {
+        let wb = sample_workbook();
+        let bytes = wb.save_to_bytes().unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+        let mut out = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let name = file.name().to_string();
+            let mut body = Vec::new();
+            file.read_to_end(&mut body).unwrap();
+            if name == "xl/styles.xml" {
+                body.truncate(body.len() - 5);
+            }
+            out.start_file(name, options).unwrap();
+            std::io::Write::write_all(&mut out, &body).unwrap();
+        }
+        let broken = out.finish().unwrap().into_inner();
+        let err = wb.verify_saved(&broken).unwrap_err().to_string();
+        assert!(err.contains("xl/styles.xml is not well-formed"), "{err}");
+    }
+}
@@ -16,20 +16,28 @@ use crate::autofilter::{
     AutoFilter, ColorFilter, CustomFilter, DynamicFilterType, FilterColumn, FilterOperator,
     FilterType, Top10Filter,
 };
+use crate::blank::BlankPolicy;
 use crate::cell::CellValue;
 use crate::conditional::{
     ColorScale, ConditionalColor, ConditionalFormat, ConditionalFormatType, ConditionalFormatting,
     ConditionalOperator, ConditionalRule, DataBar, IconSet, IconSetStyle,
 };
+use crate::docprops::DocumentProperties;
 use crate::error::{Result, RustypyxlError};
+use crate::formula_locale::FormulaLocale;
+use crate::hyperlink::Hyperlink;
+use crate::outline::DimensionProps;
 use crate::pagesetup::{Orientation, PageSetup, PaperSize};
+use crate::protection::{PasswordHash, WorkbookProtection};
+use crate::sheetview::{SheetView, SheetViewType};
 use crate::style::{
     Alignment, Border, BorderStyle, CellStyle, CellXf, Color, Fill, Font, Protection, StyleRegistry,
 };
 use crate::table::{Table, TableColumn, TableStyle, TotalsRowFunction};
 use crate::utils::{parse_coordinate, parse_coordinate_bytes, parse_f64_bytes, parse_u32_bytes};
 use crate::worksheet::{
-    cell_key, CellData, DataValidation, SheetVisibility, Worksheet, WorksheetProtection,
+    cell_key, CellData, CommentShape, DataValidation, SheetVisibility, Worksheet,
+    WorksheetProtection,
 };
 use crate::writer;
 
@@ -44,6 +52,82 @@ pub struct NamedRange {
     pub local_sheet_id: Option<u32>,
     /// Hidden from the Excel name manager UI.
     pub hidden: bool,
+    /// The comment shown in Excel's name manager.
+    pub comment: Option<String>,
+}
+
+impl NamedRange {
+    /// A visible, workbook-global name.
+    pub fn new<N: Into<String>, R: Into<String>>(name: N, range: R) -> Self {
+        NamedRange {
+            name: name.into(),
+            range: range.into(),
+            local_sheet_id: None,
+            hidden: false,
+            comment: None,
+        }
+    }
+
+    /// Scope the name to the sheet at `index`.
+    pub fn with_local_sheet(mut self, index: u32) -> Self {
+        self.local_sheet_id = Some(index);
+        self
+    }
+
+    /// Hide the name from Excel's name manager.
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Set the name manager comment.
+    pub fn with_comment<S: Into<String>>(mut self, comment: S) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
+/// A rectangle as `(min_row, min_col, max_row, max_col)`.
+type Bounds = (u32, u32, u32, u32);
+
+/// Split a defined name's reference into its sheet (unquoted) and bounds.
+/// Whole rows and columns span the sheet. None for anything but one
+/// contiguous area.
+fn parse_name_reference(reference: &str) -> Option<(Option<String>, Bounds)> {
+    let reference = reference.trim().trim_start_matches('=');
+    let (sheet, area) = match reference.rsplit_once('!') {
+        Some((sheet, area)) => {
+            let sheet = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                Some(quoted) => quoted.replace("''", "'"),
+                None => sheet.to_string(),
+            };
+            (Some(sheet), area)
+        }
+        None => (None, reference),
+    };
+    let area = area.replace('$', "");
+    let (first, last) = area.split_once(':').unwrap_or((&area, &area));
+    if first.contains(',') || last.contains(',') {
+        return None;
+    }
+    let bounds = if let (Ok(r1), Ok(r2)) = (first.parse::<u32>(), last.parse::<u32>()) {
+        (r1, 1, r2, crate::utils::MAX_COLUMN)
+    } else if first.bytes().all(|b| b.is_ascii_alphabetic())
+        && last.bytes().all(|b| b.is_ascii_alphabetic())
+    {
+        let c1 = crate::utils::letter_to_column(first).ok()?;
+        let c2 = crate::utils::letter_to_column(last).ok()?;
+        (1, c1, crate::utils::MAX_ROW, c2)
+    } else {
+        let (r1, c1) = crate::utils::parse_coordinate(first).ok()?;
+        let (r2, c2) = crate::utils::parse_coordinate(last).ok()?;
+        (r1, c1, r2, c2)
+    };
+    let (r1, c1, r2, c2) = bounds;
+    if r1 == 0 || c1 == 0 || r2 > crate::utils::MAX_ROW || c2 > crate::utils::MAX_COLUMN {
+        return None;
+    }
+    Some((sheet, (r1.min(r2), c1.min(c2), r1.max(r2), c1.max(c2))))
 }
 
 /// Compression level for saving workbooks.
@@ -60,16 +144,86 @@ pub enum CompressionLevel {
     Best,
 }
 
+/// What saving does with a number that is NaN or infinite. SpreadsheetML
+/// has no way to write either, so the value has to become something else.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum NonFiniteNumbers {
+    /// Fail the save, naming the first cell that holds one.
+    Error,
+    /// Write a `#NUM!` error cell, which is what Excel shows for a
+    /// calculation that overflows.
+    #[default]
+    NumError,
+    /// Write the cell without a value; its style is kept.
+    Empty,
+}
+
+/// How saving writes numbers (date serials included).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum NumberPrecision {
+    /// The shortest text that reads back as exactly the same double, so
+    /// `0.1 + 0.2` is written as `0.30000000000000004`.
+    #[default]
+    Shortest,
+    /// Round to 15 significant digits first, as Excel does when it saves,
+    /// so `0.1 + 0.2` is written as `0.3`. Smaller files and quieter diffs,
+    /// at the cost of the last bits of precision.
+    Excel,
+}
+
+/// Which strings saving puts in the shared string table. The rest are
+/// written inline in their cells (`t="inlineStr"`), which every reader
+/// accepts and which Excel converts back to shared strings on its next save.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SharedStrings {
+    /// Every string, as Excel writes them.
+    #[default]
+    All,
+    /// Only strings that occur at least this many times. A value used once
+    /// gains nothing from the table but still costs a lookup and an entry,
+    /// so `MinRepeats(2)` shares exactly the repeated values. Takes one
+    /// extra pass over the cells to count them.
+    MinRepeats(usize),
+    /// Choose from a sample of the string cells: share everything when
+    /// most values repeat, inline everything when almost none do, and
+    /// otherwise share only the repeated values.
+    Auto,
+    /// Every string, like `All`, but from a count of each sheet's strings
+    /// that the worksheet value setters keep up to date, so saving skips
+    /// the pass over the cells. Switching to this mode counts the existing
+    /// strings once. A string put in a cell through `cells` or
+    /// `get_cell_mut` directly is not counted and is written inline; one
+    /// replaced that way can stay in the table unused.
+    Incremental,
+    /// No table: every string is written inline. Nothing is collected on
+    /// save, which suits files written once and handed on.
+    Inline,
+}
+
 /// An Excel workbook containing worksheets.
 pub struct Workbook {
     /// List of worksheets.
     pub worksheets: Vec<Worksheet>,
-    /// Sheet names (parallel to worksheets).
-    pub sheet_names: Vec<String>,
+    /// Sheet names (parallel to worksheets), read through
+    /// [`Workbook::sheet_names`]. Private so that every change goes through
+    /// a method that keeps the name index in step.
+    pub(crate) sheet_names: Vec<String>,
     /// Named ranges defined in the workbook.
     pub named_ranges: Vec<NamedRange>,
     /// Compression level for saving.
     pub compression: CompressionLevel,
+    /// How NaN and infinite numbers are saved.
+    pub non_finite_numbers: NonFiniteNumbers,
+    /// How numbers are written on save.
+    pub number_precision: NumberPrecision,
+    /// Which strings go in the shared string table on save.
+    pub shared_strings: SharedStrings,
+    /// Write runs of filled-down formulas as shared formula groups on save.
+    pub share_formulas: bool,
+    /// How blank cells read back and how writing a blank treats a cell.
+    pub blank_policy: BlankPolicy,
+    /// Language formulas are typed in; set cells convert to en-US form.
+    pub formula_locale: Option<FormulaLocale>,
     /// Style registry for fonts, fills, borders, number formats, and cell formats.
     pub styles: StyleRegistry,
     /// Index of the active (selected) sheet tab.
@@ -78,16 +232,35 @@ pub struct Workbook {
     /// epoch). Date serials are stored as written; this preserves the flag so
     /// consumers can interpret them against the right epoch.
     pub date1904: bool,
+    /// Workbook protection (structure and window locks), if any.
+    pub protection: Option<WorkbookProtection>,
     /// Monotonic source for Worksheet::uid values; never reused so stale
     /// handles can't silently resolve to a different sheet.
     next_sheet_uid: u64,
+    /// Position of each sheet by name, so lookups by name don't scan
+    /// `sheet_names`; see [`Workbook::sheet_position`].
+    sheet_lookup: HashMap<String, usize>,
+    /// The loaded file had a calcChain part, which is dropped rather than
+    /// saved stale; see [`Workbook::needs_full_calc`].
+    calc_chain_dropped: bool,
     /// Pivot-table parts preserved verbatim from a loaded file so a load/save
     /// round-trip does not drop them. Not modeled; see [`PivotArtifacts`].
     pub pivots: PivotArtifacts,
+    /// Unmodeled workbook-level parts (theme, VBA project, external links,
+    /// custom XML, ...) kept verbatim from a loaded file.
+    pub preserved: crate::passthrough::PreservedParts,
+    /// Author, title and timestamps written to docProps; read back on load.
+    pub properties: DocumentProperties,
+    /// Theme colors and indexed palette that theme and indexed [`Color`]s
+    /// resolve against; read from the file on load, the Office defaults
+    /// otherwise. Not written: a loaded theme part is kept in `preserved`.
+    ///
+    /// [`Color`]: crate::style::Color
+    pub theme: crate::theme::Theme,
 }
 
 /// (sheet name, sheet id, relationship id, visibility) parsed from workbook.xml.
-type SheetInfo = (String, u32, String, SheetVisibility);
+pub(crate) type SheetInfo = (String, u32, String, SheetVisibility);
 
 /// A single entry from a worksheet's .rels part.
 #[derive(Clone, Debug)]
@@ -136,6 +309,11 @@ struct SheetParseInput {
     visibility: SheetVisibility,
     sheet_xml: Vec<u8>,
     comments_xml: Option<Vec<u8>>,
+    /// The legacy VML drawing holding the comment boxes, read only when the
+    /// sheet has comments.
+    comments_vml: Option<Vec<u8>>,
+    /// The sheet's threadedComments part, if it has threaded comments.
+    threaded_comments_xml: Option<Vec<u8>>,
     rels: HashMap<String, SheetRel>,
     table_xmls: Vec<Vec<u8>>,
     /// The sheet's drawing part XML, if it references one.
@@ -149,6 +327,8 @@ struct SheetParseInput {
     /// Pivot-table relationships from this sheet's .rels, as (id, type, target),
     /// preserved so pivot tables anchored on the sheet survive a save.
     pivot_rels: Vec<(String, String, String)>,
+    /// A dialog or macro sheet, kept verbatim instead of parsed.
+    opaque: Option<crate::opaque_sheet::OpaqueSheet>,
 }
 
 /// Resolve a relationship target relative to the part that declares it.
@@ -174,29 +354,43 @@ pub(crate) fn resolve_rel_target(base_part: &str, target: &str) -> String {
     parts.join("/")
 }
 
-/// Build the value of a `_xlnm.Print_Area` defined name: the range qualified
-/// with its sheet and made absolute, e.g. "Sheet1"!$A$1:$D$20. A sheet name
-/// with a space or special char is wrapped in single quotes.
-fn qualify_print_area(sheet: &str, area: &str) -> String {
+/// Build the value of a sheet-bound defined name such as `_xlnm.Print_Area`:
+/// the range qualified with its sheet and made absolute, e.g. "Sheet1"!$A$1:$D$20. A sheet name
+/// with a space or special char is wrapped in single quotes. Several
+/// comma-separated ranges are each qualified.
+pub(crate) fn qualify_sheet_range(sheet: &str, area: &str) -> String {
     let sheet_ref = if sheet.chars().all(|c| c.is_alphanumeric() || c == '_') {
         sheet.to_string()
     } else {
         format!("'{}'", sheet.replace('\'', "''"))
     };
-    let abs: String = area
-        .split(':')
-        .map(absolute_ref)
+    area.split(',')
+        .map(|range| {
+            let abs: String = range
+                .trim()
+                .split(':')
+                .map(absolute_ref)
+                .collect::<Vec<_>>()
+                .join(":");
+            format!("{}!{}", sheet_ref, abs)
+        })
         .collect::<Vec<_>>()
-        .join(":");
-    format!("{}!{}", sheet_ref, abs)
+        .join(",")
 }
 
-/// Add `$` anchors to a plain A1 cell reference (e.g. "A1" -> "$A$1"). Leaves an
+/// Add `$` anchors to a plain A1 cell reference (e.g. "A1" -> "$A$1"), or to
+/// a whole row or column ("1" -> "$1", "A" -> "$A"). Leaves an
 /// already-anchored or unparseable reference unchanged.
 fn absolute_ref(cell: &str) -> String {
     if cell.contains('$') {
         return cell.to_string();
     }
+    if !cell.is_empty()
+        && (cell.bytes().all(|b| b.is_ascii_digit())
+            || cell.bytes().all(|b| b.is_ascii_alphabetic()))
+    {
+        return format!("${}", cell);
+    }
     let bytes = cell.as_bytes();
     let split = bytes.iter().position(|b| b.is_ascii_digit());
     match split {
@@ -224,12 +418,6 @@ fn normalize_subtotal(agg: &str) -> String {
     .to_string()
 }
 
-/// Whether bytes are an OLE2/CFB container (an encrypted OOXML workbook) rather
-/// than a ZIP. Cheap magic-byte check; needs no crypto feature.
-fn looks_encrypted(data: &[u8]) -> bool {
-    data.len() >= 8 && data[..8] == [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]
-}
-
 /// Capitalize the first letter for a data-field display label ("sum" -> "Sum").
 fn cap_first(s: &str) -> String {
     let mut chars = s.chars();
@@ -241,13 +429,14 @@ fn cap_first(s: &str) -> String {
 
 /// Convert a stored cell value to a formula value (formulas are handled by the
 /// evaluator, not here, so a bare Formula maps to its cached text if any).
-fn cell_value_to_formula(value: &CellValue) -> crate::formula::FormulaValue {
+pub(crate) fn cell_value_to_formula(value: &CellValue) -> crate::formula::FormulaValue {
     use crate::formula::FormulaValue;
     match value {
         CellValue::Number(n) => FormulaValue::Number(*n),
         CellValue::Boolean(b) => FormulaValue::Bool(*b),
         CellValue::String(s) => FormulaValue::Text(s.to_string()),
-        CellValue::Date(s) => FormulaValue::Text(s.clone()),
+        // The engine's date functions count serials in the 1900 system.
+        CellValue::Date(d) => FormulaValue::Number(d.to_excel(false)),
         CellValue::Formula(f) => FormulaValue::Text(f.clone()),
         CellValue::Empty => FormulaValue::Empty,
     }
@@ -268,7 +457,7 @@ impl crate::formula::CellResolver for WorkbookResolver<'_> {
         use crate::formula::FormulaValue;
         let sheet_idx = match sheet {
             None => self.current_sheet,
-            Some(name) => match self.wb.sheet_names.iter().position(|n| n == name) {
+            Some(name) => match self.wb.sheet_position(name) {
                 Some(i) => i,
                 None => return FormulaValue::Error("#REF!".to_string()),
             },
@@ -285,7 +474,17 @@ impl crate::formula::CellResolver for WorkbookResolver<'_> {
                 if self.depth > 128 {
                     return FormulaValue::Error("#REF!".to_string());
                 }
-                let f = f.clone();
+                let f = if f.contains('[') {
+                    match self
+                        .wb
+                        .expand_structured_refs_at(sheet_idx, f, Some((row, col)))
+                    {
+                        Ok(f) => f,
+                        Err(_) => return FormulaValue::Error("#REF!".to_string()),
+                    }
+                } else {
+                    f.clone()
+                };
                 self.visited.insert(key);
                 let saved = self.current_sheet;
                 self.current_sheet = sheet_idx;
@@ -363,11 +562,23 @@ impl Workbook {
             sheet_names: Vec::new(),
             named_ranges: Vec::new(),
             compression: CompressionLevel::default(),
+            non_finite_numbers: NonFiniteNumbers::default(),
+            number_precision: NumberPrecision::default(),
+            shared_strings: SharedStrings::default(),
+            share_formulas: false,
+            blank_policy: BlankPolicy::default(),
+            formula_locale: None,
             styles: StyleRegistry::new(),
             active_sheet: 0,
             date1904: false,
+            protection: None,
             next_sheet_uid: 1,
+            sheet_lookup: HashMap::new(),
+            calc_chain_dropped: false,
             pivots: PivotArtifacts::default(),
+            preserved: crate::passthrough::PreservedParts::default(),
+            properties: DocumentProperties::default(),
+            theme: crate::theme::Theme::default(),
         }
     }
 
@@ -376,6 +587,59 @@ impl Workbook {
         self.compression = level;
     }
 
+    /// Set how NaN and infinite numbers are saved.
+    pub fn set_non_finite_numbers(&mut self, policy: NonFiniteNumbers) {
+        self.non_finite_numbers = policy;
+    }
+
+    /// Set how numbers are written on save.
+    pub fn set_number_precision(&mut self, precision: NumberPrecision) {
+        self.number_precision = precision;
+    }
+
+    /// Set which strings saving puts in the shared string table; see
+    /// [`SharedStrings`].
+    pub fn set_shared_strings(&mut self, mode: SharedStrings) {
+        let track = mode == SharedStrings::Incremental;
+        if track != (self.shared_strings == SharedStrings::Incremental) {
+            for ws in &mut self.worksheets {
+                ws.track_strings(track);
+            }
+        }
+        self.shared_strings = mode;
+    }
+
+    /// Set whether saving writes runs of filled-down formulas as shared
+    /// formula groups (`<f t="shared">`): a formula repeated down a column,
+    /// its relative references moving with the row, is written once for the
+    /// whole run. Smaller files that Excel loads faster; off by default.
+    pub fn set_share_formulas(&mut self, share: bool) {
+        self.share_formulas = share;
+    }
+
+    /// Set how blank cells read back and whether writing a blank clears a
+    /// cell or leaves it untouched.
+    pub fn set_blank_policy(&mut self, policy: BlankPolicy) {
+        self.blank_policy = policy;
+    }
+
+    /// Set the language formulas are typed in. While set, formulas stored
+    /// through [`Workbook::set_cell_value`], [`Workbook::set_cell_value_in_sheet`]
+    /// and [`Workbook::set_cell_formula`] are converted from it to the en-US
+    /// form the file stores (`SUMME(A1;A2)` becomes `SUM(A1,A2)`). `None`
+    /// stores formulas as given.
+    pub fn set_formula_locale(&mut self, locale: Option<FormulaLocale>) {
+        self.formula_locale = locale;
+    }
+
+    /// Convert a formula value typed in the workbook's formula locale.
+    fn canonical_value(&self, value: CellValue) -> CellValue {
+        match (value, &self.formula_locale) {
+            (CellValue::Formula(f), Some(locale)) => CellValue::Formula(locale.to_canonical(&f)),
+            (value, _) => value,
+        }
+    }
+
     /// Load a workbook from a file path.
     pub fn load(path: &str) -> Result<Self> {
         let file = File::open(path).map_err(|e| {
@@ -385,41 +649,32 @@ impl Workbook {
             ))
         })?;
 
-        let mut archive = ZipArchive::new(BufReader::new(file)).map_err(|e| {
-            // A CFB container fails to open as a ZIP; point at the password loader.
-            if std::fs::read(path)
-                .ok()
-                .is_some_and(|d| looks_encrypted(&d))
-            {
-                RustypyxlError::InvalidFormat(
-                    "workbook is encrypted; open it with a password via load_with_password".into(),
-                )
-            } else {
-                RustypyxlError::from(e)
-            }
-        })?;
+        // Anything that fails to open is checked against the other formats a
+        // caller might have handed over, for an error that names them.
+        let explain = |e: RustypyxlError| {
+            let info = crate::sniff::detect_format_file(path).ok();
+            crate::sniff::explain_load_error(e, info, Some("load_with_password"))
+        };
+        let mut archive =
+            ZipArchive::new(BufReader::new(file)).map_err(|e| explain(RustypyxlError::from(e)))?;
 
         let mut workbook = Workbook::new();
-        workbook.parse_workbook(&mut archive)?;
+        workbook.parse_workbook(&mut archive).map_err(explain)?;
 
         Ok(workbook)
     }
 
     /// Load a workbook from bytes (e.g., from memory or network).
     pub fn load_from_bytes(data: &[u8]) -> Result<Self> {
-        // An encrypted workbook is an OLE2/CFB container, not a ZIP; give a
-        // clear error pointing at the password-taking loader.
-        if looks_encrypted(data) {
-            return Err(RustypyxlError::InvalidFormat(
-                "workbook is encrypted; open it with a password via load_from_bytes_with_password"
-                    .into(),
-            ));
-        }
+        let explain = |e: RustypyxlError| {
+            let info = crate::sniff::detect_format(data);
+            crate::sniff::explain_load_error(e, Some(info), Some("load_from_bytes_with_password"))
+        };
         let cursor = Cursor::new(data);
-        let mut archive = ZipArchive::new(cursor)?;
+        let mut archive = ZipArchive::new(cursor).map_err(|e| explain(RustypyxlError::from(e)))?;
 
         let mut workbook = Workbook::new();
-        workbook.parse_workbook(&mut archive)?;
+        workbook.parse_workbook(&mut archive).map_err(explain)?;
 
         Ok(workbook)
     }
@@ -449,18 +704,51 @@ impl Workbook {
         Self::load_from_bytes_with_password(&data, password)
     }
 
-    /// Get the active (first) worksheet.
+    /// Get the active worksheet: the selected tab of a loaded file, or the
+    /// first sheet of a new workbook.
     pub fn active(&self) -> Result<&Worksheet> {
-        self.worksheets.first().ok_or(RustypyxlError::NoWorksheets)
+        let index = self.active_sheet_index();
+        self.worksheets
+            .get(index)
+            .ok_or(RustypyxlError::NoWorksheets)
     }
 
     /// Get a mutable reference to the active worksheet.
     pub fn active_mut(&mut self) -> Result<&mut Worksheet> {
+        let index = self.active_sheet_index();
         self.worksheets
-            .first_mut()
+            .get_mut(index)
             .ok_or(RustypyxlError::NoWorksheets)
     }
 
+    /// Index of the active sheet tab, kept within the sheets present.
+    pub fn active_sheet_index(&self) -> usize {
+        self.active_sheet
+            .min(self.worksheets.len().saturating_sub(1))
+    }
+
+    /// Make the sheet at `index` the active tab. Fails if there is no such
+    /// sheet or it is hidden: Excel opens a workbook on its active tab, so
+    /// that tab has to be visible.
+    pub fn set_active_sheet(&mut self, index: usize) -> Result<()> {
+        let ws = self.worksheets.get(index).ok_or_else(|| {
+            RustypyxlError::custom(format!(
+                "Sheet index {} is out of range ({} sheets)",
+                index,
+                self.worksheets.len()
+            ))
+        })?;
+        if ws.visibility != SheetVisibility::Visible {
+            return Err(RustypyxlError::custom(format!(
+                "Sheet '{}' is {} and cannot be the active tab",
+                self.sheet_names[index],
+                ws.visibility.as_str()
+            )));
+        }
+        self.active_sheet = index;
+        Ok(())
+    }
+
     /// Get all worksheets.
     pub fn worksheets(&self) -> &[Worksheet] {
         &self.worksheets
@@ -473,22 +761,79 @@ impl Workbook {
 
     /// Get a worksheet by name.
     pub fn get_sheet_by_name(&self, name: &str) -> Result<&Worksheet> {
-        for (idx, sheet_name) in self.sheet_names.iter().enumerate() {
-            if sheet_name == name {
-                return Ok(&self.worksheets[idx]);
-            }
-        }
-        Err(RustypyxlError::WorksheetNotFound(name.to_string()))
+        self.sheet_position(name)
+            .map(|idx| &self.worksheets[idx])
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(name.to_string()))
     }
 
     /// Get a mutable worksheet by name.
     pub fn get_sheet_by_name_mut(&mut self, name: &str) -> Result<&mut Worksheet> {
-        for (idx, sheet_name) in self.sheet_names.iter().enumerate() {
-            if sheet_name == name {
-                return Ok(&mut self.worksheets[idx]);
-            }
+        self.sheet_position(name)
+            .map(|idx| &mut self.worksheets[idx])
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(name.to_string()))
+    }
+
+    /// Position of the sheet called `name` in the tab order.
+    ///
+    /// Answered from a name index that sheet creation, removal, reordering
+    /// and [`Workbook::rename_sheet`] keep current.
+    pub fn sheet_position(&self, name: &str) -> Option<usize> {
+        let idx = self.sheet_lookup.get(name).copied();
+        debug_assert!(
+            self.sheet_lookup.len() == self.sheet_names.len()
+                && match idx {
+                    Some(idx) => self.sheet_names.get(idx).is_some_and(|n| n == name),
+                    None => !self.sheet_names.iter().any(|n| n == name),
+                },
+            "sheet name index out of sync with sheet_names looking up {name:?}"
+        );
+        idx
+    }
+
+    /// Append `worksheet` to the end of the tab order under `name`. The
+    /// sheet should already carry a uid from [`Workbook::allocate_sheet_uid`].
+    /// Fails if a sheet called `name` already exists.
+    pub fn push_sheet(&mut self, name: String, mut worksheet: Worksheet) -> Result<&mut Worksheet> {
+        if self.sheet_position(&name).is_some() {
+            return Err(RustypyxlError::WorksheetAlreadyExists(name));
+        }
+        if self.shared_strings == SharedStrings::Incremental {
+            worksheet.track_strings(true);
+        }
+        self.sheet_lookup
+            .insert(name.clone(), self.worksheets.len());
+        self.worksheets.push(worksheet);
+        self.sheet_names.push(name);
+        Ok(self.worksheets.last_mut().unwrap())
+    }
+
+    /// Rename the sheet `old` to `new`. Only the name changes: formulas
+    /// referring to the sheet by its old name are left as they are.
+    pub fn rename_sheet(&mut self, old: &str, new: &str) -> Result<()> {
+        let idx = self
+            .sheet_position(old)
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(old.to_string()))?;
+        if old == new {
+            return Ok(());
+        }
+        if self.sheet_position(new).is_some() {
+            return Err(RustypyxlError::WorksheetAlreadyExists(new.to_string()));
         }
-        Err(RustypyxlError::WorksheetNotFound(name.to_string()))
+        self.sheet_lookup.remove(old);
+        self.sheet_lookup.insert(new.to_string(), idx);
+        self.sheet_names[idx] = new.to_string();
+        self.worksheets[idx].set_title(new);
+        Ok(())
+    }
+
+    /// Rebuild the name index from `sheet_names` after sheets move.
+    fn reindex_sheets(&mut self) {
+        self.sheet_lookup = self
+            .sheet_names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.clone(), idx))
+            .collect();
     }
 
     /// Evaluate every formula cell in the workbook and store each result as the
@@ -522,7 +867,14 @@ impl Workbook {
             };
             // Seed the current cell so a self-reference is caught as circular.
             resolver.visited.insert((*sidx, row, col));
-            let value = crate::formula::evaluate(formula, &mut resolver);
+            let value = if formula.contains('[') {
+                match self.expand_structured_refs_at(*sidx, formula, Some((row, col))) {
+                    Ok(f) => crate::formula::evaluate(&f, &mut resolver),
+                    Err(_) => FormulaValue::Error("#REF!".to_string()),
+                }
+            } else {
+                crate::formula::evaluate(formula, &mut resolver)
+            };
             results.push((*sidx, *key, value));
         }
 
@@ -693,19 +1045,42 @@ impl Workbook {
     /// Evaluate a formula string in the context of a sheet, resolving cell and
     /// range references against the workbook's current cell values. Formula
     /// cells referenced by the expression are evaluated recursively (with cycle
-    /// detection). Returns an Excel-style error value on unsupported syntax or
-    /// type errors rather than failing. See [`crate::formula`] for the supported
-    /// subset.
+    /// detection). Structured references to tables (`Sales[Qty]`) resolve to
+    /// the table's cells; one that does not, or that needs the formula's own
+    /// row, yields `#REF!`. Returns an Excel-style error value on unsupported
+    /// syntax or type errors rather than failing. See [`crate::formula`] for
+    /// the supported subset.
     pub fn evaluate_formula(
         &self,
         sheet_name: &str,
         formula: &str,
+    ) -> Result<crate::formula::FormulaValue> {
+        self.evaluate_formula_at(sheet_name, formula, None)
+    }
+
+    /// [`evaluate_formula`](Self::evaluate_formula) for a formula sitting in
+    /// `cell`, which `#This Row` and unqualified structured references need.
+    pub(crate) fn evaluate_formula_at(
+        &self,
+        sheet_name: &str,
+        formula: &str,
+        cell: Option<(u32, u32)>,
     ) -> Result<crate::formula::FormulaValue> {
         let idx = self
-            .sheet_names
-            .iter()
-            .position(|n| n == sheet_name)
+            .sheet_position(sheet_name)
             .ok_or_else(|| RustypyxlError::WorksheetNotFound(sheet_name.to_string()))?;
+        let expanded;
+        let formula = if formula.contains('[') {
+            match self.expand_structured_refs_at(idx, formula, cell) {
+                Ok(f) => {
+                    expanded = f;
+                    &expanded
+                }
+                Err(_) => return Ok(crate::formula::FormulaValue::Error("#REF!".to_string())),
+            }
+        } else {
+            formula
+        };
         let mut resolver = WorkbookResolver {
             wb: self,
             current_sheet: idx,
@@ -726,7 +1101,9 @@ impl Workbook {
     ) -> Result<crate::formula::FormulaValue> {
         let ws = self.get_sheet_by_name(sheet_name)?;
         match ws.get_cell_value(row, column) {
-            Some(CellValue::Formula(f)) => self.evaluate_formula(sheet_name, f),
+            Some(CellValue::Formula(f)) => {
+                self.evaluate_formula_at(sheet_name, f, Some((row, column)))
+            }
             Some(value) => Ok(cell_value_to_formula(value)),
             None => Ok(crate::formula::FormulaValue::Empty),
         }
@@ -750,16 +1127,13 @@ impl Workbook {
     pub fn create_sheet(&mut self, title: Option<String>) -> Result<&mut Worksheet> {
         let sheet_title = title.unwrap_or_else(|| format!("Sheet{}", self.worksheets.len() + 1));
 
-        if self.sheet_names.contains(&sheet_title) {
+        if self.sheet_position(&sheet_title).is_some() {
             return Err(RustypyxlError::WorksheetAlreadyExists(sheet_title));
         }
 
         let mut worksheet = Worksheet::new(sheet_title.clone());
         worksheet.uid = self.allocate_sheet_uid();
-        self.worksheets.push(worksheet);
-        self.sheet_names.push(sheet_title);
-
-        Ok(self.worksheets.last_mut().unwrap())
+        self.push_sheet(sheet_title, worksheet)
     }
 
     /// Hand out the next stable sheet uid. Callers adding worksheets to
@@ -786,30 +1160,125 @@ impl Workbook {
     /// the index in place so it lands on the next sheet (clamped to the end),
     /// matching openpyxl.
     pub fn remove_sheet(&mut self, sheet_name: &str) -> Result<()> {
-        for (idx, name) in self.sheet_names.iter().enumerate() {
-            if name == sheet_name {
-                self.worksheets.remove(idx);
-                self.sheet_names.remove(idx);
-                if idx < self.active_sheet {
-                    self.active_sheet -= 1;
+        let idx = self
+            .sheet_position(sheet_name)
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(sheet_name.to_string()))?;
+        self.worksheets.remove(idx);
+        self.sheet_names.remove(idx);
+        self.reindex_sheets();
+        // Names scoped to the sheet go with it; later sheets move up.
+        let removed = idx as u32;
+        self.named_ranges
+            .retain(|nr| nr.local_sheet_id != Some(removed));
+        for named in &mut self.named_ranges {
+            if let Some(local) = named.local_sheet_id.as_mut() {
+                if *local > removed {
+                    *local -= 1;
                 }
-                self.active_sheet = self
-                    .active_sheet
-                    .min(self.worksheets.len().saturating_sub(1));
-                return Ok(());
             }
         }
-        Err(RustypyxlError::WorksheetNotFound(sheet_name.to_string()))
+        if idx < self.active_sheet {
+            self.active_sheet -= 1;
+        }
+        self.active_sheet = self
+            .active_sheet
+            .min(self.worksheets.len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// Move a sheet to position `index` in the tab order. An index past the
+    /// end moves it last.
+    pub fn move_sheet(&mut self, sheet_name: &str, index: usize) -> Result<()> {
+        let from = self
+            .sheet_position(sheet_name)
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(sheet_name.to_string()))?;
+        let to = index.min(self.worksheets.len() - 1);
+        let mut order: Vec<usize> = (0..self.worksheets.len()).collect();
+        let moved = order.remove(from);
+        order.insert(to, moved);
+        self.apply_sheet_order(&order);
+        Ok(())
     }
 
-    /// Set a cell value in the active worksheet.
+    /// Reorder the sheets to match `order`, which must name every sheet
+    /// exactly once.
+    pub fn set_sheet_order<S: AsRef<str>>(&mut self, order: &[S]) -> Result<()> {
+        if order.len() != self.sheet_names.len() {
+            return Err(RustypyxlError::Custom(format!(
+                "sheet order lists {} sheets but the workbook has {}",
+                order.len(),
+                self.sheet_names.len()
+            )));
+        }
+        let mut placed = vec![false; self.sheet_names.len()];
+        let mut old_indices = Vec::with_capacity(order.len());
+        for name in order {
+            let name = name.as_ref();
+            let idx = self
+                .sheet_position(name)
+                .ok_or_else(|| RustypyxlError::WorksheetNotFound(name.to_string()))?;
+            if std::mem::replace(&mut placed[idx], true) {
+                return Err(RustypyxlError::Custom(format!(
+                    "sheet {:?} appears more than once in the sheet order",
+                    name
+                )));
+            }
+            old_indices.push(idx);
+        }
+        self.apply_sheet_order(&old_indices);
+        Ok(())
+    }
+
+    /// Rearrange sheets so new position `i` holds the sheet previously at
+    /// `old_indices[i]`. Sheet-scoped names and the active tab are positional,
+    /// so they are remapped to follow their sheets; relationship ids and part
+    /// names are derived from position at save time and need no fixing up.
+    fn apply_sheet_order(&mut self, old_indices: &[usize]) {
+        let mut new_index = vec![0usize; old_indices.len()];
+        for (new, &old) in old_indices.iter().enumerate() {
+            new_index[old] = new;
+        }
+
+        let mut worksheets: Vec<Option<Worksheet>> = std::mem::take(&mut self.worksheets)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut names: Vec<Option<String>> = std::mem::take(&mut self.sheet_names)
+            .into_iter()
+            .map(Some)
+            .collect();
+        for &old in old_indices {
+            self.worksheets.push(worksheets[old].take().unwrap());
+            self.sheet_names.push(names[old].take().unwrap());
+        }
+        self.reindex_sheets();
+
+        for named in &mut self.named_ranges {
+            if let Some(local) = named.local_sheet_id.as_mut() {
+                if let Some(&new) = new_index.get(*local as usize) {
+                    *local = new as u32;
+                }
+            }
+        }
+        if let Some(&new) = new_index.get(self.active_sheet) {
+            self.active_sheet = new;
+        }
+    }
+
+    /// Set a cell value in the active worksheet. Writing
+    /// [`CellValue::Empty`] follows the workbook's [`BlankPolicy`].
     pub fn set_cell_value(&mut self, row: u32, column: u32, value: CellValue) -> Result<()> {
+        let ignore = self.blank_policy.ignores_write(&value);
+        let value = self.canonical_value(value);
         let ws = self.active_mut()?;
-        ws.set_cell_value(row, column, value);
+        if !ignore {
+            ws.set_cell_value(row, column, value);
+        }
         Ok(())
     }
 
-    /// Set a cell value in a specific worksheet.
+    /// Set a cell value in a specific worksheet. Writing
+    /// [`CellValue::Empty`] follows the workbook's [`BlankPolicy`].
     pub fn set_cell_value_in_sheet(
         &mut self,
         sheet_name: &str,
@@ -817,8 +1286,12 @@ impl Workbook {
         column: u32,
         value: CellValue,
     ) -> Result<()> {
+        let ignore = self.blank_policy.ignores_write(&value);
+        let value = self.canonical_value(value);
         let ws = self.get_sheet_by_name_mut(sheet_name)?;
-        ws.set_cell_value(row, column, value);
+        if !ignore {
+            ws.set_cell_value(row, column, value);
+        }
         Ok(())
     }
 
@@ -857,20 +1330,34 @@ impl Workbook {
 
     /// Set a cell formula in the active worksheet.
     pub fn set_cell_formula(&mut self, row: u32, column: u32, formula: String) -> Result<()> {
+        let formula = match &self.formula_locale {
+            Some(locale) => locale.to_canonical(&formula),
+            None => formula,
+        };
         let ws = self.active_mut()?;
         ws.set_cell_formula(row, column, formula);
         Ok(())
     }
 
     /// Set a cell hyperlink in the active worksheet.
-    pub fn set_cell_hyperlink(&mut self, row: u32, column: u32, url: String) -> Result<()> {
+    pub fn set_cell_hyperlink<H: Into<crate::hyperlink::Hyperlink>>(
+        &mut self,
+        row: u32,
+        column: u32,
+        link: H,
+    ) -> Result<()> {
         let ws = self.active_mut()?;
-        ws.set_cell_hyperlink(row, column, url);
+        ws.set_cell_hyperlink(row, column, link);
         Ok(())
     }
 
     /// Set a cell comment in the active worksheet.
-    pub fn set_cell_comment(&mut self, row: u32, column: u32, comment: String) -> Result<()> {
+    pub fn set_cell_comment<C: Into<crate::comments::Comment>>(
+        &mut self,
+        row: u32,
+        column: u32,
+        comment: C,
+    ) -> Result<()> {
         let ws = self.active_mut()?;
         ws.set_cell_comment(row, column, comment);
         Ok(())
@@ -883,6 +1370,21 @@ impl Workbook {
         Ok(())
     }
 
+    /// Protect the workbook structure, optionally behind a password
+    /// (hashed with SHA-512; the password itself is not kept).
+    pub fn protect_workbook(&mut self, password: Option<&str>) {
+        let protection = WorkbookProtection::new();
+        self.protection = Some(match password {
+            Some(password) => protection.with_password(password),
+            None => protection,
+        });
+    }
+
+    /// Remove workbook protection.
+    pub fn unprotect_workbook(&mut self) {
+        self.protection = None;
+    }
+
     /// Disable protection on the active worksheet.
     pub fn disable_protection(&mut self) -> Result<()> {
         let ws = self.active_mut()?;
@@ -915,20 +1417,106 @@ impl Workbook {
         Ok(())
     }
 
-    /// Create a named range.
+    /// Create a workbook-global named range.
     pub fn create_named_range(&mut self, name: String, range: String) -> Result<()> {
-        if self.named_ranges.iter().any(|nr| nr.name == name) {
-            return Err(RustypyxlError::NamedRangeAlreadyExists(name));
+        self.add_named_range(NamedRange::new(name, range))
+    }
+
+    /// Add a defined name, global or sheet-scoped. A name may be reused in a
+    /// different scope, as Excel allows, but not twice in one scope.
+    pub fn add_named_range(&mut self, named_range: NamedRange) -> Result<()> {
+        if let Some(idx) = named_range.local_sheet_id {
+            if idx as usize >= self.worksheets.len() {
+                return Err(RustypyxlError::WorksheetNotFound(format!(
+                    "sheet index {}",
+                    idx
+                )));
+            }
         }
-        self.named_ranges.push(NamedRange {
-            name,
-            range,
-            local_sheet_id: None,
-            hidden: false,
-        });
+        if self.named_ranges.iter().any(|nr| {
+            nr.name == named_range.name && nr.local_sheet_id == named_range.local_sheet_id
+        }) {
+            return Err(RustypyxlError::NamedRangeAlreadyExists(named_range.name));
+        }
+        self.named_ranges.push(named_range);
         Ok(())
     }
 
+    /// The defined names scoped to a sheet, in definition order.
+    pub fn sheet_defined_names(&self, sheet_name: &str) -> Result<Vec<&NamedRange>> {
+        let idx = self.sheet_index(sheet_name)? as u32;
+        Ok(self
+            .named_ranges
+            .iter()
+            .filter(|nr| nr.local_sheet_id == Some(idx))
+            .collect())
+    }
+
+    /// Resolve a workbook-global name to the sheet and rectangle it covers:
+    /// `(sheet, min_row, min_col, max_row, max_col)`. Fails for an unknown
+    /// name or one that is not a single area on one sheet (a constant, a
+    /// formula, or a union).
+    pub fn resolve_named_range(&self, name: &str) -> Result<(String, u32, u32, u32, u32)> {
+        let named = self
+            .named_ranges
+            .iter()
+            .find(|nr| nr.name == name && nr.local_sheet_id.is_none())
+            .ok_or_else(|| RustypyxlError::NamedRangeNotFound(name.to_string()))?;
+        self.resolve_reference(named)
+    }
+
+    /// Resolve `name` as a formula on `sheet_name` would: the sheet's own
+    /// name first, then the workbook-global one.
+    pub fn resolve_named_range_on(
+        &self,
+        sheet_name: &str,
+        name: &str,
+    ) -> Result<(String, u32, u32, u32, u32)> {
+        let idx = self.sheet_index(sheet_name)? as u32;
+        let named = self
+            .named_ranges
+            .iter()
+            .find(|nr| nr.name == name && nr.local_sheet_id == Some(idx))
+            .or_else(|| {
+                self.named_ranges
+                    .iter()
+                    .find(|nr| nr.name == name && nr.local_sheet_id.is_none())
+            })
+            .ok_or_else(|| RustypyxlError::NamedRangeNotFound(name.to_string()))?;
+        self.resolve_reference(named)
+    }
+
+    fn resolve_reference(&self, named: &NamedRange) -> Result<(String, u32, u32, u32, u32)> {
+        let (sheet, (r1, c1, r2, c2)) = parse_name_reference(&named.range).ok_or_else(|| {
+            RustypyxlError::InvalidFormat(format!(
+                "{} does not refer to a single range: {}",
+                named.name, named.range
+            ))
+        })?;
+        // An unqualified reference in a sheet-scoped name means that sheet.
+        let sheet = sheet
+            .or_else(|| {
+                named
+                    .local_sheet_id
+                    .and_then(|idx| self.sheet_names.get(idx as usize).cloned())
+            })
+            .ok_or_else(|| {
+                RustypyxlError::InvalidFormat(format!(
+                    "{} does not name a sheet: {}",
+                    named.name, named.range
+                ))
+            })?;
+        if self.sheet_position(&sheet).is_none() {
+            return Err(RustypyxlError::WorksheetNotFound(sheet));
+        }
+        Ok((sheet, r1, c1, r2, c2))
+    }
+
+    fn sheet_index(&self, sheet_name: &str) -> Result<usize> {
+        self.sheet_position(sheet_name)
+            .ok_or_else(|| RustypyxlError::WorksheetNotFound(sheet_name.to_string()))
+    }
+
     /// Get a named range by name.
     pub fn get_named_range(&self, name: &str) -> Option<&str> {
         self.named_ranges
@@ -945,19 +1533,64 @@ impl Workbook {
             .collect()
     }
 
-    /// Save the workbook to a file.
+    /// Whether a save asks Excel to recalculate every formula on open:
+    /// true when the loaded file's calcChain was dropped, or when a formula
+    /// has no cached result because it was set or edited since loading.
+    pub fn needs_full_calc(&self) -> bool {
+        self.calc_chain_dropped
+            || self.worksheets.iter().any(|ws| {
+                ws.cells.values().any(|cell| {
+                    matches!(cell.value, CellValue::Formula(_))
+                        && cell.cached_formula_value.is_none()
+                })
+            })
+    }
+
+    /// Save the workbook to a file. A target held open by another process
+    /// fails with [`RustypyxlError::FileLocked`].
+    ///
+    /// The extension picks the workbook content type: `.xlsm` and `.xltm`
+    /// are written macro-enabled, and a workbook with macros (see
+    /// [`has_macros`](Self::has_macros)) refuses a `.xlsx` or `.xltx` name
+    /// rather than write a file Excel will not open.
     pub fn save(&self, path: &str) -> Result<()> {
-        let file = File::create(path)?;
-        self.save_to_writer(file)
+        let path = std::path::Path::new(path);
+        let content_type = self.main_content_type_for(path)?;
+        let file = crate::file_lock::create_file(path)?;
+        self.save_to_writer_as(file, content_type)
+    }
+
+    /// Save the workbook to a file, handling a target that another process
+    /// (typically Excel on Windows) holds open according to `policy`.
+    /// Returns the path actually written.
+    pub fn save_with_lock_policy(
+        &self,
+        path: &str,
+        policy: &crate::file_lock::LockedFilePolicy,
+    ) -> Result<std::path::PathBuf> {
+        let path = std::path::Path::new(path);
+        let content_type = self.main_content_type_for(path)?;
+        let (file, written) = crate::file_lock::create_with_policy(path, policy)?;
+        self.save_to_writer_as(file, content_type)?;
+        Ok(written)
     }
 
     /// Save the workbook to an in-memory byte vector.
     pub fn save_to_bytes(&self) -> Result<Vec<u8>> {
-        let buffer = Cursor::new(Vec::new());
-        let mut zip = self.create_zip_writer(buffer)?;
-        self.write_workbook_contents(&mut zip)?;
-        let cursor = zip.finish()?;
-        Ok(cursor.into_inner())
+        let mut buffer = Cursor::new(Vec::new());
+        self.save_to_writer_as(&mut buffer, self.main_content_type())?;
+        Ok(buffer.into_inner())
+    }
+
+    /// Save to an in-memory byte vector destined for a file called `path`,
+    /// whose extension decides the workbook content type (see [`save`]).
+    ///
+    /// [`save`]: Self::save
+    pub fn save_to_bytes_for(&self, path: &str) -> Result<Vec<u8>> {
+        let content_type = self.main_content_type_for(std::path::Path::new(path))?;
+        let mut buffer = Cursor::new(Vec::new());
+        self.save_to_writer_as(&mut buffer, content_type)?;
+        Ok(buffer.into_inner())
     }
 
     /// Save the workbook as password-protected (agile-encrypted) bytes. Requires
@@ -969,19 +1602,40 @@ impl Workbook {
         crate::crypto::encrypt(&plain, password)
     }
 
-    /// Save the workbook as a password-protected (encrypted) file. Requires the
-    /// `encrypt` feature.
+    /// Save the workbook as a password-protected (encrypted) file: the
+    /// package is wrapped in an agile-encryption CFB container, as
+    /// [`save_to_bytes_with_password`] does. Requires the `encrypt` feature.
+    ///
+    /// [`save_to_bytes_with_password`]: Self::save_to_bytes_with_password
     #[cfg(feature = "encrypt")]
     pub fn save_with_password(&self, path: &str, password: &str) -> Result<()> {
-        let bytes = self.save_to_bytes_with_password(password)?;
-        std::fs::write(path, bytes)?;
+        let bytes = crate::crypto::encrypt(&self.save_to_bytes_for(path)?, password)?;
+        let mut file = crate::file_lock::create_file(std::path::Path::new(path))?;
+        std::io::Write::write_all(&mut file, &bytes)?;
         Ok(())
     }
 
+    /// Save the workbook encrypted with `password`; the same as
+    /// [`save_with_password`]. Requires the `encrypt` feature.
+    ///
+    /// [`save_with_password`]: Self::save_with_password
+    #[cfg(feature = "encrypt")]
+    pub fn save_encrypted(&self, path: &str, password: &str) -> Result<()> {
+        self.save_with_password(path, password)
+    }
+
     /// Save the workbook to any writer that implements Write + Seek.
     pub fn save_to_writer<W: std::io::Write + Seek>(&self, writer: W) -> Result<()> {
+        self.save_to_writer_as(writer, self.main_content_type())
+    }
+
+    fn save_to_writer_as<W: std::io::Write + Seek>(
+        &self,
+        writer: W,
+        content_type: &str,
+    ) -> Result<()> {
         let mut zip = self.create_zip_writer(writer)?;
-        self.write_workbook_contents(&mut zip)?;
+        self.write_workbook_contents(&mut zip, content_type)?;
         zip.finish()?;
         Ok(())
     }
@@ -998,7 +1652,7 @@ impl Workbook {
         use zip::write::FileOptions;
         use zip::CompressionMethod;
 
-        match self.compression {
+        let options = match self.compression {
             CompressionLevel::None => FileOptions::default()
                 .large_file(false)
                 .compression_method(CompressionMethod::Stored),
@@ -1014,22 +1668,47 @@ impl Workbook {
                 .large_file(false)
                 .compression_method(CompressionMethod::Deflated)
                 .compression_level(Some(9)),
+        };
+        match self.properties.zip_time() {
+            Some(at) => options.last_modified_time(at),
+            None => options,
         }
     }
 
-    /// Write all workbook contents to a ZipWriter.
+    /// Write all workbook contents to a ZipWriter, declaring the workbook
+    /// part with `main_content_type`.
     fn write_workbook_contents<W: std::io::Write + Seek>(
         &self,
         zip: &mut zip::ZipWriter<W>,
+        main_content_type: &str,
     ) -> Result<()> {
         use std::io::Write;
-        use zip::write::FileOptions;
 
         let options = self.get_file_options();
 
+        if self.non_finite_numbers == NonFiniteNumbers::Error {
+            for worksheet in &self.worksheets {
+                let first = worksheet
+                    .all_cells()
+                    .filter_map(|(pos, cell)| match cell.value {
+                        CellValue::Number(n) if !n.is_finite() => Some((pos, n)),
+                        _ => None,
+                    })
+                    .min_by_key(|(pos, _)| *pos);
+                if let Some(((row, col), n)) = first {
+                    return Err(RustypyxlError::custom(format!(
+                        "{}!{} holds {}, which cannot be saved as a number",
+                        worksheet.title(),
+                        crate::utils::coordinate_from_row_col(row, col),
+                        n
+                    )));
+                }
+            }
+        }
+
         // Collect shared strings first to know if we have any
         let (shared_strings_vec, shared_strings_map, shared_strings_refs) =
-            writer::collect_shared_strings(&self.worksheets);
+            writer::collect_shared_strings(&self.worksheets, self.shared_strings);
         let has_shared_strings = !shared_strings_vec.is_empty();
 
         // Pre-compute per-sheet metadata so [Content_Types].xml, the sheet
@@ -1038,16 +1717,24 @@ impl Workbook {
             .worksheets
             .iter()
             .enumerate()
-            .filter(|(_, ws)| ws.cells.values().any(|cd| cd.comment.is_some()))
+            .filter(|(_, ws)| {
+                ws.opaque.is_none() && ws.cells.values().any(|cd| cd.comment.is_some())
+            })
             .map(|(idx, _)| (idx + 1) as u32)
             .collect();
+        // Authors of threaded comments, listed once in xl/persons/person.xml.
+        let persons = crate::comments::collect_persons(&self.worksheets);
 
         // Assign each table a workbook-unique id; part path is xl/tables/table{id}.xml
         let mut table_assignments: Vec<Vec<u32>> = Vec::with_capacity(self.worksheets.len());
         let mut next_table_id: u32 = 1;
         for worksheet in &self.worksheets {
-            let ids: Vec<u32> = worksheet
-                .tables
+            let tables = if worksheet.opaque.is_some() {
+                &[][..]
+            } else {
+                &worksheet.tables[..]
+            };
+            let ids: Vec<u32> = tables
                 .iter()
                 .map(|_| {
                     let id = next_table_id;
@@ -1071,6 +1758,11 @@ impl Workbook {
         let mut image_extensions: std::collections::BTreeSet<&'static str> =
             std::collections::BTreeSet::new();
         for (idx, worksheet) in self.worksheets.iter().enumerate() {
+            if worksheet.opaque.is_some() {
+                chart_assignments.push(Vec::new());
+                image_assignments.push(Vec::new());
+                continue;
+            }
             let chart_ids: Vec<u32> = worksheet
                 .charts
                 .iter()
@@ -1124,11 +1816,111 @@ impl Workbook {
         let pivot_part_paths: Vec<String> =
             self.pivots.parts.iter().map(|(p, _)| p.clone()).collect();
 
+        // Preserved workbook-level rels get the same treatment as pivot caches:
+        // fresh ids, with `<externalReferences>` rewritten to cite them.
+        let mut external_references_xml = self.preserved.external_references_xml.clone();
+        let mut preserved_rels: Vec<crate::passthrough::PreservedWorkbookRel> = self
+            .preserved
+            .rels
+            .iter()
+            .enumerate()
+            .map(|(i, rel)| {
+                let new_id = format!("rIdKeep{}", i + 1);
+                if let Some(xml) = external_references_xml.as_mut() {
+                    *xml = xml
+                        .replace(
+                            &format!("r:id=\"{}\"", rel.id),
+                            &format!("r:id=\"{}\"", new_id),
+                        )
+                        .replace(
+                            &format!("r:id='{}'", rel.id),
+                            &format!("r:id=\"{}\"", new_id),
+                        );
+                }
+                crate::passthrough::PreservedWorkbookRel {
+                    id: new_id,
+                    ..rel.clone()
+                }
+            })
+            .collect();
+
+        // Package path, content type, and workbook relationship of each sheet
+        // part. Preserved dialog/macro sheets keep their kind and bring the
+        // parts they reference along.
+        let mut part_overrides: Vec<(String, String)> = Vec::with_capacity(self.worksheets.len());
+        let mut sheet_rels: Vec<(String, &str)> = Vec::with_capacity(self.worksheets.len());
+        for (idx, worksheet) in self.worksheets.iter().enumerate() {
+            let sheet_id = (idx + 1) as u32;
+            match &worksheet.opaque {
+                Some(opaque) => {
+                    let dir = opaque.kind.part_dir();
+                    part_overrides.push((
+                        format!("xl/{}/sheet{}.xml", dir, sheet_id),
+                        opaque.kind.content_type().to_string(),
+                    ));
+                    sheet_rels.push((
+                        format!("{}/sheet{}.xml", dir, sheet_id),
+                        opaque.kind.rel_type(),
+                    ));
+                    for rel in &opaque.rels {
+                        if let Some((_, content_type)) = &rel.part {
+                            let (path, _) = crate::opaque_sheet::related_part_path(
+                                opaque.kind,
+                                sheet_id,
+                                &rel.target,
+                            );
+                            part_overrides.push((path, content_type.clone()));
+                        }
+                    }
+                }
+                None => {
+                    part_overrides.push((
+                        format!("xl/worksheets/sheet{}.xml", sheet_id),
+                        "application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"
+                            .to_string(),
+                    ));
+                    if worksheet.has_threaded_comments() {
+                        part_overrides.push((
+                            format!("xl/threadedComments/threadedComment{}.xml", sheet_id),
+                            "application/vnd.ms-excel.threadedcomments+xml".to_string(),
+                        ));
+                    }
+                    sheet_rels.push((
+                        format!("worksheets/sheet{}.xml", sheet_id),
+                        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet",
+                    ));
+                }
+            }
+        }
+
+        for part in &self.preserved.parts {
+            if let Some(content_type) = &part.content_type {
+                part_overrides.push((part.path.clone(), content_type.clone()));
+            }
+        }
+
+        // The persons part is a workbook-level part like the preserved ones,
+        // so it rides along with them in the overrides and workbook rels.
+        if !persons.is_empty() {
+            part_overrides.push((
+                "xl/persons/person.xml".to_string(),
+                "application/vnd.ms-excel.person+xml".to_string(),
+            ));
+            preserved_rels.push(crate::passthrough::PreservedWorkbookRel {
+                id: "rIdPersons".to_string(),
+                rel_type: "http://schemas.microsoft.com/office/2017/10/relationships/person"
+                    .to_string(),
+                target: "persons/person.xml".to_string(),
+                external: false,
+            });
+        }
+
         // Write [Content_Types].xml
         writer::write_content_types(
             zip,
             &options,
-            self.worksheets.len(),
+            main_content_type,
+            &part_overrides,
             has_shared_strings,
             &comment_sheet_ids,
             table_count,
@@ -1142,7 +1934,7 @@ impl Workbook {
         writer::write_rels(zip, &options)?;
 
         // Write docProps files
-        writer::write_doc_props(zip, &options)?;
+        writer::write_doc_props(zip, &options, &self.properties)?;
 
         // Write xl/workbook.xml
         let sheet_meta: Vec<(String, crate::worksheet::SheetVisibility)> = self
@@ -1151,20 +1943,18 @@ impl Workbook {
             .zip(&self.worksheets)
             .map(|(name, ws)| (name.clone(), ws.visibility))
             .collect();
-        // Excel stores each sheet's print area as a sheet-scoped
-        // `_xlnm.Print_Area` defined name, so synthesize those alongside the
-        // user's named ranges.
+        // Excel stores each sheet's print area and print titles as
+        // sheet-scoped `_xlnm.Print_Area` / `_xlnm.Print_Titles` defined
+        // names, so synthesize those alongside the user's named ranges.
         let mut all_named_ranges = self.named_ranges.clone();
-        for (idx, ws) in self.worksheets.iter().enumerate() {
-            if let Some(area) = ws.page_setup.as_ref().and_then(|ps| ps.print_area.as_ref()) {
-                all_named_ranges.push(NamedRange {
-                    name: "_xlnm.Print_Area".to_string(),
-                    range: qualify_print_area(&self.sheet_names[idx], area),
-                    local_sheet_id: Some(idx as u32),
-                    hidden: false,
-                });
-            }
-        }
+        all_named_ranges.extend(crate::pagesetup::print_defined_names(
+            &self.sheet_names,
+            &self.worksheets,
+        ));
+        all_named_ranges.extend(crate::tags::tag_defined_names(
+            &self.sheet_names,
+            &self.worksheets,
+        ));
         writer::write_workbook_xml(
             zip,
             &options,
@@ -1172,6 +1962,9 @@ impl Workbook {
             &all_named_ranges,
             self.active_sheet,
             self.date1904,
+            self.protection.as_ref(),
+            self.needs_full_calc(),
+            external_references_xml.as_deref(),
             pivot_caches_xml.as_deref(),
         )?;
 
@@ -1179,9 +1972,10 @@ impl Workbook {
         writer::write_workbook_rels(
             zip,
             &options,
-            self.worksheets.len(),
+            &sheet_rels,
             has_shared_strings,
             &pivot_cache_rels,
+            &preserved_rels,
         )?;
 
         // Write shared strings if we have any
@@ -1189,45 +1983,28 @@ impl Workbook {
             writer::write_shared_strings(zip, &options, &shared_strings_vec, shared_strings_refs)?;
         }
 
-        // Resolve styles set through the core API into registry xfs. Cells
-        // styled via set_cell_style/set_cell_font/... carry the style on
-        // CellData but have no xf index (style_index is None), so without
-        // this pass the writer would emit them unstyled.
-        let mut styles_for_save = self.styles.clone();
-        let style_overrides: Vec<std::collections::HashMap<u64, u32>> = self
-            .worksheets
-            .iter()
-            .map(|ws| {
-                let mut overrides = std::collections::HashMap::new();
-                for (key, cell) in &ws.cells {
-                    if cell.style_index.is_none()
-                        && (cell.style.is_some() || cell.number_format.is_some())
-                    {
-                        let mut style = cell
-                            .style
-                            .as_deref()
-                            .cloned()
-                            .unwrap_or_else(crate::style::CellStyle::new);
-                        if style.number_format.is_none() {
-                            style.number_format = cell.number_format.clone();
-                        }
-                        let idx = styles_for_save.get_or_add_cell_xf(&style);
-                        overrides.insert(*key, idx as u32);
-                    }
-                }
-                overrides
-            })
-            .collect();
+        let (mut styles_for_save, style_overrides) = self.resolve_styles_for_save();
 
         // Write styles.xml with the differential formats used by
         // conditional-formatting rules (referenced by dxfId)
         let dxfs = writer::collect_dxfs(&self.worksheets);
-        writer::write_styles_xml(zip, &options, &styles_for_save, &dxfs)?;
+        let style_xfs = styles_for_save.cell_style_xfs();
+        writer::write_styles_xml(zip, &options, &styles_for_save, &style_xfs, &dxfs)?;
+
+        if !persons.is_empty() {
+            zip.start_file("xl/persons/person.xml", options.clone())?;
+            zip.write_all(crate::comments::persons_xml(&persons).as_bytes())?;
+        }
 
         // Write each worksheet, its tables/comments, and its .rels part
         for (idx, worksheet) in self.worksheets.iter().enumerate() {
             let sheet_id = (idx + 1) as u32;
+            if let Some(opaque) = &worksheet.opaque {
+                Self::write_opaque_sheet(zip, &options, opaque, sheet_id)?;
+                continue;
+            }
             let has_comments = comment_sheet_ids.contains(&sheet_id);
+            let has_threaded = has_comments && worksheet.has_threaded_comments();
             let table_ids = &table_assignments[idx];
             let table_rel_ids: Vec<String> = table_ids
                 .iter()
@@ -1253,6 +2030,10 @@ impl Workbook {
                 has_comments,
                 &style_overrides[idx],
                 drawing_rel_id,
+                self.date1904,
+                self.non_finite_numbers,
+                self.number_precision,
+                self.share_formulas,
             )?;
 
             for (table, table_id) in worksheet.tables.iter().zip(table_ids) {
@@ -1316,6 +2097,16 @@ impl Workbook {
                 writer::write_comments_xml(zip, &options, worksheet, sheet_id)?;
                 writer::write_vml_drawing(zip, &options, worksheet, sheet_id)?;
             }
+            if has_threaded {
+                zip.start_file(
+                    format!("xl/threadedComments/threadedComment{}.xml", sheet_id),
+                    options.clone(),
+                )?;
+                zip.write_all(
+                    crate::comments::threaded_comments_xml(worksheet, sheet_id, &persons)
+                        .as_bytes(),
+                )?;
+            }
 
             // The sheet .rels part ties comments, external hyperlinks, and
             // tables to the relationship ids used in the worksheet XML.
@@ -1327,11 +2118,7 @@ impl Workbook {
                 || !worksheet.pivot_rels.is_empty()
             {
                 let rels_path = format!("xl/worksheets/_rels/sheet{}.xml.rels", sheet_id);
-                let rels_options: zip::write::FileOptions<
-                    'static,
-                    zip::write::ExtendedFileOptions,
-                > = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-                zip.start_file(&rels_path, rels_options)?;
+                zip.start_file(&rels_path, options.clone())?;
 
                 let mut rels_content = String::from(
                     "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\n",
@@ -1346,11 +2133,17 @@ impl Workbook {
                         sheet_id
                     ));
                 }
-                for (i, (_, url)) in external_links.iter().enumerate() {
+                if has_threaded {
+                    rels_content.push_str(&format!(
+                        "<Relationship Id=\"rIdThreaded\" Type=\"http://schemas.microsoft.com/office/2017/10/relationships/threadedComment\" Target=\"../threadedComments/threadedComment{}.xml\"/>\n",
+                        sheet_id
+                    ));
+                }
+                for (i, (_, link)) in external_links.iter().enumerate() {
                     rels_content.push_str(&format!(
                         "<Relationship Id=\"rIdHL{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink\" Target=\"{}\" TargetMode=\"External\"/>\n",
                         i + 1,
-                        writer::escape_xml(url)
+                        writer::escape_xml(&link.target)
                     ));
                 }
                 for table_id in table_ids {
@@ -1386,6 +2179,121 @@ impl Workbook {
             zip.write_all(bytes)?;
         }
 
+        // And the other unmodeled workbook-level parts.
+        for part in &self.preserved.parts {
+            zip.start_file(&part.path, options.clone())?;
+            zip.write_all(&part.bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Resolve styles set through the core API into registry xfs, returning
+    /// the registry to save and, per sheet, the xf index of each cell that
+    /// needs one. Cells styled via set_cell_style/set_cell_font/... carry the
+    /// style on CellData but have no xf index (style_index is None), so
+    /// without this pass the writer would emit them unstyled. Date-times
+    /// without a date format get a default one here too, or they would save
+    /// as bare serial numbers.
+    pub(crate) fn resolve_styles_for_save(
+        &self,
+    ) -> (StyleRegistry, Vec<std::collections::HashMap<u64, u32>>) {
+        let mut styles_for_save = self.styles.clone();
+        let style_overrides: Vec<std::collections::HashMap<u64, u32>> = self
+            .worksheets
+            .iter()
+            .map(|ws| {
+                let mut overrides = std::collections::HashMap::new();
+                for ((row, col), cell) in ws.all_cells() {
+                    let date_format = match &cell.value {
+                        CellValue::Date(d)
+                            if !cell
+                                .number_format_code()
+                                .is_some_and(crate::dates::is_date_format) =>
+                        {
+                            Some(crate::dates::default_format(d))
+                        }
+                        _ => None,
+                    };
+                    if date_format.is_some()
+                        || (cell.style_index.is_none()
+                            && (cell.style.is_some() || cell.number_format.is_some()))
+                    {
+                        let mut style = cell
+                            .style
+                            .as_deref()
+                            .cloned()
+                            .unwrap_or_else(crate::style::CellStyle::new);
+                        if style.number_format.is_none() {
+                            style.number_format = cell.number_format.clone();
+                        }
+                        if let Some(code) = date_format {
+                            style.number_format = Some(code.into());
+                        }
+                        let idx = styles_for_save.get_or_add_cell_xf(&style);
+                        overrides.insert(crate::worksheet::cell_key(row, col), idx as u32);
+                    }
+                }
+                overrides
+            })
+            .collect();
+        (styles_for_save, style_overrides)
+    }
+
+    /// Write a preserved dialog/macro sheet: its original part, the parts its
+    /// relationships point at (moved under a per-sheet directory), and a
+    /// .rels part retargeted to match. Relationship ids are kept, so the
+    /// `r:id` references inside the sheet XML stay valid.
+    fn write_opaque_sheet<W: std::io::Write + Seek>(
+        zip: &mut zip::ZipWriter<W>,
+        options: &zip::write::FileOptions<'static, zip::write::ExtendedFileOptions>,
+        opaque: &crate::opaque_sheet::OpaqueSheet,
+        sheet_id: u32,
+    ) -> Result<()> {
+        use std::io::Write;
+
+        let dir = opaque.kind.part_dir();
+        zip.start_file(format!("xl/{}/sheet{}.xml", dir, sheet_id), options.clone())?;
+        zip.write_all(&opaque.xml)?;
+        if opaque.rels.is_empty() {
+            return Ok(());
+        }
+
+        let mut rels = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\n",
+        );
+        for rel in &opaque.rels {
+            match &rel.part {
+                Some((bytes, _)) => {
+                    let (path, target) =
+                        crate::opaque_sheet::related_part_path(opaque.kind, sheet_id, &rel.target);
+                    zip.start_file(&path, options.clone())?;
+                    zip.write_all(bytes)?;
+                    rels.push_str(&format!(
+                        "<Relationship Id=\"{}\" Type=\"{}\" Target=\"{}\"/>\n",
+                        writer::escape_xml(&rel.id),
+                        writer::escape_xml(&rel.rel_type),
+                        writer::escape_xml(&target)
+                    ));
+                }
+                None if rel.target.contains("://") || rel.target.starts_with("mailto:") => {
+                    rels.push_str(&format!(
+                        "<Relationship Id=\"{}\" Type=\"{}\" Target=\"{}\" TargetMode=\"External\"/>\n",
+                        writer::escape_xml(&rel.id),
+                        writer::escape_xml(&rel.rel_type),
+                        writer::escape_xml(&rel.target)
+                    ));
+                }
+                // An internal part that was missing from the source archive.
+                None => {}
+            }
+        }
+        rels.push_str("</Relationships>");
+        zip.start_file(
+            format!("xl/{}/_rels/sheet{}.xml.rels", dir, sheet_id),
+            options.clone(),
+        )?;
+        zip.write_all(rels.as_bytes())?;
         Ok(())
     }
 
@@ -1397,6 +2305,9 @@ impl Workbook {
             Self::read_zip_file_to_vec(archive, "xl/_rels/workbook.xml.rels").ok();
         let shared_strings_xml = Self::read_zip_file_to_vec(archive, "xl/sharedStrings.xml").ok();
         let styles_xml = Self::read_zip_file_to_vec(archive, "xl/styles.xml").ok();
+        if let Ok(core_xml) = Self::read_zip_file_to_vec(archive, "docProps/core.xml") {
+            self.properties = DocumentProperties::from_core_xml(&core_xml);
+        }
 
         // Capture pivot-table parts verbatim so they survive a save; they are
         // preserved, not modeled.
@@ -1410,30 +2321,48 @@ impl Workbook {
         self.named_ranges = named_ranges;
         self.active_sheet = active_tab;
         self.date1904 = date1904;
+        self.protection = WorkbookProtection::find_in(&workbook_xml);
 
         // Parse workbook.xml.rels to get the mapping from rId to actual file paths
-        let rels_map: HashMap<String, String> = if let Some(rels_xml) = workbook_rels_xml {
+        let rels_map: HashMap<String, SheetRel> = if let Some(rels_xml) = workbook_rels_xml {
             Self::parse_workbook_rels(Cursor::new(&rels_xml))?
         } else {
             HashMap::new()
         };
 
+        let content_types = Self::read_zip_file_to_vec(archive, "[Content_Types].xml")
+            .map(|xml| crate::opaque_sheet::ContentTypes::parse(&xml))
+            .unwrap_or_default();
+
+        self.calc_chain_dropped = rels_map
+            .values()
+            .any(|rel| rel.rel_type.ends_with("/calcChain"));
+
+        // Keep the workbook-level parts that are not modeled (theme, VBA
+        // project, external links, ...) so they survive a save.
+        self.preserved =
+            Self::capture_preserved_parts(archive, &workbook_xml, &rels_map, &content_types);
+        self.theme = self.read_theme(styles_xml.as_deref());
+
+        // Threaded comments name their authors by id from the persons part.
+        let persons = rels_map
+            .values()
+            .find(|r| r.rel_type.ends_with("/person"))
+            .and_then(|r| {
+                Self::read_zip_file_to_vec(
+                    archive,
+                    &resolve_rel_target("xl/workbook.xml", &r.target),
+                )
+                .ok()
+            })
+            .map(|xml| crate::comments::parse_persons(&xml))
+            .unwrap_or_default();
+
         // Load all worksheet XML, sheet rels, comments, and table parts into memory
         let mut sheet_data: Vec<SheetParseInput> = Vec::with_capacity(sheet_info.len());
         for (sheet_name, sheet_id, sheet_rid, visibility) in &sheet_info {
-            // Look up the actual sheet path from the relationships, or fall back to sheetId-based path
-            let sheet_path = if let Some(target) = rels_map.get(sheet_rid) {
-                // Target is relative to xl/, e.g., "worksheets/sheet1.xml"
-                if let Some(stripped) = target.strip_prefix('/') {
-                    // Absolute path within the package (rare)
-                    stripped.to_string()
-                } else {
-                    format!("xl/{}", target)
-                }
-            } else {
-                // Fallback to legacy behavior if rels file is missing or incomplete
-                format!("xl/worksheets/sheet{}.xml", sheet_id)
-            };
+            let sheet_rel = rels_map.get(sheet_rid);
+            let sheet_path = Self::sheet_part_path(sheet_rel, *sheet_id);
             let sheet_xml = Self::read_zip_file_to_vec(archive, &sheet_path)?;
 
             // The sheet's .rels part lives at <dir>/_rels/<file>.rels
@@ -1450,6 +2379,58 @@ impl Workbook {
                 Err(_) => HashMap::new(),
             };
 
+            // Dialog and macro sheets are carried through unparsed.
+            if let Some(kind) = crate::opaque_sheet::OpaqueSheetKind::detect(
+                sheet_rel.map(|r| r.rel_type.as_str()),
+                &sheet_path,
+            ) {
+                let mut preserved: Vec<crate::opaque_sheet::PreservedRel> = rels
+                    .iter()
+                    .map(|(id, rel)| {
+                        let part = if rel.external {
+                            None
+                        } else {
+                            let path = resolve_rel_target(&sheet_path, &rel.target);
+                            Self::read_zip_file_to_vec(archive, &path)
+                                .ok()
+                                .map(|bytes| {
+                                    let content_type = content_types
+                                        .lookup(&path)
+                                        .unwrap_or_else(|| "application/octet-stream".to_string());
+                                    (bytes, content_type)
+                                })
+                        };
+                        crate::opaque_sheet::PreservedRel {
+                            id: id.clone(),
+                            rel_type: rel.rel_type.clone(),
+                            target: rel.target.clone(),
+                            part,
+                        }
+                    })
+                    .collect();
+                preserved.sort_by(|a, b| a.id.cmp(&b.id));
+                sheet_data.push(SheetParseInput {
+                    name: sheet_name.clone(),
+                    visibility: *visibility,
+                    sheet_xml: Vec::new(),
+                    comments_xml: None,
+                    comments_vml: None,
+                    threaded_comments_xml: None,
+                    rels: HashMap::new(),
+                    table_xmls: Vec::new(),
+                    drawing_xml: None,
+                    drawing_media: HashMap::new(),
+                    drawing_charts: HashMap::new(),
+                    pivot_rels: Vec::new(),
+                    opaque: Some(crate::opaque_sheet::OpaqueSheet {
+                        kind,
+                        xml: sheet_xml,
+                        rels: preserved,
+                    }),
+                });
+                continue;
+            }
+
             // Comments: resolve via the sheet rels (real files use xl/comments1.xml),
             // falling back to the legacy path this library used to write.
             let comments_path = rels
@@ -1458,6 +2439,20 @@ impl Workbook {
                 .map(|r| resolve_rel_target(&sheet_path, &r.target))
                 .unwrap_or_else(|| format!("xl/comments/comment{}.xml", sheet_id));
             let comments_xml = Self::read_zip_file_to_vec(archive, &comments_path).ok();
+            let comments_vml = comments_xml.as_ref().and_then(|_| {
+                let rel = rels
+                    .values()
+                    .find(|r| r.rel_type.ends_with("/vmlDrawing"))?;
+                Self::read_zip_file_to_vec(archive, &resolve_rel_target(&sheet_path, &rel.target))
+                    .ok()
+            });
+            let threaded_comments_xml = rels
+                .values()
+                .find(|r| r.rel_type.ends_with("/threadedComment"))
+                .and_then(|r| {
+                    Self::read_zip_file_to_vec(archive, &resolve_rel_target(&sheet_path, &r.target))
+                        .ok()
+                });
 
             // Table parts referenced from this sheet
             let table_xmls: Vec<Vec<u8>> = rels
@@ -1487,12 +2482,15 @@ impl Workbook {
                 visibility: *visibility,
                 sheet_xml,
                 comments_xml,
+                comments_vml,
+                threaded_comments_xml,
                 rels,
                 table_xmls,
                 drawing_xml,
                 drawing_media,
                 drawing_charts,
                 pivot_rels,
+                opaque: None,
             });
         }
 
@@ -1520,6 +2518,13 @@ impl Workbook {
         let parse_one = |input: &SheetParseInput| -> Result<(String, Worksheet)> {
             let mut worksheet = Worksheet::new(input.name.clone());
             worksheet.visibility = input.visibility;
+            if let Some(opaque) = &input.opaque {
+                let mut opaque = opaque.clone();
+                opaque.xml =
+                    crate::opaque_sheet::inline_shared_strings(&opaque.xml, shared_strings_ref);
+                worksheet.opaque = Some(opaque);
+                return Ok((input.name.clone(), worksheet));
+            }
             Self::parse_worksheet_xml(
                 Cursor::new(&input.sheet_xml),
                 shared_strings_ref,
@@ -1528,11 +2533,18 @@ impl Workbook {
                 dxfs_ref,
                 &mut worksheet,
                 input.sheet_xml.len(),
+                date1904,
             )?;
 
             if let Some(comments) = &input.comments_xml {
                 Self::parse_comments_xml(Cursor::new(comments), &mut worksheet)?;
             }
+            if let Some(vml) = &input.comments_vml {
+                Self::parse_comment_shapes(vml, &mut worksheet);
+            }
+            if let Some(xml) = &input.threaded_comments_xml {
+                crate::comments::parse_threaded_comments(xml, &persons, &mut worksheet)?;
+            }
 
             for table_xml in &input.table_xmls {
                 if let Ok(table) = Self::parse_table_xml(Cursor::new(table_xml)) {
@@ -1566,9 +2578,10 @@ impl Workbook {
         for result in worksheets {
             let (sheet_name, mut worksheet) = result?;
             worksheet.uid = self.allocate_sheet_uid();
-            self.worksheets.push(worksheet);
-            self.sheet_names.push(sheet_name);
+            self.push_sheet(sheet_name, worksheet)?;
         }
+        crate::tags::take_tags(&mut self.named_ranges, &mut self.worksheets);
+        crate::pagesetup::take_print_names(&mut self.named_ranges, &mut self.worksheets);
 
         // Store the style registry
         self.styles = style_registry;
@@ -1576,11 +2589,45 @@ impl Workbook {
         Ok(())
     }
 
+    /// The part holding a sheet: its workbook relationship's target, or the
+    /// sheetId-based path when the rels part is missing or incomplete.
+    pub(crate) fn sheet_part_path(sheet_rel: Option<&SheetRel>, sheet_id: u32) -> String {
+        match sheet_rel.map(|r| &r.target) {
+            // Target is relative to xl/, e.g., "worksheets/sheet1.xml"; a
+            // leading '/' makes it absolute within the package (rare)
+            Some(target) => match target.strip_prefix('/') {
+                Some(stripped) => stripped.to_string(),
+                None => format!("xl/{}", target),
+            },
+            None => format!("xl/worksheets/sheet{}.xml", sheet_id),
+        }
+    }
+
+    /// The theme colors from the preserved theme part and the indexed
+    /// palette from styles.xml, each falling back to the defaults.
+    fn read_theme(&self, styles_xml: Option<&[u8]>) -> crate::theme::Theme {
+        let theme_part = self
+            .preserved
+            .rels
+            .iter()
+            .find(|rel| !rel.external && rel.rel_type.ends_with("/theme"))
+            .map(|rel| resolve_rel_target("xl/workbook.xml", &rel.target))
+            .and_then(|path| self.preserved.parts.iter().find(|p| p.path == path));
+        let mut theme = match theme_part {
+            Some(part) => crate::theme::Theme::parse(&part.bytes),
+            None => crate::theme::Theme::default(),
+        };
+        if let Some(xml) = styles_xml {
+            theme.read_indexed_colors(xml);
+        }
+        theme
+    }
+
     /// Read a file from the ZIP archive into a Vec<u8>.
     /// The declared uncompressed size in the ZIP header is untrusted: it is
     /// rejected past a hard cap and only used for pre-allocation up to a small
     /// bound, so a crafted archive cannot trigger huge allocations up front.
-    fn read_zip_file_to_vec<R: Read + Seek>(
+    pub(crate) fn read_zip_file_to_vec<R: Read + Seek>(
         archive: &mut ZipArchive<R>,
         path: &str,
     ) -> Result<Vec<u8>> {
@@ -1602,6 +2649,94 @@ impl Workbook {
         Ok(buf)
     }
 
+    /// Capture the workbook relationships the writer does not regenerate, the
+    /// parts they point at, and everything reachable from those parts through
+    /// their own `.rels`, each with its source content type.
+    fn capture_preserved_parts<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        workbook_xml: &[u8],
+        rels_map: &HashMap<String, SheetRel>,
+        content_types: &crate::opaque_sheet::ContentTypes,
+    ) -> crate::passthrough::PreservedParts {
+        use crate::passthrough::{
+            is_regenerated_rel, is_writer_owned, rels_path_for, PreservedPart,
+            PreservedWorkbookRel, XLSX_WORKBOOK_CONTENT_TYPE,
+        };
+
+        let mut preserved = crate::passthrough::PreservedParts::default();
+        let mut pending: Vec<String> = Vec::new();
+        for (id, rel) in rels_map {
+            if is_regenerated_rel(&rel.rel_type) {
+                continue;
+            }
+            if !rel.external {
+                let path = resolve_rel_target("xl/workbook.xml", &rel.target);
+                if is_writer_owned(&path) || archive.index_for_name(&path).is_none() {
+                    continue;
+                }
+                pending.push(path);
+            }
+            preserved.rels.push(PreservedWorkbookRel {
+                id: id.clone(),
+                rel_type: rel.rel_type.clone(),
+                target: rel.target.clone(),
+                external: rel.external,
+            });
+        }
+        preserved.rels.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+        while let Some(path) = pending.pop() {
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            let Ok(bytes) = Self::read_zip_file_to_vec(archive, &path) else {
+                continue;
+            };
+            preserved.parts.push(PreservedPart {
+                content_type: Some(
+                    content_types
+                        .lookup(&path)
+                        .unwrap_or_else(|| "application/octet-stream".to_string()),
+                ),
+                path: path.clone(),
+                bytes,
+            });
+
+            let rels_path = rels_path_for(&path);
+            let Ok(rels_xml) = Self::read_zip_file_to_vec(archive, &rels_path) else {
+                continue;
+            };
+            for rel in Self::parse_sheet_rels(Cursor::new(&rels_xml))
+                .unwrap_or_default()
+                .values()
+            {
+                if rel.external {
+                    continue;
+                }
+                let target = resolve_rel_target(&path, &rel.target);
+                if !is_writer_owned(&target) {
+                    pending.push(target);
+                }
+            }
+            preserved.parts.push(PreservedPart {
+                path: rels_path,
+                bytes: rels_xml,
+                content_type: None,
+            });
+        }
+        preserved.parts.sort_by(|a, b| a.path.cmp(&b.path));
+
+        if !preserved.rels.is_empty() {
+            preserved.external_references_xml =
+                extract_xml_element(workbook_xml, "externalReferences");
+        }
+        preserved.workbook_content_type = content_types
+            .lookup("xl/workbook.xml")
+            .filter(|ct| ct != XLSX_WORKBOOK_CONTENT_TYPE && ct != "application/xml");
+        preserved
+    }
+
     /// Capture the pivot-table parts of a workbook verbatim: every file under
     /// `xl/pivotCache/` and `xl/pivotTables/` (including their `_rels`), the
     /// workbook `<pivotCaches>` element, and the workbook-level
@@ -2170,7 +3305,7 @@ impl Workbook {
 
     /// Parses workbook.xml and returns sheet info (name, sheetId, rId,
     /// visibility), named ranges, and the active tab index.
-    fn parse_workbook_xml<R: BufRead>(
+    pub(crate) fn parse_workbook_xml<R: BufRead>(
         reader: R,
     ) -> Result<(Vec<SheetInfo>, Vec<NamedRange>, usize, bool)> {
         let mut reader = Reader::from_reader(reader);
@@ -2190,14 +3325,13 @@ impl Workbook {
         let mut current_range: Option<String> = None;
         let mut current_local_sheet_id: Option<u32> = None;
         let mut current_hidden = false;
+        let mut current_comment: Option<String> = None;
         let mut in_defined_name = false;
 
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Empty(e)) => {
-                    let name = e.name();
                     let local = e.local_name();
-                    let name = name.as_ref();
                     let local = local.as_ref();
 
                     if local == b"workbookPr" {
@@ -2205,7 +3339,7 @@ impl Workbook {
                     }
 
                     // Handle self-closing sheet tags
-                    if name == b"sheet" || local == b"sheet" {
+                    if local == b"sheet" {
                         let mut sheet_name: Option<String> = None;
                         let mut sheet_id: Option<u32> = None;
                         let mut sheet_rid: Option<String> = None;
@@ -2236,7 +3370,7 @@ impl Workbook {
                         {
                             sheets.push((name, id, rid, sheet_state));
                         }
-                    } else if name == b"workbookView" || local == b"workbookView" {
+                    } else if local == b"workbookView" {
                         for attr in e.attributes().flatten() {
                             if attr.key.local_name().as_ref() == b"activeTab" {
                                 active_tab =
@@ -2246,13 +3380,11 @@ impl Workbook {
                     }
                 }
                 Ok(Event::Start(e)) => {
-                    let name = e.name();
                     let local = e.local_name();
-                    let name = name.as_ref();
                     let local = local.as_ref();
-                    let is_sheet = name == b"sheet" || local == b"sheet";
-                    let is_defined_names = name == b"definedNames" || local == b"definedNames";
-                    let is_defined_name = name == b"definedName" || local == b"definedName";
+                    let is_sheet = local == b"sheet";
+                    let is_defined_names = local == b"definedNames";
+                    let is_defined_name = local == b"definedName";
 
                     if local == b"workbookPr" {
                         date1904 = Self::parse_date1904(&e);
@@ -2276,6 +3408,8 @@ impl Workbook {
                             } else if attr_key == b"hidden" || attr_local == b"hidden" {
                                 let v = String::from_utf8_lossy(&attr.value);
                                 current_hidden = v == "1" || v == "true";
+                            } else if attr_local == b"comment" {
+                                current_comment = attr.unescape_value().ok().map(|v| v.to_string());
                             }
                         }
                     } else if is_sheet {
@@ -2303,20 +3437,16 @@ impl Workbook {
                         }
                     }
                 }
-                Ok(Event::Text(e)) => {
-                    if in_defined_name && in_defined_names {
-                        let text = e.unescape().unwrap_or_default();
-                        current_range = Some(text.to_string());
-                    }
+                Ok(Event::Text(e)) if in_defined_name && in_defined_names => {
+                    let text = e.unescape().unwrap_or_default();
+                    current_range = Some(text.to_string());
                 }
                 Ok(Event::End(e)) => {
-                    let name = e.name();
                     let local = e.local_name();
-                    let name = name.as_ref();
                     let local = local.as_ref();
-                    let is_sheet = name == b"sheet" || local == b"sheet";
-                    let is_defined_names = name == b"definedNames" || local == b"definedNames";
-                    let is_defined_name = name == b"definedName" || local == b"definedName";
+                    let is_sheet = local == b"sheet";
+                    let is_defined_names = local == b"definedNames";
+                    let is_defined_name = local == b"definedName";
 
                     if is_defined_name && in_defined_name {
                         if let (Some(name), Some(range)) =
@@ -2327,10 +3457,12 @@ impl Workbook {
                                 range,
                                 local_sheet_id: current_local_sheet_id.take(),
                                 hidden: current_hidden,
+                                comment: current_comment.take(),
                             });
                         }
                         current_local_sheet_id = None;
                         current_hidden = false;
+                        current_comment = None;
                         in_defined_name = false;
                     } else if is_defined_names {
                         in_defined_names = false;
@@ -2370,53 +3502,51 @@ impl Workbook {
     }
 
     /// Parses a worksheet's .rels part into a map of relationship id -> SheetRel.
-    fn parse_sheet_rels<R: BufRead>(reader: R) -> Result<HashMap<String, SheetRel>> {
+    pub(crate) fn parse_sheet_rels<R: BufRead>(reader: R) -> Result<HashMap<String, SheetRel>> {
         let mut reader = Reader::from_reader(reader);
         reader.config_mut().trim_text(true);
 
         let mut rels = HashMap::new();
-        let mut buf = Vec::new();
-
-        loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Empty(e)) | Ok(Event::Start(e)) => {
-                    if e.local_name().as_ref() == b"Relationship" {
-                        let mut rel_id: Option<String> = None;
-                        let mut rel_type: Option<String> = None;
-                        let mut target: Option<String> = None;
-                        let mut external = false;
+        let mut buf = Vec::new();
 
-                        for attr in e.attributes().flatten() {
-                            match attr.key.as_ref() {
-                                b"Id" => {
-                                    rel_id = Some(String::from_utf8_lossy(&attr.value).to_string())
-                                }
-                                b"Type" => {
-                                    rel_type =
-                                        Some(String::from_utf8_lossy(&attr.value).to_string())
-                                }
-                                b"Target" => {
-                                    // Targets may contain escaped entities (e.g. &amp; in URLs)
-                                    target = attr.unescape_value().ok().map(|v| v.to_string())
-                                }
-                                b"TargetMode" => {
-                                    external = attr.value.as_ref() == b"External";
-                                }
-                                _ => {}
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Empty(e)) | Ok(Event::Start(e))
+                    if e.local_name().as_ref() == b"Relationship" =>
+                {
+                    let mut rel_id: Option<String> = None;
+                    let mut rel_type: Option<String> = None;
+                    let mut target: Option<String> = None;
+                    let mut external = false;
+
+                    for attr in e.attributes().flatten() {
+                        match attr.key.as_ref() {
+                            b"Id" => {
+                                rel_id = Some(String::from_utf8_lossy(&attr.value).to_string())
+                            }
+                            b"Type" => {
+                                rel_type = Some(String::from_utf8_lossy(&attr.value).to_string())
+                            }
+                            b"Target" => {
+                                // Targets may contain escaped entities (e.g. &amp; in URLs)
+                                target = attr.unescape_value().ok().map(|v| v.to_string())
                             }
+                            b"TargetMode" => {
+                                external = attr.value.as_ref() == b"External";
+                            }
+                            _ => {}
                         }
+                    }
 
-                        if let (Some(id), Some(rel_type), Some(target)) = (rel_id, rel_type, target)
-                        {
-                            rels.insert(
-                                id,
-                                SheetRel {
-                                    rel_type,
-                                    target,
-                                    external,
-                                },
-                            );
-                        }
+                    if let (Some(id), Some(rel_type), Some(target)) = (rel_id, rel_type, target) {
+                        rels.insert(
+                            id,
+                            SheetRel {
+                                rel_type,
+                                target,
+                                external,
+                            },
+                        );
                     }
                 }
                 Ok(Event::Eof) => break,
@@ -2434,8 +3564,9 @@ impl Workbook {
         Ok(rels)
     }
 
-    /// Parses workbook.xml.rels and returns a mapping of relationship IDs to target paths.
-    fn parse_workbook_rels<R: BufRead>(reader: R) -> Result<HashMap<String, String>> {
+    /// Parses workbook.xml.rels and returns each relationship by id, with its
+    /// type (worksheet, dialogsheet, ...) and target path.
+    pub(crate) fn parse_workbook_rels<R: BufRead>(reader: R) -> Result<HashMap<String, SheetRel>> {
         let mut reader = Reader::from_reader(reader);
         reader.config_mut().trim_text(true);
 
@@ -2445,26 +3576,37 @@ impl Workbook {
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Empty(e)) | Ok(Event::Start(e)) => {
-                    let name = e.name();
                     let local = e.local_name();
-                    let name = name.as_ref();
                     let local = local.as_ref();
 
-                    if name == b"Relationship" || local == b"Relationship" {
+                    if local == b"Relationship" {
                         let mut rel_id: Option<String> = None;
+                        let mut rel_type = String::new();
                         let mut target: Option<String> = None;
+                        let mut external = false;
 
                         for attr in e.attributes().flatten() {
                             let attr_key = attr.key.as_ref();
                             if attr_key == b"Id" {
                                 rel_id = Some(String::from_utf8_lossy(&attr.value).to_string());
+                            } else if attr_key == b"Type" {
+                                rel_type = String::from_utf8_lossy(&attr.value).to_string();
                             } else if attr_key == b"Target" {
                                 target = Some(String::from_utf8_lossy(&attr.value).to_string());
+                            } else if attr_key == b"TargetMode" {
+                                external = attr.value.as_ref() == b"External";
                             }
                         }
 
                         if let (Some(id), Some(tgt)) = (rel_id, target) {
-                            rels.insert(id, tgt);
+                            rels.insert(
+                                id,
+                                SheetRel {
+                                    rel_type,
+                                    target: tgt,
+                                    external,
+                                },
+                            );
                         }
                     }
                 }
@@ -2504,7 +3646,7 @@ impl Workbook {
     /// Parse sharedStrings.xml. Each `<si>` returns its concatenated plain text
     /// and, when it is rich text (built from `<r>` runs), the runs preserved for
     /// round-trip.
-    fn parse_shared_strings_xml<R: BufRead>(
+    pub(crate) fn parse_shared_strings_xml<R: BufRead>(
         reader: R,
     ) -> Result<
         Vec<(
@@ -2546,19 +3688,15 @@ impl Workbook {
                     _ => {}
                 },
                 // rPr children are usually self-closing (<b/>, <sz .../>, ...).
-                Ok(Event::Empty(e)) => {
-                    if in_rpr {
-                        Self::parse_run_prop(&e, &mut run_font);
-                    }
+                Ok(Event::Empty(e)) if in_rpr => {
+                    Self::parse_run_prop(&e, &mut run_font);
                 }
-                Ok(Event::Text(e)) => {
-                    if in_t {
-                        let text = e.unescape().unwrap_or_default();
-                        if in_run {
-                            run_text.push_str(&text);
-                        } else {
-                            plain.push_str(&text);
-                        }
+                Ok(Event::Text(e)) if in_t => {
+                    let text = e.unescape().unwrap_or_default();
+                    if in_run {
+                        run_text.push_str(&text);
+                    } else {
+                        plain.push_str(&text);
                     }
                 }
                 Ok(Event::End(e)) => match e.local_name().as_ref() {
@@ -2615,6 +3753,14 @@ impl Workbook {
         None
     }
 
+    /// The group index of a shared `<f t="shared" si="..">`, or `None` for
+    /// an ordinary formula.
+    pub(crate) fn shared_formula_group(e: &quick_xml::events::BytesStart) -> Option<String> {
+        (Self::get_attr_str(e, b"t").as_deref() == Some("shared"))
+            .then(|| Self::get_attr_str(e, b"si"))
+            .flatten()
+    }
+
     /// Get an optional u32 attribute value from an XML element.
     #[allow(dead_code)]
     fn get_attr_u32(e: &quick_xml::events::BytesStart, key: &[u8]) -> Option<u32> {
@@ -2636,7 +3782,7 @@ impl Workbook {
 
     /// Parse font properties from an XML element (handles both Start and Empty events).
     fn parse_font_element(e: &quick_xml::events::BytesStart, font: &mut Font) {
-        let name = e.name();
+        let name = e.local_name();
         let name = name.as_ref();
         match name {
             b"b" => font.bold = true,
@@ -2668,7 +3814,7 @@ impl Workbook {
 
     /// Parse fill properties from an XML element.
     fn parse_fill_element(e: &quick_xml::events::BytesStart, fill: &mut Fill) {
-        let name = e.name();
+        let name = e.local_name();
         let name = name.as_ref();
         match name {
             b"patternFill" => {
@@ -2690,7 +3836,9 @@ impl Workbook {
         (style, color)
     }
 
-    fn parse_styles_xml(xml: &[u8]) -> Result<(HashMap<u32, Arc<CellStyle>>, StyleRegistry)> {
+    pub(crate) fn parse_styles_xml(
+        xml: &[u8],
+    ) -> Result<(HashMap<u32, Arc<CellStyle>>, StyleRegistry)> {
         let mut reader = Reader::from_reader(Cursor::new(xml));
         reader.config_mut().trim_text(true);
 
@@ -2718,7 +3866,7 @@ impl Workbook {
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Empty(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
 
                     // Handle font properties
@@ -2782,7 +3930,7 @@ impl Workbook {
                     }
                 }
                 Ok(Event::Start(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
 
                     if name == b"font" {
@@ -2815,7 +3963,7 @@ impl Workbook {
                     } else if in_fill {
                         Self::parse_fill_element(&e, &mut current_fill);
                     } else if in_border {
-                        let prop_name = e.name();
+                        let prop_name = e.local_name();
                         let prop_name = prop_name.as_ref();
                         // Handle border side start elements
                         if prop_name == b"left"
@@ -2848,7 +3996,7 @@ impl Workbook {
                     }
                 }
                 Ok(Event::End(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
 
                     if name == b"font" {
@@ -2910,7 +4058,15 @@ impl Workbook {
         let mut buf2 = Vec::new();
         let mut xf_index = 0u32;
         let mut current_xf = CellStyle::default();
+        // Set inside both xf lists; in_style_xfs tells cellStyleXfs apart.
         let mut in_cell_xfs = false;
+        let mut in_style_xfs = false;
+        let mut current_link = 0u32;
+        // cellStyleXfs in order, each cellXf's xfId into them, and the
+        // cellStyle entries naming them.
+        let mut style_xfs: Vec<CellStyle> = Vec::new();
+        let mut xf_links: HashMap<u32, u32> = HashMap::new();
+        let mut style_names: Vec<crate::named_styles::CellStyleEntry> = Vec::new();
         let mut in_xf = false;
         let mut has_alignment = false;
         let mut current_align = Alignment::default();
@@ -2920,15 +4076,21 @@ impl Workbook {
         loop {
             match reader2.read_event_into(&mut buf2) {
                 Ok(Event::Start(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
                     if name == b"cellXfs" {
                         in_cell_xfs = true;
                         xf_index = 0;
+                    } else if name == b"cellStyleXfs" {
+                        in_cell_xfs = true;
+                        in_style_xfs = true;
                     } else if name == b"xf" && in_cell_xfs {
                         in_xf = true;
                         current_xf = CellStyle::default();
                         current_align = Alignment::default();
+                        current_link = Self::get_attr_str(&e, b"xfId")
+                            .and_then(|v| v.parse().ok())
+                            .unwrap_or(0);
 
                         for attr in e.attributes().flatten() {
                             let attr_key = attr.key.as_ref();
@@ -3016,7 +4178,7 @@ impl Workbook {
                     }
                 }
                 Ok(Event::End(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
                     if name == b"xf" && in_xf && in_cell_xfs {
                         current_xf.alignment = if has_alignment {
@@ -3029,19 +4191,25 @@ impl Workbook {
                         } else {
                             None
                         };
-                        cell_styles.insert(xf_index, Arc::new(current_xf.clone()));
-                        xf_index += 1;
+                        if in_style_xfs {
+                            style_xfs.push(current_xf.clone());
+                        } else {
+                            xf_links.insert(xf_index, current_link);
+                            cell_styles.insert(xf_index, Arc::new(current_xf.clone()));
+                            xf_index += 1;
+                        }
                         in_xf = false;
                         has_alignment = false;
                         has_protection = false;
                         current_align = Alignment::default();
                         current_protection = Protection::default();
-                    } else if name == b"cellXfs" {
+                    } else if name == b"cellXfs" || name == b"cellStyleXfs" {
                         in_cell_xfs = false;
+                        in_style_xfs = false;
                     }
                 }
                 Ok(Event::Empty(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
                     if name == b"alignment" && in_xf && in_cell_xfs {
                         has_alignment = true;
@@ -3128,8 +4296,18 @@ impl Workbook {
                                 }
                             }
                         }
-                        cell_styles.insert(xf_index, Arc::new(xf));
-                        xf_index += 1;
+                        if in_style_xfs {
+                            style_xfs.push(xf);
+                        } else {
+                            let link = Self::get_attr_str(&e, b"xfId")
+                                .and_then(|v| v.parse().ok())
+                                .unwrap_or(0);
+                            xf_links.insert(xf_index, link);
+                            cell_styles.insert(xf_index, Arc::new(xf));
+                            xf_index += 1;
+                        }
+                    } else if name == b"cellStyle" {
+                        style_names.push(crate::named_styles::CellStyleEntry::from_element(&e));
                     }
                 }
                 Ok(Event::Eof) => break,
@@ -3220,6 +4398,7 @@ impl Workbook {
                     apply_number_format: style.number_format.is_some(),
                     apply_alignment: style.alignment.is_some(),
                     apply_protection: style.protection.is_some(),
+                    xf_id: 0,
                 };
                 registry.cell_xfs.push(xf);
             } else {
@@ -3233,6 +4412,19 @@ impl Workbook {
             registry.cell_xfs.push(CellXf::default());
         }
 
+        // Named styles, and the link from each cell format to its style
+        let names_by_xf = registry.read_named_styles(&style_xfs, &style_names);
+        for (index, style) in cell_styles.iter_mut() {
+            let Some(name) = xf_links.get(index).and_then(|link| names_by_xf.get(link)) else {
+                continue;
+            };
+            Arc::make_mut(style).named_style = Some(name.clone());
+            let xf_id = registry.named_style_index(name).unwrap_or(0);
+            if let Some(xf) = registry.cell_xfs.get_mut(*index as usize) {
+                xf.xf_id = xf_id;
+            }
+        }
+
         Ok((cell_styles, registry))
     }
 
@@ -3284,20 +4476,84 @@ impl Workbook {
         Some(estimate.min(possible)).filter(|cap| *cap > 0)
     }
 
-    /// Apply a frozen `<pane>` element to the worksheet's freeze_panes.
+    /// Apply a frozen `<pane>` element to the worksheet's freeze_panes. The
+    /// freeze cell comes from the splits; `topLeftCell` is where the pane
+    /// below them is scrolled to, kept as the view's scroll position when it
+    /// differs.
     fn parse_pane_attrs(e: &BytesStart, worksheet: &mut Worksheet) {
         let mut top_left: Option<String> = None;
         let mut frozen = false;
+        let mut x_split = 0u32;
+        let mut y_split = 0u32;
         for attr in e.attributes().flatten() {
             let val = String::from_utf8_lossy(&attr.value);
             match attr.key.as_ref() {
                 b"topLeftCell" => top_left = Some(val.to_string()),
                 b"state" => frozen = val == "frozen" || val == "frozenSplit",
+                // Splits are whole rows/columns when frozen, though some
+                // writers emit them as "1.0"
+                b"xSplit" => x_split = val.parse::<f64>().map_or(0, |v| v as u32),
+                b"ySplit" => y_split = val.parse::<f64>().map_or(0, |v| v as u32),
                 _ => {}
             }
         }
-        if frozen {
+        if !frozen {
+            return;
+        }
+        if x_split == 0 && y_split == 0 {
             worksheet.freeze_panes = top_left;
+            return;
+        }
+        let freeze_cell = crate::utils::coordinate_from_row_col(y_split + 1, x_split + 1);
+        worksheet.sheet_view.top_left_cell = top_left.filter(|cell| *cell != freeze_cell);
+        worksheet.freeze_panes = Some(freeze_cell);
+    }
+
+    /// Apply a `<selection>`: the active cell and selected ranges. The
+    /// default, a cursor on A1 or on the freeze cell, is left unset.
+    fn parse_selection_attrs(e: &BytesStart, worksheet: &mut Worksheet) {
+        let active_cell = Self::get_attr_str(e, b"activeCell");
+        let sqref = Self::get_attr_str(e, b"sqref");
+        if active_cell.is_none() && sqref.is_none() {
+            return;
+        }
+        let selection = sqref.filter(|sqref| Some(sqref) != active_cell.as_ref());
+        let default_cell = worksheet.freeze_panes.as_deref().unwrap_or("A1");
+        let view = &mut worksheet.sheet_view;
+        view.active_cell = active_cell.filter(|cell| cell != default_cell || selection.is_some());
+        view.selection = selection;
+    }
+
+    /// Apply the display attributes of the first `<sheetView>`; further
+    /// views (other workbook windows) are not modeled.
+    fn parse_sheet_view_attrs(e: &BytesStart, worksheet: &mut Worksheet) {
+        if Self::get_attr_str(e, b"workbookViewId").is_some_and(|id| id != "0") {
+            return;
+        }
+        let view = &mut worksheet.sheet_view;
+        for attr in e.attributes().flatten() {
+            let val = String::from_utf8_lossy(&attr.value);
+            match attr.key.as_ref() {
+                b"showGridLines" => view.show_grid_lines = val != "0" && val != "false",
+                b"rightToLeft" => view.right_to_left = val == "1" || val == "true",
+                b"topLeftCell" if val != "A1" => view.top_left_cell = Some(val.to_string()),
+                b"zoomScale" => {
+                    // 0 is sometimes written for the default; anything
+                    // else out of range is clamped as Excel does
+                    if let Ok(zoom) = val.parse::<u32>() {
+                        view.zoom_scale = match zoom {
+                            0 => 100,
+                            zoom => zoom.clamp(SheetView::MIN_ZOOM, SheetView::MAX_ZOOM),
+                        };
+                    }
+                }
+                b"view" => {
+                    if let Ok(kind) = SheetViewType::parse(&val) {
+                        view.view = kind;
+                    }
+                }
+                _ => {}
+            }
         }
     }
 
@@ -3443,10 +4699,8 @@ impl Workbook {
                     _ => {}
                 },
                 Ok(Event::End(e)) => match e.local_name().as_ref() {
-                    b"filters" => {
-                        if !values.is_empty() {
-                            filter = Some(FilterType::Values(std::mem::take(&mut values)));
-                        }
+                    b"filters" if !values.is_empty() => {
+                        filter = Some(FilterType::Values(std::mem::take(&mut values)));
                     }
                     b"customFilters" => {
                         let mut conditions = custom_conditions.drain(..);
@@ -3506,9 +4760,15 @@ impl Workbook {
         }
     }
 
-    /// Apply `<pageSetup>` to the worksheet's page setup.
-    fn parse_page_setup_attrs(e: &BytesStart, worksheet: &mut Worksheet) {
+    /// Apply `<pageSetup>` to the worksheet's page setup. The fit-to-page
+    /// counts only count when sheetPr turned fitting on; Excel's default
+    /// of one page is implied when they are absent.
+    fn parse_page_setup_attrs(e: &BytesStart, worksheet: &mut Worksheet, fit_to_page: bool) {
         let ps = worksheet.page_setup.get_or_insert_with(PageSetup::new);
+        if fit_to_page {
+            ps.fit_to_width = Some(1);
+            ps.fit_to_height = Some(1);
+        }
         for attr in e.attributes().flatten() {
             let val = String::from_utf8_lossy(&attr.value);
             match attr.key.as_ref() {
@@ -3529,8 +4789,8 @@ impl Workbook {
                         ps.scale = v;
                     }
                 }
-                b"fitToWidth" => ps.fit_to_width = val.parse().ok(),
-                b"fitToHeight" => ps.fit_to_height = val.parse().ok(),
+                b"fitToWidth" if fit_to_page => ps.fit_to_width = val.parse().ok(),
+                b"fitToHeight" if fit_to_page => ps.fit_to_height = val.parse().ok(),
                 b"firstPageNumber" => ps.first_page_number = val.parse().ok(),
                 b"blackAndWhite" => ps.black_and_white = val == "1" || val == "true",
                 b"draft" => ps.draft = val == "1" || val == "true",
@@ -3586,6 +4846,7 @@ impl Workbook {
                 b"error" => dv.error_message = Some(val),
                 b"promptTitle" => dv.prompt_title = Some(val),
                 b"prompt" => dv.prompt_message = Some(val),
+                b"showDropDown" => dv.hide_dropdown = on,
                 b"sqref" => sqref = Some(val),
                 _ => {}
             }
@@ -3763,21 +5024,35 @@ impl Workbook {
         // calculatedColumnFormula is a child element of tableColumn, not an
         // attribute of it, so its text arrives in a later event.
         let mut in_calc_formula = false;
+        // Likewise the formula of a "custom" totals function.
+        let mut in_totals_formula = false;
 
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Start(e)) if e.local_name().as_ref() == b"calculatedColumnFormula" => {
                     in_calc_formula = true;
                 }
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"totalsRowFormula" => {
+                    in_totals_formula = true;
+                }
                 Ok(Event::Text(e)) if in_calc_formula => {
                     let text = e.unescape().unwrap_or_default();
                     if let Some(col) = table.columns.last_mut() {
                         col.calculated_column_formula = Some(text.into_owned());
                     }
                 }
+                Ok(Event::Text(e)) if in_totals_formula => {
+                    let text = e.unescape().unwrap_or_default();
+                    if let Some(col) = table.columns.last_mut() {
+                        col.totals_row_function = TotalsRowFunction::Custom(text.into_owned());
+                    }
+                }
                 Ok(Event::End(e)) if e.local_name().as_ref() == b"calculatedColumnFormula" => {
                     in_calc_formula = false;
                 }
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"totalsRowFormula" => {
+                    in_totals_formula = false;
+                }
                 Ok(Event::Empty(e)) | Ok(Event::Start(e)) => match e.local_name().as_ref() {
                     b"table" => {
                         for attr in e.attributes().flatten() {
@@ -3816,6 +5091,8 @@ impl Workbook {
                         col.totals_row_label = totals_label;
                         col.calculated_column_formula = formula;
                         if let Some(f) = totals_fn {
+                            // "custom" takes its formula from the
+                            // totalsRowFormula child that follows.
                             col.totals_row_function = match f.as_str() {
                                 "average" => TotalsRowFunction::Average,
                                 "count" => TotalsRowFunction::Count,
@@ -3825,6 +5102,7 @@ impl Workbook {
                                 "stdDev" => TotalsRowFunction::StdDev,
                                 "sum" => TotalsRowFunction::Sum,
                                 "var" => TotalsRowFunction::Var,
+                                "none" => TotalsRowFunction::None,
                                 other => TotalsRowFunction::Custom(other.to_string()),
                             };
                         }
@@ -3871,17 +5149,61 @@ impl Workbook {
 
     /// Read the `<row>` attributes in a single pass so the result does not
     /// depend on the order the attributes appear in.
-    fn parse_row_attrs(e: &quick_xml::events::BytesStart) -> (Option<u32>, Option<f64>) {
+    fn parse_row_attrs(
+        e: &quick_xml::events::BytesStart,
+    ) -> (Option<u32>, Option<f64>, DimensionProps) {
         let mut index = None;
         let mut height = None;
+        let mut props = DimensionProps::default();
         for attr in e.attributes().flatten() {
             match attr.key.as_ref() {
                 b"r" => index = String::from_utf8_lossy(&attr.value).parse().ok(),
                 b"ht" => height = String::from_utf8_lossy(&attr.value).parse().ok(),
-                _ => {}
+                key => Self::parse_dimension_prop(key, &attr.value, &mut props),
+            }
+        }
+        (index, height, props)
+    }
+
+    /// Read a `<col>` element's range, width and grouping onto the sheet.
+    fn parse_col_attrs(e: &quick_xml::events::BytesStart, worksheet: &mut Worksheet) {
+        let mut col_min: Option<u32> = None;
+        let mut col_max: Option<u32> = None;
+        let mut width: Option<f64> = None;
+        let mut props = DimensionProps::default();
+        for attr in e.attributes().flatten() {
+            match attr.key.as_ref() {
+                b"min" => col_min = String::from_utf8_lossy(&attr.value).parse().ok(),
+                b"max" => col_max = String::from_utf8_lossy(&attr.value).parse().ok(),
+                b"width" => width = String::from_utf8_lossy(&attr.value).parse().ok(),
+                key => Self::parse_dimension_prop(key, &attr.value, &mut props),
+            }
+        }
+        let start = col_min.unwrap_or(1);
+        let end = col_max.unwrap_or(start);
+        if let Some(w) = width {
+            worksheet.set_column_widths(start, end, w);
+        }
+        if !props.is_default() {
+            worksheet.column_props.insert_span(start, end, props);
+        }
+    }
+
+    /// Read the outline level and hidden/collapsed/bestFit flags of a `<row>`
+    /// or `<col>`.
+    fn parse_dimension_prop(key: &[u8], value: &[u8], props: &mut DimensionProps) {
+        let flag = matches!(value, b"1" | b"true");
+        match key {
+            b"hidden" => props.hidden = flag,
+            b"collapsed" => props.collapsed = flag,
+            b"bestFit" => props.best_fit = flag,
+            b"outlineLevel" => {
+                props.outline_level = String::from_utf8_lossy(value)
+                    .parse::<u8>()
+                    .map_or(0, |level| level.min(crate::outline::MAX_OUTLINE_LEVEL))
             }
+            _ => {}
         }
-        (index, height)
     }
 
     /// Map the internal one-byte cell type to its OOXML `t` attribute. The
@@ -3931,6 +5253,7 @@ impl Workbook {
         (coord, cell_type, style_id)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn parse_worksheet_xml<R: BufRead>(
         reader: R,
         shared_strings: &[(
@@ -3942,7 +5265,19 @@ impl Workbook {
         dxfs: &[ConditionalFormat],
         worksheet: &mut Worksheet,
         sheet_xml_len: usize,
+        date1904: bool,
     ) -> Result<()> {
+        // Numbers in these styles are dates.
+        let date_styles: std::collections::HashSet<u32> = styles
+            .iter()
+            .filter(|(_, style)| {
+                style
+                    .number_format
+                    .as_deref()
+                    .is_some_and(crate::dates::is_date_format)
+            })
+            .map(|(id, _)| *id)
+            .collect();
         let mut reader = Reader::from_reader(reader);
         // Don't trim text - we need to preserve whitespace in cell values
         reader.config_mut().trim_text(false);
@@ -3968,6 +5303,9 @@ impl Workbook {
         let mut current_type: u8 = 0;
         let mut current_style_id: Option<u32> = None;
         let mut current_formula: Option<String> = None;
+        // Group index of the current <f t="shared">, and the groups seen so far.
+        let mut current_shared_si: Option<String> = None;
+        let mut shared_formulas = crate::translate::SharedFormulas::default();
         let mut current_number_format: Option<crate::cell::InternedString> = None;
         // Raw <v> text of a formula cell, kept verbatim so the cached result
         // round-trips as written rather than being reformatted as an f64.
@@ -3987,7 +5325,7 @@ impl Workbook {
         let mut in_f = false;
         let mut _in_hyperlinks = false;
         let mut current_merge_ref: Option<String> = None;
-        let mut hyperlinks: HashMap<(u32, u32), String> = HashMap::new();
+        let mut hyperlinks: HashMap<(u32, u32), Hyperlink> = HashMap::new();
         let mut protection: Option<WorksheetProtection> = None;
         let mut reserved_cells = false;
         let mut current_validation: Option<(DataValidation, Option<String>)> = None;
@@ -4006,6 +5344,9 @@ impl Workbook {
         let mut cf_icon: Option<IconSet> = None;
         let mut in_odd_header = false;
         let mut in_odd_footer = false;
+        // <pageSetUpPr fitToPage="1"> in sheetPr; without it Excel ignores
+        // the fitToWidth/fitToHeight of <pageSetup>.
+        let mut fit_to_page = false;
 
         loop {
             match reader.read_event_into(&mut buf) {
@@ -4018,9 +5359,17 @@ impl Workbook {
                         // Self-closing run-property children: <b/>, <i/>, <sz/>,
                         // <color/>, <rFont/>, <vertAlign/>, ...
                         Self::parse_run_prop(&e, &mut run_font);
+                    } else if name == b"f" && in_cell {
+                        // A shared-formula member: <f t="shared" si="0"/>
+                        if let (Some(si), Some(row), Some(col)) =
+                            (Self::shared_formula_group(&e), current_row, current_col)
+                        {
+                            current_formula = shared_formulas.member(&si, row, col);
+                        }
                     } else if name == b"sheetProtection" {
                         let mut prot = WorksheetProtection {
                             sheet: true,
+                            hash: PasswordHash::from_element(&e, ""),
                             ..Default::default()
                         };
                         for attr in e.attributes().flatten() {
@@ -4078,13 +5427,20 @@ impl Workbook {
                         let mut hyperlink_ref: Option<String> = None;
                         let mut location: Option<String> = None;
                         let mut rel_id: Option<String> = None;
+                        let mut tooltip: Option<String> = None;
+                        let mut display: Option<String> = None;
                         for attr in e.attributes().flatten() {
                             let attr_key = attr.key.as_ref();
+                            let value = || attr.unescape_value().map(|v| v.into_owned()).ok();
                             if attr_key == b"ref" {
                                 hyperlink_ref =
                                     Some(String::from_utf8_lossy(&attr.value).to_string());
                             } else if attr_key == b"location" {
-                                location = Some(String::from_utf8_lossy(&attr.value).to_string());
+                                location = value();
+                            } else if attr_key == b"tooltip" {
+                                tooltip = value();
+                            } else if attr_key == b"display" {
+                                display = value();
                             } else if attr.key.local_name().as_ref() == b"id" {
                                 // r:id pointing into the sheet rels (external URL)
                                 rel_id = Some(String::from_utf8_lossy(&attr.value).to_string());
@@ -4092,21 +5448,22 @@ impl Workbook {
                         }
                         if let Some(ref_coord) = hyperlink_ref {
                             if let Ok((row, col)) = parse_coordinate(&ref_coord) {
-                                let url = rel_id
+                                // An external target may carry a location too:
+                                // the fragment within the linked document.
+                                let link = match rel_id
                                     .and_then(|id| rels.get(&id))
                                     .filter(|rel| rel.external)
-                                    .map(|rel| rel.target.clone())
-                                    .or_else(|| {
-                                        location.map(|loc| {
-                                            if loc.starts_with('#') {
-                                                loc
-                                            } else {
-                                                format!("#{}", loc)
-                                            }
-                                        })
-                                    });
-                                if let Some(url) = url {
-                                    hyperlinks.insert((row, col), url);
+                                {
+                                    Some(rel) => Some(Hyperlink::external(match location {
+                                        Some(loc) => format!("{}#{}", rel.target, loc),
+                                        None => rel.target.clone(),
+                                    })),
+                                    None => location.map(Hyperlink::internal),
+                                };
+                                if let Some(mut link) = link {
+                                    link.tooltip = tooltip;
+                                    link.display = display;
+                                    hyperlinks.insert((row, col), link);
                                 }
                             }
                         }
@@ -4126,12 +5483,32 @@ impl Workbook {
                         cf_colors.push(Self::parse_conditional_color(&e));
                     } else if name == b"pane" {
                         Self::parse_pane_attrs(&e, worksheet);
+                    } else if name == b"selection" {
+                        Self::parse_selection_attrs(&e, worksheet);
+                    } else if name == b"sheetView" {
+                        Self::parse_sheet_view_attrs(&e, worksheet);
+                    } else if name == b"tabColor" {
+                        worksheet.sheet_properties.tab_color = Self::parse_style_color(&e);
+                    } else if name == b"outlinePr" {
+                        for attr in e.attributes().flatten() {
+                            let on = !matches!(attr.value.as_ref(), b"0" | b"false");
+                            match attr.key.as_ref() {
+                                b"summaryBelow" => worksheet.sheet_properties.summary_below = on,
+                                b"summaryRight" => worksheet.sheet_properties.summary_right = on,
+                                _ => {}
+                            }
+                        }
                     } else if name == b"autoFilter" {
                         Self::parse_autofilter_attrs(&e, worksheet);
                     } else if name == b"pageMargins" {
                         Self::parse_page_margins_attrs(&e, worksheet);
+                    } else if name == b"pageSetUpPr" {
+                        fit_to_page = e.attributes().flatten().any(|a| {
+                            a.key.as_ref() == b"fitToPage"
+                                && matches!(a.value.as_ref(), b"1" | b"true")
+                        });
                     } else if name == b"pageSetup" {
-                        Self::parse_page_setup_attrs(&e, worksheet);
+                        Self::parse_page_setup_attrs(&e, worksheet, fit_to_page);
                     } else if name == b"printOptions" {
                         Self::parse_print_options_attrs(&e, worksheet);
                     } else if name == b"dataValidation" {
@@ -4139,44 +5516,20 @@ impl Workbook {
                         let (dv, sqref) = Self::parse_data_validation_attrs(&e);
                         Self::insert_data_validation(worksheet, dv, sqref);
                     } else if name == b"col" {
-                        let mut col_min: Option<u32> = None;
-                        let mut col_max: Option<u32> = None;
-                        let mut width: Option<f64> = None;
-                        for attr in e.attributes().flatten() {
-                            let attr_key = attr.key.as_ref();
-                            if attr_key == b"min" {
-                                if let Ok(num) = String::from_utf8_lossy(&attr.value).parse::<u32>()
-                                {
-                                    col_min = Some(num);
-                                }
-                            } else if attr_key == b"max" {
-                                if let Ok(num) = String::from_utf8_lossy(&attr.value).parse::<u32>()
-                                {
-                                    col_max = Some(num);
-                                }
-                            } else if attr_key == b"width" {
-                                if let Ok(w) = String::from_utf8_lossy(&attr.value).parse::<f64>() {
-                                    width = Some(w);
-                                }
-                            }
-                        }
-                        if let Some(w) = width {
-                            let start = col_min.unwrap_or(1);
-                            let end = col_max.unwrap_or(start);
-                            for col in start..=end {
-                                worksheet.set_column_width(col, w);
-                            }
-                        }
+                        Self::parse_col_attrs(&e, worksheet);
                     } else if name == b"row" {
                         // A row with no cells still carries formatting, e.g.
                         // <row r="3" ht="20" customHeight="1"/>
-                        let (index, height) = Self::parse_row_attrs(&e);
+                        let (index, height, props) = Self::parse_row_attrs(&e);
                         let row = index.unwrap_or(next_row);
                         next_row = row.saturating_add(1);
                         next_col = 1;
                         if let Some(height) = height {
                             worksheet.set_row_height(row, height);
                         }
+                        if !props.is_default() {
+                            worksheet.row_props.insert(row, props);
+                        }
                     } else if name == b"c" {
                         // Handle self-closing cell elements like <c r="A1" t="inlineStr" />
                         // These are typically empty cells but with a specific type (e.g., empty string)
@@ -4231,8 +5584,10 @@ impl Workbook {
                                 }
                             }
                         }
+                    } else if name == b"sheetView" {
+                        Self::parse_sheet_view_attrs(&e, worksheet);
                     } else if name == b"row" {
-                        let (index, height) = Self::parse_row_attrs(&e);
+                        let (index, height, props) = Self::parse_row_attrs(&e);
                         let row = index.unwrap_or(next_row);
                         current_row = Some(row);
                         next_row = row.saturating_add(1);
@@ -4240,6 +5595,9 @@ impl Workbook {
                         if let Some(height) = height {
                             worksheet.set_row_height(row, height);
                         }
+                        if !props.is_default() {
+                            worksheet.row_props.insert(row, props);
+                        }
                     } else if name == b"c" {
                         in_cell = true;
                         current_value = None;
@@ -4277,6 +5635,7 @@ impl Workbook {
                         Self::parse_run_prop(&e, &mut run_font);
                     } else if name == b"f" {
                         in_f = true;
+                        current_shared_si = Self::shared_formula_group(&e);
                     } else if name == b"mergeCell" {
                         for attr in e.attributes().flatten() {
                             let attr_key = attr.key.as_ref();
@@ -4392,34 +5751,7 @@ impl Workbook {
                     } else if name == b"formula2" {
                         in_formula2 = current_validation.is_some();
                     } else if name == b"col" {
-                        let mut col_min: Option<u32> = None;
-                        let mut col_max: Option<u32> = None;
-                        let mut width: Option<f64> = None;
-                        for attr in e.attributes().flatten() {
-                            let attr_key = attr.key.as_ref();
-                            if attr_key == b"min" {
-                                if let Ok(num) = String::from_utf8_lossy(&attr.value).parse::<u32>()
-                                {
-                                    col_min = Some(num);
-                                }
-                            } else if attr_key == b"max" {
-                                if let Ok(num) = String::from_utf8_lossy(&attr.value).parse::<u32>()
-                                {
-                                    col_max = Some(num);
-                                }
-                            } else if attr_key == b"width" {
-                                if let Ok(w) = String::from_utf8_lossy(&attr.value).parse::<f64>() {
-                                    width = Some(w);
-                                }
-                            }
-                        }
-                        if let Some(w) = width {
-                            let start = col_min.unwrap_or(1);
-                            let end = col_max.unwrap_or(start);
-                            for col in start..=end {
-                                worksheet.set_column_width(col, w);
-                            }
-                        }
+                        Self::parse_col_attrs(&e, worksheet);
                     }
                 }
                 Ok(Event::Text(e)) => {
@@ -4588,17 +5920,17 @@ impl Workbook {
                         in_formula2 = false;
                     } else if name == b"hyperlinks" {
                         _in_hyperlinks = false;
-                        for ((row, col), url) in &hyperlinks {
-                            if let Some(cell_data) = worksheet.cells.get_mut(&cell_key(*row, *col))
-                            {
-                                cell_data.hyperlink = Some(url.clone());
+                        for ((row, col), link) in hyperlinks.drain() {
+                            let link = Some(Box::new(link));
+                            if let Some(cell_data) = worksheet.cells.get_mut(&cell_key(row, col)) {
+                                cell_data.hyperlink = link;
                             } else {
                                 let cell_data = CellData {
                                     value: CellValue::Empty,
-                                    hyperlink: Some(url.clone()),
+                                    hyperlink: link,
                                     ..Default::default()
                                 };
-                                worksheet.set_cell_data(*row, *col, cell_data);
+                                worksheet.set_cell_data(row, col, cell_data);
                             }
                         }
                     } else if name == b"c" {
@@ -4639,8 +5971,18 @@ impl Workbook {
                                         }
                                     }
                                     TempValue::Bool(b) => CellValue::Boolean(b),
-                                    TempValue::Number(n) => CellValue::Number(n),
-                                    TempValue::Date(d) => CellValue::Date(d),
+                                    TempValue::Number(n) => match current_style_id
+                                        .filter(|id| date_styles.contains(id))
+                                        .and_then(|_| {
+                                            crate::cell::DateValue::from_excel(n, date1904)
+                                        }) {
+                                        Some(date) => CellValue::Date(date),
+                                        None => CellValue::Number(n),
+                                    },
+                                    TempValue::Date(d) => match crate::cell::DateValue::parse(&d) {
+                                        Some(date) => CellValue::Date(date),
+                                        None => CellValue::String(std::sync::Arc::from(d)),
+                                    },
                                     TempValue::String(s) => {
                                         CellValue::String(std::sync::Arc::from(s))
                                     }
@@ -4712,6 +6054,14 @@ impl Workbook {
                         in_rpr = false;
                     } else if name == b"f" {
                         in_f = false;
+                        if let (Some(si), Some(row), Some(col)) =
+                            (current_shared_si.take(), current_row, current_col)
+                        {
+                            match &current_formula {
+                                Some(formula) => shared_formulas.anchor(&si, row, col, formula),
+                                None => current_formula = shared_formulas.member(&si, row, col),
+                            }
+                        }
                     } else if name == b"row" {
                         current_row = None;
                     } else if name == b"mergeCell" {
@@ -4741,51 +6091,96 @@ impl Workbook {
         Ok(())
     }
 
+    /// Parse a legacy comments part: each comment's text, kept as runs when
+    /// it is formatted, and its author from the `<authors>` list.
     fn parse_comments_xml<R: BufRead>(reader: R, worksheet: &mut Worksheet) -> Result<()> {
+        use crate::rich_text::{RichText, RunFont, TextRun};
+
         let mut reader = Reader::from_reader(reader);
-        reader.config_mut().trim_text(true);
+        // Comment text keeps its whitespace, as cell strings do.
+        reader.config_mut().trim_text(false);
 
         let mut buf = Vec::new();
+        let mut authors: Vec<String> = Vec::new();
+        let mut in_author = false;
         let mut current_cell_ref: Option<String> = None;
+        let mut current_author: Option<usize> = None;
         let mut current_comment_text = String::new();
+        let mut runs: Vec<TextRun> = Vec::new();
+        let mut run_text = String::new();
+        let mut run_font = RunFont::default();
         let mut in_comment = false;
         let mut in_text = false;
+        let mut in_run = false;
+        let mut in_rpr = false;
         let mut in_t = false;
 
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Start(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
-                    if name == b"comment" {
+                    if name == b"author" {
+                        in_author = true;
+                        authors.push(String::new());
+                    } else if name == b"comment" {
                         in_comment = true;
                         current_comment_text.clear();
+                        runs.clear();
+                        current_author = None;
                         for attr in e.attributes().flatten() {
                             let attr_key = attr.key.as_ref();
                             if attr_key == b"ref" {
                                 current_cell_ref =
                                     Some(String::from_utf8_lossy(&attr.value).to_string());
+                            } else if attr_key == b"authorId" {
+                                current_author = String::from_utf8_lossy(&attr.value).parse().ok();
                             }
                         }
                     } else if name == b"text" && in_comment {
                         in_text = true;
+                    } else if name == b"r" && in_text {
+                        in_run = true;
+                        run_text.clear();
+                        run_font = RunFont::default();
+                    } else if name == b"rPr" && in_run {
+                        in_rpr = true;
                     } else if name == b"t" && in_text {
                         in_t = true;
+                    } else if in_rpr {
+                        Self::parse_run_prop(&e, &mut run_font);
                     }
                 }
-                Ok(Event::Text(e)) => {
-                    if in_t && in_text && in_comment {
-                        let text = e.unescape().unwrap_or_default();
-                        current_comment_text.push_str(&text);
+                Ok(Event::Empty(e)) if in_rpr => Self::parse_run_prop(&e, &mut run_font),
+                Ok(Event::Text(e)) if in_author => {
+                    if let Some(author) = authors.last_mut() {
+                        author.push_str(&e.unescape().unwrap_or_default());
+                    }
+                }
+                Ok(Event::Text(e)) if in_t && in_text && in_comment => {
+                    let text = e.unescape().unwrap_or_default();
+                    if in_run {
+                        run_text.push_str(&text);
                     }
+                    current_comment_text.push_str(&text);
                 }
                 Ok(Event::End(e)) => {
-                    let name = e.name();
+                    let name = e.local_name();
                     let name = name.as_ref();
-                    if name == b"comment" {
+                    if name == b"author" {
+                        in_author = false;
+                    } else if name == b"comment" {
                         if let Some(ref_coord) = current_cell_ref.take() {
                             if let Ok((row, col)) = parse_coordinate(&ref_coord) {
-                                worksheet.set_cell_comment(row, col, current_comment_text.clone());
+                                let mut comment =
+                                    crate::comments::Comment::new(current_comment_text.clone());
+                                comment.author =
+                                    current_author.and_then(|i| authors.get(i)).cloned();
+                                if !runs.is_empty() {
+                                    comment.rich_text =
+                                        Some(RichText::new(std::mem::take(&mut runs)));
+                                }
+                                worksheet.set_cell_comment(row, col, comment);
                             }
                         }
                         in_comment = false;
@@ -4794,6 +6189,19 @@ impl Workbook {
                         current_comment_text.clear();
                     } else if name == b"text" {
                         in_text = false;
+                    } else if name == b"r" && in_run {
+                        let font = if run_font.is_empty() {
+                            None
+                        } else {
+                            Some(std::mem::take(&mut run_font))
+                        };
+                        runs.push(TextRun {
+                            text: std::mem::take(&mut run_text),
+                            font,
+                        });
+                        in_run = false;
+                    } else if name == b"rPr" {
+                        in_rpr = false;
                     } else if name == b"t" {
                         in_t = false;
                     }
@@ -4812,6 +6220,56 @@ impl Workbook {
 
         Ok(())
     }
+
+    /// Recover comment box layout from the sheet's legacy VML drawing. VML
+    /// is often not well-formed XML (Excel writes bare `<br>`), so each Note
+    /// shape is picked apart as text. Only boxes that differ from the default
+    /// layout are recorded.
+    fn parse_comment_shapes(vml: &[u8], worksheet: &mut Worksheet) {
+        let text = String::from_utf8_lossy(vml);
+        let between = |s: &str, open: &str, close: &str| -> Option<String> {
+            let start = s.find(open)? + open.len();
+            let end = s[start..].find(close)? + start;
+            Some(s[start..end].trim().to_string())
+        };
+        for shape_xml in text.split("<v:shape ").skip(1) {
+            let shape_xml = shape_xml.split("</v:shape>").next().unwrap_or("");
+            if !shape_xml.contains("ObjectType=\"Note\"") {
+                continue;
+            }
+            let (Some(r0), Some(c0)) = (
+                between(shape_xml, "<x:Row>", "</x:Row>").and_then(|v| v.parse::<u32>().ok()),
+                between(shape_xml, "<x:Column>", "</x:Column>").and_then(|v| v.parse::<u32>().ok()),
+            ) else {
+                continue;
+            };
+            let mut shape = CommentShape::new();
+            shape.visible = shape_xml.contains("<x:Visible");
+            let style = between(shape_xml, "style=\"", "\"").unwrap_or_default();
+            for decl in style.split(';') {
+                let Some((key, value)) = decl.split_once(':') else {
+                    continue;
+                };
+                let points = value.trim().strip_suffix("pt").and_then(|v| v.parse().ok());
+                match (key.trim(), points) {
+                    ("width", Some(w)) => shape.width = w,
+                    ("height", Some(h)) => shape.height = h,
+                    _ => {}
+                }
+            }
+            let anchor: Vec<u32> = between(shape_xml, "<x:Anchor>", "</x:Anchor>")
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|n| n.trim().parse().ok())
+                .collect();
+            if anchor.len() == 8 && (anchor[0], anchor[2]) != (c0 + 1, r0) {
+                shape.anchor = Some((anchor[2] + 1, anchor[0] + 1));
+            }
+            if shape != CommentShape::default() {
+                worksheet.set_comment_shape(r0 + 1, c0 + 1, shape);
+            }
+        }
+    }
 }
 
 impl Default for Workbook {
@@ -4865,6 +6323,34 @@ mod tests {
         assert_eq!(wb.sheet_names[0], "Sheet2");
     }
 
+    #[test]
+    fn test_sheet_position_follows_changes() {
+        let mut wb = Workbook::new();
+        for name in ["A", "B", "C", "D"] {
+            wb.create_sheet(Some(name.to_string())).unwrap();
+        }
+        wb.remove_sheet("A").unwrap();
+        wb.move_sheet("D", 0).unwrap();
+        wb.rename_sheet("C", "Renamed").unwrap();
+        assert_eq!(wb.sheet_names, ["D", "B", "Renamed"]);
+        for (idx, name) in wb.sheet_names.clone().iter().enumerate() {
+            assert_eq!(wb.sheet_position(name), Some(idx));
+        }
+        assert_eq!(wb.sheet_position("A"), None);
+        assert_eq!(wb.sheet_position("C"), None);
+        assert_eq!(wb.get_sheet_by_name("Renamed").unwrap().title(), "Renamed");
+        assert!(matches!(
+            wb.rename_sheet("B", "D"),
+            Err(RustypyxlError::WorksheetAlreadyExists(_))
+        ));
+        assert!(matches!(
+            wb.push_sheet("B".to_string(), Worksheet::new("B")),
+            Err(RustypyxlError::WorksheetAlreadyExists(_))
+        ));
+        assert_eq!(wb.sheet_position("B"), Some(1));
+        assert_eq!(wb.worksheets.len(), 3);
+    }
+
     #[test]
     fn test_named_ranges() {
         let mut wb = Workbook::new();
@@ -4873,6 +6359,64 @@ mod tests {
         assert_eq!(wb.get_named_range("MyRange"), Some("'Sheet1'!A1:B10"));
     }
 
+    #[test]
+    fn test_scoped_names_resolve_and_follow_removal() {
+        let mut wb = Workbook::new();
+        for name in ["Data", "It's", "Other"] {
+            wb.create_sheet(Some(name.to_string())).unwrap();
+        }
+        wb.create_named_range("Rate".to_string(), "Data!$B$2".to_string())
+            .unwrap();
+        wb.add_named_range(NamedRange::new("Rate", "'It''s'!$C:$D").with_local_sheet(1))
+            .unwrap();
+        wb.add_named_range(NamedRange::new("Rows", "$3:$5").with_local_sheet(2))
+            .unwrap();
+        assert!(wb
+            .add_named_range(NamedRange::new("Rate", "Data!$A$1"))
+            .is_err());
+        assert!(wb
+            .add_named_range(NamedRange::new("X", "Data!$A$1").with_local_sheet(9))
+            .is_err());
+
+        assert_eq!(
+            wb.resolve_named_range("Rate").unwrap(),
+            ("Data".to_string(), 2, 2, 2, 2)
+        );
+        assert_eq!(
+            wb.resolve_named_range_on("It's", "Rate").unwrap(),
+            ("It's".to_string(), 1, 3, crate::utils::MAX_ROW, 4)
+        );
+        assert_eq!(
+            wb.resolve_named_range_on("Data", "Rate").unwrap().0,
+            "Data",
+            "other sheets see the global name"
+        );
+        assert_eq!(
+            wb.resolve_named_range_on("Other", "Rows").unwrap(),
+            ("Other".to_string(), 3, 1, 5, crate::utils::MAX_COLUMN)
+        );
+        assert!(wb.resolve_named_range("Rows").is_err());
+
+        wb.create_named_range("Const".to_string(), "=0.2".to_string())
+            .unwrap();
+        wb.create_named_range("Union".to_string(), "Data!$A$1,Data!$C$3".to_string())
+            .unwrap();
+        assert!(wb.resolve_named_range("Const").is_err());
+        assert!(wb.resolve_named_range("Union").is_err());
+
+        let names: Vec<&str> = wb
+            .sheet_defined_names("It's")
+            .unwrap()
+            .iter()
+            .map(|nr| nr.name.as_str())
+            .collect();
+        assert_eq!(names, ["Rate"]);
+
+        wb.remove_sheet("It's").unwrap();
+        assert!(wb.sheet_defined_names("Other").unwrap()[0].name == "Rows");
+        assert_eq!(wb.named_ranges.len(), 4);
+    }
+
     #[test]
     fn test_parse_workbook_rels() {
         let rels_xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
@@ -4884,9 +6428,11 @@ mod tests {
 
         let rels = Workbook::parse_workbook_rels(Cursor::new(rels_xml)).unwrap();
 
-        assert_eq!(rels.get("rId1"), Some(&"worksheets/sheet1.xml".to_string()));
-        assert_eq!(rels.get("rId2"), Some(&"worksheets/sheet5.xml".to_string()));
-        assert_eq!(rels.get("rId3"), Some(&"worksheets/sheet3.xml".to_string()));
+        let target = |id: &str| rels.get(id).map(|r| r.target.as_str());
+        assert_eq!(target("rId1"), Some("worksheets/sheet1.xml"));
+        assert_eq!(target("rId2"), Some("worksheets/sheet5.xml"));
+        assert_eq!(target("rId3"), Some("worksheets/sheet3.xml"));
+        assert!(rels["rId1"].rel_type.ends_with("/worksheet"));
     }
 
     #[test]
@@ -4950,6 +6496,39 @@ mod tests {
     }
 
     /// The active tab must follow the sheet it pointed at, not the index.
+    #[test]
+    fn test_sheet_order_remaps_local_names_and_active_tab() {
+        let mut wb = Workbook::new();
+        for name in ["A", "B", "C"] {
+            wb.create_sheet(Some(name.to_string())).unwrap();
+        }
+        wb.named_ranges.push(NamedRange {
+            name: "OnC".to_string(),
+            range: "C!$A$1".to_string(),
+            local_sheet_id: Some(2),
+            hidden: false,
+            comment: None,
+        });
+        wb.active_sheet = 0;
+
+        wb.set_sheet_order(&["C", "A", "B"]).unwrap();
+        assert_eq!(wb.sheet_names, vec!["C", "A", "B"]);
+        assert_eq!(wb.worksheets[0].title(), "C");
+        assert_eq!(wb.named_ranges[0].local_sheet_id, Some(0));
+        assert_eq!(wb.active_sheet, 1, "active tab follows sheet A");
+
+        wb.move_sheet("C", 99).unwrap();
+        assert_eq!(wb.sheet_names, vec!["A", "B", "C"]);
+        assert_eq!(wb.named_ranges[0].local_sheet_id, Some(2));
+        assert_eq!(wb.active_sheet, 0);
+
+        assert!(wb.set_sheet_order(&["A", "B"]).is_err());
+        assert!(wb.set_sheet_order(&["A", "A", "B"]).is_err());
+        assert!(wb.set_sheet_order(&["A", "B", "X"]).is_err());
+        assert!(wb.move_sheet("X", 0).is_err());
+        assert_eq!(wb.sheet_names, vec!["A", "B", "C"]);
+    }
+
     #[test]
     fn test_remove_sheet_tracks_the_active_tab() {
         let mut wb = Workbook::new();
@@ -4989,6 +6568,34 @@ mod tests {
         assert_eq!(wb.active_sheet, 0, "no sheets left");
     }
 
+    #[test]
+    fn test_active_sheet_selection() {
+        let mut wb = Workbook::new();
+        for name in ["A", "B", "C"] {
+            wb.create_sheet(Some(name.to_string())).unwrap();
+        }
+        wb.set_active_sheet(2).unwrap();
+        assert_eq!(wb.active().unwrap().title(), "C");
+        wb.set_cell_value(1, 1, CellValue::from("on C")).unwrap();
+        assert!(wb.worksheets[2].get_cell(1, 1).is_some());
+
+        wb.worksheets[1].visibility = SheetVisibility::Hidden;
+        assert!(wb.set_active_sheet(1).is_err());
+        assert!(wb.set_active_sheet(3).is_err());
+        assert_eq!(wb.active_sheet_index(), 2);
+
+        // A hidden active tab is written as the first visible sheet
+        wb.active_sheet = 1;
+        let loaded = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.active_sheet_index(), 0);
+        assert_eq!(loaded.worksheets[1].visibility, SheetVisibility::Hidden);
+
+        wb.worksheets[0].visibility = SheetVisibility::VeryHidden;
+        let loaded = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.active().unwrap().title(), "C");
+        assert_eq!(loaded.worksheets[0].visibility, SheetVisibility::VeryHidden);
+    }
+
     #[test]
     fn test_save_to_bytes() {
         let mut wb = Workbook::new();
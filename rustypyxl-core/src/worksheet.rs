@@ -2,14 +2,28 @@
 
 use crate::autofilter::AutoFilter;
 use crate::cell::{CellValue, InternedString};
+use crate::column_spans::ColumnSpans;
+use crate::columnar::{ColumnStore, Storage};
+use crate::comments::Comment;
 use crate::conditional::ConditionalFormatting;
+use crate::error::{Result, RustypyxlError};
+use crate::hyperlink::Hyperlink;
+use crate::numfmt::format_value;
+use crate::outline::DimensionProps;
 use crate::pagesetup::PageSetup;
+use crate::protection::PasswordHash;
+use crate::sheetview::{SheetProperties, SheetView};
+use crate::string_counts::StringCounts;
 use crate::style::CellStyle;
 use crate::table::Table;
+pub use crate::validation::DataValidation;
 #[cfg(feature = "fast-hash")]
 use hashbrown::HashMap;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 #[cfg(not(feature = "fast-hash"))]
 use std::collections::HashMap;
+use std::ops::RangeInclusive;
 use std::sync::Arc;
 
 #[cfg(feature = "fast-hash")]
@@ -42,10 +56,10 @@ pub struct CellData {
     /// Data type (s=string, n=number, b=boolean, d=date). Always one of a fixed
     /// set of codes, so it borrows rather than allocating per cell.
     pub data_type: Option<&'static str>,
-    /// Hyperlink URL.
-    pub hyperlink: Option<String>,
-    /// Cell comment text.
-    pub comment: Option<String>,
+    /// Hyperlink, boxed since few cells have one.
+    pub hyperlink: Option<Box<Hyperlink>>,
+    /// Cell comment, boxed since few cells have one.
+    pub comment: Option<Box<Comment>>,
     /// Last calculated result of a formula cell, as the raw `<v>` text.
     /// Written back on save so viewers that don't recalculate show a value;
     /// `data_type` carries the matching `t` attribute (str/b/e or numeric).
@@ -69,6 +83,42 @@ impl CellData {
             ..Default::default()
         }
     }
+
+    /// The number format that applies to this cell: the style's when it
+    /// has one, else the one set directly on the cell.
+    pub fn number_format_code(&self) -> Option<&str> {
+        self.style
+            .as_ref()
+            .and_then(|style| style.number_format.as_deref())
+            .or(self.number_format.as_deref())
+    }
+
+    /// Name of the named cell style the cell is linked to, "Normal" when
+    /// none.
+    pub fn named_style(&self) -> &str {
+        self.style
+            .as_ref()
+            .and_then(|style| style.named_style.as_deref())
+            .unwrap_or("Normal")
+    }
+
+    /// What Excel shows: the value rendered under its number format
+    /// (General when unset), or a formula's cached result under it (empty
+    /// when there is none).
+    pub fn formatted_value(&self) -> String {
+        let code = self.number_format_code().unwrap_or("General");
+        match &self.value {
+            CellValue::Formula(_) => match &self.cached_formula_value {
+                Some(cached) => match (self.data_type, cached.parse::<f64>()) {
+                    (None | Some("n"), Ok(n)) => format_value(&CellValue::Number(n), code),
+                    (Some("b"), _) => format_value(&CellValue::Boolean(cached == "1"), code),
+                    _ => cached.clone(),
+                },
+                None => String::new(),
+            },
+            value => format_value(value, code),
+        }
+    }
 }
 
 /// Sheet visibility as stored on the workbook.xml `<sheet state>` attribute.
@@ -103,117 +153,235 @@ impl SheetVisibility {
     }
 }
 
-/// Data validation rule for a cell.
-#[derive(Clone, Debug)]
-pub struct DataValidation {
-    /// Type: whole, decimal, list, date, time, textLength, custom.
-    pub validation_type: String,
-    /// Comparison operator: between, notBetween, equal, notEqual, greaterThan,
-    /// lessThan, greaterThanOrEqual, lessThanOrEqual. None means Excel's
-    /// default, "between".
-    pub operator: Option<String>,
-    /// First formula/value constraint.
-    pub formula1: Option<String>,
-    /// Second formula/value constraint (for between/notBetween).
-    pub formula2: Option<String>,
-    /// Severity of the error dialog: stop, warning, or information.
-    pub error_style: Option<String>,
-    /// Allow blank values.
-    pub allow_blank: bool,
-    /// Show error message on invalid input.
-    pub show_error: bool,
-    /// Error dialog title.
-    pub error_title: Option<String>,
-    /// Error message text.
-    pub error_message: Option<String>,
-    /// Show input message when cell is selected.
-    pub show_input: bool,
-    /// Input prompt title.
-    pub prompt_title: Option<String>,
-    /// Input prompt message.
-    pub prompt_message: Option<String>,
-    /// Full sqref the rule applies to (may span multiple cells/ranges).
-    /// When None, the rule applies to the single cell it is keyed under.
-    pub sqref: Option<String>,
+/// Placement, size and visibility of a comment's note box.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommentShape {
+    /// Keep the box on screen instead of showing it only on hover.
+    pub visible: bool,
+    /// Box width in points.
+    pub width: f64,
+    /// Box height in points.
+    pub height: f64,
+    /// Cell (row, column), 1-based, at the box's top-left corner. None puts
+    /// it just right of the commented cell, where Excel does.
+    pub anchor: Option<(u32, u32)>,
 }
 
-impl Default for DataValidation {
+impl Default for CommentShape {
     fn default() -> Self {
-        DataValidation {
-            validation_type: "whole".to_string(),
-            operator: None,
-            formula1: None,
-            formula2: None,
-            error_style: None,
-            allow_blank: true,
-            show_error: true,
-            error_title: None,
-            error_message: None,
-            show_input: true,
-            prompt_title: None,
-            prompt_message: None,
-            sqref: None,
+        CommentShape {
+            visible: false,
+            width: 108.0,
+            height: 59.25,
+            anchor: None,
         }
     }
 }
 
+impl CommentShape {
+    /// Create the default shape: hidden, 108pt x 59.25pt, beside the cell.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show the box all the time.
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Set the box size in points.
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Put the box's top-left corner at (row, column), 1-based.
+    pub fn with_anchor(mut self, row: u32, column: u32) -> Self {
+        self.anchor = Some((row, column));
+        self
+    }
+}
+
 /// Worksheet protection settings.
-#[derive(Clone, Debug, Default)]
+///
+/// Each action flag mirrors its `sheetProtection` attribute: `true` means the
+/// action is blocked while the sheet is protected. The defaults are the
+/// schema's (and openpyxl's): formatting, inserting, deleting, sorting,
+/// filtering and pivot tables are blocked; selecting cells is allowed.
+#[derive(Clone, Debug)]
 pub struct WorksheetProtection {
     /// Sheet protection enabled.
     pub sheet: bool,
     /// Plaintext password; hashed with the legacy Excel verifier on save.
+    /// [`set_password`](Self::set_password) stores an iterated hash instead.
     pub password: Option<String>,
-    /// Pre-hashed password verifier loaded from an existing file.
+    /// Pre-hashed legacy password verifier loaded from an existing file.
     /// Takes precedence over `password` on save so a loaded hash is never re-hashed.
     pub password_hash: Option<String>,
-    /// Allow selecting locked cells.
+    /// Salted, iterated password hash (SHA-512 for new passwords). Takes
+    /// precedence over both legacy forms.
+    pub hash: Option<PasswordHash>,
+    /// Block selecting locked cells.
     pub select_locked_cells: bool,
-    /// Allow selecting unlocked cells.
+    /// Block selecting unlocked cells.
     pub select_unlocked_cells: bool,
-    /// Allow formatting cells.
+    /// Block formatting cells.
     pub format_cells: bool,
-    /// Allow formatting columns.
+    /// Block formatting columns.
     pub format_columns: bool,
-    /// Allow formatting rows.
+    /// Block formatting rows.
     pub format_rows: bool,
-    /// Allow inserting columns.
+    /// Block inserting columns.
     pub insert_columns: bool,
-    /// Allow inserting rows.
+    /// Block inserting rows.
     pub insert_rows: bool,
-    /// Allow inserting hyperlinks.
+    /// Block inserting hyperlinks.
     pub insert_hyperlinks: bool,
-    /// Allow deleting columns.
+    /// Block deleting columns.
     pub delete_columns: bool,
-    /// Allow deleting rows.
+    /// Block deleting rows.
     pub delete_rows: bool,
-    /// Allow sorting.
+    /// Block sorting.
     pub sort: bool,
-    /// Allow using autofilter.
+    /// Block using autofilter.
     pub auto_filter: bool,
-    /// Allow editing pivot tables.
+    /// Block editing pivot tables.
     pub pivot_tables: bool,
-    /// Allow editing objects.
+    /// Block editing objects.
     pub objects: bool,
-    /// Allow editing scenarios.
+    /// Block editing scenarios.
     pub scenarios: bool,
 }
 
+impl Default for WorksheetProtection {
+    fn default() -> Self {
+        WorksheetProtection {
+            sheet: false,
+            password: None,
+            password_hash: None,
+            hash: None,
+            select_locked_cells: false,
+            select_unlocked_cells: false,
+            format_cells: true,
+            format_columns: true,
+            format_rows: true,
+            insert_columns: true,
+            insert_rows: true,
+            insert_hyperlinks: true,
+            delete_columns: true,
+            delete_rows: true,
+            sort: true,
+            auto_filter: true,
+            pivot_tables: true,
+            objects: false,
+            scenarios: false,
+        }
+    }
+}
+
+impl WorksheetProtection {
+    /// Require `password` to unprotect the sheet. Only its SHA-512 hash is
+    /// kept (the legacy verifier without the `encrypt` feature); any earlier
+    /// password or verifier is dropped.
+    pub fn set_password(&mut self, password: &str) {
+        let (hash, legacy) = crate::protection::new_verifier(password);
+        self.hash = hash;
+        self.password = None;
+        self.password_hash = legacy;
+    }
+
+    /// Use an already computed hash as the sheet's password verifier.
+    pub fn set_hash(&mut self, hash: PasswordHash) {
+        self.hash = Some(hash);
+        self.password = None;
+        self.password_hash = None;
+    }
+
+    /// The flag behind a `sheetProtection` attribute name, which are also
+    /// openpyxl's `SheetProtection` keywords ("formatCells", "sort", ...).
+    pub fn flag_mut(&mut self, attribute: &str) -> Option<&mut bool> {
+        Some(match attribute {
+            "sheet" => &mut self.sheet,
+            "selectLockedCells" => &mut self.select_locked_cells,
+            "selectUnlockedCells" => &mut self.select_unlocked_cells,
+            "formatCells" => &mut self.format_cells,
+            "formatColumns" => &mut self.format_columns,
+            "formatRows" => &mut self.format_rows,
+            "insertColumns" => &mut self.insert_columns,
+            "insertRows" => &mut self.insert_rows,
+            "insertHyperlinks" => &mut self.insert_hyperlinks,
+            "deleteColumns" => &mut self.delete_columns,
+            "deleteRows" => &mut self.delete_rows,
+            "sort" => &mut self.sort,
+            "autoFilter" => &mut self.auto_filter,
+            "pivotTables" => &mut self.pivot_tables,
+            "objects" => &mut self.objects,
+            "scenarios" => &mut self.scenarios,
+            _ => return None,
+        })
+    }
+
+    /// Whether a password is needed to unprotect the sheet.
+    pub fn has_password(&self) -> bool {
+        self.hash.is_some() || self.password_hash.is_some() || self.password.is_some()
+    }
+
+    /// The legacy 16-bit verifier as written to the `password` attribute
+    /// (four hex digits): the one loaded from a file, or the hash of a
+    /// plaintext `password`. None when neither is set.
+    pub fn legacy_verifier(&self) -> Option<String> {
+        self.password_hash.clone().or_else(|| {
+            self.password
+                .as_deref()
+                .map(|plain| format!("{:04X}", crate::protection::legacy_password_hash(plain)))
+        })
+    }
+
+    /// Whether `password` unprotects the sheet, checked against the iterated
+    /// hash or the legacy verifier, whichever is stored. True for any
+    /// password when none is set.
+    pub fn verify_password(&self, password: &str) -> bool {
+        match (&self.hash, &self.password_hash, &self.password) {
+            (None, None, Some(plain)) => plain == password,
+            (hash, legacy, _) => {
+                crate::protection::verify(hash.as_ref(), legacy.as_deref(), password)
+            }
+        }
+    }
+}
+
 /// Represents a worksheet in an Excel workbook.
 #[derive(Clone, Debug)]
 pub struct Worksheet {
     /// Worksheet title/name.
     pub title: String,
-    /// Cell data indexed by packed (row, column) key - both 1-indexed.
+    /// Cell data indexed by packed (row, column) key - both 1-indexed. On a
+    /// columnar sheet the cells holding only a value are not here; use the
+    /// accessors, or [`Worksheet::all_cells`], to see every cell.
     pub cells: CellMap,
     /// Merged cell ranges as (start_coord, end_coord) strings.
     pub merged_cells: Vec<(String, String)>,
-    /// Column widths indexed by column number.
-    pub column_dimensions: HashMap<u32, f64>,
+    /// Column widths by column number, kept as runs of equal width.
+    pub column_dimensions: ColumnSpans<f64>,
     /// Row heights indexed by row number.
     pub row_dimensions: HashMap<u32, f64>,
+    /// Outline level and hidden/collapsed flags of rows, for rows that have
+    /// any (see [`crate::outline`]).
+    pub row_props: HashMap<u32, DimensionProps>,
+    /// Outline level and hidden/collapsed flags of columns, as runs.
+    pub column_props: ColumnSpans<DimensionProps>,
     /// Data validations indexed by (row, column).
     pub data_validations: HashMap<(u32, u32), DataValidation>,
+    /// Note-box layout for comments, keyed by the commented (row, column).
+    /// Comments without an entry get Excel's default box.
+    pub comment_shapes: HashMap<(u32, u32), CommentShape>,
+    /// Hidden tags on rows, saved so they follow the row through edits in
+    /// Excel (see [`crate::tags`]).
+    pub row_tags: HashMap<u32, String>,
+    /// Hidden tags on cells, keyed by (row, column).
+    pub cell_tags: HashMap<(u32, u32), String>,
     /// Sheet protection settings.
     pub protection: Option<WorksheetProtection>,
     /// Maximum row with data (for optimization).
@@ -238,12 +406,29 @@ pub struct Worksheet {
     pub page_setup: Option<PageSetup>,
     /// Freeze panes anchor cell (e.g. "B2"); rows above and columns left of it stay frozen.
     pub freeze_panes: Option<String>,
+    /// Gridlines, zoom, right-to-left layout and view type.
+    pub sheet_view: SheetView,
+    /// Tab color and other `<sheetPr>` settings.
+    pub sheet_properties: SheetProperties,
     /// Sheet visibility (visible / hidden / veryHidden).
     pub visibility: SheetVisibility,
     /// Stable identity within the owning workbook. Assigned by the workbook
     /// (never reused), so handles survive sheet removal, reordering, and
     /// renames. 0 means the worksheet is not attached to a workbook.
     pub uid: u64,
+    /// Set for a dialog or macro sheet loaded from a file: the sheet has no
+    /// cells here and is saved back from this original part.
+    pub opaque: Option<crate::opaque_sheet::OpaqueSheet>,
+    /// Raw XML fragments written into this sheet's XML on save (see
+    /// [`crate::custom_xml`]).
+    pub xml_fragments: Vec<(crate::custom_xml::XmlInjectionPoint, String)>,
+    /// Count of each plain string value, kept by the value setters while
+    /// the workbook saves with [`crate::SharedStrings::Incremental`]. Values
+    /// changed through `cells` or [`Worksheet::get_cell_mut`] go uncounted.
+    pub(crate) string_counts: Option<StringCounts>,
+    /// Plain values of a [`Storage::Columnar`] sheet; `None` for the
+    /// default map storage. A position is in `cells` or here, never both.
+    pub(crate) columns: Option<ColumnStore>,
 }
 
 impl Worksheet {
@@ -253,9 +438,14 @@ impl Worksheet {
             title: title.into(),
             cells: CellMap::default(),
             merged_cells: Vec::new(),
-            column_dimensions: HashMap::new(),
+            column_dimensions: ColumnSpans::new(),
             row_dimensions: HashMap::new(),
+            row_props: HashMap::new(),
+            column_props: ColumnSpans::new(),
             data_validations: HashMap::new(),
+            comment_shapes: HashMap::new(),
+            row_tags: HashMap::new(),
+            cell_tags: HashMap::new(),
             protection: None,
             max_row: 0,
             max_column: 0,
@@ -267,8 +457,45 @@ impl Worksheet {
             pivot_rels: Vec::new(),
             page_setup: None,
             freeze_panes: None,
+            sheet_view: SheetView::default(),
+            sheet_properties: SheetProperties::default(),
             visibility: SheetVisibility::default(),
             uid: 0,
+            opaque: None,
+            xml_fragments: Vec::new(),
+            string_counts: None,
+            columns: None,
+        }
+    }
+
+    /// This worksheet with its cells kept in `storage`. See [`Storage`].
+    pub fn with_storage(mut self, storage: Storage) -> Self {
+        self.set_storage(storage);
+        self
+    }
+
+    /// Switch how the cells are stored, moving the ones already there.
+    /// Either way every cell reads back the same; columnar storage only
+    /// changes how much memory plain values take.
+    pub fn set_storage(&mut self, storage: Storage) {
+        match storage {
+            Storage::Map => {
+                self.unpack_columns();
+                self.columns = None;
+            }
+            Storage::Columnar => {
+                self.columns.get_or_insert_with(ColumnStore::default);
+                self.pack_columns();
+            }
+        }
+    }
+
+    /// How the cells are stored.
+    pub fn storage(&self) -> Storage {
+        if self.columns.is_some() {
+            Storage::Columnar
+        } else {
+            Storage::Map
         }
     }
 
@@ -277,6 +504,19 @@ impl Worksheet {
         self.freeze_panes = cell;
     }
 
+    /// Open the sheet scrolled to `cell` (e.g. "A10") with it selected. With
+    /// frozen panes the cell is the first one shown below and right of them,
+    /// so `scroll_to("A50")` under a frozen header row keeps the header and
+    /// shows row 50 directly beneath it.
+    pub fn scroll_to(&mut self, cell: &str) -> Result<()> {
+        let (row, col) = crate::utils::parse_coordinate(cell)?;
+        let cell = crate::utils::coordinate_from_row_col(row, col);
+        self.sheet_view.top_left_cell = Some(cell.clone());
+        self.sheet_view.active_cell = Some(cell);
+        self.sheet_view.selection = None;
+        Ok(())
+    }
+
     /// Set an AutoFilter for this worksheet.
     pub fn set_auto_filter(&mut self, auto_filter: AutoFilter) {
         self.auto_filter = Some(auto_filter);
@@ -292,8 +532,16 @@ impl Worksheet {
         self.tables.push(table);
     }
 
-    /// Add a chart anchored on this worksheet.
-    pub fn add_chart(&mut self, chart: crate::chart::Chart) {
+    /// Add a chart with its top-left corner at `anchor`: a cell such as
+    /// `"E5"`, or a [`ChartAnchor`](crate::chart::ChartAnchor) spanning cells.
+    /// Accepts a [`Chart`](crate::chart::Chart) or one of the typed builders.
+    pub fn add_chart(
+        &mut self,
+        chart: impl Into<crate::chart::Chart>,
+        anchor: impl Into<crate::chart::ChartAnchor>,
+    ) {
+        let mut chart = chart.into();
+        chart.anchor = Some(anchor.into());
         self.charts.push(chart);
     }
 
@@ -317,25 +565,84 @@ impl Worksheet {
         self.title = title.into();
     }
 
-    /// Get cell data at the specified row and column (1-indexed).
-    pub fn get_cell(&self, row: u32, column: u32) -> Option<&CellData> {
-        self.cells.get(&cell_key(row, column))
+    /// Get cell data at the specified row and column (1-indexed). On a
+    /// columnar sheet a cell held as a plain value comes back owned.
+    pub fn get_cell(&self, row: u32, column: u32) -> Option<Cow<'_, CellData>> {
+        if let Some(cell) = self.cells.get(&cell_key(row, column)) {
+            return Some(Cow::Borrowed(cell));
+        }
+        let value = self.columns.as_ref()?.get(row, column)?;
+        Some(Cow::Owned(CellData::with_value(value.clone())))
+    }
+
+    /// Values along `row` for each column in `columns`, in order, with
+    /// `CellValue::Empty` standing in for cells that are not stored.
+    pub fn row_values(
+        &self,
+        row: u32,
+        columns: RangeInclusive<u32>,
+    ) -> impl Iterator<Item = &CellValue> + '_ {
+        columns.map(move |col| self.get_cell_value(row, col).unwrap_or(&CellValue::Empty))
+    }
+
+    /// Values down `column` for each row in `rows`. See [`Worksheet::row_values`].
+    pub fn column_values(
+        &self,
+        column: u32,
+        rows: RangeInclusive<u32>,
+    ) -> impl Iterator<Item = &CellValue> + '_ {
+        rows.map(move |row| {
+            self.get_cell_value(row, column)
+                .unwrap_or(&CellValue::Empty)
+        })
+    }
+
+    /// The rectangle between two corners (in either order), row by row:
+    /// each row yields `(row, column, cell)` for every position, with `None`
+    /// where no cell is stored.
+    pub fn range(
+        &self,
+        row1: u32,
+        col1: u32,
+        row2: u32,
+        col2: u32,
+    ) -> impl Iterator<Item = impl Iterator<Item = (u32, u32, Option<Cow<'_, CellData>>)> + '_> + '_
+    {
+        let (min_col, max_col) = (col1.min(col2), col1.max(col2));
+        (row1.min(row2)..=row1.max(row2))
+            .map(move |row| (min_col..=max_col).map(move |col| (row, col, self.get_cell(row, col))))
     }
 
     /// Get mutable cell data at the specified row and column (1-indexed).
     pub fn get_cell_mut(&mut self, row: u32, column: u32) -> Option<&mut CellData> {
-        self.cells.get_mut(&cell_key(row, column))
+        let key = cell_key(row, column);
+        self.promote(key);
+        self.cells.get_mut(&key)
     }
 
     /// Get the cell value at the specified position.
     pub fn get_cell_value(&self, row: u32, column: u32) -> Option<&CellValue> {
-        self.cells.get(&cell_key(row, column)).map(|cd| &cd.value)
+        match self.cells.get(&cell_key(row, column)) {
+            Some(cell) => Some(&cell.value),
+            None => self.columns.as_ref()?.get(row, column),
+        }
     }
 
     /// Set a cell value at the specified row and column (1-indexed).
+    /// Any rich-text runs the cell had are dropped with its old value.
     pub fn set_cell_value<V: Into<CellValue>>(&mut self, row: u32, column: u32, value: V) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
-        cell_data.value = value.into();
+        let key = cell_key(row, column);
+        self.uncount_string(key);
+        if let Some(value) = self.place_in_column(key, value.into()) {
+            let cell_data = self.cell_entry(key);
+            if cell_data.value != value {
+                // A cached result belongs to the formula it was computed from.
+                cell_data.cached_formula_value = None;
+            }
+            cell_data.value = value;
+            cell_data.rich_text = None;
+        }
+        self.count_string(key);
         self.update_dimensions(row, column);
     }
 
@@ -344,7 +651,8 @@ impl Worksheet {
     /// string on save).
     pub fn set_cell_rich_text(&mut self, row: u32, column: u32, rich: crate::rich_text::RichText) {
         let plain = rich.plain();
-        let cell = self.cells.entry(cell_key(row, column)).or_default();
+        self.uncount_string(cell_key(row, column));
+        let cell = self.cell_entry(cell_key(row, column));
         cell.value = CellValue::String(Arc::from(plain.as_str()));
         cell.data_type = Some("s");
         cell.rich_text = Some(rich);
@@ -354,39 +662,313 @@ impl Worksheet {
     /// Get a mutable reference to a cell, creating it if it doesn't exist.
     pub fn get_or_create_cell_mut(&mut self, row: u32, column: u32) -> &mut CellData {
         self.update_dimensions(row, column);
-        self.cells.entry(cell_key(row, column)).or_default()
+        self.cell_entry(cell_key(row, column))
     }
 
     /// Set complete cell data at the specified position.
     pub fn set_cell_data(&mut self, row: u32, column: u32, data: CellData) {
-        self.cells.insert(cell_key(row, column), data);
+        let key = cell_key(row, column);
+        self.uncount_string(key);
+        if self.columns.is_some() && crate::columnar::is_bare(&data) {
+            if let Some(value) = self.place_in_column(key, data.value) {
+                self.cells.insert(key, CellData::with_value(value));
+            }
+        } else {
+            self.promote(key);
+            self.cells.insert(key, data);
+        }
+        self.count_string(key);
         self.update_dimensions(row, column);
     }
 
+    /// The cell at `key` in the cell map, created if needed; on a columnar
+    /// sheet a value held in its column moves over first. Every change to a
+    /// cell beyond its value goes through here.
+    fn cell_entry(&mut self, key: u64) -> &mut CellData {
+        self.promote(key);
+        self.cells.entry(key).or_default()
+    }
+
+    /// Move the value held in a column at `key`, if any, into the cell map.
+    fn promote(&mut self, key: u64) {
+        let (row, column) = decode_cell_key(key);
+        if let Some(value) = self.columns.as_mut().and_then(|c| c.take(row, column)) {
+            self.cells.insert(key, CellData::with_value(value));
+        }
+    }
+
+    /// On a columnar sheet, store `value` at `key` in its column when the
+    /// cell has nothing else to keep, removing any map entry. Returns the
+    /// value when it belongs in the cell map instead; an empty value always
+    /// does, so clearing a cell leaves the entry the map storage would.
+    fn place_in_column(&mut self, key: u64, value: CellValue) -> Option<CellValue> {
+        let Some(columns) = self.columns.as_mut() else {
+            return Some(value);
+        };
+        let (row, column) = decode_cell_key(key);
+        let plain = self
+            .cells
+            .get(&key)
+            .is_none_or(crate::columnar::holds_only_value);
+        if !plain {
+            return Some(value);
+        }
+        if !crate::columnar::fits_column(&value) {
+            columns.take(row, column);
+            return Some(value);
+        }
+        let rest = columns.set(row, column, value);
+        if rest.is_none() {
+            self.cells.remove(&key);
+        }
+        rest
+    }
+
+    /// Move every column-held value into the cell map. Returns whether the
+    /// sheet had columns to unpack.
+    pub(crate) fn unpack_columns(&mut self) -> bool {
+        let Some(columns) = self.columns.as_mut() else {
+            return false;
+        };
+        for ((row, column), value) in columns.drain() {
+            self.cells
+                .insert(cell_key(row, column), CellData::with_value(value));
+        }
+        true
+    }
+
+    /// Move the cells that hold nothing but a plain value from the cell map
+    /// into their columns, column by column so each run grows at its end.
+    fn pack_columns(&mut self) {
+        let Some(columns) = self.columns.as_mut() else {
+            return;
+        };
+        let mut bare: Vec<u64> = self
+            .cells
+            .iter()
+            .filter(|(_, cell)| crate::columnar::is_bare(cell))
+            .map(|(&key, _)| key)
+            .collect();
+        bare.sort_unstable_by_key(|&key| {
+            let (row, column) = decode_cell_key(key);
+            (column, row)
+        });
+        for key in bare {
+            let (row, column) = decode_cell_key(key);
+            let value = self.cells.remove(&key).unwrap().value;
+            if let Some(value) = columns.set(row, column, value) {
+                self.cells.insert(key, CellData::with_value(value));
+            }
+        }
+        columns.shrink_to_fit();
+        self.cells.shrink_to_fit();
+    }
+
+    /// Run `f` with any column-held values unpacked into the cell map, for
+    /// edits that rearrange cells wholesale, then pack them again.
+    fn with_cells_unpacked<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let columnar = self.unpack_columns();
+        let result = f(self);
+        if columnar {
+            self.pack_columns();
+        }
+        result
+    }
+
+    /// Every cell, in no particular order: the cell map's, then any held
+    /// in columns, which come back owned.
+    pub fn all_cells(&self) -> impl Iterator<Item = ((u32, u32), Cow<'_, CellData>)> + '_ {
+        self.cells
+            .iter()
+            .map(|(&key, cell)| (decode_cell_key(key), Cow::Borrowed(cell)))
+            .chain(
+                self.column_cells()
+                    .map(|(pos, value)| (pos, Cow::Owned(CellData::with_value(value.clone())))),
+            )
+    }
+
+    /// The plain values a columnar sheet holds in its columns, which have
+    /// no entry in `cells`. Empty for map storage.
+    pub fn column_cells(&self) -> impl Iterator<Item = ((u32, u32), &CellValue)> + '_ {
+        self.columns.iter().flat_map(ColumnStore::iter)
+    }
+
+    /// Number of cells, counting those held in columns.
+    pub fn cell_count(&self) -> usize {
+        self.cells.len() + self.columns.as_ref().map_or(0, ColumnStore::len)
+    }
+
+    /// Keys of every cell, in no particular order.
+    fn cell_keys(&self) -> impl Iterator<Item = u64> + '_ {
+        self.cells.keys().copied().chain(
+            self.column_cells()
+                .map(|((row, col), _)| cell_key(row, col)),
+        )
+    }
+
+    /// Start or stop keeping [`Worksheet::string_counts`]. Starting counts
+    /// the cells as they are now.
+    pub(crate) fn track_strings(&mut self, on: bool) {
+        self.string_counts = on.then(|| StringCounts::from_cells(self.all_cells().map(|(_, c)| c)));
+    }
+
+    /// Count the strings afresh after cells were moved or dropped wholesale.
+    fn recount_strings(&mut self) {
+        if self.string_counts.is_some() {
+            self.track_strings(true);
+        }
+    }
+
+    fn count_string(&mut self, key: u64) {
+        if let Some(s) = self.plain_string_at(key) {
+            self.string_counts.as_mut().unwrap().add_string(&s);
+        }
+    }
+
+    fn uncount_string(&mut self, key: u64) {
+        if let Some(s) = self.plain_string_at(key) {
+            self.string_counts.as_mut().unwrap().remove_string(&s);
+        }
+    }
+
+    /// The plain (not rich) string at `key` when strings are being counted.
+    fn plain_string_at(&self, key: u64) -> Option<InternedString> {
+        self.string_counts.as_ref()?;
+        match self.cells.get(&key) {
+            Some(cell) => crate::string_counts::plain_string(cell).cloned(),
+            None => {
+                let (row, column) = decode_cell_key(key);
+                match self.columns.as_ref()?.get(row, column)? {
+                    CellValue::String(s) => Some(s.clone()),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Write `values` into the row after the last one in use, from column A
+    /// on, like openpyxl's `ws.append`, and return that row. Empty values
+    /// leave their cell unwritten, but the row still counts as used, so the
+    /// next append goes below it.
+    pub fn append_row<I: IntoIterator<Item = CellValue>>(&mut self, values: I) -> u32 {
+        let row = self.max_row + 1;
+        for (column, value) in (1..).zip(values) {
+            if !value.is_empty() {
+                self.set_cell_value(row, column, value);
+            }
+        }
+        self.max_row = row;
+        row
+    }
+
+    /// Append each of `rows` with [`Self::append_row`] and return the rows
+    /// written, which is empty when `rows` is.
+    pub fn append_rows<R, I>(&mut self, rows: R) -> std::ops::Range<u32>
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = CellValue>,
+    {
+        let first = self.max_row + 1;
+        for values in rows {
+            self.append_row(values);
+        }
+        first..self.max_row + 1
+    }
+
     /// Set a formula in a cell.
     pub fn set_cell_formula<S: Into<String>>(&mut self, row: u32, column: u32, formula: S) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
+        self.uncount_string(cell_key(row, column));
+        let cell_data = self.cell_entry(cell_key(row, column));
         cell_data.value = CellValue::Formula(formula.into());
         self.update_dimensions(row, column);
     }
 
-    /// Set a cell's hyperlink.
-    pub fn set_cell_hyperlink(&mut self, row: u32, column: u32, url: String) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
-        cell_data.hyperlink = Some(url);
+    /// Write `formula` (with or without its leading `=`) into `start` and
+    /// the `rows - 1` cells below it, moving its relative references down a
+    /// row at a time as Excel's fill-down does: `fill_formula_down("D2",
+    /// "=B2*C2", 3)` writes `B2*C2`, `B3*C3` and `B4*C4`. Any cached results
+    /// in those cells are dropped. See [`crate::Workbook::set_share_formulas`]
+    /// to save the run as one shared formula.
+    pub fn fill_formula_down(&mut self, start: &str, formula: &str, rows: u32) -> Result<()> {
+        let (row, col) = crate::utils::parse_coordinate(&start.replace('$', ""))?;
+        let last_row = row as u64 + rows as u64 - 1;
+        if rows == 0 || last_row > crate::utils::MAX_ROW as u64 {
+            return Err(RustypyxlError::InvalidCoordinate(format!(
+                "Filling {} rows down from {} leaves the sheet",
+                rows, start
+            )));
+        }
+        let formula = formula.strip_prefix('=').unwrap_or(formula);
+        for offset in 0..rows {
+            self.uncount_string(cell_key(row + offset, col));
+            let cell = self.get_or_create_cell_mut(row + offset, col);
+            cell.value = CellValue::Formula(crate::translate::translate_formula(
+                formula,
+                offset as i64,
+                0,
+            ));
+            cell.cached_formula_value = None;
+            cell.data_type = None;
+        }
+        Ok(())
+    }
+
+    /// Set a cell's hyperlink: a [`Hyperlink`], or its address ("#Sheet2!A1"
+    /// for a place in the workbook).
+    pub fn set_cell_hyperlink<H: Into<Hyperlink>>(&mut self, row: u32, column: u32, link: H) {
+        let cell_data = self.cell_entry(cell_key(row, column));
+        cell_data.hyperlink = Some(Box::new(link.into()));
+        self.update_dimensions(row, column);
+    }
+
+    /// Make a cell a link in one call: set the link, show `display` (or the
+    /// link's address when the cell has no value), and with `styled`, give the
+    /// cell's font Excel's Hyperlink look (see [`crate::style::Font::as_hyperlink`]).
+    pub fn set_hyperlink<H: Into<Hyperlink>>(
+        &mut self,
+        row: u32,
+        column: u32,
+        link: H,
+        display: Option<&str>,
+        styled: bool,
+    ) {
+        let mut link = link.into();
+        let key = cell_key(row, column);
+        self.uncount_string(key);
+        let cell_data = self.cell_entry(key);
+        match display {
+            Some(text) => {
+                cell_data.value = CellValue::from(text);
+                link.display = Some(text.to_string());
+            }
+            None if cell_data.value.is_empty() => {
+                cell_data.value = CellValue::from(link.address().as_str())
+            }
+            None => {}
+        }
+        cell_data.hyperlink = Some(Box::new(link));
+        if styled {
+            let font = cell_data
+                .style
+                .as_ref()
+                .and_then(|style| style.font.clone())
+                .unwrap_or_default();
+            self.set_cell_font(row, column, font.as_hyperlink());
+        }
+        self.count_string(key);
         self.update_dimensions(row, column);
     }
 
-    /// Set a cell's comment.
-    pub fn set_cell_comment(&mut self, row: u32, column: u32, comment: String) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
-        cell_data.comment = Some(comment);
+    /// Set a cell's comment: a [`Comment`], or just its text.
+    pub fn set_cell_comment<C: Into<Comment>>(&mut self, row: u32, column: u32, comment: C) {
+        let cell_data = self.cell_entry(cell_key(row, column));
+        cell_data.comment = Some(Box::new(comment.into()));
         self.update_dimensions(row, column);
     }
 
     /// Set a cell's style.
     pub fn set_cell_style(&mut self, row: u32, column: u32, style: CellStyle) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
+        let cell_data = self.cell_entry(cell_key(row, column));
         cell_data.style = Some(Arc::new(style));
         // Invalidate any loaded xf index so the new style is re-resolved on save
         cell_data.style_index = None;
@@ -395,7 +977,7 @@ impl Worksheet {
 
     /// Set a cell's font, merging with any existing style on the cell.
     pub fn set_cell_font(&mut self, row: u32, column: u32, font: crate::style::Font) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
+        let cell_data = self.cell_entry(cell_key(row, column));
         let mut style = cell_data
             .style
             .as_deref()
@@ -414,7 +996,7 @@ impl Worksheet {
         column: u32,
         alignment: crate::style::Alignment,
     ) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
+        let cell_data = self.cell_entry(cell_key(row, column));
         let mut style = cell_data
             .style
             .as_deref()
@@ -428,7 +1010,7 @@ impl Worksheet {
 
     /// Set a cell's number format.
     pub fn set_cell_number_format<S: AsRef<str>>(&mut self, row: u32, column: u32, format: S) {
-        let cell_data = self.cells.entry(cell_key(row, column)).or_default();
+        let cell_data = self.cell_entry(cell_key(row, column));
         cell_data.number_format = Some(Arc::from(format.as_ref()));
         // Invalidate any loaded xf index so the format is re-resolved on save
         cell_data.style_index = None;
@@ -440,28 +1022,99 @@ impl Worksheet {
         self.merged_cells.push((start.into(), end.into()));
     }
 
-    /// Merge cells in a range (e.g., "A1:B2").
-    pub fn merge_cells(&mut self, range: &str) {
-        if let Some(colon_pos) = range.find(':') {
-            let start = range[..colon_pos].to_string();
-            let end = range[colon_pos + 1..].to_string();
-            self.merged_cells.push((start, end));
+    /// Merge cells in a range (e.g., "A1:B2") the way Excel does: the
+    /// top-left cell keeps its value and the others are blanked, keeping
+    /// their styles so borders still draw around the merged area.
+    ///
+    /// Fails for a single cell or a range overlapping an existing merge.
+    pub fn merge_cells(&mut self, range: &str) -> Result<()> {
+        let bounds = range_bounds(range)?;
+        let (min_row, min_col, max_row, max_col) = bounds;
+        if min_row == max_row && min_col == max_col {
+            return Err(RustypyxlError::custom(format!(
+                "Cannot merge {}: a merged range needs at least two cells",
+                range
+            )));
         }
+        if let Some((start, end)) = self.merged_cells.iter().find(|(start, end)| {
+            merged_bounds(start, end).is_some_and(|other| ranges_overlap(bounds, other))
+        }) {
+            return Err(RustypyxlError::custom(format!(
+                "Cannot merge {}: it overlaps the merged range {}:{}",
+                range, start, end
+            )));
+        }
+
+        let top_left = cell_key(min_row, min_col);
+        self.with_cells_unpacked(|ws| {
+            ws.cells.retain(|&key, cell| {
+                let (row, col) = decode_cell_key(key);
+                if key == top_left
+                    || row < min_row
+                    || row > max_row
+                    || col < min_col
+                    || col > max_col
+                {
+                    return true;
+                }
+                cell.value = CellValue::Empty;
+                cell.rich_text = None;
+                cell.cached_formula_value = None;
+                cell.data_type = None;
+                // Nothing left to keep once the value is gone
+                cell.style.is_some()
+                    || cell.style_index.is_some()
+                    || cell.number_format.is_some()
+                    || cell.hyperlink.is_some()
+                    || cell.comment.is_some()
+            });
+        });
+        self.recount_strings();
+
+        self.merged_cells.push((
+            crate::utils::coordinate_from_row_col(min_row, min_col),
+            crate::utils::coordinate_from_row_col(max_row, max_col),
+        ));
+        Ok(())
     }
 
-    /// Unmerge cells in a range.
-    pub fn unmerge_cells(&mut self, range: &str) {
-        if let Some(colon_pos) = range.find(':') {
-            let start = range[..colon_pos].to_string();
-            let end = range[colon_pos + 1..].to_string();
-            self.merged_cells
-                .retain(|(s, e)| !(s == &start && e == &end));
+    /// Unmerge a merged range, given as it was merged (e.g. "A1:B2"). Cells
+    /// blanked by the merge stay blank. Fails if the range is not merged.
+    pub fn unmerge_cells(&mut self, range: &str) -> Result<()> {
+        let bounds = range_bounds(range)?;
+        let before = self.merged_cells.len();
+        self.merged_cells
+            .retain(|(start, end)| merged_bounds(start, end) != Some(bounds));
+        if self.merged_cells.len() == before {
+            return Err(RustypyxlError::custom(format!(
+                "Cell range {} is not merged",
+                range
+            )));
         }
+        Ok(())
+    }
+
+    /// The merged range containing the cell, as (min_row, min_col, max_row,
+    /// max_col), or `None` if the cell is not merged.
+    pub fn merged_range_at(&self, row: u32, column: u32) -> Option<(u32, u32, u32, u32)> {
+        self.merged_cells
+            .iter()
+            .filter_map(|(start, end)| merged_bounds(start, end))
+            .find(|bounds| ranges_overlap(*bounds, (row, column, row, column)))
     }
 
     /// Set column width.
     pub fn set_column_width(&mut self, column: u32, width: f64) {
-        self.column_dimensions.insert(column, width);
+        self.set_column_widths(column, column, width);
+    }
+
+    /// Set one width for every column from `min` to `max` inclusive. A width
+    /// set by hand is no longer a best fit.
+    pub fn set_column_widths(&mut self, min: u32, max: u32, width: f64) {
+        self.column_dimensions.insert_span(min, max, width);
+        if !self.column_props.is_empty() {
+            self.update_column_props(min, max, |p| p.best_fit = false);
+        }
     }
 
     /// Get column width.
@@ -474,26 +1127,79 @@ impl Worksheet {
     ///
     /// This is an approximation, not pixel-perfect: Excel measures actual glyph
     /// widths, whereas this counts characters of the *displayed* string (the
-    /// value rendered under its number format), scales roughly by font size and
-    /// bold, adds padding, and clamps to Excel's maximum. Good enough for the
-    /// common "make the columns readable" need.
+    /// value rendered under its number format), counting East Asian wide
+    /// characters double, scales roughly by font size and bold, adds padding,
+    /// and clamps to Excel's maximum. Cells merged across several columns are
+    /// skipped, as in Excel. Good enough for the common "make the columns
+    /// readable" need.
     pub fn estimate_column_width(&self, column: u32) -> Option<f64> {
+        self.estimate_column_widths((1, column, u32::MAX, column))
+            .remove(&column)
+    }
+
+    /// Estimated widths for every populated column inside `bounds`
+    /// (min_row, min_col, max_row, max_col), measured in one pass over the
+    /// cells. See [`Self::estimate_column_width`].
+    fn estimate_column_widths(&self, bounds: (u32, u32, u32, u32)) -> BTreeMap<u32, f64> {
         // Excel's default column width plus padding for cell margins/gridline.
         const PADDING: f64 = 2.0;
         // Excel's hard maximum column width.
         const MAX_WIDTH: f64 = 255.0;
 
-        let mut max_units: f64 = 0.0;
-        let mut saw_cell = false;
-        for (key, cell) in &self.cells {
-            let (_, col) = decode_cell_key(*key);
-            if col != column {
+        let mut widths: BTreeMap<u32, f64> = BTreeMap::new();
+        self.for_each_text_width(bounds, |col, cell, mut units| {
+            if let Some(style) = &cell.style {
+                if let Some(font) = &style.font {
+                    if let Some(size) = font.size {
+                        if size > 0.0 {
+                            units *= size / 11.0;
+                        }
+                    }
+                    if font.bold {
+                        units *= 1.05;
+                    }
+                }
+            }
+            let width = widths.entry(col).or_insert(0.0);
+            *width = width.max(units);
+        });
+
+        for width in widths.values_mut() {
+            *width = (*width + PADDING).min(MAX_WIDTH);
+        }
+        widths
+    }
+
+    /// Call `f(column, cell, length)` for each populated cell inside
+    /// `bounds`, where `length` is the width in characters of the displayed
+    /// string (the value under its number format, East Asian wide characters
+    /// counting double, a multi-line string its longest line). Cells merged
+    /// across several columns are skipped, as Excel does when fitting.
+    pub(crate) fn for_each_text_width(
+        &self,
+        bounds: (u32, u32, u32, u32),
+        mut f: impl FnMut(u32, &CellData, f64),
+    ) {
+        let wide_merges: Vec<(u32, u32, u32, u32)> = self
+            .merged_cells
+            .iter()
+            .filter_map(|(start, end)| merged_bounds(start, end))
+            .filter(|(_, min_col, _, max_col)| min_col != max_col)
+            .collect();
+
+        for ((row, col), cell) in self.all_cells() {
+            if !ranges_overlap(bounds, (row, col, row, col)) {
                 continue;
             }
             if matches!(cell.value, CellValue::Empty) && cell.rich_text.is_none() {
                 continue;
             }
-            saw_cell = true;
+            if wide_merges
+                .iter()
+                .any(|merge| ranges_overlap(*merge, (row, col, row, col)))
+            {
+                continue;
+            }
 
             // The effective format code may live on the interned per-cell field
             // (set through the core API and on load) or on the cell's style (set
@@ -508,30 +1214,10 @@ impl Worksheet {
             // A wrapped/multi-line string is only as wide as its longest line.
             let longest_line = display
                 .split('\n')
-                .map(|line| line.chars().count())
-                .max()
-                .unwrap_or(0) as f64;
-
-            let mut units = longest_line;
-            if let Some(style) = &cell.style {
-                if let Some(font) = &style.font {
-                    if let Some(size) = font.size {
-                        if size > 0.0 {
-                            units *= size / 11.0;
-                        }
-                    }
-                    if font.bold {
-                        units *= 1.05;
-                    }
-                }
-            }
-            max_units = max_units.max(units);
-        }
-
-        if !saw_cell {
-            return None;
+                .map(|line| line.chars().map(char_width_units).sum::<f64>())
+                .fold(0.0, f64::max);
+            f(col, &cell, longest_line);
         }
-        Some((max_units + PADDING).min(MAX_WIDTH))
     }
 
     /// Size a column to fit its content and return the width set. Does nothing
@@ -539,21 +1225,35 @@ impl Worksheet {
     pub fn auto_fit_column(&mut self, column: u32) -> Option<f64> {
         let width = self.estimate_column_width(column)?;
         self.set_column_width(column, width);
+        self.set_column_best_fit(column, true);
         Some(width)
     }
 
     /// Auto-fit every column that has content.
     pub fn auto_fit_all(&mut self) {
-        let mut columns: Vec<u32> = self
-            .cells
-            .keys()
-            .map(|key| decode_cell_key(*key).1)
-            .collect();
-        columns.sort_unstable();
-        columns.dedup();
-        for column in columns {
-            self.auto_fit_column(column);
+        self.autofit_columns(None)
+            .expect("autofit without a range cannot fail");
+    }
+
+    /// Size columns to fit their content, since Excel only auto-fits when
+    /// asked in the UI and stores no "fit" setting to recompute on open.
+    ///
+    /// `range` limits both the columns sized and the cells measured: "A1:D20"
+    /// fits A-D to rows 1-20 only (e.g. to ignore a long title row), and a
+    /// column range such as "B:D" fits those columns to all their rows.
+    /// `None` fits every populated column. Columns with nothing to measure
+    /// keep their width. Returns the (column, width) pairs that were set.
+    pub fn autofit_columns(&mut self, range: Option<&str>) -> Result<Vec<(u32, f64)>> {
+        let bounds = match range {
+            Some(range) => autofit_bounds(range)?,
+            None => (1, 1, u32::MAX, u32::MAX),
+        };
+        let widths: Vec<(u32, f64)> = self.estimate_column_widths(bounds).into_iter().collect();
+        for &(column, width) in &widths {
+            self.set_column_width(column, width);
+            self.set_column_best_fit(column, true);
         }
+        Ok(widths)
     }
 
     /// Set row height.
@@ -576,13 +1276,46 @@ impl Worksheet {
         self.data_validations.get(&(row, column))
     }
 
-    /// Enable sheet protection.
+    /// Add a rule over its `sqref`, keyed by the top-left cell of the first
+    /// range. Returns that key; a rule already keyed there is replaced.
+    pub fn add_validation(&mut self, validation: DataValidation) -> Result<(u32, u32)> {
+        let sqref = validation.sqref.as_deref().unwrap_or("");
+        let (row, column, _, _) = crate::validation::parse_sqref(sqref)?[0];
+        self.data_validations.insert((row, column), validation);
+        Ok((row, column))
+    }
+
+    /// The rule that covers a cell, whether or not it is keyed there.
+    pub fn data_validation_at(&self, row: u32, column: u32) -> Option<&DataValidation> {
+        self.data_validations.get(&(row, column)).or_else(|| {
+            self.data_validations
+                .values()
+                .find(|dv| dv.covers(row, column))
+        })
+    }
+
+    /// Set where a cell's comment box sits, its size, and whether it is
+    /// always shown. Takes effect when the cell has a comment.
+    pub fn set_comment_shape(&mut self, row: u32, column: u32, shape: CommentShape) {
+        self.comment_shapes.insert((row, column), shape);
+    }
+
+    /// The comment box layout set for a cell, if any.
+    pub fn get_comment_shape(&self, row: u32, column: u32) -> Option<&CommentShape> {
+        self.comment_shapes.get(&(row, column))
+    }
+
+    /// Enable sheet protection with the default locks. A password is stored
+    /// only as its SHA-512 hash.
     pub fn enable_protection(&mut self, password: Option<String>) {
-        self.protection = Some(WorksheetProtection {
+        let mut protection = WorksheetProtection {
             sheet: true,
-            password,
             ..Default::default()
-        });
+        };
+        if let Some(password) = password {
+            protection.set_password(&password);
+        }
+        self.protection = Some(protection);
     }
 
     /// Disable sheet protection.
@@ -607,7 +1340,7 @@ impl Worksheet {
 
     /// Get dimensions as (min_row, min_col, max_row, max_col).
     pub fn dimensions(&self) -> (u32, u32, u32, u32) {
-        if self.cells.is_empty() {
+        if self.cell_count() == 0 {
             return (1, 1, 1, 1);
         }
 
@@ -616,7 +1349,7 @@ impl Worksheet {
         let mut max_row = 0;
         let mut max_col = 0;
 
-        for &key in self.cells.keys() {
+        for key in self.cell_keys() {
             let (row, col) = decode_cell_key(key);
             min_row = min_row.min(row);
             min_col = min_col.min(col);
@@ -627,11 +1360,54 @@ impl Worksheet {
         (min_row, min_col, max_row, max_col)
     }
 
+    /// Count the cells in `range` ("B2:D40", "C:C", "5:9") that hold a
+    /// value; styled cells without one don't count. Works on the cell keys
+    /// alone: a range smaller than the sheet probes its positions, a larger
+    /// one filters the sheet's cells, so neither copies or sorts anything.
+    pub fn non_empty_cells_in(&self, range: &str) -> Result<usize> {
+        let (min_row, min_col, max_row, max_col) = crate::utils::range_boundaries(range)?;
+        let (min_row, max_row) = (min_row.unwrap_or(1), max_row.unwrap_or(u32::MAX));
+        let (min_col, max_col) = (min_col.unwrap_or(1), max_col.unwrap_or(u32::MAX));
+        let has_value = |value: &CellValue| !value.is_empty();
+        let area = u64::from(max_row - min_row + 1) * u64::from(max_col - min_col + 1);
+        if area <= self.cell_count() as u64 {
+            Ok((min_row..=max_row)
+                .flat_map(|row| (min_col..=max_col).map(move |col| (row, col)))
+                .filter(|&(row, col)| self.get_cell_value(row, col).is_some_and(has_value))
+                .count())
+        } else {
+            let in_range = |(row, col): (u32, u32)| {
+                (min_row..=max_row).contains(&row) && (min_col..=max_col).contains(&col)
+            };
+            let in_map = self
+                .cells
+                .iter()
+                .filter(|(&key, cell)| in_range(decode_cell_key(key)) && has_value(&cell.value))
+                .count();
+            let in_columns = self
+                .column_cells()
+                .filter(|&(pos, _)| in_range(pos))
+                .count();
+            Ok(in_map + in_columns)
+        }
+    }
+
+    /// The first row, from the top, whose cell in `column` holds no value:
+    /// where the next record goes in a template filled from the top down.
+    pub fn first_empty_row(&self, column: u32) -> u32 {
+        (1..u32::MAX)
+            .find(|&row| {
+                self.get_cell_value(row, column)
+                    .is_none_or(CellValue::is_empty)
+            })
+            .unwrap_or(u32::MAX)
+    }
+
     /// Iterate over all cells in row-major order.
-    pub fn iter_cells(&self) -> impl Iterator<Item = ((u32, u32), &CellData)> {
-        let mut cells: Vec<_> = self.cells.iter().map(|(k, v)| (*k, v)).collect();
-        cells.sort_by_key(|(k, _)| decode_cell_key(*k));
-        cells.into_iter().map(|(k, v)| (decode_cell_key(k), v))
+    pub fn iter_cells(&self) -> impl Iterator<Item = ((u32, u32), Cow<'_, CellData>)> {
+        let mut cells: Vec<_> = self.all_cells().collect();
+        cells.sort_by_key(|(pos, _)| *pos);
+        cells.into_iter()
     }
 
     /// Iterate over the populated cells of one row, in column order.
@@ -639,16 +1415,13 @@ impl Worksheet {
     /// Probes the row's columns by key rather than scanning the whole cell map:
     /// filtering and sorting every cell to serve one row made row-by-row
     /// iteration O(rows * total_cells).
-    pub fn iter_row(&self, row: u32) -> impl Iterator<Item = (u32, &CellData)> + '_ {
-        (1..=self.max_column).filter_map(move |col| {
-            self.cells
-                .get(&cell_key(row, col))
-                .map(|cell_data| (col, cell_data))
-        })
+    pub fn iter_row(&self, row: u32) -> impl Iterator<Item = (u32, Cow<'_, CellData>)> + '_ {
+        (1..=self.max_column)
+            .filter_map(move |col| self.get_cell(row, col).map(|cell_data| (col, cell_data)))
     }
 
     /// Update max_row and max_column.
-    fn update_dimensions(&mut self, row: u32, column: u32) {
+    pub(crate) fn update_dimensions(&mut self, row: u32, column: u32) {
         self.max_row = self.max_row.max(row);
         self.max_column = self.max_column.max(column);
     }
@@ -684,6 +1457,83 @@ impl Worksheet {
         }
     }
 
+    /// Move the cells in `range` (e.g. "B2:D10") by `rows` and `cols`,
+    /// overwriting whatever is at the destination, as openpyxl's
+    /// `move_range` does. Values, styles, hyperlinks, comments and cell tags
+    /// go with the cells. With `translate`, relative references in the
+    /// moved formulas shift by the same amount; other cells' formulas are
+    /// never rewritten. Fails if the destination falls off the sheet.
+    pub fn move_range(&mut self, range: &str, rows: i64, cols: i64, translate: bool) -> Result<()> {
+        let ((r1, c1), (r2, c2)) = if range.contains(':') {
+            crate::utils::parse_range(range)?
+        } else {
+            let cell = crate::utils::parse_coordinate(range)?;
+            (cell, cell)
+        };
+        let (r1, r2) = (r1.min(r2), r1.max(r2));
+        let (c1, c2) = (c1.min(c2), c1.max(c2));
+        let in_bounds = |row: u32, col: u32| {
+            let (row, col) = (row as i64 + rows, col as i64 + cols);
+            (1..=crate::utils::MAX_ROW as i64).contains(&row)
+                && (1..=crate::utils::MAX_COLUMN as i64).contains(&col)
+        };
+        if !in_bounds(r1, c1) || !in_bounds(r2, c2) {
+            return Err(RustypyxlError::InvalidCoordinate(format!(
+                "Moving {} by {} rows and {} columns leaves the sheet",
+                range, rows, cols
+            )));
+        }
+        if rows == 0 && cols == 0 {
+            return Ok(());
+        }
+        let inside = |row: u32, col: u32| (r1..=r2).contains(&row) && (c1..=c2).contains(&col);
+        let dest = |row: u32, col: u32| ((row as i64 + rows) as u32, (col as i64 + cols) as u32);
+
+        self.with_cells_unpacked(|ws| {
+            // Lift everything out first so overlapping source and destination
+            // areas do not clobber each other.
+            let moving: Vec<u64> = ws
+                .cells
+                .keys()
+                .copied()
+                .filter(|&key| {
+                    let (row, col) = decode_cell_key(key);
+                    inside(row, col)
+                })
+                .collect();
+            let mut lifted = Vec::with_capacity(moving.len());
+            for key in moving {
+                if let Some(data) = ws.cells.remove(&key) {
+                    lifted.push((decode_cell_key(key), data));
+                }
+            }
+            for row in r1..=r2 {
+                for col in c1..=c2 {
+                    let (r, c) = dest(row, col);
+                    ws.cells.remove(&cell_key(r, c));
+                }
+            }
+            for ((row, col), mut data) in lifted {
+                if translate {
+                    if let CellValue::Formula(formula) = &data.value {
+                        data.value = CellValue::Formula(crate::translate::translate_formula(
+                            formula, rows, cols,
+                        ));
+                    }
+                }
+                let (r, c) = dest(row, col);
+                ws.cells.insert(cell_key(r, c), data);
+            }
+        });
+
+        move_entries(&mut self.comment_shapes, inside, dest);
+        move_entries(&mut self.cell_tags, inside, dest);
+
+        self.recount_strings();
+        self.recompute_dimensions();
+        Ok(())
+    }
+
     /// Apply a row or column insert/delete to every position-bearing part of
     /// the sheet. `is_row` selects the axis.
     fn apply_shift(&mut self, shift: Shift, is_row: bool) {
@@ -697,21 +1547,26 @@ impl Worksheet {
 
         // Cells (with their per-cell styles/hyperlinks/comments): rebuild the
         // map with shifted keys, dropping any cell in a deleted band.
-        let mut new_cells = CellMap::default();
-        new_cells.reserve(self.cells.len());
-        for (key, data) in self.cells.drain() {
-            let (row, col) = decode_cell_key(key);
-            if let Some((r, c)) = map_pos(row, col) {
-                new_cells.insert(cell_key(r, c), data);
+        self.with_cells_unpacked(|ws| {
+            let mut new_cells = CellMap::default();
+            new_cells.reserve(ws.cells.len());
+            for (key, data) in ws.cells.drain() {
+                let (row, col) = decode_cell_key(key);
+                if let Some((r, c)) = map_pos(row, col) {
+                    new_cells.insert(cell_key(r, c), data);
+                }
             }
-        }
-        self.cells = new_cells;
+            ws.cells = new_cells;
+        });
+        self.recount_strings();
 
         // Row heights / column widths: shift keys on the affected axis only.
         if is_row {
             self.row_dimensions = shift_dim_keys(&self.row_dimensions, shift);
+            self.row_props = shift_dim_keys(&self.row_props, shift);
         } else {
-            self.column_dimensions = shift_dim_keys(&self.column_dimensions, shift);
+            self.column_dimensions = shift_column_spans(&self.column_dimensions, shift);
+            self.column_props = shift_column_spans(&self.column_props, shift);
         }
 
         // Merged ranges: move/grow/shrink; drop if collapsed to nothing or to a
@@ -738,6 +1593,29 @@ impl Worksheet {
         }
         self.data_validations = new_dv;
 
+        // Comment boxes follow their cell; an explicit anchor moves too, or
+        // falls back to the default spot if its cell was deleted.
+        let mut new_shapes = HashMap::new();
+        for ((row, col), mut shape) in std::mem::take(&mut self.comment_shapes) {
+            if let Some(pos) = map_pos(row, col) {
+                shape.anchor = shape.anchor.and_then(|(r, c)| map_pos(r, c));
+                new_shapes.insert(pos, shape);
+            }
+        }
+        self.comment_shapes = new_shapes;
+
+        // Tags move with their row or cell and vanish with it.
+        if is_row {
+            self.row_tags = std::mem::take(&mut self.row_tags)
+                .into_iter()
+                .filter_map(|(row, tag)| shift.map(row).map(|r| (r, tag)))
+                .collect();
+        }
+        self.cell_tags = std::mem::take(&mut self.cell_tags)
+            .into_iter()
+            .filter_map(|((row, col), tag)| map_pos(row, col).map(|pos| (pos, tag)))
+            .collect();
+
         // Range-bearing features.
         self.conditional_formatting.retain_mut(|cf| {
             match shift_range_str(&cf.range, shift, is_row) {
@@ -768,10 +1646,10 @@ impl Worksheet {
         self.recompute_dimensions();
     }
 
-    /// Recompute max_row/max_column by scanning the (already shifted) cell map.
-    fn recompute_dimensions(&mut self) {
+    /// Recompute max_row/max_column by scanning the (already shifted) cells.
+    pub(crate) fn recompute_dimensions(&mut self) {
         let (mut max_row, mut max_col) = (0, 0);
-        for &key in self.cells.keys() {
+        for key in self.cell_keys() {
             let (r, c) = decode_cell_key(key);
             max_row = max_row.max(r);
             max_col = max_col.max(c);
@@ -863,6 +1741,62 @@ fn shift_coord_str(coord: &str, shift: Shift, is_row: bool) -> Option<String> {
     Some(crate::utils::coordinate_from_row_col(nr, nc))
 }
 
+/// (min_row, min_col, max_row, max_col) of a range such as "B2:A1" or
+/// "$A$1:$C$3", or of a single cell.
+fn range_bounds(range: &str) -> Result<(u32, u32, u32, u32)> {
+    let range = range.replace('$', "");
+    let ((r1, c1), (r2, c2)) = if range.contains(':') {
+        crate::utils::parse_range(&range)?
+    } else {
+        let cell = crate::utils::parse_coordinate(&range)?;
+        (cell, cell)
+    };
+    Ok((r1.min(r2), c1.min(c2), r1.max(r2), c1.max(c2)))
+}
+
+/// Bounds for [`Worksheet::autofit_columns`]: a cell range, a single cell,
+/// or a column range such as "B:D" covering every row.
+pub(crate) fn autofit_bounds(range: &str) -> Result<(u32, u32, u32, u32)> {
+    let trimmed = range.replace('$', "");
+    if let Some((first, last)) = trimmed.split_once(':') {
+        let is_letters = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic());
+        if is_letters(first) && is_letters(last) {
+            let (a, b) = (
+                crate::utils::letter_to_column(first)?,
+                crate::utils::letter_to_column(last)?,
+            );
+            return Ok((1, a.min(b), u32::MAX, a.max(b)));
+        }
+    }
+    range_bounds(&trimmed)
+}
+
+/// Approximate display width of a character in Excel character units: East
+/// Asian wide and fullwidth characters take two, everything else one.
+fn char_width_units(c: char) -> f64 {
+    match c {
+        '\u{1100}'..='\u{115F}'
+        | '\u{2E80}'..='\u{A4CF}'
+        | '\u{AC00}'..='\u{D7A3}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{FE30}'..='\u{FE4F}'
+        | '\u{FF00}'..='\u{FF60}'
+        | '\u{FFE0}'..='\u{FFE6}'
+        | '\u{1F300}'..='\u{1F64F}'
+        | '\u{20000}'..='\u{3FFFD}' => 2.0,
+        _ => 1.0,
+    }
+}
+
+/// Bounds of a stored merged range; `None` if either corner is unreadable.
+fn merged_bounds(start: &str, end: &str) -> Option<(u32, u32, u32, u32)> {
+    range_bounds(&format!("{}:{}", start, end)).ok()
+}
+
+fn ranges_overlap(a: (u32, u32, u32, u32), b: (u32, u32, u32, u32)) -> bool {
+    a.0 <= b.2 && b.0 <= a.2 && a.1 <= b.3 && b.1 <= a.3
+}
+
 /// Shift a merged range's `(start, end)` coordinates; None if it collapses to
 /// nothing or to a single cell (no longer a merge).
 fn shift_merge(s: &str, e: &str, shift: Shift, is_row: bool) -> Option<(String, String)> {
@@ -927,7 +1861,7 @@ fn shift_sqref(sqref: &str, shift: Shift, is_row: bool) -> Option<String> {
 }
 
 /// Shift the keys of a row/column dimension map, dropping deleted lines.
-fn shift_dim_keys(dims: &HashMap<u32, f64>, shift: Shift) -> HashMap<u32, f64> {
+fn shift_dim_keys<V: Copy>(dims: &HashMap<u32, V>, shift: Shift) -> HashMap<u32, V> {
     let mut out = HashMap::with_capacity(dims.len());
     for (&k, &v) in dims {
         if let Some(nk) = shift.map(k) {
@@ -937,6 +1871,42 @@ fn shift_dim_keys(dims: &HashMap<u32, f64>, shift: Shift) -> HashMap<u32, f64> {
     out
 }
 
+/// Shift column spans, splitting a span at an insert point and trimming the
+/// deleted columns out of the spans a delete overlaps.
+fn shift_column_spans<V: Clone + PartialEq>(
+    spans: &ColumnSpans<V>,
+    shift: Shift,
+) -> ColumnSpans<V> {
+    let mut out = ColumnSpans::new();
+    for (min, max, value) in spans.spans() {
+        match shift {
+            Shift::Insert { at, .. } if min < at && at <= max => {
+                out.insert_span(min, at - 1, value.clone());
+                out.insert_span(shift.map_start(at), shift.map_end(max), value.clone());
+            }
+            _ => out.insert_span(shift.map_start(min), shift.map_end(max), value.clone()),
+        }
+    }
+    out
+}
+
+/// Re-key the entries of a per-cell map that `inside` selects, as
+/// `move_range` does for cells.
+fn move_entries<V>(
+    map: &mut HashMap<(u32, u32), V>,
+    inside: impl Fn(u32, u32) -> bool,
+    dest: impl Fn(u32, u32) -> (u32, u32),
+) {
+    let keys: Vec<_> = map.keys().copied().filter(|&(r, c)| inside(r, c)).collect();
+    let lifted: Vec<_> = keys
+        .into_iter()
+        .filter_map(|k| map.remove_entry(&k))
+        .collect();
+    for ((row, col), value) in lifted {
+        map.insert(dest(row, col), value);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -970,6 +1940,89 @@ mod tests {
         assert_eq!(ws.iter_row(3).count(), 0, "empty row yields nothing");
     }
 
+    /// formatted_value renders through the style's format first, then the
+    /// cell's own, and shows a formula's cached result.
+    #[test]
+    fn test_formatted_value_uses_effective_format_and_cached_result() {
+        let mut cell = CellData::with_value(CellValue::Number(1234.5));
+        assert_eq!(cell.formatted_value(), "1234.5");
+        cell.number_format = Some(Arc::from("#,##0.00"));
+        assert_eq!(cell.formatted_value(), "1,234.50");
+        cell.style = Some(Arc::new(CellStyle::new().with_number_format("# ?/?")));
+        assert_eq!(cell.number_format_code(), Some("# ?/?"));
+        assert_eq!(cell.formatted_value(), "1234 1/2");
+
+        let mut formula = CellData::with_value(CellValue::Formula("=A1*2".into()));
+        formula.number_format = Some(Arc::from("0.0%"));
+        assert_eq!(formula.formatted_value(), "");
+        formula.cached_formula_value = Some("0.25".to_string());
+        assert_eq!(formula.formatted_value(), "25.0%");
+        formula.data_type = Some("e");
+        formula.cached_formula_value = Some("#DIV/0!".to_string());
+        assert_eq!(formula.formatted_value(), "#DIV/0!");
+    }
+
+    /// row_values and column_values fill gaps with Empty so every position
+    /// in the requested span is accounted for.
+    #[test]
+    fn test_row_and_column_values_fill_gaps() {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_value(2, 1, CellValue::Number(1.0));
+        ws.set_cell_value(2, 3, CellValue::Number(3.0));
+        ws.set_cell_value(4, 3, CellValue::Number(4.0));
+
+        let row: Vec<&CellValue> = ws.row_values(2, 1..=4).collect();
+        assert_eq!(
+            row,
+            vec![
+                &CellValue::Number(1.0),
+                &CellValue::Empty,
+                &CellValue::Number(3.0),
+                &CellValue::Empty,
+            ]
+        );
+
+        let column: Vec<&CellValue> = ws.column_values(3, 2..=4).collect();
+        assert_eq!(
+            column,
+            vec![
+                &CellValue::Number(3.0),
+                &CellValue::Empty,
+                &CellValue::Number(4.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_yields_every_position_row_by_row() {
+        let mut ws = Worksheet::new("S");
+        ws.set_cell_value(1, 2, CellValue::Number(2.0));
+        ws.set_cell_value(2, 1, CellValue::Number(3.0));
+
+        // Corners given bottom-right first still read top-left to bottom-right.
+        let rows: Vec<Vec<(u32, u32, Option<f64>)>> = ws
+            .range(2, 2, 1, 1)
+            .map(|row| {
+                row.map(|(r, c, cell)| {
+                    let value = cell.map(|cell| match cell.value {
+                        CellValue::Number(n) => n,
+                        _ => panic!("expected a number"),
+                    });
+                    (r, c, value)
+                })
+                .collect()
+            })
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![(1, 1, None), (1, 2, Some(2.0))],
+                vec![(2, 1, Some(3.0)), (2, 2, None)],
+            ]
+        );
+    }
+
     #[test]
     fn test_set_cell_value() {
         let mut ws = Worksheet::new("Sheet1");
@@ -993,13 +2046,48 @@ mod tests {
     #[test]
     fn test_merged_cells() {
         let mut ws = Worksheet::new("Sheet1");
-        ws.merge_cells("A1:B2");
+        ws.merge_cells("A1:B2").unwrap();
         assert_eq!(ws.merged_cells.len(), 1);
 
-        ws.unmerge_cells("A1:B2");
+        ws.unmerge_cells("A1:B2").unwrap();
         assert!(ws.merged_cells.is_empty());
     }
 
+    #[test]
+    fn test_merge_cells_blanks_all_but_top_left() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.set_cell_value(1, 1, CellValue::from("keep"));
+        ws.set_cell_value(1, 2, CellValue::from("drop"));
+        ws.set_cell_value(2, 2, CellValue::Number(2.0));
+        ws.set_cell_style(2, 2, CellStyle::new());
+        ws.set_cell_value(4, 1, CellValue::from("outside"));
+
+        ws.merge_cells("$B$2:A1").unwrap();
+        assert_eq!(ws.merged_cells, vec![("A1".to_string(), "B2".to_string())]);
+        assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::from("keep")));
+        assert!(ws.get_cell(1, 2).is_none());
+        let styled = ws.get_cell(2, 2).unwrap();
+        assert_eq!(styled.value, CellValue::Empty);
+        assert!(styled.style.is_some());
+        assert_eq!(ws.get_cell_value(4, 1), Some(&CellValue::from("outside")));
+        assert_eq!(ws.merged_range_at(2, 1), Some((1, 1, 2, 2)));
+        assert_eq!(ws.merged_range_at(3, 1), None);
+    }
+
+    #[test]
+    fn test_merge_cells_validation() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.merge_cells("B2:C3").unwrap();
+        let err = ws.merge_cells("C3:D4").unwrap_err();
+        assert!(err.to_string().contains("overlaps the merged range B2:C3"));
+        assert!(ws.merge_cells("A1").is_err());
+        assert!(ws.merge_cells("D1:E1").is_ok());
+
+        assert!(ws.unmerge_cells("B2:B3").is_err());
+        ws.unmerge_cells("C3:B2").unwrap();
+        assert_eq!(ws.merged_cells.len(), 1);
+    }
+
     #[test]
     fn test_column_dimensions() {
         let mut ws = Worksheet::new("Sheet1");
@@ -1008,6 +2096,25 @@ mod tests {
         assert_eq!(ws.get_column_width(2), None);
     }
 
+    #[test]
+    fn test_column_widths_follow_column_shifts() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.set_column_widths(2, 6, 12.0);
+        ws.insert_columns(4, 2);
+        assert_eq!(ws.get_column_width(3), Some(12.0));
+        assert_eq!(ws.get_column_width(4), None);
+        assert_eq!(ws.get_column_width(8), Some(12.0));
+        assert_eq!(ws.get_column_width(9), None);
+
+        ws.delete_columns(3, 3);
+        let spans: Vec<_> = ws
+            .column_dimensions
+            .spans()
+            .map(|(a, b, _)| (a, b))
+            .collect();
+        assert_eq!(spans, vec![(2, 5)]);
+    }
+
     #[test]
     fn test_row_dimensions() {
         let mut ws = Worksheet::new("Sheet1");
@@ -1016,6 +2123,29 @@ mod tests {
         assert_eq!(ws.get_row_height(2), None);
     }
 
+    #[test]
+    fn test_append_rows_after_last_used_row() {
+        let mut ws = Worksheet::new("Sheet1");
+        assert_eq!(
+            ws.append_row(vec![CellValue::from("Name"), CellValue::from("Qty")]),
+            1
+        );
+        ws.set_cell_value(3, 2, 7.0);
+        assert_eq!(
+            ws.append_row(vec![CellValue::Empty, CellValue::Number(5.0)]),
+            4
+        );
+        assert_eq!(ws.get_cell_value(4, 1), None);
+        assert_eq!(ws.get_cell_value(4, 2), Some(&CellValue::Number(5.0)));
+
+        // A row of nothing still takes its row.
+        assert_eq!(ws.append_row(Vec::new()), 5);
+        let rows = ws.append_rows(vec![vec![CellValue::from("a")], vec![CellValue::from("b")]]);
+        assert_eq!(rows, 6..8);
+        assert_eq!(ws.get_cell_value(7, 1), Some(&CellValue::from("b")));
+        assert!(ws.append_rows(Vec::<Vec<CellValue>>::new()).is_empty());
+    }
+
     #[test]
     fn test_protection() {
         let mut ws = Worksheet::new("Sheet1");
@@ -1023,11 +2153,31 @@ mod tests {
 
         ws.enable_protection(Some("password".to_string()));
         assert!(ws.is_protected());
+        let protection = ws.protection.as_ref().unwrap();
+        assert!(protection.password.is_none());
+        #[cfg(feature = "encrypt")]
+        assert_eq!(protection.hash.as_ref().unwrap().algorithm_name, "SHA-512");
+        #[cfg(not(feature = "encrypt"))]
+        assert_eq!(protection.password_hash.as_deref(), Some("83AF"));
+        assert!(protection.verify_password("password"));
+        assert!(!protection.verify_password("guess"));
+        assert!(protection.format_cells && !protection.select_locked_cells);
 
         ws.disable_protection();
         assert!(!ws.is_protected());
     }
 
+    #[test]
+    fn test_validation_lookup_by_range() {
+        let mut ws = Worksheet::new("Sheet1");
+        let dv = DataValidation::list(["a", "b"]).with_sqref("B2:B5 D1");
+        assert_eq!(ws.add_validation(dv).unwrap(), (2, 2));
+        assert!(ws.data_validation_at(4, 2).is_some());
+        assert!(ws.data_validation_at(1, 4).is_some());
+        assert!(ws.data_validation_at(6, 2).is_none());
+        assert!(ws.add_validation(DataValidation::new("whole")).is_err());
+    }
+
     #[test]
     fn test_dimensions() {
         let mut ws = Worksheet::new("Sheet1");
@@ -1038,4 +2188,164 @@ mod tests {
         assert_eq!((min_r, min_c), (2, 1));
         assert_eq!((max_r, max_c), (5, 3));
     }
+
+    #[test]
+    fn test_sparsity_queries() {
+        let mut ws = Worksheet::new("Sheet1");
+        for row in 1..=3 {
+            ws.set_cell_value(row, 1, f64::from(row));
+        }
+        ws.set_cell_value(5, 1, "after a gap");
+        ws.set_cell_value(2, 2, "B2");
+        // Styled but valueless: a formatted template row.
+        ws.set_cell_number_format(4, 1, "0.00");
+        ws.set_cell_number_format(4, 2, "0.00");
+
+        // A small range probes keys; a large one filters the sheet.
+        assert_eq!(ws.non_empty_cells_in("A1:B2").unwrap(), 3);
+        assert_eq!(ws.non_empty_cells_in("A1:Z1000").unwrap(), 5);
+        assert_eq!(ws.non_empty_cells_in("C1:C3").unwrap(), 0);
+        assert_eq!(ws.non_empty_cells_in("A:A").unwrap(), 4);
+        assert_eq!(ws.non_empty_cells_in("2:2").unwrap(), 2);
+        assert!(ws.non_empty_cells_in("not a range").is_err());
+
+        assert_eq!(ws.first_empty_row(1), 4);
+        assert_eq!(ws.first_empty_row(2), 1);
+        assert_eq!(ws.first_empty_row(7), 1);
+        assert_eq!(Worksheet::new("Empty").first_empty_row(1), 1);
+    }
+
+    #[test]
+    fn test_move_range() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.set_cell_value(1, 1, 1.0);
+        ws.set_cell_value(2, 1, 2.0);
+        ws.set_cell_value(3, 1, CellValue::Formula("=A1+$A$2".to_string()));
+        ws.set_cell_value(4, 2, "overwritten");
+        ws.set_cell_tag(3, 1, "total").unwrap();
+
+        // Overlapping move down one row and right one column.
+        ws.move_range("A2:A3", 1, 1, true).unwrap();
+        assert!(matches!(
+            ws.get_cell_value(2, 1),
+            None | Some(CellValue::Empty)
+        ));
+        assert_eq!(ws.get_cell_value(3, 2), Some(&CellValue::Number(2.0)));
+        assert_eq!(
+            ws.get_cell_value(4, 2),
+            Some(&CellValue::Formula("=B2+$A$2".to_string()))
+        );
+        assert_eq!(ws.find_cell_by_tag("total"), Some((4, 2)));
+        assert_eq!(ws.dimensions(), (1, 1, 4, 2));
+
+        ws.move_range("B4", 0, 1, false).unwrap();
+        assert_eq!(
+            ws.get_cell_value(4, 3),
+            Some(&CellValue::Formula("=B2+$A$2".to_string()))
+        );
+        assert!(ws.move_range("A1:C4", -1, 0, false).is_err());
+    }
+
+    #[test]
+    fn test_set_hyperlink() {
+        let mut ws = Worksheet::new("Sheet1");
+        ws.set_cell_font(1, 1, crate::style::Font::new().with_bold(true));
+        ws.set_hyperlink(1, 1, "https://example.com", None, true);
+        let cell = ws.get_cell(1, 1).unwrap();
+        assert_eq!(cell.value, CellValue::from("https://example.com"));
+        let font = cell.style.as_ref().unwrap().font.clone().unwrap();
+        assert!(font.bold);
+        assert_eq!(font.underline.as_deref(), Some("single"));
+
+        ws.set_cell_value(2, 1, "Docs");
+        ws.set_hyperlink(2, 1, "https://example.com/docs", None, false);
+        let cell = ws.get_cell(2, 1).unwrap();
+        assert_eq!(cell.value, CellValue::from("Docs"));
+        assert!(cell.style.is_none());
+        ws.set_hyperlink(2, 1, "#Sheet2!A1", Some("Jump"), false);
+        assert_eq!(ws.get_cell_value(2, 1), Some(&CellValue::from("Jump")));
+        let cell = ws.get_cell(2, 1).unwrap();
+        let link = cell.hyperlink.as_deref().unwrap();
+        assert!(!link.is_external);
+        assert_eq!(link.target, "Sheet2!A1");
+        assert_eq!(link.display.as_deref(), Some("Jump"));
+    }
+
+    /// The same edits on a map sheet and a columnar one.
+    fn filled(storage: Storage) -> Worksheet {
+        let mut ws = Worksheet::new("Data").with_storage(storage);
+        for row in 1..=50 {
+            ws.set_cell_value(row, 1, format!("id-{}", row % 7));
+            ws.set_cell_value(row, 2, row as f64 * 1.5);
+            ws.set_cell_value(row, 3, row % 2 == 0);
+        }
+        ws.set_cell_value(2, 2, CellValue::Formula("B1*2".to_string()));
+        ws.set_cell_number_format(3, 2, "0.00");
+        ws.set_cell_value(5, 3, CellValue::Empty);
+        ws.set_cell_value(100_000, 2, 7.0);
+        ws
+    }
+
+    #[test]
+    fn test_columnar_storage_reads_like_the_map() {
+        let mut map = filled(Storage::Map);
+        let mut columnar = filled(Storage::Columnar);
+        assert_eq!(columnar.storage(), Storage::Columnar);
+        // Only the formula, the formatted, cleared and far cells need the map.
+        assert_eq!(columnar.cells.len(), 4);
+
+        for ws in [&mut map, &mut columnar] {
+            ws.insert_rows(10, 2);
+            ws.delete_columns(1, 1);
+            ws.merge_cells("A20:B21").unwrap();
+            ws.set_cell_value(1, 1, "top");
+        }
+        for ws in [&map, &columnar] {
+            assert_eq!(ws.get_cell_value(3, 1), Some(&CellValue::Number(4.5)));
+            assert_eq!(
+                ws.get_cell(3, 1).unwrap().number_format.as_deref(),
+                Some("0.00")
+            );
+            assert_eq!(ws.get_cell_value(5, 2), Some(&CellValue::Empty));
+            assert_eq!(ws.get_cell_value(12, 1), Some(&CellValue::Number(15.0)));
+            assert_eq!(ws.get_cell_value(21, 2), None);
+        }
+        let values = |ws: &Worksheet| -> Vec<_> {
+            ws.iter_cells()
+                .map(|(pos, cell)| (pos, cell.value.clone(), cell.number_format.clone()))
+                .collect()
+        };
+        assert_eq!(values(&map), values(&columnar));
+        assert_eq!(map.dimensions(), columnar.dimensions());
+        assert_eq!(
+            map.non_empty_cells_in("A:B").unwrap(),
+            columnar.non_empty_cells_in("A:B").unwrap()
+        );
+
+        // Styling a value moves it to the map; switching back unpacks the rest.
+        columnar.set_cell_font(4, 1, crate::style::Font::new().with_bold(true));
+        assert!(columnar.cells.contains_key(&cell_key(4, 1)));
+        columnar.set_storage(Storage::Map);
+        assert_eq!(values(&map).len(), columnar.cells.len());
+    }
+
+    #[test]
+    fn test_columnar_storage_saves_like_the_map() {
+        let mut saved = Vec::new();
+        for storage in [Storage::Map, Storage::Columnar] {
+            let mut wb = crate::Workbook::new();
+            wb.push_sheet("Data".to_string(), filled(storage)).unwrap();
+            saved.push(wb.save_to_bytes().unwrap());
+        }
+        let (map, columnar) = (
+            crate::Workbook::load_from_bytes(&saved[0]).unwrap(),
+            crate::Workbook::load_from_bytes(&saved[1]).unwrap(),
+        );
+        let (map, columnar) = (&map.worksheets[0], &columnar.worksheets[0]);
+        assert_eq!(map.cells.len(), columnar.cells.len());
+        for ((pos, cell), (other_pos, other)) in map.iter_cells().zip(columnar.iter_cells()) {
+            assert_eq!(pos, other_pos);
+            assert_eq!(cell.value, other.value);
+        }
+    }
 }
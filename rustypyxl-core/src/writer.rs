@@ -1,16 +1,24 @@
 use crate::autofilter::FilterType;
 use crate::cell::CellValue;
 use crate::cell::InternedString;
+use crate::comments::Comment;
 use crate::conditional::{ConditionalColor, ConditionalFormat, ConditionalFormatType};
+use crate::custom_xml::XmlInjectionPoint;
+use crate::docprops::DocumentProperties;
 use crate::error::Result;
+use crate::hyperlink::Hyperlink;
 use crate::pagesetup::Orientation;
+use crate::protection::WorkbookProtection;
 use crate::rich_text::{RichText, RunFont};
 use crate::style::StyleRegistry;
+use crate::translate::translate_formula;
 use crate::utils::column_to_letter;
+use crate::workbook::{NonFiniteNumbers, NumberPrecision, SharedStrings};
 use crate::worksheet::{cell_key, decode_cell_key, CellData, SheetVisibility, Worksheet};
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::Writer;
 use rayon::prelude::*;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::{Cursor, Seek, Write};
 use zip::write::{ExtendedFileOptions, FileOptions};
@@ -19,7 +27,7 @@ use zip::ZipWriter;
 /// Returns true for C0 control characters that are illegal in XML 1.0
 /// (everything below 0x20 except tab, line feed, and carriage return).
 #[inline]
-fn is_illegal_xml_char(b: u8) -> bool {
+pub(crate) fn is_illegal_xml_char(b: u8) -> bool {
     b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r')
 }
 
@@ -39,11 +47,16 @@ pub(crate) fn strip_illegal_xml_chars(s: &str) -> std::borrow::Cow<'_, str> {
 }
 
 /// True when `<t>` text needs `xml:space="preserve"`. Without it a conforming
-/// consumer is free to collapse the surrounding whitespace, so `<t>  hi  </t>`
-/// may come back trimmed.
+/// consumer is free to collapse whitespace, so `<t>  hi  </t>` may come back
+/// trimmed and a multi-line value joined onto one line. Besides leading and
+/// trailing whitespace, line breaks, tabs and runs of spaces count as
+/// significant, as they do in openpyxl and Excel's own output.
 #[inline]
 fn needs_space_preserve(s: &str) -> bool {
-    s.starts_with(|c: char| c.is_whitespace()) || s.ends_with(|c: char| c.is_whitespace())
+    s.starts_with(char::is_whitespace)
+        || s.ends_with(char::is_whitespace)
+        || s.contains(['\n', '\r', '\t'])
+        || s.contains("  ")
 }
 
 /// Write an element whose content is user-supplied text: strip the control
@@ -62,23 +75,6 @@ fn write_text_element<W: Write>(writer: &mut Writer<W>, name: &str, text: &str)
     Ok(())
 }
 
-/// Compute the legacy 16-bit Excel sheet-protection password verifier
-/// (CreatePasswordVerifier_Method1 from MS-XLS 2.2.9), as stored in the
-/// `password` attribute of `sheetProtection`.
-pub(crate) fn legacy_password_hash(password: &str) -> u16 {
-    let bytes = password.as_bytes();
-    let mut verifier: u16 = 0;
-    for &b in bytes
-        .iter()
-        .rev()
-        .chain(std::iter::once(&(bytes.len() as u8)))
-    {
-        verifier = ((verifier >> 14) & 0x0001) | ((verifier << 1) & 0x7fff);
-        verifier ^= b as u16;
-    }
-    verifier ^ 0xCE4B
-}
-
 /// Escape XML special characters in text content.
 /// Control characters that are illegal in XML 1.0 are stripped, since
 /// emitting them produces files Excel refuses to open.
@@ -109,34 +105,20 @@ pub fn escape_xml(s: &str) -> std::borrow::Cow<'_, str> {
 /// Uses inline strings instead of shared strings for simplicity.
 #[inline]
 pub fn format_cell_value(buf: &mut String, coord: &str, value: &CellValue) {
+    format_cell_value_with(buf, coord, value, NumberPrecision::default());
+}
+
+/// [`format_cell_value`], writing numbers to `precision`.
+#[inline]
+pub fn format_cell_value_with(
+    buf: &mut String,
+    coord: &str,
+    value: &CellValue,
+    precision: NumberPrecision,
+) {
     match value {
-        CellValue::String(s) => {
-            let escaped = escape_xml(s.as_ref());
-            buf.push_str("<c r=\"");
-            buf.push_str(coord);
-            buf.push_str("\" t=\"inlineStr\"><is>");
-            if needs_space_preserve(&escaped) {
-                buf.push_str("<t xml:space=\"preserve\">");
-            } else {
-                buf.push_str("<t>");
-            }
-            buf.push_str(&escaped);
-            buf.push_str("</t></is></c>");
-        }
-        CellValue::Number(n) => {
-            if !n.is_finite() {
-                // NaN/Infinity are not valid SpreadsheetML numbers; emit an error cell.
-                buf.push_str("<c r=\"");
-                buf.push_str(coord);
-                buf.push_str("\" t=\"e\"><v>#NUM!</v></c>");
-                return;
-            }
-            buf.push_str("<c r=\"");
-            buf.push_str(coord);
-            buf.push_str("\"><v>");
-            buf.push_str(ryu::Buffer::new().format(*n));
-            buf.push_str("</v></c>");
-        }
+        CellValue::String(s) => push_inline_string_cell(buf, coord, s.as_ref()),
+        CellValue::Number(n) => push_number_cell(buf, coord, *n, None, precision),
         CellValue::Boolean(b) => {
             buf.push_str("<c r=\"");
             buf.push_str(coord);
@@ -153,11 +135,11 @@ pub fn format_cell_value(buf: &mut String, coord: &str, value: &CellValue) {
             buf.push_str("</f></c>");
         }
         CellValue::Date(d) => {
-            let escaped = escape_xml(d);
+            // No styles here to give a serial a date format, so ISO text.
             buf.push_str("<c r=\"");
             buf.push_str(coord);
             buf.push_str("\" t=\"d\"><v>");
-            buf.push_str(&escaped);
+            buf.push_str(&d.to_string());
             buf.push_str("</v></c>");
         }
         CellValue::Empty => {
@@ -166,6 +148,47 @@ pub fn format_cell_value(buf: &mut String, coord: &str, value: &CellValue) {
     }
 }
 
+/// Write an inline-string cell (`t="inlineStr"`), as streamed sheets do.
+pub(crate) fn push_inline_string_cell(buf: &mut String, coord: &str, text: &str) {
+    let escaped = escape_xml(text);
+    buf.push_str("<c r=\"");
+    buf.push_str(coord);
+    buf.push_str("\" t=\"inlineStr\"><is>");
+    if needs_space_preserve(&escaped) {
+        buf.push_str("<t xml:space=\"preserve\">");
+    } else {
+        buf.push_str("<t>");
+    }
+    buf.push_str(&escaped);
+    buf.push_str("</t></is></c>");
+}
+
+/// Write a number cell with an optional style index. NaN and infinities are
+/// not valid SpreadsheetML numbers and become a `#NUM!` error cell.
+pub(crate) fn push_number_cell(
+    buf: &mut String,
+    coord: &str,
+    n: f64,
+    style_index: Option<u32>,
+    precision: NumberPrecision,
+) {
+    buf.push_str("<c r=\"");
+    buf.push_str(coord);
+    buf.push('"');
+    if let Some(style) = style_index {
+        buf.push_str(" s=\"");
+        buf.push_str(itoa::Buffer::new().format(style));
+        buf.push('"');
+    }
+    if !n.is_finite() {
+        buf.push_str(" t=\"e\"><v>#NUM!</v></c>");
+        return;
+    }
+    buf.push_str("><v>");
+    push_number(buf, n, precision);
+    buf.push_str("</v></c>");
+}
+
 /// Write `<c r="A1" s="3"` -- every cell starts this way. The closing bracket
 /// is left to the caller, which may still need a `t` attribute.
 #[inline]
@@ -233,11 +256,178 @@ fn write_run_props(buf: &mut String, font: &RunFont) {
     buf.push_str("</rPr>");
 }
 
+/// How a formula cell is written as part of a shared formula group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SharedFormula {
+    /// The group's first cell, which holds the formula text for the group
+    /// down to `last_row`.
+    Anchor { si: u32, last_row: u32 },
+    /// A later cell of group `si`, written without formula text.
+    Member { si: u32 },
+}
+
+/// Runs of two or more formula cells down a column in which every cell
+/// holds the run's first formula moved down by its offset, as filling a
+/// formula down leaves them. Each run becomes one shared formula group.
+pub(crate) fn shared_formula_groups(worksheet: &Worksheet) -> HashMap<u64, SharedFormula> {
+    let mut formulas: Vec<(u32, u32, &str)> = worksheet
+        .cells
+        .iter()
+        .filter_map(|(&key, cell)| match &cell.value {
+            CellValue::Formula(f) => {
+                let (row, col) = decode_cell_key(key);
+                Some((col, row, f.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+    formulas.sort_unstable_by_key(|&(col, row, _)| (col, row));
+
+    let mut groups = HashMap::new();
+    let mut si = 0;
+    let mut start = 0;
+    while start < formulas.len() {
+        let (col, anchor_row, anchor) = formulas[start];
+        let mut end = start + 1;
+        while let Some(&(c, r, f)) = formulas.get(end) {
+            let offset = (end - start) as u32;
+            if c != col
+                || r != anchor_row + offset
+                || f != translate_formula(anchor, offset as i64, 0)
+            {
+                break;
+            }
+            end += 1;
+        }
+        if end - start >= 2 {
+            let last_row = formulas[end - 1].1;
+            groups.insert(
+                cell_key(anchor_row, col),
+                SharedFormula::Anchor { si, last_row },
+            );
+            for &(_, row, _) in &formulas[start + 1..end] {
+                groups.insert(cell_key(row, col), SharedFormula::Member { si });
+            }
+            si += 1;
+        }
+        start = end;
+    }
+    groups
+}
+
+/// Write `n` as a cell value: integral values without a trailing ".0", and
+/// anything else in ryu's shortest round-tripping form, after rounding to 15
+/// significant digits under [`NumberPrecision::Excel`].
+#[inline]
+pub(crate) fn push_number(buf: &mut String, n: f64, precision: NumberPrecision) {
+    let n = match precision {
+        NumberPrecision::Shortest => n,
+        NumberPrecision::Excel => round_to_excel_precision(n),
+    };
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        buf.push_str(itoa::Buffer::new().format(n as i64));
+    } else {
+        buf.push_str(ryu::Buffer::new().format(n));
+    }
+}
+
+/// `n` rounded to 15 significant digits. Formatting to 15 digits and parsing
+/// back is exact rounding, where the arithmetic alternatives drift for large
+/// and tiny exponents; the digits go through a stack buffer since this runs
+/// for every number saved.
+fn round_to_excel_precision(n: f64) -> f64 {
+    // Integers below 1e15 already fit in 15 digits.
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        return n;
+    }
+    struct Digits {
+        bytes: [u8; 32],
+        len: usize,
+    }
+    impl std::fmt::Write for Digits {
+        fn write_str(&mut self, s: &str) -> std::fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(std::fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+    let mut digits = Digits {
+        bytes: [0; 32],
+        len: 0,
+    };
+    if std::fmt::Write::write_fmt(&mut digits, format_args!("{:.14e}", n)).is_err() {
+        return n;
+    }
+    // Near f64::MAX rounding up would overflow to infinity.
+    std::str::from_utf8(&digits.bytes[..digits.len])
+        .ok()
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|rounded| rounded.is_finite())
+        .unwrap_or(n)
+}
+
+/// Open a `<row>` element with the row's height, visibility and outline
+/// attributes.
+#[inline]
+fn push_row_start(
+    buf: &mut String,
+    itoa_buf: &mut itoa::Buffer,
+    ryu_buf: &mut ryu::Buffer,
+    worksheet: &Worksheet,
+    row_num: u32,
+) {
+    buf.push_str("<row r=\"");
+    buf.push_str(itoa_buf.format(row_num));
+    buf.push('"');
+    if let Some(height) = worksheet.row_dimensions.get(&row_num) {
+        buf.push_str(" ht=\"");
+        buf.push_str(ryu_buf.format(*height));
+        buf.push_str("\" customHeight=\"1\"");
+    }
+    if let Some(props) = worksheet.row_props.get(&row_num) {
+        if props.hidden {
+            buf.push_str(" hidden=\"1\"");
+        }
+        if props.outline_level > 0 {
+            buf.push_str(" outlineLevel=\"");
+            buf.push_str(itoa_buf.format(props.outline_level));
+            buf.push('"');
+        }
+        if props.collapsed {
+            buf.push_str(" collapsed=\"1\"");
+        }
+    }
+    buf.push('>');
+}
+
+/// A cell queued for writing: a cell map entry, or a bare value a columnar
+/// sheet holds in a column.
+#[derive(Clone, Copy)]
+enum RowCell<'a> {
+    Data(&'a CellData),
+    Value(&'a CellValue),
+}
+
+impl<'a> RowCell<'a> {
+    /// The cell to write; a column value gets a plain cell around it.
+    fn data(self) -> Cow<'a, CellData> {
+        match self {
+            RowCell::Data(data) => Cow::Borrowed(data),
+            RowCell::Value(value) => Cow::Owned(CellData::with_value(value.clone())),
+        }
+    }
+}
+
 /// Write cell data directly to a string buffer (fast path, no quick_xml overhead).
 /// Uses itoa/ryu for fast number formatting. The coordinate and style attribute
 /// go straight into the buffer: building them as owned Strings first cost three
 /// heap allocations per cell, which dominates on a million-cell sheet.
 #[inline]
+#[allow(clippy::too_many_arguments)]
 fn write_cell_direct(
     buf: &mut String,
     row: u32,
@@ -245,6 +435,10 @@ fn write_cell_direct(
     cell_data: &CellData,
     style_index: Option<u32>,
     shared_string_map: &HashMap<InternedString, usize>,
+    date1904: bool,
+    non_finite: NonFiniteNumbers,
+    precision: NumberPrecision,
+    shared: Option<SharedFormula>,
 ) {
     match &cell_data.value {
         CellValue::String(s) => {
@@ -277,15 +471,20 @@ fn write_cell_direct(
         }
         CellValue::Number(n) => {
             if !n.is_finite() {
-                // NaN/Infinity are not valid SpreadsheetML numbers; emit an error cell.
+                // NaN/Infinity are not valid SpreadsheetML numbers. The save
+                // has already failed if the policy is Error.
                 write_cell_open(buf, row, col, style_index);
-                buf.push_str(" t=\"e\"><v>#NUM!</v></c>");
+                if non_finite == NonFiniteNumbers::Empty {
+                    buf.push_str("/>");
+                } else {
+                    buf.push_str(" t=\"e\"><v>#NUM!</v></c>");
+                }
                 return;
             }
             // Use ryu for fast float formatting
             write_cell_open(buf, row, col, style_index);
             buf.push_str("><v>");
-            buf.push_str(ryu::Buffer::new().format(*n));
+            push_number(buf, *n, precision);
             buf.push_str("</v></c>");
         }
         CellValue::Boolean(b) => {
@@ -307,9 +506,30 @@ fn write_cell_direct(
                     }
                 }
             }
-            buf.push_str("><f>");
-            buf.push_str(&escaped);
-            buf.push_str("</f>");
+            match shared {
+                Some(SharedFormula::Anchor { si, last_row }) => {
+                    buf.push_str("><f t=\"shared\" ref=\"");
+                    crate::utils::push_coordinate(buf, row, col);
+                    buf.push(':');
+                    crate::utils::push_coordinate(buf, last_row, col);
+                    buf.push_str("\" si=\"");
+                    buf.push_str(itoa::Buffer::new().format(si));
+                    buf.push_str("\">");
+                    buf.push_str(&escaped);
+                    buf.push_str("</f>");
+                }
+                // Members carry only the group; readers translate the anchor.
+                Some(SharedFormula::Member { si }) => {
+                    buf.push_str("><f t=\"shared\" si=\"");
+                    buf.push_str(itoa::Buffer::new().format(si));
+                    buf.push_str("\"/>");
+                }
+                None => {
+                    buf.push_str("><f>");
+                    buf.push_str(&escaped);
+                    buf.push_str("</f>");
+                }
+            }
             if let Some(ref cached) = cell_data.cached_formula_value {
                 buf.push_str("<v>");
                 buf.push_str(&escape_xml(cached));
@@ -318,10 +538,11 @@ fn write_cell_direct(
             buf.push_str("</c>");
         }
         CellValue::Date(d) => {
-            let escaped = escape_xml(d);
+            // A serial in the workbook's date system, as Excel writes dates;
+            // the number format makes it display as one.
             write_cell_open(buf, row, col, style_index);
-            buf.push_str(" t=\"d\"><v>");
-            buf.push_str(&escaped);
+            buf.push_str("><v>");
+            push_number(buf, d.to_excel(date1904), precision);
             buf.push_str("</v></c>");
         }
         CellValue::Empty => {
@@ -339,7 +560,8 @@ fn write_cell_direct(
 pub fn write_content_types<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
     options: &FileOptions<'static, ExtendedFileOptions>,
-    sheet_count: usize,
+    workbook_content_type: &str,
+    part_overrides: &[(String, String)],
     has_shared_strings: bool,
     comment_sheet_ids: &[u32],
     table_count: usize,
@@ -405,20 +627,17 @@ pub fn write_content_types<W: Write + Seek>(
     // Overrides
     let mut override1 = BytesStart::new("Override");
     override1.push_attribute(("PartName", "/xl/workbook.xml"));
-    override1.push_attribute((
-        "ContentType",
-        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml",
-    ));
+    override1.push_attribute(("ContentType", workbook_content_type));
     writer.write_event(quick_xml::events::Event::Empty(override1))?;
 
-    for i in 1..=sheet_count {
-        let part_name = format!("/xl/worksheets/sheet{}.xml", i);
+    // Parts as (package path, content type): worksheets, any preserved
+    // dialog/macro sheets and the parts they reference, and preserved
+    // workbook-level parts.
+    for (path, content_type) in part_overrides {
+        let part_name = format!("/{}", path);
         let mut override_elem = BytesStart::new("Override");
         override_elem.push_attribute(("PartName", part_name.as_str()));
-        override_elem.push_attribute((
-            "ContentType",
-            "application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml",
-        ));
+        override_elem.push_attribute(("ContentType", content_type.as_str()));
         writer.write_event(quick_xml::events::Event::Empty(override_elem))?;
     }
 
@@ -533,21 +752,15 @@ pub fn write_rels<W: Write + Seek>(
 pub fn write_doc_props<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
     options: &FileOptions<'static, ExtendedFileOptions>,
+    props: &DocumentProperties,
 ) -> Result<()> {
     // Write docProps/core.xml
     zip.start_file("docProps/core.xml", options.clone())?;
-    let core_xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:dcmitype="http://purl.org/dc/dcmitype/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
-</cp:coreProperties>"#;
-    zip.write_all(core_xml.as_bytes())?;
+    zip.write_all(props.core_xml(crate::docprops::now()).as_bytes())?;
 
     // Write docProps/app.xml
     zip.start_file("docProps/app.xml", options.clone())?;
-    let app_xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
-<Application>RustyPyXL</Application>
-</Properties>"#;
-    zip.write_all(app_xml.as_bytes())?;
+    zip.write_all(props.app_xml().as_bytes())?;
 
     Ok(())
 }
@@ -560,6 +773,9 @@ pub fn write_workbook_xml<W: Write + Seek>(
     named_ranges: &[crate::workbook::NamedRange],
     active_tab: usize,
     date1904: bool,
+    protection: Option<&WorkbookProtection>,
+    full_calc_on_load: bool,
+    external_references_xml: Option<&str>,
     pivot_caches_xml: Option<&str>,
 ) -> Result<()> {
     zip.start_file("xl/workbook.xml", options.clone())?;
@@ -583,6 +799,11 @@ pub fn write_workbook_xml<W: Write + Seek>(
     }
     writer.write_event(quick_xml::events::Event::Empty(workbook_pr))?;
 
+    // workbookProtection (after workbookPr per CT_Workbook)
+    if let Some(protection) = protection {
+        writer.write_event(quick_xml::events::Event::Empty(protection.to_element()))?;
+    }
+
     // bookViews
     writer.write_event(quick_xml::events::Event::Start(BytesStart::new(
         "bookViews",
@@ -595,7 +816,18 @@ pub fn write_workbook_xml<W: Write + Seek>(
     view.push_attribute(("showSheetTabs", "1"));
     view.push_attribute(("tabRatio", "600"));
     view.push_attribute(("firstSheet", "0"));
-    let active_tab = active_tab.min(sheets.len().saturating_sub(1));
+    // Excel opens on the active tab, so a hidden one falls back to the first
+    // visible sheet.
+    let is_visible = |idx: usize| {
+        sheets
+            .get(idx)
+            .is_some_and(|(_, visibility)| *visibility == SheetVisibility::Visible)
+    };
+    let active_tab = if is_visible(active_tab) {
+        active_tab
+    } else {
+        (0..sheets.len()).find(|&idx| is_visible(idx)).unwrap_or(0)
+    };
     view.push_attribute(("activeTab", active_tab.to_string().as_str()));
     writer.write_event(quick_xml::events::Event::Empty(view))?;
     writer.write_event(quick_xml::events::Event::End(BytesEnd::new("bookViews")))?;
@@ -614,6 +846,11 @@ pub fn write_workbook_xml<W: Write + Seek>(
     }
     writer.write_event(quick_xml::events::Event::End(BytesEnd::new("sheets")))?;
 
+    // externalReferences (preserved verbatim) follows sheets in the schema.
+    if let Some(refs) = external_references_xml {
+        writer.get_mut().write_all(refs.as_bytes())?;
+    }
+
     // definedNames (named ranges), preserving sheet scope and visibility
     if !named_ranges.is_empty() {
         writer.write_event(quick_xml::events::Event::Start(BytesStart::new(
@@ -625,6 +862,9 @@ pub fn write_workbook_xml<W: Write + Seek>(
             if let Some(sheet_id) = nr.local_sheet_id {
                 defined_name.push_attribute(("localSheetId", sheet_id.to_string().as_str()));
             }
+            if let Some(comment) = &nr.comment {
+                defined_name.push_attribute(("comment", strip_illegal_xml_chars(comment).as_ref()));
+            }
             if nr.hidden {
                 defined_name.push_attribute(("hidden", "1"));
             }
@@ -637,6 +877,11 @@ pub fn write_workbook_xml<W: Write + Seek>(
         writer.write_event(quick_xml::events::Event::End(BytesEnd::new("definedNames")))?;
     }
 
+    // calcPr follows definedNames. calcChain.xml is never written (a loaded
+    // one is dropped as stale), so when that or a new formula leaves results
+    // unknown, ask Excel to rebuild the chain and recalculate on open.
+    writer.write_event(quick_xml::events::Event::Empty(calc_pr(full_calc_on_load)))?;
+
     // pivotCaches (preserved verbatim) belongs after definedNames in the schema.
     if let Some(caches) = pivot_caches_xml {
         writer.get_mut().write_all(caches.as_bytes())?;
@@ -649,12 +894,27 @@ pub fn write_workbook_xml<W: Write + Seek>(
     Ok(())
 }
 
+/// Excel's calculation engine version, as written by openpyxl.
+pub(crate) const CALC_ID: &str = "124519";
+
+/// The `<calcPr>` element every saved workbook carries, asking for a full
+/// recalculation on open when `full_calc_on_load` is set.
+pub(crate) fn calc_pr(full_calc_on_load: bool) -> BytesStart<'static> {
+    let mut calc_pr = BytesStart::new("calcPr");
+    calc_pr.push_attribute(("calcId", CALC_ID));
+    if full_calc_on_load {
+        calc_pr.push_attribute(("fullCalcOnLoad", "1"));
+    }
+    calc_pr
+}
+
 pub fn write_workbook_rels<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
     options: &FileOptions<'static, ExtendedFileOptions>,
-    sheet_count: usize,
+    sheet_rels: &[(String, &str)],
     has_shared_strings: bool,
     pivot_cache_rels: &[(String, String)],
+    preserved_rels: &[crate::passthrough::PreservedWorkbookRel],
 ) -> Result<()> {
     zip.start_file("xl/_rels/workbook.xml.rels", options.clone())?;
 
@@ -664,11 +924,15 @@ pub fn write_workbook_rels<W: Write + Seek>(
 "#,
     );
 
-    for i in 1..=sheet_count {
+    // One rel per sheet, as (target relative to xl/, relationship type), in
+    // sheet order so rId{n} matches the n-th <sheet> in workbook.xml.
+    for (i, (target, rel_type)) in sheet_rels.iter().enumerate() {
         content.push_str(&format!(
-            r#"<Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{}.xml"/>
+            r#"<Relationship Id="rId{}" Type="{}" Target="{}"/>
 "#,
-            i, i
+            i + 1,
+            rel_type,
+            target
         ));
     }
 
@@ -691,53 +955,169 @@ pub fn write_workbook_rels<W: Write + Seek>(
         ));
     }
 
+    // Relationships to preserved workbook-level parts (ids already renumbered).
+    for rel in preserved_rels {
+        content.push_str(&format!(
+            r#"<Relationship Id="{}" Type="{}" Target="{}"{}/>
+"#,
+            escape_xml(&rel.id),
+            escape_xml(&rel.rel_type),
+            escape_xml(&rel.target),
+            if rel.external {
+                r#" TargetMode="External""#
+            } else {
+                ""
+            }
+        ));
+    }
+
     content.push_str("</Relationships>");
 
     zip.write_all(content.as_bytes())?;
     Ok(())
 }
 
-/// Returns (ordered list of strings, map from string -> index for O(1) lookup)
 /// Returns the unique strings, the value-to-index map, and the total number of
 /// cells referencing them (the sst `count`, as distinct from `uniqueCount`).
+/// Strings `mode` leaves out of the table are written inline by the sheet
+/// writer, which inlines any string the map does not hold.
 pub fn collect_shared_strings(
     worksheets: &[Worksheet],
+    mode: SharedStrings,
 ) -> (Vec<InternedString>, HashMap<InternedString, usize>, usize) {
+    let min_repeats = match mode {
+        SharedStrings::All => 1,
+        SharedStrings::MinRepeats(n) => n.max(1),
+        SharedStrings::Auto => auto_min_repeats(worksheets),
+        SharedStrings::Incremental => return counted_shared_strings(worksheets),
+        SharedStrings::Inline => usize::MAX,
+    };
+    if min_repeats == usize::MAX {
+        return (Vec::new(), HashMap::new(), 0);
+    }
+
     // Estimate capacity: count string cells across all worksheets
     let estimated_strings: usize = worksheets
         .iter()
         .map(|ws| {
             ws.cells
                 .values()
-                .filter(|c| matches!(c.value, CellValue::String(_)))
+                .map(|c| &c.value)
+                .chain(ws.column_cells().map(|(_, v)| v))
+                .filter(|v| matches!(v, CellValue::String(_)))
                 .count()
         })
         .sum();
 
+    if min_repeats > 1 {
+        let mut counts: HashMap<&InternedString, usize> = HashMap::with_capacity(estimated_strings);
+        let mut order: Vec<&InternedString> = Vec::new();
+        for s in plain_strings(worksheets) {
+            let count = counts.entry(s).or_insert(0);
+            if *count == 0 {
+                order.push(s);
+            }
+            *count += 1;
+        }
+        let mut strings = Vec::new();
+        let mut string_map = HashMap::new();
+        let mut total_refs = 0usize;
+        for s in order {
+            let count = counts[s];
+            if count >= min_repeats {
+                string_map.insert(s.clone(), strings.len());
+                strings.push(s.clone());
+                total_refs += count;
+            }
+        }
+        return (strings, string_map, total_refs);
+    }
+
     let mut strings = Vec::with_capacity(estimated_strings);
     let mut string_map = HashMap::with_capacity(estimated_strings);
     let mut total_refs = 0usize;
 
-    for worksheet in worksheets {
-        for cell_data in worksheet.cells.values() {
-            // Rich-text cells are written inline (they carry per-run formatting a
-            // shared plain string cannot hold), so they never reference the table.
-            if cell_data.rich_text.is_some() {
-                continue;
-            }
-            if let CellValue::String(s) = &cell_data.value {
-                total_refs += 1;
-                if !string_map.contains_key(s) {
-                    string_map.insert(s.clone(), strings.len());
-                    strings.push(s.clone());
-                }
-            }
+    for s in plain_strings(worksheets) {
+        total_refs += 1;
+        if !string_map.contains_key(s) {
+            string_map.insert(s.clone(), strings.len());
+            strings.push(s.clone());
         }
     }
 
     (strings, string_map, total_refs)
 }
 
+/// The table for [`SharedStrings::Incremental`], from each sheet's string
+/// counts. A sheet without counts (its worksheet was swapped in directly)
+/// has its cells read instead.
+fn counted_shared_strings(
+    worksheets: &[Worksheet],
+) -> (Vec<InternedString>, HashMap<InternedString, usize>, usize) {
+    let mut strings = Vec::new();
+    let mut string_map = HashMap::new();
+    let mut total_refs = 0usize;
+    let mut add = |s: &InternedString, count: usize| {
+        total_refs += count;
+        if !string_map.contains_key(s) {
+            string_map.insert(s.clone(), strings.len());
+            strings.push(s.clone());
+        }
+    };
+    for ws in worksheets {
+        match &ws.string_counts {
+            Some(counts) => counts.iter().for_each(|(s, count)| add(s, count)),
+            None => plain_strings(std::slice::from_ref(ws)).for_each(|s| add(s, 1)),
+        }
+    }
+    (strings, string_map, total_refs)
+}
+
+/// Every string cell value that could go in the shared table, in cell map
+/// order, then column order for values a columnar sheet holds in columns.
+/// Rich-text cells are written inline (they carry per-run formatting a
+/// shared plain string cannot hold), so they never reference the table.
+fn plain_strings(worksheets: &[Worksheet]) -> impl Iterator<Item = &InternedString> {
+    worksheets.iter().flat_map(|ws| {
+        ws.cells
+            .values()
+            .filter(|cell| cell.rich_text.is_none())
+            .map(|cell| &cell.value)
+            .chain(ws.column_cells().map(|(_, value)| value))
+            .filter_map(|value| match value {
+                CellValue::String(s) => Some(s),
+                _ => None,
+            })
+    })
+}
+
+/// The `MinRepeats` threshold [`SharedStrings::Auto`] settles on, from the
+/// share of distinct values among the first string cells. The cell map is a
+/// hash map, so those are spread across the sheet rather than its top rows.
+/// `usize::MAX` means inline every string.
+fn auto_min_repeats(worksheets: &[Worksheet]) -> usize {
+    const SAMPLE: usize = 10_000;
+    let mut seen: std::collections::HashSet<&str> = std::collections::HashSet::new();
+    let mut sampled = 0usize;
+    for s in plain_strings(worksheets).take(SAMPLE) {
+        seen.insert(s.as_ref());
+        sampled += 1;
+    }
+    if sampled == 0 {
+        return 1;
+    }
+    let distinct = seen.len() as f64 / sampled as f64;
+    if distinct < 0.5 {
+        // Mostly repeats: the table pays for itself and one pass builds it.
+        1
+    } else if distinct > 0.95 {
+        // Nearly all unique (ids, free text): skip the table entirely.
+        usize::MAX
+    } else {
+        2
+    }
+}
+
 pub fn write_shared_strings<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
     options: &FileOptions<'static, ExtendedFileOptions>,
@@ -801,6 +1181,49 @@ fn write_color_attr(xml: &mut String, element: &str, color: &crate::style::Color
     xml.push_str("/>");
 }
 
+/// The `rgb`/`theme`/`indexed` and `tint` attributes of a color element,
+/// chosen as in [`write_color_attr`].
+fn push_color_attributes(e: &mut BytesStart, color: &crate::style::Color) {
+    if let Some(argb) = color.argb() {
+        e.push_attribute(("rgb", argb.as_str()));
+    } else if let Some(theme) = color.theme {
+        e.push_attribute(("theme", theme.to_string().as_str()));
+    } else if let Some(indexed) = color.indexed {
+        e.push_attribute(("indexed", indexed.to_string().as_str()));
+    }
+    if let Some(tint) = color.tint.filter(|t| *t != 0.0) {
+        e.push_attribute(("tint", tint.to_string().as_str()));
+    }
+}
+
+/// `<sheetView>` attributes; settings at their defaults are left out.
+fn push_sheet_view_attributes(e: &mut BytesStart, view: &crate::sheetview::SheetView) {
+    if !view.show_grid_lines {
+        e.push_attribute(("showGridLines", "0"));
+    }
+    if view.right_to_left {
+        e.push_attribute(("rightToLeft", "1"));
+    }
+    if view.zoom_scale != 100 {
+        e.push_attribute(("zoomScale", view.zoom_scale.to_string().as_str()));
+    }
+    if view.view != crate::sheetview::SheetViewType::Normal {
+        e.push_attribute(("view", view.view.as_str()));
+    }
+    e.push_attribute(("workbookViewId", "0"));
+}
+
+/// `activeCell` and `sqref` of a `<selection>`; the sqref defaults to the
+/// active cell.
+fn push_selection_attributes(
+    e: &mut BytesStart,
+    active_cell: &str,
+    view: &crate::sheetview::SheetView,
+) {
+    e.push_attribute(("activeCell", active_cell));
+    e.push_attribute(("sqref", view.selection.as_deref().unwrap_or(active_cell)));
+}
+
 /// Write a single font element to the XML string.
 fn write_font_xml(xml: &mut String, font: &crate::style::Font) {
     xml.push_str("<font>");
@@ -898,6 +1321,9 @@ fn write_cell_xf_xml(xml: &mut String, xf: &crate::style::CellXf) {
         r#"<xf numFmtId="{}" fontId="{}" fillId="{}" borderId="{}""#,
         xf.num_fmt_id, xf.font_id, xf.fill_id, xf.border_id
     ));
+    if xf.xf_id != 0 {
+        xml.push_str(&format!(r#" xfId="{}""#, xf.xf_id));
+    }
     if xf.apply_font {
         xml.push_str(r#" applyFont="1""#);
     }
@@ -1051,6 +1477,7 @@ pub fn write_styles_xml<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
     options: &FileOptions<'static, ExtendedFileOptions>,
     styles: &StyleRegistry,
+    style_xfs: &[crate::style::CellXf],
     dxfs: &[ConditionalFormat],
 ) -> Result<()> {
     zip.start_file("xl/styles.xml", options.clone())?;
@@ -1103,9 +1530,11 @@ pub fn write_styles_xml<W: Write + Seek>(
     }
     xml.push_str("</borders>");
 
-    // Cell style XFs (just one default)
-    xml.push_str(r#"<cellStyleXfs count="1">"#);
-    xml.push_str(r#"<xf numFmtId="0" fontId="0" fillId="0" borderId="0"/>"#);
+    // Cell style XFs, one per named style
+    xml.push_str(&format!(r#"<cellStyleXfs count="{}">"#, style_xfs.len()));
+    for xf in style_xfs {
+        write_cell_xf_xml(&mut xml, xf);
+    }
     xml.push_str("</cellStyleXfs>");
 
     // Cell XFs
@@ -1115,9 +1544,30 @@ pub fn write_styles_xml<W: Write + Seek>(
     }
     xml.push_str("</cellXfs>");
 
-    // Cell styles (just Normal)
-    xml.push_str(r#"<cellStyles count="1">"#);
-    xml.push_str(r#"<cellStyle name="Normal" xfId="0" builtinId="0"/>"#);
+    // Named cell styles
+    if styles.named_styles.is_empty() {
+        xml.push_str(r#"<cellStyles count="1">"#);
+        xml.push_str(r#"<cellStyle name="Normal" xfId="0" builtinId="0"/>"#);
+    } else {
+        xml.push_str(&format!(
+            r#"<cellStyles count="{}">"#,
+            styles.named_styles.len()
+        ));
+        for (xf_id, named) in styles.named_styles.iter().enumerate() {
+            xml.push_str(&format!(
+                r#"<cellStyle name="{}" xfId="{}""#,
+                escape_xml(&named.name),
+                xf_id
+            ));
+            if let Some(id) = named.builtin_id {
+                xml.push_str(&format!(r#" builtinId="{}""#, id));
+            }
+            if named.hidden {
+                xml.push_str(r#" hidden="1""#);
+            }
+            xml.push_str("/>");
+        }
+    }
     xml.push_str("</cellStyles>");
 
     // Differential formats for conditional formatting (referenced by dxfId)
@@ -1151,18 +1601,18 @@ fn write_border_side(xml: &mut String, name: &str, side: &Option<crate::style::B
     }
 }
 
-/// External (URL) hyperlinks in deterministic cell order. The position in
-/// this list defines the relationship id (`rIdHL{i+1}`) shared between the
+/// External hyperlinks in deterministic cell order. The position in this
+/// list defines the relationship id (`rIdHL{i+1}`) shared between the
 /// worksheet XML and its .rels part, so both must derive it from here.
-pub fn collect_external_hyperlinks(worksheet: &Worksheet) -> Vec<((u32, u32), String)> {
-    let mut links: Vec<((u32, u32), String)> = worksheet
+pub fn collect_external_hyperlinks(worksheet: &Worksheet) -> Vec<((u32, u32), &Hyperlink)> {
+    let mut links: Vec<((u32, u32), &Hyperlink)> = worksheet
         .cells
         .iter()
         .filter_map(|(key, cd)| {
             cd.hyperlink
-                .as_ref()
-                .filter(|url| !url.starts_with('#'))
-                .map(|url| (decode_cell_key(*key), url.clone()))
+                .as_deref()
+                .filter(|link| link.is_external)
+                .map(|link| (decode_cell_key(*key), link))
         })
         .collect();
     links.sort_by_key(|(coord, _)| *coord);
@@ -1181,12 +1631,16 @@ pub fn write_worksheet_xml<W: Write + Seek>(
     has_comments: bool,
     style_overrides: &HashMap<u64, u32>,
     drawing_rel_id: Option<&str>,
+    date1904: bool,
+    non_finite: NonFiniteNumbers,
+    precision: NumberPrecision,
+    share_formulas: bool,
 ) -> Result<()> {
     let path = format!("xl/worksheets/sheet{}.xml", sheet_id);
     zip.start_file(&path, options.clone())?;
 
     // Pre-allocate buffer based on estimated size (rough estimate: 100 bytes per cell)
-    let estimated_size = worksheet.cells.len() * 100;
+    let estimated_size = worksheet.cell_count() * 100;
     let mut writer = Writer::new(Cursor::new(Vec::with_capacity(estimated_size)));
     let mut worksheet_start = BytesStart::new("worksheet");
     worksheet_start.push_attribute((
@@ -1201,13 +1655,31 @@ pub fn write_worksheet_xml<W: Write + Seek>(
 
     // sheetPr
     writer.write_event(quick_xml::events::Event::Start(BytesStart::new("sheetPr")))?;
+    if let Some(ref color) = worksheet.sheet_properties.tab_color {
+        let mut tab_color = BytesStart::new("tabColor");
+        push_color_attributes(&mut tab_color, color);
+        writer.write_event(quick_xml::events::Event::Empty(tab_color))?;
+    }
     let mut outline = BytesStart::new("outlinePr");
-    outline.push_attribute(("summaryBelow", "1"));
-    outline.push_attribute(("summaryRight", "1"));
+    let flag = |on: bool| if on { "1" } else { "0" };
+    outline.push_attribute((
+        "summaryBelow",
+        flag(worksheet.sheet_properties.summary_below),
+    ));
+    outline.push_attribute((
+        "summaryRight",
+        flag(worksheet.sheet_properties.summary_right),
+    ));
     writer.write_event(quick_xml::events::Event::Empty(outline))?;
-    writer.write_event(quick_xml::events::Event::Empty(BytesStart::new(
-        "pageSetUpPr",
-    )))?;
+    let mut page_setup_pr = BytesStart::new("pageSetUpPr");
+    if worksheet
+        .page_setup
+        .as_ref()
+        .is_some_and(|ps| ps.fits_to_page())
+    {
+        page_setup_pr.push_attribute(("fitToPage", "1"));
+    }
+    writer.write_event(quick_xml::events::Event::Empty(page_setup_pr))?;
     writer.write_event(quick_xml::events::Event::End(BytesEnd::new("sheetPr")))?;
 
     // dimension (if we have cells)
@@ -1236,6 +1708,7 @@ pub fn write_worksheet_xml<W: Write + Seek>(
                 .map(|(row, col)| (cell, row, col))
         })
         .filter(|&(_, row, col)| row > 1 || col > 1);
+    let view_settings = &worksheet.sheet_view;
     if let Some((cell, row, col)) = frozen {
         let x_split = col - 1;
         let y_split = row - 1;
@@ -1246,8 +1719,21 @@ pub fn write_worksheet_xml<W: Write + Seek>(
         } else {
             "topRight"
         };
+        // The scrollable pane starts at the freeze cell unless scrolled
+        // further; it cannot start inside the frozen rows or columns.
+        let top_left = match view_settings
+            .top_left_cell
+            .as_deref()
+            .and_then(|c| crate::utils::parse_coordinate(c).ok())
+        {
+            Some((top, left)) => crate::utils::coordinate_from_row_col(
+                if y_split > 0 { top.max(row) } else { top },
+                if x_split > 0 { left.max(col) } else { left },
+            ),
+            None => cell.to_string(),
+        };
         let mut view = BytesStart::new("sheetView");
-        view.push_attribute(("workbookViewId", "0"));
+        push_sheet_view_attributes(&mut view, view_settings);
         writer.write_event(quick_xml::events::Event::Start(view))?;
         let mut pane = BytesStart::new("pane");
         if x_split > 0 {
@@ -1256,20 +1742,40 @@ pub fn write_worksheet_xml<W: Write + Seek>(
         if y_split > 0 {
             pane.push_attribute(("ySplit", y_split.to_string().as_str()));
         }
-        pane.push_attribute(("topLeftCell", cell));
+        pane.push_attribute(("topLeftCell", top_left.as_str()));
         pane.push_attribute(("activePane", active_pane));
         pane.push_attribute(("state", "frozen"));
         writer.write_event(quick_xml::events::Event::Empty(pane))?;
+        let active_cell = view_settings.active_cell.as_deref().unwrap_or(cell);
         let mut selection = BytesStart::new("selection");
         selection.push_attribute(("pane", active_pane));
-        selection.push_attribute(("activeCell", cell));
-        selection.push_attribute(("sqref", cell));
+        push_selection_attributes(&mut selection, active_cell, view_settings);
         writer.write_event(quick_xml::events::Event::Empty(selection))?;
         writer.write_event(quick_xml::events::Event::End(BytesEnd::new("sheetView")))?;
     } else {
         let mut view = BytesStart::new("sheetView");
-        view.push_attribute(("workbookViewId", "0"));
-        writer.write_event(quick_xml::events::Event::Empty(view))?;
+        push_sheet_view_attributes(&mut view, view_settings);
+        if let Some(ref top_left) = view_settings.top_left_cell {
+            view.push_attribute(("topLeftCell", top_left.as_str()));
+        }
+        let active_cell = view_settings.active_cell.as_deref().or(view_settings
+            .selection
+            .as_deref()
+            .map(|sqref| {
+                // Default the cursor to the first selected cell
+                let first = sqref.split_whitespace().next().unwrap_or(sqref);
+                first.split(':').next().unwrap_or(first)
+            }));
+        match active_cell {
+            Some(active_cell) => {
+                writer.write_event(quick_xml::events::Event::Start(view))?;
+                let mut selection = BytesStart::new("selection");
+                push_selection_attributes(&mut selection, active_cell, view_settings);
+                writer.write_event(quick_xml::events::Event::Empty(selection))?;
+                writer.write_event(quick_xml::events::Event::End(BytesEnd::new("sheetView")))?;
+            }
+            None => writer.write_event(quick_xml::events::Event::Empty(view))?,
+        }
     }
     writer.write_event(quick_xml::events::Event::End(BytesEnd::new("sheetViews")))?;
 
@@ -1277,17 +1783,49 @@ pub fn write_worksheet_xml<W: Write + Seek>(
     let mut format_pr = BytesStart::new("sheetFormatPr");
     format_pr.push_attribute(("baseColWidth", "8"));
     format_pr.push_attribute(("defaultRowHeight", "15"));
+    let outline_rows = worksheet.max_row_outline_level();
+    if outline_rows > 0 {
+        format_pr.push_attribute(("outlineLevelRow", outline_rows.to_string().as_str()));
+    }
+    let outline_cols = worksheet.max_column_outline_level();
+    if outline_cols > 0 {
+        format_pr.push_attribute(("outlineLevelCol", outline_cols.to_string().as_str()));
+    }
     writer.write_event(quick_xml::events::Event::Empty(format_pr))?;
 
-    // cols (column dimensions)
-    if !worksheet.column_dimensions.is_empty() {
+    // cols (column widths and grouping), one element per run of columns
+    // that share both
+    let column_runs: Vec<_> = worksheet
+        .column_dimensions
+        .overlay(&worksheet.column_props, 1, u32::MAX)
+        .into_iter()
+        .filter(|(_, _, width, props)| width.is_some() || props.is_some())
+        .collect();
+    if !column_runs.is_empty() {
         writer.write_event(quick_xml::events::Event::Start(BytesStart::new("cols")))?;
-        for (&col, &width) in &worksheet.column_dimensions {
+        for (min, max, width, props) in column_runs {
             let mut col_elem = BytesStart::new("col");
-            col_elem.push_attribute(("min", col.to_string().as_str()));
-            col_elem.push_attribute(("max", col.to_string().as_str()));
-            col_elem.push_attribute(("width", width.to_string().as_str()));
-            col_elem.push_attribute(("customWidth", "1"));
+            col_elem.push_attribute(("min", min.to_string().as_str()));
+            col_elem.push_attribute(("max", max.to_string().as_str()));
+            if let Some(width) = width {
+                col_elem.push_attribute(("width", width.to_string().as_str()));
+                col_elem.push_attribute(("customWidth", "1"));
+            }
+            if let Some(props) = props {
+                if props.hidden {
+                    col_elem.push_attribute(("hidden", "1"));
+                }
+                if props.outline_level > 0 {
+                    col_elem
+                        .push_attribute(("outlineLevel", props.outline_level.to_string().as_str()));
+                }
+                if props.collapsed {
+                    col_elem.push_attribute(("collapsed", "1"));
+                }
+                if props.best_fit {
+                    col_elem.push_attribute(("bestFit", "1"));
+                }
+            }
             writer.write_event(quick_xml::events::Event::Empty(col_elem))?;
         }
         writer.write_event(quick_xml::events::Event::End(BytesEnd::new("cols")))?;
@@ -1301,12 +1839,29 @@ pub fn write_worksheet_xml<W: Write + Seek>(
     // Group cells by row. Size by the populated-cell count, not max_row: a
     // sparse sheet with one cell at row 1,000,000 would otherwise reserve a
     // million buckets for a handful of entries.
-    let estimated_rows = (worksheet.max_row as usize).min(worksheet.cells.len());
-    type RowCells<'a> = HashMap<u32, Vec<((u32, u32), &'a CellData)>>;
+    let estimated_rows = (worksheet.max_row as usize).min(worksheet.cell_count());
+    let shared_formulas = if share_formulas {
+        shared_formula_groups(worksheet)
+    } else {
+        HashMap::new()
+    };
+
+    type RowCells<'a> = HashMap<u32, Vec<((u32, u32), RowCell<'a>)>>;
     let mut rows: RowCells = HashMap::with_capacity(estimated_rows);
     for (key, cell_data) in &worksheet.cells {
         let (row, col) = decode_cell_key(*key);
-        rows.entry(row).or_default().push(((row, col), cell_data));
+        rows.entry(row)
+            .or_default()
+            .push(((row, col), RowCell::Data(cell_data)));
+    }
+    for ((row, col), value) in worksheet.column_cells() {
+        rows.entry(row)
+            .or_default()
+            .push(((row, col), RowCell::Value(value)));
+    }
+    // Grouped or hidden rows need their <row> element even without cells
+    for &row in worksheet.row_props.keys() {
+        rows.entry(row).or_default();
     }
 
     // Write rows in order
@@ -1336,30 +1891,26 @@ pub fn write_worksheet_xml<W: Write + Seek>(
                     sorted_cells.sort_by_key(|((_, col), _)| *col);
 
                     // Write row start
-                    if let Some(height) = worksheet.row_dimensions.get(&row_num) {
-                        buf.push_str("<row r=\"");
-                        buf.push_str(itoa_buf.format(row_num));
-                        buf.push_str("\" ht=\"");
-                        buf.push_str(ryu_buf.format(*height));
-                        buf.push_str("\" customHeight=\"1\">");
-                    } else {
-                        buf.push_str("<row r=\"");
-                        buf.push_str(itoa_buf.format(row_num));
-                        buf.push_str("\">");
-                    }
+                    push_row_start(&mut buf, &mut itoa_buf, &mut ryu_buf, worksheet, row_num);
 
                     // Write cells
-                    for &((row, col), cell_data) in &sorted_cells {
-                        let style_index = cell_data
-                            .style_index
-                            .or_else(|| style_overrides.get(&cell_key(row, col)).copied());
+                    for &((row, col), cell) in &sorted_cells {
+                        let cell_data = cell.data();
+                        let style_index = style_overrides
+                            .get(&cell_key(row, col))
+                            .copied()
+                            .or(cell_data.style_index);
                         write_cell_direct(
                             &mut buf,
                             row,
                             col,
-                            cell_data,
+                            &cell_data,
                             style_index,
                             shared_string_map,
+                            date1904,
+                            non_finite,
+                            precision,
+                            shared_formulas.get(&cell_key(row, col)).copied(),
                         );
                     }
 
@@ -1378,7 +1929,7 @@ pub fn write_worksheet_xml<W: Write + Seek>(
         result
     } else {
         // For small worksheets, use sequential processing (less overhead)
-        let mut buf = String::with_capacity(worksheet.cells.len() * 40);
+        let mut buf = String::with_capacity(worksheet.cell_count() * 40);
         let mut itoa_buf = itoa::Buffer::new();
         let mut ryu_buf = ryu::Buffer::new();
 
@@ -1386,29 +1937,25 @@ pub fn write_worksheet_xml<W: Write + Seek>(
             let cells = rows.get_mut(&row_num).unwrap();
             cells.sort_by_key(|((_, col), _)| *col);
 
-            if let Some(height) = worksheet.row_dimensions.get(&row_num) {
-                buf.push_str("<row r=\"");
-                buf.push_str(itoa_buf.format(row_num));
-                buf.push_str("\" ht=\"");
-                buf.push_str(ryu_buf.format(*height));
-                buf.push_str("\" customHeight=\"1\">");
-            } else {
-                buf.push_str("<row r=\"");
-                buf.push_str(itoa_buf.format(row_num));
-                buf.push_str("\">");
-            }
+            push_row_start(&mut buf, &mut itoa_buf, &mut ryu_buf, worksheet, row_num);
 
-            for &((row, col), cell_data) in cells.iter() {
-                let style_index = cell_data
-                    .style_index
-                    .or_else(|| style_overrides.get(&cell_key(row, col)).copied());
+            for &((row, col), cell) in cells.iter() {
+                let cell_data = cell.data();
+                let style_index = style_overrides
+                    .get(&cell_key(row, col))
+                    .copied()
+                    .or(cell_data.style_index);
                 write_cell_direct(
                     &mut buf,
                     row,
                     col,
-                    cell_data,
+                    &cell_data,
                     style_index,
                     shared_string_map,
+                    date1904,
+                    non_finite,
+                    precision,
+                    shared_formulas.get(&cell_key(row, col)).copied(),
                 );
             }
 
@@ -1421,6 +1968,9 @@ pub fn write_worksheet_xml<W: Write + Seek>(
     writer.get_mut().write_all(cell_buf.as_bytes())?;
 
     writer.write_event(Event::End(BytesEnd::new("sheetData")))?;
+    for fragment in worksheet.xml_fragments_at(XmlInjectionPoint::AfterSheetData) {
+        writer.get_mut().write_all(fragment.as_bytes())?;
+    }
 
     // sheetProtection (per CT_Worksheet schema order: directly after sheetData)
     if let Some(ref protection) = worksheet.protection {
@@ -1484,12 +2034,12 @@ pub fn write_worksheet_xml<W: Write + Seek>(
                 .push_attribute(("objects", if protection.objects { "1" } else { "0" }));
             sheet_protection
                 .push_attribute(("scenarios", if protection.scenarios { "1" } else { "0" }));
-            // The password attribute holds the legacy 16-bit verifier hash, never
-            // the plaintext. A value loaded from an existing file is already hashed.
-            if let Some(ref hash) = protection.password_hash {
-                sheet_protection.push_attribute(("password", hash.as_str()));
-            } else if let Some(ref pwd) = protection.password {
-                let hash = format!("{:04X}", legacy_password_hash(pwd));
+            // Only verifiers are written, never the plaintext: the iterated
+            // hash when there is one, else the legacy 16-bit hash. A value
+            // loaded from an existing file is already hashed.
+            if let Some(ref hash) = protection.hash {
+                hash.push_attributes(&mut sheet_protection, "");
+            } else if let Some(hash) = protection.legacy_verifier() {
                 sheet_protection.push_attribute(("password", hash.as_str()));
             }
             writer.write_event(quick_xml::events::Event::Empty(sheet_protection))?;
@@ -1530,7 +2080,9 @@ pub fn write_worksheet_xml<W: Write + Seek>(
         ));
         writer.write_event(quick_xml::events::Event::Start(data_validations))?;
 
-        for ((row, col), validation) in &worksheet.data_validations {
+        let mut validations: Vec<_> = worksheet.data_validations.iter().collect();
+        validations.sort_unstable_by_key(|((row, col), _)| (*row, *col));
+        for ((row, col), validation) in validations {
             let coord = format!("{}{}", column_to_letter(*col), row);
             let mut dv = BytesStart::new("dataValidation");
             dv.push_attribute((
@@ -1549,6 +2101,9 @@ pub fn write_worksheet_xml<W: Write + Seek>(
                 "showInputMessage",
                 if validation.show_input { "1" } else { "0" },
             ));
+            if validation.hide_dropdown {
+                dv.push_attribute(("showDropDown", "1"));
+            }
             // The dialog text: without these, showErrorMessage="1" only gets
             // Excel's generic message, and the rule's own wording is lost.
             if let Some(ref style) = validation.error_style {
@@ -1588,17 +2143,17 @@ pub fn write_worksheet_xml<W: Write + Seek>(
         )))?;
     }
 
-    // hyperlinks: external URLs reference the sheet rels via r:id; internal
-    // links (stored with a '#' prefix) use the location attribute.
+    // hyperlinks: external links reference the sheet rels via r:id; internal
+    // links use the location attribute.
     let external_links = collect_external_hyperlinks(worksheet);
-    let mut internal_links: Vec<((u32, u32), String)> = worksheet
+    let mut internal_links: Vec<((u32, u32), &Hyperlink)> = worksheet
         .cells
         .iter()
         .filter_map(|(key, cd)| {
             cd.hyperlink
-                .as_ref()
-                .filter(|url| url.starts_with('#'))
-                .map(|url| (decode_cell_key(*key), url.clone()))
+                .as_deref()
+                .filter(|link| !link.is_external)
+                .map(|link| (decode_cell_key(*key), link))
         })
         .collect();
     internal_links.sort_by_key(|(coord, _)| *coord);
@@ -1608,24 +2163,26 @@ pub fn write_worksheet_xml<W: Write + Seek>(
         let hyperlinks = BytesStart::new("hyperlinks");
         writer.write_event(quick_xml::events::Event::Start(hyperlinks))?;
 
-        for (i, ((row, col), _url)) in external_links.iter().enumerate() {
+        let rel_ids = (1..=external_links.len()).map(|i| Some(format!("rIdHL{}", i)));
+        let links = external_links
+            .iter()
+            .zip(rel_ids)
+            .chain(internal_links.iter().zip(std::iter::repeat(None)));
+        for (((row, col), link), rel_id) in links {
             let coord = format!("{}{}", column_to_letter(*col), row);
-            let rel_id = format!("rIdHL{}", i + 1);
             let mut hyperlink = BytesStart::new("hyperlink");
             hyperlink.push_attribute(("ref", coord.as_str()));
-            hyperlink.push_attribute(("r:id", rel_id.as_str()));
-            writer.write_event(quick_xml::events::Event::Empty(hyperlink))?;
-        }
-
-        for ((row, col), url) in internal_links {
-            let coord = format!("{}{}", column_to_letter(col), row);
-            let mut hyperlink = BytesStart::new("hyperlink");
-            hyperlink.push_attribute(("ref", coord.as_str()));
-            // The location attribute holds the anchor without the '#' prefix
-            hyperlink.push_attribute((
-                "location",
-                strip_illegal_xml_chars(url.trim_start_matches('#')).as_ref(),
-            ));
+            match rel_id {
+                Some(rel_id) => hyperlink.push_attribute(("r:id", rel_id.as_str())),
+                None => hyperlink
+                    .push_attribute(("location", strip_illegal_xml_chars(&link.target).as_ref())),
+            }
+            if let Some(ref tooltip) = link.tooltip {
+                hyperlink.push_attribute(("tooltip", strip_illegal_xml_chars(tooltip).as_ref()));
+            }
+            if let Some(ref display) = link.display {
+                hyperlink.push_attribute(("display", strip_illegal_xml_chars(display).as_ref()));
+            }
             writer.write_event(quick_xml::events::Event::Empty(hyperlink))?;
         }
 
@@ -1688,6 +2245,18 @@ pub fn write_worksheet_xml<W: Write + Seek>(
         writer.write_event(quick_xml::events::Event::End(BytesEnd::new("tableParts")))?;
     }
 
+    // extLst (last in CT_Worksheet) holds only caller-supplied fragments
+    let mut ext_fragments = worksheet
+        .xml_fragments_at(XmlInjectionPoint::ExtLst)
+        .peekable();
+    if ext_fragments.peek().is_some() {
+        writer.write_event(Event::Start(BytesStart::new("extLst")))?;
+        for fragment in ext_fragments {
+            writer.get_mut().write_all(fragment.as_bytes())?;
+        }
+        writer.write_event(Event::End(BytesEnd::new("extLst")))?;
+    }
+
     writer.write_event(quick_xml::events::Event::End(BytesEnd::new("worksheet")))?;
 
     let result = writer.into_inner().into_inner();
@@ -1695,28 +2264,41 @@ pub fn write_worksheet_xml<W: Write + Seek>(
     Ok(())
 }
 
+/// Write the sheet's legacy comments part. Each comment cites its author by
+/// position in `<authors>`; a threaded comment is listed under the
+/// `tc={id}` author Excel uses to tie it to its thread.
 pub fn write_comments_xml<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
     options: &FileOptions<'static, ExtendedFileOptions>,
     worksheet: &Worksheet,
     sheet_id: u32,
 ) -> Result<bool> {
-    // Collect comments
-    let comment_cells: Vec<((u32, u32), String)> = worksheet
-        .cells
-        .iter()
-        .filter_map(|(key, cell_data)| {
-            cell_data.comment.as_ref().map(|comment| {
-                let (row, col) = decode_cell_key(*key);
-                ((row, col), comment.clone())
-            })
-        })
-        .collect();
-
+    let comment_cells = worksheet.sorted_comments();
     if comment_cells.is_empty() {
         return Ok(false); // No comments to write
     }
 
+    let author_of = |(row, col): (u32, u32), comment: &Comment| -> String {
+        if comment.is_threaded() {
+            format!(
+                "tc={}",
+                crate::comments::thread_entry_id(sheet_id, row, col, 0)
+            )
+        } else {
+            comment
+                .author
+                .clone()
+                .unwrap_or_else(|| crate::comments::DEFAULT_AUTHOR.to_string())
+        }
+    };
+    let mut authors: Vec<String> = Vec::new();
+    for (cell, comment) in &comment_cells {
+        let author = author_of(*cell, comment);
+        if !authors.contains(&author) {
+            authors.push(author);
+        }
+    }
+
     let path = format!("xl/comments/comment{}.xml", sheet_id);
     zip.start_file(&path, options.clone())?;
 
@@ -1728,11 +2310,10 @@ pub fn write_comments_xml<W: Write + Seek>(
     ));
     writer.write_event(quick_xml::events::Event::Start(comments_start))?;
 
-    // authors
     writer.write_event(quick_xml::events::Event::Start(BytesStart::new("authors")))?;
-    writer.write_event(quick_xml::events::Event::Start(BytesStart::new("author")))?;
-    writer.write_event(quick_xml::events::Event::Text(BytesText::new("RustyPyXL")))?;
-    writer.write_event(quick_xml::events::Event::End(BytesEnd::new("author")))?;
+    for author in &authors {
+        write_text_element(&mut writer, "author", author)?;
+    }
     writer.write_event(quick_xml::events::Event::End(BytesEnd::new("authors")))?;
 
     // commentList
@@ -1740,17 +2321,25 @@ pub fn write_comments_xml<W: Write + Seek>(
     comment_list.push_attribute(("count", comment_cells.len().to_string().as_str()));
     writer.write_event(quick_xml::events::Event::Start(comment_list))?;
 
-    for ((row, col), comment_text) in comment_cells {
+    for ((row, col), comment_data) in comment_cells {
         let coord = format!("{}{}", column_to_letter(col), row);
+        let author = author_of((row, col), comment_data);
+        let author_id = authors.iter().position(|a| *a == author).unwrap_or(0);
         let mut comment = BytesStart::new("comment");
         comment.push_attribute(("ref", coord.as_str()));
-        comment.push_attribute(("authorId", "0"));
+        comment.push_attribute(("authorId", author_id.to_string().as_str()));
         comment.push_attribute(("shapeId", "0"));
         writer.write_event(quick_xml::events::Event::Start(comment))?;
 
-        // text
         writer.write_event(quick_xml::events::Event::Start(BytesStart::new("text")))?;
-        write_text_element(&mut writer, "t", &comment_text)?;
+        match &comment_data.rich_text {
+            Some(rich) if !comment_data.is_threaded() => {
+                let mut runs = String::new();
+                write_rich_runs(&mut runs, rich);
+                writer.get_mut().write_all(runs.as_bytes())?;
+            }
+            _ => write_text_element(&mut writer, "t", &comment_data.legacy_text())?,
+        }
         writer.write_event(quick_xml::events::Event::End(BytesEnd::new("text")))?;
 
         writer.write_event(quick_xml::events::Event::End(BytesEnd::new("comment")))?;
@@ -2294,13 +2883,7 @@ pub fn write_vml_drawing<W: Write + Seek>(
     let path = format!("xl/drawings/vmlDrawing{}.vml", sheet_id);
     zip.start_file(&path, options.clone())?;
 
-    let mut comment_cells: Vec<(u32, u32)> = worksheet
-        .cells
-        .iter()
-        .filter(|(_, cd)| cd.comment.is_some())
-        .map(|(key, _)| decode_cell_key(*key))
-        .collect();
-    comment_cells.sort_unstable();
+    let comment_cells = worksheet.sorted_comments();
 
     let mut xml = String::with_capacity(1024 + comment_cells.len() * 768);
     xml.push_str(
@@ -2310,27 +2893,48 @@ pub fn write_vml_drawing<W: Write + Seek>(
 <v:stroke joinstyle="miter"/><v:path gradientshapeok="t" o:connecttype="rect"/></v:shapetype>"#,
     );
 
-    for (i, (row, col)) in comment_cells.iter().enumerate() {
+    for (i, ((row, col), comment)) in comment_cells.iter().enumerate() {
         // VML anchors are 0-based; place the box one column to the right
         let r0 = row.saturating_sub(1);
         let c0 = col.saturating_sub(1);
+        // A size set on the comment itself overrides the sheet's shape.
+        let mut shape = worksheet.comment_shapes.get(&(*row, *col)).cloned();
+        if comment.width.is_some() || comment.height.is_some() {
+            let sized = shape.get_or_insert_with(crate::worksheet::CommentShape::default);
+            sized.width = comment.width.unwrap_or(sized.width);
+            sized.height = comment.height.unwrap_or(sized.height);
+        }
+        let (width, height, visible) = shape
+            .as_ref()
+            .map(|s| (s.width, s.height, s.visible))
+            .unwrap_or((108.0, 59.25, false));
+        let anchor = match &shape {
+            Some(shape) => comment_anchor(worksheet, shape, r0, c0),
+            None => [c0 + 1, 15, r0, 2, c0 + 3, 31, r0 + 4, 9],
+        };
         xml.push_str(&format!(
             r##"
-<v:shape id="_x0000_s{id}" type="#_x0000_t202" style="position:absolute;margin-left:59.25pt;margin-top:1.5pt;width:108pt;height:59.25pt;z-index:{z};visibility:hidden" fillcolor="#ffffe1" o:insetmode="auto">
+<v:shape id="_x0000_s{id}" type="#_x0000_t202" style="position:absolute;margin-left:59.25pt;margin-top:1.5pt;width:{width}pt;height:{height}pt;z-index:{z};visibility:{visibility}" fillcolor="#ffffe1" o:insetmode="auto">
 <v:fill color2="#ffffe1"/><v:shadow color="black" obscured="t"/><v:path o:connecttype="none"/>
 <v:textbox style="mso-direction-alt:auto"><div style="text-align:left"/></v:textbox>
 <x:ClientData ObjectType="Note"><x:MoveWithCells/><x:SizeWithCells/>
-<x:Anchor>{a1}, 15, {a2}, 2, {a3}, 31, {a4}, 9</x:Anchor>
-<x:AutoFill>False</x:AutoFill><x:Row>{r}</x:Row><x:Column>{c}</x:Column></x:ClientData>
+<x:Anchor>{a1}, {a2}, {a3}, {a4}, {a5}, {a6}, {a7}, {a8}</x:Anchor>
+<x:AutoFill>False</x:AutoFill><x:Row>{r}</x:Row><x:Column>{c}</x:Column>{shown}</x:ClientData>
 </v:shape>"##,
             id = 1025 + i,
             z = i + 1,
-            a1 = c0 + 1,
-            a2 = r0,
-            a3 = c0 + 3,
-            a4 = r0 + 4,
+            visibility = if visible { "visible" } else { "hidden" },
+            a1 = anchor[0],
+            a2 = anchor[1],
+            a3 = anchor[2],
+            a4 = anchor[3],
+            a5 = anchor[4],
+            a6 = anchor[5],
+            a7 = anchor[6],
+            a8 = anchor[7],
             r = r0,
             c = c0,
+            shown = if visible { "<x:Visible/>" } else { "" },
         ));
     }
 
@@ -2339,6 +2943,57 @@ pub fn write_vml_drawing<W: Write + Seek>(
     Ok(())
 }
 
+/// The eight-number VML `<x:Anchor>` (left column, x offset, top row, y
+/// offset, right column, x offset, bottom row, y offset; 0-based cells and
+/// pixel offsets) for a comment box on the 0-based cell (r0, c0). The far
+/// corner is found by walking the sheet's column widths and row heights.
+fn comment_anchor(
+    worksheet: &Worksheet,
+    shape: &crate::worksheet::CommentShape,
+    r0: u32,
+    c0: u32,
+) -> [u32; 8] {
+    let (top, left, dy, dx) = match shape.anchor {
+        Some((row, col)) => (row.saturating_sub(1), col.saturating_sub(1), 0, 0),
+        None => (r0, c0 + 1, 2, 15),
+    };
+    // Excel's defaults: 8.43-character columns (64px) and 15pt rows (20px).
+    let column_px = |c: u32| {
+        worksheet
+            .column_dimensions
+            .get(&(c + 1))
+            .map_or(64, |w| (w * 7.0 + 5.0).round().max(1.0) as u32)
+    };
+    let row_px = |r: u32| {
+        worksheet
+            .row_dimensions
+            .get(&(r + 1))
+            .map_or(20, |h| (h * 4.0 / 3.0).round().max(1.0) as u32)
+    };
+    let (right, dx_end) = walk_pixels(left, dx, points_to_px(shape.width), column_px);
+    let (bottom, dy_end) = walk_pixels(top, dy, points_to_px(shape.height), row_px);
+    [left, dx, top, dy, right, dx_end, bottom, dy_end]
+}
+
+fn points_to_px(points: f64) -> u32 {
+    (points * 4.0 / 3.0).round().max(1.0) as u32
+}
+
+/// Starting `offset` pixels into cell `start`, move `span` pixels along
+/// cells sized by `size`; returns the cell and offset reached.
+fn walk_pixels(start: u32, offset: u32, span: u32, size: impl Fn(u32) -> u32) -> (u32, u32) {
+    let (mut cell, mut offset, mut remaining) = (start, offset, span);
+    loop {
+        let room = size(cell).saturating_sub(offset);
+        if remaining <= room {
+            return (cell, offset + remaining);
+        }
+        remaining -= room;
+        cell += 1;
+        offset = 0;
+    }
+}
+
 /// Write a table XML file.
 pub fn write_table_xml<W: Write + Seek>(
     zip: &mut ZipWriter<W>,
@@ -2370,6 +3025,7 @@ pub fn write_table_xml<W: Write + Seek>(
     if table.totals_row {
         table_start.push_attribute(("totalsRowCount", "1"));
     }
+    table_start.push_attribute(("totalsRowShown", if table.totals_row { "1" } else { "0" }));
 
     writer.write_event(Event::Start(table_start))?;
 
@@ -2411,9 +3067,18 @@ pub fn write_table_xml<W: Write + Seek>(
             tc.push_attribute(("totalsRowLabel", strip_illegal_xml_chars(label).as_ref()));
         }
 
-        if let Some(ref formula) = col.calculated_column_formula {
+        let totals_formula = match &col.totals_row_function {
+            crate::table::TotalsRowFunction::Custom(f) => Some(f.strip_prefix('=').unwrap_or(f)),
+            _ => None,
+        };
+        if col.calculated_column_formula.is_some() || totals_formula.is_some() {
             writer.write_event(Event::Start(tc))?;
-            write_text_element(&mut writer, "calculatedColumnFormula", formula)?;
+            if let Some(ref formula) = col.calculated_column_formula {
+                write_text_element(&mut writer, "calculatedColumnFormula", formula)?;
+            }
+            if let Some(formula) = totals_formula {
+                write_text_element(&mut writer, "totalsRowFormula", formula)?;
+            }
             writer.write_event(Event::End(BytesEnd::new("tableColumn")))?;
         } else {
             writer.write_event(Event::Empty(tc))?;
@@ -2463,17 +3128,99 @@ mod tests {
     }
 
     #[test]
-    fn test_strip_illegal_xml_chars() {
-        assert_eq!(strip_illegal_xml_chars("a\x00b\x1fc"), "abc");
-        assert_eq!(strip_illegal_xml_chars("plain <kept> &"), "plain <kept> &");
+    fn test_shared_string_thresholds() {
+        let mut ws = Worksheet::new("Sheet1");
+        for row in 1..=6 {
+            ws.set_cell_value(row, 1, "North");
+            ws.set_cell_value(row, 2, format!("order-{row}"));
+        }
+        ws.set_cell_value(7, 1, "South");
+        ws.set_cell_value(8, 1, "South");
+        let sheets = [ws];
+        let keys = |strings: &[InternedString]| {
+            let mut keys: Vec<String> = strings.iter().map(|s| s.to_string()).collect();
+            keys.sort();
+            keys
+        };
+
+        let (all, map, refs) = collect_shared_strings(&sheets, SharedStrings::All);
+        assert_eq!((all.len(), map.len(), refs), (8, 8, 14));
+
+        let (repeated, map, refs) = collect_shared_strings(&sheets, SharedStrings::MinRepeats(2));
+        assert_eq!(keys(&repeated), vec!["North", "South"]);
+        assert_eq!(refs, 8);
+        assert_eq!(map[repeated[1].as_ref()], 1);
+
+        let (frequent, _, refs) = collect_shared_strings(&sheets, SharedStrings::MinRepeats(3));
+        assert_eq!((keys(&frequent), refs), (vec!["North".to_string()], 6));
+
+        // 8 distinct of 14 sampled: share only the repeated values.
+        let (auto, _, _) = collect_shared_strings(&sheets, SharedStrings::Auto);
+        assert_eq!(keys(&auto), vec!["North", "South"]);
     }
 
     #[test]
-    fn test_legacy_password_hash_known_vectors() {
-        // Reference values from openpyxl's hash_password / MS-XLS Method1.
-        assert_eq!(legacy_password_hash("test"), 0xCBEB);
-        assert_eq!(legacy_password_hash(""), 0xCE4B);
-        assert_eq!(legacy_password_hash("password"), 0x83AF);
+    fn test_incremental_shared_strings_follow_edits() {
+        let mut wb = crate::Workbook::new();
+        wb.create_sheet(Some("Early".to_string())).unwrap();
+        wb.get_sheet_by_name_mut("Early")
+            .unwrap()
+            .set_cell_value(1, 1, "kept");
+        wb.set_shared_strings(SharedStrings::Incremental);
+        let ws = wb.create_sheet(Some("Late".to_string())).unwrap();
+        for row in 1..=4 {
+            ws.set_cell_value(row, 1, "North");
+            ws.set_cell_value(row, 2, format!("order-{row}"));
+        }
+        ws.set_cell_value(1, 1, 5.0);
+        ws.set_cell_formula(2, 2, "A1");
+        ws.delete_rows(4, 1);
+        ws.merge_cells("A2:B3").unwrap();
+        ws.set_cell_rich_text(
+            1,
+            2,
+            crate::RichText::new(vec![crate::TextRun::plain("rich")]),
+        );
+        let counts = ws.string_counts.as_ref().unwrap();
+        assert_eq!((counts.get("North"), counts.len()), (1, 1));
+
+        let (strings, map, refs) =
+            collect_shared_strings(&wb.worksheets, SharedStrings::Incremental);
+        let mut keys: Vec<&str> = strings.iter().map(|s| s.as_ref()).collect();
+        keys.sort();
+        assert_eq!(keys, ["North", "kept"]);
+        assert_eq!((map.len(), refs), (2, 2));
+
+        // Switching away drops the counts; Inline builds no table at all.
+        wb.set_shared_strings(SharedStrings::Inline);
+        assert!(wb.worksheets.iter().all(|ws| ws.string_counts.is_none()));
+        assert!(
+            collect_shared_strings(&wb.worksheets, SharedStrings::Inline)
+                .0
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_auto_shared_strings_extremes() {
+        let mut unique = Worksheet::new("Ids");
+        let mut repeated = Worksheet::new("Codes");
+        for row in 1..=100 {
+            unique.set_cell_value(row, 1, format!("id-{row}"));
+            repeated.set_cell_value(row, 1, if row % 2 == 0 { "yes" } else { "no" });
+        }
+        assert_eq!(auto_min_repeats(std::slice::from_ref(&unique)), usize::MAX);
+        assert!(collect_shared_strings(&[unique], SharedStrings::Auto)
+            .0
+            .is_empty());
+        assert_eq!(auto_min_repeats(&[repeated]), 1);
+        assert_eq!(auto_min_repeats(&[]), 1);
+    }
+
+    #[test]
+    fn test_strip_illegal_xml_chars() {
+        assert_eq!(strip_illegal_xml_chars("a\x00b\x1fc"), "abc");
+        assert_eq!(strip_illegal_xml_chars("plain <kept> &"), "plain <kept> &");
     }
 
     #[test]
@@ -2485,9 +3232,35 @@ mod tests {
                 value: CellValue::Number(v),
                 ..Default::default()
             };
-            write_cell_direct(&mut buf, 1, 1, &cell, cell.style_index, &map);
+            write_cell_direct(
+                &mut buf,
+                1,
+                1,
+                &cell,
+                cell.style_index,
+                &map,
+                false,
+                NonFiniteNumbers::NumError,
+                NumberPrecision::Shortest,
+                None,
+            );
             assert_eq!(buf, r#"<c r="A1" t="e"><v>#NUM!</v></c>"#);
 
+            let mut empty = String::new();
+            write_cell_direct(
+                &mut empty,
+                1,
+                1,
+                &cell,
+                Some(3),
+                &map,
+                false,
+                NonFiniteNumbers::Empty,
+                NumberPrecision::Shortest,
+                None,
+            );
+            assert_eq!(empty, r#"<c r="A1" s="3"/>"#);
+
             let mut buf2 = String::new();
             format_cell_value(&mut buf2, "A1", &CellValue::Number(v));
             assert_eq!(buf2, r#"<c r="A1" t="e"><v>#NUM!</v></c>"#);
@@ -2495,10 +3268,47 @@ mod tests {
     }
 
     #[test]
-    fn test_date_value_is_escaped() {
+    fn test_number_precision() {
+        let write = |n: f64, precision| {
+            let mut buf = String::new();
+            push_number(&mut buf, n, precision);
+            buf
+        };
+        for (n, shortest, excel) in [
+            (0.1 + 0.2, "0.30000000000000004", "0.3"),
+            (3.0, "3", "3"),
+            (-42.0, "-42", "-42"),
+            (1.0 / 3.0, "0.3333333333333333", "0.333333333333333"),
+            (123456789.12345679, "123456789.12345679", "123456789.123457"),
+            (1e20, "1e20", "1e20"),
+            (1.5e-7, "1.5e-7", "1.5e-7"),
+            (9999999999999998.0, "9999999999999998.0", "1e16"),
+            (
+                -2.5e-300 / 3.0,
+                "-8.333333333333333e-301",
+                "-8.33333333333333e-301",
+            ),
+            (f64::MAX, "1.7976931348623157e308", "1.7976931348623157e308"),
+        ] {
+            assert_eq!(write(n, NumberPrecision::Shortest), shortest, "{n}");
+            assert_eq!(write(n, NumberPrecision::Excel), excel, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_date_value_is_written_as_iso() {
+        // A structured date can only render as ISO text, so nothing in it
+        // needs escaping.
         let mut buf = String::new();
-        format_cell_value(&mut buf, "A1", &CellValue::Date("<bad>&".to_string()));
-        assert_eq!(buf, r#"<c r="A1" t="d"><v>&lt;bad&gt;&amp;</v></c>"#);
+        for text in ["2024-03-15", "18:15:00", "2024-03-15T10:30:45.250"] {
+            buf.clear();
+            format_cell_value(
+                &mut buf,
+                "A1",
+                &CellValue::Date(crate::DateValue::parse(text).unwrap()),
+            );
+            assert_eq!(buf, format!(r#"<c r="A1" t="d"><v>{}</v></c>"#, text));
+        }
     }
 
     #[test]
@@ -73,3 +73,65 @@ fn width_never_exceeds_excel_maximum() {
     let ws = wb.get_sheet_by_name_mut("S").unwrap();
     assert_eq!(ws.auto_fit_column(1), Some(255.0));
 }
+
+#[test]
+fn autofit_range_limits_measured_rows_and_columns() {
+    let mut wb = sheet_with(&[
+        (
+            1,
+            1,
+            CellValue::from("A very long report title in the first row"),
+        ),
+        (2, 1, CellValue::from("Name")),
+        (3, 1, CellValue::from("Alexandra")),
+        (2, 2, CellValue::from("Amount")),
+        (2, 4, CellValue::from("untouched column")),
+    ]);
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    let set = ws.autofit_columns(Some("A2:B10")).unwrap();
+
+    assert_eq!(set, vec![(1, 11.0), (2, 8.0)]); // "Alexandra" 9 + 2, "Amount" 6 + 2
+    assert_eq!(ws.get_column_width(4), None);
+    assert!(ws.column_props(1).best_fit);
+
+    ws.autofit_columns(Some("$D:$D")).unwrap();
+    assert_eq!(ws.get_column_width(4), Some(18.0));
+    assert!(ws.autofit_columns(Some("not a range")).is_err());
+}
+
+#[test]
+fn bold_large_and_wide_text_is_wider() {
+    use rustypyxl::style::{CellStyle, Font};
+
+    let mut wb = sheet_with(&[
+        (1, 1, CellValue::from("Total")),
+        (1, 2, CellValue::from("Total")),
+        (1, 3, CellValue::from("売上合計")),
+    ]);
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.set_cell_style(
+        1,
+        2,
+        CellStyle::new().with_font(Font::new().with_bold(true).with_size(22.0)),
+    );
+    ws.autofit_columns(None).unwrap();
+
+    assert_eq!(ws.get_column_width(1), Some(7.0));
+    // 5 chars x (22 / 11) for the size x 1.05 for bold, + 2 padding.
+    let heading = ws.get_column_width(2).unwrap();
+    assert!((heading - 12.5).abs() < 0.001, "got {heading}");
+    // Four wide characters measure like eight narrow ones.
+    assert_eq!(ws.get_column_width(3), Some(10.0));
+}
+
+#[test]
+fn cells_merged_across_columns_are_not_measured() {
+    let mut wb = sheet_with(&[
+        (1, 1, CellValue::from("A heading that spans three columns")),
+        (2, 1, CellValue::from("id")),
+    ]);
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.merge_cells("A1:C1").unwrap();
+    ws.auto_fit_all();
+    assert_eq!(ws.get_column_width(1), Some(4.0));
+}
@@ -33,7 +33,9 @@ fn workbook_with_bar_chart() -> Workbook {
             .with_categories("Data!$A$1:$A$3"),
     );
     chart = chart.with_legend(ChartLegend::new().with_position("b"));
-    wb.get_sheet_by_name_mut("Data").unwrap().add_chart(chart);
+    wb.get_sheet_by_name_mut("Data")
+        .unwrap()
+        .add_chart(chart, "A1");
     wb
 }
 
@@ -80,7 +82,9 @@ fn scatter_chart_uses_two_value_axes() {
     wb.create_sheet(Some("S".to_string())).unwrap();
     let mut chart = Chart::scatter();
     chart.add_series(ChartSeries::new("S!$B$1:$B$3").with_categories("S!$A$1:$A$3"));
-    wb.get_sheet_by_name_mut("S").unwrap().add_chart(chart);
+    wb.get_sheet_by_name_mut("S")
+        .unwrap()
+        .add_chart(chart, "A1");
 
     let bytes = wb.save_to_bytes().unwrap();
     let chart = read_part(&bytes, "xl/charts/chart1.xml").unwrap();
@@ -96,7 +100,9 @@ fn pie_chart_has_no_axes() {
     wb.create_sheet(Some("S".to_string())).unwrap();
     let mut chart = Chart::pie();
     chart.add_series(ChartSeries::new("S!$B$1:$B$3"));
-    wb.get_sheet_by_name_mut("S").unwrap().add_chart(chart);
+    wb.get_sheet_by_name_mut("S")
+        .unwrap()
+        .add_chart(chart, "A1");
 
     let bytes = wb.save_to_bytes().unwrap();
     let chart = read_part(&bytes, "xl/charts/chart1.xml").unwrap();
@@ -113,11 +119,11 @@ fn charts_across_sheets_get_unique_ids() {
 
     let mut c1 = Chart::column();
     c1.add_series(ChartSeries::new("One!$A$1:$A$3"));
-    wb.get_sheet_by_name_mut("One").unwrap().add_chart(c1);
+    wb.get_sheet_by_name_mut("One").unwrap().add_chart(c1, "A1");
 
     let mut c2 = Chart::line();
     c2.add_series(ChartSeries::new("Two!$A$1:$A$3"));
-    wb.get_sheet_by_name_mut("Two").unwrap().add_chart(c2);
+    wb.get_sheet_by_name_mut("Two").unwrap().add_chart(c2, "A1");
 
     let bytes = wb.save_to_bytes().unwrap();
     // Sheet One is sheet1 -> chart1/drawing1; Sheet Two is sheet2 -> chart2/drawing2.
@@ -143,8 +149,9 @@ fn chart_survives_load_save_round_trip() {
             .with_categories("Data!$A$1:$A$3"),
     );
     chart = chart.with_legend(ChartLegend::new().with_position("b"));
-    chart = chart.with_anchor(rustypyxl::chart::ChartAnchor::at("D2"));
-    wb.get_sheet_by_name_mut("Data").unwrap().add_chart(chart);
+    wb.get_sheet_by_name_mut("Data")
+        .unwrap()
+        .add_chart(chart, "D2");
 
     let bytes = wb.save_to_bytes().unwrap();
     let reloaded = Workbook::load_from_bytes(&bytes).unwrap();
@@ -185,7 +192,9 @@ fn round_trips_line_pie_and_scatter_types() {
         wb.create_sheet(Some("S".to_string())).unwrap();
         let mut chart = make();
         chart.add_series(ChartSeries::new("S!$B$1:$B$3").with_categories("S!$A$1:$A$3"));
-        wb.get_sheet_by_name_mut("S").unwrap().add_chart(chart);
+        wb.get_sheet_by_name_mut("S")
+            .unwrap()
+            .add_chart(chart, "A1");
 
         let bytes = wb.save_to_bytes().unwrap();
         let reloaded = Workbook::load_from_bytes(&bytes).unwrap();
@@ -199,3 +208,48 @@ fn round_trips_line_pie_and_scatter_types() {
         );
     }
 }
+
+#[test]
+fn typed_charts_are_anchored_at_the_given_cell() {
+    use rustypyxl::chart::{BarChart, BarGrouping, ChartAxis, LineChart, PieChart};
+
+    let mut wb = Workbook::new();
+    wb.create_sheet(Some("S".to_string())).unwrap();
+
+    let mut bar = BarChart::new()
+        .with_title("Totals")
+        .with_grouping(BarGrouping::Stacked)
+        .with_y_axis(ChartAxis::new().with_title("Units").with_range(0.0, 50.0));
+    bar.add_data("S!$B$1:$C$4", true).unwrap();
+    bar.set_categories("S!$A$2:$A$4");
+    let mut line = LineChart::new();
+    line.add_data("S!$B$2:$B$4", false).unwrap();
+    let mut pie = PieChart::new();
+    pie.add_data("S!$C$2:$C$4", false).unwrap();
+
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.add_chart(bar, "E5");
+    ws.add_chart(line, "E20");
+    ws.add_chart(pie, "M5");
+
+    let bytes = wb.save_to_bytes().unwrap();
+    let drawing = read_part(&bytes, "xl/drawings/drawing1.xml").unwrap();
+    assert!(drawing
+        .contains("<xdr:from><xdr:col>4</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>4</xdr:row>"));
+    assert!(drawing.contains("<xdr:col>12</xdr:col>"));
+
+    let bar = read_part(&bytes, "xl/charts/chart1.xml").unwrap();
+    assert!(bar.contains(r#"<c:barDir val="col"/>"#));
+    assert_eq!(bar.matches("<c:ser>").count(), 2, "one series per column");
+    assert!(bar.contains("S!$C$2:$C$4"));
+    assert!(bar.contains("S!$A$2:$A$4"));
+    assert!(bar.contains(r#"<c:overlap val="100"/>"#));
+    assert!(bar.contains(r#"<c:max val="50"/><c:min val="0"/>"#));
+    assert!(bar.contains("<a:t>Units</a:t>"));
+    assert!(read_part(&bytes, "xl/charts/chart2.xml")
+        .unwrap()
+        .contains("<c:lineChart>"));
+    assert!(read_part(&bytes, "xl/charts/chart3.xml")
+        .unwrap()
+        .contains("<c:pieChart>"));
+}
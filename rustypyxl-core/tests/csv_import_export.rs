@@ -0,0 +1,105 @@
+//! CSV files round-trip through `Workbook::insert_from_csv` and
+//! `Worksheet::export_to_csv`, keeping types, quoting and encoding.
+
+use chrono::NaiveDate;
+use rustypyxl::{CellValue, CsvEncoding, CsvExportOptions, CsvImportOptions, CsvQuoting, Workbook};
+
+fn one_sheet() -> Workbook {
+    let mut wb = Workbook::new();
+    wb.create_sheet(Some("Data".to_string())).unwrap();
+    wb
+}
+
+#[test]
+fn export_then_import_keeps_values() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.csv");
+
+    let mut wb = one_sheet();
+    let ws = wb.get_sheet_by_name_mut("Data").unwrap();
+    ws.set_cell_value(1, 1, "name");
+    ws.set_cell_value(1, 2, "amount");
+    ws.set_cell_value(1, 3, "paid");
+    ws.set_cell_value(1, 4, "due");
+    ws.set_cell_value(2, 1, "Zoë, \"the\" first");
+    ws.set_cell_value(2, 2, 12.75);
+    ws.set_cell_value(2, 3, true);
+    let due = NaiveDate::from_ymd_opt(2024, 2, 29)
+        .unwrap()
+        .and_hms_opt(8, 0, 0)
+        .unwrap();
+    ws.set_cell_value(2, 4, due);
+
+    let exported = ws
+        .export_to_csv(
+            &path,
+            Some(
+                CsvExportOptions::new()
+                    .with_delimiter(b'\t')
+                    .with_quoting(CsvQuoting::NonNumeric)
+                    .with_encoding(CsvEncoding::Windows1252),
+            ),
+        )
+        .unwrap();
+    assert_eq!((exported.rows_exported, exported.columns_exported), (2, 4));
+    assert_eq!(exported.file_size, std::fs::metadata(&path).unwrap().len());
+
+    let mut target = one_sheet();
+    let imported = target
+        .insert_from_csv(
+            "Data",
+            &path,
+            1,
+            1,
+            Some(
+                CsvImportOptions::new()
+                    .with_delimiter(b'\t')
+                    .with_encoding(CsvEncoding::Windows1252),
+            ),
+        )
+        .unwrap();
+    assert_eq!(imported.column_names, ["name", "amount", "paid", "due"]);
+    assert_eq!(imported.data_range(), "A2:D2");
+
+    let ws = target.get_sheet_by_name("Data").unwrap();
+    assert_eq!(
+        ws.get_cell_value(2, 1),
+        Some(&CellValue::from("Zoë, \"the\" first"))
+    );
+    assert_eq!(ws.get_cell_value(2, 2), Some(&CellValue::Number(12.75)));
+    assert_eq!(ws.get_cell_value(2, 3), Some(&CellValue::Boolean(true)));
+    assert_eq!(ws.get_cell_value(2, 4), Some(&CellValue::from(due)));
+}
+
+#[test]
+fn import_streams_a_large_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.csv");
+    let mut text = String::from("id,label\n");
+    for i in 1..=20_000 {
+        text.push_str(&format!("{},\"row\n{}\"\n", i, i));
+    }
+    std::fs::write(&path, text).unwrap();
+
+    let mut wb = one_sheet();
+    let result = wb.insert_from_csv("Data", &path, 1, 1, None).unwrap();
+    assert_eq!(result.rows_imported, 20_000);
+    assert_eq!(result.end_row, 20_001);
+    let ws = wb.get_sheet_by_name("Data").unwrap();
+    assert_eq!(
+        ws.get_cell_value(20_001, 2),
+        Some(&CellValue::from("row\n20000"))
+    );
+}
+
+#[test]
+fn missing_file_and_sheet_are_errors() {
+    let mut wb = one_sheet();
+    assert!(wb
+        .insert_from_csv("Data", "/nonexistent/file.csv", 1, 1, None)
+        .is_err());
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("x.csv");
+    std::fs::write(&path, "a\n").unwrap();
+    assert!(wb.insert_from_csv("Nope", &path, 1, 1, None).is_err());
+}
@@ -0,0 +1,154 @@
+//! Date-times are written as Excel serials with a date number format, and
+//! numbers in a date format load back as `CellValue::Date`, in both the
+//! 1900 and the 1904 date system.
+
+use std::io::Read;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use rustypyxl::read_only::ReadOnlyWorkbook;
+use rustypyxl::{CellValue, Workbook};
+use zip::ZipArchive;
+
+fn dt(y: i32, m: u32, d: u32, h: u32, mi: u32, s: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(y, m, d)
+        .unwrap()
+        .and_hms_opt(h, mi, s)
+        .unwrap()
+}
+
+fn sheet_xml(bytes: &[u8]) -> String {
+    let mut archive = ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut xml = String::new();
+    archive
+        .by_name("xl/worksheets/sheet1.xml")
+        .unwrap()
+        .read_to_string(&mut xml)
+        .unwrap();
+    xml
+}
+
+fn one_sheet() -> Workbook {
+    let mut wb = Workbook::new();
+    wb.create_sheet(Some("S".to_string())).unwrap();
+    wb
+}
+
+#[test]
+fn date_times_save_as_serials_and_load_back() {
+    let mut wb = one_sheet();
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.set_cell_value(1, 1, dt(2024, 3, 15, 10, 30, 0));
+    ws.set_cell_value(2, 1, NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+    ws.set_cell_value(3, 1, 45366.4375);
+
+    let bytes = wb.save_to_bytes().unwrap();
+    assert!(sheet_xml(&bytes).contains("<v>45366.4375</v>"));
+
+    let reloaded = Workbook::load_from_bytes(&bytes).unwrap();
+    let ws = reloaded.get_sheet_by_name("S").unwrap();
+    assert_eq!(
+        ws.get_cell_value(1, 1),
+        Some(&CellValue::from(dt(2024, 3, 15, 10, 30, 0)))
+    );
+    assert_eq!(
+        ws.get_cell_value(2, 1),
+        Some(&CellValue::from(dt(2024, 3, 15, 0, 0, 0)))
+    );
+    // The same serial without a date format is just a number.
+    assert_eq!(
+        ws.get_cell_value(3, 1),
+        Some(&CellValue::Number(45366.4375))
+    );
+
+    let formats: Vec<_> = (1..=2)
+        .map(|row| ws.get_cell(row, 1).unwrap().number_format.clone())
+        .collect();
+    assert_eq!(formats[0].as_deref(), Some("yyyy-mm-dd h:mm:ss"));
+    assert_eq!(formats[1].as_deref(), Some("yyyy-mm-dd"));
+}
+
+#[test]
+fn a_date_format_of_its_own_is_kept() {
+    let mut wb = one_sheet();
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.set_cell_value(1, 1, dt(2024, 3, 15, 0, 0, 0));
+    ws.set_cell_number_format(1, 1, "dd/mm/yyyy");
+    // A number that already has a date format is a date.
+    ws.set_cell_value(2, 1, 45366.0);
+    ws.set_cell_number_format(2, 1, "d-mmm-yy");
+
+    let reloaded = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+    let ws = reloaded.get_sheet_by_name("S").unwrap();
+    for row in 1..=2 {
+        assert_eq!(
+            ws.get_cell_value(row, 1),
+            Some(&CellValue::from(dt(2024, 3, 15, 0, 0, 0)))
+        );
+    }
+    assert_eq!(
+        ws.get_cell(1, 1).unwrap().number_format.as_deref(),
+        Some("dd/mm/yyyy")
+    );
+}
+
+#[test]
+fn a_date_over_a_loaded_number_gets_a_date_format() {
+    let mut wb = one_sheet();
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.set_cell_value(1, 1, 1.5);
+    ws.set_cell_number_format(1, 1, "0.00");
+
+    let mut loaded = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+    let ws = loaded.get_sheet_by_name_mut("S").unwrap();
+    assert!(ws.get_cell(1, 1).unwrap().style_index.is_some());
+    ws.set_cell_value(1, 1, dt(2001, 2, 3, 4, 5, 6));
+
+    let reloaded = Workbook::load_from_bytes(&loaded.save_to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        reloaded
+            .get_sheet_by_name("S")
+            .unwrap()
+            .get_cell_value(1, 1),
+        Some(&CellValue::from(dt(2001, 2, 3, 4, 5, 6)))
+    );
+}
+
+#[test]
+fn the_1904_system_shifts_serials() {
+    let mut wb = one_sheet();
+    wb.date1904 = true;
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.set_cell_value(1, 1, dt(2024, 3, 15, 12, 0, 0));
+
+    let bytes = wb.save_to_bytes().unwrap();
+    assert!(sheet_xml(&bytes).contains("<v>43904.5</v>"));
+
+    let reloaded = Workbook::load_from_bytes(&bytes).unwrap();
+    assert!(reloaded.date1904);
+    assert_eq!(
+        reloaded
+            .get_sheet_by_name("S")
+            .unwrap()
+            .get_cell_value(1, 1),
+        Some(&CellValue::from(dt(2024, 3, 15, 12, 0, 0)))
+    );
+}
+
+#[test]
+fn read_only_rows_carry_date_times() {
+    let mut wb = one_sheet();
+    wb.date1904 = true;
+    let ws = wb.get_sheet_by_name_mut("S").unwrap();
+    ws.set_cell_value(1, 1, dt(1999, 12, 31, 23, 59, 59));
+    ws.set_cell_value(1, 2, 7.0);
+
+    let ro = ReadOnlyWorkbook::from_bytes(wb.save_to_bytes().unwrap()).unwrap();
+    let row = ro.rows("S").unwrap().next().unwrap().unwrap();
+    assert_eq!(
+        row.values(1, 2),
+        vec![
+            CellValue::from(dt(1999, 12, 31, 23, 59, 59)),
+            CellValue::Number(7.0)
+        ]
+    );
+}
@@ -74,3 +74,31 @@ fn larger_workbook_round_trips() {
     assert_eq!(ws.get_cell_value(500, 1), Some(&CellValue::from("row-500")));
     assert_eq!(ws.get_cell_value(250, 2), Some(&CellValue::Number(250.0)));
 }
+
+#[test]
+fn save_with_password_writes_an_encrypted_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.xlsx");
+    let path = path.to_str().unwrap();
+    sample().save_with_password(path, "s3cret").unwrap();
+
+    let bytes = std::fs::read(path).unwrap();
+    assert!(rustypyxl::crypto::is_encrypted(&bytes));
+    assert!(Workbook::load(path).is_err());
+    let wb = Workbook::load_with_password(path, "s3cret").unwrap();
+    let ws = wb.get_sheet_by_name("Secret").unwrap();
+    assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::from("hello")));
+}
+
+#[test]
+fn save_encrypted_needs_the_password_to_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("report.xlsx");
+    let path = path.to_str().unwrap();
+    sample().save_encrypted(path, "s3cret").unwrap();
+
+    assert!(Workbook::load_with_password(path, "wrong").is_err());
+    let wb = Workbook::load_with_password(path, "s3cret").unwrap();
+    let ws = wb.get_sheet_by_name("Secret").unwrap();
+    assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::from("hello")));
+}
@@ -6,9 +6,12 @@ use rustypyxl::autofilter::{
     AutoFilter, CustomFilter, DynamicFilterType, FilterColumn, FilterOperator, FilterType,
     Top10Filter,
 };
-use rustypyxl::table::{Table, TableColumn};
-use rustypyxl::worksheet::DataValidation;
-use rustypyxl::{CellValue, Workbook};
+use rustypyxl::table::{Table, TableColumn, TableStyle, TotalsRowFunction};
+use rustypyxl::worksheet::{CommentShape, DataValidation};
+use rustypyxl::{
+    CellStyle, CellValue, Comment, Font, Hyperlink, NamedStyle, RichText, RunFont, SheetProperties,
+    SheetView, SheetViewType, TextRun, ThreadedComment, Workbook,
+};
 
 fn roundtrip(wb: &Workbook) -> Workbook {
     Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap()
@@ -35,6 +38,7 @@ fn data_validation_messages_survive_roundtrip() {
         allow_blank: false,
         show_error: true,
         show_input: true,
+        hide_dropdown: false,
         sqref: None,
     };
     ws.add_data_validation(1, 1, validation);
@@ -61,6 +65,41 @@ fn data_validation_messages_survive_roundtrip() {
     assert!(!dv.allow_blank);
 }
 
+#[test]
+fn data_validation_ranges_and_dropdown_survive_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Sheet1".to_string())).unwrap();
+    ws.add_validation(
+        DataValidation::list(["Low", "High"])
+            .with_sqref("B2:B20 D2:D20")
+            .with_prompt(None, Some("Pick a level".to_string()))
+            .with_hide_dropdown(true),
+    )
+    .unwrap();
+    ws.add_validation(
+        DataValidation::new("decimal")
+            .with_operator("between")
+            .with_formula1("0")
+            .with_formula2("1")
+            .with_sqref("F1")
+            .with_error_style("information"),
+    )
+    .unwrap();
+
+    let reloaded = roundtrip(&wb);
+    let ws = reloaded.get_sheet_by_name("Sheet1").unwrap();
+    assert_eq!(ws.data_validations.len(), 2);
+    let list = ws.data_validation_at(15, 4).expect("second range covered");
+    assert_eq!(list.sqref.as_deref(), Some("B2:B20 D2:D20"));
+    assert_eq!(list.formula1.as_deref(), Some("\"Low,High\""));
+    assert_eq!(list.prompt_message.as_deref(), Some("Pick a level"));
+    assert!(list.hide_dropdown);
+    let decimal = ws.data_validation_at(1, 6).unwrap();
+    assert_eq!(decimal.formula2.as_deref(), Some("1"));
+    assert!(!decimal.hide_dropdown);
+    assert!(ws.data_validation_at(1, 1).is_none());
+}
+
 /// A validation with no dialog text must not grow empty attributes.
 #[test]
 fn data_validation_without_messages_omits_the_attributes() {
@@ -123,6 +162,79 @@ fn table_calculated_column_formula_survives_roundtrip() {
     );
 }
 
+/// A totals row added after the fact keeps its functions, label, custom
+/// formula and SUBTOTAL cells through a save/load cycle.
+#[test]
+fn table_totals_row_survives_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Sheet1".to_string())).unwrap();
+    ws.set_cell_value(1, 1, "Item");
+    ws.set_cell_value(1, 2, "Amount");
+    ws.set_cell_value(1, 3, "Qty");
+    ws.set_cell_value(2, 1, "Widget");
+    ws.set_cell_value(2, 2, CellValue::Number(9.5));
+    ws.set_cell_value(2, 3, CellValue::Number(3.0));
+    ws.add_table(Table::new(1, "Sales", "A1:C2"));
+    let row = ws
+        .add_table_totals_row(
+            "Sales",
+            &[
+                ("Amount", TotalsRowFunction::Sum),
+                (
+                    "Qty",
+                    TotalsRowFunction::Custom("=MAX(Sales[Qty])*2".into()),
+                ),
+            ],
+        )
+        .unwrap();
+    assert_eq!(row, 3);
+
+    let reloaded = roundtrip(&wb);
+    let ws = reloaded.get_sheet_by_name("Sheet1").unwrap();
+    let table = &ws.tables[0];
+    assert!(table.totals_row);
+    assert_eq!(table.range, "A1:C3");
+    assert_eq!(table.columns[0].totals_row_label.as_deref(), Some("Total"));
+    assert_eq!(table.columns[1].totals_row_function, TotalsRowFunction::Sum);
+    assert_eq!(
+        table.columns[2].totals_row_function,
+        TotalsRowFunction::Custom("MAX(Sales[Qty])*2".into())
+    );
+    assert_eq!(
+        ws.get_cell_value(3, 2),
+        Some(&CellValue::Formula("SUBTOTAL(109,Sales[Amount])".into()))
+    );
+}
+
+#[test]
+fn created_table_survives_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Sheet1".to_string())).unwrap();
+    ws.set_cell_value(1, 1, "Region");
+    ws.set_cell_value(1, 2, "Sales");
+    ws.set_cell_value(2, 1, "North");
+    ws.set_cell_value(2, 2, CellValue::Number(12.0));
+    ws.create_table("Regional", "A1:B2", TableStyle::Light(9))
+        .unwrap()
+        .show_column_stripes = true;
+    let other = wb.create_sheet(Some("Sheet2".to_string())).unwrap();
+    other
+        .create_table("Other", "A1:A3", TableStyle::default())
+        .unwrap();
+
+    let saved = wb.save_to_bytes().unwrap();
+    let reloaded = Workbook::load_from_bytes(&saved).unwrap();
+    let table = &reloaded.get_sheet_by_name("Sheet1").unwrap().tables[0];
+    assert_eq!(table.name, "Regional");
+    assert_eq!(table.range, "A1:B2");
+    assert_eq!(table.style.style_name(), "TableStyleLight9");
+    assert!(table.auto_filter && table.show_row_stripes && table.show_column_stripes);
+    let names: Vec<&str> = table.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, ["Region", "Sales"]);
+    let other = &reloaded.get_sheet_by_name("Sheet2").unwrap().tables[0];
+    assert_eq!(other.columns[0].name, "Column1");
+}
+
 /// Value filters ("show only these entries") are the common case.
 #[test]
 fn autofilter_value_criteria_survive_roundtrip() {
@@ -262,3 +374,493 @@ fn autofilter_without_criteria_roundtrips_as_range() {
     assert!(af.columns.is_empty());
     assert_eq!(af.sort_column, None);
 }
+
+/// Comment boxes were always written hidden at Excel's default size, so
+/// review notes meant to stay on screen only showed on hover.
+#[test]
+fn comment_shape_survives_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Sheet1".to_string())).unwrap();
+    ws.set_cell_comment(2, 2, "Check this total".to_string());
+    ws.set_comment_shape(
+        2,
+        2,
+        CommentShape::new()
+            .with_visible(true)
+            .with_size(200.0, 100.0)
+            .with_anchor(1, 4),
+    );
+    ws.set_cell_comment(5, 1, "Default box".to_string());
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut vml = String::new();
+    std::io::Read::read_to_string(
+        &mut zip.by_name("xl/drawings/vmlDrawing1.vml").unwrap(),
+        &mut vml,
+    )
+    .unwrap();
+    assert!(vml.contains("width:200pt;height:100pt"));
+    assert!(vml.contains("visibility:visible"));
+    assert!(vml.contains("<x:Anchor>3, 0, 0, 0, 7, 11, 6, 13</x:Anchor>"));
+    assert_eq!(vml.matches("<x:Visible/>").count(), 1);
+
+    let reloaded = Workbook::load_from_bytes(&saved).unwrap();
+    let ws = reloaded.get_sheet_by_name("Sheet1").unwrap();
+    assert_eq!(
+        ws.get_comment_shape(2, 2),
+        Some(
+            &CommentShape::new()
+                .with_visible(true)
+                .with_size(200.0, 100.0)
+                .with_anchor(1, 4)
+        )
+    );
+    assert_eq!(ws.get_comment_shape(5, 1), None);
+}
+
+#[test]
+fn sheet_view_and_tab_color_survive_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("View".to_string())).unwrap();
+    ws.sheet_view = SheetView::new()
+        .with_show_grid_lines(false)
+        .with_zoom_scale(85)
+        .with_right_to_left(true)
+        .with_view(SheetViewType::PageBreakPreview);
+    ws.sheet_properties = SheetProperties::new().with_tab_color("FF0000");
+    ws.set_freeze_panes(Some("B2".to_string()));
+    wb.create_sheet(Some("Plain".to_string())).unwrap();
+
+    let loaded = roundtrip(&wb);
+    let ws = loaded.get_sheet_by_name("View").unwrap();
+    assert!(!ws.sheet_view.show_grid_lines);
+    assert_eq!(ws.sheet_view.zoom_scale, 85);
+    assert!(ws.sheet_view.right_to_left);
+    assert_eq!(ws.sheet_view.view, SheetViewType::PageBreakPreview);
+    assert_eq!(ws.freeze_panes.as_deref(), Some("B2"));
+    let tab = ws.sheet_properties.tab_color.as_ref().unwrap();
+    assert_eq!(tab.argb().as_deref(), Some("FFFF0000"));
+
+    let plain = loaded.get_sheet_by_name("Plain").unwrap();
+    assert_eq!(plain.sheet_view, SheetView::default());
+    assert!(plain.sheet_properties.tab_color.is_none());
+}
+
+/// The scroll position and selection were not written, so every generated
+/// sheet opened at A1, and loading read a scrolled frozen pane's first
+/// visible cell as the freeze cell.
+#[test]
+fn scroll_position_and_selection_survive_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Frozen".to_string())).unwrap();
+    ws.set_freeze_panes(Some("A2".to_string()));
+    ws.scroll_to("a40").unwrap();
+    let ws = wb.create_sheet(Some("Plain".to_string())).unwrap();
+    ws.sheet_view = SheetView::new()
+        .with_top_left_cell("C10")
+        .with_selection("D12", "D12:F14 H1");
+    let ws = wb.create_sheet(Some("Clamped".to_string())).unwrap();
+    ws.set_freeze_panes(Some("B3".to_string()));
+    ws.scroll_to("A1").unwrap();
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut read = |name: &str| {
+        let mut text = String::new();
+        std::io::Read::read_to_string(&mut zip.by_name(name).unwrap(), &mut text).unwrap();
+        text
+    };
+    let frozen = read("xl/worksheets/sheet1.xml");
+    assert!(frozen.contains(
+        r#"<pane ySplit="1" topLeftCell="A40" activePane="bottomLeft" state="frozen"/>"#
+    ));
+    assert!(frozen.contains(r#"<selection pane="bottomLeft" activeCell="A40" sqref="A40"/>"#));
+    let plain = read("xl/worksheets/sheet2.xml");
+    assert!(plain.contains(r#"topLeftCell="C10""#));
+    assert!(plain.contains(r#"<selection activeCell="D12" sqref="D12:F14 H1"/>"#));
+    let clamped = read("xl/worksheets/sheet3.xml");
+    assert!(
+        clamped.contains(r#"topLeftCell="B3""#),
+        "scroll cannot enter the frozen panes"
+    );
+
+    let loaded = Workbook::load_from_bytes(&saved).unwrap();
+    let ws = loaded.get_sheet_by_name("Frozen").unwrap();
+    assert_eq!(ws.freeze_panes.as_deref(), Some("A2"));
+    assert_eq!(ws.sheet_view.top_left_cell.as_deref(), Some("A40"));
+    assert_eq!(ws.sheet_view.active_cell.as_deref(), Some("A40"));
+    let ws = loaded.get_sheet_by_name("Plain").unwrap();
+    assert_eq!(
+        ws.sheet_view,
+        SheetView::new()
+            .with_top_left_cell("C10")
+            .with_selection("D12", "D12:F14 H1")
+    );
+    let ws = loaded.get_sheet_by_name("Clamped").unwrap();
+    assert_eq!(ws.freeze_panes.as_deref(), Some("B3"));
+    assert_eq!(ws.sheet_view.top_left_cell, None);
+    assert!(rustypyxl::Worksheet::new("S").scroll_to("nope").is_err());
+}
+
+/// Print areas and titles live in workbook-level defined names; they have to
+/// come back onto the sheet, or a load/save writes them twice. Fit-to-page
+/// counts need `fitToPage` in sheetPr, or Excel ignores them.
+#[test]
+fn page_setup_and_print_names_survive_roundtrip() {
+    use rustypyxl::pagesetup::{Orientation, PageMargins, PageSetup, PaperSize, PrintTitles};
+
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Print Me".to_string())).unwrap();
+    ws.set_cell_value(1, 1, CellValue::from("x"));
+    let mut ps = PageSetup::new()
+        .with_paper_size(PaperSize::A4)
+        .with_orientation(Orientation::Landscape)
+        .with_margins(PageMargins::narrow())
+        .with_print_area("A1:D20,F1:G5")
+        .center_on_page();
+    ps.fit_to_width = Some(1);
+    ps.fit_to_height = Some(0);
+    ps.print_titles = PrintTitles::default().with_rows("1:2").with_cols("A:A");
+    ws.set_page_setup(ps);
+    wb.create_sheet(Some("Plain".to_string())).unwrap();
+
+    let bytes = wb.save_to_bytes().unwrap();
+    let loaded = Workbook::load_from_bytes(&bytes).unwrap();
+    assert!(loaded.named_ranges.is_empty(), "{:?}", loaded.named_ranges);
+    let ps = loaded
+        .get_sheet_by_name("Print Me")
+        .unwrap()
+        .page_setup
+        .as_ref()
+        .unwrap();
+    assert_eq!(ps.paper_size, PaperSize::A4);
+    assert_eq!(ps.orientation, Orientation::Landscape);
+    assert_eq!(ps.margins.left, 0.25);
+    assert_eq!(ps.print_area.as_deref(), Some("A1:D20,F1:G5"));
+    assert_eq!(ps.print_titles.rows.as_deref(), Some("1:2"));
+    assert_eq!(ps.print_titles.cols.as_deref(), Some("A:A"));
+    assert_eq!((ps.fit_to_width, ps.fit_to_height), (Some(1), Some(0)));
+    assert!(ps.center_horizontally && ps.center_vertically);
+
+    // Saving again writes each name once.
+    let again = roundtrip(&loaded);
+    let ps = again
+        .get_sheet_by_name("Print Me")
+        .unwrap()
+        .page_setup
+        .as_ref()
+        .unwrap();
+    assert_eq!(ps.print_area.as_deref(), Some("A1:D20,F1:G5"));
+    assert!(again.named_ranges.is_empty());
+}
+
+/// Columns of equal width are written as one `<col min max>` range and load
+/// back as a single span, not one entry per column.
+#[test]
+fn equal_column_widths_written_as_ranges() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Wide".to_string())).unwrap();
+    ws.set_column_widths(1, 16384, 9.5);
+    for col in 3..=5 {
+        ws.set_column_width(col, 20.0);
+    }
+    ws.set_column_width(8, 9.5);
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut sheet = String::new();
+    std::io::Read::read_to_string(
+        &mut zip.by_name("xl/worksheets/sheet1.xml").unwrap(),
+        &mut sheet,
+    )
+    .unwrap();
+    assert_eq!(sheet.matches("<col ").count(), 3);
+    assert!(sheet.contains(r#"<col min="1" max="2" width="9.5""#));
+    assert!(sheet.contains(r#"<col min="3" max="5" width="20""#));
+    assert!(sheet.contains(r#"<col min="6" max="16384" width="9.5""#));
+
+    let loaded = Workbook::load_from_bytes(&saved).unwrap();
+    let ws = loaded.get_sheet_by_name("Wide").unwrap();
+    assert_eq!(ws.column_dimensions.spans().count(), 3);
+    assert_eq!(ws.get_column_width(4), Some(20.0));
+    assert_eq!(ws.get_column_width(16384), Some(9.5));
+}
+
+/// Grouped rows and columns carry outlineLevel/hidden/collapsed, and the
+/// deepest level goes on sheetFormatPr so Excel sizes the outline gutter.
+#[test]
+fn row_and_column_groups_survive_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Outline".to_string())).unwrap();
+    ws.set_cell_value(1, 1, "Region");
+    ws.group_rows(5, 15, 1, true).unwrap();
+    ws.group_rows(8, 9, 2, true).unwrap();
+    ws.set_column_widths(2, 6, 14.0);
+    ws.group_columns(3, 4, 1, false).unwrap();
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut sheet = String::new();
+    std::io::Read::read_to_string(
+        &mut zip.by_name("xl/worksheets/sheet1.xml").unwrap(),
+        &mut sheet,
+    )
+    .unwrap();
+    assert!(sheet.contains(r#"outlineLevelRow="2""#));
+    assert!(sheet.contains(r#"outlineLevelCol="1""#));
+    assert!(sheet.contains(r#"<row r="5" hidden="1" outlineLevel="1">"#));
+    assert!(sheet.contains(r#"<row r="16" collapsed="1">"#));
+    assert!(sheet.contains(r#"<col min="3" max="4" width="14" customWidth="1" outlineLevel="1"/>"#));
+
+    let loaded = Workbook::load_from_bytes(&saved).unwrap();
+    let ws = loaded.get_sheet_by_name("Outline").unwrap();
+    assert_eq!(
+        ws.row_props,
+        wb.get_sheet_by_name("Outline").unwrap().row_props
+    );
+    assert_eq!(ws.row_props(8).outline_level, 2);
+    assert!(ws.row_props(10).collapsed);
+    assert!(ws.row_props(16).collapsed);
+    assert_eq!(ws.column_props(4).outline_level, 1);
+    assert_eq!(ws.column_props(5).outline_level, 0);
+    assert_eq!(ws.get_column_width(5), Some(14.0));
+}
+
+/// Hidden rows and columns outside any group, and bestFit on auto-fitted
+/// columns, are written next to the widths and heights and read back.
+#[test]
+fn hidden_rows_columns_and_best_fit_survive_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Hidden".to_string())).unwrap();
+    ws.set_cell_value(1, 1, "Quarterly revenue by region");
+    ws.set_row_height(2, 24.0);
+    ws.set_row_hidden(2, true);
+    ws.set_row_hidden(40, true);
+    ws.set_column_hidden(3, true);
+    ws.auto_fit_column(1).unwrap();
+
+    let loaded = roundtrip(&wb);
+    let ws = loaded.get_sheet_by_name("Hidden").unwrap();
+    assert!(ws.is_row_hidden(2));
+    assert_eq!(ws.get_row_height(2), Some(24.0));
+    assert!(ws.is_row_hidden(40));
+    assert!(!ws.is_row_hidden(3));
+    assert!(ws.is_column_hidden(3));
+    assert_eq!(ws.get_column_width(3), None);
+    assert!(ws.column_props(1).best_fit);
+    assert!(!ws.column_props(1).hidden);
+}
+
+/// Summary placement is written as `<outlinePr>` and read back, and a
+/// collapsed outline keeps its hidden rows and the collapsed summary row.
+#[test]
+fn outline_summary_settings_and_collapse_survive_roundtrip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Report".to_string())).unwrap();
+    ws.sheet_properties = SheetProperties::new().with_summary_below(false);
+    ws.group_rows(3, 6, 1, false).unwrap();
+    ws.group_columns(2, 3, 1, false).unwrap();
+    ws.collapse_outline(1).unwrap();
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut sheet = String::new();
+    std::io::Read::read_to_string(
+        &mut zip.by_name("xl/worksheets/sheet1.xml").unwrap(),
+        &mut sheet,
+    )
+    .unwrap();
+    assert!(sheet.contains(r#"<outlinePr summaryBelow="0" summaryRight="1"/>"#));
+
+    let loaded = Workbook::load_from_bytes(&saved).unwrap();
+    let ws = loaded.get_sheet_by_name("Report").unwrap();
+    assert!(!ws.sheet_properties.summary_below);
+    assert!(ws.sheet_properties.summary_right);
+    assert!(ws.row_props(2).collapsed);
+    assert!(ws.is_row_hidden(4));
+    assert!(ws.is_column_hidden(2));
+    assert!(ws.column_props(4).collapsed);
+}
+
+/// Strings left out of the shared table by a `MinRepeats` threshold are
+/// written inline and read back with the same values.
+#[test]
+fn shared_string_threshold_inlines_unique_values() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Orders".to_string())).unwrap();
+    for row in 1..=4 {
+        ws.set_cell_value(row, 1, "Pending");
+        ws.set_cell_value(row, 2, format!("note {row}"));
+    }
+    wb.set_shared_strings(rustypyxl::SharedStrings::MinRepeats(2));
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut read = |name: &str| {
+        let mut text = String::new();
+        std::io::Read::read_to_string(&mut zip.by_name(name).unwrap(), &mut text).unwrap();
+        text
+    };
+    let sst = read("xl/sharedStrings.xml");
+    assert!(sst.contains(r#"count="4" uniqueCount="1""#));
+    let sheet = read("xl/worksheets/sheet1.xml");
+    assert_eq!(sheet.matches(r#"t="inlineStr""#).count(), 4);
+
+    let loaded = Workbook::load_from_bytes(&saved).unwrap();
+    let ws = loaded.get_sheet_by_name("Orders").unwrap();
+    assert_eq!(ws.get_cell_value(3, 1), Some(&CellValue::from("Pending")));
+    assert_eq!(ws.get_cell_value(3, 2), Some(&CellValue::from("note 3")));
+}
+
+#[test]
+fn named_cell_styles_survive_roundtrip() {
+    let mut wb = Workbook::new();
+    wb.create_sheet(Some("S".to_string())).unwrap();
+    let headline = CellStyle::new().with_font(Font::new().with_bold(true).with_size(16.0));
+    wb.add_named_style(NamedStyle::new("Report Title", headline))
+        .unwrap();
+    wb.set_cell_named_style("S", 1, 1, "Report Title").unwrap();
+    wb.set_cell_named_style("S", 2, 1, "Heading 1").unwrap();
+    wb.set_cell_named_style("S", 3, 1, "Good").unwrap();
+    assert!(wb.set_cell_named_style("S", 4, 1, "Nope").is_err());
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut styles = String::new();
+    std::io::Read::read_to_string(&mut zip.by_name("xl/styles.xml").unwrap(), &mut styles).unwrap();
+    assert!(styles.contains(r#"<cellStyle name="Report Title" xfId="1"/>"#));
+    assert!(styles.contains(r#"<cellStyle name="Heading 1" xfId="2" builtinId="16"/>"#));
+
+    let back = roundtrip(&wb);
+    let names: Vec<&str> = back
+        .styles
+        .named_styles
+        .iter()
+        .map(|ns| ns.name.as_str())
+        .collect();
+    assert_eq!(names, ["Normal", "Report Title", "Heading 1", "Good"]);
+    let ws = back.get_sheet_by_name("S").unwrap();
+    let a1 = ws.get_cell(1, 1).unwrap();
+    assert_eq!(a1.named_style(), "Report Title");
+    let font = a1.style.as_ref().unwrap().font.as_ref().unwrap();
+    assert!(font.bold);
+    assert_eq!(font.size, Some(16.0));
+    assert_eq!(ws.get_cell(2, 1).unwrap().named_style(), "Heading 1");
+    assert_eq!(ws.get_cell(3, 1).unwrap().named_style(), "Good");
+}
+
+/// Comments were saved under a fixed author and loaded as bare text, so
+/// authors, formatting and threaded conversations were lost.
+#[test]
+fn comments_keep_authors_formatting_and_threads() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("S".to_string())).unwrap();
+    ws.set_cell_comment(1, 1, Comment::new("Check totals").with_author("Ann"));
+    ws.set_cell_comment(
+        2,
+        1,
+        Comment::new("")
+            .with_rich_text(RichText::new(vec![
+                TextRun::formatted(
+                    "Bob:",
+                    RunFont {
+                        bold: true,
+                        ..Default::default()
+                    },
+                ),
+                TextRun::plain(" see sheet 2"),
+            ]))
+            .with_author("Bob")
+            .with_size(200.0, 80.0),
+    );
+    ws.add_threaded_comment(3, 2, "Ann", "Why is this negative?")
+        .unwrap();
+    ws.add_threaded_comment(3, 2, "Cy", "Refund").unwrap();
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    for part in [
+        "xl/threadedComments/threadedComment1.xml",
+        "xl/persons/person.xml",
+    ] {
+        assert!(zip.by_name(part).is_ok(), "{} missing", part);
+    }
+    let mut vml = String::new();
+    std::io::Read::read_to_string(
+        &mut zip.by_name("xl/drawings/vmlDrawing1.vml").unwrap(),
+        &mut vml,
+    )
+    .unwrap();
+    assert!(vml.contains("width:200pt;height:80pt"));
+
+    let back = roundtrip(&wb);
+    let ws = back.get_sheet_by_name("S").unwrap();
+    let comment = |row, col| ws.get_cell(row, col).unwrap().comment.clone().unwrap();
+
+    assert_eq!(comment(1, 1).text, "Check totals");
+    assert_eq!(comment(1, 1).author.as_deref(), Some("Ann"));
+
+    let rich = comment(2, 1);
+    assert_eq!(rich.text, "Bob: see sheet 2");
+    assert_eq!(rich.author.as_deref(), Some("Bob"));
+    let runs = &rich.rich_text.as_ref().unwrap().runs;
+    assert!(runs[0].font.as_ref().unwrap().bold);
+    assert_eq!(ws.get_comment_shape(2, 1).unwrap().width, 200.0);
+
+    let threaded = comment(3, 2);
+    assert_eq!(threaded.text, "Why is this negative?");
+    assert_eq!(threaded.author.as_deref(), Some("Ann"));
+    assert_eq!(
+        threaded.thread,
+        [
+            ThreadedComment::new("Ann", "Why is this negative?"),
+            ThreadedComment::new("Cy", "Refund"),
+        ]
+    );
+    assert!(!back
+        .preserved
+        .parts
+        .iter()
+        .any(|p| p.path.contains("person")));
+}
+
+/// Hyperlinks were stored as bare strings, so tooltips and display text were
+/// dropped on load and never written.
+#[test]
+fn hyperlinks_keep_relationships_tooltips_and_display() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("S".to_string())).unwrap();
+    ws.set_cell_hyperlink(
+        1,
+        1,
+        Hyperlink::external("https://example.com/a?b=1&c=2").with_tooltip("Open the site"),
+    );
+    ws.set_hyperlink(2, 1, "#'Other Sheet'!B2", Some("Go"), false);
+    ws.set_cell_hyperlink(3, 1, "mailto:team@example.com");
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut zip = zip::ZipArchive::new(std::io::Cursor::new(&saved)).unwrap();
+    let mut rels = String::new();
+    std::io::Read::read_to_string(
+        &mut zip.by_name("xl/worksheets/_rels/sheet1.xml.rels").unwrap(),
+        &mut rels,
+    )
+    .unwrap();
+    assert!(rels.contains("Target=\"https://example.com/a?b=1&amp;c=2\" TargetMode=\"External\""));
+    assert!(rels.contains("Target=\"mailto:team@example.com\""));
+
+    let back = roundtrip(&wb);
+    let ws = back.get_sheet_by_name("S").unwrap();
+    let link = |row| ws.get_cell(row, 1).unwrap().hyperlink.clone().unwrap();
+
+    assert!(link(1).is_external);
+    assert_eq!(link(1).target, "https://example.com/a?b=1&c=2");
+    assert_eq!(link(1).tooltip.as_deref(), Some("Open the site"));
+
+    assert!(!link(2).is_external);
+    assert_eq!(link(2).target, "'Other Sheet'!B2");
+    assert_eq!(link(2).display.as_deref(), Some("Go"));
+
+    assert_eq!(link(3).address(), "mailto:team@example.com");
+}
@@ -0,0 +1,86 @@
+//! Format detection on real packages, and the loader errors it drives.
+
+use rustypyxl::{detect_format, detect_format_file, CellValue, FileFormat, Workbook};
+use std::io::{Cursor, Write};
+use zip::{write::SimpleFileOptions, ZipWriter};
+
+fn saved_xlsx() -> Vec<u8> {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("S".to_string())).unwrap();
+    ws.set_cell_value(1, 1, CellValue::from("x"));
+    wb.save_to_bytes().unwrap()
+}
+
+fn zip_of(parts: &[(&str, &str)]) -> Vec<u8> {
+    let mut out = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in parts {
+        out.start_file(*name, SimpleFileOptions::default()).unwrap();
+        out.write_all(content.as_bytes()).unwrap();
+    }
+    out.finish().unwrap().into_inner()
+}
+
+#[test]
+fn saved_workbooks_are_xlsx() {
+    let bytes = saved_xlsx();
+    let info = detect_format(&bytes);
+    assert_eq!(info.format, FileFormat::Xlsx);
+    assert!(info.is_loadable() && !info.has_macros && !info.template);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("book.xlsx");
+    std::fs::write(&path, &bytes).unwrap();
+    assert_eq!(detect_format_file(path.to_str().unwrap()).unwrap(), info);
+}
+
+#[test]
+fn packages_are_told_apart_by_content_types() {
+    let xltm = zip_of(&[
+        (
+            "[Content_Types].xml",
+            r#"<Types><Override PartName="/xl/workbook.xml" ContentType="application/vnd.ms-excel.template.macroEnabled.main+xml"/></Types>"#,
+        ),
+        ("xl/workbook.xml", "<workbook/>"),
+        ("xl/vbaProject.bin", ""),
+    ]);
+    let info = detect_format(&xltm);
+    assert_eq!(info.format, FileFormat::Xlsm);
+    assert!(info.has_macros && info.template);
+
+    let xlsb = zip_of(&[("[Content_Types].xml", "<Types/>"), ("xl/workbook.bin", "")]);
+    assert_eq!(detect_format(&xlsb).format, FileFormat::Xlsb);
+
+    let ods = zip_of(&[
+        ("mimetype", "application/vnd.oasis.opendocument.spreadsheet"),
+        ("content.xml", "<office:document-content/>"),
+    ]);
+    assert_eq!(detect_format(&ods).format, FileFormat::Ods);
+
+    let other = zip_of(&[("readme.txt", "hello")]);
+    assert_eq!(detect_format(&other).format, FileFormat::Zip);
+}
+
+#[test]
+fn loaders_name_the_format_they_cannot_read() {
+    let ods = zip_of(&[
+        ("mimetype", "application/vnd.oasis.opendocument.spreadsheet"),
+        ("content.xml", "<office:document-content/>"),
+    ]);
+    let err = Workbook::load_from_bytes(&ods).err().unwrap().to_string();
+    assert!(err.contains("OpenDocument"), "{err}");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.xlsx");
+    std::fs::write(&path, "id,name\n1,bolt\n").unwrap();
+    let err = Workbook::load(path.to_str().unwrap())
+        .err()
+        .unwrap()
+        .to_string();
+    assert!(err.contains("CSV"), "{err}");
+
+    // A broken workbook keeps its own error.
+    let mut bytes = saved_xlsx();
+    bytes.truncate(bytes.len() / 2);
+    let err = Workbook::load_from_bytes(&bytes).err().unwrap().to_string();
+    assert!(!err.contains("only xlsx"), "{err}");
+}
@@ -105,7 +105,9 @@ fn chart_and_image_share_one_drawing() {
     wb.create_sheet(Some("S".to_string())).unwrap();
     let mut chart = Chart::column();
     chart.add_series(ChartSeries::new("S!$A$1:$A$3"));
-    wb.get_sheet_by_name_mut("S").unwrap().add_chart(chart);
+    wb.get_sheet_by_name_mut("S")
+        .unwrap()
+        .add_chart(chart, "A1");
     let img = Image::from_bytes(PNG_1X1.to_vec(), ImageAnchor::one_cell("E1")).unwrap();
     wb.get_sheet_by_name_mut("S").unwrap().add_image(img);
 
@@ -423,8 +423,8 @@ fn test_merged_cells() {
     wb.create_sheet(Some("Test".to_string())).unwrap();
 
     let ws = wb.get_sheet_by_name_mut("Test").unwrap();
-    ws.merge_cells("A1:C1");
-    ws.merge_cells("A2:A5");
+    ws.merge_cells("A1:C1").unwrap();
+    ws.merge_cells("A2:A5").unwrap();
 
     assert_eq!(ws.merged_cells.len(), 2);
 
@@ -480,7 +480,7 @@ fn test_worksheet_element_order_follows_schema() {
 
     let ws = wb.get_sheet_by_name_mut("Test").unwrap();
     ws.enable_protection(Some("secret".to_string()));
-    ws.merge_cells("B1:C1");
+    ws.merge_cells("B1:C1").unwrap();
     ws.add_data_validation(3, 1, DataValidation::default());
     let mut ps = PageSetup::new();
     ps.print_gridlines = true;
@@ -523,13 +523,15 @@ fn test_worksheet_element_order_follows_schema() {
         sheet_xml
     );
 
-    // The password attribute must hold the legacy verifier hash, not plaintext.
-    assert!(!sheet_xml.contains("password=\"secret\""));
-    assert!(
-        sheet_xml.contains("password=\"DAA7\""),
-        "expected hashed password in {}",
-        sheet_xml
-    );
+    // The password is stored as a SHA-512 verifier (the legacy one without
+    // the encrypt feature), never as plaintext.
+    assert!(!sheet_xml.contains("secret"));
+    let hashed = if cfg!(feature = "encrypt") {
+        sheet_xml.contains("algorithmName=\"SHA-512\"") && sheet_xml.contains("hashValue=\"")
+    } else {
+        sheet_xml.contains("password=\"DAA7\"")
+    };
+    assert!(hashed, "expected hashed password in {}", sheet_xml);
 
     fs::remove_file(&path).ok();
 }
@@ -700,13 +702,17 @@ fn test_roundtrip_preserves_structure() {
     assert_eq!(ps.margins.left, 1.5);
     assert_eq!(ps.margins.top, 2.0);
 
-    let link = main.get_cell(3, 1).and_then(|c| c.hyperlink.clone());
+    let link = main
+        .get_cell(3, 1)
+        .and_then(|c| c.hyperlink.as_ref().map(|h| h.address()));
     assert_eq!(
         link.as_deref(),
         Some("https://example.com/page?a=1&b=2"),
         "external hyperlink lost"
     );
-    let internal = main.get_cell(4, 1).and_then(|c| c.hyperlink.clone());
+    let internal = main
+        .get_cell(4, 1)
+        .and_then(|c| c.hyperlink.as_ref().map(|h| h.address()));
     assert_eq!(
         internal.as_deref(),
         Some("#Secret!A1"),
@@ -734,7 +740,7 @@ fn test_roundtrip_preserves_structure() {
         wb3.get_sheet_by_name("Main")
             .unwrap()
             .get_cell(3, 1)
-            .and_then(|c| c.hyperlink.clone())
+            .and_then(|c| c.hyperlink.as_ref().map(|h| h.address()))
             .as_deref(),
         Some("https://example.com/page?a=1&b=2")
     );
@@ -913,6 +919,7 @@ fn test_roundtrip_names_formulas_headers_comments() {
         range: "S2!$C$1".to_string(),
         local_sheet_id: Some(1),
         hidden: true,
+        comment: None,
     });
 
     {
@@ -1039,8 +1046,9 @@ fn test_roundtrip_names_formulas_headers_comments() {
 
     assert_eq!(
         ws2.get_cell(3, 1)
-            .and_then(|c| c.comment.clone())
-            .as_deref(),
+            .as_deref()
+            .and_then(|c| c.comment.as_ref())
+            .map(|c| c.text.as_str()),
         Some("a comment")
     );
 
@@ -11,9 +11,6 @@ const DEFAULT_WORKBOOK_PR: &str = "<workbookPr/>";
 
 /// Assemble a minimal single-sheet xlsx around the given sheet XML body.
 fn build_xlsx(sheet_xml: &str, shared_strings: Option<&str>, workbook_pr: &str) -> Vec<u8> {
-    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
-    let options = SimpleFileOptions::default();
-
     let workbook_xml = format!(
         r#"<?xml version="1.0" encoding="UTF-8"?>
 <workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"
@@ -22,6 +19,18 @@ fn build_xlsx(sheet_xml: &str, shared_strings: Option<&str>, workbook_pr: &str)
   <sheets><sheet name="Sheet1" sheetId="1" r:id="rId1"/></sheets>
 </workbook>"#
     );
+    let mut parts = vec![("xl/worksheets/sheet1.xml", sheet_xml)];
+    if let Some(sst) = shared_strings {
+        parts.push(("xl/sharedStrings.xml", sst));
+    }
+    build_package(&workbook_xml, &parts)
+}
+
+/// Assemble an xlsx from a workbook.xml whose single sheet is `rId1`
+/// (xl/worksheets/sheet1.xml), plus the given parts.
+fn build_package(workbook_xml: &str, parts: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
 
     let rels = r#"<?xml version="1.0" encoding="UTF-8"?>
 <Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
@@ -44,13 +53,18 @@ fn build_xlsx(sheet_xml: &str, shared_strings: Option<&str>, workbook_pr: &str)
         zip.write_all(body.as_bytes()).unwrap();
     };
 
-    add("[Content_Types].xml", content_types);
+    // Callers may supply their own content types or workbook rels.
+    let supplied = |name: &str| parts.iter().any(|(part, _)| *part == name);
+    if !supplied("[Content_Types].xml") {
+        add("[Content_Types].xml", content_types);
+    }
     add("_rels/.rels", root_rels);
-    add("xl/workbook.xml", &workbook_xml);
-    add("xl/_rels/workbook.xml.rels", rels);
-    add("xl/worksheets/sheet1.xml", sheet_xml);
-    if let Some(sst) = shared_strings {
-        add("xl/sharedStrings.xml", sst);
+    add("xl/workbook.xml", workbook_xml);
+    if !supplied("xl/_rels/workbook.xml.rels") {
+        add("xl/_rels/workbook.xml.rels", rels);
+    }
+    for (name, body) in parts {
+        add(name, body);
     }
 
     zip.finish().unwrap().into_inner()
@@ -224,6 +238,110 @@ fn namespace_prefixed_elements_are_parsed() {
     assert_eq!(ws.get_cell_value(1, 2), Some(&CellValue::Number(7.0)));
 }
 
+/// Prefixing is not limited to cell data: workbook.xml, styles, merges, and
+/// comments from such producers are prefixed the same way.
+#[test]
+fn namespace_prefixed_workbook_styles_and_comments_are_parsed() {
+    let workbook = r#"<?xml version="1.0" encoding="UTF-8"?>
+<x:workbook xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main"
+            xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <x:sheets><x:sheet name="Data" sheetId="1" r:id="rId1"/></x:sheets>
+  <x:definedNames><x:definedName name="Total">Data!$B$1</x:definedName></x:definedNames>
+</x:workbook>"#;
+    let styles = r#"<?xml version="1.0" encoding="UTF-8"?>
+<x:styleSheet xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+  <x:numFmts count="1"><x:numFmt numFmtId="164" formatCode="0.000"/></x:numFmts>
+  <x:fonts count="2">
+    <x:font><x:sz val="11"/><x:name val="Calibri"/></x:font>
+    <x:font><x:b/><x:sz val="11"/><x:name val="Calibri"/></x:font>
+  </x:fonts>
+  <x:fills count="3">
+    <x:fill><x:patternFill patternType="none"/></x:fill>
+    <x:fill><x:patternFill patternType="gray125"/></x:fill>
+    <x:fill><x:patternFill patternType="solid"><x:fgColor rgb="FFFFFF00"/></x:patternFill></x:fill>
+  </x:fills>
+  <x:borders count="2">
+    <x:border><x:left/><x:right/><x:top/><x:bottom/><x:diagonal/></x:border>
+    <x:border><x:left/><x:right/><x:top/><x:bottom style="thin"><x:color rgb="FF000000"/></x:bottom><x:diagonal/></x:border>
+  </x:borders>
+  <x:cellXfs count="2">
+    <x:xf numFmtId="0" fontId="0" fillId="0" borderId="0"/>
+    <x:xf numFmtId="164" fontId="1" fillId="2" borderId="1" applyNumberFormat="1" applyFont="1" applyFill="1" applyBorder="1">
+      <x:alignment horizontal="center"/>
+      <x:protection locked="0"/>
+    </x:xf>
+  </x:cellXfs>
+</x:styleSheet>"#;
+    let sheet = r#"<x:worksheet xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+  <x:sheetData>
+    <x:row r="1"><x:c r="A1" s="1"><x:v>1.5</x:v></x:c><x:c r="B1"><x:v>2</x:v></x:c></x:row>
+  </x:sheetData>
+  <x:mergeCells count="1"><x:mergeCell ref="C1:D2"/></x:mergeCells>
+</x:worksheet>"#;
+    let sheet_rels = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments" Target="../comments1.xml"/>
+</Relationships>"#;
+    let comments = r#"<?xml version="1.0" encoding="UTF-8"?>
+<x:comments xmlns:x="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+  <x:authors><x:author>me</x:author></x:authors>
+  <x:commentList>
+    <x:comment ref="B1" authorId="0"><x:text><x:t>Check this</x:t></x:text></x:comment>
+  </x:commentList>
+</x:comments>"#;
+
+    let wb = Workbook::load_from_bytes(&build_package(
+        workbook,
+        &[
+            ("xl/worksheets/sheet1.xml", sheet),
+            ("xl/styles.xml", styles),
+            ("xl/worksheets/_rels/sheet1.xml.rels", sheet_rels),
+            ("xl/comments1.xml", comments),
+        ],
+    ))
+    .unwrap();
+
+    assert_eq!(wb.sheet_names(), ["Data"]);
+    assert_eq!(wb.named_ranges.len(), 1);
+    let ws = wb.get_sheet_by_name("Data").unwrap();
+
+    let style = ws.get_cell(1, 1).unwrap().style.clone().unwrap();
+    assert!(style.font.as_ref().unwrap().bold);
+    let fill = style.fill.as_ref().unwrap();
+    assert_eq!(fill.pattern_type.as_deref(), Some("solid"));
+    assert_eq!(
+        fill.fg_color.as_ref().unwrap().rgb.as_deref(),
+        Some("#FFFFFF00")
+    );
+    assert_eq!(
+        style
+            .border
+            .as_ref()
+            .unwrap()
+            .bottom
+            .as_ref()
+            .unwrap()
+            .style,
+        "thin"
+    );
+    assert_eq!(style.number_format.as_deref(), Some("0.000"));
+    assert_eq!(
+        style.alignment.as_ref().unwrap().horizontal.as_deref(),
+        Some("center")
+    );
+    assert!(!style.protection.as_ref().unwrap().locked);
+
+    assert_eq!(ws.merged_cells, vec![("C1".to_string(), "D2".to_string())]);
+    assert_eq!(
+        ws.get_cell(1, 2)
+            .unwrap()
+            .comment
+            .as_ref()
+            .map(|c| c.text.as_str()),
+        Some("Check this")
+    );
+}
+
 /// The cached result of a formula is echoed back on save; it must not be
 /// reformatted (an integer `5` would come back out as `5.0`).
 #[test]
@@ -294,3 +412,104 @@ fn date1904_flag_round_trips() {
     let reloaded_1900 = Workbook::load_from_bytes(&wb_1900.save_to_bytes().unwrap()).unwrap();
     assert!(!reloaded_1900.date1904);
 }
+
+/// Dialog and macro sheets load as opaque placeholders that keep their slot
+/// in the tab order, and are written back unchanged apart from shared
+/// strings, which are inlined because the saved string table is rebuilt.
+#[test]
+fn dialog_and_macro_sheets_are_preserved() {
+    use rustypyxl::opaque_sheet::OpaqueSheetKind;
+
+    let workbook_xml = r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <sheets>
+    <sheet name="Dialog1" sheetId="1" r:id="rId1"/>
+    <sheet name="Macro1" sheetId="2" r:id="rId2"/>
+    <sheet name="Data" sheetId="3" r:id="rId3"/>
+  </sheets>
+  <definedNames>
+    <definedName name="Local" localSheetId="2">Data!$A$1</definedName>
+  </definedNames>
+</workbook>"#;
+    let rels = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/dialogsheet" Target="dialogsheets/sheet1.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.microsoft.com/office/2006/relationships/xlMacrosheet" Target="macrosheets/sheet1.xml"/>
+  <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>
+  <Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings" Target="sharedStrings.xml"/>
+</Relationships>"#;
+    let content_types = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="xml" ContentType="application/xml"/>
+  <Default Extension="bin" ContentType="application/vnd.ms-office.vmlDrawing"/>
+</Types>"#;
+    let dialog = r#"<dialogsheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheetData/><legacyDrawing r:id="rId1"/></dialogsheet>"#;
+    let dialog_rels = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/vmlDrawing" Target="../drawings/dialog.bin"/>
+</Relationships>"#;
+    let macro_sheet = r#"<xm:macrosheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:xm="http://schemas.microsoft.com/office/excel/2006/main"><sheetData><row r="1"><c r="A1" t="s"><v>0</v></c></row></sheetData></xm:macrosheet>"#;
+    let sheet = r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData><row r="1"><c r="A1" t="s"><v>1</v></c></row></sheetData></worksheet>"#;
+    let shared = r#"<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="2" uniqueCount="2"><si><t>=RETURN(1)</t></si><si><t>values</t></si></sst>"#;
+
+    let bytes = build_package(
+        workbook_xml,
+        &[
+            ("[Content_Types].xml", content_types),
+            ("xl/_rels/workbook.xml.rels", rels),
+            ("xl/dialogsheets/sheet1.xml", dialog),
+            ("xl/dialogsheets/_rels/sheet1.xml.rels", dialog_rels),
+            ("xl/drawings/dialog.bin", "<xml>shapes</xml>"),
+            ("xl/macrosheets/sheet1.xml", macro_sheet),
+            ("xl/worksheets/sheet1.xml", sheet),
+            ("xl/sharedStrings.xml", shared),
+        ],
+    );
+
+    let check = |wb: &Workbook| {
+        assert_eq!(wb.sheet_names(), vec!["Dialog1", "Macro1", "Data"]);
+        let dialog = wb.get_sheet_by_name("Dialog1").unwrap();
+        let opaque = dialog.opaque.as_ref().unwrap();
+        assert_eq!(opaque.kind, OpaqueSheetKind::Dialogsheet);
+        assert_eq!(opaque.rels.len(), 1);
+        assert_eq!(
+            opaque.rels[0].part.as_ref().unwrap().0,
+            b"<xml>shapes</xml>".to_vec()
+        );
+
+        let macro_sheet = wb.get_sheet_by_name("Macro1").unwrap();
+        let opaque = macro_sheet.opaque.as_ref().unwrap();
+        assert_eq!(opaque.kind, OpaqueSheetKind::Macrosheet);
+        let xml = String::from_utf8(opaque.xml.clone()).unwrap();
+        assert!(xml.contains("t=\"inlineStr\""), "{xml}");
+        assert!(xml.contains("=RETURN(1)"), "{xml}");
+
+        let data = wb.get_sheet_by_name("Data").unwrap();
+        assert!(data.opaque.is_none());
+        assert_eq!(
+            data.get_cell_value(1, 1),
+            Some(&CellValue::String("values".into()))
+        );
+
+        let local = wb.named_ranges.iter().find(|n| n.name == "Local").unwrap();
+        assert_eq!(local.local_sheet_id, Some(2));
+    };
+
+    let wb = Workbook::load_from_bytes(&bytes).unwrap();
+    check(&wb);
+
+    let saved = wb.save_to_bytes().unwrap();
+    let mut archive = zip::ZipArchive::new(Cursor::new(saved.clone())).unwrap();
+    let mut content_types = String::new();
+    std::io::Read::read_to_string(
+        &mut archive.by_name("[Content_Types].xml").unwrap(),
+        &mut content_types,
+    )
+    .unwrap();
+    assert!(content_types
+        .contains("application/vnd.openxmlformats-officedocument.spreadsheetml.dialogsheet+xml"));
+    assert!(content_types.contains("application/vnd.ms-excel.macrosheet+xml"));
+    assert!(content_types.contains("application/vnd.ms-office.vmlDrawing"));
+
+    check(&Workbook::load_from_bytes(&saved).unwrap());
+}
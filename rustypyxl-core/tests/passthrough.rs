@@ -0,0 +1,281 @@
+//! Workbook-level parts rustypyxl does not model -- theme, VBA project,
+//! external links, custom XML -- survive a load/save round-trip with their
+//! content types and relationships intact.
+
+use rustypyxl::{CellValue, Color, Theme, Workbook};
+use std::io::{Cursor, Read, Write};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+const WORKBOOK_XML: &str = r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+  <sheets><sheet name="Data" sheetId="1" r:id="rId1"/></sheets>
+  <externalReferences><externalReference r:id="rId5"/></externalReferences>
+  <definedNames><definedName name="Remote">[1]Prices!$A$1</definedName></definedNames>
+</workbook>"#;
+
+const WORKBOOK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>
+  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme" Target="theme/theme1.xml"/>
+  <Relationship Id="rId3" Type="http://schemas.microsoft.com/office/2006/relationships/vbaProject" Target="vbaProject.bin"/>
+  <Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml" Target="../customXml/item1.xml"/>
+  <Relationship Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/externalLink" Target="externalLinks/externalLink1.xml"/>
+  <Relationship Id="rId6" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/calcChain" Target="calcChain.xml"/>
+</Relationships>"#;
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+  <Default Extension="xml" ContentType="application/xml"/>
+  <Default Extension="bin" ContentType="application/vnd.ms-office.vbaProject"/>
+  <Override PartName="/xl/workbook.xml" ContentType="application/vnd.ms-excel.sheet.macroEnabled.main+xml"/>
+  <Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>
+  <Override PartName="/xl/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>
+  <Override PartName="/xl/externalLinks/externalLink1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.externalLink+xml"/>
+  <Override PartName="/customXml/itemProps1.xml" ContentType="application/vnd.openxmlformats-officedocument.customXmlProperties+xml"/>
+</Types>"#;
+
+const EXTERNAL_LINK_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/externalLinkPath" Target="prices.xlsx" TargetMode="External"/>
+</Relationships>"#;
+
+const CUSTOM_XML_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps" Target="itemProps1.xml"/>
+</Relationships>"#;
+
+fn source() -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+    let mut add = |name: &str, body: &[u8]| {
+        zip.start_file(name, options).unwrap();
+        zip.write_all(body).unwrap();
+    };
+    add("[Content_Types].xml", CONTENT_TYPES.as_bytes());
+    add(
+        "_rels/.rels",
+        br#"<?xml version="1.0" encoding="UTF-8"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>
+</Relationships>"#,
+    );
+    add("xl/workbook.xml", WORKBOOK_XML.as_bytes());
+    add("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes());
+    add(
+        "xl/worksheets/sheet1.xml",
+        br#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData><row r="1"><c r="A1"><v>7</v></c></row></sheetData></worksheet>"#,
+    );
+    add("xl/theme/theme1.xml", b"<a:theme name=\"Custom\"/>");
+    add("xl/vbaProject.bin", &[0xD0, 0xCF, 0x11, 0xE0, 0x01, 0x02]);
+    add("xl/calcChain.xml", b"<calcChain/>");
+    add("xl/externalLinks/externalLink1.xml", b"<externalLink/>");
+    add(
+        "xl/externalLinks/_rels/externalLink1.xml.rels",
+        EXTERNAL_LINK_RELS.as_bytes(),
+    );
+    add("customXml/item1.xml", b"<item/>");
+    add("customXml/_rels/item1.xml.rels", CUSTOM_XML_RELS.as_bytes());
+    add("customXml/itemProps1.xml", b"<ds:datastoreItem/>");
+    zip.finish().unwrap().into_inner()
+}
+
+fn read_part(bytes: &[u8], name: &str) -> Option<Vec<u8>> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes.to_vec())).unwrap();
+    let mut file = zip.by_name(name).ok()?;
+    let mut out = Vec::new();
+    file.read_to_end(&mut out).unwrap();
+    Some(out)
+}
+
+fn read_text(bytes: &[u8], name: &str) -> String {
+    String::from_utf8(read_part(bytes, name).unwrap_or_else(|| panic!("{name} missing"))).unwrap()
+}
+
+#[test]
+fn unmodeled_workbook_parts_are_captured() {
+    let wb = Workbook::load_from_bytes(&source()).unwrap();
+    let kept: Vec<&str> = wb.preserved.parts.iter().map(|p| p.path.as_str()).collect();
+    assert_eq!(
+        kept,
+        vec![
+            "customXml/_rels/item1.xml.rels",
+            "customXml/item1.xml",
+            "customXml/itemProps1.xml",
+            "xl/externalLinks/_rels/externalLink1.xml.rels",
+            "xl/externalLinks/externalLink1.xml",
+            "xl/theme/theme1.xml",
+            "xl/vbaProject.bin",
+        ]
+    );
+    assert_eq!(
+        wb.preserved.rels.len(),
+        4,
+        "calcChain and the sheet are not kept"
+    );
+    assert_eq!(
+        wb.preserved.workbook_content_type.as_deref(),
+        Some("application/vnd.ms-excel.sheet.macroEnabled.main+xml")
+    );
+}
+
+#[test]
+fn unmodeled_workbook_parts_survive_save() {
+    let mut wb = Workbook::load_from_bytes(&source()).unwrap();
+    wb.set_cell_value_in_sheet("Data", 2, 1, CellValue::Number(8.0))
+        .unwrap();
+    let saved = wb.save_to_bytes().unwrap();
+
+    assert_eq!(
+        read_part(&saved, "xl/vbaProject.bin").unwrap(),
+        vec![0xD0, 0xCF, 0x11, 0xE0, 0x01, 0x02]
+    );
+    assert_eq!(
+        read_text(&saved, "xl/theme/theme1.xml"),
+        "<a:theme name=\"Custom\"/>"
+    );
+    assert_eq!(
+        read_text(&saved, "xl/externalLinks/_rels/externalLink1.xml.rels"),
+        EXTERNAL_LINK_RELS
+    );
+    assert!(read_part(&saved, "customXml/itemProps1.xml").is_some());
+    assert!(read_part(&saved, "xl/calcChain.xml").is_none());
+
+    let content_types = read_text(&saved, "[Content_Types].xml");
+    assert!(content_types.contains("application/vnd.ms-excel.sheet.macroEnabled.main+xml"));
+    assert!(content_types.contains(
+        r#"PartName="/xl/vbaProject.bin" ContentType="application/vnd.ms-office.vbaProject""#
+    ));
+    assert!(content_types.contains("application/vnd.openxmlformats-officedocument.theme+xml"));
+    assert!(!content_types.contains("_rels/item1.xml.rels"));
+
+    // The external reference cites the renumbered relationship.
+    let rels = read_text(&saved, "xl/_rels/workbook.xml.rels");
+    let workbook = read_text(&saved, "xl/workbook.xml");
+    let link_rel = rels
+        .lines()
+        .find(|l| l.contains("externalLinks/externalLink1.xml"))
+        .unwrap();
+    let id = link_rel
+        .split("Id=\"")
+        .nth(1)
+        .unwrap()
+        .split('"')
+        .next()
+        .unwrap();
+    assert!(workbook.contains(&format!(r#"<externalReference r:id="{id}"/>"#)));
+    assert!(workbook.find("</sheets>").unwrap() < workbook.find("<externalReferences>").unwrap());
+    assert!(rels.contains(r#"Target="../customXml/item1.xml""#));
+
+    // And a second round-trip still carries everything.
+    let again = Workbook::load_from_bytes(&saved).unwrap();
+    assert_eq!(again.preserved.parts.len(), 7);
+    assert_eq!(
+        again
+            .get_sheet_by_name("Data")
+            .unwrap()
+            .get_cell_value(2, 1),
+        Some(&CellValue::Number(8.0))
+    );
+}
+
+#[test]
+fn new_workbooks_preserve_nothing() {
+    let mut wb = Workbook::new();
+    wb.create_sheet(Some("S".to_string())).unwrap();
+    assert!(wb.preserved.is_empty());
+    let saved = wb.save_to_bytes().unwrap();
+    let again = Workbook::load_from_bytes(&saved).unwrap();
+    assert!(again.preserved.is_empty());
+}
+
+#[test]
+fn stale_calc_chain_is_dropped_and_recalc_requested() {
+    let mut wb = Workbook::load_from_bytes(&source()).unwrap();
+    wb.set_cell_value_in_sheet("Data", 3, 1, CellValue::Formula("=A2*2".to_string()))
+        .unwrap();
+    let saved = wb.save_to_bytes().unwrap();
+
+    assert!(read_part(&saved, "xl/calcChain.xml").is_none());
+    assert!(!read_text(&saved, "xl/_rels/workbook.xml.rels").contains("calcChain"));
+    assert!(!read_text(&saved, "[Content_Types].xml").contains("calcChain"));
+
+    let workbook = read_text(&saved, "xl/workbook.xml");
+    assert!(workbook.contains(r#"<calcPr calcId="124519" fullCalcOnLoad="1"/>"#));
+    assert!(workbook.find("</definedNames>").unwrap() < workbook.find("<calcPr").unwrap());
+}
+
+#[test]
+fn theme_colors_resolve_against_the_preserved_theme() {
+    let mut wb = Workbook::load_from_bytes(&source()).unwrap();
+    // The source's theme has no color scheme: the Office colors apply.
+    assert_eq!(wb.theme, Theme::default());
+
+    let part = wb
+        .preserved
+        .parts
+        .iter_mut()
+        .find(|p| p.path == "xl/theme/theme1.xml")
+        .unwrap();
+    part.bytes = br#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:themeElements><a:clrScheme name="Custom"><a:accent1><a:srgbClr val="C00000"/></a:accent1></a:clrScheme></a:themeElements></a:theme>"#.to_vec();
+    let again = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        Color::theme(4).to_rgb(&again.theme).as_deref(),
+        Some("FFC00000")
+    );
+    assert_eq!(
+        Color::theme(5).to_rgb(&again.theme).as_deref(),
+        Some("FFED7D31")
+    );
+}
+
+#[test]
+fn macro_workbook_saves_only_under_a_macro_extension() {
+    let wb = Workbook::load_from_bytes(&source()).unwrap();
+    assert!(wb.has_vba());
+    let dir = std::env::temp_dir().join(format!("rustypyxl-xlsm-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let xlsx = dir.join("macros.xlsx");
+    assert!(wb.save(xlsx.to_str().unwrap()).is_err());
+    assert!(!xlsx.exists(), "a refused save must not leave a file");
+
+    let xlsm = dir.join("macros.xlsm");
+    wb.save(xlsm.to_str().unwrap()).unwrap();
+    let saved = std::fs::read(&xlsm).unwrap();
+    assert!(read_part(&saved, "xl/vbaProject.bin").is_some());
+    assert!(read_text(&saved, "[Content_Types].xml")
+        .contains("application/vnd.ms-excel.sheet.macroEnabled.main+xml"));
+
+    let mut stripped = Workbook::load_from_bytes(&source()).unwrap();
+    stripped.remove_vba();
+    stripped.save(xlsx.to_str().unwrap()).unwrap();
+    let saved = std::fs::read(&xlsx).unwrap();
+    assert!(read_part(&saved, "xl/vbaProject.bin").is_none());
+    assert!(!read_text(&saved, "xl/_rels/workbook.xml.rels").contains("vbaProject"));
+    assert!(!read_text(&saved, "[Content_Types].xml").contains("macroEnabled"));
+    assert!(read_part(&saved, "xl/theme/theme1.xml").is_some());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn full_calc_is_requested_only_when_results_may_be_stale() {
+    let calc_pr = |wb: &Workbook| {
+        let workbook = read_text(&wb.save_to_bytes().unwrap(), "xl/workbook.xml");
+        let start = workbook.find("<calcPr").unwrap();
+        workbook[start..start + workbook[start..].find("/>").unwrap()].to_string()
+    };
+
+    let mut wb = Workbook::new();
+    wb.create_sheet(Some("Data".to_string())).unwrap();
+    wb.set_cell_value_in_sheet("Data", 1, 1, CellValue::Number(2.0))
+        .unwrap();
+    assert!(!wb.needs_full_calc());
+    assert!(!calc_pr(&wb).contains("fullCalcOnLoad"));
+
+    wb.set_cell_value_in_sheet("Data", 2, 1, CellValue::Formula("=A1*2".to_string()))
+        .unwrap();
+    assert!(calc_pr(&wb).contains(r#"fullCalcOnLoad="1""#));
+    assert_eq!(wb.calculate_all(), 1);
+    assert!(!calc_pr(&wb).contains("fullCalcOnLoad"));
+}
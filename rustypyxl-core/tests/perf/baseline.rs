@@ -0,0 +1,195 @@
+//! Baseline storage and the regression check.
+//!
+//! The baseline is a text file of `scenario.metric value` lines. Numbers are
+//! only meaningful on the machine that recorded them, so the file lives
+//! under `target/` rather than in the repository.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::measure::Sample;
+
+/// How much worse than its baseline a metric may get before the run fails.
+pub const TOLERANCE: f64 = 0.15;
+
+/// Peak-memory growth smaller than this is ignored, however large it is
+/// relative to a small baseline such as read-only streaming's few MB.
+const MEMORY_SLACK_MB: f64 = 16.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    HigherIsBetter,
+    LowerIsBetter,
+}
+
+#[derive(Clone, Debug)]
+pub struct Metric {
+    pub name: &'static str,
+    pub value: f64,
+    pub direction: Direction,
+}
+
+impl Metric {
+    /// Items processed per second.
+    pub fn throughput(name: &'static str, items: u64, sample: &Sample) -> Self {
+        Metric {
+            name,
+            value: items as f64 / sample.secs.max(1e-9),
+            direction: Direction::HigherIsBetter,
+        }
+    }
+
+    /// Peak heap use in MB.
+    pub fn peak_memory(name: &'static str, sample: &Sample) -> Self {
+        Metric {
+            name,
+            value: sample.peak_bytes as f64 / (1024.0 * 1024.0),
+            direction: Direction::LowerIsBetter,
+        }
+    }
+
+    /// How much worse than `baseline` this is, as a fraction, when it is
+    /// worse by more than the tolerance.
+    fn regression(&self, baseline: f64) -> Option<f64> {
+        let worse = match self.direction {
+            Direction::HigherIsBetter => (baseline - self.value) / baseline,
+            Direction::LowerIsBetter => {
+                if self.value - baseline < MEMORY_SLACK_MB {
+                    return None;
+                }
+                (self.value - baseline) / baseline.max(MEMORY_SLACK_MB)
+            }
+        };
+        (worse > TOLERANCE).then_some(worse)
+    }
+}
+
+/// The baseline file: `RUSTYPYXL_PERF_BASELINE`, else
+/// `target/perf-baseline.txt` in the workspace.
+pub fn path() -> PathBuf {
+    match std::env::var_os("RUSTYPYXL_PERF_BASELINE") {
+        Some(path) => PathBuf::from(path),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../target/perf-baseline.txt"),
+    }
+}
+
+fn read(path: &Path) -> BTreeMap<String, f64> {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return BTreeMap::new();
+    };
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (key, value) = line.split_once(' ')?;
+            Some((key.to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+fn write(path: &Path, entries: &BTreeMap<String, f64>) -> std::io::Result<()> {
+    let mut text = String::from("# rustypyxl perf baseline: scenario.metric value\n");
+    for (key, value) in entries {
+        let _ = writeln!(text, "{} {:.3}", key, value);
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, text)
+}
+
+/// Compare `metrics` of `scenario` with the baseline at `path`.
+///
+/// Metrics without a baseline value are recorded; with
+/// `RUSTYPYXL_PERF_UPDATE=1` every value is. Returns a report of the
+/// metrics that regressed beyond [`TOLERANCE`].
+pub fn check(path: &Path, scenario: &str, metrics: &[Metric]) -> Result<(), String> {
+    let update = std::env::var("RUSTYPYXL_PERF_UPDATE").is_ok_and(|v| v == "1");
+    let mut entries = read(path);
+    let mut changed = false;
+    let mut report = String::new();
+    for metric in metrics {
+        let key = format!("{}.{}", scenario, metric.name);
+        match entries.get(&key) {
+            Some(&baseline) if !update => {
+                if let Some(worse) = metric.regression(baseline) {
+                    let _ = writeln!(
+                        report,
+                        "{}: {:.1} vs baseline {:.1} ({:.0}% worse)",
+                        key,
+                        metric.value,
+                        baseline,
+                        worse * 100.0
+                    );
+                }
+            }
+            _ => {
+                entries.insert(key, metric.value);
+                changed = true;
+            }
+        }
+    }
+    if changed {
+        write(path, &entries).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    if report.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "performance regressed more than {:.0}% against {}:\n{}",
+            TOLERANCE * 100.0,
+            path.display(),
+            report
+        ))
+    }
+}
+
+#[test]
+fn first_run_records_and_later_runs_compare() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("baseline.txt");
+    let metric = |name, value, direction| Metric {
+        name,
+        value,
+        direction,
+    };
+
+    let recorded = [
+        metric("load", 1000.0, Direction::HigherIsBetter),
+        metric("peak", 200.0, Direction::LowerIsBetter),
+    ];
+    check(&path, "s", &recorded).unwrap();
+    assert_eq!(read(&path).get("s.load"), Some(&1000.0));
+
+    // Within tolerance either way, and memory growth under the slack.
+    let steady = [
+        metric("load", 900.0, Direction::HigherIsBetter),
+        metric("peak", 225.0, Direction::LowerIsBetter),
+    ];
+    check(&path, "s", &steady).unwrap();
+
+    let slower = [
+        metric("load", 800.0, Direction::HigherIsBetter),
+        metric("peak", 300.0, Direction::LowerIsBetter),
+    ];
+    let report = check(&path, "s", &slower).unwrap_err();
+    assert!(report.contains("s.load: 800.0 vs baseline 1000.0 (20% worse)"));
+    assert!(report.contains("s.peak: 300.0 vs baseline 200.0 (50% worse)"));
+    // A failing run leaves the baseline alone.
+    assert_eq!(read(&path).get("s.load"), Some(&1000.0));
+}
+
+#[test]
+fn small_memory_baselines_tolerate_noise() {
+    let metric = Metric {
+        name: "peak",
+        value: 12.0,
+        direction: Direction::LowerIsBetter,
+    };
+    assert_eq!(metric.regression(2.0), None);
+    let metric = Metric {
+        value: 40.0,
+        ..metric
+    };
+    assert!(metric.regression(2.0).is_some());
+}
@@ -0,0 +1,159 @@
+//! Canonical generated workbooks. Every fixture is deterministic, so runs
+//! on the same machine measure the same work.
+
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use rustypyxl::{Alignment, Border, BorderStyle, CellStyle, CellValue, Fill, Font, Workbook};
+
+pub const NUMERIC_COLUMNS: u32 = 4;
+pub const STRING_COLUMNS: u32 = 4;
+pub const STYLED_COLUMNS: u32 = 6;
+
+/// The `RUSTYPYXL_PERF_SCALE` row multiplier, 1 when unset.
+pub fn scale() -> f64 {
+    std::env::var("RUSTYPYXL_PERF_SCALE")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .filter(|&s: &f64| s > 0.0)
+        .unwrap_or(1.0)
+}
+
+/// `rows` scaled by [`scale`], at least 1.
+pub fn scaled(rows: u32) -> u32 {
+    ((f64::from(rows) * scale()) as u32).max(1)
+}
+
+fn numeric_value(row: u32, col: u32) -> f64 {
+    match col {
+        1 => f64::from(row),
+        2 => f64::from(row) * 1.5,
+        3 => f64::from(row % 997) / 7.0,
+        _ => f64::from(row.wrapping_mul(2_654_435_761) % 1_000_000) / 100.0,
+    }
+}
+
+/// Integers, simple and repeating decimals, and scattered values.
+pub fn numeric(rows: u32) -> Workbook {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+    for row in 1..=rows {
+        for col in 1..=NUMERIC_COLUMNS {
+            ws.set_cell_value(row, col, CellValue::Number(numeric_value(row, col)));
+        }
+    }
+    wb
+}
+
+/// The numeric fixture's cells as CSV, without a header.
+pub fn write_numeric_csv(path: &Path, rows: u32) -> std::io::Result<()> {
+    let mut out = BufWriter::new(std::fs::File::create(path)?);
+    for row in 1..=rows {
+        for col in 1..=NUMERIC_COLUMNS {
+            if col > 1 {
+                out.write_all(b",")?;
+            }
+            write!(out, "{}", numeric_value(row, col))?;
+        }
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+/// Category labels from a small set, mid-cardinality codes, unique ids and
+/// unique free text.
+pub fn strings(rows: u32) -> Workbook {
+    const CATEGORIES: [&str; 8] = [
+        "North", "South", "East", "West", "Central", "Online", "Partner", "Other",
+    ];
+    let categories: Vec<CellValue> = CATEGORIES.iter().map(|&c| CellValue::from(c)).collect();
+
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+    for row in 1..=rows {
+        ws.set_cell_value(row, 1, categories[(row % 8) as usize].clone());
+        ws.set_cell_value(row, 2, CellValue::from(format!("SKU-{:05}", row % 5_000)));
+        ws.set_cell_value(row, 3, CellValue::from(format!("ID{:08}", row)));
+        ws.set_cell_value(
+            row,
+            4,
+            CellValue::from(format!(
+                "Order {} shipped to customer #{}",
+                row,
+                row % 9_973
+            )),
+        );
+    }
+    wb
+}
+
+/// The style pool: every combination of a few fonts, fills, borders,
+/// alignments and number formats.
+fn style_pool() -> Vec<Arc<CellStyle>> {
+    let fonts = [
+        Font::new(),
+        Font::new().with_bold(true),
+        Font::new().with_italic(true).with_color("FF1F4E79"),
+        Font::new().with_name("Arial").with_size(9.0),
+    ];
+    let fills = [
+        None,
+        Some(Fill::solid("FFFFF2CC")),
+        Some(Fill::solid("FFDDEBF7")),
+        Some(Fill::solid("FFE2EFDA")),
+    ];
+    let borders = [None, Some(Border::all(BorderStyle::thin()))];
+    let alignments = [
+        None,
+        Some(Alignment::new().with_horizontal("center")),
+        Some(Alignment::new().with_wrap_text(true)),
+    ];
+    let formats = ["General", "#,##0.00", "0.0%", "yyyy-mm-dd"];
+
+    let mut pool = Vec::new();
+    for font in &fonts {
+        for fill in &fills {
+            for border in &borders {
+                for alignment in &alignments {
+                    for format in formats {
+                        let mut style = CellStyle::new()
+                            .with_font(font.clone())
+                            .with_number_format(format);
+                        if let Some(fill) = fill {
+                            style = style.with_fill(fill.clone());
+                        }
+                        if let Some(border) = border {
+                            style = style.with_border(border.clone());
+                        }
+                        if let Some(alignment) = alignment {
+                            style = style.with_alignment(alignment.clone());
+                        }
+                        pool.push(Arc::new(style));
+                    }
+                }
+            }
+        }
+    }
+    pool
+}
+
+/// Numbers and labels where every cell has a style from [`style_pool`].
+pub fn styled(rows: u32) -> Workbook {
+    let pool = style_pool();
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+    for row in 1..=rows {
+        for col in 1..=STYLED_COLUMNS {
+            let value = if col % 3 == 0 {
+                CellValue::from(format!("Item {}", row % 1_000))
+            } else {
+                CellValue::Number(numeric_value(row, col))
+            };
+            ws.set_cell_value(row, col, value);
+            let style = &pool[((row * STYLED_COLUMNS + col) as usize) % pool.len()];
+            ws.get_cell_mut(row, col).unwrap().style = Some(Arc::clone(style));
+        }
+    }
+    wb
+}
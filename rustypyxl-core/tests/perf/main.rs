@@ -0,0 +1,153 @@
+//! Performance regression suite.
+//!
+//! Each scenario generates a canonical large workbook, measures save, load,
+//! read-only streaming and CSV import throughput plus the peak heap use of
+//! each step, and compares the numbers with a baseline recorded
+//! earlier on the same machine. A metric more than 15% worse than its
+//! baseline fails the run.
+//!
+//! The scenarios are `#[ignore]`d so a plain `cargo test` stays fast. Run
+//! them in release mode, one at a time so the memory figures are their own:
+//!
+//! ```text
+//! cargo test --release -p rustypyxl --test perf -- --ignored --test-threads=1
+//! ```
+//!
+//! Environment:
+//! - `RUSTYPYXL_PERF_BASELINE`: baseline file (default
+//!   `target/perf-baseline.txt`). The first run records it.
+//! - `RUSTYPYXL_PERF_UPDATE=1`: overwrite the baseline with this run.
+//! - `RUSTYPYXL_PERF_RUNS`: times each step runs; the fastest counts
+//!   (default 3).
+//! - `RUSTYPYXL_PERF_SCALE`: multiply every fixture's row count, e.g. `0.1`
+//!   for a quick pass. Each scale keeps its own baseline entries.
+
+mod baseline;
+mod fixtures;
+mod measure;
+
+use std::io::BufReader;
+use std::sync::Mutex;
+
+use baseline::{Direction, Metric};
+use measure::measure_best;
+use rustypyxl::read_only::ReadOnlyWorkbook;
+use rustypyxl::{CsvImportOptions, Workbook};
+
+/// Scenarios run one at a time even without `--test-threads=1`: memory is
+/// counted process-wide, and they share the baseline file.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Metrics common to every scenario: save, load and read-only streaming of
+/// the generated workbook.
+fn workbook_metrics(wb: Workbook, cells: u64) -> Vec<Metric> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fixture.xlsx");
+    let path = path.to_str().unwrap();
+
+    let (saved, save) = measure_best(|| wb.save(path));
+    saved.unwrap();
+    drop(wb);
+
+    let (loaded, load) = measure_best(|| Workbook::load(path));
+    let loaded = loaded.unwrap();
+    let sheet = loaded.sheet_names()[0].clone();
+    assert_eq!(loaded.worksheets[0].cells.len() as u64, cells);
+    drop(loaded);
+
+    let (streamed, stream) = measure_best(|| {
+        let ro = ReadOnlyWorkbook::open(path)?;
+        let mut streamed = 0u64;
+        for row in ro.rows(&sheet)? {
+            streamed += row?.cells.len() as u64;
+        }
+        rustypyxl::Result::Ok(streamed)
+    });
+    assert_eq!(streamed.unwrap(), cells);
+
+    let mut metrics = vec![
+        Metric::throughput("save_cells_per_sec", cells, &save),
+        Metric::throughput("load_cells_per_sec", cells, &load),
+        Metric::throughput("read_only_cells_per_sec", cells, &stream),
+    ];
+    metrics.push(Metric::peak_memory("load_peak_mb", &load));
+    metrics.push(Metric::peak_memory("read_only_peak_mb", &stream));
+    metrics
+}
+
+fn run(scenario: &str, metrics: impl FnOnce() -> Vec<Metric>) {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let scale = fixtures::scale();
+    let scenario = if scale == 1.0 {
+        scenario.to_string()
+    } else {
+        format!("{}@{}", scenario, scale)
+    };
+    let metrics = metrics();
+    for metric in &metrics {
+        let arrow = match metric.direction {
+            Direction::HigherIsBetter => "↑",
+            Direction::LowerIsBetter => "↓",
+        };
+        eprintln!("{scenario}.{}: {:.1} {arrow}", metric.name, metric.value);
+    }
+    if let Err(report) = baseline::check(&baseline::path(), &scenario, &metrics) {
+        panic!("{}", report);
+    }
+}
+
+/// 1M rows of numbers in four columns, plus importing the same data as CSV.
+#[test]
+#[ignore = "performance suite; run with --release -- --ignored"]
+fn numeric_1m_rows() {
+    run("numeric_1m", || {
+        let rows = fixtures::scaled(1_000_000);
+        let wb = fixtures::numeric(rows);
+        let cells = u64::from(rows) * u64::from(fixtures::NUMERIC_COLUMNS);
+        let mut metrics = workbook_metrics(wb, cells);
+
+        let dir = tempfile::tempdir().unwrap();
+        let csv = dir.path().join("numeric.csv");
+        fixtures::write_numeric_csv(&csv, rows).unwrap();
+        let (imported, import) = measure_best(|| {
+            let mut wb = Workbook::new();
+            let ws = wb.create_sheet(Some("Data".to_string()))?;
+            let file = BufReader::new(std::fs::File::open(&csv)?);
+            let options = CsvImportOptions::new().with_headers(false);
+            ws.insert_csv(file, 1, 1, &options)?;
+            rustypyxl::Result::Ok(wb.worksheets[0].cells.len() as u64)
+        });
+        assert_eq!(imported.unwrap(), cells);
+        metrics.push(Metric::throughput(
+            "csv_import_cells_per_sec",
+            cells,
+            &import,
+        ));
+        metrics.push(Metric::peak_memory("csv_import_peak_mb", &import));
+        metrics
+    });
+}
+
+/// 500k rows of strings, a mix of repeated labels and unique text, so the
+/// shared string table is both deduplicated and large.
+#[test]
+#[ignore = "performance suite; run with --release -- --ignored"]
+fn strings_500k_rows() {
+    run("strings_500k", || {
+        let rows = fixtures::scaled(500_000);
+        let cells = u64::from(rows) * u64::from(fixtures::STRING_COLUMNS);
+        workbook_metrics(fixtures::strings(rows), cells)
+    });
+}
+
+/// 200k rows where every cell carries one of a few hundred distinct styles
+/// (fonts, fills, borders, alignments, number formats).
+#[test]
+#[ignore = "performance suite; run with --release -- --ignored"]
+fn heavy_styles_200k_rows() {
+    run("heavy_styles", || {
+        let rows = fixtures::scaled(200_000);
+        let cells = u64::from(rows) * u64::from(fixtures::STYLED_COLUMNS);
+        workbook_metrics(fixtures::styled(rows), cells)
+    });
+}
@@ -0,0 +1,93 @@
+//! Wall time and peak memory of a closure.
+//!
+//! Memory is counted by a global allocator wrapping the system one, not read
+//! from the process's resident size: freed pages the allocator keeps around
+//! would otherwise hide what a step after a large one really needs.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            grow(new_size);
+        }
+        new
+    }
+}
+
+fn grow(size: usize) {
+    let now = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(now, Ordering::Relaxed);
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// What one measured step cost.
+pub struct Sample {
+    pub secs: f64,
+    /// Most heap bytes live at once during the step, beyond what was live
+    /// when it started.
+    pub peak_bytes: u64,
+}
+
+/// Run `f`, timing it and tracking its peak heap use.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Sample) {
+    let before = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let start = Instant::now();
+    let out = f();
+    let secs = start.elapsed().as_secs_f64();
+    let peak_bytes = PEAK.load(Ordering::Relaxed).saturating_sub(before) as u64;
+    (out, Sample { secs, peak_bytes })
+}
+
+/// Run `f` `RUSTYPYXL_PERF_RUNS` times (default 3), keeping the last output,
+/// the fastest time and the largest memory peak. Taking the best time
+/// filters out runs slowed by unrelated machine load.
+pub fn measure_best<T>(mut f: impl FnMut() -> T) -> (T, Sample) {
+    let runs = std::env::var("RUSTYPYXL_PERF_RUNS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(3usize)
+        .max(1);
+    let (mut out, mut best) = measure(&mut f);
+    for _ in 1..runs {
+        drop(out);
+        let (next, sample) = measure(&mut f);
+        out = next;
+        best.secs = best.secs.min(sample.secs);
+        best.peak_bytes = best.peak_bytes.max(sample.peak_bytes);
+    }
+    (out, best)
+}
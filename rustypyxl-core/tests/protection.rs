@@ -0,0 +1,54 @@
+//! Protection verifiers survive a save/load round trip and still check.
+
+use rustypyxl::Workbook;
+#[cfg(feature = "encrypt")]
+use rustypyxl::{PasswordHash, WorkbookProtection};
+
+#[cfg(feature = "encrypt")]
+#[test]
+fn sheet_and_workbook_hashes_round_trip() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Locked".to_string())).unwrap();
+    ws.enable_protection(None);
+    let protection = ws.protection.as_mut().unwrap();
+    protection.set_hash(PasswordHash::with_spin_count("sheet", 500));
+    protection.sort = false;
+    wb.protection = Some(WorkbookProtection::new().with_lock_windows(true));
+    wb.protection.as_mut().unwrap().workbook_hash =
+        Some(PasswordHash::with_spin_count("book", 500));
+
+    let bytes = wb.save_to_bytes().unwrap();
+    let loaded = Workbook::load_from_bytes(&bytes).unwrap();
+
+    let sheet = loaded.worksheets[0].protection.as_ref().unwrap();
+    assert!(sheet.sheet && !sheet.sort && sheet.format_cells);
+    assert_eq!(sheet.hash.as_ref().unwrap().spin_count, 500);
+    assert!(sheet.verify_password("sheet"));
+    assert!(!sheet.verify_password("Sheet"));
+
+    let book = loaded.protection.as_ref().unwrap();
+    assert!(book.lock_structure && book.lock_windows && !book.lock_revision);
+    assert!(book.verify_password("book"));
+    assert!(!book.verify_password("sheet"));
+}
+
+#[test]
+fn legacy_verifiers_still_check() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Old".to_string())).unwrap();
+    ws.enable_protection(None);
+    ws.protection.as_mut().unwrap().password = Some("test".to_string());
+    assert_eq!(
+        ws.protection.as_ref().unwrap().legacy_verifier().as_deref(),
+        Some("CBEB")
+    );
+
+    let loaded = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+    let sheet = loaded.worksheets[0].protection.as_ref().unwrap();
+    assert_eq!(sheet.password_hash.as_deref(), Some("CBEB"));
+    assert_eq!(sheet.legacy_verifier().as_deref(), Some("CBEB"));
+    assert!(sheet.hash.is_none());
+    assert!(sheet.verify_password("test"));
+    assert!(!sheet.verify_password("tset"));
+    assert!(loaded.protection.is_none());
+}
@@ -0,0 +1,79 @@
+use rustypyxl::read_only::ReadOnlyWorkbook;
+use rustypyxl::{CellValue, Workbook};
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_read_only_streams_saved_file() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Big".to_string())).unwrap();
+    for row in 1..=20_000u32 {
+        ws.set_cell_value(row, 1, CellValue::Number(row as f64));
+        ws.set_cell_value(row, 2, CellValue::from(format!("label {}", row % 7)));
+    }
+    ws.set_cell_value(5, 3, CellValue::from("a & <b>"));
+    wb.create_sheet(Some("Second".to_string())).unwrap();
+
+    let temp_file = NamedTempFile::new().unwrap();
+    let temp_path = temp_file.path().to_str().unwrap();
+    wb.save(temp_path).unwrap();
+
+    let ro = ReadOnlyWorkbook::open(temp_path).unwrap();
+    assert_eq!(ro.sheet_names(), ["Big", "Second"]);
+    assert_eq!(ro.dimension("Big").unwrap().as_deref(), Some("A1:C20000"));
+
+    let mut count = 0;
+    let mut total = 0.0;
+    for row in ro.rows("Big").unwrap() {
+        let row = row.unwrap();
+        count += 1;
+        if let CellValue::Number(n) = row.cells[0].value {
+            total += n;
+        }
+        assert_eq!(
+            row.cells[1].value,
+            CellValue::from(format!("label {}", row.index % 7))
+        );
+        if row.index == 5 {
+            assert_eq!(row.values(3, 3), vec![CellValue::from("a & <b>")]);
+        }
+    }
+    assert_eq!(count, 20_000);
+    assert_eq!(total, (20_000.0 * 20_001.0) / 2.0);
+    assert_eq!(ro.rows("Second").unwrap().count(), 0);
+}
+
+#[test]
+fn test_read_only_rejects_non_xlsx() {
+    assert!(ReadOnlyWorkbook::from_bytes(b"not a zip".to_vec()).is_err());
+}
+
+#[test]
+fn test_read_only_cuts_long_shared_strings() {
+    use rustypyxl::read_only::{ReadOnlyOptions, TRUNCATION_MARKER};
+
+    let blob = "x".repeat(100_000);
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Notes".to_string())).unwrap();
+    ws.set_cell_value(1, 1, CellValue::from(blob.as_str()));
+    ws.set_cell_value(2, 1, CellValue::from("short"));
+    let bytes = wb.save_to_bytes().unwrap();
+
+    let options = ReadOnlyOptions::new().with_max_string_len(10);
+    let ro = ReadOnlyWorkbook::from_bytes_with_options(bytes.clone(), &options).unwrap();
+    let values: Vec<CellValue> = ro
+        .rows("Notes")
+        .unwrap()
+        .map(|row| row.unwrap().cells[0].value.clone())
+        .collect();
+    assert_eq!(
+        values,
+        [
+            CellValue::from(format!("{}{}", &blob[..10], TRUNCATION_MARKER)),
+            CellValue::from("short"),
+        ]
+    );
+
+    let whole = ReadOnlyWorkbook::from_bytes(bytes).unwrap();
+    let first = whole.rows("Notes").unwrap().next().unwrap().unwrap();
+    assert_eq!(first.cells[0].value, CellValue::from(blob));
+}
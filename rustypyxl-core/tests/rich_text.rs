@@ -94,3 +94,20 @@ fn run_with_no_font_inherits() {
     assert!(runs[0].font.is_none(), "unformatted run stays font-less");
     assert!(runs[1].font.as_ref().unwrap().bold);
 }
+
+#[test]
+fn overwriting_with_a_plain_value_drops_runs() {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("S".to_string())).unwrap();
+    ws.set_cell_rich_text(1, 1, RichText::new(vec![TextRun::formatted("old", bold())]));
+    ws.set_cell_value(1, 1, "new");
+
+    let reloaded = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+    let cell = reloaded
+        .get_sheet_by_name("S")
+        .unwrap()
+        .get_cell(1, 1)
+        .unwrap();
+    assert_eq!(cell.value, CellValue::String("new".into()));
+    assert!(cell.rich_text.is_none());
+}
@@ -0,0 +1,103 @@
+//! Filled-down formulas saved as shared formula groups, and shared formulas
+//! read back as one formula per cell.
+
+use std::io::Read;
+
+use rustypyxl::read_only::ReadOnlyWorkbook;
+use rustypyxl::{CellValue, Workbook};
+use zip::ZipArchive;
+
+fn sheet_xml(bytes: &[u8]) -> String {
+    let mut archive = ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut xml = String::new();
+    archive
+        .by_name("xl/worksheets/sheet1.xml")
+        .unwrap()
+        .read_to_string(&mut xml)
+        .unwrap();
+    xml
+}
+
+fn workbook(share: bool) -> Workbook {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+    for row in 2..=2001 {
+        ws.set_cell_value(row, 2, row as f64);
+        ws.set_cell_value(row, 3, 2.0);
+    }
+    ws.fill_formula_down("D2", "=B2*C2+ROUND(B2/C2,2)+$F$1", 2000)
+        .unwrap();
+    // Breaks the run: D6 no longer follows the pattern.
+    ws.set_cell_formula(6, 4, "B6-C6");
+    ws.fill_formula_down("E2", "SUM($B$2:B2)", 3).unwrap();
+    ws.set_cell_formula(2, 7, "A1");
+    wb.set_share_formulas(share);
+    wb
+}
+
+#[test]
+fn fill_down_translates_each_row() {
+    let wb = workbook(false);
+    let ws = wb.get_sheet_by_name("Data").unwrap();
+    assert_eq!(
+        ws.get_cell_value(2, 4),
+        Some(&CellValue::Formula("B2*C2+ROUND(B2/C2,2)+$F$1".to_string()))
+    );
+    assert_eq!(
+        ws.get_cell_value(2001, 4),
+        Some(&CellValue::Formula(
+            "B2001*C2001+ROUND(B2001/C2001,2)+$F$1".to_string()
+        ))
+    );
+    assert_eq!(
+        ws.get_cell_value(4, 5),
+        Some(&CellValue::Formula("SUM($B$2:B4)".to_string()))
+    );
+
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(None).unwrap();
+    assert!(ws.fill_formula_down("A1", "B1", 0).is_err());
+    assert!(ws.fill_formula_down("A1048576", "B1", 2).is_err());
+    assert!(ws.fill_formula_down("not a cell", "B1", 2).is_err());
+}
+
+#[test]
+fn shared_groups_are_written_and_read_back() {
+    let plain = workbook(false).save_to_bytes().unwrap();
+    assert!(!sheet_xml(&plain).contains("t=\"shared\""));
+
+    let shared = workbook(true).save_to_bytes().unwrap();
+    let xml = sheet_xml(&shared);
+    assert!(xml.contains(r#"<f t="shared" ref="D2:D5" si="0">B2*C2+ROUND(B2/C2,2)+$F$1</f>"#));
+    assert!(xml.contains(r#"<f t="shared" ref="D7:D2001" si="1">B7*C7+ROUND(B7/C7,2)+$F$1</f>"#));
+    assert!(xml.contains(r#"<c r="D3"><f t="shared" si="0"/></c>"#));
+    assert!(xml.contains(r#"<f t="shared" ref="E2:E4" si="2">SUM($B$2:B2)</f>"#));
+    assert!(xml.contains("<f>B6-C6</f>") && xml.contains("<f>A1</f>"));
+    assert!(xml.len() < sheet_xml(&plain).len());
+
+    let loaded = Workbook::load_from_bytes(&shared).unwrap();
+    let original = workbook(false);
+    let (expected, actual) = (
+        original.get_sheet_by_name("Data").unwrap(),
+        loaded.get_sheet_by_name("Data").unwrap(),
+    );
+    for row in 2..=2001 {
+        for col in 4..=5 {
+            assert_eq!(
+                actual.get_cell_value(row, col),
+                expected.get_cell_value(row, col),
+                "row {row} col {col}"
+            );
+        }
+    }
+
+    let ro = ReadOnlyWorkbook::from_bytes(shared).unwrap();
+    let row = ro.rows("Data").unwrap().nth(1998).unwrap().unwrap();
+    assert_eq!(row.index, 2000);
+    assert_eq!(
+        row.values(4, 4),
+        vec![CellValue::Formula(
+            "B2000*C2000+ROUND(B2000/C2000,2)+$F$1".to_string()
+        )]
+    );
+}
@@ -0,0 +1,87 @@
+//! Row and cell tags survive a save and follow their rows when the file is
+//! edited in between, the way Excel re-points defined names.
+
+use rustypyxl::{CellValue, Workbook};
+use std::io::{Cursor, Read, Write};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+/// Rewrite one part of a saved package, as an editing application would.
+fn edit_part(bytes: &[u8], name: &str, edit: impl Fn(String) -> String) -> Vec<u8> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut out = ZipWriter::new(Cursor::new(Vec::new()));
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).unwrap();
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).unwrap();
+        if file.name() == name {
+            content = edit(String::from_utf8(content).unwrap()).into_bytes();
+        }
+        out.start_file(file.name(), SimpleFileOptions::default())
+            .unwrap();
+        out.write_all(&content).unwrap();
+    }
+    out.finish().unwrap().into_inner()
+}
+
+fn tagged_workbook() -> Workbook {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Orders".to_string())).unwrap();
+    for row in 1..=5 {
+        ws.set_cell_value(row, 1, CellValue::Number(row as f64));
+    }
+    ws.set_row_tag(2, "order:1001").unwrap();
+    ws.set_row_tag(3, "order:1002").unwrap();
+    ws.set_cell_tag(5, 2, "grand-total").unwrap();
+    wb.create_named_range("Visible".to_string(), "Orders!$A$1".to_string())
+        .unwrap();
+    wb
+}
+
+#[test]
+fn tags_round_trip_without_showing_as_named_ranges() {
+    let saved = tagged_workbook().save_to_bytes().unwrap();
+    let wb = Workbook::load_from_bytes(&saved).unwrap();
+    let ws = wb.get_sheet_by_name("Orders").unwrap();
+    assert_eq!(ws.get_row_tag(2), Some("order:1001"));
+    assert_eq!(ws.find_row_by_tag("order:1002"), Some(3));
+    assert_eq!(ws.find_cell_by_tag("grand-total"), Some((5, 2)));
+    let names: Vec<&str> = wb.named_ranges.iter().map(|n| n.name.as_str()).collect();
+    assert_eq!(names, ["Visible"]);
+
+    // Saving again keeps them.
+    let again = Workbook::load_from_bytes(&wb.save_to_bytes().unwrap()).unwrap();
+    assert_eq!(
+        again.get_sheet_by_name("Orders").unwrap().get_row_tag(3),
+        Some("order:1002")
+    );
+}
+
+#[test]
+fn tags_follow_rows_moved_outside_rustypyxl() {
+    let saved = tagged_workbook().save_to_bytes().unwrap();
+    // What Excel writes back after edits: rows 3 and 5 moved down one and
+    // the tagged row 2 was deleted.
+    let edited = edit_part(&saved, "xl/workbook.xml", |xml| {
+        xml.replace("Orders!$3:$3", "Orders!$4:$4")
+            .replace("Orders!$5:$5", "Orders!$6:$6")
+            .replace("Orders!$B$5", "Orders!$B$6")
+            .replace("Orders!$2:$2", "Orders!#REF!")
+    });
+
+    let wb = Workbook::load_from_bytes(&edited).unwrap();
+    let ws = wb.get_sheet_by_name("Orders").unwrap();
+    assert_eq!(ws.find_row_by_tag("order:1001"), None);
+    assert_eq!(ws.find_row_by_tag("order:1002"), Some(4));
+    assert_eq!(ws.find_cell_by_tag("grand-total"), Some((6, 2)));
+}
+
+#[test]
+fn tags_shift_with_inserted_rows() {
+    let mut wb = tagged_workbook();
+    let ws = wb.get_sheet_by_name_mut("Orders").unwrap();
+    ws.insert_rows(3, 1);
+    assert_eq!(ws.find_row_by_tag("order:1002"), Some(4));
+    ws.delete_rows(2, 1);
+    assert_eq!(ws.find_row_by_tag("order:1001"), None);
+    assert_eq!(ws.find_cell_by_tag("grand-total"), Some((5, 2)));
+}
@@ -12,7 +12,10 @@ use rustypyxl::conditional::{
 use rustypyxl::pagesetup::{HeaderFooterSection, PageSetup};
 use rustypyxl::table::{Table, TableColumn};
 use rustypyxl::worksheet::DataValidation;
-use rustypyxl::{CellValue, NamedRange, Workbook};
+use rustypyxl::{
+    CellValue, NamedRange, NonFiniteNumbers, NumberPrecision, RichText, RunFont, SharedStrings,
+    TextRun, Workbook,
+};
 use zip::ZipArchive;
 
 /// A C0 control char that is illegal in XML 1.0 even when escaped.
@@ -42,6 +45,7 @@ fn dirty_workbook() -> Workbook {
         range: format!("Sheet1!A1:B2{}", DIRTY),
         local_sheet_id: None,
         hidden: false,
+        comment: None,
     });
 
     let ws = wb.create_sheet(Some("Sheet1".to_string())).unwrap();
@@ -182,6 +186,62 @@ fn significant_whitespace_is_marked_preserve() {
     );
 }
 
+/// Line breaks, tabs and inner runs of spaces are significant too: without
+/// the attribute a trimming consumer joins a multi-line value onto one line.
+/// Inline strings and rich-text runs follow the same rule as the sst.
+#[test]
+fn inner_whitespace_is_marked_preserve() {
+    let values = ["first line\nsecond", "a\tb", "two  spaces", "single spaced"];
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Sheet1".to_string())).unwrap();
+    for (row, value) in (1..).zip(values) {
+        ws.set_cell_value(row, 1, value);
+    }
+    ws.set_cell_rich_text(
+        5,
+        1,
+        RichText::new(vec![
+            TextRun::formatted(
+                "Note:",
+                RunFont {
+                    bold: true,
+                    ..Default::default()
+                },
+            ),
+            TextRun::plain("\nsee below"),
+        ]),
+    );
+    wb.set_shared_strings(SharedStrings::MinRepeats(2));
+
+    let bytes = wb.save_to_bytes().unwrap();
+    let parts = xml_parts(&bytes);
+    let sheet = &parts
+        .iter()
+        .find(|(name, _)| name.ends_with("sheet1.xml"))
+        .unwrap()
+        .1;
+    for value in &values[..3] {
+        assert!(
+            sheet.contains(&format!(r#"<t xml:space="preserve">{}</t>"#, value)),
+            "{:?} must carry xml:space=preserve, got: {}",
+            value,
+            sheet
+        );
+    }
+    assert!(sheet.contains("<t>single spaced</t>"));
+    assert!(sheet.contains("<t xml:space=\"preserve\">\nsee below</t>"));
+
+    let reloaded = Workbook::load_from_bytes(&bytes).unwrap();
+    let ws = reloaded.get_sheet_by_name("Sheet1").unwrap();
+    for (row, value) in (1..).zip(values) {
+        assert_eq!(ws.get_cell_value(row, 1), Some(&CellValue::from(value)));
+    }
+    assert_eq!(
+        ws.get_cell_value(5, 1),
+        Some(&CellValue::from("Note:\nsee below"))
+    );
+}
+
 /// sst `count` is the number of references; `uniqueCount` the table size.
 #[test]
 fn shared_string_count_is_total_references() {
@@ -255,3 +315,76 @@ fn dxf_num_fmt_ids_do_not_collide_with_custom_formats() {
         ids
     );
 }
+
+fn non_finite_workbook(policy: NonFiniteNumbers) -> Workbook {
+    let mut wb = Workbook::new();
+    let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+    ws.set_cell_value(1, 1, 1.5);
+    ws.set_cell_value(3, 1, f64::INFINITY);
+    ws.set_cell_value(2, 2, f64::NAN);
+    wb.set_non_finite_numbers(policy);
+    wb
+}
+
+#[test]
+fn non_finite_numbers_follow_the_save_policy() {
+    let bytes = non_finite_workbook(NonFiniteNumbers::NumError)
+        .save_to_bytes()
+        .unwrap();
+    let sheet = &xml_parts(&bytes)
+        .into_iter()
+        .find(|(name, _)| name == "xl/worksheets/sheet1.xml")
+        .unwrap()
+        .1;
+    assert!(sheet.contains(r#"<c r="B2" t="e"><v>#NUM!</v></c>"#));
+    assert!(!sheet.contains("NaN") && !sheet.contains("inf"));
+
+    let bytes = non_finite_workbook(NonFiniteNumbers::Empty)
+        .save_to_bytes()
+        .unwrap();
+    let loaded = Workbook::load_from_bytes(&bytes).unwrap();
+    let ws = loaded.get_sheet_by_name("Data").unwrap();
+    assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::Number(1.5)));
+    for (row, col) in [(2, 2), (3, 1)] {
+        assert!(matches!(
+            ws.get_cell_value(row, col),
+            None | Some(CellValue::Empty)
+        ));
+    }
+
+    // The first offending cell in reading order is named.
+    let err = non_finite_workbook(NonFiniteNumbers::Error)
+        .save_to_bytes()
+        .unwrap_err();
+    assert!(err.to_string().contains("Data!B2"), "{err}");
+}
+
+#[test]
+fn number_precision_controls_saved_digits() {
+    let save = |precision| {
+        let mut wb = Workbook::new();
+        let ws = wb.create_sheet(Some("Data".to_string())).unwrap();
+        ws.set_cell_value(1, 1, 0.1 + 0.2);
+        ws.set_cell_value(2, 1, 12.0);
+        wb.set_number_precision(precision);
+        wb.save_to_bytes().unwrap()
+    };
+    let sheet = |bytes: &[u8]| {
+        xml_parts(bytes)
+            .into_iter()
+            .find(|(name, _)| name == "xl/worksheets/sheet1.xml")
+            .unwrap()
+            .1
+    };
+
+    let shortest = save(NumberPrecision::Shortest);
+    assert!(sheet(&shortest).contains("<v>0.30000000000000004</v>"));
+    assert!(sheet(&shortest).contains("<v>12</v>"));
+
+    let excel = save(NumberPrecision::Excel);
+    assert!(sheet(&excel).contains("<v>0.3</v>"));
+    let loaded = Workbook::load_from_bytes(&excel).unwrap();
+    let ws = loaded.get_sheet_by_name("Data").unwrap();
+    assert_eq!(ws.get_cell_value(1, 1), Some(&CellValue::Number(0.3)));
+    assert_eq!(ws.get_cell_value(2, 1), Some(&CellValue::Number(12.0)));
+}
@@ -0,0 +1,43 @@
+//! Arrow data handed to Python through the Arrow PyCapsule interface, so
+//! pyarrow, pandas and polars can take a sheet's columns without a Python
+//! object per value.
+
+use std::ffi::CString;
+
+use arrow::ffi_stream::FFI_ArrowArrayStream;
+use arrow::record_batch::{RecordBatch, RecordBatchIterator};
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+
+/// One record batch exported as an Arrow C stream (`__arrow_c_stream__`).
+#[pyclass(name = "ArrowStream")]
+pub struct PyArrowStream {
+    pub batch: RecordBatch,
+}
+
+#[pymethods]
+impl PyArrowStream {
+    /// A fresh stream over the batch each call. A requested schema is
+    /// ignored, as the protocol allows.
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        requested_schema: Option<Bound<'py, PyAny>>,
+        py: Python<'py>,
+    ) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let reader = RecordBatchIterator::new(vec![Ok(self.batch.clone())], self.batch.schema());
+        let stream = FFI_ArrowArrayStream::new(Box::new(reader));
+        // The consumer moves the stream out of the capsule and leaves a
+        // released one behind, whose drop is then a no-op.
+        PyCapsule::new(
+            py,
+            stream,
+            Some(CString::new("arrow_array_stream").unwrap()),
+        )
+    }
+
+    fn __len__(&self) -> usize {
+        self.batch.num_rows()
+    }
+}
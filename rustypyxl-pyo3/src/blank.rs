@@ -0,0 +1,69 @@
+//! `BlankPolicy`: how blank cells read back and what writing `None` does.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+
+use rustypyxl_core::{BlankPolicy, BlankRead, BlankWrite};
+
+/// Blank-cell handling for a workbook, set with `wb.blank_policy = ...`.
+///
+/// Args:
+///     read: What blank cells read back as: "none" (the default, as in
+///         openpyxl), "empty_string", or "skip" to leave them out of
+///         iter_rows/iter_cols/read_rows along with rows that are all blank
+///     write_none: What assigning None does: "clear" (the default) empties
+///         the cell, "ignore" leaves its current value in place
+#[pyclass(name = "BlankPolicy", frozen)]
+#[derive(Clone, Debug, Default)]
+pub struct PyBlankPolicy {
+    pub inner: BlankPolicy,
+}
+
+#[pymethods]
+impl PyBlankPolicy {
+    #[new]
+    #[pyo3(signature = (read="none", write_none="clear"))]
+    fn new(read: &str, write_none: &str) -> PyResult<Self> {
+        let read = BlankRead::parse(read).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let write =
+            BlankWrite::parse(write_none).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyBlankPolicy {
+            inner: BlankPolicy::new().with_read(read).with_write(write),
+        })
+    }
+
+    #[getter]
+    fn read(&self) -> &'static str {
+        self.inner.read.as_str()
+    }
+
+    #[getter]
+    fn write_none(&self) -> &'static str {
+        self.inner.write.as_str()
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        other
+            .downcast::<Self>()
+            .is_ok_and(|other| other.get().inner == self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "BlankPolicy(read={:?}, write_none={:?})",
+            self.read(),
+            self.write_none()
+        )
+    }
+}
+
+/// What a blank cell reads back as under `policy`: None, or "" for
+/// `empty_string`. Skipping is up to the caller, which knows whether it is
+/// iterating.
+pub(crate) fn blank_to_python(policy: &BlankPolicy, py: Python<'_>) -> PyObject {
+    match policy.read {
+        BlankRead::EmptyString => PyString::new(py, "").into_any().unbind(),
+        BlankRead::None | BlankRead::Skip => py.None(),
+    }
+}
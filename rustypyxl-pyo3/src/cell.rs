@@ -85,10 +85,19 @@ impl PyCell {
                     "Worksheet no longer exists in this workbook",
                 )
             })?;
-            return Ok(Some(this.inner.sheet_names[idx].clone()));
+            return Ok(Some(this.inner.sheet_names()[idx].clone()));
         }
         Ok(None)
     }
+
+    /// What a connected cell addresses: its workbook, sheet and position.
+    /// None for a detached cell, which has no identity beyond the object.
+    fn identity_key(&self) -> Option<(usize, u64, u32, u32)> {
+        match (&self.workbook, self.sheet_uid) {
+            (Some(wb), Some(uid)) => Some((wb.as_ptr() as usize, uid, self.row, self.column)),
+            _ => None,
+        }
+    }
 }
 
 #[pymethods]
@@ -133,6 +142,11 @@ impl PyCell {
     fn set_value(&mut self, py: Python<'_>, value: PyObject) -> PyResult<()> {
         if let Some(sheet) = self.sheet_name(py)? {
             if let Some(ref wb) = self.workbook {
+                if let Some(rich) = crate::rich_text::python_to_rich_text(value.bind(py))? {
+                    return wb
+                        .borrow_mut(py)
+                        .set_rich_text(&sheet, self.row, self.column, rich);
+                }
                 // Convert before borrowing the workbook: the conversion can run
                 // arbitrary Python (__str__), which may re-enter this workbook.
                 let cell_value = crate::workbook::python_to_cell_value(value.bind(py))?;
@@ -160,6 +174,26 @@ impl PyCell {
         column_to_letter(self.column)
     }
 
+    /// The 1-based column index (openpyxl's alias for `column`).
+    #[getter]
+    fn col_idx(&self) -> u32 {
+        self.column
+    }
+
+    /// The worksheet the cell belongs to, or None for a detached cell.
+    #[getter]
+    fn parent(&self, py: Python<'_>) -> PyResult<Option<crate::worksheet::PyWorksheet>> {
+        let (Some(wb), Some(uid)) = (&self.workbook, self.sheet_uid) else {
+            return Ok(None);
+        };
+        let title = self.sheet_name(py)?.unwrap_or_default();
+        Ok(Some(crate::worksheet::PyWorksheet::connected(
+            wb.clone_ref(py),
+            uid,
+            title,
+        )))
+    }
+
     /// Get the cell's font.
     #[getter]
     fn font(&self, py: Python<'_>) -> PyResult<Option<PyFont>> {
@@ -299,16 +333,25 @@ impl PyCell {
         Ok(self.hyperlink_internal.clone())
     }
 
-    /// Set the cell's hyperlink.
+    /// Set the cell's hyperlink: an address ("#Sheet2!A1" for a place in the
+    /// workbook) or a Hyperlink (with tooltip and display text). The getter
+    /// returns the address.
     #[setter]
-    fn set_hyperlink(&mut self, py: Python<'_>, hyperlink: Option<String>) -> PyResult<()> {
+    fn set_hyperlink(
+        &mut self,
+        py: Python<'_>,
+        hyperlink: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<()> {
         if let Some(sheet) = self.sheet_name(py)? {
             if let Some(ref wb) = self.workbook {
                 let mut wb_ref = wb.borrow_mut(py);
                 return wb_ref.set_cell_hyperlink(&sheet, self.row, self.column, hyperlink);
             }
         }
-        self.hyperlink_internal = hyperlink;
+        self.hyperlink_internal = hyperlink
+            .map(crate::hyperlink::extract_hyperlink)
+            .transpose()?
+            .map(|link| link.address());
         Ok(())
     }
 
@@ -324,16 +367,20 @@ impl PyCell {
         Ok(self.comment_internal.clone())
     }
 
-    /// Set the cell's comment.
+    /// Set the cell's comment, as text or a Comment (for its author and
+    /// box size). The getter returns the text.
     #[setter]
-    fn set_comment(&mut self, py: Python<'_>, comment: Option<String>) -> PyResult<()> {
+    fn set_comment(&mut self, py: Python<'_>, comment: Option<&Bound<'_, PyAny>>) -> PyResult<()> {
         if let Some(sheet) = self.sheet_name(py)? {
             if let Some(ref wb) = self.workbook {
                 let mut wb_ref = wb.borrow_mut(py);
                 return wb_ref.set_cell_comment(&sheet, self.row, self.column, comment);
             }
         }
-        self.comment_internal = comment;
+        self.comment_internal = comment
+            .map(crate::comment::extract_comment)
+            .transpose()?
+            .map(|c| c.text);
         Ok(())
     }
 
@@ -350,9 +397,17 @@ impl PyCell {
     }
 
     /// The cell's value rendered the way Excel would display it under this
-    /// cell's number format (its format code applied to the value).
+    /// cell's number format (its format code applied to the value). A
+    /// formula shows its cached result, or "" when the file has none.
     #[getter]
     fn display_value(&self, py: Python<'_>) -> PyResult<String> {
+        if let Some(sheet) = self.sheet_name(py)? {
+            if let Some(ref wb) = self.workbook {
+                return wb
+                    .borrow(py)
+                    .get_cell_display_value(&sheet, self.row, self.column);
+            }
+        }
         let val = self.value(py)?;
         let fmt = self
             .number_format(py)?
@@ -381,6 +436,38 @@ impl PyCell {
         Ok(())
     }
 
+    /// Name of the named style the cell uses ("Normal" by default).
+    #[getter]
+    fn style(&self, py: Python<'_>) -> PyResult<String> {
+        if let Some(sheet) = self.sheet_name(py)? {
+            if let Some(ref wb) = self.workbook {
+                return wb
+                    .borrow(py)
+                    .get_cell_named_style(&sheet, self.row, self.column);
+            }
+        }
+        Ok("Normal".to_string())
+    }
+
+    /// Apply a named style, given by name (a registered or built-in
+    /// style such as "Headline 1") or as a NamedStyle.
+    #[setter]
+    fn set_style(&mut self, py: Python<'_>, style: &Bound<'_, PyAny>) -> PyResult<()> {
+        if let Some(sheet) = self.sheet_name(py)? {
+            if let Some(ref wb) = self.workbook {
+                return wb.borrow_mut(py).set_cell_named_style(
+                    &sheet,
+                    self.row,
+                    self.column,
+                    style,
+                );
+            }
+        }
+        Err(pyo3::exceptions::PyValueError::new_err(
+            "named styles can only be set on cells that belong to a workbook",
+        ))
+    }
+
     /// Get the data type of the cell: 'n' number, 's' string, 'b' bool,
     /// 'f' formula, 'd' datetime.
     #[getter]
@@ -438,6 +525,29 @@ impl PyCell {
         self.__str__(py)
     }
 
+    /// Cells addressing the same position of the same sheet are equal, so
+    /// repeated `ws["A1"]` lookups compare (and hash) alike, as in openpyxl.
+    /// A detached cell is equal only to itself.
+    fn __eq__(slf: &Bound<'_, Self>, other: &Bound<'_, PyAny>) -> bool {
+        if slf.is(other) {
+            return true;
+        }
+        other.downcast::<Self>().is_ok_and(|other| {
+            let key = slf.borrow().identity_key();
+            key.is_some() && key == other.borrow().identity_key()
+        })
+    }
+
+    fn __hash__(slf: &Bound<'_, Self>) -> u64 {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        match slf.borrow().identity_key() {
+            Some(key) => key.hash(&mut hasher),
+            None => (slf.as_ptr() as usize).hash(&mut hasher),
+        }
+        hasher.finish()
+    }
+
     /// GC support: a connected cell holds a workbook reference and possibly
     /// an arbitrary cached value, so reference cycles through Python objects
     /// must be traversable.
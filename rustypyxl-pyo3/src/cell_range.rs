@@ -0,0 +1,226 @@
+//! openpyxl-style range objects: `CellRange` for one rectangular range and
+//! `MultiCellRange` for a set of them, as returned by `ws.merged_cells`.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyString};
+
+use rustypyxl_core::{column_to_letter, parse_coordinate};
+
+/// A rectangular range of cells with 1-based bounds.
+#[pyclass(name = "CellRange", frozen)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PyCellRange {
+    #[pyo3(get)]
+    pub min_row: u32,
+    #[pyo3(get)]
+    pub min_col: u32,
+    #[pyo3(get)]
+    pub max_row: u32,
+    #[pyo3(get)]
+    pub max_col: u32,
+}
+
+impl PyCellRange {
+    /// Parse "A1:C3", "$A$1:$C$3" or a single cell such as "B2".
+    pub fn parse(range: &str) -> PyResult<Self> {
+        let range = range.replace('$', "");
+        let (start, end) = range.split_once(':').unwrap_or((&range, &range));
+        let parse =
+            |coord: &str| parse_coordinate(coord).map_err(|e| PyValueError::new_err(e.to_string()));
+        let (r1, c1) = parse(start)?;
+        let (r2, c2) = parse(end)?;
+        Ok(PyCellRange {
+            min_row: r1.min(r2),
+            min_col: c1.min(c2),
+            max_row: r1.max(r2),
+            max_col: c1.max(c2),
+        })
+    }
+
+    fn from_python(value: &Bound<'_, PyAny>) -> Option<Self> {
+        if let Ok(range) = value.downcast::<Self>() {
+            return Some(range.get().clone());
+        }
+        value
+            .extract::<String>()
+            .ok()
+            .and_then(|s| Self::parse(&s).ok())
+    }
+}
+
+#[pymethods]
+impl PyCellRange {
+    #[new]
+    #[pyo3(signature = (range_string=None, min_col=None, min_row=None, max_col=None, max_row=None))]
+    fn new(
+        range_string: Option<&str>,
+        min_col: Option<u32>,
+        min_row: Option<u32>,
+        max_col: Option<u32>,
+        max_row: Option<u32>,
+    ) -> PyResult<Self> {
+        if let Some(range) = range_string {
+            return Self::parse(range);
+        }
+        match (min_col, min_row, max_col, max_row) {
+            (Some(min_col), Some(min_row), Some(max_col), Some(max_row))
+                if min_col >= 1 && min_row >= 1 && min_col <= max_col && min_row <= max_row =>
+            {
+                Ok(PyCellRange {
+                    min_row,
+                    min_col,
+                    max_row,
+                    max_col,
+                })
+            }
+            (Some(_), Some(_), Some(_), Some(_)) => Err(PyValueError::new_err(
+                "CellRange bounds must be 1-based with min <= max",
+            )),
+            _ => Err(PyValueError::new_err(
+                "Provide a range string or min_col, min_row, max_col and max_row",
+            )),
+        }
+    }
+
+    /// The range as "A1:C3".
+    #[getter]
+    fn coord(&self) -> String {
+        format!(
+            "{}{}:{}{}",
+            column_to_letter(self.min_col),
+            self.min_row,
+            column_to_letter(self.max_col),
+            self.max_row
+        )
+    }
+
+    /// (min_col, min_row, max_col, max_row), in openpyxl's order.
+    #[getter]
+    fn bounds(&self) -> (u32, u32, u32, u32) {
+        (self.min_col, self.min_row, self.max_col, self.max_row)
+    }
+
+    /// (rows, columns) spanned by the range.
+    #[getter]
+    fn size(&self) -> (u32, u32) {
+        (
+            self.max_row - self.min_row + 1,
+            self.max_col - self.min_col + 1,
+        )
+    }
+
+    /// Whether a cell coordinate or another range lies inside this one.
+    fn __contains__(&self, other: &Bound<'_, PyAny>) -> bool {
+        Self::from_python(other).is_some_and(|other| {
+            self.min_row <= other.min_row
+                && self.min_col <= other.min_col
+                && other.max_row <= self.max_row
+                && other.max_col <= self.max_col
+        })
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        Self::from_python(other).is_some_and(|other| other == *self)
+    }
+
+    /// Hashes like the coordinate string it compares equal to.
+    fn __hash__(&self, py: Python<'_>) -> PyResult<isize> {
+        PyString::new(py, &self.coord()).hash()
+    }
+
+    fn __str__(&self) -> String {
+        self.coord()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<CellRange {}>", self.coord())
+    }
+}
+
+/// A collection of `CellRange`s, such as a sheet's merged ranges.
+#[pyclass(name = "MultiCellRange", frozen)]
+#[derive(Clone, Debug, Default)]
+pub struct PyMultiCellRange {
+    pub ranges: Vec<PyCellRange>,
+}
+
+#[pymethods]
+impl PyMultiCellRange {
+    /// The ranges, in the order they were added.
+    #[getter]
+    fn ranges(&self) -> Vec<PyCellRange> {
+        self.ranges.clone()
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        Ok(PyList::new(py, self.ranges.clone())?
+            .try_iter()?
+            .into_any()
+            .unbind())
+    }
+
+    fn __len__(&self) -> usize {
+        self.ranges.len()
+    }
+
+    fn __bool__(&self) -> bool {
+        !self.ranges.is_empty()
+    }
+
+    /// Whether a range (or "A1:B2" string) is one of the ranges, or a cell
+    /// coordinate falls inside one of them.
+    fn __contains__(&self, item: &Bound<'_, PyAny>) -> bool {
+        let Some(item) = PyCellRange::from_python(item) else {
+            return false;
+        };
+        let single = item.min_row == item.max_row && item.min_col == item.max_col;
+        self.ranges.iter().any(|range| {
+            *range == item
+                || (single
+                    && (range.min_row..=range.max_row).contains(&item.min_row)
+                    && (range.min_col..=range.max_col).contains(&item.min_col))
+        })
+    }
+
+    /// Equal to another `MultiCellRange` or any sequence of ranges or range
+    /// strings holding the same ranges, in any order.
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        let items: Vec<Bound<'_, PyAny>> = if let Ok(other) = other.downcast::<Self>() {
+            return sorted(&other.get().ranges) == sorted(&self.ranges);
+        } else if let Ok(iter) = other.try_iter() {
+            if other.is_instance_of::<PyString>() {
+                return false;
+            }
+            match iter.collect::<PyResult<_>>() {
+                Ok(items) => items,
+                Err(_) => return false,
+            }
+        } else {
+            return false;
+        };
+        let ranges: Option<Vec<PyCellRange>> = items.iter().map(PyCellRange::from_python).collect();
+        ranges.is_some_and(|ranges| sorted(&ranges) == sorted(&self.ranges))
+    }
+
+    fn __str__(&self) -> String {
+        self.ranges
+            .iter()
+            .map(|range| range.coord())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<MultiCellRange [{}]>", self.__str__())
+    }
+}
+
+fn sorted(ranges: &[PyCellRange]) -> Vec<(u32, u32, u32, u32)> {
+    let mut bounds: Vec<_> = ranges
+        .iter()
+        .map(|r| (r.min_row, r.min_col, r.max_row, r.max_col))
+        .collect();
+    bounds.sort_unstable();
+    bounds
+}
@@ -0,0 +1,236 @@
+//! Chart builders (openpyxl-compatible): `BarChart`, `LineChart` and
+//! `PieChart`, filled with `add_data`/`set_categories` and placed with
+//! `ws.add_chart(chart, "E5")`.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use rustypyxl_core::chart::{
+    series_from_reference, BarDirection, BarGrouping, Chart, ChartAxis, ChartLegend, ChartTitle,
+    ChartType,
+};
+
+/// EMUs per centimetre; openpyxl sizes charts in cm.
+const EMU_PER_CM: f64 = 360_000.0;
+
+/// Shared state and methods of the chart classes.
+#[pyclass(name = "ChartBase", subclass)]
+#[derive(Clone, Debug)]
+pub struct PyChartBase {
+    pub inner: Chart,
+}
+
+impl PyChartBase {
+    fn with_type(chart: Chart) -> Self {
+        // openpyxl's default size is 15 x 7.5 cm.
+        let mut inner = chart;
+        inner.width = (15.0 * EMU_PER_CM) as u32;
+        inner.height = (7.5 * EMU_PER_CM) as u32;
+        PyChartBase { inner }
+    }
+
+    fn axis_title(axis: &Option<ChartAxis>) -> Option<String> {
+        axis.as_ref().and_then(|a| a.title.clone())
+    }
+
+    fn set_axis_title(axis: &mut Option<ChartAxis>, title: Option<String>) {
+        match (axis.as_mut(), title) {
+            (Some(a), title) => a.title = title,
+            (None, Some(t)) => *axis = Some(ChartAxis::new().with_title(t)),
+            (None, None) => {}
+        }
+    }
+}
+
+#[pymethods]
+impl PyChartBase {
+    /// Chart title, or None.
+    #[getter]
+    fn title(&self) -> Option<String> {
+        self.inner.title.as_ref().and_then(|t| t.text.clone())
+    }
+
+    #[setter]
+    fn set_title(&mut self, value: Option<String>) {
+        self.inner.title = value.map(ChartTitle::new);
+    }
+
+    /// Legend position ("r", "l", "t", "b", "tr"), or None to hide it.
+    #[getter]
+    fn legend(&self) -> Option<String> {
+        self.inner
+            .legend
+            .as_ref()
+            .filter(|l| l.visible)
+            .map(|l| l.position.clone())
+    }
+
+    #[setter]
+    fn set_legend(&mut self, value: Option<String>) -> PyResult<()> {
+        self.inner.legend = Some(match value {
+            Some(pos) => {
+                if !matches!(pos.as_str(), "r" | "l" | "t" | "b" | "tr") {
+                    return Err(PyValueError::new_err(format!(
+                        "unknown legend position {pos:?}; expected r/l/t/b/tr"
+                    )));
+                }
+                ChartLegend::new().with_position(pos)
+            }
+            None => ChartLegend::new().with_visible(false),
+        });
+        Ok(())
+    }
+
+    /// Width in centimetres.
+    #[getter]
+    fn width(&self) -> f64 {
+        self.inner.width as f64 / EMU_PER_CM
+    }
+
+    #[setter]
+    fn set_width(&mut self, value: f64) {
+        self.inner.width = (value * EMU_PER_CM) as u32;
+    }
+
+    /// Height in centimetres.
+    #[getter]
+    fn height(&self) -> f64 {
+        self.inner.height as f64 / EMU_PER_CM
+    }
+
+    #[setter]
+    fn set_height(&mut self, value: f64) {
+        self.inner.height = (value * EMU_PER_CM) as u32;
+    }
+
+    /// Category axis title, or None.
+    #[getter]
+    fn x_axis_title(&self) -> Option<String> {
+        Self::axis_title(&self.inner.x_axis)
+    }
+
+    #[setter]
+    fn set_x_axis_title(&mut self, value: Option<String>) {
+        Self::set_axis_title(&mut self.inner.x_axis, value);
+    }
+
+    /// Value axis title, or None.
+    #[getter]
+    fn y_axis_title(&self) -> Option<String> {
+        Self::axis_title(&self.inner.y_axis)
+    }
+
+    #[setter]
+    fn set_y_axis_title(&mut self, value: Option<String>) {
+        Self::set_axis_title(&mut self.inner.y_axis, value);
+    }
+
+    /// Number of series added so far.
+    #[getter]
+    fn series_count(&self) -> usize {
+        self.inner.series.len()
+    }
+
+    /// Add one series per column of a sheet-qualified range such as
+    /// "Data!$B$1:$C$5". With `titles_from_data` the first row names the
+    /// series.
+    #[pyo3(signature = (data, titles_from_data=false))]
+    fn add_data(&mut self, data: &str, titles_from_data: bool) -> PyResult<()> {
+        let series = series_from_reference(data, titles_from_data)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.inner.series.extend(series);
+        Ok(())
+    }
+
+    /// Use `labels` (e.g. "Data!$A$2:$A$5") as the categories of every series
+    /// added so far.
+    fn set_categories(&mut self, labels: &str) {
+        for series in &mut self.inner.series {
+            series.categories = Some(labels.to_string());
+        }
+    }
+}
+
+/// Bar chart; `type` is "col" (vertical, the default) or "bar" (horizontal).
+#[pyclass(name = "BarChart", extends = PyChartBase)]
+pub struct PyBarChart;
+
+#[pymethods]
+impl PyBarChart {
+    #[new]
+    fn new() -> (Self, PyChartBase) {
+        (PyBarChart, PyChartBase::with_type(Chart::column()))
+    }
+
+    #[getter(r#type)]
+    fn get_type(self_: PyRef<'_, Self>) -> &'static str {
+        match self_.as_super().inner.bar_direction {
+            BarDirection::Bar => "bar",
+            BarDirection::Col => "col",
+        }
+    }
+
+    #[setter(r#type)]
+    fn set_type(mut self_: PyRefMut<'_, Self>, value: &str) -> PyResult<()> {
+        let inner = &mut self_.as_super().inner;
+        (inner.chart_type, inner.bar_direction) = match value {
+            "col" => (ChartType::Column, BarDirection::Col),
+            "bar" => (ChartType::Bar, BarDirection::Bar),
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown bar chart type {other:?}; expected 'col' or 'bar'"
+                )))
+            }
+        };
+        Ok(())
+    }
+
+    /// "clustered", "stacked" or "percentStacked".
+    #[getter]
+    fn grouping(self_: PyRef<'_, Self>) -> &'static str {
+        match self_.as_super().inner.bar_grouping {
+            BarGrouping::Clustered => "clustered",
+            BarGrouping::Stacked => "stacked",
+            BarGrouping::PercentStacked => "percentStacked",
+        }
+    }
+
+    #[setter]
+    fn set_grouping(mut self_: PyRefMut<'_, Self>, value: &str) -> PyResult<()> {
+        self_.as_super().inner.bar_grouping = match value {
+            "clustered" => BarGrouping::Clustered,
+            "stacked" => BarGrouping::Stacked,
+            "percentStacked" => BarGrouping::PercentStacked,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown grouping {other:?}; expected clustered/stacked/percentStacked"
+                )))
+            }
+        };
+        Ok(())
+    }
+}
+
+/// Line chart.
+#[pyclass(name = "LineChart", extends = PyChartBase)]
+pub struct PyLineChart;
+
+#[pymethods]
+impl PyLineChart {
+    #[new]
+    fn new() -> (Self, PyChartBase) {
+        (PyLineChart, PyChartBase::with_type(Chart::line()))
+    }
+}
+
+/// Pie chart. Only the first series is drawn.
+#[pyclass(name = "PieChart", extends = PyChartBase)]
+pub struct PyPieChart;
+
+#[pymethods]
+impl PyPieChart {
+    #[new]
+    fn new() -> (Self, PyChartBase) {
+        (PyPieChart, PyChartBase::with_type(Chart::pie()))
+    }
+}
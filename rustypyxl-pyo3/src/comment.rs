@@ -0,0 +1,144 @@
+//! `Comment`: a cell comment with its author and box size.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use rustypyxl_core::{Comment, ThreadedComment};
+
+/// A cell comment (openpyxl-compatible).
+///
+/// Args:
+///     text: The comment text
+///     author: Who wrote it
+///     width: Box width in points (default 108)
+///     height: Box height in points (default 59.25)
+///
+/// Assign it with `cell.comment = Comment("Check this", "Ann")`. Threaded
+/// comments, with replies, are added with `ws.add_threaded_comment`.
+#[pyclass(name = "Comment")]
+#[derive(Clone, Debug)]
+pub struct PyComment {
+    pub inner: Comment,
+}
+
+impl PyComment {
+    pub fn from_core(comment: &Comment) -> Self {
+        PyComment {
+            inner: comment.clone(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyComment {
+    #[new]
+    #[pyo3(signature = (text, author=None, width=None, height=None))]
+    fn new(
+        text: String,
+        author: Option<String>,
+        width: Option<f64>,
+        height: Option<f64>,
+    ) -> PyResult<Self> {
+        if width.is_some_and(|w| w <= 0.0) || height.is_some_and(|h| h <= 0.0) {
+            return Err(PyValueError::new_err(
+                "comment box width and height must be positive",
+            ));
+        }
+        let mut inner = Comment::new(text);
+        inner.author = author;
+        inner.width = width;
+        inner.height = height;
+        Ok(PyComment { inner })
+    }
+
+    #[getter]
+    fn text(&self) -> &str {
+        &self.inner.text
+    }
+
+    #[setter]
+    fn set_text(&mut self, text: String) {
+        self.inner.text = text;
+        self.inner.rich_text = None;
+    }
+
+    #[getter]
+    fn author(&self) -> Option<&str> {
+        self.inner.author.as_deref()
+    }
+
+    #[setter]
+    fn set_author(&mut self, author: Option<String>) {
+        self.inner.author = author;
+    }
+
+    #[getter]
+    fn width(&self) -> Option<f64> {
+        self.inner.width
+    }
+
+    #[setter]
+    fn set_width(&mut self, width: Option<f64>) {
+        self.inner.width = width;
+    }
+
+    #[getter]
+    fn height(&self) -> Option<f64> {
+        self.inner.height
+    }
+
+    #[setter]
+    fn set_height(&mut self, height: Option<f64>) {
+        self.inner.height = height;
+    }
+
+    /// Whether this is a threaded comment rather than a note.
+    #[getter]
+    fn threaded(&self) -> bool {
+        self.inner.is_threaded()
+    }
+
+    /// The conversation of a threaded comment as a list of dicts with
+    /// "author", "text" and "created", first entry first; empty for a note.
+    #[getter]
+    fn thread<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.inner
+            .thread
+            .iter()
+            .map(|entry: &ThreadedComment| {
+                let dict = PyDict::new(py);
+                dict.set_item("author", &entry.author)?;
+                dict.set_item("text", &entry.text)?;
+                dict.set_item("created", entry.created.as_deref())?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        other
+            .downcast::<Self>()
+            .is_ok_and(|other| other.borrow().inner == self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Comment(text={:?}, author={:?})",
+            self.inner.text, self.inner.author
+        )
+    }
+}
+
+/// A comment from a `cell.comment` assignment: comment text or a Comment.
+pub fn extract_comment(value: &Bound<'_, PyAny>) -> PyResult<Comment> {
+    if let Ok(text) = value.extract::<String>() {
+        return Ok(Comment::new(text));
+    }
+    match value.downcast::<PyComment>() {
+        Ok(comment) => Ok(comment.borrow().inner.clone()),
+        Err(_) => Err(pyo3::exceptions::PyTypeError::new_err(
+            "comment must be a string or a Comment",
+        )),
+    }
+}
@@ -3,7 +3,11 @@
 
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyFloat};
 use pyo3::Py;
+use rustypyxl_core::autofilter::{
+    AutoFilter, CustomFilter, FilterColumn, FilterOperator, FilterType, Top10Filter,
+};
 use rustypyxl_core::{column_to_letter, letter_to_column};
 
 use crate::workbook::PyWorkbook;
@@ -14,6 +18,15 @@ fn sheet_index(wb: &PyWorkbook, uid: u64) -> PyResult<usize> {
         .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))
 }
 
+fn check_outline_level(level: u8) -> PyResult<()> {
+    if level > rustypyxl_core::outline::MAX_OUTLINE_LEVEL {
+        return Err(PyValueError::new_err(format!(
+            "outlineLevel must be between 0 and 7, got {level}"
+        )));
+    }
+    Ok(())
+}
+
 /// The mapping returned by `ws.column_dimensions`; index by column letter.
 #[pyclass(name = "ColumnDimensions")]
 pub struct PyColumnDimensions {
@@ -31,10 +44,32 @@ impl PyColumnDimensions {
             column,
         })
     }
+
+    /// Group columns `start` to `end` (letters, inclusive), as openpyxl's
+    /// `ws.column_dimensions.group("B", "D", hidden=True)`.
+    #[pyo3(signature = (start, end=None, outline_level=1, hidden=false))]
+    fn group(
+        &self,
+        start: &str,
+        end: Option<&str>,
+        outline_level: u8,
+        hidden: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let to_column =
+            |key: &str| letter_to_column(key).map_err(|e| PyValueError::new_err(e.to_string()));
+        let first = to_column(start)?;
+        let last = end.map_or(Ok(first), to_column)?;
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        this.inner.worksheets[idx]
+            .group_columns(first, last, outline_level, hidden)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
 }
 
-/// A single column's dimension (width). Setting `width` writes through to the
-/// worksheet; reading returns the set width or None.
+/// A single column's dimension (width, visibility, grouping). Setting `width`
+/// writes through to the worksheet; reading returns the set width or None.
 #[pyclass(name = "ColumnDimension")]
 pub struct PyColumnDimension {
     workbook: Py<PyWorkbook>,
@@ -61,6 +96,59 @@ impl PyColumnDimension {
         Ok(())
     }
 
+    /// Outline level, 0 (not grouped) to 7.
+    #[getter(outlineLevel)]
+    fn outline_level(&self, py: Python<'_>) -> PyResult<u8> {
+        let this = self.workbook.borrow(py);
+        let idx = sheet_index(&this, self.uid)?;
+        Ok(this.inner.worksheets[idx]
+            .column_props(self.column)
+            .outline_level)
+    }
+
+    #[setter(outlineLevel)]
+    fn set_outline_level(&self, py: Python<'_>, level: u8) -> PyResult<()> {
+        check_outline_level(level)?;
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        this.inner.worksheets[idx]
+            .update_column_props(self.column, self.column, |p| p.outline_level = level);
+        Ok(())
+    }
+
+    #[getter]
+    fn hidden(&self, py: Python<'_>) -> PyResult<bool> {
+        let this = self.workbook.borrow(py);
+        let idx = sheet_index(&this, self.uid)?;
+        Ok(this.inner.worksheets[idx].is_column_hidden(self.column))
+    }
+
+    #[setter]
+    fn set_hidden(&self, py: Python<'_>, hidden: bool) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        this.inner.worksheets[idx].set_column_hidden(self.column, hidden);
+        Ok(())
+    }
+
+    /// Whether the width was fitted to the column's content.
+    #[getter(bestFit)]
+    fn best_fit(&self, py: Python<'_>) -> PyResult<bool> {
+        let this = self.workbook.borrow(py);
+        let idx = sheet_index(&this, self.uid)?;
+        Ok(this.inner.worksheets[idx]
+            .column_props(self.column)
+            .best_fit)
+    }
+
+    #[setter(bestFit)]
+    fn set_best_fit(&self, py: Python<'_>, best_fit: bool) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        this.inner.worksheets[idx].set_column_best_fit(self.column, best_fit);
+        Ok(())
+    }
+
     /// The column letter this proxy addresses.
     #[getter]
     fn index(&self) -> String {
@@ -88,14 +176,199 @@ impl PyAutoFilter {
             .map(|af| af.range.clone()))
     }
 
+    /// Setting a new range keeps the column filters; setting None removes
+    /// the filter entirely.
     #[setter(r#ref)]
     fn set_ref(&self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
-        use rustypyxl_core::autofilter::AutoFilter;
         let mut this = self.workbook.borrow_mut(py);
         let idx = sheet_index(&this, self.uid)?;
-        this.inner.worksheets[idx].auto_filter = value.map(AutoFilter::new);
+        let ws = &mut this.inner.worksheets[idx];
+        match (value, ws.auto_filter.as_mut()) {
+            (Some(range), Some(af)) => af.range = range,
+            (value, _) => ws.auto_filter = value.map(AutoFilter::new),
+        }
         Ok(())
     }
+
+    /// Show only rows whose value in column `col_id` (0-based, relative to
+    /// the filter range) is one of `vals`.
+    fn add_filter_column(
+        &self,
+        py: Python<'_>,
+        col_id: u32,
+        vals: Vec<Bound<'_, PyAny>>,
+    ) -> PyResult<()> {
+        let values = vals
+            .iter()
+            .map(filter_value_text)
+            .collect::<PyResult<Vec<_>>>()?;
+        self.with_filter(py, |af| af.add_filter(FilterColumn::values(col_id, values)))
+    }
+
+    /// Filter column `col_id` with a comparison such as `(">", 10)`, optionally
+    /// combined with a second one; `join` is "and" or "or".
+    #[pyo3(signature = (col_id, operator, value, operator2=None, value2=None, join="and"))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_custom_filter(
+        &self,
+        py: Python<'_>,
+        col_id: u32,
+        operator: &str,
+        value: Bound<'_, PyAny>,
+        operator2: Option<&str>,
+        value2: Option<Bound<'_, PyAny>>,
+        join: &str,
+    ) -> PyResult<()> {
+        let mut filter = CustomFilter::new(parse_operator(operator)?, &filter_value_text(&value)?);
+        match (operator2, value2) {
+            (Some(op), Some(v)) => {
+                let (op, v) = (parse_operator(op)?, filter_value_text(&v)?);
+                filter = match join {
+                    "and" => filter.and(op, &v),
+                    "or" => filter.or(op, &v),
+                    other => {
+                        return Err(PyValueError::new_err(format!(
+                            "join must be \"and\" or \"or\", not {other:?}"
+                        )))
+                    }
+                };
+            }
+            (None, None) => {}
+            _ => {
+                return Err(PyValueError::new_err(
+                    "operator2 and value2 must be given together",
+                ))
+            }
+        }
+        self.with_filter(py, |af| af.add_filter(FilterColumn::custom(col_id, filter)))
+    }
+
+    /// Show the top (or bottom) `value` items of column `col_id`, or the top
+    /// `value` percent when `percent` is true.
+    #[pyo3(signature = (col_id, value, top=true, percent=false))]
+    fn add_top10_filter(
+        &self,
+        py: Python<'_>,
+        col_id: u32,
+        value: f64,
+        top: bool,
+        percent: bool,
+    ) -> PyResult<()> {
+        let filter = FilterType::Top10Filter(Top10Filter {
+            top,
+            value,
+            percent,
+        });
+        self.with_filter(py, |af| {
+            af.add_filter(FilterColumn {
+                column_id: col_id,
+                filter,
+                show_button: true,
+            })
+        })
+    }
+
+    /// Record a sort on the column of `ref` (e.g. "B2:B15").
+    #[pyo3(signature = (r#ref, descending=false))]
+    fn add_sort_condition(&self, py: Python<'_>, r#ref: &str, descending: bool) -> PyResult<()> {
+        let letters: String = r#ref
+            .chars()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .collect();
+        let column =
+            letter_to_column(&letters).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.with_filter(py, |af| af.sort_by(column - 1, descending))
+    }
+
+    /// Remove every column filter and sort, keeping the dropdowns.
+    fn clear_filters(&self, py: Python<'_>) -> PyResult<()> {
+        self.with_filter(py, |af| {
+            af.columns.clear();
+            af.sort_column = None;
+            af.sort_descending = false;
+        })
+    }
+
+    /// The column filters as dicts with "col_id", "type" ("values",
+    /// "custom", "top10", "dynamic" or "color") and the filter's settings.
+    #[getter]
+    fn filter_columns<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let this = self.workbook.borrow(py);
+        let idx = sheet_index(&this, self.uid)?;
+        let Some(af) = this.inner.worksheets[idx].auto_filter.as_ref() else {
+            return Ok(Vec::new());
+        };
+        af.columns
+            .iter()
+            .map(|column| {
+                let d = PyDict::new(py);
+                d.set_item("col_id", column.column_id)?;
+                match &column.filter {
+                    FilterType::Values(values) => {
+                        d.set_item("type", "values")?;
+                        d.set_item("values", values)?;
+                    }
+                    FilterType::Custom(c) => {
+                        d.set_item("type", "custom")?;
+                        d.set_item("operator", c.operator1.xml_value())?;
+                        d.set_item("value", &c.value1)?;
+                        if let (Some(op), Some(v)) = (&c.operator2, &c.value2) {
+                            d.set_item("operator2", op.xml_value())?;
+                            d.set_item("value2", v)?;
+                            d.set_item("join", if c.and { "and" } else { "or" })?;
+                        }
+                    }
+                    FilterType::Top10Filter(t) => {
+                        d.set_item("type", "top10")?;
+                        d.set_item("value", t.value)?;
+                        d.set_item("top", t.top)?;
+                        d.set_item("percent", t.percent)?;
+                    }
+                    FilterType::DynamicFilter(kind) => {
+                        d.set_item("type", "dynamic")?;
+                        d.set_item("value", kind.xml_type())?;
+                    }
+                    FilterType::ColorFilter(c) => {
+                        d.set_item("type", "color")?;
+                        d.set_item("color", &c.color)?;
+                        d.set_item("cell_color", c.cell_color)?;
+                    }
+                }
+                Ok(d)
+            })
+            .collect()
+    }
+}
+
+impl PyAutoFilter {
+    /// Run `f` on the sheet's filter, which must have a range set.
+    fn with_filter(&self, py: Python<'_>, f: impl FnOnce(&mut AutoFilter)) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        let af = this.inner.worksheets[idx]
+            .auto_filter
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("Set auto_filter.ref before adding filters"))?;
+        f(af);
+        Ok(())
+    }
+}
+
+fn parse_operator(name: &str) -> PyResult<FilterOperator> {
+    FilterOperator::parse(name)
+        .ok_or_else(|| PyValueError::new_err(format!("unknown filter operator {name:?}")))
+}
+
+/// The text Excel compares a filter value against: whole floats lose their
+/// ".0" so `3.0` matches a cell showing 3.
+fn filter_value_text(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(f) = value.downcast::<PyFloat>() {
+        let f = f.value();
+        if f.fract() == 0.0 && f.abs() < 1e15 {
+            return Ok(format!("{}", f as i64));
+        }
+    }
+    Ok(value.str()?.to_string())
 }
 
 /// The mapping returned by `ws.row_dimensions`; index by 1-based row number.
@@ -114,9 +387,27 @@ impl PyRowDimensions {
             row,
         })
     }
+
+    /// Group rows `start` to `end` (inclusive), as openpyxl's
+    /// `ws.row_dimensions.group(5, 15, hidden=True)`.
+    #[pyo3(signature = (start, end=None, outline_level=1, hidden=false))]
+    fn group(
+        &self,
+        start: u32,
+        end: Option<u32>,
+        outline_level: u8,
+        hidden: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        this.inner.worksheets[idx]
+            .group_rows(start, end.unwrap_or(start), outline_level, hidden)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
 }
 
-/// A single row's dimension (height).
+/// A single row's dimension (height, visibility, grouping).
 #[pyclass(name = "RowDimension")]
 pub struct PyRowDimension {
     workbook: Py<PyWorkbook>,
@@ -143,6 +434,38 @@ impl PyRowDimension {
         Ok(())
     }
 
+    /// Outline level, 0 (not grouped) to 7.
+    #[getter(outlineLevel)]
+    fn outline_level(&self, py: Python<'_>) -> PyResult<u8> {
+        let this = self.workbook.borrow(py);
+        let idx = sheet_index(&this, self.uid)?;
+        Ok(this.inner.worksheets[idx].row_props(self.row).outline_level)
+    }
+
+    #[setter(outlineLevel)]
+    fn set_outline_level(&self, py: Python<'_>, level: u8) -> PyResult<()> {
+        check_outline_level(level)?;
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        this.inner.worksheets[idx].update_row_props(self.row, |p| p.outline_level = level);
+        Ok(())
+    }
+
+    #[getter]
+    fn hidden(&self, py: Python<'_>) -> PyResult<bool> {
+        let this = self.workbook.borrow(py);
+        let idx = sheet_index(&this, self.uid)?;
+        Ok(this.inner.worksheets[idx].is_row_hidden(self.row))
+    }
+
+    #[setter]
+    fn set_hidden(&self, py: Python<'_>, hidden: bool) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = sheet_index(&this, self.uid)?;
+        this.inner.worksheets[idx].set_row_hidden(self.row, hidden);
+        Ok(())
+    }
+
     /// The row number this proxy addresses.
     #[getter]
     fn index(&self) -> u32 {
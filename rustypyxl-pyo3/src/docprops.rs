@@ -0,0 +1,173 @@
+//! `wb.properties`: author, title and timestamps, named after openpyxl's
+//! `DocumentProperties`.
+
+#![allow(non_snake_case)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rustypyxl_core::docprops::{format_timestamp, parse_timestamp};
+use rustypyxl_core::DocumentProperties;
+
+use crate::streaming::PyStreamingWorkbook;
+use crate::workbook::PyWorkbook;
+
+/// The workbook whose properties a proxy edits.
+pub(crate) enum PropertiesOwner {
+    Workbook(Py<PyWorkbook>),
+    Streaming(Py<PyStreamingWorkbook>),
+}
+
+/// Document properties written to docProps on save:
+/// `wb.properties.creator = "Finance"`.
+///
+/// Timestamps are naive UTC datetimes. `created` and `modified` are None
+/// until set, meaning the time of saving; set both to a fixed datetime for
+/// byte-for-byte reproducible files.
+#[pyclass(name = "DocumentProperties")]
+pub struct PyDocumentProperties {
+    pub(crate) owner: PropertiesOwner,
+}
+
+impl PyDocumentProperties {
+    fn read<R>(&self, py: Python<'_>, f: impl FnOnce(&DocumentProperties) -> R) -> PyResult<R> {
+        match &self.owner {
+            PropertiesOwner::Workbook(wb) => Ok(f(&wb.borrow(py).inner.properties)),
+            PropertiesOwner::Streaming(wb) => {
+                let wb = wb.borrow(py);
+                let inner = wb
+                    .inner
+                    .as_ref()
+                    .ok_or_else(|| PyValueError::new_err("Workbook already closed"))?;
+                Ok(f(inner.properties()))
+            }
+        }
+    }
+
+    fn update(&self, py: Python<'_>, f: impl FnOnce(&mut DocumentProperties)) -> PyResult<()> {
+        match &self.owner {
+            PropertiesOwner::Workbook(wb) => f(&mut wb.borrow_mut(py).inner.properties),
+            PropertiesOwner::Streaming(wb) => {
+                let mut wb = wb.borrow_mut(py);
+                let inner = wb
+                    .inner
+                    .as_mut()
+                    .ok_or_else(|| PyValueError::new_err("Workbook already closed"))?;
+                let mut props = inner.properties().clone();
+                f(&mut props);
+                inner.set_properties(props);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A datetime, date or ISO-8601 string as a UTC timestamp; aware datetimes
+/// are converted to UTC, naive ones taken as UTC.
+fn timestamp_from_python(
+    value: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<chrono::NaiveDateTime>> {
+    let Some(value) = value.filter(|v| !v.is_none()) else {
+        return Ok(None);
+    };
+    let text = if crate::workbook::is_datetime_like(value)? {
+        value.call_method0("isoformat")?.extract::<String>()?
+    } else {
+        value.extract::<String>()?
+    };
+    parse_timestamp(&text)
+        .map(Some)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn timestamp_to_python(value: Option<chrono::NaiveDateTime>, py: Python<'_>) -> PyResult<PyObject> {
+    let Some(at) = value else {
+        return Ok(py.None());
+    };
+    let iso = format_timestamp(&at);
+    Ok(py
+        .import("datetime")?
+        .getattr("datetime")?
+        .call_method1("fromisoformat", (iso.trim_end_matches('Z'),))?
+        .unbind())
+}
+
+#[pymethods]
+impl PyDocumentProperties {
+    #[getter]
+    fn creator(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.read(py, |p| p.creator.clone())
+    }
+
+    #[setter]
+    fn set_creator(&self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.update(py, |p| p.creator = value)
+    }
+
+    #[getter]
+    fn lastModifiedBy(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.read(py, |p| p.last_modified_by.clone())
+    }
+
+    #[setter]
+    fn set_lastModifiedBy(&self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.update(py, |p| p.last_modified_by = value)
+    }
+
+    #[getter]
+    fn title(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.read(py, |p| p.title.clone())
+    }
+
+    #[setter]
+    fn set_title(&self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.update(py, |p| p.title = value)
+    }
+
+    /// The application named in docProps/app.xml ("rustypyxl").
+    #[getter]
+    fn application(&self, py: Python<'_>) -> PyResult<String> {
+        self.read(py, |p| p.application.clone())
+    }
+
+    #[setter]
+    fn set_application(&self, py: Python<'_>, value: String) -> PyResult<()> {
+        self.update(py, |p| p.application = value)
+    }
+
+    /// Creation time, or None for the time of saving. Kept from the file
+    /// when a workbook is loaded.
+    #[getter]
+    fn created(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let at = self.read(py, |p| p.created)?;
+        timestamp_to_python(at, py)
+    }
+
+    #[setter]
+    fn set_created(&self, py: Python<'_>, value: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        let at = timestamp_from_python(value.as_ref())?;
+        self.update(py, |p| p.created = at)
+    }
+
+    /// Modification time, or None for the time of saving. A fixed value
+    /// also dates the entries of the saved archive.
+    #[getter]
+    fn modified(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let at = self.read(py, |p| p.modified)?;
+        timestamp_to_python(at, py)
+    }
+
+    #[setter]
+    fn set_modified(&self, py: Python<'_>, value: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        let at = timestamp_from_python(value.as_ref())?;
+        self.update(py, |p| p.modified = at)
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        self.read(py, |p| {
+            format!(
+                "<DocumentProperties creator={:?} title={:?} lastModifiedBy={:?}>",
+                p.creator, p.title, p.last_modified_by
+            )
+        })
+    }
+}
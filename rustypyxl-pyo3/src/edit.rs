@@ -0,0 +1,154 @@
+//! Python bindings for edit mode: `load_workbook(..., edit=True)` splices
+//! cell edits into the original file on save instead of regenerating it.
+
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+
+use rustypyxl_core::edit::EditableWorkbook;
+use rustypyxl_core::utils::parse_coordinate;
+
+use crate::workbook::{cell_value_to_python, python_to_cell_value, read_source_bytes};
+
+fn to_py_err(e: rustypyxl_core::RustypyxlError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// A workbook opened with `edit=True`. Assigned cells are patched into the
+/// original worksheet XML on save; everything else in the file, including
+/// parts rustypyxl does not model, is kept byte for byte.
+#[pyclass(name = "EditableWorkbook")]
+pub struct PyEditableWorkbook {
+    inner: EditableWorkbook,
+}
+
+impl PyEditableWorkbook {
+    pub fn load(source: &Bound<'_, PyAny>, password: Option<&str>) -> PyResult<Self> {
+        let py = source.py();
+        let bytes = read_source_bytes(source)?;
+        let inner = py
+            .allow_threads(|| {
+                let bytes = match password {
+                    Some(pw) if rustypyxl_core::crypto::is_encrypted(&bytes) => {
+                        rustypyxl_core::crypto::decrypt(&bytes, pw)?
+                    }
+                    _ => bytes,
+                };
+                EditableWorkbook::from_bytes(bytes)
+            })
+            .map_err(to_py_err)?;
+        Ok(PyEditableWorkbook { inner })
+    }
+}
+
+#[pymethods]
+impl PyEditableWorkbook {
+    /// Sheet names, in workbook order.
+    #[getter]
+    fn sheetnames(&self) -> Vec<String> {
+        self.inner.sheet_names().to_vec()
+    }
+
+    fn __getitem__(slf: Bound<'_, Self>, key: &str) -> PyResult<PyEditableWorksheet> {
+        if !slf.borrow().inner.sheet_names().iter().any(|n| n == key) {
+            return Err(PyKeyError::new_err(format!(
+                "Worksheet '{}' does not exist",
+                key
+            )));
+        }
+        Ok(PyEditableWorksheet {
+            workbook: slf.unbind(),
+            title: key.to_string(),
+        })
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        self.inner.sheet_names().iter().any(|name| name == key)
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.sheet_names().len()
+    }
+
+    /// Number of cell edits waiting to be saved.
+    #[getter]
+    fn pending_edits(&self) -> usize {
+        self.inner.pending_edits()
+    }
+
+    /// Drop the edits made since the workbook was opened.
+    fn discard_edits(&mut self) {
+        self.inner.discard_edits();
+    }
+
+    /// Save the workbook with the edits applied. Only the edited worksheets
+    /// are rewritten.
+    fn save(&self, filename: std::path::PathBuf, py: Python<'_>) -> PyResult<()> {
+        py.allow_threads(|| self.inner.save(&filename.to_string_lossy()))
+            .map_err(to_py_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<EditableWorkbook sheets={:?} pending_edits={}>",
+            self.inner.sheet_names(),
+            self.inner.pending_edits()
+        )
+    }
+}
+
+/// A worksheet of an editable workbook: `ws["B2"]` reads a value and
+/// `ws["B2"] = 5` queues an edit. The cell keeps its style.
+#[pyclass(name = "EditableWorksheet")]
+pub struct PyEditableWorksheet {
+    workbook: Py<PyEditableWorkbook>,
+    title: String,
+}
+
+#[pymethods]
+impl PyEditableWorksheet {
+    #[getter]
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The value the cell will have when saved: the edit, or what the file
+    /// holds. Reading from the file scans the sheet up to the cell.
+    fn get_value(&self, row: u32, column: u32, py: Python<'_>) -> PyResult<PyObject> {
+        let wb = self.workbook.borrow(py);
+        let inner = &wb.inner;
+        let value = py
+            .allow_threads(|| inner.get_value(&self.title, row, column))
+            .map_err(to_py_err)?;
+        Ok(cell_value_to_python(&value, py))
+    }
+
+    /// Queue a new value for a cell; None clears it.
+    fn set_value(
+        &self,
+        row: u32,
+        column: u32,
+        value: &Bound<'_, PyAny>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let value = python_to_cell_value(value)?;
+        self.workbook
+            .borrow_mut(py)
+            .inner
+            .set_value(&self.title, row, column, value)
+            .map_err(to_py_err)
+    }
+
+    fn __getitem__(&self, key: &str, py: Python<'_>) -> PyResult<PyObject> {
+        let (row, column) = parse_coordinate(key).map_err(to_py_err)?;
+        self.get_value(row, column, py)
+    }
+
+    fn __setitem__(&self, key: &str, value: &Bound<'_, PyAny>, py: Python<'_>) -> PyResult<()> {
+        let (row, column) = parse_coordinate(key).map_err(to_py_err)?;
+        self.set_value(row, column, value, py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<EditableWorksheet \"{}\">", self.title)
+    }
+}
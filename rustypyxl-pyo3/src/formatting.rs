@@ -0,0 +1,467 @@
+//! openpyxl-style conditional formatting rules, exposed as
+//! `rustypyxl.formatting`: build a rule, then
+//! `ws.conditional_formatting.add("A1:A10", rule)`.
+
+#![allow(non_snake_case)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use rustypyxl_core::conditional::{
+    ColorScale, ConditionalColor, ConditionalFormat, ConditionalFormatting, ConditionalOperator,
+    ConditionalRule, DataBar, IconSet, IconSetStyle,
+};
+use rustypyxl_core::Color;
+
+use crate::style::{coerce_color, PyBorder, PyFont, PyPatternFill};
+use crate::workbook::PyWorkbook;
+
+fn conditional_color(color: Color) -> ConditionalColor {
+    ConditionalColor {
+        rgb: color.rgb,
+        theme: color.theme,
+        tint: color.tint,
+    }
+}
+
+fn color_arg(value: Option<&Bound<'_, PyAny>>) -> PyResult<Option<ConditionalColor>> {
+    Ok(coerce_color(value)?.map(conditional_color))
+}
+
+/// A threshold value as it appears in a `cfvo`: numbers lose a trailing
+/// ".0", anything else is used as its string form.
+fn threshold_value(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    if let Ok(n) = value.extract::<f64>() {
+        if !value.is_instance_of::<pyo3::types::PyString>() {
+            return Ok(if n.fract() == 0.0 && n.abs() < 1e15 {
+                (n as i64).to_string()
+            } else {
+                n.to_string()
+            });
+        }
+    }
+    Ok(value.str()?.to_string())
+}
+
+/// `formula` may be one string or a list of up to two.
+fn formulas(formula: Option<&Bound<'_, PyAny>>) -> PyResult<Vec<String>> {
+    let Some(formula) = formula.filter(|f| !f.is_none()) else {
+        return Ok(Vec::new());
+    };
+    let list: Vec<String> = match formula.extract::<String>() {
+        Ok(single) => vec![single],
+        Err(_) => formula
+            .try_iter()?
+            .map(|f| threshold_value(&f?))
+            .collect::<PyResult<_>>()?,
+    };
+    if list.len() > 2 {
+        return Err(PyValueError::new_err("a rule takes at most two formulas"));
+    }
+    // openpyxl accepts "=A1" as well as "A1"; the XML stores no '='.
+    Ok(list
+        .into_iter()
+        .map(|f| f.strip_prefix('=').map(str::to_string).unwrap_or(f))
+        .collect())
+}
+
+/// openpyxl's operator names plus the comparison symbols it also accepts.
+fn operator(name: &str) -> PyResult<ConditionalOperator> {
+    let xml = match name {
+        ">" => "greaterThan",
+        ">=" => "greaterThanOrEqual",
+        "<" => "lessThan",
+        "<=" => "lessThanOrEqual",
+        "=" | "==" => "equal",
+        "!=" => "notEqual",
+        other => other,
+    };
+    ConditionalOperator::from_xml(xml)
+        .ok_or_else(|| PyValueError::new_err(format!("unknown operator {name:?}")))
+}
+
+/// The differential format applied by cellIs/expression rules.
+fn differential_format(
+    font: Option<&PyFont>,
+    border: Option<&PyBorder>,
+    fill: Option<&PyPatternFill>,
+) -> Option<ConditionalFormat> {
+    let mut format = ConditionalFormat::new();
+    if let Some(font) = font {
+        format.font_color = font.color.clone().map(conditional_color);
+        format.bold = font.bold.then_some(true);
+        format.italic = font.italic.then_some(true);
+        format.underline = font.underline.as_ref().map(|u| u != "none");
+        format.strikethrough = font.strike.then_some(true);
+    }
+    if let Some(fill) = fill {
+        // A dxf solid fill is usually given as the end (background) color.
+        format.fill_color = fill
+            .fgColor
+            .clone()
+            .or_else(|| fill.bgColor.clone())
+            .map(conditional_color);
+    }
+    if let Some(border) = border {
+        format.border_color = [&border.left, &border.right, &border.top, &border.bottom]
+            .into_iter()
+            .flatten()
+            .find_map(|side| side.color.clone())
+            .map(conditional_color);
+    }
+    (format != ConditionalFormat::default()).then_some(format)
+}
+
+/// A conditional formatting rule. Build one with CellIsRule, FormulaRule,
+/// ColorScaleRule, DataBarRule or IconSetRule.
+#[pyclass(name = "Rule", subclass, module = "rustypyxl.formatting")]
+#[derive(Clone)]
+pub struct PyRule {
+    pub(crate) inner: ConditionalRule,
+}
+
+#[pymethods]
+impl PyRule {
+    /// The rule type, e.g. "cellIs" or "colorScale".
+    #[getter(r#type)]
+    fn rule_type(&self) -> &'static str {
+        self.inner.rule_type.xml_value()
+    }
+
+    #[getter]
+    fn operator(&self) -> Option<&'static str> {
+        self.inner.operator.as_ref().map(|op| op.xml_value())
+    }
+
+    #[getter]
+    fn formula(&self) -> Vec<String> {
+        [&self.inner.formula1, &self.inner.formula2]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    #[getter]
+    fn priority(&self) -> u32 {
+        self.inner.priority
+    }
+
+    #[getter]
+    fn stopIfTrue(&self) -> bool {
+        self.inner.stop_if_true
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Rule type={:?}>", self.rule_type())
+    }
+}
+
+/// Highlight cells whose value compares against one or two formulas:
+/// `CellIsRule(operator="greaterThan", formula=["5"], fill=PatternFill(...))`.
+#[pyclass(name = "CellIsRule", extends = PyRule, module = "rustypyxl.formatting")]
+pub struct PyCellIsRule;
+
+#[pymethods]
+impl PyCellIsRule {
+    #[new]
+    #[pyo3(signature = (operator=None, formula=None, stopIfTrue=None, font=None, border=None, fill=None))]
+    fn new(
+        operator: Option<&str>,
+        formula: Option<&Bound<'_, PyAny>>,
+        stopIfTrue: Option<bool>,
+        font: Option<PyRef<'_, PyFont>>,
+        border: Option<PyRef<'_, PyBorder>>,
+        fill: Option<PyRef<'_, PyPatternFill>>,
+    ) -> PyResult<(Self, PyRule)> {
+        let op = self::operator(operator.unwrap_or("equal"))?;
+        let mut formulas = formulas(formula)?.into_iter();
+        let mut rule = ConditionalRule::cell_is(op.clone(), &formulas.next().unwrap_or_default());
+        rule.formula2 = formulas.next();
+        if matches!(
+            op,
+            ConditionalOperator::Between | ConditionalOperator::NotBetween
+        ) && rule.formula2.is_none()
+        {
+            return Err(PyValueError::new_err(
+                "between and notBetween need two formulas",
+            ));
+        }
+        rule.stop_if_true = stopIfTrue.unwrap_or(false);
+        if let Some(format) =
+            differential_format(font.as_deref(), border.as_deref(), fill.as_deref())
+        {
+            rule = rule.with_format(format);
+        }
+        Ok((PyCellIsRule, PyRule { inner: rule }))
+    }
+}
+
+/// Highlight cells where a formula is true, written relative to the
+/// range's top-left cell: `FormulaRule(formula=["$A1>0"], font=Font(bold=True))`.
+#[pyclass(name = "FormulaRule", extends = PyRule, module = "rustypyxl.formatting")]
+pub struct PyFormulaRule;
+
+#[pymethods]
+impl PyFormulaRule {
+    #[new]
+    #[pyo3(signature = (formula=None, stopIfTrue=None, font=None, border=None, fill=None))]
+    fn new(
+        formula: Option<&Bound<'_, PyAny>>,
+        stopIfTrue: Option<bool>,
+        font: Option<PyRef<'_, PyFont>>,
+        border: Option<PyRef<'_, PyBorder>>,
+        fill: Option<PyRef<'_, PyPatternFill>>,
+    ) -> PyResult<(Self, PyRule)> {
+        let formula = formulas(formula)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| PyValueError::new_err("FormulaRule needs a formula"))?;
+        let mut rule = ConditionalRule::formula(&formula);
+        rule.stop_if_true = stopIfTrue.unwrap_or(false);
+        if let Some(format) =
+            differential_format(font.as_deref(), border.as_deref(), fill.as_deref())
+        {
+            rule = rule.with_format(format);
+        }
+        Ok((PyFormulaRule, PyRule { inner: rule }))
+    }
+}
+
+/// Shade cells along a two- or three-color gradient. Types are "min",
+/// "max", "num", "percent", "percentile" or "formula"; give the mid_* group
+/// for a three-color scale.
+#[pyclass(name = "ColorScaleRule", extends = PyRule, module = "rustypyxl.formatting")]
+pub struct PyColorScaleRule;
+
+#[pymethods]
+impl PyColorScaleRule {
+    #[new]
+    #[pyo3(signature = (start_type=None, start_value=None, start_color=None, mid_type=None, mid_value=None, mid_color=None, end_type=None, end_value=None, end_color=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        start_type: Option<String>,
+        start_value: Option<&Bound<'_, PyAny>>,
+        start_color: Option<&Bound<'_, PyAny>>,
+        mid_type: Option<String>,
+        mid_value: Option<&Bound<'_, PyAny>>,
+        mid_color: Option<&Bound<'_, PyAny>>,
+        end_type: Option<String>,
+        end_value: Option<&Bound<'_, PyAny>>,
+        end_color: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<(Self, PyRule)> {
+        let value = |v: Option<&Bound<'_, PyAny>>| -> PyResult<Option<String>> {
+            v.filter(|v| !v.is_none()).map(threshold_value).transpose()
+        };
+        let min_color =
+            color_arg(start_color)?.unwrap_or_else(|| ConditionalColor::rgb("FFF8696B"));
+        let max_color = color_arg(end_color)?.unwrap_or_else(|| ConditionalColor::rgb("FF63BE7B"));
+        let mut scale = match (mid_type, color_arg(mid_color)?) {
+            (None, None) => ColorScale::two_color(min_color, max_color),
+            (mid_type, mid_color) => {
+                let mut scale = ColorScale::three_color(
+                    min_color,
+                    mid_color.unwrap_or_else(|| ConditionalColor::rgb("FFFFEB84")),
+                    max_color,
+                );
+                if let Some(t) = mid_type {
+                    scale.mid_type = Some(t);
+                    scale.mid_value = value(mid_value)?;
+                }
+                scale
+            }
+        };
+        if let Some(t) = start_type {
+            scale.min_type = t;
+        }
+        scale.min_value = value(start_value)?;
+        if let Some(t) = end_type {
+            scale.max_type = t;
+        }
+        scale.max_value = value(end_value)?;
+        Ok((
+            PyColorScaleRule,
+            PyRule {
+                inner: ConditionalRule::with_color_scale(scale),
+            },
+        ))
+    }
+}
+
+/// Draw an in-cell bar proportional to the value.
+#[pyclass(name = "DataBarRule", extends = PyRule, module = "rustypyxl.formatting")]
+pub struct PyDataBarRule;
+
+#[pymethods]
+impl PyDataBarRule {
+    #[new]
+    #[pyo3(signature = (start_type=None, start_value=None, end_type=None, end_value=None, color=None, showValue=None))]
+    fn new(
+        start_type: Option<String>,
+        start_value: Option<&Bound<'_, PyAny>>,
+        end_type: Option<String>,
+        end_value: Option<&Bound<'_, PyAny>>,
+        color: Option<&Bound<'_, PyAny>>,
+        showValue: Option<bool>,
+    ) -> PyResult<(Self, PyRule)> {
+        let mut bar = DataBar::new();
+        if let Some(color) = color_arg(color)? {
+            bar = bar.with_color(color);
+        }
+        if let Some(t) = start_type {
+            bar.min_type = t;
+        }
+        bar.min_value = start_value
+            .filter(|v| !v.is_none())
+            .map(threshold_value)
+            .transpose()?;
+        if let Some(t) = end_type {
+            bar.max_type = t;
+        }
+        bar.max_value = end_value
+            .filter(|v| !v.is_none())
+            .map(threshold_value)
+            .transpose()?;
+        if showValue == Some(false) {
+            bar = bar.hide_value();
+        }
+        Ok((
+            PyDataBarRule,
+            PyRule {
+                inner: ConditionalRule::with_data_bar(bar),
+            },
+        ))
+    }
+}
+
+/// Show an icon per cell: `IconSetRule("3Arrows", "percent", [0, 33, 67])`.
+/// `values` holds one threshold per icon, all of kind `type`.
+#[pyclass(name = "IconSetRule", extends = PyRule, module = "rustypyxl.formatting")]
+pub struct PyIconSetRule;
+
+#[pymethods]
+impl PyIconSetRule {
+    #[new]
+    #[pyo3(signature = (icon_style=None, r#type=None, values=None, showValue=None, percent=None, reverse=None))]
+    fn new(
+        icon_style: Option<&str>,
+        r#type: Option<String>,
+        values: Option<Vec<Bound<'_, PyAny>>>,
+        showValue: Option<bool>,
+        percent: Option<bool>,
+        reverse: Option<bool>,
+    ) -> PyResult<(Self, PyRule)> {
+        let style_name = icon_style.unwrap_or("3TrafficLights1");
+        let style = IconSetStyle::from_xml(style_name)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown icon style {style_name:?}")))?;
+        let mut icons = IconSet::new(style);
+        let kind = r#type.unwrap_or_else(|| {
+            if percent == Some(false) {
+                "num"
+            } else {
+                "percent"
+            }
+            .to_string()
+        });
+        icons.thresholds = values
+            .unwrap_or_default()
+            .iter()
+            .map(|v| Ok((kind.clone(), threshold_value(v)?)))
+            .collect::<PyResult<_>>()?;
+        if showValue == Some(false) {
+            icons = icons.icon_only();
+        }
+        if reverse == Some(true) {
+            icons = icons.reversed();
+        }
+        Ok((
+            PyIconSetRule,
+            PyRule {
+                inner: ConditionalRule::with_icon_set(icons),
+            },
+        ))
+    }
+}
+
+/// The sheet's conditional formatting, as `ws.conditional_formatting`.
+#[pyclass(name = "ConditionalFormattingList", module = "rustypyxl.formatting")]
+pub struct PyConditionalFormattingList {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+impl PyConditionalFormattingList {
+    fn sheet_index(&self, wb: &PyWorkbook) -> PyResult<usize> {
+        wb.inner
+            .sheet_index_by_uid(self.uid)
+            .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))
+    }
+}
+
+#[pymethods]
+impl PyConditionalFormattingList {
+    /// Apply `cfRule` to `range_string` (e.g. "A1:A10" or "A1:A5 C1:C5").
+    /// Rules are prioritized in the order they are added.
+    fn add(&self, range_string: &str, cfRule: PyRef<'_, PyRule>, py: Python<'_>) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = self.sheet_index(&this)?;
+        let ws = &mut this.inner.worksheets[idx];
+        let priority = ws
+            .conditional_formatting
+            .iter()
+            .flat_map(|cf| &cf.rules)
+            .map(|r| r.priority)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let rule = cfRule.inner.clone().with_priority(priority);
+        // Rules on the same range share one conditionalFormatting element.
+        match ws
+            .conditional_formatting
+            .iter_mut()
+            .find(|cf| cf.range == range_string)
+        {
+            Some(cf) => cf.add_rule(rule),
+            None => {
+                let mut cf = ConditionalFormatting::new(range_string);
+                cf.add_rule(rule);
+                ws.add_conditional_formatting(cf);
+            }
+        }
+        Ok(())
+    }
+
+    /// The ranges that carry conditional formatting.
+    #[getter]
+    fn ranges(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        let this = self.workbook.borrow(py);
+        let idx = self.sheet_index(&this)?;
+        Ok(this.inner.worksheets[idx]
+            .conditional_formatting
+            .iter()
+            .map(|cf| cf.range.clone())
+            .collect())
+    }
+
+    /// The rules applied to `range_string`, in priority order.
+    fn rules(&self, range_string: &str, py: Python<'_>) -> PyResult<Vec<PyRule>> {
+        let this = self.workbook.borrow(py);
+        let idx = self.sheet_index(&this)?;
+        let mut rules: Vec<PyRule> = this.inner.worksheets[idx]
+            .conditional_formatting
+            .iter()
+            .filter(|cf| cf.range == range_string)
+            .flat_map(|cf| &cf.rules)
+            .map(|r| PyRule { inner: r.clone() })
+            .collect();
+        rules.sort_by_key(|r| r.inner.priority);
+        Ok(rules)
+    }
+
+    /// Number of formatted ranges.
+    fn __len__(&self, py: Python<'_>) -> PyResult<usize> {
+        let this = self.workbook.borrow(py);
+        let idx = self.sheet_index(&this)?;
+        Ok(this.inner.worksheets[idx].conditional_formatting.len())
+    }
+}
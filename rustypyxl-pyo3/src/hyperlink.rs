@@ -0,0 +1,126 @@
+//! `Hyperlink`: a cell link with its tooltip and display text.
+
+use pyo3::prelude::*;
+
+use rustypyxl_core::Hyperlink;
+
+/// A cell hyperlink (openpyxl-compatible).
+///
+/// Args:
+///     target: A URL, "mailto:" address or file path; None for a link
+///         within the workbook
+///     location: A place in the workbook, e.g. "Sheet2!A1"
+///     tooltip: Text shown when hovering over the cell
+///     display: The link's display text
+///
+/// Assign it with `cell.hyperlink = Hyperlink("https://example.com",
+/// tooltip="Open")`; a plain string ("#Sheet2!A1" for a location) works too.
+#[pyclass(name = "Hyperlink")]
+#[derive(Clone, Debug)]
+pub struct PyHyperlink {
+    pub inner: Hyperlink,
+}
+
+impl PyHyperlink {
+    pub fn from_core(link: &Hyperlink) -> Self {
+        PyHyperlink {
+            inner: link.clone(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyHyperlink {
+    #[new]
+    #[pyo3(signature = (target=None, location=None, tooltip=None, display=None))]
+    fn new(
+        target: Option<String>,
+        location: Option<String>,
+        tooltip: Option<String>,
+        display: Option<String>,
+    ) -> PyResult<Self> {
+        let mut inner = match (target, location) {
+            (Some(target), None) => Hyperlink::external(target),
+            (None, Some(location)) => Hyperlink::internal(location),
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "a hyperlink needs exactly one of target and location",
+                ))
+            }
+        };
+        inner.tooltip = tooltip;
+        inner.display = display;
+        Ok(PyHyperlink { inner })
+    }
+
+    /// The URL of an external link; None for a link within the workbook.
+    #[getter]
+    fn target(&self) -> Option<&str> {
+        self.inner.is_external.then_some(self.inner.target.as_str())
+    }
+
+    /// The place in the workbook ("Sheet2!A1") of an internal link.
+    #[getter]
+    fn location(&self) -> Option<&str> {
+        (!self.inner.is_external).then_some(self.inner.target.as_str())
+    }
+
+    #[getter]
+    fn is_external(&self) -> bool {
+        self.inner.is_external
+    }
+
+    #[getter]
+    fn tooltip(&self) -> Option<&str> {
+        self.inner.tooltip.as_deref()
+    }
+
+    #[setter]
+    fn set_tooltip(&mut self, tooltip: Option<String>) {
+        self.inner.tooltip = tooltip;
+    }
+
+    #[getter]
+    fn display(&self) -> Option<&str> {
+        self.inner.display.as_deref()
+    }
+
+    #[setter]
+    fn set_display(&mut self, display: Option<String>) {
+        self.inner.display = display;
+    }
+
+    /// The link as one string, as `cell.hyperlink` returns it: the URL, or
+    /// "#" and the location.
+    #[getter]
+    fn address(&self) -> String {
+        self.inner.address()
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        other
+            .downcast::<Self>()
+            .is_ok_and(|other| other.borrow().inner == self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Hyperlink({:?}, tooltip={:?})",
+            self.inner.address(),
+            self.inner.tooltip
+        )
+    }
+}
+
+/// A hyperlink from a `cell.hyperlink` assignment: an address or a Hyperlink.
+pub fn extract_hyperlink(value: &Bound<'_, PyAny>) -> PyResult<Hyperlink> {
+    if let Ok(address) = value.extract::<String>() {
+        return Ok(Hyperlink::new(address));
+    }
+    match value.downcast::<PyHyperlink>() {
+        Ok(link) => Ok(link.borrow().inner.clone()),
+        Err(_) => Err(pyo3::exceptions::PyTypeError::new_err(
+            "hyperlink must be a string or a Hyperlink",
+        )),
+    }
+}
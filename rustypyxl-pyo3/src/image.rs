@@ -0,0 +1,119 @@
+//! `Image` (openpyxl-compatible): wrap a picture, size it, and place it with
+//! `ws.add_image(img, "B2")`. Images on a loaded sheet come back from
+//! `ws.images`.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use rustypyxl_core::image::{Image, ImageAnchor};
+
+/// EMUs per pixel at 96 DPI.
+const EMU_PER_PX: u32 = 9525;
+
+/// A picture to embed. Built from a file path, bytes, or a binary file
+/// object; shown at its own pixel size until `width`/`height` are set.
+#[pyclass(name = "Image")]
+#[derive(Clone, Debug)]
+pub struct PyImage {
+    pub inner: Image,
+}
+
+impl PyImage {
+    /// Read `source` (a path, bytes, or an object with `.read()`) into an
+    /// image anchored at `anchor`.
+    pub fn from_source(source: &Bound<'_, PyAny>, anchor: ImageAnchor) -> PyResult<Image> {
+        if let Ok(data) = source.extract::<Vec<u8>>() {
+            return Image::from_bytes(data, anchor)
+                .ok_or_else(|| PyValueError::new_err("unrecognized image format"));
+        }
+        if let Ok(path) = source.extract::<std::path::PathBuf>() {
+            return Image::from_file(&path, anchor)
+                .map_err(|e| PyValueError::new_err(format!("could not read image: {e}")));
+        }
+        if source.hasattr("read")? {
+            let data = source.call_method0("read")?.extract::<Vec<u8>>()?;
+            return Image::from_bytes(data, anchor)
+                .ok_or_else(|| PyValueError::new_err("unrecognized image format"));
+        }
+        Err(PyTypeError::new_err(
+            "image must be a file path, bytes, or a binary file object",
+        ))
+    }
+}
+
+#[pymethods]
+impl PyImage {
+    #[new]
+    fn new(img: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyImage {
+            inner: Self::from_source(img, ImageAnchor::one_cell("A1"))?,
+        })
+    }
+
+    /// Displayed width in pixels.
+    #[getter]
+    fn width(&self) -> u32 {
+        self.inner.width / EMU_PER_PX
+    }
+
+    #[setter]
+    fn set_width(&mut self, value: u32) {
+        self.inner.width = value * EMU_PER_PX;
+    }
+
+    /// Displayed height in pixels.
+    #[getter]
+    fn height(&self) -> u32 {
+        self.inner.height / EMU_PER_PX
+    }
+
+    #[setter]
+    fn set_height(&mut self, value: u32) {
+        self.inner.height = value * EMU_PER_PX;
+    }
+
+    /// The cell the image's top-left corner sits in (e.g. "B2").
+    #[getter]
+    fn anchor(&self) -> String {
+        self.inner.anchor.from_cell.clone()
+    }
+
+    #[setter]
+    fn set_anchor(&mut self, value: String) {
+        self.inner.anchor = ImageAnchor::one_cell(value);
+    }
+
+    /// Image format: "png", "jpeg", "gif", "bmp", "tiff", "emf" or "wmf".
+    #[getter]
+    fn format(&self) -> &'static str {
+        self.inner.format.extension()
+    }
+
+    /// The image's name in the drawing, if any.
+    #[getter]
+    fn name(&self) -> Option<String> {
+        self.inner.name.clone()
+    }
+
+    #[setter]
+    fn set_name(&mut self, value: Option<String>) {
+        self.inner.name = value;
+    }
+
+    /// The encoded image bytes.
+    #[getter]
+    fn data<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.data)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<Image {} {}x{} at {}>",
+            self.format(),
+            self.width(),
+            self.height(),
+            self.anchor()
+        )
+    }
+}
@@ -5,20 +5,40 @@
 
 use pyo3::prelude::*;
 
+#[cfg(feature = "parquet")]
+mod arrow_stream;
+mod blank;
 mod cell;
+mod cell_range;
+mod chart;
+mod comment;
 mod dimensions;
+mod docprops;
+mod edit;
+mod formatting;
+mod hyperlink;
+mod image;
+mod page_setup;
+mod protection;
+mod read_only;
+mod report;
+mod rich_text;
+mod sheet_view;
 mod streaming;
 mod style;
+mod table;
+mod validation;
 mod workbook;
 mod worksheet;
 
 use cell::PyCell;
+use chart::{PyBarChart, PyChartBase, PyLineChart, PyPieChart};
 use streaming::PyStreamingWorkbook;
 use style::{
-    PyAlignment, PyBorder, PyColor, PyFont, PyGradientFill, PyGradientStop, PyPatternFill,
-    PyProtection, PySide,
+    PyAlignment, PyBorder, PyColor, PyFont, PyGradientFill, PyGradientStop, PyImportStyle,
+    PyNamedStyle, PyPatternFill, PyProtection, PySide,
 };
-use workbook::{PyPivotTable, PyWorkbook};
+use workbook::{PyPivotTable, PyStyleSheet, PyWorkbook};
 use worksheet::{PyCellRangeIterator, PyWorksheet};
 
 /// Load a workbook from a file path, bytes, or file-like object.
@@ -26,18 +46,155 @@ use worksheet::{PyCellRangeIterator, PyWorksheet};
 /// Args:
 ///     source: File path (str), bytes, or file-like object with .read() method
 ///     password: Password for a protected (encrypted) workbook, if any
+///     read_only: Stream rows lazily instead of loading every cell, for
+///         sheets too large to hold in memory
+///     max_string_len: With read_only, keep at most this many characters of
+///         each shared string; longer ones are cut and end with an ellipsis
+///     rich_text: Return rich-text cell values as CellRichText (runs of
+///         differently formatted text) rather than plain strings
+///     edit: Open for targeted cell edits that are patched into the
+///         original file on save, keeping everything else byte for byte
+///     keep_vba: Keep the VBA project of an .xlsm file so that saving
+///         writes the macros back; as in openpyxl they are dropped otherwise.
+///         A workbook with macros saves only to .xlsm (or .xltm)
 ///
 /// Returns:
-///     Workbook: The loaded workbook
+///     Workbook: The loaded workbook, a ReadOnlyWorkbook when read_only, or
+///     an EditableWorkbook when edit
 ///
 /// Example:
 ///     wb = load_workbook('file.xlsx')
 ///     wb = load_workbook(file_bytes)
 ///     wb = load_workbook('protected.xlsx', password='secret')
+///     for row in load_workbook('big.xlsx', read_only=True)['Data'].values:
+///         ...
+///     wb = load_workbook('macros.xlsm', keep_vba=True)
+///     wb = load_workbook('dashboard.xlsx', edit=True)
+///     wb['Inputs']['B2'] = 0.07
+///     wb.save('dashboard.xlsx')
 #[pyfunction]
-#[pyo3(signature = (source, password=None))]
-fn load_workbook(source: &Bound<'_, PyAny>, password: Option<&str>) -> PyResult<PyWorkbook> {
-    PyWorkbook::load(source, password)
+#[pyo3(signature = (source, password=None, read_only=false, max_string_len=None, rich_text=false, edit=false, keep_vba=false))]
+fn load_workbook(
+    source: &Bound<'_, PyAny>,
+    password: Option<&str>,
+    read_only: bool,
+    max_string_len: Option<usize>,
+    rich_text: bool,
+    edit: bool,
+    keep_vba: bool,
+) -> PyResult<PyObject> {
+    let py = source.py();
+    if edit {
+        if read_only || rich_text || max_string_len.is_some() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "edit=True cannot be combined with read_only, max_string_len or rich_text",
+            ));
+        }
+        let wb = edit::PyEditableWorkbook::load(source, password)?;
+        return Ok(Py::new(py, wb)?.into_any());
+    }
+    if read_only && rich_text {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "rich_text is not supported with read_only=True",
+        ));
+    }
+    if read_only {
+        let wb = read_only::PyReadOnlyWorkbook::load(source, password, max_string_len)?;
+        return Ok(Py::new(py, wb)?.into_any());
+    }
+    if max_string_len.is_some() {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "max_string_len applies only with read_only=True",
+        ));
+    }
+    Ok(Py::new(py, PyWorkbook::load(source, password, rich_text, keep_vba)?)?.into_any())
+}
+
+/// Identify a spreadsheet file by its contents rather than its extension.
+///
+/// Args:
+///     source: File path (str or os.PathLike), bytes, or file-like object
+///
+/// Returns:
+///     dict: "format" is one of "xlsx", "xlsm", "xlsb", "xls", "ods", "csv",
+///     "encrypted", "zip" or "unknown"; "has_macros" and "template" describe
+///     OOXML packages; "delimiter" is the guessed CSV delimiter (or None);
+///     "loadable" says whether load_workbook opens it without a password.
+///
+/// Example:
+///     info = detect_format('upload.bin')
+///     if info['format'] == 'encrypted':
+///         wb = load_workbook('upload.bin', password=secret)
+#[pyfunction]
+fn detect_format<'py>(source: &Bound<'py, PyAny>) -> PyResult<Bound<'py, pyo3::types::PyDict>> {
+    let py = source.py();
+    let info = match source.extract::<std::path::PathBuf>() {
+        // Paths go through the file-based check, which reads only a ZIP's
+        // directory rather than the whole workbook.
+        Ok(path) if source.extract::<Vec<u8>>().is_err() => py
+            .allow_threads(|| rustypyxl_core::detect_format_file(&path.to_string_lossy()))
+            .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?,
+        _ => {
+            let bytes = workbook::read_source_bytes(source)?;
+            rustypyxl_core::detect_format(&bytes)
+        }
+    };
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("format", info.format.as_str())?;
+    dict.set_item("has_macros", info.has_macros)?;
+    dict.set_item("template", info.template)?;
+    dict.set_item("delimiter", info.delimiter.map(String::from))?;
+    dict.set_item("loadable", info.is_loadable())?;
+    Ok(dict)
+}
+
+/// Recover what can be read from a damaged workbook, such as a truncated
+/// upload that load_workbook rejects.
+///
+/// Args:
+///     source: File path (str or os.PathLike), bytes, or file-like object
+///
+/// Returns:
+///     tuple: The partial Workbook and a dict describing the damage:
+///     "central_directory_damaged" and "truncated" (bools), "recovered_parts"
+///     (list of part names), "damaged_parts" (list of (part, reason) tuples),
+///     "recovered_sheets" and "lost_sheets" (lists of sheet names),
+///     "workbook_part_rebuilt", "defined_names_dropped" and "intact" (bools).
+///
+/// Raises:
+///     ValueError: If no worksheet could be recovered.
+///
+/// Example:
+///     wb, report = salvage_workbook('upload.xlsx')
+///     if report['lost_sheets']:
+///         print('missing:', report['lost_sheets'])
+#[pyfunction]
+fn salvage_workbook<'py>(
+    source: &Bound<'py, PyAny>,
+) -> PyResult<(PyWorkbook, Bound<'py, pyo3::types::PyDict>)> {
+    let py = source.py();
+    let bytes = workbook::read_source_bytes(source)?;
+    let (wb, report) = py
+        .allow_threads(|| rustypyxl_core::Workbook::salvage_from_bytes(&bytes))
+        .map_err(|e| pyo3::exceptions::PyValueError::new_err(e.to_string()))?;
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item(
+        "central_directory_damaged",
+        report.central_directory_damaged,
+    )?;
+    dict.set_item("truncated", report.truncated)?;
+    dict.set_item("recovered_parts", &report.recovered_parts)?;
+    dict.set_item("damaged_parts", &report.damaged_parts)?;
+    dict.set_item("recovered_sheets", &report.recovered_sheets)?;
+    dict.set_item("lost_sheets", &report.lost_sheets)?;
+    dict.set_item("workbook_part_rebuilt", report.workbook_part_rebuilt)?;
+    dict.set_item("defined_names_dropped", report.defined_names_dropped)?;
+    dict.set_item("intact", report.is_intact())?;
+    let wb = PyWorkbook {
+        inner: wb,
+        rich_text: false,
+    };
+    Ok((wb, dict))
 }
 
 /// Render a value the way Excel would display it under a number-format code.
@@ -119,15 +276,50 @@ fn datetime_to_serial(value: &Bound<'_, PyAny>) -> PyResult<f64> {
 fn rustypyxl(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Core classes
     m.add_class::<PyWorkbook>()?;
+    m.add_class::<blank::PyBlankPolicy>()?;
+    m.add_class::<docprops::PyDocumentProperties>()?;
     m.add_class::<PyPivotTable>()?;
+    m.add_class::<PyStyleSheet>()?;
     m.add_class::<PyWorksheet>()?;
     m.add_class::<dimensions::PyColumnDimensions>()?;
     m.add_class::<dimensions::PyColumnDimension>()?;
     m.add_class::<dimensions::PyRowDimensions>()?;
     m.add_class::<dimensions::PyRowDimension>()?;
     m.add_class::<dimensions::PyAutoFilter>()?;
+    m.add_class::<protection::PySheetProtection>()?;
+    m.add_class::<sheet_view::PySheetView>()?;
+    m.add_class::<sheet_view::PySheetProperties>()?;
+    m.add_class::<sheet_view::PyOutlineProperties>()?;
+    m.add_class::<page_setup::PyPrintPageSetup>()?;
+    m.add_class::<page_setup::PyPrintOptions>()?;
+    m.add_class::<page_setup::PyPageMargins>()?;
     m.add_class::<PyCell>()?;
+    m.add_class::<comment::PyComment>()?;
+    m.add_class::<hyperlink::PyHyperlink>()?;
     m.add_class::<PyCellRangeIterator>()?;
+    m.add_class::<cell_range::PyCellRange>()?;
+    m.add_class::<cell_range::PyMultiCellRange>()?;
+    m.add_class::<PyChartBase>()?;
+    m.add_class::<PyBarChart>()?;
+    m.add_class::<PyLineChart>()?;
+    m.add_class::<PyPieChart>()?;
+    m.add_class::<image::PyImage>()?;
+    m.add_class::<report::PyReport>()?;
+    m.add_class::<table::PyTable>()?;
+    m.add_class::<validation::PyDataValidation>()?;
+    m.add_class::<formatting::PyRule>()?;
+    m.add_class::<formatting::PyCellIsRule>()?;
+    m.add_class::<formatting::PyFormulaRule>()?;
+    m.add_class::<formatting::PyColorScaleRule>()?;
+    m.add_class::<formatting::PyDataBarRule>()?;
+    m.add_class::<formatting::PyIconSetRule>()?;
+    m.add_class::<formatting::PyConditionalFormattingList>()?;
+    m.add_class::<edit::PyEditableWorkbook>()?;
+    m.add_class::<edit::PyEditableWorksheet>()?;
+    m.add_class::<read_only::PyReadOnlyWorkbook>()?;
+    m.add_class::<read_only::PyReadOnlyWorksheet>()?;
+    m.add_class::<read_only::PyReadOnlyCell>()?;
+    m.add_class::<read_only::PyReadOnlyRowIterator>()?;
 
     // Streaming (write-only) classes
     m.add_class::<PyStreamingWorkbook>()?;
@@ -138,13 +330,17 @@ fn rustypyxl(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyPatternFill>()?;
     m.add_class::<PyBorder>()?;
     m.add_class::<PySide>()?;
+    m.add_class::<PyNamedStyle>()?;
     m.add_class::<PyProtection>()?;
     m.add_class::<PyColor>()?;
     m.add_class::<PyGradientFill>()?;
     m.add_class::<PyGradientStop>()?;
+    m.add_class::<PyImportStyle>()?;
 
     // Functions
     m.add_function(wrap_pyfunction!(load_workbook, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_format, m)?)?;
+    m.add_function(wrap_pyfunction!(salvage_workbook, m)?)?;
     m.add_function(wrap_pyfunction!(format_value, m)?)?;
     m.add_function(wrap_pyfunction!(encrypt_bytes, m)?)?;
     m.add_function(wrap_pyfunction!(decrypt_bytes, m)?)?;
@@ -156,17 +352,76 @@ fn rustypyxl(m: &Bound<'_, PyModule>) -> PyResult<()> {
     styles.add_class::<PyPatternFill>()?;
     styles.add_class::<PyBorder>()?;
     styles.add_class::<PySide>()?;
+    styles.add_class::<PyNamedStyle>()?;
     styles.add_class::<PyProtection>()?;
     styles.add_class::<PyColor>()?;
     styles.add_class::<PyGradientFill>()?;
     styles.add_class::<PyGradientStop>()?;
     m.add_submodule(&styles)?;
+
+    // Conditional formatting rules, importable like openpyxl's
+    // `openpyxl.formatting.rule`.
+    let formatting = PyModule::new(m.py(), "formatting")?;
+    formatting.add_class::<formatting::PyRule>()?;
+    formatting.add_class::<formatting::PyCellIsRule>()?;
+    formatting.add_class::<formatting::PyFormulaRule>()?;
+    formatting.add_class::<formatting::PyColorScaleRule>()?;
+    formatting.add_class::<formatting::PyDataBarRule>()?;
+    formatting.add_class::<formatting::PyIconSetRule>()?;
+    formatting.add_class::<formatting::PyConditionalFormattingList>()?;
+    formatting.add("rule", &formatting)?;
+    m.add_submodule(&formatting)?;
+
+    // Rich text, importable from `rustypyxl.cell.rich_text` and
+    // `rustypyxl.cell.text` like openpyxl's.
+    m.add_class::<rich_text::PyCellRichText>()?;
+    m.add_class::<rich_text::PyTextBlock>()?;
+    m.add_class::<rich_text::PyInlineFont>()?;
+    let cell = PyModule::new(m.py(), "cell")?;
+    let cell_rich_text = PyModule::new(m.py(), "rich_text")?;
+    cell_rich_text.add_class::<rich_text::PyCellRichText>()?;
+    cell_rich_text.add_class::<rich_text::PyTextBlock>()?;
+    let cell_text = PyModule::new(m.py(), "text")?;
+    cell_text.add_class::<rich_text::PyInlineFont>()?;
+    cell.add_submodule(&cell_rich_text)?;
+    cell.add_submodule(&cell_text)?;
+    m.add_submodule(&cell)?;
+
+    // `from rustypyxl.comments import Comment`, as in openpyxl.
+    let comments = PyModule::new(m.py(), "comments")?;
+    comments.add_class::<comment::PyComment>()?;
+    m.add_submodule(&comments)?;
+
+    // `from rustypyxl.worksheet.datavalidation import DataValidation`,
+    // `from rustypyxl.worksheet.cell_range import CellRange` and
+    // `from rustypyxl.worksheet.hyperlink import Hyperlink`, as in openpyxl.
+    let worksheet = PyModule::new(m.py(), "worksheet")?;
+    let datavalidation = PyModule::new(m.py(), "datavalidation")?;
+    datavalidation.add_class::<validation::PyDataValidation>()?;
+    worksheet.add_submodule(&datavalidation)?;
+    let worksheet_cell_range = PyModule::new(m.py(), "cell_range")?;
+    worksheet_cell_range.add_class::<cell_range::PyCellRange>()?;
+    worksheet_cell_range.add_class::<cell_range::PyMultiCellRange>()?;
+    worksheet.add_submodule(&worksheet_cell_range)?;
+    let worksheet_hyperlink = PyModule::new(m.py(), "hyperlink")?;
+    worksheet_hyperlink.add_class::<hyperlink::PyHyperlink>()?;
+    worksheet.add_submodule(&worksheet_hyperlink)?;
+    m.add_submodule(&worksheet)?;
+
     // add_submodule alone doesn't register the module with the import system,
     // so `from rustypyxl.styles import Font` would fail without this.
-    m.py()
-        .import("sys")?
-        .getattr("modules")?
-        .set_item("rustypyxl.styles", &styles)?;
+    let modules = m.py().import("sys")?.getattr("modules")?;
+    modules.set_item("rustypyxl.styles", &styles)?;
+    modules.set_item("rustypyxl.formatting", &formatting)?;
+    modules.set_item("rustypyxl.formatting.rule", &formatting)?;
+    modules.set_item("rustypyxl.cell", &cell)?;
+    modules.set_item("rustypyxl.cell.rich_text", &cell_rich_text)?;
+    modules.set_item("rustypyxl.cell.text", &cell_text)?;
+    modules.set_item("rustypyxl.comments", &comments)?;
+    modules.set_item("rustypyxl.worksheet", &worksheet)?;
+    modules.set_item("rustypyxl.worksheet.datavalidation", &datavalidation)?;
+    modules.set_item("rustypyxl.worksheet.cell_range", &worksheet_cell_range)?;
+    modules.set_item("rustypyxl.worksheet.hyperlink", &worksheet_hyperlink)?;
 
     Ok(())
 }
@@ -0,0 +1,345 @@
+//! `ws.page_setup`, `ws.print_options` and `ws.page_margins` proxies, named
+//! after openpyxl's `PrintPageSetup`, `PrintOptions` and `PageMargins`.
+
+#![allow(non_snake_case)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::Py;
+use rustypyxl_core::pagesetup::{Orientation, PageSetup, PaperSize};
+
+use crate::sheet_view::{read_sheet, write_sheet};
+use crate::workbook::PyWorkbook;
+
+fn read<R>(
+    workbook: &Py<PyWorkbook>,
+    uid: u64,
+    py: Python<'_>,
+    f: impl FnOnce(&PageSetup) -> R,
+) -> PyResult<R> {
+    read_sheet(workbook, uid, py, |ws| match ws.page_setup.as_ref() {
+        Some(ps) => f(ps),
+        None => f(&PageSetup::new()),
+    })
+}
+
+fn update<R>(
+    workbook: &Py<PyWorkbook>,
+    uid: u64,
+    py: Python<'_>,
+    f: impl FnOnce(&mut PageSetup) -> R,
+) -> PyResult<R> {
+    write_sheet(workbook, uid, py, |ws| {
+        f(ws.page_setup.get_or_insert_with(PageSetup::new))
+    })
+}
+
+/// Paper, orientation and scaling: `ws.page_setup.orientation = "landscape"`.
+#[pyclass(name = "PrintPageSetup")]
+pub struct PyPrintPageSetup {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+#[pymethods]
+impl PyPrintPageSetup {
+    /// "portrait" or "landscape".
+    #[getter]
+    fn orientation(&self, py: Python<'_>) -> PyResult<&'static str> {
+        read(&self.workbook, self.uid, py, |ps| match ps.orientation {
+            Orientation::Landscape => "landscape",
+            Orientation::Portrait => "portrait",
+        })
+    }
+
+    #[setter]
+    fn set_orientation(&self, py: Python<'_>, value: Option<&str>) -> PyResult<()> {
+        let orientation = match value.unwrap_or("portrait") {
+            "landscape" => Orientation::Landscape,
+            "portrait" | "default" => Orientation::Portrait,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "orientation must be 'portrait' or 'landscape', got {other:?}"
+                )))
+            }
+        };
+        update(&self.workbook, self.uid, py, |ps| {
+            ps.orientation = orientation
+        })
+    }
+
+    /// Excel's paper size code (9 is A4). Accepts a code, or a name such
+    /// as "A4" or "Letter".
+    #[getter]
+    fn paperSize(&self, py: Python<'_>) -> PyResult<u32> {
+        read(&self.workbook, self.uid, py, |ps| ps.paper_size.code())
+    }
+
+    #[setter]
+    fn set_paperSize(&self, py: Python<'_>, value: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        let size = match value.filter(|v| !v.is_none()) {
+            None => PaperSize::default(),
+            Some(v) => match v.extract::<u32>() {
+                Ok(code) => PaperSize::from_code(code),
+                Err(_) => match v.extract::<String>() {
+                    Ok(text) => match text.parse::<u32>() {
+                        Ok(code) => PaperSize::from_code(code),
+                        Err(_) => crate::worksheet::parse_paper_size(&text)?,
+                    },
+                    Err(_) => {
+                        return Err(PyValueError::new_err(
+                            "paperSize must be a paper size code or name",
+                        ))
+                    }
+                },
+            },
+        };
+        update(&self.workbook, self.uid, py, |ps| ps.paper_size = size)
+    }
+
+    /// Print scaling in percent, 10 to 400.
+    #[getter]
+    fn scale(&self, py: Python<'_>) -> PyResult<u32> {
+        read(&self.workbook, self.uid, py, |ps| ps.scale)
+    }
+
+    #[setter]
+    fn set_scale(&self, py: Python<'_>, value: Option<u32>) -> PyResult<()> {
+        let scale = value.unwrap_or(100);
+        if !(10..=400).contains(&scale) {
+            return Err(PyValueError::new_err(format!(
+                "scale must be between 10 and 400, got {scale}"
+            )));
+        }
+        update(&self.workbook, self.uid, py, |ps| ps.scale = scale)
+    }
+
+    /// Pages to fit the width to (0 for automatic); None when not fitting.
+    #[getter]
+    fn fitToWidth(&self, py: Python<'_>) -> PyResult<Option<u32>> {
+        read(&self.workbook, self.uid, py, |ps| ps.fit_to_width)
+    }
+
+    #[setter]
+    fn set_fitToWidth(&self, py: Python<'_>, value: Option<u32>) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| ps.fit_to_width = value)
+    }
+
+    /// Pages to fit the height to (0 for automatic); None when not fitting.
+    #[getter]
+    fn fitToHeight(&self, py: Python<'_>) -> PyResult<Option<u32>> {
+        read(&self.workbook, self.uid, py, |ps| ps.fit_to_height)
+    }
+
+    #[setter]
+    fn set_fitToHeight(&self, py: Python<'_>, value: Option<u32>) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| ps.fit_to_height = value)
+    }
+
+    /// Whether printing fits the sheet to fitToWidth x fitToHeight pages.
+    /// Turning it on without counts fits to one page.
+    #[getter]
+    fn fitToPage(&self, py: Python<'_>) -> PyResult<bool> {
+        read(&self.workbook, self.uid, py, |ps| ps.fits_to_page())
+    }
+
+    #[setter]
+    fn set_fitToPage(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| {
+            if !value {
+                ps.fit_to_width = None;
+                ps.fit_to_height = None;
+            } else if !ps.fits_to_page() {
+                ps.fit_to_width = Some(1);
+                ps.fit_to_height = Some(1);
+            }
+        })
+    }
+
+    #[getter]
+    fn firstPageNumber(&self, py: Python<'_>) -> PyResult<Option<u32>> {
+        read(&self.workbook, self.uid, py, |ps| ps.first_page_number)
+    }
+
+    #[setter]
+    fn set_firstPageNumber(&self, py: Python<'_>, value: Option<u32>) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| {
+            ps.first_page_number = value
+        })
+    }
+
+    #[getter]
+    fn blackAndWhite(&self, py: Python<'_>) -> PyResult<bool> {
+        read(&self.workbook, self.uid, py, |ps| ps.black_and_white)
+    }
+
+    #[setter]
+    fn set_blackAndWhite(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| {
+            ps.black_and_white = value
+        })
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let orientation = self.orientation(py)?;
+        read(&self.workbook, self.uid, py, |ps| {
+            format!(
+                "<PrintPageSetup orientation={:?} paperSize={} scale={} fitToWidth={:?} fitToHeight={:?}>",
+                orientation,
+                ps.paper_size.code(),
+                ps.scale,
+                ps.fit_to_width,
+                ps.fit_to_height
+            )
+        })
+    }
+}
+
+/// Centering, gridlines and headings: `ws.print_options.horizontalCentered = True`.
+#[pyclass(name = "PrintOptions")]
+pub struct PyPrintOptions {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+#[pymethods]
+impl PyPrintOptions {
+    #[getter]
+    fn horizontalCentered(&self, py: Python<'_>) -> PyResult<bool> {
+        read(&self.workbook, self.uid, py, |ps| ps.center_horizontally)
+    }
+
+    #[setter]
+    fn set_horizontalCentered(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| {
+            ps.center_horizontally = value
+        })
+    }
+
+    #[getter]
+    fn verticalCentered(&self, py: Python<'_>) -> PyResult<bool> {
+        read(&self.workbook, self.uid, py, |ps| ps.center_vertically)
+    }
+
+    #[setter]
+    fn set_verticalCentered(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| {
+            ps.center_vertically = value
+        })
+    }
+
+    /// Print the cell gridlines.
+    #[getter]
+    fn gridLines(&self, py: Python<'_>) -> PyResult<bool> {
+        read(&self.workbook, self.uid, py, |ps| ps.print_gridlines)
+    }
+
+    #[setter]
+    fn set_gridLines(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| {
+            ps.print_gridlines = value
+        })
+    }
+
+    /// Print the row numbers and column letters.
+    #[getter]
+    fn headings(&self, py: Python<'_>) -> PyResult<bool> {
+        read(&self.workbook, self.uid, py, |ps| ps.print_headings)
+    }
+
+    #[setter]
+    fn set_headings(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        update(&self.workbook, self.uid, py, |ps| ps.print_headings = value)
+    }
+}
+
+/// Page margins in inches: `ws.page_margins.left = 0.5`.
+#[pyclass(name = "PageMargins")]
+pub struct PyPageMargins {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+impl PyPageMargins {
+    fn set(&self, py: Python<'_>, value: f64, f: impl FnOnce(&mut PageSetup, f64)) -> PyResult<()> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "margins must be non-negative numbers of inches, got {value}"
+            )));
+        }
+        update(&self.workbook, self.uid, py, |ps| f(ps, value))
+    }
+}
+
+#[pymethods]
+impl PyPageMargins {
+    #[getter]
+    fn left(&self, py: Python<'_>) -> PyResult<f64> {
+        read(&self.workbook, self.uid, py, |ps| ps.margins.left)
+    }
+
+    #[setter]
+    fn set_left(&self, py: Python<'_>, value: f64) -> PyResult<()> {
+        self.set(py, value, |ps, v| ps.margins.left = v)
+    }
+
+    #[getter]
+    fn right(&self, py: Python<'_>) -> PyResult<f64> {
+        read(&self.workbook, self.uid, py, |ps| ps.margins.right)
+    }
+
+    #[setter]
+    fn set_right(&self, py: Python<'_>, value: f64) -> PyResult<()> {
+        self.set(py, value, |ps, v| ps.margins.right = v)
+    }
+
+    #[getter]
+    fn top(&self, py: Python<'_>) -> PyResult<f64> {
+        read(&self.workbook, self.uid, py, |ps| ps.margins.top)
+    }
+
+    #[setter]
+    fn set_top(&self, py: Python<'_>, value: f64) -> PyResult<()> {
+        self.set(py, value, |ps, v| ps.margins.top = v)
+    }
+
+    #[getter]
+    fn bottom(&self, py: Python<'_>) -> PyResult<f64> {
+        read(&self.workbook, self.uid, py, |ps| ps.margins.bottom)
+    }
+
+    #[setter]
+    fn set_bottom(&self, py: Python<'_>, value: f64) -> PyResult<()> {
+        self.set(py, value, |ps, v| ps.margins.bottom = v)
+    }
+
+    #[getter]
+    fn header(&self, py: Python<'_>) -> PyResult<f64> {
+        read(&self.workbook, self.uid, py, |ps| ps.margins.header)
+    }
+
+    #[setter]
+    fn set_header(&self, py: Python<'_>, value: f64) -> PyResult<()> {
+        self.set(py, value, |ps, v| ps.margins.header = v)
+    }
+
+    #[getter]
+    fn footer(&self, py: Python<'_>) -> PyResult<f64> {
+        read(&self.workbook, self.uid, py, |ps| ps.margins.footer)
+    }
+
+    #[setter]
+    fn set_footer(&self, py: Python<'_>, value: f64) -> PyResult<()> {
+        self.set(py, value, |ps, v| ps.margins.footer = v)
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        read(&self.workbook, self.uid, py, |ps| {
+            let m = &ps.margins;
+            format!(
+                "<PageMargins left={} right={} top={} bottom={} header={} footer={}>",
+                m.left, m.right, m.top, m.bottom, m.header, m.footer
+            )
+        })
+    }
+}
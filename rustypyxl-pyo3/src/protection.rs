@@ -0,0 +1,127 @@
+//! The `ws.protection` proxy, after openpyxl's `SheetProtection`.
+
+#![allow(non_snake_case)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::Py;
+use rustypyxl_core::WorksheetProtection;
+
+use crate::workbook::PyWorkbook;
+
+/// A sheet's protection settings and stored password verifier. Reads and
+/// writes go straight to the worksheet.
+#[pyclass(name = "SheetProtection")]
+pub struct PySheetProtection {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+impl PySheetProtection {
+    fn with_protection<R>(
+        &self,
+        py: Python<'_>,
+        f: impl FnOnce(Option<&WorksheetProtection>) -> R,
+    ) -> PyResult<R> {
+        let this = self.workbook.borrow(py);
+        let idx = this
+            .inner
+            .sheet_index_by_uid(self.uid)
+            .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+        Ok(f(this.inner.worksheets[idx].protection.as_ref()))
+    }
+}
+
+#[pymethods]
+impl PySheetProtection {
+    /// Whether the sheet is protected. Setting False removes protection and
+    /// its password; setting True protects without one.
+    #[getter]
+    fn sheet(&self, py: Python<'_>) -> PyResult<bool> {
+        self.with_protection(py, |p| p.is_some_and(|p| p.sheet))
+    }
+
+    #[setter]
+    fn set_sheet(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = this
+            .inner
+            .sheet_index_by_uid(self.uid)
+            .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+        let ws = &mut this.inner.worksheets[idx];
+        match (value, ws.protection.as_mut()) {
+            (true, Some(protection)) => protection.sheet = true,
+            (true, None) => ws.enable_protection(None),
+            (false, _) => ws.disable_protection(),
+        }
+        Ok(())
+    }
+
+    /// Protect the sheet, without a password.
+    fn enable(&self, py: Python<'_>) -> PyResult<()> {
+        self.set_sheet(py, true)
+    }
+
+    /// Remove protection.
+    fn disable(&self, py: Python<'_>) -> PyResult<()> {
+        self.set_sheet(py, false)
+    }
+
+    /// The legacy 16-bit password verifier as four hex digits, or None.
+    #[getter]
+    fn password(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_protection(py, |p| p.and_then(|p| p.legacy_verifier()))
+    }
+
+    /// Hash algorithm of the iterated verifier, e.g. "SHA-512".
+    #[getter]
+    fn algorithmName(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_protection(py, |p| {
+            p.and_then(|p| p.hash.as_ref())
+                .map(|h| h.algorithm_name.clone())
+        })
+    }
+
+    /// Base64 hash of the iterated verifier.
+    #[getter]
+    fn hashValue(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_protection(py, |p| {
+            p.and_then(|p| p.hash.as_ref())
+                .map(|h| h.hash_value.clone())
+        })
+    }
+
+    /// Base64 salt of the iterated verifier.
+    #[getter]
+    fn saltValue(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_protection(py, |p| {
+            p.and_then(|p| p.hash.as_ref())
+                .map(|h| h.salt_value.clone())
+        })
+    }
+
+    /// Hash rounds of the iterated verifier.
+    #[getter]
+    fn spinCount(&self, py: Python<'_>) -> PyResult<Option<u32>> {
+        self.with_protection(py, |p| {
+            p.and_then(|p| p.hash.as_ref()).map(|h| h.spin_count)
+        })
+    }
+
+    /// Whether `password` unprotects the sheet, checked against the
+    /// iterated hash (SHA-512/384/256) or the legacy verifier, whichever
+    /// the sheet stores. True when the sheet has no password.
+    fn verify_password(&self, py: Python<'_>, password: &str) -> PyResult<bool> {
+        self.with_protection(py, |p| p.is_none_or(|p| p.verify_password(password)))
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        self.with_protection(py, |p| {
+            format!(
+                "<SheetProtection sheet={} password={}>",
+                p.is_some_and(|p| p.sheet),
+                p.is_some_and(|p| p.has_password())
+            )
+        })
+    }
+}
@@ -0,0 +1,370 @@
+//! Python bindings for read-only workbooks: `load_workbook(..., read_only=True)`
+//! streams rows out of the file instead of loading every cell.
+
+use std::sync::Mutex;
+
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use rustypyxl_core::read_only::{ReadOnlyOptions, ReadOnlyWorkbook, RowIter, StreamedRow};
+use rustypyxl_core::utils::{coordinate_from_row_col, parse_coordinate};
+
+use crate::workbook::{cell_value_to_python, read_source_bytes};
+
+fn to_py_err(e: rustypyxl_core::RustypyxlError) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+/// A workbook opened with `read_only=True`. Sheets are parsed on demand,
+/// one row at a time, so memory use does not grow with sheet size.
+#[pyclass(name = "ReadOnlyWorkbook")]
+pub struct PyReadOnlyWorkbook {
+    inner: Option<ReadOnlyWorkbook>,
+}
+
+impl PyReadOnlyWorkbook {
+    pub fn load(
+        source: &Bound<'_, PyAny>,
+        password: Option<&str>,
+        max_string_len: Option<usize>,
+    ) -> PyResult<Self> {
+        let py = source.py();
+        let options = ReadOnlyOptions { max_string_len };
+        // A path is streamed straight from disk; anything else is read into memory.
+        let inner = match source.extract::<std::path::PathBuf>() {
+            Ok(path) if password.is_none() && source.extract::<Vec<u8>>().is_err() => {
+                py.allow_threads(|| ReadOnlyWorkbook::open_with_options(path, &options))
+            }
+            _ => {
+                let bytes = read_source_bytes(source)?;
+                py.allow_threads(|| {
+                    let bytes = match password {
+                        Some(pw) if rustypyxl_core::crypto::is_encrypted(&bytes) => {
+                            rustypyxl_core::crypto::decrypt(&bytes, pw)?
+                        }
+                        _ => bytes,
+                    };
+                    ReadOnlyWorkbook::from_bytes_with_options(bytes, &options)
+                })
+            }
+        }
+        .map_err(to_py_err)?;
+        Ok(PyReadOnlyWorkbook { inner: Some(inner) })
+    }
+
+    fn workbook(&self) -> PyResult<&ReadOnlyWorkbook> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Workbook is closed"))
+    }
+}
+
+#[pymethods]
+impl PyReadOnlyWorkbook {
+    /// Sheet names, in workbook order.
+    #[getter]
+    fn sheetnames(&self) -> PyResult<Vec<String>> {
+        Ok(self.workbook()?.sheet_names().to_vec())
+    }
+
+    /// All worksheets, in workbook order.
+    #[getter]
+    fn worksheets(&self) -> PyResult<Vec<PyReadOnlyWorksheet>> {
+        let wb = self.workbook()?;
+        Ok(wb
+            .sheet_names()
+            .iter()
+            .map(|title| PyReadOnlyWorksheet::new(wb, title))
+            .collect())
+    }
+
+    /// The first worksheet.
+    #[getter]
+    fn active(&self) -> PyResult<Option<PyReadOnlyWorksheet>> {
+        let wb = self.workbook()?;
+        Ok(wb
+            .sheet_names()
+            .first()
+            .map(|title| PyReadOnlyWorksheet::new(wb, title)))
+    }
+
+    fn __getitem__(&self, key: &str) -> PyResult<PyReadOnlyWorksheet> {
+        let wb = self.workbook()?;
+        if !wb.sheet_names().iter().any(|name| name == key) {
+            return Err(PyKeyError::new_err(format!(
+                "Worksheet '{}' does not exist",
+                key
+            )));
+        }
+        Ok(PyReadOnlyWorksheet::new(wb, key))
+    }
+
+    fn __contains__(&self, key: &str) -> PyResult<bool> {
+        Ok(self
+            .workbook()?
+            .sheet_names()
+            .iter()
+            .any(|name| name == key))
+    }
+
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(self.workbook()?.sheet_names().len())
+    }
+
+    /// Release the workbook. Worksheets and row iterators already handed
+    /// out keep working until they are dropped.
+    fn close(&mut self) {
+        self.inner = None;
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (_exc_type=None, _exc_val=None, _exc_tb=None))]
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<PyObject>,
+        _exc_val: Option<PyObject>,
+        _exc_tb: Option<PyObject>,
+    ) -> bool {
+        self.close();
+        false
+    }
+
+    fn __repr__(&self) -> String {
+        match &self.inner {
+            Some(wb) => format!("<ReadOnlyWorkbook sheets={:?}>", wb.sheet_names()),
+            None => "<ReadOnlyWorkbook closed>".to_string(),
+        }
+    }
+}
+
+/// A worksheet of a read-only workbook. Only row iteration is available.
+#[pyclass(name = "ReadOnlyWorksheet")]
+#[derive(Clone)]
+pub struct PyReadOnlyWorksheet {
+    workbook: ReadOnlyWorkbook,
+    title: String,
+}
+
+impl PyReadOnlyWorksheet {
+    fn new(workbook: &ReadOnlyWorkbook, title: &str) -> Self {
+        PyReadOnlyWorksheet {
+            workbook: workbook.clone(),
+            title: title.to_string(),
+        }
+    }
+
+    /// The bottom-right corner of the declared dimension, if any.
+    fn declared_extent(&self, py: Python<'_>) -> PyResult<Option<(u32, u32)>> {
+        let dimension = py
+            .allow_threads(|| self.workbook.dimension(&self.title))
+            .map_err(to_py_err)?;
+        Ok(dimension.and_then(|d| {
+            let corner = d.rsplit(':').next().unwrap_or(&d).to_string();
+            parse_coordinate(&corner).ok()
+        }))
+    }
+}
+
+#[pymethods]
+impl PyReadOnlyWorksheet {
+    #[getter]
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The used range the file declares (e.g. "A1:F2000000"), or None.
+    #[getter]
+    fn dimensions(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        py.allow_threads(|| self.workbook.dimension(&self.title))
+            .map_err(to_py_err)
+    }
+
+    /// The last row, from the declared dimension (None if undeclared).
+    #[getter]
+    fn max_row(&self, py: Python<'_>) -> PyResult<Option<u32>> {
+        Ok(self.declared_extent(py)?.map(|(row, _)| row))
+    }
+
+    /// The last column, from the declared dimension (None if undeclared).
+    #[getter]
+    fn max_column(&self, py: Python<'_>) -> PyResult<Option<u32>> {
+        Ok(self.declared_extent(py)?.map(|(_, col)| col))
+    }
+
+    /// Iterate rows lazily. Rows missing from the file come back empty, and
+    /// each row is padded out to `max_col` (default: the declared
+    /// dimension). Yields tuples of ReadOnlyCell, or of plain values when
+    /// `values_only` is true.
+    #[pyo3(signature = (min_row=None, max_row=None, min_col=None, max_col=None, values_only=false))]
+    fn iter_rows(
+        &self,
+        min_row: Option<u32>,
+        max_row: Option<u32>,
+        min_col: Option<u32>,
+        max_col: Option<u32>,
+        values_only: bool,
+        py: Python<'_>,
+    ) -> PyResult<PyReadOnlyRowIterator> {
+        let max_col = match max_col {
+            Some(col) => Some(col),
+            None => self.declared_extent(py)?.map(|(_, col)| col),
+        };
+        let rows = self.workbook.rows(&self.title).map_err(to_py_err)?;
+        let min_row = min_row.unwrap_or(1).max(1);
+        Ok(PyReadOnlyRowIterator {
+            rows: Mutex::new(rows),
+            pending: None,
+            exhausted: false,
+            position: min_row,
+            max_row,
+            min_col: min_col.unwrap_or(1).max(1),
+            max_col,
+            values_only,
+        })
+    }
+
+    /// All rows as tuples of values: `for row in ws.values`.
+    #[getter]
+    fn values(&self, py: Python<'_>) -> PyResult<PyReadOnlyRowIterator> {
+        self.iter_rows(None, None, None, None, true, py)
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyReadOnlyRowIterator> {
+        self.iter_rows(None, None, None, None, false, py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<ReadOnlyWorksheet \"{}\">", self.title)
+    }
+}
+
+/// A cell yielded by `ReadOnlyWorksheet.iter_rows`.
+#[pyclass(name = "ReadOnlyCell", frozen)]
+pub struct PyReadOnlyCell {
+    #[pyo3(get)]
+    row: u32,
+    #[pyo3(get)]
+    column: u32,
+    #[pyo3(get)]
+    value: PyObject,
+    #[pyo3(get)]
+    number_format: Option<String>,
+}
+
+#[pymethods]
+impl PyReadOnlyCell {
+    #[getter]
+    fn coordinate(&self) -> String {
+        coordinate_from_row_col(self.row, self.column)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<ReadOnlyCell {}>", self.coordinate())
+    }
+}
+
+/// Lazy row iterator over a read-only worksheet. The sheet is parsed on a
+/// background thread a bounded number of rows ahead; dropping the iterator
+/// stops the parse.
+#[pyclass(name = "ReadOnlyRowIterator")]
+pub struct PyReadOnlyRowIterator {
+    rows: Mutex<RowIter>,
+    /// A parsed row not yet reached by `position`.
+    pending: Option<StreamedRow>,
+    exhausted: bool,
+    /// Next row number to yield.
+    position: u32,
+    max_row: Option<u32>,
+    min_col: u32,
+    max_col: Option<u32>,
+    values_only: bool,
+}
+
+impl PyReadOnlyRowIterator {
+    /// Pull rows until one at or past `position` is pending.
+    fn fill(&mut self, py: Python<'_>) -> PyResult<()> {
+        while !self.exhausted
+            && self
+                .pending
+                .as_ref()
+                .is_none_or(|r| r.index < self.position)
+        {
+            let rows = &self.rows;
+            let next = py.allow_threads(|| rows.lock().unwrap().next());
+            match next {
+                Some(Ok(row)) => self.pending = Some(row),
+                Some(Err(e)) => {
+                    self.exhausted = true;
+                    return Err(to_py_err(e));
+                }
+                None => {
+                    self.exhausted = true;
+                    self.pending = None;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn row_to_python(
+        &self,
+        py: Python<'_>,
+        index: u32,
+        row: Option<&StreamedRow>,
+    ) -> PyResult<PyObject> {
+        let last = self
+            .max_col
+            .unwrap_or_else(|| row.and_then(|r| r.cells.last()).map_or(0, |c| c.column));
+        let mut items: Vec<PyObject> = Vec::new();
+        let mut cells = row
+            .map(|r| r.cells.as_slice())
+            .unwrap_or(&[])
+            .iter()
+            .peekable();
+        for column in self.min_col..=last {
+            while cells.next_if(|c| c.column < column).is_some() {}
+            let cell = cells.next_if(|c| c.column == column);
+            let value = cell.map_or_else(|| py.None(), |c| cell_value_to_python(&c.value, py));
+            if self.values_only {
+                items.push(value);
+            } else {
+                let cell = PyReadOnlyCell {
+                    row: index,
+                    column,
+                    value,
+                    number_format: cell
+                        .and_then(|c| c.number_format.as_deref().map(str::to_string)),
+                };
+                items.push(Py::new(py, cell)?.into_any());
+            }
+        }
+        Ok(PyTuple::new(py, items)?.into_any().unbind())
+    }
+}
+
+#[pymethods]
+impl PyReadOnlyRowIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        if self.max_row.is_some_and(|max| self.position > max) {
+            return Ok(None);
+        }
+        self.fill(py)?;
+        let index = self.position;
+        let row = match &self.pending {
+            Some(row) if row.index == index => self.pending.take(),
+            // A gap before the next stored row: yield it empty.
+            Some(_) => None,
+            None => return Ok(None),
+        };
+        self.position += 1;
+        self.row_to_python(py, index, row.as_ref()).map(Some)
+    }
+}
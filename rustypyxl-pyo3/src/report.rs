@@ -0,0 +1,153 @@
+//! The `Report` builder: describe sections, then `ws.write_report(report)`
+//! lays them out and styles them.
+
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use rustypyxl_core::report::{Report, ReportColumn, ReportLayout, ReportTable};
+use rustypyxl_core::CellValue;
+
+use crate::table::totals_function_from_python;
+use crate::workbook::python_to_cell_value;
+
+/// A declarative report: add sections in order, then write it to a sheet
+/// with `ws.write_report(report)`.
+#[pyclass(name = "Report")]
+#[derive(Clone, Debug)]
+pub struct PyReport {
+    pub inner: Report,
+}
+
+/// A column spec is a header string or a dict with "header" and optional
+/// "number_format", "width" and "total" ("sum", "average", "count",
+/// "countNums", "max", "min", "stdDev", "var", or a formula starting with
+/// "=").
+fn column_from_python(spec: &Bound<'_, PyAny>) -> PyResult<ReportColumn> {
+    if let Ok(header) = spec.extract::<String>() {
+        return Ok(ReportColumn::new(header));
+    }
+    let dict = spec
+        .downcast::<PyDict>()
+        .map_err(|_| PyTypeError::new_err("a column must be a header string or a dict"))?;
+    let header: String = dict
+        .get_item("header")?
+        .ok_or_else(|| PyValueError::new_err("column dict needs a 'header'"))?
+        .extract()?;
+    let mut column = ReportColumn::new(header);
+    for (key, value) in dict.iter() {
+        let key: String = key.extract()?;
+        match key.as_str() {
+            "header" => {}
+            "number_format" => column.number_format = value.extract()?,
+            "width" => column.width = value.extract()?,
+            "total" => column.total = totals_function_from_python(value.extract()?)?,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown column option {other:?}"
+                )))
+            }
+        }
+    }
+    Ok(column)
+}
+
+fn row_from_python(row: &Bound<'_, PyAny>) -> PyResult<Vec<CellValue>> {
+    row.try_iter()?
+        .map(|value| python_to_cell_value(&value?))
+        .collect()
+}
+
+pub(crate) fn layout_to_python(py: Python<'_>, layout: &ReportLayout) -> PyResult<PyObject> {
+    let tables = PyList::empty(py);
+    for table in &layout.tables {
+        let entry = PyDict::new(py);
+        entry.set_item("header_row", table.header_row)?;
+        entry.set_item("first_data_row", table.first_data_row)?;
+        entry.set_item("last_data_row", table.last_data_row)?;
+        entry.set_item("totals_row", table.totals_row)?;
+        entry.set_item("range", &table.range)?;
+        tables.append(entry)?;
+    }
+    let out = PyDict::new(py);
+    out.set_item("sections", &layout.sections)?;
+    out.set_item("tables", tables)?;
+    out.set_item("last_row", layout.last_row)?;
+    Ok(out.into_any().unbind())
+}
+
+#[pymethods]
+impl PyReport {
+    /// `accent` colors the title and table headers; `banding` fills every
+    /// other data row (None to turn it off).
+    #[new]
+    #[pyo3(signature = (accent=None, banding=Some("F2F2F2".to_string())))]
+    fn new(accent: Option<String>, banding: Option<String>) -> Self {
+        let mut inner = Report::new().with_banding(banding.map(Into::into));
+        if let Some(accent) = accent {
+            inner = inner.with_accent(accent);
+        }
+        PyReport { inner }
+    }
+
+    /// Add a title block.
+    #[pyo3(signature = (text, subtitle=None))]
+    fn add_title(&mut self, text: String, subtitle: Option<&str>) {
+        self.inner = std::mem::take(&mut self.inner).with_title(text, subtitle);
+    }
+
+    /// Add "label / value" lines from a dict or a list of pairs.
+    fn add_key_values(&mut self, pairs: &Bound<'_, PyAny>) -> PyResult<()> {
+        let items = match pairs.downcast::<PyDict>() {
+            Ok(dict) => dict.items().into_any(),
+            Err(_) => pairs.clone(),
+        };
+        let mut converted = Vec::new();
+        for item in items.try_iter()? {
+            let (key, value): (String, Bound<'_, PyAny>) = item?.extract()?;
+            converted.push((key, python_to_cell_value(&value)?));
+        }
+        self.inner = std::mem::take(&mut self.inner).with_key_values(converted);
+        Ok(())
+    }
+
+    /// Add a data table. Each column is a header string or a dict with
+    /// "header", "number_format", "width" and "total"; a totals row is
+    /// written when any column has a total or `totals_label` is given.
+    #[pyo3(signature = (columns, rows, caption=None, totals_label=None))]
+    fn add_table(
+        &mut self,
+        columns: Vec<Bound<'_, PyAny>>,
+        rows: &Bound<'_, PyAny>,
+        caption: Option<String>,
+        totals_label: Option<String>,
+    ) -> PyResult<()> {
+        let columns = columns
+            .iter()
+            .map(column_from_python)
+            .collect::<PyResult<Vec<_>>>()?;
+        let rows = rows
+            .try_iter()?
+            .map(|row| row_from_python(&row?))
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut table = ReportTable::new(columns).with_rows(rows);
+        table.caption = caption;
+        table.totals_label = totals_label;
+        self.inner = std::mem::take(&mut self.inner).with_table(table);
+        Ok(())
+    }
+
+    /// Add notes under an optional heading.
+    #[pyo3(signature = (lines, heading=None))]
+    fn add_notes(&mut self, lines: Vec<String>, heading: Option<&str>) {
+        self.inner = std::mem::take(&mut self.inner).with_notes(heading, lines);
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.sections.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Report sections={}>", self.inner.sections.len())
+    }
+}
@@ -0,0 +1,391 @@
+//! openpyxl 3.1-style rich text: `CellRichText` holds plain strings and
+//! `TextBlock`s, each block a run of text in its own `InlineFont`. Assign one
+//! to a cell to write a rich string; load with `rich_text=True` to get them
+//! back from `cell.value`.
+
+#![allow(non_snake_case)]
+
+use pyo3::exceptions::{PyIndexError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyList, PyString};
+
+use rustypyxl_core::style::Color;
+use rustypyxl_core::{RichText, RunFont, TextRun};
+
+use crate::style::{coerce_color, color_to_python};
+
+/// The font of one run of rich text (openpyxl's `InlineFont`).
+#[pyclass(name = "InlineFont")]
+#[derive(Clone, Debug, Default)]
+pub struct PyInlineFont {
+    pub inner: RunFont,
+}
+
+#[pymethods]
+impl PyInlineFont {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (rFont=None, b=false, i=false, strike=false, color=None, sz=None, u=None, vertAlign=None))]
+    fn new(
+        rFont: Option<String>,
+        b: bool,
+        i: bool,
+        strike: bool,
+        color: Option<Bound<'_, PyAny>>,
+        sz: Option<f64>,
+        u: Option<String>,
+        vertAlign: Option<String>,
+    ) -> PyResult<Self> {
+        Ok(PyInlineFont {
+            inner: RunFont {
+                bold: b,
+                italic: i,
+                underline: u,
+                strike,
+                size: sz,
+                color: run_color(color.as_ref())?,
+                name: rFont,
+                vert_align: vertAlign,
+            },
+        })
+    }
+
+    /// Font family name.
+    #[getter]
+    fn rFont(&self) -> Option<String> {
+        self.inner.name.clone()
+    }
+
+    #[setter]
+    fn set_rFont(&mut self, value: Option<String>) {
+        self.inner.name = value;
+    }
+
+    #[getter]
+    fn b(&self) -> bool {
+        self.inner.bold
+    }
+
+    #[setter]
+    fn set_b(&mut self, value: bool) {
+        self.inner.bold = value;
+    }
+
+    #[getter]
+    fn i(&self) -> bool {
+        self.inner.italic
+    }
+
+    #[setter]
+    fn set_i(&mut self, value: bool) {
+        self.inner.italic = value;
+    }
+
+    #[getter]
+    fn strike(&self) -> bool {
+        self.inner.strike
+    }
+
+    #[setter]
+    fn set_strike(&mut self, value: bool) {
+        self.inner.strike = value;
+    }
+
+    /// Point size.
+    #[getter]
+    fn sz(&self) -> Option<f64> {
+        self.inner.size
+    }
+
+    #[setter]
+    fn set_sz(&mut self, value: Option<f64>) {
+        self.inner.size = value;
+    }
+
+    /// Underline: "single" or "double".
+    #[getter]
+    fn u(&self) -> Option<String> {
+        self.inner.underline.clone()
+    }
+
+    #[setter]
+    fn set_u(&mut self, value: Option<String>) {
+        self.inner.underline = value;
+    }
+
+    /// "superscript" or "subscript".
+    #[getter]
+    fn vertAlign(&self) -> Option<String> {
+        self.inner.vert_align.clone()
+    }
+
+    #[setter]
+    fn set_vertAlign(&mut self, value: Option<String>) {
+        self.inner.vert_align = value;
+    }
+
+    /// A hex string, or a Color when the color is themed, indexed or tinted.
+    #[getter]
+    fn color(&self, py: Python<'_>) -> PyResult<PyObject> {
+        color_to_python(self.inner.color.as_ref(), py)
+    }
+
+    #[setter]
+    fn set_color(&mut self, value: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        self.inner.color = run_color(value.as_ref())?;
+        Ok(())
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        other
+            .downcast::<Self>()
+            .is_ok_and(|other| other.borrow().inner == self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<InlineFont {:?}>", self.inner)
+    }
+}
+
+/// A run of text in its own font.
+#[pyclass(name = "TextBlock")]
+#[derive(Clone, Debug)]
+pub struct PyTextBlock {
+    pub font: RunFont,
+    pub text: String,
+}
+
+#[pymethods]
+impl PyTextBlock {
+    #[new]
+    fn new(font: &Bound<'_, PyAny>, text: String) -> PyResult<Self> {
+        Ok(PyTextBlock {
+            font: font_from_python(font)?,
+            text,
+        })
+    }
+
+    #[getter]
+    fn font(&self) -> PyInlineFont {
+        PyInlineFont {
+            inner: self.font.clone(),
+        }
+    }
+
+    #[setter]
+    fn set_font(&mut self, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.font = font_from_python(value)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn text(&self) -> String {
+        self.text.clone()
+    }
+
+    #[setter]
+    fn set_text(&mut self, value: String) {
+        self.text = value;
+    }
+
+    fn __str__(&self) -> String {
+        self.text.clone()
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        other.downcast::<Self>().is_ok_and(|other| {
+            let other = other.borrow();
+            other.text == self.text && other.font == self.font
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<TextBlock {:?}>", self.text)
+    }
+}
+
+/// A cell string made of runs: plain strings (which take the cell's font)
+/// and `TextBlock`s. `str()` of it is the concatenated text.
+#[pyclass(name = "CellRichText")]
+#[derive(Clone, Debug, Default)]
+pub struct PyCellRichText {
+    pub inner: RichText,
+}
+
+#[pymethods]
+impl PyCellRichText {
+    /// `CellRichText("a", TextBlock(...))` or `CellRichText(["a", ...])`.
+    #[new]
+    #[pyo3(signature = (*args))]
+    fn new(args: Vec<Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut rich = PyCellRichText::default();
+        match args.as_slice() {
+            [single] if !single.is_instance_of::<PyString>() && single.hasattr("__iter__")? => {
+                rich.extend(single)?
+            }
+            _ => {
+                for item in &args {
+                    rich.append(item)?;
+                }
+            }
+        }
+        Ok(rich)
+    }
+
+    fn append(&mut self, item: &Bound<'_, PyAny>) -> PyResult<()> {
+        self.inner.runs.push(run_from_python(item)?);
+        Ok(())
+    }
+
+    fn extend(&mut self, items: &Bound<'_, PyAny>) -> PyResult<()> {
+        for item in items.try_iter()? {
+            self.append(&item?)?;
+        }
+        Ok(())
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.runs.len()
+    }
+
+    fn __getitem__(&self, index: isize, py: Python<'_>) -> PyResult<PyObject> {
+        let len = self.inner.runs.len() as isize;
+        let at = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&at) {
+            return Err(PyIndexError::new_err("CellRichText index out of range"));
+        }
+        run_to_python(&self.inner.runs[at as usize], py)
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let items = self
+            .inner
+            .runs
+            .iter()
+            .map(|run| run_to_python(run, py))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(PyList::new(py, items)?.try_iter()?.into_any().unbind())
+    }
+
+    /// The text of each run.
+    fn as_list(&self) -> Vec<String> {
+        self.inner.runs.iter().map(|run| run.text.clone()).collect()
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.plain()
+    }
+
+    fn __eq__(&self, other: &Bound<'_, PyAny>) -> bool {
+        other
+            .downcast::<Self>()
+            .is_ok_and(|other| other.borrow().inner == self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<CellRichText {:?}>", self.inner.plain())
+    }
+}
+
+/// The runs of a value being assigned to a cell: a `CellRichText` of ours or
+/// openpyxl's. `None` for anything else.
+pub(crate) fn python_to_rich_text(value: &Bound<'_, PyAny>) -> PyResult<Option<RichText>> {
+    if let Ok(rich) = value.downcast::<PyCellRichText>() {
+        return Ok(Some(rich.borrow().inner.clone()));
+    }
+    if value.get_type().name()? != "CellRichText" {
+        return Ok(None);
+    }
+    let mut rich = PyCellRichText::default();
+    rich.extend(value)?;
+    Ok(Some(rich.inner))
+}
+
+/// A cell's runs as a `CellRichText`.
+pub(crate) fn rich_text_to_python(rich: &RichText, py: Python<'_>) -> PyResult<PyObject> {
+    Ok(Py::new(
+        py,
+        PyCellRichText {
+            inner: rich.clone(),
+        },
+    )?
+    .into_any())
+}
+
+fn run_to_python(run: &TextRun, py: Python<'_>) -> PyResult<PyObject> {
+    Ok(match &run.font {
+        None => PyString::new(py, &run.text).into_any().unbind(),
+        Some(font) => Py::new(
+            py,
+            PyTextBlock {
+                font: font.clone(),
+                text: run.text.clone(),
+            },
+        )?
+        .into_any(),
+    })
+}
+
+/// A plain string, or a text block (ours or openpyxl's).
+fn run_from_python(item: &Bound<'_, PyAny>) -> PyResult<TextRun> {
+    if let Ok(text) = item.extract::<String>() {
+        return Ok(TextRun::plain(text));
+    }
+    if let Ok(block) = item.downcast::<PyTextBlock>() {
+        let block = block.borrow();
+        return Ok(TextRun::formatted(block.text.clone(), block.font.clone()));
+    }
+    if item.hasattr("font")? && item.hasattr("text")? {
+        let text = item.getattr("text")?.extract::<String>()?;
+        return Ok(TextRun::formatted(
+            text,
+            font_from_python(&item.getattr("font")?)?,
+        ));
+    }
+    Err(PyTypeError::new_err(
+        "rich text items must be strings or TextBlocks",
+    ))
+}
+
+/// Read a run font from an `InlineFont` (ours or openpyxl's) or a `Font`.
+fn font_from_python(font: &Bound<'_, PyAny>) -> PyResult<RunFont> {
+    if let Ok(inline) = font.downcast::<PyInlineFont>() {
+        return Ok(inline.borrow().inner.clone());
+    }
+    // openpyxl's InlineFont uses the short names, Font the long ones.
+    let attr = |short: &str, long: &str| -> PyResult<Option<Bound<'_, PyAny>>> {
+        for name in [short, long] {
+            if let Ok(v) = font.getattr(name) {
+                return Ok((!v.is_none()).then_some(v));
+            }
+        }
+        Ok(None)
+    };
+    let flag = |short: &str, long: &str| -> PyResult<bool> {
+        attr(short, long)?.map_or(Ok(false), |v| v.is_truthy())
+    };
+    let text = |short: &str, long: &str| -> PyResult<Option<String>> {
+        attr(short, long)?.map(|v| v.extract()).transpose()
+    };
+    Ok(RunFont {
+        bold: flag("b", "bold")?,
+        italic: flag("i", "italic")?,
+        underline: text("u", "underline")?,
+        strike: flag("strike", "strike")?,
+        size: attr("sz", "size")?.map(|v| v.extract()).transpose()?,
+        color: run_color(attr("color", "color")?.as_ref())?,
+        name: text("rFont", "name")?,
+        vert_align: text("vertAlign", "vertAlign")?,
+    })
+}
+
+/// A hex string or Color, or an openpyxl Color (read through its `rgb`).
+fn run_color(value: Option<&Bound<'_, PyAny>>) -> PyResult<Option<Color>> {
+    match coerce_color(value) {
+        Ok(color) => Ok(color),
+        Err(err) => match value.map(|v| v.getattr("rgb")) {
+            Some(Ok(rgb)) if !rgb.is_none() => Ok(Some(Color::rgb(rgb.extract::<String>()?))),
+            _ => Err(err),
+        },
+    }
+}
@@ -0,0 +1,210 @@
+//! `ws.sheet_view` and `ws.sheet_properties` proxies, named after openpyxl's
+//! `SheetView` and `WorksheetProperties` attributes.
+
+#![allow(non_snake_case)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::Py;
+use rustypyxl_core::{SheetView, SheetViewType};
+
+use crate::style::{coerce_color, color_to_python};
+use crate::workbook::PyWorkbook;
+
+pub(crate) fn read_sheet<R>(
+    workbook: &Py<PyWorkbook>,
+    uid: u64,
+    py: Python<'_>,
+    f: impl FnOnce(&rustypyxl_core::Worksheet) -> R,
+) -> PyResult<R> {
+    let this = workbook.borrow(py);
+    let idx = this
+        .inner
+        .sheet_index_by_uid(uid)
+        .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+    Ok(f(&this.inner.worksheets[idx]))
+}
+
+pub(crate) fn write_sheet<R>(
+    workbook: &Py<PyWorkbook>,
+    uid: u64,
+    py: Python<'_>,
+    f: impl FnOnce(&mut rustypyxl_core::Worksheet) -> R,
+) -> PyResult<R> {
+    let mut this = workbook.borrow_mut(py);
+    let idx = this
+        .inner
+        .sheet_index_by_uid(uid)
+        .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+    Ok(f(&mut this.inner.worksheets[idx]))
+}
+
+/// How the sheet is displayed: `ws.sheet_view.showGridLines = False`.
+#[pyclass(name = "SheetView")]
+pub struct PySheetView {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+impl PySheetView {
+    fn read<R>(&self, py: Python<'_>, f: impl FnOnce(&SheetView) -> R) -> PyResult<R> {
+        read_sheet(&self.workbook, self.uid, py, |ws| f(&ws.sheet_view))
+    }
+
+    fn update<R>(&self, py: Python<'_>, f: impl FnOnce(&mut SheetView) -> R) -> PyResult<R> {
+        write_sheet(&self.workbook, self.uid, py, |ws| f(&mut ws.sheet_view))
+    }
+}
+
+#[pymethods]
+impl PySheetView {
+    #[getter]
+    fn showGridLines(&self, py: Python<'_>) -> PyResult<bool> {
+        self.read(py, |v| v.show_grid_lines)
+    }
+
+    #[setter]
+    fn set_showGridLines(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.update(py, |v| v.show_grid_lines = value)
+    }
+
+    /// Zoom in percent, 10 to 400.
+    #[getter]
+    fn zoomScale(&self, py: Python<'_>) -> PyResult<u32> {
+        self.read(py, |v| v.zoom_scale)
+    }
+
+    #[setter]
+    fn set_zoomScale(&self, py: Python<'_>, value: Option<u32>) -> PyResult<()> {
+        self.update(py, |v| v.set_zoom_scale(value.unwrap_or(100)))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    #[getter]
+    fn rightToLeft(&self, py: Python<'_>) -> PyResult<bool> {
+        self.read(py, |v| v.right_to_left)
+    }
+
+    #[setter]
+    fn set_rightToLeft(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.update(py, |v| v.right_to_left = value)
+    }
+
+    /// "normal", "pageBreakPreview" or "pageLayout".
+    #[getter]
+    fn view(&self, py: Python<'_>) -> PyResult<&'static str> {
+        self.read(py, |v| v.view.as_str())
+    }
+
+    #[setter]
+    fn set_view(&self, py: Python<'_>, value: Option<&str>) -> PyResult<()> {
+        let kind = SheetViewType::parse(value.unwrap_or("normal"))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.update(py, |v| v.view = kind)
+    }
+
+    /// The cell the sheet is scrolled to, or None for the top.
+    #[getter]
+    fn topLeftCell(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.read(py, |v| v.top_left_cell.clone())
+    }
+
+    #[setter]
+    fn set_topLeftCell(&self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        if let Some(ref cell) = value {
+            rustypyxl_core::parse_coordinate(cell)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        }
+        self.update(py, |v| v.top_left_cell = value)
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        self.read(py, |v| {
+            format!(
+                "<SheetView showGridLines={} zoomScale={} rightToLeft={} view={:?}>",
+                v.show_grid_lines,
+                v.zoom_scale,
+                v.right_to_left,
+                v.view.as_str()
+            )
+        })
+    }
+}
+
+/// Sheet-level properties: `ws.sheet_properties.tabColor = "1072BA"`.
+#[pyclass(name = "WorksheetProperties")]
+pub struct PySheetProperties {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+#[pymethods]
+impl PySheetProperties {
+    /// The tab color: an rgb string, or a Color for theme/indexed colors.
+    /// None when the tab has no color.
+    #[getter]
+    fn tabColor(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let color = read_sheet(&self.workbook, self.uid, py, |ws| {
+            ws.sheet_properties.tab_color.clone()
+        })?;
+        color_to_python(color.as_ref(), py)
+    }
+
+    #[setter]
+    fn set_tabColor(&self, py: Python<'_>, value: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        let color = coerce_color(value.as_ref())?;
+        write_sheet(&self.workbook, self.uid, py, |ws| {
+            ws.sheet_properties.tab_color = color
+        })
+    }
+
+    /// Where outline summary rows and columns sit:
+    /// `ws.sheet_properties.outlinePr.summaryBelow = False`.
+    #[getter]
+    fn outlinePr(&self, py: Python<'_>) -> PyOutlineProperties {
+        PyOutlineProperties {
+            workbook: self.workbook.clone_ref(py),
+            uid: self.uid,
+        }
+    }
+}
+
+/// Outline summary placement, openpyxl's `Outline`.
+#[pyclass(name = "Outline")]
+pub struct PyOutlineProperties {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+}
+
+#[pymethods]
+impl PyOutlineProperties {
+    /// True when each group's summary row is below it (the default).
+    #[getter]
+    fn summaryBelow(&self, py: Python<'_>) -> PyResult<bool> {
+        read_sheet(&self.workbook, self.uid, py, |ws| {
+            ws.sheet_properties.summary_below
+        })
+    }
+
+    #[setter]
+    fn set_summaryBelow(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        write_sheet(&self.workbook, self.uid, py, |ws| {
+            ws.sheet_properties.summary_below = value
+        })
+    }
+
+    /// True when each group's summary column is to its right (the default).
+    #[getter]
+    fn summaryRight(&self, py: Python<'_>) -> PyResult<bool> {
+        read_sheet(&self.workbook, self.uid, py, |ws| {
+            ws.sheet_properties.summary_right
+        })
+    }
+
+    #[setter]
+    fn set_summaryRight(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        write_sheet(&self.workbook, self.uid, py, |ws| {
+            ws.sheet_properties.summary_right = value
+        })
+    }
+}
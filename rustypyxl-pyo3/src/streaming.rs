@@ -2,7 +2,8 @@
 
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use rustypyxl_core::streaming::{StreamingSheet, StreamingWorkbook};
+use pyo3::types::PyDict;
+use rustypyxl_core::streaming::{Checkpoint, StreamingSheet, StreamingWorkbook};
 use rustypyxl_core::CellValue;
 
 /// A write-only workbook that streams data directly to disk.
@@ -19,7 +20,7 @@ use rustypyxl_core::CellValue;
 ///         wb.append_row(["total", 1_000_000])
 #[pyclass(name = "WriteOnlyWorkbook")]
 pub struct PyStreamingWorkbook {
-    inner: Option<StreamingWorkbook>,
+    pub(crate) inner: Option<StreamingWorkbook>,
     current_sheet: Option<StreamingSheet>,
 }
 
@@ -57,6 +58,39 @@ impl PyStreamingWorkbook {
         Ok(())
     }
 
+    /// Set how NaN and infinite numbers are written: "num" (default) for a
+    /// #NUM! error cell, "empty" to leave the cell out, or "error" to make
+    /// append_row raise ValueError without writing the row.
+    fn set_non_finite_numbers(&mut self, policy: &str) -> PyResult<()> {
+        let policy = crate::workbook::non_finite_policy(policy)?;
+        self.inner
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("Workbook already closed"))?
+            .set_non_finite_numbers(policy);
+        Ok(())
+    }
+
+    /// Set how numbers are written from the next row on: "shortest"
+    /// (default) or "excel" to round to 15 significant digits first.
+    fn set_number_precision(&mut self, precision: &str) -> PyResult<()> {
+        let precision = crate::workbook::number_precision(precision)?;
+        self.inner
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("Workbook already closed"))?
+            .set_number_precision(precision);
+        Ok(())
+    }
+
+    /// Author, title and timestamps written to docProps on close. A fixed
+    /// `modified` also dates the archive entries, so set it before the first
+    /// sheet for a reproducible file. See DocumentProperties.
+    #[getter]
+    fn properties(slf: Bound<'_, Self>) -> crate::docprops::PyDocumentProperties {
+        crate::docprops::PyDocumentProperties {
+            owner: crate::docprops::PropertiesOwner::Streaming(slf.unbind()),
+        }
+    }
+
     /// Append a row to the current sheet.
     ///
     /// Args:
@@ -107,6 +141,159 @@ impl PyStreamingWorkbook {
         .map_err(|e: rustypyxl_core::RustypyxlError| PyValueError::new_err(e.to_string()))
     }
 
+    /// Append every row an iterable yields, such as a generator, without
+    /// materializing it.
+    ///
+    /// Rows are pulled and converted `chunk_size` at a time with the GIL
+    /// held, then each chunk is written with the GIL released, so a
+    /// multi-million-row stream costs one handoff per chunk rather than a
+    /// call per row. Memory stays bounded by the chunk size.
+    ///
+    /// Args:
+    ///     rows: Iterable of rows, each an iterable of values
+    ///     chunk_size: Rows converted per GIL release (default 10000)
+    ///
+    /// Returns:
+    ///     int: The number of rows appended. If a row fails, the rows of the
+    ///     chunks before it have been written.
+    #[pyo3(signature = (rows, chunk_size=10_000))]
+    fn append_rows_from(
+        &mut self,
+        rows: &Bound<'_, PyAny>,
+        chunk_size: usize,
+        py: Python<'_>,
+    ) -> PyResult<u64> {
+        if chunk_size == 0 {
+            return Err(PyValueError::new_err("chunk_size must be at least 1"));
+        }
+        self.parts_mut()?;
+        let mut rows = rows.try_iter()?;
+        let mut appended = 0;
+        loop {
+            let mut chunk: Vec<Vec<CellValue>> = Vec::with_capacity(chunk_size);
+            for row in rows.by_ref().take(chunk_size) {
+                let row = row?
+                    .try_iter()?
+                    .map(|v| crate::workbook::python_to_cell_value(&v?))
+                    .collect::<PyResult<Vec<_>>>()?;
+                chunk.push(row);
+            }
+            if chunk.is_empty() {
+                return Ok(appended);
+            }
+            let full = chunk.len() == chunk_size;
+            let (wb, sheet) = self.parts_mut()?;
+            appended += py
+                .allow_threads(|| wb.append_rows_from(sheet, chunk))
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            if !full {
+                return Ok(appended);
+            }
+        }
+    }
+
+    /// Append the rows of a Parquet file to the current sheet, below any
+    /// rows already written, starting in column A. Record batches go
+    /// straight into the sheet XML with the GIL released, so a file of any
+    /// size converts in constant memory. Date and timestamp columns get
+    /// date formats.
+    ///
+    /// Args:
+    ///     path: Path to the Parquet file
+    ///     include_headers: Write the column names as a header row (default True)
+    ///     column_renames: Dict mapping original column names to new names
+    ///     columns: List of column names to write (None = all columns)
+    ///
+    /// Returns:
+    ///     Dict with rows_imported, columns_imported, start_row, start_col,
+    ///     end_row, end_col, range, header_range, data_range, column_names
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (path, include_headers=true, column_renames=None, columns=None))]
+    fn append_from_parquet(
+        &mut self,
+        path: &str,
+        include_headers: bool,
+        column_renames: Option<std::collections::HashMap<String, String>>,
+        columns: Option<Vec<String>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use rustypyxl_core::ParquetImportOptions;
+
+        let mut opts = ParquetImportOptions::new().with_headers(include_headers);
+        if let Some(renames) = column_renames {
+            opts.column_renames = renames;
+        }
+        if let Some(cols) = columns {
+            opts.columns = cols;
+        }
+
+        let (wb, sheet) = self.parts_mut()?;
+        let result = py
+            .allow_threads(|| wb.append_from_parquet(sheet, path, Some(opts)))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("rows_imported", result.rows_imported)?;
+        dict.set_item("columns_imported", result.columns_imported)?;
+        dict.set_item("start_row", result.start_row)?;
+        dict.set_item("start_col", result.start_col)?;
+        dict.set_item("end_row", result.end_row)?;
+        dict.set_item("end_col", result.end_col)?;
+        dict.set_item("range", result.range_with_headers())?;
+        dict.set_item("header_range", result.header_range())?;
+        dict.set_item("data_range", result.data_range())?;
+        dict.set_item("column_names", result.column_names)?;
+        Ok(dict.into())
+    }
+
+    /// Push the rows appended so far through the compressor and onto disk.
+    ///
+    /// The rows then survive a crash of the process; the file itself only
+    /// opens once the workbook is closed.
+    ///
+    /// Returns:
+    ///     Dict with sequence, sheet, sheet_rows, rows_written and
+    ///     bytes_emitted (the size of the file on disk)
+    fn flush<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let wb = self
+            .inner
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("Workbook already closed"))?;
+        let checkpoint = py
+            .allow_threads(|| wb.flush())
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        checkpoint_dict(py, &checkpoint)
+    }
+
+    /// Flush automatically every `rows` appended rows, or never with None
+    /// (the default). The latest checkpoint is in last_checkpoint.
+    #[pyo3(signature = (rows))]
+    fn set_checkpoint_interval(&mut self, rows: Option<u64>) -> PyResult<()> {
+        self.inner
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("Workbook already closed"))?
+            .set_checkpoint_interval(rows);
+        Ok(())
+    }
+
+    /// The most recent checkpoint as a dict (see flush), or None.
+    #[getter]
+    fn last_checkpoint<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        match self.inner.as_ref().and_then(|wb| wb.last_checkpoint()) {
+            Some(checkpoint) => checkpoint_dict(py, checkpoint).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Rows appended across all sheets so far, flushed or not.
+    #[getter]
+    fn rows_written(&self) -> PyResult<u64> {
+        self.inner
+            .as_ref()
+            .map(|wb| wb.rows_written())
+            .ok_or_else(|| PyValueError::new_err("Workbook already closed"))
+    }
+
     /// Close the workbook and finalize the file.
     ///
     /// This must be called (or the workbook used as a context manager) to
@@ -172,3 +359,13 @@ impl PyStreamingWorkbook {
         result.map_err(|e| PyValueError::new_err(e.to_string()))
     }
 }
+
+fn checkpoint_dict<'py>(py: Python<'py>, checkpoint: &Checkpoint) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("sequence", checkpoint.sequence)?;
+    dict.set_item("sheet", checkpoint.sheet.as_deref())?;
+    dict.set_item("sheet_rows", checkpoint.sheet_rows)?;
+    dict.set_item("rows_written", checkpoint.rows_written)?;
+    dict.set_item("bytes_emitted", checkpoint.bytes_emitted)?;
+    Ok(dict)
+}
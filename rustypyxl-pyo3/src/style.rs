@@ -4,7 +4,9 @@
 
 use pyo3::exceptions::PyTypeError;
 use pyo3::prelude::*;
-use rustypyxl_core::Color;
+use rustypyxl_core::{Color, ImportStyleOptions, Theme};
+
+use crate::workbook::PyWorkbook;
 
 /// Accept either an rgb string or a Color object wherever openpyxl does.
 pub(crate) fn coerce_color(value: Option<&Bound<'_, PyAny>>) -> PyResult<Option<Color>> {
@@ -15,14 +17,8 @@ pub(crate) fn coerce_color(value: Option<&Bound<'_, PyAny>>) -> PyResult<Option<
     if let Ok(rgb) = v.extract::<String>() {
         return Ok(Some(Color::rgb(rgb)));
     }
-    if let Ok(color) = v.extract::<PyColor>() {
-        let color = Color {
-            rgb: color.rgb,
-            theme: color.theme,
-            indexed: color.indexed,
-            // 0.0 is the default, i.e. no tint at all
-            tint: (color.tint != 0.0).then_some(color.tint),
-        };
+    if let Ok(color) = v.extract::<PyRef<PyColor>>() {
+        let color = color.to_core();
         return Ok((!color.is_empty()).then_some(color));
     }
     Err(PyTypeError::new_err(
@@ -138,6 +134,9 @@ impl PyFont {
 }
 
 /// Text alignment (openpyxl-compatible).
+///
+/// Line breaks in a cell's value are always saved, but Excel only shows them
+/// as separate lines when `wrap_text` is set.
 #[pyclass(name = "Alignment")]
 #[derive(Clone, Debug, Default)]
 pub struct PyAlignment {
@@ -302,11 +301,16 @@ impl PySide {
         Ok(())
     }
 
+    /// `border_style` is openpyxl's alias for `style`.
     #[new]
-    #[pyo3(signature = (style=None, color=None))]
-    fn new(style: Option<String>, color: Option<Bound<'_, PyAny>>) -> PyResult<Self> {
+    #[pyo3(signature = (style=None, color=None, border_style=None))]
+    fn new(
+        style: Option<String>,
+        color: Option<Bound<'_, PyAny>>,
+        border_style: Option<String>,
+    ) -> PyResult<Self> {
         Ok(PySide {
-            style,
+            style: style.or(border_style),
             color: coerce_color(color.as_ref())?,
         })
     }
@@ -418,6 +422,18 @@ impl PyColor {
         self.clone()
     }
 
+    /// The concrete "FFRRGGBB" value, with theme and indexed colors looked up
+    /// in `workbook`'s theme (the default Office theme without one) and the
+    /// tint applied. None for an empty color or an out-of-range index.
+    #[pyo3(signature = (workbook=None))]
+    fn to_rgb(&self, py: Python<'_>, workbook: Option<Py<PyWorkbook>>) -> Option<String> {
+        let color = self.to_core();
+        match workbook {
+            Some(wb) => color.to_rgb(&wb.borrow(py).inner.theme),
+            None => color.to_rgb(&Theme::default()),
+        }
+    }
+
     fn __str__(&self) -> String {
         if let Some(ref rgb) = self.rgb {
             format!("<Color rgb={}>", rgb)
@@ -433,6 +449,18 @@ impl PyColor {
     }
 }
 
+impl PyColor {
+    pub(crate) fn to_core(&self) -> Color {
+        Color {
+            rgb: self.rgb.clone(),
+            theme: self.theme,
+            indexed: self.indexed,
+            // 0.0 is the default, i.e. no tint at all
+            tint: (self.tint != 0.0).then_some(self.tint),
+        }
+    }
+}
+
 impl Default for PyColor {
     fn default() -> Self {
         PyColor {
@@ -444,6 +472,65 @@ impl Default for PyColor {
     }
 }
 
+/// A named cell style (openpyxl-compatible): register it with
+/// `wb.add_named_style(style)` and apply it with `cell.style = "name"`.
+#[pyclass(name = "NamedStyle")]
+#[derive(Clone, Debug, Default)]
+pub struct PyNamedStyle {
+    #[pyo3(get, set)]
+    pub name: String,
+    #[pyo3(get, set)]
+    pub font: Option<PyFont>,
+    #[pyo3(get, set)]
+    pub fill: Option<PyPatternFill>,
+    #[pyo3(get, set)]
+    pub border: Option<PyBorder>,
+    #[pyo3(get, set)]
+    pub alignment: Option<PyAlignment>,
+    #[pyo3(get, set)]
+    pub number_format: Option<String>,
+    #[pyo3(get, set)]
+    pub protection: Option<PyProtection>,
+    #[pyo3(get, set)]
+    pub builtinId: Option<u32>,
+    #[pyo3(get, set)]
+    pub hidden: bool,
+}
+
+#[pymethods]
+impl PyNamedStyle {
+    #[new]
+    #[pyo3(signature = (name="Normal".to_string(), font=None, fill=None, border=None, alignment=None, number_format=None, protection=None, builtinId=None, hidden=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        name: String,
+        font: Option<PyFont>,
+        fill: Option<PyPatternFill>,
+        border: Option<PyBorder>,
+        alignment: Option<PyAlignment>,
+        number_format: Option<String>,
+        protection: Option<PyProtection>,
+        builtinId: Option<u32>,
+        hidden: bool,
+    ) -> Self {
+        PyNamedStyle {
+            name,
+            font,
+            fill,
+            border,
+            alignment,
+            number_format,
+            protection,
+            builtinId,
+            hidden,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<NamedStyle name={:?}>", self.name)
+    }
+}
+
 /// Protection (openpyxl-compatible).
 #[pyclass(name = "Protection")]
 #[derive(Clone, Debug, Default)]
@@ -570,3 +657,110 @@ impl PyGradientFill {
         self.__str__()
     }
 }
+
+/// Styling applied by the importers (insert_from_parquet, write_arrow) to
+/// the block they write: header font/fill/border, a frozen header row, an
+/// autofilter over the header, and column widths chosen by data type.
+///
+/// The defaults give a bold header on a light gray fill with a thin bottom
+/// border; pass None for any of the three to leave that part unstyled.
+#[pyclass(name = "ImportStyle")]
+#[derive(Clone, Debug)]
+pub struct PyImportStyle {
+    pub(crate) options: ImportStyleOptions,
+}
+
+#[pymethods]
+impl PyImportStyle {
+    #[new]
+    #[pyo3(signature = (header_font=Some(default_header_font()), header_fill=Some(default_header_fill()), header_border=Some(default_header_border()), freeze_header=true, auto_filter=true, size_columns=true))]
+    fn new(
+        header_font: Option<PyFont>,
+        header_fill: Option<PyPatternFill>,
+        header_border: Option<PyBorder>,
+        freeze_header: bool,
+        auto_filter: bool,
+        size_columns: bool,
+    ) -> Self {
+        PyImportStyle {
+            options: ImportStyleOptions {
+                header_font: header_font.as_ref().map(crate::workbook::pyfont_to_font),
+                header_fill: header_fill.as_ref().map(crate::workbook::pyfill_to_fill),
+                header_border: header_border
+                    .as_ref()
+                    .map(crate::workbook::pyborder_to_border),
+                freeze_header,
+                auto_filter,
+                size_columns,
+            },
+        }
+    }
+
+    #[getter]
+    fn freeze_header(&self) -> bool {
+        self.options.freeze_header
+    }
+
+    #[getter]
+    fn auto_filter(&self) -> bool {
+        self.options.auto_filter
+    }
+
+    #[getter]
+    fn size_columns(&self) -> bool {
+        self.options.size_columns
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<ImportStyle freeze_header={} auto_filter={} size_columns={}>",
+            self.options.freeze_header, self.options.auto_filter, self.options.size_columns
+        )
+    }
+}
+
+fn default_header_font() -> PyFont {
+    PyFont {
+        bold: true,
+        ..Default::default()
+    }
+}
+
+fn default_header_fill() -> PyPatternFill {
+    PyPatternFill {
+        fill_type: Some("solid".to_string()),
+        fgColor: Some(Color::rgb("FFD9D9D9")),
+        ..Default::default()
+    }
+}
+
+fn default_header_border() -> PyBorder {
+    PyBorder {
+        bottom: Some(PySide {
+            style: Some("thin".to_string()),
+            color: None,
+        }),
+        outline: true,
+        ..Default::default()
+    }
+}
+
+/// Resolve an importer's `style` argument: None/False for no styling, True
+/// for the presentable defaults, or an ImportStyle.
+pub(crate) fn extract_import_style(
+    value: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<ImportStyleOptions>> {
+    let Some(v) = value else { return Ok(None) };
+    if v.is_none() {
+        return Ok(None);
+    }
+    if let Ok(flag) = v.extract::<bool>() {
+        return Ok(flag.then(ImportStyleOptions::presentable));
+    }
+    if let Ok(style) = v.extract::<PyRef<'_, PyImportStyle>>() {
+        return Ok(Some(style.options.clone()));
+    }
+    Err(PyTypeError::new_err(
+        "style must be an ImportStyle, True, False, or None",
+    ))
+}
@@ -0,0 +1,196 @@
+//! The `Table` proxy returned by `ws.add_table(...)` and `ws.get_table(name)`.
+
+use std::collections::HashMap;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use rustypyxl_core::table::{TableStyle, TotalsRowFunction};
+
+use crate::workbook::PyWorkbook;
+
+/// Parse a totals function: a name ("sum", "average", "count", "countNums",
+/// "max", "min", "stdDev", "var"), a formula starting with "=", or None.
+pub(crate) fn totals_function_from_python(name: Option<String>) -> PyResult<TotalsRowFunction> {
+    match name {
+        None => Ok(TotalsRowFunction::None),
+        Some(f) if f.starts_with('=') => Ok(TotalsRowFunction::Custom(f)),
+        Some(name) => TotalsRowFunction::parse(&name)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown total function {name:?}"))),
+    }
+}
+
+/// An Excel table on a worksheet. Reads and writes go through the workbook,
+/// so the proxy stays valid as the sheet changes.
+#[pyclass(name = "Table")]
+pub struct PyTable {
+    pub(crate) workbook: Py<PyWorkbook>,
+    pub(crate) uid: u64,
+    pub(crate) name: String,
+}
+
+impl PyTable {
+    fn with_table<T>(
+        &self,
+        py: Python<'_>,
+        f: impl FnOnce(&rustypyxl_core::table::Table) -> T,
+    ) -> PyResult<T> {
+        let this = self.workbook.borrow(py);
+        let idx = this
+            .inner
+            .sheet_index_by_uid(self.uid)
+            .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+        this.inner.worksheets[idx]
+            .tables
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(&self.name))
+            .map(f)
+            .ok_or_else(|| PyValueError::new_err(format!("Table '{}' does not exist", self.name)))
+    }
+
+    fn with_table_mut(
+        &self,
+        py: Python<'_>,
+        f: impl FnOnce(&mut rustypyxl_core::table::Table),
+    ) -> PyResult<()> {
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = this
+            .inner
+            .sheet_index_by_uid(self.uid)
+            .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+        this.inner.worksheets[idx]
+            .tables
+            .iter_mut()
+            .find(|t| t.name.eq_ignore_ascii_case(&self.name))
+            .map(f)
+            .ok_or_else(|| PyValueError::new_err(format!("Table '{}' does not exist", self.name)))
+    }
+}
+
+#[pymethods]
+impl PyTable {
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Renaming a table rewrites the structured references to it
+    /// (`Sales[Qty]`) throughout the workbook.
+    #[setter]
+    fn set_name(&mut self, py: Python<'_>, name: String) -> PyResult<()> {
+        self.workbook
+            .borrow_mut(py)
+            .inner
+            .rename_table(&self.name, &name)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.name = name;
+        Ok(())
+    }
+
+    /// The table's range, including the header and totals rows.
+    #[getter(r#ref)]
+    fn get_ref(&self, py: Python<'_>) -> PyResult<String> {
+        self.with_table(py, |t| t.range.clone())
+    }
+
+    /// The column names, left to right.
+    #[getter]
+    fn columns(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        self.with_table(py, |t| t.columns.iter().map(|c| c.name.clone()).collect())
+    }
+
+    /// The table style name, e.g. "TableStyleMedium9".
+    #[getter]
+    fn style(&self, py: Python<'_>) -> PyResult<String> {
+        self.with_table(py, |t| t.style.style_name())
+    }
+
+    #[setter]
+    fn set_style(&self, py: Python<'_>, style: String) -> PyResult<()> {
+        self.with_table_mut(py, |t| t.style = TableStyle::Custom(style))
+    }
+
+    /// Whether alternate rows are banded.
+    #[getter]
+    fn row_stripes(&self, py: Python<'_>) -> PyResult<bool> {
+        self.with_table(py, |t| t.show_row_stripes)
+    }
+
+    #[setter]
+    fn set_row_stripes(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.with_table_mut(py, |t| t.show_row_stripes = value)
+    }
+
+    /// Whether alternate columns are banded.
+    #[getter]
+    fn column_stripes(&self, py: Python<'_>) -> PyResult<bool> {
+        self.with_table(py, |t| t.show_column_stripes)
+    }
+
+    #[setter]
+    fn set_column_stripes(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.with_table_mut(py, |t| t.show_column_stripes = value)
+    }
+
+    /// Whether the header row shows filter dropdowns.
+    #[getter]
+    fn auto_filter(&self, py: Python<'_>) -> PyResult<bool> {
+        self.with_table(py, |t| t.auto_filter)
+    }
+
+    #[setter]
+    fn set_auto_filter(&self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.with_table_mut(py, |t| t.auto_filter = value)
+    }
+
+    /// Whether the table has a totals row.
+    #[getter]
+    fn totals_row(&self, py: Python<'_>) -> PyResult<bool> {
+        self.with_table(py, |t| t.totals_row)
+    }
+
+    /// Add a totals row beneath the table, e.g.
+    /// `table.add_totals_row({"Amount": "sum", "Qty": "count"})`. Each
+    /// function is written as a SUBTOTAL formula (a value starting with "="
+    /// is written as-is); the first column is labelled "Total" unless it has
+    /// a function. Calling it again updates the existing totals row. Returns
+    /// the totals row number.
+    fn add_totals_row(
+        &self,
+        totals: HashMap<String, Option<String>>,
+        py: Python<'_>,
+    ) -> PyResult<u32> {
+        let totals = totals
+            .into_iter()
+            .map(|(column, function)| Ok((column, totals_function_from_python(function)?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let totals: Vec<(&str, TotalsRowFunction)> = totals
+            .iter()
+            .map(|(column, function)| (column.as_str(), function.clone()))
+            .collect();
+        let mut this = self.workbook.borrow_mut(py);
+        let idx = this
+            .inner
+            .sheet_index_by_uid(self.uid)
+            .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+        this.inner.worksheets[idx]
+            .add_table_totals_row(&self.name, &totals)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Give the table a new range, e.g. `table.resize("A1:E20")`. The header
+    /// row stays on its row and the new range must overlap the old one.
+    /// Added columns are named from their header cells; references to
+    /// columns that fall out of the table become #REF!.
+    fn resize(&self, r#ref: &str, py: Python<'_>) -> PyResult<()> {
+        self.workbook
+            .borrow_mut(py)
+            .inner
+            .resize_table(&self.name, r#ref)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Table \"{}\">", self.name)
+    }
+}
@@ -0,0 +1,367 @@
+//! openpyxl-style `DataValidation`: build a rule, attach it with
+//! `ws.add_data_validation(dv)`, and extend it with `dv.add("A1:A100")`.
+//! Like openpyxl, a rule stays live after it is attached: later `add()`
+//! calls and attribute changes reach the sheets it was added to.
+
+#![allow(non_snake_case)]
+
+use pyo3::prelude::*;
+
+use rustypyxl_core::validation::parse_sqref;
+use rustypyxl_core::DataValidation;
+
+use crate::workbook::PyWorkbook;
+use crate::worksheet::PyWorksheet;
+
+/// A data validation rule (dropdown list, number or date limits, text
+/// length, or a custom formula) over one or more ranges.
+#[pyclass(name = "DataValidation")]
+pub struct PyDataValidation {
+    pub inner: DataValidation,
+    attached: Vec<Attachment>,
+}
+
+/// A sheet the rule was added to (workbook, sheet uid) and the cell it is
+/// keyed under there, once it has a range.
+type Attachment = (Py<PyWorkbook>, u64, Option<(u32, u32)>);
+
+impl PyDataValidation {
+    /// Attach to a sheet and store the rule there.
+    pub fn attach(&mut self, ws: &PyWorksheet, py: Python<'_>) -> PyResult<()> {
+        let Some(ref wb) = ws.workbook else {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        self.attached.push((wb.clone_ref(py), ws.uid, None));
+        self.sync(py)
+    }
+
+    /// Rewrite the rule on every sheet it is attached to.
+    fn sync(&mut self, py: Python<'_>) -> PyResult<()> {
+        let has_range = !self.inner.ranges().is_empty();
+        for (wb, uid, key) in &mut self.attached {
+            let mut wb = wb.borrow_mut(py);
+            let Some(idx) = wb.inner.sheet_index_by_uid(*uid) else {
+                continue;
+            };
+            let ws = &mut wb.inner.worksheets[idx];
+            if let Some(old) = key.take() {
+                ws.data_validations.remove(&old);
+            }
+            if has_range {
+                *key = Some(ws.add_validation(self.inner.clone()).map_err(value_error)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy an openpyxl `DataValidation` (or anything with its attributes).
+    pub fn from_openpyxl(obj: &Bound<'_, PyAny>) -> PyResult<DataValidation> {
+        let text = |name: &str| -> PyResult<Option<String>> {
+            match obj.getattr(name) {
+                Ok(v) if !v.is_none() => Ok(Some(v.str()?.to_string())),
+                _ => Ok(None),
+            }
+        };
+        let flag = |name: &str| -> bool {
+            obj.getattr(name)
+                .and_then(|v| v.is_truthy())
+                .unwrap_or(false)
+        };
+        Ok(DataValidation {
+            validation_type: text("type")?.unwrap_or_else(|| "none".to_string()),
+            operator: text("operator")?,
+            formula1: text("formula1")?,
+            formula2: text("formula2")?,
+            error_style: text("errorStyle")?,
+            allow_blank: flag("allowBlank"),
+            show_error: flag("showErrorMessage"),
+            error_title: text("errorTitle")?,
+            error_message: text("error")?,
+            show_input: flag("showInputMessage"),
+            prompt_title: text("promptTitle")?,
+            prompt_message: text("prompt")?,
+            hide_dropdown: flag("showDropDown"),
+            sqref: text("sqref")?.filter(|s| !s.trim().is_empty()),
+        })
+    }
+}
+
+fn value_error(e: rustypyxl_core::RustypyxlError) -> PyErr {
+    pyo3::exceptions::PyValueError::new_err(e.to_string())
+}
+
+/// A cell or range argument: a string, or a cell object's coordinate.
+fn cell_ref(cell: &Bound<'_, PyAny>) -> PyResult<String> {
+    match cell.extract::<String>() {
+        Ok(s) => Ok(s),
+        Err(_) => cell.getattr("coordinate")?.extract(),
+    }
+}
+
+#[pymethods]
+impl PyDataValidation {
+    #[new]
+    #[pyo3(signature = (r#type=None, formula1=None, formula2=None, showErrorMessage=false, showInputMessage=false, showDropDown=false, allowBlank=false, sqref=None, promptTitle=None, errorStyle=None, error=None, prompt=None, errorTitle=None, operator=None, allow_blank=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        r#type: Option<String>,
+        formula1: Option<String>,
+        formula2: Option<String>,
+        showErrorMessage: bool,
+        showInputMessage: bool,
+        showDropDown: bool,
+        allowBlank: bool,
+        sqref: Option<String>,
+        promptTitle: Option<String>,
+        errorStyle: Option<String>,
+        error: Option<String>,
+        prompt: Option<String>,
+        errorTitle: Option<String>,
+        operator: Option<String>,
+        allow_blank: Option<bool>,
+    ) -> PyResult<Self> {
+        let sqref = sqref.filter(|s| !s.trim().is_empty());
+        if let Some(ref sqref) = sqref {
+            parse_sqref(sqref).map_err(value_error)?;
+        }
+        Ok(PyDataValidation {
+            inner: DataValidation {
+                validation_type: r#type.unwrap_or_else(|| "none".to_string()),
+                operator,
+                formula1,
+                formula2,
+                error_style: errorStyle,
+                allow_blank: allow_blank.unwrap_or(allowBlank),
+                show_error: showErrorMessage,
+                error_title: errorTitle,
+                error_message: error,
+                show_input: showInputMessage,
+                prompt_title: promptTitle,
+                prompt_message: prompt,
+                hide_dropdown: showDropDown,
+                sqref,
+            },
+            attached: Vec::new(),
+        })
+    }
+
+    /// Extend the rule to a cell or range ("B2", "A1:A100", or a cell).
+    fn add(&mut self, cell: &Bound<'_, PyAny>) -> PyResult<()> {
+        let range = cell_ref(cell)?;
+        self.inner.add_range(&range).map_err(value_error)?;
+        self.sync(cell.py())
+    }
+
+    fn __contains__(&self, cell: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let coord = cell_ref(cell)?;
+        let (row, col) =
+            rustypyxl_core::parse_coordinate(&coord.replace('$', "")).map_err(value_error)?;
+        Ok(self.inner.covers(row, col))
+    }
+
+    /// The covered ranges, space-separated (e.g. "A1:A10 C1").
+    #[getter]
+    fn sqref(&self) -> String {
+        self.inner.sqref.clone().unwrap_or_default()
+    }
+
+    #[setter]
+    fn set_sqref(&mut self, py: Python<'_>, value: String) -> PyResult<()> {
+        if !value.trim().is_empty() {
+            parse_sqref(&value).map_err(value_error)?;
+        }
+        self.inner.sqref = Some(value).filter(|s| !s.trim().is_empty());
+        self.sync(py)
+    }
+
+    /// Rule type: whole, decimal, list, date, time, textLength, custom.
+    #[getter]
+    fn r#type(&self) -> String {
+        self.inner.validation_type.clone()
+    }
+
+    #[setter]
+    fn set_type(&mut self, py: Python<'_>, value: String) -> PyResult<()> {
+        self.inner.validation_type = value;
+        self.sync(py)
+    }
+
+    /// Comparison operator, e.g. "between" or "greaterThan".
+    #[getter]
+    fn operator(&self) -> Option<String> {
+        self.inner.operator.clone()
+    }
+
+    #[setter]
+    fn set_operator(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.operator = value;
+        self.sync(py)
+    }
+
+    /// First constraint: a value, formula, list like '"A,B"', or range.
+    #[getter]
+    fn formula1(&self) -> Option<String> {
+        self.inner.formula1.clone()
+    }
+
+    #[setter]
+    fn set_formula1(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.formula1 = value;
+        self.sync(py)
+    }
+
+    /// Second constraint, for between and notBetween.
+    #[getter]
+    fn formula2(&self) -> Option<String> {
+        self.inner.formula2.clone()
+    }
+
+    #[setter]
+    fn set_formula2(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.formula2 = value;
+        self.sync(py)
+    }
+
+    /// Error box severity: stop, warning or information.
+    #[getter]
+    fn errorStyle(&self) -> Option<String> {
+        self.inner.error_style.clone()
+    }
+
+    #[setter]
+    fn set_errorStyle(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.error_style = value;
+        self.sync(py)
+    }
+
+    /// Error box title.
+    #[getter]
+    fn errorTitle(&self) -> Option<String> {
+        self.inner.error_title.clone()
+    }
+
+    #[setter]
+    fn set_errorTitle(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.error_title = value;
+        self.sync(py)
+    }
+
+    /// Error box message.
+    #[getter]
+    fn error(&self) -> Option<String> {
+        self.inner.error_message.clone()
+    }
+
+    #[setter]
+    fn set_error(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.error_message = value;
+        self.sync(py)
+    }
+
+    /// Input prompt title.
+    #[getter]
+    fn promptTitle(&self) -> Option<String> {
+        self.inner.prompt_title.clone()
+    }
+
+    #[setter]
+    fn set_promptTitle(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.prompt_title = value;
+        self.sync(py)
+    }
+
+    /// Input prompt message.
+    #[getter]
+    fn prompt(&self) -> Option<String> {
+        self.inner.prompt_message.clone()
+    }
+
+    #[setter]
+    fn set_prompt(&mut self, py: Python<'_>, value: Option<String>) -> PyResult<()> {
+        self.inner.prompt_message = value;
+        self.sync(py)
+    }
+
+    /// Whether blank cells pass.
+    #[getter]
+    fn allowBlank(&self) -> bool {
+        self.inner.allow_blank
+    }
+
+    #[setter]
+    fn set_allowBlank(&mut self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.inner.allow_blank = value;
+        self.sync(py)
+    }
+
+    /// Alias of `allowBlank`.
+    #[getter]
+    fn allow_blank(&self) -> bool {
+        self.inner.allow_blank
+    }
+
+    #[setter]
+    fn set_allow_blank(&mut self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.inner.allow_blank = value;
+        self.sync(py)
+    }
+
+    /// Show the error box on invalid input.
+    #[getter]
+    fn showErrorMessage(&self) -> bool {
+        self.inner.show_error
+    }
+
+    #[setter]
+    fn set_showErrorMessage(&mut self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.inner.show_error = value;
+        self.sync(py)
+    }
+
+    /// Show the input prompt when a covered cell is selected.
+    #[getter]
+    fn showInputMessage(&self) -> bool {
+        self.inner.show_input
+    }
+
+    #[setter]
+    fn set_showInputMessage(&mut self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.inner.show_input = value;
+        self.sync(py)
+    }
+
+    /// True *hides* a list rule's in-cell dropdown (the attribute's
+    /// name is misleading, in openpyxl as in Excel).
+    #[getter]
+    fn showDropDown(&self) -> bool {
+        self.inner.hide_dropdown
+    }
+
+    #[setter]
+    fn set_showDropDown(&mut self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.inner.hide_dropdown = value;
+        self.sync(py)
+    }
+
+    /// Alias of `showDropDown`.
+    #[getter]
+    fn hide_drop_down(&self) -> bool {
+        self.inner.hide_dropdown
+    }
+
+    #[setter]
+    fn set_hide_drop_down(&mut self, py: Python<'_>, value: bool) -> PyResult<()> {
+        self.inner.hide_dropdown = value;
+        self.sync(py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "<DataValidation type={:?} sqref={:?}>",
+            self.inner.validation_type,
+            self.sqref()
+        )
+    }
+}
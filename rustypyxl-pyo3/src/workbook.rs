@@ -4,20 +4,29 @@
 
 use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyBytes, PyDict};
 use rustypyxl_core::{
-    Alignment, Border, BorderStyle, CellStyle, CellValue, CompressionLevel, Fill, Font, Protection,
-    Workbook,
+    Alignment, BlankRead, Border, BorderStyle, CellStyle, CellValue, Color, CompressionLevel,
+    DateValue, Fill, Font, NamedStyle, Protection, RichText, Workbook,
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::style::{PyAlignment, PyBorder, PyFont, PyPatternFill, PyProtection, PySide};
+use crate::blank::{blank_to_python, PyBlankPolicy};
+use crate::rich_text::{python_to_rich_text, rich_text_to_python};
+use crate::style::{
+    PyAlignment, PyBorder, PyFont, PyNamedStyle, PyPatternFill, PyProtection, PySide,
+};
 use crate::worksheet::PyWorksheet;
 
 /// An Excel Workbook (openpyxl-compatible API).
 #[pyclass(name = "Workbook")]
 pub struct PyWorkbook {
     pub(crate) inner: Workbook,
+    /// Whether `cell.value` of a rich-text cell is a CellRichText rather
+    /// than its plain text. Off for loaded files unless asked for, as in
+    /// openpyxl.
+    pub(crate) rich_text: bool,
 }
 
 #[pymethods]
@@ -27,6 +36,7 @@ impl PyWorkbook {
     fn new() -> Self {
         PyWorkbook {
             inner: Workbook::new(),
+            rich_text: true,
         }
     }
 
@@ -35,53 +45,33 @@ impl PyWorkbook {
     /// Args:
     ///     source: File path (str or os.PathLike), bytes, or file-like object
     ///             with .read() method
+    ///     rich_text: Return rich-text cell values as CellRichText rather
+    ///             than plain strings
+    ///     keep_vba: Keep the VBA project of a macro-enabled file so it is
+    ///             written back on save; dropped otherwise, as in openpyxl
     ///
     /// Returns:
     ///     Workbook: The loaded workbook
     #[staticmethod]
-    #[pyo3(signature = (source, password=None))]
-    pub fn load(source: &Bound<'_, PyAny>, password: Option<&str>) -> PyResult<Self> {
-        let py = source.py();
-
-        // A password opens an encrypted (or plain) workbook: resolve the source
-        // to bytes and decrypt as needed.
-        if let Some(pw) = password {
-            let bytes = read_source_bytes(source)?;
-            let inner = py
-                .allow_threads(|| Workbook::load_from_bytes_with_password(&bytes, pw))
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-            return Ok(PyWorkbook { inner });
-        }
-
-        // Check if source is bytes (before PathBuf, which str also satisfies)
-        if let Ok(bytes) = source.extract::<Vec<u8>>() {
-            let inner = py
-                .allow_threads(|| Workbook::load_from_bytes(&bytes))
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-            return Ok(PyWorkbook { inner });
-        }
-
-        // Check if source is a file path (str or os.PathLike, e.g. pathlib.Path)
-        if let Ok(path) = source.extract::<std::path::PathBuf>() {
-            let inner = py
-                .allow_threads(|| Workbook::load(&path.to_string_lossy()))
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-            return Ok(PyWorkbook { inner });
-        }
-
-        // Check if source has .read() method (file-like object)
-        if source.hasattr("read")? {
-            let bytes_obj = source.call_method0("read")?;
-            let bytes = bytes_obj.extract::<Vec<u8>>()?;
-            let inner = py
-                .allow_threads(|| Workbook::load_from_bytes(&bytes))
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-            return Ok(PyWorkbook { inner });
+    #[pyo3(signature = (source, password=None, rich_text=false, keep_vba=false))]
+    pub fn load(
+        source: &Bound<'_, PyAny>,
+        password: Option<&str>,
+        rich_text: bool,
+        keep_vba: bool,
+    ) -> PyResult<Self> {
+        let mut inner = Self::read(source, password)?;
+        if !keep_vba {
+            inner.remove_vba();
         }
+        Ok(PyWorkbook { inner, rich_text })
+    }
 
-        Err(PyTypeError::new_err(
-            "Expected file path (str or os.PathLike), bytes, or file-like object with .read() method"
-        ))
+    /// Whether the workbook carries a VBA project (loaded with
+    /// keep_vba=True). Such a workbook saves only to .xlsm or .xltm.
+    #[getter]
+    fn has_vba(&self) -> bool {
+        self.inner.has_vba()
     }
 
     /// Get the active worksheet (the active tab from the loaded file, or
@@ -92,10 +82,10 @@ impl PyWorkbook {
         if this.inner.worksheets.is_empty() {
             return Err(PyValueError::new_err("No worksheets in workbook"));
         }
-        let idx = this.inner.active_sheet.min(this.inner.worksheets.len() - 1);
+        let idx = this.inner.active_sheet_index();
         let title = this
             .inner
-            .sheet_names
+            .sheet_names()
             .get(idx)
             .cloned()
             .unwrap_or_else(|| "Sheet1".to_string());
@@ -104,6 +94,7 @@ impl PyWorkbook {
     }
 
     /// Set the active worksheet, by index or by worksheet, as openpyxl allows.
+    /// Raises ValueError for a hidden sheet, which Excel cannot open on.
     #[setter]
     fn set_active(&mut self, value: &Bound<'_, PyAny>) -> PyResult<()> {
         let index = if let Ok(ws) = value.extract::<PyRef<'_, PyWorksheet>>() {
@@ -117,22 +108,50 @@ impl PyWorkbook {
                 "active must be a worksheet or a sheet index",
             ));
         };
-
-        if index >= self.inner.worksheets.len() {
-            return Err(PyValueError::new_err(format!(
-                "Sheet index {} is out of range ({} sheets)",
-                index,
-                self.inner.worksheets.len()
-            )));
-        }
-        self.inner.active_sheet = index;
-        Ok(())
+        self.inner
+            .set_active_sheet(index)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
     /// Get all sheet names.
     #[getter]
     fn sheetnames(&self) -> Vec<String> {
-        self.inner.sheet_names.clone()
+        self.inner.sheet_names().to_vec()
+    }
+
+    /// The workbook's named-style palette: `wb.styles.from_json(text)`
+    /// loads a shared palette, `wb.styles.to_json()` exports it.
+    #[getter]
+    fn styles(self_: Py<Self>) -> PyStyleSheet {
+        PyStyleSheet { workbook: self_ }
+    }
+
+    /// The theme colors as "FFRRGGBB", indexed like `Color(theme=i)`:
+    /// lt1, dk1, lt2, dk2, accent1-6, hlink, folHlink.
+    #[getter]
+    fn theme_colors(&self) -> Vec<String> {
+        (0..12)
+            .filter_map(|i| Color::theme(i).to_rgb(&self.inner.theme))
+            .collect()
+    }
+
+    /// Names of the workbook's named cell styles, "Normal" first.
+    #[getter]
+    fn named_styles(&self) -> Vec<String> {
+        self.inner
+            .styles
+            .named_styles
+            .iter()
+            .map(|ns| ns.name.clone())
+            .collect()
+    }
+
+    /// Register a named cell style, so `cell.style = style.name` applies
+    /// it. Raises ValueError when the name is taken.
+    fn add_named_style(&mut self, style: PyRef<'_, PyNamedStyle>) -> PyResult<()> {
+        self.inner
+            .add_named_style(pynamedstyle_to_named_style(&style))
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
     /// Get all worksheets.
@@ -143,7 +162,7 @@ impl PyWorkbook {
             .map(|i| {
                 let title = this
                     .inner
-                    .sheet_names
+                    .sheet_names()
                     .get(i)
                     .cloned()
                     .unwrap_or_else(|| format!("Sheet{}", i + 1));
@@ -156,7 +175,7 @@ impl PyWorkbook {
     /// Get a worksheet by name using subscript notation: wb['Sheet1'].
     fn __getitem__(self_: Py<Self>, key: &str, py: Python<'_>) -> PyResult<PyWorksheet> {
         let this = self_.borrow(py);
-        for (idx, name) in this.inner.sheet_names.iter().enumerate() {
+        for (idx, name) in this.inner.sheet_names().iter().enumerate() {
             if name == key {
                 let uid = this.inner.worksheets[idx].uid;
                 return Ok(PyWorksheet::connected(
@@ -175,7 +194,7 @@ impl PyWorkbook {
 
     /// Check if a worksheet exists: 'Sheet1' in wb.
     fn __contains__(&self, key: &str) -> bool {
-        self.inner.sheet_names.contains(&key.to_string())
+        self.inner.sheet_position(key).is_some()
     }
 
     /// Get the number of worksheets.
@@ -186,7 +205,7 @@ impl PyWorkbook {
     /// Iterate over worksheet names.
     fn __iter__(&self) -> PyResult<PySheetNameIterator> {
         Ok(PySheetNameIterator {
-            names: self.inner.sheet_names.clone(),
+            names: self.inner.sheet_names().to_vec(),
             index: 0,
         })
     }
@@ -196,38 +215,46 @@ impl PyWorkbook {
     /// Args:
     ///     title: Optional worksheet title
     ///     index: Optional position to insert the worksheet
+    ///     storage: How the sheet stores its cells, "map" (default) or
+    ///         "columnar" for large rectangular data
     ///
     /// Returns:
     ///     Worksheet: The newly created worksheet
-    #[pyo3(signature = (title=None, index=None))]
+    #[pyo3(signature = (title=None, index=None, storage=None))]
     fn create_sheet(
         self_: Py<Self>,
         title: Option<String>,
         index: Option<usize>,
+        storage: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<PyWorksheet> {
+        let storage = storage.map(crate::worksheet::parse_storage).transpose()?;
         let final_idx;
         let sheet_title;
         let sheet_uid;
         {
             let mut this = self_.borrow_mut(py);
-            this.inner
+            let ws = this
+                .inner
                 .create_sheet(title)
                 .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            if let Some(storage) = storage {
+                ws.set_storage(storage);
+            }
 
             // The sheet was appended at the end; move it to `index` if requested.
             let last = this.inner.worksheets.len() - 1;
             final_idx = match index {
                 Some(i) if i < last => {
-                    let ws = this.inner.worksheets.remove(last);
-                    let name = this.inner.sheet_names.remove(last);
-                    this.inner.worksheets.insert(i, ws);
-                    this.inner.sheet_names.insert(i, name);
+                    let name = this.inner.sheet_names()[last].clone();
+                    this.inner
+                        .move_sheet(&name, i)
+                        .map_err(|e| PyValueError::new_err(e.to_string()))?;
                     i
                 }
                 _ => last,
             };
-            sheet_title = this.inner.sheet_names[final_idx].clone();
+            sheet_title = this.inner.sheet_names()[final_idx].clone();
             sheet_uid = this.inner.worksheets[final_idx].uid;
         }
         Ok(PyWorksheet::connected(
@@ -237,18 +264,71 @@ impl PyWorkbook {
         ))
     }
 
+    /// Add a table-of-contents sheet as the first tab.
+    ///
+    /// Each sheet gets a row whose name links to its A1, with its row count
+    /// and an optional description. A sheet already called `title` is
+    /// replaced, so calling this again refreshes the index.
+    ///
+    /// Args:
+    ///     title: Title of the index sheet
+    ///     descriptions: Optional text per sheet name for a Description column
+    ///     row_counts: Whether to add a Rows column
+    ///     include_hidden: Whether to list hidden sheets
+    ///
+    /// Returns:
+    ///     Worksheet: The index sheet
+    #[pyo3(signature = (title="Contents", descriptions=None, row_counts=true, include_hidden=false))]
+    fn build_index_sheet(
+        self_: Py<Self>,
+        title: &str,
+        descriptions: Option<HashMap<String, String>>,
+        row_counts: bool,
+        include_hidden: bool,
+        py: Python<'_>,
+    ) -> PyResult<PyWorksheet> {
+        let mut options = rustypyxl_core::IndexSheetOptions::new()
+            .with_title(title)
+            .with_row_counts(row_counts)
+            .with_hidden(include_hidden);
+        options.descriptions = descriptions.unwrap_or_default();
+        let sheet_uid = self_
+            .borrow_mut(py)
+            .inner
+            .build_index_sheet(&options)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .uid;
+        Ok(PyWorksheet::connected(
+            self_.clone_ref(py),
+            sheet_uid,
+            title.to_string(),
+        ))
+    }
+
     /// Remove a worksheet.
     ///
     /// Args:
     ///     worksheet: The worksheet to remove (by name or PyWorksheet)
     fn remove(&mut self, worksheet: &PyWorksheet) -> PyResult<()> {
         let idx = worksheet.resolve_index(self)?;
-        let name = self.inner.sheet_names[idx].clone();
+        let name = self.inner.sheet_names()[idx].clone();
         self.inner
             .remove_sheet(&name)
             .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
+    /// A new workbook with this one's sheets, styles, column widths,
+    /// validations and named ranges, keeping only the first `header_rows`
+    /// rows of each sheet. Handy for turning last period's file into next
+    /// period's template.
+    #[pyo3(signature = (header_rows=1))]
+    fn clone_structure(&self, header_rows: u32) -> PyWorkbook {
+        PyWorkbook {
+            inner: self.inner.clone_structure(header_rows),
+            rich_text: self.rich_text,
+        }
+    }
+
     /// Copy a worksheet.
     ///
     /// Args:
@@ -282,14 +362,15 @@ impl PyWorkbook {
             let mut counter = 1;
             new_name = base_name.clone();
             let mut temp_name = new_name.clone();
-            while this.inner.sheet_names.contains(&temp_name) {
+            while this.inner.sheet_position(&temp_name).is_some() {
                 temp_name = format!("{} {}", base_name, counter);
                 counter += 1;
             }
             new_ws.set_title(&temp_name);
 
-            this.inner.worksheets.push(new_ws);
-            this.inner.sheet_names.push(temp_name.clone());
+            this.inner
+                .push_sheet(temp_name.clone(), new_ws)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
 
             idx = this.inner.worksheets.len() - 1;
             // Re-assign for return
@@ -299,7 +380,7 @@ impl PyWorkbook {
         let this = self_.borrow(py);
         let sheet_title = this
             .inner
-            .sheet_names
+            .sheet_names()
             .get(idx)
             .cloned()
             .unwrap_or_else(|| format!("Sheet{}", idx + 1));
@@ -310,21 +391,54 @@ impl PyWorkbook {
         ))
     }
 
-    /// Move a worksheet within the workbook.
-    fn move_sheet(&mut self, sheet: &PyWorksheet, offset: i32) -> PyResult<()> {
-        let current_idx = sheet.resolve_index(self)?;
+    /// Move a worksheet (or the sheet with that title) within the workbook.
+    ///
+    /// `offset` moves it relative to its current position, as in openpyxl;
+    /// `index` places it at an absolute position instead. Either way the
+    /// position is clamped to the ends of the tab order.
+    #[pyo3(signature = (sheet, offset=0, index=None))]
+    fn move_sheet(
+        &mut self,
+        sheet: &Bound<'_, PyAny>,
+        offset: i64,
+        index: Option<usize>,
+    ) -> PyResult<()> {
+        let current_idx = if let Ok(ws) = sheet.extract::<PyRef<'_, PyWorksheet>>() {
+            ws.resolve_index(self)?
+        } else {
+            let name: String = sheet.extract()?;
+            self.inner.sheet_position(&name).ok_or_else(|| {
+                pyo3::exceptions::PyKeyError::new_err(format!("Worksheet {} does not exist.", name))
+            })?
+        };
+        let new_idx = match index {
+            Some(_) if offset != 0 => {
+                return Err(PyValueError::new_err(
+                    "pass either offset or index, not both",
+                ))
+            }
+            Some(i) => i,
+            None => (current_idx as i64 + offset).max(0) as usize,
+        };
 
-        let new_idx = (current_idx as i32 + offset).max(0) as usize;
-        let new_idx = new_idx.min(self.inner.worksheets.len() - 1);
+        let name = self.inner.sheet_names()[current_idx].clone();
+        self.inner
+            .move_sheet(&name, new_idx)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
 
-        if current_idx != new_idx {
-            let ws = self.inner.worksheets.remove(current_idx);
-            let name = self.inner.sheet_names.remove(current_idx);
-            self.inner.worksheets.insert(new_idx, ws);
-            self.inner.sheet_names.insert(new_idx, name);
-        }
+    /// Sheet titles in tab order. Assign a reordering of the same titles to
+    /// rearrange the tabs; open Worksheet handles keep pointing at their sheets.
+    #[getter]
+    fn sheet_order(&self) -> Vec<String> {
+        self.inner.sheet_names().to_vec()
+    }
 
-        Ok(())
+    #[setter]
+    fn set_sheet_order(&mut self, order: Vec<String>) -> PyResult<()> {
+        self.inner
+            .set_sheet_order(&order)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
     /// Get the index of a worksheet.
@@ -340,13 +454,30 @@ impl PyWorkbook {
         range: String,
     ) -> PyResult<()> {
         let idx = worksheet.resolve_index(self)?;
-        let ws_title = self.inner.sheet_names[idx].clone();
+        let ws_title = self.inner.sheet_names()[idx].clone();
         let full_range = format!("'{}'!{}", ws_title, range);
         self.inner
             .create_named_range(name, full_range)
             .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
+    /// Resolve a defined name to (sheet, min_row, min_col, max_row, max_col).
+    /// With `sheet`, a name scoped to that sheet wins over a global one, as
+    /// in a formula on it. Raises ValueError for an unknown name or one that
+    /// is not a single range.
+    #[pyo3(signature = (name, sheet=None))]
+    fn resolve_named_range(
+        &self,
+        name: &str,
+        sheet: Option<&str>,
+    ) -> PyResult<(String, u32, u32, u32, u32)> {
+        match sheet {
+            Some(sheet) => self.inner.resolve_named_range_on(sheet, name),
+            None => self.inner.resolve_named_range(name),
+        }
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
     /// Get all defined names (named ranges).
     #[getter]
     fn defined_names(&self) -> Vec<(String, String)> {
@@ -357,24 +488,314 @@ impl PyWorkbook {
             .collect()
     }
 
+    /// Save an extra package part with the workbook, for features the
+    /// library doesn't model.
+    ///
+    /// Args:
+    ///     path: Package path, e.g. "customXml/item1.xml"
+    ///     data: Part content (bytes, or str written as UTF-8)
+    ///     content_type: Content type listed in [Content_Types].xml; may only
+    ///         be omitted for .rels parts
+    ///     rel_type: When given, a workbook relationship of this type is
+    ///         added pointing at the part
+    #[pyo3(signature = (path, data, content_type=None, rel_type=None))]
+    fn add_custom_part(
+        &mut self,
+        path: &str,
+        data: &Bound<'_, PyAny>,
+        content_type: Option<&str>,
+        rel_type: Option<&str>,
+    ) -> PyResult<()> {
+        let bytes = match data.extract::<String>() {
+            Ok(text) => text.into_bytes(),
+            Err(_) => data.extract::<Vec<u8>>()?,
+        };
+        self.inner
+            .add_custom_part(path, bytes, content_type, rel_type)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Evaluate the conditional formatting on a sheet against its current
+    /// values, so exports can show the highlighting Excel would.
+    ///
+    /// Returns:
+    ///     List of dicts, in row order, for the cells the rules change:
+    ///     row, column, priorities (of the rules that apply), the merged
+    ///     format as fill, font_color (ARGB hex or None), bold, italic,
+    ///     underline and strikethrough (None when not set), scale_color (a
+    ///     color scale's fill), data_bar (bar length from 0.0 to 1.0) and
+    ///     icon (an (icon set, index) pair such as ("3Arrows", 2)).
+    fn conditional_format_results(&self, sheet_name: &str, py: Python<'_>) -> PyResult<PyObject> {
+        use pyo3::types::{PyDict, PyList};
+
+        let results = self
+            .inner
+            .conditional_format_results(sheet_name)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let list = PyList::empty(py);
+        for result in results {
+            let d = PyDict::new(py);
+            d.set_item("row", result.row)?;
+            d.set_item("column", result.column)?;
+            d.set_item("priorities", &result.priorities)?;
+            let format = result.format.unwrap_or_default();
+            d.set_item("fill", format.fill_color.and_then(|c| c.rgb))?;
+            d.set_item("font_color", format.font_color.and_then(|c| c.rgb))?;
+            d.set_item("bold", format.bold)?;
+            d.set_item("italic", format.italic)?;
+            d.set_item("underline", format.underline)?;
+            d.set_item("strikethrough", format.strikethrough)?;
+            d.set_item("scale_color", result.scale_color.and_then(|c| c.rgb))?;
+            d.set_item("data_bar", result.data_bar)?;
+            d.set_item(
+                "icon",
+                result.icon.map(|(style, icon)| (style.xml_type(), icon)),
+            )?;
+            list.append(d)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    /// Find the cells whose displayed text contains `query`, ignoring case.
+    ///
+    /// Sheets are scanned in parallel. Matches are ranked whole-cell first,
+    /// then at the start of the text, at the start of a word, and anywhere;
+    /// equally good matches keep tab, row and column order.
+    ///
+    /// Args:
+    ///     query: Text to look for
+    ///     sheets: Optional sheet names to search (default: all)
+    ///     in_formulas: Also search formula text
+    ///     limit: Keep at most this many matches (None for all)
+    ///
+    /// Returns:
+    ///     List of dicts: sheet, row, column, coordinate, snippet (the text
+    ///     around the match), kind ("exact", "prefix", "word" or "contains")
+    ///     and in_formula.
+    #[pyo3(signature = (query, sheets=None, in_formulas=false, limit=Some(100)))]
+    fn search(
+        &self,
+        query: &str,
+        sheets: Option<Vec<String>>,
+        in_formulas: bool,
+        limit: Option<usize>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::{PyDict, PyList};
+
+        let options = rustypyxl_core::SearchOptions {
+            sheets,
+            in_formulas,
+            limit,
+        };
+        let found = py
+            .allow_threads(|| self.inner.search(query, &options))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let list = PyList::empty(py);
+        for m in found {
+            let d = PyDict::new(py);
+            d.set_item("sheet", m.sheet)?;
+            d.set_item("row", m.row)?;
+            d.set_item("column", m.column)?;
+            d.set_item("coordinate", m.coordinate)?;
+            d.set_item("snippet", m.snippet)?;
+            d.set_item("kind", m.kind.as_str())?;
+            d.set_item("in_formula", m.in_formula)?;
+            list.append(d)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    /// List references broken by structural edits: formulas, data
+    /// validations, conditional formats, chart series and defined names
+    /// whose text holds #REF! or names a sheet that no longer exists.
+    ///
+    /// Returns:
+    ///     List of dicts with kind ("formula", "data_validation",
+    ///     "conditional_format", "chart" or "defined_name"), sheet (None for
+    ///     a global name), location, text, problem (a readable description)
+    ///     and missing_sheet (the sheet named, or None for #REF!). Empty
+    ///     when nothing is broken.
+    fn check_references(&self, py: Python<'_>) -> PyResult<PyObject> {
+        use pyo3::types::{PyDict, PyList};
+        use rustypyxl_core::ReferenceProblem;
+
+        let list = PyList::empty(py);
+        for broken in self.inner.check_references() {
+            let d = PyDict::new(py);
+            d.set_item("kind", broken.owner.as_str())?;
+            d.set_item("sheet", &broken.sheet)?;
+            d.set_item("location", &broken.location)?;
+            d.set_item("text", &broken.text)?;
+            d.set_item("problem", broken.problem.to_string())?;
+            let missing = match &broken.problem {
+                ReferenceProblem::MissingSheet(name) => Some(name.as_str()),
+                ReferenceProblem::RefError => None,
+            };
+            d.set_item("missing_sheet", missing)?;
+            list.append(d)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    /// List everything over Excel's limits, which make it refuse or
+    /// "repair" the saved file: sheet names over 31 characters, strings over
+    /// 32,767 characters, formulas over 8,192, more than 64,000 cell formats,
+    /// more than 65,530 hyperlinks or merged ranges on a sheet, and cells
+    /// past row 1,048,576 or column XFD. Each entry is a dict with "kind",
+    /// "sheet", "location" (the cell, for lengths), "actual", "limit" and a
+    /// readable "message"; an empty list means nothing is over.
+    fn check_limits(&self, py: Python<'_>) -> PyResult<PyObject> {
+        use pyo3::types::{PyDict, PyList};
+
+        let list = PyList::empty(py);
+        for violation in self.inner.check_limits() {
+            let d = PyDict::new(py);
+            d.set_item("kind", violation.kind.as_str())?;
+            d.set_item("sheet", &violation.sheet)?;
+            d.set_item("location", &violation.location)?;
+            d.set_item("actual", violation.actual)?;
+            d.set_item("limit", violation.limit())?;
+            d.set_item("message", violation.to_string())?;
+            list.append(d)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    /// Protect the workbook structure (adding, removing, renaming and
+    /// moving sheets), optionally with a password. Passwords are stored
+    /// only as SHA-512 hashes.
+    ///
+    /// Keyword arguments follow openpyxl's `WorkbookProtection`:
+    /// `lockStructure` (default True), `lockWindows`, `lockRevision`, and
+    /// `revisionsPassword` for lifting revision locking.
+    #[pyo3(signature = (password=None, lockStructure=true, lockWindows=false, lockRevision=false, revisionsPassword=None))]
+    #[allow(non_snake_case)]
+    fn protect_workbook(
+        &mut self,
+        password: Option<&str>,
+        lockStructure: bool,
+        lockWindows: bool,
+        lockRevision: bool,
+        revisionsPassword: Option<&str>,
+    ) {
+        let mut protection = rustypyxl_core::WorkbookProtection::new()
+            .with_lock_windows(lockWindows)
+            .with_lock_revision(lockRevision);
+        protection.lock_structure = lockStructure;
+        if let Some(password) = password {
+            protection = protection.with_password(password);
+        }
+        if let Some(password) = revisionsPassword {
+            protection = protection.with_revisions_password(password);
+        }
+        self.inner.protection = Some(protection);
+    }
+
+    /// Remove workbook protection.
+    fn unprotect_workbook(&mut self) {
+        self.inner.unprotect_workbook();
+    }
+
+    /// Whether the workbook structure or windows are protected.
+    #[getter]
+    fn workbook_protected(&self) -> bool {
+        self.inner.protection.is_some()
+    }
+
+    /// Whether `password` lifts the workbook protection (True when there
+    /// is no protection or no password).
+    fn check_workbook_password(&self, password: &str) -> bool {
+        self.inner
+            .protection
+            .as_ref()
+            .is_none_or(|p| p.verify_password(password))
+    }
+
     /// Save the workbook to a file.
     ///
     /// Args:
     ///     filename: Path to save the Excel file (str or os.PathLike)
     ///     password: Encrypt the file with this password (agile encryption)
-    #[pyo3(signature = (filename, password=None))]
+    ///     on_locked: What to do when the file is open in another program
+    ///         (Excel on Windows): "fail" raises PermissionError, "retry"
+    ///         waits and tries again with backoff, "alternate" saves as
+    ///         "name (1).xlsx" and so on
+    ///     retries: How many times "retry" tries again
+    ///     verify: Read the written file back, parse every part strictly and
+    ///         compare sheets, used ranges and a sample of values with the
+    ///         workbook; raises ValueError on a difference. The file is left
+    ///         on disk either way
+    ///
+    /// Returns:
+    ///     str: The path written, which differs from filename only when
+    ///     on_locked="alternate" had to pick another name
+    #[pyo3(signature = (filename, password=None, on_locked="fail", retries=5, verify=false))]
     fn save(
         &self,
         filename: std::path::PathBuf,
         password: Option<&str>,
+        on_locked: &str,
+        retries: u32,
+        verify: bool,
+        py: Python<'_>,
+    ) -> PyResult<String> {
+        use rustypyxl_core::file_lock::{create_with_policy, LockedFilePolicy};
+        let policy = match on_locked {
+            "fail" => LockedFilePolicy::Fail,
+            "retry" => LockedFilePolicy::retry(retries),
+            "alternate" => LockedFilePolicy::SaveAlternate,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "on_locked must be 'fail', 'retry' or 'alternate', not {other:?}"
+                )))
+            }
+        };
+        let written = py
+            .allow_threads(|| match password {
+                Some(pw) => {
+                    let plain = self.inner.save_to_bytes_for(&filename.to_string_lossy())?;
+                    let bytes = rustypyxl_core::crypto::encrypt(&plain, pw)?;
+                    let (mut file, written) = create_with_policy(&filename, &policy)?;
+                    std::io::Write::write_all(&mut file, &bytes)?;
+                    Ok(written)
+                }
+                None => self
+                    .inner
+                    .save_with_lock_policy(&filename.to_string_lossy(), &policy),
+            })
+            .map_err(save_error)?;
+        if verify {
+            py.allow_threads(|| {
+                let data = std::fs::read(&written)?;
+                let data = match password {
+                    Some(pw) => rustypyxl_core::crypto::decrypt(&data, pw)?,
+                    None => data,
+                };
+                self.inner.verify_saved(&data)
+            })
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        }
+        Ok(written.to_string_lossy().into_owned())
+    }
+
+    /// Save the workbook encrypted with a password (agile encryption), as
+    /// save(filename, password=password) does.
+    ///
+    /// Args:
+    ///     filename: Path to save the Excel file (str or os.PathLike)
+    ///     password: The password needed to open the file
+    fn save_encrypted(
+        &self,
+        filename: std::path::PathBuf,
+        password: &str,
         py: Python<'_>,
     ) -> PyResult<()> {
-        let path = filename.to_string_lossy();
-        py.allow_threads(|| match password {
-            Some(pw) => self.inner.save_with_password(&path, pw),
-            None => self.inner.save(&path),
+        py.allow_threads(|| {
+            self.inner
+                .save_encrypted(&filename.to_string_lossy(), password)
         })
-        .map_err(|e| PyValueError::new_err(e.to_string()))
+        .map_err(save_error)
     }
 
     /// Save the workbook to bytes.
@@ -399,6 +820,71 @@ impl PyWorkbook {
         Ok(PyBytes::new(py, &bytes))
     }
 
+    /// Export the workbook to PDF.
+    ///
+    /// The workbook is saved to a temporary xlsx, page setup and print
+    /// areas included, and converted by an external renderer: LibreOffice
+    /// (`soffice --headless`) by default.
+    ///
+    /// Args:
+    ///     path: Where to write the PDF (str or os.PathLike)
+    ///     soffice: Path to the LibreOffice executable, if not on PATH
+    ///     command: A command to run instead, as a list of arguments;
+    ///         "{input}" and "{output}" in any argument are replaced with
+    ///         the xlsx and PDF paths
+    ///     renderer: A callable renderer(input_path, output_path) that
+    ///         writes the PDF itself
+    #[pyo3(signature = (path, soffice=None, command=None, renderer=None))]
+    fn export_pdf(
+        &self,
+        path: std::path::PathBuf,
+        soffice: Option<std::path::PathBuf>,
+        command: Option<Vec<String>>,
+        renderer: Option<Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        use rustypyxl_core::render::{CommandRenderer, LibreOfficeRenderer};
+        let path = path.to_string_lossy().into_owned();
+        let to_py = |e: rustypyxl_core::RustypyxlError| PyValueError::new_err(e.to_string());
+        if let Some(callable) = renderer {
+            if soffice.is_some() || command.is_some() {
+                return Err(PyValueError::new_err(
+                    "pass only one of soffice, command and renderer",
+                ));
+            }
+            let renderer = PyRenderer {
+                callable,
+                error: std::cell::RefCell::new(None),
+            };
+            let result = self.inner.export_pdf_with(&path, &renderer);
+            if let Some(err) = renderer.error.into_inner() {
+                return Err(err);
+            }
+            return result.map_err(to_py);
+        }
+        match (soffice, command) {
+            (Some(_), Some(_)) => Err(PyValueError::new_err(
+                "pass only one of soffice, command and renderer",
+            )),
+            (_, Some(command)) => {
+                let Some((program, args)) = command.split_first() else {
+                    return Err(PyValueError::new_err("command must not be empty"));
+                };
+                let renderer = CommandRenderer::new(program, args.to_vec());
+                py.allow_threads(|| self.inner.export_pdf_with(&path, &renderer))
+                    .map_err(to_py)
+            }
+            (soffice, None) => {
+                let mut renderer = LibreOfficeRenderer::new();
+                if let Some(program) = soffice {
+                    renderer = renderer.with_program(program);
+                }
+                py.allow_threads(|| self.inner.export_pdf_with(&path, &renderer))
+                    .map_err(to_py)
+            }
+        }
+    }
+
     /// Set compression level for saving.
     ///
     /// Args:
@@ -418,28 +904,149 @@ impl PyWorkbook {
         Ok(())
     }
 
-    /// Close the workbook (no-op for compatibility).
-    fn close(&self) {
-        // No-op - we don't hold file handles open
+    /// Set how NaN and infinite numbers are saved, since the file format
+    /// cannot hold them.
+    ///
+    /// Args:
+    ///     policy: "num" (default) writes a #NUM! error cell, "empty" leaves
+    ///             the cell without a value, "error" makes save raise
+    ///             ValueError naming the cell
+    fn set_non_finite_numbers(&mut self, policy: &str) -> PyResult<()> {
+        self.inner.non_finite_numbers = non_finite_policy(policy)?;
+        Ok(())
     }
 
-    /// Set a cell value in a specific sheet.
+    /// Set how numbers are written on save.
     ///
-    /// This is the primary method for setting cell values.
+    /// Args:
+    ///     precision: "shortest" (default) keeps every digit needed to read
+    ///                back the exact same float; "excel" rounds to 15
+    ///                significant digits first, as Excel does, so 0.1 + 0.2
+    ///                is saved as 0.3
+    fn set_number_precision(&mut self, precision: &str) -> PyResult<()> {
+        self.inner.number_precision = number_precision(precision)?;
+        Ok(())
+    }
+
+    /// Set which strings go in the shared string table on save; the rest
+    /// are written inline in their cells.
     ///
     /// Args:
-    ///     sheet_name: Name of the worksheet
-    ///     row: Row number (1-indexed)
-    ///     column: Column number (1-indexed)
-    ///     value: Value to set (string, number, boolean, or None)
-    pub fn set_cell_value(
-        self_: Py<Self>,
-        py: Python<'_>,
-        sheet_name: &str,
-        row: u32,
-        column: u32,
-        value: &Bound<'_, PyAny>,
+    ///     mode: "all" (default) shares every string, as Excel does; an int
+    ///           n shares only strings used at least n times (2 inlines the
+    ///           unique ones); "auto" picks from a sample of the strings,
+    ///           inlining everything when nearly all values are unique;
+    ///           "incremental" shares every string from counts kept as
+    ///           cells are set, so saving skips the pass over the cells;
+    ///           "inline" writes every string inline with no table
+    fn set_shared_strings(&mut self, mode: &Bound<'_, PyAny>) -> PyResult<()> {
+        use rustypyxl_core::SharedStrings;
+        let mode = if let Ok(n) = mode.extract::<usize>() {
+            if n == 0 {
+                return Err(PyValueError::new_err("minimum repeats must be at least 1"));
+            }
+            SharedStrings::MinRepeats(n)
+        } else {
+            match mode.extract::<String>()?.to_lowercase().as_str() {
+                    "all" => SharedStrings::All,
+                    "auto" => SharedStrings::Auto,
+                    "incremental" => SharedStrings::Incremental,
+                    "inline" => SharedStrings::Inline,
+                    _ => return Err(PyValueError::new_err(
+                        "Invalid shared strings mode. Use: 'all', 'auto', 'incremental', 'inline' or a minimum repeat count",
+                    )),
+                }
+        };
+        self.inner.set_shared_strings(mode);
+        Ok(())
+    }
+
+    /// Write runs of formulas filled down a column as shared formula groups
+    /// on save (`<f t="shared">`), which makes large files smaller and
+    /// faster for Excel to open. Off by default.
+    fn set_share_formulas(&mut self, share: bool) {
+        self.inner.set_share_formulas(share);
+    }
+
+    /// Language formulas are typed in ("de", "fr", "es", "it", "nl"; a
+    /// region suffix like "de-AT" is ignored), or None for en-US. While set,
+    /// formulas assigned to cells are converted to the en-US form the file
+    /// stores: `ws["A1"] = "=SUMME(B1;B2)"` stores `=SUM(B1,B2)`.
+    #[getter]
+    fn formula_locale(&self) -> Option<String> {
+        self.inner
+            .formula_locale
+            .as_ref()
+            .map(|locale| locale.code.clone())
+    }
+
+    #[setter]
+    fn set_formula_locale(&mut self, code: Option<&str>) -> PyResult<()> {
+        let locale = match code {
+            Some(code) => Some(rustypyxl_core::FormulaLocale::from_code(code).ok_or_else(
+                || {
+                    PyValueError::new_err(format!(
+                        "Unsupported formula locale '{}': expected de, fr, es, it or nl",
+                        code
+                    ))
+                },
+            )?),
+            None => None,
+        };
+        self.inner.set_formula_locale(locale);
+        Ok(())
+    }
+
+    /// Author, title and timestamps written to docProps:
+    /// `wb.properties.creator = "Finance"`. See DocumentProperties.
+    #[getter]
+    fn properties(slf: Bound<'_, Self>) -> crate::docprops::PyDocumentProperties {
+        crate::docprops::PyDocumentProperties {
+            owner: crate::docprops::PropertiesOwner::Workbook(slf.unbind()),
+        }
+    }
+
+    /// How blank cells read back (None, "" or skipped in iteration) and
+    /// whether assigning None clears a cell. See BlankPolicy.
+    #[getter]
+    fn blank_policy(&self) -> PyBlankPolicy {
+        PyBlankPolicy {
+            inner: self.inner.blank_policy,
+        }
+    }
+
+    #[setter]
+    fn set_blank_policy(&mut self, policy: PyRef<'_, PyBlankPolicy>) {
+        self.inner.set_blank_policy(policy.inner);
+    }
+
+    /// Close the workbook (no-op for compatibility).
+    fn close(&self) {
+        // No-op - we don't hold file handles open
+    }
+
+    /// Set a cell value in a specific sheet.
+    ///
+    /// This is the primary method for setting cell values.
+    ///
+    /// Args:
+    ///     sheet_name: Name of the worksheet
+    ///     row: Row number (1-indexed)
+    ///     column: Column number (1-indexed)
+    ///     value: Value to set (string, number, boolean, or None)
+    pub fn set_cell_value(
+        self_: Py<Self>,
+        py: Python<'_>,
+        sheet_name: &str,
+        row: u32,
+        column: u32,
+        value: &Bound<'_, PyAny>,
     ) -> PyResult<()> {
+        if let Some(rich) = python_to_rich_text(value)? {
+            return self_
+                .borrow_mut(py)
+                .set_rich_text(sheet_name, row, column, rich);
+        }
         // Convert before borrowing: see write_rows
         let cell_value = python_to_cell_value(value)?;
         self_
@@ -468,10 +1075,14 @@ impl PyWorkbook {
             .get_sheet_by_name(sheet_name)
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
 
-        if let Some(cell) = ws.get_cell(row, column) {
-            Ok(cell_value_to_python(&cell.value, py))
-        } else {
-            Ok(py.None())
+        match ws.get_cell(row, column) {
+            Some(cell) if !matches!(cell.value, CellValue::Empty) => {
+                if let (true, Some(rich)) = (self.rich_text, &cell.rich_text) {
+                    return rich_text_to_python(rich, py);
+                }
+                Ok(cell_value_to_python(&cell.value, py))
+            }
+            _ => Ok(blank_to_python(&self.inner.blank_policy, py)),
         }
     }
 
@@ -514,6 +1125,193 @@ impl PyWorkbook {
         self.inner.calculate_all()
     }
 
+    /// Export every worksheet to a directory as one CSV file per sheet, plus
+    /// an index.json manifest. Rows are streamed to disk sheet by sheet.
+    ///
+    /// Args:
+    ///     directory: Output directory (created if missing)
+    ///
+    /// Returns:
+    ///     List of dicts with keys sheet, file, rows, columns (the manifest
+    ///     contents)
+    fn export_all_to_csv(
+        &self,
+        directory: std::path::PathBuf,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::{PyDict, PyList};
+
+        let exports = py
+            .allow_threads(|| self.inner.export_all_to_csv(&directory))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let list = PyList::empty(py);
+        for export in exports {
+            let d = PyDict::new(py);
+            d.set_item("sheet", export.sheet)?;
+            d.set_item("file", export.file_name)?;
+            d.set_item("rows", export.rows)?;
+            d.set_item("columns", export.columns)?;
+            list.append(d)?;
+        }
+        Ok(list.into_any().unbind())
+    }
+
+    /// Import a CSV file into a worksheet, one record at a time.
+    ///
+    /// Args:
+    ///     sheet_name: Name of the worksheet to insert into
+    ///     path: Path to the CSV file
+    ///     start_row: Starting row (1-indexed, default 1)
+    ///     start_col: Starting column (1-indexed, default 1)
+    ///     has_headers: The first record holds the column names (default True)
+    ///     delimiter: Field delimiter (default ",")
+    ///     quotechar: Quote character (default '"'), or None to disable quoting
+    ///     encoding: "utf-8" (default), "latin-1" or "cp1252"
+    ///     infer_types: Turn numbers, TRUE/FALSE and ISO dates into typed
+    ///                  cells (default True); otherwise every field is text
+    ///     date_formats: Extra strftime formats to try for dates
+    ///     column_renames: Dict mapping original column names to new names
+    ///     columns: List of column names to import (None = all columns)
+    ///     style: ImportStyle for the imported block, True for the default
+    ///            presentable styling, or None (default) to leave it unstyled
+    ///
+    /// Returns:
+    ///     Dict with import results: rows_imported, columns_imported,
+    ///     range (e.g. "A1:Z1000"), header_range, data_range, column_names
+    #[pyo3(signature = (sheet_name, path, start_row=1, start_col=1, has_headers=true, delimiter=",", quotechar=Some("\""), encoding="utf-8", infer_types=true, date_formats=None, column_renames=None, columns=None, style=None))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
+    fn insert_from_csv(
+        &mut self,
+        sheet_name: &str,
+        path: std::path::PathBuf,
+        start_row: u32,
+        start_col: u32,
+        has_headers: bool,
+        delimiter: &str,
+        quotechar: Option<&str>,
+        encoding: &str,
+        infer_types: bool,
+        date_formats: Option<Vec<String>>,
+        column_renames: Option<std::collections::HashMap<String, String>>,
+        columns: Option<Vec<String>>,
+        style: Option<&Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+        use rustypyxl_core::CsvImportOptions;
+
+        let mut opts = CsvImportOptions::new()
+            .with_headers(has_headers)
+            .with_delimiter(csv_char("delimiter", delimiter)?)
+            .with_quote(quotechar.map(|q| csv_char("quotechar", q)).transpose()?)
+            .with_encoding(csv_encoding(encoding)?)
+            .with_type_inference(infer_types);
+        opts.style = crate::style::extract_import_style(style)?;
+        if let Some(formats) = date_formats {
+            opts.date_formats = formats;
+        }
+        if let Some(renames) = column_renames {
+            opts.column_renames = renames;
+        }
+        if let Some(cols) = columns {
+            opts.columns = cols;
+        }
+
+        let inner = &mut self.inner;
+        let result = py
+            .allow_threads(|| {
+                inner.insert_from_csv(sheet_name, &path, start_row, start_col, Some(opts))
+            })
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("rows_imported", result.rows_imported)?;
+        dict.set_item("columns_imported", result.columns_imported)?;
+        dict.set_item("start_row", result.start_row)?;
+        dict.set_item("start_col", result.start_col)?;
+        dict.set_item("end_row", result.end_row)?;
+        dict.set_item("end_col", result.end_col)?;
+        dict.set_item("range", result.range_with_headers())?;
+        dict.set_item("header_range", result.header_range())?;
+        dict.set_item("data_range", result.data_range())?;
+        dict.set_item("column_names", result.column_names)?;
+
+        Ok(dict.into())
+    }
+
+    /// Import a JSON file into a worksheet. The file holds either an array of
+    /// objects or one object per line (NDJSON); each object becomes a row and
+    /// each key a column, in the order keys are first seen.
+    ///
+    /// Args:
+    ///     sheet_name: Name of the worksheet to insert into
+    ///     path: Path to the JSON or NDJSON file
+    ///     start_row: Starting row (1-indexed, default 1)
+    ///     start_col: Starting column (1-indexed, default 1)
+    ///     has_headers: Write the keys as a header row (default True)
+    ///     columns: List of keys to import (None = all keys)
+    ///     column_renames: Dict mapping keys to new column names
+    ///     parse_dates: Turn ISO 8601 date strings into dates (default True)
+    ///     style: ImportStyle for the imported block, True for the default
+    ///            presentable styling, or None (default) to leave it unstyled
+    ///
+    /// Returns:
+    ///     Dict with import results: rows_imported, columns_imported,
+    ///     range (e.g. "A1:Z1000"), header_range, data_range, column_names
+    #[pyo3(signature = (sheet_name, path, start_row=1, start_col=1, has_headers=true, columns=None, column_renames=None, parse_dates=true, style=None))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
+    fn insert_from_json(
+        &mut self,
+        sheet_name: &str,
+        path: std::path::PathBuf,
+        start_row: u32,
+        start_col: u32,
+        has_headers: bool,
+        columns: Option<Vec<String>>,
+        column_renames: Option<std::collections::HashMap<String, String>>,
+        parse_dates: bool,
+        style: Option<&Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+        use rustypyxl_core::JsonImportOptions;
+
+        let mut opts = JsonImportOptions::new()
+            .with_headers(has_headers)
+            .with_date_parsing(parse_dates);
+        opts.style = crate::style::extract_import_style(style)?;
+        if let Some(renames) = column_renames {
+            opts.column_renames = renames;
+        }
+        if let Some(cols) = columns {
+            opts.columns = cols;
+        }
+
+        let inner = &mut self.inner;
+        let result = py
+            .allow_threads(|| {
+                inner.insert_from_json(sheet_name, &path, start_row, start_col, Some(opts))
+            })
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("rows_imported", result.rows_imported)?;
+        dict.set_item("columns_imported", result.columns_imported)?;
+        dict.set_item("start_row", result.start_row)?;
+        dict.set_item("start_col", result.start_col)?;
+        dict.set_item("end_row", result.end_row)?;
+        dict.set_item("end_col", result.end_col)?;
+        dict.set_item("range", result.range_with_headers())?;
+        dict.set_item("header_range", result.header_range())?;
+        dict.set_item("data_range", result.data_range())?;
+        dict.set_item("column_names", result.column_names)?;
+
+        Ok(dict.into())
+    }
+
     /// Create a pivot table from a source range and add it to a target sheet.
     ///
     /// `source_ref` is a range like "A1:C100" whose first row holds the field
@@ -626,6 +1424,7 @@ impl PyWorkbook {
             .collect::<PyResult<_>>()?;
 
         let mut this = self_.borrow_mut(py);
+        let policy = this.inner.blank_policy;
         // Get mutable reference to worksheet once (avoid repeated lookups)
         let ws = this
             .inner
@@ -635,7 +1434,9 @@ impl PyWorkbook {
         for (row_idx, row_data) in rows.into_iter().enumerate() {
             let row = start_row + row_idx as u32;
             for (col_idx, cell_value) in row_data.into_iter().enumerate() {
-                ws.set_cell_value(row, start_col + col_idx as u32, cell_value);
+                if !policy.ignores_write(&cell_value) {
+                    ws.set_cell_value(row, start_col + col_idx as u32, cell_value);
+                }
             }
         }
         Ok(())
@@ -649,10 +1450,19 @@ impl PyWorkbook {
     ///     max_row: Maximum row (default: last row with data)
     ///     min_col: Minimum column (1-indexed, default 1)
     ///     max_col: Maximum column (default: last column with data)
+    ///     converters: Optional dict from column (letter or 1-based index) to
+    ///                 "int", "float", "str", "bool", "date", "datetime", or a
+    ///                 callable taking the cell value. Named conversions run
+    ///                 in Rust; a value that can't be converted raises
+    ///                 ValueError naming the cell. Blank cells are not
+    ///                 converted; they read as blank_policy says.
     ///
     /// Returns:
-    ///     List of rows, where each row is a list of values
-    #[pyo3(signature = (sheet_name, min_row=None, max_row=None, min_col=None, max_col=None))]
+    ///     List of rows, where each row is a list of values. With a "skip"
+    ///     blank policy, blank cells and all-blank rows are left out.
+    #[pyo3(signature = (sheet_name, min_row=None, max_row=None, min_col=None, max_col=None, converters=None))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
     fn read_rows(
         &self,
         sheet_name: &str,
@@ -660,6 +1470,7 @@ impl PyWorkbook {
         max_row: Option<u32>,
         min_col: Option<u32>,
         max_col: Option<u32>,
+        converters: Option<&Bound<'_, PyDict>>,
         py: Python<'_>,
     ) -> PyResult<Vec<Vec<PyObject>>> {
         let ws = self
@@ -675,17 +1486,50 @@ impl PyWorkbook {
         let min_c = min_col.unwrap_or(dims_min_col);
         let max_c = max_col.unwrap_or(dims_max_col);
 
+        let converters = match converters {
+            Some(dict) => read_converters(dict)?,
+            None => HashMap::new(),
+        };
+        let date1904 = self.inner.date1904;
+        let policy = self.inner.blank_policy;
+        let skip_blanks = policy.read == BlankRead::Skip;
+
         let mut result = Vec::new();
         for row in min_r..=max_r {
             let mut row_data = Vec::new();
             for col in min_c..=max_c {
-                if let Some(cell) = ws.get_cell(row, col) {
-                    row_data.push(cell_value_to_python(&cell.value, py));
-                } else {
-                    row_data.push(py.None());
+                let cell = ws
+                    .get_cell(row, col)
+                    .filter(|cell| !matches!(cell.value, CellValue::Empty));
+                if cell.is_none() && skip_blanks {
+                    continue;
                 }
+                let value = match (converters.get(&col), cell) {
+                    (Some(ReadConverter::Callable(func)), cell) => {
+                        let raw = cell.map_or_else(
+                            || blank_to_python(&policy, py),
+                            |cell| cell_value_to_python(&cell.value, py),
+                        );
+                        func.call1((raw,))?.unbind()
+                    }
+                    (_, None) => blank_to_python(&policy, py),
+                    (None, Some(cell)) => cell_value_to_python(&cell.value, py),
+                    (Some(ReadConverter::Named(converter)), Some(cell)) => {
+                        let converted = converter.convert_cell(&cell, date1904).map_err(|e| {
+                            PyValueError::new_err(format!(
+                                "{}: {}",
+                                rustypyxl_core::coordinate_from_row_col(row, col),
+                                e
+                            ))
+                        })?;
+                        converted_value_to_python(converted, py)
+                    }
+                };
+                row_data.push(value);
+            }
+            if !(skip_blanks && row_data.is_empty()) {
+                result.push(row_data);
             }
-            result.push(row_data);
         }
         Ok(result)
     }
@@ -895,6 +1739,60 @@ impl PyWorkbook {
         self.set_or_merge_cell_style(sheet_name, row, column, style)
     }
 
+    /// Apply style properties to every cell in a range (e.g. "A1:D10").
+    ///
+    /// Only the properties given are changed; each cell keeps the rest of
+    /// its style, including its number format and protection. Cells sharing
+    /// a starting style share the resulting style too.
+    ///
+    /// Args:
+    ///     sheet_name: Name of the worksheet
+    ///     range: Cell range, or a single cell
+    ///     font: Optional font style
+    ///     fill: Optional fill style
+    ///     border: Optional border style
+    ///     alignment: Optional alignment style
+    ///     number_format: Optional number format string
+    ///     protection: Optional protection
+    #[pyo3(signature = (sheet_name, range, font=None, fill=None, border=None, alignment=None, number_format=None, protection=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn set_range_style(
+        &mut self,
+        sheet_name: &str,
+        range: &str,
+        font: Option<&PyFont>,
+        fill: Option<&PyPatternFill>,
+        border: Option<&PyBorder>,
+        alignment: Option<&PyAlignment>,
+        number_format: Option<&str>,
+        protection: Option<&PyProtection>,
+    ) -> PyResult<()> {
+        let mut style = rustypyxl_core::CellStyle::new();
+
+        if let Some(f) = font {
+            style = style.with_font(pyfont_to_font(f));
+        }
+        if let Some(f) = fill {
+            style = style.with_fill(pyfill_to_fill(f));
+        }
+        if let Some(b) = border {
+            style = style.with_border(pyborder_to_border(b));
+        }
+        if let Some(a) = alignment {
+            style = style.with_alignment(pyalignment_to_alignment(a));
+        }
+        if let Some(nf) = number_format {
+            style = style.with_number_format(nf);
+        }
+        if let Some(p) = protection {
+            style = style.with_protection(pyprotection_to_protection(p));
+        }
+
+        self.inner
+            .set_range_style(sheet_name, range, &style)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
     /// Get a cell's font style.
     ///
     /// Args:
@@ -991,6 +1889,52 @@ impl PyWorkbook {
         Ok(None)
     }
 
+    /// Name of the named style a cell is linked to, "Normal" when none.
+    pub fn get_cell_named_style(
+        &self,
+        sheet_name: &str,
+        row: u32,
+        column: u32,
+    ) -> PyResult<String> {
+        let ws = self
+            .inner
+            .get_sheet_by_name(sheet_name)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(ws
+            .get_cell(row, column)
+            .as_deref()
+            .map_or("Normal", |cell| cell.named_style())
+            .to_string())
+    }
+
+    /// Give a cell a named style, by name or as a NamedStyle, which is
+    /// added to the workbook when it has none of that name.
+    pub fn set_cell_named_style(
+        &mut self,
+        sheet_name: &str,
+        row: u32,
+        column: u32,
+        style: &Bound<'_, PyAny>,
+    ) -> PyResult<()> {
+        let name = if let Ok(name) = style.extract::<String>() {
+            name
+        } else if let Ok(named) = style.extract::<PyRef<'_, PyNamedStyle>>() {
+            if self.inner.styles.named_style(&named.name).is_none() {
+                self.inner
+                    .add_named_style(pynamedstyle_to_named_style(&named))
+                    .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            }
+            named.name.clone()
+        } else {
+            return Err(PyTypeError::new_err(
+                "style must be a style name or a NamedStyle",
+            ));
+        };
+        self.inner
+            .set_cell_named_style(sheet_name, row, column, &name)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
     /// Get a cell's number format.
     pub fn get_cell_number_format(
         &self,
@@ -1003,12 +1947,28 @@ impl PyWorkbook {
             .get_sheet_by_name(sheet_name)
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
 
-        if let Some(cell) = ws.get_cell(row, column) {
-            if let Some(ref style) = cell.style {
-                return Ok(style.number_format.as_deref().map(str::to_string));
-            }
-        }
-        Ok(None)
+        Ok(ws
+            .get_cell(row, column)
+            .as_deref()
+            .and_then(|cell| cell.number_format_code())
+            .map(str::to_string))
+    }
+
+    /// A cell's value as Excel would display it under its number format.
+    pub fn get_cell_display_value(
+        &self,
+        sheet_name: &str,
+        row: u32,
+        column: u32,
+    ) -> PyResult<String> {
+        let ws = self
+            .inner
+            .get_sheet_by_name(sheet_name)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(ws
+            .get_cell(row, column)
+            .map(|cell| cell.formatted_value())
+            .unwrap_or_default())
     }
 
     /// Get a cell's protection style.
@@ -1033,20 +1993,23 @@ impl PyWorkbook {
         Ok(None)
     }
 
-    /// Set a cell's hyperlink URL.
+    /// Set a cell's hyperlink, given as an address or a Hyperlink; None
+    /// removes it.
+    #[pyo3(signature = (sheet_name, row, column, url=None))]
     pub fn set_cell_hyperlink(
         &mut self,
         sheet_name: &str,
         row: u32,
         column: u32,
-        url: Option<String>,
+        url: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<()> {
+        let link = url.map(crate::hyperlink::extract_hyperlink).transpose()?;
         let ws = self
             .inner
             .get_sheet_by_name_mut(sheet_name)
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
-        match url {
-            Some(u) => ws.set_cell_hyperlink(row, column, u),
+        match link {
+            Some(link) => ws.set_cell_hyperlink(row, column, link),
             None => {
                 if let Some(cell) = ws.get_cell_mut(row, column) {
                     cell.hyperlink = None;
@@ -1056,7 +2019,8 @@ impl PyWorkbook {
         Ok(())
     }
 
-    /// Get a cell's hyperlink URL, or None.
+    /// Get a cell's hyperlink address (the URL, or "#" and the location),
+    /// or None.
     pub fn get_cell_hyperlink(
         &self,
         sheet_name: &str,
@@ -1067,17 +2031,21 @@ impl PyWorkbook {
             .inner
             .get_sheet_by_name(sheet_name)
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
-        Ok(ws.get_cell(row, column).and_then(|c| c.hyperlink.clone()))
+        Ok(ws
+            .get_cell(row, column)
+            .and_then(|c| c.hyperlink.as_ref().map(|link| link.address())))
     }
 
-    /// Set a cell's comment text.
+    /// Set a cell's comment, given as text or a Comment; None removes it.
+    #[pyo3(signature = (sheet_name, row, column, comment=None))]
     pub fn set_cell_comment(
         &mut self,
         sheet_name: &str,
         row: u32,
         column: u32,
-        comment: Option<String>,
+        comment: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<()> {
+        let comment = comment.map(crate::comment::extract_comment).transpose()?;
         let ws = self
             .inner
             .get_sheet_by_name_mut(sheet_name)
@@ -1104,7 +2072,11 @@ impl PyWorkbook {
             .inner
             .get_sheet_by_name(sheet_name)
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
-        Ok(ws.get_cell(row, column).and_then(|c| c.comment.clone()))
+        Ok(ws
+            .get_cell(row, column)
+            .as_deref()
+            .and_then(|c| c.comment.as_ref())
+            .map(|c| c.text.clone()))
     }
 
     /// Import data from a Parquet file directly into a worksheet.
@@ -1120,12 +2092,14 @@ impl PyWorkbook {
     ///     include_headers: Include column headers (default True)
     ///     column_renames: Dict mapping original column names to new names
     ///     columns: List of column names to import (None = all columns)
+    ///     style: ImportStyle for the imported block, True for the default
+    ///            presentable styling, or None (default) to leave it unstyled
     ///
     /// Returns:
     ///     Dict with import results: rows_imported, columns_imported,
     ///     range (e.g. "A1:Z1000"), header_range, data_range, column_names
     #[cfg(feature = "parquet")]
-    #[pyo3(signature = (sheet_name, path, start_row=1, start_col=1, include_headers=true, column_renames=None, columns=None))]
+    #[pyo3(signature = (sheet_name, path, start_row=1, start_col=1, include_headers=true, column_renames=None, columns=None, style=None))]
     // Mirrors a Python keyword-argument API
     #[allow(clippy::too_many_arguments)]
     fn insert_from_parquet(
@@ -1137,17 +2111,235 @@ impl PyWorkbook {
         include_headers: bool,
         column_renames: Option<std::collections::HashMap<String, String>>,
         columns: Option<Vec<String>>,
+        style: Option<&Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+        use rustypyxl_core::ParquetImportOptions;
+
+        let mut opts = ParquetImportOptions::new().with_headers(include_headers);
+        opts.style = crate::style::extract_import_style(style)?;
+
+        if let Some(renames) = column_renames {
+            opts.column_renames = renames;
+        }
+
+        if let Some(cols) = columns {
+            opts.columns = cols;
+        }
+
+        let inner = &mut self.inner;
+        let result = py
+            .allow_threads(|| {
+                inner.insert_from_parquet(sheet_name, path, start_row, start_col, Some(opts))
+            })
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        // Build result dict
+        let dict = PyDict::new(py);
+        dict.set_item("rows_imported", result.rows_imported)?;
+        dict.set_item("columns_imported", result.columns_imported)?;
+        dict.set_item("start_row", result.start_row)?;
+        dict.set_item("start_col", result.start_col)?;
+        dict.set_item("end_row", result.end_row)?;
+        dict.set_item("end_col", result.end_col)?;
+        dict.set_item("range", result.range_with_headers())?;
+        dict.set_item("header_range", result.header_range())?;
+        dict.set_item("data_range", result.data_range())?;
+        dict.set_item("column_names", result.column_names)?;
+
+        Ok(dict.into())
+    }
+
+    /// Export a worksheet to a Parquet file.
+    ///
+    /// This exports cell data directly to Parquet format with automatic type inference.
+    ///
+    /// Args:
+    ///     sheet_name: Name of the worksheet to export
+    ///     path: Output path for the Parquet file
+    ///     has_headers: Whether the first row contains headers (default True)
+    ///     compression: Compression type: "snappy", "gzip", "zstd", "lz4", "none" (default "snappy")
+    ///     column_renames: Dict mapping original column names to new names
+    ///     column_types: Dict mapping column names to types: "string", "float64", "int64", "boolean", "date", "datetime"
+    ///
+    /// Returns:
+    ///     Dict with export results: rows_exported, columns_exported, column_names, file_size
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (sheet_name, path, has_headers=true, compression="snappy", column_renames=None, column_types=None))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
+    fn export_to_parquet(
+        &self,
+        sheet_name: &str,
+        path: &str,
+        has_headers: bool,
+        compression: &str,
+        column_renames: Option<std::collections::HashMap<String, String>>,
+        column_types: Option<std::collections::HashMap<String, String>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+        use rustypyxl_core::{ParquetCompression, ParquetExportOptions};
+
+        let compression = match compression.to_lowercase().as_str() {
+            "none" => ParquetCompression::None,
+            "snappy" => ParquetCompression::Snappy,
+            "gzip" => ParquetCompression::Gzip,
+            "zstd" => ParquetCompression::Zstd,
+            "lz4" => ParquetCompression::Lz4,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid compression: {}. Use 'none', 'snappy', 'gzip', 'zstd', or 'lz4'",
+                    compression
+                )))
+            }
+        };
+
+        let mut opts = ParquetExportOptions::new()
+            .with_headers(has_headers)
+            .with_compression(compression);
+
+        if let Some(renames) = column_renames {
+            opts.column_renames = renames;
+        }
+
+        if let Some(types) = column_types {
+            for (col_name, type_str) in types {
+                opts.column_types.insert(col_name, column_type(&type_str)?);
+            }
+        }
+
+        let result = py
+            .allow_threads(|| self.inner.export_to_parquet(sheet_name, path, Some(opts)))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        // Build result dict
+        let dict = PyDict::new(py);
+        dict.set_item("rows_exported", result.rows_exported)?;
+        dict.set_item("columns_exported", result.columns_exported)?;
+        dict.set_item("column_names", result.column_names)?;
+        dict.set_item("file_size", result.file_size)?;
+
+        Ok(dict.into())
+    }
+
+    /// Export a specific range from a worksheet to a Parquet file.
+    ///
+    /// Args:
+    ///     sheet_name: Name of the worksheet to export
+    ///     path: Output path for the Parquet file
+    ///     min_row: Starting row (1-indexed)
+    ///     min_col: Starting column (1-indexed)
+    ///     max_row: Ending row (1-indexed)
+    ///     max_col: Ending column (1-indexed)
+    ///     has_headers: Whether the first row contains headers (default True)
+    ///     compression: Compression type (default "snappy")
+    ///
+    /// Returns:
+    ///     Dict with export results
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (sheet_name, path, min_row, min_col, max_row, max_col, has_headers=true, compression="snappy"))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
+    fn export_range_to_parquet(
+        &self,
+        sheet_name: &str,
+        path: &str,
+        min_row: u32,
+        min_col: u32,
+        max_row: u32,
+        max_col: u32,
+        has_headers: bool,
+        compression: &str,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+        use rustypyxl_core::{ParquetCompression, ParquetExportOptions};
+
+        let compression = match compression.to_lowercase().as_str() {
+            "none" => ParquetCompression::None,
+            "snappy" => ParquetCompression::Snappy,
+            "gzip" => ParquetCompression::Gzip,
+            "zstd" => ParquetCompression::Zstd,
+            "lz4" => ParquetCompression::Lz4,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid compression: {}",
+                    compression
+                )))
+            }
+        };
+
+        let opts = ParquetExportOptions::new()
+            .with_headers(has_headers)
+            .with_compression(compression);
+
+        let result = py
+            .allow_threads(|| {
+                self.inner.export_range_to_parquet(
+                    sheet_name,
+                    path,
+                    min_row,
+                    min_col,
+                    max_row,
+                    max_col,
+                    Some(opts),
+                )
+            })
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("rows_exported", result.rows_exported)?;
+        dict.set_item("columns_exported", result.columns_exported)?;
+        dict.set_item("column_names", result.column_names)?;
+        dict.set_item("file_size", result.file_size)?;
+
+        Ok(dict.into())
+    }
+
+    /// Import an Arrow IPC (Feather v2) file directly into a worksheet. Both
+    /// the IPC file format (pandas `to_feather`, polars `write_ipc`) and the
+    /// streaming format are read, compressed or not.
+    ///
+    /// Args:
+    ///     sheet_name: Name of the worksheet to insert into
+    ///     path: Path to the Arrow IPC file
+    ///     start_row: Starting row (1-indexed, default 1)
+    ///     start_col: Starting column (1-indexed, default 1)
+    ///     include_headers: Include column headers (default True)
+    ///     column_renames: Dict mapping original column names to new names
+    ///     columns: List of column names to import (None = all columns)
+    ///     style: ImportStyle for the imported block, True for the default
+    ///            presentable styling, or None (default) to leave it unstyled
+    ///
+    /// Returns:
+    ///     Dict with import results: rows_imported, columns_imported,
+    ///     range (e.g. "A1:Z1000"), header_range, data_range, column_names
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (sheet_name, path, start_row=1, start_col=1, include_headers=true, column_renames=None, columns=None, style=None))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
+    fn insert_from_arrow_ipc(
+        &mut self,
+        sheet_name: &str,
+        path: &str,
+        start_row: u32,
+        start_col: u32,
+        include_headers: bool,
+        column_renames: Option<std::collections::HashMap<String, String>>,
+        columns: Option<Vec<String>>,
+        style: Option<&Bound<'_, PyAny>>,
         py: Python<'_>,
     ) -> PyResult<PyObject> {
         use pyo3::types::PyDict;
         use rustypyxl_core::ParquetImportOptions;
 
         let mut opts = ParquetImportOptions::new().with_headers(include_headers);
-
+        opts.style = crate::style::extract_import_style(style)?;
         if let Some(renames) = column_renames {
             opts.column_renames = renames;
         }
-
         if let Some(cols) = columns {
             opts.columns = cols;
         }
@@ -1155,11 +2347,10 @@ impl PyWorkbook {
         let inner = &mut self.inner;
         let result = py
             .allow_threads(|| {
-                inner.insert_from_parquet(sheet_name, path, start_row, start_col, Some(opts))
+                inner.insert_from_arrow_ipc(sheet_name, path, start_row, start_col, Some(opts))
             })
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
 
-        // Build result dict
         let dict = PyDict::new(py);
         dict.set_item("rows_imported", result.rows_imported)?;
         dict.set_item("columns_imported", result.columns_imported)?;
@@ -1175,25 +2366,24 @@ impl PyWorkbook {
         Ok(dict.into())
     }
 
-    /// Export a worksheet to a Parquet file.
-    ///
-    /// This exports cell data directly to Parquet format with automatic type inference.
+    /// Export a worksheet to an Arrow IPC (Feather v2) file, typing columns
+    /// as `export_to_parquet` does.
     ///
     /// Args:
     ///     sheet_name: Name of the worksheet to export
-    ///     path: Output path for the Parquet file
+    ///     path: Output path for the Arrow IPC file
     ///     has_headers: Whether the first row contains headers (default True)
-    ///     compression: Compression type: "snappy", "gzip", "zstd", "lz4", "none" (default "snappy")
+    ///     compression: "none" (default), "lz4" or "zstd"
     ///     column_renames: Dict mapping original column names to new names
     ///     column_types: Dict mapping column names to types: "string", "float64", "int64", "boolean", "date", "datetime"
     ///
     /// Returns:
     ///     Dict with export results: rows_exported, columns_exported, column_names, file_size
     #[cfg(feature = "parquet")]
-    #[pyo3(signature = (sheet_name, path, has_headers=true, compression="snappy", column_renames=None, column_types=None))]
+    #[pyo3(signature = (sheet_name, path, has_headers=true, compression="none", column_renames=None, column_types=None))]
     // Mirrors a Python keyword-argument API
     #[allow(clippy::too_many_arguments)]
-    fn export_to_parquet(
+    fn export_to_arrow_ipc(
         &self,
         sheet_name: &str,
         path: &str,
@@ -1204,126 +2394,34 @@ impl PyWorkbook {
         py: Python<'_>,
     ) -> PyResult<PyObject> {
         use pyo3::types::PyDict;
-        use rustypyxl_core::{ColumnType, ParquetCompression, ParquetExportOptions};
+        use rustypyxl_core::{ArrowIpcExportOptions, IpcCompression};
 
         let compression = match compression.to_lowercase().as_str() {
-            "none" => ParquetCompression::None,
-            "snappy" => ParquetCompression::Snappy,
-            "gzip" => ParquetCompression::Gzip,
-            "zstd" => ParquetCompression::Zstd,
-            "lz4" => ParquetCompression::Lz4,
+            "none" | "uncompressed" => IpcCompression::None,
+            "lz4" => IpcCompression::Lz4,
+            "zstd" => IpcCompression::Zstd,
             _ => {
                 return Err(PyValueError::new_err(format!(
-                    "Invalid compression: {}. Use 'none', 'snappy', 'gzip', 'zstd', or 'lz4'",
+                    "Invalid compression: {}. Use 'none', 'lz4', or 'zstd'",
                     compression
                 )))
             }
         };
 
-        let mut opts = ParquetExportOptions::new()
+        let mut opts = ArrowIpcExportOptions::new()
             .with_headers(has_headers)
             .with_compression(compression);
-
         if let Some(renames) = column_renames {
             opts.column_renames = renames;
         }
-
         if let Some(types) = column_types {
             for (col_name, type_str) in types {
-                let col_type = match type_str.to_lowercase().as_str() {
-                    "string" | "str" => ColumnType::String,
-                    "float64" | "float" | "double" => ColumnType::Float64,
-                    "int64" | "int" | "integer" => ColumnType::Int64,
-                    "boolean" | "bool" => ColumnType::Boolean,
-                    "date" => ColumnType::Date,
-                    "datetime" | "timestamp" => ColumnType::DateTime,
-                    "auto" => ColumnType::Auto,
-                    _ => return Err(PyValueError::new_err(format!(
-                        "Invalid column type: {}. Use 'string', 'float64', 'int64', 'boolean', 'date', 'datetime', or 'auto'",
-                        type_str
-                    ))),
-                };
-                opts.column_types.insert(col_name, col_type);
+                opts.column_types.insert(col_name, column_type(&type_str)?);
             }
         }
 
         let result = py
-            .allow_threads(|| self.inner.export_to_parquet(sheet_name, path, Some(opts)))
-            .map_err(|e| PyValueError::new_err(e.to_string()))?;
-
-        // Build result dict
-        let dict = PyDict::new(py);
-        dict.set_item("rows_exported", result.rows_exported)?;
-        dict.set_item("columns_exported", result.columns_exported)?;
-        dict.set_item("column_names", result.column_names)?;
-        dict.set_item("file_size", result.file_size)?;
-
-        Ok(dict.into())
-    }
-
-    /// Export a specific range from a worksheet to a Parquet file.
-    ///
-    /// Args:
-    ///     sheet_name: Name of the worksheet to export
-    ///     path: Output path for the Parquet file
-    ///     min_row: Starting row (1-indexed)
-    ///     min_col: Starting column (1-indexed)
-    ///     max_row: Ending row (1-indexed)
-    ///     max_col: Ending column (1-indexed)
-    ///     has_headers: Whether the first row contains headers (default True)
-    ///     compression: Compression type (default "snappy")
-    ///
-    /// Returns:
-    ///     Dict with export results
-    #[cfg(feature = "parquet")]
-    #[pyo3(signature = (sheet_name, path, min_row, min_col, max_row, max_col, has_headers=true, compression="snappy"))]
-    // Mirrors a Python keyword-argument API
-    #[allow(clippy::too_many_arguments)]
-    fn export_range_to_parquet(
-        &self,
-        sheet_name: &str,
-        path: &str,
-        min_row: u32,
-        min_col: u32,
-        max_row: u32,
-        max_col: u32,
-        has_headers: bool,
-        compression: &str,
-        py: Python<'_>,
-    ) -> PyResult<PyObject> {
-        use pyo3::types::PyDict;
-        use rustypyxl_core::{ParquetCompression, ParquetExportOptions};
-
-        let compression = match compression.to_lowercase().as_str() {
-            "none" => ParquetCompression::None,
-            "snappy" => ParquetCompression::Snappy,
-            "gzip" => ParquetCompression::Gzip,
-            "zstd" => ParquetCompression::Zstd,
-            "lz4" => ParquetCompression::Lz4,
-            _ => {
-                return Err(PyValueError::new_err(format!(
-                    "Invalid compression: {}",
-                    compression
-                )))
-            }
-        };
-
-        let opts = ParquetExportOptions::new()
-            .with_headers(has_headers)
-            .with_compression(compression);
-
-        let result = py
-            .allow_threads(|| {
-                self.inner.export_range_to_parquet(
-                    sheet_name,
-                    path,
-                    min_row,
-                    min_col,
-                    max_row,
-                    max_col,
-                    Some(opts),
-                )
-            })
+            .allow_threads(|| self.inner.export_to_arrow_ipc(sheet_name, path, Some(opts)))
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
 
         let dict = PyDict::new(py);
@@ -1368,7 +2466,10 @@ impl PyWorkbook {
         let inner = py
             .allow_threads(|| Workbook::load_from_s3(bucket, key, Some(config)))
             .map_err(|e| PyValueError::new_err(e.to_string()))?;
-        Ok(PyWorkbook { inner })
+        Ok(PyWorkbook {
+            inner,
+            rich_text: false,
+        })
     }
 
     /// Save the workbook to S3.
@@ -1412,6 +2513,51 @@ impl PyWorkbook {
 }
 
 impl PyWorkbook {
+    /// Read a workbook from a file path, bytes, or file-like object.
+    fn read(source: &Bound<'_, PyAny>, password: Option<&str>) -> PyResult<Workbook> {
+        let py = source.py();
+
+        // A password opens an encrypted (or plain) workbook: resolve the source
+        // to bytes and decrypt as needed.
+        if let Some(pw) = password {
+            let bytes = read_source_bytes(source)?;
+            let inner = py
+                .allow_threads(|| Workbook::load_from_bytes_with_password(&bytes, pw))
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            return Ok(inner);
+        }
+
+        // Check if source is bytes (before PathBuf, which str also satisfies)
+        if let Ok(bytes) = source.extract::<Vec<u8>>() {
+            let inner = py
+                .allow_threads(|| Workbook::load_from_bytes(&bytes))
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            return Ok(inner);
+        }
+
+        // Check if source is a file path (str or os.PathLike, e.g. pathlib.Path)
+        if let Ok(path) = source.extract::<std::path::PathBuf>() {
+            let inner = py
+                .allow_threads(|| Workbook::load(&path.to_string_lossy()))
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            return Ok(inner);
+        }
+
+        // Check if source has .read() method (file-like object)
+        if source.hasattr("read")? {
+            let bytes_obj = source.call_method0("read")?;
+            let bytes = bytes_obj.extract::<Vec<u8>>()?;
+            let inner = py
+                .allow_threads(|| Workbook::load_from_bytes(&bytes))
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            return Ok(inner);
+        }
+
+        Err(PyTypeError::new_err(
+            "Expected file path (str or os.PathLike), bytes, or file-like object with .read() method"
+        ))
+    }
+
     /// Store an already-converted value. Callers convert from Python first, so
     /// no arbitrary Python runs while the workbook is mutably borrowed.
     pub(crate) fn set_converted_cell_value(
@@ -1426,6 +2572,21 @@ impl PyWorkbook {
             .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
+    /// Store rich-text runs; the cell's plain value becomes their text.
+    pub(crate) fn set_rich_text(
+        &mut self,
+        sheet_name: &str,
+        row: u32,
+        column: u32,
+        rich: RichText,
+    ) -> PyResult<()> {
+        self.inner
+            .get_sheet_by_name_mut(sheet_name)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?
+            .set_cell_rich_text(row, column, rich);
+        Ok(())
+    }
+
     /// Helper to set or merge a cell style with the existing style.
     fn set_or_merge_cell_style(
         &mut self,
@@ -1434,53 +2595,9 @@ impl PyWorkbook {
         column: u32,
         new_style: CellStyle,
     ) -> PyResult<()> {
-        // First, compute merged style from existing cell (if any)
-        let merged_style = {
-            let ws = self
-                .inner
-                .get_sheet_by_name(sheet_name)
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-
-            if let Some(cell) = ws.get_cell(row, column) {
-                if let Some(ref existing) = cell.style {
-                    let mut merged = (**existing).clone();
-                    if new_style.font.is_some() {
-                        merged.font = new_style.font.clone();
-                    }
-                    if new_style.fill.is_some() {
-                        merged.fill = new_style.fill.clone();
-                    }
-                    if new_style.border.is_some() {
-                        merged.border = new_style.border.clone();
-                    }
-                    if new_style.alignment.is_some() {
-                        merged.alignment = new_style.alignment.clone();
-                    }
-                    if new_style.number_format.is_some() {
-                        merged.number_format = new_style.number_format.clone();
-                    }
-                    merged
-                } else {
-                    new_style
-                }
-            } else {
-                new_style
-            }
-        };
-
-        // Get the style index for this style
-        let style_index = self.inner.styles.get_or_add_cell_xf(&merged_style);
-
-        // Now get mutable reference and set the style
-        let ws = self
-            .inner
-            .get_sheet_by_name_mut(sheet_name)
-            .map_err(|e| PyValueError::new_err(e.to_string()))?;
-        let cell = ws.get_or_create_cell_mut(row, column);
-        cell.style = Some(Arc::new(merged_style));
-        cell.style_index = Some(style_index as u32);
-
-        Ok(())
+        self.inner
+            .merge_cell_style(sheet_name, row, column, &new_style)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 }
 
@@ -1530,13 +2647,17 @@ pub(crate) fn python_to_cell_value(value: &Bound<'_, PyAny>) -> PyResult<CellVal
     if let Ok(n) = value.extract::<f64>() {
         return Ok(CellValue::Number(n));
     }
-    // datetime/date/time become ISO-8601 date cells (t="d"). The check goes
-    // through the Python datetime module rather than pyo3's PyDateTime types,
-    // which don't exist under abi3-forward-compatibility builds (the wheels
-    // for Python versions newer than pyo3's tested range, e.g. 3.13/3.14).
+    // datetime/date/time become date cells, keeping which of the three
+    // they were; aware datetimes are stored in UTC. The check
+    // goes through the Python datetime module rather than pyo3's PyDateTime
+    // types, which don't exist under abi3-forward-compatibility builds (the
+    // wheels for Python versions newer than pyo3's tested range, e.g.
+    // 3.13/3.14).
     if is_datetime_like(value)? {
         let iso = value.call_method0("isoformat")?.extract::<String>()?;
-        return Ok(CellValue::Date(iso));
+        return DateValue::parse(&iso)
+            .map(CellValue::Date)
+            .ok_or_else(|| PyValueError::new_err(format!("Unsupported date/time value: {}", iso)));
     }
     // Try to convert to string as fallback
     Ok(CellValue::from(value.str()?.to_string()))
@@ -1569,9 +2690,71 @@ fn iso_string_to_python(py: Python<'_>, s: &str) -> Option<PyObject> {
         .map(|obj| obj.unbind())
 }
 
-/// Convert a CellValue to a Python object.
+/// A non-finite number policy name ("num", "empty" or "error").
+pub(crate) fn non_finite_policy(policy: &str) -> PyResult<rustypyxl_core::NonFiniteNumbers> {
+    use rustypyxl_core::NonFiniteNumbers;
+    match policy.to_lowercase().as_str() {
+        "num" | "#num!" => Ok(NonFiniteNumbers::NumError),
+        "empty" => Ok(NonFiniteNumbers::Empty),
+        "error" => Ok(NonFiniteNumbers::Error),
+        _ => Err(PyValueError::new_err(
+            "Invalid non-finite number policy. Use: 'num', 'empty', or 'error'",
+        )),
+    }
+}
+
+/// A column type hint name for the parquet and Arrow IPC exports.
+#[cfg(feature = "parquet")]
+fn column_type(type_str: &str) -> PyResult<rustypyxl_core::ColumnType> {
+    use rustypyxl_core::ColumnType;
+    Ok(match type_str.to_lowercase().as_str() {
+        "string" | "str" => ColumnType::String,
+        "float64" | "float" | "double" => ColumnType::Float64,
+        "int64" | "int" | "integer" => ColumnType::Int64,
+        "boolean" | "bool" => ColumnType::Boolean,
+        "date" => ColumnType::Date,
+        "datetime" | "timestamp" => ColumnType::DateTime,
+        "auto" => ColumnType::Auto,
+        _ => return Err(PyValueError::new_err(format!(
+            "Invalid column type: {}. Use 'string', 'float64', 'int64', 'boolean', 'date', 'datetime', or 'auto'",
+            type_str
+        ))),
+    })
+}
+
+/// A number precision name ("shortest" or "excel").
+pub(crate) fn number_precision(precision: &str) -> PyResult<rustypyxl_core::NumberPrecision> {
+    use rustypyxl_core::NumberPrecision;
+    match precision.to_lowercase().as_str() {
+        "shortest" => Ok(NumberPrecision::Shortest),
+        "excel" => Ok(NumberPrecision::Excel),
+        _ => Err(PyValueError::new_err(
+            "Invalid number precision. Use: 'shortest' or 'excel'",
+        )),
+    }
+}
+
+/// A CSV delimiter or quote argument as its single ASCII byte.
+pub(crate) fn csv_char(name: &str, value: &str) -> PyResult<u8> {
+    match value.as_bytes() {
+        [byte] => Ok(*byte),
+        _ => Err(PyValueError::new_err(format!(
+            "{name} must be a single ASCII character, not {value:?}"
+        ))),
+    }
+}
+
+/// A CSV encoding label ("utf-8", "latin-1", "cp1252", ...).
+pub(crate) fn csv_encoding(label: &str) -> PyResult<rustypyxl_core::CsvEncoding> {
+    rustypyxl_core::CsvEncoding::from_label(label).ok_or_else(|| {
+        PyValueError::new_err(format!(
+            "unsupported encoding {label:?}: use 'utf-8', 'utf-8-sig', 'latin-1' or 'cp1252'"
+        ))
+    })
+}
+
 /// Read a load source (bytes, a file path, or a file-like object) into bytes.
-fn read_source_bytes(source: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+pub(crate) fn read_source_bytes(source: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
     if let Ok(bytes) = source.extract::<Vec<u8>>() {
         return Ok(bytes);
     }
@@ -1587,6 +2770,92 @@ fn read_source_bytes(source: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
     ))
 }
 
+/// A locked save target raises PermissionError, as it does for openpyxl;
+/// everything else is a ValueError.
+fn save_error(e: rustypyxl_core::RustypyxlError) -> PyErr {
+    match e {
+        rustypyxl_core::RustypyxlError::FileLocked(_) => {
+            pyo3::exceptions::PyPermissionError::new_err(e.to_string())
+        }
+        e => PyValueError::new_err(e.to_string()),
+    }
+}
+
+/// The `wb.styles` proxy over the workbook's style palette: named fonts,
+/// fills, borders, number formats and styles, kept as a JSON document.
+#[pyclass(name = "StyleSheet")]
+pub struct PyStyleSheet {
+    workbook: Py<PyWorkbook>,
+}
+
+#[pymethods]
+impl PyStyleSheet {
+    /// The palette as a pretty-printed JSON document.
+    fn to_json(&self, py: Python<'_>) -> String {
+        self.workbook.borrow(py).inner.styles.to_json()
+    }
+
+    /// Load a palette JSON document. Its entries are added to the palette,
+    /// replacing any with the same name.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_json(&self, py: Python<'_>, text: &str) -> PyResult<()> {
+        self.workbook
+            .borrow_mut(py)
+            .inner
+            .styles
+            .from_json(text)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Names of the palette's styles, sorted.
+    #[getter]
+    fn names(&self, py: Python<'_>) -> Vec<String> {
+        let this = self.workbook.borrow(py);
+        this.inner.styles.palette.styles.keys().cloned().collect()
+    }
+
+    /// Define (or replace) a named style from style objects, for export with
+    /// `to_json`.
+    #[pyo3(signature = (name, font=None, fill=None, border=None, alignment=None, number_format=None, protection=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn add(
+        &self,
+        py: Python<'_>,
+        name: &str,
+        font: Option<&PyFont>,
+        fill: Option<&PyPatternFill>,
+        border: Option<&PyBorder>,
+        alignment: Option<&PyAlignment>,
+        number_format: Option<String>,
+        protection: Option<&PyProtection>,
+    ) {
+        use rustypyxl_core::{PaletteRef, PaletteStyle};
+        let style = PaletteStyle {
+            font: font.map(|f| PaletteRef::Inline(pyfont_to_font(f))),
+            fill: fill.map(|f| PaletteRef::Inline(pyfill_to_fill(f))),
+            border: border.map(|b| PaletteRef::Inline(pyborder_to_border(b))),
+            alignment: alignment.map(pyalignment_to_alignment),
+            number_format,
+            protection: protection.map(pyprotection_to_protection),
+        };
+        self.workbook
+            .borrow_mut(py)
+            .inner
+            .styles
+            .palette
+            .add_style(name, style);
+    }
+
+    fn __contains__(&self, py: Python<'_>, name: &str) -> bool {
+        let this = self.workbook.borrow(py);
+        this.inner.styles.palette.styles.contains_key(name)
+    }
+
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.workbook.borrow(py).inner.styles.palette.styles.len()
+    }
+}
+
 /// A read-only view of a pivot table (openpyxl-level read support).
 #[pyclass(name = "PivotTable", frozen)]
 pub struct PyPivotTable {
@@ -1683,7 +2952,65 @@ pub(crate) fn cell_value_to_python(value: &CellValue, py: Python<'_>) -> PyObjec
         }
         CellValue::Boolean(b) => b.to_object(py),
         CellValue::Formula(f) => format!("={}", f).to_object(py),
-        CellValue::Date(d) => iso_string_to_python(py, d).unwrap_or_else(|| d.to_object(py)),
+        CellValue::Date(d) => {
+            let iso = match d {
+                DateValue::Date(date) => date.format("%Y-%m-%d").to_string(),
+                DateValue::Time(time) => time.format("%H:%M:%S%.6f").to_string(),
+                DateValue::DateTime(dt) => dt.format("%Y-%m-%dT%H:%M:%S%.6f").to_string(),
+            };
+            iso_string_to_python(py, &iso).unwrap_or_else(|| iso.to_object(py))
+        }
+    }
+}
+
+/// A `read_rows` converter: a named conversion done in Rust, or a Python
+/// callable given the cell's value.
+enum ReadConverter<'py> {
+    Named(rustypyxl_core::ValueConverter),
+    Callable(Bound<'py, PyAny>),
+}
+
+/// Parse `read_rows(converters=...)` into converters keyed by column.
+fn read_converters<'py>(dict: &Bound<'py, PyDict>) -> PyResult<HashMap<u32, ReadConverter<'py>>> {
+    let mut converters = HashMap::new();
+    for (key, value) in dict.iter() {
+        let column = crate::worksheet::column_key(&key)?;
+        let converter = if let Ok(name) = value.extract::<String>() {
+            ReadConverter::Named(rustypyxl_core::ValueConverter::parse(&name).ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "Invalid converter: {}. Use 'int', 'float', 'str', 'bool', 'date', 'datetime', or a callable",
+                    name
+                ))
+            })?)
+        } else if value.is_callable() {
+            ReadConverter::Callable(value)
+        } else {
+            return Err(PyValueError::new_err(
+                "converters values must be a converter name or a callable",
+            ));
+        };
+        converters.insert(column, converter);
+    }
+    Ok(converters)
+}
+
+/// Convert a `read_rows` converter result to the matching Python object.
+fn converted_value_to_python(value: rustypyxl_core::ConvertedValue, py: Python<'_>) -> PyObject {
+    use rustypyxl_core::ConvertedValue;
+    match value {
+        ConvertedValue::Empty => py.None(),
+        ConvertedValue::Int(n) => n.to_object(py),
+        ConvertedValue::Float(n) => n.to_object(py),
+        ConvertedValue::Str(s) => s.to_object(py),
+        ConvertedValue::Bool(b) => b.to_object(py),
+        ConvertedValue::Date(d) => {
+            let iso = d.format("%Y-%m-%d").to_string();
+            iso_string_to_python(py, &iso).unwrap_or_else(|| iso.to_object(py))
+        }
+        ConvertedValue::DateTime(dt) => {
+            let iso = dt.format("%Y-%m-%dT%H:%M:%S%.6f").to_string();
+            iso_string_to_python(py, &iso).unwrap_or_else(|| iso.to_object(py))
+        }
     }
 }
 
@@ -1715,7 +3042,7 @@ pub(crate) fn formula_value_to_python(
 // =====================
 
 /// Convert PyFont to Rust Font.
-fn pyfont_to_font(pf: &PyFont) -> Font {
+pub(crate) fn pyfont_to_font(pf: &PyFont) -> Font {
     Font {
         name: pf.name.clone(),
         size: pf.size,
@@ -1743,7 +3070,7 @@ fn font_to_pyfont(f: &Font) -> PyFont {
 }
 
 /// Convert PyPatternFill to Rust Fill.
-fn pyfill_to_fill(pf: &PyPatternFill) -> Fill {
+pub(crate) fn pyfill_to_fill(pf: &PyPatternFill) -> Fill {
     Fill {
         pattern_type: pf.fill_type.clone().or(pf.patternType.clone()),
         fg_color: pf.fgColor.clone(),
@@ -1778,7 +3105,7 @@ fn borderstyle_to_pyside(bs: &BorderStyle) -> PySide {
 }
 
 /// Convert PyBorder to Rust Border.
-fn pyborder_to_border(pb: &PyBorder) -> Border {
+pub(crate) fn pyborder_to_border(pb: &PyBorder) -> Border {
     Border {
         left: pb.left.as_ref().and_then(pyside_to_borderstyle),
         right: pb.right.as_ref().and_then(pyside_to_borderstyle),
@@ -1833,6 +3160,23 @@ fn alignment_to_pyalignment(a: &Alignment) -> PyAlignment {
     }
 }
 
+/// Convert a PyNamedStyle to the core NamedStyle.
+pub(crate) fn pynamedstyle_to_named_style(ns: &PyNamedStyle) -> NamedStyle {
+    let mut style = CellStyle::new();
+    style.font = ns.font.as_ref().map(pyfont_to_font);
+    style.fill = ns.fill.as_ref().map(pyfill_to_fill);
+    style.border = ns.border.as_ref().map(pyborder_to_border);
+    style.alignment = ns.alignment.as_ref().map(pyalignment_to_alignment);
+    style.number_format = ns.number_format.as_deref().map(Into::into);
+    style.protection = ns.protection.as_ref().map(pyprotection_to_protection);
+    NamedStyle {
+        name: ns.name.clone(),
+        style,
+        builtin_id: ns.builtinId,
+        hidden: ns.hidden,
+    }
+}
+
 /// Convert PyProtection to Rust Protection.
 fn pyprotection_to_protection(pp: &PyProtection) -> Protection {
     Protection {
@@ -1841,6 +3185,46 @@ fn pyprotection_to_protection(pp: &PyProtection) -> Protection {
     }
 }
 
+/// A style given as a palette style name (see `wb.styles.from_json`) or a
+/// dict with any of the keys font, fill, border, alignment, number_format
+/// and protection.
+pub(crate) fn python_to_cell_style(
+    style: &Bound<'_, PyAny>,
+    palette: &rustypyxl_core::StylePalette,
+) -> PyResult<CellStyle> {
+    if let Ok(name) = style.extract::<String>() {
+        return palette
+            .resolve(&name)
+            .map_err(|e| PyValueError::new_err(e.to_string()));
+    }
+    let dict = style.downcast::<PyDict>().map_err(|_| {
+        PyValueError::new_err("style must be a named style or a dict of style components")
+    })?;
+    let mut out = CellStyle::new();
+    for (key, value) in dict.iter() {
+        let key: String = key.extract()?;
+        out = match key.as_str() {
+            "font" => out.with_font(pyfont_to_font(&value.extract::<PyFont>()?)),
+            "fill" => out.with_fill(pyfill_to_fill(&value.extract::<PyPatternFill>()?)),
+            "border" => out.with_border(pyborder_to_border(&value.extract::<PyBorder>()?)),
+            "alignment" => {
+                out.with_alignment(pyalignment_to_alignment(&value.extract::<PyAlignment>()?))
+            }
+            "number_format" => out.with_number_format(value.extract::<String>()?),
+            "protection" => out.with_protection(pyprotection_to_protection(
+                &value.extract::<PyProtection>()?,
+            )),
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown style component {other:?}; expected font, fill, border, \
+                     alignment, number_format or protection"
+                )))
+            }
+        };
+    }
+    Ok(out)
+}
+
 /// Convert Rust Protection to PyProtection.
 fn protection_to_pyprotection(p: &Protection) -> PyProtection {
     PyProtection {
@@ -1848,3 +3232,25 @@ fn protection_to_pyprotection(p: &Protection) -> PyProtection {
         hidden: p.hidden,
     }
 }
+
+/// A Python callable used as a PDF renderer. An exception it raises is kept
+/// so `export_pdf` can re-raise it unchanged.
+struct PyRenderer<'py> {
+    callable: Bound<'py, PyAny>,
+    error: std::cell::RefCell<Option<PyErr>>,
+}
+
+impl rustypyxl_core::render::Renderer for PyRenderer<'_> {
+    fn render_pdf(
+        &self,
+        input: &std::path::Path,
+        output: &std::path::Path,
+    ) -> rustypyxl_core::Result<()> {
+        self.callable.call1((input, output)).map_err(|e| {
+            let message = e.to_string();
+            *self.error.borrow_mut() = Some(e);
+            rustypyxl_core::RustypyxlError::custom(message)
+        })?;
+        Ok(())
+    }
+}
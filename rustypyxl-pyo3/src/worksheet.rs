@@ -5,10 +5,15 @@ use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
 use pyo3::Py;
 use rustypyxl_core::{
-    column_to_letter, coordinate_from_row_col, parse_coordinate, CellValue, Worksheet,
+    column_to_letter, coordinate_from_row_col, parse_coordinate, BlankPolicy, BlankRead, CellValue,
+    Worksheet,
 };
 
 use crate::cell::PyCell;
+use crate::cell_range::{PyCellRange, PyMultiCellRange};
+use crate::comment::PyComment;
+use crate::hyperlink::PyHyperlink;
+use crate::rich_text::python_to_rich_text;
 use crate::workbook::{cell_value_to_python, python_to_cell_value, PyWorkbook};
 
 /// An Excel Worksheet (openpyxl-compatible API).
@@ -52,7 +57,7 @@ impl PyWorksheet {
         if let Some(ref wb) = self.workbook {
             let this = wb.borrow(py);
             let idx = self.resolve_index(&this)?;
-            return Ok(this.inner.sheet_names[idx].clone());
+            return Ok(this.inner.sheet_names()[idx].clone());
         }
         Ok(self.cached_title.clone())
     }
@@ -76,6 +81,99 @@ impl PyWorksheet {
         Ok((1, 1, 1, 1))
     }
 
+    /// Build a lazy row (or column) iterator over `[min_row, max_row,
+    /// min_col, max_col]` with openpyxl's defaults: a missing or zero bound
+    /// falls back to 1 or the used range's maximum, and a sheet with no cells
+    /// yields nothing unless a bound is given.
+    fn range_iterator(
+        &self,
+        bounds: [Option<u32>; 4],
+        values_only: bool,
+        by_columns: bool,
+        py: Python<'_>,
+    ) -> PyResult<PyCellRangeIterator> {
+        let [min_row, max_row, min_col, max_col] = bounds.map(|b| b.filter(|&n| n > 0));
+        let (_, _, dmax_r, dmax_c) = self.sheet_dims(py)?;
+        let unbounded =
+            min_row.is_none() && max_row.is_none() && min_col.is_none() && max_col.is_none();
+        let empty = match self.workbook {
+            Some(_) => self.with_sheet_ref(py, |ws| ws.cell_count() == 0)?,
+            None => true,
+        };
+
+        let min_row = min_row.unwrap_or(1);
+        let min_col = min_col.unwrap_or(1);
+        let mut iter = PyCellRangeIterator {
+            workbook: self.workbook.as_ref().map(|wb| wb.clone_ref(py)),
+            sheet_uid: self.uid,
+            min_row,
+            max_row: max_row.unwrap_or(dmax_r),
+            min_col,
+            max_col: max_col.unwrap_or(dmax_c),
+            values_only,
+            by_columns,
+            position: if by_columns { min_col } else { min_row },
+        };
+        if unbounded && empty {
+            iter.max_row = 0;
+            iter.max_col = 0;
+        }
+        Ok(iter)
+    }
+
+    /// Write a Python value (rich text included) to a cell of this sheet.
+    fn store_value(
+        &self,
+        row: u32,
+        col: u32,
+        value: &Bound<'_, PyAny>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        // Convert before borrowing the workbook: the conversion can run
+        // arbitrary Python (__str__), which may re-enter this workbook.
+        let (rich, cell_value) = match python_to_rich_text(value)? {
+            Some(rich) => (Some(rich), rustypyxl_core::CellValue::Empty),
+            None => (None, python_to_cell_value(value)?),
+        };
+        if let Some(ref wb) = self.workbook {
+            let mut this = wb.borrow_mut(py);
+            let idx = self.resolve_index(&this)?;
+            let name = this.inner.sheet_names()[idx].clone();
+            match rich {
+                Some(rich) => this.set_rich_text(&name, row, col, rich),
+                None => this.set_converted_cell_value(&name, row, col, cell_value),
+            }
+        } else {
+            Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ))
+        }
+    }
+
+    /// Cells of a rectangle, row by row, laid out by the core range iterator.
+    fn cell_block(
+        &self,
+        py: Python<'_>,
+        min_row: u32,
+        min_col: u32,
+        max_row: u32,
+        max_col: u32,
+    ) -> PyResult<Vec<Vec<PyCell>>> {
+        let coords: Vec<Vec<(u32, u32)>> = self.with_sheet_ref(py, |ws| {
+            ws.range(min_row, min_col, max_row, max_col)
+                .map(|row| row.map(|(r, c, _)| (r, c)).collect())
+                .collect()
+        })?;
+        Ok(coords
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(r, c)| self.make_cell(r, c, py))
+                    .collect()
+            })
+            .collect())
+    }
+
     /// Run a closure against the immutable core worksheet, returning its result.
     fn with_sheet_ref<R, F: FnOnce(&Worksheet) -> R>(&self, py: Python<'_>, f: F) -> PyResult<R> {
         if let Some(ref wb) = self.workbook {
@@ -132,6 +230,15 @@ impl PyWorksheet {
     }
 }
 
+/// A storage mode named as Python spells it: "map" or "columnar".
+pub(crate) fn parse_storage(value: &str) -> PyResult<rustypyxl_core::Storage> {
+    match value {
+        "map" => Ok(rustypyxl_core::Storage::Map),
+        "columnar" => Ok(rustypyxl_core::Storage::Columnar),
+        _ => Err(PyValueError::new_err("storage must be 'map' or 'columnar'")),
+    }
+}
+
 #[pymethods]
 impl PyWorksheet {
     /// Get the worksheet title (always the current name, even after the
@@ -153,6 +260,21 @@ impl PyWorksheet {
         Ok("visible".to_string())
     }
 
+    /// Sheet part type: "worksheet", or "dialogsheet" / "macrosheet" /
+    /// "intlmacrosheet" for a legacy sheet that was loaded and is preserved
+    /// as-is (it has no cells here).
+    #[getter]
+    fn sheet_type(&self, py: Python<'_>) -> PyResult<String> {
+        if let Some(ref wb) = self.workbook {
+            let this = wb.borrow(py);
+            let idx = self.resolve_index(&this)?;
+            if let Some(opaque) = &this.inner.worksheets[idx].opaque {
+                return Ok(opaque.kind.as_str().to_string());
+            }
+        }
+        Ok("worksheet".to_string())
+    }
+
     /// Set sheet visibility: "visible", "hidden", or "veryHidden".
     #[setter]
     fn set_sheet_state(&self, py: Python<'_>, value: &str) -> PyResult<()> {
@@ -165,6 +287,25 @@ impl PyWorksheet {
         self.with_sheet_mut(py, |ws| ws.visibility = state)
     }
 
+    /// How the sheet stores its cells: "map" (the default), or "columnar"
+    /// to keep plain values in dense per-column arrays, which takes several
+    /// times less memory for large rectangular data.
+    #[getter]
+    fn storage(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(match self.workbook {
+            Some(_) => self.with_sheet_ref(py, |ws| ws.storage().as_str())?,
+            None => rustypyxl_core::Storage::Map.as_str(),
+        }
+        .to_string())
+    }
+
+    /// Set how the sheet stores its cells: "map" or "columnar".
+    #[setter]
+    fn set_storage(&self, py: Python<'_>, value: &str) -> PyResult<()> {
+        let storage = parse_storage(value)?;
+        self.with_sheet_mut(py, |ws| ws.set_storage(storage))
+    }
+
     /// Rename the worksheet (e.g. ws.title = "Results").
     #[setter]
     fn set_title(&mut self, value: String) -> PyResult<()> {
@@ -172,53 +313,92 @@ impl PyWorksheet {
             Python::with_gil(|py| -> PyResult<()> {
                 let mut this = wb.borrow_mut(py);
                 let idx = self.resolve_index(&this)?;
-                if this
-                    .inner
-                    .sheet_names
-                    .iter()
-                    .enumerate()
-                    .any(|(i, n)| i != idx && n == &value)
-                {
-                    return Err(PyValueError::new_err(format!(
-                        "Worksheet '{}' already exists",
-                        value
-                    )));
-                }
-                this.inner.sheet_names[idx] = value.clone();
-                this.inner.worksheets[idx].set_title(value.clone());
-                Ok(())
+                let old = this.inner.sheet_names()[idx].clone();
+                this.inner
+                    .rename_sheet(&old, &value)
+                    .map_err(|e| PyValueError::new_err(e.to_string()))
             })?;
         }
         self.cached_title = value;
         Ok(())
     }
 
-    /// Get a cell (ws['A1']) or a range of cells (ws['A1:B2']).
+    /// Get cells by subscript, like openpyxl:
     ///
-    /// A single coordinate returns one Cell; a range returns a list of rows,
-    /// each a list of Cell objects.
-    fn __getitem__(&self, key: &str, py: Python<'_>) -> PyResult<PyObject> {
-        if let Some(colon) = key.find(':') {
-            let (r1, c1) = parse_coordinate(&key[..colon])
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-            let (r2, c2) = parse_coordinate(&key[colon + 1..])
-                .map_err(|e| PyValueError::new_err(e.to_string()))?;
-            let (min_r, max_r) = (r1.min(r2), r1.max(r2));
-            let (min_c, max_c) = (c1.min(c2), c1.max(c2));
-
-            let rows = PyList::empty(py);
-            for r in min_r..=max_r {
-                let row = PyList::empty(py);
-                for c in min_c..=max_c {
-                    row.append(Py::new(py, self.make_cell(r, c, py))?)?;
+    /// - `ws["A1"]` is one Cell;
+    /// - `ws["A1:C10"]` is a tuple of rows, each a tuple of Cells;
+    /// - `ws["B"]` and `ws[5]` (or `ws["5"]`) are one column or row as a
+    ///   tuple of Cells, reaching to the used range's last row or column;
+    /// - `ws["B:D"]`, `ws["2:4"]` and `ws[2:4]` are tuples of those.
+    fn __getitem__(&self, key: &Bound<'_, PyAny>, py: Python<'_>) -> PyResult<PyObject> {
+        use pyo3::types::{PySlice, PyTuple};
+
+        let key: String = if let Ok(row) = key.extract::<u32>() {
+            row.to_string()
+        } else if let Ok(slice) = key.downcast::<PySlice>() {
+            let start: Option<u32> = slice.getattr("start")?.extract()?;
+            let stop: Option<u32> = slice.getattr("stop")?.extract()?;
+            match (start, stop) {
+                (Some(start), Some(stop)) => format!("{}:{}", start, stop),
+                _ => return Err(PyValueError::new_err("Row slices need a start and a stop")),
+            }
+        } else {
+            key.extract()?
+        };
+        let bounds = rustypyxl_core::range_boundaries(&key)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let tuple = |cells: Vec<PyCell>| -> PyResult<Bound<'_, PyTuple>> {
+            let cells = cells
+                .into_iter()
+                .map(|cell| Py::new(py, cell))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyTuple::new(py, cells)
+        };
+        let (_, _, dmax_r, dmax_c) = self.sheet_dims(py)?;
+        match bounds {
+            (Some(row), Some(col), _, _) if !key.contains(':') => {
+                Ok(Py::new(py, self.make_cell(row, col, py))?.into_any())
+            }
+            // Whole columns: one tuple per column, a bare tuple for one
+            (None, Some(min_c), None, Some(max_c)) => {
+                let mut columns: Vec<Vec<PyCell>> = (min_c..=max_c).map(|_| Vec::new()).collect();
+                for row in self.cell_block(py, 1, min_c, dmax_r, max_c)? {
+                    for (column, cell) in columns.iter_mut().zip(row) {
+                        column.push(cell);
+                    }
+                }
+                let mut columns = columns
+                    .into_iter()
+                    .map(tuple)
+                    .collect::<PyResult<Vec<_>>>()?;
+                if min_c == max_c {
+                    return Ok(columns.remove(0).into_any().unbind());
+                }
+                Ok(PyTuple::new(py, columns)?.into_any().unbind())
+            }
+            // Whole rows: one tuple per row, a bare tuple for one
+            (Some(min_r), None, Some(max_r), None) => {
+                let mut rows = self
+                    .cell_block(py, min_r, 1, max_r, dmax_c)?
+                    .into_iter()
+                    .map(tuple)
+                    .collect::<PyResult<Vec<_>>>()?;
+                if min_r == max_r {
+                    return Ok(rows.remove(0).into_any().unbind());
                 }
-                rows.append(row)?;
+                Ok(PyTuple::new(py, rows)?.into_any().unbind())
             }
-            return Ok(rows.into_any().unbind());
+            (Some(min_r), Some(min_c), Some(max_r), Some(max_c)) => {
+                let rows = self
+                    .cell_block(py, min_r, min_c, max_r, max_c)?
+                    .into_iter()
+                    .map(tuple)
+                    .collect::<PyResult<Vec<_>>>()?;
+                Ok(PyTuple::new(py, rows)?.into_any().unbind())
+            }
+            _ => Err(PyValueError::new_err(format!("Invalid range: {}", key))),
         }
-
-        let (row, col) = parse_coordinate(key).map_err(|e| PyValueError::new_err(e.to_string()))?;
-        Ok(Py::new(py, self.make_cell(row, col, py))?.into_any())
     }
 
     /// Set a cell value using subscript notation: ws['A1'] = 'Hello'.
@@ -228,29 +408,28 @@ impl PyWorksheet {
                 "Range assignment is not supported; assign cells individually",
             ));
         }
-        let (row, col) = parse_coordinate(key).map_err(|e| PyValueError::new_err(e.to_string()))?;
-        // Convert before borrowing the workbook: the conversion can run
-        // arbitrary Python (__str__), which may re-enter this workbook.
-        let cell_value = python_to_cell_value(&value)?;
-        if let Some(ref wb) = self.workbook {
-            let mut this = wb.borrow_mut(py);
-            let idx = self.resolve_index(&this)?;
-            let name = this.inner.sheet_names[idx].clone();
-            this.set_converted_cell_value(&name, row, col, cell_value)
-        } else {
-            Err(PyValueError::new_err(
-                "Worksheet is not attached to a workbook",
-            ))
-        }
+        let (row, col) = parse_coordinate(&key.replace('$', ""))
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.store_value(row, col, &value, py)
     }
 
-    /// Get a cell at a specific row and column (both 1-indexed).
-    #[pyo3(signature = (row, column=None))]
-    fn cell(&self, row: u32, column: Option<u32>, py: Python<'_>) -> PyResult<PyCell> {
+    /// Get a cell at a specific row and column (both 1-indexed). As in
+    /// openpyxl, a `value` other than None is written to the cell first.
+    #[pyo3(signature = (row, column=None, value=None))]
+    fn cell(
+        &self,
+        row: u32,
+        column: Option<u32>,
+        value: Option<Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<PyCell> {
         let col = column.unwrap_or(1);
         if row == 0 || col == 0 {
             return Err(PyValueError::new_err("Row and column must be at least 1"));
         }
+        if let Some(value) = value {
+            self.store_value(row, col, &value, py)?;
+        }
         Ok(self.make_cell(row, col, py))
     }
 
@@ -267,18 +446,7 @@ impl PyWorksheet {
         values_only: bool,
         py: Python<'_>,
     ) -> PyResult<PyCellRangeIterator> {
-        let (_, _, dmax_r, dmax_c) = self.sheet_dims(py)?;
-        Ok(PyCellRangeIterator {
-            workbook: self.workbook.as_ref().map(|wb| wb.clone_ref(py)),
-            sheet_uid: self.uid,
-            min_row: min_row.unwrap_or(1).max(1),
-            max_row: max_row.unwrap_or(dmax_r),
-            min_col: min_col.unwrap_or(1).max(1),
-            max_col: max_col.unwrap_or(dmax_c),
-            values_only,
-            by_columns: false,
-            position: min_row.unwrap_or(1).max(1),
-        })
+        self.range_iterator([min_row, max_row, min_col, max_col], values_only, false, py)
     }
 
     /// Iterate over columns lazily (one tuple per column). See iter_rows.
@@ -292,18 +460,29 @@ impl PyWorksheet {
         values_only: bool,
         py: Python<'_>,
     ) -> PyResult<PyCellRangeIterator> {
-        let (_, _, dmax_r, dmax_c) = self.sheet_dims(py)?;
-        Ok(PyCellRangeIterator {
-            workbook: self.workbook.as_ref().map(|wb| wb.clone_ref(py)),
-            sheet_uid: self.uid,
-            min_row: min_row.unwrap_or(1).max(1),
-            max_row: max_row.unwrap_or(dmax_r),
-            min_col: min_col.unwrap_or(1).max(1),
-            max_col: max_col.unwrap_or(dmax_c),
-            values_only,
-            by_columns: true,
-            position: min_col.unwrap_or(1).max(1),
-        })
+        self.range_iterator([min_row, max_row, min_col, max_col], values_only, true, py)
+    }
+
+    /// All rows of the used range as Cell tuples, same as iter_rows().
+    #[getter]
+    fn rows(&self, py: Python<'_>) -> PyResult<PyCellRangeIterator> {
+        self.range_iterator([None; 4], false, false, py)
+    }
+
+    /// All columns of the used range as Cell tuples, same as iter_cols().
+    #[getter]
+    fn columns(&self, py: Python<'_>) -> PyResult<PyCellRangeIterator> {
+        self.range_iterator([None; 4], false, true, py)
+    }
+
+    /// All rows of the used range as value tuples.
+    #[getter]
+    fn values(&self, py: Python<'_>) -> PyResult<PyCellRangeIterator> {
+        self.range_iterator([None; 4], true, false, py)
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<PyCellRangeIterator> {
+        self.range_iterator([None; 4], false, false, py)
     }
 
     /// Get the maximum row containing data.
@@ -343,8 +522,30 @@ impl PyWorksheet {
         ))
     }
 
+    /// Number of cells in `range` ("A1:D20", "C:C", "5:9") that hold a
+    /// value. Cells are counted, not created.
+    fn non_empty_cells_in(&self, range: &str, py: Python<'_>) -> PyResult<usize> {
+        self.with_sheet_ref(py, |ws| ws.non_empty_cells_in(range))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// The first row whose cell in `column` (a letter or 1-based index) has
+    /// no value: the insertion point of a partly filled template.
+    fn first_empty_row(&self, column: &Bound<'_, PyAny>, py: Python<'_>) -> PyResult<u32> {
+        let column = column_key(column)?;
+        self.with_sheet_ref(py, |ws| ws.first_empty_row(column))
+    }
+
     /// Merge cells in a range (e.g. "A1:B2") or by explicit coordinates.
-    #[pyo3(signature = (range_string=None, start_row=None, start_column=None, end_row=None, end_column=None))]
+    /// Like Excel, only the top-left cell keeps its value. Raises ValueError
+    /// if the range overlaps an existing merge.
+    ///
+    /// `value` sets the top-left cell. `style` (a named style, or a dict of
+    /// font, fill, border, alignment, number_format and protection) is
+    /// applied to every covered cell, which Excel needs to draw borders and
+    /// fills across the whole merged block.
+    #[pyo3(signature = (range_string=None, start_row=None, start_column=None, end_row=None, end_column=None, value=None, style=None))]
+    #[allow(clippy::too_many_arguments)]
     fn merge_cells(
         &self,
         range_string: Option<&str>,
@@ -352,14 +553,43 @@ impl PyWorksheet {
         start_column: Option<u32>,
         end_row: Option<u32>,
         end_column: Option<u32>,
+        value: Option<Bound<'_, PyAny>>,
+        style: Option<Bound<'_, PyAny>>,
         py: Python<'_>,
     ) -> PyResult<()> {
         let range =
             self.resolve_range(range_string, start_row, start_column, end_row, end_column)?;
-        self.with_sheet_mut(py, move |ws| ws.merge_cells(&range))
+        let style = style.filter(|s| !s.is_none());
+        let value = match value.filter(|v| !v.is_none()) {
+            Some(v) => Some(python_to_cell_value(&v)?),
+            None => None,
+        };
+        if value.is_none() && style.is_none() {
+            let mut result = Ok(());
+            self.with_sheet_mut(py, |ws| result = ws.merge_cells(&range))?;
+            return result.map_err(|e| PyValueError::new_err(e.to_string()));
+        }
+        let Some(ref wb) = self.workbook else {
+            return Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        let mut this = wb.borrow_mut(py);
+        let idx = self.resolve_index(&this)?;
+        let sheet = this.inner.sheet_names()[idx].clone();
+        let style = match style {
+            Some(s) => Some(crate::workbook::python_to_cell_style(
+                &s,
+                &this.inner.styles.palette,
+            )?),
+            None => None,
+        };
+        this.inner
+            .merge_cells_styled(&sheet, &range, value, style.as_ref())
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
-    /// Unmerge cells in a range.
+    /// Unmerge a merged range. Raises ValueError if it is not merged.
     #[pyo3(signature = (range_string=None, start_row=None, start_column=None, end_row=None, end_column=None))]
     fn unmerge_cells(
         &self,
@@ -372,74 +602,185 @@ impl PyWorksheet {
     ) -> PyResult<()> {
         let range =
             self.resolve_range(range_string, start_row, start_column, end_row, end_column)?;
-        self.with_sheet_mut(py, move |ws| ws.unmerge_cells(&range))
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.unmerge_cells(&range))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
-    /// Get merged cell ranges as "A1:B2" strings.
+    /// Merged cell ranges. Each compares equal to its "A1:B2" string and
+    /// has `min_row`/`min_col`/`max_row`/`max_col` bounds.
     #[getter]
-    fn merged_cells(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+    fn merged_cells(&self, py: Python<'_>) -> PyResult<PyMultiCellRange> {
+        let mut merged = PyMultiCellRange::default();
         if let Some(ref wb) = self.workbook {
             let this = wb.borrow(py);
             let idx = self.resolve_index(&this)?;
-            return Ok(this.inner.worksheets[idx]
-                .merged_cells
-                .iter()
-                .map(|(s, e)| format!("{}:{}", s, e))
-                .collect());
+            for (start, end) in &this.inner.worksheets[idx].merged_cells {
+                merged
+                    .ranges
+                    .push(PyCellRange::parse(&format!("{}:{}", start, end))?);
+            }
         }
-        Ok(Vec::new())
+        Ok(merged)
+    }
+
+    /// Append a row after the last row in use and return its number.
+    /// Accepts any iterable of values (list, tuple, generator), or a dict
+    /// mapping column letters or 1-based indices to values, like openpyxl.
+    fn append(&self, iterable: Bound<'_, PyAny>, py: Python<'_>) -> PyResult<u32> {
+        // Collect the values before borrowing the workbook, since evaluating
+        // a generator can run arbitrary Python code
+        let row = AppendRow::collect(&iterable)?;
+        let mut appended = 0;
+        self.with_sheet_mut(py, |ws| appended = row.append_to(ws))?;
+        Ok(appended)
+    }
+
+    /// Append several rows at once, each taking the forms `append` accepts.
+    /// Returns the (first, last) rows written, or None when `rows` is empty.
+    fn append_rows(&self, rows: Bound<'_, PyAny>, py: Python<'_>) -> PyResult<Option<(u32, u32)>> {
+        let rows = rows
+            .try_iter()?
+            .map(|row| AppendRow::collect(&row?))
+            .collect::<PyResult<Vec<_>>>()?;
+        let mut span = None;
+        self.with_sheet_mut(py, |ws| {
+            for row in rows {
+                let appended = row.append_to(ws);
+                span = Some((span.map_or(appended, |(first, _)| first), appended));
+            }
+        })?;
+        Ok(span)
     }
 
-    /// Append a row after the last row containing data. Accepts any
-    /// iterable of values (list, tuple, generator), or a dict mapping
-    /// column letters or 1-based indices to values, like openpyxl.
-    fn append(&self, iterable: Bound<'_, PyAny>, py: Python<'_>) -> PyResult<()> {
-        // Collect (column, value) pairs before borrowing the workbook, since
-        // evaluating a generator can run arbitrary Python code
-        let mut cells: Vec<(u32, rustypyxl_core::CellValue)> = Vec::new();
-        if let Ok(dict) = iterable.downcast::<pyo3::types::PyDict>() {
-            for (key, value) in dict.iter() {
-                let column = if let Ok(idx) = key.extract::<u32>() {
-                    idx
-                } else if let Ok(letter) = key.extract::<String>() {
-                    let (_, col) = parse_coordinate(&format!("{}1", letter)).map_err(|_| {
-                        PyValueError::new_err(format!("Invalid column key '{}'", letter))
-                    })?;
-                    col
-                } else {
-                    return Err(PyValueError::new_err(
-                        "dict keys must be column letters or 1-based indices",
-                    ));
+    /// Write a list of dataclass instances, pydantic models or namedtuples
+    /// as rows, one column per field, without converting them to dicts
+    /// first. Field names are read once from the first object, or taken from
+    /// `fields`, which also picks and orders the columns. Plain objects use
+    /// their public instance attributes.
+    ///
+    /// Args:
+    ///     objects: Iterable of objects sharing the same fields
+    ///     fields: Attribute names to write, in column order (default: all)
+    ///     start_row: Starting row (1-indexed, default 1)
+    ///     start_col: Starting column (1-indexed, default 1)
+    ///     header: Write the field names as a header row (default True)
+    ///     style: ImportStyle for the written block, True for the default
+    ///            presentable styling, or None (default) to leave it unstyled
+    ///
+    /// Returns:
+    ///     Dict with rows_written, columns_written, range, column_names
+    #[pyo3(signature = (objects, fields=None, start_row=1, start_col=1, header=true, style=None))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
+    fn write_objects(
+        &self,
+        objects: &Bound<'_, PyAny>,
+        fields: Option<Vec<String>>,
+        start_row: u32,
+        start_col: u32,
+        header: bool,
+        style: Option<&Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use pyo3::types::PyString;
+        use rustypyxl_core::utils::{MAX_COLUMN, MAX_ROW};
+
+        if start_row == 0 || start_col == 0 {
+            return Err(PyValueError::new_err("start_row and start_col are 1-based"));
+        }
+        let style = crate::style::extract_import_style(style)?;
+
+        // Read every value before borrowing the workbook: attribute access
+        // can run arbitrary Python code (properties, __getattr__)
+        let mut names: Option<Vec<String>> = fields;
+        let mut keys: Vec<Bound<'_, PyString>> = Vec::new();
+        let mut rows: Vec<Vec<CellValue>> = Vec::new();
+        for obj in objects.try_iter()? {
+            let obj = obj?;
+            if keys.is_empty() {
+                let field_names = match names.take() {
+                    Some(names) => names,
+                    None => object_field_names(&obj)?,
                 };
-                if column == 0 {
-                    return Err(PyValueError::new_err("Column index must be at least 1"));
+                if field_names.is_empty() {
+                    return Err(PyValueError::new_err("objects have no fields to write"));
                 }
-                cells.push((column, python_to_cell_value(&value)?));
-            }
-        } else {
-            for (i, item) in iterable.try_iter()?.enumerate() {
-                cells.push(((i as u32) + 1, python_to_cell_value(&item?)?));
+                keys = field_names
+                    .iter()
+                    .map(|name| PyString::intern(py, name))
+                    .collect();
+                names = Some(field_names);
             }
+            let row = keys
+                .iter()
+                .map(|key| python_to_cell_value(&obj.getattr(key)?))
+                .collect::<PyResult<Vec<_>>>()?;
+            rows.push(row);
+        }
+        let names = names.unwrap_or_default();
+
+        let num_cols = names.len() as u32;
+        let num_rows = rows.len() as u32 + u32::from(header);
+        if num_cols == 0 || num_rows == 0 {
+            let dict = PyDict::new(py);
+            dict.set_item("rows_written", 0)?;
+            dict.set_item("columns_written", 0)?;
+            dict.set_item("range", py.None())?;
+            dict.set_item("column_names", names)?;
+            return Ok(dict.into());
+        }
+        let end_row = start_row as u64 + num_rows as u64 - 1;
+        let end_col = start_col as u64 + num_cols as u64 - 1;
+        if end_row > MAX_ROW as u64 || end_col > MAX_COLUMN as u64 {
+            return Err(PyValueError::new_err(
+                "objects do not fit in the sheet from the start cell",
+            ));
         }
+        let (end_row, end_col) = (end_row as u32, end_col as u32);
 
-        if let Some(ref wb) = self.workbook {
+        let Some(ref wb) = self.workbook else {
+            return Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        {
             let mut this = wb.borrow_mut(py);
             let idx = self.resolve_index(&this)?;
             let ws = &mut this.inner.worksheets[idx];
-            let target_row = if ws.cells.is_empty() {
-                1
-            } else {
-                ws.dimensions().2 + 1
-            };
-            for (column, cv) in cells {
-                ws.set_cell_value(target_row, column, cv);
+            let mut row = start_row;
+            if header {
+                for (i, name) in names.iter().enumerate() {
+                    ws.set_cell_value(row, start_col + i as u32, CellValue::from(name.as_str()));
+                }
+                row += 1;
+            }
+            for values in rows.iter_mut() {
+                for (i, value) in values.drain(..).enumerate() {
+                    if !matches!(value, CellValue::Empty) {
+                        ws.set_cell_value(row, start_col + i as u32, value);
+                    }
+                }
+                row += 1;
+            }
+            if let Some(style) = style.as_ref() {
+                ws.apply_import_style((start_row, start_col), (end_row, end_col), header, style);
             }
-            Ok(())
-        } else {
-            Err(PyValueError::new_err(
-                "Worksheet is not attached to a workbook",
-            ))
         }
+
+        let dict = PyDict::new(py);
+        dict.set_item("rows_written", rows.len())?;
+        dict.set_item("columns_written", num_cols)?;
+        dict.set_item(
+            "range",
+            format!(
+                "{}:{}",
+                coordinate_from_row_col(start_row, start_col),
+                coordinate_from_row_col(end_row, end_col)
+            ),
+        )?;
+        dict.set_item("column_names", names)?;
+        Ok(dict.into())
     }
 
     /// Insert `amount` blank rows before row `idx` (1-based; openpyxl semantics).
@@ -466,29 +807,100 @@ impl PyWorksheet {
         self.with_sheet_mut(py, |ws| ws.delete_columns(idx, amount.unwrap_or(1)))
     }
 
-    /// Add a chart anchored at `anchor` (e.g. "E1"). It is written on save and
-    /// opens in Excel with the given series, labels, title and legend.
+    /// Write `formula` into `rows` cells down from `start`, shifting its
+    /// relative references one row per cell as Excel's fill-down does, e.g.
+    /// `ws.fill_formula_down("D2", "=B2*C2", rows=10_000)`.
+    fn fill_formula_down(
+        &self,
+        start: &str,
+        formula: &str,
+        rows: u32,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.fill_formula_down(start, formula, rows))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Move the cells in `cell_range` by `rows` and `cols` (openpyxl
+    /// semantics), overwriting the destination. With `translate=True`,
+    /// relative references in the moved formulas shift too.
+    #[pyo3(signature = (cell_range, rows=0, cols=0, translate=false))]
+    fn move_range(
+        &self,
+        cell_range: &str,
+        rows: i64,
+        cols: i64,
+        translate: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| {
+            result = ws.move_range(cell_range, rows, cols, translate)
+        })?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Add a chart to the sheet. It is written on save and opens in Excel with
+    /// its series, labels, title and legend.
     ///
-    /// `chart_type` is one of bar, column, line, area, pie, doughnut, scatter.
-    /// `series` is a value reference like "Sheet1!$B$1:$B$10", or a list whose
-    /// items are such strings or dicts with keys values/name/categories/
-    /// fill_color. `categories` supplies a default category (x-axis) reference
-    /// for series that don't carry their own. `legend` is a position
-    /// (r/l/t/b/tr) or None to hide it.
-    #[pyo3(signature = (chart_type, series, anchor, title=None, categories=None, legend="r"))]
+    /// openpyxl style: pass a `BarChart`, `LineChart` or `PieChart` and the
+    /// anchor cell, e.g. `ws.add_chart(chart, "E5")` (default anchor "E15").
+    ///
+    /// Shorthand: pass a `chart_type` string (bar, column, line, area, pie,
+    /// doughnut, scatter) with `series` and `anchor`. `series` is a value
+    /// reference like "Sheet1!$B$1:$B$10", or a list whose items are such
+    /// strings or dicts with keys values/name/categories/fill_color.
+    /// `categories` supplies a default category (x-axis) reference for series
+    /// that don't carry their own. `legend` is a position (r/l/t/b/tr) or None
+    /// to hide it.
+    #[pyo3(signature = (chart, series=None, anchor=None, title=None, categories=None, legend="r"))]
     #[allow(clippy::too_many_arguments)]
     fn add_chart(
         &self,
-        chart_type: &str,
-        series: &Bound<'_, PyAny>,
-        anchor: &str,
+        chart: &Bound<'_, PyAny>,
+        series: Option<&Bound<'_, PyAny>>,
+        anchor: Option<&str>,
         title: Option<&str>,
         categories: Option<&str>,
         legend: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<()> {
-        use rustypyxl_core::chart::{Chart, ChartAnchor, ChartLegend, ChartType};
+        use rustypyxl_core::chart::{Chart, ChartLegend, ChartType};
+
+        if let Ok(built) = chart.downcast::<crate::chart::PyChartBase>() {
+            // The second positional argument is the anchor here, as in openpyxl.
+            let anchor = match (series, anchor) {
+                (Some(_), Some(_)) => {
+                    return Err(PyValueError::new_err(
+                        "pass the anchor once; series come from the chart object",
+                    ))
+                }
+                (Some(a), None) => a.extract::<String>()?,
+                (None, Some(a)) => a.to_string(),
+                (None, None) => "E15".to_string(),
+            };
+            let built = built.borrow().inner.clone();
+            if built.series.is_empty() {
+                return Err(PyValueError::new_err(
+                    "a chart needs at least one series; call add_data first",
+                ));
+            }
+            rustypyxl_core::utils::parse_coordinate(&anchor.replace('$', ""))
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            return self.with_sheet_mut(py, |ws| ws.add_chart(built, anchor));
+        }
 
+        let chart_type: String = chart.extract().map_err(|_| {
+            PyValueError::new_err(
+                "chart must be a BarChart/LineChart/PieChart or a chart type name",
+            )
+        })?;
+        let (Some(series), Some(anchor)) = (series, anchor) else {
+            return Err(PyValueError::new_err(
+                "a chart type name needs both series and anchor",
+            ));
+        };
         let ctype = match chart_type.to_ascii_lowercase().as_str() {
             "bar" => ChartType::Bar,
             "column" | "col" => ChartType::Column,
@@ -521,7 +933,6 @@ impl PyWorksheet {
             Some(pos) => chart.with_legend(ChartLegend::new().with_position(pos)),
             None => chart.with_legend(ChartLegend::new().with_visible(false)),
         };
-        chart = chart.with_anchor(ChartAnchor::at(anchor));
 
         // series may be a single reference string, a single dict, or a list of
         // refs/dicts.
@@ -543,46 +954,62 @@ impl PyWorksheet {
             return Err(PyValueError::new_err("a chart needs at least one series"));
         }
 
-        self.with_sheet_mut(py, |ws| ws.add_chart(chart))
+        self.with_sheet_mut(py, |ws| ws.add_chart(chart, anchor))
     }
 
     /// Embed an image anchored at `anchor` (e.g. "B2"). It is written into the
     /// saved workbook and opens in Excel.
     ///
-    /// `image` is a filesystem path or the raw image bytes; the format (PNG,
-    /// JPEG, GIF, BMP, TIFF) is detected from the extension or magic bytes.
-    /// Pass `to` for a two-cell anchor that resizes with the cells, or `width`
-    /// and `height` (pixels) to set an explicit size.
-    #[pyo3(signature = (image, anchor, to=None, width=None, height=None, name=None))]
+    /// `image` is an `Image`, an openpyxl `Image`, a filesystem path or the
+    /// raw image bytes; the format (PNG, JPEG, GIF, BMP, TIFF) is detected
+    /// from the extension or magic bytes. Without `anchor`, an `Image` keeps
+    /// its own anchor and anything else goes at "A1", as in openpyxl. Pass
+    /// `to` for a two-cell anchor that resizes with the cells, or `width`
+    /// and `height` (pixels) to override the image's own size.
+    #[pyo3(signature = (image, anchor=None, to=None, width=None, height=None, name=None))]
     #[allow(clippy::too_many_arguments)]
     fn add_image(
         &self,
         image: &Bound<'_, PyAny>,
-        anchor: &str,
+        anchor: Option<&str>,
         to: Option<&str>,
         width: Option<u32>,
         height: Option<u32>,
         name: Option<&str>,
         py: Python<'_>,
     ) -> PyResult<()> {
-        use rustypyxl_core::image::{Image, ImageAnchor};
-
-        let img_anchor = match to {
-            Some(to_cell) => ImageAnchor::two_cell(anchor, to_cell),
-            None => ImageAnchor::one_cell(anchor),
-        };
-
-        // A str/os.PathLike is a path; bytes is the raw image.
-        let mut img = if let Ok(data) = image.extract::<Vec<u8>>() {
-            Image::from_bytes(data, img_anchor)
-                .ok_or_else(|| PyValueError::new_err("unrecognized image format"))?
-        } else if let Ok(path) = image.extract::<std::path::PathBuf>() {
-            Image::from_file(&path, img_anchor)
-                .map_err(|e| PyValueError::new_err(format!("could not read image: {e}")))?
+        use crate::image::PyImage;
+        use rustypyxl_core::image::ImageAnchor;
+
+        let mut img = if let Ok(built) = image.downcast::<PyImage>() {
+            built.borrow().inner.clone()
+        } else if image.hasattr("_data")? {
+            // An openpyxl Image: take its bytes, size and anchor.
+            let data = image.call_method0("_data")?.extract::<Vec<u8>>()?;
+            let mut img =
+                rustypyxl_core::image::Image::from_bytes(data, ImageAnchor::one_cell("A1"))
+                    .ok_or_else(|| PyValueError::new_err("unrecognized image format"))?;
+            if let (Ok(w), Ok(h)) = (
+                image.getattr("width")?.extract::<f64>(),
+                image.getattr("height")?.extract::<f64>(),
+            ) {
+                img = img.with_size_px(w.round() as u32, h.round() as u32);
+            }
+            if let Ok(cell) = image.getattr("anchor")?.extract::<String>() {
+                img.anchor = ImageAnchor::one_cell(cell);
+            }
+            img
         } else {
-            return Err(PyValueError::new_err("image must be a file path or bytes"));
+            PyImage::from_source(image, ImageAnchor::one_cell("A1"))?
         };
 
+        let from = anchor
+            .map(str::to_string)
+            .unwrap_or_else(|| img.anchor.from_cell.clone());
+        img.anchor = match to {
+            Some(to_cell) => ImageAnchor::two_cell(from, to_cell),
+            None => ImageAnchor::one_cell(from),
+        };
         if let (Some(w), Some(h)) = (width, height) {
             img = img.with_size_px(w, h);
         }
@@ -593,6 +1020,17 @@ impl PyWorksheet {
         self.with_sheet_mut(py, |ws| ws.add_image(img))
     }
 
+    /// The images on the sheet, including those read from a loaded file.
+    #[getter]
+    fn images(&self, py: Python<'_>) -> PyResult<Vec<crate::image::PyImage>> {
+        self.with_sheet_ref(py, |ws| {
+            ws.images
+                .iter()
+                .map(|img| crate::image::PyImage { inner: img.clone() })
+                .collect()
+        })
+    }
+
     /// Size a column to fit its content and return the width set (or None if the
     /// column is empty). `column` is 1-based. The width is an estimate from the
     /// displayed text length, not pixel-perfect.
@@ -612,11 +1050,478 @@ impl PyWorksheet {
         self.with_sheet_mut(py, |ws| ws.auto_fit_all())
     }
 
+    /// Size columns to fit their displayed content, taking font size, bold
+    /// and number formats into account. `range` ("A2:D50" or "B:D") limits
+    /// the columns sized and the cells measured; by default every populated
+    /// column is fitted. Returns a dict of column letter to width set.
+    #[pyo3(signature = (range=None))]
+    fn autofit<'py>(&self, range: Option<&str>, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let mut result = Ok(Vec::new());
+        self.with_sheet_mut(py, |ws| result = ws.autofit_columns(range))?;
+        let widths = result.map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let dict = PyDict::new(py);
+        for (column, width) in widths {
+            dict.set_item(rustypyxl_core::utils::column_to_letter(column), width)?;
+        }
+        Ok(dict)
+    }
+
+    /// Displayed text width statistics per populated column, measured the
+    /// way autofit measures (values under their number formats, wide
+    /// characters counting double). `range` ("A2:D50" or "B:D") limits the
+    /// cells measured. Returns a list of dicts with "column" (letter),
+    /// "count", "max", "p95" and "mean", in column order.
+    #[pyo3(signature = (range=None))]
+    fn column_text_widths<'py>(
+        &self,
+        range: Option<&str>,
+        py: Python<'py>,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let stats = self
+            .with_sheet_ref(py, |ws| ws.column_text_widths(range))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        stats
+            .into_iter()
+            .map(|s| {
+                let dict = PyDict::new(py);
+                dict.set_item("column", rustypyxl_core::utils::column_to_letter(s.column))?;
+                dict.set_item("count", s.count)?;
+                dict.set_item("max", s.max)?;
+                dict.set_item("p95", s.p95)?;
+                dict.set_item("mean", s.mean)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// Stripe a range's rows with alternating fills. `color_a` fills the
+    /// first banded row, `color_b` the next; either may be an rgb string, a
+    /// Color, or None for no fill. With `header`, the first row is skipped.
+    /// Existing fonts, borders and number formats are kept, and all the
+    /// banded cells share two styles instead of one per cell.
+    #[pyo3(signature = (range, color_a, color_b, header=true))]
+    fn apply_banding(
+        &self,
+        range: &str,
+        color_a: Option<&Bound<'_, PyAny>>,
+        color_b: Option<&Bound<'_, PyAny>>,
+        header: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let color_a = crate::style::coerce_color(color_a)?;
+        let color_b = crate::style::coerce_color(color_b)?;
+        let Some(ref wb) = self.workbook else {
+            return Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        let mut this = wb.borrow_mut(py);
+        let idx = self.resolve_index(&this)?;
+        let name = this.inner.sheet_names()[idx].clone();
+        this.inner
+            .apply_banding(&name, range, color_a, color_b, header)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Lay out a `Report` on this sheet, starting at A1 (or two rows below
+    /// existing content). Returns where things went: a dict with
+    /// "sections" (one range per section), "tables" (header_row,
+    /// first_data_row, last_data_row, totals_row and range per table) and
+    /// "last_row".
+    fn write_report(&self, report: &crate::report::PyReport, py: Python<'_>) -> PyResult<PyObject> {
+        let Some(ref wb) = self.workbook else {
+            return Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        let layout = {
+            let mut this = wb.borrow_mut(py);
+            let idx = self.resolve_index(&this)?;
+            this.inner.worksheets[idx].write_report(&report.inner)
+        };
+        crate::report::layout_to_python(py, &layout)
+    }
+
+    /// Render the sheet, or `range` such as "A1:D20", as an HTML `<table>`
+    /// string: formatted values, merges as rowspan/colspan and, with
+    /// `include_styles`, fonts, fills, borders and alignment as inline CSS.
+    #[pyo3(signature = (range=None, include_styles=true))]
+    fn to_html(
+        &self,
+        range: Option<&str>,
+        include_styles: bool,
+        py: Python<'_>,
+    ) -> PyResult<String> {
+        self.with_sheet_ref(py, |ws| ws.to_html(range, include_styles))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Render the sheet, or `range`, as an aligned text table with
+    /// formatted values. `width_policy` is "content" (fit the longest
+    /// value), "sheet" (the sheet's column widths) or a fixed width in
+    /// characters; `markdown` gives a pipe table headed by the first row.
+    #[pyo3(signature = (range=None, width_policy=None, markdown=false))]
+    fn to_text_grid(
+        &self,
+        range: Option<&str>,
+        width_policy: Option<&Bound<'_, PyAny>>,
+        markdown: bool,
+        py: Python<'_>,
+    ) -> PyResult<String> {
+        use rustypyxl_core::WidthPolicy;
+        let policy = match width_policy {
+            None => WidthPolicy::Content,
+            Some(value) => match value.extract::<usize>() {
+                Ok(width) => WidthPolicy::Fixed(width),
+                Err(_) => match value.extract::<String>()?.as_str() {
+                    "content" => WidthPolicy::Content,
+                    "sheet" => WidthPolicy::Sheet,
+                    other => {
+                        return Err(PyValueError::new_err(format!(
+                            "width_policy must be 'content', 'sheet' or a width, not {other:?}"
+                        )))
+                    }
+                },
+            },
+        };
+        self.with_sheet_ref(py, |ws| ws.to_text_grid(range, policy, markdown))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Export the sheet, or `range` such as "A1:D20", to a CSV file,
+    /// streaming one row at a time.
+    ///
+    /// Args:
+    ///     path: Output path for the CSV file
+    ///     range: Range to export (default: the used range)
+    ///     delimiter: Field delimiter (default ",")
+    ///     quotechar: Quote character (default '"')
+    ///     quoting: "minimal" (default), "all", "nonnumeric" or "none"
+    ///     encoding: "utf-8" (default), "utf-8-sig" (with a byte-order mark,
+    ///               which Excel needs to read UTF-8), "latin-1" or "cp1252"
+    ///     line_terminator: Record terminator (default "\r\n")
+    ///     date_format: strftime format for dates (default ISO 8601)
+    ///
+    /// Returns:
+    ///     Dict with export results: rows_exported, columns_exported, file_size
+    #[pyo3(signature = (path, range=None, delimiter=",", quotechar="\"", quoting="minimal", encoding="utf-8", line_terminator="\r\n", date_format=None))]
+    // Mirrors a Python keyword-argument API
+    #[allow(clippy::too_many_arguments)]
+    fn export_to_csv(
+        &self,
+        path: std::path::PathBuf,
+        range: Option<&str>,
+        delimiter: &str,
+        quotechar: &str,
+        quoting: &str,
+        encoding: &str,
+        line_terminator: &str,
+        date_format: Option<&str>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use crate::workbook::{csv_char, csv_encoding};
+        use rustypyxl_core::{CsvExportOptions, CsvQuoting};
+
+        let quoting = match quoting.to_lowercase().as_str() {
+            "minimal" => CsvQuoting::Minimal,
+            "all" => CsvQuoting::All,
+            "nonnumeric" => CsvQuoting::NonNumeric,
+            "none" => CsvQuoting::Never,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid quoting: {}. Use 'minimal', 'all', 'nonnumeric', or 'none'",
+                    quoting
+                )))
+            }
+        };
+        let mut opts = CsvExportOptions::new()
+            .with_delimiter(csv_char("delimiter", delimiter)?)
+            .with_quote(csv_char("quotechar", quotechar)?)
+            .with_quoting(quoting)
+            .with_encoding(csv_encoding(encoding)?)
+            .with_bom(encoding.to_lowercase().replace('_', "-").ends_with("-sig"))
+            .with_line_terminator(line_terminator);
+        opts.date_format = date_format.map(str::to_string);
+        opts.range = range.map(str::to_string);
+
+        let result = self
+            .with_sheet_ref(py, |ws| ws.export_to_csv(&path, Some(opts)))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("rows_exported", result.rows_exported)?;
+        dict.set_item("columns_exported", result.columns_exported)?;
+        dict.set_item("file_size", result.file_size)?;
+        Ok(dict.into())
+    }
+
+    /// The sheet, or `range`, as a JSON array of row objects keyed by the
+    /// header row. Numbers and booleans keep their JSON types, dates are
+    /// strings, formulas give their cached result and empty cells are null.
+    ///
+    /// Args:
+    ///     range: Range to export (default: the used range)
+    ///     header: The first row holds the keys (default True); otherwise
+    ///             the keys are "Column1", "Column2", ...
+    ///     skip_nulls: Leave empty cells out of each object (default False)
+    ///     date_format: strftime format for dates (default ISO 8601)
+    #[pyo3(signature = (range=None, header=true, skip_nulls=false, date_format=None))]
+    fn to_json(
+        &self,
+        range: Option<&str>,
+        header: bool,
+        skip_nulls: bool,
+        date_format: Option<&str>,
+        py: Python<'_>,
+    ) -> PyResult<String> {
+        let opts = json_options(range, header, skip_nulls, date_format);
+        self.with_sheet_ref(py, |ws| ws.to_json(&opts))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write the same row objects as `to_json` to `path` as newline-delimited
+    /// JSON, one object per line. Returns the number of objects written.
+    #[pyo3(signature = (path, range=None, header=true, skip_nulls=false, date_format=None))]
+    fn write_ndjson(
+        &self,
+        path: std::path::PathBuf,
+        range: Option<&str>,
+        header: bool,
+        skip_nulls: bool,
+        date_format: Option<&str>,
+        py: Python<'_>,
+    ) -> PyResult<u32> {
+        use std::io::Write;
+
+        let opts = json_options(range, header, skip_nulls, date_format);
+        self.with_sheet_ref(py, |ws| -> rustypyxl_core::Result<u32> {
+            let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            let written = ws.write_ndjson(&mut out, &opts)?;
+            out.flush()?;
+            Ok(written)
+        })?
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Apply SUM, AVERAGE, MIN, MAX, COUNT, COUNTA or COUNTIF to `range`
+    /// without writing a formula, e.g. `ws.compute("SUM", "B2:B1000")` or
+    /// `ws.compute("COUNTIF", "C2:C500", ">100")`. Text and booleans are
+    /// skipped as Excel skips them; formula cells count with their cached
+    /// result. An Excel error comes back as its string, e.g. "#DIV/0!".
+    #[pyo3(signature = (function, range, criteria=None))]
+    fn compute(
+        &self,
+        function: &str,
+        range: &str,
+        criteria: Option<&str>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        let value = self
+            .with_sheet_ref(py, |ws| ws.compute(function, range, criteria))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(crate::workbook::formula_value_to_python(value, py))
+    }
+
+    /// Apply a named style from the workbook's style palette (see
+    /// `wb.styles.from_json`) to every cell in `range`. Components the style
+    /// leaves unset keep each cell's own value.
+    fn apply_named_style(&self, range: &str, name: &str, py: Python<'_>) -> PyResult<()> {
+        let Some(ref wb) = self.workbook else {
+            return Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        let mut this = wb.borrow_mut(py);
+        let idx = self.resolve_index(&this)?;
+        let sheet = this.inner.sheet_names()[idx].clone();
+        this.inner
+            .apply_named_style(&sheet, range, name)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Show the numbers in a range in a scaled unit, e.g. 42000 as "42K".
+    /// `style` is "thousands", "millions", "billions", "units", or "auto"
+    /// (default) to choose from the largest value in the range. Text, dates
+    /// and blanks are left untouched. Returns the scale applied.
+    #[pyo3(signature = (range, style="auto"))]
+    fn apply_scaled_number_format(
+        &self,
+        range: &str,
+        style: &str,
+        py: Python<'_>,
+    ) -> PyResult<String> {
+        use rustypyxl_core::NumberScale;
+
+        let scale = if style.eq_ignore_ascii_case("auto") {
+            None
+        } else {
+            Some(NumberScale::parse(style).ok_or_else(|| {
+                PyValueError::new_err(format!(
+                    "Unknown scale '{}': expected 'auto', 'units', 'thousands', 'millions' or 'billions'",
+                    style
+                ))
+            })?)
+        };
+        let mut result = Ok(NumberScale::Units);
+        self.with_sheet_mut(py, |ws| {
+            result = ws.apply_scaled_number_format(range, scale)
+        })?;
+        let applied = result.map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(match applied {
+            NumberScale::Units => "units",
+            NumberScale::Thousands => "thousands",
+            NumberScale::Millions => "millions",
+            NumberScale::Billions => "billions",
+        }
+        .to_string())
+    }
+
+    /// Write tabular data from any object implementing the Arrow PyCapsule
+    /// stream interface (`__arrow_c_stream__`): a pyarrow Table or
+    /// RecordBatchReader, a polars or pandas (2.2+) DataFrame, a duckdb
+    /// relation, and so on. The batches are converted to cells in Rust without
+    /// building Python objects per value.
+    ///
+    /// Args:
+    ///     table: The Arrow stream source
+    ///     start_row: Starting row (1-indexed, default 1)
+    ///     start_col: Starting column (1-indexed, default 1)
+    ///     include_header: Write the column names as a header row (default True)
+    ///     style: ImportStyle for the written block, True for the default
+    ///            presentable styling, or None (default) to leave it unstyled
+    ///
+    /// Returns:
+    ///     Dict with rows_written, columns_written, range, column_names
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (table, start_row=1, start_col=1, include_header=true, style=None))]
+    fn write_arrow(
+        &self,
+        table: &Bound<'_, PyAny>,
+        start_row: u32,
+        start_col: u32,
+        include_header: bool,
+        style: Option<&Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+        use arrow::record_batch::RecordBatchReader;
+        use pyo3::exceptions::PyTypeError;
+        use pyo3::types::PyCapsule;
+
+        if start_row == 0 || start_col == 0 {
+            return Err(PyValueError::new_err("start_row and start_col are 1-based"));
+        }
+        if !table.hasattr("__arrow_c_stream__")? {
+            return Err(PyTypeError::new_err(
+                "expected an object implementing __arrow_c_stream__ (e.g. a pyarrow Table)",
+            ));
+        }
+        let capsule = table.call_method0("__arrow_c_stream__")?;
+        let capsule = capsule.downcast::<PyCapsule>()?;
+        if capsule.name()?.map(|n| n.to_bytes()) != Some(b"arrow_array_stream".as_slice()) {
+            return Err(PyTypeError::new_err(
+                "__arrow_c_stream__ did not return an 'arrow_array_stream' capsule",
+            ));
+        }
+        // SAFETY: the capsule name guarantees it wraps an initialized
+        // ArrowArrayStream. from_raw moves the stream out and leaves a
+        // released one behind, so the capsule destructor becomes a no-op.
+        let stream = unsafe { FFI_ArrowArrayStream::from_raw(capsule.pointer().cast()) };
+        let reader = ArrowArrayStreamReader::try_new(stream)
+            .map_err(|e| PyValueError::new_err(format!("invalid Arrow stream: {}", e)))?;
+        let schema = reader.schema();
+        let style = crate::style::extract_import_style(style)?;
+
+        let mut imported = None;
+        self.with_sheet_mut(py, |ws| {
+            imported = Some(ws.insert_record_batches(
+                &schema,
+                reader,
+                start_row,
+                start_col,
+                include_header,
+                style.as_ref(),
+            ))
+        })?;
+        let result = imported
+            .expect("with_sheet_mut runs the closure or fails")
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("rows_written", result.rows_imported)?;
+        dict.set_item("columns_written", result.columns_imported)?;
+        dict.set_item("range", result.range_with_headers())?;
+        dict.set_item("column_names", result.column_names)?;
+        Ok(dict.into())
+    }
+
+    /// Write a pandas DataFrame (or any Arrow stream source, as for
+    /// `write_arrow`) through Arrow instead of a Python loop over its values.
+    /// `index` writes the DataFrame's index as leading columns; `header`
+    /// writes the column names. Needs pyarrow for pandas input.
+    ///
+    /// Returns:
+    ///     Dict with rows_written, columns_written, range, column_names
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (df, start_row=1, start_col=1, index=false, header=true, style=None))]
+    #[allow(clippy::too_many_arguments, clippy::wrong_self_convention)]
+    fn from_dataframe(
+        &self,
+        df: &Bound<'_, PyAny>,
+        start_row: u32,
+        start_col: u32,
+        index: bool,
+        header: bool,
+        style: Option<&Bound<'_, PyAny>>,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        let module = df.get_type().getattr("__module__")?.extract::<String>()?;
+        let table = if module.starts_with("pandas") {
+            // reset_index puts the index first; pyarrow would append it last.
+            let df = if index {
+                df.call_method0("reset_index")?
+            } else {
+                df.clone()
+            };
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("preserve_index", false)?;
+            py.import("pyarrow")?.getattr("Table")?.call_method(
+                "from_pandas",
+                (df,),
+                Some(&kwargs),
+            )?
+        } else {
+            df.clone()
+        };
+        self.write_arrow(&table, start_row, start_col, header, style, py)
+    }
+
+    /// The sheet, or `range` such as "A1:D20", as a pandas DataFrame built
+    /// from Arrow columns. With `header` the first row names the columns.
+    /// Columns are typed from their cells: integers, floats, booleans,
+    /// datetimes, and text for anything mixed. Needs pandas and pyarrow.
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (range=None, header=true))]
+    fn to_dataframe(
+        &self,
+        range: Option<&str>,
+        header: bool,
+        py: Python<'_>,
+    ) -> PyResult<PyObject> {
+        let batch = self
+            .with_sheet_ref(py, |ws| ws.to_record_batch(range, header))?
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let stream = Py::new(py, crate::arrow_stream::PyArrowStream { batch })?;
+        let table = py.import("pyarrow")?.call_method1("table", (stream,))?;
+        Ok(table.call_method0("to_pandas")?.unbind())
+    }
+
     /// Add an Excel table (ListObject) over a cell range. `name` is the table
     /// name, `ref` its range (e.g. "A1:C10"). `style` is a table style name
     /// like "TableStyleMedium9". `headers` names the columns (defaults to the
     /// values in the header row). The remaining flags toggle the table's
-    /// display options.
+    /// display options. Raises ValueError for an invalid or duplicate name
+    /// or a range that overlaps another table. Returns the new Table.
     #[pyo3(signature = (name, r#ref, style=None, headers=None, totals_row=false, header_row=true, first_column=false, last_column=false, row_stripes=true, column_stripes=false, auto_filter=true))]
     #[allow(clippy::too_many_arguments)]
     fn add_table(
@@ -633,28 +1538,96 @@ impl PyWorksheet {
         column_stripes: bool,
         auto_filter: bool,
         py: Python<'_>,
-    ) -> PyResult<()> {
-        use rustypyxl_core::table::{Table, TableStyle};
+    ) -> PyResult<crate::table::PyTable> {
+        use rustypyxl_core::table::{TableColumn, TableStyle};
 
-        let id = self.with_sheet_ref(py, |ws| ws.tables.len() as u32 + 1)?;
-        let mut table = match &headers {
-            Some(h) => {
-                let refs: Vec<&str> = h.iter().map(|s| s.as_str()).collect();
-                Table::with_headers(id, name, r#ref, &refs)
+        let style = style.map(TableStyle::Custom).unwrap_or_default();
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        {
+            let mut this = wb.borrow_mut(py);
+            let idx = self.resolve_index(&this)?;
+            let table = this.inner.worksheets[idx]
+                .create_table(name, r#ref, style)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            match &headers {
+                Some(h) if h.len() != table.columns.len() => {
+                    return Err(PyValueError::new_err(format!(
+                        "headers has {} names but the table is {} columns wide",
+                        h.len(),
+                        table.columns.len()
+                    )));
+                }
+                Some(h) => {
+                    for (column, header) in table.columns.iter_mut().zip(h) {
+                        column.name = header.clone();
+                    }
+                }
+                None if !header_row => {
+                    for (i, column) in table.columns.iter_mut().enumerate() {
+                        *column = TableColumn::new(i as u32 + 1, &format!("Column{}", i + 1));
+                    }
+                }
+                None => {}
             }
-            None => Table::new(id, name, r#ref),
-        };
-        table.header_row = header_row;
-        table.totals_row = totals_row;
-        table.show_first_column = first_column;
-        table.show_last_column = last_column;
-        table.show_row_stripes = row_stripes;
-        table.show_column_stripes = column_stripes;
-        table.auto_filter = auto_filter;
-        if let Some(s) = style {
-            table.style = TableStyle::Custom(s);
+            table.header_row = header_row;
+            table.totals_row = totals_row;
+            table.show_first_column = first_column;
+            table.show_last_column = last_column;
+            table.show_row_stripes = row_stripes;
+            table.show_column_stripes = column_stripes;
+            table.auto_filter = auto_filter;
         }
-        self.with_sheet_mut(py, |ws| ws.add_table(table))
+        self.get_table(name, py)
+    }
+
+    /// The table named `name` (case-insensitive).
+    fn get_table(&self, name: &str, py: Python<'_>) -> PyResult<crate::table::PyTable> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        let name = self
+            .with_sheet_ref(py, |ws| {
+                ws.tables
+                    .iter()
+                    .find(|t| t.name.eq_ignore_ascii_case(name))
+                    .map(|t| t.name.clone())
+            })?
+            .ok_or_else(|| {
+                pyo3::exceptions::PyKeyError::new_err(format!("Table '{}' does not exist", name))
+            })?;
+        Ok(crate::table::PyTable {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+            name,
+        })
+    }
+
+    /// Fill `target_col` (a letter or 1-based index) with a formula on each
+    /// of `rows` (first, last), e.g.
+    /// `ws.add_calculated_column("D", "=[@Qty]*[@Price]")`. `[@Col]` is the
+    /// value of column `Col` on the same row. Inside a table the formula
+    /// keeps structured references, `rows` defaults to the data rows, and a
+    /// full fill becomes the table column's calculated formula; elsewhere
+    /// `rows` is required, `[@Col]` names a header on the row above, and
+    /// A1 references are written. Returns the (first, last) rows filled.
+    #[pyo3(signature = (target_col, formula_template, rows=None))]
+    fn add_calculated_column(
+        &self,
+        target_col: &Bound<'_, PyAny>,
+        formula_template: &str,
+        rows: Option<(u32, u32)>,
+        py: Python<'_>,
+    ) -> PyResult<(u32, u32)> {
+        let target_col = column_key(target_col)?;
+        let mut result = Ok((0, 0));
+        self.with_sheet_mut(py, |ws| {
+            result = ws.add_calculated_column(target_col, formula_template, rows)
+        })?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
     /// Configure page setup for printing. `orientation` is "portrait" or
@@ -780,105 +1753,440 @@ impl PyWorksheet {
         })
     }
 
-    /// The print area range (e.g. "A1:D20"), or None.
-    #[getter]
-    fn print_area(&self, py: Python<'_>) -> PyResult<Option<String>> {
-        self.with_sheet_ref(py, |ws| {
-            ws.page_setup.as_ref().and_then(|ps| ps.print_area.clone())
+    /// The print area range (e.g. "A1:D20"), or None.
+    #[getter]
+    fn print_area(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_sheet_ref(py, |ws| {
+            ws.page_setup.as_ref().and_then(|ps| ps.print_area.clone())
+        })
+    }
+
+    /// Set the print area range. A list of ranges sets a multi-part area.
+    #[setter]
+    fn set_print_area(&self, py: Python<'_>, area: Option<Bound<'_, PyAny>>) -> PyResult<()> {
+        let area = match area.filter(|a| !a.is_none()) {
+            None => None,
+            Some(a) => match a.extract::<String>() {
+                Ok(text) => Some(text),
+                Err(_) => Some(a.extract::<Vec<String>>()?.join(",")),
+            },
+        };
+        self.with_sheet_mut(py, |ws| {
+            ws.page_setup
+                .get_or_insert_with(rustypyxl_core::pagesetup::PageSetup::new)
+                .print_area = area.filter(|a| !a.is_empty());
+        })
+    }
+
+    /// Rows repeated at the top of every printed page (e.g. "1:2"), or None.
+    #[getter]
+    fn print_title_rows(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_sheet_ref(py, |ws| {
+            ws.page_setup
+                .as_ref()
+                .and_then(|ps| ps.print_titles.rows.clone())
+        })
+    }
+
+    #[setter]
+    fn set_print_title_rows(&self, py: Python<'_>, rows: Option<String>) -> PyResult<()> {
+        if let Some(r) = rows.as_deref() {
+            let valid = r.split(':').count() == 2
+                && r.split(':')
+                    .all(|p| p.trim_start_matches('$').parse::<u32>().is_ok());
+            if !valid {
+                return Err(PyValueError::new_err(format!(
+                    "print_title_rows must be a row range like '1:2', got {r:?}"
+                )));
+            }
+        }
+        self.with_sheet_mut(py, |ws| {
+            ws.page_setup
+                .get_or_insert_with(rustypyxl_core::pagesetup::PageSetup::new)
+                .print_titles
+                .rows = rows;
+        })
+    }
+
+    /// Columns repeated at the left of every printed page (e.g. "A:B"), or None.
+    #[getter]
+    fn print_title_cols(&self, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_sheet_ref(py, |ws| {
+            ws.page_setup
+                .as_ref()
+                .and_then(|ps| ps.print_titles.cols.clone())
+        })
+    }
+
+    #[setter]
+    fn set_print_title_cols(&self, py: Python<'_>, cols: Option<String>) -> PyResult<()> {
+        if let Some(c) = cols.as_deref() {
+            let valid = c.split(':').count() == 2
+                && c.split(':').all(|p| {
+                    let p = p.trim_start_matches('$');
+                    !p.is_empty() && p.chars().all(|ch| ch.is_ascii_alphabetic())
+                });
+            if !valid {
+                return Err(PyValueError::new_err(format!(
+                    "print_title_cols must be a column range like 'A:B', got {c:?}"
+                )));
+            }
+        }
+        self.with_sheet_mut(py, |ws| {
+            ws.page_setup
+                .get_or_insert_with(rustypyxl_core::pagesetup::PageSetup::new)
+                .print_titles
+                .cols = cols.map(|c| c.to_ascii_uppercase());
+        })
+    }
+
+    /// Add a conditional-formatting rule over a cell range. `rule` is a dict
+    /// describing the rule; supported forms:
+    ///   {"type":"cellIs","operator":"greaterThan","formula":"5","fill":"FF0000"}
+    ///   {"type":"expression","formula":"$A1>0","font_color":"FF0000","bold":true}
+    ///   {"type":"colorScale","preset":"red_yellow_green"}
+    ///   {"type":"dataBar","color":"638EC6"}
+    ///   {"type":"top","rank":10,"fill":"FFFF00"}
+    ///   {"type":"containsText","text":"x","fill":"FF0000"}
+    ///   {"type":"duplicateValues"} / "uniqueValues" / "aboveAverage" / "belowAverage"
+    /// Format keys fill, font_color, and bold apply where a rule supports a
+    /// differential format.
+    fn add_conditional_formatting(
+        &self,
+        cells: &str,
+        rule: &Bound<'_, PyDict>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        use rustypyxl_core::conditional::ConditionalFormatting;
+        let built = build_conditional_rule(rule)?;
+        let mut cf = ConditionalFormatting::new(cells);
+        cf.add_rule(built);
+        self.with_sheet_mut(py, |ws| ws.add_conditional_formatting(cf))
+    }
+
+    /// Add a data-validation rule. Pass a `DataValidation` (rustypyxl's or
+    /// openpyxl's), as in openpyxl, or describe one inline over a range
+    /// (e.g. "A1:A10" or "A1:A10 C1:C10"): `type` is one of whole, decimal,
+    /// list, date, time, textLength, custom. `formula1` (and `formula2` for
+    /// between/notBetween) supply the constraint -- for a list, `formula1`
+    /// is like '"A,B,C"' or a range. `operator` is between, notBetween,
+    /// equal, notEqual, greaterThan, lessThan, greaterThanOrEqual, or
+    /// lessThanOrEqual.
+    ///
+    /// A rustypyxl `DataValidation` stays linked: cells added to it later
+    /// with `dv.add(...)` are covered too. An openpyxl one is copied.
+    #[pyo3(signature = (cells, r#type=None, formula1=None, formula2=None, operator=None, allow_blank=true, show_error=true, error_title=None, error=None, show_input=true, prompt_title=None, prompt=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn add_data_validation(
+        &self,
+        cells: &Bound<'_, PyAny>,
+        r#type: Option<&str>,
+        formula1: Option<String>,
+        formula2: Option<String>,
+        operator: Option<String>,
+        allow_blank: bool,
+        show_error: bool,
+        error_title: Option<String>,
+        error: Option<String>,
+        show_input: bool,
+        prompt_title: Option<String>,
+        prompt: Option<String>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        use crate::validation::PyDataValidation;
+        use rustypyxl_core::DataValidation;
+
+        if let Ok(dv) = cells.downcast::<PyDataValidation>() {
+            return dv.borrow_mut().attach(self, py);
+        }
+        let dv = match cells.extract::<String>() {
+            Ok(cells) => {
+                let Some(validation_type) = r#type else {
+                    return Err(pyo3::exceptions::PyTypeError::new_err(
+                        "add_data_validation() needs a type when given a range",
+                    ));
+                };
+                DataValidation {
+                    validation_type: validation_type.to_string(),
+                    operator,
+                    formula1,
+                    formula2,
+                    error_style: None,
+                    allow_blank,
+                    show_error,
+                    error_title,
+                    error_message: error,
+                    show_input,
+                    prompt_title,
+                    prompt_message: prompt,
+                    hide_dropdown: false,
+                    sqref: Some(cells),
+                }
+            }
+            Err(_) => PyDataValidation::from_openpyxl(cells)?,
+        };
+        let mut result = Ok((0, 0));
+        self.with_sheet_mut(py, |ws| result = ws.add_validation(dv))?;
+        result
+            .map(|_| ())
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Lay out the comment box of `cell` (e.g. "B2"): `visible` keeps it on
+    /// screen instead of showing on hover, `width`/`height` size it in
+    /// points (default 108 x 59.25), and `anchor` is the cell at its top-left
+    /// corner (default: just right of `cell`).
+    #[pyo3(signature = (cell, visible=false, width=None, height=None, anchor=None))]
+    fn set_comment_shape(
+        &self,
+        cell: &str,
+        visible: bool,
+        width: Option<f64>,
+        height: Option<f64>,
+        anchor: Option<&str>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        use rustypyxl_core::CommentShape;
+
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mut shape = CommentShape::new().with_visible(visible);
+        shape.width = width.unwrap_or(shape.width);
+        shape.height = height.unwrap_or(shape.height);
+        if shape.width <= 0.0 || shape.height <= 0.0 {
+            return Err(PyValueError::new_err(
+                "comment box width and height must be positive",
+            ));
+        }
+        if let Some(anchor) = anchor {
+            let (r, c) =
+                parse_coordinate(anchor).map_err(|e| PyValueError::new_err(e.to_string()))?;
+            shape = shape.with_anchor(r, c);
+        }
+        self.with_sheet_mut(py, |ws| ws.set_comment_shape(row, col, shape))
+    }
+
+    /// The comment box layout set for `cell` as a dict with keys visible,
+    /// width, height and anchor, or None when it uses the default box.
+    fn get_comment_shape(&self, cell: &str, py: Python<'_>) -> PyResult<PyObject> {
+        use pyo3::types::PyDict;
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let shape = self.with_sheet_ref(py, |ws| ws.get_comment_shape(row, col).cloned())?;
+        let Some(shape) = shape else {
+            return Ok(py.None());
+        };
+        let d = PyDict::new(py);
+        d.set_item("visible", shape.visible)?;
+        d.set_item("width", shape.width)?;
+        d.set_item("height", shape.height)?;
+        d.set_item(
+            "anchor",
+            shape.anchor.map(|(r, c)| coordinate_from_row_col(r, c)),
+        )?;
+        Ok(d.into_any().unbind())
+    }
+
+    /// The comment on `cell` (e.g. "B2") with its author, box size and, for
+    /// a threaded comment, the conversation; None when it has none.
+    fn get_comment(&self, cell: &str, py: Python<'_>) -> PyResult<Option<PyComment>> {
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.with_sheet_ref(py, |ws| {
+            ws.get_cell(row, col)
+                .as_deref()
+                .and_then(|c| c.comment.as_deref())
+                .map(PyComment::from_core)
+        })
+    }
+
+    /// The hyperlink on `cell` (e.g. "B2") with its tooltip and display
+    /// text; None when it has none.
+    fn get_hyperlink(&self, cell: &str, py: Python<'_>) -> PyResult<Option<PyHyperlink>> {
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.with_sheet_ref(py, |ws| {
+            ws.get_cell(row, col)
+                .as_deref()
+                .and_then(|c| c.hyperlink.as_deref())
+                .map(PyHyperlink::from_core)
+        })
+    }
+
+    /// Start a threaded comment on `cell`, or reply to the one already
+    /// there. Raises ValueError if the cell has a plain note.
+    fn add_threaded_comment(
+        &self,
+        cell: &str,
+        text: &str,
+        author: &str,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| {
+            result = ws.add_threaded_comment(row, col, author, text)
+        })?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Attach a hidden tag (e.g. a record id) to a row. The tag is saved so
+    /// it follows the row when rows are inserted or deleted in Excel; use
+    /// `find_row_by_tag` after reloading to find where it went.
+    fn set_row_tag(&self, row: u32, tag: String, py: Python<'_>) -> PyResult<()> {
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.set_row_tag(row, tag))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// The tag on a row, or None.
+    fn get_row_tag(&self, row: u32, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_sheet_ref(py, |ws| ws.get_row_tag(row).map(str::to_string))
+    }
+
+    /// Remove a row's tag.
+    fn remove_row_tag(&self, row: u32, py: Python<'_>) -> PyResult<()> {
+        self.with_sheet_mut(py, |ws| {
+            ws.remove_row_tag(row);
+        })
+    }
+
+    /// The row number carrying `tag`, or None.
+    fn find_row_by_tag(&self, tag: &str, py: Python<'_>) -> PyResult<Option<u32>> {
+        self.with_sheet_ref(py, |ws| ws.find_row_by_tag(tag))
+    }
+
+    /// Every row tag as a dict of row number to tag.
+    #[getter]
+    fn row_tags(&self, py: Python<'_>) -> PyResult<std::collections::BTreeMap<u32, String>> {
+        self.with_sheet_ref(py, |ws| {
+            ws.row_tags
+                .iter()
+                .map(|(row, tag)| (*row, tag.clone()))
+                .collect()
+        })
+    }
+
+    /// Link a cell (e.g. "B5") to `url` in one call. The cell shows
+    /// `display`, or the URL when it has no value yet; with `styled`, its
+    /// font gets Excel's Hyperlink look (blue, underlined), keeping the rest.
+    /// `url` may also be a Hyperlink, to give it a tooltip.
+    #[pyo3(signature = (cell, url, display=None, styled=true))]
+    fn set_hyperlink(
+        &self,
+        cell: &str,
+        url: &Bound<'_, PyAny>,
+        display: Option<&str>,
+        styled: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let link = crate::hyperlink::extract_hyperlink(url)?;
+        self.with_sheet_mut(py, |ws| ws.set_hyperlink(row, col, link, display, styled))
+    }
+
+    /// Attach a hidden tag to a cell (e.g. "B5"); it follows the cell like a
+    /// row tag follows its row.
+    fn set_cell_tag(&self, cell: &str, tag: String, py: Python<'_>) -> PyResult<()> {
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.set_cell_tag(row, col, tag))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// The tag on a cell, or None.
+    fn get_cell_tag(&self, cell: &str, py: Python<'_>) -> PyResult<Option<String>> {
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.with_sheet_ref(py, |ws| ws.get_cell_tag(row, col).map(str::to_string))
+    }
+
+    /// Remove a cell's tag.
+    fn remove_cell_tag(&self, cell: &str, py: Python<'_>) -> PyResult<()> {
+        let (row, col) =
+            parse_coordinate(cell).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.with_sheet_mut(py, |ws| {
+            ws.remove_cell_tag(row, col);
         })
     }
 
-    /// Set the print area range.
-    #[setter]
-    fn set_print_area(&self, py: Python<'_>, area: Option<String>) -> PyResult<()> {
-        self.with_sheet_mut(py, |ws| {
-            ws.page_setup
-                .get_or_insert_with(rustypyxl_core::pagesetup::PageSetup::new)
-                .print_area = area;
+    /// The coordinate (e.g. "B5") of the cell carrying `tag`, or None.
+    fn find_cell_by_tag(&self, tag: &str, py: Python<'_>) -> PyResult<Option<String>> {
+        self.with_sheet_ref(py, |ws| {
+            ws.find_cell_by_tag(tag)
+                .map(|(row, col)| coordinate_from_row_col(row, col))
         })
     }
 
-    /// Add a conditional-formatting rule over a cell range. `rule` is a dict
-    /// describing the rule; supported forms:
-    ///   {"type":"cellIs","operator":"greaterThan","formula":"5","fill":"FF0000"}
-    ///   {"type":"expression","formula":"$A1>0","font_color":"FF0000","bold":true}
-    ///   {"type":"colorScale","preset":"red_yellow_green"}
-    ///   {"type":"dataBar","color":"638EC6"}
-    ///   {"type":"top","rank":10,"fill":"FFFF00"}
-    ///   {"type":"containsText","text":"x","fill":"FF0000"}
-    ///   {"type":"duplicateValues"} / "uniqueValues" / "aboveAverage" / "belowAverage"
-    /// Format keys fill, font_color, and bold apply where a rule supports a
-    /// differential format.
-    fn add_conditional_formatting(
+    /// The defined names scoped to this sheet, as {name: reference}.
+    #[getter]
+    fn defined_names(
         &self,
-        cells: &str,
-        rule: &Bound<'_, PyDict>,
         py: Python<'_>,
-    ) -> PyResult<()> {
-        use rustypyxl_core::conditional::ConditionalFormatting;
-        let built = build_conditional_rule(rule)?;
-        let mut cf = ConditionalFormatting::new(cells);
-        cf.add_rule(built);
-        self.with_sheet_mut(py, |ws| ws.add_conditional_formatting(cf))
+    ) -> PyResult<std::collections::BTreeMap<String, String>> {
+        let Some(ref wb) = self.workbook else {
+            return Ok(Default::default());
+        };
+        let this = wb.borrow(py);
+        let idx = self.resolve_index(&this)? as u32;
+        Ok(this
+            .inner
+            .named_ranges
+            .iter()
+            .filter(|nr| nr.local_sheet_id == Some(idx))
+            .map(|nr| (nr.name.clone(), nr.range.clone()))
+            .collect())
     }
 
-    /// Add a data-validation rule over a cell range (e.g. "A1:A10"). `type` is
-    /// one of whole, decimal, list, date, time, textLength, custom. `formula1`
-    /// (and `formula2` for between/notBetween) supply the constraint -- for a
-    /// list, `formula1` is like '"A,B,C"' or a range. `operator` is between,
-    /// notBetween, equal, notEqual, greaterThan, lessThan, greaterThanOrEqual,
-    /// or lessThanOrEqual.
-    #[pyo3(signature = (cells, r#type, formula1=None, formula2=None, operator=None, allow_blank=true, show_error=true, error_title=None, error=None, show_input=true, prompt_title=None, prompt=None))]
-    #[allow(clippy::too_many_arguments)]
-    fn add_data_validation(
+    /// Define a name visible only on this sheet. A `range` without a sheet
+    /// (e.g. "A1:B10") refers to this sheet. The same name may also exist
+    /// workbook-wide; formulas on this sheet see this one.
+    #[pyo3(signature = (name, range, hidden=false, comment=None))]
+    fn add_defined_name(
         &self,
-        cells: &str,
-        r#type: &str,
-        formula1: Option<String>,
-        formula2: Option<String>,
-        operator: Option<String>,
-        allow_blank: bool,
-        show_error: bool,
-        error_title: Option<String>,
-        error: Option<String>,
-        show_input: bool,
-        prompt_title: Option<String>,
-        prompt: Option<String>,
+        name: String,
+        range: String,
+        hidden: bool,
+        comment: Option<String>,
         py: Python<'_>,
     ) -> PyResult<()> {
-        use rustypyxl_core::DataValidation;
-
-        // Key the rule at the range's top-left cell; sqref carries the full range.
-        let first = cells.split(':').next().unwrap_or(cells);
-        let (row, col) =
-            parse_coordinate(first).map_err(|e| PyValueError::new_err(e.to_string()))?;
-
-        let dv = DataValidation {
-            validation_type: r#type.to_string(),
-            operator,
-            formula1,
-            formula2,
-            error_style: None,
-            allow_blank,
-            show_error,
-            error_title,
-            error_message: error,
-            show_input,
-            prompt_title,
-            prompt_message: prompt,
-            sqref: Some(cells.to_string()),
+        let Some(ref wb) = self.workbook else {
+            return Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        let mut this = wb.borrow_mut(py);
+        let idx = self.resolve_index(&this)?;
+        let range = if range.contains('!') {
+            range
+        } else {
+            let title = this.inner.sheet_names()[idx].replace('\'', "''");
+            format!("'{}'!{}", title, range)
         };
-        self.with_sheet_mut(py, |ws| ws.add_data_validation(row, col, dv))
+        let mut named = rustypyxl_core::NamedRange::new(name, range)
+            .with_local_sheet(idx as u32)
+            .with_hidden(hidden);
+        named.comment = comment;
+        this.inner
+            .add_named_range(named)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
     /// The data-validation rules on this sheet as a list of dicts with keys
-    /// sqref, type, operator, formula1, and formula2.
+    /// sqref, type, operator, formula1, formula2, allow_blank, show_dropdown,
+    /// show_error, error_style, error_title, error, show_input, prompt_title
+    /// and prompt.
     #[getter]
     fn data_validations(&self, py: Python<'_>) -> PyResult<PyObject> {
         use pyo3::types::{PyDict, PyList};
         let list = PyList::empty(py);
         self.with_sheet_ref(py, |ws| -> PyResult<()> {
-            for ((row, col), dv) in &ws.data_validations {
+            let mut rules: Vec<_> = ws.data_validations.iter().collect();
+            rules.sort_unstable_by_key(|(key, _)| **key);
+            for ((row, col), dv) in rules {
                 let d = PyDict::new(py);
                 let sqref = dv
                     .sqref
@@ -889,6 +2197,15 @@ impl PyWorksheet {
                 d.set_item("operator", dv.operator.clone())?;
                 d.set_item("formula1", dv.formula1.clone())?;
                 d.set_item("formula2", dv.formula2.clone())?;
+                d.set_item("allow_blank", dv.allow_blank)?;
+                d.set_item("show_dropdown", !dv.hide_dropdown)?;
+                d.set_item("show_error", dv.show_error)?;
+                d.set_item("error_style", dv.error_style.clone())?;
+                d.set_item("error_title", dv.error_title.clone())?;
+                d.set_item("error", dv.error_message.clone())?;
+                d.set_item("show_input", dv.show_input)?;
+                d.set_item("prompt_title", dv.prompt_title.clone())?;
+                d.set_item("prompt", dv.prompt_message.clone())?;
                 list.append(d)?;
             }
             Ok(())
@@ -915,11 +2232,129 @@ impl PyWorksheet {
         Ok(list.into_any().unbind())
     }
 
-    /// Protect the sheet, optionally with a password (hashed with Excel's
-    /// legacy verifier on save).
-    #[pyo3(signature = (password=None))]
-    fn protect_sheet(&self, password: Option<String>, py: Python<'_>) -> PyResult<()> {
-        self.with_sheet_mut(py, |ws| ws.enable_protection(password))
+    /// Protect the sheet, optionally with a password. Only a SHA-512 hash of
+    /// the password is stored (salted, 100,000 rounds, as Excel does).
+    ///
+    /// Keyword arguments follow openpyxl's `SheetProtection`: the action
+    /// flags (`formatCells`, `insertRows`, `sort`, `selectLockedCells`, ...)
+    /// block the action when True and default as in openpyxl; `spinCount`
+    /// sets the hash rounds; `hashValue`, `saltValue` and `algorithmName`
+    /// install an existing verifier instead of a password.
+    #[pyo3(signature = (password=None, **options))]
+    fn protect_sheet(
+        &self,
+        password: Option<String>,
+        options: Option<&Bound<'_, PyDict>>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        use rustypyxl_core::protection::{DEFAULT_ALGORITHM, DEFAULT_SPIN_COUNT};
+        use rustypyxl_core::{PasswordHash, WorksheetProtection};
+
+        let mut protection = WorksheetProtection {
+            sheet: true,
+            ..Default::default()
+        };
+        let mut algorithm: Option<String> = None;
+        let mut spin_count = DEFAULT_SPIN_COUNT;
+        let mut hash_value: Option<String> = None;
+        let mut salt_value: Option<String> = None;
+        for (key, value) in options.into_iter().flatten() {
+            let key: String = key.extract()?;
+            match key.as_str() {
+                "algorithmName" => algorithm = value.extract()?,
+                "spinCount" => spin_count = value.extract()?,
+                "hashValue" => hash_value = value.extract()?,
+                "saltValue" => salt_value = value.extract()?,
+                _ => match protection.flag_mut(&key) {
+                    Some(flag) => *flag = value.extract()?,
+                    None => {
+                        return Err(pyo3::exceptions::PyTypeError::new_err(format!(
+                            "protect_sheet() got an unexpected keyword argument '{key}'"
+                        )))
+                    }
+                },
+            }
+        }
+        match (password, hash_value, salt_value) {
+            (None, None, None) => {}
+            (Some(password), None, None) => {
+                if algorithm
+                    .as_deref()
+                    .is_some_and(|a| !a.eq_ignore_ascii_case(DEFAULT_ALGORITHM))
+                {
+                    return Err(PyValueError::new_err(
+                        "new passwords are hashed with SHA-512 only",
+                    ));
+                }
+                protection.set_hash(PasswordHash::with_spin_count(&password, spin_count));
+            }
+            (None, Some(hash_value), Some(salt_value)) => {
+                protection.set_hash(PasswordHash {
+                    algorithm_name: algorithm.unwrap_or_else(|| DEFAULT_ALGORITHM.to_string()),
+                    hash_value,
+                    salt_value,
+                    spin_count,
+                });
+            }
+            (Some(_), _, _) => {
+                return Err(PyValueError::new_err(
+                    "pass either a password or hashValue and saltValue, not both",
+                ))
+            }
+            (None, _, _) => {
+                return Err(PyValueError::new_err(
+                    "hashValue and saltValue must be given together",
+                ))
+            }
+        }
+        self.with_sheet_mut(py, |ws| ws.protection = Some(protection))
+    }
+
+    /// Lock the sheet's formulas and nothing else: formula cells are locked
+    /// (and hidden from the formula bar with `hidden`), every other written
+    /// cell is unlocked, and sheet protection is turned on, keeping any
+    /// protection flags already set. Returns the number of formula cells.
+    #[pyo3(signature = (hidden=false, password=None))]
+    fn protect_formulas(
+        &self,
+        hidden: bool,
+        password: Option<&str>,
+        py: Python<'_>,
+    ) -> PyResult<usize> {
+        let Some(ref wb) = self.workbook else {
+            return Err(PyValueError::new_err(
+                "Worksheet is not attached to a workbook",
+            ));
+        };
+        let mut this = wb.borrow_mut(py);
+        let idx = self.resolve_index(&this)?;
+        let name = this.inner.sheet_names()[idx].clone();
+        this.inner
+            .protect_formulas(&name, hidden, password)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Write a raw XML fragment into this sheet's XML on save, for features
+    /// the library doesn't model. `position` is "after_sheet_data" (right
+    /// after `</sheetData>`) or "ext_lst" (inside `<extLst>`; pass
+    /// `<ext uri="...">` elements). The fragment must be well-formed; its
+    /// schema validity is up to the caller.
+    #[pyo3(signature = (xml, position="ext_lst"))]
+    fn add_xml_fragment(&self, xml: &str, position: &str, py: Python<'_>) -> PyResult<()> {
+        let point = rustypyxl_core::XmlInjectionPoint::parse(position).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Unknown position '{}': expected 'after_sheet_data' or 'ext_lst'",
+                position
+            ))
+        })?;
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.add_xml_fragment(point, xml))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Remove the XML fragments added with add_xml_fragment.
+    fn clear_xml_fragments(&self, py: Python<'_>) -> PyResult<()> {
+        self.with_sheet_mut(py, |ws| ws.clear_xml_fragments())
     }
 
     /// Remove sheet protection.
@@ -933,6 +2368,101 @@ impl PyWorksheet {
         self.with_sheet_ref(py, |ws| ws.is_protected())
     }
 
+    /// Display settings: `ws.sheet_view.zoomScale = 85`.
+    #[getter]
+    fn sheet_view(&self, py: Python<'_>) -> PyResult<crate::sheet_view::PySheetView> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        Ok(crate::sheet_view::PySheetView {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+        })
+    }
+
+    /// Print page setup: `ws.page_setup.orientation = "landscape"`.
+    #[getter]
+    fn page_setup(&self, py: Python<'_>) -> PyResult<crate::page_setup::PyPrintPageSetup> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        Ok(crate::page_setup::PyPrintPageSetup {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+        })
+    }
+
+    /// Print options: `ws.print_options.horizontalCentered = True`.
+    #[getter]
+    fn print_options(&self, py: Python<'_>) -> PyResult<crate::page_setup::PyPrintOptions> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        Ok(crate::page_setup::PyPrintOptions {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+        })
+    }
+
+    /// Page margins in inches: `ws.page_margins.left = 0.5`.
+    #[getter]
+    fn page_margins(&self, py: Python<'_>) -> PyResult<crate::page_setup::PyPageMargins> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        Ok(crate::page_setup::PyPageMargins {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+        })
+    }
+
+    /// Sheet properties: `ws.sheet_properties.tabColor = "1072BA"`.
+    #[getter]
+    fn sheet_properties(&self, py: Python<'_>) -> PyResult<crate::sheet_view::PySheetProperties> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        Ok(crate::sheet_view::PySheetProperties {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+        })
+    }
+
+    /// The sheet protection proxy: `ws.protection.verify_password("secret")`.
+    #[getter]
+    fn protection(&self, py: Python<'_>) -> PyResult<crate::protection::PySheetProtection> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        Ok(crate::protection::PySheetProtection {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+        })
+    }
+
+    /// The conditional formatting proxy:
+    /// `ws.conditional_formatting.add("A1:A10", CellIsRule(...))`.
+    #[getter]
+    fn conditional_formatting(
+        &self,
+        py: Python<'_>,
+    ) -> PyResult<crate::formatting::PyConditionalFormattingList> {
+        let wb = self
+            .workbook
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("Worksheet is not attached to a workbook"))?;
+        Ok(crate::formatting::PyConditionalFormattingList {
+            workbook: wb.clone_ref(py),
+            uid: self.uid,
+        })
+    }
+
     /// The AutoFilter proxy: `ws.auto_filter.ref = "A1:C10"`.
     #[getter]
     fn auto_filter(&self, py: Python<'_>) -> PyResult<crate::dimensions::PyAutoFilter> {
@@ -973,6 +2503,91 @@ impl PyWorksheet {
         })
     }
 
+    /// Hide (or with `hidden=False`, show) a row.
+    #[pyo3(signature = (row, hidden=true))]
+    fn set_row_hidden(&self, row: u32, hidden: bool, py: Python<'_>) -> PyResult<()> {
+        self.with_sheet_mut(py, |ws| ws.set_row_hidden(row, hidden))
+    }
+
+    /// Hide (or with `hidden=False`, show) a column, given as a letter or a
+    /// 1-based index.
+    #[pyo3(signature = (column, hidden=true))]
+    fn set_column_hidden(
+        &self,
+        column: &Bound<'_, PyAny>,
+        hidden: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let column = column_key(column)?;
+        self.with_sheet_mut(py, |ws| ws.set_column_hidden(column, hidden))
+    }
+
+    /// Group rows `start..end` (inclusive) at outline `level` (1-7). With
+    /// `hidden=True` the group is collapsed: the rows are hidden and the row
+    /// below them shows the expand button.
+    #[pyo3(signature = (start, end, level=1, hidden=false))]
+    fn group_rows(
+        &self,
+        start: u32,
+        end: u32,
+        level: u8,
+        hidden: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.group_rows(start, end, level, hidden))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Remove rows `start..end` (inclusive) from their groups and show them.
+    fn ungroup_rows(&self, start: u32, end: u32, py: Python<'_>) -> PyResult<()> {
+        self.with_sheet_mut(py, |ws| ws.ungroup_rows(start, end))
+    }
+
+    /// Group columns `start..end` (inclusive; letters or 1-based indices)
+    /// at outline `level` (1-7), like `group_rows`.
+    #[pyo3(signature = (start, end, level=1, hidden=false))]
+    fn group_cols(
+        &self,
+        start: &Bound<'_, PyAny>,
+        end: &Bound<'_, PyAny>,
+        level: u8,
+        hidden: bool,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let (start, end) = (column_key(start)?, column_key(end)?);
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| {
+            result = ws.group_columns(start, end, level, hidden)
+        })?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Remove columns `start..end` (inclusive) from their groups and show them.
+    fn ungroup_cols(
+        &self,
+        start: &Bound<'_, PyAny>,
+        end: &Bound<'_, PyAny>,
+        py: Python<'_>,
+    ) -> PyResult<()> {
+        let (start, end) = (column_key(start)?, column_key(end)?);
+        self.with_sheet_mut(py, |ws| ws.ungroup_columns(start, end))
+    }
+
+    /// Collapse the row and column outline to `level` (1-7), like Excel's
+    /// numbered outline buttons: groups at that level and deeper are hidden
+    /// and shallower ones expanded.
+    fn collapse_outline(&self, level: u8, py: Python<'_>) -> PyResult<()> {
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.collapse_outline(level))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Expand every row and column group.
+    fn expand_outline(&self, py: Python<'_>) -> PyResult<()> {
+        self.with_sheet_mut(py, |ws| ws.expand_outline())
+    }
+
     /// Get the freeze-panes anchor cell, if any.
     #[getter]
     fn freeze_panes(&self, py: Python<'_>) -> PyResult<Option<String>> {
@@ -990,6 +2605,14 @@ impl PyWorksheet {
         Python::with_gil(|py| self.with_sheet_mut(py, move |ws| ws.set_freeze_panes(cell)))
     }
 
+    /// Open the sheet scrolled to `cell` (e.g. "A10") with it selected.
+    /// Under frozen panes the cell shows directly below and right of them.
+    fn scroll_to(&self, cell: &str, py: Python<'_>) -> PyResult<()> {
+        let mut result = Ok(());
+        self.with_sheet_mut(py, |ws| result = ws.scroll_to(cell))?;
+        result.map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
     fn __str__(&self, py: Python<'_>) -> String {
         format!("<Worksheet \"{}\">", self.title(py))
     }
@@ -1015,6 +2638,52 @@ impl PyWorksheet {
 /// column for iter_cols) like openpyxl's generators. Resolves the sheet by
 /// stable uid on every step, so concurrent sheet removal raises instead of
 /// reading a neighbor.
+/// One row for `ws.append`: positional values, with rich-text cells set
+/// apart since they are written separately.
+struct AppendRow {
+    values: Vec<rustypyxl_core::CellValue>,
+    rich: Vec<(u32, rustypyxl_core::rich_text::RichText)>,
+}
+
+impl AppendRow {
+    fn collect(iterable: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let mut row = AppendRow {
+            values: Vec::new(),
+            rich: Vec::new(),
+        };
+        let mut push = |column: u32, value: &Bound<'_, PyAny>| -> PyResult<()> {
+            let slot = column as usize - 1;
+            if row.values.len() <= slot {
+                row.values
+                    .resize(slot + 1, rustypyxl_core::CellValue::Empty);
+            }
+            match python_to_rich_text(value)? {
+                Some(rich) => row.rich.push((column, rich)),
+                None => row.values[slot] = python_to_cell_value(value)?,
+            }
+            Ok(())
+        };
+        if let Ok(dict) = iterable.downcast::<PyDict>() {
+            for (key, value) in dict.iter() {
+                push(column_key(&key)?, &value)?;
+            }
+        } else {
+            for (i, item) in iterable.try_iter()?.enumerate() {
+                push((i as u32) + 1, &item?)?;
+            }
+        }
+        Ok(row)
+    }
+
+    fn append_to(self, ws: &mut rustypyxl_core::Worksheet) -> u32 {
+        let row = ws.append_row(self.values);
+        for (column, rich) in self.rich {
+            ws.set_cell_rich_text(row, column, rich);
+        }
+        row
+    }
+}
+
 #[pyclass(name = "CellRangeIterator")]
 pub struct PyCellRangeIterator {
     workbook: Option<Py<PyWorkbook>>,
@@ -1030,40 +2699,48 @@ pub struct PyCellRangeIterator {
 }
 
 impl PyCellRangeIterator {
-    /// Read a whole row (or column) of values in one pass.
+    /// Read a whole row (or column) of values in one pass, as the
+    /// workbook's blank policy reads them: `None` for a blank cell the
+    /// policy skips.
     ///
     /// Resolves the sheet once rather than scanning the workbook's sheet list
     /// for every cell, and copies the values out before converting them, so no
     /// Python object is built while the workbook is borrowed.
-    fn read_values(&self, coords: &[(u32, u32)], py: Python<'_>) -> PyResult<Vec<PyObject>> {
+    fn read_values(
+        &self,
+        outer: u32,
+        py: Python<'_>,
+    ) -> PyResult<(BlankPolicy, Vec<Option<CellValue>>)> {
         let Some(ref wb) = self.workbook else {
-            return Ok(coords.iter().map(|_| py.None()).collect());
+            let len = if self.by_columns {
+                self.max_row.saturating_sub(self.min_row) + 1
+            } else {
+                self.max_col.saturating_sub(self.min_col) + 1
+            };
+            let blank = Some(CellValue::Empty);
+            return Ok((BlankPolicy::default(), vec![blank; len as usize]));
         };
 
-        let values: Vec<CellValue> = {
-            let this = wb.borrow(py);
-            let idx = this
-                .inner
-                .sheet_index_by_uid(self.sheet_uid)
-                .ok_or_else(|| {
-                    PyValueError::new_err("Worksheet no longer exists in this workbook")
-                })?;
-            let worksheet = &this.inner.worksheets[idx];
-            coords
-                .iter()
-                .map(|&(row, col)| {
-                    worksheet
-                        .get_cell(row, col)
-                        .map(|cell| cell.value.clone())
-                        .unwrap_or(CellValue::Empty)
-                })
+        let this = wb.borrow(py);
+        let idx = this
+            .inner
+            .sheet_index_by_uid(self.sheet_uid)
+            .ok_or_else(|| PyValueError::new_err("Worksheet no longer exists in this workbook"))?;
+        let worksheet = &this.inner.worksheets[idx];
+        let policy = this.inner.blank_policy;
+        let read = |value: &CellValue| policy.read_value(value).map(|value| value.into_owned());
+        let values = if self.by_columns {
+            worksheet
+                .column_values(outer, self.min_row..=self.max_row)
+                .map(read)
+                .collect()
+        } else {
+            worksheet
+                .row_values(outer, self.min_col..=self.max_col)
+                .map(read)
                 .collect()
         };
-
-        Ok(values
-            .iter()
-            .map(|value| cell_value_to_python(value, py))
-            .collect())
+        Ok((policy, values))
     }
 
     fn make_cell(&self, row: u32, col: u32, py: Python<'_>) -> PyResult<PyObject> {
@@ -1093,31 +2770,37 @@ impl PyCellRangeIterator {
         } else {
             self.max_row
         };
-        if self.position > limit {
-            return Ok(None);
-        }
-        let outer = self.position;
-        self.position += 1;
+        // Loops only to pass over lines a "skip" blank policy leaves out
+        while self.position <= limit {
+            let outer = self.position;
+            self.position += 1;
+
+            let coords: Vec<(u32, u32)> = if self.by_columns {
+                (self.min_row..=self.max_row)
+                    .map(|row| (row, outer))
+                    .collect()
+            } else {
+                (self.min_col..=self.max_col)
+                    .map(|col| (outer, col))
+                    .collect()
+            };
 
-        let coords: Vec<(u32, u32)> = if self.by_columns {
-            (self.min_row..=self.max_row)
-                .map(|row| (row, outer))
-                .collect()
-        } else {
-            (self.min_col..=self.max_col)
-                .map(|col| (outer, col))
-                .collect()
-        };
+            let (policy, values) = self.read_values(outer, py)?;
+            if policy.read == BlankRead::Skip && values.iter().all(Option::is_none) {
+                continue;
+            }
 
-        let items: Vec<PyObject> = if self.values_only {
-            self.read_values(&coords, py)?
-        } else {
-            coords
-                .iter()
-                .map(|&(row, col)| self.make_cell(row, col, py))
-                .collect::<PyResult<_>>()?
-        };
-        Ok(Some(PyTuple::new(py, items)?.into_any().unbind()))
+            let mut items: Vec<PyObject> = Vec::with_capacity(coords.len());
+            for (&(row, col), value) in coords.iter().zip(&values) {
+                match value {
+                    None => {}
+                    Some(value) if self.values_only => items.push(cell_value_to_python(value, py)),
+                    Some(_) => items.push(self.make_cell(row, col, py)?),
+                }
+            }
+            return Ok(Some(PyTuple::new(py, items)?.into_any().unbind()));
+        }
+        Ok(None)
     }
 
     fn __traverse__(&self, visit: pyo3::PyVisit<'_>) -> Result<(), pyo3::PyTraverseError> {
@@ -1133,7 +2816,69 @@ impl PyCellRangeIterator {
 }
 
 /// Map a paper-size name to the core PaperSize.
-fn parse_paper_size(name: &str) -> PyResult<rustypyxl_core::pagesetup::PaperSize> {
+/// The field names of a record-like Python object, in declaration order:
+/// dataclass fields, pydantic model fields (v2 or v1), namedtuple fields, or
+/// failing those the object's public instance attributes.
+fn object_field_names(obj: &Bound<'_, PyAny>) -> PyResult<Vec<String>> {
+    let py = obj.py();
+    if obj.hasattr("__dataclass_fields__")? {
+        let fields = py.import("dataclasses")?.call_method1("fields", (obj,))?;
+        return fields
+            .try_iter()?
+            .map(|field| field?.getattr("name")?.extract())
+            .collect();
+    }
+    let cls = obj.get_type();
+    for attr in ["model_fields", "__fields__"] {
+        if let Ok(model_fields) = cls.getattr(attr) {
+            if let Ok(dict) = model_fields.downcast::<PyDict>() {
+                return dict.keys().iter().map(|k| k.extract()).collect();
+            }
+        }
+    }
+    if obj.is_instance_of::<pyo3::types::PyTuple>() {
+        if let Ok(fields) = cls.getattr("_fields") {
+            return fields.extract();
+        }
+    }
+    if let Ok(attrs) = obj.getattr("__dict__") {
+        if let Ok(dict) = attrs.downcast::<PyDict>() {
+            let mut names = Vec::new();
+            for key in dict.keys() {
+                let name: String = key.extract()?;
+                if !name.starts_with('_') {
+                    names.push(name);
+                }
+            }
+            return Ok(names);
+        }
+    }
+    Err(PyValueError::new_err(format!(
+        "cannot read fields from {}; pass fields=[...]",
+        cls.name()?
+    )))
+}
+
+/// A dict key naming a column: a letter ("C") or a 1-based index.
+pub(crate) fn column_key(key: &Bound<'_, PyAny>) -> PyResult<u32> {
+    let column = if let Ok(idx) = key.extract::<u32>() {
+        idx
+    } else if let Ok(letter) = key.extract::<String>() {
+        let (_, col) = parse_coordinate(&format!("{}1", letter))
+            .map_err(|_| PyValueError::new_err(format!("Invalid column key '{}'", letter)))?;
+        col
+    } else {
+        return Err(PyValueError::new_err(
+            "dict keys must be column letters or 1-based indices",
+        ));
+    };
+    if column == 0 {
+        return Err(PyValueError::new_err("Column index must be at least 1"));
+    }
+    Ok(column)
+}
+
+pub(crate) fn parse_paper_size(name: &str) -> PyResult<rustypyxl_core::pagesetup::PaperSize> {
     use rustypyxl_core::pagesetup::PaperSize;
     let size = match name.to_ascii_uppercase().as_str() {
         "LETTER" => PaperSize::Letter,
@@ -1320,3 +3065,17 @@ fn parse_series_item(
     }
     Ok(s)
 }
+
+fn json_options(
+    range: Option<&str>,
+    header: bool,
+    skip_nulls: bool,
+    date_format: Option<&str>,
+) -> rustypyxl_core::JsonExportOptions {
+    let mut opts = rustypyxl_core::JsonExportOptions::new()
+        .with_headers(header)
+        .with_skip_nulls(skip_nulls);
+    opts.range = range.map(str::to_string);
+    opts.date_format = date_format.map(str::to_string);
+    opts
+}
@@ -3,17 +3,44 @@ openpyxl-compatible API."""
 
 import datetime
 import os
-from typing import Any, BinaryIO, Iterator
+from typing import Any, BinaryIO, Callable, Iterable, Iterator, Literal, overload
 
-CellValue = str | int | float | bool | datetime.datetime | datetime.date | datetime.time | None
+CellValue = (
+    str | int | float | bool | datetime.datetime | datetime.date | datetime.time | CellRichText | None
+)
 _ColorArg = str | Color | None
 # A color reads back as the plain hex string when that is all it is, and as a
 # Color when it carries a theme, a palette index, or a tint.
 _ColorValue = str | Color | None
 
+@overload
 def load_workbook(
-    source: str | os.PathLike[str] | bytes | BinaryIO, password: str | None = None
+    source: str | os.PathLike[str] | bytes | BinaryIO,
+    password: str | None = None,
+    read_only: Literal[False] = False,
+    *,
+    rich_text: bool = False,
+    keep_vba: bool = False,
 ) -> Workbook: ...
+@overload
+def load_workbook(
+    source: str | os.PathLike[str] | bytes | BinaryIO,
+    password: str | None = None,
+    *,
+    read_only: Literal[True],
+    max_string_len: int | None = None,
+) -> ReadOnlyWorkbook: ...
+@overload
+def load_workbook(
+    source: str | os.PathLike[str] | bytes | BinaryIO,
+    password: str | None = None,
+    *,
+    edit: Literal[True],
+) -> EditableWorkbook: ...
+def detect_format(source: str | os.PathLike[str] | bytes | BinaryIO) -> dict[str, Any]: ...
+def salvage_workbook(
+    source: str | os.PathLike[str] | bytes | BinaryIO,
+) -> tuple[Workbook, dict[str, Any]]: ...
 def format_value(
     value: str | int | float | bool | datetime.datetime | datetime.date | datetime.time | None,
     number_format: str,
@@ -25,34 +52,123 @@ class Workbook:
     def __init__(self) -> None: ...
     @staticmethod
     def load(
-        source: str | os.PathLike[str] | bytes | BinaryIO, password: str | None = None
+        source: str | os.PathLike[str] | bytes | BinaryIO,
+        password: str | None = None,
+        rich_text: bool = False,
+        keep_vba: bool = False,
     ) -> Workbook: ...
     @property
+    def has_vba(self) -> bool: ...
+    @property
     def active(self) -> Worksheet: ...
     @active.setter
     def active(self, value: Worksheet | int) -> None: ...
     @property
     def sheetnames(self) -> list[str]: ...
     @property
+    def theme_colors(self) -> list[str]: ...
+    @property
+    def named_styles(self) -> list[str]: ...
+    def add_named_style(self, style: NamedStyle) -> None: ...
+    @property
     def worksheets(self) -> list[Worksheet]: ...
     @property
+    def styles(self) -> StyleSheet: ...
+    @property
+    def sheet_order(self) -> list[str]: ...
+    @sheet_order.setter
+    def sheet_order(self, value: list[str]) -> None: ...
+    @property
     def defined_names(self) -> list[tuple[str, str]]: ...
+    def conditional_format_results(self, sheet_name: str) -> list[dict[str, Any]]: ...
+    def search(
+        self,
+        query: str,
+        sheets: list[str] | None = None,
+        in_formulas: bool = False,
+        limit: int | None = 100,
+    ) -> list[dict[str, Any]]: ...
+    def check_references(self) -> list[dict[str, Any]]: ...
+    def check_limits(self) -> list[dict[str, Any]]: ...
     def __getitem__(self, key: str) -> Worksheet: ...
     def __contains__(self, key: str) -> bool: ...
     def __len__(self) -> int: ...
     def __iter__(self) -> Iterator[str]: ...
-    def create_sheet(self, title: str | None = None, index: int | None = None) -> Worksheet: ...
+    def create_sheet(
+        self,
+        title: str | None = None,
+        index: int | None = None,
+        storage: Literal["map", "columnar"] | None = None,
+    ) -> Worksheet: ...
+    def build_index_sheet(
+        self,
+        title: str = "Contents",
+        descriptions: dict[str, str] | None = None,
+        row_counts: bool = True,
+        include_hidden: bool = False,
+    ) -> Worksheet: ...
     def remove(self, worksheet: Worksheet) -> None: ...
     def copy_worksheet(self, source: Worksheet) -> Worksheet: ...
-    def move_sheet(self, sheet: Worksheet, offset: int) -> None: ...
+    def clone_structure(self, header_rows: int = 1) -> Workbook: ...
+    def move_sheet(
+        self, sheet: Worksheet | str, offset: int = 0, index: int | None = None
+    ) -> None: ...
     def index(self, worksheet: Worksheet) -> int: ...
     def create_named_range(self, name: str, worksheet: Worksheet, range: str) -> None: ...
+    def resolve_named_range(
+        self, name: str, sheet: str | None = None
+    ) -> tuple[str, int, int, int, int]: ...
     def save(
-        self, filename: str | os.PathLike[str], password: str | None = None
-    ) -> None: ...
+        self,
+        filename: str | os.PathLike[str],
+        password: str | None = None,
+        on_locked: Literal["fail", "retry", "alternate"] = "fail",
+        retries: int = 5,
+        verify: bool = False,
+    ) -> str: ...
+    def save_encrypted(self, filename: str | os.PathLike[str], password: str) -> None: ...
     def save_to_bytes(self, password: str | None = None) -> bytes: ...
+    def protect_workbook(
+        self,
+        password: str | None = None,
+        lockStructure: bool = True,
+        lockWindows: bool = False,
+        lockRevision: bool = False,
+        revisionsPassword: str | None = None,
+    ) -> None: ...
+    def unprotect_workbook(self) -> None: ...
+    @property
+    def workbook_protected(self) -> bool: ...
+    def check_workbook_password(self, password: str) -> bool: ...
+    def export_pdf(
+        self,
+        path: str | os.PathLike[str],
+        soffice: str | os.PathLike[str] | None = None,
+        command: list[str] | None = None,
+        renderer: Callable[[str, str], Any] | None = None,
+    ) -> None: ...
     def close(self) -> None: ...
     def set_compression(self, level: str) -> None: ...
+    def set_non_finite_numbers(self, policy: Literal["num", "empty", "error"]) -> None: ...
+    def set_number_precision(self, precision: Literal["shortest", "excel"]) -> None: ...
+    def set_shared_strings(
+        self, mode: Literal["all", "auto", "incremental", "inline"] | int
+    ) -> None: ...
+    def set_share_formulas(self, share: bool) -> None: ...
+    def add_custom_part(
+        self,
+        path: str,
+        data: bytes | str,
+        content_type: str | None = None,
+        rel_type: str | None = None,
+    ) -> None: ...
+    @property
+    def formula_locale(self) -> str | None: ...
+    @formula_locale.setter
+    def formula_locale(self, code: str | None) -> None: ...
+    blank_policy: BlankPolicy
+    @property
+    def properties(self) -> DocumentProperties: ...
     def write_rows(
         self,
         sheet_name: str,
@@ -67,11 +183,41 @@ class Workbook:
         max_row: int | None = None,
         min_col: int | None = None,
         max_col: int | None = None,
-    ) -> list[list[CellValue]]: ...
+        converters: dict[str | int, Literal["int", "float", "str", "bool", "date", "datetime"] | Callable[[CellValue], Any]] | None = None,
+    ) -> list[list[Any]]: ...
     def get_cell_value(self, sheet_name: str, row: int, column: int) -> CellValue: ...
     def evaluate_formula(self, sheet_name: str, formula: str) -> Any: ...
     def evaluate_cell(self, sheet_name: str, row: int, column: int) -> Any: ...
     def calculate_all(self) -> int: ...
+    def export_all_to_csv(self, directory: str | os.PathLike[str]) -> list[dict[str, Any]]: ...
+    def insert_from_csv(
+        self,
+        sheet_name: str,
+        path: str | os.PathLike[str],
+        start_row: int = 1,
+        start_col: int = 1,
+        has_headers: bool = True,
+        delimiter: str = ",",
+        quotechar: str | None = '"',
+        encoding: str = "utf-8",
+        infer_types: bool = True,
+        date_formats: list[str] | None = None,
+        column_renames: dict[str, str] | None = None,
+        columns: list[str] | None = None,
+        style: ImportStyle | bool | None = None,
+    ) -> dict[str, Any]: ...
+    def insert_from_json(
+        self,
+        sheet_name: str,
+        path: str | os.PathLike[str],
+        start_row: int = 1,
+        start_col: int = 1,
+        has_headers: bool = True,
+        columns: list[str] | None = None,
+        column_renames: dict[str, str] | None = None,
+        parse_dates: bool = True,
+        style: ImportStyle | bool | None = None,
+    ) -> dict[str, Any]: ...
     @property
     def pivot_tables(self) -> list[PivotTable]: ...
     def add_pivot_table(
@@ -97,9 +243,13 @@ class Workbook:
     def get_cell_protection(self, sheet_name: str, row: int, column: int) -> Protection | None: ...
     def set_cell_protection(self, sheet_name: str, row: int, column: int, protection: Protection) -> None: ...
     def get_cell_hyperlink(self, sheet_name: str, row: int, column: int) -> str | None: ...
-    def set_cell_hyperlink(self, sheet_name: str, row: int, column: int, url: str | None = None) -> None: ...
+    def set_cell_hyperlink(
+        self, sheet_name: str, row: int, column: int, url: str | Hyperlink | None = None
+    ) -> None: ...
     def get_cell_comment(self, sheet_name: str, row: int, column: int) -> str | None: ...
-    def set_cell_comment(self, sheet_name: str, row: int, column: int, comment: str | None = None) -> None: ...
+    def set_cell_comment(
+        self, sheet_name: str, row: int, column: int, comment: str | Comment | None = None
+    ) -> None: ...
     def get_cell_number_format(self, sheet_name: str, row: int, column: int) -> str | None: ...
     def set_cell_number_format(self, sheet_name: str, row: int, column: int, format: str) -> None: ...
     def clear_cell_number_format(self, sheet_name: str, row: int, column: int) -> None: ...
@@ -114,6 +264,17 @@ class Workbook:
         alignment: Alignment | None = None,
         number_format: str | None = None,
     ) -> None: ...
+    def set_range_style(
+        self,
+        sheet_name: str,
+        range: str,
+        font: Font | None = None,
+        fill: PatternFill | None = None,
+        border: Border | None = None,
+        alignment: Alignment | None = None,
+        number_format: str | None = None,
+        protection: Protection | None = None,
+    ) -> None: ...
     def insert_from_parquet(
         self,
         sheet_name: str,
@@ -123,6 +284,7 @@ class Workbook:
         include_headers: bool = True,
         column_renames: dict[str, str] | None = None,
         columns: list[str] | None = None,
+        style: ImportStyle | bool | None = None,
     ) -> dict[str, Any]: ...
     def export_to_parquet(
         self,
@@ -144,12 +306,37 @@ class Workbook:
         has_headers: bool = True,
         compression: str = "snappy",
     ) -> dict[str, Any]: ...
+    def insert_from_arrow_ipc(
+        self,
+        sheet_name: str,
+        path: str,
+        start_row: int = 1,
+        start_col: int = 1,
+        include_headers: bool = True,
+        column_renames: dict[str, str] | None = None,
+        columns: list[str] | None = None,
+        style: ImportStyle | bool | None = None,
+    ) -> dict[str, Any]: ...
+    def export_to_arrow_ipc(
+        self,
+        sheet_name: str,
+        path: str,
+        has_headers: bool = True,
+        compression: Literal["none", "lz4", "zstd"] = "none",
+        column_renames: dict[str, str] | None = None,
+        column_types: dict[str, str] | None = None,
+    ) -> dict[str, Any]: ...
 
 class Worksheet:
     title: str
     sheet_state: str
+    storage: Literal["map", "columnar"]
     freeze_panes: str | None
     @property
+    def sheet_type(
+        self,
+    ) -> Literal["worksheet", "dialogsheet", "macrosheet", "intlmacrosheet"]: ...
+    @property
     def dimensions(self) -> str: ...
     @property
     def max_row(self) -> int: ...
@@ -159,15 +346,23 @@ class Worksheet:
     def min_row(self) -> int: ...
     @property
     def min_column(self) -> int: ...
+    def non_empty_cells_in(self, range: str) -> int: ...
+    def first_empty_row(self, column: int | str) -> int: ...
     @property
-    def merged_cells(self) -> list[str]: ...
-    def __getitem__(self, key: str) -> Any: ...
+    def merged_cells(self) -> MultiCellRange: ...
+    def __getitem__(
+        self, key: str | int | slice
+    ) -> Cell | tuple[Cell, ...] | tuple[tuple[Cell, ...], ...]: ...
     def __setitem__(self, key: str, value: CellValue) -> None: ...
-    def cell(self, row: int, column: int | None = None) -> Cell: ...
+    def cell(self, row: int, column: int | None = None, value: CellValue = None) -> Cell: ...
     def append(
         self,
         iterable: list[CellValue] | tuple[CellValue, ...] | Iterator[CellValue] | dict[str | int, CellValue],
-    ) -> None: ...
+    ) -> int: ...
+    def append_rows(
+        self,
+        rows: Iterable[list[CellValue] | tuple[CellValue, ...] | Iterator[CellValue] | dict[str | int, CellValue]],
+    ) -> tuple[int, int] | None: ...
     def iter_rows(
         self,
         min_row: int | None = None,
@@ -184,6 +379,13 @@ class Worksheet:
         max_row: int | None = None,
         values_only: bool = False,
     ) -> CellRangeIterator: ...
+    @property
+    def rows(self) -> CellRangeIterator: ...
+    @property
+    def columns(self) -> CellRangeIterator: ...
+    @property
+    def values(self) -> CellRangeIterator: ...
+    def __iter__(self) -> CellRangeIterator: ...
     def merge_cells(
         self,
         range_string: str | None = None,
@@ -191,6 +393,8 @@ class Worksheet:
         start_column: int | None = None,
         end_row: int | None = None,
         end_column: int | None = None,
+        value: CellValue = None,
+        style: str | dict[str, Any] | None = None,
     ) -> None: ...
     def unmerge_cells(
         self,
@@ -202,13 +406,120 @@ class Worksheet:
     ) -> None: ...
     def auto_fit_column(self, column: int) -> float | None: ...
     def auto_fit_all(self) -> None: ...
+    def autofit(self, range: str | None = None) -> dict[str, float]: ...
+    def column_text_widths(self, range: str | None = None) -> list[dict[str, Any]]: ...
+    def set_row_hidden(self, row: int, hidden: bool = True) -> None: ...
+    def set_column_hidden(self, column: str | int, hidden: bool = True) -> None: ...
+    def group_rows(
+        self, start: int, end: int, level: int = 1, hidden: bool = False
+    ) -> None: ...
+    def ungroup_rows(self, start: int, end: int) -> None: ...
+    def group_cols(
+        self, start: str | int, end: str | int, level: int = 1, hidden: bool = False
+    ) -> None: ...
+    def ungroup_cols(self, start: str | int, end: str | int) -> None: ...
+    def collapse_outline(self, level: int) -> None: ...
+    def expand_outline(self) -> None: ...
+    def apply_banding(
+        self,
+        range: str,
+        color_a: _ColorArg,
+        color_b: _ColorArg,
+        header: bool = True,
+    ) -> None: ...
+    def apply_named_style(self, range: str, name: str) -> None: ...
+    def write_report(self, report: Report) -> dict[str, Any]: ...
+    def compute(
+        self,
+        function: Literal["SUM", "AVERAGE", "MIN", "MAX", "COUNT", "COUNTA", "COUNTIF"] | str,
+        range: str,
+        criteria: str | None = None,
+    ) -> int | float | str: ...
+    def export_to_csv(
+        self,
+        path: str | os.PathLike[str],
+        range: str | None = None,
+        delimiter: str = ",",
+        quotechar: str = '"',
+        quoting: Literal["minimal", "all", "nonnumeric", "none"] = "minimal",
+        encoding: str = "utf-8",
+        line_terminator: str = "\r\n",
+        date_format: str | None = None,
+    ) -> dict[str, Any]: ...
+    def to_json(
+        self,
+        range: str | None = None,
+        header: bool = True,
+        skip_nulls: bool = False,
+        date_format: str | None = None,
+    ) -> str: ...
+    def write_ndjson(
+        self,
+        path: str | os.PathLike[str],
+        range: str | None = None,
+        header: bool = True,
+        skip_nulls: bool = False,
+        date_format: str | None = None,
+    ) -> int: ...
+    def to_html(self, range: str | None = None, include_styles: bool = True) -> str: ...
+    def to_text_grid(
+        self,
+        range: str | None = None,
+        width_policy: Literal["content", "sheet"] | int = "content",
+        markdown: bool = False,
+    ) -> str: ...
+    def apply_scaled_number_format(
+        self,
+        range: str,
+        style: Literal["auto", "units", "thousands", "millions", "billions"] = "auto",
+    ) -> str: ...
+    def write_objects(
+        self,
+        objects: Iterable[Any],
+        fields: list[str] | None = None,
+        start_row: int = 1,
+        start_col: int = 1,
+        header: bool = True,
+        style: ImportStyle | bool | None = None,
+    ) -> dict[str, Any]: ...
+    def write_arrow(
+        self,
+        table: Any,
+        start_row: int = 1,
+        start_col: int = 1,
+        include_header: bool = True,
+        style: ImportStyle | bool | None = None,
+    ) -> dict[str, Any]: ...
+    def from_dataframe(
+        self,
+        df: Any,
+        start_row: int = 1,
+        start_col: int = 1,
+        index: bool = False,
+        header: bool = True,
+        style: ImportStyle | bool | None = None,
+    ) -> dict[str, Any]: ...
+    def to_dataframe(self, range: str | None = None, header: bool = True) -> Any: ...
     @property
     def auto_filter(self) -> AutoFilter: ...
     @property
+    def conditional_formatting(self) -> ConditionalFormattingList: ...
+    @property
     def sheet_protected(self) -> bool: ...
-    def protect_sheet(self, password: str | None = None) -> None: ...
+    def protect_sheet(self, password: str | None = None, **options: bool | int | str) -> None: ...
+    def add_xml_fragment(
+        self, xml: str, position: Literal["after_sheet_data", "ext_lst"] = "ext_lst"
+    ) -> None: ...
+    def clear_xml_fragments(self) -> None: ...
+    def protect_formulas(self, hidden: bool = False, password: str | None = None) -> int: ...
     def unprotect_sheet(self) -> None: ...
     @property
+    def protection(self) -> SheetProtection: ...
+    @property
+    def sheet_view(self) -> SheetView: ...
+    @property
+    def sheet_properties(self) -> WorksheetProperties: ...
+    @property
     def column_dimensions(self) -> ColumnDimensions: ...
     @property
     def row_dimensions(self) -> RowDimensions: ...
@@ -216,7 +527,18 @@ class Worksheet:
     def tables(self) -> list[dict[str, str]]: ...
     @property
     def data_validations(self) -> list[dict[str, Any]]: ...
-    print_area: str | None
+    @property
+    def page_setup(self) -> PrintPageSetup: ...
+    @property
+    def print_options(self) -> PrintOptions: ...
+    @property
+    def page_margins(self) -> PageMargins: ...
+    @property
+    def print_area(self) -> str | None: ...
+    @print_area.setter
+    def print_area(self, value: str | list[str] | None) -> None: ...
+    print_title_rows: str | None
+    print_title_cols: str | None
     def set_page_setup(
         self,
         orientation: str | None = None,
@@ -248,8 +570,8 @@ class Worksheet:
     def add_conditional_formatting(self, cells: str, rule: dict[str, Any]) -> None: ...
     def add_data_validation(
         self,
-        cells: str,
-        type: str,
+        cells: str | DataValidation | Any,
+        type: str | None = None,
         formula1: str | None = None,
         formula2: str | None = None,
         operator: str | None = None,
@@ -274,14 +596,59 @@ class Worksheet:
         row_stripes: bool = True,
         column_stripes: bool = False,
         auto_filter: bool = True,
+    ) -> Table: ...
+    def get_table(self, name: str) -> Table: ...
+    def add_calculated_column(
+        self,
+        target_col: int | str,
+        formula_template: str,
+        rows: tuple[int, int] | None = None,
+    ) -> tuple[int, int]: ...
+    def set_comment_shape(
+        self,
+        cell: str,
+        visible: bool = False,
+        width: float | None = None,
+        height: float | None = None,
+        anchor: str | None = None,
+    ) -> None: ...
+    def get_comment_shape(self, cell: str) -> dict[str, Any] | None: ...
+    def get_comment(self, cell: str) -> Comment | None: ...
+    def get_hyperlink(self, cell: str) -> Hyperlink | None: ...
+    def add_threaded_comment(self, cell: str, text: str, author: str) -> None: ...
+    def set_row_tag(self, row: int, tag: str) -> None: ...
+    def get_row_tag(self, row: int) -> str | None: ...
+    def remove_row_tag(self, row: int) -> None: ...
+    def find_row_by_tag(self, tag: str) -> int | None: ...
+    @property
+    def row_tags(self) -> dict[int, str]: ...
+    def set_hyperlink(
+        self, cell: str, url: str | Hyperlink, display: str | None = None, styled: bool = True
+    ) -> None: ...
+    def scroll_to(self, cell: str) -> None: ...
+    def set_cell_tag(self, cell: str, tag: str) -> None: ...
+    def get_cell_tag(self, cell: str) -> str | None: ...
+    def remove_cell_tag(self, cell: str) -> None: ...
+    def find_cell_by_tag(self, tag: str) -> str | None: ...
+    @property
+    def defined_names(self) -> dict[str, str]: ...
+    def add_defined_name(
+        self, name: str, range: str, hidden: bool = False, comment: str | None = None
     ) -> None: ...
     def insert_rows(self, idx: int, amount: int | None = None) -> None: ...
     def insert_cols(self, idx: int, amount: int | None = None) -> None: ...
     def delete_rows(self, idx: int, amount: int | None = None) -> None: ...
     def delete_cols(self, idx: int, amount: int | None = None) -> None: ...
+    def fill_formula_down(self, start: str, formula: str, rows: int) -> None: ...
+    def move_range(
+        self, cell_range: str, rows: int = 0, cols: int = 0, translate: bool = False
+    ) -> None: ...
+    @overload
+    def add_chart(self, chart: ChartBase, anchor: str = "E15") -> None: ...
+    @overload
     def add_chart(
         self,
-        chart_type: str,
+        chart: str,
         series: str | dict[str, str] | list[str | dict[str, str]],
         anchor: str,
         title: str | None = None,
@@ -290,13 +657,212 @@ class Worksheet:
     ) -> None: ...
     def add_image(
         self,
-        image: str | os.PathLike[str] | bytes,
-        anchor: str,
+        image: Image | str | os.PathLike[str] | bytes | BinaryIO | Any,
+        anchor: str | None = None,
         to: str | None = None,
         width: int | None = None,
         height: int | None = None,
         name: str | None = None,
     ) -> None: ...
+    @property
+    def images(self) -> list[Image]: ...
+
+class Image:
+    width: int
+    height: int
+    anchor: str
+    name: str | None
+    def __init__(self, img: str | os.PathLike[str] | bytes | BinaryIO) -> None: ...
+    @property
+    def format(self) -> str: ...
+    @property
+    def data(self) -> bytes: ...
+
+class ChartBase:
+    title: str | None
+    legend: Literal["r", "l", "t", "b", "tr"] | None
+    width: float
+    height: float
+    x_axis_title: str | None
+    y_axis_title: str | None
+    @property
+    def series_count(self) -> int: ...
+    def add_data(self, data: str, titles_from_data: bool = False) -> None: ...
+    def set_categories(self, labels: str) -> None: ...
+
+class BarChart(ChartBase):
+    type: Literal["col", "bar"]
+    grouping: Literal["clustered", "stacked", "percentStacked"]
+    def __init__(self) -> None: ...
+
+class LineChart(ChartBase):
+    def __init__(self) -> None: ...
+
+class PieChart(ChartBase):
+    def __init__(self) -> None: ...
+
+# Also importable from rustypyxl.worksheet.datavalidation.
+class DataValidation:
+    type: str
+    operator: str | None
+    formula1: str | None
+    formula2: str | None
+    errorStyle: str | None
+    errorTitle: str | None
+    error: str | None
+    promptTitle: str | None
+    prompt: str | None
+    allowBlank: bool
+    allow_blank: bool
+    showErrorMessage: bool
+    showInputMessage: bool
+    showDropDown: bool
+    hide_drop_down: bool
+    sqref: str
+    def __init__(
+        self,
+        type: str | None = None,
+        formula1: str | None = None,
+        formula2: str | None = None,
+        showErrorMessage: bool = False,
+        showInputMessage: bool = False,
+        showDropDown: bool = False,
+        allowBlank: bool = False,
+        sqref: str | None = None,
+        promptTitle: str | None = None,
+        errorStyle: str | None = None,
+        error: str | None = None,
+        prompt: str | None = None,
+        errorTitle: str | None = None,
+        operator: str | None = None,
+        allow_blank: bool | None = None,
+    ) -> None: ...
+    def add(self, cell: str | Cell) -> None: ...
+    def __contains__(self, cell: str | Cell) -> bool: ...
+
+# Conditional formatting rules; also importable from rustypyxl.formatting.
+class Rule:
+    @property
+    def type(self) -> str: ...
+    @property
+    def operator(self) -> str | None: ...
+    @property
+    def formula(self) -> list[str]: ...
+    @property
+    def priority(self) -> int: ...
+    @property
+    def stopIfTrue(self) -> bool: ...
+
+_Formula = str | int | float | list[str | int | float]
+_Threshold = str | int | float | None
+
+class CellIsRule(Rule):
+    def __init__(
+        self,
+        operator: str | None = None,
+        formula: _Formula | None = None,
+        stopIfTrue: bool | None = None,
+        font: Font | None = None,
+        border: Border | None = None,
+        fill: PatternFill | None = None,
+    ) -> None: ...
+
+class FormulaRule(Rule):
+    def __init__(
+        self,
+        formula: _Formula | None = None,
+        stopIfTrue: bool | None = None,
+        font: Font | None = None,
+        border: Border | None = None,
+        fill: PatternFill | None = None,
+    ) -> None: ...
+
+class ColorScaleRule(Rule):
+    def __init__(
+        self,
+        start_type: str | None = None,
+        start_value: _Threshold = None,
+        start_color: _ColorArg = None,
+        mid_type: str | None = None,
+        mid_value: _Threshold = None,
+        mid_color: _ColorArg = None,
+        end_type: str | None = None,
+        end_value: _Threshold = None,
+        end_color: _ColorArg = None,
+    ) -> None: ...
+
+class DataBarRule(Rule):
+    def __init__(
+        self,
+        start_type: str | None = None,
+        start_value: _Threshold = None,
+        end_type: str | None = None,
+        end_value: _Threshold = None,
+        color: _ColorArg = None,
+        showValue: bool | None = None,
+    ) -> None: ...
+
+class IconSetRule(Rule):
+    def __init__(
+        self,
+        icon_style: str | None = None,
+        type: str | None = None,
+        values: list[str | int | float] | None = None,
+        showValue: bool | None = None,
+        percent: bool | None = None,
+        reverse: bool | None = None,
+    ) -> None: ...
+
+class ConditionalFormattingList:
+    def add(self, range_string: str, cfRule: Rule) -> None: ...
+    @property
+    def ranges(self) -> list[str]: ...
+    def rules(self, range_string: str) -> list[Rule]: ...
+    def __len__(self) -> int: ...
+
+class Table:
+    @property
+    def name(self) -> str: ...
+    @name.setter
+    def name(self, value: str) -> None: ...
+    @property
+    def ref(self) -> str: ...
+    @property
+    def columns(self) -> list[str]: ...
+    @property
+    def style(self) -> str: ...
+    @style.setter
+    def style(self, value: str) -> None: ...
+    @property
+    def row_stripes(self) -> bool: ...
+    @row_stripes.setter
+    def row_stripes(self, value: bool) -> None: ...
+    @property
+    def column_stripes(self) -> bool: ...
+    @column_stripes.setter
+    def column_stripes(self, value: bool) -> None: ...
+    @property
+    def auto_filter(self) -> bool: ...
+    @auto_filter.setter
+    def auto_filter(self, value: bool) -> None: ...
+    @property
+    def totals_row(self) -> bool: ...
+    def add_totals_row(self, totals: dict[str, str | None]) -> int: ...
+    def resize(self, ref: str) -> None: ...
+
+class Report:
+    def __init__(self, accent: str | None = None, banding: str | None = "F2F2F2") -> None: ...
+    def add_title(self, text: str, subtitle: str | None = None) -> None: ...
+    def add_key_values(self, pairs: dict[str, CellValue] | list[tuple[str, CellValue]]) -> None: ...
+    def add_table(
+        self,
+        columns: list[str | dict[str, Any]],
+        rows: list[list[CellValue]],
+        caption: str | None = None,
+        totals_label: str | None = None,
+    ) -> None: ...
+    def add_notes(self, lines: list[str], heading: str | None = None) -> None: ...
+    def __len__(self) -> int: ...
 
 class Cell:
     def __init__(self, row: int, column: int) -> None: ...
@@ -309,6 +875,10 @@ class Cell:
     @property
     def column_letter(self) -> str: ...
     @property
+    def col_idx(self) -> int: ...
+    @property
+    def parent(self) -> Worksheet | None: ...
+    @property
     def data_type(self) -> str: ...
     @property
     def display_value(self) -> str: ...
@@ -316,39 +886,291 @@ class Cell:
     def rich_text(self) -> list[dict[str, Any]] | None: ...
     @property
     def is_formula(self) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
     value: CellValue
     font: Font | None
     alignment: Alignment | None
     fill: PatternFill | None
     border: Border | None
     protection: Protection | None
-    hyperlink: str | None
-    comment: str | None
     number_format: str | None
+    @property
+    def hyperlink(self) -> str | None: ...
+    @hyperlink.setter
+    def hyperlink(self, value: str | Hyperlink | None) -> None: ...
+    @property
+    def comment(self) -> str | None: ...
+    @comment.setter
+    def comment(self, value: str | Comment | None) -> None: ...
+    @property
+    def style(self) -> str: ...
+    @style.setter
+    def style(self, value: str | NamedStyle) -> None: ...
     def offset(self, row: int, column: int) -> Cell: ...
 
+class Comment:
+    text: str
+    author: str | None
+    width: float | None
+    height: float | None
+    def __init__(
+        self,
+        text: str,
+        author: str | None = None,
+        width: float | None = None,
+        height: float | None = None,
+    ) -> None: ...
+    @property
+    def threaded(self) -> bool: ...
+    @property
+    def thread(self) -> list[dict[str, Any]]: ...
+
+class Hyperlink:
+    tooltip: str | None
+    display: str | None
+    def __init__(
+        self,
+        target: str | None = None,
+        location: str | None = None,
+        tooltip: str | None = None,
+        display: str | None = None,
+    ) -> None: ...
+    @property
+    def target(self) -> str | None: ...
+    @property
+    def location(self) -> str | None: ...
+    @property
+    def is_external(self) -> bool: ...
+    @property
+    def address(self) -> str: ...
+
+class InlineFont:
+    rFont: str | None
+    b: bool
+    i: bool
+    strike: bool
+    sz: float | None
+    u: str | None
+    vertAlign: str | None
+    color: str | Color | None
+    def __init__(
+        self,
+        rFont: str | None = None,
+        b: bool = False,
+        i: bool = False,
+        strike: bool = False,
+        color: str | Color | None = None,
+        sz: float | None = None,
+        u: str | None = None,
+        vertAlign: str | None = None,
+    ) -> None: ...
+
+class TextBlock:
+    font: InlineFont
+    text: str
+    def __init__(self, font: InlineFont | Font | Any, text: str) -> None: ...
+
+class CellRichText:
+    def __init__(self, *args: str | TextBlock | Iterable[str | TextBlock]) -> None: ...
+    def append(self, item: str | TextBlock) -> None: ...
+    def extend(self, items: Iterable[str | TextBlock]) -> None: ...
+    def as_list(self) -> list[str]: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> str | TextBlock: ...
+    def __iter__(self) -> Iterator[str | TextBlock]: ...
+
+class BlankPolicy:
+    def __init__(
+        self,
+        read: Literal["none", "empty_string", "skip"] = "none",
+        write_none: Literal["clear", "ignore"] = "clear",
+    ) -> None: ...
+    @property
+    def read(self) -> str: ...
+    @property
+    def write_none(self) -> str: ...
+
+class DocumentProperties:
+    creator: str | None
+    lastModifiedBy: str | None
+    title: str | None
+    application: str
+    @property
+    def created(self) -> datetime.datetime | None: ...
+    @created.setter
+    def created(self, value: datetime.datetime | datetime.date | str | None) -> None: ...
+    @property
+    def modified(self) -> datetime.datetime | None: ...
+    @modified.setter
+    def modified(self, value: datetime.datetime | datetime.date | str | None) -> None: ...
+
 class CellRangeIterator:
     def __iter__(self) -> CellRangeIterator: ...
     def __next__(self) -> tuple[Any, ...]: ...
 
+class CellRange:
+    min_row: int
+    min_col: int
+    max_row: int
+    max_col: int
+    def __init__(
+        self,
+        range_string: str | None = None,
+        min_col: int | None = None,
+        min_row: int | None = None,
+        max_col: int | None = None,
+        max_row: int | None = None,
+    ) -> None: ...
+    @property
+    def coord(self) -> str: ...
+    @property
+    def bounds(self) -> tuple[int, int, int, int]: ...
+    @property
+    def size(self) -> tuple[int, int]: ...
+    def __contains__(self, other: str | CellRange) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
+    def __hash__(self) -> int: ...
+
+class MultiCellRange:
+    @property
+    def ranges(self) -> list[CellRange]: ...
+    def __iter__(self) -> Iterator[CellRange]: ...
+    def __len__(self) -> int: ...
+    def __contains__(self, item: str | CellRange) -> bool: ...
+    def __eq__(self, other: object) -> bool: ...
+
+class SheetView:
+    showGridLines: bool
+    zoomScale: int
+    rightToLeft: bool
+    view: Literal["normal", "pageBreakPreview", "pageLayout"]
+    topLeftCell: str | None
+
+class PrintPageSetup:
+    orientation: Literal["portrait", "landscape"]
+    @property
+    def paperSize(self) -> int: ...
+    @paperSize.setter
+    def paperSize(self, value: int | str | None) -> None: ...
+    scale: int
+    fitToWidth: int | None
+    fitToHeight: int | None
+    fitToPage: bool
+    firstPageNumber: int | None
+    blackAndWhite: bool
+
+class PrintOptions:
+    horizontalCentered: bool
+    verticalCentered: bool
+    gridLines: bool
+    headings: bool
+
+class PageMargins:
+    left: float
+    right: float
+    top: float
+    bottom: float
+    header: float
+    footer: float
+
+class Outline:
+    summaryBelow: bool
+    summaryRight: bool
+
+class WorksheetProperties:
+    tabColor: str | Color | None
+    @property
+    def outlinePr(self) -> Outline: ...
+
+class SheetProtection:
+    sheet: bool
+    @property
+    def password(self) -> str | None: ...
+    @property
+    def algorithmName(self) -> str | None: ...
+    @property
+    def hashValue(self) -> str | None: ...
+    @property
+    def saltValue(self) -> str | None: ...
+    @property
+    def spinCount(self) -> int | None: ...
+    def enable(self) -> None: ...
+    def disable(self) -> None: ...
+    def verify_password(self, password: str) -> bool: ...
+
 class AutoFilter:
     ref: str | None
+    @property
+    def filter_columns(self) -> list[dict[str, Any]]: ...
+    def add_filter_column(self, col_id: int, vals: list[CellValue]) -> None: ...
+    def add_custom_filter(
+        self,
+        col_id: int,
+        operator: str,
+        value: CellValue,
+        operator2: str | None = None,
+        value2: CellValue = None,
+        join: Literal["and", "or"] = "and",
+    ) -> None: ...
+    def add_top10_filter(
+        self, col_id: int, value: float, top: bool = True, percent: bool = False
+    ) -> None: ...
+    def add_sort_condition(self, ref: str, descending: bool = False) -> None: ...
+    def clear_filters(self) -> None: ...
 
 class ColumnDimension:
     width: float | None
+    outlineLevel: int
+    hidden: bool
+    bestFit: bool
     @property
     def index(self) -> str: ...
 
 class ColumnDimensions:
     def __getitem__(self, key: str) -> ColumnDimension: ...
+    def group(
+        self,
+        start: str,
+        end: str | None = None,
+        outline_level: int = 1,
+        hidden: bool = False,
+    ) -> None: ...
 
 class RowDimension:
     height: float | None
+    outlineLevel: int
+    hidden: bool
     @property
     def index(self) -> int: ...
 
 class RowDimensions:
     def __getitem__(self, key: int) -> RowDimension: ...
+    def group(
+        self,
+        start: int,
+        end: int | None = None,
+        outline_level: int = 1,
+        hidden: bool = False,
+    ) -> None: ...
+
+class StyleSheet:
+    def to_json(self) -> str: ...
+    def from_json(self, text: str) -> None: ...
+    @property
+    def names(self) -> list[str]: ...
+    def add(
+        self,
+        name: str,
+        font: Font | None = None,
+        fill: PatternFill | None = None,
+        border: Border | None = None,
+        alignment: Alignment | None = None,
+        number_format: str | None = None,
+        protection: Protection | None = None,
+    ) -> None: ...
+    def __contains__(self, name: str) -> bool: ...
+    def __len__(self) -> int: ...
 
 class PivotTable:
     @property
@@ -374,10 +1196,107 @@ class PivotTable:
 
 class WriteOnlyWorkbook:
     def __init__(self, path: str) -> None: ...
+    @property
+    def properties(self) -> DocumentProperties: ...
     def create_sheet(self, name: str) -> None: ...
     def append_row(self, values: list[CellValue]) -> None: ...
     def append_rows(self, rows: list[list[CellValue]]) -> None: ...
+    def append_rows_from(
+        self, rows: Iterable[Iterable[CellValue]], chunk_size: int = 10000
+    ) -> int: ...
+    def append_from_parquet(
+        self,
+        path: str | os.PathLike[str],
+        include_headers: bool = True,
+        column_renames: dict[str, str] | None = None,
+        columns: list[str] | None = None,
+    ) -> dict[str, Any]: ...
+    def set_non_finite_numbers(self, policy: Literal["num", "empty", "error"]) -> None: ...
+    def set_number_precision(self, precision: Literal["shortest", "excel"]) -> None: ...
+    def flush(self) -> dict[str, Any]: ...
+    def set_checkpoint_interval(self, rows: int | None) -> None: ...
+    @property
+    def last_checkpoint(self) -> dict[str, Any] | None: ...
+    @property
+    def rows_written(self) -> int: ...
+    def close(self) -> None: ...
+
+class EditableWorkbook:
+    @property
+    def sheetnames(self) -> list[str]: ...
+    @property
+    def pending_edits(self) -> int: ...
+    def __getitem__(self, key: str) -> EditableWorksheet: ...
+    def __contains__(self, key: str) -> bool: ...
+    def __len__(self) -> int: ...
+    def discard_edits(self) -> None: ...
+    def save(self, filename: str | os.PathLike[str]) -> None: ...
+
+class EditableWorksheet:
+    @property
+    def title(self) -> str: ...
+    def get_value(self, row: int, column: int) -> CellValue: ...
+    def set_value(self, row: int, column: int, value: CellValue) -> None: ...
+    def __getitem__(self, key: str) -> CellValue: ...
+    def __setitem__(self, key: str, value: CellValue) -> None: ...
+
+class ReadOnlyWorkbook:
+    @property
+    def sheetnames(self) -> list[str]: ...
+    @property
+    def worksheets(self) -> list[ReadOnlyWorksheet]: ...
+    @property
+    def active(self) -> ReadOnlyWorksheet | None: ...
+    def __getitem__(self, key: str) -> ReadOnlyWorksheet: ...
+    def __contains__(self, key: str) -> bool: ...
+    def __len__(self) -> int: ...
     def close(self) -> None: ...
+    def __enter__(self) -> ReadOnlyWorkbook: ...
+    def __exit__(self, exc_type: Any = None, exc_val: Any = None, exc_tb: Any = None) -> bool: ...
+
+class ReadOnlyWorksheet:
+    @property
+    def title(self) -> str: ...
+    @property
+    def dimensions(self) -> str | None: ...
+    @property
+    def max_row(self) -> int | None: ...
+    @property
+    def max_column(self) -> int | None: ...
+    @overload
+    def iter_rows(
+        self,
+        min_row: int | None = None,
+        max_row: int | None = None,
+        min_col: int | None = None,
+        max_col: int | None = None,
+        values_only: Literal[False] = False,
+    ) -> Iterator[tuple[ReadOnlyCell, ...]]: ...
+    @overload
+    def iter_rows(
+        self,
+        min_row: int | None = None,
+        max_row: int | None = None,
+        min_col: int | None = None,
+        max_col: int | None = None,
+        *,
+        values_only: Literal[True],
+    ) -> Iterator[tuple[CellValue, ...]]: ...
+    @property
+    def values(self) -> Iterator[tuple[CellValue, ...]]: ...
+    def __iter__(self) -> Iterator[tuple[ReadOnlyCell, ...]]: ...
+
+class ReadOnlyCell:
+    @property
+    def row(self) -> int: ...
+    @property
+    def column(self) -> int: ...
+    @property
+    def value(self) -> CellValue: ...
+    @property
+    def number_format(self) -> str | None: ...
+    @property
+    def coordinate(self) -> str: ...
 
 class Font:
     name: str | None
@@ -442,7 +1361,9 @@ class PatternFill:
 class Side:
     style: str | None
     color: _ColorValue
-    def __init__(self, style: str | None = None, color: _ColorArg = None) -> None: ...
+    def __init__(
+        self, style: str | None = None, color: _ColorArg = None, border_style: str | None = None
+    ) -> None: ...
     def copy(self) -> Side: ...
 
 class Border:
@@ -465,6 +1386,29 @@ class Border:
     ) -> None: ...
     def copy(self) -> Border: ...
 
+class NamedStyle:
+    name: str
+    font: Font | None
+    fill: PatternFill | None
+    border: Border | None
+    alignment: Alignment | None
+    number_format: str | None
+    protection: Protection | None
+    builtinId: int | None
+    hidden: bool
+    def __init__(
+        self,
+        name: str = "Normal",
+        font: Font | None = None,
+        fill: PatternFill | None = None,
+        border: Border | None = None,
+        alignment: Alignment | None = None,
+        number_format: str | None = None,
+        protection: Protection | None = None,
+        builtinId: int | None = None,
+        hidden: bool = False,
+    ) -> None: ...
+
 class Protection:
     locked: bool
     hidden: bool
@@ -484,6 +1428,7 @@ class Color:
         indexed: int | None = None,
     ) -> None: ...
     def copy(self) -> Color: ...
+    def to_rgb(self, workbook: Workbook | None = None) -> str | None: ...
 
 class GradientStop:
     position: float
@@ -499,3 +1444,20 @@ class GradientFill:
     bottom: float
     stop: list[GradientStop]
     def copy(self) -> GradientFill: ...
+
+class ImportStyle:
+    def __init__(
+        self,
+        header_font: Font | None = ...,
+        header_fill: PatternFill | None = ...,
+        header_border: Border | None = ...,
+        freeze_header: bool = True,
+        auto_filter: bool = True,
+        size_columns: bool = True,
+    ) -> None: ...
+    @property
+    def freeze_header(self) -> bool: ...
+    @property
+    def auto_filter(self) -> bool: ...
+    @property
+    def size_columns(self) -> bool: ...
@@ -0,0 +1,86 @@
+"""Arrow IPC (Feather v2) import and export."""
+
+import pytest
+
+pa = pytest.importorskip("pyarrow")
+feather = pytest.importorskip("pyarrow.feather")
+ipc = pytest.importorskip("pyarrow.ipc")
+
+import rustypyxl
+
+
+def _table():
+    return pa.table(
+        {
+            "name": ["Alice", "Bob", None],
+            "age": [30, 25, 35],
+            "score": [95.5, 87.25, 92.0],
+        }
+    )
+
+
+@pytest.mark.parametrize("compression", ["uncompressed", "lz4", "zstd"])
+def test_import_feather(tmp_path, compression):
+    path = tmp_path / "data.feather"
+    feather.write_feather(_table(), path, compression=compression)
+
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Data")
+    result = wb.insert_from_arrow_ipc("Data", str(path))
+    assert result["rows_imported"] == 3
+    assert result["column_names"] == ["name", "age", "score"]
+    assert result["range"] == "A1:C4"
+    ws = wb["Data"]
+    assert ws["A2"].value == "Alice"
+    assert ws["A4"].value is None
+    assert ws["B3"].value == 25
+    assert ws["C3"].value == 87.25
+
+
+def test_import_stream_format_with_selection(tmp_path):
+    path = tmp_path / "data.arrows"
+    table = _table()
+    with ipc.new_stream(str(path), table.schema) as writer:
+        writer.write_table(table)
+
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Data")
+    result = wb.insert_from_arrow_ipc(
+        "Data", str(path), start_row=2, columns=["score", "name"], column_renames={"score": "pts"}
+    )
+    assert result["column_names"] == ["pts", "name"]
+    ws = wb["Data"]
+    assert ws["A2"].value == "pts"
+    assert ws["A3"].value == 95.5
+    assert ws["B3"].value == "Alice"
+
+    with pytest.raises(ValueError):
+        wb.insert_from_arrow_ipc("Data", str(path), columns=["missing"])
+
+
+@pytest.mark.parametrize("compression", ["none", "lz4", "zstd"])
+def test_export_reads_back_in_pyarrow(tmp_path, compression):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Data")
+    ws.append(["id", "label"])
+    ws.append([1, "a"])
+    ws.append([2, "b"])
+    path = tmp_path / "out.arrow"
+    result = wb.export_to_arrow_ipc(
+        "Data", str(path), compression=compression, column_renames={"label": "tag"}
+    )
+    assert result["rows_exported"] == 2
+    assert result["file_size"] > 0
+
+    table = feather.read_table(path)
+    assert table.column_names == ["id", "tag"]
+    assert table.column("id").to_pylist() == [1, 2]
+    assert table.column("tag").to_pylist() == ["a", "b"]
+
+
+def test_export_invalid_compression(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Data")
+    ws.append(["x"])
+    with pytest.raises(ValueError):
+        wb.export_to_arrow_ipc("Data", str(tmp_path / "out.arrow"), compression="snappy")
@@ -3,6 +3,7 @@ by openpyxl.
 """
 
 import openpyxl
+import pytest
 import rustypyxl
 
 
@@ -19,3 +20,67 @@ def test_auto_filter_ref_round_trips(tmp_path):
     wb.save(out)
     ows = openpyxl.load_workbook(out)["S"]
     assert ows.auto_filter.ref == "A1:B2"
+
+
+def _sales(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows(
+        "S",
+        [["Region", "Qty", "Price"], ["North", 3, 9.5], ["South", 12, 4.0], ["East", 7, 2.5]],
+    )
+    ws.auto_filter.ref = "A1:C4"
+    return wb, ws
+
+
+def test_filter_columns_round_trip(tmp_path):
+    wb, ws = _sales(tmp_path)
+    ws.auto_filter.add_filter_column(0, ["North", "East"])
+    ws.auto_filter.add_custom_filter(1, ">", 5, "<=", 20.0)
+    ws.auto_filter.add_sort_condition("C2:C4", descending=True)
+
+    out = str(tmp_path / "filters.xlsx")
+    wb.save(out)
+
+    oaf = openpyxl.load_workbook(out)["S"].auto_filter
+    assert oaf.ref == "A1:C4"
+    by_col = {fc.colId: fc for fc in oaf.filterColumn}
+    assert sorted(by_col[0].filters.filter) == ["East", "North"]
+    custom = by_col[1].customFilters
+    assert custom._and
+    assert [(c.operator, c.val) for c in custom.customFilter] == [
+        ("greaterThan", "5"),
+        ("lessThanOrEqual", "20"),
+    ]
+    assert oaf.sortState.sortCondition[0].descending
+
+    reloaded = rustypyxl.load_workbook(out)["S"].auto_filter
+    assert [f["col_id"] for f in reloaded.filter_columns] == [0, 1]
+    assert reloaded.filter_columns[0]["values"] == ["North", "East"]
+
+
+def test_refiltering_a_column_replaces_its_filter(tmp_path):
+    _, ws = _sales(tmp_path)
+    ws.auto_filter.add_filter_column(0, ["North"])
+    ws.auto_filter.add_top10_filter(0, 2, top=False)
+    assert ws.auto_filter.filter_columns == [
+        {"col_id": 0, "type": "top10", "value": 2.0, "top": False, "percent": False}
+    ]
+
+    ws.auto_filter.ref = "A1:C10"
+    assert len(ws.auto_filter.filter_columns) == 1
+    ws.auto_filter.clear_filters()
+    assert ws.auto_filter.filter_columns == []
+    assert ws.auto_filter.ref == "A1:C10"
+
+
+def test_filters_need_a_range():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    with pytest.raises(ValueError):
+        ws.auto_filter.add_filter_column(0, ["x"])
+    ws.auto_filter.ref = "A1:B2"
+    with pytest.raises(ValueError):
+        ws.auto_filter.add_custom_filter(0, "between", 1)
+    with pytest.raises(ValueError):
+        ws.auto_filter.add_custom_filter(0, ">", 1, operator2="<")
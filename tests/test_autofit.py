@@ -48,3 +48,38 @@ def test_auto_fit_all_sizes_every_populated_column():
     a = ws.auto_fit_column(1)
     c = ws.auto_fit_column(3)
     assert c > a
+
+
+def test_autofit_skips_title_row_and_is_read_by_openpyxl(tmp_path):
+    import openpyxl
+
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "Quarterly revenue by region and product line"
+    ws["A2"] = "Region"
+    ws["A3"] = "Northwest"
+    ws["B2"] = "Revenue"
+    ws["B3"] = 1234567.5
+    ws["B3"].number_format = "#,##0.00"  # displays "1,234,567.50"
+
+    widths = ws.autofit("A2:B100")
+    assert set(widths) == {"A", "B"}
+    assert 10 < widths["A"] < 12  # "Northwest", not the title
+    assert widths["B"] > widths["A"]
+
+    out = str(tmp_path / "fit.xlsx")
+    wb.save(out)
+    dims = openpyxl.load_workbook(out)["S"].column_dimensions
+    assert abs(dims["A"].width - widths["A"]) < 0.001
+    assert dims["A"].bestFit
+
+
+def test_autofit_whole_sheet_and_bad_range():
+    import pytest
+
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["C5"] = "text"
+    assert list(ws.autofit()) == ["C"]
+    with pytest.raises(ValueError):
+        ws.autofit("nonsense")
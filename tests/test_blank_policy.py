@@ -0,0 +1,88 @@
+"""Tests for the blank-cell policy (wb.blank_policy)."""
+
+import pytest
+
+import rustypyxl
+
+
+@pytest.fixture
+def sparse_wb():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = "a"
+    ws["C1"] = 3
+    ws["B3"] = "b"
+    return wb
+
+
+class TestBlankReads:
+    def test_default_matches_openpyxl(self, sparse_wb):
+        ws = sparse_wb.active
+        assert sparse_wb.blank_policy == rustypyxl.BlankPolicy()
+        assert ws["B1"].value is None
+        rows = list(ws.iter_rows(values_only=True))
+        assert rows == [("a", None, 3), (None, None, None), (None, "b", None)]
+
+    def test_empty_string(self, sparse_wb):
+        sparse_wb.blank_policy = rustypyxl.BlankPolicy(read="empty_string")
+        ws = sparse_wb.active
+        assert ws["B1"].value == ""
+        rows = list(ws.iter_rows(values_only=True))
+        assert rows == [("a", "", 3), ("", "", ""), ("", "b", "")]
+        assert sparse_wb.read_rows(ws.title)[0] == ["a", "", 3]
+
+    def test_skip_in_iteration(self, sparse_wb):
+        sparse_wb.blank_policy = rustypyxl.BlankPolicy(read="skip")
+        ws = sparse_wb.active
+        assert list(ws.iter_rows(values_only=True)) == [("a", 3), ("b",)]
+        assert list(ws.iter_cols(values_only=True)) == [("a",), ("b",), (3,)]
+        coords = [[c.coordinate for c in row] for row in ws.iter_rows()]
+        assert coords == [["A1", "C1"], ["B3"]]
+        assert sparse_wb.read_rows(ws.title) == [["a", 3], ["b"]]
+        # A cell read directly has nothing to skip
+        assert ws["B1"].value is None
+
+    def test_converters_skip_blanks(self, sparse_wb):
+        sparse_wb.blank_policy = rustypyxl.BlankPolicy(read="empty_string")
+        ws = sparse_wb.active
+        rows = sparse_wb.read_rows(ws.title, converters={"C": "float"})
+        assert rows[0] == ["a", "", 3.0]
+        assert rows[1] == ["", "", ""]
+
+
+class TestBlankWrites:
+    def test_clear_by_default(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws["A1"] = 1
+        ws["A1"] = None
+        assert ws["A1"].value is None
+
+    def test_ignore_leaves_cells_untouched(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.append([1, 2, 3])
+        wb.blank_policy = rustypyxl.BlankPolicy(write_none="ignore")
+        ws["A1"] = None
+        ws.cell(1, 2).value = None
+        wb.write_rows(ws.title, [[None, None, 30]])
+        assert list(ws.iter_rows(values_only=True)) == [(1, 2, 30)]
+
+        ws.append([None, "x"])
+        assert ws["A2"].value is None
+        assert ws["B2"].value == "x"
+
+    def test_invalid_mode_raises(self):
+        with pytest.raises(ValueError, match="empty_string"):
+            rustypyxl.BlankPolicy(read="blank")
+        with pytest.raises(ValueError, match="ignore"):
+            rustypyxl.BlankPolicy(write_none="keep")
+
+    def test_policy_attributes(self):
+        policy = rustypyxl.BlankPolicy(read="skip", write_none="ignore")
+        assert policy.read == "skip"
+        assert policy.write_none == "ignore"
+        assert "skip" in repr(policy)
@@ -0,0 +1,54 @@
+"""ws.add_calculated_column: fill a column with a per-row formula."""
+
+import zipfile
+
+import pytest
+import rustypyxl
+
+
+def _orders():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Orders")
+    wb.write_rows(
+        "Orders",
+        [["Item", "Qty", "Price", "Total"], ["Widget", 3, 2.5], ["Gadget", 5, 4.0]],
+    )
+    return wb, ws
+
+
+def test_table_column_keeps_structured_refs(tmp_path):
+    wb, ws = _orders()
+    ws.add_table("Sales", "A1:D3")
+    assert ws.add_calculated_column("D", "=[@Qty]*[@Price]") == (2, 3)
+
+    formula = "=Sales[[#This Row],[Qty]]*Sales[[#This Row],[Price]]"
+    assert ws["D2"].value == formula
+    assert ws["D3"].value == formula
+
+    out = tmp_path / "calc.xlsx"
+    wb.save(str(out))
+    with zipfile.ZipFile(out) as zf:
+        table_xml = zf.read("xl/tables/table1.xml").decode()
+    assert (
+        "<calculatedColumnFormula>Sales[[#This Row],[Qty]]*Sales[[#This Row],[Price]]"
+        "</calculatedColumnFormula>" in table_xml
+    )
+    assert rustypyxl.load_workbook(str(out))["Orders"]["D3"].value == formula
+
+
+def test_outside_a_table_writes_a1_refs():
+    wb, ws = _orders()
+    assert ws.add_calculated_column(4, "=[@Qty]*[@Price]", rows=(2, 3)) == (2, 3)
+    assert ws["D2"].value == "=B2*C2"
+    assert ws["D3"].value == "=B3*C3"
+
+
+def test_errors():
+    wb, ws = _orders()
+    with pytest.raises(ValueError):
+        ws.add_calculated_column("D", "=[@Qty]")
+    with pytest.raises(ValueError):
+        ws.add_calculated_column("D", "=[@Missing]", rows=(2, 3))
+    ws.add_table("Sales", "A1:D3")
+    with pytest.raises(ValueError):
+        ws.add_calculated_column("D", "=[@Qty]", rows=(1, 3))
@@ -112,3 +112,36 @@ class TestCellRepr:
         cell = ws.cell(1, 1)
         r = repr(cell)
         assert "Cell" in r
+
+
+class TestCellIdentity:
+    """Cells addressing the same position compare and hash alike."""
+
+    def test_repeated_lookups_are_equal(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        assert ws["A1"] == ws["A1"]
+        assert ws["A1"] == ws.cell(row=1, column=1)
+        assert ws["A1"] != ws["B1"]
+        assert hash(ws["C3"]) == hash(ws.cell(3, 3))
+        assert len({ws["A1"], ws["A1"], ws["B2"]}) == 2
+
+    def test_cells_of_other_sheets_differ(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        other = workbook_with_sheet.create_sheet("Other")
+        assert ws["A1"] != other["A1"]
+        assert ws["A1"] != rustypyxl.Cell(1, 1)
+        assert ws["A1"] != "A1"
+
+    def test_detached_cell_equals_only_itself(self):
+        cell = rustypyxl.Cell(2, 3)
+        assert cell == cell
+        assert cell != rustypyxl.Cell(2, 3)
+        assert cell.parent is None
+
+    def test_position_attributes(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        cell = ws["AB10"]
+        assert (cell.row, cell.column, cell.col_idx) == (10, 28, 28)
+        assert cell.column_letter == "AB"
+        assert cell.coordinate == "AB10"
+        assert cell.parent.title == ws.title
@@ -130,3 +130,77 @@ def test_chart_needs_a_series(tmp_path):
     ws = wb.create_sheet("S")
     with pytest.raises(ValueError):
         ws.add_chart("column", [], anchor="B1")
+
+
+class TestChartObjects:
+    """openpyxl-style chart objects placed with ws.add_chart(chart, anchor)."""
+
+    @staticmethod
+    def _data(wb):
+        ws = wb.create_sheet("Data")
+        wb.write_rows(
+            "Data",
+            [["Quarter", "North", "South"], ["Q1", 10, 7], ["Q2", 25, 9], ["Q3", 18, 12]],
+        )
+        return ws
+
+    def test_bar_chart_from_reference(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        ws = self._data(wb)
+        chart = rustypyxl.BarChart()
+        chart.title = "Sales"
+        chart.grouping = "stacked"
+        chart.y_axis_title = "Units"
+        chart.add_data("Data!$B$1:$C$4", titles_from_data=True)
+        chart.set_categories("Data!$A$2:$A$4")
+        assert chart.series_count == 2
+        ws.add_chart(chart, "E5")
+        out = str(tmp_path / "bar.xlsx")
+        wb.save(out)
+
+        from openpyxl.chart import BarChart
+
+        loaded = openpyxl.load_workbook(out)["Data"]._charts
+        assert len(loaded) == 1
+        got = loaded[0]
+        assert isinstance(got, BarChart)
+        assert got.type == "col"
+        assert got.grouping == "stacked"
+        assert len(got.series) == 2
+        assert got.anchor._from.col == 4 and got.anchor._from.row == 4
+
+    def test_line_and_pie_charts(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        ws = self._data(wb)
+        line = rustypyxl.LineChart()
+        line.add_data("Data!$B$2:$B$4")
+        pie = rustypyxl.PieChart()
+        pie.legend = None
+        pie.add_data("Data!$C$1:$C$4", titles_from_data=True)
+        pie.set_categories("Data!$A$2:$A$4")
+        ws.add_chart(line, "E1")
+        ws.add_chart(pie, anchor="M1")
+        out = str(tmp_path / "line_pie.xlsx")
+        wb.save(out)
+
+        from openpyxl.chart import LineChart, PieChart
+
+        kinds = {type(c) for c in openpyxl.load_workbook(out)["Data"]._charts}
+        assert kinds == {LineChart, PieChart}
+
+    def test_horizontal_bar_and_validation(self):
+        chart = rustypyxl.BarChart()
+        chart.type = "bar"
+        assert chart.type == "bar"
+        with pytest.raises(ValueError):
+            chart.type = "column"
+        with pytest.raises(ValueError):
+            chart.grouping = "piled"
+        with pytest.raises(ValueError):
+            chart.add_data("A1:A3")  # no sheet name
+
+    def test_empty_chart_object_rejected(self):
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("S")
+        with pytest.raises(ValueError):
+            ws.add_chart(rustypyxl.BarChart(), "B2")
@@ -0,0 +1,41 @@
+"""wb.check_limits(): reporting content over Excel's limits before saving."""
+
+import rustypyxl
+
+
+def test_clean_workbook_has_no_violations():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Data")
+    ws["A1"] = "fine"
+    ws["A2"] = "=SUM(B1:B10)"
+    ws["A3"].hyperlink = "https://example.com"
+    assert wb.check_limits() == []
+
+
+def test_long_string_and_formula():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Data")
+    ws["B3"] = "x" * 32_768
+    ws["C1"] = "=" + "1+" * 5_000 + "1"
+
+    found = wb.check_limits()
+    assert [v["kind"] for v in found] == ["formula_length", "string_length"]
+
+    string = found[1]
+    assert string["sheet"] == "Data"
+    assert string["location"] == "B3"
+    assert string["actual"] == 32_768
+    assert string["limit"] == 32_767
+    assert "exceeds Excel's maximum" in string["message"]
+
+
+def test_too_many_hyperlinks():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Links")
+    for row in range(1, 65_532):
+        ws.cell(row=row, column=1).hyperlink = "https://example.com"
+
+    (found,) = wb.check_limits()
+    assert found["kind"] == "hyperlinks_per_sheet"
+    assert found["actual"] == 65_531
+    assert found["location"] is None
@@ -0,0 +1,46 @@
+"""wb.check_references(): formulas, validations and names left pointing at
+removed sheets or holding #REF! after structural edits."""
+
+import rustypyxl
+
+
+def _workbook():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    summary = wb.active
+    summary.title = "Summary"
+    q1 = wb.create_sheet("Q1")
+    q1["B2"] = 10
+    summary["A1"] = "=SUM(Q1!B2:B9)"
+    summary["A2"] = "=A1*2"
+    summary["A3"] = '="Q1!B2 is just text"'
+    wb.create_named_range("Totals", q1, "$B$10")
+    return wb
+
+
+def test_clean_workbook_has_no_broken_references():
+    assert _workbook().check_references() == []
+
+
+def test_removed_sheet_is_reported():
+    wb = _workbook()
+    wb.remove(wb["Q1"])
+    broken = wb.check_references()
+    kinds = [(b["kind"], b["location"]) for b in broken]
+    assert kinds == [("formula", "A1"), ("defined_name", "Totals")]
+    first = broken[0]
+    assert first["sheet"] == "Summary"
+    assert first["text"] == "SUM(Q1!B2:B9)"
+    assert first["missing_sheet"] == "Q1"
+    assert "Q1" in first["problem"]
+    assert broken[1]["sheet"] is None
+
+
+def test_ref_error_is_reported():
+    wb = _workbook()
+    wb["Summary"]["C1"] = "=#REF!+1"
+    broken = wb.check_references()
+    assert len(broken) == 1
+    assert broken[0]["location"] == "C1"
+    assert broken[0]["missing_sheet"] is None
+    assert "#REF!" in broken[0]["problem"]
@@ -0,0 +1,59 @@
+"""wb.clone_structure: next-period templates without last period's data."""
+
+import openpyxl
+import rustypyxl
+
+
+def _last_period():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.title = "Sales"
+    ws["A1"] = "Region"
+    ws["B1"] = "Amount"
+    ws["A2"] = "North"
+    ws["B2"] = 120
+    ws["A3"] = "South"
+    ws["B3"] = 95
+    ws.column_dimensions["A"].width = 24
+    ws.merge_cells("C1:D1")
+    ws.add_data_validation("A2:A500", type="list", formula1='"North,South"')
+    wb.create_named_range("Regions", ws, "$A$2:$A$500")
+    wb.create_sheet("Notes")
+    return wb
+
+
+def test_keeps_headers_and_drops_data():
+    template = _last_period().clone_structure()
+    assert template.sheetnames == ["Sales", "Notes"]
+    ws = template["Sales"]
+    assert ws["A1"].value == "Region"
+    assert ws["B1"].value == "Amount"
+    assert ws["A2"].value is None
+    assert ws.max_row == 1
+
+
+def test_header_rows_is_configurable():
+    template = _last_period().clone_structure(header_rows=2)
+    ws = template["Sales"]
+    assert ws["A2"].value == "North"
+    assert ws["A3"].value is None
+
+
+def test_layout_survives_a_save(tmp_path):
+    path = tmp_path / "template.xlsx"
+    _last_period().clone_structure().save(str(path))
+
+    wb = openpyxl.load_workbook(path)
+    ws = wb["Sales"]
+    assert ws.column_dimensions["A"].width == 24
+    assert "C1:D1" in {str(r) for r in ws.merged_cells.ranges}
+    assert [str(dv.sqref) for dv in ws.data_validations.dataValidation] == ["A2:A500"]
+    assert "Regions" in wb.defined_names
+    assert ws["B2"].value is None
+
+
+def test_source_is_untouched():
+    wb = _last_period()
+    wb.clone_structure()
+    assert wb["Sales"]["B3"].value == 95
@@ -0,0 +1,47 @@
+"""Per-column text width statistics: ws.column_text_widths(range)."""
+
+import pytest
+import rustypyxl
+
+
+@pytest.fixture
+def ws():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Data")
+    ws.append(["Name", "Amount"])
+    for i in range(20):
+        ws.append(["abcde", 1234567])
+    ws.append(["a much longer outlier value", 1])
+    for row in range(2, 23):
+        ws.cell(row=row, column=2).number_format = "#,##0"
+    return ws
+
+
+def test_stats_per_column(ws):
+    stats = ws.column_text_widths()
+    assert [s["column"] for s in stats] == ["A", "B"]
+
+    a, b = stats
+    assert a["count"] == 22
+    assert a["max"] == 27
+    assert a["p95"] == 5
+    # 1234567 is measured as displayed: "1,234,567".
+    assert b["max"] == 9
+
+
+def test_range_limits_measured_cells(ws):
+    stats = ws.column_text_widths("A1:B1")
+    assert [s["max"] for s in stats] == [4, 6]
+    assert ws.column_text_widths("C:C") == []
+
+
+def test_wide_characters_count_double():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Data")
+    ws["A1"] = "東京"
+    assert ws.column_text_widths()[0]["max"] == 4
+
+
+def test_invalid_range(ws):
+    with pytest.raises(ValueError):
+        ws.column_text_widths("not a range")
@@ -0,0 +1,49 @@
+"""ws.storage = "columnar": plain values in per-column arrays, same results."""
+
+import pytest
+
+import rustypyxl
+
+
+def _fill(ws):
+    for row in range(1, 201):
+        ws.cell(row, 1).value = f"item-{row % 5}"
+        ws.cell(row, 2).value = row * 0.5
+    ws["B3"].number_format = "0.00"
+    ws["C1"].value = "=SUM(B1:B200)"
+
+
+def test_columnar_sheet_reads_and_saves_like_map(tmp_path):
+    wb = rustypyxl.Workbook()
+    assert wb.create_sheet("Plain").storage == "map"
+    ws = wb.create_sheet("Data", storage="columnar")
+    assert ws.storage == "columnar"
+    _fill(ws)
+    ws.insert_rows(2)
+
+    assert ws["A1"].value == "item-1"
+    assert ws["B4"].value == 1.5
+    assert ws["B4"].number_format == "0.00"
+    assert ws["B201"].value == 100.0
+    assert ws.max_row == 201
+
+    out = str(tmp_path / "columnar.xlsx")
+    wb.save(out)
+    loaded = rustypyxl.load_workbook(out)["Data"]
+    assert loaded["A201"].value == "item-0"
+    assert loaded["B4"].number_format == "0.00"
+    assert loaded["C1"].value == "=SUM(B1:B200)"
+
+    ws.storage = "map"
+    assert ws.storage == "map"
+    assert ws["B201"].value == 100.0
+
+
+def test_unknown_storage_is_rejected():
+    wb = rustypyxl.Workbook()
+    with pytest.raises(ValueError):
+        wb.create_sheet("Data", storage="rows")
+    ws = wb.create_sheet("Other")
+    with pytest.raises(ValueError):
+        ws.storage = "rows"
+    assert ws.storage == "map"
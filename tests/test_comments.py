@@ -1,5 +1,6 @@
 """Tests for comment support."""
 
+import pytest
 import rustypyxl
 
 
@@ -46,3 +47,103 @@ class TestCommentRoundtrip:
         assert ws["A1"].comment is not None, "comment lost on load"
         assert "first comment" in ws["A1"].comment
         assert "second comment" in ws.cell(2, 2).comment
+
+
+class TestCommentShape:
+    """Test comment box placement, size and visibility."""
+
+    def test_default_shape_is_none(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws["A1"].comment = "note"
+        assert ws.get_comment_shape("A1") is None
+
+    def test_shape_round_trips(self, workbook_with_sheet, tmp_path):
+        ws = workbook_with_sheet.active
+        ws["B2"].comment = "Always visible"
+        ws.set_comment_shape("B2", visible=True, width=200, height=100, anchor="D1")
+        assert ws.get_comment_shape("B2") == {
+            "visible": True,
+            "width": 200.0,
+            "height": 100.0,
+            "anchor": "D1",
+        }
+
+        path = str(tmp_path / "shapes.xlsx")
+        workbook_with_sheet.save(path)
+        ws2 = rustypyxl.load_workbook(path).active
+        assert ws2["B2"].comment == "Always visible"
+        assert ws2.get_comment_shape("B2") == ws.get_comment_shape("B2")
+
+    def test_shape_moves_with_inserted_rows(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws["B2"].comment = "moves"
+        ws.set_comment_shape("B2", visible=True, anchor="D2")
+        ws.insert_rows(1, 2)
+        assert ws.get_comment_shape("B2") is None
+        assert ws.get_comment_shape("B4")["anchor"] == "D4"
+
+    def test_invalid_size_rejected(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        with pytest.raises(ValueError):
+            ws.set_comment_shape("A1", width=0)
+
+
+class TestCommentObjects:
+    """Comments with authors, box sizes and threads."""
+
+    def test_comment_object_round_trips(self, workbook_with_sheet, tmp_path):
+        from rustypyxl.comments import Comment
+
+        ws = workbook_with_sheet.active
+        ws["A1"].comment = Comment("Check totals", "Ann", width=200, height=80)
+        assert ws["A1"].comment == "Check totals"
+
+        path = str(tmp_path / "authors.xlsx")
+        workbook_with_sheet.save(path)
+        comment = rustypyxl.load_workbook(path).active.get_comment("A1")
+        assert comment.text == "Check totals"
+        assert comment.author == "Ann"
+        assert not comment.threaded
+
+        import openpyxl
+
+        ox = openpyxl.load_workbook(path).active["A1"].comment
+        assert ox.text == "Check totals"
+        assert ox.author == "Ann"
+
+    def test_author_loaded_from_openpyxl_file(self, fixtures_dir):
+        ws = rustypyxl.load_workbook(str(fixtures_dir / "comments.xlsx"))["Commented"]
+        assert ws.get_comment("A1").author == "author"
+        assert ws.get_comment("C3") is None
+
+    def test_threaded_comments(self, workbook_with_sheet, tmp_path):
+        ws = workbook_with_sheet.active
+        ws.add_threaded_comment("B2", "Why is this negative?", "Ann")
+        ws.add_threaded_comment("B2", "It's a refund", "Bob")
+        assert ws["B2"].comment == "Why is this negative?"
+
+        path = str(tmp_path / "threaded.xlsx")
+        workbook_with_sheet.save(path)
+        comment = rustypyxl.load_workbook(path).active.get_comment("B2")
+        assert comment.threaded
+        assert [(e["author"], e["text"]) for e in comment.thread] == [
+            ("Ann", "Why is this negative?"),
+            ("Bob", "It's a refund"),
+        ]
+
+        # Older readers see the placeholder Excel writes in the legacy part.
+        import openpyxl
+
+        legacy = openpyxl.load_workbook(path).active["B2"].comment
+        assert legacy.text.startswith("[Threaded comment]")
+
+    def test_replying_to_a_note_fails(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws["A1"].comment = "plain note"
+        with pytest.raises(ValueError):
+            ws.add_threaded_comment("A1", "reply", "Ann")
+
+    def test_invalid_comment_value(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        with pytest.raises(TypeError):
+            ws["A1"].comment = 42
@@ -0,0 +1,59 @@
+"""ws.compute: SUM/AVERAGE/MIN/MAX/COUNTIF over a range without a formula."""
+
+import openpyxl
+import pytest
+import rustypyxl
+
+
+def _sheet():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.append(["Item", "Amount"])
+    for item, amount in [("a", 10), ("b", 2.5), ("c", "n/a"), ("d", -4), ("e", True)]:
+        ws.append([item, amount])
+    return ws
+
+
+def test_aggregates():
+    ws = _sheet()
+    assert ws.compute("SUM", "B2:B1000") == 8.5
+    assert ws.compute("average", "B2:B1000") == pytest.approx(8.5 / 3)
+    assert ws.compute("MIN", "B2:B1000") == -4
+    assert ws.compute("MAX", "B2:B1000") == 10
+    assert ws.compute("COUNT", "B1:B1000") == 3
+
+
+def test_countif():
+    ws = _sheet()
+    assert ws.compute("COUNTIF", "B2:B6", ">0") == 2
+    assert ws.compute("COUNTIF", "A2:A100", "c") == 1
+
+
+def test_no_numbers():
+    ws = _sheet()
+    assert ws.compute("AVERAGE", "A2:A6") == "#DIV/0!"
+    assert ws.compute("SUM", "D1:D10") == 0
+
+
+def test_cached_formula_results(tmp_path):
+    path = tmp_path / "totals.xlsx"
+    owb = openpyxl.Workbook()
+    ows = owb.active
+    ows["A1"] = 5
+    ows["A2"] = "=A1*2"
+    owb.save(path)
+    # openpyxl writes no cached value, so the formula counts as blank.
+    ws = rustypyxl.load_workbook(path).active
+    assert ws.compute("SUM", "A1:A2") == 5
+    assert ws.compute("COUNTA", "A1:A2") == 1
+
+
+def test_errors():
+    ws = _sheet()
+    with pytest.raises(ValueError):
+        ws.compute("MEDIAN", "B2:B6")
+    with pytest.raises(ValueError):
+        ws.compute("COUNTIF", "B2:B6")
+    with pytest.raises(ValueError):
+        ws.compute("SUM", "nonsense")
@@ -0,0 +1,31 @@
+"""wb.conditional_format_results(): which cells the conditional formats
+highlight, worked out from the current values."""
+
+import rustypyxl
+
+
+def test_results_after_round_trip(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows("S", [[5], [15], [25]])
+    ws.add_conditional_formatting(
+        "A1:A3",
+        {"type": "cellIs", "operator": "greaterThan", "formula": "10", "fill": "FFFF0000"},
+    )
+    out = str(tmp_path / "cf.xlsx")
+    wb.save(out)
+
+    results = rustypyxl.load_workbook(out).conditional_format_results("S")
+    assert [(r["row"], r["column"]) for r in results] == [(2, 1), (3, 1)]
+    assert results[0]["fill"] == "FFFF0000"
+    assert results[0]["data_bar"] is None
+
+
+def test_color_scale_and_data_bar():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows("S", [[0], [50], [100]])
+    ws.add_conditional_formatting("A1:A3", {"type": "dataBar", "color": "638EC6"})
+    results = wb.conditional_format_results("S")
+    assert [r["data_bar"] for r in results] == [0.0, 0.5, 1.0]
+    assert wb.conditional_format_results("S")[0]["fill"] is None
@@ -0,0 +1,49 @@
+"""Tests for exporting a whole workbook to CSV files."""
+
+import csv
+import json
+
+import rustypyxl
+
+
+def _read_csv(path):
+    with open(path, newline="", encoding="utf-8") as f:
+        return list(csv.reader(f))
+
+
+class TestExportAllToCsv:
+    def test_one_file_per_sheet_with_manifest(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sales")
+        wb.create_sheet("Q1/Q2")
+        wb.write_rows("Sales", [["Name", "Amount"], ["Alice", 10], ["Bob", 2.5]])
+        wb.write_rows("Q1/Q2", [["x, y", 'say "hi"']])
+
+        result = wb.export_all_to_csv(tmp_path / "out")
+
+        assert [r["file"] for r in result] == ["Sales.csv", "Q1_Q2.csv"]
+        manifest = json.loads((tmp_path / "out" / "index.json").read_text())
+        assert manifest["sheets"][0] == {
+            "name": "Sales",
+            "file": "Sales.csv",
+            "rows": 3,
+            "columns": 2,
+        }
+        assert manifest["sheets"][1]["name"] == "Q1/Q2"
+
+        assert _read_csv(tmp_path / "out" / "Sales.csv") == [
+            ["Name", "Amount"],
+            ["Alice", "10"],
+            ["Bob", "2.5"],
+        ]
+        assert _read_csv(tmp_path / "out" / "Q1_Q2.csv") == [["x, y", 'say "hi"']]
+
+    def test_colliding_names_are_deduplicated(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("a:b")
+        wb.create_sheet("a?b")
+
+        result = wb.export_all_to_csv(tmp_path)
+
+        assert [r["file"] for r in result] == ["a_b.csv", "a_b_2.csv"]
+        assert (tmp_path / "a_b_2.csv").read_text() == ""
@@ -0,0 +1,135 @@
+"""wb.insert_from_csv and ws.export_to_csv: single-sheet CSV import/export."""
+
+import csv
+import datetime
+
+import pytest
+import rustypyxl
+
+
+def _sheet(wb, name="Data"):
+    wb.create_sheet(name)
+    return wb[name]
+
+
+class TestInsertFromCsv:
+    def test_types_are_inferred(self, tmp_path):
+        path = tmp_path / "in.csv"
+        path.write_text(
+            "id,qty,ok,when,note\n"
+            '007,1.5,TRUE,2024-03-15 10:30:00,"a, ""b"""\n'
+            "8,-2,false,2024-03-16,\n",
+            encoding="utf-8",
+        )
+        wb = rustypyxl.Workbook()
+        ws = _sheet(wb)
+        result = wb.insert_from_csv("Data", path)
+        assert result["rows_imported"] == 2
+        assert result["range"] == "A1:E3"
+        assert result["column_names"] == ["id", "qty", "ok", "when", "note"]
+        assert ws["A2"].value == "007"
+        assert ws["B2"].value == 1.5
+        assert ws["C2"].value is True
+        assert ws["D2"].value == datetime.datetime(2024, 3, 15, 10, 30)
+        assert ws["E2"].value == 'a, "b"'
+        assert ws["A3"].value == 8
+        assert ws["E3"].value is None
+
+    def test_dialect_and_selection(self, tmp_path):
+        path = tmp_path / "in.csv"
+        path.write_bytes(b"na\xefve;05/01/2024;x\n")
+        wb = rustypyxl.Workbook()
+        ws = _sheet(wb)
+        result = wb.insert_from_csv(
+            "Data",
+            str(path),
+            start_row=2,
+            start_col=2,
+            has_headers=False,
+            delimiter=";",
+            encoding="latin-1",
+            date_formats=["%d/%m/%Y"],
+            columns=["Column2", "Column1"],
+        )
+        assert result["column_names"] == ["Column2", "Column1"]
+        assert ws["B2"].value == datetime.datetime(2024, 1, 5)
+        assert ws["C2"].value == "naïve"
+
+    def test_text_only_and_errors(self, tmp_path):
+        path = tmp_path / "in.csv"
+        path.write_text("a\n1\n")
+        wb = rustypyxl.Workbook()
+        ws = _sheet(wb)
+        wb.insert_from_csv("Data", path, infer_types=False)
+        assert ws["A2"].value == "1"
+        with pytest.raises(ValueError):
+            wb.insert_from_csv("Data", path, delimiter=";;")
+        with pytest.raises(ValueError):
+            wb.insert_from_csv("Data", path, encoding="utf-16")
+        with pytest.raises(ValueError):
+            wb.insert_from_csv("Data", tmp_path / "missing.csv")
+
+
+class TestExportToCsv:
+    def test_export_reads_back_with_csv_module(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.append(["name", "amount", "paid"])
+        ws.append(["x, y", 2.5, True])
+        ws.append(["z", 3, False])
+        path = tmp_path / "out.csv"
+        result = ws.export_to_csv(path)
+        assert result["rows_exported"] == 3
+        assert result["columns_exported"] == 3
+        assert result["file_size"] == path.stat().st_size
+        with open(path, newline="", encoding="utf-8") as f:
+            assert list(csv.reader(f)) == [
+                ["name", "amount", "paid"],
+                ["x, y", "2.5", "TRUE"],
+                ["z", "3", "FALSE"],
+            ]
+
+    def test_export_options(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws["A1"] = "café"
+        ws["B1"] = 1
+        ws["A2"] = datetime.datetime(2024, 3, 15, 9, 5)
+        path = tmp_path / "out.csv"
+        ws.export_to_csv(
+            path,
+            delimiter="\t",
+            quoting="nonnumeric",
+            encoding="utf-8-sig",
+            line_terminator="\n",
+            date_format="%d/%m/%Y",
+        )
+        assert path.read_bytes() == (
+            b'\xef\xbb\xbf"caf\xc3\xa9"\t1\n"15/03/2024"\t\n'
+        )
+        ws.export_to_csv(path, range="A1", encoding="cp1252")
+        assert path.read_bytes() == b"caf\xe9\r\n"
+
+    def test_round_trip(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.append(["label", "value"])
+        ws.append(["line\nbreak", 1.25])
+        path = tmp_path / "rt.csv"
+        ws.export_to_csv(path)
+        target = rustypyxl.Workbook()
+        copy = _sheet(target, "Copy")
+        target.insert_from_csv("Copy", path)
+        assert copy["A2"].value == "line\nbreak"
+        assert copy["B2"].value == 1.25
+
+    def test_bad_quoting(self, tmp_path):
+        ws = rustypyxl.Workbook().create_sheet("Sheet")
+        ws["A1"] = "a,b"
+        with pytest.raises(ValueError):
+            ws.export_to_csv(tmp_path / "x.csv", quoting="sometimes")
+        with pytest.raises(ValueError):
+            ws.export_to_csv(tmp_path / "x.csv", quoting="none")
@@ -0,0 +1,72 @@
+"""Raw XML fragments in sheet XML and extra package parts."""
+
+import zipfile
+
+import pytest
+
+import rustypyxl
+
+EXT = '<ext uri="{0000-custom}" xmlns:my="urn:my"><my:note>hi</my:note></ext>'
+
+
+def _read(path, name):
+    with zipfile.ZipFile(path) as zf:
+        return zf.read(name).decode()
+
+
+def test_fragments_written_at_positions(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = 1
+    ws.add_xml_fragment('<sheetCalcPr fullCalcOnLoad="1"/>', position="after_sheet_data")
+    ws.add_xml_fragment(EXT)
+    path = tmp_path / "frag.xlsx"
+    wb.save(str(path))
+
+    xml = _read(path, "xl/worksheets/sheet1.xml")
+    assert '</sheetData><sheetCalcPr fullCalcOnLoad="1"/>' in xml
+    assert xml.endswith(f"<extLst>{EXT}</extLst></worksheet>")
+    assert rustypyxl.load_workbook(str(path)).active["A1"].value == 1
+
+
+def test_clear_fragments(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active.add_xml_fragment(EXT)
+    wb.active.clear_xml_fragments()
+    path = tmp_path / "clear.xlsx"
+    wb.save(str(path))
+    assert "extLst" not in _read(path, "xl/worksheets/sheet1.xml")
+
+
+def test_bad_fragments_rejected():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    with pytest.raises(ValueError):
+        ws.add_xml_fragment("<ext><open></ext>")
+    with pytest.raises(ValueError):
+        ws.add_xml_fragment(EXT, position="somewhere")
+
+
+def test_custom_part_saved_with_relationship(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.add_custom_part(
+        "customXml/item1.xml",
+        "<data/>",
+        content_type="application/xml",
+        rel_type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml",
+    )
+    path = tmp_path / "part.xlsx"
+    wb.save(str(path))
+
+    assert _read(path, "customXml/item1.xml") == "<data/>"
+    assert 'PartName="/customXml/item1.xml"' in _read(path, "[Content_Types].xml")
+    assert 'Target="../customXml/item1.xml"' in _read(path, "xl/_rels/workbook.xml.rels")
+
+
+def test_custom_part_paths_checked():
+    wb = rustypyxl.Workbook()
+    with pytest.raises(ValueError):
+        wb.add_custom_part("xl/styles.xml", b"<x/>", content_type="application/xml")
+    with pytest.raises(ValueError):
+        wb.add_custom_part("extra/a.bin", b"\x00")
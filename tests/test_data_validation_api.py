@@ -38,3 +38,88 @@ def test_whole_number_between_validation(tmp_path):
     dv = list(ows.data_validations.dataValidation)[0]
     assert dv.type == "whole"
     assert dv.operator == "between"
+
+
+def test_openpyxl_style_class_with_late_add(tmp_path):
+    from rustypyxl.worksheet.datavalidation import DataValidation
+
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    dv = DataValidation(
+        type="list",
+        formula1='"Dog,Cat,Bat"',
+        allow_blank=True,
+        showErrorMessage=True,
+        errorTitle="Invalid",
+        error="Pick from the list",
+        promptTitle="Pet",
+        prompt="Choose a pet",
+        showInputMessage=True,
+    )
+    ws.add_data_validation(dv)
+    assert ws.data_validations == []
+
+    # Cells added after attaching still count, as in openpyxl.
+    dv.add("A1:A100")
+    dv.add(ws["C5"])
+    assert dv.sqref == "A1:A100 C5"
+    assert "A50" in dv and "C5" in dv and "B1" not in dv
+
+    rules = ws.data_validations
+    assert len(rules) == 1
+    assert rules[0]["sqref"] == "A1:A100 C5"
+    assert rules[0]["error"] == "Pick from the list"
+
+    dv.prompt = "Choose one pet"
+    assert ws.data_validations[0]["prompt"] == "Choose one pet"
+
+    out = tmp_path / "pets.xlsx"
+    wb.save(out)
+    rule = openpyxl.load_workbook(out).active.data_validations.dataValidation[0]
+    assert str(rule.sqref) == "A1:A100 C5"
+    assert rule.errorTitle == "Invalid" and rule.prompt == "Choose one pet"
+    assert rule.allow_blank and rule.showErrorMessage and rule.showInputMessage
+
+
+def test_dropdown_flag_and_bad_ranges():
+    import pytest
+
+    dv = rustypyxl.DataValidation(type="list", formula1="$Z$1:$Z$3", showDropDown=True)
+    assert dv.hide_drop_down
+    with pytest.raises(ValueError):
+        dv.add("not a range")
+    with pytest.raises(ValueError):
+        rustypyxl.DataValidation(sqref="A1:??")
+
+
+def test_openpyxl_rule_is_accepted(tmp_path):
+    from openpyxl.worksheet.datavalidation import DataValidation as OxDV
+
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    odv = OxDV(type="whole", operator="greaterThan", formula1="0", showErrorMessage=True)
+    odv.add("B2:B9")
+    ws.add_data_validation(odv)
+    rule = ws.data_validations[0]
+    assert (rule["type"], rule["operator"], rule["sqref"]) == ("whole", "greaterThan", "B2:B9")
+
+
+def test_validations_load_from_openpyxl_files(tmp_path):
+    from openpyxl.worksheet.datavalidation import DataValidation as OxDV
+
+    owb = openpyxl.Workbook()
+    ows = owb.active
+    odv = OxDV(type="textLength", operator="lessThanOrEqual", formula1="5", showDropDown=True)
+    odv.add("A1:A3")
+    odv.add("C1")
+    ows.add_data_validation(odv)
+    path = tmp_path / "from_openpyxl.xlsx"
+    owb.save(path)
+
+    rules = rustypyxl.load_workbook(str(path)).active.data_validations
+    assert len(rules) == 1
+    assert rules[0]["sqref"] == "A1:A3 C1"
+    assert rules[0]["type"] == "textLength"
+    assert rules[0]["show_dropdown"] is False
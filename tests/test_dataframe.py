@@ -0,0 +1,75 @@
+"""ws.from_dataframe / ws.to_dataframe: pandas interop through Arrow."""
+
+import datetime
+
+import pytest
+
+pd = pytest.importorskip("pandas")
+pytest.importorskip("pyarrow")
+
+import rustypyxl
+
+
+def _frame():
+    return pd.DataFrame(
+        {
+            "name": ["a", "b", None],
+            "qty": [1, 2, 3],
+            "price": [1.5, 2.25, 4.0],
+            "ok": [True, False, True],
+            "when": pd.to_datetime(["2024-03-15 10:30", "2024-03-16", "2024-03-17 23:59:59"]),
+        }
+    )
+
+
+def test_from_dataframe_writes_header_and_values():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    result = ws.from_dataframe(_frame())
+    assert result["rows_written"] == 3
+    assert result["column_names"] == ["name", "qty", "price", "ok", "when"]
+    assert [c.value for c in ws[1]] == ["name", "qty", "price", "ok", "when"]
+    assert ws["A2"].value == "a"
+    assert ws["A4"].value is None
+    assert ws["B3"].value == 2
+    assert ws["C3"].value == 2.25
+    assert ws["D3"].value is False
+    assert ws["E2"].value == datetime.datetime(2024, 3, 15, 10, 30)
+
+
+def test_from_dataframe_index_and_offset():
+    df = pd.DataFrame({"v": [10, 20]}, index=pd.Index(["x", "y"], name="key"))
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    ws.from_dataframe(df, start_row=3, start_col=2, index=True)
+    assert ws["B3"].value == "key"
+    assert ws["C3"].value == "v"
+    assert ws["B4"].value == "x"
+    assert ws["C5"].value == 20
+
+
+def test_round_trip_keeps_column_types():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    ws.from_dataframe(_frame())
+    out = ws.to_dataframe()
+    assert list(out.columns) == ["name", "qty", "price", "ok", "when"]
+    assert out["qty"].tolist() == [1, 2, 3]
+    assert out["price"].tolist() == [1.5, 2.25, 4.0]
+    assert out["ok"].tolist() == [True, False, True]
+    assert pd.api.types.is_datetime64_any_dtype(out["when"])
+    assert out["when"].iloc[2] == pd.Timestamp("2024-03-17 23:59:59")
+    assert out["name"].iloc[2] is None
+
+
+def test_to_dataframe_range_without_header():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    ws.append(["h1", "h2"])
+    ws.append([1, "x"])
+    ws.append([2, "y"])
+    out = ws.to_dataframe("A2:B3", header=False)
+    assert list(out.columns) == ["Column1", "Column2"]
+    assert out["Column1"].tolist() == [1, 2]
+    assert out["Column2"].tolist() == ["x", "y"]
+
+
+def test_empty_sheet_gives_empty_frame():
+    out = rustypyxl.Workbook().create_sheet("Sheet").to_dataframe()
+    assert out.empty
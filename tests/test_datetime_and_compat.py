@@ -28,8 +28,9 @@ class TestDatetimeValues:
         ws["A1"] = d
         wb.save(temp_xlsx_path)
 
+        # Saved as a serial, so it loads as a datetime at midnight, as in openpyxl.
         wb2 = rustypyxl.load_workbook(temp_xlsx_path)
-        assert wb2["Data"]["A1"].value == d
+        assert wb2["Data"]["A1"].value == datetime.datetime(2024, 3, 15)
 
     def test_openpyxl_reads_datetime_cells(self, temp_xlsx_path):
         wb = rustypyxl.Workbook()
@@ -43,7 +44,8 @@ class TestDatetimeValues:
         owb = openpyxl.load_workbook(temp_xlsx_path)
         ows = owb["Data"]
         assert ows["A1"].value == dt
-        assert ows["A2"].value == d
+        assert ows["A2"].value == datetime.datetime(2024, 3, 15)
+        assert ows["A2"].is_date
 
     def test_streaming_datetime_not_dropped(self, temp_xlsx_path):
         dt = datetime.datetime(2025, 1, 2, 3, 4, 5)
@@ -57,6 +59,112 @@ class TestDatetimeValues:
         assert wb["S"]["B1"].value == 7
 
 
+class TestStructuredDates:
+    """Dates given as date/time/datetime keep their kind, in memory and
+    through a save."""
+
+    def test_kind_is_kept_in_memory(self, workbook_with_sheet):
+        ws = workbook_with_sheet["Test"]
+        ws["A1"] = datetime.date(2024, 3, 15)
+        ws["A2"] = datetime.time(18, 15, 30)
+        ws["A3"] = datetime.datetime(2024, 3, 15, 10, 30, 45, 250000)
+        assert ws["A1"].value == datetime.date(2024, 3, 15)
+        assert type(ws["A1"].value) is datetime.date
+        assert ws["A2"].value == datetime.time(18, 15, 30)
+        assert ws["A3"].value == datetime.datetime(2024, 3, 15, 10, 30, 45, 250000)
+
+    def test_time_roundtrip(self, temp_xlsx_path):
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+        ws["A1"] = datetime.time(7, 5, 3)
+        wb.save(temp_xlsx_path)
+
+        assert rustypyxl.load_workbook(temp_xlsx_path)["Data"]["A1"].value == (
+            datetime.time(7, 5, 3)
+        )
+
+    def test_aware_datetime_stored_in_utc(self, workbook_with_sheet):
+        ws = workbook_with_sheet["Test"]
+        tz = datetime.timezone(datetime.timedelta(hours=2))
+        ws["A1"] = datetime.datetime(2024, 3, 1, 14, 0, tzinfo=tz)
+        assert ws["A1"].value == datetime.datetime(2024, 3, 1, 12, 0)
+
+    def test_date_cells_format_like_serials(self, workbook_with_sheet):
+        ws = workbook_with_sheet["Test"]
+        ws["A1"] = datetime.date(2023, 1, 15)
+        assert ws["A1"].display_value == "2023-01-15"
+        ws["A1"].number_format = "d-mmm-yyyy"
+        assert ws["A1"].display_value == "15-Jan-2023"
+
+
+class TestExcelSerialDates:
+    """Numbers in a date format (how openpyxl and Excel store dates) load as
+    datetime objects, as they do in openpyxl."""
+
+    def test_openpyxl_dates_load_as_datetimes(self, temp_xlsx_path):
+        owb = openpyxl.Workbook()
+        ows = owb.active
+        ows["A1"] = datetime.datetime(2024, 3, 15, 10, 30, 45)
+        ows["A2"] = datetime.date(2024, 3, 15)
+        ows["A3"] = datetime.time(18, 15)
+        ows["A4"] = 45366
+        owb.save(temp_xlsx_path)
+
+        ws = rustypyxl.load_workbook(temp_xlsx_path).active
+        assert ws["A1"].value == datetime.datetime(2024, 3, 15, 10, 30, 45)
+        assert ws["A2"].value == datetime.datetime(2024, 3, 15)
+        assert ws["A3"].value == datetime.time(18, 15)
+        assert ws["A4"].value == 45366
+
+    def test_matches_openpyxl(self, temp_xlsx_path):
+        owb = openpyxl.Workbook()
+        ows = owb.active
+        ows["A1"] = 45366.4375
+        ows["A1"].number_format = "dd/mm/yyyy hh:mm"
+        ows["A2"] = 0.5
+        ows["A2"].number_format = "h:mm"
+        owb.save(temp_xlsx_path)
+
+        expected = openpyxl.load_workbook(temp_xlsx_path).active
+        ws = rustypyxl.load_workbook(temp_xlsx_path).active
+        for coord in ("A1", "A2"):
+            assert ws[coord].value == expected[coord].value
+
+    def test_1904_workbooks(self, temp_xlsx_path):
+        from openpyxl.utils.datetime import CALENDAR_MAC_1904
+
+        owb = openpyxl.Workbook()
+        owb.epoch = CALENDAR_MAC_1904
+        owb.active["A1"] = datetime.datetime(2024, 3, 15, 12)
+        owb.save(temp_xlsx_path)
+
+        wb = rustypyxl.load_workbook(temp_xlsx_path)
+        assert wb.active["A1"].value == datetime.datetime(2024, 3, 15, 12)
+        wb.save(temp_xlsx_path)
+        assert openpyxl.load_workbook(temp_xlsx_path).active["A1"].value == (
+            datetime.datetime(2024, 3, 15, 12)
+        )
+
+    def test_read_only_rows(self, temp_xlsx_path):
+        owb = openpyxl.Workbook()
+        owb.active.append([datetime.datetime(2001, 2, 3, 4, 5, 6), 1.5])
+        owb.save(temp_xlsx_path)
+
+        wb = rustypyxl.load_workbook(temp_xlsx_path, read_only=True)
+        rows = list(wb.active.iter_rows(values_only=True))
+        assert rows == [(datetime.datetime(2001, 2, 3, 4, 5, 6), 1.5)]
+
+    def test_resave_keeps_the_date(self, temp_xlsx_path):
+        owb = openpyxl.Workbook()
+        owb.active["A1"] = datetime.datetime(1999, 12, 31, 23, 59, 59)
+        owb.save(temp_xlsx_path)
+
+        rustypyxl.load_workbook(temp_xlsx_path).save(temp_xlsx_path)
+        ows = openpyxl.load_workbook(temp_xlsx_path).active
+        assert ows["A1"].value == datetime.datetime(1999, 12, 31, 23, 59, 59)
+        assert ows["A1"].is_date
+
+
 class TestNonFiniteFloats:
     def test_nan_and_inf_produce_loadable_file(self, temp_xlsx_path):
         wb = rustypyxl.Workbook()
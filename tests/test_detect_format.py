@@ -0,0 +1,89 @@
+"""rustypyxl.detect_format: identify a file by content, not extension."""
+
+import io
+import zipfile
+
+import openpyxl
+import pytest
+import rustypyxl
+
+
+def _xlsx_bytes():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active["A1"] = "x"
+    buf = io.BytesIO()
+    wb.save(buf)
+    return buf.getvalue()
+
+
+def _zip_bytes(parts):
+    buf = io.BytesIO()
+    with zipfile.ZipFile(buf, "w") as zf:
+        for name, content in parts.items():
+            zf.writestr(name, content)
+    return buf.getvalue()
+
+
+def test_xlsx_from_bytes_path_and_file(tmp_path):
+    data = _xlsx_bytes()
+    path = tmp_path / "misnamed.csv"
+    path.write_bytes(data)
+    expected = {
+        "format": "xlsx",
+        "has_macros": False,
+        "template": False,
+        "delimiter": None,
+        "loadable": True,
+    }
+    assert rustypyxl.detect_format(data) == expected
+    assert rustypyxl.detect_format(path) == expected
+    assert rustypyxl.detect_format(str(path)) == expected
+    with open(path, "rb") as f:
+        assert rustypyxl.detect_format(f) == expected
+
+
+def test_openpyxl_template_is_flagged(tmp_path):
+    wb = openpyxl.Workbook()
+    path = tmp_path / "book.xltx"
+    wb.template = True
+    wb.save(path)
+    info = rustypyxl.detect_format(path)
+    assert info["format"] == "xlsx"
+    assert info["template"]
+
+
+def test_other_formats():
+    ods = _zip_bytes({"mimetype": "application/vnd.oasis.opendocument.spreadsheet"})
+    assert rustypyxl.detect_format(ods)["format"] == "ods"
+    xlsb = _zip_bytes({"[Content_Types].xml": "<Types/>", "xl/workbook.bin": ""})
+    assert rustypyxl.detect_format(xlsb)["format"] == "xlsb"
+    assert rustypyxl.detect_format(_zip_bytes({"a.txt": "hi"}))["format"] == "zip"
+
+    csv = rustypyxl.detect_format(b"name\tqty\nbolt\t4\n")
+    assert csv["format"] == "csv"
+    assert csv["delimiter"] == "\t"
+    assert not csv["loadable"]
+    assert rustypyxl.detect_format(b"\x00\x01\x02")["format"] == "unknown"
+
+
+def test_encrypted_package():
+    ooxml = pytest.importorskip("msoffcrypto.format.ooxml")
+
+    enc = io.BytesIO()
+    ooxml.OOXMLFile(io.BytesIO(_xlsx_bytes())).encrypt("pw", enc)
+    info = rustypyxl.detect_format(enc.getvalue())
+    assert info["format"] == "encrypted"
+    assert not info["loadable"]
+
+
+def test_load_workbook_names_unsupported_formats(tmp_path):
+    path = tmp_path / "export.xlsx"
+    path.write_text("id,name\n1,bolt\n")
+    with pytest.raises(ValueError, match="CSV"):
+        rustypyxl.load_workbook(str(path))
+    ods = _zip_bytes({"mimetype": "application/vnd.oasis.opendocument.spreadsheet"})
+    with pytest.raises(ValueError, match="OpenDocument"):
+        rustypyxl.load_workbook(ods)
+    with pytest.raises(ValueError, match="OpenDocument"):
+        rustypyxl.load_workbook(ods, read_only=True)
@@ -35,3 +35,48 @@ def test_dimension_index_accessors():
     ws = wb.create_sheet("S")
     assert ws.column_dimensions["D"].index == "D"
     assert ws.row_dimensions[7].index == 7
+
+
+def test_hidden_rows_and_columns_round_trip(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "visible"
+    ws["A2"] = "secret"
+    ws.row_dimensions[2].hidden = True
+    ws.row_dimensions[2].height = 18.0
+    ws.column_dimensions["B"].hidden = True
+    ws.set_column_hidden("D")
+    ws.set_row_hidden(9)
+    assert ws.row_dimensions[2].hidden
+    assert ws.column_dimensions["D"].hidden
+    assert not ws.column_dimensions["C"].hidden
+
+    out = str(tmp_path / "hidden.xlsx")
+    wb.save(out)
+
+    ows = openpyxl.load_workbook(out)["S"]
+    assert ows.row_dimensions[2].hidden
+    assert abs(ows.row_dimensions[2].height - 18.0) < 1e-6
+    assert ows.row_dimensions[9].hidden
+    assert ows.column_dimensions["B"].hidden
+    assert ows.column_dimensions["D"].hidden
+    assert not ows.row_dimensions[1].hidden
+
+    reloaded = rustypyxl.load_workbook(out)["S"]
+    assert reloaded.row_dimensions[9].hidden
+    reloaded.set_row_hidden(9, hidden=False)
+    assert not reloaded.row_dimensions[9].hidden
+
+
+def test_best_fit_column(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "a heading that needs room"
+    ws.auto_fit_column(1)
+    assert ws.column_dimensions["A"].bestFit
+    out = str(tmp_path / "fit.xlsx")
+    wb.save(out)
+
+    ows = openpyxl.load_workbook(out)["S"]
+    assert ows.column_dimensions["A"].bestFit
+    assert ows.column_dimensions["A"].width > 10
@@ -0,0 +1,106 @@
+"""Tests for document properties (wb.properties) written to docProps."""
+
+import datetime
+import io
+
+import openpyxl
+import pytest
+
+import rustypyxl
+
+
+FIXED = datetime.datetime(2024, 3, 1, 12, 0, 0)
+
+
+def reopen_with_openpyxl(data):
+    return openpyxl.load_workbook(io.BytesIO(data))
+
+
+class TestWorkbookProperties:
+    def test_defaults(self):
+        props = rustypyxl.Workbook().properties
+        assert props.creator == "rustypyxl"
+        assert props.lastModifiedBy is None
+        assert props.title is None
+        assert props.created is None
+        assert props.modified is None
+
+    def test_written_for_openpyxl(self):
+        wb = rustypyxl.Workbook()
+        wb.properties.creator = "Finance"
+        wb.properties.lastModifiedBy = "Nightly job"
+        wb.properties.title = "Q3 report"
+        wb.properties.created = FIXED
+        wb.properties.modified = FIXED
+
+        props = reopen_with_openpyxl(wb.save_to_bytes()).properties
+        assert props.creator == "Finance"
+        assert props.lastModifiedBy == "Nightly job"
+        assert props.title == "Q3 report"
+        assert props.created == FIXED
+        assert props.modified == FIXED
+
+    def test_unset_timestamps_stamp_save_time(self):
+        before = datetime.datetime.now(datetime.timezone.utc).replace(
+            tzinfo=None, microsecond=0
+        )
+        props = reopen_with_openpyxl(rustypyxl.Workbook().save_to_bytes()).properties
+        assert props.created >= before
+        assert props.modified >= before
+
+    def test_aware_datetime_converted_to_utc(self):
+        wb = rustypyxl.Workbook()
+        tz = datetime.timezone(datetime.timedelta(hours=2))
+        wb.properties.created = datetime.datetime(2024, 3, 1, 14, 0, tzinfo=tz)
+        assert wb.properties.created == FIXED
+
+    def test_invalid_timestamp(self):
+        with pytest.raises(ValueError):
+            rustypyxl.Workbook().properties.created = "last tuesday"
+
+    def test_fixed_timestamps_reproducible(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        wb.active["A1"] = "same"
+        wb.properties.created = FIXED
+        wb.properties.modified = FIXED
+        assert wb.save_to_bytes() == wb.save_to_bytes()
+
+    def test_loaded_properties_kept(self, tmp_path):
+        source = openpyxl.Workbook()
+        source.properties.creator = "Someone"
+        source.properties.title = "Budget"
+        source.properties.created = FIXED
+        path = tmp_path / "source.xlsx"
+        source.save(path)
+
+        props = rustypyxl.load_workbook(str(path)).properties
+        assert props.creator == "Someone"
+        assert props.title == "Budget"
+        assert props.created == FIXED
+        # The next save records its own modification time
+        assert props.modified is None
+
+
+class TestWriteOnlyProperties:
+    def test_streaming_properties(self, tmp_path):
+        path = tmp_path / "stream.xlsx"
+        wb = rustypyxl.WriteOnlyWorkbook(str(path))
+        wb.properties.creator = "ETL"
+        wb.properties.title = "Export"
+        wb.properties.created = FIXED
+        wb.create_sheet("Data")
+        wb.append_row([1, 2, 3])
+        wb.close()
+
+        props = openpyxl.load_workbook(path).properties
+        assert props.creator == "ETL"
+        assert props.title == "Export"
+        assert props.created == FIXED
+
+    def test_closed_workbook(self, tmp_path):
+        wb = rustypyxl.WriteOnlyWorkbook(str(tmp_path / "closed.xlsx"))
+        props = wb.properties
+        wb.close()
+        with pytest.raises(ValueError, match="closed"):
+            props.title = "late"
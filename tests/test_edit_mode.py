@@ -0,0 +1,107 @@
+"""load_workbook(..., edit=True): patch cells in place, keep the rest of the file."""
+
+import io
+import zipfile
+
+import pytest
+import rustypyxl
+
+
+UNKNOWN_PART = "xl/slicers/slicer1.xml"
+UNKNOWN_XML = b'<slicers xmlns="urn:example"><slicer name="Region"/></slicers>'
+
+
+def _source_file(path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.title = "Data"
+    ws["A1"] = "Region"
+    ws["B1"] = 10
+    ws["A2"] = "North"
+    ws["B2"] = 20
+    ws["C2"] = "=B1+B2"
+    wb.create_sheet("Notes")["A1"] = "keep me"
+
+    # Add a part rustypyxl does not model, as a slicer-bearing file would have.
+    with zipfile.ZipFile(io.BytesIO(wb.save_to_bytes())) as src, zipfile.ZipFile(
+        path, "w", zipfile.ZIP_DEFLATED
+    ) as dst:
+        for info in src.infolist():
+            dst.writestr(info, src.read(info.filename))
+        dst.writestr(UNKNOWN_PART, UNKNOWN_XML)
+
+
+def _parts(path):
+    with zipfile.ZipFile(path) as zf:
+        return {name: zf.read(name) for name in zf.namelist()}
+
+
+def test_edit_preserves_unmodeled_parts(tmp_path):
+    src = tmp_path / "src.xlsx"
+    out = tmp_path / "out.xlsx"
+    _source_file(src)
+
+    wb = rustypyxl.load_workbook(str(src), edit=True)
+    assert wb.sheetnames == ["Data", "Notes"]
+    ws = wb["Data"]
+    assert ws["B2"] == 20
+    ws["B2"] = 25
+    ws["D5"] = "added"
+    assert ws["B2"] == 25
+    assert wb.pending_edits == 2
+    wb.save(str(out))
+
+    before, after = _parts(src), _parts(out)
+    assert set(before) == set(after)
+    assert after[UNKNOWN_PART] == UNKNOWN_XML
+    changed = {name for name in before if before[name] != after[name]}
+    assert changed == {"xl/worksheets/sheet1.xml"}
+
+    reloaded = rustypyxl.load_workbook(str(out))
+    assert reloaded["Data"]["B2"].value == 25
+    assert reloaded["Data"]["D5"].value == "added"
+    assert reloaded["Data"]["A2"].value == "North"
+    assert reloaded["Notes"]["A1"].value == "keep me"
+
+
+def test_edit_keeps_cell_style(tmp_path):
+    src = tmp_path / "styled.xlsx"
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = 1.5
+    ws["A1"].number_format = "0.00%"
+    wb.save(str(src))
+
+    editable = rustypyxl.load_workbook(str(src), edit=True)
+    editable[editable.sheetnames[0]].set_value(1, 1, 0.25)
+    editable.save(str(src))
+
+    ws = rustypyxl.load_workbook(str(src)).active
+    assert ws["A1"].value == 0.25
+    assert ws["A1"].number_format == "0.00%"
+
+
+def test_clearing_and_discarding(tmp_path):
+    src = tmp_path / "src.xlsx"
+    _source_file(src)
+    wb = rustypyxl.load_workbook(str(src), edit=True)
+    ws = wb["Data"]
+    ws["A1"] = None
+    assert ws["A1"] is None
+    wb.discard_edits()
+    assert wb.pending_edits == 0
+    assert ws["A1"] == "Region"
+
+
+def test_edit_mode_errors(tmp_path):
+    src = tmp_path / "src.xlsx"
+    _source_file(src)
+    with pytest.raises(ValueError):
+        rustypyxl.load_workbook(str(src), edit=True, read_only=True)
+    wb = rustypyxl.load_workbook(str(src), edit=True)
+    with pytest.raises(KeyError):
+        wb["Missing"]
+    assert "Notes" in wb
+    assert len(wb) == 2
@@ -56,3 +56,15 @@ def test_save_encrypted_to_path(tmp_path):
     plain = io.BytesIO()
     of.decrypt(plain)
     assert openpyxl.load_workbook(io.BytesIO(plain.getvalue()))["S"]["A1"].value == "secret data"
+
+
+def test_save_encrypted_method(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "secret data"
+    out = str(tmp_path / "protected.xlsx")
+    wb.save_encrypted(out, "pw123")
+
+    assert msoffcrypto.OfficeFile(open(out, "rb")).is_encrypted()
+    loaded = rustypyxl.load_workbook(out, password="pw123")
+    assert loaded["S"]["A1"].value == "secret data"
@@ -0,0 +1,78 @@
+"""wb.export_pdf: print-ready PDF through an external renderer."""
+
+import io
+import shutil
+import sys
+
+import openpyxl
+import pytest
+import rustypyxl
+
+
+def _workbook():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.title = "Report"
+    ws["A1"] = "Quarterly totals"
+    ws["A2"] = 42
+    return wb
+
+
+def test_callable_renderer_gets_the_saved_workbook(tmp_path):
+    seen = {}
+
+    def render(input_path, output_path):
+        with open(input_path, "rb") as f:
+            seen["xlsx"] = f.read()
+        with open(output_path, "wb") as f:
+            f.write(b"%PDF-1.7\n")
+
+    out = tmp_path / "report.pdf"
+    _workbook().export_pdf(out, renderer=render)
+    assert out.read_bytes().startswith(b"%PDF")
+    loaded = openpyxl.load_workbook(io.BytesIO(seen["xlsx"]))
+    assert loaded["Report"]["A1"].value == "Quarterly totals"
+
+
+def test_renderer_exceptions_propagate(tmp_path):
+    def render(input_path, output_path):
+        raise RuntimeError("printer on fire")
+
+    with pytest.raises(RuntimeError, match="printer on fire"):
+        _workbook().export_pdf(tmp_path / "x.pdf", renderer=render)
+
+
+def test_renderer_must_write_the_file(tmp_path):
+    with pytest.raises(ValueError, match="did not write"):
+        _workbook().export_pdf(tmp_path / "x.pdf", renderer=lambda i, o: None)
+
+
+def test_command_renderer_substitutes_paths(tmp_path):
+    out = tmp_path / "copy.pdf"
+    script = "import shutil, sys; shutil.copy(sys.argv[1], sys.argv[2])"
+    _workbook().export_pdf(out, command=[sys.executable, "-c", script, "{input}", "{output}"])
+    assert out.read_bytes().startswith(b"PK")
+
+
+def test_failing_command_reports_stderr(tmp_path):
+    script = "import sys; sys.stderr.write('no fonts'); sys.exit(2)"
+    with pytest.raises(ValueError, match="no fonts"):
+        _workbook().export_pdf(tmp_path / "x.pdf", command=[sys.executable, "-c", script])
+
+
+def test_only_one_renderer_choice(tmp_path):
+    with pytest.raises(ValueError):
+        _workbook().export_pdf(tmp_path / "x.pdf", command=["true"], renderer=lambda i, o: None)
+    with pytest.raises(ValueError):
+        _workbook().export_pdf(tmp_path / "x.pdf", command=[])
+
+
+@pytest.mark.skipif(shutil.which("soffice") is None, reason="LibreOffice not installed")
+def test_libreoffice_end_to_end(tmp_path):
+    wb = _workbook()
+    ws = wb.active
+    ws.set_page_setup(orientation="landscape")
+    out = tmp_path / "report.pdf"
+    wb.export_pdf(out)
+    assert out.read_bytes().startswith(b"%PDF")
@@ -0,0 +1,114 @@
+"""openpyxl-style rule classes from rustypyxl.formatting, applied through
+ws.conditional_formatting.add and read back by openpyxl and rustypyxl.
+"""
+
+import openpyxl
+import pytest
+import rustypyxl
+from rustypyxl.formatting import (
+    CellIsRule,
+    ColorScaleRule,
+    DataBarRule,
+    FormulaRule,
+    IconSetRule,
+    Rule,
+)
+from rustypyxl.styles import Font, PatternFill
+
+
+def _saved_rules(wb, tmp_path, sheet="S"):
+    out = str(tmp_path / "cf.xlsx")
+    wb.save(out)
+    ws = openpyxl.load_workbook(out)[sheet]
+    return {str(cf.sqref): cf.rules for cf in ws.conditional_formatting}
+
+
+def test_rule_classes_share_a_base():
+    rule = CellIsRule(operator=">", formula=["5"])
+    assert isinstance(rule, Rule)
+    assert rule.type == "cellIs"
+    assert rule.operator == "greaterThan"
+    assert rule.formula == ["5"]
+
+
+def test_import_path_matches_openpyxl():
+    from rustypyxl.formatting.rule import CellIsRule as Aliased
+
+    assert Aliased is CellIsRule
+
+
+def test_cell_is_between_and_formula_rules(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws.conditional_formatting.add(
+        "A1:A10",
+        CellIsRule(operator="between", formula=[1, 10], fill=PatternFill(start_color="FFFF0000", fill_type="solid")),
+    )
+    ws.conditional_formatting.add(
+        "B1:B10", FormulaRule(formula=["$A1>5"], font=Font(bold=True), stopIfTrue=True)
+    )
+
+    rules = _saved_rules(wb, tmp_path)
+    (between,) = rules["A1:A10"]
+    assert between.operator == "between"
+    assert between.formula == ["1", "10"]
+    assert between.dxf.fill is not None
+    (expression,) = rules["B1:B10"]
+    assert expression.type == "expression"
+    assert expression.formula == ["$A1>5"]
+    assert expression.stopIfTrue
+    assert expression.dxf.font.b
+    assert between.priority < expression.priority
+
+
+def test_color_scale_data_bar_and_icon_set(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws.conditional_formatting.add(
+        "A1:A10",
+        ColorScaleRule(start_type="min", start_color="FFF8696B", end_type="max", end_color="FF63BE7B"),
+    )
+    ws.conditional_formatting.add(
+        "B1:B10",
+        ColorScaleRule(
+            start_type="num", start_value=0, start_color="FF0000FF",
+            mid_type="percentile", mid_value=50, mid_color="FFFFFFFF",
+            end_type="num", end_value=100, end_color="FFFF0000",
+        ),
+    )
+    ws.conditional_formatting.add("C1:C10", DataBarRule(start_type="min", end_type="max", color="FF638EC6"))
+    ws.conditional_formatting.add("D1:D10", IconSetRule("3Arrows", "percent", [0, 33, 67]))
+
+    rules = _saved_rules(wb, tmp_path)
+    two = rules["A1:A10"][0].colorScale
+    assert len(two.color) == 2
+    three = rules["B1:B10"][0].colorScale
+    assert [v.type for v in three.cfvo] == ["num", "percentile", "num"]
+    assert [v.val for v in three.cfvo] == [0, 50, 100]
+    assert rules["C1:C10"][0].type == "dataBar"
+    icons = rules["D1:D10"][0].iconSet
+    assert icons.iconSet == "3Arrows"
+    assert [v.val for v in icons.cfvo] == [0, 33, 67]
+
+
+def test_rules_on_one_range_are_grouped():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    cf = ws.conditional_formatting
+    cf.add("A1:A5", CellIsRule(operator="lessThan", formula="0"))
+    cf.add("A1:A5", CellIsRule(operator="greaterThan", formula="100"))
+    cf.add("B1:B5", DataBarRule())
+    assert len(cf) == 2
+    assert cf.ranges == ["A1:A5", "B1:B5"]
+    assert [r.priority for r in cf.rules("A1:A5")] == [1, 2]
+
+
+def test_invalid_rules_raise():
+    with pytest.raises(ValueError):
+        CellIsRule(operator="around", formula=["1"])
+    with pytest.raises(ValueError):
+        CellIsRule(operator="between", formula=["1"])
+    with pytest.raises(ValueError):
+        FormulaRule()
+    with pytest.raises(ValueError):
+        IconSetRule("7Stars")
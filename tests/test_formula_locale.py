@@ -0,0 +1,63 @@
+"""Formulas typed with localized function names and separators."""
+
+import pytest
+
+import rustypyxl
+
+
+@pytest.fixture
+def wb():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.formula_locale = "de-DE"
+    return wb
+
+
+def test_default_is_en_us():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    assert wb.formula_locale is None
+    wb.active["A1"] = "=SUMME(B1;B2)"
+    assert wb.active["A1"].value == "=SUMME(B1;B2)"
+
+
+def test_german_formula_is_stored_canonical(wb):
+    assert wb.formula_locale == "de"
+    ws = wb.active
+    ws["A1"] = "=SUMME(B1;B2)"
+    ws["A2"] = '=WENN(B1>0,5;"ja";"nein")'
+    ws.cell(row=3, column=1).value = "=zählenwenn(B:B;WAHR)"
+    assert ws["A1"].value == "=SUM(B1,B2)"
+    assert ws["A2"].value == '=IF(B1>0.5,"ja","nein")'
+    assert ws["A3"].value == "=COUNTIF(B:B,TRUE)"
+
+
+def test_plain_text_and_literals_untouched(wb):
+    ws = wb.active
+    ws["A1"] = "SUMME(B1;B2)"
+    ws["A2"] = '=VERKETTEN("SUMME(1;2)";B1)'
+    assert ws["A1"].value == "SUMME(B1;B2)"
+    assert ws["A2"].value == '=CONCATENATE("SUMME(1;2)",B1)'
+
+
+def test_switching_back_stores_as_given(wb):
+    wb.formula_locale = None
+    ws = wb.active
+    ws["A1"] = "=SUMME(B1;B2)"
+    assert ws["A1"].value == "=SUMME(B1;B2)"
+
+
+def test_saved_file_uses_english_names(wb, tmp_path):
+    ws = wb.active
+    ws["B1"] = 1
+    ws["B2"] = 2
+    ws["A1"] = "=SUMME(B1;B2)"
+    path = tmp_path / "de.xlsx"
+    wb.save(str(path))
+    assert rustypyxl.load_workbook(str(path)).active["A1"].value == "=SUM(B1,B2)"
+
+
+def test_unknown_locale_rejected():
+    wb = rustypyxl.Workbook()
+    with pytest.raises(ValueError):
+        wb.formula_locale = "xx"
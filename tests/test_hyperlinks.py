@@ -1,5 +1,6 @@
 """Tests for hyperlink support."""
 
+import pytest
 import rustypyxl
 
 
@@ -51,3 +52,104 @@ class TestHyperlinkRoundtrip:
         ws = wb["Links"]
         assert ws["A1"].hyperlink == "https://example.com/page", "external URL lost"
         assert ws["A2"].hyperlink == "mailto:someone@example.com"
+
+
+class TestSetHyperlink:
+    """ws.set_hyperlink: link, display text and Hyperlink look in one call."""
+
+    def test_styled_link_opens_in_openpyxl(self, temp_xlsx_path):
+        import openpyxl
+
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws["A1"].font = rustypyxl.Font(bold=True, size=14)
+        ws.set_hyperlink("A1", "https://example.com", display="Example")
+        ws.set_hyperlink("A2", "https://example.com/raw")
+        wb.save(temp_xlsx_path)
+
+        loaded = openpyxl.load_workbook(temp_xlsx_path).active
+        a1 = loaded["A1"]
+        assert a1.value == "Example"
+        assert a1.hyperlink.target == "https://example.com"
+        assert a1.font.u == "single"
+        assert a1.font.color.rgb == "FF0563C1"
+        assert a1.font.b and a1.font.sz == 14
+        assert loaded["A2"].value == "https://example.com/raw"
+
+    def test_unstyled_keeps_value_and_font(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws["B2"] = "Docs"
+        ws.set_hyperlink("B2", "https://example.com/docs", styled=False)
+        assert ws["B2"].value == "Docs"
+        assert ws["B2"].hyperlink == "https://example.com/docs"
+        font = ws["B2"].font
+        assert font is None or not font.underline
+
+
+class TestHyperlinkObjects:
+    """Hyperlink objects: tooltip and display text, written with the
+    relationship Excel needs to follow external links."""
+
+    def test_tooltip_round_trip(self, temp_xlsx_path):
+        import openpyxl
+
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws["A1"] = "Site"
+        ws["A1"].hyperlink = rustypyxl.Hyperlink(
+            "https://example.com/?a=1&b=2", tooltip="Open the site"
+        )
+        ws["A2"].hyperlink = rustypyxl.Hyperlink(location="Sheet!B2", display="Jump")
+        wb.save(temp_xlsx_path)
+
+        loaded = rustypyxl.load_workbook(temp_xlsx_path).active
+        assert loaded["A1"].hyperlink == "https://example.com/?a=1&b=2"
+        link = loaded.get_hyperlink("A1")
+        assert link.is_external
+        assert link.target == "https://example.com/?a=1&b=2"
+        assert link.tooltip == "Open the site"
+        internal = loaded.get_hyperlink("A2")
+        assert internal.location == "Sheet!B2"
+        assert internal.target is None
+        assert internal.display == "Jump"
+        assert loaded["A2"].hyperlink == "#Sheet!B2"
+        assert loaded.get_hyperlink("C3") is None
+
+        ox = openpyxl.load_workbook(temp_xlsx_path).active
+        assert ox["A1"].hyperlink.target == "https://example.com/?a=1&b=2"
+        assert ox["A1"].hyperlink.tooltip == "Open the site"
+        assert ox["A2"].hyperlink.location == "Sheet!B2"
+
+    def test_reads_openpyxl_links(self, temp_xlsx_path):
+        import openpyxl
+        from openpyxl.worksheet.hyperlink import Hyperlink as OxHyperlink
+
+        ox = openpyxl.Workbook()
+        ws = ox.active
+        ws["A1"] = "Docs"
+        ws["A1"].hyperlink = OxHyperlink(
+            ref="A1", target="https://example.com/docs", tooltip="Read me"
+        )
+        ox.save(temp_xlsx_path)
+
+        link = rustypyxl.load_workbook(temp_xlsx_path).active.get_hyperlink("A1")
+        assert link.target == "https://example.com/docs"
+        assert link.tooltip == "Read me"
+
+    def test_constructor_needs_one_destination(self):
+        with pytest.raises(ValueError):
+            rustypyxl.Hyperlink()
+        with pytest.raises(ValueError):
+            rustypyxl.Hyperlink("https://example.com", location="Sheet!A1")
+
+    def test_rejects_other_types(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        with pytest.raises(TypeError):
+            ws["A1"].hyperlink = 42
+
+    def test_importable_from_openpyxl_path(self):
+        from rustypyxl.worksheet.hyperlink import Hyperlink
+
+        assert Hyperlink is rustypyxl.Hyperlink
@@ -70,3 +70,56 @@ def test_bad_image_bytes_raise(tmp_path):
     ws = wb.create_sheet("S")
     with pytest.raises(ValueError):
         ws.add_image(b"not an image", anchor="A1")
+
+
+def _png(width, height):
+    # A PNG header is enough for sizing; the IHDR chunk carries the pixels.
+    return (
+        PNG_1X1[:16]
+        + width.to_bytes(4, "big")
+        + height.to_bytes(4, "big")
+        + PNG_1X1[24:]
+    )
+
+
+def test_image_class_matches_openpyxl_usage(tmp_path):
+    img = rustypyxl.Image(io.BytesIO(_png(120, 40)))
+    assert (img.width, img.height, img.format) == (120, 40, "png")
+    img.width = 60
+
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.add_image(img, "B2")
+    out = tmp_path / "img.xlsx"
+    wb.save(str(out))
+
+    [loaded] = openpyxl.load_workbook(out).active._images
+    assert loaded.anchor._from.col == 1 and loaded.anchor._from.row == 1
+    assert (loaded.width, loaded.height) == (60, 40)
+
+
+def test_image_keeps_its_anchor_and_reads_back(tmp_path):
+    img = rustypyxl.Image(_png(10, 20))
+    img.anchor = "D4"
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active.add_image(img)
+    out = tmp_path / "img.xlsx"
+    wb.save(str(out))
+
+    [read] = rustypyxl.load_workbook(str(out)).active.images
+    assert read.anchor == "D4"
+    assert (read.width, read.height) == (10, 20)
+    assert read.data == _png(10, 20)
+
+
+def test_add_image_accepts_openpyxl_image(tmp_path):
+    from openpyxl.drawing.image import Image as OpenpyxlImage
+
+    img = OpenpyxlImage(io.BytesIO(PNG_1X1))
+    img.width, img.height = 30, 15
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active.add_image(img, "C3")
+    assert [(i.anchor, i.width, i.height) for i in wb.active.images] == [("C3", 30, 15)]
@@ -0,0 +1,39 @@
+"""wb.build_index_sheet(): a first tab linking to every sheet."""
+
+import rustypyxl
+
+
+def _workbook():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sales")
+    wb.create_sheet("Q1 Costs")
+    wb.create_sheet("Scratch").sheet_state = "hidden"
+    wb.write_rows("Sales", [[1], [2], [3]])
+    return wb
+
+
+def test_index_links_to_each_visible_sheet(tmp_path):
+    wb = _workbook()
+    index = wb.build_index_sheet(descriptions={"Sales": "Daily sales"})
+    assert index.title == "Contents"
+    assert wb.sheetnames == ["Contents", "Sales", "Q1 Costs", "Scratch"]
+
+    out = str(tmp_path / "index.xlsx")
+    wb.save(out)
+    ws = rustypyxl.load_workbook(out)["Contents"]
+    assert [ws.cell(1, c).value for c in (1, 2, 3)] == ["Sheet", "Rows", "Description"]
+    assert ws["A2"].value == "Sales"
+    assert ws["B2"].value == 3
+    assert ws["C2"].value == "Daily sales"
+    assert ws["A3"].hyperlink == "#'Q1 Costs'!$A$1"
+    assert ws["A4"].value is None
+
+
+def test_rebuild_replaces_index():
+    wb = _workbook()
+    wb.build_index_sheet(title="TOC", row_counts=False, include_hidden=True)
+    wb.create_sheet("Later")
+    index = wb.build_index_sheet(title="TOC", row_counts=False, include_hidden=True)
+    assert wb.sheetnames == ["TOC", "Sales", "Q1 Costs", "Scratch", "Later"]
+    assert index["B1"].value is None
+    assert index["A5"].value == "Later"
@@ -0,0 +1,86 @@
+"""Lazy ws.iter_rows / ws.iter_cols with openpyxl's bounds and placeholders."""
+
+import rustypyxl
+
+
+def _sheet():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = 1
+    ws["C1"] = 3
+    ws["B3"] = "b3"
+    return wb, ws
+
+
+class TestIterRows:
+    def test_gaps_are_none_placeholders(self):
+        _, ws = _sheet()
+        assert list(ws.iter_rows(values_only=True)) == [
+            (1, None, 3),
+            (None, None, None),
+            (None, "b3", None),
+        ]
+
+    def test_cell_placeholders_have_coordinates(self):
+        _, ws = _sheet()
+        row = next(ws.iter_rows(min_row=2, max_row=2))
+        assert [c.coordinate for c in row] == ["A2", "B2", "C2"]
+        assert all(c.value is None for c in row)
+
+    def test_explicit_bounds_beyond_used_range(self):
+        _, ws = _sheet()
+        rows = list(ws.iter_rows(min_row=3, max_row=4, min_col=2, max_col=4, values_only=True))
+        assert rows == [("b3", None, None), (None, None, None)]
+
+    def test_zero_bound_means_default(self):
+        _, ws = _sheet()
+        assert len(list(ws.iter_rows(max_row=0, values_only=True))) == 3
+
+    def test_is_lazy_over_huge_bounds(self):
+        _, ws = _sheet()
+        it = ws.iter_rows(max_row=1_048_576, max_col=3, values_only=True)
+        assert iter(it) is it
+        assert next(it) == (1, None, 3)
+        assert next(it) == (None, None, None)
+
+    def test_sees_writes_made_during_iteration(self):
+        _, ws = _sheet()
+        it = ws.iter_rows(values_only=True)
+        next(it)
+        ws["A2"] = "late"
+        assert next(it) == ("late", None, None)
+
+    def test_empty_sheet_yields_nothing(self):
+        ws = rustypyxl.Workbook().create_sheet("Sheet")
+        assert list(ws.iter_rows()) == []
+        assert list(ws.iter_cols()) == []
+        assert list(ws.iter_rows(max_row=1, max_col=2, values_only=True)) == [(None, None)]
+
+
+class TestIterCols:
+    def test_columns_with_placeholders(self):
+        _, ws = _sheet()
+        assert list(ws.iter_cols(values_only=True)) == [
+            (1, None, None),
+            (None, None, "b3"),
+            (3, None, None),
+        ]
+
+    def test_bounds(self):
+        _, ws = _sheet()
+        cols = list(ws.iter_cols(min_col=2, max_col=2, min_row=3, max_row=3))
+        assert [[c.coordinate for c in col] for col in cols] == [["B3"]]
+
+
+class TestShortcuts:
+    def test_rows_columns_values(self):
+        _, ws = _sheet()
+        assert [tuple(c.value for c in r) for r in ws.rows] == list(ws.values)
+        assert [tuple(c.value for c in col) for col in ws.columns] == list(
+            ws.iter_cols(values_only=True)
+        )
+
+    def test_iterating_worksheet_yields_rows(self):
+        _, ws = _sheet()
+        assert [[c.coordinate for c in r] for r in ws][0] == ["A1", "B1", "C1"]
@@ -0,0 +1,89 @@
+"""ws.to_json / ws.write_ndjson / wb.insert_from_json: sheets as JSON records."""
+
+import datetime
+import json
+
+import pytest
+import rustypyxl
+
+
+def _sheet():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.append(["name", "qty", "paid"])
+    ws.append(["Widget", 3, True])
+    ws.append(["Gear", 0.5, None])
+    ws["D2"] = datetime.datetime(2024, 3, 15, 12, 0)
+    return wb, ws
+
+
+def test_to_json_records():
+    _, ws = _sheet()
+    records = json.loads(ws.to_json())
+    assert records == [
+        {"name": "Widget", "qty": 3, "paid": True, "Column4": "2024-03-15T12:00:00"},
+        {"name": "Gear", "qty": 0.5, "paid": None, "Column4": None},
+    ]
+
+
+def test_to_json_options():
+    _, ws = _sheet()
+    records = json.loads(
+        ws.to_json("A2:D3", header=False, skip_nulls=True, date_format="%Y-%m-%d")
+    )
+    assert records == [
+        {"Column1": "Widget", "Column2": 3, "Column3": True, "Column4": "2024-03-15"},
+        {"Column1": "Gear", "Column2": 0.5},
+    ]
+    assert rustypyxl.Workbook().create_sheet("Sheet").to_json() == "[]"
+
+
+def test_ndjson_round_trip(tmp_path):
+    wb, ws = _sheet()
+    path = tmp_path / "rows.ndjson"
+    assert ws.write_ndjson(path) == 2
+    lines = path.read_text().splitlines()
+    assert json.loads(lines[1]) == {"name": "Gear", "qty": 0.5, "paid": None, "Column4": None}
+
+    target = wb.create_sheet("Copy")
+    result = wb.insert_from_json("Copy", path)
+    assert result["rows_imported"] == 2
+    assert result["range"] == "A1:D3"
+    assert result["column_names"] == ["name", "qty", "paid", "Column4"]
+    assert target["A2"].value == "Widget"
+    assert target["C2"].value is True
+    assert target["C3"].value is None
+    assert target["D2"].value == datetime.datetime(2024, 3, 15, 12, 0)
+
+
+def test_insert_json_array_options(tmp_path):
+    path = tmp_path / "rows.json"
+    path.write_text(json.dumps([{"a": 1, "b": {"x": [1, 2]}}, {"c": "2024-01-01", "a": 2}]))
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    result = wb.insert_from_json(
+        wb.active.title,
+        path,
+        start_row=2,
+        start_col=2,
+        columns=["c", "b"],
+        column_renames={"c": "when"},
+        parse_dates=False,
+    )
+    ws = wb.active
+    assert result["column_names"] == ["when", "b"]
+    assert ws["B2"].value == "when"
+    assert ws["C3"].value == '{"x":[1,2]}'
+    assert ws["B4"].value == "2024-01-01"
+
+
+def test_insert_json_errors(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    path = tmp_path / "bad.ndjson"
+    path.write_text('{"a": 1}\n[1]\n')
+    with pytest.raises(ValueError):
+        wb.insert_from_json(wb.active.title, path)
+    with pytest.raises(ValueError):
+        wb.insert_from_json(wb.active.title, tmp_path / "missing.json")
@@ -7,6 +7,7 @@ implementation cannot pass.
 import io
 
 import openpyxl
+import pytest
 import rustypyxl
 
 
@@ -53,3 +54,119 @@ class TestMergedCellBasics:
         ws.merge_cells("A3:C3")
         assert set(ws.merged_cells) == {"A1:B1", "A3:C3"}
         assert merged_ranges_via_openpyxl(wb, "Test") == {"A1:B1", "A3:C3"}
+
+
+class TestMergedCellBehavior:
+    """Excel-like value handling, validation and range objects."""
+
+    def test_merge_blanks_all_but_top_left(self, workbook_with_sheet):
+        wb = workbook_with_sheet
+        ws = wb.active
+        ws["A1"] = "keep"
+        ws["B1"] = "drop"
+        ws["C3"] = 3
+        ws["D1"] = "outside"
+        ws.merge_cells("A1:C3")
+        assert ws["A1"].value == "keep"
+        assert ws["B1"].value is None
+        assert ws["C3"].value is None
+        assert ws["D1"].value == "outside"
+
+        op = openpyxl.load_workbook(io.BytesIO(wb.save_to_bytes()))["Test"]
+        assert op["A1"].value == "keep"
+        assert op["B1"].value is None
+
+    def test_overlapping_merge_raises(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws.merge_cells("B2:C3")
+        with pytest.raises(ValueError, match="overlaps"):
+            ws.merge_cells("C3:D4")
+        assert ws.merged_cells == ["B2:C3"]
+
+    def test_single_cell_merge_raises(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        with pytest.raises(ValueError):
+            ws.merge_cells("A1:A1")
+
+    def test_unmerge_unknown_range_raises(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws.merge_cells("A1:B2")
+        with pytest.raises(ValueError, match="not merged"):
+            ws.unmerge_cells("A1:B3")
+        assert len(ws.merged_cells) == 1
+
+    def test_ranges_expose_bounds(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws.merge_cells("$B$2:$D$5")
+        (rng,) = ws.merged_cells.ranges
+        assert (rng.min_row, rng.min_col, rng.max_row, rng.max_col) == (2, 2, 5, 4)
+        assert rng.coord == "B2:D5"
+        assert str(rng) == "B2:D5"
+        assert rng.bounds == (2, 2, 4, 5)
+        assert "C3" in rng
+        assert "C3" in ws.merged_cells
+        assert "A1" not in ws.merged_cells
+
+    def test_cell_range_importable_like_openpyxl(self):
+        from rustypyxl.worksheet.cell_range import CellRange
+
+        rng = CellRange("A1:C3")
+        assert rng == "A1:C3"
+        assert rng.size == (3, 3)
+        assert rng == CellRange(min_col=1, min_row=1, max_col=3, max_row=3)
+
+
+class TestStyledMerge:
+    """merge_cells(value=..., style=...) styles the whole block."""
+
+    def test_value_and_style_cover_block(self, workbook_with_sheet):
+        wb = workbook_with_sheet
+        ws = wb.active
+        thin = rustypyxl.Side(style="thin")
+        ws.merge_cells(
+            "B2:D3",
+            value="Quarterly totals",
+            style={
+                "fill": rustypyxl.PatternFill(fill_type="solid", fgColor="FFFFCC00"),
+                "border": rustypyxl.Border(left=thin, right=thin, top=thin, bottom=thin),
+                "font": rustypyxl.Font(bold=True),
+            },
+        )
+
+        op = openpyxl.load_workbook(io.BytesIO(wb.save_to_bytes()))["Test"]
+        assert {str(r) for r in op.merged_cells.ranges} == {"B2:D3"}
+        assert op["B2"].value == "Quarterly totals"
+        assert op["B2"].font.bold
+        for row in op["B2:D3"]:
+            for cell in row:
+                assert cell.fill.fgColor.rgb == "FFFFCC00"
+                assert cell.border.right.style == "thin"
+        assert op["A1"].fill.fill_type is None
+
+    def test_named_style(self, workbook_with_sheet):
+        wb = workbook_with_sheet
+        wb.styles.from_json(
+            '{"version": 1,'
+            ' "fills": {"blue": {"pattern": "solid", "fg_color": "FF336699"}},'
+            ' "styles": {"banner": {"fill": "blue"}}}'
+        )
+        ws = wb.active
+        ws.merge_cells("A1:C1", value="Banner", style="banner")
+
+        op = openpyxl.load_workbook(io.BytesIO(wb.save_to_bytes()))["Test"]
+        assert op["A1"].value == "Banner"
+        assert op["C1"].fill.fgColor.rgb == "FF336699"
+
+    def test_overlap_leaves_sheet_untouched(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws.merge_cells("A1:B2")
+        with pytest.raises(ValueError):
+            ws.merge_cells("B2:C3", value="x", style={"font": rustypyxl.Font(bold=True)})
+        assert ws["C3"].value is None
+        assert ws.merged_cells == ["A1:B2"]
+
+    def test_unknown_style_key(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        with pytest.raises(ValueError, match="colour"):
+            ws.merge_cells("A1:B1", style={"colour": "red"})
+        assert ws.merged_cells == []
@@ -0,0 +1,60 @@
+"""ws.move_range, matching openpyxl's signature and formula translation."""
+
+import openpyxl
+import pytest
+import rustypyxl
+
+
+@pytest.fixture
+def ws():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = 1
+    ws["A2"] = 2
+    ws["B2"] = "=A1+$A$1+SUM(A1:A2)"
+    ws["B2"].font = rustypyxl.Font(bold=True)
+    return ws
+
+
+def test_moves_values_and_styles(ws):
+    ws.move_range("A1:B2", rows=2, cols=1)
+    assert ws["A1"].value is None
+    assert ws["B3"].value == 1
+    assert ws["C4"].value == "=A1+$A$1+SUM(A1:A2)"
+    assert ws["C4"].font.bold
+
+
+def test_translate_shifts_relative_references(ws):
+    ws.move_range("B2", rows=3, cols=1, translate=True)
+    assert ws["C5"].value == "=B4+$A$1+SUM(B4:B5)"
+    ws["C1"] = "=A1*2"
+    ws.move_range("C1", rows=1, translate=True)
+    assert ws["C2"].value == "=A2*2"
+
+
+def test_overwrites_destination(ws):
+    ws["D1"] = "old"
+    ws.move_range("A1", cols=3)
+    assert ws["D1"].value == 1
+
+
+def test_off_sheet_destination_raises(ws):
+    with pytest.raises(ValueError):
+        ws.move_range("A1:B2", rows=-1)
+    assert ws["A1"].value == 1
+
+
+def test_matches_openpyxl(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["C3"] = "=SUM(A1:B2)+Sheet2!A1+$C$1"
+    ws.move_range("C3", rows=1, cols=1, translate=True)
+    path = tmp_path / "moved.xlsx"
+    wb.save(str(path))
+
+    expected = openpyxl.Workbook().active
+    expected["C3"] = "=SUM(A1:B2)+Sheet2!A1+$C$1"
+    expected.move_range("C3", rows=1, cols=1, translate=True)
+    assert openpyxl.load_workbook(path).active["D4"].value == expected["D4"].value
@@ -1,5 +1,6 @@
 """Tests for named range support."""
 
+import pytest
 import rustypyxl
 
 
@@ -49,3 +50,42 @@ class TestNamedRangeRoundtrip:
         names = dict(wb.defined_names)
         assert "MyRange" in names, f"named range lost: {names}"
         assert "Named" in names["MyRange"]
+
+
+class TestSheetScopedNames:
+    """Names scoped to one sheet, and resolving names to ranges."""
+
+    def test_scoped_name_round_trips(self, temp_xlsx_path):
+        wb = rustypyxl.Workbook()
+        data = wb.create_sheet("Data")
+        other = wb.create_sheet("Other")
+        wb.create_named_range("Rate", data, "$B$2")
+        other.add_defined_name("Rate", "$C$3:$D$4", comment="local rate")
+        wb.save(temp_xlsx_path)
+
+        wb2 = rustypyxl.load_workbook(temp_xlsx_path)
+        assert wb2["Other"].defined_names == {"Rate": "'Other'!$C$3:$D$4"}
+        assert wb2["Data"].defined_names == {}
+        assert wb2.resolve_named_range("Rate") == ("Data", 2, 2, 2, 2)
+        assert wb2.resolve_named_range("Rate", sheet="Other") == ("Other", 3, 3, 4, 4)
+
+    def test_openpyxl_sees_the_scope(self, temp_xlsx_path):
+        import openpyxl
+
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Inputs")
+        ws.add_defined_name("Start", "A1", hidden=True)
+        wb.save(temp_xlsx_path)
+
+        loaded = openpyxl.load_workbook(temp_xlsx_path)
+        assert "Start" in loaded["Inputs"].defined_names
+        assert "Start" not in loaded.defined_names
+
+    def test_duplicates_in_one_scope_and_bad_names_raise(self):
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("S")
+        ws.add_defined_name("N", "A1")
+        with pytest.raises(ValueError):
+            ws.add_defined_name("N", "B2")
+        with pytest.raises(ValueError):
+            wb.resolve_named_range("Missing")
@@ -0,0 +1,89 @@
+"""Named cell styles: wb.add_named_style(NamedStyle(...)) and
+cell.style = "name", read and written as cellStyleXfs / <cellStyles>.
+"""
+
+import openpyxl
+import pytest
+import rustypyxl
+from rustypyxl import Font, NamedStyle
+
+
+def test_cells_default_to_normal():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Sheet1")
+    ws["A1"] = "plain"
+    assert ws["A1"].style == "Normal"
+    assert wb.named_styles == ["Normal"]
+
+
+def test_builtin_style_by_name(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Sheet1")
+    ws["A1"] = "Title"
+    ws["A1"].style = "Headline 1"
+    assert ws["A1"].style == "Headline 1"
+    assert "Headline 1" in wb.named_styles
+
+    path = tmp_path / "builtin.xlsx"
+    wb.save(str(path))
+
+    ox = openpyxl.load_workbook(path)
+    assert ox["Sheet1"]["A1"].style == "Headline 1"
+    assert ox["Sheet1"]["A1"].font.b
+
+
+def test_custom_named_style_round_trip(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Sheet1")
+    wb.add_named_style(
+        NamedStyle(name="Report Title", font=Font(bold=True, size=16), number_format="0.00")
+    )
+    ws["A1"] = 1.5
+    ws["A1"].style = "Report Title"
+
+    path = tmp_path / "custom.xlsx"
+    wb.save(str(path))
+
+    loaded = rustypyxl.load_workbook(str(path))
+    cell = loaded["Sheet1"]["A1"]
+    assert cell.style == "Report Title"
+    assert cell.font.bold
+    assert cell.number_format == "0.00"
+    assert "Report Title" in loaded.named_styles
+
+    ox = openpyxl.load_workbook(path)
+    assert ox["Sheet1"]["A1"].style == "Report Title"
+    assert "Report Title" in ox.style_names
+
+
+def test_assigning_a_named_style_object_registers_it():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Sheet1")
+    ws["B2"].style = NamedStyle(name="Highlight", font=Font(italic=True))
+    assert ws["B2"].style == "Highlight"
+    assert "Highlight" in wb.named_styles
+
+
+def test_reads_openpyxl_named_styles(tmp_path):
+    ox = openpyxl.Workbook()
+    ox.active.title = "Data"
+    style = openpyxl.styles.NamedStyle(name="Money", number_format="#,##0.00")
+    ox.add_named_style(style)
+    ox["Data"]["A1"] = 10
+    ox["Data"]["A1"].style = "Money"
+    path = tmp_path / "openpyxl.xlsx"
+    ox.save(path)
+
+    wb = rustypyxl.load_workbook(str(path))
+    assert wb["Data"]["A1"].style == "Money"
+    assert wb["Data"]["A1"].number_format == "#,##0.00"
+
+
+def test_errors():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Sheet1")
+    with pytest.raises(ValueError):
+        ws["A1"].style = "No Such Style"
+    wb.add_named_style(NamedStyle(name="Once"))
+    with pytest.raises(ValueError):
+        wb.add_named_style(NamedStyle(name="Once"))
@@ -0,0 +1,62 @@
+"""NaN and infinity on save: #NUM! by default, or empty cells, or an error."""
+
+import math
+
+import pytest
+import rustypyxl
+
+
+def _workbook():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = 1.5
+    ws["B2"] = math.nan
+    ws["A3"] = math.inf
+    return wb
+
+
+def test_default_writes_num_error(tmp_path):
+    path = tmp_path / "out.xlsx"
+    _workbook().save(str(path))
+    ws = rustypyxl.load_workbook(str(path)).active
+    assert ws["A1"].value == 1.5
+    assert ws["B2"].value == "#NUM!"
+    assert ws["A3"].value == "#NUM!"
+
+
+def test_empty_policy(tmp_path):
+    path = tmp_path / "out.xlsx"
+    wb = _workbook()
+    wb.set_non_finite_numbers("empty")
+    wb.save(str(path))
+    ws = rustypyxl.load_workbook(str(path)).active
+    assert ws["B2"].value is None
+    assert ws["A3"].value is None
+
+
+def test_error_policy_names_the_cell(tmp_path):
+    wb = _workbook()
+    wb.set_non_finite_numbers("error")
+    with pytest.raises(ValueError, match="B2"):
+        wb.save(str(tmp_path / "out.xlsx"))
+    with pytest.raises(ValueError):
+        wb.set_non_finite_numbers("zero")
+
+
+def test_write_only_policies(tmp_path):
+    path = tmp_path / "stream.xlsx"
+    wb = rustypyxl.WriteOnlyWorkbook(str(path))
+    wb.create_sheet("Data")
+    wb.append_row([math.nan])
+    wb.set_non_finite_numbers("error")
+    with pytest.raises(ValueError):
+        wb.append_row([1, -math.inf])
+    wb.set_non_finite_numbers("empty")
+    wb.append_row([math.inf, 2])
+    wb.close()
+
+    loaded = rustypyxl.load_workbook(str(path))
+    assert loaded.get_cell_value("Data", 1, 1) == "#NUM!"
+    assert loaded.get_cell_value("Data", 2, 1) is None
+    assert loaded.get_cell_value("Data", 2, 2) == 2
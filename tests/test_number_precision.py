@@ -0,0 +1,55 @@
+"""Number precision on save: shortest round-trip by default, or Excel's 15 digits."""
+
+import zipfile
+
+import pytest
+import rustypyxl
+
+
+def _sheet_xml(path):
+    with zipfile.ZipFile(path) as zf:
+        return zf.read("xl/worksheets/sheet1.xml").decode()
+
+
+def _save(tmp_path, precision=None):
+    path = tmp_path / "out.xlsx"
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = 0.1 + 0.2
+    ws["A2"] = 12
+    if precision is not None:
+        wb.set_number_precision(precision)
+    wb.save(str(path))
+    return path
+
+
+def test_default_keeps_every_digit(tmp_path):
+    path = _save(tmp_path)
+    xml = _sheet_xml(path)
+    assert "<v>0.30000000000000004</v>" in xml
+    assert "<v>12</v>" in xml
+    assert rustypyxl.load_workbook(str(path)).active["A1"].value == 0.1 + 0.2
+
+
+def test_excel_precision_rounds_to_15_digits(tmp_path):
+    path = _save(tmp_path, "excel")
+    assert "<v>0.3</v>" in _sheet_xml(path)
+    ws = rustypyxl.load_workbook(str(path)).active
+    assert ws["A1"].value == 0.3
+    assert ws["A2"].value == 12
+
+
+def test_write_only_precision(tmp_path):
+    path = tmp_path / "stream.xlsx"
+    wb = rustypyxl.WriteOnlyWorkbook(str(path))
+    wb.create_sheet("Data")
+    wb.set_number_precision("excel")
+    wb.append_row([1 / 3])
+    wb.close()
+    assert rustypyxl.load_workbook(str(path)).get_cell_value("Data", 1, 1) == 0.333333333333333
+
+
+def test_invalid_precision():
+    with pytest.raises(ValueError):
+        rustypyxl.Workbook().set_number_precision("17")
@@ -23,6 +23,13 @@ import rustypyxl
         ("hello", "@", "hello"),
         ("hi", '"<"@">"', "<hi>"),
         (True, "0", "TRUE"),
+        (1.5, "# ?/?", "1 1/2"),
+        (0.75, "# ?/?", " 3/4"),
+        (3.14159, "# ??/??", "3 14/99"),
+        (0.5, "# ?/8", " 4/8"),
+        (12345.678, "0.00E+00", "1.23E+04"),
+        (0.00012, "0.00E+00", "1.20E-04"),
+        (12345.0, "##0.0E+0", "12.3E+3"),
     ],
 )
 def test_format_value_numbers_and_text(value, code, expected):
@@ -55,3 +62,46 @@ def test_cell_display_value_defaults_to_general():
     ws = wb.create_sheet("S")
     ws["A1"] = 42
     assert ws["A1"].display_value == "42"
+
+
+def test_cell_display_value_fraction_format():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = 2.25
+    ws["A1"].number_format = "# ?/?"
+    assert ws["A1"].display_value == "2 1/4"
+
+
+def test_cell_display_value_formula_without_cached_result_is_blank():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "=1+1"
+    assert ws["A1"].display_value == ""
+
+
+def test_scaled_number_format_auto():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "Revenue"
+    ws["A2"] = 1250000
+    ws["A3"] = 830000
+    assert ws.apply_scaled_number_format("A1:A3") == "millions"
+    assert ws["A1"].number_format in (None, "General")
+    assert ws["A2"].display_value == "1.3M"
+    assert ws["A3"].display_value == "0.8M"
+
+
+def test_scaled_number_format_explicit():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = 42000
+    assert ws.apply_scaled_number_format("A1", style="thousands") == "thousands"
+    assert ws["A1"].number_format == '#,##0,"K"'
+    assert ws["A1"].display_value == "42K"
+
+
+def test_scaled_number_format_rejects_unknown_style():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    with pytest.raises(ValueError):
+        ws.apply_scaled_number_format("A1:A2", style="lakhs")
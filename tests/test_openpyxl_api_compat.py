@@ -113,6 +113,65 @@ class TestAppendInputs:
         with pytest.raises(ValueError):
             ws.append({"not a column": 1})
 
+    def test_append_returns_row_after_last_used(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        assert ws.append(["id", "name"]) == 1
+        ws["A4"] = "gap"
+        assert ws.append([None, None]) == 5
+        assert ws.append([1, "next"]) == 6
+        assert ws["B6"].value == "next"
+
+    def test_append_rows_batch(self, workbook_with_sheet, temp_xlsx_path):
+        ws = workbook_with_sheet.active
+        ws.append(["id", "name"])
+        rows = ([i, f"item {i}"] for i in range(1, 4))
+        assert ws.append_rows(rows) == (2, 4)
+        assert ws.append_rows([]) is None
+        assert ws.append_rows([{"B": "last"}]) == (5, 5)
+        workbook_with_sheet.save(temp_xlsx_path)
+
+        openpyxl = pytest.importorskip("openpyxl")
+        ows = openpyxl.load_workbook(temp_xlsx_path)["Test"]
+        assert [r for r in ows.iter_rows(values_only=True)] == [
+            ("id", "name"),
+            (1, "item 1"),
+            (2, "item 2"),
+            (3, "item 3"),
+            (None, "last"),
+        ]
+
+
+class TestCellWriteThrough:
+    def test_subscript_and_value_assignment(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws["B2"] = 42
+        ws["$C$3"] = "anchored"
+        ws["D4"].value = 7.5
+        assert ws.cell(row=2, column=2).value == 42
+        assert ws["C3"].value == "anchored"
+        assert ws["D4"].value == 7.5
+
+    def test_cell_with_value_writes_first(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        cell = ws.cell(row=3, column=2, value="hello")
+        assert cell.value == "hello"
+        assert ws["B3"].value == "hello"
+        # value=None reads without clearing, as in openpyxl
+        assert ws.cell(row=3, column=2, value=None).value == "hello"
+
+    def test_style_assignment_on_fresh_handles(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws["A1"] = "title"
+        ws["A1"].font = Font(bold=True)
+        ws["A1"].fill = PatternFill("solid", fgColor="FFFF00")
+        ws["A1"].border = rustypyxl.Border(bottom=Side(border_style="thin"))
+        cell = ws["A1"]
+        assert cell.font.bold
+        assert "FFFF00" in cell.fill.fgColor.upper()
+        assert cell.border.bottom.style == "thin"
+        # Each assignment merged into the cell's style rather than replacing it
+        assert ws["A1"].value == "title"
+
 
 class TestGarbageCollection:
     def test_cell_reference_cycle_is_collectable(self):
@@ -0,0 +1,120 @@
+"""Row and column grouping: ws.group_rows / ws.group_cols and the openpyxl
+style row_dimensions.group / column_dimensions.group, read back by openpyxl.
+"""
+
+import openpyxl
+import pytest
+
+import rustypyxl
+
+
+def test_grouped_rows_and_columns_read_by_openpyxl(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "Region"
+    ws.group_rows(5, 15, level=1, hidden=True)
+    ws.group_rows(8, 9, level=2)
+    ws.group_cols("C", "D", hidden=True)
+    out = str(tmp_path / "outline.xlsx")
+    wb.save(out)
+
+    ows = openpyxl.load_workbook(out)["S"]
+    assert ows.row_dimensions[5].outlineLevel == 1
+    assert ows.row_dimensions[5].hidden
+    assert ows.row_dimensions[8].outlineLevel == 2
+    assert ows.row_dimensions[16].collapsed
+    assert ows.column_dimensions["C"].outlineLevel == 1
+    assert ows.column_dimensions["C"].hidden
+    assert ows.column_dimensions["E"].collapsed
+    assert ows.sheet_format.outlineLevelRow == 2
+    assert ows.sheet_format.outlineLevelCol == 1
+
+
+def test_openpyxl_style_group_and_levels():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.row_dimensions.group(2, 4, outline_level=2)
+    ws.column_dimensions.group("B", "C")
+    assert ws.row_dimensions[3].outlineLevel == 2
+    assert ws.row_dimensions[5].outlineLevel == 0
+    assert ws.column_dimensions["B"].outlineLevel == 1
+
+    ws.row_dimensions[3].outlineLevel = 0
+    assert ws.row_dimensions[3].outlineLevel == 0
+    ws.ungroup_cols(2, 3)
+    assert ws.column_dimensions["C"].outlineLevel == 0
+
+
+def test_groups_survive_reload(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws.group_rows(3, 6, hidden=True)
+    ws.group_cols(2, 4, level=3)
+    out = str(tmp_path / "reload.xlsx")
+    wb.save(out)
+
+    ws = rustypyxl.load_workbook(out)["S"]
+    assert ws.row_dimensions[4].outlineLevel == 1
+    assert ws.column_dimensions["D"].outlineLevel == 3
+    ws.ungroup_rows(3, 6)
+    assert ws.row_dimensions[4].outlineLevel == 0
+
+
+def test_invalid_levels():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    with pytest.raises(ValueError):
+        ws.group_rows(1, 3, level=8)
+    with pytest.raises(ValueError):
+        ws.group_rows(5, 3)
+    with pytest.raises(ValueError):
+        ws.row_dimensions[1].outlineLevel = 9
+
+
+def test_summary_settings_read_by_openpyxl(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    outline = ws.sheet_properties.outlinePr
+    assert outline.summaryBelow and outline.summaryRight
+    outline.summaryBelow = False
+    outline.summaryRight = False
+    ws.group_rows(5, 8, hidden=True)
+    assert ws.row_dimensions[4].hidden is False
+    out = str(tmp_path / "summary.xlsx")
+    wb.save(out)
+
+    ows = openpyxl.load_workbook(out)["S"]
+    assert ows.sheet_properties.outlinePr.summaryBelow is False
+    assert ows.sheet_properties.outlinePr.summaryRight is False
+    assert ows.row_dimensions[6].hidden
+
+    reloaded = rustypyxl.load_workbook(out)["S"]
+    assert reloaded.sheet_properties.outlinePr.summaryBelow is False
+
+
+def test_collapse_outline_levels(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws.group_rows(2, 10, level=1)
+    ws.group_rows(4, 6, level=2)
+    ws.group_cols("B", "C")
+
+    ws.collapse_outline(2)
+    assert ws.row_dimensions[5].hidden
+    assert not ws.row_dimensions[3].hidden
+    assert not ws.column_dimensions["B"].hidden
+
+    ws.collapse_outline(1)
+    assert ws.row_dimensions[3].hidden
+    assert ws.column_dimensions["C"].hidden
+    out = str(tmp_path / "collapsed.xlsx")
+    wb.save(out)
+
+    ows = openpyxl.load_workbook(out)["S"]
+    assert ows.row_dimensions[3].hidden
+    assert ows.row_dimensions[11].collapsed
+
+    ws.expand_outline()
+    assert not any(ws.row_dimensions[r].hidden for r in range(2, 11))
+    with pytest.raises(ValueError):
+        ws.collapse_outline(0)
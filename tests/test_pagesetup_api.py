@@ -3,6 +3,8 @@ binding, read back by openpyxl.
 """
 
 import openpyxl
+import pytest
+
 import rustypyxl
 
 
@@ -42,3 +44,87 @@ def test_header_footer(tmp_path):
     wb.save(out)
     ows = openpyxl.load_workbook(out)["S"]
     assert ows.oddHeader.center.text == "Report"
+
+
+def test_openpyxl_style_page_setup(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws["A1"] = "x"
+    ws.page_setup.orientation = "landscape"
+    ws.page_setup.paperSize = 9
+    ws.page_setup.fitToPage = True
+    ws.page_setup.fitToHeight = 0
+    ws.print_options.horizontalCentered = True
+    ws.print_options.gridLines = True
+    ws.page_margins.left = 0.25
+    ws.page_margins.footer = 0.1
+    assert ws.page_setup.fitToWidth == 1
+    out = str(tmp_path / "proxies.xlsx")
+    wb.save(out)
+
+    ows = openpyxl.load_workbook(out)["S"]
+    assert ows.page_setup.orientation == "landscape"
+    assert int(ows.page_setup.paperSize) == 9
+    assert ows.sheet_properties.pageSetUpPr.fitToPage
+    assert int(ows.page_setup.fitToHeight) == 0
+    assert ows.print_options.horizontalCentered
+    assert ows.print_options.gridLines
+    assert abs(ows.page_margins.left - 0.25) < 1e-6
+    assert abs(ows.page_margins.footer - 0.1) < 1e-6
+
+
+def test_page_setup_validation():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    with pytest.raises(ValueError):
+        ws.page_setup.scale = 5
+    with pytest.raises(ValueError):
+        ws.page_setup.orientation = "sideways"
+    with pytest.raises(ValueError):
+        ws.page_margins.left = -1
+    with pytest.raises(ValueError):
+        ws.print_title_rows = "A:B"
+
+
+def test_print_titles_and_multi_area(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Report")
+    ws["A1"] = "x"
+    ws.print_title_rows = "1:2"
+    ws.print_title_cols = "a:b"
+    ws.print_area = ["A1:D10", "F1:H10"]
+    out = str(tmp_path / "titles.xlsx")
+    wb.save(out)
+
+    ows = openpyxl.load_workbook(out)["Report"]
+    assert ows.print_title_rows == "$1:$2"
+    assert ows.print_title_cols == "$A:$B"
+    assert ows.print_area.replace("'", "") == "Report!$A$1:$D$10,Report!$F$1:$H$10"
+
+
+def test_page_setup_loaded_from_openpyxl(tmp_path):
+    source = openpyxl.Workbook()
+    ows = source.active
+    ows.title = "Data"
+    ows["A1"] = 1
+    ows.page_setup.orientation = "landscape"
+    ows.page_setup.paperSize = ows.PAPERSIZE_A4
+    ows.print_options.verticalCentered = True
+    ows.print_area = "A1:C5"
+    ows.print_title_rows = "1:1"
+    path = str(tmp_path / "source.xlsx")
+    source.save(path)
+
+    wb = rustypyxl.load_workbook(path)
+    ws = wb["Data"]
+    assert ws.page_setup.orientation == "landscape"
+    assert ws.page_setup.paperSize == 9
+    assert ws.print_options.verticalCentered
+    assert ws.print_area == "A1:C5"
+    assert ws.print_title_rows == "1:1"
+
+    out = str(tmp_path / "resaved.xlsx")
+    wb.save(out)
+    reread = openpyxl.load_workbook(out)
+    assert reread["Data"].print_area.replace("'", "") == "Data!$A$1:$C$5"
+    assert reread["Data"].print_title_rows == "$1:$1"
+    assert list(reread.defined_names) == []
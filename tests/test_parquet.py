@@ -378,3 +378,135 @@ class TestParquetTypeFidelity:
         assert t.column("a")[0].as_py() == 0
         assert t.column("a")[9_999].as_py() == 9_999
         assert t.column("b")[9_999].as_py() == 9_999 * 0.5
+
+
+class TestWriteArrow:
+    """Tests for writing Arrow C stream sources into a worksheet."""
+
+    def test_write_pyarrow_table(self):
+        table = pa.table({"name": ["Alice", "Bob"], "age": [30, 25]})
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+
+        result = ws.write_arrow(table, start_row=2, start_col=2)
+
+        assert result["rows_written"] == 2
+        assert result["range"] == "B2:C4"
+        assert wb.read_rows("Data", min_row=2, min_col=2) == [
+            ["name", "age"],
+            ["Alice", 30],
+            ["Bob", 25],
+        ]
+
+    def test_write_without_header(self):
+        table = pa.table({"x": [1.5, 2.5]})
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+
+        ws.write_arrow(table, include_header=False)
+
+        assert wb.get_cell_value("Data", 1, 1) == 1.5
+        assert wb.get_cell_value("Data", 2, 1) == 2.5
+
+    def test_write_record_batch_reader(self):
+        table = pa.table({"v": list(range(10))})
+        reader = pa.RecordBatchReader.from_batches(table.schema, table.to_batches(max_chunksize=3))
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+
+        result = ws.write_arrow(reader)
+
+        assert result["rows_written"] == 10
+        assert wb.get_cell_value("Data", 11, 1) == 9
+
+    def test_rejects_non_arrow_objects(self):
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+        with pytest.raises(TypeError):
+            ws.write_arrow([[1, 2]])
+
+
+class TestImportStyle:
+    """Tests for styling applied by the importers."""
+
+    def test_style_true_gives_presentable_sheet(self, tmp_path):
+        table = pa.table({"name": ["Alice", "Bob"], "score": [95.5, 87.0]})
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+
+        ws.write_arrow(table, style=True)
+
+        assert wb.get_cell_font("Data", 1, 1).bold
+        assert wb.get_cell_font("Data", 2, 1) is None
+        assert ws.freeze_panes == "A2"
+        assert ws.auto_filter.ref == "A1:B3"
+
+        path = tmp_path / "styled.xlsx"
+        wb.save(str(path))
+        wb2 = rustypyxl.load_workbook(str(path))
+        assert wb2.get_cell_font("Data", 1, 2).bold
+
+    def test_custom_style_on_parquet_import(self, tmp_path):
+        parquet_path = tmp_path / "data.parquet"
+        pq.write_table(pa.table({"flag": [True, False]}), parquet_path)
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+
+        style = rustypyxl.ImportStyle(header_fill=None, freeze_header=False, auto_filter=False)
+        wb.insert_from_parquet("Data", str(parquet_path), style=style)
+
+        assert wb.get_cell_font("Data", 1, 1).bold
+        assert wb.get_cell_fill("Data", 1, 1) is None
+        assert ws.freeze_panes is None
+        assert ws.column_dimensions["A"].width == 8
+
+    def test_rejects_bad_style(self):
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Data")
+        with pytest.raises(TypeError):
+            ws.write_arrow(pa.table({"x": [1]}), style="bold")
+
+
+class TestStreamingParquet:
+    """Tests for WriteOnlyWorkbook.append_from_parquet."""
+
+    def test_rows_follow_existing_rows(self, tmp_path):
+        import datetime
+
+        parquet_path = tmp_path / "data.parquet"
+        table = pa.table({
+            "name": ["Alice", None, "Charlie"],
+            "qty": [1, 2, 3],
+            "day": [datetime.date(2024, 5, d) for d in (1, 2, 3)],
+        })
+        pq.write_table(table, parquet_path, row_group_size=2)
+        out = tmp_path / "out.xlsx"
+
+        with rustypyxl.WriteOnlyWorkbook(str(out)) as wb:
+            wb.create_sheet("Data")
+            wb.append_row(["Report"])
+            result = wb.append_from_parquet(
+                str(parquet_path), column_renames={"qty": "Quantity"}, columns=["name", "qty", "day"]
+            )
+            wb.append_row(["end"])
+
+        assert result["rows_imported"] == 3
+        assert result["range"] == "A2:C5"
+        assert result["column_names"] == ["name", "Quantity", "day"]
+
+        ws = rustypyxl.load_workbook(str(out))["Data"]
+        assert ws["A2"].value == "name"
+        assert ws["B2"].value == "Quantity"
+        assert ws["A3"].value == "Alice"
+        assert ws["A4"].value is None
+        assert ws["B5"].value == 3
+        assert ws["C3"].value == datetime.datetime(2024, 5, 1)
+        assert ws["A6"].value == "end"
+
+    def test_requires_a_sheet(self, tmp_path):
+        parquet_path = tmp_path / "data.parquet"
+        pq.write_table(pa.table({"x": [1]}), parquet_path)
+        wb = rustypyxl.WriteOnlyWorkbook(str(tmp_path / "out.xlsx"))
+        with pytest.raises(ValueError):
+            wb.append_from_parquet(str(parquet_path))
+        wb.close()
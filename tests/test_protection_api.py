@@ -1,6 +1,7 @@
 """Sheet protection set through the Python binding, read back by openpyxl."""
 
 import openpyxl
+import pytest
 import rustypyxl
 
 
@@ -31,3 +32,170 @@ def test_protect_without_password(tmp_path):
     out = str(tmp_path / "prot2.xlsx")
     wb.save(out)
     assert openpyxl.load_workbook(out)["S"].protection.sheet is True
+
+
+def _sheet_protection_attrs(path, sheet="xl/worksheets/sheet1.xml"):
+    import re
+    import zipfile
+
+    with zipfile.ZipFile(path) as zf:
+        xml = zf.read(sheet).decode()
+    tag = re.search(r"<sheetProtection[^>]*/>", xml).group(0)
+    return dict(re.findall(r'(\w+)="([^"]*)"', tag))
+
+
+def test_password_is_stored_as_sha512_hash(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.protect_sheet(password="secret")
+    out = tmp_path / "hashed.xlsx"
+    wb.save(out)
+
+    attrs = _sheet_protection_attrs(out)
+    assert attrs["algorithmName"] == "SHA-512"
+    assert attrs["spinCount"] == "100000"
+    assert attrs["hashValue"] and attrs["saltValue"]
+    assert "password" not in attrs
+    assert b"secret" not in out.read_bytes()
+
+    prot = openpyxl.load_workbook(out).active.protection
+    assert prot.algorithmName == "SHA-512"
+    assert prot.hashValue == attrs["hashValue"]
+
+
+def test_openpyxl_keywords(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.protect_sheet(password="pw", spinCount=1000, formatCells=False, sort=False, objects=True)
+    out = tmp_path / "flags.xlsx"
+    wb.save(out)
+
+    prot = openpyxl.load_workbook(out).active.protection
+    assert prot.sheet
+    assert prot.formatCells is False and prot.sort is False
+    assert prot.objects is True
+    assert prot.insertRows is True and prot.selectLockedCells is False
+    assert prot.spinCount == 1000
+
+    with pytest.raises(TypeError):
+        ws.protect_sheet(formatcells=True)
+    with pytest.raises(ValueError):
+        ws.protect_sheet(password="pw", algorithmName="MD5")
+
+
+def test_existing_verifier_is_kept(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active.protect_sheet(
+        algorithmName="SHA-512", hashValue="aGFzaA==", saltValue="c2FsdA==", spinCount=10
+    )
+    out = tmp_path / "verifier.xlsx"
+    wb.save(out)
+    attrs = _sheet_protection_attrs(out)
+    assert (attrs["hashValue"], attrs["saltValue"], attrs["spinCount"]) == ("aGFzaA==", "c2FsdA==", "10")
+
+    with pytest.raises(ValueError):
+        wb.active.protect_sheet(hashValue="aGFzaA==")
+
+
+def test_workbook_protection_roundtrip(tmp_path):
+    wb = rustypyxl.Workbook()
+    assert not wb.workbook_protected
+    wb.protect_workbook(password="book", lockWindows=True)
+    assert wb.workbook_protected
+    assert wb.check_workbook_password("book")
+    assert not wb.check_workbook_password("guess")
+
+    out = tmp_path / "book.xlsx"
+    wb.save(out)
+    security = openpyxl.load_workbook(out).security
+    assert security.lockStructure and security.lockWindows
+    assert security.workbookAlgorithmName == "SHA-512"
+
+    reloaded = rustypyxl.load_workbook(str(out))
+    assert reloaded.workbook_protected
+    assert reloaded.check_workbook_password("book")
+
+    reloaded.unprotect_workbook()
+    assert not reloaded.workbook_protected
+
+
+def test_openpyxl_legacy_workbook_password_verifies(tmp_path):
+    from openpyxl.workbook.protection import WorkbookProtection
+
+    owb = openpyxl.Workbook()
+    owb.security = WorkbookProtection(workbookPassword="legacy", lockStructure=True)
+    out = tmp_path / "legacy.xlsx"
+    owb.save(out)
+
+    wb = rustypyxl.load_workbook(str(out))
+    assert wb.check_workbook_password("legacy")
+    assert not wb.check_workbook_password("other")
+
+
+def test_verify_sheet_password_sha512(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    assert ws.protection.verify_password("anything")
+    ws.protect_sheet(password="secret", spinCount=1000)
+    assert ws.protection.sheet
+    assert ws.protection.algorithmName == "SHA-512"
+    assert ws.protection.spinCount == 1000
+    assert ws.protection.verify_password("secret")
+    assert not ws.protection.verify_password("Secret")
+
+    out = tmp_path / "sheet.xlsx"
+    wb.save(out)
+    reloaded = rustypyxl.load_workbook(str(out)).active
+    assert reloaded.protection.verify_password("secret")
+    assert not reloaded.protection.verify_password("")
+
+
+def test_verify_openpyxl_legacy_sheet_password(tmp_path):
+    owb = openpyxl.Workbook()
+    owb.active.protection.password = "test"
+    owb.active.protection.sheet = True
+    out = tmp_path / "legacy_sheet.xlsx"
+    owb.save(out)
+
+    ws = rustypyxl.load_workbook(str(out)).active
+    assert ws.protection.password == "CBEB"
+    assert ws.protection.hashValue is None
+    assert ws.protection.verify_password("test")
+    assert not ws.protection.verify_password("tset")
+
+
+def test_sheet_protection_toggle():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    ws.protection.sheet = True
+    assert ws.sheet_protected
+    assert ws.protection.password is None
+    ws.protection.disable()
+    assert not ws.sheet_protected
+
+
+def test_protect_formulas_locks_only_formulas(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Model")
+    ws["A1"] = 100
+    ws["A2"] = 0.2
+    ws["A3"] = "=A1*A2"
+    ws["B3"] = "=A3*2"
+    ws.protect_sheet(sort=False)
+
+    assert ws.protect_formulas(hidden=True, password="model") == 2
+    assert ws.sheet_protected is True
+    assert ws.protection.verify_password("model")
+
+    out = str(tmp_path / "model.xlsx")
+    wb.save(out)
+    ows = openpyxl.load_workbook(out)["Model"]
+    assert ows.protection.sheet is True
+    assert ows.protection.sort is False
+    assert ows["A3"].protection.locked and ows["A3"].protection.hidden
+    assert ows["B3"].protection.locked
+    assert not ows["A1"].protection.locked
+    assert not ows["A2"].protection.locked
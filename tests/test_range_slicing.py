@@ -0,0 +1,72 @@
+"""openpyxl-style subscripting: ws["A1:C3"], ws["B"], ws[2], ws[1:2]."""
+
+import pytest
+
+import rustypyxl
+
+
+@pytest.fixture
+def ws():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    for row in range(1, 4):
+        for col in range(1, 4):
+            ws.cell(row=row, column=col).value = row * 10 + col
+    return ws
+
+
+def coords(cells):
+    return [c.coordinate for c in cells]
+
+
+def test_single_cell(ws):
+    assert ws["B2"].value == 22
+
+
+def test_rectangle_is_tuple_of_row_tuples(ws):
+    block = ws["A1:B3"]
+    assert isinstance(block, tuple) and all(isinstance(r, tuple) for r in block)
+    assert [coords(r) for r in block] == [["A1", "B1"], ["A2", "B2"], ["A3", "B3"]]
+    assert [[c.value for c in r] for r in ws["$B$3:A2"]] == [[21, 22], [31, 32]]
+
+
+def test_for_row_in_range(ws):
+    total = 0
+    for row in ws["A1:C2"]:
+        for cell in row:
+            total += cell.value
+    assert total == sum(r * 10 + c for r in (1, 2) for c in (1, 2, 3))
+
+
+def test_single_column(ws):
+    assert coords(ws["B"]) == ["B1", "B2", "B3"]
+
+
+def test_column_span(ws):
+    cols = ws["B:C"]
+    assert len(cols) == 2
+    assert coords(cols[1]) == ["C1", "C2", "C3"]
+
+
+def test_single_row_by_int_or_string(ws):
+    assert coords(ws[2]) == ["A2", "B2", "C2"]
+    assert coords(ws["2"]) == ["A2", "B2", "C2"]
+
+
+def test_row_span_and_slice(ws):
+    assert [coords(r) for r in ws["2:3"]] == [coords(ws[2]), coords(ws[3])]
+    assert [coords(r) for r in ws[1:2]] == [coords(ws[1]), coords(ws[2])]
+
+
+def test_empty_cells_are_placeholders(ws):
+    row = ws["A5:B5"][0]
+    assert coords(row) == ["A5", "B5"]
+    assert all(c.value is None for c in row)
+
+
+def test_invalid_keys(ws):
+    with pytest.raises(ValueError):
+        ws["A1:C"]
+    with pytest.raises(ValueError):
+        ws[0]
+    with pytest.raises(ValueError):
+        ws[1:]
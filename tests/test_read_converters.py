@@ -0,0 +1,62 @@
+"""read_rows(converters=...): per-column typed conversion on read."""
+
+import datetime
+
+import pytest
+import rustypyxl
+
+
+@pytest.fixture
+def wb():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.title = "Data"
+    ws.append(["id", "qty", "price", "when", "flag"])
+    ws.append(["1", 2.9, 3, 45413, "yes"])
+    ws.append(["2", None, 4.5, "2024-05-02 08:30", "no"])
+    return wb
+
+
+def test_named_converters(wb):
+    rows = wb.read_rows(
+        "Data",
+        min_row=2,
+        converters={"A": "int", "B": "int", "C": "float", "D": "date", 5: "bool"},
+    )
+    assert rows[0] == [1, 2, 3.0, datetime.date(2024, 5, 1), True]
+    assert isinstance(rows[0][2], float)
+    assert rows[1] == [2, None, 4.5, datetime.date(2024, 5, 2), False]
+
+
+def test_datetime_and_str(wb):
+    rows = wb.read_rows("Data", min_row=3, converters={"D": "datetime", "C": "str"})
+    assert rows[0][3] == datetime.datetime(2024, 5, 2, 8, 30)
+    assert rows[0][2] == "4.5"
+
+
+def test_callables_get_the_raw_value(wb):
+    seen = []
+
+    def upper(value):
+        seen.append(value)
+        return value.upper()
+
+    rows = wb.read_rows("Data", min_row=2, max_col=5, converters={"E": upper})
+    assert [row[4] for row in rows] == ["YES", "NO"]
+    assert seen == ["yes", "no"]
+
+
+def test_unconverted_columns_are_unchanged(wb):
+    plain = wb.read_rows("Data")
+    assert wb.read_rows("Data", converters={}) == plain
+
+
+def test_bad_value_names_the_cell(wb):
+    with pytest.raises(ValueError, match="A1: cannot convert id to int"):
+        wb.read_rows("Data", converters={"A": "int"})
+
+
+def test_unknown_converter_name(wb):
+    with pytest.raises(ValueError, match="Invalid converter"):
+        wb.read_rows("Data", converters={"A": "decimal"})
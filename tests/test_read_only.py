@@ -0,0 +1,112 @@
+"""Tests for load_workbook(..., read_only=True) row streaming."""
+
+import io
+
+import pytest
+import rustypyxl
+
+
+@pytest.fixture
+def sample_path(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Data")
+    ws.append(["name", "qty", "price"])
+    for i in range(1, 501):
+        ws.append([f"item{i}", i, i * 1.25])
+    ws["E10"] = "far"
+    ws["B2"].number_format = "0.00"
+    other = wb.create_sheet("Other")
+    other["A3"] = True
+    path = tmp_path / "sample.xlsx"
+    wb.save(str(path))
+    return path
+
+
+class TestReadOnlyWorkbook:
+    def test_returns_read_only_workbook(self, sample_path):
+        wb = rustypyxl.load_workbook(str(sample_path), read_only=True)
+        assert isinstance(wb, rustypyxl.ReadOnlyWorkbook)
+        assert wb.sheetnames == ["Data", "Other"]
+        assert "Data" in wb
+        assert len(wb) == 2
+        assert wb.active.title == "Data"
+
+    def test_unknown_sheet_raises_key_error(self, sample_path):
+        wb = rustypyxl.load_workbook(str(sample_path), read_only=True)
+        with pytest.raises(KeyError):
+            wb["Missing"]
+
+    def test_bytes_and_file_like_sources(self, sample_path):
+        data = sample_path.read_bytes()
+        for source in (data, io.BytesIO(data), sample_path):
+            wb = rustypyxl.load_workbook(source, read_only=True)
+            assert wb.sheetnames == ["Data", "Other"]
+
+    def test_close(self, sample_path):
+        with rustypyxl.load_workbook(str(sample_path), read_only=True) as wb:
+            ws = wb["Data"]
+        with pytest.raises(ValueError):
+            wb.sheetnames
+        # Worksheets taken before closing still stream.
+        assert next(ws.values)[0] == "name"
+
+
+class TestReadOnlyWorksheet:
+    def test_dimensions(self, sample_path):
+        ws = rustypyxl.load_workbook(str(sample_path), read_only=True)["Data"]
+        assert ws.dimensions == "A1:E501"
+        assert ws.max_row == 501
+        assert ws.max_column == 5
+
+    def test_values_match_full_load(self, sample_path):
+        full = rustypyxl.load_workbook(str(sample_path))["Data"]
+        ws = rustypyxl.load_workbook(str(sample_path), read_only=True)["Data"]
+        streamed = list(ws.values)
+        assert streamed == list(full.iter_rows(values_only=True))
+        assert streamed[0] == ("name", "qty", "price", None, None)
+        assert streamed[9][4] == "far"
+
+    def test_iter_rows_bounds(self, sample_path):
+        ws = rustypyxl.load_workbook(str(sample_path), read_only=True)["Data"]
+        rows = list(ws.iter_rows(min_row=2, max_row=4, min_col=2, max_col=3, values_only=True))
+        assert rows == [(1, 1.25), (2, 2.5), (3, 3.75)]
+
+    def test_cells(self, sample_path):
+        ws = rustypyxl.load_workbook(str(sample_path), read_only=True)["Data"]
+        row = next(ws.iter_rows(min_row=2, max_row=2, max_col=3))
+        assert [c.coordinate for c in row] == ["A2", "B2", "C2"]
+        assert row[1].value == 1
+        assert row[1].number_format == "0.00"
+        assert row[0].number_format is None
+
+    def test_gaps_yield_empty_rows(self, sample_path):
+        ws = rustypyxl.load_workbook(str(sample_path), read_only=True)["Other"]
+        assert list(ws.values) == [(None,), (None,), (True,)]
+
+    def test_iteration_is_lazy(self, sample_path):
+        ws = rustypyxl.load_workbook(str(sample_path), read_only=True)["Data"]
+        it = ws.iter_rows(values_only=True)
+        assert next(it)[0] == "name"
+        assert next(it)[0] == "item1"
+        del it  # the background parse stops with the iterator
+
+
+class TestLongStrings:
+    def test_max_string_len_cuts_shared_strings(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws["A1"] = "x" * 50_000
+        ws["A2"] = "short"
+        path = tmp_path / "blob.xlsx"
+        wb.save(str(path))
+
+        for source in (str(path), path.read_bytes()):
+            ro = rustypyxl.load_workbook(source, read_only=True, max_string_len=8)
+            assert list(ro.active.values) == [("x" * 8 + "…",), ("short",)]
+        whole = rustypyxl.load_workbook(str(path), read_only=True)
+        assert next(whole.active.values)[0] == "x" * 50_000
+
+    def test_max_string_len_needs_read_only(self, sample_path):
+        with pytest.raises(ValueError, match="read_only"):
+            rustypyxl.load_workbook(str(sample_path), max_string_len=10)
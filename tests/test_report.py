@@ -0,0 +1,65 @@
+"""Report builder: sections laid out and styled by ws.write_report."""
+
+import pytest
+import rustypyxl
+
+
+def _report():
+    report = rustypyxl.Report()
+    report.add_title("Quarterly sales", subtitle="All regions")
+    report.add_key_values({"Quarter": "Q3", "Owner": "Ops"})
+    report.add_table(
+        columns=[
+            "Region",
+            {"header": "Units", "total": "sum"},
+            {"header": "Revenue", "number_format": "#,##0.00", "total": "average"},
+        ],
+        rows=[["North", 10, 1200.0], ["South", 4, 800.0]],
+        caption="By region",
+        totals_label="Total",
+    )
+    report.add_notes(["Figures are unaudited."], heading="Notes")
+    return report
+
+
+def test_layout_and_cells():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Report")
+    layout = ws.write_report(_report())
+
+    assert layout["sections"] == ["A1:C2", "A4:B5", "A7:C11", "A13:A14"]
+    table = layout["tables"][0]
+    assert table["header_row"] == 8
+    assert table["totals_row"] == 11
+    assert table["range"] == "A8:C10"
+    assert layout["last_row"] == 14
+
+    assert ws["A1"].value == "Quarterly sales"
+    assert ws["A1"].font.bold is True
+    assert ws["B4"].value == "Q3"
+    assert ws["A8"].fill.fill_type == "solid"
+    assert ws["A11"].value == "Total"
+    assert ws["B11"].value == "=SUM(B9:B10)"
+    assert ws["C9"].number_format == "#,##0.00"
+
+
+def test_survives_save(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Report")
+    ws.write_report(_report())
+    path = tmp_path / "report.xlsx"
+    wb.save(str(path))
+
+    ws2 = rustypyxl.load_workbook(str(path))["Report"]
+    assert ws2["A1"].value == "Quarterly sales"
+    assert ws2["C11"].value == "=AVERAGE(C9:C10)"
+
+
+def test_bad_column_specs():
+    report = rustypyxl.Report()
+    with pytest.raises(ValueError):
+        report.add_table([{"header": "X", "total": "median"}], [])
+    with pytest.raises(ValueError):
+        report.add_table([{"title": "X"}], [])
+    with pytest.raises(TypeError):
+        report.add_table([42], [])
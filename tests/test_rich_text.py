@@ -77,3 +77,66 @@ def test_plain_string_is_not_rich(workbook_with_sheet):
     ws = workbook_with_sheet.active
     ws["A1"] = "just text"
     assert ws["A1"].rich_text is None
+
+
+def test_assign_cell_rich_text(tmp_path):
+    from rustypyxl.cell.rich_text import CellRichText as RichText, TextBlock as Block
+    from rustypyxl.cell.text import InlineFont as Font
+
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.title = "S"
+    ws["A1"] = RichText("Total: ", Block(Font(b=True, color="FFFF0000"), "42"))
+
+    value = ws["A1"].value
+    assert isinstance(value, RichText)
+    assert str(value) == "Total: 42"
+    assert value[0] == "Total: "
+    assert value[1].text == "42"
+    assert value[1].font.b is True
+    assert value.as_list() == ["Total: ", "42"]
+
+    out = str(tmp_path / "assigned.xlsx")
+    wb.save(out)
+    loaded = openpyxl.load_workbook(out, rich_text=True)["S"]["A1"].value
+    assert isinstance(loaded, CellRichText)
+    assert str(loaded) == "Total: 42"
+    assert loaded[1].font.b is True
+    assert loaded[1].font.color.rgb == "FFFF0000"
+
+
+def test_assign_openpyxl_cell_rich_text(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = CellRichText(["x = ", TextBlock(InlineFont(i=True, sz=12), "y")])
+    ws.append([CellRichText([TextBlock(InlineFont(b=True), "row two")])])
+
+    assert str(ws["A1"].value) == "x = y"
+    assert ws["A1"].value[1].font.i is True
+    assert ws["A1"].value[1].font.sz == 12.0
+    assert ws["A2"].value[0].font.b is True
+
+
+def test_load_with_rich_text_flag(tmp_path):
+    src = str(tmp_path / "rich.xlsx")
+    _authored(src)
+
+    plain = rustypyxl.load_workbook(src)["S"]["A1"].value
+    assert plain == "BoldRedplain"
+
+    value = rustypyxl.load_workbook(src, rich_text=True)["S"]["A1"].value
+    assert isinstance(value, rustypyxl.CellRichText)
+    assert len(value) == 3
+    assert value[0].font.b is True
+    assert value[1].font.sz == 14.0
+    assert value[2] == "plain"
+
+
+def test_plain_assignment_replaces_rich_text():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    ws["A1"] = rustypyxl.CellRichText(rustypyxl.TextBlock(rustypyxl.InlineFont(b=True), "old"))
+    ws["A1"] = "new"
+    assert ws["A1"].value == "new"
+    assert ws["A1"].rich_text is None
@@ -125,6 +125,39 @@ class TestStructureApi:
         wb = rustypyxl.load_workbook(str(path))
         assert wb.active.title == "Second"
 
+    def test_active_tab_written_for_openpyxl(self, tmp_path):
+        path = tmp_path / "active_out.xlsx"
+        wb = rustypyxl.Workbook()
+        for name in ("A", "B", "Secret"):
+            wb.create_sheet(name)
+        wb["Secret"].sheet_state = "veryHidden"
+        wb.active = wb["B"]
+        wb.save(str(path))
+
+        owb = openpyxl.load_workbook(path)
+        assert owb.active.title == "B"
+        assert owb["Secret"].sheet_state == "veryHidden"
+
+    def test_hidden_sheet_cannot_be_active(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        for name in ("A", "B"):
+            wb.create_sheet(name)
+        wb["B"].sheet_state = "hidden"
+        with pytest.raises(ValueError):
+            wb.active = wb["B"]
+        with pytest.raises(ValueError):
+            wb.active = 1
+        with pytest.raises(ValueError):
+            wb.active = 5
+
+        # Hiding the active sheet afterwards: the file opens on a visible one
+        wb.active = 0
+        wb["A"].sheet_state = "hidden"
+        wb["B"].sheet_state = "visible"
+        path = tmp_path / "hidden_active.xlsx"
+        wb.save(str(path))
+        assert openpyxl.load_workbook(path).active.title == "B"
+
     def test_rustypyxl_written_hyperlink_works_in_openpyxl(self, tmp_path):
         path = tmp_path / "links.xlsx"
         wb = rustypyxl.Workbook()
@@ -267,3 +300,80 @@ class TestNamesFormulasHeadersComments:
         assert openpyxl.load_workbook(full_resaved)["Main"]["C1"].value == "=A1+B1"
         cached = openpyxl.load_workbook(full_resaved, data_only=True)["Main"]["C1"].value
         assert cached == 5, f"cached formula value lost: {cached!r}"
+
+
+class TestLegacySheets:
+    """Dialog and macro sheets load as preserved placeholders."""
+
+    @staticmethod
+    def _with_macrosheet(path: pathlib.Path) -> pathlib.Path:
+        import zipfile
+
+        src = openpyxl.Workbook()
+        src.active.title = "Data"
+        src.active["A1"] = "values"
+        src.save(path)
+
+        out = path.with_name("macro.xlsx")
+        with zipfile.ZipFile(path) as zin, zipfile.ZipFile(out, "w") as zout:
+            for item in zin.namelist():
+                body = zin.read(item).decode()
+                if item == "xl/workbook.xml":
+                    body = body.replace(
+                        "</sheets>",
+                        '<sheet name="Macro1" sheetId="9" r:id="rIdMacro"/></sheets>',
+                    )
+                elif item == "xl/_rels/workbook.xml.rels":
+                    body = body.replace(
+                        "</Relationships>",
+                        '<Relationship Id="rIdMacro" '
+                        'Type="http://schemas.microsoft.com/office/2006/relationships/xlMacrosheet" '
+                        'Target="macrosheets/sheet1.xml"/></Relationships>',
+                    )
+                elif item == "[Content_Types].xml":
+                    body = body.replace(
+                        "</Types>",
+                        '<Override PartName="/xl/macrosheets/sheet1.xml" '
+                        'ContentType="application/vnd.ms-excel.macrosheet+xml"/></Types>',
+                    )
+                zout.writestr(item, body)
+            zout.writestr(
+                "xl/macrosheets/sheet1.xml",
+                '<xm:macrosheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" '
+                'xmlns:xm="http://schemas.microsoft.com/office/excel/2006/main">'
+                '<sheetData><row r="1"><c r="A1" t="str"><f>RETURN(1)</f><v>1</v></c></row>'
+                "</sheetData></xm:macrosheet>",
+            )
+        return out
+
+    def test_macrosheet_loads_and_survives_save(self, tmp_path):
+        import zipfile
+
+        src = self._with_macrosheet(tmp_path / "base.xlsx")
+        wb = rustypyxl.load_workbook(str(src))
+        assert wb.sheetnames == ["Data", "Macro1"]
+        assert wb["Data"].sheet_type == "worksheet"
+        assert wb["Macro1"].sheet_type == "macrosheet"
+
+        out = tmp_path / "resaved.xlsx"
+        wb.save(str(out))
+        with zipfile.ZipFile(out) as z:
+            assert "xl/macrosheets/sheet2.xml" in z.namelist()
+            assert "RETURN(1)" in z.read("xl/macrosheets/sheet2.xml").decode()
+        assert rustypyxl.load_workbook(str(out))["Data"]["A1"].value == "values"
+
+
+class TestUnmodeledParts:
+    def test_theme_part_survives_roundtrip(self, rich_source, tmp_path):
+        import zipfile
+
+        with zipfile.ZipFile(rich_source) as z:
+            theme = z.read("xl/theme/theme1.xml")
+
+        out = tmp_path / "resaved.xlsx"
+        rustypyxl.load_workbook(str(rich_source)).save(str(out))
+        with zipfile.ZipFile(out) as z:
+            assert z.read("xl/theme/theme1.xml") == theme
+            rels = z.read("xl/_rels/workbook.xml.rels").decode()
+            assert "theme/theme1.xml" in rels
+        openpyxl.load_workbook(out)
@@ -0,0 +1,65 @@
+"""Hidden row and cell tags for matching generated rows after a human edit."""
+
+import openpyxl
+import pytest
+import rustypyxl
+
+
+@pytest.fixture
+def tagged(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Orders")
+    ws.append(["id", "amount"])
+    for i, amount in enumerate([10, 20, 30], start=1001):
+        ws.append([i, amount])
+        ws.set_row_tag(ws.max_row, f"order:{i}")
+    ws.set_cell_tag("B5", "total")
+    path = tmp_path / "tagged.xlsx"
+    wb.save(str(path))
+    return path
+
+
+def test_tags_round_trip(tagged):
+    ws = rustypyxl.load_workbook(str(tagged))["Orders"]
+    assert ws.row_tags == {2: "order:1001", 3: "order:1002", 4: "order:1003"}
+    assert ws.get_row_tag(3) == "order:1002"
+    assert ws.find_cell_by_tag("total") == "B5"
+    assert ws.get_cell_tag("A1") is None
+
+
+def test_tags_follow_rows_edited_elsewhere(tagged):
+    # Insert a row above the first order in another application.
+    owb = openpyxl.load_workbook(str(tagged))
+    ows = owb["Orders"]
+    ows.insert_rows(2)
+    for name in list(ows.defined_names):
+        dn = ows.defined_names[name]
+        dn.attr_text = (
+            dn.attr_text.replace("$4:$4", "$5:$5")
+            .replace("$3:$3", "$4:$4")
+            .replace("$2:$2", "$3:$3")
+        )
+    owb.save(str(tagged))
+
+    ws = rustypyxl.load_workbook(str(tagged))["Orders"]
+    assert ws.find_row_by_tag("order:1001") == 3
+    assert ws.find_row_by_tag("order:1003") == 5
+
+
+def test_tags_hidden_from_named_ranges(tagged):
+    wb = rustypyxl.load_workbook(str(tagged))
+    assert not any(name.startswith("_rpxTag") for name, _ in wb.defined_names)
+    owb = openpyxl.load_workbook(str(tagged))
+    assert all(dn.hidden for dn in owb["Orders"].defined_names.values())
+
+
+def test_tag_validation_and_removal():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    with pytest.raises(ValueError):
+        ws.set_row_tag(1, "")
+    with pytest.raises(ValueError):
+        ws.set_row_tag(1, "x" * 256)
+    ws.set_row_tag(1, "keep")
+    ws.remove_row_tag(1)
+    assert ws.find_row_by_tag("keep") is None
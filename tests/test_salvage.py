@@ -0,0 +1,63 @@
+"""rustypyxl.salvage_workbook: recover sheets from a damaged archive."""
+
+import io
+import zipfile
+
+import pytest
+import rustypyxl
+
+
+def _two_sheet_bytes():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active.title = "Data"
+    wb.active["A1"] = 42
+    wb.create_sheet("Notes")["A1"] = 7
+    return wb.save_to_bytes()
+
+
+def _header_offset(data, name):
+    with zipfile.ZipFile(io.BytesIO(data)) as zf:
+        return zf.getinfo(name).header_offset
+
+
+def test_intact_file_reports_no_damage():
+    wb, report = rustypyxl.salvage_workbook(_two_sheet_bytes())
+    assert report["intact"]
+    assert report["recovered_sheets"] == ["Data", "Notes"]
+    assert report["damaged_parts"] == []
+    assert wb["Notes"]["A1"].value == 7
+
+
+def test_truncated_upload_keeps_complete_sheets(tmp_path):
+    data = _two_sheet_bytes()
+    # Past sheet2's local header, so the part is named but its data is cut.
+    cut = data[: _header_offset(data, "xl/worksheets/sheet2.xml") + 80]
+    with pytest.raises(Exception):
+        rustypyxl.load_workbook(cut)
+
+    path = tmp_path / "truncated.xlsx"
+    path.write_bytes(cut)
+    wb, report = rustypyxl.salvage_workbook(str(path))
+    assert report["central_directory_damaged"]
+    assert report["truncated"]
+    assert not report["intact"]
+    assert report["recovered_sheets"] == ["Data"]
+    assert report["lost_sheets"] == ["Notes"]
+    assert "xl/worksheets/sheet2.xml" in [name for name, _ in report["damaged_parts"]]
+    assert wb.sheetnames == ["Data"]
+    assert wb["Data"]["A1"].value == 42
+
+
+def test_salvaged_workbook_saves_cleanly(tmp_path):
+    data = _two_sheet_bytes()
+    cut = data[: _header_offset(data, "xl/worksheets/sheet2.xml") + 80]
+    wb, _ = rustypyxl.salvage_workbook(io.BytesIO(cut))
+    path = tmp_path / "recovered.xlsx"
+    wb.save(str(path))
+    assert rustypyxl.load_workbook(str(path))["Data"]["A1"].value == 42
+
+
+def test_nothing_recoverable_raises():
+    with pytest.raises(ValueError):
+        rustypyxl.salvage_workbook(b"definitely not a workbook")
@@ -0,0 +1,52 @@
+"""wb.save(on_locked=...) options. Real sharing violations only happen on
+Windows with the file open in Excel, so the locked path itself is covered
+by the core unit tests; these check the argument handling and return value.
+"""
+
+import sys
+
+import pytest
+import rustypyxl
+
+
+def _workbook():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("S")["A1"] = "x"
+    return wb
+
+
+def test_save_returns_written_path(tmp_path):
+    path = tmp_path / "out.xlsx"
+    for policy in ("fail", "retry", "alternate"):
+        assert _workbook().save(path, on_locked=policy) == str(path)
+    assert rustypyxl.load_workbook(str(path))["S"]["A1"].value == "x"
+
+
+def test_save_with_password_and_policy(tmp_path):
+    path = tmp_path / "secret.xlsx"
+    assert _workbook().save(path, password="pw", on_locked="alternate") == str(path)
+    assert rustypyxl.load_workbook(str(path), password="pw")["S"]["A1"].value == "x"
+
+
+def test_unknown_policy_raises(tmp_path):
+    with pytest.raises(ValueError, match="on_locked"):
+        _workbook().save(tmp_path / "out.xlsx", on_locked="wait")
+
+
+@pytest.mark.skipif(sys.platform != "win32", reason="sharing violations are Windows-only")
+def test_locked_file_raises_permission_error(tmp_path):
+    import ctypes
+
+    path = tmp_path / "open.xlsx"
+    _workbook().save(path)
+    kernel32 = ctypes.windll.kernel32
+    kernel32.CreateFileW.restype = ctypes.c_void_p
+    GENERIC_READ, OPEN_EXISTING = 0x80000000, 3
+    # Share mode 0, like Excel holding the workbook open.
+    handle = kernel32.CreateFileW(str(path), GENERIC_READ, 0, None, OPEN_EXISTING, 0, None)
+    try:
+        with pytest.raises(PermissionError):
+            _workbook().save(path)
+        assert _workbook().save(path, on_locked="alternate") == str(tmp_path / "open (1).xlsx")
+    finally:
+        kernel32.CloseHandle(ctypes.c_void_p(handle))
@@ -0,0 +1,52 @@
+"""Tests for wb.save(path, verify=True)."""
+
+import datetime
+
+import rustypyxl
+
+
+def build_workbook():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.title = "Data"
+    for row in range(1, 3001):
+        ws.cell(row=row, column=1, value=row / 7)
+        ws.cell(row=row, column=2, value=f"item {row}")
+    ws["C1"] = "=SUM(A1:A10)"
+    ws["C2"] = datetime.datetime(2024, 5, 17, 9, 30, 15, 250000)
+    ws["C3"] = True
+    wb.create_sheet("Empty")
+    return wb
+
+
+class TestSaveVerify:
+    def test_clean_save_passes(self, tmp_path):
+        wb = build_workbook()
+        path = tmp_path / "verified.xlsx"
+        assert wb.save(path, verify=True) == str(path)
+        reopened = rustypyxl.load_workbook(str(path))
+        assert reopened.sheetnames == ["Data", "Empty"]
+        assert reopened["Data"]["B3000"].value == "item 3000"
+
+    def test_verify_with_password(self, tmp_path):
+        wb = build_workbook()
+        path = tmp_path / "encrypted.xlsx"
+        wb.save(path, password="secret", verify=True)
+        reopened = rustypyxl.load_workbook(str(path), password="secret")
+        assert reopened["Data"]["A7"].value == 1.0
+
+    def test_tolerates_writer_normalisation(self, tmp_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws["A1"] = "bell\x07 and tab\t"
+        ws["A2"] = float("nan")
+        ws["A3"] = 0.1 + 0.2
+        wb.set_number_precision("excel")
+        wb.save(tmp_path / "normalised.xlsx", verify=True)
+
+    def test_default_does_not_verify(self, tmp_path):
+        wb = build_workbook()
+        path = tmp_path / "plain.xlsx"
+        assert wb.save(path) == str(path)
@@ -0,0 +1,39 @@
+"""wb.search(): find where a value lives across sheets."""
+
+import pytest
+
+import rustypyxl
+
+
+def _workbook():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Model")
+    wb.create_sheet("Notes")
+    wb.write_rows("Model", [["Revenue"], ["Net revenue after tax"], ["Prerevenue costs"]])
+    wb["Model"]["A4"] = "=SUM(A1:A3)"
+    wb.write_rows("Notes", [["revenue"]])
+    return wb
+
+
+def test_matches_are_ranked():
+    found = _workbook().search("REVENUE")
+    assert [(m["sheet"], m["coordinate"], m["kind"]) for m in found] == [
+        ("Model", "A1", "exact"),
+        ("Notes", "A1", "exact"),
+        ("Model", "A2", "word"),
+        ("Model", "A3", "contains"),
+    ]
+    assert found[2]["snippet"] == "Net revenue after tax"
+    assert found[2]["row"] == 2 and found[2]["column"] == 1
+
+
+def test_formulas_sheets_and_limit():
+    wb = _workbook()
+    assert wb.search("sum(") == []
+    found = wb.search("sum(", sheets=["Model"], in_formulas=True)
+    assert [(m["coordinate"], m["in_formula"]) for m in found] == [("A4", True)]
+    assert found[0]["snippet"] == "=SUM(A1:A3)"
+    assert len(wb.search("revenue", limit=2)) == 2
+    assert len(wb.search("revenue", limit=None)) == 4
+    with pytest.raises(ValueError):
+        wb.search("revenue", sheets=["Missing"])
@@ -0,0 +1,67 @@
+"""fill_formula_down and shared formula groups on save."""
+
+import zipfile
+
+import pytest
+import rustypyxl
+
+
+def _sheet_xml(path):
+    with zipfile.ZipFile(path) as zf:
+        return zf.read("xl/worksheets/sheet1.xml").decode()
+
+
+def _filled(rows=10):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    for r in range(2, rows + 2):
+        ws.cell(row=r, column=2, value=r)
+        ws.cell(row=r, column=3, value=2)
+    ws.fill_formula_down("D2", "=B2*C2", rows=rows)
+    return wb, ws
+
+
+def test_fill_down_translates_references():
+    _, ws = _filled()
+    assert ws["D2"].value == "=B2*C2"
+    assert ws["D11"].value == "=B11*C11"
+
+
+def test_absolute_references_stay_put():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.fill_formula_down("E1", "=A1*$F$1", rows=3)
+    assert ws["E3"].value == "=A3*$F$1"
+
+
+def test_rows_must_be_positive():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    with pytest.raises(ValueError):
+        ws.fill_formula_down("D2", "=B2", rows=0)
+
+
+def test_plain_formulas_by_default(tmp_path):
+    wb, _ = _filled()
+    path = tmp_path / "plain.xlsx"
+    wb.save(str(path))
+    assert 't="shared"' not in _sheet_xml(path)
+
+
+def test_shared_groups_round_trip(tmp_path):
+    wb, _ = _filled()
+    wb.set_share_formulas(True)
+    path = tmp_path / "shared.xlsx"
+    wb.save(str(path))
+    xml = _sheet_xml(path)
+    assert '<f t="shared" ref="D2:D11" si="0">B2*C2</f>' in xml
+    assert '<f t="shared" si="0"/>' in xml
+
+    ws = rustypyxl.load_workbook(str(path)).active
+    assert ws["D2"].value == "=B2*C2"
+    assert ws["D7"].value == "=B7*C7"
+
+    ro = rustypyxl.load_workbook(str(path), read_only=True)
+    rows = list(ro.active.iter_rows(min_row=11, max_row=11, values_only=True))
+    assert rows[0][3] == "=B11*C11"
@@ -0,0 +1,107 @@
+"""Shared string table threshold on save: every string by default, only the
+repeated ones with a minimum count, or chosen from a sample with "auto";
+"incremental" shares every string from counts kept as cells are set, and
+"inline" writes no table."""
+
+import zipfile
+
+import openpyxl
+import pytest
+import rustypyxl
+
+
+def _save(tmp_path, mode=None, unique=True):
+    path = tmp_path / "out.xlsx"
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    for row in range(1, 201):
+        ws.cell(row=row, column=1).value = "open" if row % 2 else "closed"
+        if unique:
+            ws.cell(row=row, column=2).value = f"ticket-{row}"
+    if mode is not None:
+        wb.set_shared_strings(mode)
+    wb.save(str(path))
+    return path
+
+
+def _parts(path):
+    with zipfile.ZipFile(path) as zf:
+        names = zf.namelist()
+        sst = zf.read("xl/sharedStrings.xml").decode() if "xl/sharedStrings.xml" in names else None
+        return sst, zf.read("xl/worksheets/sheet1.xml").decode()
+
+
+def _values(path):
+    ws = openpyxl.load_workbook(path).active
+    return [row for row in ws.iter_rows(values_only=True)]
+
+
+def test_default_shares_every_string(tmp_path):
+    sst, sheet = _parts(_save(tmp_path))
+    assert 'uniqueCount="202"' in sst
+    assert "inlineStr" not in sheet
+
+
+def test_min_repeats_inlines_unique_values(tmp_path):
+    path = _save(tmp_path, 2)
+    sst, sheet = _parts(path)
+    assert 'count="200" uniqueCount="2"' in sst
+    assert sheet.count('t="inlineStr"') == 200
+    values = _values(path)
+    assert values[0] == ("open", "ticket-1")
+    assert values[199] == ("closed", "ticket-200")
+
+
+def test_auto_inlines_when_values_are_unique(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    for row in range(1, 101):
+        ws.cell(row=row, column=1).value = f"id-{row}"
+    wb.set_shared_strings("auto")
+    path = tmp_path / "ids.xlsx"
+    wb.save(str(path))
+    sst, sheet = _parts(path)
+    assert sst is None
+    assert openpyxl.load_workbook(path).active["A42"].value == "id-42"
+
+
+def test_auto_shares_repeated_values(tmp_path):
+    sst, sheet = _parts(_save(tmp_path, "auto", unique=False))
+    assert 'uniqueCount="2"' in sst
+    assert "inlineStr" not in sheet
+
+
+def test_incremental_matches_all(tmp_path):
+    path = tmp_path / "out.xlsx"
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.set_shared_strings("incremental")
+    ws = wb.active
+    for row in range(1, 201):
+        ws.cell(row=row, column=1).value = "open" if row % 2 else "closed"
+    ws["A1"] = "reopened"
+    ws["A2"] = 7
+    wb.save(str(path))
+    sst, sheet = _parts(path)
+    assert 'count="199" uniqueCount="3"' in sst
+    assert "inlineStr" not in sheet
+    values = _values(path)
+    assert values[:3] == [("reopened",), (7,), ("open",)]
+
+
+def test_inline_writes_no_table(tmp_path):
+    path = _save(tmp_path, "inline")
+    sst, sheet = _parts(path)
+    assert sst is None
+    assert sheet.count('t="inlineStr"') == 400
+    assert _values(path)[0] == ("open", "ticket-1")
+
+
+def test_invalid_mode():
+    wb = rustypyxl.Workbook()
+    with pytest.raises(ValueError):
+        wb.set_shared_strings("sometimes")
+    with pytest.raises(ValueError):
+        wb.set_shared_strings(0)
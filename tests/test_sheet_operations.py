@@ -296,3 +296,52 @@ class TestSheetOrderPreservation:
         wb2 = Workbook.load(io.BytesIO(data))
 
         assert wb2.sheetnames == names
+
+
+class TestSheetReordering:
+    """sheet_order and absolute move_sheet."""
+
+    def _workbook(self):
+        wb = Workbook()
+        for name in ["A", "B", "C", "D"]:
+            wb.create_sheet(name)
+            wb[name]["A1"] = name
+        return wb
+
+    def test_sheet_order_roundtrip(self):
+        wb = self._workbook()
+        wb.sheet_order = ["D", "B", "A", "C"]
+        assert wb.sheetnames == ["D", "B", "A", "C"]
+
+        wb2 = Workbook.load(io.BytesIO(wb.save_to_bytes()))
+        assert wb2.sheetnames == ["D", "B", "A", "C"]
+        for name in wb2.sheetnames:
+            assert wb2[name]["A1"].value == name
+
+    def test_sheet_order_rejects_bad_lists(self):
+        wb = self._workbook()
+        with pytest.raises(ValueError):
+            wb.sheet_order = ["A", "B"]
+        with pytest.raises(ValueError):
+            wb.sheet_order = ["A", "A", "B", "C"]
+        assert wb.sheetnames == ["A", "B", "C", "D"]
+
+    def test_move_sheet_to_index(self):
+        wb = self._workbook()
+        handle = wb["D"]
+        wb.move_sheet("D", index=0)
+        assert wb.sheetnames == ["D", "A", "B", "C"]
+        wb.move_sheet(wb["A"], index=10)
+        assert wb.sheetnames == ["D", "B", "C", "A"]
+        handle["B1"] = "still D"
+        assert wb["D"]["B1"].value == "still D"
+        with pytest.raises(ValueError):
+            wb.move_sheet("B", 1, index=0)
+
+    def test_active_tab_follows_its_sheet(self):
+        wb = self._workbook()
+        wb.active = wb["C"]
+        wb.sheet_order = ["C", "D", "A", "B"]
+        assert wb.active.title == "C"
+        wb.move_sheet("C", 2)
+        assert wb.active.title == "C"
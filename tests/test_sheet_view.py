@@ -0,0 +1,146 @@
+"""Tests for sheet view settings and tab color (ws.sheet_view, ws.sheet_properties)."""
+
+import io
+
+import openpyxl
+import pytest
+
+import rustypyxl
+
+
+def reopen_with_openpyxl(wb):
+    return openpyxl.load_workbook(io.BytesIO(wb.save_to_bytes()))
+
+
+class TestSheetView:
+    def test_defaults(self):
+        view = rustypyxl.Workbook().create_sheet("Sheet").sheet_view
+        assert view.showGridLines is True
+        assert view.zoomScale == 100
+        assert view.rightToLeft is False
+        assert view.view == "normal"
+
+    def test_settings_written_for_openpyxl(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.sheet_view.showGridLines = False
+        ws.sheet_view.zoomScale = 85
+        ws.sheet_view.rightToLeft = True
+        ws.sheet_view.view = "pageBreakPreview"
+
+        view = reopen_with_openpyxl(wb).active.sheet_view
+        assert view.showGridLines is False
+        assert view.zoomScale == 85
+        assert view.rightToLeft is True
+        assert view.view == "pageBreakPreview"
+
+    def test_settings_read_from_openpyxl(self):
+        owb = openpyxl.Workbook()
+        ows = owb.active
+        ows.sheet_view.showGridLines = False
+        ows.sheet_view.zoomScale = 150
+        ows.sheet_view.view = "pageLayout"
+        buf = io.BytesIO()
+        owb.save(buf)
+
+        ws = rustypyxl.load_workbook(buf.getvalue()).active
+        assert ws.sheet_view.showGridLines is False
+        assert ws.sheet_view.zoomScale == 150
+        assert ws.sheet_view.view == "pageLayout"
+
+    def test_invalid_values_raise(self):
+        view = rustypyxl.Workbook().create_sheet("Sheet").sheet_view
+        with pytest.raises(ValueError):
+            view.zoomScale = 5
+        with pytest.raises(ValueError):
+            view.view = "print"
+        assert view.zoomScale == 100
+
+    def test_freeze_panes_keep_view_settings(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.freeze_panes = "B2"
+        ws.sheet_view.zoomScale = 70
+        ows = reopen_with_openpyxl(wb).active
+        assert ows.freeze_panes == "B2"
+        assert ows.sheet_view.zoomScale == 70
+
+
+class TestTabColor:
+    def test_tab_color_roundtrip(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        assert ws.sheet_properties.tabColor is None
+        ws.sheet_properties.tabColor = "1072BA"
+        assert ws.sheet_properties.tabColor == "1072BA"
+
+        ows = reopen_with_openpyxl(wb).active
+        assert ows.sheet_properties.tabColor.rgb == "FF1072BA"
+
+        reloaded = rustypyxl.load_workbook(wb.save_to_bytes()).active
+        assert reloaded.sheet_properties.tabColor.endswith("1072BA")
+
+    def test_theme_tab_color(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        wb.active.sheet_properties.tabColor = rustypyxl.Color(theme=4)
+        ows = reopen_with_openpyxl(wb).active
+        assert ows.sheet_properties.tabColor.theme == 4
+
+    def test_clear_tab_color(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.sheet_properties.tabColor = "FF0000"
+        ws.sheet_properties.tabColor = None
+        assert reopen_with_openpyxl(wb).active.sheet_properties.tabColor is None
+
+
+class TestScrollPosition:
+    def test_scroll_below_frozen_header(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.freeze_panes = "A2"
+        ws.scroll_to("A50")
+        assert ws.sheet_view.topLeftCell == "A50"
+
+        ows = reopen_with_openpyxl(wb).active
+        assert ows.freeze_panes == "A2"
+        assert ows.sheet_view.pane.topLeftCell == "A50"
+        assert ows.sheet_view.selection[0].activeCell == "A50"
+
+    def test_scroll_without_panes(self):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.scroll_to("C10")
+
+        ows = reopen_with_openpyxl(wb).active
+        assert ows.sheet_view.topLeftCell == "C10"
+        assert ows.active_cell == "C10"
+
+        loaded = rustypyxl.load_workbook(io.BytesIO(wb.save_to_bytes())).active
+        assert loaded.sheet_view.topLeftCell == "C10"
+
+    def test_reads_openpyxl_scroll_position(self):
+        owb = openpyxl.Workbook()
+        ows = owb.active
+        ows.freeze_panes = "B2"
+        ows.sheet_view.pane.topLeftCell = "B30"
+        buf = io.BytesIO()
+        owb.save(buf)
+
+        ws = rustypyxl.load_workbook(io.BytesIO(buf.getvalue())).active
+        assert ws.freeze_panes == "B2"
+        assert ws.sheet_view.topLeftCell == "B30"
+
+    def test_invalid_cell(self):
+        ws = rustypyxl.Workbook().create_sheet("Sheet")
+        with pytest.raises(ValueError):
+            ws.scroll_to("not a cell")
+        with pytest.raises(ValueError):
+            ws.sheet_view.topLeftCell = "??"
@@ -0,0 +1,50 @@
+"""Counting filled cells and finding the next free row without touching
+every cell: ws.non_empty_cells_in(range) and ws.first_empty_row(column).
+"""
+
+import pytest
+import rustypyxl
+
+
+@pytest.fixture
+def template():
+    """A partly filled report template: a header, three records, then
+    formatted but empty rows."""
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Report")
+    ws.append(["Region", "Sales"])
+    for region, sales in [("North", 10), ("South", 20), ("East", 30)]:
+        ws.append([region, sales])
+    for row in range(5, 10):
+        ws.cell(row=row, column=2).number_format = "#,##0"
+    return ws
+
+
+def test_non_empty_cells_in(template):
+    assert template.non_empty_cells_in("A1:B4") == 8
+    assert template.non_empty_cells_in("A2:A1000") == 3
+    assert template.non_empty_cells_in("B5:B9") == 0
+    assert template.non_empty_cells_in("B:B") == 4
+    assert template.non_empty_cells_in("1:1") == 2
+
+
+def test_counting_does_not_create_cells(template):
+    before = template.dimensions
+    template.non_empty_cells_in("A1:Z500")
+    assert template.dimensions == before
+
+
+def test_first_empty_row(template):
+    assert template.first_empty_row(1) == 5
+    assert template.first_empty_row("B") == 5
+    assert template.first_empty_row("C") == 1
+
+    template.cell(row=5, column=1, value="West")
+    assert template.first_empty_row("A") == 6
+
+
+def test_invalid_arguments(template):
+    with pytest.raises(ValueError):
+        template.non_empty_cells_in("not a range")
+    with pytest.raises(ValueError):
+        template.first_empty_row(0)
@@ -322,3 +322,105 @@ class TestWriteOnlyWorkbookBatch:
         with pytest.raises(ValueError, match="column limit"):
             wb.append_rows([[1] * 16_385])
         wb.close()
+
+
+class TestWriteOnlyFlush:
+    """Explicit flushes and periodic checkpoints."""
+
+    def test_flush_reports_progress(self, tmp_path):
+        path = tmp_path / "flush.xlsx"
+        wb = rustypyxl.WriteOnlyWorkbook(str(path))
+        assert wb.last_checkpoint is None
+        wb.create_sheet("Data")
+        wb.append_rows([[i, f"row {i}"] for i in range(500)])
+
+        checkpoint = wb.flush()
+        assert checkpoint["sequence"] == 1
+        assert checkpoint["sheet"] == "Data"
+        assert checkpoint["sheet_rows"] == 500
+        assert checkpoint["rows_written"] == 500
+        assert checkpoint["bytes_emitted"] == os.path.getsize(path)
+        assert wb.last_checkpoint == checkpoint
+        wb.close()
+
+        loaded = rustypyxl.load_workbook(str(path))
+        assert loaded["Data"]["B500"].value == "row 499"
+
+    def test_checkpoint_interval(self, tmp_path):
+        path = tmp_path / "interval.xlsx"
+        with rustypyxl.WriteOnlyWorkbook(str(path)) as wb:
+            wb.set_checkpoint_interval(100)
+            wb.create_sheet("A")
+            wb.append_rows([[i] for i in range(150)])
+            wb.create_sheet("B")
+            wb.append_rows([[i] for i in range(60)])
+            checkpoint = wb.last_checkpoint
+            assert checkpoint["sequence"] == 2
+            assert checkpoint["sheet"] == "B"
+            assert checkpoint["rows_written"] == 200
+            assert wb.rows_written == 210
+
+            wb.set_checkpoint_interval(None)
+            wb.append_rows([[i] for i in range(200)])
+            assert wb.last_checkpoint["sequence"] == 2
+
+    def test_flush_after_close_raises(self, tmp_path):
+        wb = rustypyxl.WriteOnlyWorkbook(str(tmp_path / "closed.xlsx"))
+        wb.close()
+        with pytest.raises(ValueError):
+            wb.flush()
+
+
+class TestWriteOnlyAppendRowsFrom:
+    """Pulling rows from a generator in chunks."""
+
+    def test_generator_in_chunks(self, tmp_path):
+        path = tmp_path / "generated.xlsx"
+        pulled = []
+
+        def rows():
+            for i in range(2_500):
+                pulled.append(i)
+                yield (i, f"row {i}", i % 2 == 0)
+
+        with rustypyxl.WriteOnlyWorkbook(str(path)) as wb:
+            wb.create_sheet("Data")
+            wb.append_row(["n", "label", "even"])
+            assert wb.append_rows_from(rows(), chunk_size=1_000) == 2_500
+
+        assert len(pulled) == 2_500
+        ws = rustypyxl.load_workbook(str(path))["Data"]
+        assert ws["A2"].value == 0
+        assert ws["B2501"].value == "row 2499"
+        assert ws["C2501"].value is False
+        assert ws.max_row == 2_501
+
+    def test_empty_iterable_and_bad_chunk_size(self, tmp_path):
+        wb = rustypyxl.WriteOnlyWorkbook(str(tmp_path / "empty.xlsx"))
+        wb.create_sheet("Data")
+        assert wb.append_rows_from(iter([])) == 0
+        with pytest.raises(ValueError):
+            wb.append_rows_from([[1]], chunk_size=0)
+        wb.close()
+
+    def test_generator_error_propagates(self, tmp_path):
+        path = tmp_path / "partial.xlsx"
+
+        def rows():
+            yield [1]
+            yield [2]
+            raise RuntimeError("source failed")
+
+        wb = rustypyxl.WriteOnlyWorkbook(str(path))
+        wb.create_sheet("Data")
+        with pytest.raises(RuntimeError, match="source failed"):
+            wb.append_rows_from(rows(), chunk_size=1)
+        wb.close()
+        ws = rustypyxl.load_workbook(str(path))["Data"]
+        assert ws["A2"].value == 2
+
+    def test_requires_open_sheet(self, tmp_path):
+        wb = rustypyxl.WriteOnlyWorkbook(str(tmp_path / "nosheet.xlsx"))
+        with pytest.raises(ValueError):
+            wb.append_rows_from([[1]])
+        wb.close()
@@ -699,3 +699,33 @@ class TestThemeAndTintColors:
         color = reloaded["T"]["A1"].font.color
         assert isinstance(color, str), "an rgb color must stay a str for compatibility"
         assert "FF0000" in color.upper()
+
+
+class TestRangeStyleMerge:
+    """set_range_style only replaces the components it is given."""
+
+    def test_border_keeps_protection_and_number_format(self, tmp_path):
+        path = tmp_path / "range_style.xlsx"
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Test")
+        ws["A1"] = 0.25
+        ws["A1"].number_format = "0.00%"
+        ws["A1"].protection = rustypyxl.Protection(locked=False)
+        thin = rustypyxl.Side(style="thin")
+        wb.set_range_style(
+            "Test", "A1:B2", border=rustypyxl.Border(left=thin, right=thin, top=thin, bottom=thin)
+        )
+        wb.save(str(path))
+
+        cell = rustypyxl.load_workbook(str(path))["Test"]["A1"]
+        assert cell.number_format == "0.00%"
+        assert cell.protection.locked is False
+        assert cell.border.left.style == "thin"
+
+    def test_protection_merges_into_existing_style(self):
+        wb = rustypyxl.Workbook()
+        ws = wb.create_sheet("Test")
+        ws["A1"].font = rustypyxl.Font(bold=True)
+        wb.set_range_style("Test", "A1", protection=rustypyxl.Protection(locked=False))
+        assert ws["A1"].font.bold is True
+        assert ws["A1"].protection.locked is False
@@ -161,6 +161,42 @@ class TestBorder:
         assert "Border" in s
 
 
+class TestColorResolution:
+    """Theme and indexed colors resolve to concrete ARGB values."""
+
+    def test_rgb_passes_through(self):
+        assert rustypyxl.Color(rgb="#336699").to_rgb() == "FF336699"
+
+    def test_default_theme(self):
+        assert rustypyxl.Color(theme=1).to_rgb() == "FF000000"
+        assert rustypyxl.Color(theme=4).to_rgb() == "FF4472C4"
+
+    def test_indexed_palette(self):
+        assert rustypyxl.Color(indexed=2).to_rgb() == "FFFF0000"
+        assert rustypyxl.Color(indexed=64).to_rgb() == "FF000000"
+        assert rustypyxl.Color(indexed=200).to_rgb() is None
+
+    def test_tint(self):
+        assert rustypyxl.Color(theme=0, tint=-0.1499984740745262).to_rgb() == "FFD9D9D9"
+        assert rustypyxl.Color(theme=1, tint=0.5).to_rgb() == "FF808080"
+
+    def test_empty_color(self):
+        assert rustypyxl.Color().to_rgb() is None
+
+    def test_loaded_theme(self, sample_xlsx_path):
+        # openpyxl writes the Office 2007 theme.
+        wb = rustypyxl.load_workbook(sample_xlsx_path)
+        assert len(wb.theme_colors) == 12
+        assert wb.theme_colors[4] == "FF4F81BD"
+        assert rustypyxl.Color(theme=4).to_rgb(wb) == "FF4F81BD"
+        assert rustypyxl.Color(theme=3).to_rgb(wb) == "FF1F497D"
+
+    def test_new_workbook_uses_office_theme(self):
+        wb = rustypyxl.Workbook()
+        assert wb.theme_colors[:2] == ["FFFFFFFF", "FF000000"]
+        assert wb.theme_colors[10] == "FF0563C1"
+
+
 class TestCellStyling:
     """Test applying styles to cells."""
 
@@ -185,3 +221,99 @@ class TestCellStyling:
 
         assert cell.alignment is not None
         assert cell.alignment.horizontal == "center"
+
+
+class TestBanding:
+    """Test Worksheet.apply_banding."""
+
+    def test_alternating_fills_skip_header(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws.apply_banding("A1:C5", "EEEEEE", "FFFFFF")
+
+        assert ws["A1"].fill is None or ws["A1"].fill.fill_type != "solid"
+        assert ws["A2"].fill.fill_type == "solid"
+        assert "EEEEEE" in ws["B2"].fill.fgColor
+        assert "FFFFFF" in ws["C3"].fill.fgColor
+        assert "EEEEEE" in ws["A4"].fill.fgColor
+
+    def test_keeps_existing_font(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        ws["A1"] = "x"
+        ws["A1"].font = rustypyxl.Font(bold=True)
+        ws.apply_banding("A1:A2", "DDEBF7", None, header=False)
+
+        assert ws["A1"].font.bold is True
+        assert ws["A1"].fill.fill_type == "solid"
+
+    def test_survives_save(self, temp_xlsx_path):
+        wb = rustypyxl.Workbook()
+        wb.create_sheet("Sheet")
+        ws = wb.active
+        ws.apply_banding("A1:B4", "EEEEEE", "FFFFFF", header=True)
+        wb.save(temp_xlsx_path)
+
+        ws2 = rustypyxl.load_workbook(temp_xlsx_path).active
+        assert ws2["A2"].fill.fill_type == "solid"
+        assert "EEEEEE" in ws2["A2"].fill.fgColor
+
+    def test_bad_color_type(self, workbook_with_sheet):
+        ws = workbook_with_sheet.active
+        with pytest.raises(TypeError):
+            ws.apply_banding("A1:B2", 42, None)
+
+
+PALETTE = """{
+  "version": 1,
+  "fonts": {"heading": {"name": "Calibri", "size": 14, "bold": true, "color": "FF1F4E79"}},
+  "fills": {"band": {"pattern": "solid", "fg_color": "FFDDEBF7"}},
+  "number_formats": {"money": "#,##0.00"},
+  "styles": {
+    "header": {"font": "heading", "fill": "band", "alignment": {"horizontal": "center"}},
+    "amount": {"number_format": "money"}
+  }
+}"""
+
+
+class TestStylePalette:
+    """Test wb.styles JSON import/export and ws.apply_named_style."""
+
+    def test_load_and_apply(self, workbook_with_sheet):
+        wb = workbook_with_sheet
+        wb.styles.from_json(PALETTE)
+        assert wb.styles.names == ["amount", "header"]
+        assert "header" in wb.styles
+
+        ws = wb.active
+        ws["A1"] = "Total"
+        ws["B1"] = 12.5
+        ws.apply_named_style("A1:A1", "header")
+        ws.apply_named_style("B1", "amount")
+
+        assert ws["A1"].font.bold is True
+        assert ws["A1"].font.size == 14
+        assert ws["A1"].fill.fill_type == "solid"
+        assert ws["A1"].alignment.horizontal == "center"
+        assert ws["B1"].number_format == "#,##0.00"
+
+    def test_round_trip_between_workbooks(self):
+        source = rustypyxl.Workbook()
+        source.styles.add(
+            "note",
+            font=rustypyxl.Font(italic=True),
+            number_format="0.0%",
+        )
+        exported = source.styles.to_json()
+
+        target = rustypyxl.Workbook()
+        target.styles.from_json(exported)
+        assert target.styles.names == ["note"]
+        assert target.styles.to_json() == exported
+
+    def test_invalid_documents(self, workbook_with_sheet):
+        wb = workbook_with_sheet
+        with pytest.raises(ValueError):
+            wb.styles.from_json("{not json")
+        with pytest.raises(ValueError):
+            wb.styles.from_json('{"styles": {"h": {"font": "missing"}}}')
+        with pytest.raises(ValueError):
+            wb.active.apply_named_style("A1", "undefined")
@@ -3,6 +3,7 @@ openpyxl (the core supported tables; this exercises the new pyo3 binding).
 """
 
 import openpyxl
+import pytest
 import rustypyxl
 
 
@@ -31,3 +32,116 @@ def test_add_table_with_options(tmp_path):
     out = str(tmp_path / "t.xlsx")
     wb.save(out)
     assert "T" in openpyxl.load_workbook(out)["S"].tables
+
+
+def test_add_totals_row(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows("S", [["Item", "Amount", "Qty"], ["Widget", 9.5, 3], ["Gadget", 4, 5]])
+    table = ws.add_table("Sales", "A1:C3")
+
+    assert table.add_totals_row({"Amount": "sum", "Qty": "count"}) == 4
+    assert table.ref == "A1:C4"
+    assert table.totals_row
+    assert ws["A4"].value == "Total"
+    assert ws["B4"].value == "=SUBTOTAL(109,Sales[Amount])"
+    assert ws["C4"].value == "=SUBTOTAL(103,Sales[Qty])"
+
+    # Updating keeps the same row.
+    assert ws.get_table("sales").add_totals_row({"Amount": "average"}) == 4
+    assert ws["B4"].value == "=SUBTOTAL(101,Sales[Amount])"
+
+    out = str(tmp_path / "totals.xlsx")
+    wb.save(out)
+    otable = openpyxl.load_workbook(out)["S"].tables["Sales"]
+    assert otable.ref == "A1:C4"
+    assert otable.totalsRowCount == 1
+    assert otable.totalsRowShown
+    functions = {c.name: c.totalsRowFunction for c in otable.tableColumns}
+    assert functions == {"Item": None, "Amount": "average", "Qty": "count"}
+
+
+def test_add_totals_row_rejects_unknown_input():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows("S", [["A", "B"], [1, 2]])
+    table = ws.add_table("T", "A1:B2")
+    with pytest.raises(ValueError):
+        table.add_totals_row({"Missing": "sum"})
+    with pytest.raises(ValueError):
+        table.add_totals_row({"B": "median"})
+    with pytest.raises(KeyError):
+        ws.get_table("Nope")
+
+
+def test_columns_come_from_header_row(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows("S", [["Region", None, "Region"], ["North", 1, 2]])
+    table = ws.add_table("Sales", "A1:C2")
+    assert table.columns == ["Region", "Column2", "Region2"]
+
+    out = str(tmp_path / "cols.xlsx")
+    wb.save(out)
+    otable = openpyxl.load_workbook(out)["S"].tables["Sales"]
+    assert [c.name for c in otable.tableColumns] == ["Region", "Column2", "Region2"]
+
+
+def test_style_and_banding_round_trip(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows("S", [["A", "B"], [1, 2], [3, 4]])
+    table = ws.add_table("Banded", "A1:B3", style="TableStyleLight9")
+    table.column_stripes = True
+    table.row_stripes = False
+    assert table.style == "TableStyleLight9"
+
+    out = str(tmp_path / "banded.xlsx")
+    wb.save(out)
+    otable = openpyxl.load_workbook(out)["S"].tables["Banded"]
+    assert otable.tableStyleInfo.name == "TableStyleLight9"
+    assert otable.tableStyleInfo.showColumnStripes
+    assert not otable.tableStyleInfo.showRowStripes
+    assert otable.autoFilter.ref == "A1:B3"
+
+    reloaded = rustypyxl.load_workbook(out)["S"].get_table("Banded")
+    assert reloaded.columns == ["A", "B"]
+    assert reloaded.style == "TableStyleLight9"
+    assert reloaded.column_stripes
+
+
+def test_add_table_validation():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    ws.add_table("First", "A1:B3")
+    with pytest.raises(ValueError):
+        ws.add_table("first", "D1:E3")
+    with pytest.raises(ValueError):
+        ws.add_table("Overlap", "B3:C5")
+    with pytest.raises(ValueError):
+        ws.add_table("Has Space", "D1:E3")
+    with pytest.raises(ValueError):
+        ws.add_table("A1", "D1:E3")
+    with pytest.raises(ValueError):
+        ws.add_table("Wide", "D1:E3", headers=["only one"])
+
+
+def test_rename_and_resize_keep_structured_refs():
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("S")
+    wb.write_rows("S", [["Item", "Qty", "Price"], ["Widget", 3, 2.5], ["Gadget", 5, 4.0]])
+    table = ws.add_table("Sales", "A1:C3")
+    ws["E1"] = "=SUM(Sales[Qty])"
+    ws["E2"] = "=SUM(Sales[Price])"
+
+    table.name = "Orders"
+    assert table.name == "Orders"
+    assert ws["E1"].value == "=SUM(Orders[Qty])"
+    assert wb.evaluate_cell("S", 1, 5) == 8
+
+    table.resize("A1:B3")
+    assert table.ref == "A1:B3"
+    assert table.columns == ["Item", "Qty"]
+    assert ws["E2"].value == "=SUM(#REF!)"
+    with pytest.raises(ValueError):
+        table.resize("A2:B3")
@@ -0,0 +1,52 @@
+"""ws.to_html: an HTML table preview of a sheet, rendered in Rust."""
+
+import pytest
+import rustypyxl
+from rustypyxl.styles import Alignment, Border, Font, PatternFill, Side
+
+
+def _sheet():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws["A1"] = "Region & Team"
+    ws["A2"] = "North"
+    ws["B2"] = 0.125
+    ws["B2"].number_format = "0.0%"
+    ws.merge_cells("A1:B1")
+    return ws
+
+
+def test_plain_table():
+    html = _sheet().to_html(include_styles=False)
+    assert html == (
+        '<table><tr><td colspan="2">Region &amp; Team</td></tr>'
+        "<tr><td>North</td><td>12.5%</td></tr></table>"
+    )
+
+
+def test_range_selects_cells():
+    html = _sheet().to_html("A2:B2", include_styles=False)
+    assert html == "<table><tr><td>North</td><td>12.5%</td></tr></table>"
+
+
+def test_styles_are_inline_css():
+    ws = _sheet()
+    ws["A1"].font = Font(bold=True, color="FF0000")
+    ws["A1"].fill = PatternFill(fill_type="solid", start_color="FFFFCC")
+    ws["A1"].alignment = Alignment(horizontal="center")
+    ws["A2"].border = Border(bottom=Side(style="medium", color="0000FF"))
+    ws.column_dimensions["A"].width = 20
+
+    html = ws.to_html()
+    assert html.startswith('<table style="border-collapse:collapse"><colgroup>')
+    assert "font-weight:bold;" in html
+    assert "color:#FF0000;" in html
+    assert "background-color:#FFFFCC;" in html
+    assert "text-align:center;" in html
+    assert "border-bottom:2px solid #0000FF;" in html
+
+
+def test_bad_range_raises():
+    with pytest.raises(ValueError):
+        _sheet().to_html("nonsense")
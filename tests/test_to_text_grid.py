@@ -0,0 +1,57 @@
+"""ws.to_text_grid: aligned plaintext and markdown previews of a sheet."""
+
+import pytest
+import rustypyxl
+
+
+def _sheet():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.append(["Region", "Sales"])
+    ws.append(["North", 1234.5])
+    ws.append(["South", 0.125])
+    ws["B2"].number_format = "#,##0.00"
+    ws["B3"].number_format = "0.0%"
+    return ws
+
+
+def test_plain_grid():
+    assert _sheet().to_text_grid() == (
+        "+--------+----------+\n"
+        "| Region | Sales    |\n"
+        "| North  | 1,234.50 |\n"
+        "| South  |    12.5% |\n"
+        "+--------+----------+\n"
+    )
+
+
+def test_markdown_grid():
+    assert _sheet().to_text_grid(markdown=True) == (
+        "| Region | Sales    |\n"
+        "| ------ | -------: |\n"
+        "| North  | 1,234.50 |\n"
+        "| South  |    12.5% |\n"
+    )
+
+
+def test_range_and_fixed_width():
+    assert _sheet().to_text_grid("A2:B2", width_policy=5) == (
+        "+-------+-------+\n"
+        "| North | ##### |\n"
+        "+-------+-------+\n"
+    )
+
+
+def test_sheet_column_widths():
+    ws = _sheet()
+    ws.column_dimensions["A"].width = 3
+    grid = ws.to_text_grid("A1:A2", width_policy="sheet")
+    assert grid == "+-----+\n| Re… |\n| No… |\n+-----+\n"
+
+
+def test_empty_sheet_and_bad_policy():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    assert ws.to_text_grid() == ""
+    with pytest.raises(ValueError):
+        _sheet().to_text_grid(width_policy="auto")
@@ -0,0 +1,43 @@
+"""Whitespace in string values survives a save: strings with leading or
+trailing spaces, line breaks, tabs or runs of spaces are written with
+xml:space="preserve", whether shared, inline or rich text.
+"""
+
+import zipfile
+
+import openpyxl
+import rustypyxl
+
+VALUES = ["  padded  ", "first line\nsecond", "a\tb", "two  spaces"]
+
+
+def test_values_round_trip(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Sheet1")
+    for row, value in enumerate(VALUES, start=1):
+        ws.cell(row=row, column=1).value = value
+    ws["A2"].alignment = rustypyxl.Alignment(wrap_text=True)
+    path = tmp_path / "whitespace.xlsx"
+    wb.save(str(path))
+
+    with zipfile.ZipFile(path) as zf:
+        sst = zf.read("xl/sharedStrings.xml").decode()
+    assert sst.count('xml:space="preserve"') == len(VALUES)
+
+    loaded = rustypyxl.load_workbook(str(path))["Sheet1"]
+    assert [loaded.cell(row=r, column=1).value for r in range(1, 5)] == VALUES
+    assert loaded["A2"].alignment.wrap_text
+
+    ox = openpyxl.load_workbook(path)["Sheet1"]
+    assert [ox.cell(row=r, column=1).value for r in range(1, 5)] == VALUES
+
+
+def test_plain_strings_are_not_marked(tmp_path):
+    wb = rustypyxl.Workbook()
+    ws = wb.create_sheet("Sheet1")
+    ws["A1"] = "single spaced words"
+    path = tmp_path / "plain.xlsx"
+    wb.save(str(path))
+
+    with zipfile.ZipFile(path) as zf:
+        assert "xml:space" not in zf.read("xl/sharedStrings.xml").decode()
@@ -0,0 +1,98 @@
+"""ws.write_objects: dataclasses, namedtuples and pydantic models as rows."""
+
+import datetime
+from collections import namedtuple
+from dataclasses import dataclass, field
+from typing import ClassVar
+
+import pytest
+import rustypyxl
+
+
+@dataclass
+class Order:
+    sku: str
+    qty: int
+    price: float
+    shipped: datetime.date | None = None
+    tags: list = field(default_factory=list)
+    currency: ClassVar[str] = "EUR"
+
+
+ORDERS = [
+    Order("A-1", 3, 9.5, datetime.date(2024, 5, 1)),
+    Order("B-2", 1, 20.0),
+]
+
+
+def test_dataclasses_get_a_header_and_one_row_each():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    result = ws.write_objects(ORDERS)
+    assert result["column_names"] == ["sku", "qty", "price", "shipped", "tags"]
+    assert result["rows_written"] == 2
+    assert result["columns_written"] == 5
+    assert result["range"] == "A1:E3"
+    assert ws["A1"].value == "sku"
+    assert ws["B2"].value == 3
+    assert ws["D2"].value == datetime.date(2024, 5, 1)
+    assert ws["D3"].value is None
+
+
+def test_fields_pick_and_order_columns():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    result = ws.write_objects(ORDERS, fields=["price", "sku"], start_row=3, start_col=2, header=False)
+    assert result["range"] == "B3:C4"
+    assert ws["B3"].value == 9.5
+    assert ws["C4"].value == "B-2"
+    assert ws["A1"].value is None
+
+
+def test_namedtuples_and_plain_objects():
+    Point = namedtuple("Point", "x y")
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    ws.write_objects([Point(1, 2), Point(3, 4)])
+    assert [ws["A1"].value, ws["B1"].value, ws["B3"].value] == ["x", "y", 4]
+
+    class Row:
+        def __init__(self, name):
+            self.name = name
+            self._secret = "hidden"
+
+    ws.write_objects([Row("a"), Row("b")], start_col=4)
+    assert ws["D1"].value == "name"
+    assert ws["D3"].value == "b"
+    assert ws["E1"].value is None
+
+
+def test_pydantic_models():
+    pydantic = pytest.importorskip("pydantic")
+
+    class Item(pydantic.BaseModel):
+        name: str
+        cost: float
+
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    ws.write_objects([Item(name="pen", cost=1.25)])
+    assert [ws["A1"].value, ws["B1"].value, ws["B2"].value] == ["name", "cost", 1.25]
+
+
+def test_missing_attribute_raises():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    with pytest.raises(AttributeError):
+        ws.write_objects(ORDERS, fields=["sku", "nope"])
+
+
+def test_empty_input_writes_nothing():
+    ws = rustypyxl.Workbook().create_sheet("Sheet")
+    result = ws.write_objects([])
+    assert result["rows_written"] == 0
+    assert result["range"] is None
+
+
+def test_style_applies_to_the_block():
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    ws = wb.active
+    ws.write_objects(ORDERS, style=True)
+    assert wb.get_cell_font(ws.title, 1, 1).bold
+    assert ws.freeze_panes == "A2"
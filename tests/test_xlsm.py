@@ -0,0 +1,101 @@
+"""Macro-enabled workbooks: keep_vba, .xlsm content type, and refusing .xlsx."""
+
+import io
+import zipfile
+
+import pytest
+import rustypyxl
+
+
+MACRO_CT = "application/vnd.ms-excel.sheet.macroEnabled.main+xml"
+VBA_REL = (
+    '<Relationship Id="rIdVba" '
+    'Type="http://schemas.microsoft.com/office/2006/relationships/vbaProject" '
+    'Target="vbaProject.bin"/>'
+)
+VBA_BYTES = b"\xd0\xcf\x11\xe0fake vba project"
+
+
+def _xlsm(path):
+    """A plain workbook rewritten into a macro-enabled one with a VBA part."""
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active["A1"] = "macro host"
+    with zipfile.ZipFile(io.BytesIO(wb.save_to_bytes())) as src, zipfile.ZipFile(
+        path, "w", zipfile.ZIP_DEFLATED
+    ) as dst:
+        for info in src.infolist():
+            data = src.read(info.filename)
+            if info.filename == "[Content_Types].xml":
+                data = data.replace(
+                    b"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml",
+                    MACRO_CT.encode(),
+                ).replace(
+                    b"</Types>",
+                    b'<Override PartName="/xl/vbaProject.bin" '
+                    b'ContentType="application/vnd.ms-office.vbaProject"/></Types>',
+                )
+            elif info.filename == "xl/_rels/workbook.xml.rels":
+                data = data.replace(b"</Relationships>", VBA_REL.encode() + b"</Relationships>")
+            dst.writestr(info, data)
+        dst.writestr("xl/vbaProject.bin", VBA_BYTES)
+
+
+def _read(path, name):
+    with zipfile.ZipFile(path) as zf:
+        return zf.read(name) if name in zf.namelist() else None
+
+
+def test_keep_vba_round_trips_macros(tmp_path):
+    src = tmp_path / "macros.xlsm"
+    _xlsm(src)
+    wb = rustypyxl.load_workbook(str(src), keep_vba=True)
+    assert wb.has_vba
+    wb.active["A2"] = "edited"
+    out = tmp_path / "out.xlsm"
+    wb.save(str(out))
+    assert _read(out, "xl/vbaProject.bin") == VBA_BYTES
+    assert MACRO_CT.encode() in _read(out, "[Content_Types].xml")
+
+
+def test_macros_cannot_be_saved_as_xlsx(tmp_path):
+    src = tmp_path / "macros.xlsm"
+    _xlsm(src)
+    wb = rustypyxl.load_workbook(str(src), keep_vba=True)
+    target = tmp_path / "out.xlsx"
+    with pytest.raises(ValueError, match="xlsm"):
+        wb.save(str(target))
+    assert not target.exists()
+
+
+def test_vba_dropped_by_default(tmp_path):
+    src = tmp_path / "macros.xlsm"
+    _xlsm(src)
+    wb = rustypyxl.load_workbook(str(src))
+    assert not wb.has_vba
+    out = tmp_path / "out.xlsx"
+    wb.save(str(out))
+    assert _read(out, "xl/vbaProject.bin") is None
+    assert b"macroEnabled" not in _read(out, "[Content_Types].xml")
+    assert rustypyxl.load_workbook(str(out)).active["A1"].value == "macro host"
+
+
+def test_plain_workbook_saved_as_xlsm_is_macro_enabled(tmp_path):
+    wb = rustypyxl.Workbook()
+    wb.create_sheet("Sheet")
+    wb.active["A1"] = 1
+    out = tmp_path / "new.xlsm"
+    wb.save(str(out))
+    assert MACRO_CT.encode() in _read(out, "[Content_Types].xml")
+    assert rustypyxl.detect_format(str(out))["format"] == "xlsm"
+
+
+def test_edit_mode_refuses_xlsx_for_macro_file(tmp_path):
+    src = tmp_path / "macros.xlsm"
+    _xlsm(src)
+    wb = rustypyxl.load_workbook(str(src), edit=True)
+    wb[wb.sheetnames[0]]["B1"] = 2
+    with pytest.raises(ValueError):
+        wb.save(str(tmp_path / "out.xlsx"))
+    wb.save(str(tmp_path / "out.xlsm"))
+    assert _read(tmp_path / "out.xlsm", "xl/vbaProject.bin") == VBA_BYTES